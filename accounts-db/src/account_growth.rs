@@ -0,0 +1,58 @@
+//! Counters of the accounts created and deleted, kept as the accounts index is updated, from
+//! which the growth of the accounts over time is derived without scanning them.
+//!
+//! An account is created when its pubkey is first inserted into the index by a store, and deleted
+//! when its pubkey is removed from the index, e.g. by clean once it has zero lamports. The stores
+//! to forks which are not rooted count too, so an account created and then purged with its fork
+//! counts as both created and deleted.
+
+use {
+    solana_pubkey::Pubkey,
+    std::{collections::HashMap, sync::Mutex},
+};
+
+/// The accounts created for an owner
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerAccountGrowth {
+    pub num_accounts_created: u64,
+    /// The data len of the accounts as created
+    pub data_len_created: u64,
+}
+
+impl OwnerAccountGrowth {
+    pub fn add(&mut self, other: &Self) {
+        self.num_accounts_created += other.num_accounts_created;
+        self.data_len_created += other.data_len_created;
+    }
+}
+
+/// The accounts in the index, and the ones created and deleted since startup
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AccountGrowthCounters {
+    pub num_accounts: u64,
+    pub num_accounts_created: u64,
+    pub num_accounts_deleted: u64,
+}
+
+/// Tracks the accounts created by owner, until they are taken
+#[derive(Debug, Default)]
+pub(crate) struct OwnerAccountGrowthTracker {
+    by_owner: Mutex<HashMap<Pubkey, OwnerAccountGrowth>>,
+}
+
+impl OwnerAccountGrowthTracker {
+    pub(crate) fn record(&self, by_owner: HashMap<Pubkey, OwnerAccountGrowth>) {
+        if by_owner.is_empty() {
+            return;
+        }
+        let mut tracked = self.by_owner.lock().unwrap();
+        for (owner, growth) in by_owner {
+            tracked.entry(owner).or_default().add(&growth);
+        }
+    }
+
+    /// Returns the accounts created by owner since they were last taken
+    pub(crate) fn take(&self) -> HashMap<Pubkey, OwnerAccountGrowth> {
+        std::mem::take(&mut *self.by_owner.lock().unwrap())
+    }
+}
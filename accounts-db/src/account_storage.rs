@@ -177,7 +177,6 @@ impl AccountStorage {
         }
     }
 
-    #[cfg(test)]
     pub(crate) fn len(&self) -> usize {
         self.map.len()
     }
@@ -110,13 +110,38 @@ impl Accounts {
         loaded_addresses: &mut LoadedAddresses,
     ) -> std::result::Result<Slot, AddressLookupError> {
         let table_account = self
-            .accounts_db
-            .load_with_fixed_root(ancestors, address_table_lookup.account_key)
-            .map(|(account, _rent)| account)
+            .load_lookup_table_account(ancestors, address_table_lookup.account_key)
             .ok_or(AddressLookupError::LookupTableAccountNotFound)?;
+        Self::lookup_table_addresses_into(
+            &table_account,
+            ancestors.max_slot(),
+            address_table_lookup,
+            slot_hashes,
+            loaded_addresses,
+        )
+    }
+
+    /// Load the account of the address lookup table at `address`
+    pub fn load_lookup_table_account(
+        &self,
+        ancestors: &Ancestors,
+        address: &Pubkey,
+    ) -> Option<AccountSharedData> {
+        self.accounts_db
+            .load_with_fixed_root(ancestors, address)
+            .map(|(account, _rent)| account)
+    }
 
+    /// Fill `loaded_addresses` from the already loaded `table_account` and return the deactivation
+    /// slot.
+    pub fn lookup_table_addresses_into(
+        table_account: &AccountSharedData,
+        current_slot: Slot,
+        address_table_lookup: SVMMessageAddressTableLookup,
+        slot_hashes: &SlotHashes,
+        loaded_addresses: &mut LoadedAddresses,
+    ) -> std::result::Result<Slot, AddressLookupError> {
         if table_account.owner() == &address_lookup_table::program::id() {
-            let current_slot = ancestors.max_slot();
             let lookup_table = AddressLookupTable::deserialize(table_account.data())
                 .map_err(|_ix_err| AddressLookupError::InvalidAccountData)?;
 
@@ -27,6 +27,7 @@ pub mod tests;
 use qualifier_attr::qualifiers;
 use {
     crate::{
+        account_growth::{AccountGrowthCounters, OwnerAccountGrowth, OwnerAccountGrowthTracker},
         account_info::{AccountInfo, Offset, StorageLocation},
         account_storage::{
             stored_account_info::{StoredAccountInfo, StoredAccountInfoWithoutData},
@@ -42,11 +43,11 @@ use {
             StorageAccess, ALIGN_BOUNDARY_OFFSET,
         },
         accounts_hash::{
-            AccountHash, AccountLtHash, AccountsDeltaHash, AccountsHash, AccountsHashKind,
-            AccountsHasher, AccountsLtHash, CalcAccountsHashConfig, CalculateHashIntermediate,
-            HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash, SerdeAccountsHash,
-            SerdeIncrementalAccountsHash, ZeroLamportAccounts, ZERO_LAMPORT_ACCOUNT_HASH,
-            ZERO_LAMPORT_ACCOUNT_LT_HASH,
+            AccountHash, AccountLtHash, AccountsDeltaHash, AccountsHash, AccountsHashCheckpoint,
+            AccountsHashKind, AccountsHasher, AccountsLtHash, CalcAccountsHashConfig,
+            CalculateHashIntermediate, HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash,
+            SerdeAccountsHash, SerdeIncrementalAccountsHash, ZeroLamportAccounts,
+            ZERO_LAMPORT_ACCOUNT_HASH, ZERO_LAMPORT_ACCOUNT_LT_HASH,
         },
         accounts_index::{
             in_mem_accounts_index::StartupStats, AccountSecondaryIndexes, AccountsIndex,
@@ -56,17 +57,20 @@ use {
         },
         accounts_index_storage::Startup,
         accounts_partition::RentPayingAccountsByPartition,
-        accounts_update_notifier_interface::AccountsUpdateNotifier,
+        accounts_update_notifier_interface::{AccountUpdate, AccountsUpdateNotifier},
         active_stats::{ActiveStatItem, ActiveStats},
         ancestors::Ancestors,
         ancient_append_vecs::{
             get_ancient_append_vec_capacity, is_ancient, AccountsToStore, StorageSelector,
         },
+        ancient_packing_policy::{AncientPackingPolicy, AncientPackingPolicyError},
         append_vec::{aligned_stored_size, StoredAccountMeta, STORE_META_OVERHEAD},
         cache_hash_data::{CacheHashData, DeletionPolicy as CacheHashDeletionPolicy},
         contains::Contains,
         epoch_accounts_hash::EpochAccountsHashManager,
+        file_io::IoBackend,
         is_zero_lamport::IsZeroLamport,
+        owner_disk_usage::{AccountsDiskUsage, OwnerDiskUsageTracker},
         partitioned_rewards::{
             PartitionedEpochRewardsConfig, DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
         },
@@ -100,7 +104,7 @@ use {
         boxed::Box,
         collections::{BTreeSet, HashMap, HashSet, VecDeque},
         fs,
-        hash::{Hash as StdHash, Hasher as StdHasher},
+        hash::{DefaultHasher, Hash as StdHash, Hasher as StdHasher},
         io::Result as IoResult,
         iter,
         num::{NonZeroUsize, Saturating},
@@ -507,12 +511,17 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     ancient_append_vec_offset: None,
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
+    ancient_storage_shrink_ratio: None,
+    ancient_storage_compression_level: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
     test_skip_rewrites_but_include_in_bank_hash: false,
     storage_access: StorageAccess::File,
+    io_backend: IoBackend::Pread,
+    track_owner_disk_usage: false,
+    track_account_growth: false,
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
@@ -521,6 +530,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     num_foreground_threads: None,
     num_hash_threads: None,
     hash_calculation_pubkey_bins: Some(4),
+    checkpoint_hash_calculation: false,
 };
 pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig {
     index: Some(ACCOUNTS_INDEX_CONFIG_FOR_BENCHMARKS),
@@ -535,12 +545,17 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     ancient_append_vec_offset: None,
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
+    ancient_storage_shrink_ratio: None,
+    ancient_storage_compression_level: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
     test_skip_rewrites_but_include_in_bank_hash: false,
     storage_access: StorageAccess::File,
+    io_backend: IoBackend::Pread,
+    track_owner_disk_usage: false,
+    track_account_growth: false,
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
@@ -549,6 +564,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     num_foreground_threads: None,
     num_hash_threads: None,
     hash_calculation_pubkey_bins: None,
+    checkpoint_hash_calculation: false,
 };
 
 pub type BinnedHashData = Vec<Vec<CalculateHashIntermediate>>;
@@ -639,7 +655,7 @@ const ANCIENT_APPEND_VEC_DEFAULT_OFFSET: Option<i64> = Some(100_000);
 /// The smallest size of ideal ancient storage.
 /// The setting can be overridden on the command line
 /// with --accounts-db-ancient-ideal-storage-size option.
-const DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE: u64 = 100_000;
+pub(crate) const DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE: u64 = 100_000;
 /// Default value for the number of ancient storages the ancient slot
 /// combining should converge to.
 pub const DEFAULT_MAX_ANCIENT_STORAGES: usize = 100_000;
@@ -665,7 +681,15 @@ pub struct AccountsDbConfig {
     pub ancient_append_vec_offset: Option<i64>,
     pub ancient_storage_ideal_size: Option<u64>,
     pub max_ancient_storages: Option<usize>,
+    /// if Some, the ratio of alive bytes under which ancient storages are shrunk, instead of
+    /// `shrink_ratio`
+    pub ancient_storage_shrink_ratio: Option<f64>,
+    /// if Some, ancient storages are compressed with zstd at this level once written
+    pub ancient_storage_compression_level: Option<i32>,
     pub hash_calculation_pubkey_bins: Option<usize>,
+    /// if true, the full accounts hash calculation is checkpointed, so that it resumes from the
+    /// checkpoint if it is interrupted by a restart
+    pub checkpoint_hash_calculation: bool,
     pub test_skip_rewrites_but_include_in_bank_hash: bool,
     pub skip_initial_hash_calc: bool,
     pub exhaustively_verify_refcounts: bool,
@@ -673,6 +697,12 @@ pub struct AccountsDbConfig {
     pub create_ancient_storage: CreateAncientStorage,
     pub partitioned_epoch_rewards_config: PartitionedEpochRewardsConfig,
     pub storage_access: StorageAccess,
+    /// how to issue batches of reads of storages
+    pub io_backend: IoBackend,
+    /// if true, the disk usage of the storages by owner is tracked
+    pub track_owner_disk_usage: bool,
+    /// if true, the accounts created by owner are tracked, until taken
+    pub track_account_growth: bool,
     pub scan_filter_for_shrinking: ScanFilter,
     pub enable_experimental_accumulator_hash: bool,
     pub verify_experimental_accumulator_hash: bool,
@@ -1264,6 +1294,28 @@ impl AccountStorageEntry {
         })
     }
 
+    /// open a new instance of the storage that is compressed with the zstd compression `level`
+    fn compress(&self, level: i32) -> Option<Self> {
+        let count_and_status = self.count_and_status.lock_write();
+        match self.accounts.compress(level) {
+            Ok(accounts) => accounts.map(|accounts| Self {
+                id: self.id,
+                slot: self.slot,
+                count_and_status: SeqLock::new(*count_and_status),
+                alive_bytes: AtomicUsize::new(self.alive_bytes()),
+                accounts,
+                zero_lamport_single_ref_offsets: RwLock::default(),
+            }),
+            Err(err) => {
+                error!(
+                    "Failed to compress storage {} of slot {}: {err}",
+                    self.id, self.slot
+                );
+                None
+            }
+        }
+    }
+
     pub fn new_existing(
         slot: Slot,
         id: AccountsFileId,
@@ -1327,7 +1379,7 @@ impl AccountStorageEntry {
     }
 
     /// Return the "alive_bytes" minus "zero_lamport_single_ref_accounts bytes".
-    fn alive_bytes_exclude_zero_lamport_single_ref_accounts(&self) -> usize {
+    pub(crate) fn alive_bytes_exclude_zero_lamport_single_ref_accounts(&self) -> usize {
         let zero_lamport_dead_bytes = self
             .accounts
             .dead_bytes_due_to_zero_lamport_single_ref(self.num_zero_lamport_single_ref_accounts());
@@ -1479,14 +1531,18 @@ pub struct AccountsDb {
     /// Some(offset) iff we want to squash old append vecs together into 'ancient append vecs'
     /// Some(offset) means for slots up to (max_slot - (slots_per_epoch - 'offset')), put them in ancient append vecs
     pub ancient_append_vec_offset: Option<i64>,
-    pub ancient_storage_ideal_size: u64,
-    pub max_ancient_storages: usize,
+    /// how ancient storages are packed, which can be changed at runtime
+    ancient_packing_policy: RwLock<AncientPackingPolicy>,
     /// true iff we want to skip the initial hash calculation on startup
     pub skip_initial_hash_calc: bool,
 
     /// The number of pubkey bins used for accounts hash calculation
     pub hash_calculation_pubkey_bins: usize,
 
+    /// Held by the full accounts hash calculation which is checkpointed, if checkpointing is
+    /// enabled.  The other calculations running at the same time are not checkpointed.
+    hash_calculation_checkpoint_lock: Option<Mutex<()>>,
+
     pub storage: AccountStorage,
 
     /// from AccountsDbConfig
@@ -1605,6 +1661,18 @@ pub struct AccountsDb {
     /// method to use for accessing storages
     storage_access: StorageAccess,
 
+    /// how to issue batches of reads of storages, when loading many accounts at once
+    io_backend: IoBackend,
+
+    /// disk usage of the storages by owner, if it is tracked
+    owner_disk_usage: Option<OwnerDiskUsageTracker>,
+
+    /// accounts created by owner since they were last taken, if they are tracked
+    owner_account_growth: Option<OwnerAccountGrowthTracker>,
+
+    /// zstd compression level of ancient storages, if they are compressed
+    ancient_storage_compression_level: Option<i32>,
+
     /// index scan filtering for shrinking
     scan_filter_for_shrinking: ScanFilter,
 
@@ -1889,6 +1957,9 @@ pub struct PubkeyHashAccount {
 
 impl AccountsDb {
     pub const DEFAULT_ACCOUNTS_HASH_CACHE_DIR: &'static str = "accounts_hash_cache";
+    /// The dir, in the accounts hash cache dir, of the checkpoint of the full accounts hash
+    /// calculation
+    const ACCOUNTS_HASH_CHECKPOINT_DIR: &'static str = "checkpoint";
 
     // The default high and low watermark sizes for the accounts read cache.
     // If the cache size exceeds MAX_SIZE_HI, it'll evict entries until the size is <= MAX_SIZE_LO.
@@ -2021,15 +2092,21 @@ impl AccountsDb {
             ancient_append_vec_offset: accounts_db_config
                 .ancient_append_vec_offset
                 .or(ANCIENT_APPEND_VEC_DEFAULT_OFFSET),
-            ancient_storage_ideal_size: accounts_db_config
-                .ancient_storage_ideal_size
-                .unwrap_or(DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE),
-            max_ancient_storages: accounts_db_config
-                .max_ancient_storages
-                .unwrap_or(DEFAULT_MAX_ANCIENT_STORAGES),
+            ancient_packing_policy: RwLock::new(AncientPackingPolicy {
+                ideal_storage_size: accounts_db_config
+                    .ancient_storage_ideal_size
+                    .unwrap_or(DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE),
+                max_ancient_storages: accounts_db_config
+                    .max_ancient_storages
+                    .unwrap_or(DEFAULT_MAX_ANCIENT_STORAGES),
+                shrink_ratio: accounts_db_config.ancient_storage_shrink_ratio,
+            }),
             hash_calculation_pubkey_bins: accounts_db_config
                 .hash_calculation_pubkey_bins
                 .unwrap_or(DEFAULT_HASH_CALCULATION_PUBKEY_BINS),
+            hash_calculation_checkpoint_lock: accounts_db_config
+                .checkpoint_hash_calculation
+                .then(Mutex::default),
             account_indexes: accounts_db_config.account_indexes.unwrap_or_default(),
             shrink_ratio: accounts_db_config.shrink_ratio,
             accounts_update_notifier,
@@ -2045,6 +2122,14 @@ impl AccountsDb {
             test_skip_rewrites_but_include_in_bank_hash: accounts_db_config
                 .test_skip_rewrites_but_include_in_bank_hash,
             storage_access: accounts_db_config.storage_access,
+            io_backend: accounts_db_config.io_backend,
+            owner_disk_usage: accounts_db_config
+                .track_owner_disk_usage
+                .then(OwnerDiskUsageTracker::default),
+            owner_account_growth: accounts_db_config
+                .track_account_growth
+                .then(OwnerAccountGrowthTracker::default),
+            ancient_storage_compression_level: accounts_db_config.ancient_storage_compression_level,
             scan_filter_for_shrinking: accounts_db_config.scan_filter_for_shrinking,
             is_experimental_accumulator_hash_enabled: accounts_db_config
                 .enable_experimental_accumulator_hash
@@ -3117,12 +3202,25 @@ impl AccountsDb {
         reclaims_time.stop();
         drop(active_guard);
 
+        let (num_owner_disk_usage_storages_summarized, summarize_owner_disk_usage_us) =
+            measure_us!(self.summarize_owner_disk_usage());
+
         measure_all.stop();
 
         self.clean_accounts_stats.report();
         datapoint_info!(
             "clean_accounts",
             ("max_clean_root", max_clean_root_inclusive, Option<i64>),
+            (
+                "num_owner_disk_usage_storages_summarized",
+                num_owner_disk_usage_storages_summarized,
+                i64
+            ),
+            (
+                "summarize_owner_disk_usage_us",
+                summarize_owner_disk_usage_us,
+                i64
+            ),
             ("total_us", measure_all.as_us(), i64),
             (
                 "collect_delta_keys_us",
@@ -3599,6 +3697,22 @@ impl AccountsDb {
     }
 
     #[cfg(feature = "dev-context-only-utils")]
+    /// Returns how ancient storages are packed
+    pub fn ancient_packing_policy(&self) -> AncientPackingPolicy {
+        *self.ancient_packing_policy.read().unwrap()
+    }
+
+    /// Changes how ancient storages are packed, from the next packing on
+    pub fn set_ancient_packing_policy(
+        &self,
+        ancient_packing_policy: AncientPackingPolicy,
+    ) -> Result<(), AncientPackingPolicyError> {
+        ancient_packing_policy.validate()?;
+        info!("Setting the ancient packing policy to {ancient_packing_policy:?}");
+        *self.ancient_packing_policy.write().unwrap() = ancient_packing_policy;
+        Ok(())
+    }
+
     pub fn set_storage_access(&mut self, storage_access: StorageAccess) {
         self.storage_access = storage_access;
     }
@@ -4026,11 +4140,58 @@ impl AccountsDb {
         );
 
         self.reopen_storage_as_readonly_shrinking_in_progress_ok(slot);
+        self.summarize_owner_disk_usage_shrinking_in_progress_ok(slot);
 
         Self::update_shrink_stats(&self.shrink_stats, stats_sub, true);
         self.shrink_stats.report();
     }
 
+    /// summarize the disk usage by owner of some of the storages which are not summarized yet,
+    /// if it is tracked. Returns the # of storages summarized.
+    fn summarize_owner_disk_usage(&self) -> usize {
+        self.owner_disk_usage
+            .as_ref()
+            .map_or(0, |owner_disk_usage| {
+                owner_disk_usage.summarize_storages(
+                    &self.storage,
+                    OwnerDiskUsageTracker::MAX_STORAGES_SUMMARIZED_PER_CLEAN,
+                )
+            })
+    }
+
+    /// summarize the disk usage by owner of the storage of `slot`, if it is tracked
+    fn summarize_owner_disk_usage_shrinking_in_progress_ok(&self, slot: Slot) {
+        if let Some(owner_disk_usage) = &self.owner_disk_usage {
+            if let Some(storage) = self
+                .storage
+                .get_slot_storage_entry_shrinking_in_progress_ok(slot)
+            {
+                owner_disk_usage.summarize_storage(slot, &storage);
+            }
+        }
+    }
+
+    /// Returns the disk usage of the storages by owner, as of the storages summarized so far, or
+    /// None if it is not tracked.
+    pub fn owner_disk_usage(&self) -> Option<AccountsDiskUsage> {
+        self.owner_disk_usage
+            .as_ref()
+            .map(|owner_disk_usage| owner_disk_usage.disk_usage(&self.storage))
+    }
+
+    /// Returns the # of accounts, and the # created and deleted since startup
+    pub fn account_growth_counters(&self) -> AccountGrowthCounters {
+        self.accounts_index.account_growth_counters()
+    }
+
+    /// Returns the accounts created by owner since they were last taken, or None if they are not
+    /// tracked.
+    pub fn take_owner_account_growth(&self) -> Option<HashMap<Pubkey, OwnerAccountGrowth>> {
+        self.owner_account_growth
+            .as_ref()
+            .map(OwnerAccountGrowthTracker::take)
+    }
+
     pub(crate) fn update_shrink_stats(
         shrink_stats: &ShrinkStats,
         stats_sub: ShrinkStatsSub,
@@ -4121,6 +4282,36 @@ impl AccountsDb {
         }
     }
 
+    /// we are done writing to the ancient storage at `slot`. It is compressed if ancient storages
+    /// are configured to be.
+    pub(crate) fn compress_ancient_storage_shrinking_in_progress_ok(&self, slot: Slot) {
+        let Some(level) = self.ancient_storage_compression_level else {
+            return;
+        };
+        if let Some(storage) = self
+            .storage
+            .get_slot_storage_entry_shrinking_in_progress_ok(slot)
+        {
+            if let Some(new_storage) = storage.compress(level) {
+                // same race condition as when reopening as readonly, so the storages have to be
+                // exactly equivalent.
+                assert_eq!(storage.id(), new_storage.id());
+                assert_eq!(storage.accounts.len(), new_storage.accounts.len());
+                let stats = &self.shrink_ancient_stats;
+                stats.compressed_storages.fetch_add(1, Ordering::Relaxed);
+                stats
+                    .bytes_before_compression
+                    .fetch_add(storage.capacity(), Ordering::Relaxed);
+                stats.bytes_after_compression.fetch_add(
+                    new_storage.accounts.compressed_len().unwrap_or_default(),
+                    Ordering::Relaxed,
+                );
+                self.storage
+                    .replace_storage_with_equivalent(slot, Arc::new(new_storage));
+            }
+        }
+    }
+
     /// return a store that can contain 'size' bytes
     pub fn get_store_for_shrink(&self, slot: Slot, size: u64) -> ShrinkInProgress<'_> {
         let shrunken_store = self.create_store(slot, size, "shrink", self.shrink_paths.as_slice());
@@ -4513,6 +4704,7 @@ impl AccountsDb {
 
             // we filled one up
             self.reopen_storage_as_readonly_shrinking_in_progress_ok(current_ancient.slot());
+            self.compress_ancient_storage_shrinking_in_progress_ok(current_ancient.slot());
 
             // Now we create an ancient append vec at `slot` to store the overflows.
             let (shrink_in_progress_overflow, time_us) = measure_us!(current_ancient
@@ -4890,6 +5082,8 @@ impl AccountsDb {
             IndexKey::ProgramId(key) => key,
             IndexKey::SplTokenMint(key) => key,
             IndexKey::SplTokenOwner(key) => key,
+            IndexKey::DataSize(owner, _data_len) => owner,
+            IndexKey::NonceAuthority(key) => key,
         };
         if !self.account_indexes.include_key(key) {
             // the requested key was not indexed in the secondary index, so do a normal scan
@@ -5075,6 +5269,55 @@ impl AccountsDb {
         );
     }
 
+    /// load the accounts with `pubkeys` which are in storages into the read only accounts cache,
+    /// reading them from their storages in a batch.
+    /// Only io_uring reads batches faster than one account after the other, so this does nothing
+    /// with the other io backends.
+    pub fn load_accounts_into_read_cache<'a>(
+        &self,
+        ancestors: &Ancestors,
+        pubkeys: impl IntoIterator<Item = &'a Pubkey>,
+    ) {
+        if self.io_backend != IoBackend::IoUring {
+            return;
+        }
+        let mut loaded_pubkeys = HashSet::new();
+        let mut stored_accounts = Vec::new();
+        for pubkey in pubkeys {
+            if !loaded_pubkeys.insert(*pubkey) {
+                continue;
+            }
+            let Some((slot, StorageLocation::AppendVec(store_id, offset), _)) =
+                self.read_index_for_accessor_or_load_slow(ancestors, pubkey, None, false)
+            else {
+                // not in a storage, or in the write cache
+                continue;
+            };
+            if self.read_only_accounts_cache.in_cache(pubkey, slot) {
+                continue;
+            }
+            if let Some(storage) = self.storage.get_account_storage_entry(slot, store_id) {
+                stored_accounts.push((*pubkey, slot, storage, offset));
+            }
+        }
+        if stored_accounts.is_empty() {
+            return;
+        }
+
+        let locations: Vec<_> = stored_accounts
+            .iter()
+            .map(|(_pubkey, _slot, storage, offset)| (&storage.accounts, *offset))
+            .collect();
+        let accounts = AccountsFile::get_accounts_shared_data_batch(self.io_backend, &locations);
+        for ((pubkey, slot, _storage, _offset), account) in stored_accounts.iter().zip(accounts) {
+            // See `do_load_with_populate_read_cache()` for why storing accounts loaded from
+            // storages into the read only cache is safe.
+            if let Some(account) = account {
+                self.read_only_accounts_cache.store(*pubkey, *slot, account);
+            }
+        }
+    }
+
     /// note this returns None for accounts with zero lamports
     pub fn load_with_fixed_root(
         &self,
@@ -6529,16 +6772,9 @@ impl AccountsDb {
         accounts_and_meta_to_store: &impl StorableAccounts<'b>,
         txs: Option<&[&SanitizedTransaction]>,
     ) -> Vec<AccountInfo> {
-        let mut current_write_version = if self.accounts_update_notifier.is_some() {
-            self.write_version
-                .fetch_add(accounts_and_meta_to_store.len() as u64, Ordering::AcqRel)
-        } else {
-            0
-        };
-
-        let (account_infos, cached_accounts) = (0..accounts_and_meta_to_store.len())
+        let (account_infos, cached_accounts): (Vec<_>, Vec<_>) = (0..accounts_and_meta_to_store
+            .len())
             .map(|index| {
-                let txn = txs.map(|txs| *txs.get(index).expect("txs must be present if provided"));
                 let mut account_info = AccountInfo::default();
                 accounts_and_meta_to_store.account_default_if_zero_lamport(index, |account| {
                     let account_shared_data = account.to_account_shared_data();
@@ -6546,15 +6782,6 @@ impl AccountsDb {
                     account_info =
                         AccountInfo::new(StorageLocation::Cached, account.is_zero_lamport());
 
-                    self.notify_account_at_accounts_update(
-                        slot,
-                        &account_shared_data,
-                        &txn,
-                        pubkey,
-                        current_write_version,
-                    );
-                    current_write_version = current_write_version.saturating_add(1);
-
                     let cached_account =
                         self.accounts_cache.store(slot, pubkey, account_shared_data);
                     (account_info, cached_account)
@@ -6562,6 +6789,24 @@ impl AccountsDb {
             })
             .unzip();
 
+        if self.accounts_update_notifier.is_some() {
+            let first_write_version = self
+                .write_version
+                .fetch_add(cached_accounts.len() as u64, Ordering::AcqRel);
+            let accounts: Vec<_> = cached_accounts
+                .iter()
+                .zip(first_write_version..)
+                .enumerate()
+                .map(|(index, (cached_account, write_version))| AccountUpdate {
+                    pubkey: cached_account.pubkey(),
+                    account: &cached_account.account,
+                    txn: txs.map(|txs| *txs.get(index).expect("txs must be present if provided")),
+                    write_version,
+                })
+                .collect();
+            self.notify_accounts_at_accounts_update(slot, &accounts);
+        }
+
         // hash this accounts in bg
         if let Some(sender) = self.sender_bg_hasher.read().unwrap().as_ref() {
             let _ = sender.send(cached_accounts);
@@ -6759,6 +7004,29 @@ impl AccountsDb {
         (accounts_hash, total_lamports)
     }
 
+    /// # of stored accounts read in a batch while calculating the accounts lt hash from the index
+    const LT_HASH_READ_BATCH_SIZE: usize = 256;
+
+    /// Mixes the lt hashes of the `stored_accounts`, which are read in a batch, into `lt_hash`,
+    /// and clears `stored_accounts`
+    fn mix_in_stored_accounts_lt_hashes(
+        &self,
+        lt_hash: &mut LtHash,
+        stored_accounts: &mut Vec<(Pubkey, Arc<AccountStorageEntry>, usize)>,
+    ) {
+        let locations: Vec<_> = stored_accounts
+            .iter()
+            .map(|(_pubkey, storage, offset)| (&storage.accounts, *offset))
+            .collect();
+        let accounts = AccountsFile::get_accounts_shared_data_batch(self.io_backend, &locations);
+        for ((pubkey, _storage, _offset), account) in stored_accounts.iter().zip(accounts) {
+            // SAFETY: The index said this pubkey exists, so there must be an account to load.
+            let account = account.unwrap();
+            lt_hash.mix_in(&Self::lt_hash_account(&account, pubkey).0);
+        }
+        stored_accounts.clear();
+    }
+
     /// Calculates the accounts lt hash
     ///
     /// Only intended to be called at startup (or by tests).
@@ -6782,6 +7050,8 @@ impl AccountsDb {
             .fold(
                 LtHash::identity,
                 |mut accumulator_lt_hash, accounts_index_bin| {
+                    // With io_uring, the stored accounts are read in batches instead.
+                    let mut stored_accounts = Vec::new();
                     for pubkey in accounts_index_bin.keys() {
                         let account_lt_hash = self
                             .accounts_index
@@ -6791,26 +7061,48 @@ impl AccountsDb {
                                 Some(startup_slot),
                                 false,
                                 |(slot, account_info)| {
-                                    (!account_info.is_zero_lamport()).then(|| {
-                                        self.get_account_accessor(
-                                            slot,
-                                            &pubkey,
-                                            &account_info.storage_location(),
-                                        )
-                                        .get_loaded_account(|loaded_account| {
-                                            Self::lt_hash_account(&loaded_account, &pubkey)
-                                        })
-                                        // SAFETY: The index said this pubkey exists, so
-                                        // there must be an account to load.
-                                        .unwrap()
-                                    })
+                                    if account_info.is_zero_lamport() {
+                                        return None;
+                                    }
+                                    let storage_location = account_info.storage_location();
+                                    if let (
+                                        IoBackend::IoUring,
+                                        StorageLocation::AppendVec(store_id, offset),
+                                    ) = (self.io_backend, storage_location)
+                                    {
+                                        if let Some(storage) =
+                                            self.storage.get_account_storage_entry(slot, store_id)
+                                        {
+                                            stored_accounts.push((pubkey, storage, offset));
+                                            return None;
+                                        }
+                                    }
+                                    Some(
+                                        self.get_account_accessor(slot, &pubkey, &storage_location)
+                                            .get_loaded_account(|loaded_account| {
+                                                Self::lt_hash_account(&loaded_account, &pubkey)
+                                            })
+                                            // SAFETY: The index said this pubkey exists, so
+                                            // there must be an account to load.
+                                            .unwrap(),
+                                    )
                                 },
                             )
                             .flatten();
                         if let Some(account_lt_hash) = account_lt_hash {
                             accumulator_lt_hash.mix_in(&account_lt_hash.0);
                         }
+                        if stored_accounts.len() >= Self::LT_HASH_READ_BATCH_SIZE {
+                            self.mix_in_stored_accounts_lt_hashes(
+                                &mut accumulator_lt_hash,
+                                &mut stored_accounts,
+                            );
+                        }
                     }
+                    self.mix_in_stored_accounts_lt_hashes(
+                        &mut accumulator_lt_hash,
+                        &mut stored_accounts,
+                    );
                     accumulator_lt_hash
                 },
             )
@@ -7300,6 +7592,18 @@ impl AccountsDb {
             .expect("create transient accounts hash cache dir");
         let transient_accounts_hash_cache_path =
             transient_accounts_hash_cache_dir.path().to_path_buf();
+        // The scan of the storages is saved in the cache files anyway, so only the dedup of the
+        // full accounts hash calculation is worth checkpointing.
+        let checkpoint_guard = self
+            .hash_calculation_checkpoint_lock
+            .as_ref()
+            .filter(|_| {
+                kind == CalcAccountsHashKind::Full && !config.store_detailed_debug_info_on_failure
+            })
+            .and_then(|lock| lock.try_lock().ok());
+        let checkpoint = checkpoint_guard
+            .as_ref()
+            .and_then(|_| self.open_accounts_hash_checkpoint(storages));
         let scan_and_hash = || {
             let (cache_hash_data, cache_hash_data_us) = measure_us!(Self::get_cache_hash_data(
                 accounts_hash_cache_path,
@@ -7319,6 +7623,7 @@ impl AccountsDb {
                 zero_lamport_accounts: kind.zero_lamport_accounts(),
                 dir_for_temp_cache_files: transient_accounts_hash_cache_path,
                 active_stats: &self.active_stats,
+                checkpoint: checkpoint.as_ref(),
             };
 
             // get raw data by scanning
@@ -7374,11 +7679,45 @@ impl AccountsDb {
         } else {
             scan_and_hash()
         };
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.remove();
+        }
         stats.total_us = total_time.end_as_us();
         stats.log();
         result
     }
 
+    /// Opens the checkpoint of the full accounts hash calculation of `storages`.
+    ///
+    /// Returns None if the storages can't be identified, or if the checkpoint can't be opened.
+    fn open_accounts_hash_checkpoint(
+        &self,
+        storages: &SortedStorages<'_>,
+    ) -> Option<AccountsHashCheckpoint> {
+        // Like the cache files of the scan, the calculation is identified by its storages.
+        let mut hasher = DefaultHasher::new();
+        storages.max_slot_inclusive().hash(&mut hasher);
+        for (slot, storage) in storages.iter_range(storages.range()) {
+            if let Some(storage) = storage {
+                if !Self::hash_storage_info(&mut hasher, storage, slot) {
+                    return None;
+                }
+            }
+        }
+        let dir = self
+            .accounts_hash_cache_path
+            .join(Self::ACCOUNTS_HASH_CHECKPOINT_DIR);
+        AccountsHashCheckpoint::open(&dir, hasher.finish(), self.hash_calculation_pubkey_bins)
+            .inspect_err(|err| {
+                warn!(
+                    "Unable to open accounts hash checkpoint at {}, calculating the accounts hash \
+                     without it: {err}",
+                    dir.display()
+                );
+            })
+            .ok()
+    }
+
     /// Verify accounts hash at startup (or tests)
     ///
     /// Calculate accounts hash(es) and compare them to the values set at startup.
@@ -7690,12 +8029,13 @@ impl AccountsDb {
 
         let update = |start, end| {
             let mut reclaims = Vec::with_capacity((end - start) / 2);
+            let mut created = HashMap::<Pubkey, OwnerAccountGrowth>::new();
 
             (start..end).for_each(|i| {
                 let info = infos[i];
                 accounts.account(i, |account| {
                     let old_slot = accounts.slot(i);
-                    self.accounts_index.upsert(
+                    let is_created = self.accounts_index.upsert(
                         target_slot,
                         old_slot,
                         account.pubkey(),
@@ -7705,8 +8045,16 @@ impl AccountsDb {
                         &mut reclaims,
                         reclaim,
                     );
+                    if is_created && self.owner_account_growth.is_some() {
+                        let growth = created.entry(*account.owner()).or_default();
+                        growth.num_accounts_created += 1;
+                        growth.data_len_created += account.data().len() as u64;
+                    }
                 });
             });
+            if let Some(owner_account_growth) = &self.owner_account_growth {
+                owner_account_growth.record(created);
+            }
             reclaims
         };
 
@@ -1,9 +1,15 @@
 use {
     crate::{
         accounts_db::{AccountStorageEntry, AccountsDb},
-        accounts_update_notifier_interface::AccountsUpdateNotifierInterface,
+        accounts_index::{ScanConfig, ScanOrder},
+        accounts_update_notifier_interface::{
+            AccountForGeyser, AccountUpdate, AccountsUpdateNotifierInterface,
+            SnapshotReplayProgress,
+        },
+        ancestors::Ancestors,
     },
-    solana_account::AccountSharedData,
+    log::*,
+    solana_account::{AccountSharedData, ReadableAccount},
     solana_clock::Slot,
     solana_measure::meas_dur,
     solana_metrics::*,
@@ -16,6 +22,9 @@ use {
     },
 };
 
+/// # of accounts replayed from a snapshot between notifications of the progress of the replay
+const SNAPSHOT_REPLAY_PROGRESS_INTERVAL: u64 = 100_000;
+
 #[derive(Default)]
 pub struct GeyserPluginNotifyAtSnapshotRestoreStats {
     pub notified_accounts: usize,
@@ -55,10 +64,16 @@ impl AccountsDb {
     ///
     /// Since accounts may have multiple versions in different slots, plugins must handle
     /// deduplication by inspected the slot and write version of each account notification.
+    ///
+    /// If the snapshot is replayed, nothing is notified of here, see
+    /// `notify_snapshot_replay()`.
     pub fn notify_account_restore_from_snapshot(&self) {
         let Some(accounts_update_notifier) = &self.accounts_update_notifier else {
             return;
         };
+        if accounts_update_notifier.snapshot_replay_enabled() {
+            return;
+        }
 
         let mut notify_stats = GeyserPluginNotifyAtSnapshotRestoreStats::default();
         if accounts_update_notifier.snapshot_notifications_enabled() {
@@ -77,6 +92,79 @@ impl AccountsDb {
         notify_stats.report();
     }
 
+    /// Replay the accounts restored from a snapshot to the plugins, if enabled: the latest
+    /// version of each account which is alive, in increasing pubkey order.
+    ///
+    /// Unlike `notify_account_restore_from_snapshot()`, this needs the accounts index, so must
+    /// be called once the index is generated.
+    pub fn notify_snapshot_replay(&self) {
+        let Some(accounts_update_notifier) = &self.accounts_update_notifier else {
+            return;
+        };
+        if !accounts_update_notifier.snapshot_replay_enabled() {
+            return;
+        }
+
+        let mut progress = SnapshotReplayProgress {
+            slot: self.accounts_index.max_root_inclusive(),
+            num_accounts: self
+                .accounts_index
+                .account_maps
+                .iter()
+                .map(|map_bin| map_bin.len_for_stats() as u64)
+                .sum(),
+            ..SnapshotReplayProgress::default()
+        };
+        info!(
+            "Replaying about {} accounts of the snapshot at slot {} to geyser plugins",
+            progress.num_accounts, progress.slot,
+        );
+        let mut pure_notify_time = Duration::ZERO;
+        let notifying_start = Instant::now();
+        self.unchecked_scan_accounts(
+            "notify_snapshot_replay",
+            &Ancestors::default(),
+            |pubkey, loaded_account, slot| {
+                if loaded_account.lamports() == 0 {
+                    return;
+                }
+                let account = AccountForGeyser {
+                    pubkey,
+                    lamports: loaded_account.lamports(),
+                    owner: loaded_account.owner(),
+                    executable: loaded_account.executable(),
+                    rent_epoch: loaded_account.rent_epoch(),
+                    data: loaded_account.data(),
+                };
+                progress.num_accounts_notified += 1;
+                progress.last_pubkey = Some(*pubkey);
+                // Each account is notified of once, so the write versions only need to be unique.
+                let (_, notify_dur) = meas_dur!(accounts_update_notifier
+                    .notify_account_restore_from_snapshot(
+                        slot,
+                        progress.num_accounts_notified,
+                        &account,
+                    ));
+                pure_notify_time += notify_dur;
+                if progress.num_accounts_notified % SNAPSHOT_REPLAY_PROGRESS_INTERVAL == 0 {
+                    accounts_update_notifier.notify_snapshot_replay_progress(&progress);
+                }
+            },
+            &ScanConfig::new(ScanOrder::Sorted),
+        );
+        progress.num_accounts = progress.num_accounts_notified;
+        progress.is_done = true;
+        accounts_update_notifier.notify_snapshot_replay_progress(&progress);
+
+        accounts_update_notifier.notify_end_of_restore_from_snapshot();
+        GeyserPluginNotifyAtSnapshotRestoreStats {
+            notified_accounts: progress.num_accounts_notified as usize,
+            elapsed_notifying: notifying_start.elapsed(),
+            total_pure_notify: pure_notify_time,
+        }
+        .report();
+    }
+
     pub fn notify_account_at_accounts_update(
         &self,
         slot: Slot,
@@ -96,6 +184,13 @@ impl AccountsDb {
         }
     }
 
+    /// Notify the plugins of the accounts updated by a store, at once
+    pub fn notify_accounts_at_accounts_update(&self, slot: Slot, accounts: &[AccountUpdate<'_>]) {
+        if let Some(accounts_update_notifier) = &self.accounts_update_notifier {
+            accounts_update_notifier.notify_accounts_update(slot, accounts);
+        }
+    }
+
     fn notify_accounts_in_storage(
         notifier: &dyn AccountsUpdateNotifierInterface,
         storage: &AccountStorageEntry,
@@ -136,7 +231,7 @@ pub mod tests {
         solana_account::ReadableAccount as _,
         std::sync::{
             atomic::{AtomicBool, Ordering},
-            Arc,
+            Arc, Mutex,
         },
     };
 
@@ -150,6 +245,10 @@ pub mod tests {
     struct GeyserTestPlugin {
         pub accounts_notified: DashMap<Pubkey, Vec<(Slot, u64, AccountSharedData)>>,
         pub is_startup_done: AtomicBool,
+        pub snapshot_replay_enabled: bool,
+        /// The accounts restored from the snapshot, in the order they were notified of
+        pub restored_pubkeys: Mutex<Vec<Pubkey>>,
+        pub snapshot_replay_progress: Mutex<Vec<SnapshotReplayProgress>>,
     }
 
     impl AccountsUpdateNotifierInterface for GeyserTestPlugin {
@@ -157,6 +256,10 @@ pub mod tests {
             true
         }
 
+        fn snapshot_replay_enabled(&self) -> bool {
+            self.snapshot_replay_enabled
+        }
+
         /// Notified when an account is updated at runtime, due to transaction activities
         fn notify_account_update(
             &self,
@@ -185,6 +288,14 @@ pub mod tests {
                 .entry(*account.pubkey)
                 .or_default()
                 .push((slot, write_version, account.to_account_shared_data()));
+            self.restored_pubkeys.lock().unwrap().push(*account.pubkey);
+        }
+
+        fn notify_snapshot_replay_progress(&self, progress: &SnapshotReplayProgress) {
+            self.snapshot_replay_progress
+                .lock()
+                .unwrap()
+                .push(progress.clone());
         }
 
         fn notify_end_of_restore_from_snapshot(&self) {
@@ -254,6 +365,54 @@ pub mod tests {
         assert!(notifier.is_startup_done.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_notify_snapshot_replay() {
+        let mut accounts = AccountsDb::new_single_for_tests();
+        let mut keys: Vec<_> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let account = AccountSharedData::new(1, 0, &Pubkey::default());
+        let zero_lamport_account = AccountSharedData::new(0, 0, &Pubkey::default());
+
+        // The account with keys[0] is updated in two slots, and is replayed once, at the last.
+        // The account with keys[1] is closed, and is not replayed.
+        accounts.store_uncached(0, &[(&keys[0], &account), (&keys[1], &account)]);
+        accounts.store_uncached(1, &[(&keys[0], &account), (&keys[2], &account)]);
+        accounts.store_uncached(1, &[(&keys[1], &zero_lamport_account)]);
+        accounts.store_uncached(2, &[(&keys[3], &account)]);
+        for slot in 0..=2 {
+            accounts.add_root(slot);
+        }
+
+        let notifier = Arc::new(GeyserTestPlugin {
+            snapshot_replay_enabled: true,
+            ..GeyserTestPlugin::default()
+        });
+        accounts.set_geyser_plugin_notifer(Some(notifier.clone()));
+
+        // Replayed accounts are not notified of while restoring the storages.
+        accounts.notify_account_restore_from_snapshot();
+        assert!(notifier.accounts_notified.is_empty());
+        assert!(!notifier.is_startup_done.load(Ordering::Relaxed));
+
+        accounts.notify_snapshot_replay();
+        let closed_key = keys.remove(1);
+        let mut sorted_keys = keys.clone();
+        sorted_keys.sort_unstable();
+        assert_eq!(*notifier.restored_pubkeys.lock().unwrap(), sorted_keys);
+        assert!(!notifier.accounts_notified.contains_key(&closed_key));
+        assert_eq!(notifier.accounts_notified.get(&keys[0]).unwrap()[0].0, 1);
+        assert_eq!(
+            notifier.snapshot_replay_progress.lock().unwrap().last(),
+            Some(&SnapshotReplayProgress {
+                slot: 2,
+                num_accounts_notified: 3,
+                num_accounts: 3,
+                last_pubkey: sorted_keys.last().copied(),
+                is_done: true,
+            }),
+        );
+        assert!(notifier.is_startup_done.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn test_notify_account_at_accounts_update() {
         let mut accounts = AccountsDb::new_single_for_tests();
@@ -256,6 +256,11 @@ impl LatestAccountsIndexRootsStats {
                 "append_vecs_open_as_file_io",
                 APPEND_VEC_STATS.open_as_file_io.load(Ordering::Relaxed),
                 i64
+            ),
+            (
+                "append_vecs_open_as_compressed",
+                APPEND_VEC_STATS.open_as_compressed.load(Ordering::Relaxed),
+                i64
             )
         );
 
@@ -305,6 +310,10 @@ pub struct ShrinkAncientStats {
     pub total_alive_bytes: AtomicU64,
     pub slot: AtomicU64,
     pub ideal_storage_size: AtomicU64,
+    pub bytes_reclaimed: AtomicU64,
+    pub compressed_storages: AtomicU64,
+    pub bytes_before_compression: AtomicU64,
+    pub bytes_after_compression: AtomicU64,
 }
 
 #[derive(Debug, Default)]
@@ -756,6 +765,26 @@ impl ShrinkAncientStats {
                 self.ideal_storage_size.swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "bytes_reclaimed",
+                self.bytes_reclaimed.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "compressed_storages",
+                self.compressed_storages.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "bytes_before_compression",
+                self.bytes_before_compression.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "bytes_after_compression",
+                self.bytes_after_compression.swap(0, Ordering::Relaxed),
+                i64
+            ),
         );
     }
 }
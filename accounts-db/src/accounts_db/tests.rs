@@ -11,6 +11,7 @@ use {
             aligned_stored_size, test_utils::TempFile, AccountMeta, AppendVec, StoredAccountMeta,
             StoredMeta,
         },
+        owner_disk_usage::OwnerDiskUsage,
         storable_accounts::AccountForStorage,
     },
     assert_matches::assert_matches,
@@ -6582,6 +6583,97 @@ fn test_hash_storage_info() {
     }
 }
 
+#[test]
+fn test_set_ancient_packing_policy() {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            max_ancient_storages: Some(10),
+            ancient_storage_shrink_ratio: Some(0.5),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let policy = AncientPackingPolicy {
+        max_ancient_storages: 10,
+        shrink_ratio: Some(0.5),
+        ..AncientPackingPolicy::default()
+    };
+    assert_eq!(db.ancient_packing_policy(), policy);
+
+    let new_policy = AncientPackingPolicy {
+        ideal_storage_size: 1_000_000,
+        ..policy
+    };
+    db.set_ancient_packing_policy(new_policy).unwrap();
+    assert_eq!(db.ancient_packing_policy(), new_policy);
+
+    // invalid policies are rejected
+    assert!(db
+        .set_ancient_packing_policy(AncientPackingPolicy {
+            max_ancient_storages: 0,
+            ..new_policy
+        })
+        .is_err());
+    assert_eq!(db.ancient_packing_policy(), new_policy);
+}
+
+#[test]
+fn test_compress_ancient_storage() {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            ancient_storage_compression_level: Some(3),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let slot = 0;
+    let pubkey = solana_pubkey::new_rand();
+    let account = AccountSharedData::new(1, 10_000, &Pubkey::default());
+    db.store_for_tests(slot, &[(&pubkey, &account)]);
+    db.add_root_and_flush_write_cache(slot);
+    let storage = db.get_and_assert_single_storage(slot);
+    assert_eq!(storage.accounts.compressed_len(), None);
+
+    db.compress_ancient_storage_shrinking_in_progress_ok(slot);
+    let compressed_storage = db.get_and_assert_single_storage(slot);
+    assert_eq!(compressed_storage.id(), storage.id());
+    assert_eq!(compressed_storage.count(), storage.count());
+    let compressed_len = compressed_storage.accounts.compressed_len().unwrap();
+    assert!(compressed_len < storage.capacity());
+    assert_eq!(
+        db.shrink_ancient_stats
+            .compressed_storages
+            .load(Ordering::Relaxed),
+        1
+    );
+    assert_eq!(
+        db.shrink_ancient_stats
+            .bytes_after_compression
+            .load(Ordering::Relaxed),
+        compressed_len
+    );
+    drop(storage);
+    assert_eq!(
+        db.load_without_fixed_root(&Ancestors::default(), &pubkey)
+            .unwrap()
+            .0,
+        account
+    );
+
+    // compressed storages are not compressed again
+    db.compress_ancient_storage_shrinking_in_progress_ok(slot);
+    assert_eq!(
+        db.shrink_ancient_stats
+            .compressed_storages
+            .load(Ordering::Relaxed),
+        1
+    );
+}
+
 #[test]
 fn test_sweep_get_oldest_non_ancient_slot_max() {
     let epoch_schedule = EpochSchedule::default();
@@ -8260,3 +8352,256 @@ fn test_clean_old_storages_with_reclaims_unrooted() {
     assert_eq!(slot_list.len(), slots.len());
     assert!(slot_list.iter().map(|(slot, _)| slot).eq(slots.iter()));
 }
+
+#[test_case(IoBackend::Pread)]
+#[test_case(IoBackend::IoUring)]
+fn test_load_accounts_into_read_cache(io_backend: IoBackend) {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            io_backend,
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let pubkeys: Vec<_> = (0..3).map(|_| solana_pubkey::new_rand()).collect();
+    // the last account is larger than a page
+    let accounts: Vec<_> = [10, 100, 10_000]
+        .into_iter()
+        .map(|data_len| AccountSharedData::new(1, data_len, &Pubkey::default()))
+        .collect();
+    for (pubkey, account) in pubkeys.iter().zip(&accounts) {
+        db.store_for_tests(0, &[(pubkey, account)]);
+    }
+    db.add_root_and_flush_write_cache(0);
+    // accounts in the write cache are not loaded into the read cache
+    let cached_pubkey = solana_pubkey::new_rand();
+    db.store_for_tests(1, &[(&cached_pubkey, &accounts[0])]);
+
+    let ancestors = Ancestors::from(vec![0, 1]);
+    db.load_accounts_into_read_cache(
+        &ancestors,
+        pubkeys.iter().chain([&cached_pubkey, &pubkeys[0]]),
+    );
+    // only io_uring reads batches of accounts
+    let is_batched = io_backend == IoBackend::IoUring;
+    for (pubkey, account) in pubkeys.iter().zip(&accounts) {
+        assert_eq!(
+            db.read_only_accounts_cache.load(*pubkey, 0).as_ref(),
+            is_batched.then_some(account)
+        );
+    }
+    assert!(!db.read_only_accounts_cache.in_cache(&cached_pubkey, 1));
+}
+
+#[test]
+fn test_owner_disk_usage() {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            track_owner_disk_usage: true,
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let owner0 = Pubkey::new_unique();
+    let owner1 = Pubkey::new_unique();
+    let pubkey = Pubkey::new_unique();
+    db.store_for_tests(
+        0,
+        &[
+            (&pubkey, &AccountSharedData::new(1, 10, &owner0)),
+            (
+                &Pubkey::new_unique(),
+                &AccountSharedData::new(2, 20, &owner0),
+            ),
+            (
+                &Pubkey::new_unique(),
+                &AccountSharedData::new(3, 5, &owner1),
+            ),
+        ],
+    );
+    db.add_root_and_flush_write_cache(0);
+    // the storage is only summarized by clean
+    let disk_usage = db.owner_disk_usage().unwrap();
+    assert_eq!(disk_usage.num_storages, 1);
+    assert_eq!(disk_usage.num_summarized_storages, 0);
+    assert!(disk_usage.by_owner.is_empty());
+
+    db.clean_accounts_for_tests();
+    let disk_usage = db.owner_disk_usage().unwrap();
+    assert_eq!(disk_usage.num_summarized_storages, 1);
+    assert_eq!(
+        disk_usage.by_owner,
+        HashMap::from([
+            (
+                owner0,
+                OwnerDiskUsage {
+                    num_accounts: 2,
+                    data_len: 30,
+                    lamports: 3,
+                }
+            ),
+            (
+                owner1,
+                OwnerDiskUsage {
+                    num_accounts: 1,
+                    data_len: 5,
+                    lamports: 3,
+                }
+            ),
+        ])
+    );
+
+    // the overwritten account still uses the disk of its storage
+    db.store_for_tests(1, &[(&pubkey, &AccountSharedData::new(4, 10, &owner1))]);
+    db.add_root_and_flush_write_cache(1);
+    db.clean_accounts_for_tests();
+    let disk_usage = db.owner_disk_usage().unwrap();
+    assert_eq!(disk_usage.num_storages, 2);
+    assert_eq!(disk_usage.num_summarized_storages, 2);
+    assert_eq!(disk_usage.by_owner[&owner0].num_accounts, 2);
+    assert_eq!(disk_usage.by_owner[&owner1].num_accounts, 2);
+    assert_eq!(disk_usage.by_owner[&owner1].lamports, 7);
+
+    // usage is not tracked by default
+    let db = AccountsDb::new_single_for_tests();
+    assert_eq!(db.owner_disk_usage(), None);
+}
+
+#[test]
+fn test_account_growth() {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            track_account_growth: true,
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let owner = Pubkey::new_unique();
+    let pubkey = Pubkey::new_unique();
+    db.store_for_tests(
+        0,
+        &[
+            (&pubkey, &AccountSharedData::new(1, 10, &owner)),
+            (
+                &Pubkey::new_unique(),
+                &AccountSharedData::new(2, 20, &owner),
+            ),
+        ],
+    );
+    // updating an account does not create it
+    db.store_for_tests(0, &[(&pubkey, &AccountSharedData::new(3, 40, &owner))]);
+    assert_eq!(
+        db.take_owner_account_growth(),
+        Some(HashMap::from([(
+            owner,
+            OwnerAccountGrowth {
+                num_accounts_created: 2,
+                data_len_created: 30,
+            }
+        )]))
+    );
+    assert_eq!(db.take_owner_account_growth(), Some(HashMap::new()));
+
+    // the account is deleted once its zero lamport version is cleaned
+    db.store_for_tests(1, &[(&pubkey, &AccountSharedData::new(0, 0, &owner))]);
+    db.calculate_accounts_delta_hash(0);
+    db.add_root_and_flush_write_cache(0);
+    db.calculate_accounts_delta_hash(1);
+    db.add_root_and_flush_write_cache(1);
+    db.clean_accounts_for_tests();
+    assert_eq!(
+        db.account_growth_counters(),
+        AccountGrowthCounters {
+            num_accounts: 1,
+            num_accounts_created: 2,
+            num_accounts_deleted: 1,
+        }
+    );
+
+    // the accounts created are not tracked by default
+    let db = AccountsDb::new_single_for_tests();
+    assert_eq!(db.take_owner_account_growth(), None);
+}
+
+#[test]
+fn test_accounts_hash_checkpoint() {
+    let db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            checkpoint_hash_calculation: true,
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    // all the accounts are in the last of the 4 bins
+    let accounts: Vec<_> = (0..3u8)
+        .map(|i| {
+            let mut pubkey = [0xff; PUBKEY_BYTES];
+            pubkey[PUBKEY_BYTES - 1] = i;
+            (
+                Pubkey::new_from_array(pubkey),
+                AccountSharedData::new(u64::from(i) + 1, 0, &Pubkey::default()),
+            )
+        })
+        .collect();
+    let accounts: Vec<_> = accounts
+        .iter()
+        .map(|(pubkey, account)| (pubkey, account))
+        .collect();
+    db.store_for_tests(0, &accounts);
+    db.add_root_and_flush_write_cache(0);
+
+    let (storages, _) = db.get_storages(..=0);
+    let storages = SortedStorages::new(&storages);
+    let calculate_accounts_hash = || {
+        db.calculate_accounts_hash(
+            &CalcAccountsHashConfig::default(),
+            &storages,
+            HashStats::default(),
+        )
+    };
+    let checkpoint_dir = db
+        .accounts_hash_cache_path
+        .join(AccountsDb::ACCOUNTS_HASH_CHECKPOINT_DIR);
+    let (accounts_hash, capitalization) = calculate_accounts_hash();
+    assert_eq!(capitalization, 6);
+    // the checkpoint is removed once the calculation is done
+    assert!(!checkpoint_dir.exists());
+
+    // An interrupted calculation leaves the bin ranges it completed in the checkpoint, which the
+    // next calculation of the same storages resumes from.
+    let checkpoint = db.open_accounts_hash_checkpoint(&storages).unwrap();
+    checkpoint
+        .complete_bin_range(&mut [checkpoint.create_hashes_file(0)], 10)
+        .unwrap();
+    drop(checkpoint);
+    assert_eq!(
+        calculate_accounts_hash(),
+        (accounts_hash, capitalization + 10)
+    );
+    assert!(!checkpoint_dir.exists());
+
+    // The checkpoint of other storages is not resumed from.
+    db.store_for_tests(1, &accounts[..1]);
+    db.add_root_and_flush_write_cache(1);
+    let checkpoint = db.open_accounts_hash_checkpoint(&storages).unwrap();
+    checkpoint
+        .complete_bin_range(&mut [checkpoint.create_hashes_file(0)], 10)
+        .unwrap();
+    drop(checkpoint);
+    let (storages, _) = db.get_storages(..=1);
+    let storages = SortedStorages::new(&storages);
+    let (_, capitalization) = db.calculate_accounts_hash(
+        &CalcAccountsHashConfig::default(),
+        &storages,
+        HashStats::default(),
+    );
+    assert_eq!(capitalization, 6);
+}
@@ -7,6 +7,7 @@ use {
         accounts_db::AccountsFileId,
         accounts_update_notifier_interface::AccountForGeyser,
         append_vec::{AppendVec, AppendVecError, IndexInfo},
+        file_io::IoBackend,
         storable_accounts::StorableAccounts,
         tiered_storage::{
             error::TieredStorageError, hot::HOT_FORMAT, index::IndexOffset, TieredStorage,
@@ -104,6 +105,22 @@ impl AccountsFile {
         }
     }
 
+    /// if storage is not compressed, compress it into another instance with the zstd compression `level`
+    pub(crate) fn compress(&self, level: i32) -> Result<Option<Self>> {
+        match self {
+            Self::AppendVec(av) => Ok(av.compress(level)?.map(Self::AppendVec)),
+            Self::TieredStorage(_) => Ok(None),
+        }
+    }
+
+    /// Return the length of the file of the storage, if it is compressed
+    pub fn compressed_len(&self) -> Option<u64> {
+        match self {
+            Self::AppendVec(av) => av.compressed_len(),
+            Self::TieredStorage(_) => None,
+        }
+    }
+
     /// Return the total number of bytes of the zero lamport single ref accounts in the storage.
     /// Those bytes are "dead" and can be shrunk away.
     pub(crate) fn dead_bytes_due_to_zero_lamport_single_ref(&self, count: usize) -> usize {
@@ -211,6 +228,32 @@ impl AccountsFile {
         }
     }
 
+    /// return the `AccountSharedData`s for the accounts at the offsets of the accounts files, like
+    /// `get_account_shared_data()` does for each.
+    ///
+    /// Only the accounts of append vecs are read in batches with `io_backend`.
+    pub(crate) fn get_accounts_shared_data_batch(
+        io_backend: IoBackend,
+        accounts: &[(&AccountsFile, usize)],
+    ) -> Vec<Option<AccountSharedData>> {
+        let append_vec_accounts: Vec<_> = accounts
+            .iter()
+            .filter_map(|(accounts_file, offset)| match accounts_file {
+                Self::AppendVec(av) => Some((av, *offset)),
+                Self::TieredStorage(_) => None,
+            })
+            .collect();
+        let mut append_vec_accounts =
+            AppendVec::get_accounts_shared_data_batch(io_backend, &append_vec_accounts).into_iter();
+        accounts
+            .iter()
+            .map(|(accounts_file, offset)| match accounts_file {
+                Self::AppendVec(_) => append_vec_accounts.next().unwrap(),
+                Self::TieredStorage(_) => accounts_file.get_account_shared_data(*offset),
+            })
+            .collect()
+    }
+
     /// returns an `IndexInfo` for an account at `offset`, if any.  Otherwise, return None.
     ///
     /// Only intended to be used with the accounts index.
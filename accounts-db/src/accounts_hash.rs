@@ -1,3 +1,6 @@
+mod checkpoint;
+
+pub(crate) use checkpoint::AccountsHashCheckpoint;
 use {
     crate::{
         accounts_db::AccountStorageEntry,
@@ -21,6 +24,7 @@ use {
         convert::TryInto,
         fs::File,
         io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+        ops::Range,
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -32,7 +36,7 @@ use {
 pub const MERKLE_FANOUT: usize = 16;
 
 /// 1 file containing account hashes sorted by pubkey
-struct AccountHashesFile {
+pub(crate) struct AccountHashesFile {
     /// Writer for hash files created in the temp directory, which will be deleted on drop.
     writer: Option<BufWriter<File>>,
 
@@ -49,9 +53,13 @@ impl AccountHashesFile {
                 dir_for_temp_cache_files.as_ref().display()
             )
         });
-        // Now that we have a file, create a writer.
+        Self::from_file(file, 0)
+    }
+
+    /// Create an AccountHashesFile with a writer to `file`, which holds `count` hashes already.
+    fn from_file(file: File, count: usize) -> Self {
         let writer = Some(BufWriter::new(file));
-        Self { writer, count: 0 }
+        Self { writer, count }
     }
 
     /// Return a file reader for the underlying file.
@@ -85,6 +93,13 @@ impl AccountHashesFile {
             .expect("write hash success");
         self.count += 1;
     }
+
+    /// Flush the hashes written so far, and sync them to disk
+    fn sync(&mut self) -> std::io::Result<()> {
+        let writer = self.writer.as_mut().unwrap();
+        writer.flush()?;
+        writer.get_ref().sync_data()
+    }
 }
 
 /// parameters to calculate accounts hash
@@ -116,6 +131,8 @@ pub struct HashStats {
     pub drop_hash_files_us: u64,
     pub sort_time_total_us: u64,
     pub hash_total: usize,
+    /// # of bin ranges which were loaded from the accounts hash checkpoint instead of deduped
+    pub num_checkpointed_bin_ranges_resumed: usize,
     pub num_snapshot_storage: usize,
     pub scan_chunks: usize,
     pub num_slots: usize,
@@ -173,6 +190,11 @@ impl HashStats {
             ("drop_hash_files_us", self.drop_hash_files_us, i64),
             ("sort_us", self.sort_time_total_us, i64),
             ("hash_total", self.hash_total, i64),
+            (
+                "num_checkpointed_bin_ranges_resumed",
+                self.num_checkpointed_bin_ranges_resumed,
+                i64
+            ),
             ("storage_sort_us", self.storage_sort_us, i64),
             ("collect_snapshots_us", self.collect_snapshots_us, i64),
             ("num_snapshot_storage", self.num_snapshot_storage, i64),
@@ -467,6 +489,9 @@ pub struct AccountsHasher<'a> {
     /// The directory where temporary cache files are put
     pub dir_for_temp_cache_files: PathBuf,
     pub(crate) active_stats: &'a ActiveStats,
+    /// If set, the hashes files are kept in the checkpoint, and the bins are deduped one bin
+    /// range at a time, from the bin ranges which are not completed yet
+    pub(crate) checkpoint: Option<&'a AccountsHashCheckpoint>,
 }
 
 /// Pointer to a specific item in chunked accounts hash slices.
@@ -796,6 +821,73 @@ impl AccountsHasher<'_> {
         sorted_data_by_pubkey: &[&[CalculateHashIntermediate]],
         stats: &mut HashStats,
         max_bin: usize,
+    ) -> (Vec<AccountHashesFile>, u64) {
+        let Some(checkpoint) = self.checkpoint else {
+            return self.de_dup_accounts_in_bins(sorted_data_by_pubkey, stats, 0..max_bin, max_bin);
+        };
+
+        let mut hashes = Vec::with_capacity(max_bin);
+        let mut lamports_total = 0u64;
+        for index in 0..checkpoint.num_bin_ranges() {
+            let bin_range = checkpoint.bin_range(index);
+            let loaded = if index < checkpoint.num_completed_bin_ranges() {
+                match checkpoint.load_bin_range(index) {
+                    Ok(loaded) => Some(loaded),
+                    Err(err) => {
+                        warn!(
+                            "Unable to load bin range {index} of accounts hash checkpoint, \
+                             deduping it again: {err}"
+                        );
+                        checkpoint.truncate(index);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let (mut hashes_range, lamports_range) = match loaded {
+                Some((hashes_range, lamports_range)) => {
+                    stats.num_checkpointed_bin_ranges_resumed += 1;
+                    stats.hash_total += hashes_range
+                        .iter()
+                        .map(AccountHashesFile::count)
+                        .sum::<usize>();
+                    (hashes_range, lamports_range)
+                }
+                None => {
+                    let (mut hashes_range, lamports_range) = self.de_dup_accounts_in_bins(
+                        sorted_data_by_pubkey,
+                        stats,
+                        bin_range,
+                        max_bin,
+                    );
+                    if let Err(err) =
+                        checkpoint.complete_bin_range(&mut hashes_range, lamports_range)
+                    {
+                        warn!(
+                            "Unable to record bin range {index} in accounts hash checkpoint: \
+                             {err}"
+                        );
+                    }
+                    (hashes_range, lamports_range)
+                }
+            };
+            lamports_total = lamports_total
+                .checked_add(lamports_range)
+                .expect("summing capitalization cannot overflow");
+            hashes.append(&mut hashes_range);
+        }
+        (hashes, lamports_total)
+    }
+
+    /// Dedups the accounts of the bins in `bin_range`, of the `max_bin` bins, like
+    /// `de_dup_accounts()`.
+    fn de_dup_accounts_in_bins(
+        &self,
+        sorted_data_by_pubkey: &[&[CalculateHashIntermediate]],
+        stats: &mut HashStats,
+        bin_range: Range<usize>,
+        max_bin: usize,
     ) -> (Vec<AccountHashesFile>, u64) {
         // 1. eliminate zero lamport accounts
         // 2. pick the highest slot or (slot = and highest version) of each pubkey
@@ -817,7 +909,7 @@ impl AccountsHasher<'_> {
             hashes_files: hashes,
             hashes_count: hash_total,
             lamports_sum: lamports_total,
-        } = (0..max_bin)
+        } = bin_range
             .into_par_iter()
             .fold(DedupResult::default, |mut accum, bin| {
                 let (hashes_file, lamports_bin) =
@@ -1136,7 +1228,10 @@ impl AccountsHasher<'_> {
             stats,
         );
 
-        let mut hashes = AccountHashesFile::new(&self.dir_for_temp_cache_files);
+        let mut hashes = match self.checkpoint {
+            Some(checkpoint) => checkpoint.create_hashes_file(pubkey_bin),
+            None => AccountHashesFile::new(&self.dir_for_temp_cache_files),
+        };
 
         let mut overall_sum: u64 = 0;
 
@@ -1357,6 +1452,7 @@ mod tests {
                 zero_lamport_accounts: ZeroLamportAccounts::Excluded,
                 dir_for_temp_cache_files,
                 active_stats: &ACTIVE_STATS,
+                checkpoint: None,
             }
         }
     }
@@ -0,0 +1,306 @@
+//! Checkpoints of the full accounts hash calculation.
+//!
+//! The scan of the storages is already saved in the accounts hash cache files, so a calculation
+//! which is restarted does not scan the storages again.  The dedup of the scanned accounts into
+//! the account hashes is checkpointed here: the bins are deduped one range at a time, and once a
+//! range is done, its hashes files are synced and the range is recorded in the manifest.  A
+//! calculation of the same storages resumes from the ranges recorded, instead of deduping all the
+//! bins again.
+
+use {
+    super::AccountHashesFile,
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_hash::Hash,
+    std::{
+        fs::{self, File},
+        io,
+        ops::Range,
+        path::{Path, PathBuf},
+        sync::Mutex,
+    },
+};
+
+/// # of bin ranges a calculation is checkpointed at most
+const MAX_NUM_BIN_RANGES: usize = 64;
+
+const MANIFEST_FILE_NAME: &str = "manifest";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Manifest {
+    /// Identifies the calculation which is checkpointed
+    fingerprint: u64,
+    bins: usize,
+    num_bin_ranges: usize,
+    /// The bin ranges which are deduped, in order
+    completed_bin_ranges: Vec<CompletedBinRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CompletedBinRange {
+    /// # hashes in each bin of the range
+    num_hashes: Vec<usize>,
+    lamports: u64,
+}
+
+/// Checkpoint of a full accounts hash calculation
+#[derive(Debug)]
+pub(crate) struct AccountsHashCheckpoint {
+    dir: PathBuf,
+    bins: usize,
+    num_bin_ranges: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    manifest: Manifest,
+    /// False once the manifest fails to be written, after which no more bin ranges are recorded
+    is_recording: bool,
+}
+
+impl AccountsHashCheckpoint {
+    /// Opens the checkpoint of the calculation identified by `fingerprint` in `dir`.
+    ///
+    /// If `dir` holds the checkpoint of another calculation, it is discarded.
+    pub(crate) fn open(dir: impl Into<PathBuf>, fingerprint: u64, bins: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        let manifest = Manifest {
+            fingerprint,
+            bins,
+            num_bin_ranges: bins.clamp(1, MAX_NUM_BIN_RANGES),
+            completed_bin_ranges: Vec::default(),
+        };
+        let manifest = match Self::read_manifest(&dir) {
+            Some(existing)
+                if existing.fingerprint == manifest.fingerprint
+                    && existing.bins == manifest.bins
+                    && existing.num_bin_ranges == manifest.num_bin_ranges =>
+            {
+                info!(
+                    "Resuming accounts hash calculation from checkpoint at {}: {} of {} bin \
+                     ranges are done",
+                    dir.display(),
+                    existing.completed_bin_ranges.len(),
+                    existing.num_bin_ranges,
+                );
+                existing
+            }
+            _ => {
+                match fs::remove_dir_all(&dir) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+                fs::create_dir_all(&dir)?;
+                manifest
+            }
+        };
+        Ok(Self {
+            dir,
+            bins: manifest.bins,
+            num_bin_ranges: manifest.num_bin_ranges,
+            state: Mutex::new(State {
+                manifest,
+                is_recording: true,
+            }),
+        })
+    }
+
+    fn read_manifest(dir: &Path) -> Option<Manifest> {
+        let file = File::open(dir.join(MANIFEST_FILE_NAME)).ok()?;
+        bincode::deserialize_from(io::BufReader::new(file)).ok()
+    }
+
+    pub(crate) fn num_bin_ranges(&self) -> usize {
+        self.num_bin_ranges
+    }
+
+    pub(crate) fn num_completed_bin_ranges(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .manifest
+            .completed_bin_ranges
+            .len()
+    }
+
+    /// Returns the bins of the bin range at `index`
+    pub(crate) fn bin_range(&self, index: usize) -> Range<usize> {
+        let (bins, num_bin_ranges) = (self.bins, self.num_bin_ranges);
+        (index * bins / num_bin_ranges)..((index + 1) * bins / num_bin_ranges)
+    }
+
+    fn hashes_file_path(&self, bin: usize) -> PathBuf {
+        self.dir.join(format!("{bin}.hashes"))
+    }
+
+    /// Creates the hashes file of `bin`, in place of the one of a previous calculation, if any
+    pub(crate) fn create_hashes_file(&self, bin: usize) -> AccountHashesFile {
+        let path = self.hashes_file_path(bin);
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap_or_else(|err| panic!("Unable to create file {}: {err}", path.display()));
+        AccountHashesFile::from_file(file, 0)
+    }
+
+    /// Returns the hashes files and the lamports of the completed bin range at `index`
+    pub(crate) fn load_bin_range(&self, index: usize) -> io::Result<(Vec<AccountHashesFile>, u64)> {
+        let state = self.state.lock().unwrap();
+        let completed_bin_range = &state.manifest.completed_bin_ranges[index];
+        let hashes_files = self
+            .bin_range(index)
+            .zip(&completed_bin_range.num_hashes)
+            .map(|(bin, &num_hashes)| {
+                let file = File::options()
+                    .read(true)
+                    .write(true)
+                    .open(self.hashes_file_path(bin))?;
+                let expected_len = num_hashes * std::mem::size_of::<Hash>();
+                if file.metadata()?.len() != expected_len as u64 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("hashes file of bin {bin} is not {expected_len} bytes"),
+                    ));
+                }
+                Ok(AccountHashesFile::from_file(file, num_hashes))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok((hashes_files, completed_bin_range.lamports))
+    }
+
+    /// Records the bin range after the completed ones, with its `hashes_files` and `lamports`.
+    ///
+    /// The hashes files are synced first, so that a recorded range is never lost.  If recording
+    /// fails, the calculation goes on without recording the rest of its bin ranges.
+    pub(crate) fn complete_bin_range(
+        &self,
+        hashes_files: &mut [AccountHashesFile],
+        lamports: u64,
+    ) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.manifest.completed_bin_ranges.push(CompletedBinRange {
+            num_hashes: hashes_files.iter().map(AccountHashesFile::count).collect(),
+            lamports,
+        });
+        if !state.is_recording {
+            return Ok(());
+        }
+
+        let result = (|| {
+            for hashes_file in hashes_files.iter_mut() {
+                hashes_file.sync()?;
+            }
+            let path = self.dir.join(MANIFEST_FILE_NAME);
+            let tmp_path = self.dir.join(format!("{MANIFEST_FILE_NAME}.tmp"));
+            let mut file = File::create(&tmp_path)?;
+            bincode::serialize_into(&mut file, &state.manifest).map_err(io::Error::other)?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, &path)
+        })();
+        state.is_recording = result.is_ok();
+        datapoint_info!(
+            "accounts_hash_checkpoint",
+            (
+                "num_completed_bin_ranges",
+                state.manifest.completed_bin_ranges.len(),
+                i64
+            ),
+            ("num_bin_ranges", self.num_bin_ranges, i64),
+            ("recorded", result.is_ok(), bool),
+        );
+        result
+    }
+
+    /// Discards the bin ranges from `index` on, so that they are deduped again
+    pub(crate) fn truncate(&self, index: usize) {
+        self.state
+            .lock()
+            .unwrap()
+            .manifest
+            .completed_bin_ranges
+            .truncate(index);
+    }
+
+    /// Removes the checkpoint, once the calculation is done
+    pub(crate) fn remove(self) {
+        if let Err(err) = fs::remove_dir_all(&self.dir) {
+            warn!(
+                "Unable to remove accounts hash checkpoint at {}: {err}",
+                self.dir.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    #[test]
+    fn test_accounts_hash_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("checkpoint");
+        let bins = 4;
+
+        let checkpoint = AccountsHashCheckpoint::open(&dir, 1, bins).unwrap();
+        assert_eq!(checkpoint.num_bin_ranges(), bins);
+        assert_eq!(checkpoint.num_completed_bin_ranges(), 0);
+        assert_eq!(checkpoint.bin_range(0), 0..1);
+        assert_eq!(checkpoint.bin_range(3), 3..4);
+
+        let mut hashes_files = vec![checkpoint.create_hashes_file(0)];
+        hashes_files[0].write(&Hash::new_unique());
+        hashes_files[0].write(&Hash::new_unique());
+        checkpoint
+            .complete_bin_range(&mut hashes_files, 10)
+            .unwrap();
+        assert_eq!(checkpoint.num_completed_bin_ranges(), 1);
+        drop(checkpoint);
+
+        // The same calculation resumes from the completed bin ranges.
+        let checkpoint = AccountsHashCheckpoint::open(&dir, 1, bins).unwrap();
+        assert_eq!(checkpoint.num_completed_bin_ranges(), 1);
+        let (hashes_files, lamports) = checkpoint.load_bin_range(0).unwrap();
+        assert_eq!(lamports, 10);
+        assert_eq!(
+            hashes_files
+                .iter()
+                .map(AccountHashesFile::count)
+                .collect::<Vec<_>>(),
+            vec![2],
+        );
+        drop(hashes_files);
+
+        // A checkpoint with a truncated hashes file is not loaded.
+        fs::write(checkpoint.hashes_file_path(0), [0; 3]).unwrap();
+        assert!(checkpoint.load_bin_range(0).is_err());
+
+        // Another calculation discards the checkpoint.
+        let checkpoint = AccountsHashCheckpoint::open(&dir, 2, bins).unwrap();
+        assert_eq!(checkpoint.num_completed_bin_ranges(), 0);
+        assert!(!checkpoint.hashes_file_path(0).exists());
+
+        checkpoint.remove();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_bin_ranges() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkpoint = AccountsHashCheckpoint::open(temp_dir.path(), 1, 65536).unwrap();
+        assert_eq!(checkpoint.num_bin_ranges(), MAX_NUM_BIN_RANGES);
+        let mut next_bin = 0;
+        for index in 0..checkpoint.num_bin_ranges() {
+            let bin_range = checkpoint.bin_range(index);
+            assert_eq!(bin_range.start, next_bin);
+            assert_eq!(bin_range.len(), 1024);
+            next_bin = bin_range.end;
+        }
+        assert_eq!(next_bin, 65536);
+    }
+}
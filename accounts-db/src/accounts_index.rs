@@ -5,6 +5,7 @@ mod roots_tracker;
 mod secondary;
 use {
     crate::{
+        account_growth::AccountGrowthCounters,
         accounts_index_storage::{AccountsIndexStorage, Startup},
         accounts_partition::RentPayingAccountsByPartition,
         ancestors::Ancestors,
@@ -25,6 +26,7 @@ use {
         ThreadPool,
     },
     roots_tracker::RootsTracker,
+    secondary::{data_size_index_key, nonce_authority},
     solana_account::ReadableAccount,
     solana_clock::{BankId, Slot},
     solana_measure::measure::Measure,
@@ -268,6 +270,8 @@ pub struct AccountsIndex<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> {
     program_id_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    data_size_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    nonce_authority_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     pub roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -325,6 +329,12 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            data_size_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
+                "data_size_index_stats",
+            ),
+            nonce_authority_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
+                "nonce_authority_index_stats",
+            ),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -643,6 +653,26 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                     config,
                 );
             }
+            ScanTypes::Indexed(IndexKey::DataSize(owner, data_len)) => {
+                self.do_scan_secondary_index(
+                    ancestors,
+                    func,
+                    &self.data_size_index,
+                    &data_size_index_key(&owner, data_len),
+                    Some(max_root),
+                    config,
+                );
+            }
+            ScanTypes::Indexed(IndexKey::NonceAuthority(authority)) => {
+                self.do_scan_secondary_index(
+                    ancestors,
+                    func,
+                    &self.nonce_authority_index,
+                    &authority,
+                    Some(max_root),
+                    config,
+                );
+            }
         }
 
         {
@@ -1265,6 +1295,13 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 .index
                 .get(index_key)
                 .map(|x| x.len()),
+            // keyed by an owner and a data length, not by a single key
+            AccountIndex::DataSize => None,
+            AccountIndex::NonceAuthority => self
+                .nonce_authority_index
+                .index
+                .get(index_key)
+                .map(|x| x.len()),
         }
     }
 
@@ -1282,6 +1319,17 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        if !self.data_size_index.index.is_empty() {
+            info!(
+                "secondary index: {:?}, keys: {}",
+                AccountIndex::DataSize,
+                self.data_size_index.index.len()
+            );
+        }
+        if !self.nonce_authority_index.index.is_empty() {
+            info!("secondary index: {:?}", AccountIndex::NonceAuthority);
+            self.nonce_authority_index.log_contents();
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1302,6 +1350,21 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         {
             self.program_id_index.insert(account_owner, pubkey);
         }
+        if account_indexes.contains(&AccountIndex::DataSize)
+            && account_indexes.include_key(account_owner)
+        {
+            self.data_size_index.insert(
+                &data_size_index_key(account_owner, account_data.len() as u64),
+                pubkey,
+            );
+        }
+        if account_indexes.contains(&AccountIndex::NonceAuthority) {
+            if let Some(authority) = nonce_authority(account_owner, account_data) {
+                if account_indexes.include_key(&authority) {
+                    self.nonce_authority_index.insert(&authority, pubkey);
+                }
+            }
+        }
         // Note because of the below check below on the account data length, when an
         // account hits zero lamports and is reset to AccountSharedData::Default, then we skip
         // the below updates to the secondary indexes.
@@ -1507,6 +1570,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
 
     /// Updates the given pubkey at the given slot with the new account information.
     /// on return, the index's previous account info may be returned in 'reclaims' depending on 'previous_slot_entry_was_cached'
+    /// Returns true if the pubkey was not in the index.
     pub fn upsert(
         &self,
         new_slot: Slot,
@@ -1517,7 +1581,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         account_info: T,
         reclaims: &mut SlotList<T>,
         reclaim: UpsertReclaim,
-    ) {
+    ) -> bool {
         // vast majority of updates are to item already in accounts index, so store as raw to avoid unnecessary allocations
         let store_raw = true;
 
@@ -1540,8 +1604,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         );
         let map = self.get_bin(pubkey);
 
-        map.upsert(pubkey, new_item, Some(old_slot), reclaims, reclaim);
+        let created = map.upsert(pubkey, new_item, Some(old_slot), reclaims, reclaim);
         self.update_secondary_indexes(pubkey, account, account_indexes);
+        created
+    }
+
+    /// Returns the # of accounts in the index, and the # created and deleted since startup
+    pub fn account_growth_counters(&self) -> AccountGrowthCounters {
+        self.storage.storage.stats.account_growth_counters()
     }
 
     pub fn ref_count_from_storage(&self, pubkey: &Pubkey) -> RefCount {
@@ -1570,6 +1640,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        if account_indexes.contains(&AccountIndex::DataSize) {
+            self.data_size_index.remove_by_inner_key(inner_key);
+        }
+
+        if account_indexes.contains(&AccountIndex::NonceAuthority) {
+            self.nonce_authority_index.remove_by_inner_key(inner_key);
+        }
     }
 
     fn purge_older_root_entries(
@@ -1758,7 +1836,13 @@ pub mod tests {
         crate::bucket_map_holder::{AtomicAge, BucketMapHolder},
         account_map_entry::AccountMapEntryMeta,
         solana_account::{AccountSharedData, WritableAccount},
+        solana_hash::Hash,
+        solana_nonce::{
+            state::{Data as NonceData, DurableNonce, State as NonceState},
+            versions::Versions as NonceVersions,
+        },
         solana_pubkey::PUBKEY_BYTES,
+        solana_sdk_ids::system_program,
         spl_generic_token::{spl_token_ids, token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET},
         std::ops::{
             Bound::{Excluded, Included, Unbounded},
@@ -3380,6 +3464,144 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_data_size_secondary_index() {
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::DataSize]),
+        };
+        let account_key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = AccountSharedData::create(1, vec![0; 42], owner, false, 0);
+
+        // Inserting the same index multiple times should be ok
+        for _ in 0..2 {
+            index.update_secondary_indexes(&account_key, &account, &secondary_indexes);
+            check_secondary_index_mapping_correct(
+                &index.data_size_index,
+                &[data_size_index_key(&owner, 42)],
+                &account_key,
+            );
+        }
+        assert_ne!(
+            data_size_index_key(&owner, 42),
+            data_size_index_key(&owner, 43)
+        );
+        assert_ne!(
+            data_size_index_key(&owner, 42),
+            data_size_index_key(&Pubkey::new_unique(), 42)
+        );
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::DataSize, &owner),
+            None
+        );
+
+        // Excluded owners are not indexed
+        let excluded_key = Pubkey::new_unique();
+        let excluded_owner = Pubkey::new_unique();
+        index.update_secondary_indexes(
+            &excluded_key,
+            &AccountSharedData::create(1, vec![0; 42], excluded_owner, false, 0),
+            &AccountSecondaryIndexes {
+                keys: Some(AccountSecondaryIndexesIncludeExclude {
+                    keys: HashSet::from([excluded_owner]),
+                    exclude: true,
+                }),
+                ..secondary_indexes.clone()
+            },
+        );
+        assert!(index
+            .data_size_index
+            .reverse_index
+            .get(&excluded_key)
+            .is_none());
+
+        index.upsert(
+            0,
+            0,
+            &account_key,
+            &account,
+            &secondary_indexes,
+            true,
+            &mut vec![],
+            UPSERT_POPULATE_RECLAIMS,
+        );
+        index.slot_list_mut(&account_key, |slot_list| slot_list.clear());
+
+        // Everything should be deleted
+        let _ = index.handle_dead_keys(&[&account_key], &secondary_indexes);
+        assert!(index.data_size_index.index.is_empty());
+        assert!(index.data_size_index.reverse_index.is_empty());
+    }
+
+    #[test]
+    fn test_nonce_authority_secondary_index() {
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+        let secondary_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::NonceAuthority]),
+        };
+        let account_key = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let new_nonce_account = |state| {
+            AccountSharedData::new_data(1, &NonceVersions::new(state), &system_program::id())
+                .unwrap()
+        };
+        let account = new_nonce_account(NonceState::Initialized(NonceData::new(
+            authority,
+            DurableNonce::from_blockhash(&Hash::new_unique()),
+            5_000,
+        )));
+
+        // Inserting the same index multiple times should be ok
+        for _ in 0..2 {
+            index.update_secondary_indexes(&account_key, &account, &secondary_indexes);
+            check_secondary_index_mapping_correct(
+                &index.nonce_authority_index,
+                &[authority],
+                &account_key,
+            );
+        }
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::NonceAuthority, &authority),
+            Some(1)
+        );
+
+        // Uninitialized nonce accounts, and accounts of other programs, are not indexed
+        let other_key = Pubkey::new_unique();
+        index.update_secondary_indexes(
+            &other_key,
+            &new_nonce_account(NonceState::Uninitialized),
+            &secondary_indexes,
+        );
+        let mut other_account = account.clone();
+        other_account.set_owner(Pubkey::new_unique());
+        index.update_secondary_indexes(&other_key, &other_account, &secondary_indexes);
+        assert!(index
+            .nonce_authority_index
+            .reverse_index
+            .get(&other_key)
+            .is_none());
+
+        index.upsert(
+            0,
+            0,
+            &account_key,
+            &account,
+            &secondary_indexes,
+            true,
+            &mut vec![],
+            UPSERT_POPULATE_RECLAIMS,
+        );
+        index.slot_list_mut(&account_key, |slot_list| slot_list.clear());
+
+        // Everything should be deleted
+        let _ = index.handle_dead_keys(&[&account_key], &secondary_indexes);
+        assert!(index.nonce_authority_index.index.is_empty());
+        assert!(index.nonce_authority_index.reverse_index.is_empty());
+    }
+
     fn run_test_secondary_indexes_same_slot_and_forks<
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
     >(
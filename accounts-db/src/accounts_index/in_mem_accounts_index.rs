@@ -556,6 +556,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> InMemAccountsIndex<T,
         self.set_age_to_future(entry, upsert_cached);
     }
 
+    /// Returns true if `pubkey` was not in the index.
     pub fn upsert(
         &self,
         pubkey: &Pubkey,
@@ -563,8 +564,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> InMemAccountsIndex<T,
         other_slot: Option<Slot>,
         reclaims: &mut SlotList<T>,
         reclaim: UpsertReclaim,
-    ) {
+    ) -> bool {
         let mut updated_in_mem = true;
+        let mut created = false;
         // try to get it just from memory first using only a read lock
         self.get_only_in_mem(pubkey, false, |entry| {
             if let Some(entry) = entry {
@@ -600,7 +602,8 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> InMemAccountsIndex<T,
                             disk_entry
                         } else {
                             // not on disk, so insert new thing
-                            self.stats().inc_insert();
+                            created = true;
+                            self.stats().inc_account_created();
                             new_value.into_account_map_entry(&self.storage)
                         };
                         assert!(new_value.dirty());
@@ -616,6 +619,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> InMemAccountsIndex<T,
         if updated_in_mem {
             Self::update_stat(&self.stats().updates_in_mem, 1);
         }
+        created
     }
 
     fn update_entry_stats(&self, stopped_measure: Measure, found: bool) {
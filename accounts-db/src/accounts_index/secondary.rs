@@ -1,4 +1,10 @@
-use {solana_pubkey::Pubkey, std::collections::HashSet};
+use {
+    solana_nonce::{state::State as NonceState, versions::Versions as NonceVersions},
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program,
+    solana_sha256_hasher::hashv,
+    std::collections::HashSet,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct AccountSecondaryIndexes {
@@ -32,6 +38,10 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    /// Indexes accounts by their owner and data length, for `dataSize` filters
+    DataSize,
+    /// Indexes the initialized nonce accounts by their authority
+    NonceAuthority,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -39,4 +49,25 @@ pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    /// The owner and the data length of accounts
+    DataSize(Pubkey, u64),
+    NonceAuthority(Pubkey),
+}
+
+/// Returns the key of the data size index for the accounts owned by `owner` with `data_len` bytes
+/// of data. Both are hashed into a single key, so scans of the index must still check the owner
+/// and data length of the accounts they return.
+pub(crate) fn data_size_index_key(owner: &Pubkey, data_len: u64) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[owner.as_ref(), &data_len.to_le_bytes()]).to_bytes())
+}
+
+/// Returns the authority of the account, if it is an initialized nonce account
+pub(crate) fn nonce_authority(owner: &Pubkey, data: &[u8]) -> Option<Pubkey> {
+    if owner != &system_program::id() || data.len() != NonceState::size() {
+        return None;
+    }
+    match bincode::deserialize::<NonceVersions>(data).ok()?.state() {
+        NonceState::Uninitialized => None,
+        NonceState::Initialized(data) => Some(data.authority),
+    }
 }
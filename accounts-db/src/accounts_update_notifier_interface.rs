@@ -10,6 +10,12 @@ pub trait AccountsUpdateNotifierInterface: std::fmt::Debug {
     /// Enable account notifications from snapshot
     fn snapshot_notifications_enabled(&self) -> bool;
 
+    /// Replay the accounts restored from a snapshot in pubkey order, instead of notifying of
+    /// each version of them in the snapshot's storages
+    fn snapshot_replay_enabled(&self) -> bool {
+        false
+    }
+
     /// Notified when an account is updated at runtime, due to transaction activities
     fn notify_account_update(
         &self,
@@ -20,6 +26,20 @@ pub trait AccountsUpdateNotifierInterface: std::fmt::Debug {
         write_version: u64,
     );
 
+    /// Notified when accounts are updated at runtime, with the accounts of a store at once.
+    /// Defaults to notifying of each account on its own.
+    fn notify_accounts_update(&self, slot: Slot, accounts: &[AccountUpdate<'_>]) {
+        for account in accounts {
+            self.notify_account_update(
+                slot,
+                account.account,
+                &account.txn,
+                account.pubkey,
+                account.write_version,
+            );
+        }
+    }
+
     /// Notified when the AccountsDb is initialized at start when restored
     /// from a snapshot.
     fn notify_account_restore_from_snapshot(
@@ -29,12 +49,38 @@ pub trait AccountsUpdateNotifierInterface: std::fmt::Debug {
         account: &AccountForGeyser<'_>,
     );
 
+    /// Notified periodically while the accounts restored from a snapshot are replayed, and once
+    /// they all are.
+    fn notify_snapshot_replay_progress(&self, _progress: &SnapshotReplayProgress) {}
+
     /// Notified when all accounts have been notified when restoring from a snapshot.
     fn notify_end_of_restore_from_snapshot(&self);
 }
 
 pub type AccountsUpdateNotifier = Arc<dyn AccountsUpdateNotifierInterface + Sync + Send>;
 
+/// An account updated at runtime, as notified in batches
+#[derive(Debug, Clone, Copy)]
+pub struct AccountUpdate<'a> {
+    pub pubkey: &'a Pubkey,
+    pub account: &'a AccountSharedData,
+    /// The transaction which updated the account, if any
+    pub txn: Option<&'a SanitizedTransaction>,
+    pub write_version: u64,
+}
+
+/// The progress of the replay of the accounts restored from a snapshot
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotReplayProgress {
+    /// The slot of the snapshot
+    pub slot: Slot,
+    pub num_accounts_notified: u64,
+    /// The # of accounts to notify, which is an estimate until the replay is done
+    pub num_accounts: u64,
+    pub last_pubkey: Option<Pubkey>,
+    pub is_done: bool,
+}
+
 /// Account type with only the fields necessary for Geyser
 #[derive(Debug, Clone)]
 pub struct AccountForGeyser<'a> {
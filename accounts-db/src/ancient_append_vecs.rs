@@ -48,6 +48,9 @@ struct PackedAncientStorageTuning {
     can_randomly_shrink: bool,
     /// limit the max # of output storages to prevent packing from running too long
     max_resulting_storages: NonZeroU64,
+    /// if Some, storages with a lower ratio of alive bytes should be shrunk, instead of the ones
+    /// which are candidates for the normal shrink
+    shrink_ratio: Option<f64>,
 }
 
 /// info about a storage eligible to be combined into an ancient append vec.
@@ -340,15 +343,17 @@ impl AccountsDb {
         sorted_slots: Vec<Slot>,
         can_randomly_shrink: bool,
     ) {
+        let policy = self.ancient_packing_policy();
         let tuning = PackedAncientStorageTuning {
             // Slots old enough to be ancient.
-            max_ancient_slots: self.max_ancient_storages,
+            max_ancient_slots: policy.max_ancient_storages,
             // Don't re-pack anything just to shrink.
             // shrink_candidate_slots will handle these old storages.
             percent_of_alive_shrunk_data: 0,
             ideal_storage_size: NonZeroU64::new(get_ancient_append_vec_capacity()).unwrap(),
             can_randomly_shrink,
             max_resulting_storages: NonZeroU64::new(10).unwrap(),
+            shrink_ratio: policy.shrink_ratio,
         };
 
         let _guard = self.active_stats.activate(ActiveStatItem::SquashAncient);
@@ -504,6 +509,23 @@ impl AccountsDb {
                 );
             });
 
+        // bytes of the storages replaced by the packed ones, minus the bytes written to them
+        let bytes_combined = accounts_to_combine
+            .accounts_to_combine
+            .iter()
+            .map(|combine| combine.capacity)
+            .sum::<u64>();
+        let bytes_packed = pack.iter().map(|packed| packed.bytes).sum::<u64>()
+            + accounts_to_combine
+                .accounts_keep_slots
+                .values()
+                .map(|alive| alive.bytes as u64)
+                .sum::<u64>();
+        self.shrink_ancient_stats.bytes_reclaimed.fetch_add(
+            bytes_combined.saturating_sub(bytes_packed),
+            Ordering::Relaxed,
+        );
+
         let write_ancient_accounts = self.write_packed_storages(&accounts_to_combine, pack);
 
         self.finish_combine_ancient_slots_packed_internal(
@@ -525,7 +547,7 @@ impl AccountsDb {
         // divided by half of max ancient slots
         tuning.ideal_storage_size = NonZeroU64::new(
             (ancient_slot_infos.total_alive_bytes.0 * 2 / tuning.max_ancient_slots.max(1) as u64)
-                .max(self.ancient_storage_ideal_size),
+                .max(self.ancient_packing_policy().ideal_storage_size),
         )
         .unwrap();
 
@@ -580,7 +602,14 @@ impl AccountsDb {
         let is_high_slot = |slot| slot >= high_slot_boundary;
         for slot in &slots {
             if let Some(storage) = self.storage.get_slot_storage_entry(*slot) {
-                let is_candidate_for_shrink = self.is_candidate_for_shrink(&storage);
+                let is_candidate_for_shrink = match tuning.shrink_ratio {
+                    Some(shrink_ratio) => {
+                        let alive_bytes =
+                            storage.alive_bytes_exclude_zero_lamport_single_ref_accounts();
+                        (alive_bytes as f64) < storage.capacity() as f64 * shrink_ratio
+                    }
+                    None => self.is_candidate_for_shrink(&storage),
+                };
                 if infos.add(
                     *slot,
                     storage,
@@ -740,6 +769,7 @@ impl AccountsDb {
             if reopen {
                 // 'shrink_in_progress' is dead now. We can safely 'reopen' the new storage for 'slot'.
                 self.reopen_storage_as_readonly_shrinking_in_progress_ok(slot);
+                self.compress_ancient_storage_shrinking_in_progress_ok(slot);
             }
         }
         self.handle_dropped_roots_for_ancient(dropped_roots.into_iter());
@@ -2649,6 +2679,32 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_calc_ancient_slot_info_shrink_ratio() {
+        let alive = true;
+        let slots = 1;
+        // 1_040_000 is big enough relative to page size to cause shrink ratio to be triggered
+        let (db, slot1) = create_db_with_storages_and_index(alive, slots, Some(1_040_000));
+        let storage = db.storage.get_slot_storage_entry(slot1).unwrap();
+        let alive_ratio = storage.alive_bytes() as f64 / storage.capacity() as f64;
+        assert!(alive_ratio < 1.0);
+        for (shrink_ratio, should_shrink) in [
+            (None, db.is_candidate_for_shrink(&storage)),
+            (Some(alive_ratio / 2.0), false),
+            (Some(1.0), true),
+        ] {
+            let tuning = PackedAncientStorageTuning {
+                // irrelevant for what this test is trying to test, but necessary to avoid minimums
+                ideal_storage_size: NonZeroU64::new(get_ancient_append_vec_capacity()).unwrap(),
+                shrink_ratio,
+                ..default_tuning()
+            };
+            let infos = db.calc_ancient_slot_info(vec![slot1], &tuning);
+            assert_eq!(infos.all_infos.len(), 1);
+            assert_storage_info(&infos.all_infos[0], &storage, should_shrink);
+        }
+    }
+
     #[test]
     fn test_calc_ancient_slot_info_several() {
         let can_randomly_shrink = false;
@@ -3231,6 +3287,7 @@ pub mod tests {
             ideal_storage_size: NonZeroU64::new(1).unwrap(),
             can_randomly_shrink: false,
             max_resulting_storages: NonZeroU64::new(10).unwrap(),
+            shrink_ratio: None,
         }
     }
 
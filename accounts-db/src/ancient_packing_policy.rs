@@ -0,0 +1,121 @@
+//! The policy of packing ancient storages, which can be changed while the
+//! validator is running to trade disk i/o for disk footprint.
+
+use {
+    crate::accounts_db::{DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE, DEFAULT_MAX_ANCIENT_STORAGES},
+    serde_derive::{Deserialize, Serialize},
+    std::{fs::File, io, path::Path},
+    thiserror::Error,
+};
+
+#[derive(Error, Debug)]
+pub enum AncientPackingPolicyError {
+    #[error("failed to read the ancient packing policy file: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("failed to parse the ancient packing policy file: {0}")]
+    Parse(#[from] serde_yaml::Error),
+
+    #[error("the ideal storage size must not be 0")]
+    ZeroIdealStorageSize,
+
+    #[error("the max number of ancient storages must not be 0")]
+    ZeroMaxAncientStorages,
+
+    #[error("the shrink ratio must be greater than 0 and at most 1, got {0}")]
+    InvalidShrinkRatio(f64),
+}
+
+/// How ancient storages are packed
+///
+/// In the policy file, which is YAML, the fields that are not set keep their
+/// default values.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AncientPackingPolicy {
+    /// The smallest size of the ancient storages accounts are packed into.
+    /// The storages are bigger when there are more alive bytes to pack than
+    /// fit into half of `max_ancient_storages` of this size.
+    pub ideal_storage_size: u64,
+    /// The number of ancient storages the packing should converge to.
+    /// Storages are combined while there are more of them.
+    pub max_ancient_storages: usize,
+    /// Ancient storages with a lower ratio of alive bytes are shrunk by
+    /// repacking them. If None, the shrink ratio of all storages applies.
+    pub shrink_ratio: Option<f64>,
+}
+
+impl Default for AncientPackingPolicy {
+    fn default() -> Self {
+        Self {
+            ideal_storage_size: DEFAULT_ANCIENT_STORAGE_IDEAL_SIZE,
+            max_ancient_storages: DEFAULT_MAX_ANCIENT_STORAGES,
+            shrink_ratio: None,
+        }
+    }
+}
+
+impl AncientPackingPolicy {
+    /// Loads the policy from the YAML file at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AncientPackingPolicyError> {
+        let policy: Self = serde_yaml::from_reader(File::open(path)?)?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), AncientPackingPolicyError> {
+        if self.ideal_storage_size == 0 {
+            return Err(AncientPackingPolicyError::ZeroIdealStorageSize);
+        }
+        if self.max_ancient_storages == 0 {
+            return Err(AncientPackingPolicyError::ZeroMaxAncientStorages);
+        }
+        if let Some(shrink_ratio) = self.shrink_ratio {
+            if shrink_ratio.is_nan() || shrink_ratio <= 0.0 || shrink_ratio > 1.0 {
+                return Err(AncientPackingPolicyError::InvalidShrinkRatio(shrink_ratio));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::fs, tempfile::TempDir};
+
+    #[test]
+    fn test_load_ancient_packing_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("policy.yml");
+
+        fs::write(&path, "max_ancient_storages: 1000\nshrink_ratio: 0.5\n").unwrap();
+        assert_eq!(
+            AncientPackingPolicy::load(&path).unwrap(),
+            AncientPackingPolicy {
+                max_ancient_storages: 1000,
+                shrink_ratio: Some(0.5),
+                ..AncientPackingPolicy::default()
+            },
+        );
+
+        fs::write(&path, "shrink_ratio: 1.5\n").unwrap();
+        assert!(matches!(
+            AncientPackingPolicy::load(&path),
+            Err(AncientPackingPolicyError::InvalidShrinkRatio(_)),
+        ));
+        fs::write(&path, "ideal_storage_size: 0\n").unwrap();
+        assert!(matches!(
+            AncientPackingPolicy::load(&path),
+            Err(AncientPackingPolicyError::ZeroIdealStorageSize),
+        ));
+        fs::write(&path, "max_ancient_slots: 10\n").unwrap();
+        assert!(matches!(
+            AncientPackingPolicy::load(&path),
+            Err(AncientPackingPolicyError::Parse(_)),
+        ));
+        assert!(matches!(
+            AncientPackingPolicy::load(temp_dir.path().join("missing.yml")),
+            Err(AncientPackingPolicyError::Io(_)),
+        ));
+    }
+}
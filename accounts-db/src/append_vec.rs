@@ -4,6 +4,7 @@
 //!
 //! <https://docs.solanalabs.com/implemented-proposals/persistent-account-storage>
 
+mod compressed;
 mod meta;
 pub mod test_utils;
 
@@ -25,7 +26,7 @@ use {
         },
         accounts_hash::AccountHash,
         buffered_reader::{BufferedReader, BufferedReaderStatus, Stack},
-        file_io::read_into_buffer,
+        file_io::{read_batch, read_into_buffer, BatchRead, IoBackend},
         is_zero_lamport::IsZeroLamport,
         storable_accounts::StorableAccounts,
         u64_align,
@@ -177,6 +178,12 @@ enum AppendVecFileBacking {
     /// This was opened as a read only file
     #[cfg_attr(not(unix), allow(dead_code))]
     File(File),
+    /// A read only compressed file, decompressed into anonymous memory when opened
+    Compressed {
+        mmap: MmapMut,
+        /// Length of the compressed file
+        compressed_len: u64,
+    },
 }
 
 /// A thread-safe, file-backed block of memory used to store `Account` instances. Append operations
@@ -217,6 +224,7 @@ const PAGE_SIZE: usize = 4 * 1024;
 pub struct AppendVecStat {
     pub open_as_mmap: AtomicU64,
     pub open_as_file_io: AtomicU64,
+    pub open_as_compressed: AtomicU64,
     pub files_open: AtomicU64,
     pub files_dirty: AtomicU64,
 }
@@ -225,6 +233,7 @@ lazy_static! {
     pub static ref APPEND_VEC_STATS: AppendVecStat = AppendVecStat {
         open_as_mmap: AtomicU64::new(0),
         open_as_file_io: AtomicU64::new(0),
+        open_as_compressed: AtomicU64::new(0),
         files_open: AtomicU64::new(0),
         files_dirty: AtomicU64::new(0),
     };
@@ -249,6 +258,11 @@ impl Drop for AppendVec {
                     .open_as_file_io
                     .fetch_sub(1, Ordering::Relaxed);
             }
+            AppendVecFileBacking::Compressed { .. } => {
+                APPEND_VEC_STATS
+                    .open_as_compressed
+                    .fetch_sub(1, Ordering::Relaxed);
+            }
         }
         if self.remove_file_on_drop.load(Ordering::Acquire) {
             // If we're reopening in readonly mode, we don't delete the file. See
@@ -363,6 +377,8 @@ impl AppendVec {
                 AppendVecFileBacking::File(file) => {
                     file.sync_all()?;
                 }
+                // compressed files are synced when written, and never modified
+                AppendVecFileBacking::Compressed { .. } => {}
             }
             APPEND_VEC_STATS.files_dirty.fetch_sub(1, Ordering::Relaxed);
         }
@@ -386,8 +402,8 @@ impl AppendVec {
 
         #[cfg(unix)]
         match &self.backing {
-            AppendVecFileBacking::File(_file) => {
-                // already a file, so already read-only
+            AppendVecFileBacking::File(_file) | AppendVecFileBacking::Compressed { .. } => {
+                // already read-only
                 None
             }
             AppendVecFileBacking::Mmap(_mmap) => {
@@ -460,14 +476,36 @@ impl AppendVec {
     ) -> Result<Self> {
         let path = path.into();
         let file_size = std::fs::metadata(&path)?.len();
-        Self::sanitize_len_and_size(current_len, file_size as usize)?;
-
         let data = OpenOptions::new()
             .read(true)
             .write(true)
             .create(false)
             .open(&path)?;
 
+        if let Some(decompressed_len) = compressed::read_header(&data)? {
+            Self::sanitize_len_and_size(current_len, decompressed_len)?;
+            let mmap = compressed::decompress(&data, decompressed_len)?;
+            APPEND_VEC_STATS.files_open.fetch_add(1, Ordering::Relaxed);
+
+            APPEND_VEC_STATS
+                .open_as_compressed
+                .fetch_add(1, Ordering::Relaxed);
+
+            return Ok(AppendVec {
+                path,
+                backing: AppendVecFileBacking::Compressed {
+                    mmap,
+                    compressed_len: file_size,
+                },
+                append_lock: Mutex::new(()),
+                current_len: AtomicUsize::new(current_len),
+                file_size: decompressed_len as u64,
+                remove_file_on_drop: AtomicBool::new(true),
+                is_dirty: AtomicBool::new(false),
+            });
+        }
+        Self::sanitize_len_and_size(current_len, file_size as usize)?;
+
         #[cfg(unix)]
         // we must use mmap on non-linux
         if storage_access == StorageAccess::File {
@@ -518,8 +556,11 @@ impl AppendVec {
     #[cfg(feature = "dev-context-only-utils")]
     pub fn new_for_store_tool(path: impl Into<PathBuf>) -> Result<Self> {
         let path = path.into();
-        let file_size = std::fs::metadata(&path)?.len();
-        Self::new_from_file_unchecked(path, file_size as usize, StorageAccess::default())
+        let len = match compressed::read_header(&File::open(&path)?)? {
+            Some(decompressed_len) => decompressed_len,
+            None => std::fs::metadata(&path)?.len() as usize,
+        };
+        Self::new_from_file_unchecked(path, len, StorageAccess::default())
     }
 
     fn sanitize_layout_and_length(&self) -> (bool, usize) {
@@ -577,7 +618,7 @@ impl AppendVec {
                 };
                 *offset = pos + len;
             }
-            AppendVecFileBacking::File(_file) => {
+            AppendVecFileBacking::File(_file) | AppendVecFileBacking::Compressed { .. } => {
                 unimplemented!();
             }
         }
@@ -655,7 +696,7 @@ impl AppendVec {
         mut callback: impl for<'local> FnMut(StoredAccountMeta<'local>) -> Ret,
     ) -> Option<Ret> {
         match &self.backing {
-            AppendVecFileBacking::Mmap(mmap) => {
+            AppendVecFileBacking::Mmap(mmap) | AppendVecFileBacking::Compressed { mmap, .. } => {
                 let slice = self.get_valid_slice_from_mmap(mmap);
                 let (meta, next) = Self::get_type::<StoredMeta>(slice, offset)?;
                 let (account_meta, next) = Self::get_type::<AccountMeta>(slice, next)?;
@@ -741,7 +782,7 @@ impl AppendVec {
         mut callback: impl for<'local> FnMut(StoredAccountNoData<'local>) -> Ret,
     ) -> Option<Ret> {
         match &self.backing {
-            AppendVecFileBacking::Mmap(mmap) => {
+            AppendVecFileBacking::Mmap(mmap) | AppendVecFileBacking::Compressed { mmap, .. } => {
                 let slice = self.get_valid_slice_from_mmap(mmap);
                 let (meta, next) = Self::get_type::<StoredMeta>(slice, offset)?;
                 let (account_meta, _) = Self::get_type::<AccountMeta>(slice, next)?;
@@ -784,7 +825,7 @@ impl AppendVec {
     /// This is on the critical path of tx processing for accounts not in the read or write caches.
     pub fn get_account_shared_data(&self, offset: usize) -> Option<AccountSharedData> {
         match &self.backing {
-            AppendVecFileBacking::Mmap(_) => self
+            AppendVecFileBacking::Mmap(_) | AppendVecFileBacking::Compressed { .. } => self
                 .get_stored_account_meta_callback(offset, |account| {
                     account.to_account_shared_data()
                 }),
@@ -845,6 +886,115 @@ impl AppendVec {
         }
     }
 
+    /// Returns the `AccountSharedData`s of the accounts at the offsets of the append vecs, like
+    /// `get_account_shared_data()` does for each.
+    ///
+    /// The accounts of file backed append vecs are read in batches with `io_backend`: first a
+    /// page at the start of every account, which holds the whole of most accounts, and then the
+    /// rest of the data of the accounts which overrun their page.
+    pub(crate) fn get_accounts_shared_data_batch(
+        io_backend: IoBackend,
+        accounts: &[(&AppendVec, usize)],
+    ) -> Vec<Option<AccountSharedData>> {
+        let mut loaded_accounts = vec![None; accounts.len()];
+        let mut file_accounts = Vec::new();
+        for (i, (append_vec, offset)) in accounts.iter().enumerate() {
+            match &append_vec.backing {
+                AppendVecFileBacking::File(file) => file_accounts.push((i, file)),
+                AppendVecFileBacking::Mmap(_) | AppendVecFileBacking::Compressed { .. } => {
+                    loaded_accounts[i] = append_vec.get_account_shared_data(*offset)
+                }
+            }
+        }
+        if file_accounts.is_empty() {
+            return loaded_accounts;
+        }
+
+        let mut pages = vec![[0u8; PAGE_SIZE]; file_accounts.len()];
+        let mut reads: Vec<_> = file_accounts
+            .iter()
+            .zip(pages.iter_mut())
+            .map(|(&(i, file), page)| BatchRead {
+                file,
+                valid_file_len: accounts[i].0.len(),
+                offset: accounts[i].1,
+                buffer: page,
+            })
+            .collect();
+        let bytes_read = read_batch(io_backend, &mut reads);
+        drop(reads);
+
+        // (index, account meta, offset of the data from the account, data) of the accounts which
+        // overrun their page
+        let mut large_accounts = Vec::new();
+        for ((&(i, _file), page), bytes_read) in file_accounts.iter().zip(&pages).zip(bytes_read) {
+            let Ok(bytes_read) = bytes_read else {
+                continue;
+            };
+            let valid_bytes = ValidSlice(&page[..bytes_read]);
+            let Some((meta, next)) = Self::get_type::<StoredMeta>(valid_bytes, 0) else {
+                continue;
+            };
+            let Some((account_meta, next)) = Self::get_type::<AccountMeta>(valid_bytes, next)
+            else {
+                continue;
+            };
+            let Some((_hash, next)) = Self::get_type::<AccountHash>(valid_bytes, next) else {
+                continue;
+            };
+            let data_len = meta.data_len as usize;
+            if let Some((data, _next)) = Self::get_slice(valid_bytes, next, data_len) {
+                // we already read enough data to load this account
+                loaded_accounts[i] = Some(AccountSharedData::create(
+                    account_meta.lamports,
+                    data.to_vec(),
+                    account_meta.owner,
+                    account_meta.executable,
+                    account_meta.rent_epoch,
+                ));
+            } else {
+                assert!(meta.data_len <= MAX_PERMITTED_DATA_LENGTH, "{data_len}");
+                large_accounts.push((i, account_meta.clone(), next, vec![0; data_len]));
+            }
+        }
+        if large_accounts.is_empty() {
+            return loaded_accounts;
+        }
+
+        let mut reads: Vec<_> = large_accounts
+            .iter_mut()
+            .map(|(i, _account_meta, next, data)| {
+                let (append_vec, offset) = accounts[*i];
+                let AppendVecFileBacking::File(file) = &append_vec.backing else {
+                    unreachable!("only file backed append vecs are read in batches");
+                };
+                BatchRead {
+                    file,
+                    valid_file_len: append_vec.len(),
+                    offset: offset + *next,
+                    buffer: data,
+                }
+            })
+            .collect();
+        let bytes_read = read_batch(io_backend, &mut reads);
+        drop(reads);
+        for ((i, account_meta, _next, data), bytes_read) in
+            large_accounts.into_iter().zip(bytes_read)
+        {
+            // eof or otherwise couldn't read all the data
+            if bytes_read.is_ok_and(|bytes_read| bytes_read == data.len()) {
+                loaded_accounts[i] = Some(AccountSharedData::create(
+                    account_meta.lamports,
+                    data,
+                    account_meta.owner,
+                    account_meta.executable,
+                    account_meta.rent_epoch,
+                ));
+            }
+        }
+        loaded_accounts
+    }
+
     /// Returns the `IndexInfo` for the account at `offset`.
     ///
     /// Only intended to be used with the accounts index.
@@ -1001,7 +1151,7 @@ impl AppendVec {
         mut callback: impl for<'local> FnMut(StoredAccountMeta<'local>),
     ) {
         match &self.backing {
-            AppendVecFileBacking::Mmap(_mmap) => {
+            AppendVecFileBacking::Mmap(_mmap) | AppendVecFileBacking::Compressed { .. } => {
                 let mut offset = 0;
                 while self
                     .get_stored_account_meta_callback(offset, |account| {
@@ -1104,7 +1254,7 @@ impl AppendVec {
         let self_len = self.len();
         let mut account_sizes = Vec::with_capacity(sorted_offsets.len());
         match &self.backing {
-            AppendVecFileBacking::Mmap(mmap) => {
+            AppendVecFileBacking::Mmap(mmap) | AppendVecFileBacking::Compressed { mmap, .. } => {
                 let slice = self.get_valid_slice_from_mmap(mmap);
                 for &offset in sorted_offsets {
                     let Some((stored_meta, _)) = Self::get_type::<StoredMeta>(slice, offset) else {
@@ -1165,7 +1315,7 @@ impl AppendVec {
     fn scan_stored_accounts_no_data(&self, mut callback: impl FnMut(StoredAccountNoData)) {
         let self_len = self.len();
         match &self.backing {
-            AppendVecFileBacking::Mmap(mmap) => {
+            AppendVecFileBacking::Mmap(mmap) | AppendVecFileBacking::Compressed { mmap, .. } => {
                 let mut offset = 0;
                 let slice = self.get_valid_slice_from_mmap(mmap);
                 loop {
@@ -1314,7 +1464,7 @@ impl AppendVec {
 
     pub(crate) fn can_append(&self) -> bool {
         match &self.backing {
-            AppendVecFileBacking::File(_file) => false,
+            AppendVecFileBacking::File(_file) | AppendVecFileBacking::Compressed { .. } => false,
             AppendVecFileBacking::Mmap(_mmap) => true,
         }
     }
@@ -1325,7 +1475,62 @@ impl AppendVec {
             AppendVecFileBacking::File(_file) => InternalsForArchive::FileIo(self.path()),
             // note this returns the entire mmap slice, even bytes that we consider invalid
             AppendVecFileBacking::Mmap(mmap) => InternalsForArchive::Mmap(mmap),
+            // the decompressed bytes are archived, so that archives can be
+            // loaded by nodes which don't support compressed append vecs
+            AppendVecFileBacking::Compressed { mmap, .. } => InternalsForArchive::Mmap(mmap),
+        }
+    }
+
+    /// Returns the length of the file of this append vec if it is compressed
+    pub fn compressed_len(&self) -> Option<u64> {
+        match &self.backing {
+            AppendVecFileBacking::Mmap(_) | AppendVecFileBacking::File(_) => None,
+            AppendVecFileBacking::Compressed { compressed_len, .. } => Some(*compressed_len),
+        }
+    }
+
+    /// Compresses this append vec, with the zstd compression `level`, into a
+    /// file replacing its own, and opens it.  Like `reopen_as_readonly()`,
+    /// the file of `self` is left for its readers until it is dropped.
+    ///
+    /// Returns None if this append vec can't be compressed because it is
+    /// already or is empty, or if it would not get smaller.
+    pub(crate) fn compress(&self, level: i32) -> Result<Option<Self>> {
+        let len = self.len();
+        if len == 0 || self.compressed_len().is_some() {
+            return Ok(None);
+        }
+        // the compressed file must not miss the latest appends
+        self.flush()?;
+
+        let compressing_path = compressed::compressing_path(&self.path);
+        // a previous compression may have been interrupted
+        let _ignored = remove_file(&compressing_path);
+        let result = match &self.backing {
+            AppendVecFileBacking::Mmap(mmap) => {
+                compressed::compress(&mmap[..len], len, &compressing_path, level)
+            }
+            AppendVecFileBacking::File(_file) => {
+                compressed::compress(File::open(&self.path)?, len, &compressing_path, level)
+            }
+            AppendVecFileBacking::Compressed { .. } => unreachable!(),
+        };
+        let compressed_len = match result {
+            Ok(compressed_len) => compressed_len,
+            Err(err) => {
+                let _ignored = remove_file(&compressing_path);
+                return Err(err.into());
+            }
+        };
+        if compressed_len >= len as u64 {
+            remove_file(&compressing_path)?;
+            return Ok(None);
         }
+
+        std::fs::rename(&compressing_path, &self.path)?;
+        // the file at `path` is the compressed one now, so don't remove it when `self` is dropped
+        self.remove_file_on_drop.store(false, Ordering::Release);
+        Self::new_from_file_unchecked(self.path.clone(), len, StorageAccess::File).map(Some)
     }
 }
 
@@ -1692,6 +1897,34 @@ pub mod tests {
         }
     }
 
+    #[test_case(IoBackend::Pread)]
+    #[test_case(IoBackend::IoUring)]
+    fn test_get_accounts_shared_data_batch(io_backend: IoBackend) {
+        let (av_mmap, test_accounts, path) = rand_exhaustive_append_vec(100);
+        let av_file = AppendVec::new_from_file(&path.path, av_mmap.len(), StorageAccess::File)
+            .unwrap()
+            .0;
+        let mut offsets = vec![];
+        av_mmap.scan_accounts_stored_meta(|v| offsets.push(v.offset()));
+
+        // Mix the accounts of both append vecs, in reverse order, and past the end of the file.
+        let accounts: Vec<_> = offsets
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &offset)| (if i % 2 == 0 { &av_file } else { &*av_mmap }, offset))
+            .chain([(&av_file, av_file.len())])
+            .collect();
+        let loaded_accounts = AppendVec::get_accounts_shared_data_batch(io_backend, &accounts);
+        assert_eq!(loaded_accounts.len(), accounts.len());
+        for (loaded_account, (_pubkey, account)) in
+            loaded_accounts.iter().zip(test_accounts.iter().rev())
+        {
+            assert_eq!(loaded_account.as_ref(), Some(account));
+        }
+        assert_eq!(loaded_accounts.last().unwrap(), &None);
+    }
+
     #[test]
     fn test_append_vec_append_many() {
         let path = get_append_vec_path("test_append_many");
@@ -1894,6 +2127,53 @@ pub mod tests {
         }
     }
 
+    #[test_case(StorageAccess::Mmap)]
+    #[test_case(StorageAccess::File)]
+    fn test_append_vec_compress(storage_access: StorageAccess) {
+        let file = get_append_vec_path("test_append_vec_compress");
+        let path = &file.path;
+        let (accounts_len, indexes, accounts) = {
+            // wrap AppendVec in ManuallyDrop to ensure we do not remove the backing file when dropped
+            let av = ManuallyDrop::new(AppendVec::new(path, true, 1024 * 1024));
+            let accounts: Vec<_> = (0..100).map(create_test_account).collect();
+            let indexes: Vec<_> = accounts
+                .iter()
+                .map(|account| av.append_account_test(account).unwrap())
+                .collect();
+            (av.len(), indexes, accounts)
+        };
+        let (av, _) = AppendVec::new_from_file(path, accounts_len, storage_access).unwrap();
+        assert_eq!(av.compressed_len(), None);
+
+        let compressed = av.compress(3).unwrap().unwrap();
+        let compressed_len = compressed.compressed_len().unwrap();
+        assert!(compressed_len < accounts_len as u64);
+        assert_eq!(std::fs::metadata(path).unwrap().len(), compressed_len);
+        assert_eq!(compressed.len(), accounts_len);
+        assert!(!compressed.can_append());
+        assert!(compressed.reopen_as_readonly().is_none());
+        assert!(compressed.compress(3).unwrap().is_none());
+        // the compressed file is not removed with the original append vec
+        drop(av);
+        for (index, account) in indexes.iter().zip(&accounts) {
+            assert_eq!(compressed.get_account_test(*index).unwrap(), *account);
+        }
+        let InternalsForArchive::Mmap(archived) = compressed.internals_for_archive() else {
+            panic!("compressed append vecs are archived decompressed");
+        };
+        assert_eq!(archived.len(), accounts_len);
+
+        // compressed append vecs are decompressed when opened again
+        mem::forget(compressed);
+        let (av, num_accounts) =
+            AppendVec::new_from_file(path, accounts_len, storage_access).unwrap();
+        assert_eq!(num_accounts, accounts.len());
+        assert_eq!(av.compressed_len(), Some(compressed_len));
+        for (index, account) in indexes.iter().zip(&accounts) {
+            assert_eq!(av.get_account_test(*index).unwrap(), *account);
+        }
+    }
+
     #[test_case(StorageAccess::Mmap)]
     #[test_case(StorageAccess::File)]
     fn test_new_from_file_too_large_data_len(storage_access: StorageAccess) {
@@ -0,0 +1,120 @@
+//! Zstd-compressed append vecs, for the storages of cold slots.
+//!
+//! A compressed append vec file starts with a header made of a magic number
+//! and the length of the append vec, followed by a zstd frame of the bytes of
+//! the append vec.  The file is read only, and decompressed into anonymous
+//! memory when opened, so that it is accessed like an mmapped append vec.
+
+use {
+    memmap2::MmapMut,
+    std::{
+        ffi::OsString,
+        fs::{File, OpenOptions},
+        io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Magic number at the start of compressed append vec files.  Append vec
+/// files start with the obsolete write version of their first account, which
+/// is now 0, and was a counter far from reaching this before.
+const MAGIC: [u8; 8] = *b"SOLAVZS1";
+const HEADER_SIZE: usize = MAGIC.len() + std::mem::size_of::<u64>();
+
+/// Returns the length of the append vec compressed in `file`, or None if
+/// `file` is not a compressed append vec.
+pub(super) fn read_header(mut file: &File) -> io::Result<Option<usize>> {
+    let mut header = [0; HEADER_SIZE];
+    file.seek(SeekFrom::Start(0))?;
+    match file.read_exact(&mut header) {
+        Ok(()) => (),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    if header[..MAGIC.len()] != MAGIC {
+        return Ok(None);
+    }
+    let len = u64::from_le_bytes(header[MAGIC.len()..].try_into().unwrap());
+    usize::try_from(len)
+        .map(Some)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "append vec is too large"))
+}
+
+/// Decompresses the append vec of `len` bytes in `file` into anonymous memory
+pub(super) fn decompress(mut file: &File, len: usize) -> io::Result<MmapMut> {
+    file.seek(SeekFrom::Start(HEADER_SIZE as u64))?;
+    let mut decoder = zstd::stream::read::Decoder::new(file)?;
+    let mut mmap = MmapMut::map_anon(len)?;
+    decoder.read_exact(&mut mmap)?;
+    if decoder.read(&mut [0])? != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "compressed append vec is longer than its header says",
+        ));
+    }
+    Ok(mmap)
+}
+
+/// Path of the file the append vec at `path` is compressed into, before it
+/// replaces the append vec
+pub(super) fn compressing_path(path: &Path) -> PathBuf {
+    let mut compressing_path = OsString::from(path);
+    compressing_path.push(".compressing");
+    PathBuf::from(compressing_path)
+}
+
+/// Compresses the `len` bytes of the append vec read from `reader` into a new
+/// file at `path`, with the zstd compression `level`.
+///
+/// Returns the length of the compressed file.
+pub(super) fn compress(reader: impl Read, len: usize, path: &Path, level: i32) -> io::Result<u64> {
+    let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&(len as u64).to_le_bytes())?;
+    let mut encoder = zstd::stream::write::Encoder::new(writer, level)?;
+    let copied_len = io::copy(&mut reader.take(len as u64), &mut encoder)?;
+    if copied_len != len as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("append vec has {copied_len} bytes instead of {len}"),
+        ));
+    }
+    let file = encoder
+        .finish()?
+        .into_inner()
+        .map_err(|err| err.into_error())?;
+    file.sync_all()?;
+    Ok(file.metadata()?.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    #[test]
+    fn test_compress_and_decompress() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("0.0");
+        let bytes: Vec<u8> = (0..10_000u32).flat_map(|i| (i % 7).to_le_bytes()).collect();
+        let compressed_len = compress(&bytes[..], bytes.len(), &path, 3).unwrap();
+        assert!(compressed_len < bytes.len() as u64);
+
+        let file = File::open(&path).unwrap();
+        assert_eq!(read_header(&file).unwrap(), Some(bytes.len()));
+        assert_eq!(&decompress(&file, bytes.len()).unwrap()[..], &bytes[..]);
+        // A header with a shorter length than the append vec is rejected.
+        assert!(decompress(&file, bytes.len() - 1).is_err());
+
+        // Neither short files nor uncompressed append vecs have a header.
+        let path = temp_dir.path().join("0.1");
+        std::fs::write(&path, &bytes[..4]).unwrap();
+        assert_eq!(read_header(&File::open(&path).unwrap()).unwrap(), None);
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(read_header(&File::open(&path).unwrap()).unwrap(), None);
+
+        // The append vec must be as long as what is given.
+        let path = temp_dir.path().join("0.2");
+        assert!(compress(&bytes[..], bytes.len() + 1, &path, 3).is_err());
+    }
+}
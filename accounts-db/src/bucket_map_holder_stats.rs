@@ -1,5 +1,6 @@
 use {
     crate::{
+        account_growth::AccountGrowthCounters,
         accounts_index::{in_mem_accounts_index::InMemAccountsIndex, DiskIndexValue, IndexValue},
         bucket_map_holder::{Age, AtomicAge, BucketMapHolder},
     },
@@ -45,6 +46,10 @@ pub struct BucketMapHolderStats {
     pub buckets_scanned: AtomicU64,
     pub inserts: AtomicU64,
     count: AtomicUsize,
+    /// # of pubkeys inserted by stores, unlike `inserts` never reset
+    num_accounts_created: AtomicU64,
+    /// # of pubkeys removed, unlike `deletes` never reset
+    num_accounts_deleted: AtomicU64,
     pub bg_waiting_us: AtomicU64,
     pub bg_throttling_wait_us: AtomicU64,
     pub count_in_mem: AtomicUsize,
@@ -86,6 +91,21 @@ impl BucketMapHolderStats {
     pub fn inc_delete(&self) {
         self.deletes.fetch_add(1, Ordering::Relaxed);
         self.count.fetch_sub(1, Ordering::Relaxed);
+        self.num_accounts_deleted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a pubkey inserted by a store, once the index is generated
+    pub fn inc_account_created(&self) {
+        self.inc_insert();
+        self.num_accounts_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn account_growth_counters(&self) -> AccountGrowthCounters {
+        AccountGrowthCounters {
+            num_accounts: self.total_count() as u64,
+            num_accounts_created: self.num_accounts_created.load(Ordering::Relaxed),
+            num_accounts_deleted: self.num_accounts_deleted.load(Ordering::Relaxed),
+        }
     }
 
     pub fn inc_mem_count(&self, bin: usize) {
@@ -1,8 +1,31 @@
 //! File i/o helper functions.
+#[cfg(target_os = "linux")]
+mod io_uring;
+
 #[cfg(unix)]
 use std::os::unix::prelude::FileExt;
 use std::{fs::File, ops::Range};
 
+/// How batches of reads of accounts storage files are issued
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IoBackend {
+    /// one `pread` after the other
+    #[default]
+    Pread,
+    /// all together through an io_uring, on Linux only
+    IoUring,
+}
+
+/// A read of a batch, from `offset` into `buffer`
+#[derive(Debug)]
+pub struct BatchRead<'a> {
+    pub file: &'a File,
+    /// # of valid bytes in the file. This may be <= file length.
+    pub valid_file_len: usize,
+    pub offset: usize,
+    pub buffer: &'a mut [u8],
+}
+
 /// `buffer` contains `valid_bytes` of data at its end.
 /// Move those valid bytes to the beginning of `buffer`, then read from `offset` to fill the rest of `buffer`.
 /// Update `offset` for the next read and update `valid_bytes` to specify valid portion of `buffer`.
@@ -83,6 +106,25 @@ pub fn read_into_buffer(
     panic!("unimplemented");
 }
 
+/// Does all the `reads`, each like `read_into_buffer()`, and returns the # bytes read by each.
+///
+/// With `IoBackend::IoUring`, the reads are submitted together, so that the device can serve
+/// them in parallel. If io_uring is not available, they are done one after the other instead.
+pub fn read_batch(io_backend: IoBackend, reads: &mut [BatchRead]) -> Vec<std::io::Result<usize>> {
+    #[cfg(target_os = "linux")]
+    if io_backend == IoBackend::IoUring {
+        if let Some(bytes_read) = io_uring::read_batch(reads) {
+            return bytes_read;
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = io_backend;
+    reads
+        .iter_mut()
+        .map(|read| read_into_buffer(read.file, read.valid_file_len, read.offset, read.buffer))
+        .collect()
+}
+
 #[cfg(all(unix, test))]
 mod tests {
 
@@ -193,4 +235,37 @@ mod tests {
             bytes[start_offset..file_size]
         );
     }
+
+    #[test]
+    fn test_read_batch() {
+        let file_size = 64;
+        let bytes: Vec<u8> = (0..file_size as u8).collect();
+        let mut sample_file = tempfile().unwrap();
+        sample_file.write_all(&bytes).unwrap();
+        let valid_len = 48;
+
+        for io_backend in [IoBackend::Pread, IoBackend::IoUring] {
+            let mut buffers = [[0u8; 16]; 4];
+            let offsets = [0, 40, 8, 48];
+            let mut reads: Vec<_> = buffers
+                .iter_mut()
+                .zip(offsets)
+                .map(|(buffer, offset)| BatchRead {
+                    file: &sample_file,
+                    valid_file_len: valid_len,
+                    offset,
+                    buffer,
+                })
+                .collect();
+            let bytes_read: Vec<_> = read_batch(io_backend, &mut reads)
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+            // The reads stop at `valid_len`, and start at their own offset.
+            assert_eq!(bytes_read, [16, 8, 16, 0]);
+            assert_eq!(buffers[0], bytes[0..16]);
+            assert_eq!(buffers[1][..8], bytes[40..48]);
+            assert_eq!(buffers[2], bytes[8..24]);
+        }
+    }
 }
@@ -0,0 +1,134 @@
+//! Batches of reads through an io_uring.
+//!
+//! Each thread doing batches of reads sets up its own ring on its first batch. If the kernel
+//! does not support io_uring, or forbids it, the batches of all threads fall back to `pread`.
+
+use {
+    super::BatchRead,
+    io_uring::{opcode, types, IoUring},
+    log::*,
+    std::{
+        cell::RefCell,
+        collections::VecDeque,
+        io,
+        os::fd::AsRawFd,
+        sync::atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// # of reads in flight in a ring at most
+const QUEUE_DEPTH: u32 = 128;
+
+/// Set when io_uring can't be used, so that the setup of rings is not retried for every batch
+static IO_URING_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static RING: RefCell<Option<IoUring>> = const { RefCell::new(None) };
+}
+
+/// Does all the `reads` through the ring of this thread, and returns the # bytes read by each.
+///
+/// Returns None if io_uring can't be used, in which case the reads are not done.
+pub(super) fn read_batch(reads: &mut [BatchRead]) -> Option<Vec<io::Result<usize>>> {
+    if IO_URING_UNAVAILABLE.load(Ordering::Relaxed) {
+        return None;
+    }
+    RING.with_borrow_mut(|ring| {
+        if ring.is_none() {
+            match IoUring::new(QUEUE_DEPTH) {
+                Ok(new_ring) => *ring = Some(new_ring),
+                Err(err) => {
+                    if !IO_URING_UNAVAILABLE.swap(true, Ordering::Relaxed) {
+                        warn!("io_uring is not available, reading accounts with pread: {err}");
+                    }
+                    return None;
+                }
+            }
+        }
+        Some(read_batch_with_ring(ring.as_mut().unwrap(), reads))
+    })
+}
+
+fn read_batch_with_ring(ring: &mut IoUring, reads: &mut [BatchRead]) -> Vec<io::Result<usize>> {
+    // Like `read_into_buffer()`, nothing past the valid length of the file is read.
+    let lens: Vec<_> = reads
+        .iter()
+        .map(|read| {
+            read.buffer
+                .len()
+                .min(read.valid_file_len.saturating_sub(read.offset))
+        })
+        .collect();
+    let mut bytes_read = vec![0; reads.len()];
+    let mut errors: Vec<Option<io::Error>> = (0..reads.len()).map(|_| None).collect();
+    let mut pending: VecDeque<_> = (0..reads.len()).filter(|&i| lens[i] > 0).collect();
+    let mut num_in_flight = 0;
+
+    while !pending.is_empty() || num_in_flight > 0 {
+        {
+            let mut submission = ring.submission();
+            while let Some(&i) = pending.front() {
+                let read = &mut reads[i];
+                let remaining = &mut read.buffer[bytes_read[i]..lens[i]];
+                let entry = opcode::Read::new(
+                    types::Fd(read.file.as_raw_fd()),
+                    remaining.as_mut_ptr(),
+                    u32::try_from(remaining.len()).unwrap_or(u32::MAX),
+                )
+                .offset((read.offset + bytes_read[i]) as u64)
+                .build()
+                .user_data(i as u64);
+                // SAFETY: The buffer outlives the read, as no read is left in flight on return.
+                if unsafe { submission.push(&entry) }.is_err() {
+                    // the submission queue is full
+                    break;
+                }
+                pending.pop_front();
+                num_in_flight += 1;
+            }
+        }
+        match ring.submit_and_wait(1) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            // The buffers of the reads in flight can't be released while the kernel may still
+            // write into them.
+            Err(err) => panic!("failed to submit {num_in_flight} reads to io_uring: {err}"),
+        }
+
+        for completion in ring.completion() {
+            num_in_flight -= 1;
+            let i = completion.user_data() as usize;
+            match completion.result() {
+                result if result < 0 => {
+                    let err = io::Error::from_raw_os_error(-result);
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+                    ) {
+                        pending.push_back(i);
+                    } else {
+                        errors[i] = Some(err);
+                    }
+                }
+                // eof
+                0 => {}
+                result => {
+                    bytes_read[i] += result as usize;
+                    // Reads may return partial results, so read the rest again.
+                    if bytes_read[i] < lens[i] {
+                        pending.push_back(i);
+                    }
+                }
+            }
+        }
+    }
+
+    bytes_read
+        .into_iter()
+        .zip(errors)
+        .map(|(bytes_read, error)| match error {
+            Some(err) => Err(err),
+            None => Ok(bytes_read),
+        })
+        .collect()
+}
@@ -4,6 +4,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod account_growth;
 pub mod account_info;
 pub mod account_locks;
 pub mod account_storage;
@@ -19,6 +20,7 @@ pub mod accounts_update_notifier_interface;
 mod active_stats;
 pub mod ancestors;
 mod ancient_append_vecs;
+pub mod ancient_packing_policy;
 pub mod append_vec;
 pub mod blockhash_queue;
 mod bucket_map_holder;
@@ -28,9 +30,10 @@ mod cache_hash_data;
 mod cache_hash_data_stats;
 pub mod contains;
 pub mod epoch_accounts_hash;
-mod file_io;
+pub mod file_io;
 pub mod hardened_unpack;
 mod is_zero_lamport;
+pub mod owner_disk_usage;
 pub mod partitioned_rewards;
 pub mod pubkey_bins;
 #[cfg(feature = "dev-context-only-utils")]
@@ -0,0 +1,140 @@
+//! Disk usage of the account storages by owner.
+//!
+//! Instead of scanning all the storages at once, each storage is summarized by owner when it is
+//! written by shrink, and the storages which are not summarized yet are, a few at a time, by
+//! clean. The disk usage is the sum of the summaries of the current storages, so it covers all of
+//! them only after enough clean passes.
+//!
+//! All the accounts in a storage use its disk until it is shrunk, so the accounts which are dead
+//! but not shrunk yet are counted too.
+
+use {
+    crate::{
+        account_storage::AccountStorage,
+        accounts_db::{AccountStorageEntry, AccountsFileId},
+    },
+    dashmap::DashMap,
+    solana_clock::Slot,
+    solana_pubkey::Pubkey,
+    std::collections::HashMap,
+};
+
+/// Disk usage of the accounts of an owner
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerDiskUsage {
+    pub num_accounts: u64,
+    pub data_len: u64,
+    pub lamports: u64,
+}
+
+impl OwnerDiskUsage {
+    fn add(&mut self, other: &Self) {
+        self.num_accounts += other.num_accounts;
+        self.data_len += other.data_len;
+        self.lamports = self.lamports.saturating_add(other.lamports);
+    }
+}
+
+/// Disk usage of the summarized storages by owner
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccountsDiskUsage {
+    pub num_storages: usize,
+    /// # of storages the disk usage is of
+    pub num_summarized_storages: usize,
+    pub by_owner: HashMap<Pubkey, OwnerDiskUsage>,
+}
+
+#[derive(Debug)]
+struct StorageSummary {
+    /// The storage which is summarized, as the storage of a slot is replaced when it is shrunk
+    id: AccountsFileId,
+    by_owner: HashMap<Pubkey, OwnerDiskUsage>,
+}
+
+/// Tracks the disk usage of the account storages by owner
+#[derive(Debug, Default)]
+pub(crate) struct OwnerDiskUsageTracker {
+    summaries: DashMap<Slot, StorageSummary>,
+}
+
+impl OwnerDiskUsageTracker {
+    /// # of storages a clean pass summarizes at most
+    pub(crate) const MAX_STORAGES_SUMMARIZED_PER_CLEAN: usize = 256;
+
+    /// Summarizes the disk usage of `storage` by owner, in place of the summary of the storage it
+    /// replaced in `slot`, if any.
+    pub(crate) fn summarize_storage(&self, slot: Slot, storage: &AccountStorageEntry) {
+        let mut by_owner = HashMap::<Pubkey, OwnerDiskUsage>::new();
+        storage.accounts.scan_accounts_without_data(|account| {
+            let usage = by_owner.entry(*account.owner).or_default();
+            usage.num_accounts += 1;
+            usage.data_len += account.data_len as u64;
+            usage.lamports = usage.lamports.saturating_add(account.lamports);
+        });
+        self.summaries.insert(
+            slot,
+            StorageSummary {
+                id: storage.id(),
+                by_owner,
+            },
+        );
+    }
+
+    /// Drops the summaries of the storages which are gone, and summarizes up to `max_storages`
+    /// storages which are not summarized yet, oldest first.
+    ///
+    /// Returns the # of storages summarized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if shrink is in progress.
+    pub(crate) fn summarize_storages(
+        &self,
+        storage: &AccountStorage,
+        max_storages: usize,
+    ) -> usize {
+        self.summaries
+            .retain(|slot, summary| Self::is_current(storage, *slot, summary));
+        let mut slots = storage.all_slots();
+        slots.sort_unstable();
+        let mut num_summarized = 0;
+        for slot in slots {
+            if num_summarized >= max_storages {
+                break;
+            }
+            if self.summaries.contains_key(&slot) {
+                continue;
+            }
+            if let Some(slot_storage) = storage.get_slot_storage_entry(slot) {
+                self.summarize_storage(slot, &slot_storage);
+                num_summarized += 1;
+            }
+        }
+        num_summarized
+    }
+
+    /// Returns the disk usage of the storages summarized so far
+    pub(crate) fn disk_usage(&self, storage: &AccountStorage) -> AccountsDiskUsage {
+        let mut disk_usage = AccountsDiskUsage {
+            num_storages: storage.len(),
+            ..AccountsDiskUsage::default()
+        };
+        for entry in self.summaries.iter() {
+            if !Self::is_current(storage, *entry.key(), entry.value()) {
+                continue;
+            }
+            disk_usage.num_summarized_storages += 1;
+            for (owner, usage) in &entry.value().by_owner {
+                disk_usage.by_owner.entry(*owner).or_default().add(usage);
+            }
+        }
+        disk_usage
+    }
+
+    /// Returns true if `summary` is of the current storage of `slot`
+    fn is_current(storage: &AccountStorage, slot: Slot, summary: &StorageSummary) -> bool {
+        storage
+            .get_slot_storage_entry_shrinking_in_progress_ok(slot)
+            .is_some_and(|slot_storage| slot_storage.id() == summary.id)
+    }
+}
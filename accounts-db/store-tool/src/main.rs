@@ -150,6 +150,9 @@ fn do_inspect(file: impl AsRef<Path>, verbose: bool) -> Result<(), String> {
         storage.capacity(),
         lamports,
     );
+    if let Some(compressed_len) = storage.compressed_len() {
+        println!("compressed file size: {compressed_len}");
+    }
     Ok(())
 }
 
@@ -20,7 +20,10 @@ use {
         leader_schedule_cache::LeaderScheduleCache,
     },
     solana_measure::measure::Measure,
-    solana_perf::packet::{to_packet_batches, PacketBatch},
+    solana_perf::{
+        packet::{to_packet_batches, PacketBatch},
+        thread::ThreadPinning,
+    },
     solana_poh::poh_recorder::{create_test_recorder, PohRecorder, WorkingBankEntry},
     solana_runtime::{
         bank::Bank, bank_forks::BankForks, prioritization_fee_cache::PrioritizationFeeCache,
@@ -476,6 +479,15 @@ fn main() {
         None,
         bank_forks.clone(),
         &prioritization_fee_cache,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        ThreadPinning::default(),
     );
 
     // This is so that the signal_receiver does not go out of scope after the closure.
@@ -13,8 +13,8 @@ use {
     borsh::BorshDeserialize,
     futures::future::join_all,
     solana_banks_interface::{
-        BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation,
+        BanksRequest, BanksResponse, BanksTransactionResultWithDetailedSimulation,
+        BanksTransactionResultWithMetadata, BanksTransactionResultWithSimulation,
     },
     solana_program::{
         clock::Slot, hash::Hash, program_pack::Pack, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
@@ -153,6 +153,22 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn simulate_transaction_detailed_with_commitment_and_context(
+        &self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> Result<BanksTransactionResultWithDetailedSimulation, BanksClientError> {
+        self.inner
+            .simulate_transaction_detailed_with_commitment_and_context(
+                ctx,
+                transaction.into(),
+                commitment,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn get_account_with_commitment_and_context(
         &self,
         ctx: Context,
@@ -329,6 +345,33 @@ impl BanksClient {
             .await
     }
 
+    /// Simulate a transaction at the given commitment level, returning the
+    /// compute units consumed by each instruction, the fees, the size of the
+    /// accounts loaded and the accounts as the simulation left them along with
+    /// the simulation details
+    pub async fn simulate_transaction_detailed_with_commitment(
+        &self,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+    ) -> Result<BanksTransactionResultWithDetailedSimulation, BanksClientError> {
+        self.simulate_transaction_detailed_with_commitment_and_context(
+            context::current(),
+            transaction,
+            commitment,
+        )
+        .await
+    }
+
+    /// Simulate a transaction at the default commitment level, returning the
+    /// resource usage of the transaction along with the simulation details
+    pub async fn simulate_transaction_detailed(
+        &self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<BanksTransactionResultWithDetailedSimulation, BanksClientError> {
+        self.simulate_transaction_detailed_with_commitment(transaction, CommitmentLevel::default())
+            .await
+    }
+
     /// Return the most recent rooted slot. All transactions at or below this slot
     /// are said to be finalized. The cluster will not fork to a higher slot.
     pub async fn get_root_slot(&self) -> Result<Slot, BanksClientError> {
@@ -588,6 +631,63 @@ mod tests {
         })
     }
 
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_simulate_transaction_detailed() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let instructions = [
+            system_instruction::transfer(&mint_pubkey, &bob_pubkey, 1),
+            system_instruction::transfer(&mint_pubkey, &bob_pubkey, 2),
+        ];
+        let message = Message::new(&instructions, Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport =
+                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
+                    .await;
+            let banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let transaction = Transaction::new(&[&genesis.mint_keypair], message, recent_blockhash);
+            let simulation_result = banks_client
+                .simulate_transaction_detailed(transaction)
+                .await
+                .unwrap();
+            assert!(simulation_result.result.unwrap().is_ok());
+            let simulation_details = simulation_result.simulation_details.unwrap();
+            let resource_usage = simulation_result.resource_usage.unwrap();
+            assert_eq!(resource_usage.instruction_units_consumed.len(), 2);
+            assert_eq!(
+                resource_usage
+                    .instruction_units_consumed
+                    .iter()
+                    .sum::<u64>(),
+                simulation_details.units_consumed
+            );
+            assert!(resource_usage.fee_details.is_some());
+            assert!(resource_usage.loaded_accounts_data_size > 0);
+            let bob_account = simulation_result
+                .post_simulation_accounts
+                .iter()
+                .find(|(pubkey, _)| *pubkey == bob_pubkey)
+                .map(|(_, account)| account.lamports);
+            assert_eq!(bob_account, Some(3));
+
+            // Nothing is committed by the simulation.
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 0);
+            Ok(())
+        })
+    }
+
     #[test]
     #[allow(clippy::result_large_err)]
     fn test_banks_server_transfer_via_client() -> Result<(), BanksClientError> {
@@ -40,6 +40,24 @@ pub struct TransactionSimulationDetails {
     pub inner_instructions: Option<Vec<InnerInstructions>>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFeeDetails {
+    pub transaction_fee: u64,
+    pub prioritization_fee: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionResourceUsage {
+    /// Compute units consumed by each top level instruction executed, up to
+    /// the one which failed
+    pub instruction_units_consumed: Vec<u64>,
+    /// Fees the transaction would be charged, if it could pay for them
+    pub fee_details: Option<TransactionFeeDetails>,
+    pub loaded_accounts_data_size: u32,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionMetadata {
@@ -54,6 +72,15 @@ pub struct BanksTransactionResultWithSimulation {
     pub simulation_details: Option<TransactionSimulationDetails>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanksTransactionResultWithDetailedSimulation {
+    pub result: Option<transaction::Result<()>>,
+    pub simulation_details: Option<TransactionSimulationDetails>,
+    pub resource_usage: Option<TransactionResourceUsage>,
+    /// The accounts of the transaction as the simulation left them
+    pub post_simulation_accounts: Vec<(Pubkey, Account)>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BanksTransactionResultWithMetadata {
     pub result: transaction::Result<()>,
@@ -82,6 +109,10 @@ pub trait Banks {
         transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> BanksTransactionResultWithSimulation;
+    async fn simulate_transaction_detailed_with_commitment_and_context(
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithDetailedSimulation;
     async fn get_account_with_commitment_and_context(
         address: Pubkey,
         commitment: CommitmentLevel,
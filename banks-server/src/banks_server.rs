@@ -3,9 +3,10 @@ use {
     crossbeam_channel::{unbounded, Receiver, Sender},
     futures::{future, prelude::stream::StreamExt},
     solana_banks_interface::{
-        Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionMetadata,
-        TransactionSimulationDetails, TransactionStatus,
+        Banks, BanksRequest, BanksResponse, BanksTransactionResultWithDetailedSimulation,
+        BanksTransactionResultWithMetadata, BanksTransactionResultWithSimulation,
+        TransactionConfirmationStatus, TransactionFeeDetails, TransactionMetadata,
+        TransactionResourceUsage, TransactionSimulationDetails, TransactionStatus,
     },
     solana_client::connection_cache::ConnectionCache,
     solana_runtime::{
@@ -159,33 +160,81 @@ impl BanksServer {
     }
 }
 
-fn simulate_transaction(
+fn sanitize_and_simulate_transaction(
     bank: &Bank,
     transaction: VersionedTransaction,
-) -> BanksTransactionResultWithSimulation {
-    let sanitized_transaction = match RuntimeTransaction::try_create(
+) -> transaction::Result<TransactionSimulationResult> {
+    let sanitized_transaction = RuntimeTransaction::try_create(
         transaction,
         MessageHash::Compute,
         Some(false), // is_simple_vote_tx
         bank,
         bank.get_reserved_account_keys(),
-    ) {
+    )?;
+    Ok(bank.simulate_transaction_unchecked(&sanitized_transaction, true))
+}
+
+fn simulate_transaction(
+    bank: &Bank,
+    transaction: VersionedTransaction,
+) -> BanksTransactionResultWithSimulation {
+    let TransactionSimulationResult {
+        result,
+        logs,
+        post_simulation_accounts: _,
+        units_consumed,
+        return_data,
+        inner_instructions,
+        instruction_units_consumed: _,
+        fee_details: _,
+        loaded_accounts_data_size: _,
+    } = match sanitize_and_simulate_transaction(bank, transaction) {
         Err(err) => {
             return BanksTransactionResultWithSimulation {
                 result: Some(Err(err)),
                 simulation_details: None,
             };
         }
-        Ok(tx) => tx,
+        Ok(simulation_result) => simulation_result,
     };
+
+    let simulation_details = TransactionSimulationDetails {
+        logs,
+        units_consumed,
+        return_data,
+        inner_instructions,
+    };
+    BanksTransactionResultWithSimulation {
+        result: Some(result),
+        simulation_details: Some(simulation_details),
+    }
+}
+
+fn simulate_transaction_detailed(
+    bank: &Bank,
+    transaction: VersionedTransaction,
+) -> BanksTransactionResultWithDetailedSimulation {
     let TransactionSimulationResult {
         result,
         logs,
-        post_simulation_accounts: _,
+        post_simulation_accounts,
         units_consumed,
         return_data,
         inner_instructions,
-    } = bank.simulate_transaction_unchecked(&sanitized_transaction, true);
+        instruction_units_consumed,
+        fee_details,
+        loaded_accounts_data_size,
+    } = match sanitize_and_simulate_transaction(bank, transaction) {
+        Err(err) => {
+            return BanksTransactionResultWithDetailedSimulation {
+                result: Some(Err(err)),
+                simulation_details: None,
+                resource_usage: None,
+                post_simulation_accounts: vec![],
+            };
+        }
+        Ok(simulation_result) => simulation_result,
+    };
 
     let simulation_details = TransactionSimulationDetails {
         logs,
@@ -193,9 +242,22 @@ fn simulate_transaction(
         return_data,
         inner_instructions,
     };
-    BanksTransactionResultWithSimulation {
+    let resource_usage = TransactionResourceUsage {
+        instruction_units_consumed: instruction_units_consumed.unwrap_or_default(),
+        fee_details: fee_details.map(|fee_details| TransactionFeeDetails {
+            transaction_fee: fee_details.transaction_fee(),
+            prioritization_fee: fee_details.prioritization_fee(),
+        }),
+        loaded_accounts_data_size,
+    };
+    BanksTransactionResultWithDetailedSimulation {
         result: Some(result),
         simulation_details: Some(simulation_details),
+        resource_usage: Some(resource_usage),
+        post_simulation_accounts: post_simulation_accounts
+            .into_iter()
+            .map(|(pubkey, account)| (pubkey, Account::from(account)))
+            .collect(),
     }
 }
 
@@ -293,6 +355,15 @@ impl Banks for BanksServer {
         simulate_transaction(&self.bank(commitment), transaction)
     }
 
+    async fn simulate_transaction_detailed_with_commitment_and_context(
+        self,
+        _: Context,
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithDetailedSimulation {
+        simulate_transaction_detailed(&self.bank(commitment), transaction)
+    }
+
     async fn process_transaction_with_commitment_and_context(
         self,
         _: Context,
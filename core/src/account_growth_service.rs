@@ -0,0 +1,177 @@
+//! Samples the growth of the accounts at the root periodically, from the counters of the accounts
+//! created and deleted kept by accounts-db, reports it as metrics and records it for
+//! `getAccountGrowth`.
+
+use {
+    solana_accounts_db::accounts_db::AccountsDb,
+    solana_rpc::account_growth::{top_programs, AccountGrowthSample, AccountGrowthTracker},
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_sdk::rent::{Rent, ACCOUNT_STORAGE_OVERHEAD},
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+const SLEEP_INTERVAL: Duration = Duration::from_millis(500);
+/// The number of the programs creating the largest accounts reported for each sample
+const MAX_REPORTED_PROGRAMS: usize = 5;
+
+pub struct AccountGrowthService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl AccountGrowthService {
+    pub fn new(
+        bank_forks: &Arc<RwLock<BankForks>>,
+        tracker: Arc<AccountGrowthTracker>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let bank_forks = bank_forks.clone();
+
+        let thread_hdl = Builder::new()
+            .name("solAcctGrowth".to_string())
+            .spawn(move || {
+                info!("AccountGrowthService has started");
+                Self::run(bank_forks, tracker, exit);
+                info!("AccountGrowthService has stopped");
+            })
+            .unwrap();
+
+        Self { thread_hdl }
+    }
+
+    fn run(
+        bank_forks: Arc<RwLock<BankForks>>,
+        tracker: Arc<AccountGrowthTracker>,
+        exit: Arc<AtomicBool>,
+    ) {
+        let root_bank = bank_forks.read().unwrap().root_bank();
+        let accounts_db = root_bank.rc.accounts.accounts_db.clone();
+        // The accounts created before the first sample period are not attributed to it.
+        accounts_db.take_owner_account_growth();
+        let mut last_sample = Self::sample(&root_bank, &accounts_db, Duration::ZERO);
+        let mut last_sample_time = Instant::now();
+
+        while !exit.load(Ordering::Relaxed) {
+            let elapsed = last_sample_time.elapsed();
+            if elapsed >= SAMPLE_INTERVAL {
+                last_sample_time = Instant::now();
+                let root_bank = bank_forks.read().unwrap().root_bank();
+                let mut sample = Self::sample(&root_bank, &accounts_db, elapsed);
+                let num_accounts_created = sample.num_accounts_created;
+                let num_accounts_deleted = sample.num_accounts_deleted;
+                let accounts_data_size = sample.accounts_data_size;
+                sample.num_accounts_created =
+                    num_accounts_created.saturating_sub(last_sample.num_accounts_created);
+                sample.num_accounts_deleted =
+                    num_accounts_deleted.saturating_sub(last_sample.num_accounts_deleted);
+                sample.accounts_data_size_delta = (accounts_data_size as i64)
+                    .saturating_sub(last_sample.accounts_data_size as i64);
+                Self::report(&sample);
+
+                // Store the totals to compare against in the next iteration of the loop.
+                last_sample = AccountGrowthSample {
+                    num_accounts_created,
+                    num_accounts_deleted,
+                    accounts_data_size,
+                    ..AccountGrowthSample::default()
+                };
+                tracker.record(sample);
+            }
+            sleep(SLEEP_INTERVAL);
+        }
+    }
+
+    /// Samples the accounts at `root_bank`, with the accounts created and deleted since startup
+    fn sample(
+        root_bank: &Bank,
+        accounts_db: &AccountsDb,
+        sample_period: Duration,
+    ) -> AccountGrowthSample {
+        let counters = accounts_db.account_growth_counters();
+        let accounts_data_size = root_bank.load_accounts_data_size();
+        AccountGrowthSample {
+            slot: root_bank.slot(),
+            epoch: root_bank.epoch(),
+            sample_period,
+            num_accounts: counters.num_accounts,
+            accounts_data_size,
+            rent_exempt_reserve: rent_exempt_reserve(
+                &root_bank.rent_collector().rent,
+                counters.num_accounts,
+                accounts_data_size,
+            ),
+            num_accounts_created: counters.num_accounts_created,
+            num_accounts_deleted: counters.num_accounts_deleted,
+            accounts_data_size_delta: 0,
+            created_by_owner: accounts_db.take_owner_account_growth().unwrap_or_default(),
+        }
+    }
+
+    fn report(sample: &AccountGrowthSample) {
+        datapoint_info!(
+            "account_growth",
+            ("slot", sample.slot, i64),
+            ("sample_period_ms", sample.sample_period.as_millis(), i64),
+            ("num_accounts", sample.num_accounts, i64),
+            ("accounts_data_size", sample.accounts_data_size, i64),
+            ("rent_exempt_reserve", sample.rent_exempt_reserve, i64),
+            ("num_accounts_created", sample.num_accounts_created, i64),
+            ("num_accounts_deleted", sample.num_accounts_deleted, i64),
+            (
+                "accounts_data_size_delta",
+                sample.accounts_data_size_delta,
+                i64
+            ),
+        );
+        for (rank, (program_id, growth)) in
+            top_programs(&sample.created_by_owner, MAX_REPORTED_PROGRAMS)
+                .into_iter()
+                .enumerate()
+        {
+            datapoint_info!(
+                "account_growth-top_programs",
+                ("slot", sample.slot, i64),
+                ("rank", rank, i64),
+                ("program_id", program_id.to_string(), String),
+                ("num_accounts_created", growth.num_accounts_created, i64),
+                ("data_len_created", growth.data_len_created, i64),
+            );
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+/// The lamports for `num_accounts` accounts holding `accounts_data_size` bytes of data to be rent
+/// exempt
+fn rent_exempt_reserve(rent: &Rent, num_accounts: u64, accounts_data_size: u64) -> u64 {
+    let num_bytes = num_accounts
+        .saturating_mul(ACCOUNT_STORAGE_OVERHEAD)
+        .saturating_add(accounts_data_size);
+    (num_bytes.saturating_mul(rent.lamports_per_byte_year) as f64 * rent.exemption_threshold) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rent_exempt_reserve() {
+        let rent = Rent::default();
+        assert_eq!(rent_exempt_reserve(&rent, 0, 0), 0);
+        assert_eq!(rent_exempt_reserve(&rent, 1, 0), rent.minimum_balance(0));
+        assert_eq!(
+            rent_exempt_reserve(&rent, 3, 300),
+            3 * rent.minimum_balance(100)
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //! Service to calculate accounts hashes
 
 use {
-    crate::snapshot_packager_service::PendingSnapshotPackages,
+    crate::{
+        epoch_accounts_hash_scheduling::EpochAccountsHashScheduler,
+        snapshot_packager_service::PendingSnapshotPackages,
+    },
     crossbeam_channel::{Receiver, Sender},
     solana_accounts_db::{
         accounts_db::CalcAccountsHashKind,
@@ -30,7 +33,7 @@ use {
             Arc, Mutex,
         },
         thread::{self, Builder, JoinHandle},
-        time::Duration,
+        time::{Duration, Instant},
     },
 };
 
@@ -45,6 +48,7 @@ impl AccountsHashVerifier {
         pending_snapshot_packages: Arc<Mutex<PendingSnapshotPackages>>,
         exit: Arc<AtomicBool>,
         snapshot_controller: Arc<SnapshotController>,
+        epoch_accounts_hash_scheduler: Arc<EpochAccountsHashScheduler>,
     ) -> Self {
         // If there are no accounts packages to process, limit how often we re-check
         const LOOP_LIMITER: Duration = Duration::from_millis(DEFAULT_MS_PER_SLOT);
@@ -77,6 +81,8 @@ impl AccountsHashVerifier {
                         accounts_package,
                         &pending_snapshot_packages,
                         snapshot_config,
+                        &epoch_accounts_hash_scheduler,
+                        &exit,
                     ));
                     if let Err(err) = result {
                         error!(
@@ -217,9 +223,29 @@ impl AccountsHashVerifier {
         accounts_package: AccountsPackage,
         pending_snapshot_packages: &Mutex<PendingSnapshotPackages>,
         snapshot_config: &SnapshotConfig,
+        epoch_accounts_hash_scheduler: &EpochAccountsHashScheduler,
+        exit: &AtomicBool,
     ) -> IoResult<()> {
+        let is_epoch_accounts_hash =
+            accounts_package.package_kind == AccountsPackageKind::EpochAccountsHash;
+        if is_epoch_accounts_hash {
+            epoch_accounts_hash_scheduler.wait_to_start(
+                accounts_package.slot,
+                &accounts_package.epoch_schedule,
+                accounts_package.enqueued,
+                exit,
+            );
+        }
+        let start = Instant::now();
         let (merkle_or_lattice_accounts_hash, bank_incremental_snapshot_persistence) =
-            Self::calculate_and_verify_accounts_hash(&accounts_package, snapshot_config)?;
+            Self::calculate_and_verify_accounts_hash(
+                &accounts_package,
+                snapshot_config,
+                epoch_accounts_hash_scheduler,
+            )?;
+        if is_epoch_accounts_hash {
+            epoch_accounts_hash_scheduler.record_calculated(accounts_package.slot, start.elapsed());
+        }
 
         Self::save_epoch_accounts_hash(&accounts_package, &merkle_or_lattice_accounts_hash);
 
@@ -239,6 +265,7 @@ impl AccountsHashVerifier {
     fn calculate_and_verify_accounts_hash(
         accounts_package: &AccountsPackage,
         snapshot_config: &SnapshotConfig,
+        epoch_accounts_hash_scheduler: &EpochAccountsHashScheduler,
     ) -> IoResult<(
         MerkleOrLatticeAccountsHash,
         Option<BankIncrementalSnapshotPersistence>,
@@ -270,8 +297,18 @@ impl AccountsHashVerifier {
         let (accounts_hash_kind, bank_incremental_snapshot_persistence) =
             match accounts_hash_calculation_kind {
                 CalcAccountsHashKind::Full => {
-                    let (accounts_hash, _capitalization) =
-                        Self::_calculate_full_accounts_hash(accounts_package);
+                    let (accounts_hash, _capitalization) = match accounts_package.package_kind {
+                        AccountsPackageKind::EpochAccountsHash => epoch_accounts_hash_scheduler
+                            .calculate(|use_bg_thread_pool| {
+                                Self::_calculate_full_accounts_hash(
+                                    accounts_package,
+                                    use_bg_thread_pool,
+                                )
+                            }),
+                        AccountsPackageKind::Snapshot(_) => {
+                            Self::_calculate_full_accounts_hash(accounts_package, true)
+                        }
+                    };
                     (accounts_hash.into(), None)
                 }
                 CalcAccountsHashKind::Incremental => {
@@ -325,6 +362,7 @@ impl AccountsHashVerifier {
 
     fn _calculate_full_accounts_hash(
         accounts_package: &AccountsPackage,
+        use_bg_thread_pool: bool,
     ) -> (AccountsHash, /*capitalization*/ u64) {
         let (sorted_storages, storage_sort_us) =
             measure_us!(SortedStorages::new(&accounts_package.snapshot_storages));
@@ -336,7 +374,7 @@ impl AccountsHashVerifier {
         timings.calc_storage_size_quartiles(&accounts_package.snapshot_storages);
 
         let calculate_accounts_hash_config = CalcAccountsHashConfig {
-            use_bg_thread_pool: true,
+            use_bg_thread_pool,
             ancestors: None,
             epoch_schedule: &accounts_package.epoch_schedule,
             rent_collector: &accounts_package.rent_collector,
@@ -1,10 +1,23 @@
 use {
     crate::{
+        bank_hash_breakdowns::BankHashBreakdownTracker,
+        banking_stage::BankingStageHandle,
+        blockstore_compaction_service::BlockstoreCompactionScheduler,
         cluster_slots_service::cluster_slots::ClusterSlots,
+        epoch_accounts_hash_scheduling::EpochAccountsHashScheduler,
+        failover::FailoverCoordinator,
+        forensic_bundles::ForensicBundles,
+        leader_pause::LeaderPause,
+        program_precompilation::ProgramPrecompiler,
         repair::{outstanding_requests::OutstandingRequests, serve_repair::ShredRepairType},
     },
-    solana_gossip::cluster_info::ClusterInfo,
-    solana_runtime::bank_forks::BankForks,
+    solana_gossip::{cluster_info::ClusterInfo, duplicate_shred_proofs::DuplicateShredProofs},
+    solana_poh::poh_calibration_service::PohCalibration,
+    solana_rpc::{commission_guard::CommissionGuard, rpc_health::RpcHealth},
+    solana_runtime::{
+        bank_forks::BankForks,
+        snapshot_utils::{SnapshotArchiveVerificationStatus, SnapshotPackagingProgress},
+    },
     solana_sdk::{pubkey::Pubkey, quic::NotifyKeyUpdate},
     std::{
         collections::HashSet,
@@ -23,4 +36,24 @@ pub struct AdminRpcRequestMetadataPostInit {
     pub repair_socket: Arc<UdpSocket>,
     pub outstanding_repair_requests: Arc<RwLock<OutstandingRequests<ShredRepairType>>>,
     pub cluster_slots: Arc<ClusterSlots>,
+    /// `None` if the node does not run a banking stage
+    pub banking_stage: Option<BankingStageHandle>,
+    pub duplicate_shred_proofs: Arc<DuplicateShredProofs>,
+    /// `None` if forensic bundles are not captured
+    pub forensic_bundles: Option<Arc<ForensicBundles>>,
+    /// `None` if PoH is not calibrated
+    pub poh_calibration: Option<Arc<PohCalibration>>,
+    /// `None` if commission changes are not guarded
+    pub commission_guard: Option<Arc<CommissionGuard>>,
+    pub blockstore_compaction_scheduler: Arc<BlockstoreCompactionScheduler>,
+    pub program_precompiler: Arc<ProgramPrecompiler>,
+    pub bank_hash_breakdowns: Arc<BankHashBreakdownTracker>,
+    /// `None` if failover is disabled
+    pub failover: Option<Arc<FailoverCoordinator>>,
+    pub leader_pause: Arc<LeaderPause>,
+    pub epoch_accounts_hash_scheduler: Arc<EpochAccountsHashScheduler>,
+    pub snapshot_packaging_progress: Arc<SnapshotPackagingProgress>,
+    pub snapshot_archive_verification_status: Arc<SnapshotArchiveVerificationStatus>,
+    /// `None` if the RPC service is not enabled
+    pub rpc_health: Option<Arc<RpcHealth>>,
 }
@@ -0,0 +1,45 @@
+//! Samples the bandwidth the subsystems of the node use, reporting the rates
+//! as metrics and keeping the latest ones for the admin RPC `bandwidth`.
+
+use {
+    solana_streamer::bandwidth_accounting,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct BandwidthAccountingService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl BandwidthAccountingService {
+    pub fn new(exit: Arc<AtomicBool>) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solBandwidth".to_string())
+            .spawn(move || {
+                let mut last_sample = Instant::now();
+                while !exit.load(Ordering::Relaxed) {
+                    if last_sample.elapsed() >= SAMPLE_INTERVAL {
+                        bandwidth_accounting::bandwidth_accounting().sample();
+                        last_sample = Instant::now();
+                    }
+                    sleep(EXIT_CHECK_INTERVAL);
+                }
+            })
+            .unwrap();
+
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
@@ -0,0 +1,162 @@
+//! The components of the bank hashes of the recently frozen banks, recorded by
+//! replay and served through the admin RPC `bankHashBreakdowns`, so that the
+//! bank hash of a slot can be compared component by component with the one of
+//! another validator when they diverge.
+//!
+//! A slot frozen more than once, e.g. as a duplicate version was dumped and
+//! the cluster's version replayed, retains the components of each version.
+
+use {
+    solana_runtime::bank::{bank_hash_details::BankHashBreakdown, Bank},
+    solana_sdk::clock::Slot,
+    std::{collections::BTreeMap, sync::Mutex},
+};
+
+pub const DEFAULT_BANK_HASH_BREAKDOWN_SLOTS: usize = 256;
+
+struct TrackerState {
+    /// The versions of each slot in the order they were frozen
+    slots: Mutex<BTreeMap<Slot, Vec<BankHashBreakdown>>>,
+    max_slots: usize,
+}
+
+/// The bank hash components of the most recent frozen slots
+#[derive(Default)]
+pub struct BankHashBreakdownTracker {
+    /// `None` if tracking is disabled
+    state: Option<TrackerState>,
+}
+
+impl BankHashBreakdownTracker {
+    /// Creates a tracker retaining the components of up to `max_slots` slots.
+    /// A `max_slots` of zero disables tracking.
+    pub fn new(max_slots: usize) -> Self {
+        Self {
+            state: (max_slots > 0).then(|| TrackerState {
+                slots: Mutex::default(),
+                max_slots,
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records the components of the frozen `bank`, evicting the oldest slot
+    /// once `max_slots` are retained.
+    pub fn record_frozen_bank(&self, bank: &Bank) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let breakdown = match BankHashBreakdown::new_from_bank(bank) {
+            Ok(breakdown) => breakdown,
+            Err(err) => {
+                warn!("Unable to record the bank hash breakdown: {err}");
+                return;
+            }
+        };
+        let mut slots = state.slots.lock().unwrap();
+        slots.entry(bank.slot()).or_default().push(breakdown);
+        while slots.len() > state.max_slots {
+            slots.pop_first();
+        }
+    }
+
+    /// The versions of `slot` retained, in the order they were frozen
+    pub fn get(&self, slot: Slot) -> Vec<BankHashBreakdown> {
+        let Some(state) = &self.state else {
+            return Vec::new();
+        };
+        let slots = state.slots.lock().unwrap();
+        slots.get(&slot).cloned().unwrap_or_default()
+    }
+
+    /// The versions of up to the `num_slots` most recent slots retained, in
+    /// increasing slot order
+    pub fn recent(&self, num_slots: usize) -> Vec<BankHashBreakdown> {
+        let Some(state) = &self.state else {
+            return Vec::new();
+        };
+        let slots = state.slots.lock().unwrap();
+        let mut recent: Vec<_> = slots
+            .values()
+            .rev()
+            .take(num_slots)
+            .flatten()
+            .cloned()
+            .collect();
+        recent.sort_by_key(|breakdown| breakdown.slot);
+        recent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_runtime::genesis_utils::create_genesis_config, solana_sdk::pubkey::Pubkey,
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_disabled() {
+        let tracker = BankHashBreakdownTracker::default();
+        assert!(!tracker.is_enabled());
+        let bank = Bank::new_for_tests(&create_genesis_config(1).genesis_config);
+        bank.freeze();
+        tracker.record_frozen_bank(&bank);
+        assert!(tracker.get(0).is_empty());
+        assert!(tracker.recent(10).is_empty());
+    }
+
+    #[test]
+    fn test_record_frozen_bank() {
+        let tracker = BankHashBreakdownTracker::new(2);
+        assert!(tracker.is_enabled());
+        let bank0 = Arc::new(Bank::new_for_tests(
+            &create_genesis_config(1).genesis_config,
+        ));
+        // Banks which are not frozen are not recorded.
+        tracker.record_frozen_bank(&bank0);
+        assert!(tracker.get(0).is_empty());
+        bank0.freeze();
+        tracker.record_frozen_bank(&bank0);
+        assert_eq!(tracker.get(0).len(), 1);
+        assert_eq!(tracker.get(0)[0].bank_hash, bank0.hash().to_string());
+
+        // Each version of a slot is retained.
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::new_unique(), 1);
+        bank1.freeze();
+        tracker.record_frozen_bank(&bank1);
+        let other_bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::new_unique(), 1);
+        other_bank1.freeze();
+        tracker.record_frozen_bank(&other_bank1);
+        let versions: Vec<_> = tracker
+            .get(1)
+            .into_iter()
+            .map(|breakdown| breakdown.bank_hash)
+            .collect();
+        assert_eq!(
+            versions,
+            vec![bank1.hash().to_string(), other_bank1.hash().to_string()]
+        );
+        let slots: Vec<_> = tracker
+            .recent(1)
+            .iter()
+            .map(|breakdown| breakdown.slot)
+            .collect();
+        assert_eq!(slots, vec![1, 1]);
+
+        // The oldest slots are evicted.
+        let bank2 = Bank::new_from_parent(Arc::new(bank1), &Pubkey::default(), 2);
+        bank2.freeze();
+        tracker.record_frozen_bank(&bank2);
+        assert!(tracker.get(0).is_empty());
+        let slots: Vec<_> = tracker
+            .recent(10)
+            .iter()
+            .map(|breakdown| breakdown.slot)
+            .collect();
+        assert_eq!(slots, vec![1, 1, 2]);
+    }
+}
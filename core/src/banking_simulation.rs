@@ -25,6 +25,7 @@ use {
         leader_schedule_cache::LeaderScheduleCache,
     },
     solana_net_utils::bind_to_localhost,
+    solana_perf::thread::ThreadPinning,
     solana_poh::{
         poh_recorder::{PohRecorder, GRACE_TICKS_FACTOR, MAX_GRACE_SLOTS},
         poh_service::{PohService, DEFAULT_HASHES_PER_BATCH, DEFAULT_PINNED_CPU_CORE},
@@ -817,6 +818,8 @@ impl BankingSimulator {
             bank_forks.clone(),
             shred_version,
             sender,
+            None, // remote_shred_signer
+            None, // fec_ratio
         );
 
         info!("Start banking stage!...");
@@ -836,6 +839,15 @@ impl BankingSimulator {
             None,
             bank_forks.clone(),
             prioritization_fee_cache,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
         );
 
         let (&_slot, &raw_base_event_time) = freeze_time_by_slot
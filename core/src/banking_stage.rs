@@ -6,8 +6,10 @@
 use qualifier_attr::qualifiers;
 use {
     self::{
-        committer::Committer, consumer::Consumer, decision_maker::DecisionMaker,
-        latest_unprocessed_votes::LatestUnprocessedVotes, packet_receiver::PacketReceiver,
+        account_conflicts::AccountConflictTracker, committer::Committer, consumer::Consumer,
+        decision_maker::DecisionMaker, event_recorder::BankingEventRecorder,
+        latest_unprocessed_votes::LatestUnprocessedVotes, leader_slot_dump::LeaderSlotDumpSender,
+        packet_provenance::PacketProvenanceTracker, packet_receiver::PacketReceiver,
         qos_service::QosService, vote_storage::VoteStorage,
     },
     crate::{
@@ -17,18 +19,27 @@ use {
             transaction_scheduler::{
                 prio_graph_scheduler::PrioGraphScheduler,
                 scheduler_controller::SchedulerController, scheduler_error::SchedulerError,
+                thread_aware_account_locks::MAX_THREADS,
             },
         },
+        pipeline_tracing::PipelineTracer,
         validator::{BlockProductionMethod, TransactionStructure},
     },
     agave_banking_stage_ingress_types::BankingPacketReceiver,
     conditional_mod::conditional_vis_mod,
     crossbeam_channel::{unbounded, Receiver, Sender},
     histogram::Histogram,
+    solana_geyser_plugin_manager::vote_notifier_interface::VoteNotifierArc,
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfoQuery},
-    solana_ledger::blockstore_processor::TransactionStatusSender,
-    solana_perf::packet::PACKETS_PER_BATCH,
+    solana_ledger::{
+        blockstore_processor::TransactionStatusSender, cost_calibration::CostCalibration,
+    },
+    solana_perf::{packet::PACKETS_PER_BATCH, thread::ThreadPinning},
     solana_poh::{poh_recorder::PohRecorder, transaction_recorder::TransactionRecorder},
+    solana_rpc::{
+        buffered_transactions::BufferedTransactionSampler,
+        leader_slot_stats::LeaderSlotStatsTracker,
+    },
     solana_runtime::{
         bank::Bank, bank_forks::BankForks, prioritization_fee_cache::PrioritizationFeeCache,
         vote_sender_types::ReplayVoteSender,
@@ -38,12 +49,13 @@ use {
         cmp, env,
         ops::Deref,
         sync::{
-            atomic::{AtomicU64, AtomicUsize, Ordering},
-            Arc, RwLock,
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            Arc, Mutex, RwLock,
         },
         thread::{self, Builder, JoinHandle},
         time::Duration,
     },
+    thiserror::Error,
     transaction_scheduler::{
         greedy_scheduler::{GreedyScheduler, GreedySchedulerConfig},
         prio_graph_scheduler::PrioGraphSchedulerConfig,
@@ -56,9 +68,14 @@ use {
 };
 
 // Below modules are pub to allow use by banking_stage bench
+pub mod account_conflicts;
 pub mod committer;
 pub mod consumer;
+pub mod event_recorder;
+pub mod event_replayer;
+pub mod leader_slot_dump;
 pub mod leader_slot_metrics;
+pub mod packet_provenance;
 pub mod qos_service;
 pub mod vote_storage;
 
@@ -88,6 +105,7 @@ const TOTAL_BUFFERED_PACKETS: usize = 100_000;
 const NUM_VOTE_PROCESSING_THREADS: u32 = 2;
 const MIN_THREADS_BANKING: u32 = 1;
 const MIN_TOTAL_THREADS: u32 = NUM_VOTE_PROCESSING_THREADS + MIN_THREADS_BANKING;
+const MAX_THREADS_BANKING: u32 = MAX_THREADS as u32;
 
 const SLOT_BOUNDARY_CHECK_PERIOD: Duration = Duration::from_millis(10);
 
@@ -339,7 +357,174 @@ pub struct BatchedTransactionErrorDetails {
 
 /// Stores the stage's thread handle and output receiver.
 pub struct BankingStage {
-    bank_thread_hdls: Vec<JoinHandle<()>>,
+    vote_thread_hdl: JoinHandle<()>,
+    non_vote_threads: Arc<Mutex<NonVoteThreads>>,
+    account_conflicts: Arc<AccountConflictTracker>,
+}
+
+#[derive(Debug, Error)]
+pub enum SetNumWorkersError {
+    #[error(
+        "number of worker threads must be between {MIN_THREADS_BANKING} and \
+         {MAX_THREADS_BANKING}, got {0}"
+    )]
+    InvalidNumWorkers(u32),
+    #[error("banking stage has shut down")]
+    ShutDown,
+    #[error("banking stage thread panicked")]
+    ThreadPanicked,
+}
+
+/// Handle to the banking stage that allows the number of threads processing
+/// non-vote transactions to be changed while the stage is running
+#[derive(Clone)]
+pub struct BankingStageHandle {
+    non_vote_threads: Arc<Mutex<NonVoteThreads>>,
+    account_conflicts: Arc<AccountConflictTracker>,
+}
+
+impl BankingStageHandle {
+    /// Returns the number of threads processing non-vote transactions
+    pub fn num_workers(&self) -> u32 {
+        self.non_vote_threads.lock().unwrap().num_workers
+    }
+
+    /// Replaces the transaction scheduler and its workers with a scheduler
+    /// that spreads work over `num_workers` threads.
+    ///
+    /// The current scheduler stops scheduling and waits for the batches that
+    /// are in flight before it exits, so no work is lost on the workers.
+    /// Transactions that are still buffered in the scheduler are dropped and
+    /// have to be resent by clients, as on a leader handoff.
+    ///
+    /// Votes are always processed by a single, dedicated thread.
+    pub fn set_num_workers(&self, num_workers: u32) -> Result<(), SetNumWorkersError> {
+        if !(MIN_THREADS_BANKING..=MAX_THREADS_BANKING).contains(&num_workers) {
+            return Err(SetNumWorkersError::InvalidNumWorkers(num_workers));
+        }
+        self.non_vote_threads.lock().unwrap().respawn(num_workers)
+    }
+
+    /// The writable accounts most contended by the transactions of the
+    /// recent leader slots
+    pub fn account_conflicts(&self) -> &AccountConflictTracker {
+        &self.account_conflicts
+    }
+}
+
+/// Everything needed to spawn a transaction scheduler and the workers it
+/// schedules non-vote transactions onto
+struct NonVoteContext {
+    transaction_struct: TransactionStructure,
+    use_greedy_scheduler: bool,
+    non_vote_receiver: BankingPacketReceiver,
+    decision_maker: DecisionMaker,
+    committer: Committer,
+    poh_recorder: Arc<RwLock<PohRecorder>>,
+    transaction_recorder: TransactionRecorder,
+    log_messages_bytes_limit: Option<usize>,
+    bank_forks: Arc<RwLock<BankForks>>,
+    packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+    leader_slot_dump: Option<LeaderSlotDumpSender>,
+    pipeline_tracer: Option<Arc<PipelineTracer>>,
+    buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+    event_recorder: Option<Arc<BankingEventRecorder>>,
+    account_conflicts: Arc<AccountConflictTracker>,
+    thread_pinning: ThreadPinning,
+}
+
+impl NonVoteContext {
+    fn spawn(&self, num_workers: u32, exit: &Arc<AtomicBool>) -> Vec<JoinHandle<()>> {
+        // + 1 for the central scheduler thread
+        let mut thread_hdls = Vec::with_capacity(num_workers as usize + 1);
+        match self.transaction_struct {
+            TransactionStructure::Sdk => {
+                let receive_and_buffer =
+                    SanitizedTransactionReceiveAndBuffer::new_with_packet_provenance(
+                        PacketDeserializer::new(self.non_vote_receiver.clone())
+                            .with_pipeline_tracer(self.pipeline_tracer.clone())
+                            .with_event_recorder(self.event_recorder.clone()),
+                        self.bank_forks.clone(),
+                        self.packet_provenance.clone(),
+                    );
+                BankingStage::spawn_scheduler_and_workers(
+                    &mut thread_hdls,
+                    receive_and_buffer,
+                    self,
+                    num_workers,
+                    exit.clone(),
+                );
+            }
+            TransactionStructure::View => {
+                let receive_and_buffer = TransactionViewReceiveAndBuffer {
+                    receiver: self.non_vote_receiver.clone(),
+                    bank_forks: self.bank_forks.clone(),
+                    event_recorder: self.event_recorder.clone(),
+                };
+                BankingStage::spawn_scheduler_and_workers(
+                    &mut thread_hdls,
+                    receive_and_buffer,
+                    self,
+                    num_workers,
+                    exit.clone(),
+                );
+            }
+        }
+        thread_hdls
+    }
+}
+
+/// The transaction scheduler and the workers that process non-vote
+/// transactions
+struct NonVoteThreads {
+    context: NonVoteContext,
+    num_workers: u32,
+    /// Signals the current scheduler to stop and exit
+    exit: Arc<AtomicBool>,
+    thread_hdls: Vec<JoinHandle<()>>,
+    /// Set once the banking stage is joined
+    shut_down: bool,
+}
+
+impl NonVoteThreads {
+    fn new(context: NonVoteContext, num_workers: u32) -> Self {
+        let exit = Arc::new(AtomicBool::new(false));
+        let thread_hdls = context.spawn(num_workers, &exit);
+        Self {
+            context,
+            num_workers,
+            exit,
+            thread_hdls,
+            shut_down: false,
+        }
+    }
+
+    fn respawn(&mut self, num_workers: u32) -> Result<(), SetNumWorkersError> {
+        if self.shut_down {
+            return Err(SetNumWorkersError::ShutDown);
+        }
+        if num_workers == self.num_workers {
+            return Ok(());
+        }
+
+        // The scheduler exits once its in-flight batches complete, dropping
+        // its work senders, which in turn makes the workers exit
+        self.exit.store(true, Ordering::Relaxed);
+        for thread_hdl in self.thread_hdls.drain(..) {
+            thread_hdl
+                .join()
+                .map_err(|_| SetNumWorkersError::ThreadPanicked)?;
+        }
+
+        info!(
+            "Changing number of banking stage worker threads from {} to {num_workers}",
+            self.num_workers
+        );
+        self.exit = Arc::new(AtomicBool::new(false));
+        self.thread_hdls = self.context.spawn(num_workers, &self.exit);
+        self.num_workers = num_workers;
+        Ok(())
+    }
 }
 
 pub trait LikeClusterInfo: Send + Sync + 'static + Clone {
@@ -375,6 +560,15 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+        leader_slot_dump: Option<LeaderSlotDumpSender>,
+        vote_notifier: Option<VoteNotifierArc>,
+        leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+        cost_calibration: Option<Arc<CostCalibration>>,
+        pipeline_tracer: Option<Arc<PipelineTracer>>,
+        buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+        event_recorder: Option<Arc<BankingEventRecorder>>,
+        thread_pinning: ThreadPinning,
     ) -> Self {
         Self::new_num_threads(
             block_production_method,
@@ -391,6 +585,15 @@ impl BankingStage {
             log_messages_bytes_limit,
             bank_forks,
             prioritization_fee_cache,
+            packet_provenance,
+            leader_slot_dump,
+            vote_notifier,
+            leader_slot_stats,
+            cost_calibration,
+            pipeline_tracer,
+            buffered_transaction_sampler,
+            event_recorder,
+            thread_pinning,
         )
     }
 
@@ -410,6 +613,15 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+        leader_slot_dump: Option<LeaderSlotDumpSender>,
+        vote_notifier: Option<VoteNotifierArc>,
+        leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+        cost_calibration: Option<Arc<CostCalibration>>,
+        pipeline_tracer: Option<Arc<PipelineTracer>>,
+        buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+        event_recorder: Option<Arc<BankingEventRecorder>>,
+        thread_pinning: ThreadPinning,
     ) -> Self {
         match block_production_method {
             BlockProductionMethod::CentralScheduler
@@ -433,6 +645,15 @@ impl BankingStage {
                     log_messages_bytes_limit,
                     bank_forks,
                     prioritization_fee_cache,
+                    packet_provenance,
+                    leader_slot_dump,
+                    vote_notifier,
+                    leader_slot_stats,
+                    cost_calibration,
+                    pipeline_tracer,
+                    buffered_transaction_sampler,
+                    event_recorder,
+                    thread_pinning,
                 )
             }
         }
@@ -454,6 +675,15 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+        leader_slot_dump: Option<LeaderSlotDumpSender>,
+        vote_notifier: Option<VoteNotifierArc>,
+        leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+        cost_calibration: Option<Arc<CostCalibration>>,
+        pipeline_tracer: Option<Arc<PipelineTracer>>,
+        buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+        event_recorder: Option<Arc<BankingEventRecorder>>,
+        thread_pinning: ThreadPinning,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Keeps track of extraneous vote transactions for the vote threads
@@ -467,13 +697,15 @@ impl BankingStage {
             transaction_status_sender.clone(),
             replay_vote_sender.clone(),
             prioritization_fee_cache.clone(),
-        );
-
-        // + 1 for the central scheduler thread
-        let mut bank_thread_hdls = Vec::with_capacity(num_threads as usize + 1);
+            packet_provenance.clone(),
+            leader_slot_stats,
+            cost_calibration,
+        )
+        .with_pipeline_tracer(pipeline_tracer.clone());
+        let account_conflicts = Arc::new(AccountConflictTracker::default());
 
         // Spawn legacy voting thread
-        bank_thread_hdls.push(Self::spawn_vote_worker(
+        let vote_thread_hdl = Self::spawn_vote_worker(
             tpu_vote_receiver,
             gossip_vote_receiver,
             decision_maker.clone(),
@@ -481,66 +713,74 @@ impl BankingStage {
             committer.clone(),
             transaction_recorder.clone(),
             log_messages_bytes_limit,
-            VoteStorage::new(latest_unprocessed_votes),
-        ));
+            VoteStorage::new(latest_unprocessed_votes, vote_notifier),
+            account_conflicts.clone(),
+            thread_pinning.clone(),
+        );
 
-        match transaction_struct {
-            TransactionStructure::Sdk => {
-                let receive_and_buffer = SanitizedTransactionReceiveAndBuffer::new(
-                    PacketDeserializer::new(non_vote_receiver),
-                    bank_forks.clone(),
-                );
-                Self::spawn_scheduler_and_workers(
-                    &mut bank_thread_hdls,
-                    receive_and_buffer,
-                    use_greedy_scheduler,
-                    decision_maker,
-                    committer,
-                    poh_recorder,
-                    transaction_recorder,
-                    num_threads,
-                    log_messages_bytes_limit,
-                    bank_forks,
-                );
-            }
-            TransactionStructure::View => {
-                let receive_and_buffer = TransactionViewReceiveAndBuffer {
-                    receiver: non_vote_receiver,
-                    bank_forks: bank_forks.clone(),
-                };
-                Self::spawn_scheduler_and_workers(
-                    &mut bank_thread_hdls,
-                    receive_and_buffer,
-                    use_greedy_scheduler,
-                    decision_maker,
-                    committer,
-                    poh_recorder,
-                    transaction_recorder,
-                    num_threads,
-                    log_messages_bytes_limit,
-                    bank_forks,
-                );
-            }
+        let non_vote_context = NonVoteContext {
+            transaction_struct,
+            use_greedy_scheduler,
+            non_vote_receiver,
+            decision_maker,
+            committer,
+            poh_recorder: poh_recorder.clone(),
+            transaction_recorder,
+            log_messages_bytes_limit,
+            bank_forks,
+            packet_provenance,
+            leader_slot_dump,
+            pipeline_tracer,
+            buffered_transaction_sampler,
+            event_recorder,
+            account_conflicts: account_conflicts.clone(),
+            thread_pinning,
+        };
+        let num_workers = num_threads.saturating_sub(NUM_VOTE_PROCESSING_THREADS);
+        let non_vote_threads = NonVoteThreads::new(non_vote_context, num_workers);
+
+        Self {
+            vote_thread_hdl,
+            non_vote_threads: Arc::new(Mutex::new(non_vote_threads)),
+            account_conflicts,
         }
+    }
 
-        Self { bank_thread_hdls }
+    pub fn handle(&self) -> BankingStageHandle {
+        BankingStageHandle {
+            non_vote_threads: self.non_vote_threads.clone(),
+            account_conflicts: self.account_conflicts.clone(),
+        }
     }
 
-    #[allow(clippy::too_many_arguments)]
     fn spawn_scheduler_and_workers<R: ReceiveAndBuffer + Send + Sync + 'static>(
         bank_thread_hdls: &mut Vec<JoinHandle<()>>,
         receive_and_buffer: R,
-        use_greedy_scheduler: bool,
-        decision_maker: DecisionMaker,
-        committer: Committer,
-        poh_recorder: &Arc<RwLock<PohRecorder>>,
-        transaction_recorder: TransactionRecorder,
-        num_threads: u32,
-        log_messages_bytes_limit: Option<usize>,
-        bank_forks: Arc<RwLock<BankForks>>,
+        context: &NonVoteContext,
+        num_workers: u32,
+        exit: Arc<AtomicBool>,
     ) {
+        let NonVoteContext {
+            use_greedy_scheduler,
+            decision_maker,
+            committer,
+            poh_recorder,
+            transaction_recorder,
+            log_messages_bytes_limit,
+            bank_forks,
+            leader_slot_dump,
+            buffered_transaction_sampler,
+            event_recorder,
+            account_conflicts,
+            thread_pinning,
+            ..
+        } = context;
+        let decision_maker = decision_maker.clone();
+        let bank_forks = bank_forks.clone();
+        let buffered_transaction_sampler = buffered_transaction_sampler.clone();
+        let event_recorder = event_recorder.clone();
+
         // Create channels for communication between scheduler and workers
-        let num_workers = (num_threads).saturating_sub(NUM_VOTE_PROCESSING_THREADS);
         let (work_senders, work_receivers): (Vec<Sender<_>>, Vec<Receiver<_>>) =
             (0..num_workers).map(|_| unbounded()).unzip();
         let (finished_work_sender, finished_work_receiver) = unbounded();
@@ -556,17 +796,23 @@ impl BankingStage {
                     committer.clone(),
                     transaction_recorder.clone(),
                     QosService::new(id),
-                    log_messages_bytes_limit,
+                    *log_messages_bytes_limit,
+                    leader_slot_dump
+                        .as_ref()
+                        .map(|leader_slot_dump| leader_slot_dump.for_worker(id)),
                 ),
                 finished_work_sender.clone(),
                 poh_recorder.read().unwrap().new_leader_bank_notifier(),
+                account_conflicts.clone(),
             );
 
             worker_metrics.push(consume_worker.metrics_handle());
+            let thread_pinning = thread_pinning.clone();
             bank_thread_hdls.push(
                 Builder::new()
                     .name(format!("solCoWorker{id:02}"))
                     .spawn(move || {
+                        thread_pinning.pin_this_thread();
                         let _ = consume_worker.run();
                     })
                     .unwrap(),
@@ -576,19 +822,24 @@ impl BankingStage {
         // Macro to spawn the scheduler. Different type on `scheduler` and thus
         // scheduler_controller mean we cannot have an easy if for `scheduler`
         // assignment without introducing `dyn`.
+        let thread_pinning = thread_pinning.clone();
         macro_rules! spawn_scheduler {
             ($scheduler:ident) => {
                 bank_thread_hdls.push(
                     Builder::new()
                         .name("solBnkTxSched".to_string())
                         .spawn(move || {
+                            thread_pinning.pin_this_thread();
                             let scheduler_controller = SchedulerController::new(
                                 decision_maker.clone(),
                                 receive_and_buffer,
                                 bank_forks,
                                 $scheduler,
                                 worker_metrics,
-                            );
+                                exit,
+                            )
+                            .with_buffered_transaction_sampler(buffered_transaction_sampler)
+                            .with_event_recorder(event_recorder);
 
                             match scheduler_controller.run() {
                                 Ok(_) => {}
@@ -604,7 +855,7 @@ impl BankingStage {
         }
 
         // Spawn the central scheduler thread
-        if use_greedy_scheduler {
+        if *use_greedy_scheduler {
             let scheduler = GreedyScheduler::new(
                 work_senders,
                 finished_work_receiver,
@@ -630,6 +881,8 @@ impl BankingStage {
         transaction_recorder: TransactionRecorder,
         log_messages_bytes_limit: Option<usize>,
         vote_storage: VoteStorage,
+        account_conflicts: Arc<AccountConflictTracker>,
+        thread_pinning: ThreadPinning,
     ) -> JoinHandle<()> {
         let tpu_receiver = PacketReceiver::new(tpu_receiver);
         let gossip_receiver = PacketReceiver::new(gossip_receiver);
@@ -638,11 +891,13 @@ impl BankingStage {
             transaction_recorder,
             QosService::new(0),
             log_messages_bytes_limit,
+            None,
         );
 
         Builder::new()
             .name("solBanknStgVote".to_string())
             .spawn(move || {
+                thread_pinning.pin_this_thread();
                 VoteWorker::new(
                     decision_maker,
                     tpu_receiver,
@@ -650,6 +905,7 @@ impl BankingStage {
                     vote_storage,
                     bank_forks,
                     consumer,
+                    account_conflicts,
                 )
                 .run()
             })
@@ -666,8 +922,17 @@ impl BankingStage {
     }
 
     pub fn join(self) -> thread::Result<()> {
-        for bank_thread_hdl in self.bank_thread_hdls {
-            bank_thread_hdl.join()?;
+        self.vote_thread_hdl.join()?;
+        let thread_hdls = {
+            let mut non_vote_threads = self.non_vote_threads.lock().unwrap();
+            non_vote_threads.shut_down = true;
+            // Outstanding handles keep the context alive, so release the
+            // dump sender here to let the dump service flush and exit
+            non_vote_threads.context.leader_slot_dump = None;
+            std::mem::take(&mut non_vote_threads.thread_hdls)
+        };
+        for thread_hdl in thread_hdls {
+            thread_hdl.join()?;
         }
         Ok(())
     }
@@ -787,6 +1052,15 @@ mod tests {
             None,
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
         );
         drop(non_vote_sender);
         drop(tpu_vote_sender);
@@ -796,6 +1070,82 @@ mod tests {
         poh_service.join().unwrap();
     }
 
+    #[test_case(TransactionStructure::Sdk)]
+    #[test_case(TransactionStructure::View)]
+    fn test_banking_stage_set_num_workers(transaction_struct: TransactionStructure) {
+        let genesis_config = create_genesis_config(2).genesis_config;
+        let (bank, bank_forks) = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+        let banking_tracer = BankingTracer::new_disabled();
+        let Channels {
+            non_vote_sender,
+            non_vote_receiver,
+            tpu_vote_sender,
+            tpu_vote_receiver,
+            gossip_vote_sender,
+            gossip_vote_receiver,
+        } = banking_tracer.create_channels(false);
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(
+            Blockstore::open(ledger_path.path())
+                .expect("Expected to be able to open database ledger"),
+        );
+        let (exit, poh_recorder, transaction_recorder, poh_service, _entry_receiever) =
+            create_test_recorder(bank, blockstore, None, None);
+        let (_, cluster_info) = new_test_cluster_info(/*keypair:*/ None);
+        let cluster_info = Arc::new(cluster_info);
+        let (replay_vote_sender, _replay_vote_receiver) = unbounded();
+
+        let banking_stage = BankingStage::new_num_threads(
+            BlockProductionMethod::CentralScheduler,
+            transaction_struct,
+            &cluster_info,
+            &poh_recorder,
+            transaction_recorder,
+            non_vote_receiver,
+            tpu_vote_receiver,
+            gossip_vote_receiver,
+            4,
+            None,
+            replay_vote_sender,
+            None,
+            bank_forks,
+            &Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
+        );
+        let handle = banking_stage.handle();
+        assert_eq!(handle.num_workers(), 2);
+
+        handle.set_num_workers(5).unwrap();
+        assert_eq!(handle.num_workers(), 5);
+        handle.set_num_workers(1).unwrap();
+        assert_eq!(handle.num_workers(), 1);
+        assert_matches!(
+            handle.set_num_workers(0),
+            Err(SetNumWorkersError::InvalidNumWorkers(0))
+        );
+        assert_matches!(
+            handle.set_num_workers(MAX_THREADS_BANKING + 1),
+            Err(SetNumWorkersError::InvalidNumWorkers(_))
+        );
+        assert_eq!(handle.num_workers(), 1);
+
+        drop(non_vote_sender);
+        drop(tpu_vote_sender);
+        drop(gossip_vote_sender);
+        exit.store(true, Ordering::Relaxed);
+        banking_stage.join().unwrap();
+        poh_service.join().unwrap();
+        assert_matches!(handle.set_num_workers(2), Err(SetNumWorkersError::ShutDown));
+    }
+
     #[test_case(TransactionStructure::Sdk)]
     #[test_case(TransactionStructure::View)]
     fn test_banking_stage_tick(transaction_struct: TransactionStructure) {
@@ -845,6 +1195,15 @@ mod tests {
             None,
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
         );
         trace!("sending bank");
         drop(non_vote_sender);
@@ -912,6 +1271,15 @@ mod tests {
             None,
             bank_forks.clone(), // keep a local-copy of bank-forks so worker threads do not lose weak access to bank-forks
             &Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
         );
 
         // fund another account so we can send 2 good transactions in a single batch.
@@ -1063,6 +1431,15 @@ mod tests {
                 None,
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                ThreadPinning::default(),
             );
 
             // wait for banking_stage to eat the packets
@@ -1252,6 +1629,15 @@ mod tests {
             None,
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            ThreadPinning::default(),
         );
 
         let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
@@ -0,0 +1,247 @@
+//! The writable accounts which serialize block production, by the lock
+//! conflicts and the retries of the transactions writing them in each leader
+//! slot.
+//!
+//! A transaction which is retried, because an account is locked by another
+//! worker or because it would exceed a block or account cost limit, counts
+//! towards every account it writes. The accounts of the most contended
+//! programs then stand out, as all of their transactions write them.
+
+use {
+    solana_pubkey::Pubkey,
+    solana_sdk::clock::Slot,
+    solana_svm_transaction::svm_message::SVMMessage,
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::Mutex,
+    },
+};
+
+/// The number of the most recent leader slots retained
+pub const ACCOUNT_CONFLICT_SLOTS: usize = 32;
+/// The number of the most contended accounts reported for each slot
+pub const MAX_REPORTED_ACCOUNTS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountConflictCounts {
+    /// The transactions which could not lock the account
+    pub num_lock_conflicts: u64,
+    /// The transactions retried, for any reason
+    pub num_retries: u64,
+}
+
+impl AccountConflictCounts {
+    fn accumulate(&mut self, other: &Self) {
+        self.num_lock_conflicts = self
+            .num_lock_conflicts
+            .saturating_add(other.num_lock_conflicts);
+        self.num_retries = self.num_retries.saturating_add(other.num_retries);
+    }
+}
+
+/// The conflicts on the writable accounts of the transactions of a slot
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountConflictMetrics {
+    accounts: HashMap<Pubkey, AccountConflictCounts>,
+}
+
+impl AccountConflictMetrics {
+    /// Records the conflicts of the retried transactions of a batch, given the
+    /// sorted indexes of the ones which could not lock their accounts and of
+    /// all the retried ones.
+    pub(crate) fn record_batch(
+        &mut self,
+        transactions: &[impl SVMMessage],
+        lock_conflict_indexes: &[usize],
+        retryable_indexes: &[usize],
+    ) {
+        for &index in retryable_indexes {
+            let is_lock_conflict = lock_conflict_indexes.binary_search(&index).is_ok();
+            let transaction = &transactions[index];
+            for (account_index, account) in transaction.account_keys().iter().enumerate() {
+                if !transaction.is_writable(account_index) {
+                    continue;
+                }
+                let counts = self.accounts.entry(*account).or_default();
+                counts.num_lock_conflicts += u64::from(is_lock_conflict);
+                counts.num_retries += 1;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    pub(crate) fn accumulate(&mut self, other: &Self) {
+        for (account, counts) in &other.accounts {
+            self.accounts
+                .entry(*account)
+                .or_default()
+                .accumulate(counts);
+        }
+    }
+
+    /// The `num_accounts` most contended accounts, by lock conflicts and then
+    /// by retries
+    pub fn top_accounts(&self, num_accounts: usize) -> Vec<(Pubkey, AccountConflictCounts)> {
+        let mut accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|(account, counts)| (*account, *counts))
+            .collect();
+        accounts.sort_unstable_by(|(account, counts), (other_account, other_counts)| {
+            (
+                other_counts.num_lock_conflicts,
+                other_counts.num_retries,
+                account,
+            )
+                .cmp(&(counts.num_lock_conflicts, counts.num_retries, other_account))
+        });
+        accounts.truncate(num_accounts);
+        accounts
+    }
+
+    pub(crate) fn report(&self, slot: Slot) {
+        for (rank, (account, counts)) in self
+            .top_accounts(MAX_REPORTED_ACCOUNTS)
+            .into_iter()
+            .enumerate()
+        {
+            datapoint_info!(
+                "banking_stage-leader_slot_account_conflicts",
+                ("slot", slot, i64),
+                ("rank", rank, i64),
+                ("account", account.to_string(), String),
+                ("num_lock_conflicts", counts.num_lock_conflicts, i64),
+                ("num_retries", counts.num_retries, i64),
+            );
+        }
+    }
+}
+
+/// The account conflicts of the most recent leader slots, recorded by every
+/// banking stage worker
+#[derive(Debug, Default)]
+pub struct AccountConflictTracker {
+    slots: Mutex<BTreeMap<Slot, AccountConflictMetrics>>,
+}
+
+impl AccountConflictTracker {
+    /// Adds the conflicts a worker observed in the leader slot. The slots
+    /// before the new ones are reported, as no worker records them anymore.
+    pub(crate) fn record(&self, slot: Slot, metrics: &AccountConflictMetrics) {
+        if metrics.is_empty() {
+            return;
+        }
+        let mut slots = self.slots.lock().unwrap();
+        if !slots.contains_key(&slot) {
+            if let Some((last_slot, last_metrics)) = slots.last_key_value() {
+                if *last_slot < slot {
+                    last_metrics.report(*last_slot);
+                }
+            }
+        }
+        slots.entry(slot).or_default().accumulate(metrics);
+        while slots.len() > ACCOUNT_CONFLICT_SLOTS {
+            slots.pop_first();
+        }
+    }
+
+    /// The `num_accounts` most contended accounts of `slot`, or of every
+    /// retained slot if `None`, in increasing slot order
+    pub fn top_accounts(
+        &self,
+        slot: Option<Slot>,
+        num_accounts: usize,
+    ) -> Vec<(Slot, Vec<(Pubkey, AccountConflictCounts)>)> {
+        self.slots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(retained_slot, _)| slot.is_none_or(|slot| **retained_slot == slot))
+            .map(|(slot, metrics)| (*slot, metrics.top_accounts(num_accounts)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
+        solana_sdk::{
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+            signature::Keypair,
+            signer::Signer,
+            transaction::{SanitizedTransaction, Transaction},
+        },
+    };
+
+    fn new_transaction(
+        payer: &Keypair,
+        writable: &[Pubkey],
+    ) -> RuntimeTransaction<SanitizedTransaction> {
+        let accounts = writable
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect();
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], accounts);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        RuntimeTransaction::from_transaction_for_tests(Transaction::new_unsigned(message))
+    }
+
+    #[test]
+    fn test_account_conflict_metrics() {
+        let (payer, hot, warm) = (Keypair::new(), Pubkey::new_unique(), Pubkey::new_unique());
+        let transactions = [
+            new_transaction(&payer, &[hot]),
+            new_transaction(&payer, &[hot, warm]),
+            new_transaction(&payer, &[warm]),
+            new_transaction(&payer, &[Pubkey::new_unique()]),
+        ];
+        let mut metrics = AccountConflictMetrics::default();
+        // The last transaction is not retried.
+        metrics.record_batch(&transactions, &[0, 1], &[0, 1, 2]);
+        let counts = |num_lock_conflicts, num_retries| AccountConflictCounts {
+            num_lock_conflicts,
+            num_retries,
+        };
+        assert_eq!(
+            metrics.top_accounts(usize::MAX),
+            [
+                (payer.pubkey(), counts(2, 3)),
+                (hot, counts(2, 2)),
+                (warm, counts(1, 2)),
+            ]
+        );
+        assert_eq!(metrics.top_accounts(1), [(payer.pubkey(), counts(2, 3))]);
+
+        let tracker = AccountConflictTracker::default();
+        tracker.record(5, &AccountConflictMetrics::default());
+        assert!(tracker.top_accounts(None, 1).is_empty());
+        tracker.record(5, &metrics);
+        tracker.record(5, &metrics);
+        tracker.record(6, &metrics);
+        assert_eq!(
+            tracker.top_accounts(Some(5), 1),
+            [(5, vec![(payer.pubkey(), counts(4, 6))])]
+        );
+        assert_eq!(
+            tracker
+                .top_accounts(None, 1)
+                .into_iter()
+                .map(|(slot, _)| slot)
+                .collect::<Vec<_>>(),
+            [5, 6]
+        );
+
+        // Only the most recent slots are retained.
+        for slot in 7..7 + ACCOUNT_CONFLICT_SLOTS as Slot {
+            tracker.record(slot, &metrics);
+        }
+        assert!(tracker.top_accounts(Some(6), 1).is_empty());
+        assert_eq!(tracker.top_accounts(None, 1).len(), ACCOUNT_CONFLICT_SLOTS);
+    }
+}
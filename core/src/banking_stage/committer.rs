@@ -1,11 +1,17 @@
 use {
-    super::leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
+    super::{
+        leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
+        packet_provenance::PacketProvenanceTracker,
+    },
+    crate::pipeline_tracing::PipelineTracer,
     itertools::Itertools,
     solana_cost_model::cost_model::CostModel,
     solana_ledger::{
-        blockstore_processor::TransactionStatusSender, token_balances::collect_token_balances,
+        blockstore_processor::TransactionStatusSender, cost_calibration::CostCalibration,
+        token_balances::collect_token_balances,
     },
     solana_measure::measure_us,
+    solana_rpc::leader_slot_stats::{LeaderSlotStats, LeaderSlotStatsTracker},
     solana_runtime::{
         bank::{Bank, ProcessedTransactionCounts, TransactionBalancesSet},
         bank_utils,
@@ -14,7 +20,7 @@ use {
         vote_sender_types::ReplayVoteSender,
     },
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
-    solana_sdk::{pubkey::Pubkey, saturating_add_assign},
+    solana_sdk::{clock::Slot, pubkey::Pubkey, saturating_add_assign},
     solana_svm::{
         transaction_commit_result::{TransactionCommitResult, TransactionCommitResultExtensions},
         transaction_processing_result::TransactionProcessingResult,
@@ -22,7 +28,7 @@ use {
     solana_transaction_status::{
         token_balances::TransactionTokenBalancesSet, TransactionTokenBalance,
     },
-    std::{collections::HashMap, sync::Arc},
+    std::{collections::HashMap, sync::Arc, time::SystemTime},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -46,6 +52,10 @@ pub struct Committer {
     transaction_status_sender: Option<TransactionStatusSender>,
     replay_vote_sender: ReplayVoteSender,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+    leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+    cost_calibration: Option<Arc<CostCalibration>>,
+    pipeline_tracer: Option<Arc<PipelineTracer>>,
 }
 
 impl Committer {
@@ -53,14 +63,26 @@ impl Committer {
         transaction_status_sender: Option<TransactionStatusSender>,
         replay_vote_sender: ReplayVoteSender,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+        leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+        cost_calibration: Option<Arc<CostCalibration>>,
     ) -> Self {
         Self {
             transaction_status_sender,
             replay_vote_sender,
             prioritization_fee_cache,
+            packet_provenance,
+            leader_slot_stats,
+            cost_calibration,
+            pipeline_tracer: None,
         }
     }
 
+    pub fn with_pipeline_tracer(mut self, pipeline_tracer: Option<Arc<PipelineTracer>>) -> Self {
+        self.pipeline_tracer = pipeline_tracer;
+        self
+    }
+
     pub(super) fn transaction_status_sender_enabled(&self) -> bool {
         self.transaction_status_sender.is_some()
     }
@@ -75,6 +97,7 @@ impl Committer {
         execute_and_commit_timings: &mut LeaderExecuteAndCommitTimings,
         processed_counts: &ProcessedTransactionCounts,
     ) -> (u64, Vec<CommitTransactionDetails>) {
+        let commit_start = SystemTime::now();
         let (commit_results, commit_time_us) = measure_us!(bank.commit_transactions(
             batch.sanitized_transactions(),
             processing_results,
@@ -82,6 +105,14 @@ impl Committer {
             &mut execute_and_commit_timings.execute_timings,
         ));
         execute_and_commit_timings.commit_us = commit_time_us;
+        if let Some(cost_calibration) = &self.cost_calibration {
+            cost_calibration.record_program_timings(
+                &execute_and_commit_timings
+                    .execute_timings
+                    .details
+                    .per_program_timings,
+            );
+        }
 
         let commit_transaction_statuses = commit_results
             .iter()
@@ -111,7 +142,18 @@ impl Committer {
                 .zip(batch.sanitized_transactions())
                 .filter_map(|(commit_result, tx)| commit_result.was_committed().then_some(tx));
             self.prioritization_fee_cache
-                .update(bank, committed_transactions);
+                .update(bank, committed_transactions.clone());
+            if let Some(pipeline_tracer) = &self.pipeline_tracer {
+                pipeline_tracer.track_committed(
+                    bank.slot(),
+                    committed_transactions.clone().map(|tx| tx.signature()),
+                    commit_start,
+                );
+            }
+            if let Some(packet_provenance) = &self.packet_provenance {
+                packet_provenance
+                    .track_committed(bank.slot(), committed_transactions.map(|tx| tx.signature()));
+            }
 
             self.collect_balances_and_send_status_batch(
                 commit_results,
@@ -125,6 +167,54 @@ impl Committer {
         (commit_time_us, commit_transaction_statuses)
     }
 
+    /// Records the execution in the leader slot `slot`, started at `start`,
+    /// of the transactions of `batch` which could be locked
+    pub(super) fn record_pipeline_execution(
+        &self,
+        slot: Slot,
+        batch: &TransactionBatch<impl TransactionWithMeta>,
+        start: SystemTime,
+    ) {
+        let Some(pipeline_tracer) = &self.pipeline_tracer else {
+            return;
+        };
+        let executed_transactions = batch
+            .sanitized_transactions()
+            .iter()
+            .zip(batch.lock_results())
+            .filter(|(_, lock_result)| lock_result.is_ok())
+            .map(|(tx, _)| tx.signature());
+        pipeline_tracer.track_executed(slot, executed_transactions, start);
+    }
+
+    /// Adds the `transactions` attempted in the leader slot `slot` to its
+    /// statistics, with the details of those which were committed, if any.
+    pub(super) fn record_leader_slot_stats(
+        &self,
+        slot: Slot,
+        transactions: &[impl TransactionWithMeta],
+        commit_transaction_statuses: &[CommitTransactionDetails],
+    ) {
+        let Some(leader_slot_stats) = &self.leader_slot_stats else {
+            return;
+        };
+        let mut stats = LeaderSlotStats {
+            attempted_transactions: transactions.len() as u64,
+            ..LeaderSlotStats::default()
+        };
+        for (tx, status) in transactions.iter().zip(commit_transaction_statuses) {
+            if let CommitTransactionDetails::Committed { compute_units, .. } = status {
+                if tx.is_simple_vote_transaction() {
+                    stats.committed_vote_transactions += 1;
+                } else {
+                    stats.committed_non_vote_transactions += 1;
+                }
+                stats.compute_units = stats.compute_units.saturating_add(*compute_units);
+            }
+        }
+        leader_slot_stats.record(slot, &stats);
+    }
+
     fn collect_balances_and_send_status_batch(
         &self,
         commit_results: Vec<TransactionCommitResult>,
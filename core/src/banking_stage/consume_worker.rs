@@ -1,5 +1,6 @@
 use {
     super::{
+        account_conflicts::AccountConflictTracker,
         consumer::{Consumer, ExecuteAndCommitTransactionsOutput, ProcessTransactionBatchOutput},
         leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
         scheduler_messages::{ConsumeWork, FinishedConsumeWork},
@@ -36,6 +37,7 @@ pub(crate) struct ConsumeWorker<Tx> {
 
     leader_bank_notifier: Arc<LeaderBankNotifier>,
     metrics: Arc<ConsumeWorkerMetrics>,
+    account_conflicts: Arc<AccountConflictTracker>,
 }
 
 impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
@@ -45,6 +47,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
         consumer: Consumer,
         consumed_sender: Sender<FinishedConsumeWork<Tx>>,
         leader_bank_notifier: Arc<LeaderBankNotifier>,
+        account_conflicts: Arc<AccountConflictTracker>,
     ) -> Self {
         Self {
             consume_receiver,
@@ -52,6 +55,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
             consumed_sender,
             leader_bank_notifier,
             metrics: Arc::new(ConsumeWorkerMetrics::new(id)),
+            account_conflicts,
         }
     }
 
@@ -120,6 +124,12 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
 
         self.metrics.update_for_consume(&output);
         self.metrics.has_data.store(true, Ordering::Relaxed);
+        self.account_conflicts.record(
+            bank.slot(),
+            &output
+                .execute_and_commit_transactions_output
+                .account_conflicts,
+        );
 
         self.consumed_sender.send(FinishedConsumeWork {
             work,
@@ -795,6 +805,7 @@ mod tests {
 
         consume_sender: Sender<ConsumeWork<RuntimeTransaction<SanitizedTransaction>>>,
         consumed_receiver: Receiver<FinishedConsumeWork<RuntimeTransaction<SanitizedTransaction>>>,
+        account_conflicts: Arc<AccountConflictTracker>,
     }
 
     fn setup_test_frame() -> (
@@ -838,17 +849,22 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let (consume_sender, consume_receiver) = unbounded();
         let (consumed_sender, consumed_receiver) = unbounded();
+        let account_conflicts = Arc::new(AccountConflictTracker::default());
         let worker = ConsumeWorker::new(
             0,
             consume_receiver,
             consumer,
             consumed_sender,
             poh_recorder.read().unwrap().new_leader_bank_notifier(),
+            account_conflicts.clone(),
         );
 
         (
@@ -864,6 +880,7 @@ mod tests {
                 _replay_vote_receiver: replay_vote_receiver,
                 consume_sender,
                 consumed_receiver,
+                account_conflicts,
             },
             worker,
         )
@@ -972,6 +989,7 @@ mod tests {
             poh_recorder,
             consume_sender,
             consumed_receiver,
+            account_conflicts,
             ..
         } = &test_frame;
         let worker_thread = std::thread::spawn(move || worker.run());
@@ -1010,6 +1028,15 @@ mod tests {
         assert_eq!(consumed.work.max_ages, vec![max_age, max_age]);
         assert_eq!(consumed.retryable_indexes, vec![1]); // id2 is retryable since lock conflict
 
+        // Both writable accounts of the retried transfer conflicted once.
+        let conflicts = account_conflicts.top_accounts(Some(bank.slot()), usize::MAX);
+        assert_eq!(conflicts.len(), 1);
+        let conflicted_accounts: Vec<_> =
+            conflicts[0].1.iter().map(|(account, _)| *account).collect();
+        assert_eq!(conflicted_accounts.len(), 2);
+        assert!(conflicted_accounts.contains(&mint_keypair.pubkey()));
+        assert!(conflicted_accounts.contains(&pubkey2));
+
         drop(test_frame);
         let _ = worker_thread.join().unwrap();
     }
@@ -1,6 +1,8 @@
 use {
     super::{
+        account_conflicts::AccountConflictMetrics,
         committer::{CommitTransactionDetails, Committer, PreBalanceInfo},
+        leader_slot_dump::LeaderSlotDumpSender,
         leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
         qos_service::QosService,
         scheduler_messages::MaxAge,
@@ -28,7 +30,7 @@ use {
         transaction_processor::{ExecutionRecordingConfig, TransactionProcessingConfig},
     },
     solana_timings::ExecuteTimings,
-    std::{num::Saturating, sync::Arc},
+    std::{num::Saturating, sync::Arc, time::SystemTime},
 };
 
 /// Consumer will create chunks of transactions from buffer with up to this size.
@@ -56,6 +58,8 @@ pub struct ExecuteAndCommitTransactionsOutput {
     pub(crate) error_counters: TransactionErrorMetrics,
     pub(crate) min_prioritization_fees: u64,
     pub(crate) max_prioritization_fees: u64,
+    // The conflicts on the writable accounts of the retryable transactions
+    pub(crate) account_conflicts: AccountConflictMetrics,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -75,6 +79,7 @@ pub struct Consumer {
     transaction_recorder: TransactionRecorder,
     qos_service: QosService,
     log_messages_bytes_limit: Option<usize>,
+    leader_slot_dump: Option<LeaderSlotDumpSender>,
 }
 
 impl Consumer {
@@ -83,12 +88,14 @@ impl Consumer {
         transaction_recorder: TransactionRecorder,
         qos_service: QosService,
         log_messages_bytes_limit: Option<usize>,
+        leader_slot_dump: Option<LeaderSlotDumpSender>,
     ) -> Self {
         Self {
             committer,
             transaction_recorder,
             qos_service,
             log_messages_bytes_limit,
+            leader_slot_dump,
         }
     }
 
@@ -266,6 +273,7 @@ impl Consumer {
             min_max.into_option().unwrap_or_default();
 
         let mut error_counters = TransactionErrorMetrics::default();
+        let mut lock_conflict_indexes = Vec::new();
         let mut retryable_transaction_indexes: Vec<_> = batch
             .lock_results()
             .iter()
@@ -274,6 +282,7 @@ impl Consumer {
                 // following are retryable errors
                 Err(TransactionError::AccountInUse) => {
                     error_counters.account_in_use += 1;
+                    lock_conflict_indexes.push(index);
                     Some(index)
                 }
                 Err(TransactionError::WouldExceedMaxBlockCostLimit) => {
@@ -302,6 +311,7 @@ impl Consumer {
             })
             .collect();
 
+        let execution_start = SystemTime::now();
         let (load_and_execute_transactions_output, load_execute_us) = measure_us!(bank
             .load_and_execute_transactions(
                 batch,
@@ -319,6 +329,8 @@ impl Consumer {
                 }
             ));
         execute_and_commit_timings.load_execute_us = load_execute_us;
+        self.committer
+            .record_pipeline_execution(bank.slot(), batch, execution_start);
 
         let LoadAndExecuteTransactionsOutput {
             processing_results,
@@ -374,6 +386,28 @@ impl Consumer {
             // `extend` can cause that assumption to be violated.
             retryable_transaction_indexes.sort_unstable();
 
+            if let Some(leader_slot_dump) = &self.leader_slot_dump {
+                leader_slot_dump.send_batch(
+                    bank.slot(),
+                    batch.sanitized_transactions(),
+                    &processing_results,
+                    &retryable_transaction_indexes,
+                );
+            }
+
+            self.committer.record_leader_slot_stats(
+                bank.slot(),
+                batch.sanitized_transactions(),
+                &[],
+            );
+
+            let mut account_conflicts = AccountConflictMetrics::default();
+            account_conflicts.record_batch(
+                batch.sanitized_transactions(),
+                &lock_conflict_indexes,
+                &retryable_transaction_indexes,
+            );
+
             return ExecuteAndCommitTransactionsOutput {
                 transaction_counts,
                 retryable_transaction_indexes,
@@ -382,9 +416,19 @@ impl Consumer {
                 error_counters,
                 min_prioritization_fees,
                 max_prioritization_fees,
+                account_conflicts,
             };
         }
 
+        if let Some(leader_slot_dump) = &self.leader_slot_dump {
+            leader_slot_dump.send_batch(
+                bank.slot(),
+                batch.sanitized_transactions(),
+                &processing_results,
+                &retryable_transaction_indexes,
+            );
+        }
+
         let (commit_time_us, commit_transaction_statuses) =
             if processed_counts.processed_transactions_count != 0 {
                 self.committer.commit_transactions(
@@ -424,6 +468,19 @@ impl Consumer {
             commit_transaction_statuses.len() as u64,
         );
 
+        self.committer.record_leader_slot_stats(
+            bank.slot(),
+            batch.sanitized_transactions(),
+            &commit_transaction_statuses,
+        );
+
+        let mut account_conflicts = AccountConflictMetrics::default();
+        account_conflicts.record_batch(
+            batch.sanitized_transactions(),
+            &lock_conflict_indexes,
+            &retryable_transaction_indexes,
+        );
+
         ExecuteAndCommitTransactionsOutput {
             transaction_counts,
             retryable_transaction_indexes,
@@ -432,6 +489,7 @@ impl Consumer {
             error_counters,
             min_prioritization_fees,
             max_prioritization_fees,
+            account_conflicts,
         }
     }
 
@@ -490,8 +548,9 @@ impl Consumer {
 mod tests {
     use {
         super::*,
-        crate::banking_stage::tests::{
-            create_slow_genesis_config, sanitize_transactions, simulate_poh,
+        crate::banking_stage::{
+            account_conflicts::AccountConflictCounts,
+            tests::{create_slow_genesis_config, sanitize_transactions, simulate_poh},
         },
         agave_reserved_account_keys::ReservedAccountKeys,
         crossbeam_channel::{unbounded, Receiver},
@@ -508,7 +567,9 @@ mod tests {
             leader_schedule_cache::LeaderScheduleCache,
         },
         solana_poh::poh_recorder::{PohRecorder, Record},
-        solana_rpc::transaction_status_service::TransactionStatusService,
+        solana_rpc::transaction_status_service::{
+            TransactionStatusFilter, TransactionStatusService,
+        },
         solana_runtime::prioritization_fee_cache::PrioritizationFeeCache,
         solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
         solana_sdk::{
@@ -582,8 +643,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
         let process_transactions_summary =
             consumer.process_and_record_transactions(&bank, &transactions);
 
@@ -691,8 +755,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions);
@@ -883,8 +950,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions);
@@ -985,8 +1055,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions);
@@ -1062,8 +1135,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let get_block_cost = || bank.read_cost_tracker().unwrap().block_cost();
         let get_tx_count = || bank.read_cost_tracker().unwrap().transaction_count();
@@ -1222,8 +1298,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions);
@@ -1239,6 +1318,7 @@ mod tests {
             transaction_counts,
             retryable_transaction_indexes,
             commit_transactions_result,
+            account_conflicts,
             ..
         } = process_transactions_batch_output.execute_and_commit_transactions_output;
 
@@ -1252,6 +1332,20 @@ mod tests {
         );
         assert_eq!(retryable_transaction_indexes, vec![1]);
         assert!(commit_transactions_result.is_ok());
+        // The conflict counts towards both writable accounts of the transfer.
+        let mut conflicted_accounts = account_conflicts.top_accounts(usize::MAX);
+        conflicted_accounts.sort_unstable_by_key(|(account, _)| *account);
+        let mut expected_accounts = [mint_keypair.pubkey(), pubkey1].map(|account| {
+            (
+                account,
+                AccountConflictCounts {
+                    num_lock_conflicts: 1,
+                    num_retries: 1,
+                },
+            )
+        });
+        expected_accounts.sort_unstable_by_key(|(account, _)| *account);
+        assert_eq!(conflicted_accounts, expected_accounts);
     }
 
     #[test]
@@ -1414,8 +1508,11 @@ mod tests {
             None,
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder.clone(), QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder.clone(), QosService::new(1), None, None);
 
         let process_transactions_summary =
             consumer.process_and_record_transactions(&bank, &transactions);
@@ -1546,6 +1643,8 @@ mod tests {
             None,
             blockstore.clone(),
             false,
+            false,
+            TransactionStatusFilter::default(),
             tss_exit.clone(),
         );
 
@@ -1556,8 +1655,11 @@ mod tests {
             }),
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let _ = consumer.process_and_record_transactions(&bank, &transactions);
 
@@ -1691,6 +1793,8 @@ mod tests {
             None,
             blockstore.clone(),
             false,
+            false,
+            TransactionStatusFilter::default(),
             tss_exit.clone(),
         );
 
@@ -1701,8 +1805,11 @@ mod tests {
             }),
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
+            None,
+            None,
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(committer, recorder, QosService::new(1), None, None);
 
         let consumer_output =
             consumer.process_and_record_transactions(&bank, &[sanitized_tx.clone()]);
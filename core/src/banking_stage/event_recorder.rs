@@ -0,0 +1,288 @@
+//! Optional recording of the packet batches the transaction scheduler receives
+//! during the node's leader slots, along with the boundaries of the slots, so
+//! that they can be fed back to a scheduler deterministically.
+//!
+//! The events of consecutive leader slots are written to one file, starting
+//! with the version of the file format followed by the bincode serialized
+//! events.
+
+use {
+    crate::banking_stage::decision_maker::BufferedPacketsDecision,
+    agave_banking_stage_ingress_types::BankingPacketBatch,
+    crossbeam_channel::{unbounded, Receiver, Sender},
+    solana_sdk::clock::Slot,
+    std::{
+        fs::{self, File},
+        io::{self, BufRead, BufReader, BufWriter, Read, Write},
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        thread::{self, Builder, JoinHandle},
+    },
+};
+
+const BANKING_EVENTS_VERSION: u8 = 1;
+const BANKING_EVENTS_EXTENSION: &str = "bankingevents";
+
+/// Returns the path of the events of the leader slots starting at
+/// `first_slot` in `dir`
+pub fn banking_events_path(dir: &Path, first_slot: Slot) -> PathBuf {
+    dir.join(format!("{first_slot}.{BANKING_EVENTS_EXTENSION}"))
+}
+
+/// Returns whether `path` looks like a banking events file
+pub fn is_banking_events(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == BANKING_EVENTS_EXTENSION)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BankingEvent {
+    /// The scheduler started to consume transactions for the block of `slot`
+    LeaderSlotStart { slot: Slot, parent_slot: Slot },
+    /// A packet batch received by the scheduler
+    PacketBatch(BankingPacketBatch),
+    /// The scheduler stopped consuming transactions for the block of `slot`
+    LeaderSlotEnd { slot: Slot },
+}
+
+/// Reads the events recorded to the file at `path`.
+///
+/// A file truncated by an unclean shutdown yields the events before the
+/// truncated one.
+pub fn read_banking_events(path: &Path) -> io::Result<Vec<BankingEvent>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut version = [0u8; 1];
+    reader.read_exact(&mut version)?;
+    if version[0] != BANKING_EVENTS_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported banking events version {}", version[0]),
+        ));
+    }
+
+    let mut events = vec![];
+    while !reader.fill_buf()?.is_empty() {
+        match bincode::deserialize_from(&mut reader) {
+            Ok(event) => events.push(event),
+            Err(err) => match *err {
+                bincode::ErrorKind::Io(io_err) if io_err.kind() == io::ErrorKind::UnexpectedEof => {
+                    warn!("{} is truncated: {io_err}", path.display());
+                    break;
+                }
+                err => return Err(io::Error::other(err)),
+            },
+        }
+    }
+    Ok(events)
+}
+
+/// Used by the transaction scheduler to record the events it sees.
+///
+/// The scheduler is the only one to record events, so that they are recorded
+/// in the order it sees them.
+pub struct BankingEventRecorder {
+    sender: Sender<BankingEvent>,
+    /// The leader slot the scheduler consumes transactions for, if any
+    leader_slot: Mutex<Option<Slot>>,
+}
+
+impl BankingEventRecorder {
+    /// Records the boundaries of the leader slots, as the `decision` of the
+    /// scheduler starts or stops consuming transactions for a bank
+    pub(crate) fn record_decision(&self, decision: &BufferedPacketsDecision) {
+        let bank = decision
+            .bank_start()
+            .map(|bank_start| &bank_start.working_bank);
+        let mut leader_slot = self.leader_slot.lock().unwrap();
+        if *leader_slot == bank.map(|bank| bank.slot()) {
+            return;
+        }
+        if let Some(slot) = leader_slot.take() {
+            self.send(BankingEvent::LeaderSlotEnd { slot });
+        }
+        if let Some(bank) = bank {
+            *leader_slot = Some(bank.slot());
+            self.send(BankingEvent::LeaderSlotStart {
+                slot: bank.slot(),
+                parent_slot: bank.parent_slot(),
+            });
+        }
+    }
+
+    /// Records the packet batches received by the scheduler during a leader
+    /// slot
+    pub(crate) fn record_packet_batches<'a>(
+        &self,
+        packet_batches: impl IntoIterator<Item = &'a BankingPacketBatch>,
+    ) {
+        if self.leader_slot.lock().unwrap().is_none() {
+            return;
+        }
+        for packet_batch in packet_batches {
+            self.send(BankingEvent::PacketBatch(packet_batch.clone()));
+        }
+    }
+
+    fn send(&self, event: BankingEvent) {
+        // The service only goes away on shutdown
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Writes out the events recorded by the scheduler
+pub struct BankingEventRecorderService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl BankingEventRecorderService {
+    /// The service exits once the recorder is dropped
+    pub fn new(dir: PathBuf) -> io::Result<(Self, Arc<BankingEventRecorder>)> {
+        fs::create_dir_all(&dir)?;
+        let (sender, receiver) = unbounded();
+        let thread_hdl = Builder::new()
+            .name("solBnkEvntRec".to_string())
+            .spawn(move || Self::run(receiver, &dir))?;
+        let recorder = BankingEventRecorder {
+            sender,
+            leader_slot: Mutex::default(),
+        };
+        Ok((Self { thread_hdl }, Arc::new(recorder)))
+    }
+
+    fn run(receiver: Receiver<BankingEvent>, dir: &Path) {
+        let mut writer = None;
+        let mut last_leader_slot = None;
+        for event in receiver.iter() {
+            if let BankingEvent::LeaderSlotStart { slot, .. } = event {
+                // Consecutive leader slots go to the same file, as the
+                // transactions buffered in one are carried over to the next
+                if writer.is_none() || last_leader_slot != slot.checked_sub(1) {
+                    if let Some(writer) = writer.take() {
+                        Self::finish(writer);
+                    }
+                    writer = Self::create(dir, slot);
+                }
+                last_leader_slot = Some(slot);
+            }
+            let Some((file_writer, path)) = &mut writer else {
+                continue;
+            };
+            if let Err(err) = bincode::serialize_into(&mut *file_writer, &event) {
+                warn!(
+                    "failed to record banking event to {}: {err}",
+                    path.display()
+                );
+                writer = None;
+            }
+        }
+
+        if let Some(writer) = writer {
+            Self::finish(writer);
+        }
+    }
+
+    fn create(dir: &Path, first_slot: Slot) -> Option<(BufWriter<File>, PathBuf)> {
+        let path = banking_events_path(dir, first_slot);
+        let create = || -> io::Result<_> {
+            let mut writer = BufWriter::new(File::create(&path)?);
+            writer.write_all(&[BANKING_EVENTS_VERSION])?;
+            Ok(writer)
+        };
+        match create() {
+            Ok(writer) => Some((writer, path)),
+            Err(err) => {
+                warn!("failed to create {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    fn finish((mut writer, path): (BufWriter<File>, PathBuf)) {
+        match writer.flush() {
+            Ok(()) => debug!("recorded banking events to {}", path.display()),
+            Err(err) => warn!(
+                "failed to record banking events to {}: {err}",
+                path.display()
+            ),
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::banking_trace::for_test::sample_packet_batch,
+        solana_poh::poh_recorder::BankStart,
+        solana_runtime::{bank::Bank, genesis_utils::create_genesis_config},
+        solana_sdk::pubkey::Pubkey,
+        std::time::Instant,
+        tempfile::TempDir,
+    };
+
+    fn consume(bank: &Arc<Bank>) -> BufferedPacketsDecision {
+        BufferedPacketsDecision::Consume(BankStart {
+            working_bank: bank.clone(),
+            bank_creation_time: Arc::new(Instant::now()),
+        })
+    }
+
+    #[test]
+    fn test_banking_event_recorder() {
+        let dir = TempDir::new().unwrap();
+        let (service, recorder) =
+            BankingEventRecorderService::new(dir.path().to_path_buf()).unwrap();
+
+        let genesis_bank = Arc::new(Bank::new_for_tests(
+            &create_genesis_config(1).genesis_config,
+        ));
+        let bank = |slot| {
+            Arc::new(Bank::new_from_parent(
+                genesis_bank.clone(),
+                &Pubkey::default(),
+                slot,
+            ))
+        };
+        let packet_batch = sample_packet_batch();
+
+        // The batches received outside of the leader slots are not recorded.
+        recorder.record_packet_batches([&packet_batch]);
+        recorder.record_decision(&consume(&bank(4)));
+        recorder.record_decision(&consume(&bank(4)));
+        recorder.record_packet_batches([&packet_batch, &packet_batch]);
+        recorder.record_decision(&consume(&bank(5)));
+        recorder.record_packet_batches([&packet_batch]);
+        recorder.record_decision(&BufferedPacketsDecision::Hold);
+        recorder.record_packet_batches([&packet_batch]);
+        recorder.record_decision(&consume(&bank(9)));
+        drop(recorder);
+        service.join().unwrap();
+
+        let events = read_banking_events(&banking_events_path(dir.path(), 4)).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                BankingEvent::LeaderSlotStart {
+                    slot: 4,
+                    parent_slot: 0
+                },
+                BankingEvent::PacketBatch(_),
+                BankingEvent::PacketBatch(_),
+                BankingEvent::LeaderSlotEnd { slot: 4 },
+                BankingEvent::LeaderSlotStart { slot: 5, .. },
+                BankingEvent::PacketBatch(_),
+                BankingEvent::LeaderSlotEnd { slot: 5 },
+            ]
+        ));
+        let path = banking_events_path(dir.path(), 9);
+        assert!(is_banking_events(&path));
+        assert!(matches!(
+            read_banking_events(&path).unwrap().as_slice(),
+            [BankingEvent::LeaderSlotStart { slot: 9, .. }]
+        ));
+    }
+}
@@ -0,0 +1,358 @@
+//! Deterministic replay of the events recorded by the
+//! [`BankingEventRecorder`](super::event_recorder::BankingEventRecorder), to
+//! regression test changes to the transaction schedulers.
+//!
+//! The recorded packet batches are fed to the scheduler one at a time, in
+//! the order it received them, and the scheduler schedules all it can after
+//! each of them. The workers are emulated: each batch handed to them
+//! completes at once without being executed, so that replaying the same
+//! events on the same banks always yields the same schedule.
+
+use {
+    super::{
+        decision_maker::BufferedPacketsDecision,
+        event_recorder::BankingEvent,
+        packet_deserializer::PacketDeserializer,
+        scheduler_messages::{ConsumeWork, FinishedConsumeWork},
+        transaction_scheduler::{
+            greedy_scheduler::{GreedyScheduler, GreedySchedulerConfig},
+            prio_graph_scheduler::{PrioGraphScheduler, PrioGraphSchedulerConfig},
+            receive_and_buffer::{
+                ReceiveAndBuffer, SanitizedTransactionReceiveAndBuffer,
+                TransactionViewReceiveAndBuffer,
+            },
+            scheduler::{PreLockFilterAction, Scheduler},
+            scheduler_controller::SchedulerController,
+            scheduler_metrics::{SchedulerCountMetrics, SchedulerTimingMetrics},
+            transaction_state_container::StateContainer,
+        },
+        TOTAL_BUFFERED_PACKETS,
+    },
+    crate::validator::TransactionStructure,
+    agave_banking_stage_ingress_types::BankingPacketBatch,
+    crossbeam_channel::{unbounded, Receiver, Sender},
+    solana_poh::poh_recorder::BankStart,
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_sdk::{
+        clock::{Slot, MAX_PROCESSING_AGE},
+        signature::Signature,
+    },
+    std::{
+        sync::{Arc, RwLock},
+        time::Instant,
+    },
+};
+
+/// A batch of transactions the scheduler handed to a worker
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduledBatch {
+    pub slot: Slot,
+    pub worker_id: usize,
+    pub signatures: Vec<Signature>,
+}
+
+/// Replays recorded banking events onto a scheduler
+pub struct BankingEventReplayer {
+    bank_forks: Arc<RwLock<BankForks>>,
+    transaction_struct: TransactionStructure,
+    use_greedy_scheduler: bool,
+    num_workers: usize,
+}
+
+/// The workers of the scheduler, which complete their batches at once
+struct EmulatedWorkers<Tx> {
+    work_receivers: Vec<Receiver<ConsumeWork<Tx>>>,
+    finished_work_sender: Sender<FinishedConsumeWork<Tx>>,
+}
+
+impl BankingEventReplayer {
+    pub fn new(
+        bank_forks: Arc<RwLock<BankForks>>,
+        transaction_struct: TransactionStructure,
+        use_greedy_scheduler: bool,
+        num_workers: usize,
+    ) -> Self {
+        Self {
+            bank_forks,
+            transaction_struct,
+            use_greedy_scheduler,
+            num_workers,
+        }
+    }
+
+    /// Replays `events`, returning the batches the scheduler handed to the
+    /// workers in order.
+    ///
+    /// The bank of each leader slot which is not in the bank forks is created
+    /// from the bank of its parent slot, or from the working bank if the
+    /// parent slot is not in the bank forks either.
+    pub fn replay(&self, events: impl IntoIterator<Item = BankingEvent>) -> Vec<ScheduledBatch> {
+        let (packet_sender, packet_receiver) = unbounded();
+        match self.transaction_struct {
+            TransactionStructure::Sdk => self.replay_with_scheduler(
+                SanitizedTransactionReceiveAndBuffer::new(
+                    PacketDeserializer::new(packet_receiver),
+                    self.bank_forks.clone(),
+                ),
+                packet_sender,
+                events,
+            ),
+            TransactionStructure::View => self.replay_with_scheduler(
+                TransactionViewReceiveAndBuffer {
+                    receiver: packet_receiver,
+                    bank_forks: self.bank_forks.clone(),
+                    event_recorder: None,
+                },
+                packet_sender,
+                events,
+            ),
+        }
+    }
+
+    fn replay_with_scheduler<R: ReceiveAndBuffer>(
+        &self,
+        receive_and_buffer: R,
+        packet_sender: Sender<BankingPacketBatch>,
+        events: impl IntoIterator<Item = BankingEvent>,
+    ) -> Vec<ScheduledBatch> {
+        let (work_senders, work_receivers): (Vec<_>, Vec<_>) =
+            (0..self.num_workers).map(|_| unbounded()).unzip();
+        let (finished_work_sender, finished_work_receiver) = unbounded();
+        let workers = EmulatedWorkers {
+            work_receivers,
+            finished_work_sender,
+        };
+        if self.use_greedy_scheduler {
+            let scheduler = GreedyScheduler::new(
+                work_senders,
+                finished_work_receiver,
+                GreedySchedulerConfig::default(),
+            );
+            self.replay_with(
+                receive_and_buffer,
+                scheduler,
+                &workers,
+                packet_sender,
+                events,
+            )
+        } else {
+            let scheduler = PrioGraphScheduler::new(
+                work_senders,
+                finished_work_receiver,
+                PrioGraphSchedulerConfig::default(),
+            );
+            self.replay_with(
+                receive_and_buffer,
+                scheduler,
+                &workers,
+                packet_sender,
+                events,
+            )
+        }
+    }
+
+    fn replay_with<R: ReceiveAndBuffer, S: Scheduler<R::Transaction>>(
+        &self,
+        mut receive_and_buffer: R,
+        mut scheduler: S,
+        workers: &EmulatedWorkers<R::Transaction>,
+        packet_sender: Sender<BankingPacketBatch>,
+        events: impl IntoIterator<Item = BankingEvent>,
+    ) -> Vec<ScheduledBatch> {
+        let mut container = R::Container::with_capacity(TOTAL_BUFFERED_PACKETS);
+        let mut timing_metrics = SchedulerTimingMetrics::default();
+        let mut count_metrics = SchedulerCountMetrics::default();
+        let mut decision = BufferedPacketsDecision::Hold;
+        let mut scheduled_batches = vec![];
+        for event in events {
+            match event {
+                BankingEvent::LeaderSlotStart { slot, parent_slot } => {
+                    decision = BufferedPacketsDecision::Consume(BankStart {
+                        working_bank: self.leader_bank(slot, parent_slot),
+                        bank_creation_time: Arc::new(Instant::now()),
+                    });
+                }
+                BankingEvent::PacketBatch(packet_batch) => {
+                    packet_sender
+                        .send(packet_batch)
+                        .expect("the replayer owns the receiver");
+                    // Only fails once the sender is dropped
+                    let _ = receive_and_buffer.receive_and_buffer_packets(
+                        &mut container,
+                        &mut timing_metrics,
+                        &mut count_metrics,
+                        &decision,
+                    );
+                }
+                BankingEvent::LeaderSlotEnd { .. } => {
+                    decision = BufferedPacketsDecision::Hold;
+                }
+            }
+
+            if let BufferedPacketsDecision::Consume(bank_start) = &decision {
+                Self::schedule_all::<R, S>(
+                    &mut scheduler,
+                    &mut container,
+                    workers,
+                    &bank_start.working_bank,
+                    &mut scheduled_batches,
+                );
+            }
+        }
+        scheduled_batches
+    }
+
+    /// Schedules the buffered transactions until the scheduler is done with
+    /// them, or they are blocked by the cost limits of the bank
+    fn schedule_all<R: ReceiveAndBuffer, S: Scheduler<R::Transaction>>(
+        scheduler: &mut S,
+        container: &mut R::Container,
+        workers: &EmulatedWorkers<R::Transaction>,
+        bank: &Bank,
+        scheduled_batches: &mut Vec<ScheduledBatch>,
+    ) {
+        const EXPECT_CONNECTED: &str = "the replayer owns the workers";
+        loop {
+            scheduler
+                .receive_completed(container)
+                .expect(EXPECT_CONNECTED);
+            scheduler
+                .schedule(
+                    container,
+                    |transactions, results| {
+                        SchedulerController::<R, S>::pre_graph_filter(
+                            transactions,
+                            results,
+                            bank,
+                            MAX_PROCESSING_AGE,
+                        )
+                    },
+                    |_| PreLockFilterAction::AttemptToSchedule,
+                )
+                .expect(EXPECT_CONNECTED);
+
+            let num_scheduled_batches = scheduled_batches.len();
+            for (worker_id, work_receiver) in workers.work_receivers.iter().enumerate() {
+                for work in work_receiver.try_iter() {
+                    scheduled_batches.push(ScheduledBatch {
+                        slot: bank.slot(),
+                        worker_id,
+                        signatures: work.transactions.iter().map(|tx| *tx.signature()).collect(),
+                    });
+                    workers
+                        .finished_work_sender
+                        .send(FinishedConsumeWork {
+                            work,
+                            retryable_indexes: vec![],
+                        })
+                        .expect(EXPECT_CONNECTED);
+                }
+            }
+            if scheduled_batches.len() == num_scheduled_batches {
+                break;
+            }
+        }
+    }
+
+    fn leader_bank(&self, slot: Slot, parent_slot: Slot) -> Arc<Bank> {
+        let mut bank_forks = self.bank_forks.write().unwrap();
+        if let Some(bank) = bank_forks.get(slot) {
+            return bank;
+        }
+        let parent = bank_forks
+            .get(parent_slot)
+            .unwrap_or_else(|| bank_forks.working_bank());
+        let bank = Bank::new_from_parent(parent.clone(), parent.collector_id(), slot);
+        bank_forks.insert(bank).clone_without_scheduler()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::packet::{to_packet_batches, NUM_PACKETS},
+        solana_runtime::genesis_utils::{create_genesis_config, GenesisConfigInfo},
+        solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, system_transaction},
+        test_case::test_case,
+    };
+
+    #[test_case(TransactionStructure::Sdk, false; "sdk_prio_graph")]
+    #[test_case(TransactionStructure::View, true; "view_greedy")]
+    fn test_banking_event_replayer(
+        transaction_struct: TransactionStructure,
+        use_greedy_scheduler: bool,
+    ) {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000);
+        let payers: Vec<_> = (0..4).map(|_| Keypair::new()).collect();
+        let new_bank_forks = || {
+            let (bank, bank_forks) = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+            for payer in &payers {
+                bank.transfer(1_000_000, &mint_keypair, &payer.pubkey())
+                    .unwrap();
+            }
+            bank_forks
+        };
+        let blockhash = new_bank_forks()
+            .read()
+            .unwrap()
+            .root_bank()
+            .last_blockhash();
+        let transactions: Vec<_> = payers
+            .iter()
+            .map(|payer| system_transaction::transfer(payer, &Pubkey::new_unique(), 1, blockhash))
+            .collect();
+        let events = || {
+            vec![
+                BankingEvent::LeaderSlotStart {
+                    slot: 1,
+                    parent_slot: 0,
+                },
+                BankingEvent::PacketBatch(BankingPacketBatch::new(to_packet_batches(
+                    &transactions[..2],
+                    NUM_PACKETS,
+                ))),
+                BankingEvent::LeaderSlotEnd { slot: 1 },
+                BankingEvent::LeaderSlotStart {
+                    slot: 2,
+                    parent_slot: 1,
+                },
+                BankingEvent::PacketBatch(BankingPacketBatch::new(to_packet_batches(
+                    &transactions[2..],
+                    NUM_PACKETS,
+                ))),
+                BankingEvent::LeaderSlotEnd { slot: 2 },
+            ]
+        };
+
+        let replay = || {
+            BankingEventReplayer::new(
+                new_bank_forks(),
+                transaction_struct.clone(),
+                use_greedy_scheduler,
+                2,
+            )
+            .replay(events())
+        };
+        let scheduled_batches = replay();
+        assert_eq!(scheduled_batches, replay());
+
+        for (slot, transactions) in [(1, &transactions[..2]), (2, &transactions[2..])] {
+            let mut signatures: Vec<_> = scheduled_batches
+                .iter()
+                .filter(|batch| batch.slot == slot)
+                .flat_map(|batch| batch.signatures.iter().copied())
+                .collect();
+            signatures.sort_unstable();
+            let mut expected_signatures: Vec<_> =
+                transactions.iter().map(|tx| tx.signatures[0]).collect();
+            expected_signatures.sort_unstable();
+            assert_eq!(signatures, expected_signatures);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    super::packet_filter::PacketFilterFailure,
+    super::{packet_filter::PacketFilterFailure, packet_provenance::PacketProvenance},
     agave_feature_set::FeatureSet,
     solana_compute_budget::compute_budget_limits::ComputeBudgetLimits,
     solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
@@ -58,6 +58,7 @@ pub struct ImmutableDeserializedPacket {
     is_simple_vote: bool,
     compute_unit_price: u64,
     compute_unit_limit: u32,
+    provenance: Option<PacketProvenance>,
 }
 
 impl ImmutableDeserializedPacket {
@@ -95,9 +96,16 @@ impl ImmutableDeserializedPacket {
             is_simple_vote,
             compute_unit_price,
             compute_unit_limit,
+            provenance: None,
         })
     }
 
+    /// Tags the packet with where and when it was received
+    pub fn with_provenance(mut self, provenance: PacketProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
     pub fn forwarded(&self) -> bool {
         self.forwarded
     }
@@ -122,6 +130,10 @@ impl ImmutableDeserializedPacket {
         u64::from(self.compute_unit_limit)
     }
 
+    pub fn provenance(&self) -> Option<&PacketProvenance> {
+        self.provenance.as_ref()
+    }
+
     // This function deserializes packets into transactions, computes the blake3 hash of transaction
     // messages.
     // Additionally, this returns the minimum deactivation slot of the resolved addresses.
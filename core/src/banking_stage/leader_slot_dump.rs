@@ -0,0 +1,355 @@
+//! Optional recording of every transaction the banking stage attempts to
+//! process during the node's leader slots, written to one file per slot.
+//!
+//! Transactions are recorded by the consume workers, so transactions that the
+//! scheduler drops from its buffer before handing them to a worker (e.g.
+//! because they expired) are not recorded.
+
+use {
+    crossbeam_channel::{unbounded, Receiver, Sender},
+    solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_sdk::{clock::Slot, signature::Signature, transaction::TransactionError},
+    solana_svm::transaction_processing_result::TransactionProcessingResult,
+    std::{
+        collections::BTreeMap,
+        fs::{self, File},
+        io::{self, BufReader, BufWriter, Read, Write},
+        path::{Path, PathBuf},
+        thread::{self, Builder, JoinHandle},
+        time::Instant,
+    },
+};
+
+const LEADER_SLOT_DUMP_VERSION: u8 = 1;
+const LEADER_SLOT_DUMP_EXTENSION: &str = "txdump";
+
+/// Returns the path of the dump of `slot` in `dump_dir`
+pub fn leader_slot_dump_path(dump_dir: &Path, slot: Slot) -> PathBuf {
+    dump_dir.join(format!("{slot}.{LEADER_SLOT_DUMP_EXTENSION}"))
+}
+
+/// Returns whether `path` looks like a leader slot dump
+pub fn is_leader_slot_dump(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == LEADER_SLOT_DUMP_EXTENSION)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TransactionOutcome {
+    /// Committed and executed successfully
+    Committed,
+    /// Committed, but execution failed or only the fee was charged
+    CommittedWithError,
+    /// Returned to the scheduler to be retried, e.g. because of an account
+    /// lock conflict, a cost limit, or because the slot ended
+    Retried,
+    /// Not committed and not retried
+    Dropped,
+}
+
+/// The transactions that were attempted during a single leader slot, stored
+/// column by column
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderSlotDump {
+    pub slot: Slot,
+    pub signatures: Vec<Signature>,
+    /// Microseconds since the first batch of the slot was attempted
+    pub offsets_us: Vec<u32>,
+    pub worker_ids: Vec<u32>,
+    pub outcomes: Vec<TransactionOutcome>,
+    pub compute_units: Vec<u64>,
+    /// The error of each transaction that was not committed successfully,
+    /// if known
+    pub errors: Vec<Option<TransactionError>>,
+}
+
+impl LeaderSlotDump {
+    fn new(slot: Slot) -> Self {
+        Self {
+            slot,
+            ..Self::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&[LEADER_SLOT_DUMP_VERSION])?;
+        bincode::serialize_into(&mut writer, self).map_err(io::Error::other)?;
+        writer.flush()
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != LEADER_SLOT_DUMP_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported leader slot dump version {}", version[0]),
+            ));
+        }
+        bincode::deserialize_from(reader).map_err(io::Error::other)
+    }
+
+    fn append(&mut self, batch: LeaderSlotDumpBatch, slot_start: Instant) {
+        let offset_us = batch
+            .attempted
+            .saturating_duration_since(slot_start)
+            .as_micros()
+            .try_into()
+            .unwrap_or(u32::MAX);
+        self.offsets_us
+            .extend(std::iter::repeat_n(offset_us, batch.signatures.len()));
+        self.worker_ids
+            .extend(std::iter::repeat_n(batch.worker_id, batch.signatures.len()));
+        self.signatures.extend(batch.signatures);
+        self.outcomes.extend(batch.outcomes);
+        self.compute_units.extend(batch.compute_units);
+        self.errors.extend(batch.errors);
+    }
+}
+
+/// The outcome of a batch of transactions processed by one worker
+struct LeaderSlotDumpBatch {
+    slot: Slot,
+    worker_id: u32,
+    attempted: Instant,
+    signatures: Vec<Signature>,
+    outcomes: Vec<TransactionOutcome>,
+    compute_units: Vec<u64>,
+    errors: Vec<Option<TransactionError>>,
+}
+
+/// Used by the consume workers to record the transactions they processed
+#[derive(Clone)]
+pub struct LeaderSlotDumpSender {
+    sender: Sender<LeaderSlotDumpBatch>,
+    worker_id: u32,
+}
+
+impl LeaderSlotDumpSender {
+    /// Returns a sender that tags the transactions it records with `worker_id`
+    pub fn for_worker(&self, worker_id: u32) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            worker_id,
+        }
+    }
+
+    /// Records the outcome of a batch of transactions processed in `slot`.
+    ///
+    /// `retryable_indexes` must be sorted.
+    pub(crate) fn send_batch(
+        &self,
+        slot: Slot,
+        transactions: &[impl TransactionWithMeta],
+        processing_results: &[TransactionProcessingResult],
+        retryable_indexes: &[usize],
+    ) {
+        let attempted = Instant::now();
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut compute_units = Vec::with_capacity(transactions.len());
+        let mut errors = Vec::with_capacity(transactions.len());
+        for (index, processing_result) in processing_results.iter().enumerate() {
+            let (outcome, units, error) = match processing_result {
+                _ if retryable_indexes.binary_search(&index).is_ok() => (
+                    TransactionOutcome::Retried,
+                    0,
+                    processing_result.as_ref().err().cloned(),
+                ),
+                Ok(processed) => match processed.status() {
+                    Ok(()) => (
+                        TransactionOutcome::Committed,
+                        processed.executed_units(),
+                        None,
+                    ),
+                    Err(err) => (
+                        TransactionOutcome::CommittedWithError,
+                        processed.executed_units(),
+                        Some(err),
+                    ),
+                },
+                Err(err) => (TransactionOutcome::Dropped, 0, Some(err.clone())),
+            };
+            outcomes.push(outcome);
+            compute_units.push(units);
+            errors.push(error);
+        }
+
+        // The service only goes away on shutdown
+        let _ = self.sender.send(LeaderSlotDumpBatch {
+            slot,
+            worker_id: self.worker_id,
+            attempted,
+            signatures: transactions.iter().map(|tx| *tx.signature()).collect(),
+            outcomes,
+            compute_units,
+            errors,
+        });
+    }
+}
+
+/// Collects the transactions recorded by the workers and writes out the dump
+/// of each leader slot once it is over
+pub struct LeaderSlotDumpService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl LeaderSlotDumpService {
+    /// The service exits once all senders are dropped
+    pub fn new(dump_dir: PathBuf) -> io::Result<(Self, LeaderSlotDumpSender)> {
+        fs::create_dir_all(&dump_dir)?;
+        let (sender, receiver) = unbounded();
+        let thread_hdl = Builder::new()
+            .name("solBnkSlotDump".to_string())
+            .spawn(move || Self::run(receiver, &dump_dir))?;
+        let sender = LeaderSlotDumpSender {
+            sender,
+            worker_id: 0,
+        };
+        Ok((Self { thread_hdl }, sender))
+    }
+
+    fn run(receiver: Receiver<LeaderSlotDumpBatch>, dump_dir: &Path) {
+        // Workers may still complete batches of the previous slot after
+        // others have started on a new one, so keep the last two slots open
+        let mut in_progress = BTreeMap::<Slot, (LeaderSlotDump, Instant)>::new();
+        for batch in receiver.iter() {
+            let slot = batch.slot;
+            while let Some(entry) = in_progress.first_entry() {
+                if *entry.key() >= slot.saturating_sub(1) {
+                    break;
+                }
+                let (dump, _slot_start) = entry.remove();
+                Self::write_dump(dump_dir, &dump);
+            }
+            let (dump, slot_start) = in_progress
+                .entry(slot)
+                .or_insert_with(|| (LeaderSlotDump::new(slot), batch.attempted));
+            dump.append(batch, *slot_start);
+        }
+
+        for (dump, _slot_start) in in_progress.into_values() {
+            Self::write_dump(dump_dir, &dump);
+        }
+    }
+
+    fn write_dump(dump_dir: &Path, dump: &LeaderSlotDump) {
+        let path = leader_slot_dump_path(dump_dir, dump.slot);
+        match dump.write(&path) {
+            Ok(()) => debug!(
+                "wrote {} transactions of leader slot {} to {}",
+                dump.len(),
+                dump.slot,
+                path.display()
+            ),
+            Err(err) => warn!(
+                "failed to write leader slot dump to {}: {err}",
+                path.display()
+            ),
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
+        solana_sdk::{
+            hash::Hash,
+            pubkey::Pubkey,
+            signature::Keypair,
+            system_transaction,
+            transaction::{SanitizedTransaction, Transaction},
+        },
+        tempfile::TempDir,
+    };
+
+    fn new_transaction() -> RuntimeTransaction<SanitizedTransaction> {
+        let transaction: Transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            Hash::default(),
+        );
+        RuntimeTransaction::from_transaction_for_tests(transaction)
+    }
+
+    #[test]
+    fn test_leader_slot_dump_roundtrip() {
+        let dump_dir = TempDir::new().unwrap();
+        let path = leader_slot_dump_path(dump_dir.path(), 42);
+        assert!(is_leader_slot_dump(&path));
+
+        let dump = LeaderSlotDump {
+            slot: 42,
+            signatures: vec![Signature::new_unique(), Signature::new_unique()],
+            offsets_us: vec![0, 150],
+            worker_ids: vec![2, 3],
+            outcomes: vec![TransactionOutcome::Committed, TransactionOutcome::Dropped],
+            compute_units: vec![450, 0],
+            errors: vec![None, Some(TransactionError::BlockhashNotFound)],
+        };
+        dump.write(&path).unwrap();
+        assert_eq!(LeaderSlotDump::read(&path).unwrap(), dump);
+    }
+
+    #[test]
+    fn test_leader_slot_dump_service() {
+        let dump_dir = TempDir::new().unwrap();
+        let (service, sender) = LeaderSlotDumpService::new(dump_dir.path().to_path_buf()).unwrap();
+
+        let transactions = [new_transaction(), new_transaction(), new_transaction()];
+        let processing_results: [TransactionProcessingResult; 3] = [
+            Err(TransactionError::AccountInUse),
+            Err(TransactionError::BlockhashNotFound),
+            Err(TransactionError::AlreadyProcessed),
+        ];
+        sender
+            .for_worker(2)
+            .send_batch(5, &transactions, &processing_results, &[0]);
+        sender
+            .for_worker(3)
+            .send_batch(6, &transactions[..1], &processing_results[..1], &[0]);
+        drop(sender);
+        service.join().unwrap();
+
+        let dump = LeaderSlotDump::read(&leader_slot_dump_path(dump_dir.path(), 5)).unwrap();
+        assert_eq!(dump.slot, 5);
+        assert_eq!(dump.len(), 3);
+        assert_eq!(
+            dump.signatures,
+            transactions
+                .iter()
+                .map(|tx| *tx.signature())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(dump.worker_ids, vec![2, 2, 2]);
+        assert_eq!(
+            dump.outcomes,
+            vec![
+                TransactionOutcome::Retried,
+                TransactionOutcome::Dropped,
+                TransactionOutcome::Dropped
+            ]
+        );
+        assert_eq!(dump.errors[1], Some(TransactionError::BlockhashNotFound));
+
+        let dump = LeaderSlotDump::read(&leader_slot_dump_path(dump_dir.path(), 6)).unwrap();
+        assert_eq!(dump.worker_ids, vec![3]);
+        assert_eq!(dump.outcomes, vec![TransactionOutcome::Retried]);
+    }
+}
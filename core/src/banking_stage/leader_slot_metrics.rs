@@ -1,5 +1,6 @@
 use {
     super::{
+        account_conflicts::{AccountConflictMetrics, AccountConflictTracker},
         consumer::LeaderProcessedTransactionCounts,
         latest_unprocessed_votes::VoteBatchInsertionMetrics,
         leader_slot_timing_metrics::{LeaderExecuteAndCommitTimings, LeaderSlotTimingMetrics},
@@ -8,7 +9,7 @@ use {
     solana_poh::poh_recorder::BankStart,
     solana_sdk::{clock::Slot, saturating_add_assign},
     solana_svm::transaction_error_metrics::*,
-    std::time::Instant,
+    std::{sync::Arc, time::Instant},
 };
 
 /// A summary of what happened to transactions passed to the processing pipeline.
@@ -45,6 +46,9 @@ pub(crate) struct ProcessTransactionsSummary {
     /// Breakdown of all the transaction errors from transactions passed for
     /// execution
     pub error_counters: TransactionErrorMetrics,
+
+    /// The conflicts on the writable accounts of the retryable transactions
+    pub account_conflicts: AccountConflictMetrics,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -395,6 +399,8 @@ pub(crate) struct LeaderSlotMetrics {
 
     timing_metrics: LeaderSlotTimingMetrics,
 
+    account_conflict_metrics: AccountConflictMetrics,
+
     // Used by tests to check if the `self.report()` method was called
     is_reported: bool,
 }
@@ -407,6 +413,7 @@ impl LeaderSlotMetrics {
             transaction_error_metrics: TransactionErrorMetrics::new(),
             vote_packet_count_metrics: VotePacketCountMetrics::new(),
             timing_metrics: LeaderSlotTimingMetrics::new(bank_creation_time),
+            account_conflict_metrics: AccountConflictMetrics::default(),
             is_reported: false,
         }
     }
@@ -473,9 +480,19 @@ pub struct LeaderSlotMetricsTracker {
     // Only `Some` if BankingStage detects it's time to construct our leader slot,
     // otherwise `None`
     leader_slot_metrics: Option<LeaderSlotMetrics>,
+    // Where the account conflicts of each leader slot are published once the
+    // slot is reported
+    account_conflicts: Option<Arc<AccountConflictTracker>>,
 }
 
 impl LeaderSlotMetricsTracker {
+    pub(crate) fn new(account_conflicts: Arc<AccountConflictTracker>) -> Self {
+        Self {
+            leader_slot_metrics: None,
+            account_conflicts: Some(account_conflicts),
+        }
+    }
+
     // Check leader slot, return MetricsTrackerAction to be applied by apply_action()
     pub(crate) fn check_leader_slot_boundary(
         &mut self,
@@ -516,11 +533,7 @@ impl LeaderSlotMetricsTracker {
         match action {
             MetricsTrackerAction::Noop => None,
             MetricsTrackerAction::ReportAndResetTracker => {
-                let mut reported_slot = None;
-                if let Some(leader_slot_metrics) = self.leader_slot_metrics.as_mut() {
-                    leader_slot_metrics.report();
-                    reported_slot = leader_slot_metrics.reported_slot();
-                }
+                let reported_slot = self.report_leader_slot_metrics();
                 self.leader_slot_metrics = None;
                 reported_slot
             }
@@ -529,17 +542,25 @@ impl LeaderSlotMetricsTracker {
                 self.leader_slot_metrics.as_ref().unwrap().reported_slot()
             }
             MetricsTrackerAction::ReportAndNewTracker(new_slot_metrics) => {
-                let mut reported_slot = None;
-                if let Some(leader_slot_metrics) = self.leader_slot_metrics.as_mut() {
-                    leader_slot_metrics.report();
-                    reported_slot = leader_slot_metrics.reported_slot();
-                }
+                let reported_slot = self.report_leader_slot_metrics();
                 self.leader_slot_metrics = new_slot_metrics;
                 reported_slot
             }
         }
     }
 
+    fn report_leader_slot_metrics(&mut self) -> Option<Slot> {
+        let leader_slot_metrics = self.leader_slot_metrics.as_mut()?;
+        leader_slot_metrics.report();
+        if let Some(account_conflicts) = &self.account_conflicts {
+            account_conflicts.record(
+                leader_slot_metrics.slot,
+                &leader_slot_metrics.account_conflict_metrics,
+            );
+        }
+        leader_slot_metrics.reported_slot()
+    }
+
     pub(crate) fn accumulate_process_transactions_summary(
         &mut self,
         process_transactions_summary: &ProcessTransactionsSummary,
@@ -552,6 +573,7 @@ impl LeaderSlotMetricsTracker {
                 cost_model_us,
                 ref execute_and_commit_timings,
                 error_counters,
+                account_conflicts,
                 ..
             } = process_transactions_summary;
 
@@ -630,6 +652,10 @@ impl LeaderSlotMetricsTracker {
                 .timing_metrics
                 .execute_and_commit_timings
                 .accumulate(execute_and_commit_timings);
+
+            leader_slot_metrics
+                .account_conflict_metrics
+                .accumulate(account_conflicts);
         }
     }
 
@@ -2,14 +2,20 @@
 
 use {
     super::{
+        event_recorder::BankingEventRecorder,
         immutable_deserialized_packet::{DeserializedPacketError, ImmutableDeserializedPacket},
         packet_filter::PacketFilterFailure,
+        packet_provenance::{PacketProvenance, PacketReceipt},
     },
+    crate::pipeline_tracing::PipelineTracer,
     agave_banking_stage_ingress_types::{BankingPacketBatch, BankingPacketReceiver},
     crossbeam_channel::RecvTimeoutError,
     solana_perf::packet::PacketBatch,
     solana_sdk::saturating_add_assign,
-    std::time::{Duration, Instant},
+    std::{
+        sync::Arc,
+        time::{Duration, Instant, SystemTime},
+    },
 };
 
 /// Results from deserializing packet batches.
@@ -24,6 +30,10 @@ pub struct ReceivePacketResults {
 pub struct PacketDeserializer {
     /// Receiver for packet batches from sigverify stage
     packet_batch_receiver: BankingPacketReceiver,
+    /// Whether to tag deserialized packets with their provenance
+    track_provenance: bool,
+    pipeline_tracer: Option<Arc<PipelineTracer>>,
+    event_recorder: Option<Arc<BankingEventRecorder>>,
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -75,9 +85,30 @@ impl PacketDeserializer {
     pub fn new(packet_batch_receiver: BankingPacketReceiver) -> Self {
         Self {
             packet_batch_receiver,
+            track_provenance: false,
+            pipeline_tracer: None,
+            event_recorder: None,
         }
     }
 
+    pub fn with_provenance_tracking(mut self, track_provenance: bool) -> Self {
+        self.track_provenance = track_provenance;
+        self
+    }
+
+    pub fn with_pipeline_tracer(mut self, pipeline_tracer: Option<Arc<PipelineTracer>>) -> Self {
+        self.pipeline_tracer = pipeline_tracer;
+        self
+    }
+
+    pub fn with_event_recorder(
+        mut self,
+        event_recorder: Option<Arc<BankingEventRecorder>>,
+    ) -> Self {
+        self.event_recorder = event_recorder;
+        self
+    }
+
     /// Handles receiving packet batches from sigverify and returns a vector of deserialized packets
     pub fn receive_packets(
         &self,
@@ -88,19 +119,46 @@ impl PacketDeserializer {
         ) -> Result<ImmutableDeserializedPacket, PacketFilterFailure>,
     ) -> Result<ReceivePacketResults, RecvTimeoutError> {
         let (packet_count, packet_batches) = self.receive_until(recv_timeout, capacity)?;
+        let receipt = self.track_provenance.then(PacketReceipt::now);
+        if let Some(pipeline_tracer) = &self.pipeline_tracer {
+            Self::track_sampled_batches(pipeline_tracer, &packet_batches);
+        }
+        if let Some(event_recorder) = &self.event_recorder {
+            event_recorder.record_packet_batches(&packet_batches);
+        }
 
         Ok(Self::deserialize_and_collect_packets(
             packet_count,
             &packet_batches,
+            receipt,
             packet_filter,
         ))
     }
 
+    /// Follows the transactions of the batches sampled by `pipeline_tracer`
+    fn track_sampled_batches(
+        pipeline_tracer: &PipelineTracer,
+        banking_batches: &[BankingPacketBatch],
+    ) {
+        let received = SystemTime::now();
+        for (batch_id, banking_batch) in pipeline_tracer.take_sampled(banking_batches) {
+            let signatures = banking_batch
+                .iter()
+                .flat_map(|batch| batch.iter())
+                .filter(|pkt| !pkt.meta().discard())
+                .filter_map(|pkt| ImmutableDeserializedPacket::new(pkt).ok())
+                .filter_map(|pkt| pkt.transaction().get_signatures().first().copied());
+            pipeline_tracer.track_received(batch_id, signatures, received);
+        }
+    }
+
     /// Deserialize packet batches, aggregates tracer packet stats, and collect
-    /// them into ReceivePacketResults
+    /// them into ReceivePacketResults. If `receipt` is set, packets are tagged
+    /// with their provenance.
     fn deserialize_and_collect_packets(
         packet_count: usize,
         banking_batches: &[BankingPacketBatch],
+        receipt: Option<PacketReceipt>,
         packet_filter: impl Fn(
             ImmutableDeserializedPacket,
         ) -> Result<ImmutableDeserializedPacket, PacketFilterFailure>,
@@ -115,7 +173,11 @@ impl PacketDeserializer {
             .filter_map(|pkt| {
                 match ImmutableDeserializedPacket::new(pkt)
                     .and_then(|pkt| packet_filter(pkt).map_err(Into::into))
-                {
+                    .map(|deserialized| match receipt {
+                        Some(receipt) => deserialized
+                            .with_provenance(PacketProvenance::new(pkt.meta().addr, receipt)),
+                        None => deserialized,
+                    }) {
                     Ok(pkt) => Some(pkt),
                     Err(err) => {
                         saturating_add_assign!(errors, 1);
@@ -196,6 +258,7 @@ mod tests {
             hash::Hash, pubkey::Pubkey, signature::Keypair, system_transaction,
             transaction::Transaction,
         },
+        std::net::SocketAddr,
     };
 
     fn random_transfer() -> Transaction {
@@ -204,7 +267,7 @@ mod tests {
 
     #[test]
     fn test_deserialize_and_collect_packets_empty() {
-        let results = PacketDeserializer::deserialize_and_collect_packets(0, &[], Ok);
+        let results = PacketDeserializer::deserialize_and_collect_packets(0, &[], None, Ok);
         assert_eq!(results.deserialized_packets.len(), 0);
         assert_eq!(results.packet_stats.passed_sigverify_count, 0);
         assert_eq!(results.packet_stats.failed_sigverify_count, 0);
@@ -220,6 +283,7 @@ mod tests {
         let results = PacketDeserializer::deserialize_and_collect_packets(
             packet_count,
             &[BankingPacketBatch::new(packet_batches)],
+            None,
             Ok,
         );
         assert_eq!(results.deserialized_packets.len(), 2);
@@ -238,10 +302,34 @@ mod tests {
         let results = PacketDeserializer::deserialize_and_collect_packets(
             packet_count,
             &[BankingPacketBatch::new(packet_batches)],
+            None,
             Ok,
         );
         assert_eq!(results.deserialized_packets.len(), 1);
         assert_eq!(results.packet_stats.passed_sigverify_count, 1);
         assert_eq!(results.packet_stats.failed_sigverify_count, 1);
     }
+
+    #[test]
+    fn test_deserialize_and_collect_packets_with_provenance() {
+        let transactions = vec![random_transfer(), random_transfer()];
+        let mut packet_batches = to_packet_batches(&transactions, 1);
+        let remote_addr = SocketAddr::from(([10, 0, 0, 1], 8000));
+        packet_batches[0][0]
+            .meta_mut()
+            .set_socket_addr(&remote_addr);
+
+        let packet_count: usize = packet_batches.iter().map(|x| x.len()).sum();
+        let receipt = PacketReceipt::now();
+        let results = PacketDeserializer::deserialize_and_collect_packets(
+            packet_count,
+            &[BankingPacketBatch::new(packet_batches)],
+            Some(receipt),
+            Ok,
+        );
+        assert_eq!(results.deserialized_packets.len(), 2);
+        let provenance = results.deserialized_packets[0].provenance().unwrap();
+        assert_eq!(provenance.remote_addr(), remote_addr.ip());
+        assert!(results.deserialized_packets[1].provenance().is_some());
+    }
 }
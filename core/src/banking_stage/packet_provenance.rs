@@ -0,0 +1,283 @@
+//! Optional tracking of where and when transaction packets entered the
+//! banking stage, used to measure ingest latency up to commit.
+//!
+//! Packet metadata does not carry a timestamp, so latency is measured from
+//! the moment the banking stage receives a packet from sigverify.
+
+use {
+    super::immutable_deserialized_packet::ImmutableDeserializedPacket,
+    lru::LruCache,
+    solana_rpc::transaction_provenance::TransactionProvenanceCache,
+    solana_sdk::{
+        clock::Slot,
+        signature::Signature,
+        timing::{timestamp, AtomicInterval},
+    },
+    solana_transaction_status::TransactionProvenance,
+    std::{
+        net::IpAddr,
+        sync::{Arc, Mutex},
+        time::Instant,
+    },
+};
+
+const PACKET_PROVENANCE_REPORT_INTERVAL_MS: u64 = 1_000;
+
+/// When a group of packets was received by the banking stage
+#[derive(Clone, Copy, Debug)]
+pub struct PacketReceipt {
+    received: Instant,
+    /// Milliseconds since the UNIX epoch
+    received_at: u64,
+}
+
+impl PacketReceipt {
+    pub fn now() -> Self {
+        Self {
+            received: Instant::now(),
+            received_at: timestamp(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PacketProvenance {
+    remote_addr: IpAddr,
+    receipt: PacketReceipt,
+}
+
+impl PacketProvenance {
+    pub fn new(remote_addr: IpAddr, receipt: PacketReceipt) -> Self {
+        Self {
+            remote_addr,
+            receipt,
+        }
+    }
+
+    pub fn remote_addr(&self) -> IpAddr {
+        self.remote_addr
+    }
+
+    /// Milliseconds since the UNIX epoch at which the packet was received
+    pub fn received_at(&self) -> u64 {
+        self.receipt.received_at
+    }
+}
+
+struct PendingProvenance {
+    provenance: PacketProvenance,
+    buffered: Instant,
+}
+
+#[derive(Default)]
+struct PacketProvenanceStats {
+    num_buffered: u64,
+    num_committed: u64,
+    total_buffer_latency_us: u64,
+    total_commit_latency_us: u64,
+    max_commit_latency_us: u64,
+}
+
+impl PacketProvenanceStats {
+    fn report(&mut self) {
+        datapoint_info!(
+            "banking_stage-packet_provenance",
+            ("num_buffered", self.num_buffered, i64),
+            ("num_committed", self.num_committed, i64),
+            (
+                "mean_buffer_latency_us",
+                self.total_buffer_latency_us
+                    .checked_div(self.num_committed)
+                    .unwrap_or_default(),
+                i64
+            ),
+            (
+                "mean_commit_latency_us",
+                self.total_commit_latency_us
+                    .checked_div(self.num_committed)
+                    .unwrap_or_default(),
+                i64
+            ),
+            ("max_commit_latency_us", self.max_commit_latency_us, i64),
+        );
+        *self = Self::default();
+    }
+}
+
+struct PacketProvenanceTrackerInner {
+    /// Provenance of buffered transactions that have not been committed yet.
+    /// Transactions that are dropped are eventually evicted.
+    pending: LruCache<Signature, PendingProvenance>,
+    stats: PacketProvenanceStats,
+}
+
+/// Follows buffered transactions through to commit, publishing the
+/// provenance of committed transactions to `committed`
+pub struct PacketProvenanceTracker {
+    inner: Mutex<PacketProvenanceTrackerInner>,
+    committed: Arc<TransactionProvenanceCache>,
+    last_report: AtomicInterval,
+}
+
+impl PacketProvenanceTracker {
+    pub fn new(committed: Arc<TransactionProvenanceCache>, capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(PacketProvenanceTrackerInner {
+                pending: LruCache::new(capacity.max(1)),
+                stats: PacketProvenanceStats::default(),
+            }),
+            committed,
+            last_report: AtomicInterval::default(),
+        }
+    }
+
+    /// Starts tracking `packets` that carry a provenance tag
+    pub(crate) fn track_buffered<'a>(
+        &self,
+        packets: impl IntoIterator<Item = &'a ImmutableDeserializedPacket>,
+    ) {
+        let buffered = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        for packet in packets {
+            let Some(provenance) = packet.provenance() else {
+                continue;
+            };
+            let Some(signature) = packet.transaction().get_signatures().first() else {
+                continue;
+            };
+            inner.pending.put(
+                *signature,
+                PendingProvenance {
+                    provenance: *provenance,
+                    buffered,
+                },
+            );
+            inner.stats.num_buffered = inner.stats.num_buffered.saturating_add(1);
+        }
+    }
+
+    /// Records that the transactions identified by `signatures` were
+    /// committed in `slot`
+    pub(crate) fn track_committed<'a>(
+        &self,
+        slot: Slot,
+        signatures: impl IntoIterator<Item = &'a Signature>,
+    ) {
+        let committed = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        for signature in signatures {
+            let Some(PendingProvenance {
+                provenance,
+                buffered,
+            }) = inner.pending.pop(signature)
+            else {
+                continue;
+            };
+            let received = provenance.receipt.received;
+            let buffer_latency_us = buffered.duration_since(received).as_micros() as u64;
+            let commit_latency_us = committed.duration_since(received).as_micros() as u64;
+
+            let stats = &mut inner.stats;
+            stats.num_committed = stats.num_committed.saturating_add(1);
+            stats.total_buffer_latency_us = stats
+                .total_buffer_latency_us
+                .saturating_add(buffer_latency_us);
+            stats.total_commit_latency_us = stats
+                .total_commit_latency_us
+                .saturating_add(commit_latency_us);
+            stats.max_commit_latency_us = stats.max_commit_latency_us.max(commit_latency_us);
+
+            self.committed.insert(
+                *signature,
+                TransactionProvenance {
+                    remote_addr: provenance.remote_addr.to_string(),
+                    received_at: provenance.received_at(),
+                    buffer_latency_us,
+                    commit_latency_us,
+                    commit_slot: slot,
+                },
+            );
+        }
+
+        if self
+            .last_report
+            .should_update(PACKET_PROVENANCE_REPORT_INTERVAL_MS)
+        {
+            inner.stats.report();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash, pubkey::Pubkey, signature::Keypair, system_transaction,
+            transaction::Transaction,
+        },
+        std::net::{Ipv4Addr, SocketAddr},
+    };
+
+    fn new_packet(remote_addr: IpAddr) -> (Transaction, ImmutableDeserializedPacket) {
+        let transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            Hash::default(),
+        );
+        let mut packet = Packet::from_data(None, &transaction).unwrap();
+        packet
+            .meta_mut()
+            .set_socket_addr(&SocketAddr::new(remote_addr, 8000));
+        let packet = ImmutableDeserializedPacket::new(&packet)
+            .unwrap()
+            .with_provenance(PacketProvenance::new(remote_addr, PacketReceipt::now()));
+        (transaction, packet)
+    }
+
+    #[test]
+    fn test_track_committed() {
+        let cache = Arc::new(TransactionProvenanceCache::new(10));
+        let tracker = PacketProvenanceTracker::new(cache.clone(), 10);
+        let remote_addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let (committed_tx, committed_packet) = new_packet(remote_addr);
+        let (dropped_tx, dropped_packet) = new_packet(remote_addr);
+
+        tracker.track_buffered([&committed_packet, &dropped_packet]);
+        tracker.track_committed(5, [&committed_tx.signatures[0]]);
+
+        let provenance = cache.get(&committed_tx.signatures[0]).unwrap();
+        assert_eq!(provenance.remote_addr, "10.0.0.1");
+        assert_eq!(provenance.commit_slot, 5);
+        assert!(provenance.buffer_latency_us <= provenance.commit_latency_us);
+        assert_eq!(cache.get(&dropped_tx.signatures[0]), None);
+
+        // Committing again does not overwrite the published provenance
+        tracker.track_committed(6, [&committed_tx.signatures[0]]);
+        assert_eq!(
+            cache.get(&committed_tx.signatures[0]).unwrap().commit_slot,
+            5
+        );
+    }
+
+    #[test]
+    fn test_untagged_packets_are_ignored() {
+        let cache = Arc::new(TransactionProvenanceCache::new(10));
+        let tracker = PacketProvenanceTracker::new(cache.clone(), 10);
+        let transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            Hash::default(),
+        );
+        let packet = Packet::from_data(None, &transaction).unwrap();
+        let packet = ImmutableDeserializedPacket::new(&packet).unwrap();
+        assert!(packet.provenance().is_none());
+
+        tracker.track_buffered([&packet]);
+        tracker.track_committed(5, [&transaction.signatures[0]]);
+        assert_eq!(cache.get(&transaction.signatures[0]), None);
+    }
+}
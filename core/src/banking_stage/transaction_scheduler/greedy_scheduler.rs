@@ -357,6 +357,7 @@ mod test {
                 MaxAge::MAX,
                 compute_unit_price,
                 TEST_TRANSACTION_COST,
+                0,
             );
         }
 
@@ -9,7 +9,7 @@ pub(crate) mod scheduler;
 pub(crate) mod scheduler_common;
 pub(crate) mod scheduler_controller;
 pub(crate) mod scheduler_error;
-conditional_vis_mod!(scheduler_metrics, feature = "dev-context-only-utils", pub);
+conditional_vis_mod!(scheduler_metrics, feature = "dev-context-only-utils", pub, pub(crate));
 mod thread_aware_account_locks;
 mod transaction_priority_id;
 mod transaction_state;
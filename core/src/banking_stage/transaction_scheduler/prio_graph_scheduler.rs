@@ -524,6 +524,7 @@ mod tests {
                 MaxAge::MAX,
                 compute_unit_price,
                 TEST_TRANSACTION_COST,
+                0,
             );
         }
 
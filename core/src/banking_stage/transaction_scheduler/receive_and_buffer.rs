@@ -12,9 +12,11 @@ use {
     },
     crate::banking_stage::{
         consumer::Consumer, decision_maker::BufferedPacketsDecision,
+        event_recorder::BankingEventRecorder,
         immutable_deserialized_packet::ImmutableDeserializedPacket,
         packet_deserializer::PacketDeserializer, packet_filter::MAX_ALLOWED_PRECOMPILE_SIGNATURES,
-        scheduler_messages::MaxAge, TransactionStateContainer,
+        packet_provenance::PacketProvenanceTracker, scheduler_messages::MaxAge,
+        TransactionStateContainer,
     },
     agave_banking_stage_ingress_types::{BankingPacketBatch, BankingPacketReceiver},
     agave_transaction_view::{
@@ -72,6 +74,7 @@ pub(crate) struct SanitizedTransactionReceiveAndBuffer {
     /// Packet/Transaction ingress.
     packet_receiver: PacketDeserializer,
     bank_forks: Arc<RwLock<BankForks>>,
+    packet_provenance: Option<Arc<PacketProvenanceTracker>>,
 }
 
 impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
@@ -150,9 +153,18 @@ impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
 
 impl SanitizedTransactionReceiveAndBuffer {
     pub fn new(packet_receiver: PacketDeserializer, bank_forks: Arc<RwLock<BankForks>>) -> Self {
+        Self::new_with_packet_provenance(packet_receiver, bank_forks, None)
+    }
+
+    pub fn new_with_packet_provenance(
+        packet_receiver: PacketDeserializer,
+        bank_forks: Arc<RwLock<BankForks>>,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+    ) -> Self {
         Self {
-            packet_receiver,
+            packet_receiver: packet_receiver.with_provenance_tracking(packet_provenance.is_some()),
             bank_forks,
+            packet_provenance,
         }
     }
 
@@ -165,6 +177,9 @@ impl SanitizedTransactionReceiveAndBuffer {
     ) {
         // Convert to Arcs
         let packets: Vec<_> = packets.into_iter().map(Arc::new).collect();
+        if let Some(packet_provenance) = &self.packet_provenance {
+            packet_provenance.track_buffered(packets.iter().map(Arc::as_ref));
+        }
         // Sanitize packets, generate IDs, and insert into the container.
         let (root_bank, working_bank) = {
             let bank_forks = self.bank_forks.read().unwrap();
@@ -246,7 +261,13 @@ impl SanitizedTransactionReceiveAndBuffer {
                 let (priority, cost) =
                     calculate_priority_and_cost(&transaction, &fee_budget_limits, &working_bank);
 
-                if container.insert_new_transaction(transaction, max_age, priority, cost) {
+                if container.insert_new_transaction(
+                    transaction,
+                    max_age,
+                    priority,
+                    cost,
+                    working_bank.slot(),
+                ) {
                     saturating_add_assign!(num_dropped_on_capacity, 1);
                 }
                 saturating_add_assign!(num_buffered, 1);
@@ -286,6 +307,7 @@ impl SanitizedTransactionReceiveAndBuffer {
 pub(crate) struct TransactionViewReceiveAndBuffer {
     pub receiver: BankingPacketReceiver,
     pub bank_forks: Arc<RwLock<BankForks>>,
+    pub event_recorder: Option<Arc<BankingEventRecorder>>,
 }
 
 impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
@@ -328,6 +350,9 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
             match self.receiver.recv_timeout(TIMEOUT) {
                 Ok(packet_batch_message) => {
                     received_message = true;
+                    if let Some(event_recorder) = &self.event_recorder {
+                        event_recorder.record_packet_batches([&packet_batch_message]);
+                    }
                     num_received += self.handle_packet_batch_message(
                         container,
                         timing_metrics,
@@ -351,6 +376,9 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
             match self.receiver.try_recv() {
                 Ok(packet_batch_message) => {
                     received_message = true;
+                    if let Some(event_recorder) = &self.event_recorder {
+                        event_recorder.record_packet_batches([&packet_batch_message]);
+                    }
                     num_received += self.handle_packet_batch_message(
                         container,
                         timing_metrics,
@@ -602,7 +630,13 @@ impl TransactionViewReceiveAndBuffer {
         let fee_budget_limits = FeeBudgetLimits::from(compute_budget_limits);
         let (priority, cost) = calculate_priority_and_cost(&view, &fee_budget_limits, working_bank);
 
-        Ok(TransactionState::new(view, max_age, priority, cost))
+        Ok(TransactionState::new(
+            view,
+            max_age,
+            priority,
+            cost,
+            working_bank.slot(),
+        ))
     }
 }
 
@@ -718,6 +752,7 @@ mod tests {
         let receive_and_buffer = SanitizedTransactionReceiveAndBuffer {
             packet_receiver: PacketDeserializer::new(receiver),
             bank_forks,
+            packet_provenance: None,
         };
         let container = TransactionStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
@@ -733,6 +768,7 @@ mod tests {
         let receive_and_buffer = TransactionViewReceiveAndBuffer {
             receiver,
             bank_forks,
+            event_recorder: None,
         };
         let container = TransactionViewStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
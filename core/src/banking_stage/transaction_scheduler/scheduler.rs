@@ -41,6 +41,16 @@ pub(crate) trait Scheduler<Tx: TransactionWithMeta> {
         Ok((total_num_transactions, total_num_retryable))
     }
 
+    /// Returns the number of transactions that were sent to workers and have
+    /// not completed yet.
+    fn num_in_flight(&mut self) -> usize {
+        self.scheduling_common_mut()
+            .in_flight_tracker
+            .num_in_flight_per_thread()
+            .iter()
+            .sum()
+    }
+
     /// All schedulers should have access to the common context for shared
     /// implementation.
     fn scheduling_common_mut(&mut self) -> &mut SchedulingCommon<Tx>;
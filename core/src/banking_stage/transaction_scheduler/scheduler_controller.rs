@@ -7,24 +7,44 @@ use {
         scheduler::{PreLockFilterAction, Scheduler},
         scheduler_error::SchedulerError,
         scheduler_metrics::{
-            SchedulerCountMetrics, SchedulerLeaderDetectionMetrics, SchedulerTimingMetrics,
-            SchedulingDetails,
+            SchedulerCountMetrics, SchedulerLeaderDetectionMetrics, SchedulerSlotAgeMetrics,
+            SchedulerTimingMetrics, SchedulingDetails, TransactionAge,
         },
     },
     crate::banking_stage::{
         consume_worker::ConsumeWorkerMetrics,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
+        event_recorder::BankingEventRecorder,
         transaction_scheduler::transaction_state_container::StateContainer,
         TOTAL_BUFFERED_PACKETS,
     },
     solana_measure::measure_us,
+    solana_rpc::buffered_transactions::{BufferedTransaction, BufferedTransactionSampler},
     solana_runtime::{bank::Bank, bank_forks::BankForks},
-    solana_sdk::{self, clock::MAX_PROCESSING_AGE, saturating_add_assign},
+    solana_sdk::{
+        self,
+        clock::{Slot, MAX_PROCESSING_AGE},
+        saturating_add_assign,
+    },
     solana_svm::transaction_error_metrics::TransactionErrorMetrics,
-    std::sync::{Arc, RwLock},
+    solana_svm_transaction::svm_transaction::SVMTransaction,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        time::{Duration, Instant},
+    },
 };
 
+/// Maximum time the scheduler waits for in-flight batches when it is asked
+/// to exit
+const MAX_DRAIN_TIME: Duration = Duration::from_secs(1);
+
+/// Interval at which the buffered transactions are sampled for RPC
+const BUFFERED_TRANSACTIONS_SAMPLE_INTERVAL: Duration = Duration::from_millis(400);
+
 /// Controls packet and transaction flow into scheduler, and scheduling execution.
 pub(crate) struct SchedulerController<R, S>
 where
@@ -52,6 +72,16 @@ where
     worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
     /// Detailed scheduling metrics.
     scheduling_details: SchedulingDetails,
+    /// Metrics tracking the ages of the buffered transactions at the end of
+    /// each leader slot.
+    slot_age_metrics: SchedulerSlotAgeMetrics,
+    /// Sampler of the buffered transactions served by RPC, if enabled.
+    buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+    last_buffered_transaction_sample: Instant,
+    /// Recorder of the leader slot boundaries, if enabled.
+    event_recorder: Option<Arc<BankingEventRecorder>>,
+    /// Signals the scheduler to stop scheduling and exit.
+    exit: Arc<AtomicBool>,
 }
 
 impl<R, S> SchedulerController<R, S>
@@ -65,6 +95,7 @@ where
         bank_forks: Arc<RwLock<BankForks>>,
         scheduler: S,
         worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
+        exit: Arc<AtomicBool>,
     ) -> Self {
         Self {
             decision_maker,
@@ -77,11 +108,37 @@ where
             timing_metrics: SchedulerTimingMetrics::default(),
             worker_metrics,
             scheduling_details: SchedulingDetails::default(),
+            slot_age_metrics: SchedulerSlotAgeMetrics::default(),
+            buffered_transaction_sampler: None,
+            last_buffered_transaction_sample: Instant::now(),
+            event_recorder: None,
+            exit,
         }
     }
 
+    pub fn with_buffered_transaction_sampler(
+        mut self,
+        buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+    ) -> Self {
+        self.buffered_transaction_sampler = buffered_transaction_sampler;
+        self
+    }
+
+    pub fn with_event_recorder(
+        mut self,
+        event_recorder: Option<Arc<BankingEventRecorder>>,
+    ) -> Self {
+        self.event_recorder = event_recorder;
+        self
+    }
+
     pub fn run(mut self) -> Result<(), SchedulerError> {
         loop {
+            if self.exit.load(Ordering::Relaxed) {
+                self.drain_in_flight()?;
+                break;
+            }
+
             // BufferedPacketsDecision is shared with legacy BankingStage, which will forward
             // packets. Initially, not renaming these decision variants but the actions taken
             // are different, since new BankingStage will not forward packets.
@@ -97,9 +154,22 @@ where
             self.timing_metrics.update(|timing_metrics| {
                 saturating_add_assign!(timing_metrics.decision_time_us, decision_time_us);
             });
+            if let Some(event_recorder) = &self.event_recorder {
+                event_recorder.record_decision(&decision);
+            }
             let new_leader_slot = decision.bank_start().map(|b| b.working_bank.slot());
             self.leader_detection_metrics
                 .update_and_maybe_report(decision.bank_start());
+            self.slot_age_metrics.maybe_report_and_reset_slot(
+                new_leader_slot,
+                self.container
+                    .transaction_states()
+                    .map(|state| TransactionAge {
+                        received_slot: state.received_slot(),
+                        num_retries: state.num_retries(),
+                        priority: state.priority(),
+                    }),
+            );
             self.count_metrics
                 .maybe_report_and_reset_slot(new_leader_slot);
             self.timing_metrics
@@ -125,11 +195,50 @@ where
                 .iter()
                 .for_each(|metrics| metrics.maybe_report_and_reset());
             self.scheduling_details.maybe_report();
+            self.maybe_sample_buffered_transactions();
         }
 
         Ok(())
     }
 
+    /// Samples the buffered transactions for RPC, once per interval.
+    fn maybe_sample_buffered_transactions(&mut self) {
+        let Some(sampler) = &self.buffered_transaction_sampler else {
+            return;
+        };
+        if self.last_buffered_transaction_sample.elapsed() < BUFFERED_TRANSACTIONS_SAMPLE_INTERVAL {
+            return;
+        }
+        self.last_buffered_transaction_sample = Instant::now();
+        let slot = self.bank_forks.read().unwrap().working_bank().slot();
+        self.sample_buffered_transactions(sampler, slot);
+    }
+
+    /// Samples transactions evenly spread over the container, skipping the
+    /// ones being processed.
+    fn sample_buffered_transactions(&self, sampler: &BufferedTransactionSampler, slot: Slot) {
+        let num_buffered = self.container.buffer_size();
+        let max_samples = sampler.max_samples();
+        let step = num_buffered.div_ceil(max_samples).max(1);
+        let transactions = self
+            .container
+            .transaction_states()
+            .step_by(step)
+            .filter_map(|state| {
+                let transaction = state.try_transaction()?;
+                Some(BufferedTransaction {
+                    signature: *transaction.signature(),
+                    priority: state.priority(),
+                    cost: state.cost(),
+                    slots_waited: slot.saturating_sub(state.received_slot()),
+                    num_retries: state.num_retries(),
+                })
+            })
+            .take(max_samples)
+            .collect();
+        sampler.record(slot, num_buffered, transactions);
+    }
+
     /// Process packets based on decision.
     fn process_transactions(
         &mut self,
@@ -196,7 +305,7 @@ where
         Ok(())
     }
 
-    fn pre_graph_filter(
+    pub(crate) fn pre_graph_filter(
         transactions: &[&R::Transaction],
         results: &mut [bool],
         bank: &Bank,
@@ -301,6 +410,17 @@ where
         });
     }
 
+    /// Waits for the workers to complete the batches that are in flight, so
+    /// that they are idle by the time the scheduler drops its work senders.
+    fn drain_in_flight(&mut self) -> Result<(), SchedulerError> {
+        let start = Instant::now();
+        while self.scheduler.num_in_flight() > 0 && start.elapsed() < MAX_DRAIN_TIME {
+            self.receive_completed()?;
+            std::thread::yield_now();
+        }
+        Ok(())
+    }
+
     /// Receives completed transactions from the workers and updates metrics.
     fn receive_completed(&mut self) -> Result<(), SchedulerError> {
         let ((num_transactions, num_retryable), receive_completed_time_us) =
@@ -401,6 +521,7 @@ mod tests {
         TransactionViewReceiveAndBuffer {
             receiver,
             bank_forks,
+            event_recorder: None,
         }
     }
 
@@ -465,6 +586,7 @@ mod tests {
             bank_forks,
             scheduler,
             vec![], // no actual workers with metrics to report, this can be empty
+            Arc::default(),
         );
 
         (test_frame, scheduler_controller)
@@ -528,6 +650,19 @@ mod tests {
         assert!(scheduler_controller.process_transactions(&decision).is_ok());
     }
 
+    #[test_case(test_create_sanitized_transaction_receive_and_buffer; "Sdk")]
+    #[test_case(test_create_transaction_view_receive_and_buffer; "View")]
+    fn test_exit<R: ReceiveAndBuffer>(
+        create_receive_and_buffer: impl FnOnce(BankingPacketReceiver, Arc<RwLock<BankForks>>) -> R,
+    ) {
+        let (_test_frame, scheduler_controller) = create_test_frame(1, create_receive_and_buffer);
+
+        // The packet sender is still connected, so `run` only returns because
+        // of the exit signal
+        scheduler_controller.exit.store(true, Ordering::Relaxed);
+        scheduler_controller.run().unwrap();
+    }
+
     #[test_case(test_create_sanitized_transaction_receive_and_buffer; "Sdk")]
     #[test_case(test_create_transaction_view_receive_and_buffer; "View")]
     #[should_panic(expected = "batch id 0 is not being tracked")]
@@ -615,6 +750,74 @@ mod tests {
         assert_eq!(message_hashes, vec![&tx2_hash, &tx1_hash]);
     }
 
+    #[test_case(test_create_sanitized_transaction_receive_and_buffer; "Sdk")]
+    #[test_case(test_create_transaction_view_receive_and_buffer; "View")]
+    fn test_sample_buffered_transactions<R: ReceiveAndBuffer>(
+        create_receive_and_buffer: impl FnOnce(BankingPacketReceiver, Arc<RwLock<BankForks>>) -> R,
+    ) {
+        let (test_frame, mut scheduler_controller) =
+            create_test_frame(1, create_receive_and_buffer);
+        let TestFrame {
+            bank,
+            mint_keypair,
+            poh_recorder,
+            banking_packet_sender,
+            ..
+        } = &test_frame;
+
+        poh_recorder
+            .write()
+            .unwrap()
+            .set_bank_for_test(bank.clone());
+
+        let txs: Vec<_> = [1000, 2000, 3000]
+            .into_iter()
+            .map(|compute_unit_price| {
+                create_and_fund_prioritized_transfer(
+                    bank,
+                    mint_keypair,
+                    &Keypair::new(),
+                    &Pubkey::new_unique(),
+                    1,
+                    compute_unit_price,
+                    bank.last_blockhash(),
+                )
+            })
+            .collect();
+        banking_packet_sender
+            .send(to_banking_packet_batch(&txs))
+            .unwrap();
+        let decision = scheduler_controller
+            .decision_maker
+            .make_consume_or_forward_decision();
+        while scheduler_controller
+            .receive_and_buffer_packets(&decision)
+            .map(|n| n > 0)
+            .unwrap_or_default()
+        {}
+
+        // Samples are spread over the buffer.
+        let sampler = BufferedTransactionSampler::new(2);
+        scheduler_controller.sample_buffered_transactions(&sampler, bank.slot() + 2);
+        let sample = sampler.to_rpc().unwrap();
+        assert_eq!(sample.num_buffered, 3);
+        assert_eq!(
+            sample
+                .transactions
+                .iter()
+                .map(|transaction| transaction.signature.clone())
+                .collect_vec(),
+            vec![
+                txs[0].signatures[0].to_string(),
+                txs[2].signatures[0].to_string()
+            ]
+        );
+        assert!(sample
+            .transactions
+            .iter()
+            .all(|transaction| transaction.slots_waited == 2 && transaction.num_retries == 0));
+    }
+
     #[test_case(test_create_sanitized_transaction_receive_and_buffer; "Sdk")]
     #[test_case(test_create_transaction_view_receive_and_buffer; "View")]
     fn test_schedule_consume_single_threaded_conflict<R: ReceiveAndBuffer>(
@@ -404,6 +404,118 @@ impl SchedulerLeaderDetectionMetrics {
     }
 }
 
+/// The age of a buffered transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionAge {
+    pub received_slot: Slot,
+    pub num_retries: u32,
+    pub priority: u64,
+}
+
+/// Reports the distribution of the ages of the buffered transactions at the
+/// end of each leader slot, which shows whether the transactions of low
+/// priority starve.
+#[derive(Default)]
+pub struct SchedulerSlotAgeMetrics {
+    slot: Option<Slot>,
+}
+
+impl SchedulerSlotAgeMetrics {
+    pub fn maybe_report_and_reset_slot(
+        &mut self,
+        slot: Option<Slot>,
+        ages: impl Iterator<Item = TransactionAge>,
+    ) {
+        if self.slot != slot {
+            // Only report if there was an assigned slot.
+            if let Some(ended_slot) = self.slot {
+                BufferedAges::new(ended_slot, ages.collect()).report(ended_slot);
+            }
+            self.slot = slot;
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct BufferedAges {
+    num_buffered: usize,
+    slots_waited_p50: u64,
+    slots_waited_p90: u64,
+    slots_waited_max: u64,
+    num_retries_p50: u64,
+    num_retries_p90: u64,
+    num_retries_max: u64,
+    /// Median slots waited by the quarter of the transactions of the lowest
+    /// priority
+    low_priority_slots_waited_p50: u64,
+    /// Median slots waited by the quarter of the transactions of the highest
+    /// priority
+    high_priority_slots_waited_p50: u64,
+}
+
+impl BufferedAges {
+    fn new(slot: Slot, mut ages: Vec<TransactionAge>) -> Self {
+        if ages.is_empty() {
+            return Self::default();
+        }
+        // Of values sorted in ascending order
+        fn percentile(values: &[u64], percent: usize) -> u64 {
+            values[(values.len() - 1) * percent / 100]
+        }
+        let slots_waited = |ages: &[TransactionAge]| {
+            let mut slots_waited: Vec<_> = ages
+                .iter()
+                .map(|age| slot.saturating_sub(age.received_slot))
+                .collect();
+            slots_waited.sort_unstable();
+            slots_waited
+        };
+        ages.sort_unstable_by_key(|age| age.priority);
+        let quarter = ages.len().div_ceil(4);
+        let all_slots_waited = slots_waited(&ages);
+        let mut num_retries: Vec<_> = ages.iter().map(|age| u64::from(age.num_retries)).collect();
+        num_retries.sort_unstable();
+        Self {
+            num_buffered: ages.len(),
+            slots_waited_p50: percentile(&all_slots_waited, 50),
+            slots_waited_p90: percentile(&all_slots_waited, 90),
+            slots_waited_max: percentile(&all_slots_waited, 100),
+            num_retries_p50: percentile(&num_retries, 50),
+            num_retries_p90: percentile(&num_retries, 90),
+            num_retries_max: percentile(&num_retries, 100),
+            low_priority_slots_waited_p50: percentile(&slots_waited(&ages[..quarter]), 50),
+            high_priority_slots_waited_p50: percentile(
+                &slots_waited(&ages[ages.len() - quarter..]),
+                50,
+            ),
+        }
+    }
+
+    fn report(&self, slot: Slot) {
+        datapoint_info!(
+            "banking_stage_scheduler_slot_ages",
+            ("slot", slot, i64),
+            ("num_buffered", self.num_buffered, i64),
+            ("slots_waited_p50", self.slots_waited_p50, i64),
+            ("slots_waited_p90", self.slots_waited_p90, i64),
+            ("slots_waited_max", self.slots_waited_max, i64),
+            ("num_retries_p50", self.num_retries_p50, i64),
+            ("num_retries_p90", self.num_retries_p90, i64),
+            ("num_retries_max", self.num_retries_max, i64),
+            (
+                "low_priority_slots_waited_p50",
+                self.low_priority_slots_waited_p50,
+                i64
+            ),
+            (
+                "high_priority_slots_waited_p50",
+                self.high_priority_slots_waited_p50,
+                i64
+            ),
+        );
+    }
+}
+
 pub struct SchedulingDetails {
     pub last_report: Instant,
     pub num_schedule_calls: usize,
@@ -511,3 +623,36 @@ impl SchedulingDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffered_ages() {
+        assert_eq!(BufferedAges::new(10, vec![]), BufferedAges::default());
+
+        // The lower the priority, the older the transaction.
+        let ages = (0..8)
+            .map(|priority| TransactionAge {
+                received_slot: priority,
+                num_retries: priority as u32 % 2,
+                priority,
+            })
+            .collect();
+        assert_eq!(
+            BufferedAges::new(10, ages),
+            BufferedAges {
+                num_buffered: 8,
+                slots_waited_p50: 6,
+                slots_waited_p90: 9,
+                slots_waited_max: 10,
+                num_retries_p50: 0,
+                num_retries_p90: 1,
+                num_retries_max: 1,
+                low_priority_slots_waited_p50: 9,
+                high_priority_slots_waited_p50: 3,
+            }
+        );
+    }
+}
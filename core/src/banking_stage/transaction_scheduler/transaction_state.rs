@@ -1,6 +1,6 @@
-use crate::banking_stage::scheduler_messages::MaxAge;
 #[cfg(feature = "dev-context-only-utils")]
 use qualifier_attr::qualifiers;
+use {crate::banking_stage::scheduler_messages::MaxAge, solana_sdk::clock::Slot};
 
 /// TransactionState is used to track the state of a transaction in the transaction scheduler
 /// and banking stage as a whole.
@@ -23,16 +23,28 @@ pub(crate) struct TransactionState<Tx> {
     priority: u64,
     /// Estimated cost of the transaction.
     cost: u64,
+    /// The working slot when the transaction was received.
+    received_slot: Slot,
+    /// Number of times the transaction was retried.
+    num_retries: u32,
 }
 
 impl<Tx> TransactionState<Tx> {
     /// Creates a new `TransactionState` in the `Unprocessed` state.
-    pub(crate) fn new(transaction: Tx, max_age: MaxAge, priority: u64, cost: u64) -> Self {
+    pub(crate) fn new(
+        transaction: Tx,
+        max_age: MaxAge,
+        priority: u64,
+        cost: u64,
+        received_slot: Slot,
+    ) -> Self {
         Self {
             transaction: Some(transaction),
             max_age,
             priority,
             cost,
+            received_slot,
+            num_retries: 0,
         }
     }
 
@@ -48,6 +60,16 @@ impl<Tx> TransactionState<Tx> {
         self.cost
     }
 
+    /// Return the working slot when the transaction was received.
+    pub(crate) fn received_slot(&self) -> Slot {
+        self.received_slot
+    }
+
+    /// Return the number of times the transaction was retried.
+    pub(crate) fn num_retries(&self) -> u32 {
+        self.num_retries
+    }
+
     /// Intended to be called when a transaction is scheduled. This method
     /// takes ownership of the transaction from the state.
     ///
@@ -71,6 +93,7 @@ impl<Tx> TransactionState<Tx> {
             self.transaction.replace(transaction).is_none(),
             "transaction is pending"
         );
+        self.num_retries = self.num_retries.saturating_add(1);
     }
 
     /// Get a reference to the transaction.
@@ -82,6 +105,11 @@ impl<Tx> TransactionState<Tx> {
             .as_ref()
             .expect("transaction is not pending")
     }
+
+    /// Get a reference to the transaction, if it is not in the `Pending` state.
+    pub(crate) fn try_transaction(&self) -> Option<&Tx> {
+        self.transaction.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -117,6 +145,7 @@ mod tests {
             MaxAge::MAX,
             compute_unit_price,
             TEST_TRANSACTION_COST,
+            0,
         )
     }
 
@@ -153,8 +182,11 @@ mod tests {
         assert!(transaction_state.transaction.is_some());
         let (transaction, _max_age) = transaction_state.take_transaction_for_scheduling();
         assert!(transaction_state.transaction.is_none());
+        assert!(transaction_state.try_transaction().is_none());
         transaction_state.retry_transaction(transaction);
         assert!(transaction_state.transaction.is_some());
+        assert!(transaction_state.try_transaction().is_some());
+        assert_eq!(transaction_state.num_retries(), 1);
     }
 
     #[test]
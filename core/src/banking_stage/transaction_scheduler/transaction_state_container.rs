@@ -10,7 +10,7 @@ use {
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_with_meta::TransactionWithMeta,
     },
-    solana_sdk::packet::PACKET_DATA_SIZE,
+    solana_sdk::{clock::Slot, packet::PACKET_DATA_SIZE},
     std::sync::Arc,
 };
 
@@ -96,6 +96,10 @@ pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
 
     fn get_min_max_priority(&self) -> MinMaxResult<u64>;
 
+    /// Iterate over the states of all transactions in the container,
+    /// including the ones being processed.
+    fn transaction_states(&self) -> impl Iterator<Item = &TransactionState<Tx>>;
+
     #[cfg(feature = "dev-context-only-utils")]
     fn clear(&mut self);
 }
@@ -181,6 +185,10 @@ impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<T
         }
     }
 
+    fn transaction_states(&self) -> impl Iterator<Item = &TransactionState<Tx>> {
+        self.id_to_transaction_state.iter().map(|(_, state)| state)
+    }
+
     #[cfg(feature = "dev-context-only-utils")]
     fn clear(&mut self) {
         self.priority_queue.clear();
@@ -197,11 +205,18 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
         max_age: MaxAge,
         priority: u64,
         cost: u64,
+        received_slot: Slot,
     ) -> bool {
         let priority_id = {
             let entry = self.get_vacant_map_entry();
             let transaction_id = entry.key();
-            entry.insert(TransactionState::new(transaction, max_age, priority, cost));
+            entry.insert(TransactionState::new(
+                transaction,
+                max_age,
+                priority,
+                cost,
+                received_slot,
+            ));
             TransactionPriorityId::new(priority, transaction_id)
         };
 
@@ -332,6 +347,11 @@ impl StateContainer<RuntimeTransactionView> for TransactionViewStateContainer {
         self.inner.get_min_max_priority()
     }
 
+    #[inline]
+    fn transaction_states(&self) -> impl Iterator<Item = &TransactionViewState> {
+        self.inner.transaction_states()
+    }
+
     #[cfg(feature = "dev-context-only-utils")]
     #[inline]
     fn clear(&mut self) {
@@ -384,7 +404,7 @@ mod tests {
     ) {
         for priority in 0..num as u64 {
             let (transaction, max_age, priority, cost) = test_transaction(priority);
-            container.insert_new_transaction(transaction, max_age, priority, cost);
+            container.insert_new_transaction(transaction, max_age, priority, cost, 0);
         }
     }
 
@@ -449,7 +469,7 @@ mod tests {
             )
             .unwrap();
 
-            Ok(TransactionState::new(view, MaxAge::MAX, priority, cost))
+            Ok(TransactionState::new(view, MaxAge::MAX, priority, cost, 0))
         };
 
         // Push 2 transactions into the queue so buffer is full.
@@ -6,22 +6,31 @@ use {
             VoteSource,
         },
     },
+    solana_geyser_plugin_manager::vote_notifier_interface::{
+        self, ObservedVote, VoteNotifier, VoteNotifierArc,
+    },
     solana_runtime::bank::Bank,
-    std::sync::Arc,
+    std::{sync::Arc, time::SystemTime},
 };
 
 /// Maximum number of votes a single receive call will accept
 const MAX_NUM_VOTES_RECEIVE: usize = 10_000;
 
-#[derive(Debug)]
 pub struct VoteStorage {
     latest_unprocessed_votes: LatestUnprocessedVotes,
+    /// Notified of the votes received from the TPU. Those from gossip are
+    /// notified by the cluster info vote listener.
+    vote_notifier: Option<VoteNotifierArc>,
 }
 
 impl VoteStorage {
-    pub fn new(latest_unprocessed_votes: LatestUnprocessedVotes) -> Self {
+    pub fn new(
+        latest_unprocessed_votes: LatestUnprocessedVotes,
+        vote_notifier: Option<VoteNotifierArc>,
+    ) -> Self {
         Self {
             latest_unprocessed_votes,
+            vote_notifier,
         }
     }
 
@@ -42,6 +51,11 @@ impl VoteStorage {
         vote_source: VoteSource,
         deserialized_packets: Vec<ImmutableDeserializedPacket>,
     ) -> VoteBatchInsertionMetrics {
+        let received_at = SystemTime::now();
+        let vote_notifier = self
+            .vote_notifier
+            .as_deref()
+            .filter(|_| matches!(vote_source, VoteSource::Tpu));
         self.latest_unprocessed_votes.insert_batch(
             deserialized_packets
                 .into_iter()
@@ -53,11 +67,36 @@ impl VoteStorage {
                             .should_deprecate_legacy_vote_ixs(),
                     )
                     .ok()
+                })
+                .inspect(|vote| {
+                    if let Some(vote_notifier) = vote_notifier {
+                        Self::notify_tpu_vote(vote_notifier, vote, received_at);
+                    }
                 }),
             false, // should_replenish_taken_votes
         )
     }
 
+    fn notify_tpu_vote(
+        vote_notifier: &(dyn VoteNotifier + Sync + Send),
+        vote: &LatestValidatorVotePacket,
+        received_at: SystemTime,
+    ) {
+        let packet = vote.get_vote_packet();
+        let Some(signature) = packet.transaction().get_signatures().first() else {
+            return;
+        };
+        vote_notifier.notify_vote(&ObservedVote {
+            signature,
+            vote_pubkey: &vote.vote_pubkey(),
+            slot: vote.slot(),
+            hash: &vote.hash(),
+            timestamp: vote.timestamp(),
+            source: vote_notifier_interface::VoteSource::Tpu,
+            received_at,
+        });
+    }
+
     // Re-insert re-tryable packets.
     pub(crate) fn reinsert_packets(
         &mut self,
@@ -99,11 +138,12 @@ mod tests {
         solana_runtime::genesis_utils,
         solana_sdk::{
             hash::Hash,
-            signature::{Keypair, Signer},
+            pubkey::Pubkey,
+            signature::{Keypair, Signature, Signer},
         },
         solana_vote::vote_transaction::new_tower_sync_transaction,
         solana_vote_program::vote_state::TowerSync,
-        std::error::Error,
+        std::{error::Error, sync::Mutex},
     };
 
     #[test]
@@ -129,7 +169,7 @@ mod tests {
 
         let latest_unprocessed_votes =
             LatestUnprocessedVotes::new_for_tests(&[vote_keypair.pubkey()]);
-        let mut transaction_storage = VoteStorage::new(latest_unprocessed_votes);
+        let mut transaction_storage = VoteStorage::new(latest_unprocessed_votes, None);
 
         transaction_storage.insert_batch(
             VoteSource::Tpu,
@@ -145,4 +185,66 @@ mod tests {
         assert_eq!(1, transaction_storage.len());
         Ok(())
     }
+
+    #[derive(Default)]
+    struct TestVoteNotifier {
+        votes: Mutex<Vec<(Signature, Pubkey, vote_notifier_interface::VoteSource)>>,
+    }
+
+    impl VoteNotifier for TestVoteNotifier {
+        fn notify_vote(&self, vote: &ObservedVote) {
+            self.votes
+                .lock()
+                .unwrap()
+                .push((*vote.signature, *vote.vote_pubkey, vote.source));
+        }
+    }
+
+    #[test]
+    fn test_insert_batch_notifies_tpu_votes() -> Result<(), Box<dyn Error>> {
+        let node_keypair = Keypair::new();
+        let vote_keypairs = [Keypair::new(), Keypair::new()];
+        let votes = vote_keypairs
+            .iter()
+            .map(|vote_keypair| {
+                let mut vote = Packet::from_data(
+                    None,
+                    new_tower_sync_transaction(
+                        TowerSync::from(vec![(1, 1)]),
+                        Hash::new_unique(),
+                        &node_keypair,
+                        vote_keypair,
+                        vote_keypair,
+                        None,
+                    ),
+                )?;
+                vote.meta_mut().flags.set(PacketFlags::SIMPLE_VOTE_TX, true);
+                Ok(ImmutableDeserializedPacket::new(&vote)?)
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        let tpu_vote_signature = votes[0].transaction().get_signatures()[0];
+
+        let latest_unprocessed_votes = LatestUnprocessedVotes::new_for_tests(&[
+            vote_keypairs[0].pubkey(),
+            vote_keypairs[1].pubkey(),
+        ]);
+        let vote_notifier = Arc::new(TestVoteNotifier::default());
+        let mut transaction_storage =
+            VoteStorage::new(latest_unprocessed_votes, Some(vote_notifier.clone()));
+        let mut votes = votes.into_iter();
+        transaction_storage.insert_batch(VoteSource::Tpu, votes.next().into_iter().collect());
+        transaction_storage.insert_batch(VoteSource::Gossip, votes.collect());
+        assert_eq!(2, transaction_storage.len());
+
+        // Only the vote from the TPU is notified.
+        assert_eq!(
+            *vote_notifier.votes.lock().unwrap(),
+            vec![(
+                tpu_vote_signature,
+                vote_keypairs[0].pubkey(),
+                vote_notifier_interface::VoteSource::Tpu,
+            )]
+        );
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use {
     super::{
+        account_conflicts::AccountConflictTracker,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
         immutable_deserialized_packet::ImmutableDeserializedPacket,
@@ -49,6 +50,7 @@ pub struct VoteWorker {
     storage: VoteStorage,
     bank_forks: Arc<RwLock<BankForks>>,
     consumer: Consumer,
+    account_conflicts: Arc<AccountConflictTracker>,
 }
 
 impl VoteWorker {
@@ -59,6 +61,7 @@ impl VoteWorker {
         storage: VoteStorage,
         bank_forks: Arc<RwLock<BankForks>>,
         consumer: Consumer,
+        account_conflicts: Arc<AccountConflictTracker>,
     ) -> Self {
         Self {
             decision_maker,
@@ -67,12 +70,14 @@ impl VoteWorker {
             storage,
             bank_forks,
             consumer,
+            account_conflicts,
         }
     }
 
     pub fn run(mut self) {
         let mut banking_stage_stats = BankingStageStats::new();
-        let mut slot_metrics_tracker = LeaderSlotMetricsTracker::default();
+        let mut slot_metrics_tracker =
+            LeaderSlotMetricsTracker::new(self.account_conflicts.clone());
 
         let mut last_metrics_update = Instant::now();
 
@@ -397,6 +402,7 @@ impl VoteWorker {
             commit_transactions_result,
             execute_and_commit_timings,
             error_counters,
+            account_conflicts,
             ..
         } = execute_and_commit_transactions_output;
 
@@ -429,6 +435,7 @@ impl VoteWorker {
             cost_model_us,
             execute_and_commit_timings,
             error_counters,
+            account_conflicts,
         }
     }
 
@@ -0,0 +1,214 @@
+//! Runs the manual compactions of blockstore column families scheduled
+//! through the admin RPC service.
+//!
+//! A manual compaction rewrites the whole column family and can stall the
+//! IO of the node for a while, so compactions are only started when the node
+//! is not leader within MIN_SLOTS_UNTIL_LEADER slots. A compaction is not
+//! interrupted once started.
+
+use {
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_ledger::{
+        blockstore::{self, Blockstore},
+        leader_schedule_cache::LeaderScheduleCache,
+    },
+    solana_measure::measure::Measure,
+    solana_runtime::bank_forks::BankForks,
+    std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const COMPACTION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Compactions are deferred while the node is leader within this many slots,
+/// about two minutes.
+pub const MIN_SLOTS_UNTIL_LEADER: u64 = 300;
+
+/// Queue of the column families to compact
+pub struct BlockstoreCompactionScheduler {
+    blockstore: Arc<Blockstore>,
+    queue: Mutex<VecDeque<String>>,
+}
+
+impl BlockstoreCompactionScheduler {
+    pub fn new(blockstore: Arc<Blockstore>) -> Self {
+        Self {
+            blockstore,
+            queue: Mutex::default(),
+        }
+    }
+
+    /// Queues the compaction of the given column families, or of all of them
+    /// if none are given. Column families which are already queued are not
+    /// queued again. Returns the queued column families.
+    pub fn schedule(&self, columns: Vec<String>) -> blockstore::Result<Vec<String>> {
+        let columns = if columns.is_empty() {
+            Blockstore::column_names()
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            columns
+        };
+        // Validates the names before queuing any of them.
+        for column in &columns {
+            self.blockstore.pending_compaction_bytes(column)?;
+        }
+        let mut queue = self.queue.lock().unwrap();
+        for column in columns {
+            if !queue.contains(&column) {
+                queue.push_back(column);
+            }
+        }
+        Ok(queue.iter().cloned().collect())
+    }
+
+    /// Returns the column families waiting to be compacted, the next first.
+    pub fn queued(&self) -> Vec<String> {
+        self.queue.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Returns the estimated compaction debt, in bytes, of each column family.
+    pub fn pending_compaction_bytes(&self) -> Vec<(&'static str, u64)> {
+        Blockstore::column_names()
+            .iter()
+            .map(|&column| {
+                let bytes = self
+                    .blockstore
+                    .pending_compaction_bytes(column)
+                    .unwrap_or_default();
+                (column, bytes)
+            })
+            .collect()
+    }
+
+    fn pop(&self) -> Option<String> {
+        self.queue.lock().unwrap().pop_front()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queue.lock().unwrap().is_empty()
+    }
+}
+
+pub struct BlockstoreCompactionService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl BlockstoreCompactionService {
+    pub fn new(
+        scheduler: Arc<BlockstoreCompactionScheduler>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        leader_schedule_cache: Arc<LeaderScheduleCache>,
+        cluster_info: Arc<ClusterInfo>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solBstoreCompact".to_string())
+            .spawn(move || {
+                info!("BlockstoreCompactionService has started");
+                while !exit.load(Ordering::Relaxed) {
+                    sleep(COMPACTION_CHECK_INTERVAL);
+                    if scheduler.is_empty()
+                        || is_leader_soon(&bank_forks, &leader_schedule_cache, &cluster_info)
+                    {
+                        continue;
+                    }
+                    if let Some(column) = scheduler.pop() {
+                        compact_column(&scheduler.blockstore, &column);
+                    }
+                }
+                info!("BlockstoreCompactionService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+fn is_leader_soon(
+    bank_forks: &RwLock<BankForks>,
+    leader_schedule_cache: &LeaderScheduleCache,
+    cluster_info: &ClusterInfo,
+) -> bool {
+    let bank = bank_forks.read().unwrap().working_bank();
+    leader_schedule_cache
+        .next_leader_slot(
+            &cluster_info.id(),
+            bank.slot(),
+            &bank,
+            None, // blockstore
+            MIN_SLOTS_UNTIL_LEADER,
+        )
+        .is_some()
+}
+
+fn compact_column(blockstore: &Blockstore, column: &str) {
+    let pending_bytes_before = blockstore
+        .pending_compaction_bytes(column)
+        .unwrap_or_default();
+    info!("Compacting blockstore column {column}");
+    let mut measure = Measure::start("compact_column");
+    if let Err(err) = blockstore.compact_column(column) {
+        error!("Failed to compact blockstore column {column}: {err}");
+        return;
+    }
+    measure.stop();
+    info!(
+        "Compacted blockstore column {column} in {}ms",
+        measure.as_ms()
+    );
+    datapoint_info!(
+        "blockstore_manual_compaction",
+        "cf_name" => column.to_string(),
+        ("duration_ms", measure.as_ms(), i64),
+        ("pending_bytes_before", pending_bytes_before, i64),
+        (
+            "pending_bytes_after",
+            blockstore.pending_compaction_bytes(column).unwrap_or_default(),
+            i64
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_ledger::get_tmp_ledger_path_auto_delete};
+
+    #[test]
+    fn test_schedule() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let scheduler = BlockstoreCompactionScheduler::new(blockstore);
+        assert!(scheduler.queued().is_empty());
+
+        let queued = scheduler
+            .schedule(vec!["data_shred".to_string(), "meta".to_string()])
+            .unwrap();
+        assert_eq!(queued, vec!["data_shred", "meta"]);
+        // Unknown columns are rejected, leaving the queue unchanged.
+        assert!(scheduler
+            .schedule(vec!["code_shred".to_string(), "unknown".to_string()])
+            .is_err());
+        assert_eq!(scheduler.queued(), vec!["data_shred", "meta"]);
+        // No columns schedules all of them, once.
+        let queued = scheduler.schedule(vec![]).unwrap();
+        assert_eq!(queued.len(), Blockstore::column_names().len());
+        assert_eq!(queued[..2], ["data_shred", "meta"]);
+
+        assert_eq!(scheduler.pop().as_deref(), Some("data_shred"));
+        assert_eq!(
+            scheduler.pending_compaction_bytes().len(),
+            Blockstore::column_names().len()
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Compares the local clock with the clock of the cluster, so that a drifting
+//! clock is reported before it shows up as vote timestamp or deduplication
+//! anomalies.
+//!
+//! The clock of the cluster is estimated from the wallclocks of the votes
+//! received through gossip, weighted by the stake of their origins. The
+//! timestamp of the working bank, which is derived from PoH and the
+//! timestamps in votes, is reported alongside.
+
+use {
+    solana_gossip::{cluster_info::ClusterInfo, crds::Cursor},
+    solana_runtime::bank_forks::BankForks,
+    solana_sdk::{pubkey::Pubkey, timing::timestamp},
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+pub const DEFAULT_MAX_CLOCK_DRIFT_MS: u64 = 5_000;
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const SLEEP_INTERVAL: Duration = Duration::from_millis(500);
+/// The estimate from gossip is only trusted if the nodes which voted since
+/// the previous check hold at least this share of the stake.
+const MIN_SAMPLED_STAKE_PCT: u64 = 33;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockDriftConfig {
+    /// Drift of the local clock, in milliseconds, beyond which it is reported
+    pub max_drift_ms: u64,
+    /// Stops voting while the drift exceeds max_drift_ms
+    pub refuse_to_vote: bool,
+}
+
+impl Default for ClockDriftConfig {
+    fn default() -> Self {
+        Self {
+            max_drift_ms: DEFAULT_MAX_CLOCK_DRIFT_MS,
+            refuse_to_vote: false,
+        }
+    }
+}
+
+/// The latest verdict of the clock drift service, shared with replay
+#[derive(Debug, Default)]
+pub struct ClockDrift {
+    exceeded: AtomicBool,
+    refuse_to_vote: bool,
+}
+
+impl ClockDrift {
+    pub fn new(refuse_to_vote: bool) -> Self {
+        Self {
+            exceeded: AtomicBool::default(),
+            refuse_to_vote,
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+
+    pub fn should_refuse_to_vote(&self) -> bool {
+        self.refuse_to_vote && self.is_exceeded()
+    }
+}
+
+pub struct ClockDriftService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl ClockDriftService {
+    pub fn new(
+        max_drift_ms: u64,
+        clock_drift: Arc<ClockDrift>,
+        cluster_info: Arc<ClusterInfo>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solClockDrift".to_string())
+            .spawn(move || {
+                info!("ClockDriftService has started");
+                Self::run(
+                    max_drift_ms,
+                    &clock_drift,
+                    &cluster_info,
+                    &bank_forks,
+                    &exit,
+                );
+                info!("ClockDriftService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn run(
+        max_drift_ms: u64,
+        clock_drift: &ClockDrift,
+        cluster_info: &ClusterInfo,
+        bank_forks: &RwLock<BankForks>,
+        exit: &AtomicBool,
+    ) {
+        let mut cursor = Cursor::default();
+        let mut last_check = Instant::now();
+        while !exit.load(Ordering::Relaxed) {
+            sleep(SLEEP_INTERVAL);
+            if last_check.elapsed() < CHECK_INTERVAL {
+                continue;
+            }
+            last_check = Instant::now();
+
+            let offsets = cluster_info.get_vote_clock_offsets(&mut cursor);
+            let bank = bank_forks.read().unwrap().working_bank();
+            let bank_timestamp_ms = bank.clock().unix_timestamp.saturating_mul(1000);
+            let bank_drift_ms = (timestamp() as i64).saturating_sub(bank_timestamp_ms);
+            let min_sampled_stake = bank.total_epoch_stake() * MIN_SAMPLED_STAKE_PCT / 100;
+            let gossip_drift_ms =
+                estimate_gossip_drift(&offsets, &bank.current_epoch_staked_nodes())
+                    .filter(|&(_, sampled_stake)| sampled_stake >= min_sampled_stake)
+                    .map(|(drift_ms, _)| drift_ms);
+
+            let exceeded =
+                gossip_drift_ms.is_some_and(|drift_ms| drift_ms.unsigned_abs() > max_drift_ms);
+            if exceeded {
+                warn!(
+                    "local clock is off by {}ms from the cluster, which exceeds {max_drift_ms}ms{}",
+                    gossip_drift_ms.unwrap_or_default(),
+                    if clock_drift.refuse_to_vote {
+                        "; not voting until the clock is fixed"
+                    } else {
+                        ""
+                    },
+                );
+            } else if clock_drift.is_exceeded() {
+                info!("local clock is back in sync with the cluster");
+            }
+            clock_drift.exceeded.store(exceeded, Ordering::Relaxed);
+
+            datapoint_info!(
+                "clock_drift",
+                ("gossip_drift_ms", gossip_drift_ms, Option<i64>),
+                ("bank_drift_ms", bank_drift_ms, i64),
+                ("bank_slot", bank.slot(), i64),
+                ("num_samples", offsets.len(), i64),
+                ("exceeded", exceeded, bool),
+            );
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+/// Returns how far, in milliseconds, the local clock is ahead of the
+/// stake-weighted median of the clocks of the nodes which sent the votes,
+/// along with the stake of those nodes.
+///
+/// The largest offset of each node is used, as it was observed with the
+/// shortest propagation delay.
+fn estimate_gossip_drift(
+    offsets: &[(Pubkey, i64)],
+    stakes: &HashMap<Pubkey, u64>,
+) -> Option<(i64, u64)> {
+    let mut max_offsets = HashMap::<Pubkey, i64>::new();
+    for (pubkey, offset) in offsets {
+        max_offsets
+            .entry(*pubkey)
+            .and_modify(|max_offset| *max_offset = (*max_offset).max(*offset))
+            .or_insert(*offset);
+    }
+    let mut samples: Vec<(i64, u64)> = max_offsets
+        .into_iter()
+        .filter_map(|(pubkey, offset)| {
+            let stake = stakes.get(&pubkey).copied().filter(|&stake| stake > 0)?;
+            Some((offset, stake))
+        })
+        .collect();
+    samples.sort_unstable();
+    let sampled_stake: u64 = samples.iter().map(|(_, stake)| stake).sum();
+    let mut stake = 0;
+    samples.into_iter().find_map(|(offset, sample_stake)| {
+        stake += sample_stake;
+        (stake.saturating_mul(2) >= sampled_stake).then(|| (offset.saturating_neg(), sampled_stake))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_gossip_drift() {
+        let pubkeys: Vec<_> = std::iter::repeat_with(Pubkey::new_unique).take(4).collect();
+        let stakes = HashMap::from([
+            (pubkeys[0], 10),
+            (pubkeys[1], 40),
+            (pubkeys[2], 30),
+            (pubkeys[3], 0),
+        ]);
+        assert_eq!(estimate_gossip_drift(&[], &stakes), None);
+
+        let offsets = [
+            (pubkeys[0], -9_000),
+            // Only the largest offset of each node is used.
+            (pubkeys[1], -3_000),
+            (pubkeys[1], -2_000),
+            (pubkeys[2], 1_000),
+            // Unstaked nodes are ignored.
+            (pubkeys[3], -50_000),
+            (Pubkey::new_unique(), -50_000),
+        ];
+        // The local clock is ahead of the clock of the node holding the
+        // median stake by 2s.
+        assert_eq!(estimate_gossip_drift(&offsets, &stakes), Some((2_000, 80)));
+
+        let clock_drift = ClockDrift::new(/*refuse_to_vote:*/ true);
+        assert!(!clock_drift.should_refuse_to_vote());
+        clock_drift.exceeded.store(true, Ordering::Relaxed);
+        assert!(clock_drift.should_refuse_to_vote());
+        let clock_drift = ClockDrift::new(/*refuse_to_vote:*/ false);
+        clock_drift.exceeded.store(true, Ordering::Relaxed);
+        assert!(!clock_drift.should_refuse_to_vote());
+    }
+}
@@ -10,6 +10,9 @@ use {
     agave_banking_stage_ingress_types::BankingPacketBatch,
     crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Select, Sender},
     log::*,
+    solana_geyser_plugin_manager::vote_notifier_interface::{
+        ObservedVote, VoteNotifier, VoteNotifierArc, VoteSource,
+    },
     solana_gossip::{
         cluster_info::{ClusterInfo, GOSSIP_SLEEP_MILLIS},
         crds::Cursor,
@@ -19,6 +22,7 @@ use {
     solana_metrics::inc_new_counter_debug,
     solana_perf::packet::{self, PacketBatch},
     solana_rpc::{
+        confirmation_proofs::{ConfirmationProofCache, ConfirmationVote},
         optimistically_confirmed_bank_tracker::{BankNotification, BankNotificationSender},
         rpc_subscriptions::RpcSubscriptions,
     },
@@ -52,7 +56,7 @@ use {
             Arc, Mutex, RwLock,
         },
         thread::{self, sleep, Builder, JoinHandle},
-        time::{Duration, Instant},
+        time::{Duration, Instant, SystemTime},
     },
 };
 
@@ -97,9 +101,18 @@ impl SlotVoteTracker {
 pub struct VoteTracker {
     // Map from a slot to a set of validators who have voted for that slot
     slot_vote_trackers: RwLock<HashMap<Slot, Arc<RwLock<SlotVoteTracker>>>>,
+    // The proofs of the optimistically confirmed slots, if served by RPC
+    confirmation_proofs: Option<Arc<ConfirmationProofCache>>,
 }
 
 impl VoteTracker {
+    pub fn new(confirmation_proofs: Option<Arc<ConfirmationProofCache>>) -> Self {
+        Self {
+            slot_vote_trackers: RwLock::default(),
+            confirmation_proofs,
+        }
+    }
+
     fn get_or_insert_slot_tracker(&self, slot: Slot) -> Arc<RwLock<SlotVoteTracker>> {
         if let Some(slot_vote_tracker) = self.slot_vote_trackers.read().unwrap().get(&slot) {
             return slot_vote_tracker.clone();
@@ -135,6 +148,9 @@ impl VoteTracker {
             .write()
             .unwrap()
             .retain(|slot, _| *slot >= new_root);
+        if let Some(confirmation_proofs) = &self.confirmation_proofs {
+            confirmation_proofs.record_root(new_root);
+        }
     }
 
     fn progress_with_new_root_bank(&self, root_bank: &Bank) {
@@ -202,6 +218,7 @@ impl ClusterInfoVoteListener {
         blockstore: Arc<Blockstore>,
         bank_notification_sender: Option<BankNotificationSender>,
         duplicate_confirmed_slot_sender: DuplicateConfirmedSlotsSender,
+        vote_notifier: Option<VoteNotifierArc>,
     ) -> Self {
         let (verified_vote_transactions_sender, verified_vote_transactions_receiver) = unbounded();
         let listen_thread = {
@@ -216,6 +233,7 @@ impl ClusterInfoVoteListener {
                         &mut root_bank_cache,
                         verified_packets_sender,
                         verified_vote_transactions_sender,
+                        vote_notifier,
                     );
                 })
                 .unwrap()
@@ -258,13 +276,18 @@ impl ClusterInfoVoteListener {
         root_bank_cache: &mut RootBankCache,
         verified_packets_sender: BankingPacketSender,
         verified_vote_transactions_sender: VerifiedVoteTransactionsSender,
+        vote_notifier: Option<VoteNotifierArc>,
     ) -> Result<()> {
         let mut cursor = Cursor::default();
         while !exit.load(Ordering::Relaxed) {
             let votes = cluster_info.get_votes(&mut cursor);
+            let received_at = SystemTime::now();
             inc_new_counter_debug!("cluster_info_vote_listener-recv_count", votes.len());
             if !votes.is_empty() {
                 let (vote_txs, packets) = Self::verify_votes(votes, root_bank_cache);
+                if let Some(vote_notifier) = vote_notifier.as_deref() {
+                    Self::notify_gossip_votes(vote_notifier, &vote_txs, received_at);
+                }
                 verified_vote_transactions_sender.send(vote_txs)?;
                 verified_packets_sender.send(BankingPacketBatch::new(packets))?;
             }
@@ -313,6 +336,32 @@ impl ClusterInfoVoteListener {
             .unzip()
     }
 
+    /// Notifies the verified votes received from gossip, before they land in blocks
+    fn notify_gossip_votes(
+        vote_notifier: &(dyn VoteNotifier + Sync + Send),
+        vote_txs: &[Transaction],
+        received_at: SystemTime,
+    ) {
+        for tx in vote_txs {
+            let Some((vote_pubkey, vote, _, signature)) = vote_parser::parse_vote_transaction(tx)
+            else {
+                continue;
+            };
+            let Some(slot) = vote.last_voted_slot() else {
+                continue;
+            };
+            vote_notifier.notify_vote(&ObservedVote {
+                signature: &signature,
+                vote_pubkey: &vote_pubkey,
+                slot,
+                hash: &vote.hash(),
+                timestamp: vote.timestamp(),
+                source: VoteSource::Gossip,
+                received_at,
+            });
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn process_votes_loop(
         exit: Arc<AtomicBool>,
@@ -445,6 +494,7 @@ impl ClusterInfoVoteListener {
         vote: VoteTransaction,
         vote_pubkey: &Pubkey,
         vote_transaction_signature: Signature,
+        transaction: Option<&Transaction>,
         vote_tracker: &VoteTracker,
         root_bank: &Bank,
         subscriptions: &RpcSubscriptions,
@@ -535,6 +585,25 @@ impl ClusterInfoVoteListener {
                     let _ = gossip_verified_vote_hash_sender.send((*vote_pubkey, slot, hash));
                 }
 
+                if let Some(confirmation_proofs) = &vote_tracker.confirmation_proofs {
+                    if is_new && stake > 0 {
+                        confirmation_proofs.record_vote(
+                            slot,
+                            hash,
+                            ConfirmationVote {
+                                vote_account: *vote_pubkey,
+                                stake,
+                                voted_slot: last_vote_slot,
+                                signature: vote_transaction_signature,
+                                transaction: transaction.cloned(),
+                            },
+                        );
+                    }
+                    if reached_threshold_results[1] {
+                        confirmation_proofs.record_confirmation(slot, hash, total_stake);
+                    }
+                }
+
                 if reached_threshold_results[0] {
                     if let Some(sender) = duplicate_confirmed_slot_sender {
                         let _ = sender.send(vec![(slot, hash)]);
@@ -614,16 +683,23 @@ impl ClusterInfoVoteListener {
 
         // Process votes from gossip and ReplayStage
         let mut gossip_vote_txn_processing_time = Measure::start("gossip_vote_processing_time");
+        // Only the gossip votes come with their transactions.
         let votes = gossip_vote_txs
             .iter()
-            .filter_map(vote_parser::parse_vote_transaction)
+            .filter_map(|tx| Some((vote_parser::parse_vote_transaction(tx)?, Some(tx))))
             .zip(repeat(/*is_gossip:*/ true))
-            .chain(replayed_votes.into_iter().zip(repeat(/*is_gossip:*/ false)));
-        for ((vote_pubkey, vote, _switch_proof, signature), is_gossip) in votes {
+            .chain(
+                replayed_votes
+                    .into_iter()
+                    .map(|vote| (vote, None))
+                    .zip(repeat(/*is_gossip:*/ false)),
+            );
+        for (((vote_pubkey, vote, _switch_proof, signature), transaction), is_gossip) in votes {
             Self::track_new_votes_and_notify_confirmations(
                 vote,
                 &vote_pubkey,
                 signature,
+                transaction,
                 vote_tracker,
                 root_bank,
                 subscriptions,
@@ -1454,6 +1530,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_confirmation_proofs() {
+        let SetupComponents {
+            bank,
+            validator_voting_keypairs,
+            subscriptions,
+            bank_forks,
+            ..
+        } = setup();
+        let confirmation_proofs = Arc::new(ConfirmationProofCache::new(4));
+        let vote_tracker = VoteTracker::new(Some(confirmation_proofs.clone()));
+        let mut latest_vote_slot_per_validator = HashMap::new();
+        let mut bank_hash_cache = BankHashCache::new(bank_forks);
+        let (verified_vote_sender, _verified_vote_receiver) = unbounded();
+        let (gossip_verified_vote_hash_sender, _gossip_verified_vote_hash_receiver) = unbounded();
+
+        let voted_slot = bank.slot() + 1;
+        let vote_txs: Vec<_> = validator_voting_keypairs
+            .iter()
+            .map(|keypairs| {
+                vote_transaction::new_tower_sync_transaction(
+                    TowerSync::from(vec![(voted_slot, 1)]),
+                    Hash::default(),
+                    &keypairs.node_keypair,
+                    &keypairs.vote_keypair,
+                    &keypairs.vote_keypair,
+                    None,
+                )
+            })
+            .collect();
+        let confirmed_slots = ClusterInfoVoteListener::filter_and_confirm_with_new_votes(
+            &vote_tracker,
+            vote_txs.clone(),
+            vec![],
+            &bank,
+            &subscriptions,
+            &gossip_verified_vote_hash_sender,
+            &verified_vote_sender,
+            &None,
+            &None,
+            &mut None,
+            &mut latest_vote_slot_per_validator,
+            &mut bank_hash_cache,
+            &Mutex::new(false),
+        );
+        assert_eq!(confirmed_slots, vec![(voted_slot, Hash::default())]);
+
+        // The proof holds the votes up to the confirmation, with their
+        // transactions from gossip.
+        let proof = confirmation_proofs.to_rpc(voted_slot).unwrap();
+        assert_eq!(proof.bank_hash, Hash::default().to_string());
+        assert!(proof.confirmed_stake as f64 > proof.total_stake as f64 * VOTE_THRESHOLD_SIZE);
+        assert!(proof.votes.len() < vote_txs.len());
+        for (vote, tx) in proof.votes.iter().zip(&vote_txs) {
+            assert_eq!(vote.voted_slot, voted_slot);
+            assert_eq!(vote.signature, tx.signatures[0].to_string());
+            assert!(vote.transaction.is_some());
+        }
+    }
+
     struct SetupComponents {
         vote_tracker: Arc<VoteTracker>,
         bank: Arc<Bank>,
@@ -1562,6 +1698,48 @@ mod tests {
         run_test_verify_votes_1_pass(Some(Hash::default()));
     }
 
+    #[derive(Default)]
+    struct TestVoteNotifier {
+        votes: Mutex<Vec<(Signature, Pubkey, Slot, Hash, VoteSource, SystemTime)>>,
+    }
+
+    impl VoteNotifier for TestVoteNotifier {
+        fn notify_vote(&self, vote: &ObservedVote) {
+            self.votes.lock().unwrap().push((
+                *vote.signature,
+                *vote.vote_pubkey,
+                vote.slot,
+                *vote.hash,
+                vote.source,
+                vote.received_at,
+            ));
+        }
+    }
+
+    #[test]
+    fn test_notify_gossip_votes() {
+        let validator_vote_keypairs = ValidatorVoteKeypairs::new_rand();
+        let vote_tx = test_vote_tx(Some(&validator_vote_keypairs), None);
+        let vote_notifier = TestVoteNotifier::default();
+        let received_at = SystemTime::now();
+        ClusterInfoVoteListener::notify_gossip_votes(
+            &vote_notifier,
+            &[vote_tx.clone()],
+            received_at,
+        );
+        assert_eq!(
+            *vote_notifier.votes.lock().unwrap(),
+            vec![(
+                vote_tx.signatures[0],
+                validator_vote_keypairs.vote_keypair.pubkey(),
+                0,
+                Hash::default(),
+                VoteSource::Gossip,
+                received_at,
+            )]
+        );
+    }
+
     fn run_test_bad_vote(hash: Option<Hash>) {
         let voting_keypairs: Vec<_> = repeat_with(ValidatorVoteKeypairs::new_rand)
             .take(10)
@@ -1662,6 +1840,7 @@ mod tests {
             vote,
             &vote_pubkey,
             signature,
+            None,
             &vote_tracker,
             &bank,
             &subscriptions,
@@ -1695,6 +1874,7 @@ mod tests {
             vote,
             &vote_pubkey,
             signature,
+            None,
             &vote_tracker,
             &bank,
             &subscriptions,
@@ -7,6 +7,7 @@ mod tower1_7_14;
 pub mod tower_storage;
 pub(crate) mod tower_vote_state;
 pub mod tree_diff;
+pub mod vote_refresh;
 pub mod vote_stake_tracker;
 
 use {
@@ -11,9 +11,15 @@ use {
         fs::{self, File},
         io::{self, BufReader},
         path::PathBuf,
+        time::Duration,
     },
 };
 
+const REMOTE_TOWER_STORAGE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Header of the requests to the remote tower storage identifying the instance of the validator
+pub const REMOTE_TOWER_INSTANCE_HEADER: &str = "solana-tower-instance";
+
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum SavedTowerVersions {
@@ -22,7 +28,7 @@ pub enum SavedTowerVersions {
 }
 
 impl SavedTowerVersions {
-    fn try_into_tower(&self, node_pubkey: &Pubkey) -> Result<Tower> {
+    pub(crate) fn try_into_tower(&self, node_pubkey: &Pubkey) -> Result<Tower> {
         // This method assumes that `self` was just deserialized
         assert_eq!(self.pubkey(), Pubkey::default());
 
@@ -369,6 +375,120 @@ impl TowerStorage for EtcdTowerStorage {
     }
 }
 
+/// Stores the tower in a service over HTTP, such as one running alongside the
+/// remote signer of the validator identity, so that a hot spare taking over the
+/// identity resumes from the latest tower without copying tower files.
+///
+/// For each node pubkey, the service keeps the saved tower and the instance of
+/// the validator holding it:
+/// - `PUT {url}/{node_pubkey}/instance`, with the instance id as the body, makes
+///   the instance the holder of the tower.
+/// - `GET {url}/{node_pubkey}/tower` returns the bincode serialized
+///   `SavedTowerVersions`, or `404 Not Found` if none is stored.
+/// - `PUT {url}/{node_pubkey}/tower` stores it.
+///
+/// The tower requests carry the instance id in the `solana-tower-instance`
+/// header, and must be rejected with `409 Conflict` unless the instance holds
+/// the tower. A validator thus stops voting once another instance loaded the
+/// tower.
+pub struct RemoteTowerStorage {
+    url: String,
+    instance_id: String,
+    client: reqwest::blocking::Client,
+}
+
+impl RemoteTowerStorage {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REMOTE_TOWER_STORAGE_TIMEOUT)
+            .build()
+            .map_err(Self::reqwest_to_tower_error)?;
+        Ok(Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            instance_id: solana_sdk::timing::timestamp().to_string(),
+            client,
+        })
+    }
+
+    fn instance_url(&self, node_pubkey: &Pubkey) -> String {
+        format!("{}/{node_pubkey}/instance", self.url)
+    }
+
+    fn tower_url(&self, node_pubkey: &Pubkey) -> String {
+        format!("{}/{node_pubkey}/tower", self.url)
+    }
+
+    fn reqwest_to_tower_error(error: reqwest::Error) -> TowerError {
+        TowerError::IoError(io::Error::other(error.to_string()))
+    }
+
+    fn check_status(
+        response: reqwest::blocking::Response,
+        node_pubkey: &Pubkey,
+    ) -> Result<reqwest::blocking::Response> {
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            reqwest::StatusCode::NOT_FOUND => Err(TowerError::IoError(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No remote saved tower for {node_pubkey}"),
+            ))),
+            reqwest::StatusCode::CONFLICT => Err(TowerError::IoError(io::Error::other(format!(
+                "Lost remote tower instance lock for {node_pubkey}"
+            )))),
+            status => Err(TowerError::IoError(io::Error::other(format!(
+                "Remote tower storage responded {status} for {node_pubkey}"
+            )))),
+        }
+    }
+}
+
+impl TowerStorage for RemoteTowerStorage {
+    fn load(&self, node_pubkey: &Pubkey) -> Result<Tower> {
+        let response = self
+            .client
+            .put(self.instance_url(node_pubkey))
+            .body(self.instance_id.clone())
+            .send()
+            .map_err(|err| {
+                error!("Failed to acquire remote tower instance lock: {}", err);
+                Self::reqwest_to_tower_error(err)
+            })?;
+        Self::check_status(response, node_pubkey)?;
+
+        let response = self
+            .client
+            .get(self.tower_url(node_pubkey))
+            .header(REMOTE_TOWER_INSTANCE_HEADER, &self.instance_id)
+            .send()
+            .map_err(|err| {
+                error!("Failed to read remote saved tower: {}", err);
+                Self::reqwest_to_tower_error(err)
+            })?;
+        let data = Self::check_status(response, node_pubkey)?
+            .bytes()
+            .map_err(Self::reqwest_to_tower_error)?;
+        bincode::deserialize(&data)
+            .map_err(|e| e.into())
+            .and_then(|t: SavedTowerVersions| t.try_into_tower(node_pubkey))
+    }
+
+    fn store(&self, saved_tower: &SavedTowerVersions) -> Result<()> {
+        let node_pubkey = saved_tower.pubkey();
+        let response = self
+            .client
+            .put(self.tower_url(&node_pubkey))
+            .header(REMOTE_TOWER_INSTANCE_HEADER, &self.instance_id)
+            .body(bincode::serialize(saved_tower)?)
+            .send()
+            .map_err(|err| {
+                error!("Failed to write remote saved tower: {}", err);
+                Self::reqwest_to_tower_error(err)
+            })?;
+        Self::check_status(response, &node_pubkey)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use {
@@ -382,6 +502,7 @@ pub mod test {
         solana_vote_program::vote_state::{
             BlockTimestamp, LandedVote, Vote, VoteState, VoteState1_14_11, MAX_LOCKOUT_HISTORY,
         },
+        std::io::{BufRead, Read, Write},
         tempfile::TempDir,
     };
 
@@ -422,4 +543,90 @@ pub mod test {
         assert_eq!(loaded.vote_state.root_slot, Some(1));
         assert_eq!(loaded.stray_restored_slot(), None);
     }
+
+    // Serves the remote tower storage requests one at a time, returning its
+    // url. The connections are closed after each response.
+    fn spawn_remote_tower_storage_service() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let mut instance = None::<String>;
+            let mut tower = None::<Vec<u8>>;
+            for stream in listener.incoming() {
+                let mut stream = BufReader::new(stream.unwrap());
+                let mut line = String::new();
+                stream.read_line(&mut line).unwrap();
+                let mut request = line.split_whitespace();
+                let method = request.next().unwrap().to_string();
+                let path = request.next().unwrap().to_string();
+                let (mut content_length, mut request_instance) = (0, None);
+                loop {
+                    line.clear();
+                    stream.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => content_length = value.parse().unwrap(),
+                        REMOTE_TOWER_INSTANCE_HEADER => request_instance = Some(value.to_string()),
+                        _ => (),
+                    }
+                }
+                let mut body = vec![0; content_length];
+                stream.read_exact(&mut body).unwrap();
+                let (status, response) = match (method.as_str(), path.ends_with("/tower")) {
+                    ("PUT", false) => {
+                        instance = Some(String::from_utf8(body).unwrap());
+                        ("200 OK", vec![])
+                    }
+                    _ if request_instance != instance => ("409 Conflict", vec![]),
+                    ("GET", true) => match &tower {
+                        Some(tower) => ("200 OK", tower.clone()),
+                        None => ("404 Not Found", vec![]),
+                    },
+                    ("PUT", true) => {
+                        tower = Some(body);
+                        ("200 OK", vec![])
+                    }
+                    _ => ("400 Bad Request", vec![]),
+                };
+                let stream = stream.get_mut();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    response.len()
+                )
+                .unwrap();
+                stream.write_all(&response).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_remote_tower_storage() {
+        let url = spawn_remote_tower_storage_service();
+        let identity_keypair = Keypair::new();
+        let node_pubkey = identity_keypair.pubkey();
+        let mut tower_storage = RemoteTowerStorage::new(format!("{url}/")).unwrap();
+        tower_storage.instance_id = "1".to_string();
+        assert!(Tower::restore(&tower_storage, &node_pubkey)
+            .unwrap_err()
+            .is_file_missing());
+
+        let tower = Tower::new_random(node_pubkey);
+        tower.save(&tower_storage, &identity_keypair).unwrap();
+        let loaded = Tower::restore(&tower_storage, &node_pubkey).unwrap();
+        assert_eq!(loaded.node_pubkey, node_pubkey);
+        assert_eq!(loaded.vote_state.root_slot, tower.vote_state.root_slot);
+
+        // Another instance takes over the tower, so the first one can no
+        // longer store it.
+        let mut spare_tower_storage = RemoteTowerStorage::new(url).unwrap();
+        spare_tower_storage.instance_id = "2".to_string();
+        let loaded = Tower::restore(&spare_tower_storage, &node_pubkey).unwrap();
+        assert_eq!(loaded.vote_state.root_slot, tower.vote_state.root_slot);
+        assert!(tower.save(&tower_storage, &identity_keypair).is_err());
+        tower.save(&spare_tower_storage, &identity_keypair).unwrap();
+    }
 }
@@ -0,0 +1,196 @@
+//! The configuration of the refreshing of the last vote, which replay resends
+//! with a newer blockhash when it has not landed on the voted fork, and the
+//! metrics of how effective these refreshes are.
+//!
+//! A refreshed vote is reported in the `vote_refresh_result` metric once it
+//! lands, or once a newer vote supersedes it, with the number of refreshes it
+//! took.
+
+use {
+    solana_sdk::clock::{Slot, MAX_PROCESSING_AGE, NUM_CONSECUTIVE_LEADER_SLOTS},
+    std::time::{Duration, Instant},
+    strum::VariantNames,
+    strum_macros::{Display, EnumString, EnumVariantNames, IntoStaticStr},
+};
+
+// Give at least 4 leaders the chance to pack our vote
+pub const DEFAULT_VOTE_REFRESH_BLOCKHEIGHT: usize = 16;
+pub const DEFAULT_VOTE_REFRESH_INTERVAL_MS: u64 = 5000;
+
+#[derive(
+    Clone, Copy, Debug, Default, Display, EnumString, EnumVariantNames, Eq, IntoStaticStr, PartialEq,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum VoteRefreshMode {
+    /// Refresh once the blockhash of the last vote is `blockheight` blocks
+    /// old on the heaviest bank of the voted fork
+    #[default]
+    Blockheight,
+    /// Also refresh whenever the heaviest bank of the voted fork belongs to a
+    /// leader rotation after the one the last vote was sent or refreshed in
+    LeaderRotation,
+}
+
+impl VoteRefreshMode {
+    pub const fn cli_names() -> &'static [&'static str] {
+        Self::VARIANTS
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoteRefreshConfig {
+    pub mode: VoteRefreshMode,
+    /// Age of the blockhash of the last vote, in blocks, from which it is
+    /// refreshed
+    pub blockheight: usize,
+    /// Minimum time between two refreshes
+    pub min_interval: Duration,
+}
+
+impl Default for VoteRefreshConfig {
+    fn default() -> Self {
+        Self {
+            mode: VoteRefreshMode::default(),
+            blockheight: DEFAULT_VOTE_REFRESH_BLOCKHEIGHT,
+            min_interval: Duration::from_millis(DEFAULT_VOTE_REFRESH_INTERVAL_MS),
+        }
+    }
+}
+
+impl VoteRefreshConfig {
+    /// A refreshed vote must still be valid for the leaders to pack, so the
+    /// blockheight must be below the maximum processing age.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.blockheight == 0 || self.blockheight >= MAX_PROCESSING_AGE {
+            return Err(format!(
+                "vote refresh blockheight must be between 1 and {}, got {}",
+                MAX_PROCESSING_AGE - 1,
+                self.blockheight
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Why the last vote was refreshed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum VoteRefreshReason {
+    Blockheight,
+    LeaderRotation,
+}
+
+pub(crate) fn leader_rotation(slot: Slot) -> u64 {
+    slot / NUM_CONSECUTIVE_LEADER_SLOTS
+}
+
+/// The refreshes of the last refreshed vote
+#[derive(Debug)]
+struct PendingRefreshes {
+    last_voted_slot: Slot,
+    num_refreshes: u64,
+    first_refresh: Instant,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct VoteRefreshStats {
+    pending: Option<PendingRefreshes>,
+}
+
+impl VoteRefreshStats {
+    pub(crate) fn record_refresh(&mut self, last_voted_slot: Slot) {
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| pending.last_voted_slot != last_voted_slot)
+        {
+            self.resolve(/*landed:*/ false);
+        }
+        self.pending
+            .get_or_insert_with(|| PendingRefreshes {
+                last_voted_slot,
+                num_refreshes: 0,
+                first_refresh: Instant::now(),
+            })
+            .num_refreshes += 1;
+    }
+
+    /// Records the latest landed vote on the voted fork, and the last vote of
+    /// the tower, resolving the pending refreshes if their vote landed or was
+    /// superseded.
+    pub(crate) fn record_votes(&mut self, latest_landed_vote_slot: Slot, last_voted_slot: Slot) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+        if latest_landed_vote_slot >= pending.last_voted_slot {
+            self.resolve(/*landed:*/ true);
+        } else if last_voted_slot > pending.last_voted_slot {
+            self.resolve(/*landed:*/ false);
+        }
+    }
+
+    pub(crate) fn num_refreshes(&self) -> u64 {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.num_refreshes)
+            .unwrap_or_default()
+    }
+
+    fn resolve(&mut self, landed: bool) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        datapoint_info!(
+            "vote_refresh_result",
+            ("last_voted_slot", pending.last_voted_slot, i64),
+            ("num_refreshes", pending.num_refreshes, i64),
+            ("landed", landed, bool),
+            (
+                "elapsed_ms",
+                pending.first_refresh.elapsed().as_millis(),
+                i64
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(VoteRefreshConfig::default().validate().is_ok());
+        for blockheight in [0, MAX_PROCESSING_AGE] {
+            let config = VoteRefreshConfig {
+                blockheight,
+                ..VoteRefreshConfig::default()
+            };
+            assert!(config.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_vote_refresh_stats() {
+        let mut stats = VoteRefreshStats::default();
+        stats.record_votes(1, 2);
+        assert_eq!(stats.num_refreshes(), 0);
+
+        stats.record_refresh(2);
+        stats.record_refresh(2);
+        assert_eq!(stats.num_refreshes(), 2);
+        stats.record_votes(1, 2);
+        assert_eq!(stats.num_refreshes(), 2);
+        // The refreshed vote landed.
+        stats.record_votes(2, 2);
+        assert_eq!(stats.num_refreshes(), 0);
+
+        stats.record_refresh(3);
+        // A newer vote supersedes the refreshed vote.
+        stats.record_votes(2, 4);
+        assert_eq!(stats.num_refreshes(), 0);
+        stats.record_refresh(4);
+        stats.record_refresh(5);
+        assert_eq!(stats.num_refreshes(), 1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Local scheduling of the epoch accounts hash calculation.
+//!
+//! The slot the epoch accounts hash is based on, and the slot which must
+//! include it, are fixed by the cluster. Each node only chooses when, within
+//! the calculation window, it calculates the hash of the accounts of the start
+//! slot, and with how many threads. A node which struggles to keep up with
+//! the cluster can delay the calculation, or defer it for as long as it is
+//! deprioritized, so that it competes less with replay.
+//!
+//! The calculation never starts later than half the calculation interval
+//! after the start slot, so that it completes in time on any node able to
+//! calculate it within the other half.
+
+use {
+    rayon::{ThreadPool, ThreadPoolBuilder},
+    solana_sdk::{
+        clock::{Slot, DEFAULT_MS_PER_SLOT},
+        epoch_schedule::EpochSchedule,
+    },
+    std::{
+        num::NonZeroUsize,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
+        thread,
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+const WAIT_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Error, Debug)]
+pub enum EpochAccountsHashSchedulerError {
+    #[error("failed to build the epoch accounts hash thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EpochAccountsHashSchedulingConfig {
+    /// The slots the calculation is delayed by after the start slot is rooted
+    pub start_delay_slots: Slot,
+    /// The threads of the calculation, `None` to share the accounts hash
+    /// threads of accounts-db
+    pub num_threads: Option<NonZeroUsize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochAccountsHashStatus {
+    pub is_deprioritized: bool,
+    /// The start slot of the calculation waiting or in progress, if any
+    pub pending_slot: Option<Slot>,
+    pub is_calculating: bool,
+    pub last_calculated_slot: Option<Slot>,
+    pub last_calculation_ms: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct EpochAccountsHashScheduler {
+    start_delay_slots: Slot,
+    /// `None` if the accounts hash threads of accounts-db are shared
+    thread_pool: Option<ThreadPool>,
+    is_deprioritized: AtomicBool,
+    status: Mutex<EpochAccountsHashStatus>,
+}
+
+impl EpochAccountsHashScheduler {
+    pub fn new(
+        config: EpochAccountsHashSchedulingConfig,
+    ) -> Result<Self, EpochAccountsHashSchedulerError> {
+        let thread_pool = config
+            .num_threads
+            .map(|num_threads| {
+                ThreadPoolBuilder::new()
+                    .num_threads(num_threads.get())
+                    .thread_name(|i| format!("solEpochAcctHs{i:02}"))
+                    .build()
+            })
+            .transpose()?;
+        Ok(Self {
+            start_delay_slots: config.start_delay_slots,
+            thread_pool,
+            ..Self::default()
+        })
+    }
+
+    /// Defers the calculation which has yet to start until the latest time it
+    /// can start, or restores its schedule. A calculation in progress is
+    /// unaffected.
+    pub fn set_deprioritized(&self, is_deprioritized: bool) {
+        self.is_deprioritized
+            .store(is_deprioritized, Ordering::Relaxed);
+    }
+
+    pub fn status(&self) -> EpochAccountsHashStatus {
+        EpochAccountsHashStatus {
+            is_deprioritized: self.is_deprioritized.load(Ordering::Relaxed),
+            ..*self.status.lock().unwrap()
+        }
+    }
+
+    /// The most slots the calculation of the hash of `slot` can be delayed by
+    fn max_start_delay_slots(slot: Slot, epoch_schedule: &EpochSchedule) -> Slot {
+        let slots_per_epoch = epoch_schedule.get_slots_in_epoch(epoch_schedule.get_epoch(slot));
+        // The calculation interval is half the epoch.
+        slots_per_epoch / 4
+    }
+
+    fn slots_duration(num_slots: Slot) -> Duration {
+        Duration::from_millis(num_slots.saturating_mul(DEFAULT_MS_PER_SLOT))
+    }
+
+    /// Waits until the calculation of the hash of `slot`, which was requested
+    /// at `requested`, is due to start
+    pub(crate) fn wait_to_start(
+        &self,
+        slot: Slot,
+        epoch_schedule: &EpochSchedule,
+        requested: Instant,
+        exit: &AtomicBool,
+    ) {
+        self.status.lock().unwrap().pending_slot = Some(slot);
+        let max_start_delay_slots = Self::max_start_delay_slots(slot, epoch_schedule);
+        let delay = Self::slots_duration(self.start_delay_slots.min(max_start_delay_slots));
+        let deadline = Self::slots_duration(max_start_delay_slots);
+        let is_due = |elapsed: Duration| {
+            elapsed >= deadline
+                || (elapsed >= delay && !self.is_deprioritized.load(Ordering::Relaxed))
+        };
+        while !exit.load(Ordering::Relaxed) && !is_due(requested.elapsed()) {
+            thread::sleep(WAIT_INTERVAL);
+        }
+        let waited = requested.elapsed();
+        self.status.lock().unwrap().is_calculating = true;
+        datapoint_info!(
+            "epoch_accounts_hash_scheduling",
+            ("slot", slot, i64),
+            ("event", "start", String),
+            ("waited_ms", waited.as_millis(), i64),
+            (
+                "is_deprioritized",
+                self.is_deprioritized.load(Ordering::Relaxed),
+                bool
+            ),
+        );
+    }
+
+    /// Runs the calculation on the threads of the calculation, passing
+    /// whether it has to use the accounts hash threads of accounts-db instead
+    pub(crate) fn calculate<R: Send>(&self, calculate: impl FnOnce(bool) -> R + Send) -> R {
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(|| calculate(false)),
+            None => calculate(true),
+        }
+    }
+
+    /// Records the completion of the calculation of the hash of `slot`
+    pub(crate) fn record_calculated(&self, slot: Slot, elapsed: Duration) {
+        {
+            let mut status = self.status.lock().unwrap();
+            status.pending_slot = None;
+            status.is_calculating = false;
+            status.last_calculated_slot = Some(slot);
+            status.last_calculation_ms = Some(elapsed.as_millis() as u64);
+        }
+        datapoint_info!(
+            "epoch_accounts_hash_scheduling",
+            ("slot", slot, i64),
+            ("event", "complete", String),
+            ("calculation_ms", elapsed.as_millis(), i64),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_accounts_hash_scheduler() {
+        let epoch_schedule = EpochSchedule::custom(32, 32, false);
+        assert_eq!(
+            EpochAccountsHashScheduler::max_start_delay_slots(40, &epoch_schedule),
+            8
+        );
+
+        let scheduler = EpochAccountsHashScheduler::new(EpochAccountsHashSchedulingConfig {
+            start_delay_slots: 1,
+            num_threads: NonZeroUsize::new(2),
+        })
+        .unwrap();
+        let exit = AtomicBool::new(false);
+        let requested = Instant::now();
+        scheduler.wait_to_start(40, &epoch_schedule, requested, &exit);
+        assert!(requested.elapsed() >= EpochAccountsHashScheduler::slots_duration(1));
+        assert_eq!(
+            scheduler.status(),
+            EpochAccountsHashStatus {
+                is_deprioritized: false,
+                pending_slot: Some(40),
+                is_calculating: true,
+                last_calculated_slot: None,
+                last_calculation_ms: None,
+            }
+        );
+        assert_eq!(
+            scheduler
+                .calculate(|use_bg_thread_pool| (use_bg_thread_pool, rayon::current_num_threads())),
+            (false, 2)
+        );
+        scheduler.record_calculated(40, Duration::from_millis(5));
+        assert_eq!(
+            scheduler.status(),
+            EpochAccountsHashStatus {
+                is_deprioritized: false,
+                pending_slot: None,
+                is_calculating: false,
+                last_calculated_slot: Some(40),
+                last_calculation_ms: Some(5),
+            }
+        );
+
+        // A deprioritized calculation waits until the exit, at most.
+        let scheduler = EpochAccountsHashScheduler::default();
+        scheduler.set_deprioritized(true);
+        assert!(scheduler.status().is_deprioritized);
+        exit.store(true, Ordering::Relaxed);
+        scheduler.wait_to_start(40, &epoch_schedule, Instant::now(), &exit);
+        assert!(scheduler.calculate(|use_bg_thread_pool| use_bg_thread_pool));
+    }
+}
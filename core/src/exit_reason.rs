@@ -0,0 +1,241 @@
+//! Classification of the fatal conditions that cause the validator to exit.
+//!
+//! Each class of fatal condition maps to a distinct process exit code, and
+//! the reason for the most recent exit is written to a machine-readable file
+//! in the ledger directory. This allows process supervisors (systemd,
+//! Kubernetes, etc.) to automate an appropriate remediation per failure class
+//! instead of treating every non-zero exit the same way.
+
+use {
+    crate::validator::ValidatorError,
+    serde::{Deserialize, Serialize},
+    solana_ledger::blockstore::BlockstoreError,
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+        sync::OnceLock,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Name of the file, relative to the ledger directory, that records why the
+/// validator last exited
+pub const LAST_EXIT_REASON_FILE: &str = "last-exit-reason.json";
+
+static LAST_EXIT_REASON_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExitReason {
+    /// The validator shut down as requested
+    Clean,
+    /// A fatal condition that does not fall into any of the other classes
+    Unclassified,
+    /// A bank hash computed locally does not match the cluster's bank hash
+    /// for the same slot
+    BankHashMismatch,
+    /// The blockstore contains data that could not be read back
+    LedgerCorruption,
+    /// The node's genesis, shred version or feature set is incompatible with
+    /// the cluster it is trying to join
+    IncompatibleFeatureSet,
+    /// The filesystem backing the ledger or accounts ran out of space
+    OutOfDisk,
+    /// A wen restart completed and the validator must be restarted with
+    /// --wait-for-supermajority
+    WenRestartFinished,
+}
+
+impl ExitReason {
+    /// Returns the process exit code for this class of exit
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Clean => 0,
+            Self::Unclassified => 1,
+            Self::BankHashMismatch => 10,
+            Self::LedgerCorruption => 11,
+            Self::IncompatibleFeatureSet => 12,
+            Self::OutOfDisk => 13,
+            // 200 is a special error code, see
+            // https://github.com/solana-foundation/solana-improvement-documents/pull/46
+            Self::WenRestartFinished => 200,
+        }
+    }
+
+    /// Classifies an error returned by `Validator::new()`
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(err) = cause.downcast_ref::<ValidatorError>() {
+                return Self::from(err);
+            }
+            if let Some(err) = cause.downcast_ref::<BlockstoreError>() {
+                return Self::from(err);
+            }
+            if let Some(err) = cause.downcast_ref::<io::Error>() {
+                if err.kind() == io::ErrorKind::StorageFull {
+                    return Self::OutOfDisk;
+                }
+            }
+        }
+        Self::Unclassified
+    }
+}
+
+impl From<&ValidatorError> for ExitReason {
+    fn from(err: &ValidatorError) -> Self {
+        match err {
+            ValidatorError::BankHashMismatch(..) => Self::BankHashMismatch,
+            ValidatorError::Blockstore(err) => Self::from(err),
+            ValidatorError::GenesisHashMismatch(..)
+            | ValidatorError::ShredVersionMismatch { .. } => Self::IncompatibleFeatureSet,
+            ValidatorError::WenRestartFinished => Self::WenRestartFinished,
+            ValidatorError::NotEnoughLedgerData(..)
+            | ValidatorError::OpenGenesisConfig(_)
+            | ValidatorError::Other(_)
+            | ValidatorError::PohTooSlow { .. }
+            | ValidatorError::TraceError(_) => Self::Unclassified,
+        }
+    }
+}
+
+impl From<&BlockstoreError> for ExitReason {
+    fn from(err: &BlockstoreError) -> Self {
+        if err.is_out_of_disk() {
+            Self::OutOfDisk
+        } else if err.is_corruption() {
+            Self::LedgerCorruption
+        } else {
+            Self::Unclassified
+        }
+    }
+}
+
+/// Contents of the last exit reason file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastExitReason {
+    pub reason: ExitReason,
+    pub exit_code: i32,
+    pub message: String,
+    /// Seconds since the UNIX epoch at which the exit reason was recorded
+    pub timestamp: u64,
+    pub version: String,
+}
+
+impl LastExitReason {
+    pub fn new(reason: ExitReason, message: impl Into<String>) -> Self {
+        Self {
+            reason,
+            exit_code: reason.exit_code(),
+            message: message.into(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            version: solana_version::version!().to_string(),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        // Write to a temporary file first so that a reader never observes a
+        // partially written file
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let contents = fs::read(path)?;
+        serde_json::from_slice(&contents).map_err(io::Error::other)
+    }
+}
+
+/// Enables recording of exit reasons into `ledger_path`. Until this is called
+/// (e.g. in tests), exit reasons only update the panic exit code.
+pub fn init(ledger_path: &Path) {
+    let _ = LAST_EXIT_REASON_PATH.set(ledger_path.join(LAST_EXIT_REASON_FILE));
+}
+
+/// Records `reason` as the reason the process is about to exit.
+///
+/// This must be called before panicking on a known fatal condition so that
+/// the panic hook exits with the matching exit code.
+pub fn record(reason: ExitReason, message: impl Into<String>) {
+    solana_metrics::set_panic_exit_code(reason.exit_code());
+    let Some(path) = LAST_EXIT_REASON_PATH.get() else {
+        return;
+    };
+    let last_exit_reason = LastExitReason::new(reason, message);
+    if let Err(err) = last_exit_reason.write(path) {
+        warn!(
+            "Unable to write last exit reason to {}: {err}",
+            path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::hash::Hash, tempfile::TempDir};
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let reasons = [
+            ExitReason::Clean,
+            ExitReason::Unclassified,
+            ExitReason::BankHashMismatch,
+            ExitReason::LedgerCorruption,
+            ExitReason::IncompatibleFeatureSet,
+            ExitReason::OutOfDisk,
+            ExitReason::WenRestartFinished,
+        ];
+        let exit_codes: std::collections::HashSet<_> =
+            reasons.iter().map(ExitReason::exit_code).collect();
+        assert_eq!(exit_codes.len(), reasons.len());
+    }
+
+    #[test]
+    fn test_from_error() {
+        let err = anyhow::Error::new(ValidatorError::BankHashMismatch(
+            Hash::new_unique(),
+            Hash::new_unique(),
+        ));
+        assert_eq!(ExitReason::from_error(&err), ExitReason::BankHashMismatch);
+
+        let err = anyhow::Error::new(ValidatorError::ShredVersionMismatch {
+            actual: 1,
+            expected: 2,
+        });
+        assert_eq!(
+            ExitReason::from_error(&err),
+            ExitReason::IncompatibleFeatureSet
+        );
+
+        let err = anyhow::Error::new(ValidatorError::Blockstore(BlockstoreError::Io(
+            io::Error::from(io::ErrorKind::StorageFull),
+        )));
+        assert_eq!(ExitReason::from_error(&err), ExitReason::OutOfDisk);
+
+        let err = anyhow::Error::new(io::Error::from(io::ErrorKind::StorageFull))
+            .context("failed to create snapshot directory");
+        assert_eq!(ExitReason::from_error(&err), ExitReason::OutOfDisk);
+
+        let err = anyhow::anyhow!("something else went wrong");
+        assert_eq!(ExitReason::from_error(&err), ExitReason::Unclassified);
+    }
+
+    #[test]
+    fn test_last_exit_reason_roundtrip() {
+        let ledger_dir = TempDir::new().unwrap();
+        let path = ledger_dir.path().join(LAST_EXIT_REASON_FILE);
+
+        let last_exit_reason = LastExitReason::new(ExitReason::LedgerCorruption, "bad block");
+        last_exit_reason.write(&path).unwrap();
+        let read = LastExitReason::read(&path).unwrap();
+        assert_eq!(read, last_exit_reason);
+        assert_eq!(read.exit_code, 11);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"reason\": \"ledger-corruption\""));
+    }
+}
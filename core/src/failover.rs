@@ -0,0 +1,711 @@
+//! Hot-standby failover between two validators sharing an identity.
+//!
+//! Both validators are configured with the shared identity, the one of the
+//! vote account, and with a node keypair of their own. The active validator
+//! runs with the shared identity, the standby with its node keypair, under
+//! which it cannot vote. They exchange heartbeats over UDP, signed by their
+//! node keypairs. The heartbeats of the active validator carry its saved
+//! tower, so that the standby holds its latest votes.
+//!
+//! The standby promotes itself, i.e. takes the shared identity, once the
+//! active validator missed `max_missed_heartbeats`, or when it hands over
+//! after being demoted through the admin RPC. Before doing so, it saves the
+//! latest tower it received to its tower storage, from which replay loads the
+//! tower of the new identity, and refuses to promote if that tower is older
+//! than the last vote reported by the active validator. Each promotion
+//! increments a term: should both validators be active, e.g. once a partition
+//! between them heals, the one with the older term demotes itself as soon as
+//! it receives a heartbeat of the other.
+//!
+//! The votes cast by the active validator after its last heartbeat are only
+//! known from its vote account, from which replay adjusts the tower.
+
+use {
+    crate::{
+        consensus::{
+            tower_storage::{SavedTower, SavedTowerVersions, TowerStorage},
+            Tower, TowerError,
+        },
+        identity_switch,
+    },
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_sdk::{
+        clock::Slot,
+        pubkey::Pubkey,
+        quic::NotifyKeyUpdate,
+        signature::{Keypair, Signature, Signer},
+        timing::timestamp,
+    },
+    std::{
+        io,
+        net::{SocketAddr, UdpSocket},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+pub const DEFAULT_FAILOVER_HEARTBEAT_INTERVAL_MS: u64 = 500;
+pub const DEFAULT_FAILOVER_MAX_MISSED_HEARTBEATS: u64 = 6;
+/// Heartbeats whose wallclock differs more from the local clock are discarded
+const MAX_HEARTBEAT_CLOCK_SKEW_MS: u64 = 10_000;
+/// A heartbeat carrying a tower exceeds the MTU, so it is fragmented
+const MAX_HEARTBEAT_SIZE: usize = 64 * 1024;
+const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug)]
+pub struct FailoverConfig {
+    pub bind_address: SocketAddr,
+    pub peer_address: SocketAddr,
+    /// The pubkey of the node keypair of the peer, which signs its heartbeats
+    pub peer_pubkey: Pubkey,
+    /// Signs the heartbeats, and is the identity of the validator while it
+    /// stands by
+    pub node_keypair: Arc<Keypair>,
+    /// The identity of the active validator
+    pub shared_identity: Arc<Keypair>,
+    pub heartbeat_interval: Duration,
+    pub max_missed_heartbeats: u64,
+    /// Whether the standby promotes itself once the active validator missed
+    /// `max_missed_heartbeats`
+    pub auto_promote: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum FailoverError {
+    #[error("the validator is already active")]
+    AlreadyActive,
+
+    #[error("the validator is not active")]
+    NotActive,
+
+    #[error("the peer is active, its last heartbeat was received {0}ms ago")]
+    PeerActive(u64),
+
+    #[error("no tower of the shared identity is available")]
+    NoTower,
+
+    #[error(
+        "the latest tower voted slot {tower_slot:?} but the peer reported voting slot {peer_slot}"
+    )]
+    StaleTower {
+        tower_slot: Option<Slot>,
+        peer_slot: Slot,
+    },
+
+    #[error("invalid heartbeat: {0}")]
+    InvalidHeartbeat(&'static str),
+
+    #[error(transparent)]
+    Tower(#[from] TowerError),
+
+    #[error(transparent)]
+    Serialize(#[from] bincode::Error),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct Heartbeat {
+    from: Pubkey,
+    /// Increases with each heartbeat, so that replayed heartbeats are discarded
+    sequence: u64,
+    wallclock: u64,
+    term: u64,
+    is_active: bool,
+    /// Set by a validator which was demoted through the admin RPC, until the
+    /// peer is active
+    is_handing_over: bool,
+    last_voted_slot: Option<Slot>,
+    /// The saved tower of the shared identity, sent by the active validator
+    tower: Option<SavedTowerVersions>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignedHeartbeat {
+    signature: Signature,
+    /// The serialized heartbeat
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+struct PeerState {
+    heartbeat: Heartbeat,
+    received: Instant,
+    /// The latest tower received from the peer
+    tower: Option<Tower>,
+}
+
+struct FailoverState {
+    /// Incremented by each promotion of either validator
+    term: u64,
+    sequence: u64,
+    auto_promote: bool,
+    is_handing_over: bool,
+    /// `None` until a heartbeat of the peer was received, as the standby does
+    /// not promote itself before
+    peer: Option<PeerState>,
+    /// Why the latest automatic promotion was refused
+    refusal: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailoverStatus {
+    pub is_active: bool,
+    pub identity: Pubkey,
+    pub term: u64,
+    pub auto_promote: bool,
+    pub is_handing_over: bool,
+    pub peer_pubkey: Pubkey,
+    pub peer_address: SocketAddr,
+    pub peer_is_active: Option<bool>,
+    pub peer_term: Option<u64>,
+    pub peer_last_voted_slot: Option<Slot>,
+    pub ms_since_peer_heartbeat: Option<u64>,
+    pub peer_tower_last_voted_slot: Option<Slot>,
+    pub refusal: Option<String>,
+}
+
+/// Switches the identity of the validator between the shared identity and
+/// its node keypair
+pub struct FailoverCoordinator {
+    config: FailoverConfig,
+    cluster_info: Arc<ClusterInfo>,
+    tower_storage: Arc<dyn TowerStorage>,
+    notifies: Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>,
+    state: Mutex<FailoverState>,
+}
+
+impl FailoverCoordinator {
+    pub fn new(
+        config: FailoverConfig,
+        cluster_info: Arc<ClusterInfo>,
+        tower_storage: Arc<dyn TowerStorage>,
+        notifies: Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>,
+    ) -> Self {
+        let state = FailoverState {
+            term: 0,
+            // Start from the wallclock so that the heartbeats sent after a
+            // restart are not discarded by the peer.
+            sequence: timestamp(),
+            auto_promote: config.auto_promote,
+            is_handing_over: false,
+            peer: None,
+            refusal: None,
+        };
+        Self {
+            config,
+            cluster_info,
+            tower_storage,
+            notifies,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.cluster_info.id() == self.config.shared_identity.pubkey()
+    }
+
+    pub fn status(&self) -> FailoverStatus {
+        let state = self.state.lock().unwrap();
+        let peer = state.peer.as_ref();
+        FailoverStatus {
+            is_active: self.is_active(),
+            identity: self.cluster_info.id(),
+            term: state.term,
+            auto_promote: state.auto_promote,
+            is_handing_over: state.is_handing_over,
+            peer_pubkey: self.config.peer_pubkey,
+            peer_address: self.config.peer_address,
+            peer_is_active: peer.map(|peer| peer.heartbeat.is_active),
+            peer_term: peer.map(|peer| peer.heartbeat.term),
+            peer_last_voted_slot: peer.and_then(|peer| peer.heartbeat.last_voted_slot),
+            ms_since_peer_heartbeat: peer.map(|peer| peer.received.elapsed().as_millis() as u64),
+            peer_tower_last_voted_slot: peer
+                .and_then(|peer| peer.tower.as_ref())
+                .and_then(Tower::last_voted_slot),
+            refusal: state.refusal.clone(),
+        }
+    }
+
+    pub fn set_auto_promote(&self, auto_promote: bool) {
+        self.state.lock().unwrap().auto_promote = auto_promote;
+        info!("Failover automatic promotion set to {auto_promote}");
+    }
+
+    /// Takes the shared identity. Unless `force` is set, the promotion is
+    /// refused while the peer is active, or if the latest tower is older than
+    /// the last vote the peer reported.
+    pub fn promote(&self, force: bool) -> Result<(), FailoverError> {
+        let mut state = self.state.lock().unwrap();
+        self.do_promote(&mut state, force)
+    }
+
+    /// Takes the node keypair as identity, and hands over to the peer
+    pub fn demote(&self) -> Result<(), FailoverError> {
+        let mut state = self.state.lock().unwrap();
+        if !self.is_active() {
+            return Err(FailoverError::NotActive);
+        }
+        state.is_handing_over = true;
+        self.set_identity(&self.config.node_keypair);
+        warn!(
+            "Demoted to standby, handing over to {}",
+            self.config.peer_pubkey
+        );
+        Ok(())
+    }
+
+    fn do_promote(&self, state: &mut FailoverState, force: bool) -> Result<(), FailoverError> {
+        if self.is_active() {
+            return Err(FailoverError::AlreadyActive);
+        }
+        let heartbeat_timeout = self.heartbeat_timeout();
+        if let Some(peer) = &state.peer {
+            if !force && peer.heartbeat.is_active && peer.received.elapsed() < heartbeat_timeout {
+                return Err(FailoverError::PeerActive(
+                    peer.received.elapsed().as_millis() as u64,
+                ));
+            }
+        }
+
+        // Use the latest of the tower in the tower storage and the one
+        // received from the peer.
+        let shared_pubkey = self.config.shared_identity.pubkey();
+        let stored_tower = match Tower::restore(self.tower_storage.as_ref(), &shared_pubkey) {
+            Ok(tower) => Some(tower),
+            Err(err) if err.is_file_missing() || force => {
+                warn!("Unable to load the tower of {shared_pubkey}: {err}");
+                None
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let peer = state.peer.as_ref();
+        let peer_tower = peer
+            .and_then(|peer| peer.tower.as_ref())
+            .filter(|peer_tower| {
+                peer_tower.last_voted_slot()
+                    > stored_tower.as_ref().and_then(Tower::last_voted_slot)
+            });
+        let tower_slot = match peer_tower {
+            Some(peer_tower) => {
+                peer_tower.save(self.tower_storage.as_ref(), &self.config.shared_identity)?;
+                peer_tower.last_voted_slot()
+            }
+            None if stored_tower.is_none() && !force => return Err(FailoverError::NoTower),
+            None => stored_tower.as_ref().and_then(Tower::last_voted_slot),
+        };
+        if let Some(peer_slot) = peer.and_then(|peer| peer.heartbeat.last_voted_slot) {
+            if !force && tower_slot < Some(peer_slot) {
+                return Err(FailoverError::StaleTower {
+                    tower_slot,
+                    peer_slot,
+                });
+            }
+        }
+
+        let peer_term = peer.map(|peer| peer.heartbeat.term).unwrap_or_default();
+        state.term = state.term.max(peer_term).saturating_add(1);
+        state.is_handing_over = false;
+        state.refusal = None;
+        self.set_identity(&self.config.shared_identity);
+        warn!(
+            "Promoted to active with term {} and the tower voting slot {tower_slot:?}",
+            state.term
+        );
+        Ok(())
+    }
+
+    fn set_identity(&self, keypair: &Arc<Keypair>) {
+        identity_switch::set_identity(&self.cluster_info, &self.notifies, keypair.clone());
+    }
+
+    fn heartbeat_timeout(&self) -> Duration {
+        self.config
+            .heartbeat_interval
+            .saturating_mul(self.config.max_missed_heartbeats as u32)
+    }
+
+    /// Returns the next heartbeat, signed by the node keypair
+    fn new_heartbeat(&self) -> Result<Vec<u8>, FailoverError> {
+        let is_active = self.is_active();
+        let tower = if is_active {
+            let shared_pubkey = self.config.shared_identity.pubkey();
+            match Tower::restore(self.tower_storage.as_ref(), &shared_pubkey) {
+                Ok(tower) => Some(tower),
+                Err(err) => {
+                    // The tower was not saved yet, i.e. no vote was cast.
+                    debug!("Unable to load the tower of {shared_pubkey}: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let mut state = self.state.lock().unwrap();
+        state.sequence = state.sequence.saturating_add(1);
+        let heartbeat = Heartbeat {
+            from: self.config.node_keypair.pubkey(),
+            sequence: state.sequence,
+            wallclock: timestamp(),
+            term: state.term,
+            is_active,
+            is_handing_over: state.is_handing_over,
+            last_voted_slot: tower.as_ref().and_then(Tower::last_voted_slot),
+            tower: tower
+                .map(|tower| SavedTower::new(&tower, &*self.config.shared_identity))
+                .transpose()?
+                .map(SavedTowerVersions::from),
+        };
+        let data = bincode::serialize(&heartbeat)?;
+        let signature = self.config.node_keypair.sign_message(&data);
+        Ok(bincode::serialize(&SignedHeartbeat { signature, data })?)
+    }
+
+    /// Verifies a heartbeat of the peer and records it, demoting the
+    /// validator if both are active and the peer prevails
+    fn receive_heartbeat(&self, packet: &[u8]) -> Result<(), FailoverError> {
+        let signed: SignedHeartbeat = bincode::deserialize(packet)?;
+        if !signed
+            .signature
+            .verify(self.config.peer_pubkey.as_ref(), &signed.data)
+        {
+            return Err(FailoverError::InvalidHeartbeat("invalid signature"));
+        }
+        let heartbeat: Heartbeat = bincode::deserialize(&signed.data)?;
+        if heartbeat.from != self.config.peer_pubkey {
+            return Err(FailoverError::InvalidHeartbeat("unexpected sender"));
+        }
+        if heartbeat.wallclock.abs_diff(timestamp()) > MAX_HEARTBEAT_CLOCK_SKEW_MS {
+            return Err(FailoverError::InvalidHeartbeat("wallclock out of range"));
+        }
+        let shared_pubkey = self.config.shared_identity.pubkey();
+        let tower = heartbeat
+            .tower
+            .as_ref()
+            .map(|tower| tower.try_into_tower(&shared_pubkey))
+            .transpose()?;
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(peer) = &state.peer {
+            if heartbeat.sequence <= peer.heartbeat.sequence {
+                return Err(FailoverError::InvalidHeartbeat("replayed heartbeat"));
+            }
+        }
+        if heartbeat.is_active {
+            state.is_handing_over = false;
+            // Both validators are active: the one promoted last prevails.
+            if self.is_active()
+                && (heartbeat.term, heartbeat.from)
+                    > (state.term, self.config.node_keypair.pubkey())
+            {
+                error!(
+                    "The peer {} is active with term {} while this validator is active with \
+                     term {}, demoting",
+                    heartbeat.from, heartbeat.term, state.term
+                );
+                self.set_identity(&self.config.node_keypair);
+            }
+        }
+        let tower = tower.or_else(|| state.peer.take().and_then(|peer| peer.tower));
+        state.peer = Some(PeerState {
+            heartbeat,
+            received: Instant::now(),
+            tower,
+        });
+        Ok(())
+    }
+
+    /// Promotes the standby if the active validator missed
+    /// `max_missed_heartbeats` or hands over
+    fn maybe_auto_promote(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.auto_promote || state.is_handing_over || self.is_active() {
+            return;
+        }
+        let Some(peer) = &state.peer else {
+            return;
+        };
+        let reason = if peer.received.elapsed() >= self.heartbeat_timeout() {
+            "missed heartbeats"
+        } else if peer.heartbeat.is_handing_over && !peer.heartbeat.is_active {
+            "handover"
+        } else {
+            return;
+        };
+        info!("Promoting on {reason} of {}", self.config.peer_pubkey);
+        if let Err(err) = self.do_promote(&mut state, false) {
+            let refusal = format!("promotion on {reason} refused: {err}");
+            if state.refusal.as_ref() != Some(&refusal) {
+                error!("{refusal}");
+                state.refusal = Some(refusal);
+            }
+        }
+    }
+}
+
+pub struct FailoverService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl FailoverService {
+    pub fn new(coordinator: Arc<FailoverCoordinator>, exit: Arc<AtomicBool>) -> io::Result<Self> {
+        let socket = UdpSocket::bind(coordinator.config.bind_address)?;
+        socket.set_read_timeout(Some(RECV_TIMEOUT))?;
+        let thread_hdl = Builder::new()
+            .name("solFailover".to_string())
+            .spawn(move || {
+                info!("FailoverService has started");
+                Self::run(&coordinator, &socket, &exit);
+                info!("FailoverService has stopped");
+            })
+            .unwrap();
+        Ok(Self { thread_hdl })
+    }
+
+    fn run(coordinator: &FailoverCoordinator, socket: &UdpSocket, exit: &AtomicBool) {
+        let mut buffer = vec![0u8; MAX_HEARTBEAT_SIZE];
+        let mut last_heartbeat: Option<Instant> = None;
+        while !exit.load(Ordering::Relaxed) {
+            if last_heartbeat.map_or(true, |sent| {
+                sent.elapsed() >= coordinator.config.heartbeat_interval
+            }) {
+                last_heartbeat = Some(Instant::now());
+                match coordinator.new_heartbeat() {
+                    Ok(heartbeat) => {
+                        if let Err(err) =
+                            socket.send_to(&heartbeat, coordinator.config.peer_address)
+                        {
+                            warn!("Unable to send the failover heartbeat: {err}");
+                        }
+                    }
+                    Err(err) => error!("Unable to create the failover heartbeat: {err}"),
+                }
+            }
+            match socket.recv_from(&mut buffer) {
+                Ok((len, from)) => {
+                    if let Err(err) = coordinator.receive_heartbeat(&buffer[..len]) {
+                        warn!("Discarded the failover heartbeat from {from}: {err}");
+                    }
+                }
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) => {}
+                Err(err) => warn!("Unable to receive failover heartbeats: {err}"),
+            }
+            coordinator.maybe_auto_promote();
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::consensus::tower_storage::FileTowerStorage,
+        solana_gossip::contact_info::ContactInfo, solana_sdk::hash::Hash,
+        solana_streamer::socket::SocketAddrSpace, tempfile::TempDir,
+    };
+
+    struct TestNode {
+        coordinator: FailoverCoordinator,
+        _tower_path: TempDir,
+    }
+
+    fn new_node(
+        node_keypair: &Arc<Keypair>,
+        peer_keypair: &Keypair,
+        shared_identity: &Arc<Keypair>,
+        is_active: bool,
+    ) -> TestNode {
+        let identity = if is_active {
+            shared_identity.clone()
+        } else {
+            node_keypair.clone()
+        };
+        let cluster_info = Arc::new(ClusterInfo::new(
+            ContactInfo::new_localhost(&identity.pubkey(), timestamp()),
+            identity,
+            SocketAddrSpace::Unspecified,
+        ));
+        let tower_path = TempDir::new().unwrap();
+        let config = FailoverConfig {
+            bind_address: "127.0.0.1:0".parse().unwrap(),
+            peer_address: "127.0.0.1:0".parse().unwrap(),
+            peer_pubkey: peer_keypair.pubkey(),
+            node_keypair: node_keypair.clone(),
+            shared_identity: shared_identity.clone(),
+            heartbeat_interval: Duration::from_millis(100),
+            max_missed_heartbeats: 3,
+            auto_promote: true,
+        };
+        TestNode {
+            coordinator: FailoverCoordinator::new(
+                config,
+                cluster_info,
+                Arc::new(FileTowerStorage::new(tower_path.path().to_path_buf())),
+                vec![],
+            ),
+            _tower_path: tower_path,
+        }
+    }
+
+    fn save_tower(node: &TestNode, shared_identity: &Keypair, slots: &[Slot]) {
+        let mut tower = Tower {
+            node_pubkey: shared_identity.pubkey(),
+            ..Tower::default()
+        };
+        for slot in slots {
+            tower.record_vote(*slot, Hash::new_unique());
+        }
+        tower
+            .save(node.coordinator.tower_storage.as_ref(), shared_identity)
+            .unwrap();
+    }
+
+    fn send_heartbeat(from: &TestNode, to: &TestNode) -> Result<(), FailoverError> {
+        to.coordinator
+            .receive_heartbeat(&from.coordinator.new_heartbeat().unwrap())
+    }
+
+    #[test]
+    fn test_failover_on_missed_heartbeats() {
+        let primary_keypair = Arc::new(Keypair::new());
+        let standby_keypair = Arc::new(Keypair::new());
+        let shared_identity = Arc::new(Keypair::new());
+        let primary = new_node(&primary_keypair, &standby_keypair, &shared_identity, true);
+        let standby = new_node(&standby_keypair, &primary_keypair, &shared_identity, false);
+        assert!(primary.coordinator.is_active());
+        assert!(!standby.coordinator.is_active());
+
+        // The standby does not promote itself before hearing from the primary.
+        thread::sleep(Duration::from_millis(400));
+        standby.coordinator.maybe_auto_promote();
+        assert!(!standby.coordinator.is_active());
+
+        // The tower of the primary is transferred with its heartbeats.
+        save_tower(&primary, &shared_identity, &[1, 2, 3]);
+        send_heartbeat(&primary, &standby).unwrap();
+        send_heartbeat(&standby, &primary).unwrap();
+        let status = standby.coordinator.status();
+        assert_eq!(status.peer_is_active, Some(true));
+        assert_eq!(status.peer_last_voted_slot, Some(3));
+        assert_eq!(status.peer_tower_last_voted_slot, Some(3));
+        standby.coordinator.maybe_auto_promote();
+        assert!(!standby.coordinator.is_active());
+        assert!(matches!(
+            standby.coordinator.promote(false),
+            Err(FailoverError::PeerActive(_))
+        ));
+
+        thread::sleep(Duration::from_millis(400));
+        standby.coordinator.maybe_auto_promote();
+        assert!(standby.coordinator.is_active());
+        assert_eq!(standby.coordinator.status().term, 1);
+        let tower = Tower::restore(
+            standby.coordinator.tower_storage.as_ref(),
+            &shared_identity.pubkey(),
+        )
+        .unwrap();
+        assert_eq!(tower.last_voted_slot(), Some(3));
+
+        // The former primary steps down once it hears from the new one.
+        send_heartbeat(&standby, &primary).unwrap();
+        assert!(!primary.coordinator.is_active());
+        assert_eq!(
+            primary.coordinator.status().identity,
+            primary_keypair.pubkey()
+        );
+    }
+
+    #[test]
+    fn test_failover_handover() {
+        let primary_keypair = Arc::new(Keypair::new());
+        let standby_keypair = Arc::new(Keypair::new());
+        let shared_identity = Arc::new(Keypair::new());
+        let primary = new_node(&primary_keypair, &standby_keypair, &shared_identity, true);
+        let standby = new_node(&standby_keypair, &primary_keypair, &shared_identity, false);
+        standby.coordinator.set_auto_promote(false);
+
+        save_tower(&primary, &shared_identity, &[5]);
+        send_heartbeat(&primary, &standby).unwrap();
+        assert!(matches!(
+            standby.coordinator.demote(),
+            Err(FailoverError::NotActive)
+        ));
+        primary.coordinator.demote().unwrap();
+        assert!(!primary.coordinator.is_active());
+        send_heartbeat(&primary, &standby).unwrap();
+
+        // The automatic promotion is paused.
+        standby.coordinator.maybe_auto_promote();
+        assert!(!standby.coordinator.is_active());
+        standby.coordinator.set_auto_promote(true);
+        standby.coordinator.maybe_auto_promote();
+        assert!(standby.coordinator.is_active());
+
+        // The former primary no longer hands over once the standby is active.
+        send_heartbeat(&standby, &primary).unwrap();
+        assert!(!primary.coordinator.status().is_handing_over);
+        assert!(matches!(
+            primary.coordinator.promote(false),
+            Err(FailoverError::PeerActive(_))
+        ));
+    }
+
+    #[test]
+    fn test_failover_refuses_stale_tower() {
+        let primary_keypair = Arc::new(Keypair::new());
+        let standby_keypair = Arc::new(Keypair::new());
+        let shared_identity = Arc::new(Keypair::new());
+        let primary = new_node(&primary_keypair, &standby_keypair, &shared_identity, true);
+        let standby = new_node(&standby_keypair, &primary_keypair, &shared_identity, false);
+
+        // No tower is available.
+        send_heartbeat(&primary, &standby).unwrap();
+        thread::sleep(Duration::from_millis(400));
+        standby.coordinator.maybe_auto_promote();
+        assert!(!standby.coordinator.is_active());
+        assert!(standby.coordinator.status().refusal.is_some());
+        assert!(matches!(
+            standby.coordinator.promote(false),
+            Err(FailoverError::NoTower)
+        ));
+        standby.coordinator.promote(true).unwrap();
+        assert!(standby.coordinator.is_active());
+    }
+
+    #[test]
+    fn test_receive_invalid_heartbeat() {
+        let primary_keypair = Arc::new(Keypair::new());
+        let standby_keypair = Arc::new(Keypair::new());
+        let shared_identity = Arc::new(Keypair::new());
+        let primary = new_node(&primary_keypair, &standby_keypair, &shared_identity, true);
+        let standby = new_node(&standby_keypair, &primary_keypair, &shared_identity, false);
+        let other = new_node(
+            &Arc::new(Keypair::new()),
+            &standby_keypair,
+            &shared_identity,
+            true,
+        );
+
+        let heartbeat = primary.coordinator.new_heartbeat().unwrap();
+        standby.coordinator.receive_heartbeat(&heartbeat).unwrap();
+        assert!(matches!(
+            standby.coordinator.receive_heartbeat(&heartbeat),
+            Err(FailoverError::InvalidHeartbeat("replayed heartbeat"))
+        ));
+        assert!(matches!(
+            send_heartbeat(&other, &standby),
+            Err(FailoverError::InvalidHeartbeat("invalid signature"))
+        ));
+        assert!(standby.coordinator.receive_heartbeat(&[0; 8]).is_err());
+    }
+}
@@ -10,8 +10,9 @@ use {
         clock::{DEFAULT_TICKS_PER_SLOT, HOLD_TRANSACTIONS_SLOT_OFFSET},
         packet::{Packet, PacketFlags},
     },
-    solana_streamer::streamer::{
-        self, PacketBatchReceiver, PacketBatchSender, StreamerReceiveStats,
+    solana_streamer::{
+        bandwidth_accounting::Subsystem,
+        streamer::{self, PacketBatchReceiver, PacketBatchSender, StreamerReceiveStats},
     },
     solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
     std::{
@@ -154,7 +155,8 @@ impl FetchStage {
     ) -> Self {
         let recycler: PacketBatchRecycler = Recycler::warmed(1000, 1024);
 
-        let tpu_stats = Arc::new(StreamerReceiveStats::new("tpu_receiver"));
+        let tpu_stats =
+            Arc::new(StreamerReceiveStats::new("tpu_receiver").with_subsystem(Subsystem::Tpu));
 
         let tpu_threads: Vec<_> = if tpu_enable_udp {
             tpu_sockets
@@ -179,7 +181,9 @@ impl FetchStage {
             Vec::default()
         };
 
-        let tpu_forward_stats = Arc::new(StreamerReceiveStats::new("tpu_forwards_receiver"));
+        let tpu_forward_stats = Arc::new(
+            StreamerReceiveStats::new("tpu_forwards_receiver").with_subsystem(Subsystem::Tpu),
+        );
         let tpu_forwards_threads: Vec<_> = if tpu_enable_udp {
             tpu_forwards_sockets
                 .into_iter()
@@ -203,7 +207,8 @@ impl FetchStage {
             Vec::default()
         };
 
-        let tpu_vote_stats = Arc::new(StreamerReceiveStats::new("tpu_vote_receiver"));
+        let tpu_vote_stats =
+            Arc::new(StreamerReceiveStats::new("tpu_vote_receiver").with_subsystem(Subsystem::Tpu));
         let tpu_vote_threads: Vec<_> = tpu_vote_sockets
             .into_iter()
             .enumerate()
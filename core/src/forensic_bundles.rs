@@ -0,0 +1,379 @@
+//! Forensic bundles of the slots found to be duplicate, or frozen with a
+//! different bank hash than the one the cluster confirmed, written to a
+//! directory to speed up the triage of consensus bugs.
+//!
+//! A bundle is a directory named after its slot, its reason and its creation
+//! time, holding:
+//! - `manifest.json`: the slot, the reason, the hashes and the files of the
+//!   bundle
+//! - `data_shreds.bin` and `coding_shreds.bin`: the bincode encoded payloads of
+//!   the shreds of the slot in the blockstore
+//! - `duplicate_shred_1.bin` and `duplicate_shred_2.bin`: the conflicting
+//!   shreds of a duplicate shred proof
+//! - `bank_hash_details.json`: the bank hash components of a mismatched bank,
+//!   including the accounts written by the slot, which the accounts delta hash
+//!   is computed from
+//!
+//! Bundles are written to a hidden directory first and renamed once complete,
+//! so that partial bundles are never listed.
+
+use {
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_gossip::duplicate_shred_proofs::{DuplicateShredProof, DuplicateShredProofNotifier},
+    solana_ledger::blockstore::Blockstore,
+    solana_runtime::bank::{
+        bank_hash_details::{BankHashDetails, SlotDetails},
+        Bank,
+    },
+    solana_sdk::{clock::Slot, hash::Hash, timing::timestamp},
+    std::{
+        collections::HashSet,
+        fs, io,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    },
+};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ForensicBundleReason {
+    /// A duplicate shred proof of the slot was observed in gossip
+    DuplicateShredProof,
+    /// The slot was frozen with a different bank hash than the one the
+    /// cluster confirmed
+    BankHashMismatch,
+}
+
+impl ForensicBundleReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DuplicateShredProof => "duplicate-shred-proof",
+            Self::BankHashMismatch => "bank-hash-mismatch",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForensicBundleManifest {
+    /// The name of the directory of the bundle
+    pub name: String,
+    pub slot: Slot,
+    pub reason: ForensicBundleReason,
+    /// Milliseconds since the UNIX epoch at which the bundle was captured
+    pub created: u64,
+    /// The version of the validator which captured the bundle
+    pub version: String,
+    pub leader: Option<String>,
+    pub frozen_hash: Option<String>,
+    pub cluster_hash: Option<String>,
+    /// The files of the bundle, besides the manifest
+    pub files: Vec<String>,
+}
+
+/// Captures forensic bundles into a directory
+pub struct ForensicBundles {
+    dir: PathBuf,
+    blockstore: Arc<Blockstore>,
+    /// The mismatched bank hashes already captured
+    captured_mismatches: Mutex<HashSet<(Slot, Hash)>>,
+}
+
+impl ForensicBundles {
+    pub fn new(dir: PathBuf, blockstore: Arc<Blockstore>) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            blockstore,
+            captured_mismatches: Mutex::default(),
+        })
+    }
+
+    /// Captures the proof and the shreds of a duplicate slot
+    pub fn capture_duplicate_shred_proof(
+        &self,
+        proof: &DuplicateShredProof,
+    ) -> io::Result<ForensicBundleManifest> {
+        let mut files = self.slot_shreds(proof.slot)?;
+        files.push(("duplicate_shred_1.bin", proof.shred1.clone()));
+        files.push(("duplicate_shred_2.bin", proof.shred2.clone()));
+        let manifest = self.new_manifest(
+            proof.slot,
+            ForensicBundleReason::DuplicateShredProof,
+            Some(proof.leader.to_string()),
+            None,
+            None,
+        );
+        self.write_bundle(manifest, files)
+    }
+
+    /// Captures the shreds and the bank hash components of a frozen `bank`
+    /// whose hash differs from `cluster_hash`. Returns `None` if this bank
+    /// hash of the slot was already captured.
+    pub fn capture_bank_hash_mismatch(
+        &self,
+        bank: &Bank,
+        cluster_hash: &Hash,
+    ) -> io::Result<Option<ForensicBundleManifest>> {
+        let slot = bank.slot();
+        let frozen_hash = bank.hash();
+        if !self
+            .captured_mismatches
+            .lock()
+            .unwrap()
+            .insert((slot, frozen_hash))
+        {
+            return Ok(None);
+        }
+        let slot_details =
+            SlotDetails::new_from_bank(bank, /*include_bank_hash_components:*/ true)
+                .map_err(io::Error::other)?;
+        let bank_hash_details =
+            serde_json::to_vec_pretty(&BankHashDetails::new(vec![slot_details]))
+                .map_err(io::Error::other)?;
+        let mut files = self.slot_shreds(slot)?;
+        files.push(("bank_hash_details.json", bank_hash_details));
+        let manifest = self.new_manifest(
+            slot,
+            ForensicBundleReason::BankHashMismatch,
+            Some(bank.collector_id().to_string()),
+            Some(frozen_hash.to_string()),
+            Some(cluster_hash.to_string()),
+        );
+        self.write_bundle(manifest, files).map(Some)
+    }
+
+    /// Returns the manifests of the bundles, oldest first
+    pub fn list(&self) -> io::Result<Vec<ForensicBundleManifest>> {
+        let mut manifests: Vec<ForensicBundleManifest> = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let manifest = match fs::read(entry.path().join(MANIFEST_FILE))
+                .and_then(|manifest| serde_json::from_slice(&manifest).map_err(io::Error::other))
+            {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    warn!(
+                        "Failed to read the forensic bundle manifest of {}: {err}",
+                        entry.path().display()
+                    );
+                    continue;
+                }
+            };
+            manifests.push(manifest);
+        }
+        manifests.sort_by(|a, b| (a.created, &a.name).cmp(&(b.created, &b.name)));
+        Ok(manifests)
+    }
+
+    /// Returns the manifest and the files of the bundle `name`
+    pub fn read(&self, name: &str) -> io::Result<(ForensicBundleManifest, Vec<(String, Vec<u8>)>)> {
+        let manifest = self
+            .list()?
+            .into_iter()
+            .find(|manifest| manifest.name == name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Forensic bundle {name} not found"),
+                )
+            })?;
+        let dir = self.dir.join(&manifest.name);
+        let files = manifest
+            .files
+            .iter()
+            .map(|file| Ok((file.clone(), fs::read(dir.join(file))?)))
+            .collect::<io::Result<_>>()?;
+        Ok((manifest, files))
+    }
+
+    fn new_manifest(
+        &self,
+        slot: Slot,
+        reason: ForensicBundleReason,
+        leader: Option<String>,
+        frozen_hash: Option<String>,
+        cluster_hash: Option<String>,
+    ) -> ForensicBundleManifest {
+        let created = timestamp();
+        ForensicBundleManifest {
+            name: format!("{slot}-{}-{created}", reason.as_str()),
+            slot,
+            reason,
+            created,
+            version: solana_version::version!().to_string(),
+            leader,
+            frozen_hash,
+            cluster_hash,
+            files: vec![],
+        }
+    }
+
+    // Returns the bincode encoded payloads of the data and coding shreds of
+    // `slot`.
+    fn slot_shreds(&self, slot: Slot) -> io::Result<Vec<(&'static str, Vec<u8>)>> {
+        let data_shreds: Vec<Box<[u8]>> = self
+            .blockstore
+            .slot_data_iterator(slot, 0)
+            .map_err(io::Error::other)?
+            .map(|(_, payload)| payload)
+            .collect();
+        let coding_shreds: Vec<Box<[u8]>> = self
+            .blockstore
+            .slot_coding_iterator(slot, 0)
+            .map_err(io::Error::other)?
+            .map(|(_, payload)| payload)
+            .collect();
+        Ok(vec![
+            (
+                "data_shreds.bin",
+                bincode::serialize(&data_shreds).map_err(io::Error::other)?,
+            ),
+            (
+                "coding_shreds.bin",
+                bincode::serialize(&coding_shreds).map_err(io::Error::other)?,
+            ),
+        ])
+    }
+
+    fn write_bundle(
+        &self,
+        mut manifest: ForensicBundleManifest,
+        files: Vec<(&'static str, Vec<u8>)>,
+    ) -> io::Result<ForensicBundleManifest> {
+        let tmp_dir = self.dir.join(format!(".{}", manifest.name));
+        fs::create_dir_all(&tmp_dir)?;
+        for (file, contents) in files {
+            fs::write(tmp_dir.join(file), contents)?;
+            manifest.files.push(file.to_string());
+        }
+        fs::write(
+            tmp_dir.join(MANIFEST_FILE),
+            serde_json::to_vec_pretty(&manifest).map_err(io::Error::other)?,
+        )?;
+        fs::rename(&tmp_dir, self.dir.join(&manifest.name))?;
+        Ok(manifest)
+    }
+}
+
+impl DuplicateShredProofNotifier for ForensicBundles {
+    fn notify_duplicate_shred_proof(&self, proof: &DuplicateShredProof) {
+        match self.capture_duplicate_shred_proof(proof) {
+            Ok(manifest) => info!("Captured forensic bundle {}", manifest.name),
+            Err(err) => warn!(
+                "Failed to capture the forensic bundle of duplicate slot {}: {err}",
+                proof.slot
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::{blockstore::make_slot_entries, get_tmp_ledger_path_auto_delete},
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::pubkey::Pubkey,
+    };
+
+    #[test]
+    fn test_capture_duplicate_shred_proof() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let (shreds, _) = make_slot_entries(1, 0, 4, true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        let payloads: Vec<Vec<u8>> = blockstore
+            .get_data_shreds_for_slot(1, 0)
+            .unwrap()
+            .iter()
+            .map(|shred| shred.payload().to_vec())
+            .collect();
+        assert!(!payloads.is_empty());
+
+        let bundle_dir = tempfile::TempDir::new().unwrap();
+        let forensic_bundles =
+            ForensicBundles::new(bundle_dir.path().to_path_buf(), blockstore).unwrap();
+        let proof = DuplicateShredProof {
+            slot: 1,
+            leader: Pubkey::new_unique(),
+            reporter: Pubkey::new_unique(),
+            shred1: vec![1; 8],
+            shred2: vec![2; 8],
+        };
+        forensic_bundles.notify_duplicate_shred_proof(&proof);
+
+        let manifests = forensic_bundles.list().unwrap();
+        assert_eq!(manifests.len(), 1);
+        let manifest = &manifests[0];
+        assert_eq!(manifest.slot, 1);
+        assert_eq!(manifest.reason, ForensicBundleReason::DuplicateShredProof);
+        assert_eq!(manifest.leader, Some(proof.leader.to_string()));
+        assert!(manifest.name.starts_with("1-duplicate-shred-proof-"));
+
+        let (read_manifest, files) = forensic_bundles.read(&manifest.name).unwrap();
+        assert_eq!(&read_manifest, manifest);
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|(file, _)| file == name)
+                .map(|(_, contents)| contents.clone())
+                .unwrap()
+        };
+        let data_shreds: Vec<Vec<u8>> = bincode::deserialize(&file("data_shreds.bin")).unwrap();
+        assert_eq!(data_shreds, payloads);
+        assert_eq!(file("duplicate_shred_1.bin"), proof.shred1);
+        assert_eq!(file("duplicate_shred_2.bin"), proof.shred2);
+
+        assert_eq!(
+            forensic_bundles.read("missing").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(forensic_bundles.read("../ledger").is_err());
+    }
+
+    #[test]
+    fn test_capture_bank_hash_mismatch() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let bundle_dir = tempfile::TempDir::new().unwrap();
+        let forensic_bundles =
+            ForensicBundles::new(bundle_dir.path().to_path_buf(), blockstore).unwrap();
+
+        let genesis_config = create_genesis_config(10_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::new_unique(), 1);
+        bank1.freeze();
+        let cluster_hash = Hash::new_unique();
+
+        let manifest = forensic_bundles
+            .capture_bank_hash_mismatch(&bank1, &cluster_hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(manifest.reason, ForensicBundleReason::BankHashMismatch);
+        assert_eq!(manifest.frozen_hash, Some(bank1.hash().to_string()));
+        assert_eq!(manifest.cluster_hash, Some(cluster_hash.to_string()));
+        assert!(manifest
+            .files
+            .contains(&"bank_hash_details.json".to_string()));
+        // The same bank hash is captured once.
+        assert!(forensic_bundles
+            .capture_bank_hash_mismatch(&bank1, &cluster_hash)
+            .unwrap()
+            .is_none());
+
+        let (_, files) = forensic_bundles.read(&manifest.name).unwrap();
+        let (_, bank_hash_details) = files
+            .iter()
+            .find(|(file, _)| file == "bank_hash_details.json")
+            .unwrap();
+        let bank_hash_details: BankHashDetails = serde_json::from_slice(bank_hash_details).unwrap();
+        assert_eq!(bank_hash_details.bank_hash_details[0].slot, 1);
+    }
+}
@@ -2,7 +2,7 @@
 //! packets to a node that is or will be leader soon.
 
 use {
-    crate::next_leader::next_leaders,
+    crate::next_leader::{next_distinct_leaders, next_leaders},
     agave_banking_stage_ingress_types::BankingPacketBatch,
     agave_transaction_view::transaction_view::SanitizedTransactionView,
     async_trait::async_trait,
@@ -28,7 +28,10 @@ use {
         transaction::MessageHash,
         transport::TransportError,
     },
-    solana_streamer::sendmmsg::{batch_send, SendPktsError},
+    solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
+        sendmmsg::{batch_send, SendPktsError},
+    },
     solana_tpu_client_next::{
         connection_workers_scheduler::{BindTarget, ConnectionWorkersSchedulerConfig, Fanout},
         leader_updater::LeaderUpdater,
@@ -73,6 +76,16 @@ const FORWARD_BATCH_SIZE: usize = 128;
 /// all lookahead slots is negligible.
 const NUM_LOOKAHEAD_LEADERS: u64 = 3;
 
+/// Default number of distinct upcoming leaders that each buffered transaction
+/// is forwarded to.
+///
+/// Forwarding to more than the immediate next leader improves the chance of a
+/// transaction landing across leader transitions, e.g. when the next leader
+/// skips its slots or the transaction arrives right before the leader rotates.
+pub const DEFAULT_FORWARDING_LEADER_FANOUT: usize = 2;
+/// Upper bound on the number of leaders each transaction is forwarded to.
+pub const MAX_FORWARDING_LEADER_FANOUT: usize = 8;
+
 /// [`ForwardAddressGetter`] provides helper methods for retrieving forwarding
 /// addresses for both vote and non-vote transactions.
 #[derive(Clone)]
@@ -100,15 +113,41 @@ impl ForwardAddressGetter {
         })
     }
 
-    /// Returns the TPU vote forwarding address of the next leader, if
-    /// available.
-    fn get_vote_forwarding_addresses(&self, max_count: u64) -> Vec<SocketAddr> {
-        next_leaders(&self.cluster_info, &self.poh_recorder, max_count, |node| {
-            node.tpu_vote(Protocol::UDP)
-        })
+    /// Returns the forwarding addresses of the next `num_leaders` distinct
+    /// leaders for non-vote transactions.
+    fn get_non_vote_forwarding_addresses_for_distinct_leaders(
+        &self,
+        num_leaders: usize,
+        protocol: Protocol,
+    ) -> Vec<SocketAddr> {
+        next_distinct_leaders(
+            &self.cluster_info,
+            &self.poh_recorder,
+            num_leaders,
+            lookahead_leaders(num_leaders),
+            |node| node.tpu_forwards(protocol),
+        )
+    }
+
+    /// Returns the TPU vote forwarding addresses of the next `num_leaders`
+    /// distinct leaders.
+    fn get_vote_forwarding_addresses(&self, num_leaders: usize) -> Vec<SocketAddr> {
+        next_distinct_leaders(
+            &self.cluster_info,
+            &self.poh_recorder,
+            num_leaders,
+            lookahead_leaders(num_leaders),
+            |node| node.tpu_vote(Protocol::UDP),
+        )
     }
 }
 
+/// Number of leader rotations to look ahead in order to find `num_leaders`
+/// distinct leaders with valid forwarding addresses.
+fn lookahead_leaders(num_leaders: usize) -> u64 {
+    NUM_LOOKAHEAD_LEADERS.saturating_add(num_leaders.saturating_sub(1) as u64)
+}
+
 pub(crate) fn spawn_forwarding_stage(
     receiver: Receiver<(BankingPacketBatch, bool)>,
     client: ForwardingClientOption<'_>,
@@ -116,18 +155,25 @@ pub(crate) fn spawn_forwarding_stage(
     root_bank_cache: RootBankCache,
     forward_address_getter: ForwardAddressGetter,
     data_budget: DataBudget,
+    leader_fanout: usize,
 ) -> JoinHandle<()> {
-    let vote_client = VoteClient::new(vote_client_udp_socket, forward_address_getter.clone());
+    let leader_fanout = leader_fanout.clamp(1, MAX_FORWARDING_LEADER_FANOUT);
+    let vote_client = VoteClient::new(
+        vote_client_udp_socket,
+        forward_address_getter.clone(),
+        leader_fanout,
+    );
     match client {
         ForwardingClientOption::ConnectionCache(connection_cache) => {
             let non_vote_client =
-                ConnectionCacheClient::new(connection_cache, forward_address_getter);
+                ConnectionCacheClient::new(connection_cache, forward_address_getter, leader_fanout);
             let forwarding_stage = ForwardingStage::new(
                 receiver,
                 vote_client,
                 non_vote_client.clone(),
                 root_bank_cache,
                 data_budget,
+                leader_fanout,
             );
             Builder::new()
                 .name("solFwdStage".to_string())
@@ -144,6 +190,7 @@ pub(crate) fn spawn_forwarding_stage(
                 forward_address_getter,
                 Some(stake_identity),
                 tpu_client_socket,
+                leader_fanout,
             );
             let forwarding_stage = ForwardingStage::new(
                 receiver,
@@ -151,6 +198,7 @@ pub(crate) fn spawn_forwarding_stage(
                 non_vote_client,
                 root_bank_cache,
                 data_budget,
+                leader_fanout,
             );
             Builder::new()
                 .name("solFwdStage".to_string())
@@ -167,6 +215,8 @@ struct ForwardingStage<VoteClient: ForwardingClient, NonVoteClient: ForwardingCl
     vote_client: VoteClient,
     non_vote_client: NonVoteClient,
     data_budget: DataBudget,
+    /// Number of distinct leaders each packet is forwarded to.
+    leader_fanout: usize,
     metrics: ForwardingStageMetrics,
 }
 
@@ -179,6 +229,7 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
         non_vote_client: NonVoteClient,
         root_bank_cache: RootBankCache,
         data_budget: DataBudget,
+        leader_fanout: usize,
     ) -> Self {
         Self {
             receiver,
@@ -187,6 +238,7 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
             non_vote_client,
             vote_client,
             data_budget,
+            leader_fanout,
             metrics: ForwardingStageMetrics::default(),
         }
     }
@@ -318,7 +370,8 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
 
     /// Forwards packets that have been buffered. This will loop through all
     /// packets. If the data budget is exceeded then remaining packets are
-    /// dropped.
+    /// dropped. Each packet is charged against the data budget once for every
+    /// leader it is forwarded to.
     fn forward_buffered_packets(&mut self) {
         self.metrics.did_something |= !self.packet_container.is_empty();
         self.refresh_data_budget();
@@ -329,7 +382,10 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
         // Loop through packets creating batches of packets to forward.
         while let Some(packet) = self.packet_container.pop_and_remove_max() {
             // If it exceeds our data-budget, drop.
-            if !self.data_budget.take(packet.meta().size) {
+            if !self
+                .data_budget
+                .take(packet.meta().size.saturating_mul(self.leader_fanout))
+            {
                 self.metrics.votes_dropped_on_data_budget +=
                     usize::from(packet.meta().is_simple_vote_tx());
                 self.metrics.non_votes_dropped_on_data_budget +=
@@ -361,6 +417,7 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
         // Send out remaining packets
         if !vote_batch.is_empty() {
             let num_votes = vote_batch.len();
+            record_forwarded_bytes(&vote_batch);
             self.metrics.votes_forwarded += num_votes;
             if self
                 .vote_client
@@ -372,6 +429,7 @@ impl<VoteClient: ForwardingClient, NonVoteClient: ForwardingClient>
         }
         if !non_vote_batch.is_empty() {
             let num_non_votes = non_vote_batch.len();
+            record_forwarded_bytes(&non_vote_batch);
             self.metrics.non_votes_forwarded += num_non_votes;
             if self
                 .non_vote_client
@@ -422,6 +480,13 @@ impl From<TransportError> for ForwardingClientError {
     }
 }
 
+// Accounts each transaction once, whatever the number of leaders it is
+// forwarded to
+fn record_forwarded_bytes(wire_transactions: &[Vec<u8>]) {
+    let num_bytes = wire_transactions.iter().map(Vec::len).sum();
+    bandwidth_accounting::record_egress(Subsystem::Tpu, wire_transactions.len(), num_bytes);
+}
+
 /// [`ForwardingClient`] trait defines a generic interface for clients that can
 /// forward transactions to other validators.
 trait ForwardingClient: Send + Sync + 'static {
@@ -436,21 +501,25 @@ trait ForwardingClient: Send + Sync + 'static {
 struct VoteClient {
     bind_socket: UdpSocket,
     forward_address_getter: ForwardAddressGetter,
+    leader_fanout: usize,
 }
 
 impl VoteClient {
-    fn new(bind_socket: UdpSocket, forward_address_getter: ForwardAddressGetter) -> Self {
+    fn new(
+        bind_socket: UdpSocket,
+        forward_address_getter: ForwardAddressGetter,
+        leader_fanout: usize,
+    ) -> Self {
         Self {
             bind_socket,
             forward_address_getter,
+            leader_fanout,
         }
     }
 
-    fn get_next_valid_leader(&self) -> Option<SocketAddr> {
-        let node_addresses = self
-            .forward_address_getter
-            .get_vote_forwarding_addresses(NUM_LOOKAHEAD_LEADERS);
-        node_addresses.first().copied()
+    fn get_next_valid_leaders(&self) -> Vec<SocketAddr> {
+        self.forward_address_getter
+            .get_vote_forwarding_addresses(self.leader_fanout)
     }
 }
 
@@ -459,12 +528,13 @@ impl ForwardingClient for VoteClient {
         &self,
         wire_transactions: Vec<Vec<u8>>,
     ) -> Result<(), ForwardingClientError> {
-        let Some(current_address) = self.get_next_valid_leader() else {
+        let addresses = self.get_next_valid_leaders();
+        if addresses.is_empty() {
             return Err(ForwardingClientError::LeaderContactMissing);
-        };
-        let batch_with_addresses = wire_transactions
+        }
+        let batch_with_addresses = addresses
             .iter()
-            .map(|bytes| (bytes, current_address));
+            .flat_map(|address| wire_transactions.iter().map(move |bytes| (bytes, *address)));
         batch_send(&self.bind_socket, batch_with_addresses)?;
         Ok(())
     }
@@ -474,26 +544,28 @@ impl ForwardingClient for VoteClient {
 struct ConnectionCacheClient {
     connection_cache: Arc<ConnectionCache>,
     forward_address_getter: ForwardAddressGetter,
+    leader_fanout: usize,
 }
 
 impl ConnectionCacheClient {
     fn new(
         connection_cache: Arc<ConnectionCache>,
         forward_address_getter: ForwardAddressGetter,
+        leader_fanout: usize,
     ) -> Self {
         Self {
             connection_cache,
             forward_address_getter,
+            leader_fanout,
         }
     }
-    fn get_next_valid_leader(&self) -> Option<SocketAddr> {
-        let node_addresses = self
-            .forward_address_getter
-            .get_non_vote_forwarding_addresses(
-                NUM_LOOKAHEAD_LEADERS,
+
+    fn get_next_valid_leaders(&self) -> Vec<SocketAddr> {
+        self.forward_address_getter
+            .get_non_vote_forwarding_addresses_for_distinct_leaders(
+                self.leader_fanout,
                 self.connection_cache.protocol(),
-            );
-        node_addresses.first().copied()
+            )
     }
 }
 
@@ -502,12 +574,25 @@ impl ForwardingClient for ConnectionCacheClient {
         &self,
         wire_transactions: Vec<Vec<u8>>,
     ) -> Result<(), ForwardingClientError> {
-        let Some(current_address) = self.get_next_valid_leader() else {
+        let addresses = self.get_next_valid_leaders();
+        let Some((last_address, other_addresses)) = addresses.split_last() else {
             return Err(ForwardingClientError::LeaderContactMissing);
         };
-        let conn = self.connection_cache.get_connection(&current_address);
-        conn.send_data_batch_async(wire_transactions)?;
-        Ok(())
+        // The batch is considered sent if it reached at least one leader.
+        let mut sent = false;
+        for address in other_addresses {
+            let conn = self.connection_cache.get_connection(address);
+            sent |= conn
+                .send_data_batch_async(wire_transactions.clone())
+                .is_ok();
+        }
+        let conn = self.connection_cache.get_connection(last_address);
+        sent |= conn.send_data_batch_async(wire_transactions).is_ok();
+        if sent {
+            Ok(())
+        } else {
+            Err(ForwardingClientError::Failed)
+        }
     }
 }
 
@@ -532,13 +617,14 @@ impl TpuClientNextClient {
         forward_address_getter: ForwardAddressGetter,
         stake_identity: Option<&Keypair>,
         bind_socket: UdpSocket,
+        leader_fanout: usize,
     ) -> Self {
         // For now use large channel, the more suitable size to be found later.
         let (sender, receiver) = mpsc::channel(128);
         let cancel = CancellationToken::new();
         let leader_updater = forward_address_getter.clone();
 
-        let config = Self::create_config(bind_socket, stake_identity);
+        let config = Self::create_config(bind_socket, stake_identity, leader_fanout);
         let scheduler: ConnectionWorkersScheduler =
             ConnectionWorkersScheduler::new(Box::new(leader_updater), receiver);
         // leaking handle to this task, as it will run until the cancel signal is received
@@ -554,6 +640,7 @@ impl TpuClientNextClient {
     fn create_config(
         bind_socket: UdpSocket,
         stake_identity: Option<&Keypair>,
+        leader_fanout: usize,
     ) -> ConnectionWorkersSchedulerConfig {
         ConnectionWorkersSchedulerConfig {
             bind: BindTarget::Socket(bind_socket),
@@ -564,11 +651,12 @@ impl TpuClientNextClient {
             skip_check_transaction_age: true,
             worker_channel_size: 2,
             max_reconnect_attempts: 4,
-            // Send to the next leader only, but verify that connections exist
-            // for the leaders of the next `4 * NUM_CONSECUTIVE_SLOTS`.
+            // Send to the next `leader_fanout` leaders, but verify that
+            // connections exist for the leaders of the next
+            // `4 * NUM_CONSECUTIVE_SLOTS`.
             leaders_fanout: Fanout {
-                send: 1,
-                connect: 4,
+                send: leader_fanout,
+                connect: leader_fanout.max(4),
             },
         }
     }
@@ -654,6 +742,7 @@ fn send_batch_if_full(
         let mut swap_batch = Vec::with_capacity(FORWARD_BATCH_SIZE);
         std::mem::swap(batch, &mut swap_batch);
 
+        record_forwarded_bytes(&swap_batch);
         if client.send_transactions_in_batch(swap_batch).is_err() {
             *dropped_counter += FORWARD_BATCH_SIZE;
         }
@@ -853,6 +942,17 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_lookahead_leaders() {
+        assert_eq!(lookahead_leaders(0), NUM_LOOKAHEAD_LEADERS);
+        assert_eq!(lookahead_leaders(1), NUM_LOOKAHEAD_LEADERS);
+        assert_eq!(lookahead_leaders(2), NUM_LOOKAHEAD_LEADERS + 1);
+        assert_eq!(
+            lookahead_leaders(MAX_FORWARDING_LEADER_FANOUT),
+            NUM_LOOKAHEAD_LEADERS + MAX_FORWARDING_LEADER_FANOUT as u64 - 1
+        );
+    }
+
     #[test]
     fn test_forwarding() {
         let (packet_batch_sender, packet_batch_receiver) = unbounded();
@@ -868,6 +968,7 @@ mod tests {
             non_vote_mock_client.clone(),
             root_bank_cache,
             DataBudget::default(),
+            DEFAULT_FORWARDING_LEADER_FANOUT,
         );
 
         // Send packet batches.
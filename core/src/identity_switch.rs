@@ -0,0 +1,464 @@
+//! Switch of the validator identity at runtime, for `set-identity`.
+//!
+//! The staged switch validates the new identity before switching to it, the
+//! way replay will use it: the vote account must be linked to the identity,
+//! its authorized voter for the current epoch must be available, and the
+//! saved tower of the identity must load and hold the latest vote of the vote
+//! account, as a tower missing votes may lead to lockout violations.
+//!
+//! Gossip is then watched for another node advertising the identity, e.g.
+//! the validator the identity is taken over from, which is still running.
+//! The switch is aborted if such a node refreshes its contact info during the
+//! observation period, and rolled back if it does so within the observation
+//! period following the switch.
+
+use {
+    crate::consensus::{tower_storage::TowerStorage, Tower, TowerError},
+    solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfo},
+    solana_runtime::bank_forks::BankForks,
+    solana_sdk::{
+        clock::{Epoch, Slot},
+        pubkey::Pubkey,
+        quic::NotifyKeyUpdate,
+        signature::{Keypair, Signer},
+    },
+    std::{
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+        thread,
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+/// Longer than the interval at which a node refreshes its contact info
+pub const DEFAULT_IDENTITY_OBSERVATION_MS: u64 = 10_000;
+const OBSERVATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Error, Debug)]
+pub enum StagedIdentityError {
+    #[error("vote account {0} not found")]
+    VoteAccountNotFound(Pubkey),
+
+    #[error("vote account {vote_account} is linked to the identity {node_pubkey}")]
+    VoteAccountLinkage {
+        vote_account: Pubkey,
+        node_pubkey: Pubkey,
+    },
+
+    #[error("vote account {vote_account} has no authorized voter for epoch {epoch}")]
+    NoAuthorizedVoter { vote_account: Pubkey, epoch: Epoch },
+
+    #[error("the authorized voter {0} is not an authorized voter keypair of the validator")]
+    AuthorizedVoterMissing(Pubkey),
+
+    #[error("unable to load the tower: {0}")]
+    Tower(#[from] TowerError),
+
+    #[error(
+        "the tower voted slot {tower_slot:?} but the vote account voted slot {vote_account_slot}"
+    )]
+    StaleTower {
+        tower_slot: Option<Slot>,
+        vote_account_slot: Slot,
+    },
+
+    #[error("the tower voted slot {tower_slot:?}, more than {max_lag} slots before root {root}")]
+    TowerTooOld {
+        tower_slot: Option<Slot>,
+        root: Slot,
+        max_lag: u64,
+    },
+
+    #[error("another node advertises the identity from {0:?}")]
+    ConflictingNode(Option<SocketAddr>),
+
+    #[error(
+        "rolled back to {previous_identity}, another node advertises the identity from {gossip:?}"
+    )]
+    RolledBack {
+        previous_identity: Pubkey,
+        gossip: Option<SocketAddr>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct StagedIdentityConfig {
+    /// Refuse a tower whose last vote is more slots before the root
+    pub max_tower_lag_slots: Option<u64>,
+    /// How long gossip is watched before and after the switch
+    pub observation_period: Duration,
+}
+
+impl Default for StagedIdentityConfig {
+    fn default() -> Self {
+        Self {
+            max_tower_lag_slots: None,
+            observation_period: Duration::from_millis(DEFAULT_IDENTITY_OBSERVATION_MS),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StagedIdentityReport {
+    pub previous_identity: Pubkey,
+    pub identity: Pubkey,
+    pub authorized_voter: Pubkey,
+    pub root_slot: Slot,
+    pub tower_last_voted_slot: Option<Slot>,
+    pub vote_account_last_voted_slot: Option<Slot>,
+}
+
+/// Sets the identity of the validator, without any validation
+pub fn set_identity(
+    cluster_info: &ClusterInfo,
+    notifies: &[Arc<dyn NotifyKeyUpdate + Sync + Send>],
+    identity_keypair: Arc<Keypair>,
+) {
+    for notify in notifies {
+        if let Err(err) = notify.update_key(&identity_keypair) {
+            error!("Error updating network layer keypair: {err}");
+        }
+    }
+    solana_metrics::set_host_id(identity_keypair.pubkey().to_string());
+    cluster_info.set_keypair(identity_keypair);
+    warn!("Identity set to {}", cluster_info.id());
+}
+
+/// The state a new identity is validated against, and the services whose
+/// identity is switched
+pub struct StagedIdentityContext<'a> {
+    pub cluster_info: &'a ClusterInfo,
+    pub notifies: &'a [Arc<dyn NotifyKeyUpdate + Sync + Send>],
+    pub bank_forks: &'a RwLock<BankForks>,
+    pub vote_account: &'a Pubkey,
+    pub authorized_voter_keypairs: &'a [Arc<Keypair>],
+    pub tower_storage: &'a dyn TowerStorage,
+}
+
+impl StagedIdentityContext<'_> {
+    /// Validates that replay can vote with `identity` and its saved tower
+    pub fn validate(
+        &self,
+        identity: &Pubkey,
+        max_tower_lag_slots: Option<u64>,
+    ) -> Result<StagedIdentityReport, StagedIdentityError> {
+        let (root_bank, working_bank) = {
+            let bank_forks = self.bank_forks.read().unwrap();
+            (bank_forks.root_bank(), bank_forks.working_bank())
+        };
+
+        let vote_account = working_bank
+            .get_vote_account(self.vote_account)
+            .ok_or(StagedIdentityError::VoteAccountNotFound(*self.vote_account))?;
+        let vote_state_view = vote_account.vote_state_view();
+        if vote_state_view.node_pubkey() != identity {
+            return Err(StagedIdentityError::VoteAccountLinkage {
+                vote_account: *self.vote_account,
+                node_pubkey: *vote_state_view.node_pubkey(),
+            });
+        }
+        let authorized_voter = *vote_state_view
+            .get_authorized_voter(working_bank.epoch())
+            .ok_or(StagedIdentityError::NoAuthorizedVoter {
+                vote_account: *self.vote_account,
+                epoch: working_bank.epoch(),
+            })?;
+        if !self
+            .authorized_voter_keypairs
+            .iter()
+            .any(|keypair| keypair.pubkey() == authorized_voter)
+        {
+            return Err(StagedIdentityError::AuthorizedVoterMissing(
+                authorized_voter,
+            ));
+        }
+
+        // Load the tower as replay does, but refuse to create a new one.
+        let tower = Tower::restore(self.tower_storage, identity)?
+            .adjust_lockouts_after_replay(root_bank.slot(), &root_bank.get_slot_history())?;
+        let tower_slot = tower.last_voted_slot();
+        let vote_account_slot = vote_state_view.last_voted_slot();
+        if let Some(vote_account_slot) = vote_account_slot {
+            if tower_slot < Some(vote_account_slot) {
+                return Err(StagedIdentityError::StaleTower {
+                    tower_slot,
+                    vote_account_slot,
+                });
+            }
+        }
+        if let Some(max_lag) = max_tower_lag_slots {
+            if root_bank
+                .slot()
+                .saturating_sub(tower_slot.unwrap_or_default())
+                > max_lag
+            {
+                return Err(StagedIdentityError::TowerTooOld {
+                    tower_slot,
+                    root: root_bank.slot(),
+                    max_lag,
+                });
+            }
+        }
+
+        Ok(StagedIdentityReport {
+            previous_identity: self.cluster_info.id(),
+            identity: *identity,
+            authorized_voter,
+            root_slot: root_bank.slot(),
+            tower_last_voted_slot: tower_slot,
+            vote_account_last_voted_slot: vote_account_slot,
+        })
+    }
+
+    /// Validates `identity_keypair`, then sets it as the identity of the
+    /// validator unless another node advertises it during the observation
+    /// period. Returns to the previous identity if another node advertises
+    /// it during the observation period following the switch.
+    pub fn set_identity_staged(
+        &self,
+        identity_keypair: Arc<Keypair>,
+        config: &StagedIdentityConfig,
+    ) -> Result<StagedIdentityReport, StagedIdentityError> {
+        let identity = identity_keypair.pubkey();
+        let report = self.validate(&identity, config.max_tower_lag_slots)?;
+        let my_gossip = self.cluster_info.my_contact_info().gossip();
+        let baseline = self.conflicting_wallclock(&identity, my_gossip);
+
+        self.observe_gossip(&identity, my_gossip, baseline, config.observation_period)
+            .map_err(StagedIdentityError::ConflictingNode)?;
+        // The tower may have been saved again by another node meanwhile.
+        let report = StagedIdentityReport {
+            previous_identity: report.previous_identity,
+            ..self.validate(&identity, config.max_tower_lag_slots)?
+        };
+
+        let previous_keypair = self.cluster_info.keypair().clone();
+        set_identity(self.cluster_info, self.notifies, identity_keypair);
+        if let Err(gossip) =
+            self.observe_gossip(&identity, my_gossip, baseline, config.observation_period)
+        {
+            error!("Another node advertises the identity {identity} from {gossip:?}, rolling back");
+            set_identity(self.cluster_info, self.notifies, previous_keypair);
+            return Err(StagedIdentityError::RolledBack {
+                previous_identity: report.previous_identity,
+                gossip,
+            });
+        }
+        Ok(report)
+    }
+
+    // Returns the wallclock of the contact info of `identity` in gossip if
+    // it was advertised by another node.
+    fn conflicting_wallclock(
+        &self,
+        identity: &Pubkey,
+        my_gossip: Option<SocketAddr>,
+    ) -> Option<(Option<SocketAddr>, u64)> {
+        self.cluster_info
+            .lookup_contact_info(identity, |node: &ContactInfo| {
+                (node.gossip(), node.wallclock())
+            })
+            .filter(|(gossip, _)| *gossip != my_gossip)
+    }
+
+    // Returns the gossip address of another node refreshing the contact info
+    // of `identity` past the one of `baseline` within `period`.
+    fn observe_gossip(
+        &self,
+        identity: &Pubkey,
+        my_gossip: Option<SocketAddr>,
+        baseline: Option<(Option<SocketAddr>, u64)>,
+        period: Duration,
+    ) -> Result<(), Option<SocketAddr>> {
+        let baseline_wallclock = baseline.map(|(_, wallclock)| wallclock);
+        let start = Instant::now();
+        loop {
+            if let Some((gossip, wallclock)) = self.conflicting_wallclock(identity, my_gossip) {
+                if Some(wallclock) > baseline_wallclock {
+                    return Err(gossip);
+                }
+            }
+            if start.elapsed() >= period {
+                return Ok(());
+            }
+            thread::sleep(OBSERVATION_POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::consensus::tower_storage::FileTowerStorage,
+        solana_runtime::{
+            bank::Bank,
+            genesis_utils::{create_genesis_config_with_vote_accounts, ValidatorVoteKeypairs},
+        },
+        solana_sdk::{hash::Hash, timing::timestamp},
+        solana_streamer::socket::SocketAddrSpace,
+        tempfile::TempDir,
+    };
+
+    struct TestContext {
+        cluster_info: ClusterInfo,
+        bank_forks: Arc<RwLock<BankForks>>,
+        vote_keypairs: ValidatorVoteKeypairs,
+        vote_account: Pubkey,
+        authorized_voter_keypairs: Vec<Arc<Keypair>>,
+        tower_storage: FileTowerStorage,
+        _tower_path: TempDir,
+    }
+
+    impl TestContext {
+        fn new() -> Self {
+            let vote_keypairs = ValidatorVoteKeypairs::new_rand();
+            let genesis_config_info = create_genesis_config_with_vote_accounts(
+                1_000_000_000,
+                &[&vote_keypairs],
+                vec![100],
+            );
+            let bank = Bank::new_for_tests(&genesis_config_info.genesis_config);
+            // Freezing the bank records its slot in the slot history.
+            bank.freeze();
+            let keypair = Arc::new(Keypair::new());
+            let tower_path = TempDir::new().unwrap();
+            Self {
+                cluster_info: ClusterInfo::new(
+                    ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+                    keypair,
+                    SocketAddrSpace::Unspecified,
+                ),
+                bank_forks: BankForks::new_rw_arc(bank),
+                vote_account: vote_keypairs.vote_keypair.pubkey(),
+                authorized_voter_keypairs: vec![Arc::new(
+                    vote_keypairs.vote_keypair.insecure_clone(),
+                )],
+                vote_keypairs,
+                tower_storage: FileTowerStorage::new(tower_path.path().to_path_buf()),
+                _tower_path: tower_path,
+            }
+        }
+
+        fn context(&self) -> StagedIdentityContext {
+            StagedIdentityContext {
+                cluster_info: &self.cluster_info,
+                notifies: &[],
+                bank_forks: &self.bank_forks,
+                vote_account: &self.vote_account,
+                authorized_voter_keypairs: &self.authorized_voter_keypairs,
+                tower_storage: &self.tower_storage,
+            }
+        }
+
+        fn identity(&self) -> Arc<Keypair> {
+            Arc::new(self.vote_keypairs.node_keypair.insecure_clone())
+        }
+
+        fn save_tower(&self, slots: &[Slot]) {
+            let mut tower = Tower {
+                node_pubkey: self.vote_keypairs.node_keypair.pubkey(),
+                ..Tower::default()
+            };
+            for slot in slots {
+                tower.record_vote(*slot, Hash::new_unique());
+            }
+            tower
+                .save(&self.tower_storage, &self.vote_keypairs.node_keypair)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let test_context = TestContext::new();
+        let context = test_context.context();
+        let identity = test_context.identity().pubkey();
+        let authorized_voter = test_context.vote_account;
+
+        let other = Pubkey::new_unique();
+        assert!(matches!(
+            context.validate(&other, None),
+            Err(StagedIdentityError::VoteAccountLinkage { node_pubkey, .. })
+                if node_pubkey == identity
+        ));
+        let context = StagedIdentityContext {
+            authorized_voter_keypairs: &[],
+            ..test_context.context()
+        };
+        assert!(matches!(
+            context.validate(&identity, None),
+            Err(StagedIdentityError::AuthorizedVoterMissing(voter)) if voter == authorized_voter
+        ));
+
+        // The tower is required.
+        let context = test_context.context();
+        assert!(matches!(
+            context.validate(&identity, None),
+            Err(StagedIdentityError::Tower(err)) if err.is_file_missing()
+        ));
+        test_context.save_tower(&[1, 2, 3]);
+        let report = context.validate(&identity, None).unwrap();
+        assert_eq!(
+            report,
+            StagedIdentityReport {
+                previous_identity: test_context.cluster_info.id(),
+                identity,
+                authorized_voter,
+                root_slot: 0,
+                tower_last_voted_slot: Some(3),
+                vote_account_last_voted_slot: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_identity_staged() {
+        let test_context = TestContext::new();
+        let context = test_context.context();
+        let identity = test_context.identity();
+        test_context.save_tower(&[1]);
+        let config = StagedIdentityConfig {
+            max_tower_lag_slots: None,
+            observation_period: Duration::from_millis(200),
+        };
+        let report = context
+            .set_identity_staged(identity.clone(), &config)
+            .unwrap();
+        assert_eq!(report.identity, identity.pubkey());
+        assert_eq!(test_context.cluster_info.id(), identity.pubkey());
+    }
+
+    #[test]
+    fn test_set_identity_staged_conflicting_node() {
+        let test_context = TestContext::new();
+        let previous_identity = test_context.cluster_info.id();
+        let identity = test_context.identity();
+        test_context.save_tower(&[1]);
+        let mut node = ContactInfo::new_localhost(&identity.pubkey(), timestamp());
+        node.set_gossip(([127, 0, 0, 2], 8000)).unwrap();
+        test_context.cluster_info.insert_info(node.clone());
+
+        // The other node refreshes its contact info while it is observed.
+        let config = StagedIdentityConfig {
+            max_tower_lag_slots: None,
+            observation_period: Duration::from_millis(1_000),
+        };
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(200));
+                node.set_wallclock(timestamp() + 1);
+                test_context.cluster_info.insert_info(node);
+            });
+            assert!(matches!(
+                test_context
+                    .context()
+                    .set_identity_staged(identity.clone(), &config),
+                Err(StagedIdentityError::ConflictingNode(Some(gossip)))
+                    if gossip == "127.0.0.2:8000".parse().unwrap()
+            ));
+        });
+        assert_eq!(test_context.cluster_info.id(), previous_identity);
+    }
+}
@@ -0,0 +1,218 @@
+//! Pause of the block production of the validator, for maintenance windows,
+//! through the admin RPC `pauseLeader` and `resumeLeader`.
+//!
+//! While paused, replay does not start the blocks of the upcoming leader
+//! slots of the validator, which keeps replaying and voting. A block started
+//! before the pause completes. The TPU and TPU forwards addresses are
+//! withdrawn from the contact info of the validator in gossip, so that the
+//! cluster stops sending it transactions, and advertised again on resume.
+
+use {
+    solana_gossip::{cluster_info::ClusterInfo, contact_info::Protocol},
+    solana_sdk::clock::Slot,
+    std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Instant,
+    },
+    thiserror::Error,
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LeaderPauseError {
+    #[error("block production is already paused")]
+    AlreadyPaused,
+
+    #[error("block production is not paused")]
+    NotPaused,
+}
+
+struct PausedState {
+    since: Instant,
+    /// The addresses advertised before the pause
+    tpu: Option<SocketAddr>,
+    tpu_forwards: Option<SocketAddr>,
+    num_skipped_slots: u64,
+    last_skipped_slot: Option<Slot>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LeaderPauseStatus {
+    pub is_paused: bool,
+    /// The following are `None` unless paused
+    pub paused_ms: Option<u64>,
+    pub num_skipped_slots: Option<u64>,
+    pub last_skipped_slot: Option<Slot>,
+}
+
+pub struct LeaderPause {
+    cluster_info: Arc<ClusterInfo>,
+    is_paused: AtomicBool,
+    /// `None` unless paused
+    state: Mutex<Option<PausedState>>,
+}
+
+impl LeaderPause {
+    pub fn new(cluster_info: Arc<ClusterInfo>) -> Self {
+        Self {
+            cluster_info,
+            is_paused: AtomicBool::default(),
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::Relaxed)
+    }
+
+    pub fn pause(&self) -> Result<(), LeaderPauseError> {
+        let mut state = self.state.lock().unwrap();
+        if state.is_some() {
+            return Err(LeaderPauseError::AlreadyPaused);
+        }
+        let contact_info = self.cluster_info.my_contact_info();
+        *state = Some(PausedState {
+            since: Instant::now(),
+            tpu: contact_info.tpu(Protocol::UDP),
+            tpu_forwards: contact_info.tpu_forwards(Protocol::UDP),
+            num_skipped_slots: 0,
+            last_skipped_slot: None,
+        });
+        self.is_paused.store(true, Ordering::Relaxed);
+        self.cluster_info.remove_tpu();
+        self.cluster_info.remove_tpu_forwards();
+        warn!("Block production paused");
+        datapoint_info!("leader_pause", ("paused", true, bool));
+        Ok(())
+    }
+
+    /// Returns the status of the pause which ended
+    pub fn resume(&self) -> Result<LeaderPauseStatus, LeaderPauseError> {
+        let mut state = self.state.lock().unwrap();
+        let status = Self::paused_status(state.as_ref());
+        let Some(paused) = state.take() else {
+            return Err(LeaderPauseError::NotPaused);
+        };
+        self.is_paused.store(false, Ordering::Relaxed);
+        if let Some(tpu) = paused.tpu {
+            if let Err(err) = self.cluster_info.set_tpu(tpu) {
+                error!("Unable to advertise the TPU address {tpu} again: {err}");
+            }
+        }
+        if let Some(tpu_forwards) = paused.tpu_forwards {
+            if let Err(err) = self.cluster_info.set_tpu_forwards(tpu_forwards) {
+                error!("Unable to advertise the TPU forwards address {tpu_forwards} again: {err}");
+            }
+        }
+        warn!(
+            "Block production resumed after {}ms, {} leader slots skipped",
+            status.paused_ms.unwrap_or_default(),
+            paused.num_skipped_slots
+        );
+        datapoint_info!(
+            "leader_pause",
+            ("paused", false, bool),
+            ("paused_ms", status.paused_ms, Option<i64>),
+            ("num_skipped_slots", paused.num_skipped_slots, i64),
+        );
+        Ok(status)
+    }
+
+    /// Records that replay did not start the leader slot `slot` as block
+    /// production is paused
+    pub fn record_skipped_slot(&self, slot: Slot) {
+        let mut state = self.state.lock().unwrap();
+        let Some(paused) = state.as_mut() else {
+            return;
+        };
+        if paused.last_skipped_slot >= Some(slot) {
+            return;
+        }
+        paused.last_skipped_slot = Some(slot);
+        paused.num_skipped_slots += 1;
+        info!("Block production is paused, skipping leader slot {slot}");
+        datapoint_info!("replay_stage-paused_leader_slot", ("slot", slot, i64));
+    }
+
+    pub fn status(&self) -> LeaderPauseStatus {
+        Self::paused_status(self.state.lock().unwrap().as_ref())
+    }
+
+    fn paused_status(paused: Option<&PausedState>) -> LeaderPauseStatus {
+        let Some(paused) = paused else {
+            return LeaderPauseStatus::default();
+        };
+        LeaderPauseStatus {
+            is_paused: true,
+            paused_ms: Some(paused.since.elapsed().as_millis() as u64),
+            num_skipped_slots: Some(paused.num_skipped_slots),
+            last_skipped_slot: paused.last_skipped_slot,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_gossip::contact_info::ContactInfo,
+        solana_sdk::{
+            signature::{Keypair, Signer},
+            timing::timestamp,
+        },
+        solana_streamer::socket::SocketAddrSpace,
+    };
+
+    // The TPU addresses advertised by `contact_info`
+    fn tpu_addresses(contact_info: &ContactInfo) -> [Option<SocketAddr>; 4] {
+        [
+            contact_info.tpu(Protocol::UDP),
+            contact_info.tpu(Protocol::QUIC),
+            contact_info.tpu_forwards(Protocol::UDP),
+            contact_info.tpu_forwards(Protocol::QUIC),
+        ]
+    }
+
+    #[test]
+    fn test_leader_pause() {
+        let keypair = Arc::new(Keypair::new());
+        let cluster_info = Arc::new(ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair,
+            SocketAddrSpace::Unspecified,
+        ));
+        let advertised = tpu_addresses(&cluster_info.my_contact_info());
+        assert!(advertised.iter().all(Option::is_some));
+        let leader_pause = LeaderPause::new(cluster_info.clone());
+        assert!(!leader_pause.is_paused());
+        assert_eq!(leader_pause.resume(), Err(LeaderPauseError::NotPaused));
+
+        leader_pause.pause().unwrap();
+        assert!(leader_pause.is_paused());
+        assert_eq!(leader_pause.pause(), Err(LeaderPauseError::AlreadyPaused));
+        assert_eq!(
+            tpu_addresses(&cluster_info.my_contact_info()),
+            [None, None, None, None]
+        );
+        leader_pause.record_skipped_slot(4);
+        // Replay may check the same slot more than once.
+        leader_pause.record_skipped_slot(4);
+        leader_pause.record_skipped_slot(5);
+        let status = leader_pause.status();
+        assert!(status.is_paused);
+        assert_eq!(status.num_skipped_slots, Some(2));
+        assert_eq!(status.last_skipped_slot, Some(5));
+
+        let status = leader_pause.resume().unwrap();
+        assert_eq!(status.num_skipped_slots, Some(2));
+        assert!(!leader_pause.is_paused());
+        assert_eq!(leader_pause.status(), LeaderPauseStatus::default());
+        assert_eq!(tpu_addresses(&cluster_info.my_contact_info()), advertised);
+        // Slots are no longer recorded once resumed.
+        leader_pause.record_skipped_slot(6);
+        assert_eq!(leader_pause.status().num_skipped_slots, None);
+    }
+}
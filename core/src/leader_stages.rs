@@ -0,0 +1,151 @@
+//! The stages which ingest transactions, produce blocks and broadcast them
+//! are only needed by nodes which can become leader. They are constructed
+//! behind the `LeaderStages` trait so that nodes built with the `rpc-only`
+//! feature can replace them with `RpcOnlyStages`, leaving the TPU, banking
+//! and broadcast stages out of the binary.
+
+use {
+    crate::{
+        banking_stage::BankingStageHandle,
+        banking_trace::{Channels, TracerThread},
+        cluster_info_vote_listener::{
+            ClusterInfoVoteListener, DuplicateConfirmedSlotsSender, GossipVerifiedVoteHashSender,
+            VerifiedVoteSender, VoteTracker,
+        },
+        tpu::Tpu,
+    },
+    crossbeam_channel::Receiver,
+    solana_geyser_plugin_manager::vote_notifier_interface::VoteNotifierArc,
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_ledger::blockstore::Blockstore,
+    solana_poh::poh_recorder::WorkingBankEntry,
+    solana_rpc::{
+        optimistically_confirmed_bank_tracker::BankNotificationSender,
+        rpc_subscriptions::RpcSubscriptions,
+    },
+    solana_runtime::{bank_forks::BankForks, vote_sender_types::ReplayVoteReceiver},
+    solana_sdk::clock::Slot,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const DRAIN_INTERVAL: Duration = Duration::from_millis(100);
+
+pub trait LeaderStages: Send {
+    /// Returns the handle to the banking stage, if the node runs one.
+    fn banking_stage_handle(&self) -> Option<BankingStageHandle>;
+
+    fn join(self: Box<Self>) -> thread::Result<()>;
+}
+
+impl LeaderStages for Tpu {
+    fn banking_stage_handle(&self) -> Option<BankingStageHandle> {
+        Some(Tpu::banking_stage_handle(self))
+    }
+
+    fn join(self: Box<Self>) -> thread::Result<()> {
+        Tpu::join(*self)
+    }
+}
+
+/// Stands in for the TPU on nodes which never produce blocks nor vote.
+///
+/// Replay still needs the votes observed in gossip to track optimistic
+/// confirmation, so the cluster-info vote listener keeps running. Everything
+/// which the TPU would otherwise consume, i.e. PoH entries, slots to
+/// retransmit and gossip votes for the banking stage, is dropped.
+pub struct RpcOnlyStages {
+    cluster_info_vote_listener: ClusterInfoVoteListener,
+    drain_thread: JoinHandle<()>,
+    tracer_thread_hdl: TracerThread,
+}
+
+impl RpcOnlyStages {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        cluster_info: &Arc<ClusterInfo>,
+        entry_receiver: Receiver<WorkingBankEntry>,
+        retransmit_slots_receiver: Receiver<Slot>,
+        subscriptions: &Arc<RpcSubscriptions>,
+        blockstore: Arc<Blockstore>,
+        exit: Arc<AtomicBool>,
+        vote_tracker: Arc<VoteTracker>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        verified_vote_sender: VerifiedVoteSender,
+        gossip_verified_vote_hash_sender: GossipVerifiedVoteHashSender,
+        replay_vote_receiver: ReplayVoteReceiver,
+        bank_notification_sender: Option<BankNotificationSender>,
+        duplicate_confirmed_slot_sender: DuplicateConfirmedSlotsSender,
+        banking_tracer_channels: Channels,
+        tracer_thread_hdl: TracerThread,
+        vote_notifier: Option<VoteNotifierArc>,
+    ) -> Self {
+        let Channels {
+            gossip_vote_sender,
+            gossip_vote_receiver,
+            ..
+        } = banking_tracer_channels;
+
+        let cluster_info_vote_listener = ClusterInfoVoteListener::new(
+            exit.clone(),
+            cluster_info.clone(),
+            gossip_vote_sender,
+            vote_tracker,
+            bank_forks,
+            subscriptions.clone(),
+            verified_vote_sender,
+            gossip_verified_vote_hash_sender,
+            replay_vote_receiver,
+            blockstore,
+            bank_notification_sender,
+            duplicate_confirmed_slot_sender,
+            vote_notifier,
+        );
+
+        // The senders give up once the receivers are dropped, so the
+        // receivers are kept alive and drained instead.
+        let drain_thread = Builder::new()
+            .name("solRpcOnlyDrain".to_string())
+            .spawn(move || {
+                while !exit.load(Ordering::Relaxed) {
+                    entry_receiver.try_iter().for_each(drop);
+                    retransmit_slots_receiver.try_iter().for_each(drop);
+                    gossip_vote_receiver.try_iter().for_each(drop);
+                    thread::sleep(DRAIN_INTERVAL);
+                }
+            })
+            .unwrap();
+
+        Self {
+            cluster_info_vote_listener,
+            drain_thread,
+            tracer_thread_hdl,
+        }
+    }
+}
+
+impl LeaderStages for RpcOnlyStages {
+    fn banking_stage_handle(&self) -> Option<BankingStageHandle> {
+        None
+    }
+
+    fn join(self: Box<Self>) -> thread::Result<()> {
+        self.cluster_info_vote_listener.join()?;
+        self.drain_thread.join()?;
+        if let Some(tracer_thread_hdl) = self.tracer_thread_hdl {
+            if let Err(tracer_result) = tracer_thread_hdl.join()? {
+                error!(
+                    "banking tracer thread returned error after successful thread join: {:?}",
+                    tracer_result
+                );
+            }
+        }
+        Ok(())
+    }
+}
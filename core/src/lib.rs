@@ -8,11 +8,16 @@
 //! command-line tools to spin up validators and a Rust library
 //!
 
+pub mod account_growth_service;
 pub mod accounts_hash_verifier;
 pub mod admin_rpc_post_init;
+pub mod bandwidth_accounting_service;
+pub mod bank_hash_breakdowns;
 pub mod banking_simulation;
 pub mod banking_stage;
 pub mod banking_trace;
+pub mod blockstore_compaction_service;
+pub mod clock_drift_service;
 pub mod cluster_info_vote_listener;
 pub mod cluster_slots_service;
 pub mod commitment_service;
@@ -20,11 +25,21 @@ pub mod completed_data_sets_service;
 pub mod consensus;
 pub mod cost_update_service;
 pub mod drop_bank_service;
+pub mod epoch_accounts_hash_scheduling;
+pub mod exit_reason;
+pub mod failover;
 pub mod fetch_stage;
+pub mod forensic_bundles;
 pub mod forwarding_stage;
 pub mod gen_keys;
+pub mod identity_switch;
+pub mod leader_pause;
+pub mod leader_stages;
 pub mod next_leader;
+pub mod node_update_service;
 pub mod optimistic_confirmation_verifier;
+pub mod pipeline_tracing;
+pub mod program_precompilation;
 pub mod repair;
 pub mod replay_stage;
 mod result;
@@ -61,3 +61,36 @@ pub(crate) fn next_leaders(
         })
         .collect()
 }
+
+/// Returns the addresses of up to `max_leaders` distinct upcoming leaders,
+/// looking ahead at most `lookahead_leaders` leader rotations. A leader that is
+/// scheduled for several of the upcoming rotations is only returned once, and
+/// leaders without a valid `port_selector` address are skipped.
+pub(crate) fn next_distinct_leaders(
+    cluster_info: &impl LikeClusterInfo,
+    poh_recorder: &RwLock<PohRecorder>,
+    max_leaders: usize,
+    lookahead_leaders: u64,
+    port_selector: impl ContactInfoQuery<Option<SocketAddr>>,
+) -> Vec<SocketAddr> {
+    let recorder = poh_recorder.read().unwrap();
+    let leader_pubkeys: Vec<_> = (0..lookahead_leaders)
+        .filter_map(|i| {
+            recorder.leader_after_n_slots(
+                FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET + i * NUM_CONSECUTIVE_LEADER_SLOTS,
+            )
+        })
+        .collect();
+    drop(recorder);
+
+    leader_pubkeys
+        .into_iter()
+        .unique()
+        .filter_map(|leader_pubkey| {
+            cluster_info.lookup_contact_info(&leader_pubkey, &port_selector)?
+        })
+        // leaders could potentially share the same socket
+        .unique()
+        .take(max_leaders)
+        .collect()
+}
@@ -0,0 +1,189 @@
+//! Notifies Geyser plugins of changes to the cluster nodes observed in
+//! gossip, so that indexers need not poll `getClusterNodes`.
+//!
+//! The contact infos in gossip are compared with the previously notified ones
+//! every NODE_UPDATE_INTERVAL. All nodes are notified once at startup.
+
+use {
+    solana_geyser_plugin_manager::node_update_notifier_interface::{
+        NodeStatus, NodeUpdateNotifier, NodeUpdateNotifierArc,
+    },
+    solana_gossip::{
+        cluster_info::ClusterInfo,
+        contact_info::{ContactInfo, Protocol},
+    },
+    solana_sdk::{pubkey::Pubkey, timing::timestamp},
+    std::{
+        collections::{hash_map::Entry, HashMap, HashSet},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const NODE_UPDATE_INTERVAL: Duration = Duration::from_secs(2);
+/// Nodes which have not refreshed their contact info for this long are
+/// notified as delinquent.
+const NODE_DELINQUENT_TIMEOUT_MS: u64 = 60_000;
+
+pub struct NodeUpdateService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl NodeUpdateService {
+    pub fn new(
+        cluster_info: Arc<ClusterInfo>,
+        node_update_notifier: NodeUpdateNotifierArc,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solNodeUpdate".to_string())
+            .spawn(move || {
+                info!("NodeUpdateService has started");
+                let mut nodes = HashMap::new();
+                while !exit.load(Ordering::Relaxed) {
+                    notify_node_updates(
+                        &mut nodes,
+                        cluster_info.all_peers(),
+                        timestamp(),
+                        node_update_notifier.as_ref(),
+                    );
+                    sleep(NODE_UPDATE_INTERVAL);
+                }
+                info!("NodeUpdateService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+// The fields of the contact info which are notified, other than the
+// wallclock which changes every time the node refreshes its contact info.
+fn notified_fields(node: &ContactInfo) -> impl PartialEq + '_ {
+    let sockets = [
+        node.gossip(),
+        node.tvu(Protocol::UDP),
+        node.tpu(Protocol::UDP),
+        node.tpu(Protocol::QUIC),
+        node.tpu_forwards(Protocol::UDP),
+        node.tpu_forwards(Protocol::QUIC),
+        node.tpu_vote(Protocol::UDP),
+        node.serve_repair(Protocol::UDP),
+        node.rpc(),
+        node.rpc_pubsub(),
+    ];
+    (node.shred_version(), node.version(), sockets)
+}
+
+/// Notifies the nodes which are new or changed since the previous call, given
+/// the contact infos in gossip along with the local time at which they were
+/// last updated.
+fn notify_node_updates(
+    nodes: &mut HashMap<Pubkey, (ContactInfo, NodeStatus)>,
+    peers: Vec<(ContactInfo, /*local_timestamp:*/ u64)>,
+    now: u64,
+    node_update_notifier: &(dyn NodeUpdateNotifier + Sync + Send),
+) {
+    let mut removed: HashSet<Pubkey> = nodes.keys().copied().collect();
+    for (node, local_timestamp) in peers {
+        removed.remove(node.pubkey());
+        let status = if now.saturating_sub(local_timestamp) > NODE_DELINQUENT_TIMEOUT_MS {
+            NodeStatus::Delinquent
+        } else {
+            NodeStatus::Active
+        };
+        match nodes.entry(*node.pubkey()) {
+            Entry::Vacant(entry) => {
+                node_update_notifier.notify_node_update(&node, status);
+                entry.insert((node, status));
+            }
+            Entry::Occupied(mut entry) => {
+                let (prev_node, prev_status) = entry.get();
+                if *prev_status != status || notified_fields(prev_node) != notified_fields(&node) {
+                    node_update_notifier.notify_node_update(&node, status);
+                }
+                entry.insert((node, status));
+            }
+        }
+    }
+    for pubkey in removed {
+        if let Some((node, _)) = nodes.remove(&pubkey) {
+            node_update_notifier.notify_node_update(&node, NodeStatus::Removed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::Mutex};
+
+    #[derive(Default)]
+    struct TestNotifier(Mutex<Vec<(Pubkey, NodeStatus)>>);
+
+    impl NodeUpdateNotifier for TestNotifier {
+        fn notify_node_update(&self, node: &ContactInfo, status: NodeStatus) {
+            self.0.lock().unwrap().push((*node.pubkey(), status));
+        }
+    }
+
+    impl TestNotifier {
+        fn take(&self) -> Vec<(Pubkey, NodeStatus)> {
+            let mut updates = std::mem::take(&mut *self.0.lock().unwrap());
+            updates.sort_by_key(|(pubkey, _)| *pubkey);
+            updates
+        }
+    }
+
+    #[test]
+    fn test_notify_node_updates() {
+        let notifier = TestNotifier::default();
+        let mut nodes = HashMap::new();
+        let now = 1_000_000;
+        let mut peers: Vec<_> = std::iter::repeat_with(|| {
+            ContactInfo::new_localhost(&Pubkey::new_unique(), /*wallclock:*/ now)
+        })
+        .take(3)
+        .collect();
+        let pubkeys: Vec<_> = peers.iter().map(|node| *node.pubkey()).collect();
+
+        // All nodes are notified the first time.
+        let snapshot = |peers: &[ContactInfo]| {
+            peers
+                .iter()
+                .map(|node| (node.clone(), now))
+                .collect::<Vec<_>>()
+        };
+        notify_node_updates(&mut nodes, snapshot(&peers), now, &notifier);
+        assert_eq!(notifier.take().len(), pubkeys.len());
+
+        // Refreshing the contact info is not notified.
+        peers[0].set_wallclock(now + 1);
+        notify_node_updates(&mut nodes, snapshot(&peers), now, &notifier);
+        assert!(notifier.take().is_empty());
+
+        // New addresses are.
+        peers[0].set_rpc(([10, 0, 0, 1], 8899)).unwrap();
+        notify_node_updates(&mut nodes, snapshot(&peers), now, &notifier);
+        assert_eq!(notifier.take(), vec![(pubkeys[0], NodeStatus::Active)]);
+
+        // So are delinquent and removed nodes.
+        let later = now + NODE_DELINQUENT_TIMEOUT_MS + 1;
+        let peers = vec![(peers[0].clone(), later), (peers[1].clone(), now)];
+        notify_node_updates(&mut nodes, peers, later, &notifier);
+        assert_eq!(
+            notifier.take(),
+            vec![
+                (pubkeys[1], NodeStatus::Delinquent),
+                (pubkeys[2], NodeStatus::Removed),
+            ]
+        );
+        assert_eq!(nodes.len(), 2);
+    }
+}
@@ -0,0 +1,512 @@
+//! Optional tracing of sampled packet batches through the transaction
+//! pipeline, exported as OpenTelemetry spans over OTLP when built with the
+//! `otel` feature.
+//!
+//! The trace of a packet batch starts with a `sigverify` span, from the
+//! moment sigverify receives the batch from the fetch stage to the moment it
+//! sends it to the banking stage. Packets carry no receive timestamp, so the
+//! time spent in the fetch stage itself is not measured. The transactions of
+//! the batch are then followed by signature through the banking stage:
+//!
+//! - `banking_scheduler`, from the receipt of the transactions by the banking
+//!   stage to the start of their first execution,
+//! - `execution`, for every attempt at executing them,
+//! - `commit`, once they are committed.
+//!
+//! Each of these spans covers the transactions of the batch which were
+//! processed together, and is a child of the `sigverify` span of the batch.
+//! Every span carries the `batch_id` of its packet batch.
+
+use {
+    agave_banking_stage_ingress_types::BankingPacketBatch,
+    lru::LruCache,
+    solana_sdk::{clock::Slot, signature::Signature},
+    std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        time::SystemTime,
+    },
+    thiserror::Error,
+};
+
+pub const DEFAULT_PIPELINE_TRACING_SAMPLE_INTERVAL: u64 = 100;
+/// Sampled batches sent by sigverify which the banking stage never received
+/// are eventually evicted
+const MAX_SENT_BATCHES: usize = 1_024;
+/// Traced transactions which are never committed are eventually evicted
+const MAX_TRACED_TRANSACTIONS: usize = 100_000;
+
+#[derive(Error, Debug)]
+pub enum PipelineTracingError {
+    #[error("OpenTelemetry is not supported, build with the otel feature")]
+    Unsupported,
+
+    #[error("failed to start the OTLP exporter: {0}")]
+    Exporter(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PipelineTracingConfig {
+    /// The OTLP gRPC endpoint of the collector, e.g. `http://127.0.0.1:4317`
+    pub otlp_endpoint: String,
+    /// One in every `sample_interval` packet batches is traced
+    pub sample_interval: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineStage {
+    SigVerify,
+    Scheduler,
+    Execution,
+    Commit,
+}
+
+impl PipelineStage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SigVerify => "sigverify",
+            Self::Scheduler => "banking_scheduler",
+            Self::Execution => "execution",
+            Self::Commit => "commit",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PipelineSpan {
+    pub batch_id: u64,
+    pub stage: PipelineStage,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    /// Number of packets of the batch covered by the span
+    pub num_packets: usize,
+    /// The leader slot of the execution and commit spans
+    pub slot: Option<Slot>,
+}
+
+pub trait PipelineSpanExporter: Send + Sync {
+    fn export(&self, spans: Vec<PipelineSpan>);
+}
+
+struct TracedTransaction {
+    batch_id: u64,
+    received: SystemTime,
+    executed: bool,
+}
+
+/// The transactions of a batch processed together
+struct BatchGroup {
+    num_packets: usize,
+    num_unexecuted: usize,
+    first_received: SystemTime,
+}
+
+struct PipelineTracerInner {
+    /// The ids of the sampled batches sent by sigverify, by their address,
+    /// until the banking stage receives them
+    sent: LruCache<usize, u64>,
+    transactions: LruCache<Signature, TracedTransaction>,
+}
+
+pub struct PipelineTracer {
+    sample_interval: u64,
+    num_batches: AtomicU64,
+    inner: Mutex<PipelineTracerInner>,
+    exporter: Box<dyn PipelineSpanExporter>,
+}
+
+impl PipelineTracer {
+    pub fn new(sample_interval: u64, exporter: Box<dyn PipelineSpanExporter>) -> Self {
+        Self {
+            sample_interval: sample_interval.max(1),
+            num_batches: AtomicU64::default(),
+            inner: Mutex::new(PipelineTracerInner {
+                sent: LruCache::new(MAX_SENT_BATCHES),
+                transactions: LruCache::new(MAX_TRACED_TRANSACTIONS),
+            }),
+            exporter,
+        }
+    }
+
+    /// Creates a tracer exporting its spans to the OTLP collector of `config`
+    pub fn new_otlp(config: &PipelineTracingConfig) -> Result<Self, PipelineTracingError> {
+        #[cfg(feature = "otel")]
+        {
+            let exporter = otlp::OtlpSpanExporter::new(&config.otlp_endpoint)?;
+            info!(
+                "Tracing one in every {} packet batches to {}",
+                config.sample_interval, config.otlp_endpoint
+            );
+            Ok(Self::new(config.sample_interval, Box::new(exporter)))
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            let _ = config;
+            Err(PipelineTracingError::Unsupported)
+        }
+    }
+
+    /// Samples `batch`, which sigverify received at `received` and is about
+    /// to send to the banking stage
+    pub fn track_sent(&self, batch: &BankingPacketBatch, received: SystemTime) {
+        let batch_id = self.num_batches.fetch_add(1, Ordering::Relaxed);
+        if batch_id % self.sample_interval != 0 {
+            return;
+        }
+        self.inner
+            .lock()
+            .unwrap()
+            .sent
+            .put(batch_address(batch), batch_id);
+        let num_packets = batch
+            .iter()
+            .flat_map(|batch| batch.iter())
+            .filter(|packet| !packet.meta().discard())
+            .count();
+        self.exporter.export(vec![PipelineSpan {
+            batch_id,
+            stage: PipelineStage::SigVerify,
+            start: received,
+            end: SystemTime::now(),
+            num_packets,
+            slot: None,
+        }]);
+    }
+
+    /// Returns the sampled batches among `batches`, received by the banking
+    /// stage, with their ids
+    pub fn take_sampled<'a>(
+        &self,
+        batches: &'a [BankingPacketBatch],
+    ) -> Vec<(u64, &'a BankingPacketBatch)> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.sent.is_empty() {
+            return vec![];
+        }
+        batches
+            .iter()
+            .filter_map(|batch| {
+                let batch_id = inner.sent.pop(&batch_address(batch))?;
+                Some((batch_id, batch))
+            })
+            .collect()
+    }
+
+    /// Follows the transactions identified by `signatures` of the sampled
+    /// batch `batch_id`, received by the banking stage at `received`
+    pub fn track_received(
+        &self,
+        batch_id: u64,
+        signatures: impl IntoIterator<Item = Signature>,
+        received: SystemTime,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        for signature in signatures {
+            inner.transactions.put(
+                signature,
+                TracedTransaction {
+                    batch_id,
+                    received,
+                    executed: false,
+                },
+            );
+        }
+    }
+
+    /// Records the execution in `slot`, started at `start`, of the
+    /// transactions identified by `signatures`
+    pub fn track_executed<'a>(
+        &self,
+        slot: Slot,
+        signatures: impl IntoIterator<Item = &'a Signature>,
+        start: SystemTime,
+    ) {
+        let groups = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.transactions.is_empty() {
+                return;
+            }
+            Self::group_by_batch(signatures, |signature| {
+                let transaction = inner.transactions.get_mut(signature)?;
+                let unexecuted = !transaction.executed;
+                transaction.executed = true;
+                Some((transaction.batch_id, transaction.received, unexecuted))
+            })
+        };
+        if groups.is_empty() {
+            return;
+        }
+        let end = SystemTime::now();
+        let mut spans = Vec::with_capacity(groups.len().saturating_mul(2));
+        for (batch_id, group) in groups {
+            if group.num_unexecuted > 0 {
+                spans.push(PipelineSpan {
+                    batch_id,
+                    stage: PipelineStage::Scheduler,
+                    start: group.first_received,
+                    end: start,
+                    num_packets: group.num_unexecuted,
+                    slot: None,
+                });
+            }
+            spans.push(PipelineSpan {
+                batch_id,
+                stage: PipelineStage::Execution,
+                start,
+                end,
+                num_packets: group.num_packets,
+                slot: Some(slot),
+            });
+        }
+        self.exporter.export(spans);
+    }
+
+    /// Records the commit in `slot`, started at `start`, of the transactions
+    /// identified by `signatures`, which are no longer followed
+    pub fn track_committed<'a>(
+        &self,
+        slot: Slot,
+        signatures: impl IntoIterator<Item = &'a Signature>,
+        start: SystemTime,
+    ) {
+        let groups = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.transactions.is_empty() {
+                return;
+            }
+            Self::group_by_batch(signatures, |signature| {
+                let transaction = inner.transactions.pop(signature)?;
+                Some((transaction.batch_id, transaction.received, false))
+            })
+        };
+        if groups.is_empty() {
+            return;
+        }
+        let end = SystemTime::now();
+        let spans = groups
+            .into_iter()
+            .map(|(batch_id, group)| PipelineSpan {
+                batch_id,
+                stage: PipelineStage::Commit,
+                start,
+                end,
+                num_packets: group.num_packets,
+                slot: Some(slot),
+            })
+            .collect();
+        self.exporter.export(spans);
+    }
+
+    /// Groups the traced transactions among `signatures` by batch,
+    /// `transaction` returning the batch id, receipt time and whether the
+    /// transaction was not executed before, of the traced ones
+    fn group_by_batch<'a>(
+        signatures: impl IntoIterator<Item = &'a Signature>,
+        mut transaction: impl FnMut(&Signature) -> Option<(u64, SystemTime, bool)>,
+    ) -> BTreeMap<u64, BatchGroup> {
+        let mut groups = BTreeMap::<u64, BatchGroup>::new();
+        for signature in signatures {
+            let Some((batch_id, received, unexecuted)) = transaction(signature) else {
+                continue;
+            };
+            let group = groups.entry(batch_id).or_insert(BatchGroup {
+                num_packets: 0,
+                num_unexecuted: 0,
+                first_received: received,
+            });
+            group.num_packets += 1;
+            if unexecuted {
+                group.num_unexecuted += 1;
+            }
+            group.first_received = group.first_received.min(received);
+        }
+        groups
+    }
+}
+
+/// Identifies a batch between sigverify and the banking stage, which share
+/// the same allocation of the batch
+fn batch_address(batch: &BankingPacketBatch) -> usize {
+    std::sync::Arc::as_ptr(batch) as usize
+}
+
+#[cfg(feature = "otel")]
+mod otlp {
+    use {
+        super::*,
+        opentelemetry::{
+            trace::{Span, SpanContext, SpanKind, TraceContextExt, Tracer, TracerProvider as _},
+            Context, KeyValue,
+        },
+        opentelemetry_otlp::WithExportConfig,
+        opentelemetry_sdk::{
+            runtime,
+            trace::{self as sdk_trace, TracerProvider},
+            Resource,
+        },
+    };
+
+    pub(super) struct OtlpSpanExporter {
+        /// Runs the batch span processor
+        _runtime: tokio::runtime::Runtime,
+        provider: TracerProvider,
+        tracer: sdk_trace::Tracer,
+        /// The `sigverify` spans of the recently traced batches, the parents
+        /// of their other spans
+        roots: Mutex<LruCache<u64, SpanContext>>,
+    }
+
+    impl OtlpSpanExporter {
+        pub(super) fn new(endpoint: &str) -> Result<Self, PipelineTracingError> {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("solPipeTraceExp")
+                .enable_all()
+                .build()
+                .map_err(|err| PipelineTracingError::Exporter(err.to_string()))?;
+            let provider = {
+                let _guard = runtime.enter();
+                let exporter = opentelemetry_otlp::SpanExporter::builder()
+                    .with_tonic()
+                    .with_endpoint(endpoint)
+                    .build()
+                    .map_err(|err| PipelineTracingError::Exporter(err.to_string()))?;
+                TracerProvider::builder()
+                    .with_batch_exporter(exporter, runtime::Tokio)
+                    .with_resource(Resource::new([KeyValue::new(
+                        "service.name",
+                        "agave-validator",
+                    )]))
+                    .build()
+            };
+            let tracer = provider.tracer("solana-core");
+            Ok(Self {
+                _runtime: runtime,
+                provider,
+                tracer,
+                roots: Mutex::new(LruCache::new(MAX_SENT_BATCHES)),
+            })
+        }
+    }
+
+    impl PipelineSpanExporter for OtlpSpanExporter {
+        fn export(&self, spans: Vec<PipelineSpan>) {
+            for span in spans {
+                let root = match span.stage {
+                    PipelineStage::SigVerify => None,
+                    _ => self.roots.lock().unwrap().get(&span.batch_id).cloned(),
+                };
+                let parent = match root {
+                    Some(root) => Context::new().with_remote_span_context(root),
+                    None => Context::new(),
+                };
+                let mut attributes = vec![
+                    KeyValue::new("batch_id", span.batch_id as i64),
+                    KeyValue::new("num_packets", span.num_packets as i64),
+                ];
+                if let Some(slot) = span.slot {
+                    attributes.push(KeyValue::new("slot", slot as i64));
+                }
+                let mut otel_span = self
+                    .tracer
+                    .span_builder(span.stage.name())
+                    .with_kind(SpanKind::Internal)
+                    .with_start_time(span.start)
+                    .with_attributes(attributes)
+                    .start_with_context(&self.tracer, &parent);
+                if span.stage == PipelineStage::SigVerify {
+                    self.roots
+                        .lock()
+                        .unwrap()
+                        .put(span.batch_id, otel_span.span_context().clone());
+                }
+                otel_span.end_with_timestamp(span.end);
+            }
+        }
+    }
+
+    impl Drop for OtlpSpanExporter {
+        fn drop(&mut self) {
+            if let Err(err) = self.provider.shutdown() {
+                warn!("Failed to flush the pipeline spans: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::packet::{Packet, PacketBatch},
+        std::sync::Arc,
+    };
+
+    #[derive(Default)]
+    struct TestExporter {
+        spans: Arc<Mutex<Vec<PipelineSpan>>>,
+    }
+
+    impl PipelineSpanExporter for TestExporter {
+        fn export(&self, spans: Vec<PipelineSpan>) {
+            self.spans.lock().unwrap().extend(spans);
+        }
+    }
+
+    fn new_batch(num_packets: usize) -> BankingPacketBatch {
+        BankingPacketBatch::new(vec![PacketBatch::new(vec![Packet::default(); num_packets])])
+    }
+
+    #[test]
+    fn test_pipeline_tracer() {
+        let exporter = TestExporter::default();
+        let spans = exporter.spans.clone();
+        let tracer = PipelineTracer::new(2, Box::new(exporter));
+        let received = SystemTime::now();
+        let sampled = new_batch(2);
+        let unsampled = new_batch(3);
+        tracer.track_sent(&sampled, received);
+        tracer.track_sent(&unsampled, received);
+        assert_eq!(spans.lock().unwrap().len(), 1);
+
+        let batches = [unsampled, sampled.clone()];
+        let sampled_batches = tracer.take_sampled(&batches);
+        assert_eq!(sampled_batches.len(), 1);
+        assert_eq!(sampled_batches[0].0, 0);
+        assert!(Arc::ptr_eq(sampled_batches[0].1, &sampled));
+        // A batch is received only once.
+        assert!(tracer.take_sampled(&batches).is_empty());
+
+        let signatures = [Signature::new_unique(), Signature::new_unique()];
+        tracer.track_received(0, signatures, received);
+        let untraced = Signature::new_unique();
+        let start = SystemTime::now();
+        tracer.track_executed(5, [&signatures[0], &untraced], start);
+        tracer.track_executed(5, &signatures, start);
+        tracer.track_committed(5, &signatures, start);
+        // Committed transactions are no longer followed.
+        tracer.track_committed(6, &signatures, start);
+
+        let spans = spans.lock().unwrap();
+        let stages: Vec<_> = spans
+            .iter()
+            .map(|span| (span.stage, span.num_packets, span.slot))
+            .collect();
+        assert_eq!(
+            stages,
+            [
+                (PipelineStage::SigVerify, 2, None),
+                (PipelineStage::Scheduler, 1, None),
+                (PipelineStage::Execution, 1, Some(5)),
+                (PipelineStage::Scheduler, 1, None),
+                (PipelineStage::Execution, 2, Some(5)),
+                (PipelineStage::Commit, 2, Some(5)),
+            ]
+        );
+        assert!(spans.iter().all(|span| span.batch_id == 0));
+        assert!(spans.iter().all(|span| span.start <= span.end));
+    }
+}
@@ -0,0 +1,188 @@
+//! Compilation of the most invoked programs into the program cache ahead of
+//! their first invocation.
+//!
+//! The program cache is empty after a restart, so the first transactions
+//! invoking a program wait for it to be loaded, verified and JIT compiled,
+//! which delays the replay of the first slots once the validator votes. The
+//! validator can count the invocations of each program in the latest rooted
+//! slots of its blockstore, and compile the most invoked ones into the program
+//! cache before it starts voting. The admin RPC `precompilePrograms` compiles
+//! them again on demand, e.g. after they were evicted.
+
+use {
+    solana_ledger::blockstore::{self, Blockstore},
+    solana_measure::measure::Measure,
+    solana_runtime::bank_forks::BankForks,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    },
+};
+
+/// The number of rooted slots whose invocations are counted by default
+pub const DEFAULT_PRECOMPILE_PROGRAMS_SLOTS: u64 = 1_000;
+
+/// Returns up to `num_programs` programs invoked by the transactions of the
+/// rooted slots of the `num_slots` slots up to `max_slot`, along with their
+/// number of invocations, the most invoked first. The slots whose entries are
+/// missing from the blockstore are skipped.
+pub fn most_invoked_programs(
+    blockstore: &Blockstore,
+    max_slot: Slot,
+    num_slots: u64,
+    num_programs: usize,
+) -> blockstore::Result<Vec<(Pubkey, u64)>> {
+    let mut invocations = HashMap::<Pubkey, u64>::new();
+    let start_slot = max_slot.saturating_sub(num_slots.saturating_sub(1));
+    for slot in blockstore.rooted_slot_iterator(start_slot)? {
+        if slot > max_slot {
+            break;
+        }
+        let Ok(entries) = blockstore.get_slot_entries(slot, 0) else {
+            continue;
+        };
+        for transaction in entries.iter().flat_map(|entry| &entry.transactions) {
+            // The program of an instruction is never loaded from an address
+            // lookup table.
+            let account_keys = transaction.message.static_account_keys();
+            for instruction in transaction.message.instructions() {
+                if let Some(program_id) =
+                    account_keys.get(usize::from(instruction.program_id_index))
+                {
+                    *invocations.entry(*program_id).or_default() += 1;
+                }
+            }
+        }
+    }
+    let mut programs: Vec<_> = invocations.into_iter().collect();
+    programs.sort_unstable_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+    programs.truncate(num_programs);
+    Ok(programs)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProgramPrecompilationReport {
+    /// The root bank the programs were compiled for
+    pub slot: Slot,
+    /// The most invoked programs, including the builtins and the programs
+    /// which were already compiled
+    pub num_candidates: usize,
+    pub num_compiled: usize,
+    pub elapsed_ms: u64,
+}
+
+/// Compiles the most invoked programs of the latest rooted slots into the
+/// program cache of the root bank
+pub struct ProgramPrecompiler {
+    blockstore: Arc<Blockstore>,
+    bank_forks: Arc<RwLock<BankForks>>,
+    num_slots: u64,
+}
+
+impl ProgramPrecompiler {
+    /// Creates a precompiler counting the invocations of the latest
+    /// `num_slots` rooted slots.
+    pub fn new(
+        blockstore: Arc<Blockstore>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        num_slots: u64,
+    ) -> Self {
+        Self {
+            blockstore,
+            bank_forks,
+            num_slots,
+        }
+    }
+
+    /// Compiles the `num_programs` most invoked programs which are not
+    /// compiled yet.
+    pub fn precompile(
+        &self,
+        num_programs: usize,
+    ) -> blockstore::Result<ProgramPrecompilationReport> {
+        let mut measure = Measure::start("precompile_programs");
+        let root_bank = self.bank_forks.read().unwrap().root_bank();
+        let programs = most_invoked_programs(
+            &self.blockstore,
+            root_bank.slot(),
+            self.num_slots,
+            num_programs,
+        )?;
+        let num_compiled = root_bank.precompile_programs(&programs);
+        measure.stop();
+        Ok(ProgramPrecompilationReport {
+            slot: root_bank.slot(),
+            num_candidates: programs.len(),
+            num_compiled,
+            elapsed_ms: measure.as_ms(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_entry::entry::Entry,
+        solana_ledger::{blockstore::entries_to_test_shreds, get_tmp_ledger_path_auto_delete},
+        solana_sdk::{
+            hash::Hash,
+            instruction::Instruction,
+            message::Message,
+            signature::{Keypair, Signer},
+            transaction::Transaction,
+        },
+    };
+
+    fn insert_slot(blockstore: &Blockstore, slot: Slot, program_ids: &[Pubkey]) {
+        let payer = Keypair::new();
+        let transactions = program_ids
+            .iter()
+            .map(|program_id| {
+                let instruction = Instruction::new_with_bytes(*program_id, &[], vec![]);
+                let message =
+                    Message::new(&[instruction.clone(), instruction], Some(&payer.pubkey()));
+                Transaction::new(&[&payer], message, Hash::default())
+            })
+            .collect();
+        let entries = vec![Entry::new(&Hash::default(), 1, transactions)];
+        let shreds = entries_to_test_shreds(&entries, slot, slot.saturating_sub(1), true, 0, true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+    }
+
+    #[test]
+    fn test_most_invoked_programs() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let programs = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        insert_slot(&blockstore, 1, &[programs[2]]);
+        insert_slot(&blockstore, 2, &[programs[0], programs[1]]);
+        insert_slot(&blockstore, 3, &[programs[1]]);
+        // Not rooted
+        insert_slot(&blockstore, 4, &[programs[0], programs[0]]);
+        blockstore.set_roots([1, 2, 3].iter()).unwrap();
+
+        // Each transaction invokes its program twice.
+        assert_eq!(
+            most_invoked_programs(&blockstore, 4, 3, 10).unwrap(),
+            vec![(programs[1], 4), (programs[0], 2)]
+        );
+        assert_eq!(
+            most_invoked_programs(&blockstore, 3, 10, 1).unwrap(),
+            vec![(programs[1], 4)]
+        );
+        let mut all = most_invoked_programs(&blockstore, 3, 10, 10).unwrap();
+        all.sort_unstable();
+        let mut expected = vec![(programs[0], 2), (programs[1], 4), (programs[2], 2)];
+        expected.sort_unstable();
+        assert_eq!(all, expected);
+        assert!(most_invoked_programs(&blockstore, 0, 10, 10)
+            .unwrap()
+            .is_empty());
+    }
+}
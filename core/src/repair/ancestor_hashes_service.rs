@@ -27,13 +27,15 @@ use {
     solana_runtime::bank::Bank,
     solana_sdk::{
         clock::{Slot, DEFAULT_MS_PER_SLOT},
-        genesis_config::ClusterType,
         pubkey::Pubkey,
         signature::Signable,
         signer::keypair::Keypair,
         timing::timestamp,
     },
-    solana_streamer::streamer::{self, PacketBatchReceiver, StreamerReceiveStats},
+    solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
+        streamer::{self, PacketBatchReceiver, StreamerReceiveStats},
+    },
     std::{
         collections::HashSet,
         io::{Cursor, Read},
@@ -168,9 +170,10 @@ impl AncestorHashesService {
             exit.clone(),
             response_sender.clone(),
             Recycler::default(),
-            Arc::new(StreamerReceiveStats::new(
-                "ancestor_hashes_response_receiver",
-            )),
+            Arc::new(
+                StreamerReceiveStats::new("ancestor_hashes_response_receiver")
+                    .with_subsystem(Subsystem::Repair),
+            ),
             Some(Duration::from_millis(1)), // coalesce
             false,                          // use_pinned_memory
             None,                           // in_vote_only_mode
@@ -667,7 +670,6 @@ impl AncestorHashesService {
         request_throttle: &mut Vec<u64>,
     ) {
         let root_bank = repair_info.bank_forks.read().unwrap().root_bank();
-        let cluster_type = root_bank.cluster_type();
         for (slot, request_type) in retryable_slots_receiver.try_iter() {
             datapoint_info!("ancestor-repair-retry", ("slot", slot, i64));
             if request_type.is_pruned() {
@@ -761,7 +763,7 @@ impl AncestorHashesService {
                 outstanding_requests,
                 identity_keypair,
                 request_type,
-                cluster_type,
+                repair_info.repair_protocol,
             ) {
                 request_throttle.push(timestamp());
                 if request_type.is_pruned() {
@@ -839,9 +841,8 @@ impl AncestorHashesService {
         outstanding_requests: &RwLock<OutstandingAncestorHashesRepairs>,
         identity_keypair: &Keypair,
         request_type: AncestorRequestType,
-        cluster_type: ClusterType,
+        repair_protocol: Protocol,
     ) -> bool {
-        let repair_protocol = serve_repair::get_repair_protocol(cluster_type);
         let Ok(sampled_validators) = serve_repair.repair_request_ancestor_hashes_sample_peers(
             duplicate_slot,
             cluster_slots,
@@ -851,7 +852,7 @@ impl AncestorHashesService {
             return false;
         };
 
-        for (pubkey, socket_addr) in &sampled_validators {
+        for (pubkey, socket_addr, protocol) in &sampled_validators {
             repair_stats
                 .ancestor_requests
                 .update(pubkey, duplicate_slot, 0);
@@ -868,8 +869,9 @@ impl AncestorHashesService {
             ) else {
                 continue;
             };
-            match repair_protocol {
+            match protocol {
                 Protocol::UDP => {
+                    bandwidth_accounting::record_egress(Subsystem::Repair, 1, request_bytes.len());
                     let _ = ancestor_hashes_request_socket.send_to(&request_bytes, socket_addr);
                 }
                 Protocol::QUIC => {
@@ -887,7 +889,7 @@ impl AncestorHashesService {
         let ancestor_request_status = AncestorRequestStatus::new(
             sampled_validators
                 .into_iter()
-                .map(|(_pk, socket_addr)| socket_addr),
+                .map(|(_pk, socket_addr, _protocol)| socket_addr),
             duplicate_slot,
             request_type,
         );
@@ -906,6 +908,7 @@ mod test {
             repair::{
                 cluster_slot_state_verifier::{DuplicateSlotsToRepair, PurgeRepairSlotCounter},
                 duplicate_repair_status::DuplicateAncestorDecision,
+                repair_peer_selector::RepairPeerSelector,
                 serve_repair::MAX_ANCESTOR_RESPONSES,
                 serve_repair_service::adapt_repair_requests_packets,
             },
@@ -1382,6 +1385,8 @@ mod test {
                 repair_validators: None,
                 repair_whitelist,
                 wen_restart_repair_slots: None,
+                repair_protocol: Protocol::UDP,
+                repair_peer_selector: Arc::<RepairPeerSelector>::default(),
             };
 
             let (ancestor_hashes_replay_update_sender, ancestor_hashes_replay_update_receiver) =
@@ -1523,7 +1528,7 @@ mod test {
             &outstanding_requests,
             &requester_cluster_info.keypair(),
             AncestorRequestType::DeadDuplicateConfirmed,
-            ClusterType::Development,
+            Protocol::UDP,
         );
         assert!(ancestor_hashes_request_statuses.is_empty());
 
@@ -1573,7 +1578,7 @@ mod test {
             &outstanding_requests,
             &requester_cluster_info.keypair(),
             AncestorRequestType::DeadDuplicateConfirmed,
-            ClusterType::Development,
+            Protocol::UDP,
         );
 
         assert_eq!(ancestor_hashes_request_statuses.len(), 1);
@@ -1635,7 +1640,7 @@ mod test {
             &outstanding_requests,
             &requester_cluster_info.keypair(),
             AncestorRequestType::PopularPruned,
-            ClusterType::Development,
+            Protocol::UDP,
         );
 
         assert_eq!(ancestor_hashes_request_statuses.len(), 1);
@@ -5,6 +5,7 @@ pub mod outstanding_requests;
 pub mod packet_threshold;
 pub(crate) mod quic_endpoint;
 pub mod repair_generic_traversal;
+pub mod repair_peer_selector;
 pub mod repair_response;
 pub mod repair_service;
 pub mod repair_weight;
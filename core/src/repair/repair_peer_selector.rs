@@ -0,0 +1,298 @@
+//! Policies for choosing the peers that repairs are requested from, and the
+//! per-peer request and response statistics they rely on.
+//!
+//! Peers are sampled with weights derived from the slots and stakes they
+//! advertise in gossip. The policy then scales these weights:
+//!   * stake-weighted: unchanged.
+//!   * lowest-latency: inversely to the expected latency of the peer, the
+//!     EWMA of the latencies of its past responses divided by its response
+//!     rate.
+//!   * geo-diverse: inversely to the number of peers in the same network,
+//!     approximated by the /16 prefix of IPv4 or /32 prefix of IPv6 addresses.
+
+use {
+    lazy_static::lazy_static,
+    lru::LruCache,
+    solana_gossip::contact_info::{ContactInfo, Protocol},
+    solana_ledger::shred::Nonce,
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        net::IpAddr,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+    strum::VariantNames,
+    strum_macros::{Display, EnumString, EnumVariantNames, IntoStaticStr},
+};
+
+const REQUESTS_CACHE_CAPACITY: usize = 16 * 1024;
+// Weight of the latest response in the EWMA of the latencies.
+const LATENCY_EWMA_ALPHA: f64 = 0.125;
+// Latency assumed for peers which have not responded yet.
+const DEFAULT_LATENCY_MS: f64 = 100.0;
+// Counts of requests and responses are halved past this many requests, so
+// that the response rate follows the recent behavior of the peer.
+const MAX_NUM_REQUESTS: u64 = 1024;
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(
+    Clone, Copy, Debug, Default, Display, EnumString, EnumVariantNames, Eq, IntoStaticStr, PartialEq,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RepairPeerSelection {
+    #[default]
+    StakeWeighted,
+    LowestLatency,
+    GeoDiverse,
+}
+
+impl RepairPeerSelection {
+    pub const fn cli_names() -> &'static [&'static str] {
+        Self::VARIANTS
+    }
+
+    pub fn cli_message() -> &'static str {
+        lazy_static! {
+            static ref MESSAGE: String = format!(
+                "Policy for choosing the validators repairs are requested from [default: {}]",
+                RepairPeerSelection::default()
+            );
+        };
+
+        &MESSAGE
+    }
+}
+
+#[derive(Default)]
+pub struct RepairPeerSelector {
+    selection: RepairPeerSelection,
+    stats: Mutex<RepairPeerStats>,
+}
+
+struct RepairPeerStats {
+    // Peer and timestamp of the requests awaiting their first response.
+    requests: LruCache<Nonce, (Pubkey, /*timestamp:*/ u64)>,
+    peers: HashMap<Pubkey, PeerStats>,
+    last_report: Instant,
+}
+
+#[derive(Default)]
+struct PeerStats {
+    num_requests: u64,
+    num_responses: u64,
+    // EWMA of the latencies of the responses, in milliseconds.
+    latency_ms: Option<f64>,
+    // Counts since the last report.
+    report_num_requests: u64,
+    report_num_responses: u64,
+}
+
+impl Default for RepairPeerStats {
+    fn default() -> Self {
+        Self {
+            requests: LruCache::new(REQUESTS_CACHE_CAPACITY),
+            peers: HashMap::new(),
+            last_report: Instant::now(),
+        }
+    }
+}
+
+impl PeerStats {
+    fn expected_latency_ms(&self) -> f64 {
+        let response_rate = (self.num_responses + 1) as f64 / (self.num_requests + 1) as f64;
+        self.latency_ms.unwrap_or(DEFAULT_LATENCY_MS) / response_rate
+    }
+}
+
+impl RepairPeerSelector {
+    pub fn new(selection: RepairPeerSelection) -> Self {
+        Self {
+            selection,
+            stats: Mutex::default(),
+        }
+    }
+
+    pub fn selection(&self) -> RepairPeerSelection {
+        self.selection
+    }
+
+    /// Scales the sampling weights of the repair peers according to the
+    /// policy.
+    pub(crate) fn weights(&self, peers: &[ContactInfo], weights: Vec<u64>) -> Vec<u64> {
+        let scale = |weight: u64, scale: f64| {
+            if weight == 0 {
+                0
+            } else {
+                ((weight as f64 * scale) as u64).max(1)
+            }
+        };
+        match self.selection {
+            RepairPeerSelection::StakeWeighted => weights,
+            RepairPeerSelection::LowestLatency => {
+                let stats = self.stats.lock().unwrap();
+                peers
+                    .iter()
+                    .zip(weights)
+                    .map(|(peer, weight)| {
+                        let latency_ms = stats
+                            .peers
+                            .get(peer.pubkey())
+                            .map(PeerStats::expected_latency_ms)
+                            .unwrap_or(DEFAULT_LATENCY_MS);
+                        scale(weight, DEFAULT_LATENCY_MS / latency_ms.max(1.0))
+                    })
+                    .collect()
+            }
+            RepairPeerSelection::GeoDiverse => {
+                let networks: Vec<_> = peers.iter().map(get_network).collect();
+                let mut counts = HashMap::<_, usize>::new();
+                for network in &networks {
+                    *counts.entry(*network).or_default() += 1;
+                }
+                networks
+                    .iter()
+                    .zip(weights)
+                    .map(|(network, weight)| scale(weight, 1.0 / counts[network] as f64))
+                    .collect()
+            }
+        }
+    }
+
+    pub(crate) fn record_request(&self, nonce: Nonce, peer: Pubkey, now: u64) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.requests.put(nonce, (peer, now));
+        let peer = stats.peers.entry(peer).or_default();
+        peer.num_requests += 1;
+        peer.report_num_requests += 1;
+        if peer.num_requests > MAX_NUM_REQUESTS {
+            peer.num_requests /= 2;
+            peer.num_responses /= 2;
+        }
+    }
+
+    /// Records the first response to a request; later responses to the same
+    /// request are ignored.
+    pub(crate) fn record_response(&self, nonce: Nonce, now: u64) {
+        let mut stats = self.stats.lock().unwrap();
+        let Some((peer, timestamp)) = stats.requests.pop(&nonce) else {
+            return;
+        };
+        let Some(peer) = stats.peers.get_mut(&peer) else {
+            return;
+        };
+        let latency_ms = now.saturating_sub(timestamp) as f64;
+        peer.num_responses = (peer.num_responses + 1).min(peer.num_requests);
+        peer.report_num_responses += 1;
+        peer.latency_ms = Some(match peer.latency_ms {
+            None => latency_ms,
+            Some(ewma) => ewma + LATENCY_EWMA_ALPHA * (latency_ms - ewma),
+        });
+    }
+
+    pub(crate) fn maybe_report(&self) {
+        let mut stats = self.stats.lock().unwrap();
+        if stats.last_report.elapsed() < REPORT_INTERVAL {
+            return;
+        }
+        stats.last_report = Instant::now();
+        for (pubkey, peer) in &mut stats.peers {
+            if peer.report_num_requests == 0 {
+                continue;
+            }
+            datapoint_info!(
+                "repair_peer_stats",
+                "selection" => self.selection.to_string(),
+                ("peer", pubkey.to_string(), String),
+                ("num_requests", peer.report_num_requests, i64),
+                ("num_responses", peer.report_num_responses, i64),
+                ("latency_ms", peer.latency_ms.unwrap_or_default(), f64),
+            );
+            peer.report_num_requests = 0;
+            peer.report_num_responses = 0;
+        }
+    }
+}
+
+// Returns the prefix of the IP address of the repair socket of the node
+// identifying its network.
+fn get_network(node: &ContactInfo) -> Option<IpAddr> {
+    let addr = node.serve_repair(Protocol::UDP)?;
+    Some(match addr.ip() {
+        IpAddr::V4(ip) => {
+            let [a, b, _, _] = ip.octets();
+            IpAddr::from([a, b, 0, 0])
+        }
+        IpAddr::V6(ip) => {
+            let [a, b, ..] = ip.segments();
+            IpAddr::from([a, b, 0, 0, 0, 0, 0, 0])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::timing::timestamp, std::net::Ipv4Addr};
+
+    fn new_peer(ip: [u8; 4]) -> ContactInfo {
+        let mut node = ContactInfo::new_localhost(&Pubkey::new_unique(), timestamp());
+        node.set_serve_repair(Protocol::UDP, (Ipv4Addr::from(ip), 8008))
+            .unwrap();
+        node
+    }
+
+    #[test]
+    fn test_stake_weighted() {
+        let selector = RepairPeerSelector::default();
+        assert_eq!(selector.selection(), RepairPeerSelection::StakeWeighted);
+        let peers = [new_peer([10, 0, 0, 1]), new_peer([10, 0, 0, 2])];
+        assert_eq!(selector.weights(&peers, vec![10, 20]), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_lowest_latency() {
+        let selector = RepairPeerSelector::new(RepairPeerSelection::LowestLatency);
+        let peers = [
+            new_peer([10, 0, 0, 1]),
+            new_peer([10, 0, 0, 2]),
+            new_peer([10, 0, 0, 3]),
+        ];
+        let now = timestamp();
+        // A fast peer, a slow peer and a peer which has not responded.
+        selector.record_request(1, *peers[0].pubkey(), now);
+        selector.record_response(1, now + 10);
+        selector.record_request(2, *peers[1].pubkey(), now);
+        selector.record_response(2, now + 400);
+        // Only the first response is recorded.
+        selector.record_response(2, now + 10);
+        selector.record_request(3, *peers[2].pubkey(), now);
+        let weights = selector.weights(&peers, vec![1000, 1000, 1000]);
+        assert!(weights[0] > weights[2]);
+        assert!(weights[2] > weights[1]);
+        assert!(weights[1] > 0);
+        // Zero weights stay zero.
+        assert_eq!(selector.weights(&peers, vec![0, 0, 0]), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_geo_diverse() {
+        let selector = RepairPeerSelector::new(RepairPeerSelection::GeoDiverse);
+        let peers = [
+            new_peer([10, 0, 0, 1]),
+            new_peer([10, 0, 1, 2]),
+            new_peer([10, 1, 0, 3]),
+        ];
+        assert_eq!(
+            selector.weights(&peers, vec![100, 100, 100]),
+            vec![50, 50, 100]
+        );
+    }
+
+    #[test]
+    fn test_cli_names() {
+        for name in RepairPeerSelection::cli_names() {
+            let selection: RepairPeerSelection = name.parse().unwrap();
+            assert_eq!(&selection.to_string(), name);
+        }
+    }
+}
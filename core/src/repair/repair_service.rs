@@ -15,10 +15,11 @@ use {
             },
             duplicate_repair_status::AncestorDuplicateSlotToRepair,
             outstanding_requests::OutstandingRequests,
+            repair_peer_selector::RepairPeerSelector,
             repair_weight::RepairWeight,
             serve_repair::{
-                self, RepairPeers, RepairProtocol, RepairRequestHeader, ServeRepair,
-                ShredRepairType, REPAIR_PEERS_CACHE_CAPACITY,
+                RepairPeers, RepairProtocol, RepairRequestHeader, ServeRepair, ShredRepairType,
+                REPAIR_PEERS_CACHE_CAPACITY,
             },
         },
     },
@@ -41,7 +42,10 @@ use {
         pubkey::Pubkey,
         timing::timestamp,
     },
-    solana_streamer::sendmmsg::{batch_send, SendPktsError},
+    solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
+        sendmmsg::{batch_send, SendPktsError},
+    },
     std::{
         collections::{hash_map::Entry, HashMap, HashSet},
         iter::Iterator,
@@ -363,6 +367,10 @@ pub struct RepairInfo {
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
     // A given list of slots to repair when in wen_restart
     pub wen_restart_repair_slots: Option<Arc<RwLock<Vec<Slot>>>>,
+    // Protocol repairs are requested over, if supported by the peer
+    pub repair_protocol: Protocol,
+    // Chooses the peers repairs are requested from
+    pub repair_peer_selector: Arc<RepairPeerSelector>,
 }
 
 pub struct RepairSlotRange {
@@ -650,6 +658,7 @@ impl RepairService {
                             &repair_info.cluster_info.keypair(),
                             repair_request_quic_sender,
                             repair_protocol,
+                            &repair_info.repair_peer_selector,
                         )
                         .ok()??;
                     Some((req, to))
@@ -661,6 +670,8 @@ impl RepairService {
         let mut batch_send_repairs_elapsed = Measure::start("batch_send_repairs_elapsed");
         if !batch.is_empty() {
             let num_pkts = batch.len();
+            let num_bytes = batch.iter().map(|(bytes, _)| bytes.len()).sum();
+            bandwidth_accounting::record_egress(Subsystem::Repair, num_pkts, num_bytes);
             let batch = batch.iter().map(|(bytes, addr)| (bytes, addr));
             match batch_send(repair_socket, batch) {
                 Ok(()) => (),
@@ -737,7 +748,7 @@ impl RepairService {
             repair_info,
             outstanding_requests,
             repair_socket,
-            serve_repair::get_repair_protocol(root_bank.cluster_type()),
+            repair_info.repair_protocol,
             repair_metrics,
         );
     }
@@ -776,6 +787,7 @@ impl RepairService {
                 repair_socket,
             );
             repair_tracker.repair_metrics.maybe_report();
+            repair_info.repair_peer_selector.maybe_report();
             sleep(Duration::from_millis(REPAIR_MS));
         }
     }
@@ -860,15 +872,18 @@ impl RepairService {
                 None => vec![],
             }
         } else {
+            let missing_indexes = blockstore.find_missing_data_indexes(
+                slot,
+                slot_meta.first_shred_timestamp,
+                defer_repair_threshold_ticks,
+                slot_meta.consumed,
+                slot_meta.received,
+                max_repairs,
+            );
+            // Skip the data shreds which can be recovered from erasure coding
+            // once the others are repaired.
             blockstore
-                .find_missing_data_indexes(
-                    slot,
-                    slot_meta.first_shred_timestamp,
-                    defer_repair_threshold_ticks,
-                    slot_meta.consumed,
-                    slot_meta.received,
-                    max_repairs,
-                )
+                .prune_recoverable_data_indexes(slot, missing_indexes)
                 .into_iter()
                 .filter_map(|i| {
                     RepairService::request_repair_if_needed(
@@ -1266,7 +1281,7 @@ pub(crate) fn sleep_shred_deferment_period() {
 mod test {
     use {
         super::*,
-        crate::repair::quic_endpoint::RemoteRequest,
+        crate::repair::{quic_endpoint::RemoteRequest, serve_repair},
         solana_gossip::{cluster_info::Node, contact_info::ContactInfo},
         solana_ledger::{
             blockstore::{
@@ -4,6 +4,7 @@ use {
         repair::{
             duplicate_repair_status::get_ancestor_hash_repair_sample_size,
             quic_endpoint::RemoteRequest,
+            repair_peer_selector::RepairPeerSelector,
             repair_response,
             repair_service::{OutstandingShredRepairs, RepairStats, REPAIR_MS},
             request_response::RequestResponse,
@@ -36,7 +37,6 @@ use {
     solana_runtime::{bank_forks::BankForks, root_bank_cache::RootBankCache},
     solana_sdk::{
         clock::Slot,
-        genesis_config::ClusterType,
         hash::{Hash, HASH_BYTES},
         packet::PACKET_DATA_SIZE,
         pubkey::{Pubkey, PUBKEY_BYTES},
@@ -49,6 +49,7 @@ use {
         socket::SocketAddrSpace,
         streamer::PacketBatchSender,
     },
+    solana_turbine::fec_ratio::FecRatio,
     std::{
         cmp::Reverse,
         collections::{HashMap, HashSet},
@@ -339,6 +340,8 @@ pub struct ServeRepair {
     cluster_info: Arc<ClusterInfo>,
     root_bank_cache: RootBankCache,
     repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
+    // Records the repairs of the leader slots of the FEC ratio experiment.
+    fec_ratio: Option<Arc<FecRatio>>,
 }
 
 // Cache entry for repair peers for a slot.
@@ -351,7 +354,8 @@ pub(crate) struct RepairPeers {
 struct Node {
     pubkey: Pubkey,
     serve_repair: SocketAddr,
-    serve_repair_quic: SocketAddr,
+    // None if the node does not advertise a QUIC repair socket.
+    serve_repair_quic: Option<SocketAddr>,
 }
 
 impl RepairPeers {
@@ -366,7 +370,7 @@ impl RepairPeers {
                 let node = Node {
                     pubkey: *peer.pubkey(),
                     serve_repair: peer.serve_repair(Protocol::UDP)?,
-                    serve_repair_quic: peer.serve_repair(Protocol::QUIC)?,
+                    serve_repair_quic: peer.serve_repair(Protocol::QUIC),
                 };
                 Some((node, weight))
             })
@@ -406,9 +410,15 @@ impl ServeRepair {
             cluster_info,
             root_bank_cache: RootBankCache::new(bank_forks),
             repair_whitelist,
+            fec_ratio: None,
         }
     }
 
+    pub fn with_fec_ratio(mut self, fec_ratio: Option<Arc<FecRatio>>) -> Self {
+        self.fec_ratio = fec_ratio;
+        self
+    }
+
     pub(crate) fn my_id(&self) -> Pubkey {
         self.cluster_info.id()
     }
@@ -1009,6 +1019,14 @@ impl ServeRepair {
                 }
             }
             stats.processed += 1;
+            if let (
+                Some(fec_ratio),
+                RepairProtocol::WindowIndex { slot, .. }
+                | RepairProtocol::HighestWindowIndex { slot, .. },
+            ) = (&self.fec_ratio, &request)
+            {
+                fec_ratio.record_repair_request(*slot);
+            }
             let Some(rsp) =
                 Self::handle_repair(recycler, &from_addr, blockstore, request, stats, ping_cache)
             else {
@@ -1075,6 +1093,7 @@ impl ServeRepair {
         identity_keypair: &Keypair,
         repair_request_quic_sender: &AsyncSender<(SocketAddr, Bytes)>,
         repair_protocol: Protocol,
+        repair_peer_selector: &RepairPeerSelector,
     ) -> Result<Option<(SocketAddr, Vec<u8>)>> {
         // find a peer that appears to be accepting replication and has the desired slot, as indicated
         // by a valid tvu port location
@@ -1085,13 +1104,16 @@ impl ServeRepair {
                 peers_cache.pop(&slot);
                 let repair_peers = self.repair_peers(repair_validators, slot);
                 let weights = cluster_slots.compute_weights(slot, &repair_peers);
+                let weights = repair_peer_selector.weights(&repair_peers, weights);
                 let repair_peers = RepairPeers::new(Instant::now(), &repair_peers, &weights)?;
                 peers_cache.put(slot, repair_peers);
                 peers_cache.get(&slot).unwrap()
             }
         };
         let peer = repair_peers.sample(&mut rand::thread_rng());
-        let nonce = outstanding_requests.add_request(repair_request, timestamp());
+        let now = timestamp();
+        let nonce = outstanding_requests.add_request(repair_request, now);
+        repair_peer_selector.record_request(nonce, peer.pubkey, now);
         let out = self.map_repair_request(
             &repair_request,
             &peer.pubkey,
@@ -1105,11 +1127,12 @@ impl ServeRepair {
             peer.pubkey,
             repair_request
         );
-        match repair_protocol {
-            Protocol::UDP => Ok(Some((peer.serve_repair, out))),
-            Protocol::QUIC => {
+        // Fall back to UDP if the peer does not support repair over QUIC.
+        match (repair_protocol, peer.serve_repair_quic) {
+            (Protocol::UDP, _) | (Protocol::QUIC, None) => Ok(Some((peer.serve_repair, out))),
+            (Protocol::QUIC, Some(serve_repair_quic)) => {
                 repair_request_quic_sender
-                    .blocking_send((peer.serve_repair_quic, Bytes::from(out)))
+                    .blocking_send((serve_repair_quic, Bytes::from(out)))
                     .map_err(|_| Error::SendError)?;
                 Ok(None)
             }
@@ -1122,7 +1145,7 @@ impl ServeRepair {
         cluster_slots: &ClusterSlots,
         repair_validators: &Option<HashSet<Pubkey>>,
         repair_protocol: Protocol,
-    ) -> Result<Vec<(Pubkey, SocketAddr)>> {
+    ) -> Result<Vec<(Pubkey, SocketAddr, Protocol)>> {
         let repair_peers: Vec<_> = self.repair_peers(repair_validators, slot);
         if repair_peers.is_empty() {
             return Err(ClusterInfoError::NoPeers.into());
@@ -1132,8 +1155,14 @@ impl ServeRepair {
             .shuffle(&mut rand::thread_rng())
             .map(|i| index[i])
             .filter_map(|i| {
-                let addr = repair_peers[i].serve_repair(repair_protocol)?;
-                Some((*repair_peers[i].pubkey(), addr))
+                let peer = &repair_peers[i];
+                // Fall back to UDP if the peer does not support repair over
+                // QUIC.
+                let (addr, protocol) = match peer.serve_repair(repair_protocol) {
+                    Some(addr) => (addr, repair_protocol),
+                    None => (peer.serve_repair(Protocol::UDP)?, Protocol::UDP),
+                };
+                Some((*peer.pubkey(), addr, protocol))
             })
             .take(get_ancestor_hash_repair_sample_size())
             .collect();
@@ -1400,11 +1429,6 @@ impl ServeRepair {
     }
 }
 
-#[inline]
-pub(crate) fn get_repair_protocol(_: ClusterType) -> Protocol {
-    Protocol::UDP
-}
-
 pub(crate) fn deserialize_request<T>(
     request: &RemoteRequest,
 ) -> std::result::Result<T, bincode::Error>
@@ -2020,6 +2044,7 @@ mod tests {
             &identity_keypair,
             &repair_request_quic_sender,
             Protocol::UDP, // repair_protocol
+            &RepairPeerSelector::default(),
         );
         assert_matches!(rv, Err(Error::ClusterInfo(ClusterInfoError::NoPeers)));
 
@@ -2052,6 +2077,7 @@ mod tests {
                 &identity_keypair,
                 &repair_request_quic_sender,
                 Protocol::UDP, // repair_protocol
+                &RepairPeerSelector::default(),
             )
             .unwrap()
             .unwrap();
@@ -2091,6 +2117,7 @@ mod tests {
                     &identity_keypair,
                     &repair_request_quic_sender,
                     Protocol::UDP, // repair_protocol
+                    &RepairPeerSelector::default(),
                 )
                 .unwrap()
                 .unwrap();
@@ -2104,6 +2131,68 @@ mod tests {
         assert!(one && two);
     }
 
+    #[test]
+    fn test_repair_request_quic_fallback() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let cluster_slots = ClusterSlots::default();
+        let cluster_info = Arc::new(new_test_cluster_info());
+        let serve_repair = ServeRepair::new(
+            cluster_info.clone(),
+            bank_forks,
+            Arc::new(RwLock::new(HashSet::default())),
+        );
+        let identity_keypair = cluster_info.keypair().clone();
+        let mut outstanding_requests = OutstandingShredRepairs::default();
+        let (repair_request_quic_sender, mut repair_request_quic_receiver) =
+            tokio::sync::mpsc::channel(/*buffer:*/ 128);
+        let mut repair_request = || {
+            serve_repair
+                .repair_request(
+                    &cluster_slots,
+                    ShredRepairType::Shred(0, 0),
+                    &mut LruCache::new(100),
+                    &mut RepairStats::default(),
+                    &None,
+                    &mut outstanding_requests,
+                    &identity_keypair,
+                    &repair_request_quic_sender,
+                    Protocol::QUIC, // repair_protocol
+                    &RepairPeerSelector::default(),
+                )
+                .unwrap()
+        };
+
+        // Peers which do not advertise a QUIC repair socket are sent the
+        // request over UDP.
+        let serve_repair_addr = socketaddr!(Ipv4Addr::LOCALHOST, 1243);
+        let mut node = ContactInfo::new(
+            solana_pubkey::new_rand(),
+            timestamp(), // wallclock
+            0u16,        // shred_version
+        );
+        node.set_gossip((Ipv4Addr::LOCALHOST, 1234)).unwrap();
+        node.set_tvu(Protocol::UDP, (Ipv4Addr::LOCALHOST, 1235))
+            .unwrap();
+        node.set_serve_repair(Protocol::UDP, serve_repair_addr)
+            .unwrap();
+        cluster_info.insert_info(node.clone());
+        let (addr, _) = repair_request().unwrap();
+        assert_eq!(addr, serve_repair_addr);
+        assert!(repair_request_quic_receiver.try_recv().is_err());
+
+        // The others over QUIC.
+        let serve_repair_quic_addr = socketaddr!(Ipv4Addr::LOCALHOST, 1244);
+        node.set_serve_repair(Protocol::QUIC, serve_repair_quic_addr)
+            .unwrap();
+        node.set_wallclock(timestamp() + 1);
+        cluster_info.insert_info(node);
+        assert_eq!(repair_request(), None);
+        let (addr, _) = repair_request_quic_receiver.try_recv().unwrap();
+        assert_eq!(addr, serve_repair_quic_addr);
+    }
+
     #[test]
     fn test_run_orphan() {
         run_orphan(2, 3, 9);
@@ -2359,6 +2448,7 @@ mod tests {
                     &identity_keypair,
                     &repair_request_quic_sender,
                     Protocol::UDP, // repair_protocol
+                    &RepairPeerSelector::default(),
                 ),
                 Err(Error::ClusterInfo(ClusterInfoError::NoPeers))
             );
@@ -2380,6 +2470,7 @@ mod tests {
                 &identity_keypair,
                 &repair_request_quic_sender,
                 Protocol::UDP, // repair_protocol
+                &RepairPeerSelector::default(),
             ),
             Ok(Some(_))
         );
@@ -2405,6 +2496,7 @@ mod tests {
                 &identity_keypair,
                 &repair_request_quic_sender,
                 Protocol::UDP, // repair_protocol
+                &RepairPeerSelector::default(),
             ),
             Ok(Some(_))
         );
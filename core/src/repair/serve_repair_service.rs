@@ -5,6 +5,7 @@ use {
     solana_ledger::blockstore::Blockstore,
     solana_perf::{packet::PacketBatch, recycler::Recycler},
     solana_streamer::{
+        bandwidth_accounting::Subsystem,
         socket::SocketAddrSpace,
         streamer::{self, StreamerReceiveStats},
     },
@@ -46,7 +47,10 @@ impl ServeRepairService {
             exit.clone(),
             request_sender,
             Recycler::default(),
-            Arc::new(StreamerReceiveStats::new("serve_repair_receiver")),
+            Arc::new(
+                StreamerReceiveStats::new("serve_repair_receiver")
+                    .with_subsystem(Subsystem::RepairServe),
+            ),
             Some(Duration::from_millis(1)), // coalesce
             false,                          // use_pinned_memory
             None,                           // in_vote_only_mode
@@ -59,6 +63,7 @@ impl ServeRepairService {
         let (response_sender, response_receiver) = unbounded();
         let t_responder = streamer::responder(
             "Repair",
+            Subsystem::RepairServe,
             serve_repair_socket,
             response_receiver,
             socket_addr_space,
@@ -2,8 +2,10 @@
 
 use {
     crate::{
+        bank_hash_breakdowns::BankHashBreakdownTracker,
         banking_stage::update_bank_forks_and_poh_recorder_for_new_tpu_bank,
         banking_trace::BankingTracer,
+        clock_drift_service::ClockDrift,
         cluster_info_vote_listener::{
             DuplicateConfirmedSlotsReceiver, GossipVerifiedVoteHashReceiver, VoteTracker,
         },
@@ -16,10 +18,17 @@ use {
             progress_map::{ForkProgress, ProgressMap, PropagatedStats},
             tower_storage::{SavedTower, SavedTowerVersions, TowerStorage},
             tower_vote_state::TowerVoteState,
+            vote_refresh::{
+                leader_rotation, VoteRefreshConfig, VoteRefreshMode, VoteRefreshReason,
+                VoteRefreshStats,
+            },
             BlockhashStatus, ComputedBankState, Stake, SwitchForkDecision, Tower, TowerError,
             VotedStakes, SWITCH_FORK_THRESHOLD,
         },
         cost_update_service::CostUpdate,
+        exit_reason::{self, ExitReason},
+        forensic_bundles::ForensicBundles,
+        leader_pause::LeaderPause,
         repair::{
             ancestor_hashes_service::AncestorHashesReplayUpdateSender,
             cluster_slot_state_verifier::*,
@@ -45,21 +54,28 @@ use {
             self, BlockstoreProcessorError, ConfirmationProgress, ExecuteBatchesInternalMetrics,
             ReplaySlotStats, TransactionStatusSender,
         },
+        cost_calibration::CostCalibration,
         entry_notifier_service::EntryNotifierSender,
+        entry_verification_pool::EntryVerificationPool,
         leader_schedule_cache::LeaderScheduleCache,
         leader_schedule_utils::first_of_consecutive_leader_slots,
     },
     solana_measure::measure::Measure,
     solana_poh::poh_recorder::{PohLeaderStatus, PohRecorder, GRACE_TICKS_FACTOR, MAX_GRACE_SLOTS},
     solana_rpc::{
+        account_congestion::AccountCongestionTracker,
         block_meta_service::BlockMetaSender,
         optimistically_confirmed_bank_tracker::{BankNotification, BankNotificationSenderConfig},
         rpc_subscriptions::RpcSubscriptions,
+        skipped_slot_reasons::SkippedSlotReasonsTracker,
         slot_status_notifier::SlotStatusNotifier,
     },
     solana_rpc_client_api::response::SlotUpdate,
     solana_runtime::{
-        bank::{bank_hash_details, Bank, NewBankOptions},
+        bank::{
+            bank_hash_details::{self, BankHashBreakdown},
+            Bank, NewBankOptions,
+        },
         bank_forks::{BankForks, SetRootError, MAX_ROOT_DISTANCE_FOR_VOTE_ONLY},
         commitment::BlockCommitmentCache,
         installed_scheduler_pool::BankWithScheduler,
@@ -98,13 +114,8 @@ pub const DUPLICATE_LIVENESS_THRESHOLD: f64 = 0.1;
 pub const DUPLICATE_THRESHOLD: f64 = 1.0 - SWITCH_FORK_THRESHOLD - DUPLICATE_LIVENESS_THRESHOLD;
 
 const MAX_VOTE_SIGNATURES: usize = 200;
-const MAX_VOTE_REFRESH_INTERVAL_MILLIS: usize = 5000;
 const MAX_REPAIR_RETRY_LOOP_ATTEMPTS: usize = 10;
 
-#[cfg(test)]
-static_assertions::const_assert!(REFRESH_VOTE_BLOCKHEIGHT < solana_sdk::clock::MAX_PROCESSING_AGE);
-// Give at least 4 leaders the chance to pack our vote
-const REFRESH_VOTE_BLOCKHEIGHT: usize = 16;
 #[derive(PartialEq, Eq, Debug)]
 pub enum HeaviestForkFailures {
     LockedOut(u64),
@@ -128,7 +139,15 @@ pub enum HeaviestForkFailures {
 
 enum ForkReplayMode {
     Serial,
-    Parallel(ThreadPool),
+    /// Forks which do not share a parent are replayed concurrently, each on
+    /// a thread of `fork_thread_pool` with a transaction thread pool of its
+    /// own.
+    Parallel {
+        fork_thread_pool: ThreadPool,
+        /// The transaction thread pool of each thread of `fork_thread_pool`,
+        /// indexed by thread
+        fork_tx_thread_pools: Vec<ThreadPool>,
+    },
 }
 
 enum GenerateVoteTxResult {
@@ -181,6 +200,9 @@ struct ReplaySlotFromBlockstore {
 struct LastVoteRefreshTime {
     last_refresh_time: Instant,
     last_print_time: Instant,
+    // The slot of the bank the last vote was last refreshed on.
+    last_refresh_bank_slot: Slot,
+    stats: VoteRefreshStats,
 }
 
 #[derive(Default)]
@@ -265,6 +287,12 @@ pub struct ReplayStageConfig {
     pub wait_to_vote_slot: Option<Slot>,
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
+    /// Number of threads replaying the transactions of each fork replayed
+    /// concurrently with other forks
+    pub replay_fork_transactions_threads: NonZeroUsize,
+    /// Cores the PoH verification of the replayed entries is pinned to, on a
+    /// thread pool of its own. Empty to verify on the replay thread pools
+    pub entry_verification_cpus: Vec<usize>,
     pub blockstore: Arc<Blockstore>,
     pub bank_forks: Arc<RwLock<BankForks>>,
     pub cluster_info: Arc<ClusterInfo>,
@@ -276,6 +304,21 @@ pub struct ReplayStageConfig {
     pub prioritization_fee_cache: Arc<PrioritizationFeeCache>,
     pub banking_tracer: Arc<BankingTracer>,
     pub snapshot_controller: Option<Arc<SnapshotController>>,
+    // Stops voting while the local clock drifts too far from the cluster.
+    pub clock_drift: Option<Arc<ClockDrift>>,
+    // Captures the slots frozen with a different hash than the cluster's.
+    pub forensic_bundles: Option<Arc<ForensicBundles>>,
+    pub vote_refresh_config: VoteRefreshConfig,
+    // Determines the probable causes of the skipped leader slots.
+    pub skipped_slot_reasons: Option<Arc<SkippedSlotReasonsTracker>>,
+    // Calibrates the cost model from the execution times of the replayed slots.
+    pub cost_calibration: Option<Arc<CostCalibration>>,
+    // Records the most contended accounts of the frozen banks.
+    pub account_congestion: Option<Arc<AccountCongestionTracker>>,
+    // Records the bank hash components of the frozen banks.
+    pub bank_hash_breakdowns: Option<Arc<BankHashBreakdownTracker>>,
+    // Skips the leader slots while block production is paused.
+    pub leader_pause: Option<Arc<LeaderPause>>,
 }
 
 pub struct ReplaySenders {
@@ -556,6 +599,8 @@ impl ReplayStage {
             wait_to_vote_slot,
             replay_forks_threads,
             replay_transactions_threads,
+            replay_fork_transactions_threads,
+            entry_verification_cpus,
             blockstore,
             bank_forks,
             cluster_info,
@@ -567,6 +612,14 @@ impl ReplayStage {
             prioritization_fee_cache,
             banking_tracer,
             snapshot_controller,
+            clock_drift,
+            forensic_bundles,
+            vote_refresh_config,
+            skipped_slot_reasons,
+            cost_calibration,
+            account_congestion,
+            bank_hash_breakdowns,
+            leader_pause,
         } = config;
 
         let ReplaySenders {
@@ -663,6 +716,8 @@ impl ReplayStage {
             let mut last_vote_refresh_time = LastVoteRefreshTime {
                 last_refresh_time: Instant::now(),
                 last_print_time: Instant::now(),
+                last_refresh_bank_slot: 0,
+                stats: VoteRefreshStats::default(),
             };
             let (working_bank, in_vote_only_mode) = {
                 let r_bank_forks = bank_forks.read().unwrap();
@@ -676,12 +731,24 @@ impl ReplayStage {
             let replay_mode = if replay_forks_threads.get() == 1 {
                 ForkReplayMode::Serial
             } else {
-                let pool = rayon::ThreadPoolBuilder::new()
+                let fork_thread_pool = rayon::ThreadPoolBuilder::new()
                     .num_threads(replay_forks_threads.get())
                     .thread_name(|i| format!("solReplayFork{i:02}"))
                     .build()
                     .expect("new rayon threadpool");
-                ForkReplayMode::Parallel(pool)
+                let fork_tx_thread_pools = (0..replay_forks_threads.get())
+                    .map(|fork_index| {
+                        rayon::ThreadPoolBuilder::new()
+                            .num_threads(replay_fork_transactions_threads.get())
+                            .thread_name(move |i| format!("solRpFork{fork_index}Tx{i:02}"))
+                            .build()
+                            .expect("new rayon threadpool")
+                    })
+                    .collect();
+                ForkReplayMode::Parallel {
+                    fork_thread_pool,
+                    fork_tx_thread_pools,
+                }
             };
             // Thread pool to replay multiple transactions within one block in parallel
             let replay_tx_thread_pool = rayon::ThreadPoolBuilder::new()
@@ -689,6 +756,8 @@ impl ReplayStage {
                 .thread_name(|i| format!("solReplayTx{i:02}"))
                 .build()
                 .expect("new rayon threadpool");
+            let entry_verification_pool = (!entry_verification_cpus.is_empty())
+                .then(|| EntryVerificationPool::new(entry_verification_cpus));
 
             Self::reset_poh_recorder(
                 &my_pubkey,
@@ -734,6 +803,7 @@ impl ReplayStage {
                     block_meta_sender.as_ref(),
                     entry_notification_sender.as_ref(),
                     &verify_recyclers,
+                    entry_verification_pool.as_ref(),
                     &mut heaviest_subtree_fork_choice,
                     &replay_vote_sender,
                     &bank_notification_sender,
@@ -755,10 +825,17 @@ impl ReplayStage {
                     &replay_tx_thread_pool,
                     &prioritization_fee_cache,
                     &mut purge_repair_slot_counter,
+                    skipped_slot_reasons.as_deref(),
+                    cost_calibration.as_deref(),
+                    account_congestion.as_deref(),
+                    bank_hash_breakdowns.as_deref(),
                 );
                 replay_active_banks_time.stop();
 
                 let forks_root = bank_forks.read().unwrap().root();
+                if let Some(skipped_slot_reasons) = &skipped_slot_reasons {
+                    skipped_slot_reasons.record_root(forks_root, &my_pubkey);
+                }
 
                 // Process cluster-agreed versions of duplicate slots for which we potentially
                 // have the wrong version. Our version was dead or pruned.
@@ -942,6 +1019,17 @@ impl ReplayStage {
                 );
                 select_vote_and_reset_forks_time.stop();
 
+                // A vote for a slot which is never reached is never sent, while the
+                // tower keeps tracking the fork choice.
+                let wait_to_vote_slot = if clock_drift
+                    .as_deref()
+                    .is_some_and(ClockDrift::should_refuse_to_vote)
+                {
+                    Some(Slot::MAX)
+                } else {
+                    wait_to_vote_slot
+                };
+
                 if vote_bank.is_none() {
                     Self::maybe_refresh_last_vote(
                         &mut tower,
@@ -953,6 +1041,7 @@ impl ReplayStage {
                         &mut voted_signatures,
                         has_new_vote_been_rooted,
                         &mut last_vote_refresh_time,
+                        &vote_refresh_config,
                         &voting_sender,
                         wait_to_vote_slot,
                     );
@@ -1135,6 +1224,7 @@ impl ReplayStage {
                     &dumped_slots_sender,
                     &my_pubkey,
                     &leader_schedule_cache,
+                    forensic_bundles.as_deref(),
                 );
                 dump_then_repair_correct_slots_time.stop();
 
@@ -1165,6 +1255,8 @@ impl ReplayStage {
                         &banking_tracer,
                         has_new_vote_been_rooted,
                         transaction_status_sender.is_some(),
+                        skipped_slot_reasons.as_deref(),
+                        leader_pause.as_deref(),
                     );
 
                     let poh_bank = poh_recorder.read().unwrap().bank();
@@ -1476,6 +1568,16 @@ impl ReplayStage {
         (progress, heaviest_subtree_fork_choice)
     }
 
+    // Describes the components of the bank hash of the frozen version of
+    // `slot`, so that they can be compared with the ones of the cluster's
+    // version.
+    fn bank_hash_breakdown_message(bank_forks: &RwLock<BankForks>, slot: Slot) -> String {
+        let bank = bank_forks.read().unwrap().get(slot);
+        bank.and_then(|bank| BankHashBreakdown::new_from_bank(&bank).ok())
+            .map(|breakdown| format!(". Our bank hash components: {breakdown}"))
+            .unwrap_or_default()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn dump_then_repair_correct_slots(
         duplicate_slots_to_repair: &mut DuplicateSlotsToRepair,
@@ -1489,6 +1591,7 @@ impl ReplayStage {
         dumped_slots_sender: &DumpedSlotsSender,
         my_pubkey: &Pubkey,
         leader_schedule_cache: &LeaderScheduleCache,
+        forensic_bundles: Option<&ForensicBundles>,
     ) {
         if duplicate_slots_to_repair.is_empty() {
             return;
@@ -1548,6 +1651,22 @@ impl ReplayStage {
                         );
                     }
 
+                    if let Some(forensic_bundles) = forensic_bundles {
+                        let bank = bank_forks.read().unwrap().get(*duplicate_slot);
+                        if let Some(bank) = bank.filter(|bank| bank.is_frozen()) {
+                            match forensic_bundles.capture_bank_hash_mismatch(&bank, correct_hash) {
+                                Ok(Some(manifest)) => {
+                                    info!("Captured forensic bundle {}", manifest.name)
+                                }
+                                Ok(None) => (),
+                                Err(err) => warn!(
+                                    "Failed to capture the forensic bundle of mismatched slot \
+                                     {duplicate_slot}: {err}"
+                                ),
+                            }
+                        }
+                    }
+
                     // Should not dump slots for which we were the leader
                     if Some(*my_pubkey)
                         == leader_schedule_cache.slot_leader_at(*duplicate_slot, None)
@@ -1564,13 +1683,17 @@ impl ReplayStage {
                                  while attempting to write bank hash details file"
                             );
                         }
-                        panic!(
+                        let message = format!(
                             "We are attempting to dump a block that we produced. This indicates \
                              that we are producing duplicate blocks, or that there is a bug in \
                              our runtime/replay code which causes us to compute different bank \
                              hashes than the rest of the cluster. We froze slot {duplicate_slot} \
-                             with hash {frozen_hash:?} while the cluster hash is {correct_hash}"
+                             with hash {frozen_hash:?} while the cluster hash is \
+                             {correct_hash}{}",
+                            Self::bank_hash_breakdown_message(bank_forks, *duplicate_slot),
                         );
+                        exit_reason::record(ExitReason::BankHashMismatch, &message);
+                        panic!("{message}");
                     }
 
                     let attempt_no = purge_repair_slot_counter
@@ -1578,13 +1701,17 @@ impl ReplayStage {
                         .and_modify(|x| *x += 1)
                         .or_insert(1);
                     if *attempt_no > MAX_REPAIR_RETRY_LOOP_ATTEMPTS {
-                        panic!(
+                        let message = format!(
                             "We have tried to repair duplicate slot: {duplicate_slot} more than \
                              {MAX_REPAIR_RETRY_LOOP_ATTEMPTS} times and are unable to freeze a \
                              block with bankhash {correct_hash}, instead we have a block with \
                              bankhash {frozen_hash:?}. This is most likely a bug in the runtime. \
-                             At this point manual intervention is needed to make progress. Exiting"
+                             At this point manual intervention is needed to make progress. \
+                             Exiting{}",
+                            Self::bank_hash_breakdown_message(bank_forks, *duplicate_slot),
                         );
+                        exit_reason::record(ExitReason::BankHashMismatch, &message);
+                        panic!("{message}");
                     }
 
                     Self::purge_unconfirmed_duplicate_slot(
@@ -2085,6 +2212,8 @@ impl ReplayStage {
         banking_tracer: &Arc<BankingTracer>,
         has_new_vote_been_rooted: bool,
         track_transaction_indexes: bool,
+        skipped_slot_reasons: Option<&SkippedSlotReasonsTracker>,
+        leader_pause: Option<&LeaderPause>,
     ) -> bool {
         // all the individual calls to poh_recorder.read() are designed to
         // increase granularity, decrease contention
@@ -2149,6 +2278,11 @@ impl ReplayStage {
                 return false;
             }
 
+            if let Some(leader_pause) = leader_pause.filter(|pause| pause.is_paused()) {
+                leader_pause.record_skipped_slot(poh_slot);
+                return false;
+            }
+
             datapoint_info!(
                 "replay_stage-new_leader",
                 ("slot", poh_slot, i64),
@@ -2215,6 +2349,11 @@ impl ReplayStage {
             // new()-ing of its child bank
             banking_tracer.hash_event(parent.slot(), &parent.last_blockhash(), &parent.hash());
 
+            if let Some(skipped_slot_reasons) = skipped_slot_reasons {
+                let poh_tick_height = poh_recorder.read().unwrap().tick_height();
+                skipped_slot_reasons.record_leader_block_started(&tpu_bank, poh_tick_height);
+            }
+
             update_bank_forks_and_poh_recorder_for_new_tpu_bank(
                 bank_forks,
                 poh_recorder,
@@ -2239,11 +2378,19 @@ impl ReplayStage {
         entry_notification_sender: Option<&EntryNotifierSender>,
         replay_vote_sender: &ReplayVoteSender,
         verify_recyclers: &VerifyRecyclers,
+        entry_verification_pool: Option<&EntryVerificationPool>,
         log_messages_bytes_limit: Option<usize>,
         prioritization_fee_cache: &PrioritizationFeeCache,
+        record_entry_summaries: bool,
     ) -> result::Result<usize, BlockstoreProcessorError> {
         let mut w_replay_stats = replay_stats.write().unwrap();
         let mut w_replay_progress = replay_progress.write().unwrap();
+        // The summaries are only complete if recorded from the first entry.
+        if record_entry_summaries && w_replay_progress.num_entries == 0 {
+            w_replay_progress
+                .entry_summaries
+                .get_or_insert_with(Vec::new);
+        }
         let tx_count_before = w_replay_progress.num_txs;
         // All errors must lead to marking the slot as dead, otherwise,
         // the `check_slot_agrees_with_cluster()` called by `replay_active_banks()`
@@ -2259,6 +2406,7 @@ impl ReplayStage {
             entry_notification_sender,
             Some(replay_vote_sender),
             verify_recyclers,
+            entry_verification_pool,
             false,
             log_messages_bytes_limit,
             prioritization_fee_cache,
@@ -2587,8 +2735,10 @@ impl ReplayStage {
     /// - Our latest vote attempt for `last_vote_slot` has not been cleared from the progress map
     /// - `latest_landed_vote_slot` < `last_vote_slot`
     /// - The difference in block height of `heaviest_bank_on_same_fork` and `last_vote_slot`
-    ///   is at least `REFRESH_VOTE_BLOCKHEIGHT` as indicated by the blockhash queue
-    /// - It has been at least `MAX_VOTE_REFRESH_INTERVAL_MILLIS` ms since our last refresh
+    ///   is at least the configured blockheight as indicated by the blockhash queue, or, in
+    ///   the `LeaderRotation` mode, `heaviest_bank_on_same_fork` belongs to a later leader
+    ///   rotation than the one of our last vote or refresh
+    /// - It has been at least the configured interval since our last refresh
     ///
     /// If the conditions are met, we update the timestamp and blockhash of our original vote
     /// for `last_vote_slot` and resend it to the cluster
@@ -2605,6 +2755,7 @@ impl ReplayStage {
         vote_signatures: &mut Vec<Signature>,
         has_new_vote_been_rooted: bool,
         last_vote_refresh_time: &mut LastVoteRefreshTime,
+        vote_refresh_config: &VoteRefreshConfig,
         voting_sender: &Sender<VoteOp>,
         wait_to_vote_slot: Option<Slot>,
     ) -> bool {
@@ -2649,6 +2800,9 @@ impl ReplayStage {
             );
         }
 
+        last_vote_refresh_time
+            .stats
+            .record_votes(latest_landed_vote_slot, last_voted_slot);
         if latest_landed_vote_slot >= last_voted_slot {
             // Our vote or a subsequent vote landed do not refresh
             return false;
@@ -2667,23 +2821,29 @@ impl ReplayStage {
             BlockhashStatus::Blockhash(blockhash) => Some(blockhash),
         };
 
-        if last_vote_tx_blockhash.is_some()
-            && heaviest_bank_on_same_fork
-                .is_hash_valid_for_age(&last_vote_tx_blockhash.unwrap(), REFRESH_VOTE_BLOCKHEIGHT)
-        {
+        let refresh_reason = if last_vote_tx_blockhash.is_some_and(|blockhash| {
+            heaviest_bank_on_same_fork
+                .is_hash_valid_for_age(&blockhash, vote_refresh_config.blockheight)
+        }) {
             // Check the blockhash queue to see if enough blocks have been built on our last voted fork
-            return false;
-        }
+            let last_refresh_slot =
+                last_voted_slot.max(last_vote_refresh_time.last_refresh_bank_slot);
+            if vote_refresh_config.mode != VoteRefreshMode::LeaderRotation
+                || leader_rotation(heaviest_bank_on_same_fork.slot())
+                    <= leader_rotation(last_refresh_slot)
+            {
+                return false;
+            }
+            // A new leader is building on our last voted fork without our vote
+            VoteRefreshReason::LeaderRotation
+        } else {
+            VoteRefreshReason::Blockheight
+        };
 
-        if last_vote_refresh_time
-            .last_refresh_time
-            .elapsed()
-            .as_millis()
-            < MAX_VOTE_REFRESH_INTERVAL_MILLIS as u128
-        {
+        if last_vote_refresh_time.last_refresh_time.elapsed() < vote_refresh_config.min_interval {
             // This avoids duplicate refresh in case there are multiple forks descending from our last voted fork
             // It also ensures that if the first refresh fails we will continue attempting to refresh at an interval no less
-            // than MAX_VOTE_REFRESH_INTERVAL_MILLIS
+            // than the configured interval
             return false;
         }
 
@@ -2698,6 +2858,7 @@ impl ReplayStage {
             vote_signatures,
             has_new_vote_been_rooted,
             last_vote_refresh_time,
+            refresh_reason,
             voting_sender,
             wait_to_vote_slot,
         )
@@ -2714,6 +2875,7 @@ impl ReplayStage {
         vote_signatures: &mut Vec<Signature>,
         has_new_vote_been_rooted: bool,
         last_vote_refresh_time: &mut LastVoteRefreshTime,
+        refresh_reason: VoteRefreshReason,
         voting_sender: &Sender<VoteOp>,
         wait_to_vote_slot: Option<Slot>,
     ) -> bool {
@@ -2735,6 +2897,8 @@ impl ReplayStage {
         if let GenerateVoteTxResult::Tx(vote_tx) = vote_tx_result {
             let recent_blockhash = vote_tx.message.recent_blockhash;
             tower.refresh_last_vote_tx_blockhash(recent_blockhash);
+            last_vote_refresh_time.stats.record_refresh(last_voted_slot);
+            last_vote_refresh_time.last_refresh_bank_slot = heaviest_bank_on_same_fork.slot();
 
             // Send the votes to the TPU and gossip for network propagation
             let hash_string = format!("{recent_blockhash}");
@@ -2743,6 +2907,12 @@ impl ReplayStage {
                 ("last_voted_slot", last_voted_slot, i64),
                 ("target_bank_slot", heaviest_bank_on_same_fork.slot(), i64),
                 ("target_bank_hash", hash_string, String),
+                ("reason", <&str>::from(refresh_reason), String),
+                (
+                    "num_refreshes",
+                    last_vote_refresh_time.stats.num_refreshes(),
+                    i64
+                ),
             );
             voting_sender
                 .send(VoteOp::RefreshVote {
@@ -2859,112 +3029,163 @@ impl ReplayStage {
         );
     }
 
+    /// Returns the active banks grouped by fork, where the banks of a fork
+    /// share their parent, in the order of the first bank of each fork.
+    fn group_active_banks_by_fork(
+        bank_forks: &RwLock<BankForks>,
+        active_bank_slots: &[Slot],
+    ) -> Vec<Vec<Slot>> {
+        let bank_forks = bank_forks.read().unwrap();
+        let mut forks: Vec<(Option<Slot>, Vec<Slot>)> = vec![];
+        for &slot in active_bank_slots {
+            let parent_slot = bank_forks.get(slot).map(|bank| bank.parent_slot());
+            match forks
+                .iter_mut()
+                .find(|(parent, _)| parent.is_some() && *parent == parent_slot)
+            {
+                Some((_, slots)) => slots.push(slot),
+                None => forks.push((parent_slot, vec![slot])),
+            }
+        }
+        forks.into_iter().map(|(_, slots)| slots).collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn replay_active_banks_concurrently(
         blockstore: &Blockstore,
         bank_forks: &RwLock<BankForks>,
         fork_thread_pool: &ThreadPool,
-        replay_tx_thread_pool: &ThreadPool,
+        fork_tx_thread_pools: &[ThreadPool],
         my_pubkey: &Pubkey,
         vote_account: &Pubkey,
         progress: &mut ProgressMap,
         transaction_status_sender: Option<&TransactionStatusSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verification_pool: Option<&EntryVerificationPool>,
         replay_vote_sender: &ReplayVoteSender,
         replay_timing: &mut ReplayLoopTiming,
         log_messages_bytes_limit: Option<usize>,
-        active_bank_slots: &[Slot],
+        forks: &[Vec<Slot>],
         prioritization_fee_cache: &PrioritizationFeeCache,
+        record_entry_summaries: bool,
     ) -> Vec<ReplaySlotFromBlockstore> {
         // Make mutable shared structures thread safe.
         let progress = RwLock::new(progress);
         let longest_replay_time_us = AtomicU64::new(0);
 
-        // Allow for concurrent replaying of slots from different forks.
+        // Allow for concurrent replaying of slots from different forks. The
+        // banks of a fork are replayed one after the other.
         let replay_result_vec: Vec<ReplaySlotFromBlockstore> = fork_thread_pool.install(|| {
-            active_bank_slots
+            forks
                 .into_par_iter()
-                .map(|bank_slot| {
-                    let bank_slot = *bank_slot;
-                    let mut replay_result = ReplaySlotFromBlockstore {
-                        is_slot_dead: false,
-                        bank_slot,
-                        replay_result: None,
-                    };
-                    let my_pubkey = &my_pubkey.clone();
-                    trace!(
-                        "Replay active bank: slot {}, thread_idx {}",
-                        bank_slot,
-                        fork_thread_pool.current_thread_index().unwrap_or_default()
-                    );
-                    let mut progress_lock = progress.write().unwrap();
-                    if progress_lock
-                        .get(&bank_slot)
-                        .map(|p| p.is_dead)
-                        .unwrap_or(false)
-                    {
-                        // If the fork was marked as dead, don't replay it
-                        debug!("bank_slot {:?} is marked dead", bank_slot);
-                        replay_result.is_slot_dead = true;
-                        return replay_result;
-                    }
-
-                    let bank = bank_forks
-                        .read()
-                        .unwrap()
-                        .get_with_scheduler(bank_slot)
-                        .unwrap();
-                    let parent_slot = bank.parent_slot();
-                    let (num_blocks_on_fork, num_dropped_blocks_on_fork) = {
-                        let stats = progress_lock
-                            .get(&parent_slot)
-                            .expect("parent of active bank must exist in progress map");
-                        let num_blocks_on_fork = stats.num_blocks_on_fork + 1;
-                        let new_dropped_blocks = bank.slot() - parent_slot - 1;
-                        let num_dropped_blocks_on_fork =
-                            stats.num_dropped_blocks_on_fork + new_dropped_blocks;
-                        (num_blocks_on_fork, num_dropped_blocks_on_fork)
-                    };
-                    let prev_leader_slot = progress_lock.get_bank_prev_leader_slot(&bank);
-
-                    let bank_progress = progress_lock.entry(bank.slot()).or_insert_with(|| {
-                        ForkProgress::new_from_bank(
-                            &bank,
-                            my_pubkey,
-                            &vote_account.clone(),
-                            prev_leader_slot,
-                            num_blocks_on_fork,
-                            num_dropped_blocks_on_fork,
-                        )
-                    });
-
-                    let replay_stats = bank_progress.replay_stats.clone();
-                    let replay_progress = bank_progress.replay_progress.clone();
-                    drop(progress_lock);
-
-                    if bank.collector_id() != my_pubkey {
-                        let mut replay_blockstore_time =
-                            Measure::start("replay_blockstore_into_bank");
-                        let blockstore_result = Self::replay_blockstore_into_bank(
-                            &bank,
-                            blockstore,
-                            replay_tx_thread_pool,
-                            &replay_stats,
-                            &replay_progress,
-                            transaction_status_sender,
-                            entry_notification_sender,
-                            &replay_vote_sender.clone(),
-                            &verify_recyclers.clone(),
-                            log_messages_bytes_limit,
-                            prioritization_fee_cache,
+                .flat_map_iter(|fork| {
+                    let fork_index = fork_thread_pool.current_thread_index().unwrap_or_default();
+                    let replay_tx_thread_pool = &fork_tx_thread_pools[fork_index];
+                    let mut fork_replay_time = Measure::start("replay_fork");
+                    let mut num_transactions = 0;
+                    let replay_results: Vec<_> = fork
+                        .iter()
+                        .map(|&bank_slot| {
+                            let mut replay_result = ReplaySlotFromBlockstore {
+                                is_slot_dead: false,
+                                bank_slot,
+                                replay_result: None,
+                            };
+                            let my_pubkey = &my_pubkey.clone();
+                            trace!(
+                                "Replay active bank: slot {}, thread_idx {}",
+                                bank_slot,
+                                fork_index
+                            );
+                            let mut progress_lock = progress.write().unwrap();
+                            if progress_lock
+                                .get(&bank_slot)
+                                .map(|p| p.is_dead)
+                                .unwrap_or(false)
+                            {
+                                // If the fork was marked as dead, don't replay it
+                                debug!("bank_slot {:?} is marked dead", bank_slot);
+                                replay_result.is_slot_dead = true;
+                                return replay_result;
+                            }
+
+                            let bank = bank_forks
+                                .read()
+                                .unwrap()
+                                .get_with_scheduler(bank_slot)
+                                .unwrap();
+                            let parent_slot = bank.parent_slot();
+                            let (num_blocks_on_fork, num_dropped_blocks_on_fork) = {
+                                let stats = progress_lock
+                                    .get(&parent_slot)
+                                    .expect("parent of active bank must exist in progress map");
+                                let num_blocks_on_fork = stats.num_blocks_on_fork + 1;
+                                let new_dropped_blocks = bank.slot() - parent_slot - 1;
+                                let num_dropped_blocks_on_fork =
+                                    stats.num_dropped_blocks_on_fork + new_dropped_blocks;
+                                (num_blocks_on_fork, num_dropped_blocks_on_fork)
+                            };
+                            let prev_leader_slot = progress_lock.get_bank_prev_leader_slot(&bank);
+
+                            let bank_progress =
+                                progress_lock.entry(bank.slot()).or_insert_with(|| {
+                                    ForkProgress::new_from_bank(
+                                        &bank,
+                                        my_pubkey,
+                                        &vote_account.clone(),
+                                        prev_leader_slot,
+                                        num_blocks_on_fork,
+                                        num_dropped_blocks_on_fork,
+                                    )
+                                });
+
+                            let replay_stats = bank_progress.replay_stats.clone();
+                            let replay_progress = bank_progress.replay_progress.clone();
+                            drop(progress_lock);
+
+                            if bank.collector_id() != my_pubkey {
+                                let mut replay_blockstore_time =
+                                    Measure::start("replay_blockstore_into_bank");
+                                let blockstore_result = Self::replay_blockstore_into_bank(
+                                    &bank,
+                                    blockstore,
+                                    replay_tx_thread_pool,
+                                    &replay_stats,
+                                    &replay_progress,
+                                    transaction_status_sender,
+                                    entry_notification_sender,
+                                    &replay_vote_sender.clone(),
+                                    &verify_recyclers.clone(),
+                                    entry_verification_pool,
+                                    log_messages_bytes_limit,
+                                    prioritization_fee_cache,
+                                    record_entry_summaries,
+                                );
+                                replay_blockstore_time.stop();
+                                if let Ok(tx_count) = &blockstore_result {
+                                    num_transactions += *tx_count;
+                                }
+                                replay_result.replay_result = Some(blockstore_result);
+                                longest_replay_time_us
+                                    .fetch_max(replay_blockstore_time.as_us(), Ordering::Relaxed);
+                            }
+                            replay_result
+                        })
+                        .collect();
+                    fork_replay_time.stop();
+                    if num_transactions > 0 {
+                        datapoint_info!(
+                            "replay_stage-fork_progress",
+                            ("first_slot", fork[0], i64),
+                            ("num_banks", fork.len(), i64),
+                            ("num_forks", forks.len(), i64),
+                            ("fork_index", fork_index, i64),
+                            ("num_transactions", num_transactions, i64),
+                            ("replay_us", fork_replay_time.as_us(), i64),
                         );
-                        replay_blockstore_time.stop();
-                        replay_result.replay_result = Some(blockstore_result);
-                        longest_replay_time_us
-                            .fetch_max(replay_blockstore_time.as_us(), Ordering::Relaxed);
                     }
-                    replay_result
+                    replay_results
                 })
                 .collect()
         });
@@ -2986,11 +3207,13 @@ impl ReplayStage {
         transaction_status_sender: Option<&TransactionStatusSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verification_pool: Option<&EntryVerificationPool>,
         replay_vote_sender: &ReplayVoteSender,
         replay_timing: &mut ReplayLoopTiming,
         log_messages_bytes_limit: Option<usize>,
         bank_slot: Slot,
         prioritization_fee_cache: &PrioritizationFeeCache,
+        record_entry_summaries: bool,
     ) -> ReplaySlotFromBlockstore {
         let mut replay_result = ReplaySlotFromBlockstore {
             is_slot_dead: false,
@@ -3045,8 +3268,10 @@ impl ReplayStage {
                     entry_notification_sender,
                     &replay_vote_sender.clone(),
                     &verify_recyclers.clone(),
+                    entry_verification_pool,
                     log_messages_bytes_limit,
                     prioritization_fee_cache,
+                    record_entry_summaries,
                 );
                 replay_blockstore_time.stop();
                 replay_result.replay_result = Some(blockstore_result);
@@ -3080,6 +3305,10 @@ impl ReplayStage {
         replay_result_vec: &[ReplaySlotFromBlockstore],
         purge_repair_slot_counter: &mut PurgeRepairSlotCounter,
         my_pubkey: &Pubkey,
+        skipped_slot_reasons: Option<&SkippedSlotReasonsTracker>,
+        cost_calibration: Option<&CostCalibration>,
+        account_congestion: Option<&AccountCongestionTracker>,
+        bank_hash_breakdowns: Option<&BankHashBreakdownTracker>,
     ) -> bool {
         // TODO: See if processing of blockstore replay results and bank completion can be made thread safe.
         let mut did_complete_bank = false;
@@ -3227,6 +3456,17 @@ impl ReplayStage {
                     ("slot", bank_slot, i64),
                     ("hash", bank.hash().to_string(), String),
                 );
+                if is_leader_block {
+                    if let Some(skipped_slot_reasons) = skipped_slot_reasons {
+                        skipped_slot_reasons.record_leader_block_completed(bank);
+                    }
+                }
+                if let Some(account_congestion) = account_congestion {
+                    account_congestion.record_frozen_bank(bank);
+                }
+                if let Some(bank_hash_breakdowns) = bank_hash_breakdowns {
+                    bank_hash_breakdowns.record_frozen_bank(bank);
+                }
                 // report cost tracker stats
                 cost_update_sender
                     .send(CostUpdate::FrozenBank {
@@ -3328,6 +3568,7 @@ impl ReplayStage {
                         Some(bank.block_height()),
                         bank.executed_transaction_count(),
                         r_replay_progress.num_entries as u64,
+                        r_replay_progress.entry_summaries.as_deref(),
                     )
                 }
                 bank_complete_time.stop();
@@ -3341,6 +3582,15 @@ impl ReplayStage {
                     is_unified_scheduler_enabled,
                 );
                 execute_timings.accumulate(&r_replay_stats.batch_execute.totals);
+                if let Some(cost_calibration) = cost_calibration {
+                    cost_calibration.record_program_timings(
+                        &r_replay_stats
+                            .batch_execute
+                            .totals
+                            .details
+                            .per_program_timings,
+                    );
+                }
             } else {
                 trace!(
                     "bank {} not completed tick_height: {}, max_tick_height: {}",
@@ -3365,6 +3615,7 @@ impl ReplayStage {
         block_meta_sender: Option<&BlockMetaSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verification_pool: Option<&EntryVerificationPool>,
         heaviest_subtree_fork_choice: &mut HeaviestSubtreeForkChoice,
         replay_vote_sender: &ReplayVoteSender,
         bank_notification_sender: &Option<BankNotificationSenderConfig>,
@@ -3386,6 +3637,10 @@ impl ReplayStage {
         replay_tx_thread_pool: &ThreadPool,
         prioritization_fee_cache: &PrioritizationFeeCache,
         purge_repair_slot_counter: &mut PurgeRepairSlotCounter,
+        skipped_slot_reasons: Option<&SkippedSlotReasonsTracker>,
+        cost_calibration: Option<&CostCalibration>,
+        account_congestion: Option<&AccountCongestionTracker>,
+        bank_hash_breakdowns: Option<&BankHashBreakdownTracker>,
     ) -> bool /* completed a bank */ {
         let active_bank_slots = bank_forks.read().unwrap().active_bank_slots();
         let num_active_banks = active_bank_slots.len();
@@ -3398,28 +3653,40 @@ impl ReplayStage {
             return false;
         }
 
-        let replay_result_vec = match replay_mode {
-            // Skip the overhead of the threadpool if there is only one bank to play
-            ForkReplayMode::Parallel(fork_thread_pool) if num_active_banks > 1 => {
-                Self::replay_active_banks_concurrently(
-                    blockstore,
-                    bank_forks,
-                    fork_thread_pool,
-                    replay_tx_thread_pool,
-                    my_pubkey,
-                    vote_account,
-                    progress,
-                    transaction_status_sender,
-                    entry_notification_sender,
-                    verify_recyclers,
-                    replay_vote_sender,
-                    replay_timing,
-                    log_messages_bytes_limit,
-                    &active_bank_slots,
-                    prioritization_fee_cache,
-                )
+        let record_entry_summaries = block_metadata_notifier
+            .as_ref()
+            .is_some_and(|notifier| notifier.entry_summaries_enabled());
+        let forks = match replay_mode {
+            ForkReplayMode::Parallel { .. } if num_active_banks > 1 => {
+                Self::group_active_banks_by_fork(bank_forks, &active_bank_slots)
             }
-            ForkReplayMode::Serial | ForkReplayMode::Parallel(_) => active_bank_slots
+            ForkReplayMode::Serial | ForkReplayMode::Parallel { .. } => vec![],
+        };
+        let replay_result_vec = match replay_mode {
+            // Skip the overhead of the threadpool if there is only one fork to play
+            ForkReplayMode::Parallel {
+                fork_thread_pool,
+                fork_tx_thread_pools,
+            } if forks.len() > 1 => Self::replay_active_banks_concurrently(
+                blockstore,
+                bank_forks,
+                fork_thread_pool,
+                fork_tx_thread_pools,
+                my_pubkey,
+                vote_account,
+                progress,
+                transaction_status_sender,
+                entry_notification_sender,
+                verify_recyclers,
+                entry_verification_pool,
+                replay_vote_sender,
+                replay_timing,
+                log_messages_bytes_limit,
+                &forks,
+                prioritization_fee_cache,
+                record_entry_summaries,
+            ),
+            ForkReplayMode::Serial | ForkReplayMode::Parallel { .. } => active_bank_slots
                 .iter()
                 .map(|bank_slot| {
                     Self::replay_active_bank(
@@ -3432,11 +3699,13 @@ impl ReplayStage {
                         transaction_status_sender,
                         entry_notification_sender,
                         verify_recyclers,
+                        entry_verification_pool,
                         replay_vote_sender,
                         replay_timing,
                         log_messages_bytes_limit,
                         *bank_slot,
                         prioritization_fee_cache,
+                        record_entry_summaries,
                     )
                 })
                 .collect(),
@@ -3465,6 +3734,10 @@ impl ReplayStage {
             &replay_result_vec,
             purge_repair_slot_counter,
             my_pubkey,
+            skipped_slot_reasons,
+            cost_calibration,
+            account_congestion,
+            bank_hash_breakdowns,
         )
     }
 
@@ -4333,6 +4606,9 @@ pub(crate) mod tests {
                 progress_map::{ValidatorStakeInfo, RETRANSMIT_BASE_DELAY_MS},
                 tower_storage::{FileTowerStorage, NullTowerStorage},
                 tree_diff::TreeDiff,
+                vote_refresh::{
+                    DEFAULT_VOTE_REFRESH_BLOCKHEIGHT, DEFAULT_VOTE_REFRESH_INTERVAL_MS,
+                },
                 ThresholdDecision, Tower, VOTE_THRESHOLD_DEPTH,
             },
             replay_stage::ReplayStage,
@@ -4356,6 +4632,7 @@ pub(crate) mod tests {
         solana_rpc::{
             optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
             rpc::{create_test_transaction_entries, populate_blockstore_for_tests},
+            slot_account_filter::SlotAccountFilter,
             slot_status_notifier::SlotStatusNotifierInterface,
         },
         solana_runtime::{
@@ -4650,6 +4927,28 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn test_group_active_banks_by_fork() {
+        let genesis_config = create_genesis_config(10_000).genesis_config;
+        let bank0 = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank0);
+        for (slot, parent_slot) in [(1, 0), (2, 0), (3, 1), (4, 3), (5, 0)] {
+            let parent = bank_forks.read().unwrap().get(parent_slot).unwrap();
+            let bank = Bank::new_from_parent(parent, &Pubkey::default(), slot);
+            bank_forks.write().unwrap().insert(bank);
+        }
+        // The banks sharing a parent are in the same fork, and a missing bank
+        // is a fork of its own.
+        assert_eq!(
+            ReplayStage::group_active_banks_by_fork(&bank_forks, &[2, 4, 5, 9, 3]),
+            vec![vec![2, 5], vec![4], vec![9], vec![3]]
+        );
+        assert_eq!(
+            ReplayStage::group_active_banks_by_fork(&bank_forks, &[9, 10]),
+            vec![vec![9], vec![10]]
+        );
+    }
+
     #[test]
     fn test_handle_new_root() {
         let genesis_config = create_genesis_config(10_000).genesis_config;
@@ -5051,6 +5350,8 @@ pub(crate) mod tests {
         fn notify_slot_dead(&self, slot: Slot, _error: String) {
             self.dead_slots.lock().unwrap().insert(slot);
         }
+
+        fn notify_slot_account_filter(&self, _filter: &SlotAccountFilter) {}
     }
 
     // Given a shred and a fatal expected error, check that replaying that shred causes causes the fork to be
@@ -5107,7 +5408,9 @@ pub(crate) mod tests {
                 &replay_vote_sender,
                 &VerifyRecyclers::default(),
                 None,
+                None,
                 &PrioritizationFeeCache::new(0u64),
+                false,
             );
             let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
             let max_complete_rewards_slot = Arc::new(AtomicU64::default());
@@ -7166,6 +7469,7 @@ pub(crate) mod tests {
             &dumped_slots_sender,
             &Pubkey::new_unique(),
             leader_schedule_cache,
+            None,
         );
         assert_eq!(should_be_dumped, dumped_slots_receiver.recv().ok().unwrap());
 
@@ -7285,6 +7589,7 @@ pub(crate) mod tests {
             &dumped_slots_sender,
             &Pubkey::new_unique(),
             leader_schedule_cache,
+            None,
         );
 
         // Check everything was purged properly
@@ -7607,6 +7912,8 @@ pub(crate) mod tests {
         let mut last_vote_refresh_time = LastVoteRefreshTime {
             last_refresh_time: Instant::now(),
             last_print_time: Instant::now(),
+            last_refresh_bank_slot: 0,
+            stats: VoteRefreshStats::default(),
         };
         let has_new_vote_been_rooted = false;
         let mut voted_signatures = vec![];
@@ -7740,6 +8047,7 @@ pub(crate) mod tests {
                 &mut voted_signatures,
                 has_new_vote_been_rooted,
                 &mut last_vote_refresh_time,
+                &VoteRefreshConfig::default(),
                 &voting_sender,
                 None,
             ));
@@ -7829,6 +8137,7 @@ pub(crate) mod tests {
             &mut voted_signatures,
             has_new_vote_been_rooted,
             &mut last_vote_refresh_time,
+            &VoteRefreshConfig::default(),
             &voting_sender,
             None,
         ));
@@ -7845,7 +8154,7 @@ pub(crate) mod tests {
         // Create a bank where the last vote transaction will have expired
         let expired_bank = {
             let mut parent_bank = bank2.clone();
-            for _ in 0..REFRESH_VOTE_BLOCKHEIGHT {
+            for _ in 0..DEFAULT_VOTE_REFRESH_BLOCKHEIGHT {
                 let slot = parent_bank.slot() + 1;
                 parent_bank = new_bank_from_parent_with_bank_forks(
                     bank_forks.as_ref(),
@@ -7882,9 +8191,7 @@ pub(crate) mod tests {
             .block_height = expired_bank.block_height();
         last_vote_refresh_time.last_refresh_time = last_vote_refresh_time
             .last_refresh_time
-            .checked_sub(Duration::from_millis(
-                MAX_VOTE_REFRESH_INTERVAL_MILLIS as u64 + 1,
-            ))
+            .checked_sub(Duration::from_millis(DEFAULT_VOTE_REFRESH_INTERVAL_MS + 1))
             .unwrap();
         let clone_refresh_time = last_vote_refresh_time.last_refresh_time;
         assert!(ReplayStage::maybe_refresh_last_vote(
@@ -7897,6 +8204,7 @@ pub(crate) mod tests {
             &mut voted_signatures,
             has_new_vote_been_rooted,
             &mut last_vote_refresh_time,
+            &VoteRefreshConfig::default(),
             &voting_sender,
             None,
         ));
@@ -7963,7 +8271,7 @@ pub(crate) mod tests {
         // Trying to refresh the vote on a sibling bank where:
         // 1) The vote for slot 1 hasn't landed
         // 2) The blockheight is still eligble for a refresh
-        // This will still not refresh because `MAX_VOTE_REFRESH_INTERVAL_MILLIS` has not expired yet
+        // This will still not refresh because the refresh interval has not expired yet
         let expired_bank_sibling = {
             let mut parent_bank = bank2.clone();
             for i in 0..expired_bank_child_slot {
@@ -7991,6 +8299,7 @@ pub(crate) mod tests {
             &mut voted_signatures,
             has_new_vote_been_rooted,
             &mut last_vote_refresh_time,
+            &VoteRefreshConfig::default(),
             &voting_sender,
             None,
         );
@@ -8651,6 +8960,7 @@ pub(crate) mod tests {
             &dumped_slots_sender,
             my_pubkey,
             &leader_schedule_cache,
+            None,
         );
         assert_eq!(
             dumped_slots_receiver.recv_timeout(Duration::from_secs(1)),
@@ -8683,6 +8993,8 @@ pub(crate) mod tests {
             &banking_tracer,
             has_new_vote_been_rooted,
             track_transaction_indexes,
+            None,
+            None,
         ));
     }
 
@@ -8731,6 +9043,7 @@ pub(crate) mod tests {
             &dumped_slots_sender,
             my_pubkey,
             leader_schedule_cache,
+            None,
         );
     }
 
@@ -9346,6 +9659,8 @@ pub(crate) mod tests {
             &banking_tracer,
             has_new_vote_been_rooted,
             track_transaction_indexes,
+            None,
+            None,
         ));
 
         // Register another slots worth of ticks  with PoH recorder
@@ -9373,6 +9688,8 @@ pub(crate) mod tests {
             &banking_tracer,
             has_new_vote_been_rooted,
             track_transaction_indexes,
+            None,
+            None,
         ));
         // Get the new working bank, which is also the new leader bank/slot
         let working_bank = bank_forks.read().unwrap().working_bank();
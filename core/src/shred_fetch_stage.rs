@@ -1,7 +1,10 @@
 //! The `shred_fetch_stage` pulls shreds from UDP sockets and sends it to a channel.
 
 use {
-    crate::repair::{repair_service::OutstandingShredRepairs, serve_repair::ServeRepair},
+    crate::repair::{
+        repair_peer_selector::RepairPeerSelector, repair_service::OutstandingShredRepairs,
+        serve_repair::ServeRepair,
+    },
     agave_feature_set::{self as feature_set, FeatureSet},
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
@@ -11,6 +14,7 @@ use {
     solana_perf::packet::{
         Packet, PacketBatch, PacketBatchRecycler, PacketFlags, PACKETS_PER_BATCH,
     },
+    solana_rpc::shred_latency::ShredLatencyTracker,
     solana_runtime::bank_forks::BankForks,
     solana_sdk::{
         clock::{Slot, DEFAULT_MS_PER_SLOT},
@@ -18,8 +22,12 @@ use {
         packet::{Meta, PACKET_DATA_SIZE},
         pubkey::Pubkey,
         signature::Keypair,
+        timing::timestamp,
+    },
+    solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
+        streamer::{self, PacketBatchReceiver, StreamerReceiveStats},
     },
-    solana_streamer::streamer::{self, PacketBatchReceiver, StreamerReceiveStats},
     std::{
         net::{SocketAddr, UdpSocket},
         sync::{
@@ -47,6 +55,7 @@ struct RepairContext {
     repair_socket: Arc<UdpSocket>,
     cluster_info: Arc<ClusterInfo>,
     outstanding_repair_requests: Arc<RwLock<OutstandingShredRepairs>>,
+    repair_peer_selector: Arc<RepairPeerSelector>,
 }
 
 impl ShredFetchStage {
@@ -60,6 +69,8 @@ impl ShredFetchStage {
         name: &'static str,
         flags: PacketFlags,
         repair_context: Option<&RepairContext>,
+        // Only turbine shreds are recorded.
+        shred_latency_tracker: Option<&ShredLatencyTracker>,
         turbine_disabled: Arc<AtomicBool>,
     ) {
         // Only repair shreds need repair context.
@@ -117,7 +128,7 @@ impl ShredFetchStage {
                     );
                 }
                 // Discard packets if repair nonce does not verify.
-                let now = solana_sdk::timing::timestamp();
+                let now = timestamp();
                 let mut outstanding_repair_requests =
                     repair_context.outstanding_repair_requests.write().unwrap();
                 packet_batch
@@ -127,7 +138,12 @@ impl ShredFetchStage {
                         // Have to set repair flag here so that the nonce is
                         // taken off the shred's payload.
                         packet.meta_mut().flags |= PacketFlags::REPAIR;
-                        if !verify_repair_nonce(packet, now, &mut outstanding_repair_requests) {
+                        if !verify_repair_nonce(
+                            packet,
+                            now,
+                            &mut outstanding_repair_requests,
+                            &repair_context.repair_peer_selector,
+                        ) {
                             packet.meta_mut().set_discard(true);
                         }
                     });
@@ -161,6 +177,15 @@ impl ShredFetchStage {
                     packet.meta_mut().flags.insert(flags);
                 }
             }
+            if let Some(shred_latency_tracker) = shred_latency_tracker {
+                let shred_slots = packet_batch
+                    .iter()
+                    .filter(|packet| !packet.meta().discard())
+                    .filter_map(|packet| {
+                        shred::layout::get_slot(shred::layout::get_shred(packet)?)
+                    });
+                shred_latency_tracker.record(shred_slots, timestamp());
+            }
             if stats.maybe_submit(name, STATS_SUBMIT_CADENCE) {
                 if let Some(stats) = recvr_stats.as_ref() {
                     stats.report();
@@ -186,10 +211,13 @@ impl ShredFetchStage {
         receiver_name: &'static str,
         flags: PacketFlags,
         repair_context: Option<RepairContext>,
+        shred_latency_tracker: Option<Arc<ShredLatencyTracker>>,
         turbine_disabled: Arc<AtomicBool>,
     ) -> (Vec<JoinHandle<()>>, JoinHandle<()>) {
         let (packet_sender, packet_receiver) = unbounded();
-        let receiver_stats = Arc::new(StreamerReceiveStats::new(receiver_name));
+        let receiver_stats = Arc::new(
+            StreamerReceiveStats::new(receiver_name).with_subsystem(shreds_subsystem(flags)),
+        );
         let streamers = sockets
             .into_iter()
             .enumerate()
@@ -220,6 +248,7 @@ impl ShredFetchStage {
                     name,
                     flags,
                     repair_context.as_ref(),
+                    shred_latency_tracker.as_deref(),
                     turbine_disabled,
                 )
             })
@@ -238,14 +267,19 @@ impl ShredFetchStage {
         bank_forks: Arc<RwLock<BankForks>>,
         cluster_info: Arc<ClusterInfo>,
         outstanding_repair_requests: Arc<RwLock<OutstandingShredRepairs>>,
+        repair_peer_selector: Arc<RepairPeerSelector>,
+        shred_latency_tracker: Arc<ShredLatencyTracker>,
         turbine_disabled: Arc<AtomicBool>,
         exit: Arc<AtomicBool>,
     ) -> Self {
         let recycler = PacketBatchRecycler::warmed(100, 1024);
+        let shred_latency_tracker =
+            Some(shred_latency_tracker).filter(|tracker| tracker.is_enabled());
         let repair_context = RepairContext {
             repair_socket: repair_socket.clone(),
             cluster_info,
             outstanding_repair_requests,
+            repair_peer_selector,
         };
 
         let (mut tvu_threads, tvu_filter) = Self::packet_modifier(
@@ -261,6 +295,7 @@ impl ShredFetchStage {
             "shred_fetch_receiver",
             PacketFlags::empty(),
             None, // repair_context
+            shred_latency_tracker.clone(),
             turbine_disabled.clone(),
         );
 
@@ -277,6 +312,7 @@ impl ShredFetchStage {
             "shred_fetch_repair_receiver",
             PacketFlags::REPAIR,
             Some(repair_context.clone()),
+            None, // shred_latency_tracker
             turbine_disabled.clone(),
         );
 
@@ -317,6 +353,7 @@ impl ShredFetchStage {
                             PacketFlags::REPAIR,
                             // No ping packets but need to verify repair nonce.
                             Some(&repair_context),
+                            None, // shred_latency_tracker
                             turbine_disabled,
                         )
                     })
@@ -350,6 +387,7 @@ impl ShredFetchStage {
                         "shred_fetch_quic",
                         PacketFlags::empty(),
                         None, // repair_context
+                        shred_latency_tracker.as_deref(),
                         turbine_disabled,
                     )
                 })
@@ -380,14 +418,19 @@ fn verify_repair_nonce(
     packet: &Packet,
     now: u64, // solana_sdk::timing::timestamp()
     outstanding_repair_requests: &mut OutstandingShredRepairs,
+    repair_peer_selector: &RepairPeerSelector,
 ) -> bool {
     debug_assert!(packet.meta().flags.contains(PacketFlags::REPAIR));
     let Some((shred, Some(nonce))) = shred::layout::get_shred_and_repair_nonce(packet) else {
         return false;
     };
-    outstanding_repair_requests
+    let verified = outstanding_repair_requests
         .register_response(nonce, shred, now, |_| ())
-        .is_some()
+        .is_some();
+    if verified {
+        repair_peer_selector.record_response(nonce, now);
+    }
+    verified
 }
 
 pub(crate) fn receive_quic_datagrams(
@@ -430,6 +473,8 @@ pub(crate) fn receive_quic_datagrams(
             .count();
         if size > 0 {
             packet_batch.truncate(size);
+            let num_bytes = packet_batch.iter().map(|packet| packet.meta().size).sum();
+            bandwidth_accounting::record_ingress(shreds_subsystem(flags), size, num_bytes);
             if sender.send(packet_batch).is_err() {
                 return; // The receiver end of the channel is disconnected.
             }
@@ -437,6 +482,15 @@ pub(crate) fn receive_quic_datagrams(
     }
 }
 
+// The subsystem the shreds received with the flags are attributed to
+fn shreds_subsystem(flags: PacketFlags) -> Subsystem {
+    if flags.contains(PacketFlags::REPAIR) {
+        Subsystem::Repair
+    } else {
+        Subsystem::Turbine
+    }
+}
+
 // Returns true if the feature is effective for the shred slot.
 #[must_use]
 fn check_feature_activation(
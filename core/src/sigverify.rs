@@ -10,11 +10,13 @@ pub use solana_perf::sigverify::{
 use {
     crate::{
         banking_trace::BankingPacketSender,
+        pipeline_tracing::PipelineTracer,
         sigverify_stage::{SigVerifier, SigVerifyServiceError},
     },
     agave_banking_stage_ingress_types::BankingPacketBatch,
     crossbeam_channel::Sender,
     solana_perf::{cuda_runtime::PinnedVec, packet::PacketBatch, recycler::Recycler, sigverify},
+    std::{sync::Arc, time::SystemTime},
 };
 
 pub struct TransactionSigVerifier {
@@ -23,6 +25,9 @@ pub struct TransactionSigVerifier {
     recycler: Recycler<TxOffset>,
     recycler_out: Recycler<PinnedVec<u8>>,
     reject_non_vote: bool,
+    pipeline_tracer: Option<Arc<PipelineTracer>>,
+    /// When the batches being verified were received, if traced
+    received: Option<SystemTime>,
 }
 
 impl TransactionSigVerifier {
@@ -46,8 +51,15 @@ impl TransactionSigVerifier {
             recycler: Recycler::warmed(50, 4096),
             recycler_out: Recycler::warmed(50, 4096),
             reject_non_vote: false,
+            pipeline_tracer: None,
+            received: None,
         }
     }
+
+    pub fn with_pipeline_tracer(mut self, pipeline_tracer: Option<Arc<PipelineTracer>>) -> Self {
+        self.pipeline_tracer = pipeline_tracer;
+        self
+    }
 }
 
 impl SigVerifier for TransactionSigVerifier {
@@ -58,6 +70,9 @@ impl SigVerifier for TransactionSigVerifier {
         packet_batches: Vec<PacketBatch>,
     ) -> Result<(), SigVerifyServiceError<Self::SendType>> {
         let banking_packet_batch = BankingPacketBatch::new(packet_batches);
+        if let (Some(pipeline_tracer), Some(received)) = (&self.pipeline_tracer, self.received) {
+            pipeline_tracer.track_sent(&banking_packet_batch, received);
+        }
         if let Some(forward_stage_sender) = &self.forward_stage_sender {
             self.banking_stage_sender
                 .send(banking_packet_batch.clone())?;
@@ -69,6 +84,12 @@ impl SigVerifier for TransactionSigVerifier {
         Ok(())
     }
 
+    fn on_packets_received(&mut self) {
+        if self.pipeline_tracer.is_some() {
+            self.received = Some(SystemTime::now());
+        }
+    }
+
     fn verify_batches(
         &self,
         mut batches: Vec<PacketBatch>,
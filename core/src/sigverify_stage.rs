@@ -17,6 +17,7 @@ use {
         sigverify::{
             count_discarded_packets, count_packets_in_batches, count_valid_packets, shrink_batches,
         },
+        thread::ThreadPinning,
     },
     solana_sdk::timing,
     solana_streamer::streamer::{self, StreamerError},
@@ -58,6 +59,8 @@ pub trait SigVerifier {
     type SendType: std::fmt::Debug;
     fn verify_batches(&self, batches: Vec<PacketBatch>, valid_packets: usize) -> Vec<PacketBatch>;
     fn send_packets(&mut self, packet_batches: Vec<PacketBatch>) -> Result<(), Self::SendType>;
+    /// Called when the stage receives the batches it is about to verify
+    fn on_packets_received(&mut self) {}
 }
 
 #[derive(Default, Clone)]
@@ -237,8 +240,30 @@ impl SigVerifyStage {
         thread_name: &'static str,
         metrics_name: &'static str,
     ) -> Self {
-        let thread_hdl =
-            Self::verifier_service(packet_receiver, verifier, thread_name, metrics_name);
+        Self::new_with_thread_pinning(
+            packet_receiver,
+            verifier,
+            thread_name,
+            metrics_name,
+            ThreadPinning::default(),
+        )
+    }
+
+    /// Creates the stage, with its thread pinned as `thread_pinning` tells
+    pub fn new_with_thread_pinning<T: SigVerifier + 'static + Send>(
+        packet_receiver: Receiver<PacketBatch>,
+        verifier: T,
+        thread_name: &'static str,
+        metrics_name: &'static str,
+        thread_pinning: ThreadPinning,
+    ) -> Self {
+        let thread_hdl = Self::verifier_service(
+            packet_receiver,
+            verifier,
+            thread_name,
+            metrics_name,
+            thread_pinning,
+        );
         Self { thread_hdl }
     }
 
@@ -290,6 +315,7 @@ impl SigVerifyStage {
         stats: &mut SigVerifierStats,
     ) -> Result<(), T::SendType> {
         let (mut batches, num_packets, recv_duration) = streamer::recv_packet_batches(recvr)?;
+        verifier.on_packets_received();
 
         let batches_len = batches.len();
         debug!(
@@ -383,6 +409,7 @@ impl SigVerifyStage {
         mut verifier: T,
         thread_name: &'static str,
         metrics_name: &'static str,
+        thread_pinning: ThreadPinning,
     ) -> JoinHandle<()> {
         let mut stats = SigVerifierStats::default();
         let mut last_print = Instant::now();
@@ -392,6 +419,7 @@ impl SigVerifyStage {
         Builder::new()
             .name(thread_name.to_string())
             .spawn(move || {
+                thread_pinning.pin_this_thread();
                 let mut rng = rand::thread_rng();
                 let mut deduper = Deduper::<2, [u8]>::new(&mut rng, DEDUPER_NUM_BITS);
                 loop {
@@ -1,7 +1,9 @@
 mod pending_snapshot_packages;
+mod snapshot_archive_verifier;
 mod snapshot_gossip_manager;
 pub use pending_snapshot_packages::PendingSnapshotPackages;
 use {
+    snapshot_archive_verifier::SnapshotArchiveVerifier,
     snapshot_gossip_manager::SnapshotGossipManager,
     solana_gossip::cluster_info::ClusterInfo,
     solana_measure::{measure::Measure, measure_us},
@@ -44,6 +46,15 @@ impl SnapshotPackagerService {
                 renice_this_thread(snapshot_config.packager_thread_niceness_adj).unwrap();
                 let mut snapshot_gossip_manager = enable_gossip_push
                     .then(|| SnapshotGossipManager::new(cluster_info, starting_snapshot_hashes));
+                // When verifying archives, only the verifier pushes snapshot
+                // hashes, once their archives are verified.
+                let snapshot_archive_verifier = snapshot_config.verify_archives.then(|| {
+                    SnapshotArchiveVerifier::new(
+                        snapshot_controller.clone(),
+                        snapshot_gossip_manager.take(),
+                        exit.clone(),
+                    )
+                });
 
                 loop {
                     if exit.load(Ordering::Relaxed) {
@@ -71,6 +82,7 @@ impl SnapshotPackagerService {
                         measure_us!(snapshot_utils::serialize_and_archive_snapshot_package(
                             snapshot_package,
                             snapshot_config,
+                            Some(snapshot_controller.packaging_progress().as_ref()),
                         ));
                     if let Err(err) = archive_result {
                         error!(
@@ -81,7 +93,10 @@ impl SnapshotPackagerService {
                         break;
                     }
 
-                    if let Some(snapshot_gossip_manager) = snapshot_gossip_manager.as_mut() {
+                    if let Some(snapshot_archive_verifier) = snapshot_archive_verifier.as_ref() {
+                        snapshot_archive_verifier
+                            .verify(snapshot_kind, (snapshot_slot, snapshot_hash));
+                    } else if let Some(snapshot_gossip_manager) = snapshot_gossip_manager.as_mut() {
                         snapshot_gossip_manager
                             .push_snapshot_hash(snapshot_kind, (snapshot_slot, snapshot_hash));
                     }
@@ -118,6 +133,9 @@ impl SnapshotPackagerService {
                         ("purge_old_archives_time_us", purge_archives_time_us, i64),
                     );
                 }
+                if let Some(snapshot_archive_verifier) = snapshot_archive_verifier {
+                    snapshot_archive_verifier.join().unwrap();
+                }
                 info!("SnapshotPackagerService has stopped");
             })
             .unwrap();
@@ -0,0 +1,208 @@
+use {
+    super::snapshot_gossip_manager::SnapshotGossipManager,
+    crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
+    solana_measure::measure::Measure,
+    solana_runtime::{
+        snapshot_controller::SnapshotController,
+        snapshot_hash::SnapshotHash,
+        snapshot_package::SnapshotKind,
+        snapshot_utils::{self, SnapshotArchiveVerificationRecord},
+    },
+    solana_sdk::clock::Slot,
+    std::{
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, SystemTime},
+    },
+};
+
+/// Verifies the snapshot archives made by the snapshot packager service in a
+/// thread of its own, so that archiving is not delayed by it, and pushes the
+/// snapshot hashes of the verified archives to gossip
+pub struct SnapshotArchiveVerifier {
+    archive_sender: Sender<(SnapshotKind, Slot, SnapshotHash)>,
+    t_verifier: JoinHandle<()>,
+}
+
+impl SnapshotArchiveVerifier {
+    /// If there are no snapshot archives to verify, limit how often we
+    /// re-check for exit
+    const LOOP_LIMITER: Duration = Duration::from_millis(100);
+
+    pub fn new(
+        snapshot_controller: Arc<SnapshotController>,
+        snapshot_gossip_manager: Option<SnapshotGossipManager>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let (archive_sender, archive_receiver) = unbounded();
+        let t_verifier = Builder::new()
+            .name("solSnapshotVrfy".to_string())
+            .spawn(move || {
+                info!("SnapshotArchiveVerifier has started");
+                Self::run(
+                    &snapshot_controller,
+                    snapshot_gossip_manager,
+                    &archive_receiver,
+                    &exit,
+                );
+                info!("SnapshotArchiveVerifier has stopped");
+            })
+            .unwrap();
+        Self {
+            archive_sender,
+            t_verifier,
+        }
+    }
+
+    /// Queues the snapshot archive for verification
+    pub fn verify(&self, snapshot_kind: SnapshotKind, snapshot_hash: (Slot, SnapshotHash)) {
+        let (slot, hash) = snapshot_hash;
+        // The verifier only stops on exit, after which nothing is sent.
+        let _ = self.archive_sender.send((snapshot_kind, slot, hash));
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        drop(self.archive_sender);
+        self.t_verifier.join()
+    }
+
+    fn run(
+        snapshot_controller: &SnapshotController,
+        mut snapshot_gossip_manager: Option<SnapshotGossipManager>,
+        archive_receiver: &Receiver<(SnapshotKind, Slot, SnapshotHash)>,
+        exit: &AtomicBool,
+    ) {
+        let snapshot_config = snapshot_controller.snapshot_config();
+        while !exit.load(Ordering::Relaxed) {
+            let (snapshot_kind, slot, hash) =
+                match archive_receiver.recv_timeout(Self::LOOP_LIMITER) {
+                    Ok(archive) => archive,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+            let archive_path = match snapshot_kind {
+                SnapshotKind::FullSnapshot => snapshot_utils::build_full_snapshot_archive_path(
+                    &snapshot_config.full_snapshot_archives_dir,
+                    slot,
+                    &hash,
+                    snapshot_config.archive_format,
+                ),
+                SnapshotKind::IncrementalSnapshot(base_slot) => {
+                    snapshot_utils::build_incremental_snapshot_archive_path(
+                        &snapshot_config.incremental_snapshot_archives_dir,
+                        base_slot,
+                        slot,
+                        &hash,
+                        snapshot_config.archive_format,
+                    )
+                }
+            };
+            // Archives are purged as newer ones are made, which may happen
+            // before an archive is verified if the verifier falls behind.
+            if !archive_path.exists() {
+                info!(
+                    "Skipping verification of purged snapshot archive {}",
+                    archive_path.display()
+                );
+                continue;
+            }
+
+            let record = Self::verify_archive(&archive_path, snapshot_kind, slot);
+            datapoint_info!(
+                "snapshot_archive_verification",
+                ("slot", slot, i64),
+                ("is_full", snapshot_kind == SnapshotKind::FullSnapshot, bool),
+                ("verified", record.result.is_ok(), bool),
+                ("duration_us", record.duration.as_micros(), i64),
+                (
+                    "num_storages",
+                    record
+                        .result
+                        .as_ref()
+                        .map_or(0, |verification| verification.num_storages),
+                    i64
+                ),
+                (
+                    "accounts_lt_hash_verified",
+                    record
+                        .result
+                        .as_ref()
+                        .is_ok_and(|verification| verification.accounts_lt_hash_verified),
+                    bool
+                ),
+            );
+            let verified = match &record.result {
+                Ok(verification) => {
+                    info!(
+                        "Verified snapshot archive {} in {:?}: {verification:?}",
+                        archive_path.display(),
+                        record.duration,
+                    );
+                    true
+                }
+                Err(err) => {
+                    error!(
+                        "Snapshot archive {} is invalid, its snapshot hash will not be pushed to \
+                         gossip: {err}",
+                        archive_path.display(),
+                    );
+                    false
+                }
+            };
+            snapshot_controller
+                .archive_verification_status()
+                .record(record);
+
+            if let (true, Some(snapshot_gossip_manager)) =
+                (verified, snapshot_gossip_manager.as_mut())
+            {
+                // An incremental snapshot can only be pushed along with its
+                // full snapshot, which is not if it failed its verification.
+                if let SnapshotKind::IncrementalSnapshot(base_slot) = snapshot_kind {
+                    if snapshot_gossip_manager.latest_full_snapshot_slot() != Some(base_slot) {
+                        warn!(
+                            "Not pushing the snapshot hash of incremental snapshot archive {} to \
+                             gossip, as its full snapshot at slot {base_slot} was not pushed",
+                            archive_path.display(),
+                        );
+                        continue;
+                    }
+                }
+                snapshot_gossip_manager.push_snapshot_hash(snapshot_kind, (slot, hash));
+            }
+        }
+    }
+
+    fn verify_archive(
+        archive_path: &Path,
+        snapshot_kind: SnapshotKind,
+        slot: Slot,
+    ) -> SnapshotArchiveVerificationRecord {
+        let measure = Measure::start("");
+        let scratch_dir = archive_path.parent().unwrap_or(archive_path);
+        // The prefix gets the scratch directory cleaned up at startup, along
+        // with the staging directories of unfinished archives.
+        let result = tempfile::Builder::new()
+            .prefix(&format!(
+                "{}verification-",
+                snapshot_utils::TMP_SNAPSHOT_ARCHIVE_PREFIX
+            ))
+            .tempdir_in(scratch_dir)
+            .map_err(|err| format!("failed to create a scratch directory: {err}"))
+            .and_then(|scratch_dir| {
+                snapshot_utils::verify_snapshot_archive(archive_path, scratch_dir.path())
+                    .map_err(|err| err.to_string())
+            });
+        SnapshotArchiveVerificationRecord {
+            slot,
+            kind: snapshot_kind,
+            verified_at: SystemTime::now(),
+            duration: Duration::from_micros(measure.end_as_us()),
+            result,
+        }
+    }
+}
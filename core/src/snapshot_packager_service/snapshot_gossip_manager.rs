@@ -46,6 +46,13 @@ impl SnapshotGossipManager {
         self.push_latest_snapshot_hashes_to_cluster();
     }
 
+    /// Slot of the latest full snapshot pushed to the cluster, if any
+    pub fn latest_full_snapshot_slot(&self) -> Option<Slot> {
+        self.latest_snapshot_hashes
+            .as_ref()
+            .map(|latest_snapshot_hashes| latest_snapshot_hashes.full.0 .0)
+    }
+
     /// Push new snapshot hash to the cluster via CRDS
     pub fn push_snapshot_hash(
         &mut self,
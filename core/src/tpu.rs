@@ -10,7 +10,10 @@ pub use solana_streamer::quic::DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER as MAX_QUIC
 pub use {crate::forwarding_stage::ForwardingClientOption, solana_sdk::net::DEFAULT_TPU_COALESCE};
 use {
     crate::{
-        banking_stage::BankingStage,
+        banking_stage::{
+            event_recorder::BankingEventRecorder, leader_slot_dump::LeaderSlotDumpSender,
+            packet_provenance::PacketProvenanceTracker, BankingStage, BankingStageHandle,
+        },
         banking_trace::{Channels, TracerThread},
         cluster_info_vote_listener::{
             ClusterInfoVoteListener, DuplicateConfirmedSlotsSender, GossipVerifiedVoteHashSender,
@@ -18,6 +21,7 @@ use {
         },
         fetch_stage::FetchStage,
         forwarding_stage::{spawn_forwarding_stage, ForwardAddressGetter},
+        pipeline_tracing::PipelineTracer,
         sigverify::TransactionSigVerifier,
         sigverify_stage::SigVerifyStage,
         staked_nodes_updater_service::StakedNodesUpdaterService,
@@ -28,17 +32,20 @@ use {
     bytes::Bytes,
     crossbeam_channel::{bounded, unbounded, Receiver},
     solana_client::connection_cache::ConnectionCache,
+    solana_geyser_plugin_manager::vote_notifier_interface::VoteNotifierArc,
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
         blockstore::Blockstore, blockstore_processor::TransactionStatusSender,
-        entry_notifier_service::EntryNotifierSender,
+        cost_calibration::CostCalibration, entry_notifier_service::EntryNotifierSender,
     },
-    solana_perf::data_budget::DataBudget,
+    solana_perf::{data_budget::DataBudget, thread::ThreadPinning},
     solana_poh::{
         poh_recorder::{PohRecorder, WorkingBankEntry},
         transaction_recorder::TransactionRecorder,
     },
     solana_rpc::{
+        buffered_transactions::BufferedTransactionSampler,
+        leader_slot_stats::LeaderSlotStatsTracker,
         optimistically_confirmed_bank_tracker::BankNotificationSender,
         rpc_subscriptions::RpcSubscriptions,
     },
@@ -53,7 +60,11 @@ use {
         quic::{spawn_server_multi, QuicServerParams, SpawnServerResult},
         streamer::StakedNodes,
     },
-    solana_turbine::broadcast_stage::{BroadcastStage, BroadcastStageType},
+    solana_turbine::{
+        broadcast_stage::{BroadcastStage, BroadcastStageType},
+        fec_ratio::FecRatio,
+        remote_shred_signer::RemoteShredSigner,
+    },
     std::{
         collections::HashMap,
         net::{SocketAddr, UdpSocket},
@@ -149,6 +160,19 @@ impl Tpu {
         block_production_method: BlockProductionMethod,
         transaction_struct: TransactionStructure,
         enable_block_production_forwarding: bool,
+        forwarding_leader_fanout: usize,
+        packet_provenance: Option<Arc<PacketProvenanceTracker>>,
+        leader_slot_dump: Option<LeaderSlotDumpSender>,
+        vote_notifier: Option<VoteNotifierArc>,
+        leader_slot_stats: Option<Arc<LeaderSlotStatsTracker>>,
+        cost_calibration: Option<Arc<CostCalibration>>,
+        pipeline_tracer: Option<Arc<PipelineTracer>>,
+        buffered_transaction_sampler: Option<Arc<BufferedTransactionSampler>>,
+        banking_event_recorder: Option<Arc<BankingEventRecorder>>,
+        remote_shred_signer: Option<Arc<RemoteShredSigner>>,
+        fec_ratio: Option<Arc<FecRatio>>,
+        banking_thread_pinning: ThreadPinning,
+        sigverify_thread_pinning: ThreadPinning,
         _generator_config: Option<GeneratorConfig>, /* vestigial code for replay invalidator */
     ) -> (Self, Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>) {
         let TpuSockets {
@@ -276,12 +300,14 @@ impl Tpu {
             let verifier = TransactionSigVerifier::new(
                 non_vote_sender,
                 enable_block_production_forwarding.then(|| forward_stage_sender.clone()),
-            );
-            SigVerifier::Local(SigVerifyStage::new(
+            )
+            .with_pipeline_tracer(pipeline_tracer.clone());
+            SigVerifier::Local(SigVerifyStage::new_with_thread_pinning(
                 packet_receiver,
                 verifier,
                 "solSigVerTpu",
                 "tpu-verifier",
+                sigverify_thread_pinning.clone(),
             ))
         };
 
@@ -290,11 +316,12 @@ impl Tpu {
                 tpu_vote_sender,
                 Some(forward_stage_sender),
             );
-            SigVerifyStage::new(
+            SigVerifyStage::new_with_thread_pinning(
                 vote_packet_receiver,
                 verifier,
                 "solSigVerTpuVot",
                 "tpu-vote-verifier",
+                sigverify_thread_pinning,
             )
         };
 
@@ -311,6 +338,7 @@ impl Tpu {
             blockstore.clone(),
             bank_notification_sender,
             duplicate_confirmed_slot_sender,
+            vote_notifier.clone(),
         );
 
         let banking_stage = BankingStage::new(
@@ -327,6 +355,15 @@ impl Tpu {
             log_messages_bytes_limit,
             bank_forks.clone(),
             prioritization_fee_cache,
+            packet_provenance,
+            leader_slot_dump,
+            vote_notifier,
+            leader_slot_stats,
+            cost_calibration,
+            pipeline_tracer,
+            buffered_transaction_sampler,
+            banking_event_recorder,
+            banking_thread_pinning,
         );
 
         let client = ForwardingClientOption::ConnectionCache(connection_cache.clone());
@@ -338,6 +375,7 @@ impl Tpu {
             RootBankCache::new(bank_forks.clone()),
             ForwardAddressGetter::new(cluster_info.clone(), poh_recorder.clone()),
             DataBudget::default(),
+            forwarding_leader_fanout,
         );
 
         let (entry_receiver, tpu_entry_notifier) =
@@ -364,6 +402,8 @@ impl Tpu {
             bank_forks,
             shred_version,
             turbine_quic_endpoint_sender,
+            remote_shred_signer,
+            fec_ratio,
         );
 
         let mut key_updaters: Vec<Arc<dyn NotifyKeyUpdate + Send + Sync>> = Vec::new();
@@ -394,6 +434,10 @@ impl Tpu {
         )
     }
 
+    pub fn banking_stage_handle(&self) -> BankingStageHandle {
+        self.banking_stage.handle()
+    }
+
     pub fn join(self) -> thread::Result<()> {
         let results = vec![
             self.fetch_stage.join(),
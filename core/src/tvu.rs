@@ -3,17 +3,24 @@
 
 use {
     crate::{
+        bank_hash_breakdowns::BankHashBreakdownTracker,
         banking_trace::BankingTracer,
+        clock_drift_service::ClockDrift,
         cluster_info_vote_listener::{
             DuplicateConfirmedSlotsReceiver, GossipVerifiedVoteHashReceiver, VerifiedVoteReceiver,
             VoteTracker,
         },
         cluster_slots_service::{cluster_slots::ClusterSlots, ClusterSlotsService},
         completed_data_sets_service::CompletedDataSetsSender,
-        consensus::{tower_storage::TowerStorage, Tower},
+        consensus::{tower_storage::TowerStorage, vote_refresh::VoteRefreshConfig, Tower},
         cost_update_service::CostUpdateService,
         drop_bank_service::DropBankService,
-        repair::repair_service::{OutstandingShredRepairs, RepairInfo, RepairServiceChannels},
+        forensic_bundles::ForensicBundles,
+        leader_pause::LeaderPause,
+        repair::{
+            repair_peer_selector::{RepairPeerSelection, RepairPeerSelector},
+            repair_service::{OutstandingShredRepairs, RepairInfo, RepairServiceChannels},
+        },
         replay_stage::{ReplayReceivers, ReplaySenders, ReplayStage, ReplayStageConfig},
         shred_fetch_stage::ShredFetchStage,
         voting_service::VotingService,
@@ -22,22 +29,24 @@ use {
     },
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, Sender},
-    solana_client::connection_cache::ConnectionCache,
+    solana_client::connection_cache::{ConnectionCache, Protocol},
     solana_geyser_plugin_manager::block_metadata_notifier_interface::BlockMetadataNotifierArc,
     solana_gossip::{
         cluster_info::ClusterInfo, duplicate_shred_handler::DuplicateShredHandler,
         duplicate_shred_listener::DuplicateShredListener,
+        duplicate_shred_proofs::DuplicateShredProofs,
     },
     solana_ledger::{
         blockstore::Blockstore, blockstore_cleanup_service::BlockstoreCleanupService,
-        blockstore_processor::TransactionStatusSender, entry_notifier_service::EntryNotifierSender,
-        leader_schedule_cache::LeaderScheduleCache,
+        blockstore_processor::TransactionStatusSender, cost_calibration::CostCalibration,
+        entry_notifier_service::EntryNotifierSender, leader_schedule_cache::LeaderScheduleCache,
     },
     solana_poh::poh_recorder::PohRecorder,
     solana_rpc::{
-        block_meta_service::BlockMetaSender, max_slots::MaxSlots,
-        optimistically_confirmed_bank_tracker::BankNotificationSenderConfig,
-        rpc_subscriptions::RpcSubscriptions, slot_status_notifier::SlotStatusNotifier,
+        account_congestion::AccountCongestionTracker, block_meta_service::BlockMetaSender,
+        max_slots::MaxSlots, optimistically_confirmed_bank_tracker::BankNotificationSenderConfig,
+        rpc_subscriptions::RpcSubscriptions, shred_latency::ShredLatencyTracker,
+        skipped_slot_reasons::SkippedSlotReasonsTracker, slot_status_notifier::SlotStatusNotifier,
     },
     solana_runtime::{
         bank_forks::BankForks, commitment::BlockCommitmentCache,
@@ -46,7 +55,10 @@ use {
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Keypair},
     solana_streamer::evicting_sender::EvictingSender,
-    solana_turbine::{retransmit_stage::RetransmitStage, xdp::XdpConfig},
+    solana_turbine::{
+        egress_shaper::EgressShaperConfig, retransmit_stage::RetransmitStage,
+        shredstream::ShredstreamConfig, xdp::XdpConfig,
+    },
     std::{
         collections::HashSet,
         net::{SocketAddr, UdpSocket},
@@ -96,8 +108,32 @@ pub struct TvuConfig {
     pub wait_for_vote_to_start_leader: bool,
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
+    pub replay_fork_transactions_threads: NonZeroUsize,
+    // Cores the PoH verification of the replayed entries is pinned to
+    pub entry_verification_cpus: Vec<usize>,
     pub shred_sigverify_threads: NonZeroUsize,
     pub retransmit_xdp: Option<XdpConfig>,
+    pub retransmit_egress_shaper: Option<EgressShaperConfig>,
+    pub shredstream: Option<ShredstreamConfig>,
+    // Stops voting while the local clock drifts too far from the cluster
+    pub clock_drift: Option<Arc<ClockDrift>>,
+    // Captures the slots frozen with a different hash than the cluster's
+    pub forensic_bundles: Option<Arc<ForensicBundles>>,
+    pub vote_refresh_config: VoteRefreshConfig,
+    // Determines the probable causes of the skipped leader slots
+    pub skipped_slot_reasons: Option<Arc<SkippedSlotReasonsTracker>>,
+    // Calibrates the cost model from the execution times of the replayed slots
+    pub cost_calibration: Option<Arc<CostCalibration>>,
+    // Records the most contended accounts of the frozen banks
+    pub account_congestion: Option<Arc<AccountCongestionTracker>>,
+    // Records the bank hash components of the frozen banks
+    pub bank_hash_breakdowns: Option<Arc<BankHashBreakdownTracker>>,
+    // Skips the leader slots while block production is paused
+    pub leader_pause: Option<Arc<LeaderPause>>,
+    // Protocol repairs are requested over, if supported by the peer
+    pub repair_protocol: Protocol,
+    // Policy for choosing the peers repairs are requested from
+    pub repair_peer_selection: RepairPeerSelection,
 }
 
 impl Default for TvuConfig {
@@ -110,8 +146,22 @@ impl Default for TvuConfig {
             wait_for_vote_to_start_leader: false,
             replay_forks_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            replay_fork_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            entry_verification_cpus: Vec::new(),
             shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             retransmit_xdp: None,
+            retransmit_egress_shaper: None,
+            shredstream: None,
+            clock_drift: None,
+            forensic_bundles: None,
+            vote_refresh_config: VoteRefreshConfig::default(),
+            skipped_slot_reasons: None,
+            cost_calibration: None,
+            account_congestion: None,
+            bank_hash_breakdowns: None,
+            leader_pause: None,
+            repair_protocol: Protocol::UDP,
+            repair_peer_selection: RepairPeerSelection::default(),
         }
     }
 }
@@ -171,6 +221,8 @@ impl Tvu {
         wen_restart_repair_slots: Option<Arc<RwLock<Vec<Slot>>>>,
         slot_status_notifier: Option<SlotStatusNotifier>,
         vote_connection_cache: Arc<ConnectionCache>,
+        duplicate_shred_proofs: Arc<DuplicateShredProofs>,
+        shred_latency_tracker: Arc<ShredLatencyTracker>,
     ) -> Result<Self, String> {
         let in_wen_restart = wen_restart_repair_slots.is_some();
 
@@ -186,6 +238,8 @@ impl Tvu {
         let repair_socket = Arc::new(repair_socket);
         let ancestor_hashes_socket = Arc::new(ancestor_hashes_socket);
         let fetch_sockets: Vec<Arc<UdpSocket>> = fetch_sockets.into_iter().map(Arc::new).collect();
+        let repair_peer_selector =
+            Arc::new(RepairPeerSelector::new(tvu_config.repair_peer_selection));
         let fetch_stage = ShredFetchStage::new(
             fetch_sockets,
             turbine_quic_endpoint_receiver,
@@ -196,6 +250,8 @@ impl Tvu {
             bank_forks.clone(),
             cluster_info.clone(),
             outstanding_repair_requests.clone(),
+            repair_peer_selector.clone(),
+            shred_latency_tracker,
             turbine_disabled,
             exit.clone(),
         );
@@ -226,6 +282,8 @@ impl Tvu {
             Some(rpc_subscriptions.clone()),
             slot_status_notifier.clone(),
             tvu_config.retransmit_xdp.clone(),
+            tvu_config.retransmit_egress_shaper,
+            tvu_config.shredstream.clone(),
         );
 
         let (ancestor_duplicate_slots_sender, ancestor_duplicate_slots_receiver) = unbounded();
@@ -250,6 +308,8 @@ impl Tvu {
                 cluster_info: cluster_info.clone(),
                 cluster_slots: cluster_slots.clone(),
                 wen_restart_repair_slots,
+                repair_protocol: tvu_config.repair_protocol,
+                repair_peer_selector,
             };
             let repair_service_channels = RepairServiceChannels::new(
                 repair_request_quic_sender,
@@ -331,6 +391,8 @@ impl Tvu {
             wait_to_vote_slot,
             replay_forks_threads: tvu_config.replay_forks_threads,
             replay_transactions_threads: tvu_config.replay_transactions_threads,
+            replay_fork_transactions_threads: tvu_config.replay_fork_transactions_threads,
+            entry_verification_cpus: tvu_config.entry_verification_cpus,
             blockstore: blockstore.clone(),
             bank_forks: bank_forks.clone(),
             cluster_info: cluster_info.clone(),
@@ -342,6 +404,14 @@ impl Tvu {
             prioritization_fee_cache: prioritization_fee_cache.clone(),
             banking_tracer,
             snapshot_controller,
+            clock_drift: tvu_config.clock_drift,
+            forensic_bundles: tvu_config.forensic_bundles,
+            vote_refresh_config: tvu_config.vote_refresh_config,
+            skipped_slot_reasons: tvu_config.skipped_slot_reasons,
+            cost_calibration: tvu_config.cost_calibration,
+            account_congestion: tvu_config.account_congestion,
+            bank_hash_breakdowns: tvu_config.bank_hash_breakdowns,
+            leader_pause: tvu_config.leader_pause,
         };
 
         let voting_service = VotingService::new(
@@ -386,6 +456,7 @@ impl Tvu {
                 leader_schedule_cache.clone(),
                 bank_forks.clone(),
                 duplicate_slots_sender,
+                duplicate_shred_proofs,
                 tvu_config.shred_version,
             ),
         );
@@ -603,6 +674,8 @@ pub mod tests {
             wen_restart_repair_slots,
             None,
             Arc::new(connection_cache),
+            Arc::<DuplicateShredProofs>::default(),
+            Arc::<ShredLatencyTracker>::default(),
         )
         .expect("assume success");
         if enable_wen_restart {
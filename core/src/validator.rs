@@ -3,19 +3,43 @@
 pub use solana_perf::report_target_features;
 use {
     crate::{
+        account_growth_service::AccountGrowthService,
         accounts_hash_verifier::AccountsHashVerifier,
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        bandwidth_accounting_service::BandwidthAccountingService,
+        bank_hash_breakdowns::BankHashBreakdownTracker,
+        banking_stage::{
+            event_recorder::BankingEventRecorderService, leader_slot_dump::LeaderSlotDumpService,
+            packet_provenance::PacketProvenanceTracker,
+        },
         banking_trace::{self, BankingTracer, TraceError},
+        blockstore_compaction_service::{
+            BlockstoreCompactionScheduler, BlockstoreCompactionService,
+        },
+        clock_drift_service::{ClockDrift, ClockDriftConfig, ClockDriftService},
         cluster_info_vote_listener::VoteTracker,
         completed_data_sets_service::CompletedDataSetsService,
         consensus::{
             reconcile_blockstore_roots_with_external_source,
             tower_storage::{NullTowerStorage, TowerStorage},
+            vote_refresh::VoteRefreshConfig,
             ExternalRootSource, Tower,
         },
+        epoch_accounts_hash_scheduling::{
+            EpochAccountsHashScheduler, EpochAccountsHashSchedulingConfig,
+        },
+        failover::{FailoverConfig, FailoverCoordinator, FailoverService},
+        forensic_bundles::ForensicBundles,
+        forwarding_stage::DEFAULT_FORWARDING_LEADER_FANOUT,
+        leader_pause::LeaderPause,
+        leader_stages::{LeaderStages, RpcOnlyStages},
+        node_update_service::NodeUpdateService,
+        pipeline_tracing::{PipelineTracer, PipelineTracingConfig},
+        program_precompilation::{ProgramPrecompiler, DEFAULT_PRECOMPILE_PROGRAMS_SLOTS},
         repair::{
             self,
             quic_endpoint::{RepairQuicAsyncSenders, RepairQuicSenders, RepairQuicSockets},
+            repair_peer_selector::RepairPeerSelection,
             serve_repair::ServeRepair,
             serve_repair_service::ServeRepairService,
         },
@@ -42,7 +66,6 @@ use {
         utils::{move_and_async_delete_path, move_and_async_delete_path_contents},
     },
     solana_client::connection_cache::{ConnectionCache, Protocol},
-    solana_entry::poh::compute_hash_time,
     solana_geyser_plugin_manager::{
         geyser_plugin_service::GeyserPluginService, GeyserPluginManagerRequest,
     },
@@ -53,7 +76,10 @@ use {
         },
         contact_info::ContactInfo,
         crds_gossip_pull::CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
+        crds_priority::CrdsPriorityWeights,
+        duplicate_shred_proofs::DuplicateShredProofs,
         gossip_service::GossipService,
+        quic_endpoint::GossipQuicChannels,
     },
     solana_ledger::{
         bank_forks_utils,
@@ -64,6 +90,7 @@ use {
         blockstore_metric_report_service::BlockstoreMetricReportService,
         blockstore_options::{BlockstoreOptions, BLOCKSTORE_DIRECTORY_ROCKS_LEVEL},
         blockstore_processor::{self, TransactionStatusSender},
+        cost_calibration::CostCalibration,
         entry_notifier_interface::EntryNotifierArc,
         entry_notifier_service::{EntryNotifierSender, EntryNotifierService},
         leader_schedule::FixedSchedule,
@@ -72,14 +99,23 @@ use {
     },
     solana_measure::measure::Measure,
     solana_metrics::{datapoint_info, metrics::metrics_config_sanity_check},
+    solana_perf::thread::ThreadPinning,
     solana_poh::{
+        poh_calibration_service::{self, PohCalibration, PohCalibrationService},
         poh_recorder::PohRecorder,
         poh_service::{self, PohService},
         transaction_recorder::TransactionRecorder,
     },
     solana_rayon_threadlimit::{get_max_thread_count, get_thread_count},
     solana_rpc::{
+        account_congestion::AccountCongestionTracker,
+        account_growth::AccountGrowthTracker,
         block_meta_service::{BlockMetaSender, BlockMetaService},
+        buffered_transactions::BufferedTransactionSampler,
+        commission_guard::{CommissionGuard, CommissionGuardConfig},
+        confirmation_latency::ConfirmationLatencyTracker,
+        confirmation_proofs::ConfirmationProofCache,
+        leader_slot_stats::LeaderSlotStatsTracker,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::{
             BankNotificationSenderConfig, OptimisticallyConfirmedBank,
@@ -90,8 +126,12 @@ use {
         rpc_pubsub_service::{PubSubConfig, PubSubService},
         rpc_service::{ClientOption, JsonRpcService, JsonRpcServiceConfig},
         rpc_subscriptions::RpcSubscriptions,
+        shred_latency::ShredLatencyTracker,
+        skipped_slot_reasons::SkippedSlotReasonsTracker,
+        slot_account_filter::SlotAccountFilterCache,
         transaction_notifier_interface::TransactionNotifierArc,
-        transaction_status_service::TransactionStatusService,
+        transaction_provenance::TransactionProvenanceCache,
+        transaction_status_service::{TransactionStatusFilter, TransactionStatusService},
     },
     solana_runtime::{
         accounts_background_service::{
@@ -127,7 +167,15 @@ use {
     solana_tpu_client::tpu_client::{
         DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_TPU_USE_QUIC, DEFAULT_VOTE_USE_QUIC,
     },
-    solana_turbine::{self, broadcast_stage::BroadcastStageType, xdp::XdpConfig},
+    solana_turbine::{
+        self,
+        broadcast_stage::BroadcastStageType,
+        egress_shaper::EgressShaperConfig,
+        fec_ratio::{FecRatio, FecRatioConfig},
+        remote_shred_signer::{RemoteShredSigner, RemoteShredSignerConfig},
+        shredstream::ShredstreamConfig,
+        xdp::XdpConfig,
+    },
     solana_unified_scheduler_pool::DefaultSchedulerPool,
     solana_vote_program::vote_state,
     solana_wen_restart::wen_restart::{wait_for_wen_restart, WenRestartConfig},
@@ -277,6 +325,13 @@ pub struct ValidatorConfig {
     pub debug_keys: Option<Arc<HashSet<Pubkey>>>,
     pub contact_debug_interval: u64,
     pub contact_save_interval: u64,
+    /// Order in which outgoing gossip values are sent when gossip is saturated
+    pub gossip_priority_weights: CrdsPriorityWeights,
+    /// Advertise a gossip QUIC address, and gossip over QUIC with peers which
+    /// advertise one too
+    pub enable_gossip_quic: bool,
+    /// Limits commission changes of the vote account submitted through RPC
+    pub commission_guard_config: Option<CommissionGuardConfig>,
     pub send_transaction_service_config: SendTransactionServiceConfig,
     pub no_poh_speed_test: bool,
     pub no_os_memory_stats_reporting: bool,
@@ -284,7 +339,12 @@ pub struct ValidatorConfig {
     pub no_os_cpu_stats_reporting: bool,
     pub no_os_disk_stats_reporting: bool,
     pub poh_pinned_cpu_core: usize,
+    /// NUMA node the memory of the PoH tick producer is preferably allocated on
+    pub poh_numa_node: Option<usize>,
     pub poh_hashes_per_batch: u64,
+    /// How often the hash rate of PoH is measured to tune its hashes per
+    /// batch. Zero disables the calibration.
+    pub poh_calibration_interval_ms: u64,
     pub process_ledger_before_services: bool,
     pub accounts_db_config: Option<AccountsDbConfig>,
     pub warp_slot: Option<Slot>,
@@ -296,12 +356,81 @@ pub struct ValidatorConfig {
     pub validator_exit: Arc<RwLock<Exit>>,
     pub no_wait_for_vote_to_start_leader: bool,
     pub wait_to_vote_slot: Option<Slot>,
+    pub clock_drift_config: ClockDriftConfig,
+    pub vote_refresh_config: VoteRefreshConfig,
+    /// Number of recent slots whose turbine shred arrival times are retained
+    /// for metrics and RPC. Zero disables shred latency tracking.
+    pub shred_latency_slots: usize,
+    /// Number of recent leader slots whose banking statistics are retained
+    /// for RPC. Zero disables leader slot statistics.
+    pub leader_slot_stats_slots: usize,
+    /// Number of recent slots whose optimistic confirmation and finalization
+    /// latencies are retained for RPC. Zero disables confirmation latency
+    /// tracking.
+    pub confirmation_latency_slots: usize,
+    /// Number of recent skipped leader slots whose probable causes are
+    /// retained for RPC. Zero disables skipped slot analysis.
+    pub skipped_slot_reasons_slots: usize,
+    /// Number of recent slots whose most contended write locked accounts are
+    /// retained for RPC. Zero disables account congestion tracking.
+    pub account_congestion_slots: usize,
+    /// Number of recent optimistically confirmed slots whose confirmation
+    /// proofs are retained for RPC. Zero disables confirmation proofs.
+    pub confirmation_proof_slots: usize,
+    /// Number of the transactions buffered by the banking stage which are
+    /// sampled for RPC. Zero disables the sampling.
+    pub buffered_transaction_samples: usize,
+    /// Number of recent epochs whose account growth is retained for RPC.
+    /// Zero disables the account growth sampling.
+    pub account_growth_epochs: usize,
+    /// Number of recent slots whose bank hash components are retained for the
+    /// admin RPC. Zero disables bank hash breakdown tracking.
+    pub bank_hash_breakdown_slots: usize,
+    /// How often the execution costs of the programs measured while replaying
+    /// and producing blocks are reported against the cost model. Zero
+    /// disables the cost calibration.
+    pub cost_calibration_report_interval_ms: u64,
+    /// Traces sampled packet batches through the transaction pipeline to an
+    /// OTLP collector. `None` disables pipeline tracing.
+    pub pipeline_tracing_config: Option<PipelineTracingConfig>,
+    /// Programs which are never evicted from the program cache once loaded
+    pub pinned_programs: HashSet<Pubkey>,
+    /// Number of the most invoked programs compiled into the program cache
+    /// before the validator starts voting. Zero disables the precompilation
+    /// at startup.
+    pub precompile_programs: usize,
+    /// Number of the latest rooted slots whose program invocations are counted
+    /// to pick the programs to precompile
+    pub precompile_programs_slots: u64,
+    /// Hot-standby failover with a peer validator sharing the identity.
+    /// `None` disables failover.
+    pub failover_config: Option<FailoverConfig>,
+    /// Directory to capture the forensic bundles of duplicate slots, and of
+    /// slots frozen with a different bank hash than the cluster's, into.
+    /// `None` disables forensic bundles.
+    pub forensic_bundle_dir: Option<PathBuf>,
+    /// Request repairs over QUIC from the peers which support it, and over
+    /// UDP from the others.
+    pub repair_quic: bool,
+    pub repair_peer_selection: RepairPeerSelection,
     pub runtime_config: RuntimeConfig,
     pub banking_trace_dir_byte_limit: banking_trace::DirByteLimit,
     pub block_verification_method: BlockVerificationMethod,
     pub block_production_method: BlockProductionMethod,
     pub transaction_struct: TransactionStructure,
     pub enable_block_production_forwarding: bool,
+    /// Number of distinct upcoming leaders that unprocessed transactions are
+    /// forwarded to.
+    pub forwarding_leader_fanout: usize,
+    /// Number of committed transactions whose packet provenance is retained
+    /// for RPC. Zero disables packet provenance tracking.
+    pub packet_provenance_cache_size: usize,
+    /// Directory that the transactions attempted during each leader slot are
+    /// dumped to
+    pub dump_leader_slot_transactions: Option<PathBuf>,
+    /// Directory that the packet batches received by the banking stage during
+    /// the leader slots are recorded to, for a deterministic replay
+    pub record_banking_events: Option<PathBuf>,
     pub generator_config: Option<GeneratorConfig>,
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
     pub wen_restart_proto_path: Option<PathBuf>,
@@ -311,10 +440,33 @@ pub struct ValidatorConfig {
     pub rayon_global_threads: NonZeroUsize,
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
+    /// Number of threads replaying the transactions of each fork replayed
+    /// concurrently with other forks
+    pub replay_fork_transactions_threads: NonZeroUsize,
+    /// Cores the PoH verification of the replayed entries is pinned to, on a
+    /// thread pool of its own. Empty to verify on the replay thread pools
+    pub entry_verification_cpus: Vec<usize>,
+    /// Cores and NUMA node the banking stage threads are pinned to
+    pub banking_thread_pinning: ThreadPinning,
+    /// Cores and NUMA node the TPU sigverify threads, and the sigverify
+    /// thread pool, are pinned to
+    pub sigverify_thread_pinning: ThreadPinning,
     pub tvu_shred_sigverify_threads: NonZeroUsize,
     pub delay_leader_block_for_pending_fork: bool,
     pub use_tpu_client_next: bool,
     pub retransmit_xdp: Option<XdpConfig>,
+    /// Caps the bandwidth of the retransmitted shreds, unlimited if `None`
+    pub retransmit_egress_shaper: Option<EgressShaperConfig>,
+    /// Signs the shreds broadcast as leader remotely instead of with the
+    /// local identity keypair, if any
+    pub remote_shred_signer: Option<RemoteShredSignerConfig>,
+    /// The coding shreds of the erasure batches broadcast as leader, if not
+    /// the default
+    pub broadcast_fec_ratio: Option<FecRatioConfig>,
+    /// The subscribers the received and recovered shreds are forwarded to,
+    /// if any
+    pub shredstream: Option<ShredstreamConfig>,
+    pub epoch_accounts_hash_scheduling: EpochAccountsHashSchedulingConfig,
 }
 
 impl Default for ValidatorConfig {
@@ -351,6 +503,9 @@ impl Default for ValidatorConfig {
             debug_keys: None,
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
             contact_save_interval: DEFAULT_CONTACT_SAVE_INTERVAL_MILLIS,
+            gossip_priority_weights: CrdsPriorityWeights::default(),
+            enable_gossip_quic: false,
+            commission_guard_config: None,
             send_transaction_service_config: SendTransactionServiceConfig::default(),
             no_poh_speed_test: true,
             no_os_memory_stats_reporting: true,
@@ -358,7 +513,9 @@ impl Default for ValidatorConfig {
             no_os_cpu_stats_reporting: true,
             no_os_disk_stats_reporting: true,
             poh_pinned_cpu_core: poh_service::DEFAULT_PINNED_CPU_CORE,
+            poh_numa_node: None,
             poh_hashes_per_batch: poh_service::DEFAULT_HASHES_PER_BATCH,
+            poh_calibration_interval_ms: 0,
             process_ledger_before_services: false,
             warp_slot: None,
             accounts_db_test_hash_calculation: false,
@@ -370,12 +527,36 @@ impl Default for ValidatorConfig {
             no_wait_for_vote_to_start_leader: true,
             accounts_db_config: None,
             wait_to_vote_slot: None,
+            clock_drift_config: ClockDriftConfig::default(),
+            vote_refresh_config: VoteRefreshConfig::default(),
+            shred_latency_slots: 0,
+            leader_slot_stats_slots: 0,
+            confirmation_latency_slots: 0,
+            skipped_slot_reasons_slots: 0,
+            account_congestion_slots: 0,
+            confirmation_proof_slots: 0,
+            buffered_transaction_samples: 0,
+            account_growth_epochs: 0,
+            bank_hash_breakdown_slots: 0,
+            cost_calibration_report_interval_ms: 0,
+            pipeline_tracing_config: None,
+            pinned_programs: HashSet::default(),
+            precompile_programs: 0,
+            precompile_programs_slots: DEFAULT_PRECOMPILE_PROGRAMS_SLOTS,
+            failover_config: None,
+            forensic_bundle_dir: None,
+            repair_quic: false,
+            repair_peer_selection: RepairPeerSelection::default(),
             runtime_config: RuntimeConfig::default(),
             banking_trace_dir_byte_limit: 0,
             block_verification_method: BlockVerificationMethod::default(),
             block_production_method: BlockProductionMethod::default(),
             transaction_struct: TransactionStructure::default(),
             enable_block_production_forwarding: false,
+            forwarding_leader_fanout: DEFAULT_FORWARDING_LEADER_FANOUT,
+            packet_provenance_cache_size: 0,
+            dump_leader_slot_transactions: None,
+            record_banking_events: None,
             generator_config: None,
             use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup::default(),
             wen_restart_proto_path: None,
@@ -385,10 +566,19 @@ impl Default for ValidatorConfig {
             rayon_global_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             replay_forks_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            replay_fork_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            entry_verification_cpus: Vec::new(),
+            banking_thread_pinning: ThreadPinning::default(),
+            sigverify_thread_pinning: ThreadPinning::default(),
             tvu_shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             delay_leader_block_for_pending_fork: false,
             use_tpu_client_next: false,
             retransmit_xdp: None,
+            retransmit_egress_shaper: None,
+            remote_shred_signer: None,
+            broadcast_fec_ratio: None,
+            shredstream: None,
+            epoch_accounts_hash_scheduling: EpochAccountsHashSchedulingConfig::default(),
         }
     }
 }
@@ -447,6 +637,7 @@ pub enum ValidatorStartProgress {
         max_slot: Slot,
     },
     StartingServices,
+    CompilingPrograms,
     Halted, // Validator halted due to `--dev-halt-at-slot` argument
     WaitingForSupermajority {
         slot: Slot,
@@ -565,7 +756,13 @@ pub struct Validator {
     block_meta_service: Option<BlockMetaService>,
     entry_notifier_service: Option<EntryNotifierService>,
     system_monitor_service: Option<SystemMonitorService>,
+    bandwidth_accounting_service: BandwidthAccountingService,
     sample_performance_service: Option<SamplePerformanceService>,
+    account_growth_service: Option<AccountGrowthService>,
+    clock_drift_service: ClockDriftService,
+    blockstore_compaction_service: BlockstoreCompactionService,
+    node_update_service: Option<NodeUpdateService>,
+    failover_service: Option<FailoverService>,
     stats_reporter_service: StatsReporterService,
     gossip_service: GossipService,
     serve_repair_service: ServeRepairService,
@@ -573,7 +770,10 @@ pub struct Validator {
     snapshot_packager_service: Option<SnapshotPackagerService>,
     poh_recorder: Arc<RwLock<PohRecorder>>,
     poh_service: PohService,
-    tpu: Tpu,
+    poh_calibration_service: Option<PohCalibrationService>,
+    leader_stages: Box<dyn LeaderStages>,
+    leader_slot_dump_service: Option<LeaderSlotDumpService>,
+    banking_event_recorder_service: Option<BankingEventRecorderService>,
     tvu: Tvu,
     ip_echo_server: Option<solana_net_utils::IpEchoServer>,
     pub cluster_info: Arc<ClusterInfo>,
@@ -583,6 +783,9 @@ pub struct Validator {
     blockstore_metric_report_service: BlockstoreMetricReportService,
     accounts_background_service: AccountsBackgroundService,
     accounts_hash_verifier: AccountsHashVerifier,
+    gossip_quic_endpoint: Option<Endpoint>,
+    gossip_quic_endpoint_runtime: Option<TokioRuntime>,
+    gossip_quic_endpoint_join_handle: Option<solana_gossip::quic_endpoint::AsyncTryJoinHandle>,
     turbine_quic_endpoint: Option<Endpoint>,
     turbine_quic_endpoint_runtime: Option<TokioRuntime>,
     turbine_quic_endpoint_join_handle: Option<solana_turbine::quic_endpoint::AsyncTryJoinHandle>,
@@ -638,6 +841,11 @@ impl Validator {
         info!("identity pubkey: {id}");
         info!("vote account pubkey: {vote_account}");
 
+        config
+            .vote_refresh_config
+            .validate()
+            .map_err(ValidatorError::Other)?;
+
         if !config.no_os_network_stats_reporting {
             verify_net_stats_access().map_err(|e| {
                 ValidatorError::Other(format!("Failed to access network stats: {e:?}"))
@@ -678,7 +886,7 @@ impl Validator {
                 None
             };
 
-        if config.voting_disabled {
+        if config.voting_disabled || cfg!(feature = "rpc-only") {
             warn!("voting disabled");
             authorized_voter_keypairs.write().unwrap().clear();
         } else {
@@ -697,6 +905,13 @@ impl Validator {
             info!("Initializing sigverify...");
         }
         sigverify::init();
+        if !config.sigverify_thread_pinning.is_empty()
+            && !solana_perf::sigverify::set_thread_pool_pinning(
+                config.sigverify_thread_pinning.clone(),
+            )
+        {
+            warn!("the sigverify thread pool is already started, it is not pinned");
+        }
         info!("Initializing sigverify done.");
 
         if !ledger_path.is_dir() {
@@ -777,12 +992,22 @@ impl Validator {
             .as_ref()
             .and_then(|geyser_plugin_service| geyser_plugin_service.get_slot_status_notifier());
 
+        let node_update_notifier = geyser_plugin_service
+            .as_ref()
+            .and_then(|geyser_plugin_service| geyser_plugin_service.get_node_update_notifier());
+
+        let vote_notifier = geyser_plugin_service
+            .as_ref()
+            .and_then(|geyser_plugin_service| geyser_plugin_service.get_vote_notifier());
+
         info!(
             "Geyser plugin: accounts_update_notifier: {}, transaction_notifier: {}, \
-             entry_notifier: {}",
+             entry_notifier: {}, node_update_notifier: {}, vote_notifier: {}",
             accounts_update_notifier.is_some(),
             transaction_notifier.is_some(),
-            entry_notifier.is_some()
+            entry_notifier.is_some(),
+            node_update_notifier.is_some(),
+            vote_notifier.is_some()
         );
 
         let system_monitor_service = Some(SystemMonitorService::new(
@@ -794,6 +1019,7 @@ impl Validator {
                 report_os_disk_stats: !config.no_os_disk_stats_reporting,
             },
         ));
+        let bandwidth_accounting_service = BandwidthAccountingService::new(exit.clone());
 
         let (
             bank_forks,
@@ -830,6 +1056,18 @@ impl Validator {
             check_poh_speed(&bank_forks.read().unwrap().root_bank(), None)?;
         }
 
+        if !config.pinned_programs.is_empty() {
+            info!(
+                "Pinning programs in the program cache: {:?}",
+                config.pinned_programs
+            );
+            bank_forks
+                .read()
+                .unwrap()
+                .root_bank()
+                .set_pinned_programs_in_program_cache(config.pinned_programs.clone());
+        }
+
         let (root_slot, hard_forks) = {
             let root_bank = bank_forks.read().unwrap().root_bank();
             (root_bank.slot(), root_bank.hard_forks())
@@ -869,6 +1107,15 @@ impl Validator {
 
         node.info.set_shred_version(shred_version);
         node.info.set_wallclock(timestamp());
+        if config.enable_gossip_quic {
+            let gossip_quic_addr = SocketAddr::new(
+                node.info.gossip().unwrap().ip(),
+                node.sockets.gossip_quic.local_addr().unwrap().port(),
+            );
+            node.info
+                .set_gossip_quic(gossip_quic_addr)
+                .expect("Operator must spin up node with valid gossip QUIC address");
+        }
         Self::print_node_info(&node);
 
         let mut cluster_info = ClusterInfo::new(
@@ -877,6 +1124,7 @@ impl Validator {
             socket_addr_space,
         );
         cluster_info.set_contact_debug_interval(config.contact_debug_interval);
+        cluster_info.set_crds_priority_weights(config.gossip_priority_weights.clone());
         cluster_info.set_entrypoints(cluster_entrypoints);
         cluster_info.restore_contact_info(ledger_path, config.contact_save_interval);
         let cluster_info = Arc::new(cluster_info);
@@ -909,6 +1157,10 @@ impl Validator {
             None
         };
 
+        let epoch_accounts_hash_scheduler = Arc::new(
+            EpochAccountsHashScheduler::new(config.epoch_accounts_hash_scheduling.clone())
+                .map_err(|err| ValidatorError::Other(err.to_string()))?,
+        );
         let (accounts_package_sender, accounts_package_receiver) = crossbeam_channel::unbounded();
         let accounts_hash_verifier = AccountsHashVerifier::new(
             accounts_package_sender.clone(),
@@ -916,6 +1168,7 @@ impl Validator {
             pending_snapshot_packages,
             exit.clone(),
             snapshot_controller.clone(),
+            epoch_accounts_hash_scheduler.clone(),
         );
         let snapshot_request_handler = SnapshotRequestHandler {
             snapshot_controller: snapshot_controller.clone(),
@@ -1082,7 +1335,93 @@ impl Validator {
             None,
         ));
 
+        let duplicate_shred_proofs = Arc::new(DuplicateShredProofs::default());
+        if config
+            .pubsub_config
+            .enable_duplicate_shred_proof_subscription
+        {
+            duplicate_shred_proofs.add_notifier(rpc_subscriptions.clone());
+        }
+        let forensic_bundles = config
+            .forensic_bundle_dir
+            .clone()
+            .map(|dir| {
+                ForensicBundles::new(dir, blockstore.clone())
+                    .map(Arc::new)
+                    .map_err(|err| {
+                        ValidatorError::Other(format!(
+                            "Failed to create forensic bundle dir: {err}"
+                        ))
+                    })
+            })
+            .transpose()?;
+        if let Some(forensic_bundles) = &forensic_bundles {
+            duplicate_shred_proofs.add_notifier(forensic_bundles.clone());
+        }
+        if config.pubsub_config.enable_prioritization_fee_subscription {
+            prioritization_fee_cache.add_notifier(rpc_subscriptions.clone());
+        }
+
         let max_slots = Arc::new(MaxSlots::default());
+        let transaction_provenance_cache = Arc::new(TransactionProvenanceCache::new(
+            config.packet_provenance_cache_size,
+        ));
+        let commission_guard = config
+            .commission_guard_config
+            .clone()
+            .map(|config| Arc::new(CommissionGuard::new(*vote_account, config)));
+        let slot_account_filter_cache = Arc::new(SlotAccountFilterCache::new(
+            config.rpc_config.slot_account_filter_slots,
+        ));
+        let shred_latency_tracker = Arc::new(ShredLatencyTracker::new(
+            config.shred_latency_slots,
+            leader_schedule_cache.clone(),
+        ));
+        let leader_slot_stats_tracker =
+            Arc::new(LeaderSlotStatsTracker::new(config.leader_slot_stats_slots));
+        let confirmation_latency_tracker = Arc::new(ConfirmationLatencyTracker::new(
+            config.confirmation_latency_slots,
+            blockstore.clone(),
+        ));
+        let skipped_slot_reasons_tracker = Arc::new(SkippedSlotReasonsTracker::new(
+            config.skipped_slot_reasons_slots,
+            blockstore.clone(),
+            leader_schedule_cache.clone(),
+        ));
+        let account_congestion_tracker = Arc::new(AccountCongestionTracker::new(
+            config.account_congestion_slots,
+        ));
+        let confirmation_proof_cache =
+            Arc::new(ConfirmationProofCache::new(config.confirmation_proof_slots));
+        let buffered_transaction_sampler = Arc::new(BufferedTransactionSampler::new(
+            config.buffered_transaction_samples,
+        ));
+        let account_growth_tracker =
+            Arc::new(AccountGrowthTracker::new(config.account_growth_epochs));
+        let account_growth_service = account_growth_tracker.is_enabled().then(|| {
+            AccountGrowthService::new(&bank_forks, account_growth_tracker.clone(), exit.clone())
+        });
+        let bank_hash_breakdowns = Arc::new(BankHashBreakdownTracker::new(
+            config.bank_hash_breakdown_slots,
+        ));
+        let leader_pause = Arc::new(LeaderPause::new(cluster_info.clone()));
+        let cost_calibration = (config.cost_calibration_report_interval_ms > 0).then(|| {
+            Arc::new(CostCalibration::new(
+                config.cost_calibration_report_interval_ms,
+            ))
+        });
+        let pipeline_tracer = config
+            .pipeline_tracing_config
+            .as_ref()
+            .map(|config| PipelineTracer::new_otlp(config).map(Arc::new))
+            .transpose()
+            .map_err(|err| ValidatorError::Other(err.to_string()))?;
+        let remote_shred_signer = config
+            .remote_shred_signer
+            .clone()
+            .map(|config| RemoteShredSigner::new(config).map(Arc::new))
+            .transpose()
+            .map_err(|err| ValidatorError::Other(err.to_string()))?;
 
         let staked_nodes = Arc::new(RwLock::new(StakedNodes::default()));
 
@@ -1183,6 +1522,17 @@ impl Validator {
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
                 prioritization_fee_cache: prioritization_fee_cache.clone(),
+                transaction_provenance_cache: transaction_provenance_cache.clone(),
+                slot_account_filter_cache: slot_account_filter_cache.clone(),
+                shred_latency_tracker: shred_latency_tracker.clone(),
+                leader_slot_stats_tracker: leader_slot_stats_tracker.clone(),
+                confirmation_latency_tracker: confirmation_latency_tracker.clone(),
+                skipped_slot_reasons_tracker: skipped_slot_reasons_tracker.clone(),
+                account_congestion_tracker: account_congestion_tracker.clone(),
+                confirmation_proof_cache: confirmation_proof_cache.clone(),
+                buffered_transaction_sampler: buffered_transaction_sampler.clone(),
+                account_growth_tracker: account_growth_tracker.clone(),
+                commission_guard: commission_guard.clone(),
                 client_option: if config.use_tpu_client_next {
                     ClientOption::TpuClientNext(
                         Arc::as_ref(&identity_keypair),
@@ -1256,6 +1606,8 @@ impl Validator {
                     rpc_subscriptions.clone(),
                     confirmed_bank_subscribers,
                     prioritization_fee_cache.clone(),
+                    slot_account_filter_cache,
+                    confirmation_latency_tracker,
                 ));
             let bank_notification_sender_config = Some(BankNotificationSenderConfig {
                 sender: bank_notification_sender,
@@ -1301,20 +1653,63 @@ impl Validator {
         let stats_reporter_service =
             StatsReporterService::new(stats_reporter_receiver, exit.clone());
 
+        // test-validator crate may start the validator in a tokio runtime
+        // context which forces us to use the same runtime because a nested
+        // runtime will cause panic at drop.
+        // Outside test-validator crate, we always need a tokio runtime (and
+        // the respective handle) to initialize the gossip and turbine QUIC
+        // endpoints.
+        let current_runtime_handle = tokio::runtime::Handle::try_current();
+        let gossip_quic_endpoint_runtime =
+            (current_runtime_handle.is_err() && config.enable_gossip_quic).then(|| {
+                tokio::runtime::Builder::new_multi_thread()
+                    .enable_all()
+                    .thread_name("solGossipQuic")
+                    .build()
+                    .unwrap()
+            });
+        let (gossip_quic_endpoint, gossip_quic_channels, gossip_quic_endpoint_join_handle) =
+            if config.enable_gossip_quic {
+                let (sender, receiver) = unbounded();
+                let (endpoint, sender, join_handle) =
+                    solana_gossip::quic_endpoint::new_quic_endpoint(
+                        gossip_quic_endpoint_runtime
+                            .as_ref()
+                            .map(TokioRuntime::handle)
+                            .unwrap_or_else(|| current_runtime_handle.as_ref().unwrap()),
+                        &identity_keypair,
+                        node.sockets.gossip_quic,
+                        sender,
+                    )
+                    .unwrap();
+                (
+                    Some(endpoint),
+                    Some(GossipQuicChannels { sender, receiver }),
+                    Some(join_handle),
+                )
+            } else {
+                (None, None, None)
+            };
         let gossip_service = GossipService::new(
             &cluster_info,
             Some(bank_forks.clone()),
             node.sockets.gossip,
+            gossip_quic_channels,
             config.gossip_validators.clone(),
             should_check_duplicate_instance,
             Some(stats_reporter_sender.clone()),
             exit.clone(),
         );
+        let fec_ratio = config
+            .broadcast_fec_ratio
+            .clone()
+            .map(|config| Arc::new(FecRatio::new(config)));
         let serve_repair = ServeRepair::new(
             cluster_info.clone(),
             bank_forks.clone(),
             config.repair_whitelist.clone(),
-        );
+        )
+        .with_fec_ratio(fec_ratio.clone());
         let (repair_request_quic_sender, repair_request_quic_receiver) = unbounded();
         let (repair_response_quic_sender, repair_response_quic_receiver) = unbounded();
         let (ancestor_hashes_response_quic_sender, ancestor_hashes_response_quic_receiver) =
@@ -1335,22 +1730,36 @@ impl Validator {
         let wait_for_vote_to_start_leader =
             !waited_for_supermajority && !config.no_wait_for_vote_to_start_leader;
 
-        let poh_service = PohService::new(
+        let poh_calibration = Arc::new(PohCalibration::new(config.poh_hashes_per_batch));
+        let poh_service = PohService::new_with_calibration(
             poh_recorder.clone(),
             &genesis_config.poh_config,
             exit.clone(),
             bank_forks.read().unwrap().root_bank().ticks_per_slot(),
             config.poh_pinned_cpu_core,
-            config.poh_hashes_per_batch,
+            config.poh_numa_node,
+            poh_calibration.clone(),
             record_receiver,
         );
+        let poh_calibration_service = (config.poh_calibration_interval_ms > 0).then(|| {
+            PohCalibrationService::new(
+                poh_calibration.clone(),
+                bank_forks.clone(),
+                Duration::from_millis(config.poh_calibration_interval_ms),
+                exit.clone(),
+            )
+        });
         assert_eq!(
             blockstore.get_new_shred_signals_len(),
             1,
             "New shred signal for the TVU should be the same as the clear bank signal."
         );
 
-        let vote_tracker = Arc::<VoteTracker>::default();
+        let vote_tracker = Arc::new(VoteTracker::new(
+            confirmation_proof_cache
+                .is_enabled()
+                .then_some(confirmation_proof_cache),
+        ));
 
         let (retransmit_slots_sender, retransmit_slots_receiver) = unbounded();
         let (verified_vote_sender, verified_vote_receiver) = unbounded();
@@ -1361,12 +1770,6 @@ impl Validator {
             .as_ref()
             .map(|service| service.sender_cloned());
 
-        // test-validator crate may start the validator in a tokio runtime
-        // context which forces us to use the same runtime because a nested
-        // runtime will cause panic at drop.
-        // Outside test-validator crate, we always need a tokio runtime (and
-        // the respective handle) to initialize the turbine QUIC endpoint.
-        let current_runtime_handle = tokio::runtime::Handle::try_current();
         let turbine_quic_endpoint_runtime = (current_runtime_handle.is_err()
             && genesis_config.cluster_type != ClusterType::MainnetBeta)
             .then(|| {
@@ -1438,6 +1841,14 @@ impl Validator {
                 })
                 .unwrap()
             };
+        let repair_protocol = if !config.repair_quic {
+            Protocol::UDP
+        } else if repair_quic_endpoints.is_none() {
+            warn!("Repair QUIC endpoints are not started, requesting repairs over UDP");
+            Protocol::UDP
+        } else {
+            Protocol::QUIC
+        };
         let serve_repair_service = ServeRepairService::new(
             serve_repair,
             // Incoming UDP repair requests are adapted into RemoteRequest
@@ -1473,6 +1884,44 @@ impl Validator {
         };
         let last_vote = tower.last_vote();
 
+        let program_precompiler = Arc::new(ProgramPrecompiler::new(
+            blockstore.clone(),
+            bank_forks.clone(),
+            config.precompile_programs_slots,
+        ));
+        if config.precompile_programs > 0 {
+            *start_progress.write().unwrap() = ValidatorStartProgress::CompilingPrograms;
+            match program_precompiler.precompile(config.precompile_programs) {
+                Ok(report) => info!(
+                    "Precompiled {} of the {} most invoked programs for slot {} in {}ms",
+                    report.num_compiled, report.num_candidates, report.slot, report.elapsed_ms
+                ),
+                Err(err) => warn!("Unable to precompile the most invoked programs: {err}"),
+            }
+            *start_progress.write().unwrap() = ValidatorStartProgress::StartingServices;
+        }
+
+        let clock_drift = Arc::new(ClockDrift::new(config.clock_drift_config.refuse_to_vote));
+        let clock_drift_service = ClockDriftService::new(
+            config.clock_drift_config.max_drift_ms,
+            clock_drift.clone(),
+            cluster_info.clone(),
+            bank_forks.clone(),
+            exit.clone(),
+        );
+        let node_update_service = node_update_notifier.map(|node_update_notifier| {
+            NodeUpdateService::new(cluster_info.clone(), node_update_notifier, exit.clone())
+        });
+        let blockstore_compaction_scheduler =
+            Arc::new(BlockstoreCompactionScheduler::new(blockstore.clone()));
+        let blockstore_compaction_service = BlockstoreCompactionService::new(
+            blockstore_compaction_scheduler.clone(),
+            bank_forks.clone(),
+            leader_schedule_cache.clone(),
+            cluster_info.clone(),
+            exit.clone(),
+        );
+
         let outstanding_repair_requests =
             Arc::<RwLock<repair::repair_service::OutstandingShredRepairs>>::default();
         let cluster_slots =
@@ -1523,8 +1972,28 @@ impl Validator {
                 wait_for_vote_to_start_leader,
                 replay_forks_threads: config.replay_forks_threads,
                 replay_transactions_threads: config.replay_transactions_threads,
+                replay_fork_transactions_threads: config.replay_fork_transactions_threads,
+                entry_verification_cpus: config.entry_verification_cpus.clone(),
                 shred_sigverify_threads: config.tvu_shred_sigverify_threads,
                 retransmit_xdp: config.retransmit_xdp.clone(),
+                retransmit_egress_shaper: config.retransmit_egress_shaper,
+                shredstream: config.shredstream.clone(),
+                clock_drift: Some(clock_drift),
+                forensic_bundles: forensic_bundles.clone(),
+                vote_refresh_config: config.vote_refresh_config.clone(),
+                skipped_slot_reasons: skipped_slot_reasons_tracker
+                    .is_enabled()
+                    .then_some(skipped_slot_reasons_tracker),
+                cost_calibration: cost_calibration.clone(),
+                account_congestion: account_congestion_tracker
+                    .is_enabled()
+                    .then_some(account_congestion_tracker),
+                bank_hash_breakdowns: bank_hash_breakdowns
+                    .is_enabled()
+                    .then(|| bank_hash_breakdowns.clone()),
+                leader_pause: Some(leader_pause.clone()),
+                repair_protocol,
+                repair_peer_selection: config.repair_peer_selection,
             },
             &max_slots,
             block_metadata_notifier,
@@ -1545,6 +2014,8 @@ impl Validator {
             wen_restart_repair_slots.clone(),
             slot_status_notifier,
             vote_connection_cache,
+            duplicate_shred_proofs.clone(),
+            shred_latency_tracker,
         )
         .map_err(ValidatorError::Other)?;
 
@@ -1568,57 +2039,134 @@ impl Validator {
             return Err(ValidatorError::WenRestartFinished.into());
         }
 
-        let (tpu, mut key_notifies) = Tpu::new(
-            &cluster_info,
-            &poh_recorder,
-            transaction_recorder,
-            entry_receiver,
-            retransmit_slots_receiver,
-            TpuSockets {
-                transactions: node.sockets.tpu,
-                transaction_forwards: node.sockets.tpu_forwards,
-                vote: node.sockets.tpu_vote,
-                broadcast: node.sockets.broadcast,
-                transactions_quic: node.sockets.tpu_quic,
-                transactions_forwards_quic: node.sockets.tpu_forwards_quic,
-                vote_quic: node.sockets.tpu_vote_quic,
-                vote_forwards_client: node.sockets.tpu_vote_forwards_client,
-                vortexor_receivers: node.sockets.vortexor_receivers,
-            },
-            &rpc_subscriptions,
-            transaction_status_sender,
-            entry_notification_sender,
-            blockstore.clone(),
-            &config.broadcast_stage_type,
-            exit,
-            node.info.shred_version(),
-            vote_tracker,
-            bank_forks.clone(),
-            verified_vote_sender,
-            gossip_verified_vote_hash_sender,
-            replay_vote_receiver,
-            replay_vote_sender,
-            bank_notification_sender.map(|sender| sender.sender),
-            config.tpu_coalesce,
-            duplicate_confirmed_slot_sender,
-            &connection_cache,
-            turbine_quic_endpoint_sender,
-            &identity_keypair,
-            config.runtime_config.log_messages_bytes_limit,
-            &staked_nodes,
-            config.staked_nodes_overrides.clone(),
-            banking_tracer_channels,
-            tracer_thread,
-            tpu_enable_udp,
-            tpu_quic_server_config,
-            tpu_fwd_quic_server_config,
-            vote_quic_server_config,
-            &prioritization_fee_cache,
-            config.block_production_method.clone(),
-            config.transaction_struct.clone(),
-            config.enable_block_production_forwarding,
-            config.generator_config.clone(),
-        );
+        let (leader_slot_dump_service, leader_slot_dump_sender) =
+            match &config.dump_leader_slot_transactions {
+                Some(dump_dir) => {
+                    let (service, sender) = LeaderSlotDumpService::new(dump_dir.clone())
+                        .with_context(|| {
+                            format!("failed to start leader slot dump to {}", dump_dir.display())
+                        })?;
+                    info!("Dumping leader slot transactions to {}", dump_dir.display());
+                    (Some(service), Some(sender))
+                }
+                None => (None, None),
+            };
+        let (banking_event_recorder_service, banking_event_recorder) =
+            match &config.record_banking_events {
+                Some(events_dir) => {
+                    let (service, recorder) = BankingEventRecorderService::new(events_dir.clone())
+                        .with_context(|| {
+                            format!(
+                                "failed to start recording banking events to {}",
+                                events_dir.display()
+                            )
+                        })?;
+                    info!("Recording banking events to {}", events_dir.display());
+                    (Some(service), Some(recorder))
+                }
+                None => (None, None),
+            };
+
+        // Nodes built with the rpc-only feature never produce blocks, so the
+        // TPU is left out of the binary.
+        let (leader_stages, mut key_notifies): (Box<dyn LeaderStages>, Vec<_>) =
+            if cfg!(feature = "rpc-only") {
+                info!("RPC-only node, not starting the TPU");
+                let rpc_only_stages = RpcOnlyStages::new(
+                    &cluster_info,
+                    entry_receiver,
+                    retransmit_slots_receiver,
+                    &rpc_subscriptions,
+                    blockstore.clone(),
+                    exit,
+                    vote_tracker,
+                    bank_forks.clone(),
+                    verified_vote_sender,
+                    gossip_verified_vote_hash_sender,
+                    replay_vote_receiver,
+                    bank_notification_sender.map(|sender| sender.sender),
+                    duplicate_confirmed_slot_sender,
+                    banking_tracer_channels,
+                    tracer_thread,
+                    vote_notifier,
+                );
+                (Box::new(rpc_only_stages), Vec::new())
+            } else {
+                let (tpu, key_notifies) = Tpu::new(
+                    &cluster_info,
+                    &poh_recorder,
+                    transaction_recorder,
+                    entry_receiver,
+                    retransmit_slots_receiver,
+                    TpuSockets {
+                        transactions: node.sockets.tpu,
+                        transaction_forwards: node.sockets.tpu_forwards,
+                        vote: node.sockets.tpu_vote,
+                        broadcast: node.sockets.broadcast,
+                        transactions_quic: node.sockets.tpu_quic,
+                        transactions_forwards_quic: node.sockets.tpu_forwards_quic,
+                        vote_quic: node.sockets.tpu_vote_quic,
+                        vote_forwards_client: node.sockets.tpu_vote_forwards_client,
+                        vortexor_receivers: node.sockets.vortexor_receivers,
+                    },
+                    &rpc_subscriptions,
+                    transaction_status_sender,
+                    entry_notification_sender,
+                    blockstore.clone(),
+                    &config.broadcast_stage_type,
+                    exit,
+                    node.info.shred_version(),
+                    vote_tracker,
+                    bank_forks.clone(),
+                    verified_vote_sender,
+                    gossip_verified_vote_hash_sender,
+                    replay_vote_receiver,
+                    replay_vote_sender,
+                    bank_notification_sender.map(|sender| sender.sender),
+                    config.tpu_coalesce,
+                    duplicate_confirmed_slot_sender,
+                    &connection_cache,
+                    turbine_quic_endpoint_sender,
+                    &identity_keypair,
+                    config.runtime_config.log_messages_bytes_limit,
+                    &staked_nodes,
+                    config.staked_nodes_overrides.clone(),
+                    banking_tracer_channels,
+                    tracer_thread,
+                    tpu_enable_udp,
+                    tpu_quic_server_config,
+                    tpu_fwd_quic_server_config,
+                    vote_quic_server_config,
+                    &prioritization_fee_cache,
+                    config.block_production_method.clone(),
+                    config.transaction_struct.clone(),
+                    config.enable_block_production_forwarding,
+                    config.forwarding_leader_fanout,
+                    transaction_provenance_cache.is_enabled().then(|| {
+                        Arc::new(PacketProvenanceTracker::new(
+                            transaction_provenance_cache,
+                            config.packet_provenance_cache_size,
+                        ))
+                    }),
+                    leader_slot_dump_sender,
+                    vote_notifier,
+                    leader_slot_stats_tracker
+                        .is_enabled()
+                        .then_some(leader_slot_stats_tracker),
+                    cost_calibration,
+                    pipeline_tracer,
+                    buffered_transaction_sampler
+                        .is_enabled()
+                        .then_some(buffered_transaction_sampler),
+                    banking_event_recorder,
+                    remote_shred_signer,
+                    fec_ratio,
+                    config.banking_thread_pinning.clone(),
+                    config.sigverify_thread_pinning.clone(),
+                    config.generator_config.clone(),
+                );
+                (Box::new(tpu), key_notifies)
+            };
 
         datapoint_info!(
             "validator-new",
@@ -1639,6 +2187,20 @@ impl Validator {
         // add connection_cache because it is still used in Forwarder.
         key_notifies.push(connection_cache);
 
+        let failover = config.failover_config.clone().map(|failover_config| {
+            Arc::new(FailoverCoordinator::new(
+                failover_config,
+                cluster_info.clone(),
+                config.tower_storage.clone(),
+                key_notifies.clone(),
+            ))
+        });
+        let failover_service = failover
+            .as_ref()
+            .map(|failover| FailoverService::new(failover.clone(), exit.clone()))
+            .transpose()
+            .context("failed to start the failover service")?;
+
         *admin_rpc_service_post_init.write().unwrap() = Some(AdminRpcRequestMetadataPostInit {
             bank_forks: bank_forks.clone(),
             cluster_info: cluster_info.clone(),
@@ -1648,6 +2210,22 @@ impl Validator {
             repair_socket: Arc::new(node.sockets.repair),
             outstanding_repair_requests,
             cluster_slots,
+            banking_stage: leader_stages.banking_stage_handle(),
+            duplicate_shred_proofs,
+            forensic_bundles,
+            poh_calibration: poh_calibration_service.is_some().then_some(poh_calibration),
+            commission_guard,
+            blockstore_compaction_scheduler,
+            program_precompiler,
+            bank_hash_breakdowns,
+            failover,
+            leader_pause,
+            epoch_accounts_hash_scheduler,
+            snapshot_packaging_progress: snapshot_controller.packaging_progress().clone(),
+            snapshot_archive_verification_status: snapshot_controller
+                .archive_verification_status()
+                .clone(),
+            rpc_health: json_rpc_service.as_ref().map(JsonRpcService::health),
         });
 
         Ok(Self {
@@ -1662,12 +2240,21 @@ impl Validator {
             block_meta_service,
             entry_notifier_service,
             system_monitor_service,
+            bandwidth_accounting_service,
             sample_performance_service,
+            account_growth_service,
+            clock_drift_service,
+            blockstore_compaction_service,
+            node_update_service,
+            failover_service,
             snapshot_packager_service,
             completed_data_sets_service,
-            tpu,
+            leader_stages,
+            leader_slot_dump_service,
+            banking_event_recorder_service,
             tvu,
             poh_service,
+            poh_calibration_service,
             poh_recorder,
             ip_echo_server,
             validator_exit: config.validator_exit.clone(),
@@ -1678,6 +2265,9 @@ impl Validator {
             blockstore_metric_report_service,
             accounts_background_service,
             accounts_hash_verifier,
+            gossip_quic_endpoint,
+            gossip_quic_endpoint_runtime,
+            gossip_quic_endpoint_join_handle,
             turbine_quic_endpoint,
             turbine_quic_endpoint_runtime,
             turbine_quic_endpoint_join_handle,
@@ -1730,6 +2320,11 @@ impl Validator {
         drop(self.cluster_info);
 
         self.poh_service.join().expect("poh_service");
+        if let Some(poh_calibration_service) = self.poh_calibration_service {
+            poh_calibration_service
+                .join()
+                .expect("poh_calibration_service");
+        }
         drop(self.poh_recorder);
 
         if let Some(json_rpc_service) = self.json_rpc_service {
@@ -1770,12 +2365,38 @@ impl Validator {
                 .expect("system_monitor_service");
         }
 
+        self.bandwidth_accounting_service
+            .join()
+            .expect("bandwidth_accounting_service");
+
         if let Some(sample_performance_service) = self.sample_performance_service {
             sample_performance_service
                 .join()
                 .expect("sample_performance_service");
         }
 
+        if let Some(account_growth_service) = self.account_growth_service {
+            account_growth_service
+                .join()
+                .expect("account_growth_service");
+        }
+
+        self.clock_drift_service
+            .join()
+            .expect("clock_drift_service");
+
+        self.blockstore_compaction_service
+            .join()
+            .expect("blockstore_compaction_service");
+
+        if let Some(node_update_service) = self.node_update_service {
+            node_update_service.join().expect("node_update_service");
+        }
+
+        if let Some(failover_service) = self.failover_service {
+            failover_service.join().expect("failover_service");
+        }
+
         if let Some(entry_notifier_service) = self.entry_notifier_service {
             entry_notifier_service
                 .join()
@@ -1787,6 +2408,15 @@ impl Validator {
         }
 
         self.gossip_service.join().expect("gossip_service");
+        if let Some(gossip_quic_endpoint) = &self.gossip_quic_endpoint {
+            solana_gossip::quic_endpoint::close_quic_endpoint(gossip_quic_endpoint);
+        }
+        if let Some(gossip_quic_endpoint_join_handle) = self.gossip_quic_endpoint_join_handle {
+            self.gossip_quic_endpoint_runtime
+                .map(|runtime| runtime.block_on(gossip_quic_endpoint_join_handle))
+                .transpose()
+                .unwrap();
+        }
         self.repair_quic_endpoints
             .iter()
             .flatten()
@@ -1815,7 +2445,17 @@ impl Validator {
         if let Some(turbine_quic_endpoint) = &self.turbine_quic_endpoint {
             solana_turbine::quic_endpoint::close_quic_endpoint(turbine_quic_endpoint);
         }
-        self.tpu.join().expect("tpu");
+        self.leader_stages.join().expect("leader_stages");
+        if let Some(leader_slot_dump_service) = self.leader_slot_dump_service {
+            leader_slot_dump_service
+                .join()
+                .expect("leader_slot_dump_service");
+        }
+        if let Some(banking_event_recorder_service) = self.banking_event_recorder_service {
+            banking_event_recorder_service
+                .join()
+                .expect("banking_event_recorder_service");
+        }
         self.tvu.join().expect("tvu");
         if let Some(turbine_quic_endpoint_join_handle) = self.turbine_quic_endpoint_join_handle {
             self.turbine_quic_endpoint_runtime
@@ -1853,16 +2493,11 @@ fn check_poh_speed(bank: &Bank, maybe_hash_samples: Option<u64>) -> Result<(), V
         return Ok(());
     };
 
-    let ticks_per_slot = bank.ticks_per_slot();
-    let hashes_per_slot = hashes_per_tick * ticks_per_slot;
+    let hashes_per_slot = hashes_per_tick * bank.ticks_per_slot();
     let hash_samples = maybe_hash_samples.unwrap_or(hashes_per_slot);
-
-    let hash_time = compute_hash_time(hash_samples);
-    let my_hashes_per_second = (hash_samples as f64 / hash_time.as_secs_f64()) as u64;
-
-    let target_slot_duration = Duration::from_nanos(bank.ns_per_slot as u64);
-    let target_hashes_per_second =
-        (hashes_per_slot as f64 / target_slot_duration.as_secs_f64()) as u64;
+    let my_hashes_per_second = poh_calibration_service::measure_hashes_per_second(hash_samples);
+    let target_hashes_per_second = poh_calibration_service::target_hashes_per_second(bank)
+        .expect("the bank has hashes per tick");
 
     info!(
         "PoH speed check: computed hashes per second {my_hashes_per_second}, target hashes per \
@@ -2070,6 +2705,8 @@ fn load_blockstore(
                 exit.clone(),
                 enable_rpc_transaction_history,
                 config.rpc_config.enable_extended_tx_metadata_storage,
+                config.rpc_config.enable_program_signature_index,
+                config.rpc_config.transaction_status_filter,
                 transaction_notifier,
             )
         } else {
@@ -2493,6 +3130,8 @@ fn initialize_rpc_transaction_history_services(
     exit: Arc<AtomicBool>,
     enable_rpc_transaction_history: bool,
     enable_extended_tx_metadata_storage: bool,
+    enable_program_signature_index: bool,
+    transaction_status_filter: TransactionStatusFilter,
     transaction_notifier: Option<TransactionNotifierArc>,
 ) -> TransactionHistoryServices {
     let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(blockstore.max_root()));
@@ -2507,6 +3146,8 @@ fn initialize_rpc_transaction_history_services(
         transaction_notifier,
         blockstore.clone(),
         enable_extended_tx_metadata_storage,
+        enable_program_signature_index,
+        transaction_status_filter,
         exit.clone(),
     ));
 
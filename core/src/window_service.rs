@@ -29,6 +29,7 @@ use {
     solana_turbine::cluster_nodes,
     std::{
         borrow::Cow,
+        collections::BTreeSet,
         net::UdpSocket,
         sync::{
             atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -103,14 +104,90 @@ impl WindowServiceMetrics {
     }
 }
 
+#[derive(Default)]
+struct DuplicateShredStats {
+    num_last_index_conflicts: usize,
+    num_erasure_conflicts: usize,
+    num_merkle_root_conflicts: usize,
+    num_chained_merkle_root_conflicts: usize,
+    // Chained merkle root conflicts before the feature activation, which are
+    // neither stored nor gossiped.
+    num_chained_merkle_root_conflicts_inactive: usize,
+    num_existing_shred_conflicts: usize,
+    // Slots for which a proof was already gossiped.
+    num_proofs_deduped: usize,
+    num_proofs_pushed: usize,
+    num_push_errors: usize,
+    num_store_errors: usize,
+}
+
+impl DuplicateShredStats {
+    fn record_conflict(&mut self, shred: &PossibleDuplicateShred) {
+        match shred {
+            PossibleDuplicateShred::LastIndexConflict(..) => self.num_last_index_conflicts += 1,
+            PossibleDuplicateShred::ErasureConflict(..) => self.num_erasure_conflicts += 1,
+            PossibleDuplicateShred::MerkleRootConflict(..) => self.num_merkle_root_conflicts += 1,
+            PossibleDuplicateShred::ChainedMerkleRootConflict(..) => {
+                self.num_chained_merkle_root_conflicts += 1
+            }
+            PossibleDuplicateShred::Exists(_) => self.num_existing_shred_conflicts += 1,
+        }
+    }
+
+    fn report_metrics(&self) {
+        datapoint_info!(
+            "window-service-duplicate-shreds",
+            (
+                "num_last_index_conflicts",
+                self.num_last_index_conflicts,
+                i64
+            ),
+            ("num_erasure_conflicts", self.num_erasure_conflicts, i64),
+            (
+                "num_merkle_root_conflicts",
+                self.num_merkle_root_conflicts,
+                i64
+            ),
+            (
+                "num_chained_merkle_root_conflicts",
+                self.num_chained_merkle_root_conflicts,
+                i64
+            ),
+            (
+                "num_chained_merkle_root_conflicts_inactive",
+                self.num_chained_merkle_root_conflicts_inactive,
+                i64
+            ),
+            (
+                "num_existing_shred_conflicts",
+                self.num_existing_shred_conflicts,
+                i64
+            ),
+            ("num_proofs_deduped", self.num_proofs_deduped, i64),
+            ("num_proofs_pushed", self.num_proofs_pushed, i64),
+            ("num_push_errors", self.num_push_errors, i64),
+            ("num_store_errors", self.num_store_errors, i64),
+        );
+    }
+}
+
+// Constructs and gossips a duplicate proof of each slot with conflicting
+// shreds, and notifies the duplicate consensus state machine of the slot.
+// gossiped_slots are the slots at or above the root whose proofs were
+// already gossiped, which are not pushed again. Failures to store or gossip a
+// proof are counted and the remaining shreds are still handled, and a proof
+// which failed to gossip is retried on the next conflict of the slot.
 fn run_check_duplicate(
     cluster_info: &ClusterInfo,
     blockstore: &Blockstore,
     shred_receiver: &Receiver<PossibleDuplicateShred>,
     duplicate_slots_sender: &DuplicateSlotSender,
     bank_forks: &RwLock<BankForks>,
+    gossiped_slots: &mut BTreeSet<Slot>,
+    stats: &mut DuplicateShredStats,
 ) -> Result<()> {
     let mut root_bank = bank_forks.read().unwrap().root_bank();
+    *gossiped_slots = gossiped_slots.split_off(&root_bank.slot());
     let mut last_updated = Instant::now();
     let check_duplicate = |shred: PossibleDuplicateShred| -> Result<()> {
         if last_updated.elapsed().as_millis() as u64 > DEFAULT_MS_PER_SLOT {
@@ -119,6 +196,11 @@ fn run_check_duplicate(
             root_bank = bank_forks.read().unwrap().root_bank();
         }
         let shred_slot = shred.slot();
+        stats.record_conflict(&shred);
+        if gossiped_slots.contains(&shred_slot) {
+            stats.num_proofs_deduped += 1;
+            return Ok(());
+        }
         let chained_merkle_conflict_duplicate_proofs = cluster_nodes::check_feature_activation(
             &feature_set::chained_merkle_conflict_duplicate_proofs::id(),
             shred_slot,
@@ -136,13 +218,17 @@ fn run_check_duplicate(
                     if blockstore.has_duplicate_shreds_in_slot(shred_slot) {
                         return Ok(());
                     }
-                    blockstore.store_duplicate_slot(
+                    if let Err(err) = blockstore.store_duplicate_slot(
                         shred_slot,
                         conflict.clone(),
                         shred.clone().into_payload(),
-                    )?;
+                    ) {
+                        error!("failed to store duplicate proof of slot {shred_slot}: {err}");
+                        stats.num_store_errors += 1;
+                    }
                     (shred, conflict)
                 } else {
+                    stats.num_chained_merkle_root_conflicts_inactive += 1;
                     return Ok(());
                 }
             }
@@ -156,17 +242,29 @@ fn run_check_duplicate(
                 let Some(existing_shred_payload) = blockstore.is_shred_duplicate(&shred) else {
                     return Ok(()); // Not a duplicate
                 };
-                blockstore.store_duplicate_slot(
+                if let Err(err) = blockstore.store_duplicate_slot(
                     shred_slot,
                     existing_shred_payload.clone(),
                     shred.clone().into_payload(),
-                )?;
+                ) {
+                    error!("failed to store duplicate proof of slot {shred_slot}: {err}");
+                    stats.num_store_errors += 1;
+                }
                 (shred, shred::Payload::from(existing_shred_payload))
             }
         };
 
         // Propagate duplicate proof through gossip
-        cluster_info.push_duplicate_shred(&shred1, &shred2)?;
+        match cluster_info.push_duplicate_shred(&shred1, &shred2) {
+            Ok(()) => {
+                stats.num_proofs_pushed += 1;
+                gossiped_slots.insert(shred_slot);
+            }
+            Err(err) => {
+                warn!("failed to push duplicate proof of slot {shred_slot}: {err}");
+                stats.num_push_errors += 1;
+            }
+        }
         // Notify duplicate consensus state machine
         duplicate_slots_sender.send(shred_slot)?;
 
@@ -353,6 +451,9 @@ impl WindowService {
         Builder::new()
             .name("solWinCheckDup".to_string())
             .spawn(move || {
+                let mut gossiped_slots = BTreeSet::new();
+                let mut stats = DuplicateShredStats::default();
+                let mut last_print = Instant::now();
                 while !exit.load(Ordering::Relaxed) {
                     if let Err(e) = run_check_duplicate(
                         &cluster_info,
@@ -360,11 +461,19 @@ impl WindowService {
                         &duplicate_receiver,
                         &duplicate_slots_sender,
                         &bank_forks,
+                        &mut gossiped_slots,
+                        &mut stats,
                     ) {
                         if Self::should_exit_on_error(e, &handle_error) {
                             break;
                         }
                     }
+
+                    if last_print.elapsed().as_secs() > 2 {
+                        stats.report_metrics();
+                        stats = DuplicateShredStats::default();
+                        last_print = Instant::now();
+                    }
                 }
             })
             .unwrap()
@@ -547,12 +656,16 @@ mod test {
             Arc::new(keypair),
             SocketAddrSpace::Unspecified,
         );
+        let mut gossiped_slots = BTreeSet::new();
+        let mut stats = DuplicateShredStats::default();
         run_check_duplicate(
             &cluster_info,
             &blockstore,
             &receiver,
             &duplicate_slot_sender,
             &bank_forks,
+            &mut gossiped_slots,
+            &mut stats,
         )
         .unwrap();
 
@@ -566,6 +679,31 @@ mod test {
             duplicate_slot_receiver.try_recv().unwrap(),
             duplicate_shred_slot
         );
+        assert_eq!(stats.num_existing_shred_conflicts, 1);
+        assert_eq!(stats.num_proofs_pushed, 1);
+        assert!(gossiped_slots.contains(&duplicate_shred_slot));
+
+        // The proof of the slot is gossiped only once, whatever the conflict.
+        sender
+            .send(PossibleDuplicateShred::MerkleRootConflict(
+                duplicate_shred.clone(),
+                original_shred.payload().clone(),
+            ))
+            .unwrap();
+        run_check_duplicate(
+            &cluster_info,
+            &blockstore,
+            &receiver,
+            &duplicate_slot_sender,
+            &bank_forks,
+            &mut gossiped_slots,
+            &mut stats,
+        )
+        .unwrap();
+        assert_eq!(stats.num_merkle_root_conflicts, 1);
+        assert_eq!(stats.num_proofs_deduped, 1);
+        assert_eq!(stats.num_proofs_pushed, 1);
+        assert!(duplicate_slot_receiver.try_recv().is_err());
     }
 
     #[test]
@@ -11,6 +11,7 @@ use {
     },
     solana_core::{
         accounts_hash_verifier::AccountsHashVerifier,
+        epoch_accounts_hash_scheduling::EpochAccountsHashScheduler,
         snapshot_packager_service::{PendingSnapshotPackages, SnapshotPackagerService},
     },
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfo},
@@ -199,6 +200,7 @@ impl BackgroundServices {
             pending_snapshot_packages,
             exit.clone(),
             snapshot_controller.clone(),
+            Arc::new(EpochAccountsHashScheduler::default()),
         );
 
         let snapshot_request_handler = SnapshotRequestHandler {
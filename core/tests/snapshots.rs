@@ -11,6 +11,7 @@ use {
     },
     solana_core::{
         accounts_hash_verifier::AccountsHashVerifier,
+        epoch_accounts_hash_scheduling::EpochAccountsHashScheduler,
         snapshot_packager_service::{PendingSnapshotPackages, SnapshotPackagerService},
     },
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfo},
@@ -748,6 +749,7 @@ fn test_snapshots_with_background_services(
         pending_snapshot_packages,
         exit.clone(),
         snapshot_controller.clone(),
+        Arc::new(EpochAccountsHashScheduler::default()),
     );
 
     let accounts_background_service = AccountsBackgroundService::new(
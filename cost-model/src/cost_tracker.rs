@@ -57,6 +57,26 @@ pub struct UpdatedCosts {
     pub updated_costliest_account_cost: u64,
 }
 
+/// The compute units of the transactions which write lock an account, as
+/// tracked for the block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WritableAccountCost {
+    pub account: Pubkey,
+    /// Compute units of the transactions added to the block
+    pub cost: u64,
+    /// Compute units of the transactions which did not fit as they would have
+    /// exceeded the account limit. A transaction retried in the same block is
+    /// counted each time it does not fit.
+    pub rejected_cost: u64,
+}
+
+impl WritableAccountCost {
+    /// The compute units transactions demanded of the account
+    pub fn demanded_cost(&self) -> u64 {
+        self.cost.saturating_add(self.rejected_cost)
+    }
+}
+
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Debug)]
 pub struct CostTracker {
@@ -64,6 +84,7 @@ pub struct CostTracker {
     block_cost_limit: u64,
     vote_cost_limit: u64,
     cost_by_writable_accounts: HashMap<Pubkey, u64, ahash::RandomState>,
+    rejected_cost_by_writable_accounts: HashMap<Pubkey, u64, ahash::RandomState>,
     block_cost: u64,
     vote_cost: u64,
     transaction_count: Saturating<u64>,
@@ -94,6 +115,7 @@ impl Default for CostTracker {
                 WRITABLE_ACCOUNTS_PER_BLOCK,
                 ahash::RandomState::new(),
             ),
+            rejected_cost_by_writable_accounts: HashMap::default(),
             block_cost: 0,
             vote_cost: 0,
             transaction_count: Saturating(0),
@@ -120,6 +142,7 @@ impl CostTracker {
 
     pub fn reset(&mut self) {
         self.cost_by_writable_accounts.clear();
+        self.rejected_cost_by_writable_accounts.clear();
         self.block_cost = 0;
         self.vote_cost = 0;
         self.transaction_count = Saturating(0);
@@ -164,7 +187,12 @@ impl CostTracker {
         &mut self,
         tx_cost: &TransactionCost<impl TransactionWithMeta>,
     ) -> Result<UpdatedCosts, CostTrackerError> {
-        self.would_fit(tx_cost)?;
+        if let Err(err) = self.would_fit(tx_cost) {
+            if err == CostTrackerError::WouldExceedAccountMaxLimit {
+                self.add_rejected_transaction_cost(tx_cost);
+            }
+            return Err(err);
+        }
         let updated_costliest_account_cost = self.add_transaction_cost(tx_cost);
         Ok(UpdatedCosts {
             updated_block_cost: self.block_cost,
@@ -267,6 +295,47 @@ impl CostTracker {
         );
     }
 
+    /// Get the limit of the compute units of the transactions which write lock
+    /// an account.
+    pub fn get_account_limit(&self) -> u64 {
+        self.account_cost_limit
+    }
+
+    /// The up to `num_accounts` write locked accounts with the most demanded
+    /// compute units, in decreasing order of demand
+    pub fn most_demanded_writable_accounts(&self, num_accounts: usize) -> Vec<WritableAccountCost> {
+        let rejected_only_accounts = self
+            .rejected_cost_by_writable_accounts
+            .keys()
+            .filter(|account| !self.cost_by_writable_accounts.contains_key(*account));
+        let mut accounts: Vec<_> = self
+            .cost_by_writable_accounts
+            .keys()
+            .chain(rejected_only_accounts)
+            .map(|account| WritableAccountCost {
+                account: *account,
+                cost: self
+                    .cost_by_writable_accounts
+                    .get(account)
+                    .copied()
+                    .unwrap_or_default(),
+                rejected_cost: self
+                    .rejected_cost_by_writable_accounts
+                    .get(account)
+                    .copied()
+                    .unwrap_or_default(),
+            })
+            .filter(|account_cost| account_cost.demanded_cost() > 0)
+            .collect();
+        accounts.sort_unstable_by(|a, b| {
+            b.demanded_cost()
+                .cmp(&a.demanded_cost())
+                .then(a.account.cmp(&b.account))
+        });
+        accounts.truncate(num_accounts);
+        accounts
+    }
+
     fn find_costliest_account(&self) -> (Pubkey, u64) {
         self.cost_by_writable_accounts
             .iter()
@@ -322,6 +391,29 @@ impl CostTracker {
         Ok(())
     }
 
+    // Adds the cost of a transaction which does not fit to the write locked
+    // accounts it would have exceeded the limit of
+    fn add_rejected_transaction_cost(
+        &mut self,
+        tx_cost: &TransactionCost<impl TransactionWithMeta>,
+    ) {
+        let cost = tx_cost.sum();
+        for account_key in tx_cost.writable_accounts() {
+            let account_cost = self
+                .cost_by_writable_accounts
+                .get(account_key)
+                .copied()
+                .unwrap_or_default();
+            if account_cost.saturating_add(cost) > self.account_cost_limit {
+                let rejected_cost = self
+                    .rejected_cost_by_writable_accounts
+                    .entry(*account_key)
+                    .or_insert(0);
+                *rejected_cost = rejected_cost.saturating_add(cost);
+            }
+        }
+    }
+
     // Returns the highest account cost for all write-lock accounts `TransactionCost` updated
     fn add_transaction_cost(&mut self, tx_cost: &TransactionCost<impl TransactionWithMeta>) -> u64 {
         self.allocated_accounts_data_size += tx_cost.allocated_accounts_data_size();
@@ -965,4 +1057,41 @@ mod tests {
         assert_eq!(0, cost_tracker.vote_cost);
         assert_eq!(0, cost_tracker.allocated_accounts_data_size.0);
     }
+
+    #[test]
+    fn test_most_demanded_writable_accounts() {
+        let hot_account = Pubkey::new_unique();
+        let cold_account = Pubkey::new_unique();
+        let hot_tx = WritableKeysTransaction(vec![hot_account]);
+        let both_tx = WritableKeysTransaction(vec![hot_account, cold_account]);
+        let hot_tx_cost = simple_transaction_cost(&hot_tx, 5);
+        let both_tx_cost = simple_transaction_cost(&both_tx, 5);
+        let cost = hot_tx_cost.sum();
+        let mut testee = CostTracker::new(cost, cost * 10, cost * 10);
+        assert!(testee.most_demanded_writable_accounts(2).is_empty());
+
+        assert!(testee.try_add(&hot_tx_cost).is_ok());
+        // Would exceed the limit of the hot account only.
+        assert_eq!(
+            testee.try_add(&both_tx_cost).unwrap_err(),
+            CostTrackerError::WouldExceedAccountMaxLimit
+        );
+        assert_eq!(
+            testee.try_add(&hot_tx_cost).unwrap_err(),
+            CostTrackerError::WouldExceedAccountMaxLimit
+        );
+        let both_cost = both_tx_cost.sum();
+        assert_eq!(
+            testee.most_demanded_writable_accounts(2),
+            vec![WritableAccountCost {
+                account: hot_account,
+                cost,
+                rejected_cost: both_cost + cost,
+            }]
+        );
+        assert_eq!(testee.get_account_limit(), cost);
+
+        testee.reset();
+        assert!(testee.most_demanded_writable_accounts(2).is_empty());
+    }
 }
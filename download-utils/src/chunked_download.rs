@@ -0,0 +1,629 @@
+//! Download of a file in chunks with HTTP range requests, which is resumed
+//! from the chunks already downloaded when interrupted, and which fetches the
+//! chunks in parallel from all the RPC peers serving the same file.
+//!
+//! Peers other than the primary one are only used when the file they serve
+//! has the same length and the same fingerprint, the hash of sampled ranges
+//! of the file, as the one served by the primary. The downloaded file has its
+//! length and fingerprint verified against the primary before being moved to
+//! its destination.
+
+use {
+    indicatif::{ProgressBar, ProgressStyle},
+    log::*,
+    reqwest::{
+        blocking::{Client, Response},
+        header::{CONTENT_RANGE, RANGE},
+        StatusCode,
+    },
+    sha2::{Digest, Sha256},
+    solana_file_download::{DownloadProgressCallbackOption, DownloadProgressRecord},
+    std::{
+        collections::{HashSet, VecDeque},
+        fmt,
+        fs::{self, File, OpenOptions},
+        io::{self, Read, Seek, SeekFrom, Write},
+        net::SocketAddr,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            Mutex,
+        },
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+const CHUNK_SIZE: u64 = 32 * 1024 * 1024;
+// Peers downloading at the same time, including the primary one
+const MAX_DOWNLOAD_PEERS: usize = 4;
+// A peer stops being used after failing to download this many chunks.
+const MAX_PEER_FAILURES: usize = 3;
+const NUM_FINGERPRINT_SAMPLES: u64 = 8;
+const FINGERPRINT_SAMPLE_SIZE: u64 = 64 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Error of a chunked download
+#[derive(Debug)]
+pub enum ChunkedDownloadError {
+    /// The file is not served by the primary peer
+    NotFound,
+    /// The primary peer serves the file but not ranges of it
+    RangesNotSupported,
+    Failed(String),
+}
+
+impl fmt::Display for ChunkedDownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "file not found"),
+            Self::RangesNotSupported => write!(f, "ranges not supported"),
+            Self::Failed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Downloads the file `file_name` from `primary_rpc_addr`, and from the
+/// `mirror_rpc_addrs` serving the same file, to `destination_path`.
+///
+/// The chunks downloaded so far are kept in a partial file next to the
+/// destination, with a progress file listing them, so that an interrupted
+/// download continues where it stopped the next time it is attempted.
+pub fn download_file_in_chunks(
+    file_name: &str,
+    primary_rpc_addr: &SocketAddr,
+    mirror_rpc_addrs: &[SocketAddr],
+    destination_path: &Path,
+    use_progress_bar: bool,
+    progress_notify_callback: &mut DownloadProgressCallbackOption<'_>,
+) -> Result<(), ChunkedDownloadError> {
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|err| ChunkedDownloadError::Failed(err.to_string()))?;
+    let primary = Peer::new(primary_rpc_addr, file_name);
+    let total_bytes = match primary.file_length(&client)? {
+        Some(total_bytes) => total_bytes,
+        None => return Err(ChunkedDownloadError::RangesNotSupported),
+    };
+    let fingerprint = primary
+        .fingerprint(&client, total_bytes)
+        .map_err(ChunkedDownloadError::Failed)?;
+
+    let mut peers = vec![primary];
+    for rpc_addr in mirror_rpc_addrs {
+        if peers.len() >= MAX_DOWNLOAD_PEERS {
+            break;
+        }
+        if rpc_addr == primary_rpc_addr {
+            continue;
+        }
+        let peer = Peer::new(rpc_addr, file_name);
+        match peer.verify(&client, total_bytes, &fingerprint) {
+            Ok(()) => peers.push(peer),
+            Err(err) => info!("Not downloading {file_name} from {rpc_addr}: {err}"),
+        }
+    }
+
+    let partial_path = sibling_path(destination_path, "partial");
+    let progress_path = sibling_path(destination_path, "progress");
+    let completed_chunks = load_completed_chunks(&partial_path, &progress_path, total_bytes)
+        .map_err(|err| ChunkedDownloadError::Failed(format!("failed to resume download: {err}")))?;
+    if !completed_chunks.is_empty() {
+        info!(
+            "Resuming download of {file_name} with {} of {} chunks downloaded",
+            completed_chunks.len(),
+            num_chunks(total_bytes),
+        );
+    }
+    info!(
+        "Downloading {file_name} ({total_bytes} bytes) from {} peers: {:?}",
+        peers.len(),
+        peers.iter().map(|peer| peer.rpc_addr).collect::<Vec<_>>(),
+    );
+
+    let download = ChunkedDownload {
+        total_bytes,
+        partial_path: &partial_path,
+        progress_file: Mutex::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&progress_path)
+                .map_err(|err| ChunkedDownloadError::Failed(err.to_string()))?,
+        ),
+        remaining_chunks: Mutex::new(
+            (0..num_chunks(total_bytes))
+                .filter(|chunk| !completed_chunks.contains(chunk))
+                .collect(),
+        ),
+        downloaded_bytes: AtomicU64::new(
+            completed_chunks
+                .iter()
+                .map(|&chunk| chunk_range(chunk, total_bytes).1)
+                .sum(),
+        ),
+        num_active_peers: AtomicUsize::new(peers.len()),
+        abort: AtomicBool::new(false),
+    };
+    download.run(
+        &client,
+        &peers,
+        file_name,
+        use_progress_bar,
+        progress_notify_callback,
+    )?;
+
+    let file_fingerprint = ranges_fingerprint(total_bytes, |start, length| {
+        read_range(&partial_path, start, length)
+    })
+    .map_err(ChunkedDownloadError::Failed)?;
+    if fs::metadata(&partial_path)
+        .map(|metadata| metadata.len())
+        .ok()
+        != Some(total_bytes)
+        || file_fingerprint != fingerprint
+    {
+        // Downloading again from scratch is the only way to recover from a
+        // peer which served a different file.
+        let _ignored = fs::remove_file(&partial_path);
+        let _ignored = fs::remove_file(&progress_path);
+        return Err(ChunkedDownloadError::Failed(format!(
+            "verification of the downloaded {file_name} failed"
+        )));
+    }
+    fs::rename(&partial_path, destination_path).map_err(|err| {
+        ChunkedDownloadError::Failed(format!(
+            "failed to rename {}: {err}",
+            partial_path.display()
+        ))
+    })?;
+    let _ignored = fs::remove_file(&progress_path);
+    Ok(())
+}
+
+struct Peer {
+    rpc_addr: SocketAddr,
+    url: String,
+}
+
+impl Peer {
+    fn new(rpc_addr: &SocketAddr, file_name: &str) -> Self {
+        Self {
+            rpc_addr: *rpc_addr,
+            url: format!("http://{rpc_addr}/{file_name}"),
+        }
+    }
+
+    // Returns the length of the file, or `None` if the peer does not serve
+    // ranges of it.
+    fn file_length(&self, client: &Client) -> Result<Option<u64>, ChunkedDownloadError> {
+        let response = client
+            .get(&self.url)
+            .header(RANGE, "bytes=0-0")
+            .send()
+            .map_err(|err| ChunkedDownloadError::Failed(err.to_string()))?;
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT => parse_content_range_length(&response)
+                .map(Some)
+                .ok_or_else(|| {
+                    ChunkedDownloadError::Failed(format!("invalid response from {}", self.url))
+                }),
+            StatusCode::OK => Ok(None),
+            StatusCode::NOT_FOUND => Err(ChunkedDownloadError::NotFound),
+            status => Err(ChunkedDownloadError::Failed(format!(
+                "unexpected response from {}: {status}",
+                self.url
+            ))),
+        }
+    }
+
+    fn fingerprint(&self, client: &Client, total_bytes: u64) -> Result<Vec<u8>, String> {
+        ranges_fingerprint(total_bytes, |start, length| {
+            let mut bytes = vec![];
+            self.get_range(client, start, length)?
+                .read_to_end(&mut bytes)
+                .map_err(|err| err.to_string())?;
+            Ok(bytes)
+        })
+    }
+
+    // Checks that the peer serves the same file as the primary peer.
+    fn verify(&self, client: &Client, total_bytes: u64, fingerprint: &[u8]) -> Result<(), String> {
+        match self.file_length(client) {
+            Ok(Some(length)) if length == total_bytes => (),
+            Ok(Some(length)) => return Err(format!("unexpected length {length}")),
+            Ok(None) => return Err("ranges not supported".to_string()),
+            Err(err) => return Err(err.to_string()),
+        }
+        if self.fingerprint(client, total_bytes)? != fingerprint {
+            return Err("file differs from the primary peer".to_string());
+        }
+        Ok(())
+    }
+
+    fn get_range(&self, client: &Client, start: u64, length: u64) -> Result<Response, String> {
+        let response = client
+            .get(&self.url)
+            .header(RANGE, format!("bytes={start}-{}", start + length - 1))
+            .send()
+            .map_err(|err| err.to_string())?;
+        if response.status() != StatusCode::PARTIAL_CONTENT
+            || response.content_length() != Some(length)
+        {
+            return Err(format!(
+                "unexpected response from {}: {}",
+                self.url,
+                response.status()
+            ));
+        }
+        Ok(response)
+    }
+}
+
+struct ChunkedDownload<'a> {
+    total_bytes: u64,
+    partial_path: &'a Path,
+    progress_file: Mutex<File>,
+    remaining_chunks: Mutex<VecDeque<u64>>,
+    downloaded_bytes: AtomicU64,
+    num_active_peers: AtomicUsize,
+    abort: AtomicBool,
+}
+
+impl ChunkedDownload<'_> {
+    // Downloads the remaining chunks with a thread per peer, while reporting
+    // the progress of the download.
+    fn run(
+        &self,
+        client: &Client,
+        peers: &[Peer],
+        file_name: &str,
+        use_progress_bar: bool,
+        progress_notify_callback: &mut DownloadProgressCallbackOption<'_>,
+    ) -> Result<(), ChunkedDownloadError> {
+        let progress_bar = use_progress_bar.then(|| {
+            let progress_bar = ProgressBar::new(self.total_bytes);
+            progress_bar.set_style(
+                ProgressStyle::default_bar()
+                    .template(
+                        "{spinner:.green}{msg} [{bar:40.cyan/blue}] {bytes}/{total_bytes} \
+                         ({bytes_per_sec}, {eta})",
+                    )
+                    .expect("ProgressStyle::template direct input to be correct")
+                    .progress_chars("=> "),
+            );
+            progress_bar.set_message(format!(
+                " Downloading {file_name} from {} peers",
+                peers.len()
+            ));
+            progress_bar
+        });
+
+        thread::scope(|scope| {
+            for peer in peers {
+                thread::Builder::new()
+                    .name("solDownloadChnk".to_string())
+                    .spawn_scoped(scope, || self.download_chunks(client, peer))
+                    .unwrap();
+            }
+
+            let start = Instant::now();
+            let start_bytes = self.downloaded_bytes.load(Ordering::Relaxed);
+            let mut last_report = start;
+            let mut last_bytes = start_bytes;
+            let mut notification_count = 0;
+            while self.num_active_peers.load(Ordering::Relaxed) > 0 {
+                thread::sleep(Duration::from_millis(100));
+                let downloaded_bytes = self.downloaded_bytes.load(Ordering::Relaxed);
+                if let Some(progress_bar) = &progress_bar {
+                    progress_bar.set_position(downloaded_bytes);
+                }
+                if last_report.elapsed() < PROGRESS_INTERVAL {
+                    continue;
+                }
+                let elapsed_time = start.elapsed();
+                let last_elapsed_time = last_report.elapsed();
+                let total_throughput =
+                    (downloaded_bytes - start_bytes) as f32 / elapsed_time.as_secs_f32();
+                let progress_record = DownloadProgressRecord {
+                    elapsed_time,
+                    last_elapsed_time,
+                    last_throughput: (downloaded_bytes - last_bytes) as f32
+                        / last_elapsed_time.as_secs_f32(),
+                    total_throughput,
+                    total_bytes: self.total_bytes as usize,
+                    current_bytes: downloaded_bytes as usize,
+                    percentage_done: 100f32 * downloaded_bytes as f32 / self.total_bytes as f32,
+                    estimated_remaining_time: (self.total_bytes - downloaded_bytes) as f32
+                        / total_throughput,
+                    notification_count,
+                };
+                notification_count += 1;
+                last_report = Instant::now();
+                last_bytes = downloaded_bytes;
+                if progress_bar.is_none() {
+                    info!(
+                        "downloaded {downloaded_bytes} bytes {:.1}% {:.1} bytes/s",
+                        progress_record.percentage_done, progress_record.last_throughput,
+                    );
+                }
+                if let Some(callback) = progress_notify_callback {
+                    if !callback(&progress_record) {
+                        self.abort.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+        if let Some(progress_bar) = progress_bar {
+            progress_bar.finish_and_clear();
+        }
+
+        if self.abort.load(Ordering::Relaxed) {
+            return Err(ChunkedDownloadError::Failed(format!(
+                "download of {file_name} aborted"
+            )));
+        }
+        let num_remaining_chunks = self.remaining_chunks.lock().unwrap().len();
+        if num_remaining_chunks > 0 {
+            return Err(ChunkedDownloadError::Failed(format!(
+                "failed to download {num_remaining_chunks} chunks of {file_name}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn download_chunks(&self, client: &Client, peer: &Peer) {
+        let mut num_failures = 0;
+        while num_failures < MAX_PEER_FAILURES && !self.abort.load(Ordering::Relaxed) {
+            let Some(chunk) = self.remaining_chunks.lock().unwrap().pop_front() else {
+                break;
+            };
+            match self.download_chunk(client, peer, chunk) {
+                Ok(()) => num_failures = 0,
+                Err(err) => {
+                    warn!(
+                        "Failed to download chunk {chunk} from {}: {err}",
+                        peer.rpc_addr
+                    );
+                    num_failures += 1;
+                    self.remaining_chunks.lock().unwrap().push_back(chunk);
+                }
+            }
+        }
+        self.num_active_peers.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn download_chunk(&self, client: &Client, peer: &Peer, chunk: u64) -> io::Result<()> {
+        let (start, length) = chunk_range(chunk, self.total_bytes);
+        let mut response = peer
+            .get_range(client, start, length)
+            .map_err(io::Error::other)?;
+        // Chunks are written to disjoint ranges of the file, so each thread
+        // writes through its own handle.
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.partial_path)?;
+        file.seek(SeekFrom::Start(start))?;
+        let mut buffer = vec![0; 1024 * 1024];
+        let mut num_bytes = 0;
+        while num_bytes < length {
+            if self.abort.load(Ordering::Relaxed) {
+                return Err(io::Error::other("download aborted"));
+            }
+            let n = response.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            file.write_all(&buffer[..n])?;
+            num_bytes += n as u64;
+            self.downloaded_bytes.fetch_add(n as u64, Ordering::Relaxed);
+        }
+        if num_bytes != length {
+            self.downloaded_bytes
+                .fetch_sub(num_bytes, Ordering::Relaxed);
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("received {num_bytes} of {length} bytes"),
+            ));
+        }
+        // The chunk is only recorded once its bytes are on disk, so that a
+        // resumed download never skips bytes which have been lost.
+        file.sync_data()?;
+        writeln!(self.progress_file.lock().unwrap(), "{chunk}")
+    }
+}
+
+fn num_chunks(total_bytes: u64) -> u64 {
+    total_bytes.div_ceil(CHUNK_SIZE)
+}
+
+// Returns the offset and the length of the chunk.
+fn chunk_range(chunk: u64, total_bytes: u64) -> (u64, u64) {
+    let start = chunk * CHUNK_SIZE;
+    (start, CHUNK_SIZE.min(total_bytes - start))
+}
+
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    path.with_file_name(file_name)
+}
+
+// Returns the chunks recorded in the progress file of a previous download of
+// a file of the same length. The first line of the progress file holds the
+// length of the file, and every other line a downloaded chunk.
+fn load_completed_chunks(
+    partial_path: &Path,
+    progress_path: &Path,
+    total_bytes: u64,
+) -> io::Result<HashSet<u64>> {
+    let header = format!("{total_bytes} {CHUNK_SIZE}");
+    let completed_chunks = match File::open(progress_path) {
+        Ok(mut file) if partial_path.is_file() => {
+            let mut progress = String::new();
+            file.read_to_string(&mut progress)?;
+            let mut lines = progress.split_inclusive('\n');
+            match lines.next() {
+                Some(line) if line.trim_end() == header => lines
+                    // A line partially written when the download got
+                    // interrupted is ignored.
+                    .filter_map(|line| line.strip_suffix('\n')?.parse().ok())
+                    .filter(|&chunk| chunk < num_chunks(total_bytes))
+                    .collect(),
+                _ => HashSet::new(),
+            }
+        }
+        Ok(_) => HashSet::new(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => HashSet::new(),
+        Err(err) => return Err(err),
+    };
+    if completed_chunks.is_empty() {
+        let _ignored = fs::remove_file(partial_path);
+        let mut progress_file = File::create(progress_path)?;
+        writeln!(progress_file, "{header}")?;
+    }
+    Ok(completed_chunks)
+}
+
+// Returns the hash of the ranges of the file sampled evenly from its start to
+// its end, reading the ranges with `read_range`.
+fn ranges_fingerprint(
+    total_bytes: u64,
+    mut read_range: impl FnMut(u64, u64) -> Result<Vec<u8>, String>,
+) -> Result<Vec<u8>, String> {
+    let mut hasher = Sha256::new();
+    hasher.update(total_bytes.to_le_bytes());
+    let sample_size = FINGERPRINT_SAMPLE_SIZE.min(total_bytes);
+    let mut last_start = None;
+    for sample in 0..NUM_FINGERPRINT_SAMPLES {
+        let start = (total_bytes - sample_size) * sample / (NUM_FINGERPRINT_SAMPLES - 1);
+        if sample_size == 0 || last_start == Some(start) {
+            continue;
+        }
+        last_start = Some(start);
+        hasher.update(read_range(start, sample_size)?);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+fn read_range(path: &Path, start: u64, length: u64) -> Result<Vec<u8>, String> {
+    let mut file = File::open(path).map_err(|err| err.to_string())?;
+    file.seek(SeekFrom::Start(start))
+        .map_err(|err| err.to_string())?;
+    let mut bytes = vec![];
+    file.take(length)
+        .read_to_end(&mut bytes)
+        .map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
+
+// Returns the length of the file from a `Content-Range: bytes 0-0/<length>`
+// header.
+fn parse_content_range_length(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    #[test]
+    fn test_chunk_range() {
+        let total_bytes = 2 * CHUNK_SIZE + 10;
+        assert_eq!(num_chunks(total_bytes), 3);
+        assert_eq!(num_chunks(2 * CHUNK_SIZE), 2);
+        assert_eq!(chunk_range(0, total_bytes), (0, CHUNK_SIZE));
+        assert_eq!(chunk_range(2, total_bytes), (2 * CHUNK_SIZE, 10));
+    }
+
+    #[test]
+    fn test_ranges_fingerprint() {
+        let bytes: Vec<u8> = (0..1_000_000u32).map(|i| i as u8).collect();
+        let read = |bytes: &[u8], start: u64, length: u64| -> Result<Vec<u8>, String> {
+            Ok(bytes[start as usize..(start + length) as usize].to_vec())
+        };
+        let fingerprint = ranges_fingerprint(bytes.len() as u64, |start, length| {
+            read(&bytes, start, length)
+        })
+        .unwrap();
+
+        // The last byte is always sampled.
+        let mut other_bytes = bytes.clone();
+        *other_bytes.last_mut().unwrap() += 1;
+        assert_ne!(
+            ranges_fingerprint(other_bytes.len() as u64, |start, length| read(
+                &other_bytes,
+                start,
+                length
+            ))
+            .unwrap(),
+            fingerprint
+        );
+
+        // Files smaller than a sample are hashed whole.
+        let small_bytes = &bytes[..10];
+        let mut num_reads = 0;
+        ranges_fingerprint(small_bytes.len() as u64, |start, length| {
+            num_reads += 1;
+            read(small_bytes, start, length)
+        })
+        .unwrap();
+        assert_eq!(num_reads, 1);
+    }
+
+    #[test]
+    fn test_load_completed_chunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let destination_path = temp_dir.path().join("snapshot.tar.zst");
+        let partial_path = sibling_path(&destination_path, "partial");
+        let progress_path = sibling_path(&destination_path, "progress");
+        assert_eq!(
+            partial_path,
+            temp_dir.path().join("snapshot.tar.zst.partial")
+        );
+        let total_bytes = 3 * CHUNK_SIZE;
+
+        // A new download starts from scratch.
+        assert!(
+            load_completed_chunks(&partial_path, &progress_path, total_bytes)
+                .unwrap()
+                .is_empty()
+        );
+        File::create(&partial_path).unwrap();
+        let mut progress_file = OpenOptions::new()
+            .append(true)
+            .open(&progress_path)
+            .unwrap();
+        // The last chunk was being recorded when the download got interrupted.
+        write!(progress_file, "2\n0\n1").unwrap();
+        assert_eq!(
+            load_completed_chunks(&partial_path, &progress_path, total_bytes).unwrap(),
+            HashSet::from([0, 2])
+        );
+
+        // The download of a file of another length starts from scratch.
+        assert!(
+            load_completed_chunks(&partial_path, &progress_path, total_bytes + 1)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(!partial_path.exists());
+        assert_eq!(
+            fs::read_to_string(&progress_path).unwrap(),
+            format!("{} {CHUNK_SIZE}\n", total_bytes + 1)
+        );
+    }
+}
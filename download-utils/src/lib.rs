@@ -1,5 +1,8 @@
+mod chunked_download;
+
 pub use solana_file_download::DownloadProgressRecord;
 use {
+    crate::chunked_download::{download_file_in_chunks, ChunkedDownloadError},
     log::*,
     solana_clock::Slot,
     solana_file_download::{download_file, DownloadProgressCallbackOption},
@@ -52,6 +55,37 @@ pub fn download_snapshot_archive(
     maximum_incremental_snapshot_archives_to_retain: NonZeroUsize,
     use_progress_bar: bool,
     progress_notify_callback: &mut DownloadProgressCallbackOption<'_>,
+) -> Result<(), String> {
+    download_snapshot_archive_from_peers(
+        rpc_addr,
+        &[],
+        full_snapshot_archives_dir,
+        incremental_snapshot_archives_dir,
+        desired_snapshot_hash,
+        snapshot_kind,
+        maximum_full_snapshot_archives_to_retain,
+        maximum_incremental_snapshot_archives_to_retain,
+        use_progress_bar,
+        progress_notify_callback,
+    )
+}
+
+/// Download a snapshot archive from `rpc_addr`, fetching chunks of it in parallel from the
+/// `mirror_rpc_addrs` serving the same archive.  A download interrupted earlier is resumed from
+/// the chunks already downloaded.  Falls back to downloading the whole archive from `rpc_addr`
+/// when it does not serve ranges of the archive.
+#[allow(clippy::too_many_arguments)]
+pub fn download_snapshot_archive_from_peers(
+    rpc_addr: &SocketAddr,
+    mirror_rpc_addrs: &[SocketAddr],
+    full_snapshot_archives_dir: &Path,
+    incremental_snapshot_archives_dir: &Path,
+    desired_snapshot_hash: (Slot, SnapshotHash),
+    snapshot_kind: SnapshotKind,
+    maximum_full_snapshot_archives_to_retain: NonZeroUsize,
+    maximum_incremental_snapshot_archives_to_retain: NonZeroUsize,
+    use_progress_bar: bool,
+    progress_notify_callback: &mut DownloadProgressCallbackOption<'_>,
 ) -> Result<(), String> {
     snapshot_utils::purge_old_snapshot_archives(
         full_snapshot_archives_dir,
@@ -98,12 +132,24 @@ pub fn download_snapshot_archive(
             return Ok(());
         }
 
+        let file_name = destination_path.file_name().unwrap().to_str().unwrap();
+        match download_file_in_chunks(
+            file_name,
+            rpc_addr,
+            mirror_rpc_addrs,
+            &destination_path,
+            use_progress_bar,
+            progress_notify_callback,
+        ) {
+            Ok(()) => return Ok(()),
+            Err(ChunkedDownloadError::RangesNotSupported) => (),
+            Err(err) => {
+                info!("Failed to download {file_name} from {rpc_addr}: {err}");
+                continue;
+            }
+        }
         match download_file(
-            &format!(
-                "http://{}/{}",
-                rpc_addr,
-                destination_path.file_name().unwrap().to_str().unwrap()
-            ),
+            &format!("http://{rpc_addr}/{file_name}"),
             &destination_path,
             use_progress_bar,
             progress_notify_callback,
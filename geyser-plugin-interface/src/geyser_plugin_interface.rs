@@ -1,16 +1,75 @@
 /// The interface for Geyser plugins. A plugin must implement
 /// the GeyserPlugin trait to work with the runtime.
 /// In addition, the dynamic library must export a "C" function _create_plugin which
-/// creates the implementation of the plugin.
+/// creates the implementation of the plugin, and may export a handshake with
+/// `declare_geyser_plugin_handshake!`.
 use {
     solana_clock::{Slot, UnixTimestamp},
     solana_signature::Signature,
     solana_transaction::sanitized::SanitizedTransaction,
     solana_transaction_status::{Reward, RewardsAndNumPartitions, TransactionStatusMeta},
-    std::{any::Any, error, io},
+    std::{any::Any, error, io, net::SocketAddr},
     thiserror::Error,
 };
 
+/// The version of the plugin interface. It is bumped whenever the interface changes in a way
+/// which breaks plugins built against the previous version, such as a new method of the
+/// `GeyserPlugin` trait.
+pub const GEYSER_PLUGIN_INTERFACE_VERSION: u32 = 2;
+
+/// The name of the "C" function the dynamic library of a plugin may export, which returns its
+/// `GeyserPluginHandshake`. See `declare_geyser_plugin_handshake!`.
+pub const GEYSER_PLUGIN_HANDSHAKE_SYMBOL: &[u8] = b"_geyser_plugin_handshake";
+
+/// What a plugin declares to the validator before it is created: the version of the interface
+/// it is built against and its capabilities. The validator refuses to load plugins built
+/// against an interface version it does not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct GeyserPluginHandshake {
+    /// The `GEYSER_PLUGIN_INTERFACE_VERSION` the plugin is built against
+    pub interface_version: u32,
+    /// The `capabilities` of the plugin, or'ed together
+    pub capabilities: u64,
+}
+
+impl GeyserPluginHandshake {
+    pub fn has_capability(&self, capability: u64) -> bool {
+        self.capabilities & capability == capability
+    }
+}
+
+/// The capabilities a plugin can declare in its handshake
+pub mod capabilities {
+    /// The plugin can be unloaded, reloaded or replaced while the validator runs. A plugin with
+    /// a handshake which does not declare it stays loaded until the validator exits.
+    pub const HOT_RELOAD: u64 = 1 << 0;
+    /// The plugin may return `GeyserPluginError::Backpressure`.
+    pub const BACKPRESSURE: u64 = 1 << 1;
+    /// The plugin implements `notify_accounts_batch()` and `notify_transactions_batch()`.
+    pub const BATCHED_NOTIFICATIONS: u64 = 1 << 2;
+}
+
+/// Exports the handshake of a plugin from its dynamic library, with the interface version it is
+/// built against and the given capabilities.
+///
+/// ```ignore
+/// declare_geyser_plugin_handshake!(capabilities::HOT_RELOAD | capabilities::BACKPRESSURE);
+/// ```
+#[macro_export]
+macro_rules! declare_geyser_plugin_handshake {
+    ($capabilities:expr) => {
+        #[no_mangle]
+        pub extern "C" fn _geyser_plugin_handshake(
+        ) -> $crate::geyser_plugin_interface::GeyserPluginHandshake {
+            $crate::geyser_plugin_interface::GeyserPluginHandshake {
+                interface_version: $crate::geyser_plugin_interface::GEYSER_PLUGIN_INTERFACE_VERSION,
+                capabilities: $capabilities,
+            }
+        }
+    };
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[repr(C)]
 /// Information about an account being updated
@@ -113,6 +172,7 @@ pub struct ReplicaAccountInfoV3<'a> {
 /// If there were a change to the structure of ReplicaAccountInfo,
 /// there would be new enum entry for the newer version, forcing
 /// plugin implementations to handle the change.
+#[derive(Clone, Copy)]
 #[repr(u32)]
 pub enum ReplicaAccountInfoVersions<'a> {
     V0_0_1(&'a ReplicaAccountInfo<'a>),
@@ -161,6 +221,7 @@ pub struct ReplicaTransactionInfoV2<'a> {
 /// If there were a change to the structure of ReplicaTransactionInfo,
 /// there would be new enum entry for the newer version, forcing
 /// plugin implementations to handle the change.
+#[derive(Clone, Copy)]
 #[repr(u32)]
 pub enum ReplicaTransactionInfoVersions<'a> {
     V0_0_1(&'a ReplicaTransactionInfo<'a>),
@@ -264,12 +325,166 @@ pub struct ReplicaBlockInfoV4<'a> {
     pub entry_count: u64,
 }
 
+/// The summary of an entry of a block, locating it in the block without its transactions
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaEntrySummary<'a> {
+    /// The Entry's index in the block
+    pub index: usize,
+    /// The Entry's SHA-256 hash
+    pub hash: &'a [u8],
+    /// Whether the Entry is a tick, i.e. has no transactions
+    pub is_tick: bool,
+    /// The number of executed transactions in the Entry
+    pub executed_transaction_count: u64,
+    /// The index-in-block of the first executed transaction in this Entry
+    pub starting_transaction_index: usize,
+}
+
+/// Extending ReplicaBlockInfo by sending the summaries of the entries.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaBlockInfoV5<'a> {
+    pub parent_slot: Slot,
+    pub parent_blockhash: &'a str,
+    pub slot: Slot,
+    pub blockhash: &'a str,
+    pub rewards: &'a RewardsAndNumPartitions,
+    pub block_time: Option<UnixTimestamp>,
+    pub block_height: Option<u64>,
+    pub executed_transaction_count: u64,
+    pub entry_count: u64,
+    /// The summaries of the entries of the block, in order
+    pub entries: &'a [ReplicaEntrySummary<'a>],
+}
+
 #[repr(u32)]
 pub enum ReplicaBlockInfoVersions<'a> {
     V0_0_1(&'a ReplicaBlockInfo<'a>),
     V0_0_2(&'a ReplicaBlockInfoV2<'a>),
     V0_0_3(&'a ReplicaBlockInfoV3<'a>),
     V0_0_4(&'a ReplicaBlockInfoV4<'a>),
+    V0_0_5(&'a ReplicaBlockInfoV5<'a>),
+}
+
+/// A bloom filter of the accounts written in a frozen slot. An account may
+/// have been written in the slot only if, for every `k` in `0..num_hashes`,
+/// bit `fnv1a_64(pubkey, k) % num_bits` is set, where `fnv1a_64(pubkey, k)` is
+/// the 64-bit FNV-1a hash of the account's address using `k` as the offset
+/// basis.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaSlotAccountFilterInfo<'a> {
+    pub slot: Slot,
+    /// The number of distinct accounts written in the slot
+    pub num_accounts: u64,
+    pub num_bits: u64,
+    pub num_hashes: u64,
+    /// The bits of the filter; bit `i` is bit `i % 64` of word `i / 64`
+    pub bits: &'a [u64],
+}
+
+#[repr(u32)]
+pub enum ReplicaSlotAccountFilterVersions<'a> {
+    V0_0_1(&'a ReplicaSlotAccountFilterInfo<'a>),
+}
+
+/// The progress of the replay of the accounts restored from a snapshot, in pubkey order
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaSnapshotReplayProgress<'a> {
+    /// The slot of the snapshot
+    pub slot: Slot,
+    /// The # of accounts notified so far
+    pub num_accounts_notified: u64,
+    /// The # of accounts to notify, which is an estimate until the replay is done
+    pub num_accounts: u64,
+    /// The last account notified, or None if none is yet
+    pub last_pubkey: Option<&'a [u8]>,
+    /// Whether all the accounts are notified
+    pub is_done: bool,
+}
+
+#[repr(u32)]
+pub enum ReplicaSnapshotReplayProgressVersions<'a> {
+    V0_0_1(&'a ReplicaSnapshotReplayProgress<'a>),
+}
+
+/// The status of a cluster node as observed in gossip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NodeStatus {
+    /// The node refreshes its contact info in gossip.
+    Active,
+
+    /// The node has not refreshed its contact info for a while.
+    Delinquent,
+
+    /// The contact info of the node was purged from gossip.
+    Removed,
+}
+
+/// The contact info of a cluster node, as in the `getClusterNodes` RPC
+/// method. The addresses use UDP, except for those suffixed with `_quic`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaNodeInfo<'a> {
+    pub pubkey: &'a [u8],
+    pub status: NodeStatus,
+    /// The wallclock of the node when it signed its contact info
+    pub wallclock: u64,
+    pub shred_version: u16,
+    pub version: &'a str,
+    pub feature_set: u32,
+    pub gossip: Option<SocketAddr>,
+    pub tvu: Option<SocketAddr>,
+    pub tpu: Option<SocketAddr>,
+    pub tpu_quic: Option<SocketAddr>,
+    pub tpu_forwards: Option<SocketAddr>,
+    pub tpu_forwards_quic: Option<SocketAddr>,
+    pub tpu_vote: Option<SocketAddr>,
+    pub serve_repair: Option<SocketAddr>,
+    pub rpc: Option<SocketAddr>,
+    pub pubsub: Option<SocketAddr>,
+}
+
+#[repr(u32)]
+pub enum ReplicaNodeInfoVersions<'a> {
+    V0_0_1(&'a ReplicaNodeInfo<'a>),
+}
+
+/// Where a vote transaction was observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VoteSource {
+    /// The vote was sent to the TPU vote port of this node.
+    Tpu,
+
+    /// The vote was gossiped by the cluster.
+    Gossip,
+}
+
+/// A vote transaction observed before it lands in a block. The same vote may
+/// be observed more than once, from both sources.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaVoteInfo<'a> {
+    /// The signature of the vote transaction
+    pub signature: &'a Signature,
+    pub vote_pubkey: &'a [u8],
+    /// The last slot voted on
+    pub slot: Slot,
+    /// The bank hash of the last slot voted on
+    pub hash: &'a [u8],
+    pub timestamp: Option<UnixTimestamp>,
+    pub source: VoteSource,
+    /// When the vote was received, in microseconds since the unix epoch
+    pub received_at_us: u64,
+}
+
+#[repr(u32)]
+pub enum ReplicaVoteInfoVersions<'a> {
+    V0_0_1(&'a ReplicaVoteInfo<'a>),
 }
 
 /// Errors returned by plugin calls
@@ -301,6 +516,14 @@ pub enum GeyserPluginError {
     /// Error when updating the transaction.
     #[error("Error updating transaction. Error message: ({msg})")]
     TransactionUpdateError { msg: String },
+
+    /// The plugin can't keep up, and only took the first `accepted` notifications of the
+    /// batch, or none of the single notification. The validator buffers the account
+    /// notifications which were not taken in a bounded spill file, and notifies the plugin of
+    /// them again, in order and as `ReplicaAccountInfoVersions::V0_0_2`, before the next ones.
+    /// Other notifications are not buffered, and are dropped.
+    #[error("Backpressure: only {accepted} notifications were accepted")]
+    Backpressure { accepted: usize },
 }
 
 /// The current status of a slot
@@ -410,6 +633,30 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Called with a batch of accounts updated at a slot, like `update_account()` for each
+    /// account of the batch, in order.
+    ///
+    /// The default implementation calls `update_account()` for each account, and stops at the
+    /// first error. If that error is `GeyserPluginError::Backpressure`, it is returned with the
+    /// # of accounts which were accepted before.
+    fn notify_accounts_batch(
+        &self,
+        accounts: &[ReplicaAccountInfoVersions],
+        slot: Slot,
+        is_startup: bool,
+    ) -> Result<()> {
+        for (accepted, account) in accounts.iter().enumerate() {
+            match self.update_account(*account, slot, is_startup) {
+                Ok(()) => {}
+                Err(GeyserPluginError::Backpressure { .. }) => {
+                    return Err(GeyserPluginError::Backpressure { accepted });
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
     /// Called when all accounts are notified of during startup.
     fn notify_end_of_startup(&self) -> Result<()> {
         Ok(())
@@ -436,18 +683,76 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Called with a batch of transactions processed in a slot, like `notify_transaction()` for
+    /// each transaction of the batch, in order.
+    ///
+    /// The default implementation calls `notify_transaction()` for each transaction, and stops
+    /// at the first error. If that error is `GeyserPluginError::Backpressure`, it is returned
+    /// with the # of transactions which were accepted before.
+    fn notify_transactions_batch(
+        &self,
+        transactions: &[ReplicaTransactionInfoVersions],
+        slot: Slot,
+    ) -> Result<()> {
+        for (accepted, transaction) in transactions.iter().enumerate() {
+            match self.notify_transaction(*transaction, slot) {
+                Ok(()) => {}
+                Err(GeyserPluginError::Backpressure { .. }) => {
+                    return Err(GeyserPluginError::Backpressure { accepted });
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
     /// Called when an entry is executed.
     #[allow(unused_variables)]
     fn notify_entry(&self, entry: ReplicaEntryInfoVersions) -> Result<()> {
         Ok(())
     }
 
-    /// Called when block's metadata is updated.
+    /// Called when block's metadata is updated. Plugins which enable
+    /// `block_metadata_entry_summaries_enabled()` are sent `V0_0_5`, with the
+    /// summaries of the entries of the block, if the block is replayed from
+    /// the blockstore. Otherwise, plugins are sent `V0_0_4`.
     #[allow(unused_variables)]
     fn notify_block_metadata(&self, blockinfo: ReplicaBlockInfoVersions) -> Result<()> {
         Ok(())
     }
 
+    /// Called when the filter of the accounts written in a frozen slot is
+    /// built. Filters are only built if the validator enables them.
+    #[allow(unused_variables)]
+    fn notify_slot_account_filter(&self, filter: ReplicaSlotAccountFilterVersions) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called periodically while the accounts restored from a snapshot are replayed in pubkey
+    /// order, and once they all are, before `notify_end_of_startup()`.
+    #[allow(unused_variables)]
+    fn notify_snapshot_replay_progress(
+        &self,
+        progress: ReplicaSnapshotReplayProgressVersions,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a cluster node is first observed in gossip, when its
+    /// addresses, version or shred version change, and when its status
+    /// changes.
+    #[allow(unused_variables)]
+    fn notify_node_update(&self, node: ReplicaNodeInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a vote transaction is received from the TPU or from
+    /// gossip, before it lands in a block.
+    #[allow(unused_variables)]
+    fn notify_vote(&self, vote: ReplicaVoteInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
     /// Check if the plugin is interested in account data
     /// Default is true -- if the plugin is not interested in
     /// account data, please return false.
@@ -463,6 +768,16 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         true
     }
 
+    /// Check if the plugin wants the accounts restored from a snapshot replayed in pubkey
+    /// order. Instead of every version of the accounts in the snapshot, plugins are then
+    /// notified at startup of the latest version of each account, once, in increasing pubkey
+    /// order, so that they can bootstrap their state from it.
+    /// Default is false -- if the plugin wants the replay, return true. If any plugin does,
+    /// all the plugins get the replay.
+    fn account_data_snapshot_replay_enabled(&self) -> bool {
+        false
+    }
+
     /// Check if the plugin is interested in transaction data
     /// Default is false -- if the plugin is interested in
     /// transaction data, please return true.
@@ -476,4 +791,34 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
     fn entry_notifications_enabled(&self) -> bool {
         false
     }
+
+    /// Check if the plugin wants the summaries of the entries of blocks in
+    /// their metadata, to locate entry boundaries without the entry
+    /// notifications
+    /// Default is false -- if the plugin wants entry summaries, return true.
+    fn block_metadata_entry_summaries_enabled(&self) -> bool {
+        false
+    }
+
+    /// Check if the plugin is interested in slot account filters
+    /// Default is false -- if the plugin is interested in
+    /// slot account filters, return true.
+    fn slot_account_filter_notifications_enabled(&self) -> bool {
+        false
+    }
+
+    /// Check if the plugin is interested in cluster node updates
+    /// Default is false -- if the plugin is interested in
+    /// cluster node updates, return true.
+    fn node_update_notifications_enabled(&self) -> bool {
+        false
+    }
+
+    /// Check if the plugin is interested in the votes observed before they
+    /// land in blocks
+    /// Default is false -- if the plugin is interested in
+    /// observed votes, return true.
+    fn vote_notifications_enabled(&self) -> bool {
+        false
+    }
 }
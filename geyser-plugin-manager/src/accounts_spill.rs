@@ -0,0 +1,455 @@
+//! Spill file of the account notifications a plugin pushed back on.
+//!
+//! When a plugin returns `GeyserPluginError::Backpressure`, the account notifications it did not
+//! take are appended to its spill file, and the next notifications are appended behind them, so
+//! that the plugin is notified of all of them in order.  The spilled batches are redelivered, a
+//! few at a time, ahead of each new notification, until the spill file is drained.
+//!
+//! The spill file is bounded: once it is full, the notifications which do not fit are dropped.
+
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        GeyserPlugin, GeyserPluginError, ReplicaAccountInfoV2, ReplicaAccountInfoV3,
+        ReplicaAccountInfoVersions,
+    },
+    log::*,
+    solana_clock::Slot,
+    solana_signature::Signature,
+    std::{
+        fs::{self, File},
+        io::{self, Read, Seek, SeekFrom, Write},
+        path::PathBuf,
+    },
+};
+
+/// Default size of a spill file at most
+pub const DEFAULT_ACCOUNTS_SPILL_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// # of spilled batches redelivered at most ahead of a new notification
+pub(crate) const MAX_BATCHES_REDELIVERED_PER_NOTIFICATION: usize = 16;
+
+/// Result of redelivering spilled batches to a plugin
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Redelivery {
+    /// The spill file is drained
+    Drained,
+    /// The plugin pushed back, or the max # of batches was redelivered
+    Pending,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SpilledAccount {
+    pubkey: [u8; 32],
+    owner: [u8; 32],
+    lamports: u64,
+    executable: bool,
+    rent_epoch: u64,
+    write_version: u64,
+    txn_signature: Option<Signature>,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SpilledBatch {
+    slot: Slot,
+    is_startup: bool,
+    accounts: Vec<SpilledAccount>,
+}
+
+/// The spill file of a plugin
+#[derive(Debug)]
+pub struct AccountsSpill {
+    path: PathBuf,
+    max_bytes: u64,
+    /// Created on the first spill
+    file: Option<File>,
+    /// Offset of the first batch which is not redelivered yet
+    read_offset: u64,
+    /// Offset past the last batch
+    write_offset: u64,
+    /// # of accounts of the first batch which were already redelivered
+    head_accepted: usize,
+}
+
+impl AccountsSpill {
+    /// Creates the spill of the plugin `name`, in `dir`, of up to `max_bytes`
+    pub fn new(dir: impl Into<PathBuf>, name: &str, max_bytes: u64) -> Self {
+        let file_name = format!("{name}-{}.accounts-spill", std::process::id());
+        Self {
+            path: dir.into().join(file_name),
+            max_bytes,
+            file: None,
+            read_offset: 0,
+            write_offset: 0,
+            head_accepted: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read_offset == self.write_offset
+    }
+
+    /// Returns the # of bytes spilled which are not redelivered yet
+    pub fn len(&self) -> u64 {
+        self.write_offset - self.read_offset
+    }
+
+    /// Appends the batch of `accounts` to the spill file.
+    ///
+    /// Returns false if the batch does not fit, in which case it is dropped.
+    pub(crate) fn append(
+        &mut self,
+        accounts: &[ReplicaAccountInfoV3],
+        slot: Slot,
+        is_startup: bool,
+    ) -> io::Result<bool> {
+        let record = encode_batch(accounts, slot, is_startup);
+        if self.write_offset + record.len() as u64 > self.max_bytes {
+            return Ok(false);
+        }
+        if self.file.is_none() {
+            let file = File::options()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&self.path)?;
+            self.file = Some(file);
+        }
+        let file = self.file.as_mut().unwrap();
+        file.seek(SeekFrom::Start(self.write_offset))?;
+        file.write_all(&record)?;
+        self.write_offset += record.len() as u64;
+        Ok(true)
+    }
+
+    /// Drops all the spilled batches
+    pub(crate) fn clear(&mut self) {
+        self.read_offset = 0;
+        self.write_offset = 0;
+        self.head_accepted = 0;
+        if let Some(file) = &self.file {
+            if let Err(err) = file.set_len(0) {
+                warn!(
+                    "Unable to truncate accounts spill file {}: {err}",
+                    self.path.display()
+                );
+            }
+        }
+    }
+
+    /// Notifies `plugin` of up to `max_batches` spilled batches, in order.
+    ///
+    /// Batches which fail with errors other than backpressure are dropped, like notifications
+    /// which are not spilled.
+    pub(crate) fn redeliver(
+        &mut self,
+        plugin: &dyn GeyserPlugin,
+        max_batches: usize,
+    ) -> io::Result<Redelivery> {
+        for _ in 0..max_batches {
+            if self.is_empty() {
+                break;
+            }
+            let (batch, record_len) = self.read_head()?;
+            let accounts: Vec<_> = batch.accounts[self.head_accepted..]
+                .iter()
+                .map(SpilledAccount::to_replica_account_info)
+                .collect();
+            let accounts: Vec<_> = accounts
+                .iter()
+                .map(ReplicaAccountInfoVersions::V0_0_2)
+                .collect();
+            match plugin.notify_accounts_batch(&accounts, batch.slot, batch.is_startup) {
+                Err(GeyserPluginError::Backpressure { accepted }) => {
+                    self.head_accepted += accepted.min(accounts.len());
+                    return Ok(Redelivery::Pending);
+                }
+                Err(err) => {
+                    error!(
+                        "Failed to redeliver {} spilled accounts at slot {} to plugin {}, error: \
+                         {err}",
+                        accounts.len(),
+                        batch.slot,
+                        plugin.name(),
+                    );
+                }
+                Ok(()) => {}
+            }
+            self.read_offset += record_len;
+            self.head_accepted = 0;
+        }
+
+        if !self.is_empty() {
+            return Ok(Redelivery::Pending);
+        }
+        // Once drained, the spill file is reused from its start.
+        self.read_offset = 0;
+        self.write_offset = 0;
+        if let Some(file) = &self.file {
+            file.set_len(0)?;
+        }
+        Ok(Redelivery::Drained)
+    }
+
+    /// Returns the first batch which is not redelivered yet, and the length of its record
+    fn read_head(&mut self) -> io::Result<(SpilledBatch, u64)> {
+        let file = self
+            .file
+            .as_mut()
+            .expect("the spill file exists if it is not empty");
+        file.seek(SeekFrom::Start(self.read_offset))?;
+        let mut len = [0; 8];
+        file.read_exact(&mut len)?;
+        let len = u64::from_le_bytes(len);
+        let mut record = vec![0; len as usize];
+        file.read_exact(&mut record)?;
+        let batch = decode_batch(&record).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid spilled batch at offset {}", self.read_offset),
+            )
+        })?;
+        Ok((batch, len + 8))
+    }
+}
+
+impl Drop for AccountsSpill {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            if let Err(err) = fs::remove_file(&self.path) {
+                warn!(
+                    "Unable to remove accounts spill file {}: {err}",
+                    self.path.display()
+                );
+            }
+        }
+    }
+}
+
+impl SpilledAccount {
+    fn to_replica_account_info(&self) -> ReplicaAccountInfoV2<'_> {
+        ReplicaAccountInfoV2 {
+            pubkey: &self.pubkey,
+            lamports: self.lamports,
+            owner: &self.owner,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+            data: &self.data,
+            write_version: self.write_version,
+            txn_signature: self.txn_signature.as_ref(),
+        }
+    }
+}
+
+/// Encodes the batch into a record: its length, followed by the slot, whether it is at startup,
+/// the # of accounts and the accounts, all little endian.
+fn encode_batch(accounts: &[ReplicaAccountInfoV3], slot: Slot, is_startup: bool) -> Vec<u8> {
+    let mut record = vec![0; 8];
+    record.extend_from_slice(&slot.to_le_bytes());
+    record.push(is_startup as u8);
+    record.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for account in accounts {
+        record.extend_from_slice(account.pubkey);
+        record.extend_from_slice(account.owner);
+        record.extend_from_slice(&account.lamports.to_le_bytes());
+        record.push(account.executable as u8);
+        record.extend_from_slice(&account.rent_epoch.to_le_bytes());
+        record.extend_from_slice(&account.write_version.to_le_bytes());
+        match account.txn {
+            Some(txn) => {
+                record.push(1);
+                record.extend_from_slice(txn.signature().as_ref());
+            }
+            None => record.push(0),
+        }
+        record.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        record.extend_from_slice(account.data);
+    }
+    let len = record.len() as u64 - 8;
+    record[..8].copy_from_slice(&len.to_le_bytes());
+    record
+}
+
+/// Decodes the batch from a record, without its length
+fn decode_batch(mut record: &[u8]) -> Option<SpilledBatch> {
+    fn take<'a>(record: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if record.len() < len {
+            return None;
+        }
+        let (bytes, rest) = record.split_at(len);
+        *record = rest;
+        Some(bytes)
+    }
+    fn take_u64(record: &mut &[u8]) -> Option<u64> {
+        Some(u64::from_le_bytes(take(record, 8)?.try_into().unwrap()))
+    }
+    fn take_bool(record: &mut &[u8]) -> Option<bool> {
+        Some(take(record, 1)?[0] != 0)
+    }
+
+    let slot = take_u64(&mut record)?;
+    let is_startup = take_bool(&mut record)?;
+    let num_accounts = take_u64(&mut record)?;
+    let mut accounts = Vec::new();
+    for _ in 0..num_accounts {
+        let pubkey = take(&mut record, 32)?.try_into().unwrap();
+        let owner = take(&mut record, 32)?.try_into().unwrap();
+        let lamports = take_u64(&mut record)?;
+        let executable = take_bool(&mut record)?;
+        let rent_epoch = take_u64(&mut record)?;
+        let write_version = take_u64(&mut record)?;
+        let txn_signature = if take_bool(&mut record)? {
+            let signature: [u8; 64] = take(&mut record, 64)?.try_into().unwrap();
+            Some(Signature::from(signature))
+        } else {
+            None
+        };
+        let data_len = take_u64(&mut record)?;
+        let data = take(&mut record, usize::try_from(data_len).ok()?)?.to_vec();
+        accounts.push(SpilledAccount {
+            pubkey,
+            owner,
+            lamports,
+            executable,
+            rent_epoch,
+            write_version,
+            txn_signature,
+            data,
+        });
+    }
+    record.is_empty().then_some(SpilledBatch {
+        slot,
+        is_startup,
+        accounts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        agave_geyser_plugin_interface::geyser_plugin_interface::Result,
+        solana_pubkey::Pubkey,
+        std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+        tempfile::TempDir,
+    };
+
+    /// Accepts `capacity` accounts, and pushes back on the rest
+    #[derive(Debug, Default)]
+    struct SlowPlugin {
+        capacity: AtomicUsize,
+        notified: Mutex<Vec<(Slot, u64)>>,
+    }
+
+    impl GeyserPlugin for SlowPlugin {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        fn update_account(
+            &self,
+            account: ReplicaAccountInfoVersions,
+            slot: Slot,
+            _is_startup: bool,
+        ) -> Result<()> {
+            let ReplicaAccountInfoVersions::V0_0_2(account) = account else {
+                panic!("spilled accounts are redelivered as V0_0_2");
+            };
+            if self
+                .capacity
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |capacity| {
+                    capacity.checked_sub(1)
+                })
+                .is_err()
+            {
+                return Err(GeyserPluginError::Backpressure { accepted: 0 });
+            }
+            self.notified
+                .lock()
+                .unwrap()
+                .push((slot, account.write_version));
+            Ok(())
+        }
+    }
+
+    fn new_account<'a>(
+        pubkey: &'a Pubkey,
+        data: &'a [u8],
+        write_version: u64,
+    ) -> ReplicaAccountInfoV3<'a> {
+        ReplicaAccountInfoV3 {
+            pubkey: pubkey.as_ref(),
+            lamports: 1,
+            owner: pubkey.as_ref(),
+            executable: false,
+            rent_epoch: 0,
+            data,
+            write_version,
+            txn: None,
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_batch() {
+        let pubkey = Pubkey::new_unique();
+        let accounts = [
+            new_account(&pubkey, &[1, 2, 3], 7),
+            new_account(&pubkey, &[], 8),
+        ];
+        let record = encode_batch(&accounts, 5, true);
+        assert_eq!(
+            u64::from_le_bytes(record[..8].try_into().unwrap()),
+            record.len() as u64 - 8
+        );
+        let batch = decode_batch(&record[8..]).unwrap();
+        assert_eq!(batch.slot, 5);
+        assert!(batch.is_startup);
+        assert_eq!(batch.accounts.len(), 2);
+        assert_eq!(batch.accounts[0].pubkey, pubkey.to_bytes());
+        assert_eq!(batch.accounts[0].data, vec![1, 2, 3]);
+        assert_eq!(batch.accounts[1].write_version, 8);
+        assert_eq!(batch.accounts[1].txn_signature, None);
+        // Truncated records are rejected.
+        assert_eq!(decode_batch(&record[8..record.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_accounts_spill() {
+        let temp_dir = TempDir::new().unwrap();
+        let pubkey = Pubkey::new_unique();
+        let accounts: Vec<_> = (0..4).map(|i| new_account(&pubkey, &[], i)).collect();
+        let record_len = encode_batch(&accounts[..2], 0, false).len() as u64;
+        let mut spill = AccountsSpill::new(temp_dir.path(), "test", 2 * record_len);
+        assert!(spill.is_empty());
+
+        assert!(spill.append(&accounts[..2], 1, false).unwrap());
+        assert!(spill.append(&accounts[2..], 2, false).unwrap());
+        assert_eq!(spill.len(), 2 * record_len);
+        // The spill is full.
+        assert!(!spill.append(&accounts[..1], 3, false).unwrap());
+
+        // The plugin takes some of the first batch, and the rest of it the next time.
+        let plugin = SlowPlugin::default();
+        plugin.capacity.store(1, Ordering::Relaxed);
+        assert_eq!(spill.redeliver(&plugin, 16).unwrap(), Redelivery::Pending);
+        plugin.capacity.store(usize::MAX, Ordering::Relaxed);
+        assert_eq!(spill.redeliver(&plugin, 1).unwrap(), Redelivery::Pending);
+        assert_eq!(spill.len(), record_len);
+        assert_eq!(spill.redeliver(&plugin, 16).unwrap(), Redelivery::Drained);
+        assert!(spill.is_empty());
+        assert_eq!(
+            *plugin.notified.lock().unwrap(),
+            vec![(1, 0), (1, 1), (2, 2), (2, 3)],
+        );
+
+        // The spill file is removed with the spill.
+        let path = spill.path.clone();
+        assert!(path.exists());
+        drop(spill);
+        assert!(!path.exists());
+    }
+}
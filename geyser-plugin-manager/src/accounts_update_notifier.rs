@@ -2,12 +2,12 @@
 use {
     crate::geyser_plugin_manager::GeyserPluginManager,
     agave_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaAccountInfoV3, ReplicaAccountInfoVersions,
+        ReplicaAccountInfoV3, ReplicaSnapshotReplayProgress, ReplicaSnapshotReplayProgressVersions,
     },
     log::*,
     solana_account::{AccountSharedData, ReadableAccount},
     solana_accounts_db::accounts_update_notifier_interface::{
-        AccountForGeyser, AccountsUpdateNotifierInterface,
+        AccountForGeyser, AccountUpdate, AccountsUpdateNotifierInterface, SnapshotReplayProgress,
     },
     solana_clock::Slot,
     solana_measure::measure::Measure,
@@ -20,6 +20,7 @@ use {
 pub(crate) struct AccountsUpdateNotifierImpl {
     plugin_manager: Arc<RwLock<GeyserPluginManager>>,
     snapshot_notifications_enabled: bool,
+    snapshot_replay_enabled: bool,
 }
 
 impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
@@ -27,6 +28,10 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
         self.snapshot_notifications_enabled
     }
 
+    fn snapshot_replay_enabled(&self) -> bool {
+        self.snapshot_replay_enabled
+    }
+
     fn notify_account_update(
         &self,
         slot: Slot,
@@ -37,7 +42,22 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
     ) {
         let account_info =
             self.accountinfo_from_shared_account_data(account, txn, pubkey, write_version);
-        self.notify_plugins_of_account_update(account_info, slot, false);
+        self.notify_plugins_of_accounts_update(&[account_info], slot, false);
+    }
+
+    fn notify_accounts_update(&self, slot: Slot, accounts: &[AccountUpdate<'_>]) {
+        let account_infos: Vec<_> = accounts
+            .iter()
+            .map(|account| {
+                self.accountinfo_from_shared_account_data(
+                    account.account,
+                    &account.txn,
+                    account.pubkey,
+                    account.write_version,
+                )
+            })
+            .collect();
+        self.notify_plugins_of_accounts_update(&account_infos, slot, false);
     }
 
     fn notify_account_restore_from_snapshot(
@@ -60,7 +80,7 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
             100000
         );
 
-        self.notify_plugins_of_account_update(account, slot, true);
+        self.notify_plugins_of_accounts_update(&[account], slot, true);
 
         measure_all.stop();
 
@@ -72,6 +92,32 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
         );
     }
 
+    fn notify_snapshot_replay_progress(&self, progress: &SnapshotReplayProgress) {
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        let progress = ReplicaSnapshotReplayProgress {
+            slot: progress.slot,
+            num_accounts_notified: progress.num_accounts_notified,
+            num_accounts: progress.num_accounts,
+            last_pubkey: progress.last_pubkey.as_ref().map(AsRef::as_ref),
+            is_done: progress.is_done,
+        };
+        info!(
+            "Replayed {} of about {} accounts of the snapshot at slot {} to geyser plugins",
+            progress.num_accounts_notified, progress.num_accounts, progress.slot,
+        );
+        for plugin in plugin_manager.plugins.iter() {
+            if let Err(err) = plugin.notify_snapshot_replay_progress(
+                ReplicaSnapshotReplayProgressVersions::V0_0_1(&progress),
+            ) {
+                error!(
+                    "Failed to notify the progress of the snapshot replay, error: {} to plugin {}",
+                    err,
+                    plugin.name()
+                )
+            }
+        }
+    }
+
     fn notify_end_of_restore_from_snapshot(&self) {
         let plugin_manager = self.plugin_manager.read().unwrap();
         if plugin_manager.plugins.is_empty() {
@@ -80,6 +126,8 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
 
         for plugin in plugin_manager.plugins.iter() {
             let mut measure = Measure::start("geyser-plugin-end-of-restore-from-snapshot");
+            // The plugin is notified of all the accounts restored before the end of startup.
+            plugin.drain_accounts_spill(None);
             match plugin.notify_end_of_startup() {
                 Err(err) => {
                     error!(
@@ -108,10 +156,12 @@ impl AccountsUpdateNotifierImpl {
     pub fn new(
         plugin_manager: Arc<RwLock<GeyserPluginManager>>,
         snapshot_notifications_enabled: bool,
+        snapshot_replay_enabled: bool,
     ) -> Self {
         AccountsUpdateNotifierImpl {
             plugin_manager,
             snapshot_notifications_enabled,
+            snapshot_replay_enabled,
         }
     }
 
@@ -150,9 +200,9 @@ impl AccountsUpdateNotifierImpl {
         }
     }
 
-    fn notify_plugins_of_account_update(
+    fn notify_plugins_of_accounts_update(
         &self,
-        account: ReplicaAccountInfoV3,
+        accounts: &[ReplicaAccountInfoV3],
         slot: Slot,
         is_startup: bool,
     ) {
@@ -164,15 +214,11 @@ impl AccountsUpdateNotifierImpl {
         }
         for plugin in plugin_manager.plugins.iter() {
             let mut measure = Measure::start("geyser-plugin-update-account");
-            match plugin.update_account(
-                ReplicaAccountInfoVersions::V0_0_3(&account),
-                slot,
-                is_startup,
-            ) {
+            match plugin.notify_accounts(accounts, slot, is_startup) {
                 Err(err) => {
                     error!(
-                        "Failed to update account {} at slot {}, error: {} to plugin {}",
-                        bs58::encode(account.pubkey).into_string(),
+                        "Failed to update {} accounts at slot {}, error: {} to plugin {}",
+                        accounts.len(),
                         slot,
                         err,
                         plugin.name()
@@ -180,8 +226,8 @@ impl AccountsUpdateNotifierImpl {
                 }
                 Ok(_) => {
                     trace!(
-                        "Successfully updated account {} at slot {} to plugin {}",
-                        bs58::encode(account.pubkey).into_string(),
+                        "Successfully updated {} accounts at slot {} to plugin {}",
+                        accounts.len(),
                         slot,
                         plugin.name()
                     );
@@ -4,10 +4,11 @@ use {
         geyser_plugin_manager::GeyserPluginManager,
     },
     agave_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaBlockInfoV4, ReplicaBlockInfoVersions,
+        ReplicaBlockInfoV4, ReplicaBlockInfoV5, ReplicaBlockInfoVersions, ReplicaEntrySummary,
     },
     log::*,
     solana_clock::UnixTimestamp,
+    solana_ledger::blockstore_processor::ReplayedEntrySummary,
     solana_measure::measure::Measure,
     solana_metrics::*,
     solana_runtime::bank::KeyedRewardsAndNumPartitions,
@@ -32,6 +33,7 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
         block_height: Option<u64>,
         executed_transaction_count: u64,
         entry_count: u64,
+        entry_summaries: Option<&[ReplayedEntrySummary]>,
     ) {
         let plugin_manager = self.plugin_manager.read().unwrap();
         if plugin_manager.plugins.is_empty() {
@@ -50,10 +52,21 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
             executed_transaction_count,
             entry_count,
         );
+        let entries = entry_summaries
+            .filter(|_| plugin_manager.block_metadata_entry_summaries_enabled())
+            .map(Self::build_replica_entry_summaries);
+        let block_info_with_entries = entries
+            .as_ref()
+            .map(|entries| Self::build_replica_block_info_with_entries(&block_info, entries));
 
         for plugin in plugin_manager.plugins.iter() {
             let mut measure = Measure::start("geyser-plugin-update-slot");
-            let block_info = ReplicaBlockInfoVersions::V0_0_4(&block_info);
+            let block_info = match &block_info_with_entries {
+                Some(block_info) if plugin.block_metadata_entry_summaries_enabled() => {
+                    ReplicaBlockInfoVersions::V0_0_5(block_info)
+                }
+                _ => ReplicaBlockInfoVersions::V0_0_4(&block_info),
+            };
             match plugin.notify_block_metadata(block_info) {
                 Err(err) => {
                     error!(
@@ -80,6 +93,13 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
             );
         }
     }
+
+    fn entry_summaries_enabled(&self) -> bool {
+        self.plugin_manager
+            .read()
+            .unwrap()
+            .block_metadata_entry_summaries_enabled()
+    }
 }
 
 impl BlockMetadataNotifierImpl {
@@ -124,6 +144,39 @@ impl BlockMetadataNotifierImpl {
         }
     }
 
+    fn build_replica_entry_summaries(
+        entry_summaries: &[ReplayedEntrySummary],
+    ) -> Vec<ReplicaEntrySummary> {
+        entry_summaries
+            .iter()
+            .map(|entry_summary| ReplicaEntrySummary {
+                index: entry_summary.index,
+                hash: entry_summary.hash.as_ref(),
+                is_tick: entry_summary.is_tick(),
+                executed_transaction_count: entry_summary.num_transactions,
+                starting_transaction_index: entry_summary.starting_transaction_index,
+            })
+            .collect()
+    }
+
+    fn build_replica_block_info_with_entries<'a>(
+        block_info: &ReplicaBlockInfoV4<'a>,
+        entries: &'a [ReplicaEntrySummary<'a>],
+    ) -> ReplicaBlockInfoV5<'a> {
+        ReplicaBlockInfoV5 {
+            parent_slot: block_info.parent_slot,
+            parent_blockhash: block_info.parent_blockhash,
+            slot: block_info.slot,
+            blockhash: block_info.blockhash,
+            rewards: block_info.rewards,
+            block_time: block_info.block_time,
+            block_height: block_info.block_height,
+            executed_transaction_count: block_info.executed_transaction_count,
+            entry_count: block_info.entry_count,
+            entries,
+        }
+    }
+
     pub fn new(plugin_manager: Arc<RwLock<GeyserPluginManager>>) -> Self {
         Self { plugin_manager }
     }
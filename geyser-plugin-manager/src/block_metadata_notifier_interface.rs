@@ -1,10 +1,12 @@
 use {
-    solana_clock::UnixTimestamp, solana_runtime::bank::KeyedRewardsAndNumPartitions, std::sync::Arc,
+    solana_clock::UnixTimestamp, solana_ledger::blockstore_processor::ReplayedEntrySummary,
+    solana_runtime::bank::KeyedRewardsAndNumPartitions, std::sync::Arc,
 };
 
 /// Interface for notifying block metadata changes
 pub trait BlockMetadataNotifier {
-    /// Notify the block metadata
+    /// Notify the block metadata, with the summaries of the entries of the block if they were
+    /// recorded
     #[allow(clippy::too_many_arguments)]
     fn notify_block_metadata(
         &self,
@@ -17,7 +19,11 @@ pub trait BlockMetadataNotifier {
         block_height: Option<u64>,
         executed_transaction_count: u64,
         entry_count: u64,
+        entry_summaries: Option<&[ReplayedEntrySummary]>,
     );
+
+    /// Check if the summaries of the entries of blocks should be recorded for their metadata
+    fn entry_summaries_enabled(&self) -> bool;
 }
 
 pub type BlockMetadataNotifierArc = Arc<dyn BlockMetadataNotifier + Sync + Send>;
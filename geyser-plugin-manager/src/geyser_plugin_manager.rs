@@ -1,19 +1,46 @@
 use {
-    agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
+    crate::accounts_spill::{
+        AccountsSpill, Redelivery, DEFAULT_ACCOUNTS_SPILL_MAX_BYTES,
+        MAX_BATCHES_REDELIVERED_PER_NOTIFICATION,
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        capabilities, GeyserPlugin, GeyserPluginError, GeyserPluginHandshake, ReplicaAccountInfoV3,
+        ReplicaAccountInfoVersions, Result as PluginResult, GEYSER_PLUGIN_INTERFACE_VERSION,
+    },
     jsonrpc_core::{ErrorCode, Result as JsonRpcResult},
     libloading::Library,
     log::*,
+    solana_clock::Slot,
+    solana_metrics::inc_new_counter_info,
     std::{
         ops::{Deref, DerefMut},
         path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
+        thread,
+        time::{Duration, Instant},
     },
     tokio::sync::oneshot::Sender as OneShotSender,
 };
 
+/// How long to wait for a plugin which pushes back, before redelivering its spilled accounts
+const SPILL_DRAIN_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long a plugin which is unloaded or reloaded gets to be notified of its spilled accounts
+const SPILL_DRAIN_TIMEOUT_ON_UNLOAD: Duration = Duration::from_secs(10);
+
 #[derive(Debug)]
 pub struct LoadedGeyserPlugin {
     name: String,
     plugin: Box<dyn GeyserPlugin>,
+    /// The account notifications the plugin pushed back on
+    accounts_spill: Mutex<AccountsSpill>,
+    /// Whether the accounts spill is not empty, to not lock it for every notification
+    has_spilled_accounts: AtomicBool,
+    /// None if the library of the plugin does not export a handshake
+    handshake: Option<GeyserPluginHandshake>,
     // NOTE: While we do not access the library, the plugin we have loaded most
     // certainly does. To ensure we don't SIGSEGV we must declare the library
     // after the plugin so the plugin is dropped first.
@@ -28,9 +55,18 @@ pub struct LoadedGeyserPlugin {
 
 impl LoadedGeyserPlugin {
     pub fn new(library: Library, plugin: Box<dyn GeyserPlugin>, name: Option<String>) -> Self {
+        let name = name.unwrap_or_else(|| plugin.name().to_owned());
+        let accounts_spill = AccountsSpill::new(
+            std::env::temp_dir(),
+            &name,
+            DEFAULT_ACCOUNTS_SPILL_MAX_BYTES,
+        );
         Self {
-            name: name.unwrap_or_else(|| plugin.name().to_owned()),
+            name,
             plugin,
+            accounts_spill: Mutex::new(accounts_spill),
+            has_spilled_accounts: AtomicBool::new(false),
+            handshake: None,
             library,
         }
     }
@@ -38,6 +74,158 @@ impl LoadedGeyserPlugin {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn handshake(&self) -> Option<&GeyserPluginHandshake> {
+        self.handshake.as_ref()
+    }
+
+    pub(crate) fn set_handshake(&mut self, handshake: Option<GeyserPluginHandshake>) {
+        self.handshake = handshake;
+    }
+
+    /// Whether the plugin can be unloaded or reloaded while the validator runs. Plugins without
+    /// a handshake can be, as they always could.
+    pub fn is_hot_reloadable(&self) -> bool {
+        self.handshake
+            .is_none_or(|handshake| handshake.has_capability(capabilities::HOT_RELOAD))
+    }
+
+    pub(crate) fn set_accounts_spill(&mut self, accounts_spill: AccountsSpill) {
+        self.has_spilled_accounts
+            .store(!accounts_spill.is_empty(), Ordering::Release);
+        self.accounts_spill = Mutex::new(accounts_spill);
+    }
+
+    /// Takes the accounts spill of the plugin, leaving it an empty one
+    fn take_accounts_spill(&mut self) -> AccountsSpill {
+        let accounts_spill = AccountsSpill::new(
+            std::env::temp_dir(),
+            &self.name,
+            DEFAULT_ACCOUNTS_SPILL_MAX_BYTES,
+        );
+        self.has_spilled_accounts.store(false, Ordering::Release);
+        std::mem::replace(self.accounts_spill.get_mut().unwrap(), accounts_spill)
+    }
+
+    /// Notifies the plugin of the batch of `accounts`, after the accounts it pushed back on
+    /// before, if any.
+    ///
+    /// The accounts the plugin pushes back on are spilled, and notified of again later, in order.
+    pub(crate) fn notify_accounts(
+        &self,
+        accounts: &[ReplicaAccountInfoV3],
+        slot: Slot,
+        is_startup: bool,
+    ) -> PluginResult<()> {
+        if self.has_spilled_accounts.load(Ordering::Acquire) {
+            let mut accounts_spill = self.accounts_spill.lock().unwrap();
+            let redelivery = self.redeliver_spilled_accounts(
+                &mut accounts_spill,
+                MAX_BATCHES_REDELIVERED_PER_NOTIFICATION,
+            );
+            if redelivery == Redelivery::Pending {
+                self.spill_accounts(&mut accounts_spill, accounts, slot, is_startup);
+                return Ok(());
+            }
+        }
+
+        let account_versions: Vec<_> = accounts
+            .iter()
+            .map(ReplicaAccountInfoVersions::V0_0_3)
+            .collect();
+        match self
+            .plugin
+            .notify_accounts_batch(&account_versions, slot, is_startup)
+        {
+            Err(GeyserPluginError::Backpressure { accepted }) => {
+                let mut accounts_spill = self.accounts_spill.lock().unwrap();
+                let accounts = &accounts[accepted.min(accounts.len())..];
+                self.spill_accounts(&mut accounts_spill, accounts, slot, is_startup);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Notifies the plugin of all the accounts it pushed back on, waiting for it as needed, for
+    /// up to `timeout` if given.
+    ///
+    /// Returns false if the plugin still has spilled accounts.
+    pub(crate) fn drain_accounts_spill(&self, timeout: Option<Duration>) -> bool {
+        if !self.has_spilled_accounts.load(Ordering::Acquire) {
+            return true;
+        }
+        info!(
+            "Waiting for plugin {} to be notified of its spilled accounts",
+            self.name
+        );
+        let start = Instant::now();
+        loop {
+            let mut accounts_spill = self.accounts_spill.lock().unwrap();
+            if self.redeliver_spilled_accounts(&mut accounts_spill, usize::MAX)
+                == Redelivery::Drained
+            {
+                return true;
+            }
+            drop(accounts_spill);
+            if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                return false;
+            }
+            thread::sleep(SPILL_DRAIN_RETRY_INTERVAL);
+        }
+    }
+
+    fn redeliver_spilled_accounts(
+        &self,
+        accounts_spill: &mut AccountsSpill,
+        max_batches: usize,
+    ) -> Redelivery {
+        let redelivery = accounts_spill
+            .redeliver(&*self.plugin, max_batches)
+            .unwrap_or_else(|err| {
+                error!(
+                    "Failed to read the accounts spill of plugin {}, dropping {} spilled bytes: \
+                     {err}",
+                    self.name,
+                    accounts_spill.len(),
+                );
+                accounts_spill.clear();
+                Redelivery::Drained
+            });
+        self.has_spilled_accounts
+            .store(!accounts_spill.is_empty(), Ordering::Release);
+        redelivery
+    }
+
+    fn spill_accounts(
+        &self,
+        accounts_spill: &mut AccountsSpill,
+        accounts: &[ReplicaAccountInfoV3],
+        slot: Slot,
+        is_startup: bool,
+    ) {
+        if accounts.is_empty() {
+            return;
+        }
+        match accounts_spill.append(accounts, slot, is_startup) {
+            Ok(true) => {
+                self.has_spilled_accounts.store(true, Ordering::Release);
+                inc_new_counter_info!("geyser-plugin-spilled-accounts", accounts.len());
+                return;
+            }
+            Ok(false) => error!(
+                "The accounts spill of plugin {} is full, dropping {} accounts at slot {slot}",
+                self.name,
+                accounts.len(),
+            ),
+            Err(err) => error!(
+                "Failed to spill {} accounts at slot {slot} of plugin {}, dropping them: {err}",
+                accounts.len(),
+                self.name,
+            ),
+        }
+        inc_new_counter_info!("geyser-plugin-spill-dropped-accounts", accounts.len());
+    }
 }
 
 impl Deref for LoadedGeyserPlugin {
@@ -95,6 +283,16 @@ impl GeyserPluginManager {
         false
     }
 
+    /// Check if there is any plugin which wants the accounts restored from snapshot replayed
+    pub fn account_data_snapshot_replay_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.account_data_snapshot_replay_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Check if there is any plugin interested in transaction data
     pub fn transaction_notifications_enabled(&self) -> bool {
         for plugin in &self.plugins {
@@ -115,6 +313,36 @@ impl GeyserPluginManager {
         false
     }
 
+    /// Check if there is any plugin interested in cluster node updates
+    pub fn node_update_notifications_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.node_update_notifications_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Check if there is any plugin interested in the summaries of the entries of blocks
+    pub fn block_metadata_entry_summaries_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.block_metadata_entry_summaries_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Check if there is any plugin interested in the votes observed before they land in blocks
+    pub fn vote_notifications_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.vote_notifications_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Admin RPC request handler
     pub(crate) fn list_plugins(&self) -> JsonRpcResult<Vec<String>> {
         Ok(self.plugins.iter().map(|p| p.name().to_owned()).collect())
@@ -191,8 +419,17 @@ impl GeyserPluginManager {
                 data: None,
             });
         };
-
-        // Unload and drop plugin and lib
+        Self::check_hot_reloadable(&self.plugins[idx])?;
+
+        // Unload and drop plugin and lib, once it is notified of its spilled accounts
+        let accounts_spill = self.drain_accounts_spill_on_unload(idx);
+        if !accounts_spill.is_empty() {
+            warn!(
+                "Dropping {} bytes of accounts spilled by plugin {name}, which it was not \
+                 notified of before being unloaded",
+                accounts_spill.len(),
+            );
+        }
         self._drop_plugin(idx);
 
         Ok(())
@@ -215,6 +452,11 @@ impl GeyserPluginManager {
                 data: None,
             });
         };
+        Self::check_hot_reloadable(&self.plugins[idx])?;
+
+        // Notifications are not in flight while the manager is locked. The accounts spilled by
+        // the current plugin which it can't be notified of in time are handed to the new plugin.
+        let accounts_spill = self.drain_accounts_spill_on_unload(idx);
 
         // Unload and drop current plugin first in case plugin requires exclusive access to resource,
         // such as a particular port or database.
@@ -251,6 +493,14 @@ impl GeyserPluginManager {
         match new_plugin.on_load(new_parsed_config_file, true) {
             // On success, push plugin and library
             Ok(()) => {
+                if !accounts_spill.is_empty() {
+                    info!(
+                        "Handing {} bytes of spilled accounts to reloaded plugin {}",
+                        accounts_spill.len(),
+                        new_plugin.name(),
+                    );
+                    new_plugin.set_accounts_spill(accounts_spill);
+                }
                 self.plugins.push(new_plugin);
             }
 
@@ -269,6 +519,28 @@ impl GeyserPluginManager {
         Ok(())
     }
 
+    fn check_hot_reloadable(plugin: &LoadedGeyserPlugin) -> JsonRpcResult<()> {
+        if plugin.is_hot_reloadable() {
+            return Ok(());
+        }
+        Err(jsonrpc_core::Error {
+            code: ErrorCode::InvalidRequest,
+            message: format!(
+                "Plugin {} does not declare the HOT_RELOAD capability, it can only be unloaded \
+                 by restarting the validator",
+                plugin.name()
+            ),
+            data: None,
+        })
+    }
+
+    /// Notifies the plugin at `idx` of its spilled accounts, for a while, and takes the rest
+    fn drain_accounts_spill_on_unload(&mut self, idx: usize) -> AccountsSpill {
+        let plugin = &mut self.plugins[idx];
+        plugin.drain_accounts_spill(Some(SPILL_DRAIN_TIMEOUT_ON_UNLOAD));
+        plugin.take_accounts_spill()
+    }
+
     fn _drop_plugin(&mut self, idx: usize) {
         let mut current_plugin = self.plugins.remove(idx);
         let name = current_plugin.name().to_string();
@@ -336,6 +608,30 @@ pub enum GeyserPluginManagerError {
 
     #[error("The GeyserPlugin on_load method failed (error: {0})")]
     PluginStartError(String),
+
+    #[error("The plugin is not compatible with the validator (error: {0})")]
+    IncompatiblePlugin(String),
+}
+
+/// Checks the handshake of a plugin, if its library exports one
+pub(crate) fn check_handshake(
+    handshake: Option<&GeyserPluginHandshake>,
+) -> Result<(), GeyserPluginManagerError> {
+    let Some(handshake) = handshake else {
+        warn!(
+            "The plugin does not export a handshake, so its interface version can't be checked \
+             against the validator's, which is {GEYSER_PLUGIN_INTERFACE_VERSION}"
+        );
+        return Ok(());
+    };
+    if handshake.interface_version != GEYSER_PLUGIN_INTERFACE_VERSION {
+        return Err(GeyserPluginManagerError::IncompatiblePlugin(format!(
+            "the plugin is built against interface version {}, while the validator supports \
+             version {GEYSER_PLUGIN_INTERFACE_VERSION}",
+            handshake.interface_version
+        )));
+    }
+    Ok(())
 }
 
 /// # Safety
@@ -351,7 +647,11 @@ pub(crate) fn load_plugin_from_config(
 ) -> Result<(LoadedGeyserPlugin, &str), GeyserPluginManagerError> {
     use std::{fs::File, io::Read, path::PathBuf};
     type PluginConstructor = unsafe fn() -> *mut dyn GeyserPlugin;
-    use libloading::Symbol;
+    type PluginHandshake = extern "C" fn() -> GeyserPluginHandshake;
+    use {
+        agave_geyser_plugin_interface::geyser_plugin_interface::GEYSER_PLUGIN_HANDSHAKE_SYMBOL,
+        libloading::Symbol,
+    };
 
     let mut file = match File::open(geyser_plugin_config_file) {
         Ok(file) => file,
@@ -393,24 +693,49 @@ pub(crate) fn load_plugin_from_config(
 
     let plugin_name = result["name"].as_str().map(|s| s.to_owned());
 
+    let accounts_spill_dir = result["accounts_spill_dir"]
+        .as_str()
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let accounts_spill_max_bytes = match &result["accounts_spill_max_bytes"] {
+        serde_json::Value::Null => DEFAULT_ACCOUNTS_SPILL_MAX_BYTES,
+        value => value.as_u64().ok_or_else(|| {
+            GeyserPluginManagerError::InvalidConfigFileFormat(format!(
+                "accounts_spill_max_bytes in the config file {geyser_plugin_config_file:?} is not \
+                 a number of bytes"
+            ))
+        })?,
+    };
+
     let config_file = geyser_plugin_config_file
         .as_os_str()
         .to_str()
         .ok_or(GeyserPluginManagerError::InvalidPluginPath)?;
 
-    let (plugin, lib) = unsafe {
+    let (plugin, lib, handshake) = unsafe {
         let lib = Library::new(libpath)
             .map_err(|e| GeyserPluginManagerError::PluginLoadError(e.to_string()))?;
+        // The handshake is checked before the plugin is created with an interface it may not
+        // match.
+        let handshake = lib
+            .get::<PluginHandshake>(GEYSER_PLUGIN_HANDSHAKE_SYMBOL)
+            .ok()
+            .map(|handshake| handshake());
+        check_handshake(handshake.as_ref())?;
         let constructor: Symbol<PluginConstructor> = lib
             .get(b"_create_plugin")
             .map_err(|e| GeyserPluginManagerError::PluginLoadError(e.to_string()))?;
         let plugin_raw = constructor();
-        (Box::from_raw(plugin_raw), lib)
+        (Box::from_raw(plugin_raw), lib, handshake)
     };
-    Ok((
-        LoadedGeyserPlugin::new(lib, plugin, plugin_name),
-        config_file,
-    ))
+    let mut plugin = LoadedGeyserPlugin::new(lib, plugin, plugin_name);
+    plugin.set_handshake(handshake);
+    plugin.set_accounts_spill(AccountsSpill::new(
+        accounts_spill_dir,
+        plugin.name(),
+        accounts_spill_max_bytes,
+    ));
+    Ok((plugin, config_file))
 }
 
 #[cfg(test)]
@@ -448,9 +773,12 @@ pub(crate) fn load_plugin_from_config(
 mod tests {
     use {
         crate::geyser_plugin_manager::{
-            GeyserPluginManager, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG, TESTPLUGIN_CONFIG,
+            check_handshake, GeyserPluginManager, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG,
+            TESTPLUGIN_CONFIG,
+        },
+        agave_geyser_plugin_interface::geyser_plugin_interface::{
+            capabilities, GeyserPlugin, GeyserPluginHandshake, GEYSER_PLUGIN_INTERFACE_VERSION,
         },
-        agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
         libloading::Library,
         std::sync::{Arc, RwLock},
     };
@@ -568,4 +896,45 @@ mod tests {
         assert!(unload_result.is_ok());
         assert_eq!(plugin_manager_lock.plugins.len(), 0);
     }
+
+    #[test]
+    fn test_check_handshake() {
+        // Plugins without a handshake are loaded as before.
+        assert!(check_handshake(None).is_ok());
+        let mut handshake = GeyserPluginHandshake {
+            interface_version: GEYSER_PLUGIN_INTERFACE_VERSION,
+            capabilities: capabilities::HOT_RELOAD,
+        };
+        assert!(check_handshake(Some(&handshake)).is_ok());
+        handshake.interface_version += 1;
+        assert!(check_handshake(Some(&handshake)).is_err());
+    }
+
+    #[test]
+    fn test_plugin_hot_reload_capability() {
+        let plugin_manager = Arc::new(RwLock::new(GeyserPluginManager::new()));
+        let mut plugin_manager_lock = plugin_manager.write().unwrap();
+
+        // A plugin with a handshake which does not declare HOT_RELOAD stays loaded
+        let (mut plugin, config) = dummy_plugin_and_library(TestPlugin, DUMMY_CONFIG);
+        let mut handshake = GeyserPluginHandshake {
+            interface_version: GEYSER_PLUGIN_INTERFACE_VERSION,
+            capabilities: capabilities::BACKPRESSURE,
+        };
+        plugin.set_handshake(Some(handshake));
+        plugin.on_load(config, false).unwrap();
+        plugin_manager_lock.plugins.push(plugin);
+        assert!(!plugin_manager_lock.plugins[0].is_hot_reloadable());
+        assert!(plugin_manager_lock
+            .reload_plugin(DUMMY_NAME, TESTPLUGIN2_CONFIG)
+            .is_err());
+        assert!(plugin_manager_lock.unload_plugin(DUMMY_NAME).is_err());
+        assert_eq!(plugin_manager_lock.plugins.len(), 1);
+
+        // Once it declares HOT_RELOAD, it can be unloaded
+        handshake.capabilities |= capabilities::HOT_RELOAD;
+        plugin_manager_lock.plugins[0].set_handshake(Some(handshake));
+        assert!(plugin_manager_lock.unload_plugin(DUMMY_NAME).is_ok());
+        assert!(plugin_manager_lock.plugins.is_empty());
+    }
 }
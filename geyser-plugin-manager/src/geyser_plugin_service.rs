@@ -5,9 +5,13 @@ use {
         block_metadata_notifier_interface::BlockMetadataNotifierArc,
         entry_notifier::EntryNotifierImpl,
         geyser_plugin_manager::{GeyserPluginManager, GeyserPluginManagerRequest},
+        node_update_notifier::NodeUpdateNotifierImpl,
+        node_update_notifier_interface::NodeUpdateNotifierArc,
         slot_status_notifier::SlotStatusNotifierImpl,
         slot_status_observer::SlotStatusObserver,
         transaction_notifier::TransactionNotifierImpl,
+        vote_notifier::VoteNotifierImpl,
+        vote_notifier_interface::VoteNotifierArc,
     },
     crossbeam_channel::Receiver,
     log::*,
@@ -39,6 +43,8 @@ pub struct GeyserPluginService {
     entry_notifier: Option<EntryNotifierArc>,
     block_metadata_notifier: Option<BlockMetadataNotifierArc>,
     slot_status_notifier: Option<SlotStatusNotifier>,
+    node_update_notifier: Option<NodeUpdateNotifierArc>,
+    vote_notifier: Option<VoteNotifierArc>,
 }
 
 impl GeyserPluginService {
@@ -90,12 +96,19 @@ impl GeyserPluginService {
 
         let account_data_notifications_enabled =
             plugin_manager.account_data_notifications_enabled() || geyser_plugin_always_enabled;
-        let account_data_snapshot_notifications_enabled =
-            plugin_manager.account_data_snapshot_notifications_enabled();
+        let account_data_snapshot_replay_enabled =
+            plugin_manager.account_data_snapshot_replay_enabled();
+        let account_data_snapshot_notifications_enabled = plugin_manager
+            .account_data_snapshot_notifications_enabled()
+            || account_data_snapshot_replay_enabled;
         let transaction_notifications_enabled =
             plugin_manager.transaction_notifications_enabled() || geyser_plugin_always_enabled;
         let entry_notifications_enabled =
             plugin_manager.entry_notifications_enabled() || geyser_plugin_always_enabled;
+        let node_update_notifications_enabled =
+            plugin_manager.node_update_notifications_enabled() || geyser_plugin_always_enabled;
+        let vote_notifications_enabled =
+            plugin_manager.vote_notifications_enabled() || geyser_plugin_always_enabled;
         let plugin_manager = Arc::new(RwLock::new(plugin_manager));
 
         let accounts_update_notifier: Option<AccountsUpdateNotifier> =
@@ -103,6 +116,7 @@ impl GeyserPluginService {
                 let accounts_update_notifier = AccountsUpdateNotifierImpl::new(
                     plugin_manager.clone(),
                     account_data_snapshot_notifications_enabled,
+                    account_data_snapshot_replay_enabled,
                 );
                 Some(Arc::new(accounts_update_notifier))
             } else {
@@ -124,6 +138,21 @@ impl GeyserPluginService {
             None
         };
 
+        let node_update_notifier: Option<NodeUpdateNotifierArc> =
+            if node_update_notifications_enabled {
+                let node_update_notifier = NodeUpdateNotifierImpl::new(plugin_manager.clone());
+                Some(Arc::new(node_update_notifier))
+            } else {
+                None
+            };
+
+        let vote_notifier: Option<VoteNotifierArc> = if vote_notifications_enabled {
+            let vote_notifier = VoteNotifierImpl::new(plugin_manager.clone());
+            Some(Arc::new(vote_notifier))
+        } else {
+            None
+        };
+
         let (slot_status_observer, block_metadata_notifier, slot_status_notifier): (
             Option<SlotStatusObserver>,
             Option<BlockMetadataNotifierArc>,
@@ -163,6 +192,8 @@ impl GeyserPluginService {
             entry_notifier,
             block_metadata_notifier,
             slot_status_notifier,
+            node_update_notifier,
+            vote_notifier,
         })
     }
 
@@ -196,6 +227,14 @@ impl GeyserPluginService {
         self.slot_status_notifier.clone()
     }
 
+    pub fn get_node_update_notifier(&self) -> Option<NodeUpdateNotifierArc> {
+        self.node_update_notifier.clone()
+    }
+
+    pub fn get_vote_notifier(&self) -> Option<VoteNotifierArc> {
+        self.vote_notifier.clone()
+    }
+
     pub fn join(self) -> thread::Result<()> {
         if let Some(mut slot_status_observer) = self.slot_status_observer {
             slot_status_observer.join()?;
@@ -1,11 +1,16 @@
+pub mod accounts_spill;
 pub mod accounts_update_notifier;
 pub mod block_metadata_notifier;
 pub mod block_metadata_notifier_interface;
 pub mod entry_notifier;
 pub mod geyser_plugin_manager;
 pub mod geyser_plugin_service;
+pub mod node_update_notifier;
+pub mod node_update_notifier_interface;
 pub mod slot_status_notifier;
 pub mod slot_status_observer;
 pub mod transaction_notifier;
+pub mod vote_notifier;
+pub mod vote_notifier_interface;
 
 pub use geyser_plugin_manager::GeyserPluginManagerRequest;
@@ -0,0 +1,94 @@
+/// Module responsible for notifying plugins about cluster node updates
+use {
+    crate::{
+        geyser_plugin_manager::GeyserPluginManager,
+        node_update_notifier_interface::NodeUpdateNotifier,
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        NodeStatus, ReplicaNodeInfo, ReplicaNodeInfoVersions,
+    },
+    log::*,
+    solana_gossip::contact_info::{ContactInfo, Protocol},
+    solana_measure::measure::Measure,
+    solana_metrics::*,
+    std::sync::{Arc, RwLock},
+};
+
+pub(crate) struct NodeUpdateNotifierImpl {
+    plugin_manager: Arc<RwLock<GeyserPluginManager>>,
+}
+
+impl NodeUpdateNotifier for NodeUpdateNotifierImpl {
+    fn notify_node_update(&self, node: &ContactInfo, status: NodeStatus) {
+        let mut measure = Measure::start("geyser-plugin-notify_plugins_of_node_update");
+
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let version = node.version().to_string();
+        let node_info = Self::build_replica_node_info(node, status, &version);
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.node_update_notifications_enabled() {
+                continue;
+            }
+            match plugin.notify_node_update(ReplicaNodeInfoVersions::V0_0_1(&node_info)) {
+                Err(err) => {
+                    error!(
+                        "Failed to notify update of node {}, error: ({}) to plugin {}",
+                        node.pubkey(),
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified update of node {} to plugin {}",
+                        node.pubkey(),
+                        plugin.name()
+                    );
+                }
+            }
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify_plugins_of_node_update-us",
+            measure.as_us() as usize,
+            10000,
+            10000
+        );
+    }
+}
+
+impl NodeUpdateNotifierImpl {
+    pub fn new(plugin_manager: Arc<RwLock<GeyserPluginManager>>) -> Self {
+        Self { plugin_manager }
+    }
+
+    fn build_replica_node_info<'a>(
+        node: &'a ContactInfo,
+        status: NodeStatus,
+        version: &'a str,
+    ) -> ReplicaNodeInfo<'a> {
+        ReplicaNodeInfo {
+            pubkey: node.pubkey().as_ref(),
+            status,
+            wallclock: node.wallclock(),
+            shred_version: node.shred_version(),
+            version,
+            feature_set: node.version().feature_set,
+            gossip: node.gossip(),
+            tvu: node.tvu(Protocol::UDP),
+            tpu: node.tpu(Protocol::UDP),
+            tpu_quic: node.tpu(Protocol::QUIC),
+            tpu_forwards: node.tpu_forwards(Protocol::UDP),
+            tpu_forwards_quic: node.tpu_forwards(Protocol::QUIC),
+            tpu_vote: node.tpu_vote(Protocol::UDP),
+            serve_repair: node.serve_repair(Protocol::UDP),
+            rpc: node.rpc(),
+            pubsub: node.rpc_pubsub(),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub use agave_geyser_plugin_interface::geyser_plugin_interface::NodeStatus;
+use {solana_gossip::contact_info::ContactInfo, std::sync::Arc};
+
+/// Interface for notifying changes to the cluster nodes observed in gossip
+pub trait NodeUpdateNotifier {
+    /// Notify the contact info and the status of a node
+    fn notify_node_update(&self, node: &ContactInfo, status: NodeStatus);
+}
+
+pub type NodeUpdateNotifierArc = Arc<dyn NodeUpdateNotifier + Sync + Send>;
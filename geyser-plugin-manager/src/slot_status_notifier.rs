@@ -1,11 +1,15 @@
 use {
     crate::geyser_plugin_manager::GeyserPluginManager,
-    agave_geyser_plugin_interface::geyser_plugin_interface::SlotStatus,
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        ReplicaSlotAccountFilterInfo, ReplicaSlotAccountFilterVersions, SlotStatus,
+    },
     log::*,
     solana_clock::Slot,
     solana_measure::measure::Measure,
     solana_metrics::*,
-    solana_rpc::slot_status_notifier::SlotStatusNotifierInterface,
+    solana_rpc::{
+        slot_account_filter::SlotAccountFilter, slot_status_notifier::SlotStatusNotifierInterface,
+    },
     std::sync::{Arc, RwLock},
 };
 
@@ -41,6 +45,58 @@ impl SlotStatusNotifierInterface for SlotStatusNotifierImpl {
     fn notify_slot_dead(&self, slot: Slot, error: String) {
         self.notify_slot_status(slot, None, SlotStatus::Dead(error));
     }
+
+    fn notify_slot_account_filter(&self, filter: &SlotAccountFilter) {
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if !plugin_manager
+            .plugins
+            .iter()
+            .any(|plugin| plugin.slot_account_filter_notifications_enabled())
+        {
+            return;
+        }
+
+        let bits = filter.words();
+        let filter_info = ReplicaSlotAccountFilterInfo {
+            slot: filter.slot(),
+            num_accounts: filter.num_accounts() as u64,
+            num_bits: filter.num_bits(),
+            num_hashes: filter.num_hashes(),
+            bits: &bits,
+        };
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.slot_account_filter_notifications_enabled() {
+                continue;
+            }
+            let mut measure = Measure::start("geyser-plugin-notify-slot-account-filter");
+            match plugin
+                .notify_slot_account_filter(ReplicaSlotAccountFilterVersions::V0_0_1(&filter_info))
+            {
+                Err(err) => {
+                    error!(
+                        "Failed to notify slot account filter at slot {}, error: {} to plugin {}",
+                        filter_info.slot,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified slot account filter at slot {} to plugin {}",
+                        filter_info.slot,
+                        plugin.name()
+                    );
+                }
+            }
+            measure.stop();
+            inc_new_counter_debug!(
+                "geyser-plugin-notify-slot-account-filter-us",
+                measure.as_us() as usize,
+                1000,
+                1000
+            );
+        }
+    }
 }
 
 impl SlotStatusNotifierImpl {
@@ -73,6 +73,12 @@ impl SlotStatusObserver {
                                     .unwrap()
                                     .notify_slot_rooted(slot, Some(parent));
                             }
+                            SlotNotification::AccountFilter(filter) => {
+                                slot_status_notifier
+                                    .read()
+                                    .unwrap()
+                                    .notify_slot_account_filter(&filter);
+                            }
                         }
                     }
                 }
@@ -2,13 +2,14 @@
 use {
     crate::geyser_plugin_manager::GeyserPluginManager,
     agave_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaTransactionInfoV2, ReplicaTransactionInfoVersions,
+        GeyserPluginError, ReplicaTransactionInfoV2, ReplicaTransactionInfoVersions,
+        Result as PluginResult,
     },
     log::*,
     solana_clock::Slot,
     solana_measure::measure::Measure,
     solana_metrics::*,
-    solana_rpc::transaction_notifier_interface::TransactionNotifier,
+    solana_rpc::transaction_notifier_interface::{TransactionNotification, TransactionNotifier},
     solana_signature::Signature,
     solana_transaction::sanitized::SanitizedTransaction,
     solana_transaction_status::TransactionStatusMeta,
@@ -50,24 +51,51 @@ impl TransactionNotifier for TransactionNotifierImpl {
             if !plugin.transaction_notifications_enabled() {
                 continue;
             }
-            match plugin.notify_transaction(
+            let result = plugin.notify_transaction(
                 ReplicaTransactionInfoVersions::V0_0_2(&transaction_log_info),
                 slot,
-            ) {
-                Err(err) => {
-                    error!(
-                        "Failed to notify transaction, error: ({}) to plugin {}",
-                        err,
-                        plugin.name()
-                    )
-                }
-                Ok(_) => {
-                    trace!(
-                        "Successfully notified transaction to plugin {}",
-                        plugin.name()
-                    );
-                }
+            );
+            Self::log_result(result, 1, plugin.name());
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify_plugins_of_transaction_info-us",
+            measure.as_us() as usize,
+            10000,
+            10000
+        );
+    }
+
+    fn notify_transactions(&self, slot: Slot, transactions: &[TransactionNotification<'_>]) {
+        let mut measure = Measure::start("geyser-plugin-notify_plugins_of_transaction_info");
+        let plugin_manager = self.plugin_manager.read().unwrap();
+
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let transaction_log_infos: Vec<_> = transactions
+            .iter()
+            .map(|transaction| {
+                Self::build_replica_transaction_info(
+                    transaction.transaction_slot_index,
+                    transaction.signature,
+                    transaction.transaction_status_meta,
+                    transaction.transaction,
+                )
+            })
+            .collect();
+        let transaction_versions: Vec<_> = transaction_log_infos
+            .iter()
+            .map(ReplicaTransactionInfoVersions::V0_0_2)
+            .collect();
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.transaction_notifications_enabled() {
+                continue;
             }
+            let result = plugin.notify_transactions_batch(&transaction_versions, slot);
+            Self::log_result(result, transactions.len(), plugin.name());
         }
         measure.stop();
         inc_new_counter_debug!(
@@ -84,6 +112,30 @@ impl TransactionNotifierImpl {
         Self { plugin_manager }
     }
 
+    /// Logs the result of notifying `plugin` of `num_transactions` transactions.
+    ///
+    /// Transactions are not spilled, so those a plugin pushes back on are dropped.
+    fn log_result(result: PluginResult<()>, num_transactions: usize, plugin: &str) {
+        match result {
+            Err(GeyserPluginError::Backpressure { accepted }) => {
+                let num_dropped = num_transactions.saturating_sub(accepted);
+                warn!(
+                    "Plugin {plugin} pushed back on transactions, dropping {num_dropped} of them"
+                );
+                inc_new_counter_info!("geyser-plugin-dropped-transactions", num_dropped);
+            }
+            Err(err) => {
+                error!(
+                    "Failed to notify transaction, error: ({}) to plugin {}",
+                    err, plugin
+                )
+            }
+            Ok(_) => {
+                trace!("Successfully notified transaction to plugin {}", plugin);
+            }
+        }
+    }
+
     fn build_replica_transaction_info<'a>(
         index: usize,
         signature: &'a Signature,
@@ -0,0 +1,90 @@
+/// Module responsible for notifying plugins about the votes observed before they land in blocks
+use {
+    crate::{
+        geyser_plugin_manager::GeyserPluginManager,
+        vote_notifier_interface::{ObservedVote, VoteNotifier},
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        ReplicaVoteInfo, ReplicaVoteInfoVersions,
+    },
+    log::*,
+    solana_measure::measure::Measure,
+    solana_metrics::*,
+    std::{
+        sync::{Arc, RwLock},
+        time::UNIX_EPOCH,
+    },
+};
+
+pub(crate) struct VoteNotifierImpl {
+    plugin_manager: Arc<RwLock<GeyserPluginManager>>,
+}
+
+impl VoteNotifier for VoteNotifierImpl {
+    fn notify_vote(&self, vote: &ObservedVote) {
+        let mut measure = Measure::start("geyser-plugin-notify_plugins_of_vote");
+
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let vote_info = Self::build_replica_vote_info(vote);
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.vote_notifications_enabled() {
+                continue;
+            }
+            match plugin.notify_vote(ReplicaVoteInfoVersions::V0_0_1(&vote_info)) {
+                Err(err) => {
+                    error!(
+                        "Failed to notify vote {} of {} for slot {}, error: ({}) to plugin {}",
+                        vote.signature,
+                        vote.vote_pubkey,
+                        vote.slot,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified vote {} of {} for slot {} to plugin {}",
+                        vote.signature,
+                        vote.vote_pubkey,
+                        vote.slot,
+                        plugin.name()
+                    );
+                }
+            }
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify_plugins_of_vote-us",
+            measure.as_us() as usize,
+            10000,
+            10000
+        );
+    }
+}
+
+impl VoteNotifierImpl {
+    pub fn new(plugin_manager: Arc<RwLock<GeyserPluginManager>>) -> Self {
+        Self { plugin_manager }
+    }
+
+    fn build_replica_vote_info<'a>(vote: &'a ObservedVote) -> ReplicaVoteInfo<'a> {
+        ReplicaVoteInfo {
+            signature: vote.signature,
+            vote_pubkey: vote.vote_pubkey.as_ref(),
+            slot: vote.slot,
+            hash: vote.hash.as_ref(),
+            timestamp: vote.timestamp,
+            source: vote.source,
+            received_at_us: vote
+                .received_at
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_micros() as u64)
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+pub use agave_geyser_plugin_interface::geyser_plugin_interface::VoteSource;
+use {
+    solana_clock::{Slot, UnixTimestamp},
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::{sync::Arc, time::SystemTime},
+};
+
+/// A vote transaction observed before it lands in a block
+#[derive(Debug)]
+pub struct ObservedVote<'a> {
+    pub signature: &'a Signature,
+    pub vote_pubkey: &'a Pubkey,
+    /// The last slot voted on
+    pub slot: Slot,
+    /// The bank hash of the last slot voted on
+    pub hash: &'a Hash,
+    pub timestamp: Option<UnixTimestamp>,
+    pub source: VoteSource,
+    pub received_at: SystemTime,
+}
+
+/// Interface for notifying the vote transactions received from the TPU or from gossip
+pub trait VoteNotifier {
+    /// Notify a vote transaction received before it lands in a block
+    fn notify_vote(&self, vote: &ObservedVote);
+}
+
+pub type VoteNotifierArc = Arc<dyn VoteNotifier + Sync + Send>;
@@ -25,11 +25,14 @@ use {
             get_max_bloom_filter_bytes, CrdsFilter, CrdsTimeouts, ProcessPullStats, PullRequest,
             CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
         },
+        crds_inspect::{self, CrdsInspectFilter, CrdsInspectPage},
+        crds_priority::CrdsPriorityWeights,
         crds_value::{CrdsValue, CrdsValueLabel},
         duplicate_shred::DuplicateShred,
         epoch_slots::EpochSlots,
         epoch_specs::EpochSpecs,
         gossip_error::GossipError,
+        peer_scores::{PeerOffense, PeerScores, MAX_PULL_REQUESTS_PER_BATCH},
         ping_pong::Pong,
         protocol::{
             split_gossip_messages, Ping, PingCache, Protocol, PruneData,
@@ -172,6 +175,7 @@ pub struct ClusterInfo {
     contact_save_interval: u64,  // milliseconds, 0 = disabled
     contact_info_path: PathBuf,
     socket_addr_space: SocketAddrSpace,
+    peer_scores: PeerScores,
 }
 
 // Returns false if the CRDS value should be discarded.
@@ -236,15 +240,24 @@ impl ClusterInfo {
             contact_info_path: PathBuf::default(),
             contact_save_interval: 0, // disabled
             socket_addr_space,
+            peer_scores: PeerScores::default(),
         };
         me.refresh_my_gossip_contact_info();
         me
     }
 
+    pub fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     pub fn set_contact_debug_interval(&mut self, new: u64) {
         self.contact_debug_interval = new;
     }
 
+    pub fn set_crds_priority_weights(&mut self, priority_weights: CrdsPriorityWeights) {
+        self.gossip.priority_weights = priority_weights;
+    }
+
     pub fn socket_addr_space(&self) -> &SocketAddrSpace {
         &self.socket_addr_space
     }
@@ -442,6 +455,16 @@ impl ClusterInfo {
         Ok(())
     }
 
+    pub fn remove_tpu(&self) {
+        self.my_contact_info.write().unwrap().remove_tpu();
+        self.refresh_my_gossip_contact_info();
+    }
+
+    pub fn remove_tpu_forwards(&self) {
+        self.my_contact_info.write().unwrap().remove_tpu_forwards();
+        self.refresh_my_gossip_contact_info();
+    }
+
     pub fn lookup_contact_info<R>(
         &self,
         id: &Pubkey,
@@ -920,6 +943,21 @@ impl ClusterInfo {
         txs
     }
 
+    /// Returns the origin and clock offset of every vote inserted since the
+    /// given cursor. The offset is the wallclock of the origin when it signed
+    /// the vote less the local time at which the vote was inserted; if both
+    /// clocks are in sync it is the negated propagation delay.
+    pub fn get_vote_clock_offsets(&self, cursor: &mut Cursor) -> Vec<(Pubkey, i64)> {
+        self.time_gossip_read_lock("get_votes", &self.stats.get_votes)
+            .get_votes(cursor)
+            .map(|vote| {
+                let offset =
+                    (vote.value.wallclock() as i64).saturating_sub(vote.local_timestamp as i64);
+                (vote.value.pubkey(), offset)
+            })
+            .collect()
+    }
+
     /// Returns votes and the associated labels inserted since the given cursor.
     pub fn get_votes_with_labels(
         &self,
@@ -1028,6 +1066,13 @@ impl ClusterInfo {
             .collect()
     }
 
+    /// Returns a page of the values in the crds table matching the filter,
+    /// along with per-type counts of the whole table.
+    pub fn inspect_crds(&self, filter: &CrdsInspectFilter) -> CrdsInspectPage {
+        let gossip_crds = self.gossip.crds.read().unwrap();
+        crds_inspect::inspect_crds(&gossip_crds, filter)
+    }
+
     pub fn get_node_version(&self, pubkey: &Pubkey) -> Option<solana_version::Version> {
         let gossip_crds = self.gossip.crds.read().unwrap();
         gossip_crds
@@ -1075,6 +1120,25 @@ impl ClusterInfo {
             .collect()
     }
 
+    /// Returns the gossip QUIC addresses of the peers which advertise one,
+    /// keyed by both their gossip UDP and their gossip QUIC addresses.
+    pub(crate) fn gossip_quic_addrs(&self) -> HashMap<SocketAddr, SocketAddr> {
+        let me = self.id();
+        let gossip_crds = self.gossip.crds.read().unwrap();
+        gossip_crds
+            .get_nodes_contact_info()
+            .filter(|node| node.pubkey() != &me)
+            .filter_map(|node| {
+                let quic_addr = node.gossip_quic()?;
+                self.socket_addr_space.check(&quic_addr).then(|| {
+                    let udp_addr = node.gossip().map(|udp_addr| (udp_addr, quic_addr));
+                    udp_addr.into_iter().chain([(quic_addr, quic_addr)])
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
     /// all validators that have a valid tvu port regardless of `shred_version`.
     pub fn all_tvu_peers(&self) -> Vec<ContactInfo> {
         let self_pubkey = self.id();
@@ -1287,6 +1351,9 @@ impl ClusterInfo {
         self.stats
             .push_fanout_num_entries
             .add_relaxed(entries.len() as u64);
+        self.stats
+            .push_backlog_num_entries
+            .add_relaxed(self.gossip.push.backlog_len() as u64);
         self.stats
             .push_fanout_num_nodes
             .add_relaxed(num_pushes as u64);
@@ -1690,10 +1757,13 @@ impl ClusterInfo {
                 &self.stats,
             )
         };
-        // Prioritize more recent values, staked values and ContactInfos.
+        // Prioritize more recent values, staked values and types of values
+        // with greater priority weights.
+        let priority_weights = &self.gossip.priority_weights;
         let get_score = |value: &CrdsValue| -> u64 {
             let age = now.saturating_sub(value.wallclock());
-            // score CrdsValue: 2x score if staked; 2x score if ContactInfo
+            // score CrdsValue: 2x score if staked; scaled by the weight of
+            // its type
             let score = DEFAULT_EPOCH_DURATION_MS
                 .saturating_sub(age)
                 .div(CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS)
@@ -1703,11 +1773,7 @@ impl ClusterInfo {
             } else {
                 score
             };
-            let score = match value.data() {
-                CrdsData::ContactInfo(_) => 2 * score,
-                _ => score,
-            };
-            score
+            score.saturating_mul(priority_weights.weight(value))
         };
         let mut num_crds_values = 0;
         let (scores, mut pull_responses): (Vec<_>, Vec<_>) = requests
@@ -2012,6 +2078,11 @@ impl ClusterInfo {
         let mut prune_messages = vec![];
         let mut ping_messages = vec![];
         let mut pong_messages = vec![];
+        // Per peer offenses, penalized once the whole batch is split.
+        let now = timestamp();
+        let mut num_pull_requests = HashMap::<IpAddr, u64>::new();
+        let mut num_duplicate_values = HashMap::<IpAddr, u64>::new();
+        let mut pushed_values = HashSet::<(IpAddr, Hash)>::new();
         for (from_addr, packet) in packets.drain(..).flatten() {
             match packet {
                 Protocol::PullRequest(filter, caller) => {
@@ -2019,6 +2090,10 @@ impl ClusterInfo {
                         self.stats.skip_pull_shred_version.add_relaxed(1);
                         continue;
                     }
+                    *num_pull_requests.entry(from_addr.ip()).or_default() += 1;
+                    if self.peer_scores.check_deprioritized(&from_addr.ip(), now) {
+                        continue;
+                    }
                     let request = PullRequest {
                         pubkey: caller.pubkey(),
                         addr: from_addr,
@@ -2044,6 +2119,14 @@ impl ClusterInfo {
                     if should_check_duplicate_instance {
                         check_duplicate_instance(&data)?;
                     }
+                    let num_duplicates = data
+                        .iter()
+                        .filter(|value| !pushed_values.insert((from_addr.ip(), *value.hash())))
+                        .count();
+                    if num_duplicates > 0 {
+                        *num_duplicate_values.entry(from_addr.ip()).or_default() +=
+                            num_duplicates as u64;
+                    }
                     data.retain(&mut verify_gossip_addr);
                     if !data.is_empty() {
                         self.stats
@@ -2057,6 +2140,15 @@ impl ClusterInfo {
                 Protocol::PongMessage(pong) => pong_messages.push((from_addr, pong)),
             }
         }
+        for (addr, count) in num_pull_requests {
+            let excess = count.saturating_sub(MAX_PULL_REQUESTS_PER_BATCH);
+            self.peer_scores
+                .record(addr, PeerOffense::ExcessPullRequest, excess, now);
+        }
+        for (addr, count) in num_duplicate_values {
+            self.peer_scores
+                .record(addr, PeerOffense::DuplicateValue, count, now);
+        }
         let pings = pings
             .into_iter()
             .map(|(addr, ping)| (addr, Protocol::PingMessage(ping)));
@@ -2113,7 +2205,13 @@ impl ClusterInfo {
             packet: &Packet,
             stakes: &HashMap<Pubkey, u64>,
             stats: &GossipStats,
+            peer_scores: &PeerScores,
+            now: u64,
         ) -> Option<(SocketAddr, Protocol)> {
+            let addr = packet.meta().socket_addr();
+            if peer_scores.check_shunned(&addr.ip(), now) {
+                return None;
+            }
             let mut protocol: Protocol =
                 stats.record_received_packet(packet.deserialize_slice::<Protocol, _>(..))?;
             protocol.sanitize().ok()?;
@@ -2129,29 +2227,27 @@ impl ClusterInfo {
                     return None;
                 }
             }
-            protocol.par_verify().then(|| {
-                stats.packets_received_verified_count.add_relaxed(1);
-                (packet.meta().socket_addr(), protocol)
-            })
+            if !protocol.par_verify() {
+                peer_scores.record(addr.ip(), PeerOffense::InvalidSignature, 1, now);
+                return None;
+            }
+            stats.packets_received_verified_count.add_relaxed(1);
+            Some((addr, protocol))
         }
         let stakes = epoch_specs
             .map(EpochSpecs::current_epoch_staked_nodes)
             .cloned()
             .unwrap_or_default();
+        let now = timestamp();
+        let verify =
+            |packet: &Packet| verify_packet(packet, &stakes, &self.stats, &self.peer_scores, now);
         let packets_verified: Vec<_> = {
             let _st = ScopedTimer::from(&self.stats.verify_gossip_packets_time);
             thread_pool.install(|| {
                 if packet_buf.len() == 1 {
-                    packet_buf[0]
-                        .par_iter()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
-                        .collect()
+                    packet_buf[0].par_iter().filter_map(verify).collect()
                 } else {
-                    packet_buf
-                        .par_iter()
-                        .flatten()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
-                        .collect()
+                    packet_buf.par_iter().flatten().filter_map(verify).collect()
                 }
             })
         };
@@ -2339,6 +2435,8 @@ impl ClusterInfo {
 #[derive(Debug)]
 pub struct Sockets {
     pub gossip: UdpSocket,
+    /// Only advertised in the contact-info if gossip over QUIC is enabled
+    pub gossip_quic: UdpSocket,
     pub ip_echo: Option<TcpListener>,
     pub tvu: Vec<UdpSocket>,
     pub tvu_quic: UdpSocket,
@@ -2427,6 +2525,7 @@ impl Node {
         let (gossip_port, (gossip, ip_echo)) =
             bind_common_in_range_with_config(localhost_ip_addr, port_range, udp_config).unwrap();
         let gossip_addr = SocketAddr::new(localhost_ip_addr, gossip_port);
+        let gossip_quic = bind_to_localhost().unwrap();
         let tvu = bind_to_localhost().unwrap();
         let tvu_quic = bind_to_localhost().unwrap();
         let ((_tpu_forwards_port, tpu_forwards), (_tpu_forwards_quic_port, tpu_forwards_quic)) =
@@ -2521,6 +2620,7 @@ impl Node {
             info,
             sockets: Sockets {
                 gossip,
+                gossip_quic,
                 ip_echo: Some(ip_echo),
                 tvu: vec![tvu],
                 tvu_quic,
@@ -2566,6 +2666,7 @@ impl Node {
 
         let socket_config = SocketConfig::default();
         let socket_config_reuseport = SocketConfig::default().reuseport(true);
+        let (_, gossip_quic) = Self::bind_with_config(bind_ip_addr, port_range, socket_config);
         let (tvu_port, tvu) = Self::bind_with_config(bind_ip_addr, port_range, socket_config);
         let (tvu_quic_port, tvu_quic) =
             Self::bind_with_config(bind_ip_addr, port_range, socket_config);
@@ -2677,6 +2778,7 @@ impl Node {
             info,
             sockets: Sockets {
                 gossip,
+                gossip_quic,
                 ip_echo: Some(ip_echo),
                 tvu: vec![tvu],
                 tvu_quic,
@@ -2722,6 +2824,8 @@ impl Node {
         let socket_config = SocketConfig::default();
         let socket_config_reuseport = SocketConfig::default().reuseport(true);
 
+        let (_, gossip_quic) = Self::bind_with_config(bind_ip_addr, port_range, socket_config);
+
         let (tvu_port, tvu_sockets) = multi_bind_in_range_with_config(
             bind_ip_addr,
             port_range,
@@ -2853,6 +2957,7 @@ impl Node {
         trace!("new ContactInfo: {:?}", info);
         let sockets = Sockets {
             gossip,
+            gossip_quic,
             tvu: tvu_sockets,
             tvu_quic,
             tpu: tpu_sockets,
@@ -3330,6 +3435,7 @@ mod tests {
 
     fn check_node_sockets(node: &Node, ip: IpAddr, range: (u16, u16)) {
         check_socket(&node.sockets.gossip, ip, range);
+        check_socket(&node.sockets.gossip_quic, ip, range);
         check_socket(&node.sockets.repair, ip, range);
         check_socket(&node.sockets.tvu_quic, ip, range);
 
@@ -4088,6 +4194,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_push_duplicate_shred_partial_proof() {
+        let keypair = Arc::new(Keypair::new());
+        let node = Node::new_localhost_with_pubkey(&keypair.pubkey());
+        let cluster_info =
+            ClusterInfo::new(node.info, keypair.clone(), SocketAddrSpace::Unspecified);
+        let mut cursor = Cursor::default();
+        let mut rng = rand::thread_rng();
+        let shredder = Shredder::new(53084024, 53084023, 0, 0).unwrap();
+        let leader = Arc::new(Keypair::new());
+        let shred1 = new_rand_shred(&mut rng, 353, &shredder, &leader);
+        let shred2 = new_rand_shred(&mut rng, 353, &shredder, &leader);
+        cluster_info
+            .push_duplicate_shred(&shred1, shred2.payload())
+            .unwrap();
+        assert_eq!(cluster_info.get_duplicate_shreds(&mut cursor).len(), 3);
+        // A complete proof is not pushed again.
+        cluster_info
+            .push_duplicate_shred(&shred1, shred2.payload())
+            .unwrap();
+        assert!(cluster_info.get_duplicate_shreds(&mut cursor).is_empty());
+
+        // Once a chunk is lost, the whole proof is pushed again.
+        cluster_info.gossip.crds.write().unwrap().remove(
+            &CrdsValueLabel::DuplicateShred(1, keypair.pubkey()),
+            timestamp(),
+        );
+        sleep(Duration::from_millis(2));
+        cluster_info
+            .push_duplicate_shred(&shred1, shred2.payload())
+            .unwrap();
+        let entries = cluster_info.get_duplicate_shreds(&mut cursor);
+        let mut chunk_indices: Vec<_> = entries.iter().map(|dup| dup.chunk_index()).collect();
+        chunk_indices.sort_unstable();
+        assert_eq!(chunk_indices, [0, 1, 2]);
+    }
+
     #[test]
     fn test_push_restart_last_voted_fork_slots() {
         let keypair = Arc::new(Keypair::new());
@@ -150,6 +150,7 @@ pub struct GossipStats {
     pub(crate) pull_request_ping_pong_check_failed_count: Counter,
     pub(crate) purge: Counter,
     pub(crate) purge_count: Counter,
+    pub(crate) push_backlog_num_entries: Counter,
     pub(crate) push_fanout_num_entries: Counter,
     pub(crate) push_fanout_num_nodes: Counter,
     pub(crate) push_message_value_count: Counter,
@@ -453,6 +454,11 @@ pub(crate) fn submit_gossip_stats(
             crds_stats.num_duplicate_push_messages,
             i64
         ),
+        (
+            "push_backlog_num_entries",
+            stats.push_backlog_num_entries.clear(),
+            i64
+        ),
         (
             "push_fanout_num_entries",
             stats.push_fanout_num_entries.clear(),
@@ -35,6 +35,7 @@ const EMPTY_SOCKET_ADDR_CACHE: [SocketAddr; SOCKET_CACHE_SIZE] =
     [SOCKET_ADDR_UNSPECIFIED; SOCKET_CACHE_SIZE];
 
 const SOCKET_TAG_GOSSIP: u8 = 0;
+const SOCKET_TAG_GOSSIP_QUIC: u8 = 13;
 const SOCKET_TAG_RPC: u8 = 2;
 const SOCKET_TAG_RPC_PUBSUB: u8 = 3;
 const SOCKET_TAG_SERVE_REPAIR: u8 = 4;
@@ -47,8 +48,8 @@ const SOCKET_TAG_TPU_VOTE: u8 = 9;
 const SOCKET_TAG_TPU_VOTE_QUIC: u8 = 12;
 const SOCKET_TAG_TVU: u8 = 10;
 const SOCKET_TAG_TVU_QUIC: u8 = 11;
-const_assert_eq!(SOCKET_CACHE_SIZE, 13);
-const SOCKET_CACHE_SIZE: usize = SOCKET_TAG_TPU_VOTE_QUIC as usize + 1usize;
+const_assert_eq!(SOCKET_CACHE_SIZE, 14);
+const SOCKET_CACHE_SIZE: usize = SOCKET_TAG_GOSSIP_QUIC as usize + 1usize;
 
 // An alias for a function that reads data from a ContactInfo entry stored in
 // the gossip CRDS table.
@@ -241,7 +242,7 @@ impl ContactInfo {
     }
 
     #[inline]
-    pub(crate) fn version(&self) -> &solana_version::Version {
+    pub fn version(&self) -> &solana_version::Version {
         &self.version
     }
 
@@ -261,6 +262,7 @@ impl ContactInfo {
     }
 
     get_socket!(gossip, SOCKET_TAG_GOSSIP);
+    get_socket!(gossip_quic, SOCKET_TAG_GOSSIP_QUIC);
     get_socket!(rpc, SOCKET_TAG_RPC);
     get_socket!(rpc_pubsub, SOCKET_TAG_RPC_PUBSUB);
     get_socket!(
@@ -278,6 +280,7 @@ impl ContactInfo {
     get_socket!(tvu, SOCKET_TAG_TVU, SOCKET_TAG_TVU_QUIC);
 
     set_socket!(set_gossip, SOCKET_TAG_GOSSIP);
+    set_socket!(set_gossip_quic, SOCKET_TAG_GOSSIP_QUIC);
     set_socket!(set_rpc, SOCKET_TAG_RPC);
     set_socket!(set_rpc_pubsub, SOCKET_TAG_RPC_PUBSUB);
     set_socket!(set_tpu, SOCKET_TAG_TPU, SOCKET_TAG_TPU_QUIC);
@@ -290,6 +293,7 @@ impl ContactInfo {
     set_socket!(@multi set_tpu_vote, SOCKET_TAG_TPU_VOTE, SOCKET_TAG_TPU_VOTE_QUIC);
     set_socket!(@multi set_tvu, SOCKET_TAG_TVU, SOCKET_TAG_TVU_QUIC);
 
+    remove_socket!(remove_gossip_quic, SOCKET_TAG_GOSSIP_QUIC);
     remove_socket!(
         remove_serve_repair,
         SOCKET_TAG_SERVE_REPAIR,
@@ -871,6 +875,10 @@ mod tests {
                 }
             }
             assert_eq!(node.gossip().as_ref(), sockets.get(&SOCKET_TAG_GOSSIP));
+            assert_eq!(
+                node.gossip_quic().as_ref(),
+                sockets.get(&SOCKET_TAG_GOSSIP_QUIC)
+            );
             assert_eq!(node.rpc().as_ref(), sockets.get(&SOCKET_TAG_RPC));
             assert_eq!(
                 node.rpc_pubsub().as_ref(),
@@ -138,7 +138,12 @@ pub struct VersionedCrdsValue {
 pub struct Cursor(u64);
 
 impl Cursor {
-    fn ordinal(&self) -> u64 {
+    /// Returns a cursor positioned at the value with the given ordinal.
+    pub(crate) fn new(ordinal: u64) -> Self {
+        Self(ordinal)
+    }
+
+    pub(crate) fn ordinal(&self) -> u64 {
         self.0
     }
 
@@ -165,6 +170,11 @@ impl VersionedCrdsValue {
             num_push_recv,
         }
     }
+
+    /// Ordinal index indicating insert order.
+    pub(crate) fn ordinal(&self) -> u64 {
+        self.ordinal
+    }
 }
 
 impl Default for Crds {
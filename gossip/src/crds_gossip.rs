@@ -15,7 +15,8 @@ use {
             CrdsFilter, CrdsGossipPull, CrdsTimeouts, ProcessPullStats, PullRequest,
         },
         crds_gossip_push::CrdsGossipPush,
-        crds_value::CrdsValue,
+        crds_priority::CrdsPriorityWeights,
+        crds_value::{CrdsValue, CrdsValueLabel},
         duplicate_shred::{self, DuplicateShredIndex, MAX_DUPLICATE_SHREDS},
         protocol::{Ping, PingCache},
     },
@@ -43,6 +44,9 @@ pub struct CrdsGossip {
     pub crds: RwLock<Crds>,
     pub push: CrdsGossipPush,
     pub pull: CrdsGossipPull,
+    /// Order in which outgoing values are sent when push or pull capacity is
+    /// saturated
+    pub priority_weights: CrdsPriorityWeights,
 }
 
 impl CrdsGossip {
@@ -82,8 +86,14 @@ impl CrdsGossip {
         HashMap<Pubkey, Vec</*index:*/ usize>>,
         usize, // number of push messages
     ) {
-        self.push
-            .new_push_messages(pubkey, &self.crds, now, stakes, should_retain_crds_value)
+        self.push.new_push_messages(
+            pubkey,
+            &self.crds,
+            now,
+            stakes,
+            &self.priority_weights,
+            should_retain_crds_value,
+        )
     }
 
     pub(crate) fn push_duplicate_shred<F>(
@@ -100,19 +110,29 @@ impl CrdsGossip {
         F: FnOnce(Slot) -> Option<Pubkey>,
     {
         let pubkey = keypair.pubkey();
-        // Skip if there are already records of duplicate shreds for this slot.
         let shred_slot = shred.slot();
         let mut crds = self.crds.write().unwrap();
-        if crds
-            .get_records(&pubkey)
-            .any(|value| match value.value.data() {
-                CrdsData::DuplicateShred(_, value) => value.slot == shred_slot,
-                _ => false,
-            })
-        {
+        // (index, num_chunks, chunk_index) of the chunks of this slot, and
+        // (wallclock, index) of the chunks of the other slots.
+        let mut slot_chunks = Vec::new();
+        let mut other_chunks = Vec::new();
+        for value in crds.get_records(&pubkey) {
+            if let CrdsData::DuplicateShred(ix, value) = value.value.data() {
+                if value.slot == shred_slot {
+                    slot_chunks.push((*ix, value.num_chunks(), value.chunk_index()));
+                } else {
+                    other_chunks.push((value.wallclock, *ix));
+                }
+            }
+        }
+        // Skip if there is already a complete proof for this slot. A partial
+        // proof, with some of its chunks overwritten by the proofs of other
+        // slots or failed to insert, can never be reassembled by the other
+        // nodes, so the proof is pushed again in full.
+        if is_complete_proof(&slot_chunks) {
             return Ok(());
         }
-        let chunks = duplicate_shred::from_shred(
+        let chunks: Vec<_> = duplicate_shred::from_shred(
             shred.clone(),
             pubkey,
             Vec::from(other_payload),
@@ -120,37 +140,39 @@ impl CrdsGossip {
             timestamp(),
             max_payload_size,
             shred_version,
-        )?;
-        // Find the index of oldest duplicate shred.
-        let mut num_dup_shreds = 0;
-        let offset = crds
-            .get_records(&pubkey)
-            .filter_map(|value| match value.value.data() {
-                CrdsData::DuplicateShred(ix, value) => {
-                    num_dup_shreds += 1;
-                    Some((value.wallclock, *ix))
-                }
-                _ => None,
-            })
-            .min() // Override the oldest records.
-            .map(|(_ /*wallclock*/, ix)| ix)
-            .unwrap_or(0);
-        let offset = if num_dup_shreds < MAX_DUPLICATE_SHREDS {
-            num_dup_shreds
-        } else {
-            offset
-        };
-        let entries = chunks.enumerate().map(|(k, chunk)| {
-            let index = (offset + k as DuplicateShredIndex) % MAX_DUPLICATE_SHREDS;
-            let data = CrdsData::DuplicateShred(index, chunk);
-            CrdsValue::new(data, keypair)
-        });
+        )?
+        .collect();
+        // Overwrite the partial proof of this slot first, then fill the unused
+        // indices, and only then override the oldest proofs of other slots.
+        let used_indices: HashSet<DuplicateShredIndex> = slot_chunks
+            .iter()
+            .map(|(ix, _, _)| *ix)
+            .chain(other_chunks.iter().map(|(_, ix)| *ix))
+            .collect();
+        other_chunks.sort_unstable();
+        let indices = slot_chunks
+            .iter()
+            .map(|(ix, _, _)| *ix)
+            .chain((0..MAX_DUPLICATE_SHREDS).filter(|ix| !used_indices.contains(ix)))
+            .chain(other_chunks.iter().map(|(_, ix)| *ix));
+        let num_chunks = chunks.len();
         let now = timestamp();
-        for entry in entries {
+        let mut num_failed = 0;
+        for (index, chunk) in indices.zip(chunks) {
+            let entry = CrdsValue::new(CrdsData::DuplicateShred(index, chunk), keypair);
             if let Err(err) = crds.insert(entry, now, GossipRoute::LocalMessage) {
                 error!("push_duplicate_shred failed: {:?}", err);
+                num_failed += 1;
             }
         }
+        // Remove the leftover chunks of a partial proof of this slot which had
+        // more chunks, so that they are not mixed with the new ones.
+        for (ix, _, _) in slot_chunks.iter().skip(num_chunks) {
+            crds.remove(&CrdsValueLabel::DuplicateShred(*ix, pubkey), now);
+        }
+        if num_failed > 0 {
+            return Err(duplicate_shred::Error::ChunkInsertFailed(num_failed));
+        }
         Ok(())
     }
 
@@ -409,6 +431,21 @@ pub(crate) fn maybe_ping_gossip_addresses<R: Rng + CryptoRng>(
         .collect()
 }
 
+// Returns true if the (index, num_chunks, chunk_index) of the chunks of a
+// slot make up all the chunks of a single proof.
+fn is_complete_proof(chunks: &[(DuplicateShredIndex, u8, u8)]) -> bool {
+    let Some(&(_, num_chunks, _)) = chunks.first() else {
+        return false;
+    };
+    chunks.iter().all(|&(_, n, _)| n == num_chunks)
+        && chunks
+            .iter()
+            .map(|&(_, _, chunk_index)| chunk_index)
+            .unique()
+            .count()
+            == usize::from(num_chunks)
+}
+
 #[cfg(test)]
 mod test {
     use {super::*, solana_sha256_hasher::hash, solana_time_utils::timestamp};
@@ -15,7 +15,9 @@ use {
     crate::{
         cluster_info::CRDS_UNIQUE_PUBKEY_CAPACITY,
         crds::{Crds, CrdsError, Cursor, GossipRoute},
+        crds_data::CrdsData,
         crds_gossip,
+        crds_priority::CrdsPriorityWeights,
         crds_value::CrdsValue,
         protocol::{Ping, PingCache},
         push_active_set::PushActiveSet,
@@ -28,6 +30,7 @@ use {
     solana_streamer::socket::SocketAddrSpace,
     solana_time_utils::timestamp,
     std::{
+        cmp::Reverse,
         collections::{HashMap, HashSet},
         iter::repeat,
         net::SocketAddr,
@@ -48,12 +51,26 @@ const CRDS_GOSSIP_PRUNE_MSG_TIMEOUT_MS: u64 = 500;
 const CRDS_GOSSIP_PRUNE_STAKE_THRESHOLD_PCT: f64 = 0.15;
 const CRDS_GOSSIP_PRUNE_MIN_INGRESS_NODES: usize = 2;
 const CRDS_GOSSIP_PUSH_ACTIVE_SET_SIZE: usize = CRDS_GOSSIP_PUSH_FANOUT + 3;
+// Votes from nodes with at least this share of the epoch stake are pushed to
+// the whole active set, so that they reach the leaders in fewer hops.
+const CRDS_GOSSIP_PUSH_HIGH_STAKE_THRESHOLD_PCT: f64 = 0.001;
+const CRDS_GOSSIP_PUSH_HIGH_STAKE_VOTE_FANOUT: usize = CRDS_GOSSIP_PUSH_ACTIVE_SET_SIZE;
+// Values from unstaked nodes are pushed to fewer peers and otherwise rely on
+// pull requests, which offsets the egress of the larger vote fanout.
+const CRDS_GOSSIP_PUSH_UNSTAKED_FANOUT: usize = CRDS_GOSSIP_PUSH_FANOUT - 3;
+// Maximum number of push messages generated in one round.
+const MAX_NUM_PUSHES: usize = 1 << 12;
+// Maximum number of values deferred to the next round once MAX_NUM_PUSHES is
+// reached. Any further values are only propagated through pull requests.
+const MAX_PUSH_BACKLOG: usize = 1 << 13;
 
 pub struct CrdsGossipPush {
     /// Active set of validators for push
     active_set: RwLock<PushActiveSet>,
     /// Cursor into the crds table for values to push.
     crds_cursor: Mutex<Cursor>,
+    /// Values which did not fit in previous rounds of push messages.
+    backlog: Mutex<Vec<CrdsValue>>,
     /// Cache that tracks which validators a message was received from
     /// This cache represents a lagging view of which validators
     /// currently have this node in their `active_set`
@@ -71,6 +88,7 @@ impl Default for CrdsGossipPush {
         Self {
             active_set: RwLock::default(),
             crds_cursor: Mutex::default(),
+            backlog: Mutex::default(),
             received_cache: Mutex::new(ReceivedCache::new(2 * CRDS_UNIQUE_PUBKEY_CAPACITY)),
             push_fanout: CRDS_GOSSIP_PUSH_FANOUT,
             msg_timeout: CRDS_GOSSIP_PUSH_MSG_TIMEOUT_MS,
@@ -87,6 +105,12 @@ impl CrdsGossipPush {
         crds.read().unwrap().get_entries(&mut cursor).count()
     }
 
+    /// Returns the number of values deferred to the next round of push
+    /// messages.
+    pub(crate) fn backlog_len(&self) -> usize {
+        self.backlog.lock().unwrap().len()
+    }
+
     pub(crate) fn prune_received_cache<I>(
         &self,
         self_pubkey: &Pubkey,
@@ -113,6 +137,29 @@ impl CrdsGossipPush {
             .into_group_map()
     }
 
+    /// Returns the number of peers the value is pushed to. Votes from nodes
+    /// with high stake are pushed to more peers and values from unstaked
+    /// nodes to fewer. This node's own values always use the default fanout.
+    fn push_fanout(
+        &self,
+        pubkey: &Pubkey, // This node.
+        value: &CrdsValue,
+        stakes: &HashMap<Pubkey, u64>,
+        min_high_stake: u64,
+    ) -> usize {
+        let origin = value.pubkey();
+        if stakes.is_empty() || &origin == pubkey {
+            return self.push_fanout;
+        }
+        match stakes.get(&origin).copied().unwrap_or_default() {
+            0 => CRDS_GOSSIP_PUSH_UNSTAKED_FANOUT,
+            stake if stake >= min_high_stake && matches!(value.data(), CrdsData::Vote(_, _)) => {
+                CRDS_GOSSIP_PUSH_HIGH_STAKE_VOTE_FANOUT
+            }
+            _ => self.push_fanout,
+        }
+    }
+
     fn wallclock_window(&self, now: u64) -> impl RangeBounds<u64> {
         now.saturating_sub(self.msg_timeout)..=now.saturating_add(self.msg_timeout)
     }
@@ -162,12 +209,17 @@ impl CrdsGossipPush {
     /// peers.
     /// The list of push messages is created such that all the randomly selected peers have not
     /// pruned the source addresses.
+    /// Values are pushed in the order of their priority. Values which do not
+    /// fit in this round are deferred to the next one.
+    /// The number of peers each value is pushed to depends on the stake of
+    /// its origin; see `push_fanout`.
     pub(crate) fn new_push_messages(
         &self,
         pubkey: &Pubkey, // This node.
         crds: &RwLock<Crds>,
         now: u64,
         stakes: &HashMap<Pubkey, u64>,
+        priority_weights: &CrdsPriorityWeights,
         // Predicate returning false if the CRDS value should be discarded.
         should_retain_crds_value: impl Fn(&CrdsValue) -> bool,
     ) -> (
@@ -176,22 +228,39 @@ impl CrdsGossipPush {
         HashMap<Pubkey, Vec</*index:*/ usize>>,
         usize, // number of push messages
     ) {
-        const MAX_NUM_PUSHES: usize = 1 << 12;
         let mut num_pushes = 0;
         let mut values = Vec::new();
         let mut push_messages = HashMap::<Pubkey, Vec</*index:*/ usize>>::new();
         let wallclock_window = self.wallclock_window(now);
+        let total_stake: u64 = stakes.values().sum();
+        let min_high_stake =
+            (total_stake as f64 * CRDS_GOSSIP_PUSH_HIGH_STAKE_THRESHOLD_PCT) as u64;
         let active_set = self.active_set.read().unwrap();
         let mut crds_cursor = self.crds_cursor.lock().unwrap();
-        // crds should be locked last after self.{active_set,crds_cursor}.
+        let mut backlog = self.backlog.lock().unwrap();
+        // crds should be locked last after self.{active_set,crds_cursor,backlog}.
         let crds = crds.read().unwrap();
-        let entries = crds
-            .get_entries(crds_cursor.deref_mut())
-            .map(|entry| &entry.value)
+        // Deferred values which have since been overwritten are dropped.
+        let mut entries: Vec<CrdsValue> = std::mem::take(&mut *backlog)
+            .into_iter()
+            .filter(|value| {
+                crds.get::<&CrdsValue>(&value.label())
+                    .is_some_and(|entry| entry.hash() == value.hash())
+            })
+            .chain(
+                crds.get_entries(crds_cursor.deref_mut())
+                    .map(|entry| entry.value.clone()),
+            )
             .filter(|value| wallclock_window.contains(&value.wallclock()))
-            .filter(|value| should_retain_crds_value(value));
-        'outer: for value in entries {
+            .filter(|value| should_retain_crds_value(value))
+            .collect();
+        // The sort is stable, so deferred values go first among values of the
+        // same priority.
+        entries.sort_by_cached_key(|value| Reverse(priority_weights.priority(value, stakes)));
+        let mut entries = entries.into_iter();
+        'outer: for value in entries.by_ref() {
             let origin = value.pubkey();
+            let push_fanout = self.push_fanout(pubkey, &value, stakes, min_high_stake);
             let mut nodes = active_set
                 .get_nodes(
                     pubkey,
@@ -199,7 +268,7 @@ impl CrdsGossipPush {
                     |node| value.should_force_push(node),
                     stakes,
                 )
-                .take(self.push_fanout)
+                .take(push_fanout)
                 .peekable();
             let index = values.len();
             if nodes.peek().is_some() {
@@ -213,7 +282,9 @@ impl CrdsGossipPush {
                 }
             }
         }
+        backlog.extend(entries.take(MAX_PUSH_BACKLOG));
         drop(crds);
+        drop(backlog);
         drop(crds_cursor);
         drop(active_set);
         self.num_pushes.fetch_add(num_pushes, Ordering::Relaxed);
@@ -318,6 +389,7 @@ mod tests {
                 crds,
                 now,
                 stakes,
+                &CrdsPriorityWeights::default(),
                 |_| true, // should_retain_crds_value
             );
             messages
@@ -649,4 +721,126 @@ mod tests {
             .process_push_message(&crds, vec![(Pubkey::default(), vec![value])], 0)
             .is_empty());
     }
+
+    #[test]
+    fn test_push_messages_priority() {
+        let mut rng = rand::thread_rng();
+        let vote = std::iter::repeat_with(|| CrdsValue::new_rand(&mut rng, None))
+            .find(|value| matches!(value.data(), CrdsData::Vote(_, _)))
+            .unwrap();
+        let now = vote.wallclock();
+        let push = CrdsGossipPush::default();
+        let mut ping_cache = new_ping_cache();
+        let peer = ContactInfo::new_localhost(&solana_pubkey::new_rand(), 0);
+        ping_cache.mock_pong(*peer.pubkey(), peer.gossip().unwrap(), Instant::now());
+        let mut crds = Crds::default();
+        crds.insert(
+            CrdsValue::new_unsigned(CrdsData::from(peer)),
+            now,
+            GossipRoute::LocalMessage,
+        )
+        .unwrap();
+        let crds = RwLock::new(crds);
+        push.refresh_push_active_set(
+            &crds,
+            &HashMap::new(), // stakes
+            None,            // gossip_validtors
+            &Keypair::new(),
+            0, // self_shred_version
+            &Mutex::new(ping_cache),
+            &mut Vec::new(), // pings
+            &SocketAddrSpace::Unspecified,
+        );
+
+        // Saturate push with contact-infos inserted ahead of the vote.
+        let contact_infos: Vec<_> = std::iter::repeat_with(|| {
+            CrdsValue::new_unsigned(CrdsData::from(ContactInfo::new_localhost(
+                &solana_pubkey::new_rand(),
+                now,
+            )))
+        })
+        .take(MAX_NUM_PUSHES)
+        .collect();
+        {
+            let mut crds = crds.write().unwrap();
+            for value in contact_infos.iter().chain([&vote]) {
+                crds.insert(value.clone(), now, GossipRoute::LocalMessage)
+                    .unwrap();
+            }
+        }
+        let (values, _, num_pushes) = push.new_push_messages(
+            &Pubkey::default(),
+            &crds,
+            now,
+            &HashMap::new(), // stakes
+            &CrdsPriorityWeights::default(),
+            |_| true, // should_retain_crds_value
+        );
+        assert_eq!(num_pushes, MAX_NUM_PUSHES);
+        assert_eq!(values[0], vote);
+        assert_eq!(push.backlog_len(), 1);
+
+        // The deferred contact-info goes out in the next round.
+        let (values, _, num_pushes) = push.new_push_messages(
+            &Pubkey::default(),
+            &crds,
+            now,
+            &HashMap::new(), // stakes
+            &CrdsPriorityWeights::default(),
+            |_| true, // should_retain_crds_value
+        );
+        assert_eq!(num_pushes, 1);
+        assert_eq!(values, vec![contact_infos[MAX_NUM_PUSHES - 1].clone()]);
+        assert_eq!(push.backlog_len(), 0);
+    }
+
+    #[test]
+    fn test_push_fanout() {
+        let mut rng = rand::thread_rng();
+        let push = CrdsGossipPush::default();
+        let pubkey = solana_pubkey::new_rand();
+        let keypairs: Vec<_> = std::iter::repeat_with(Keypair::new).take(3).collect();
+        let mut new_value = |keypair: &Keypair, is_vote: bool| {
+            std::iter::repeat_with(|| CrdsValue::new_rand(&mut rng, Some(keypair)))
+                .find(|value| matches!(value.data(), CrdsData::Vote(_, _)) == is_vote)
+                .unwrap()
+        };
+        let high_stake_vote = new_value(&keypairs[0], true);
+        let high_stake_value = new_value(&keypairs[0], false);
+        let low_stake_vote = new_value(&keypairs[1], true);
+        let unstaked_vote = new_value(&keypairs[2], true);
+        let stakes = HashMap::from([
+            (pubkey, 1),
+            (keypairs[0].pubkey(), 1_000),
+            (keypairs[1].pubkey(), 1),
+        ]);
+        let min_high_stake = 100;
+        assert_eq!(
+            push.push_fanout(&pubkey, &high_stake_vote, &stakes, min_high_stake),
+            CRDS_GOSSIP_PUSH_HIGH_STAKE_VOTE_FANOUT
+        );
+        assert_eq!(
+            push.push_fanout(&pubkey, &high_stake_value, &stakes, min_high_stake),
+            CRDS_GOSSIP_PUSH_FANOUT
+        );
+        assert_eq!(
+            push.push_fanout(&pubkey, &low_stake_vote, &stakes, min_high_stake),
+            CRDS_GOSSIP_PUSH_FANOUT
+        );
+        assert_eq!(
+            push.push_fanout(&pubkey, &unstaked_vote, &stakes, min_high_stake),
+            CRDS_GOSSIP_PUSH_UNSTAKED_FANOUT
+        );
+        // This node's own values and values pushed while the stakes are
+        // unknown use the default fanout.
+        let origin = keypairs[2].pubkey();
+        assert_eq!(
+            push.push_fanout(&origin, &unstaked_vote, &stakes, min_high_stake),
+            CRDS_GOSSIP_PUSH_FANOUT
+        );
+        assert_eq!(
+            push.push_fanout(&pubkey, &unstaked_vote, &HashMap::new(), min_high_stake),
+            CRDS_GOSSIP_PUSH_FANOUT
+        );
+    }
 }
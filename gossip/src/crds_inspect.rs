@@ -0,0 +1,316 @@
+//! Read-only views of the CRDS table for operators debugging gossip, e.g.
+//! partitions or nodes missing from the table, without attaching a debugger.
+//!
+//! Values are returned in insertion order, one page at a time. A value which
+//! is updated in between two pages moves to the end of the table and may be
+//! returned again.
+
+use {
+    crate::{
+        crds::{Crds, Cursor, VersionedCrdsValue},
+        crds_data::CrdsData,
+    },
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    std::{collections::BTreeMap, fmt, str::FromStr},
+    thiserror::Error,
+};
+
+/// Number of values returned in a single page unless specified otherwise
+pub const DEFAULT_CRDS_INSPECT_LIMIT: usize = 100;
+/// Maximum number of values returned in a single page
+pub const MAX_CRDS_INSPECT_LIMIT: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CrdsValueType {
+    ContactInfo,
+    Vote,
+    LowestSlot,
+    EpochSlots,
+    DuplicateShred,
+    SnapshotHashes,
+    Version,
+    NodeInstance,
+    /// Values used during a cluster restart
+    Restart,
+    /// Deprecated types
+    Other,
+}
+
+impl CrdsValueType {
+    pub const NAMES: [&'static str; 10] = [
+        "contact-info",
+        "vote",
+        "lowest-slot",
+        "epoch-slots",
+        "duplicate-shred",
+        "snapshot-hashes",
+        "version",
+        "node-instance",
+        "restart",
+        "other",
+    ];
+
+    fn new(data: &CrdsData) -> Self {
+        match data {
+            CrdsData::ContactInfo(_) | CrdsData::LegacyContactInfo(_) => Self::ContactInfo,
+            CrdsData::Vote(_, _) => Self::Vote,
+            CrdsData::LowestSlot(_, _) => Self::LowestSlot,
+            CrdsData::EpochSlots(_, _) => Self::EpochSlots,
+            CrdsData::DuplicateShred(_, _) => Self::DuplicateShred,
+            CrdsData::SnapshotHashes(_) => Self::SnapshotHashes,
+            CrdsData::Version(_) | CrdsData::LegacyVersion(_) => Self::Version,
+            CrdsData::NodeInstance(_) => Self::NodeInstance,
+            CrdsData::RestartLastVotedForkSlots(_) | CrdsData::RestartHeaviestFork(_) => {
+                Self::Restart
+            }
+            CrdsData::LegacySnapshotHashes(_) | CrdsData::AccountsHashes(_) => Self::Other,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[*self as usize]
+    }
+}
+
+impl fmt::Display for CrdsValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error(
+    "unknown CRDS value type {0}, expected one of {names}",
+    names = CrdsValueType::NAMES.join(", ")
+)]
+pub struct ParseCrdsValueTypeError(String);
+
+impl FromStr for CrdsValueType {
+    type Err = ParseCrdsValueTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const TYPES: [CrdsValueType; 10] = [
+            CrdsValueType::ContactInfo,
+            CrdsValueType::Vote,
+            CrdsValueType::LowestSlot,
+            CrdsValueType::EpochSlots,
+            CrdsValueType::DuplicateShred,
+            CrdsValueType::SnapshotHashes,
+            CrdsValueType::Version,
+            CrdsValueType::NodeInstance,
+            CrdsValueType::Restart,
+            CrdsValueType::Other,
+        ];
+        TYPES
+            .into_iter()
+            .find(|value_type| value_type.name() == s)
+            .ok_or_else(|| ParseCrdsValueTypeError(s.to_string()))
+    }
+}
+
+/// Selects the values of a page
+#[derive(Clone, Debug, Default)]
+pub struct CrdsInspectFilter {
+    /// Only return values of these types; all types if empty
+    pub types: Vec<CrdsValueType>,
+    /// Only return values which originate from this node
+    pub origin: Option<Pubkey>,
+    /// Start at the value with this ordinal; the `next_cursor` of the
+    /// previous page
+    pub cursor: u64,
+    /// Maximum number of values to return, capped at MAX_CRDS_INSPECT_LIMIT
+    pub limit: usize,
+}
+
+impl CrdsInspectFilter {
+    fn matches(&self, value: &VersionedCrdsValue) -> bool {
+        (self.types.is_empty() || self.types.contains(&CrdsValueType::new(value.value.data())))
+            && self
+                .origin
+                .is_none_or(|origin| value.value.pubkey() == origin)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrdsValueInfo {
+    /// Position of the value in insertion order
+    pub ordinal: u64,
+    pub origin: Pubkey,
+    pub value_type: CrdsValueType,
+    pub hash: Hash,
+    /// The wallclock of the origin when it created the value
+    pub wallclock: u64,
+    /// Local time at which the value was inserted or last updated
+    pub local_timestamp: u64,
+    pub num_bytes: usize,
+    /// A short description of the contents, e.g. the slot of a vote
+    pub summary: String,
+}
+
+impl CrdsValueInfo {
+    fn new(value: &VersionedCrdsValue) -> Self {
+        let data = value.value.data();
+        let summary = match data {
+            CrdsData::ContactInfo(node) => format!(
+                "gossip {}, shred version {}",
+                node.gossip()
+                    .map(|addr| addr.to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+                node.shred_version()
+            ),
+            CrdsData::Vote(index, vote) => match vote.slot() {
+                Some(slot) => format!("index {index}, slot {slot}"),
+                None => format!("index {index}"),
+            },
+            CrdsData::LowestSlot(_, lowest_slot) => format!("lowest {}", lowest_slot.lowest),
+            CrdsData::EpochSlots(index, epoch_slots) => match epoch_slots.first_slot() {
+                Some(slot) => format!("index {index}, first slot {slot}"),
+                None => format!("index {index}"),
+            },
+            CrdsData::DuplicateShred(index, shred) => {
+                format!(
+                    "index {index}, slot {}, chunk {}",
+                    shred.slot,
+                    shred.chunk_index()
+                )
+            }
+            CrdsData::SnapshotHashes(hashes) => format!("full {}", hashes.full.0),
+            CrdsData::RestartLastVotedForkSlots(slots) => {
+                format!("last voted slot {}", slots.last_voted_slot)
+            }
+            CrdsData::RestartHeaviestFork(fork) => format!(
+                "last slot {}, observed stake {}",
+                fork.last_slot, fork.observed_stake
+            ),
+            _ => String::default(),
+        };
+        Self {
+            ordinal: value.ordinal(),
+            origin: value.value.pubkey(),
+            value_type: CrdsValueType::new(data),
+            hash: *value.value.hash(),
+            wallclock: value.value.wallclock(),
+            local_timestamp: value.local_timestamp,
+            num_bytes: value.value.bincode_serialized_size(),
+            summary,
+        }
+    }
+
+    /// Milliseconds since the value was inserted or last updated
+    pub fn age(&self, now: u64) -> u64 {
+        now.saturating_sub(self.local_timestamp)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CrdsInspectPage {
+    pub values: Vec<CrdsValueInfo>,
+    /// Cursor of the next page; equal to the requested cursor if there are
+    /// no more values
+    pub next_cursor: u64,
+    /// Number of values of each type in the table, regardless of the filter
+    pub num_values_by_type: BTreeMap<CrdsValueType, usize>,
+    /// Number of values in the table
+    pub num_values: usize,
+    /// Number of nodes with values in the table
+    pub num_origins: usize,
+}
+
+pub(crate) fn inspect_crds(crds: &Crds, filter: &CrdsInspectFilter) -> CrdsInspectPage {
+    let limit = filter.limit.min(MAX_CRDS_INSPECT_LIMIT);
+    let mut cursor = Cursor::new(filter.cursor);
+    let values = crds
+        .get_entries(&mut cursor)
+        .filter(|value| filter.matches(value))
+        .take(limit)
+        .map(CrdsValueInfo::new)
+        .collect();
+    let mut num_values_by_type = BTreeMap::<CrdsValueType, usize>::new();
+    for value in crds.values() {
+        *num_values_by_type
+            .entry(CrdsValueType::new(value.value.data()))
+            .or_default() += 1;
+    }
+    CrdsInspectPage {
+        values,
+        next_cursor: cursor.ordinal(),
+        num_values_by_type,
+        num_values: crds.len(),
+        num_origins: crds.num_pubkeys(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{contact_info::ContactInfo, crds::GossipRoute, crds_value::CrdsValue},
+        solana_keypair::Keypair,
+        solana_signer::Signer,
+        solana_time_utils::timestamp,
+    };
+
+    #[test]
+    fn test_parse_crds_value_type() {
+        for name in CrdsValueType::NAMES {
+            assert_eq!(name.parse::<CrdsValueType>().unwrap().name(), name);
+        }
+        assert!("votes".parse::<CrdsValueType>().is_err());
+    }
+
+    #[test]
+    fn test_inspect_crds() {
+        let mut rng = rand::thread_rng();
+        let mut crds = Crds::default();
+        let keypairs: Vec<_> = std::iter::repeat_with(Keypair::new).take(4).collect();
+        let now = timestamp();
+        for keypair in &keypairs {
+            let node = ContactInfo::new_localhost(&keypair.pubkey(), now);
+            let value = CrdsValue::new(CrdsData::from(node), keypair);
+            crds.insert(value, now, GossipRoute::LocalMessage).unwrap();
+            for _ in 0..8 {
+                let value = CrdsValue::new_rand(&mut rng, Some(keypair));
+                let _ = crds.insert(value, now, GossipRoute::LocalMessage);
+            }
+        }
+
+        // Page through the contact-infos.
+        let mut filter = CrdsInspectFilter {
+            types: vec![CrdsValueType::ContactInfo],
+            limit: 3,
+            ..CrdsInspectFilter::default()
+        };
+        let page = inspect_crds(&crds, &filter);
+        assert_eq!(page.values.len(), 3);
+        assert_eq!(page.num_values, crds.len());
+        assert_eq!(page.num_origins, keypairs.len());
+        assert_eq!(page.num_values_by_type.values().sum::<usize>(), crds.len());
+        assert!(page.num_values_by_type[&CrdsValueType::ContactInfo] >= keypairs.len());
+        filter.cursor = page.next_cursor;
+        let next_page = inspect_crds(&crds, &filter);
+        assert!(!next_page.values.is_empty());
+        assert!(next_page
+            .values
+            .iter()
+            .all(|value| value.ordinal >= page.next_cursor));
+        assert!(page
+            .values
+            .iter()
+            .chain(&next_page.values)
+            .all(|value| value.value_type == CrdsValueType::ContactInfo && value.age(now) == 0));
+
+        // Filter by origin.
+        let origin = keypairs[1].pubkey();
+        let page = inspect_crds(
+            &crds,
+            &CrdsInspectFilter {
+                origin: Some(origin),
+                limit: MAX_CRDS_INSPECT_LIMIT,
+                ..CrdsInspectFilter::default()
+            },
+        );
+        assert_eq!(page.values.len(), crds.get_records(&origin).count());
+        assert!(page.values.iter().all(|value| value.origin == origin));
+    }
+}
@@ -0,0 +1,183 @@
+//! Relative priorities of outgoing CRDS values.
+//!
+//! When push or pull capacity is saturated, not every value can go out in
+//! the same round. Values are then ordered by the configured weight of their
+//! type, and by the stake of their origin among values of the same weight, so
+//! that votes and duplicate shred proofs are not crowded out by contact-info
+//! churn.
+
+use {
+    crate::{crds_data::CrdsData, crds_value::CrdsValue},
+    solana_pubkey::Pubkey,
+    std::{collections::HashMap, str::FromStr},
+    thiserror::Error,
+};
+
+/// Relative weights of the types of CRDS values
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrdsPriorityWeights {
+    pub contact_info: u64,
+    pub vote: u64,
+    pub duplicate_shred: u64,
+    /// EpochSlots and LowestSlot
+    pub epoch_slots: u64,
+    /// Values used during a cluster restart
+    pub restart: u64,
+    /// Snapshot hashes, versions, node instances and deprecated types
+    pub other: u64,
+}
+
+impl Default for CrdsPriorityWeights {
+    fn default() -> Self {
+        Self {
+            contact_info: 2,
+            vote: 4,
+            duplicate_shred: 8,
+            epoch_slots: 1,
+            restart: 4,
+            other: 1,
+        }
+    }
+}
+
+impl CrdsPriorityWeights {
+    const NAMES: [&'static str; 6] = [
+        "contact-info",
+        "vote",
+        "duplicate-shred",
+        "epoch-slots",
+        "restart",
+        "other",
+    ];
+
+    fn weight_mut(&mut self, name: &str) -> Option<&mut u64> {
+        match name {
+            "contact-info" => Some(&mut self.contact_info),
+            "vote" => Some(&mut self.vote),
+            "duplicate-shred" => Some(&mut self.duplicate_shred),
+            "epoch-slots" => Some(&mut self.epoch_slots),
+            "restart" => Some(&mut self.restart),
+            "other" => Some(&mut self.other),
+            _ => None,
+        }
+    }
+
+    /// Returns the configured weight of the type of `value`
+    pub fn weight(&self, value: &CrdsValue) -> u64 {
+        match value.data() {
+            CrdsData::ContactInfo(_) | CrdsData::LegacyContactInfo(_) => self.contact_info,
+            CrdsData::Vote(_, _) => self.vote,
+            CrdsData::DuplicateShred(_, _) => self.duplicate_shred,
+            CrdsData::EpochSlots(_, _) | CrdsData::LowestSlot(_, _) => self.epoch_slots,
+            CrdsData::RestartLastVotedForkSlots(_) | CrdsData::RestartHeaviestFork(_) => {
+                self.restart
+            }
+            CrdsData::LegacySnapshotHashes(_)
+            | CrdsData::AccountsHashes(_)
+            | CrdsData::LegacyVersion(_)
+            | CrdsData::Version(_)
+            | CrdsData::NodeInstance(_)
+            | CrdsData::SnapshotHashes(_) => self.other,
+        }
+    }
+
+    /// Returns the priority of `value`; values with a greater priority are
+    /// sent out first.
+    pub fn priority(&self, value: &CrdsValue, stakes: &HashMap<Pubkey, u64>) -> (u64, u64) {
+        let stake = stakes.get(&value.pubkey()).copied().unwrap_or_default();
+        (self.weight(value), stake)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{0}")]
+pub struct ParseCrdsPriorityWeightsError(String);
+
+/// Parses a comma separated list of `TYPE=WEIGHT` pairs, e.g.
+/// `vote=8,contact-info=1`. Types which are not listed keep their default
+/// weight.
+impl FromStr for CrdsPriorityWeights {
+    type Err = ParseCrdsPriorityWeightsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = Self::default();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let error = |reason: &str| ParseCrdsPriorityWeightsError(format!("{pair}: {reason}"));
+            let (name, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| error("expected TYPE=WEIGHT"))?;
+            let weight: u64 = weight.trim().parse().map_err(|_| error("invalid weight"))?;
+            if weight == 0 {
+                return Err(error("weights must be positive"));
+            }
+            *weights.weight_mut(name.trim()).ok_or_else(|| {
+                error(&format!(
+                    "unknown type, expected one of {}",
+                    Self::NAMES.join(", ")
+                ))
+            })? = weight;
+        }
+        Ok(weights)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::contact_info::ContactInfo,
+        rand::Rng,
+        solana_keypair::Keypair,
+        solana_signer::Signer,
+    };
+
+    fn new_rand_vote<R: Rng>(rng: &mut R, keypair: &Keypair) -> CrdsValue {
+        loop {
+            let value = CrdsValue::new_rand(rng, Some(keypair));
+            if matches!(value.data(), CrdsData::Vote(_, _)) {
+                return value;
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_crds_priority_weights() {
+        assert_eq!(
+            "".parse::<CrdsPriorityWeights>().unwrap(),
+            CrdsPriorityWeights::default()
+        );
+        assert_eq!(
+            "vote=16, contact-info=1".parse::<CrdsPriorityWeights>(),
+            Ok(CrdsPriorityWeights {
+                vote: 16,
+                contact_info: 1,
+                ..CrdsPriorityWeights::default()
+            })
+        );
+        assert!("vote".parse::<CrdsPriorityWeights>().is_err());
+        assert!("vote=0".parse::<CrdsPriorityWeights>().is_err());
+        assert!("vote=-1".parse::<CrdsPriorityWeights>().is_err());
+        assert!("shreds=1".parse::<CrdsPriorityWeights>().is_err());
+    }
+
+    #[test]
+    fn test_crds_priority() {
+        let mut rng = rand::thread_rng();
+        let weights = CrdsPriorityWeights::default();
+        let staked = Keypair::new();
+        let stakes = HashMap::from([(staked.pubkey(), 42)]);
+
+        let staked_vote = new_rand_vote(&mut rng, &staked);
+        let unstaked_vote = new_rand_vote(&mut rng, &Keypair::new());
+        let contact_info = CrdsValue::new_unsigned(CrdsData::from(ContactInfo::new_localhost(
+            &staked.pubkey(),
+            0,
+        )));
+        assert_eq!(weights.priority(&staked_vote, &stakes), (4, 42));
+        assert_eq!(weights.priority(&unstaked_vote, &stakes), (4, 0));
+        assert_eq!(weights.priority(&contact_info, &stakes), (2, 42));
+        assert!(
+            weights.priority(&unstaked_vote, &stakes) > weights.priority(&contact_info, &stakes)
+        );
+    }
+}
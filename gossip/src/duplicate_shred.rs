@@ -53,6 +53,8 @@ impl DuplicateShred {
 pub enum Error {
     #[error("block store save error")]
     BlockstoreInsertFailed(#[from] BlockstoreError),
+    #[error("failed to insert {0} duplicate shred chunks")]
+    ChunkInsertFailed(usize),
     #[error("data chunk mismatch")]
     DataChunkMismatch,
     #[error("unable to send duplicate slot to state machine")]
@@ -103,6 +105,7 @@ impl Error {
             | Self::InvalidLastIndexConflict
             | Self::InvalidErasureMetaConflict => true,
             Self::BlockstoreInsertFailed(_)
+            | Self::ChunkInsertFailed(_)
             | Self::DataChunkMismatch
             | Self::DuplicateSlotSenderFailure
             | Self::InvalidChunkIndex { .. }
@@ -2,6 +2,7 @@ use {
     crate::{
         duplicate_shred::{self, DuplicateShred, Error},
         duplicate_shred_listener::DuplicateShredHandlerTrait,
+        duplicate_shred_proofs::{DuplicateShredProof, DuplicateShredProofs},
     },
     crossbeam_channel::Sender,
     log::error,
@@ -42,9 +43,12 @@ pub struct DuplicateShredHandler {
     // Cache information from root bank so we could function correctly without reading roots.
     cached_on_epoch: Epoch,
     cached_staked_nodes: Arc<HashMap<Pubkey, u64>>,
+    cached_total_stake: u64,
     cached_slots_in_epoch: u64,
     // Used to notify duplicate consensus state machine
     duplicate_slots_sender: Sender<Slot>,
+    // Tracks the ingested proofs and how far they spread through gossip.
+    duplicate_shred_proofs: Arc<DuplicateShredProofs>,
     shred_version: u16,
 }
 
@@ -72,6 +76,7 @@ impl DuplicateShredHandler {
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         bank_forks: Arc<RwLock<BankForks>>,
         duplicate_slots_sender: Sender<Slot>,
+        duplicate_shred_proofs: Arc<DuplicateShredProofs>,
         shred_version: u16,
     ) -> Self {
         Self {
@@ -80,11 +85,13 @@ impl DuplicateShredHandler {
             last_root: 0,
             cached_on_epoch: 0,
             cached_staked_nodes: Arc::new(HashMap::new()),
+            cached_total_stake: 0,
             cached_slots_in_epoch: 0,
             blockstore,
             leader_schedule_cache,
             bank_forks,
             duplicate_slots_sender,
+            duplicate_shred_proofs,
             shred_version,
         }
     }
@@ -101,6 +108,7 @@ impl DuplicateShredHandler {
             if self.cached_staked_nodes.is_empty() || self.cached_on_epoch < epoch_info.epoch {
                 self.cached_on_epoch = epoch_info.epoch;
                 if let Some(cached_staked_nodes) = root_bank.epoch_staked_nodes(epoch_info.epoch) {
+                    self.cached_total_stake = cached_staked_nodes.values().sum();
                     self.cached_staked_nodes = cached_staked_nodes;
                 }
                 self.cached_slots_in_epoch = epoch_info.slots_in_epoch;
@@ -108,11 +116,27 @@ impl DuplicateShredHandler {
         }
     }
 
+    fn get_stake(&self, pubkey: &Pubkey) -> u64 {
+        self.cached_staked_nodes
+            .get(pubkey)
+            .copied()
+            .unwrap_or_default()
+    }
+
     fn handle_shred_data(&mut self, chunk: DuplicateShred) -> Result<(), Error> {
+        // Chunks are signed by the node which published the proof, so any
+        // chunk of a slot which already has a proof shows how far proofs of
+        // the slot have spread.
+        self.duplicate_shred_proofs.add_reporter(
+            chunk.slot,
+            chunk.from,
+            self.get_stake(&chunk.from),
+        );
         if !self.should_consume_slot(chunk.slot) {
             return Ok(());
         }
         let slot = chunk.slot;
+        let reporter = chunk.from;
         let num_chunks = chunk.num_chunks();
         let chunk_index = chunk.chunk_index();
         if usize::from(num_chunks) > MAX_NUM_CHUNKS || chunk_index >= num_chunks {
@@ -138,6 +162,13 @@ impl DuplicateShredHandler {
                 .ok_or(Error::UnknownSlotLeader(slot))?;
             let (shred1, shred2) =
                 duplicate_shred::into_shreds(&pubkey, chunks, self.shred_version)?;
+            let proof = DuplicateShredProof {
+                slot,
+                leader: pubkey,
+                reporter,
+                shred1: shred1.payload().to_vec(),
+                shred2: shred2.payload().to_vec(),
+            };
             if !self.blockstore.has_duplicate_shreds_in_slot(slot) {
                 self.blockstore.store_duplicate_slot(
                     slot,
@@ -150,10 +181,28 @@ impl DuplicateShredHandler {
                     .map_err(|_| Error::DuplicateSlotSenderFailure)?;
             }
             self.consumed.insert(slot, true);
+            self.record_proof(proof);
         }
         Ok(())
     }
 
+    fn record_proof(&self, proof: DuplicateShredProof) {
+        let slot = proof.slot;
+        let reporter_stake = self.get_stake(&proof.reporter);
+        if !self
+            .duplicate_shred_proofs
+            .insert_proof(proof, reporter_stake, self.cached_total_stake)
+        {
+            return;
+        }
+        // Nodes whose proofs of the slot are still incomplete have published
+        // proofs as well.
+        for (_, pubkey) in self.buffer.keys().filter(|(buffered, _)| *buffered == slot) {
+            self.duplicate_shred_proofs
+                .add_reporter(slot, *pubkey, self.get_stake(pubkey));
+        }
+    }
+
     fn should_consume_slot(&mut self, slot: Slot) -> bool {
         slot > self.last_root
             && slot < self.last_root.saturating_add(self.cached_slots_in_epoch)
@@ -305,6 +354,7 @@ mod tests {
             &bank_forks_arc.read().unwrap().working_bank(),
         ));
         let (sender, receiver) = unbounded();
+        let duplicate_shred_proofs = Arc::new(DuplicateShredProofs::default());
         let start_slot: Slot = 10;
 
         let mut duplicate_shred_handler = DuplicateShredHandler::new(
@@ -312,6 +362,7 @@ mod tests {
             leader_schedule_cache,
             bank_forks_arc,
             sender,
+            duplicate_shred_proofs.clone(),
             shred_version,
         );
         let chunks = create_duplicate_proof(
@@ -345,6 +396,14 @@ mod tests {
             receiver.try_iter().collect_vec(),
             vec![start_slot, start_slot + 1]
         );
+        let proofs = duplicate_shred_proofs.proofs();
+        assert_eq!(
+            proofs.iter().map(|info| info.slot).collect_vec(),
+            vec![start_slot, start_slot + 1]
+        );
+        assert_eq!(proofs[0].leader, my_pubkey);
+        assert_eq!(proofs[0].num_reporters, 1);
+        assert_eq!(proofs[0].spread_stake_pct(), 100.0);
 
         // Test all kinds of bad proofs.
         for error in [
@@ -396,11 +455,13 @@ mod tests {
             &bank_forks_arc.read().unwrap().working_bank(),
         ));
         let (sender, receiver) = unbounded();
+        let duplicate_shred_proofs = Arc::new(DuplicateShredProofs::default());
         let mut duplicate_shred_handler = DuplicateShredHandler::new(
             blockstore.clone(),
             leader_schedule_cache,
             bank_forks_arc,
             sender,
+            duplicate_shred_proofs.clone(),
             shred_version,
         );
         let start_slot: Slot = 10;
@@ -460,5 +521,25 @@ mod tests {
         }
         assert!(blockstore.has_duplicate_shreds_in_slot(start_slot));
         assert_eq!(receiver.try_iter().collect_vec(), vec![start_slot]);
+
+        // Proofs of the same slot from other nodes are discarded, but count
+        // towards the spread of the proof.
+        let other_pubkey = Pubkey::new_unique();
+        for chunk in create_duplicate_proof(
+            Arc::new(Keypair::new()),
+            Some(other_pubkey),
+            start_slot,
+            None,
+            DUPLICATE_SHRED_MAX_PAYLOAD_SIZE,
+            shred_version,
+        )
+        .unwrap()
+        {
+            duplicate_shred_handler.handle(chunk);
+        }
+        assert!(receiver.is_empty());
+        let info = duplicate_shred_proofs.get(start_slot).unwrap();
+        assert_eq!(info.num_reporters, 2);
+        assert_eq!(info.spread_stake_pct(), 100.0);
     }
 }
@@ -0,0 +1,223 @@
+//! Tracks the duplicate shred proofs ingested from gossip: which slots have
+//! proofs, the leaders of those slots, and how far the proofs have spread
+//! through gossip. External services, e.g. slashing services, can register a
+//! notifier to learn of newly observed proofs.
+
+use {
+    solana_clock::Slot,
+    solana_pubkey::Pubkey,
+    solana_time_utils::timestamp,
+    std::{
+        collections::{BTreeMap, HashSet},
+        sync::{Arc, RwLock},
+    },
+};
+
+/// Maximum number of slots whose proofs are tracked. The oldest slots are
+/// evicted first.
+const MAX_TRACKED_SLOTS: usize = 512;
+
+/// A duplicate shred proof observed for the first time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateShredProof {
+    pub slot: Slot,
+    /// The leader of the slot, which signed both shreds
+    pub leader: Pubkey,
+    /// The node which published the proof in gossip
+    pub reporter: Pubkey,
+    pub shred1: Vec<u8>,
+    pub shred2: Vec<u8>,
+}
+
+pub trait DuplicateShredProofNotifier: Send + Sync {
+    fn notify_duplicate_shred_proof(&self, proof: &DuplicateShredProof);
+}
+
+/// The gossip propagation state of the proofs of a slot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateShredProofInfo {
+    pub slot: Slot,
+    pub leader: Pubkey,
+    /// Milliseconds since the UNIX epoch at which the first proof of the slot
+    /// was ingested
+    pub first_observed: u64,
+    /// Number of nodes which published a proof of the slot
+    pub num_reporters: usize,
+    /// Stake of the nodes which published a proof of the slot
+    pub reporters_stake: u64,
+    /// Total stake of the epoch the proof was ingested in
+    pub total_stake: u64,
+}
+
+impl DuplicateShredProofInfo {
+    /// Percentage of the total stake held by the nodes which published a
+    /// proof of the slot
+    pub fn spread_stake_pct(&self) -> f64 {
+        if self.total_stake == 0 {
+            return 0.0;
+        }
+        100.0 * self.reporters_stake as f64 / self.total_stake as f64
+    }
+
+    fn report_metrics(&self) {
+        datapoint_info!(
+            "duplicate_shred_proof",
+            ("slot", self.slot, i64),
+            ("leader", self.leader.to_string(), String),
+            ("num_reporters", self.num_reporters, i64),
+            ("spread_stake_pct", self.spread_stake_pct(), f64),
+        );
+    }
+}
+
+struct SlotProofs {
+    info: DuplicateShredProofInfo,
+    reporters: HashSet<Pubkey>,
+}
+
+#[derive(Default)]
+pub struct DuplicateShredProofs {
+    slots: RwLock<BTreeMap<Slot, SlotProofs>>,
+    notifiers: RwLock<Vec<Arc<dyn DuplicateShredProofNotifier>>>,
+}
+
+impl DuplicateShredProofs {
+    pub fn add_notifier(&self, notifier: Arc<dyn DuplicateShredProofNotifier>) {
+        self.notifiers.write().unwrap().push(notifier);
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<DuplicateShredProofInfo> {
+        let slots = self.slots.read().unwrap();
+        slots.get(&slot).map(|proofs| proofs.info.clone())
+    }
+
+    /// Returns the state of every tracked slot, oldest first
+    pub fn proofs(&self) -> Vec<DuplicateShredProofInfo> {
+        let slots = self.slots.read().unwrap();
+        slots.values().map(|proofs| proofs.info.clone()).collect()
+    }
+
+    /// Records the first proof of a slot and notifies the registered
+    /// notifiers. Returns false if a proof of the slot was already recorded.
+    pub(crate) fn insert_proof(
+        &self,
+        proof: DuplicateShredProof,
+        reporter_stake: u64,
+        total_stake: u64,
+    ) -> bool {
+        {
+            let mut slots = self.slots.write().unwrap();
+            if slots.contains_key(&proof.slot) {
+                return false;
+            }
+            let info = DuplicateShredProofInfo {
+                slot: proof.slot,
+                leader: proof.leader,
+                first_observed: timestamp(),
+                num_reporters: 1,
+                reporters_stake: reporter_stake,
+                total_stake,
+            };
+            info.report_metrics();
+            slots.insert(
+                proof.slot,
+                SlotProofs {
+                    info,
+                    reporters: HashSet::from([proof.reporter]),
+                },
+            );
+            while slots.len() > MAX_TRACKED_SLOTS {
+                slots.pop_first();
+            }
+        }
+        for notifier in self.notifiers.read().unwrap().iter() {
+            notifier.notify_duplicate_shred_proof(&proof);
+        }
+        true
+    }
+
+    /// Records that `reporter` also published a proof of `slot`. Slots
+    /// without a recorded proof are ignored.
+    pub(crate) fn add_reporter(&self, slot: Slot, reporter: Pubkey, reporter_stake: u64) {
+        if !self
+            .slots
+            .read()
+            .unwrap()
+            .get(&slot)
+            .is_some_and(|proofs| !proofs.reporters.contains(&reporter))
+        {
+            return;
+        }
+        let mut slots = self.slots.write().unwrap();
+        let Some(proofs) = slots.get_mut(&slot) else {
+            return;
+        };
+        if proofs.reporters.insert(reporter) {
+            proofs.info.num_reporters = proofs.reporters.len();
+            proofs.info.reporters_stake =
+                proofs.info.reporters_stake.saturating_add(reporter_stake);
+            proofs.info.report_metrics();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::Mutex};
+
+    #[derive(Default)]
+    struct TestNotifier(Mutex<Vec<DuplicateShredProof>>);
+
+    impl DuplicateShredProofNotifier for TestNotifier {
+        fn notify_duplicate_shred_proof(&self, proof: &DuplicateShredProof) {
+            self.0.lock().unwrap().push(proof.clone());
+        }
+    }
+
+    fn new_proof(slot: Slot, reporter: Pubkey) -> DuplicateShredProof {
+        DuplicateShredProof {
+            slot,
+            leader: Pubkey::new_unique(),
+            reporter,
+            shred1: vec![1; 8],
+            shred2: vec![2; 8],
+        }
+    }
+
+    #[test]
+    fn test_duplicate_shred_proofs() {
+        let proofs = DuplicateShredProofs::default();
+        let notifier = Arc::new(TestNotifier::default());
+        proofs.add_notifier(notifier.clone());
+
+        let reporter = Pubkey::new_unique();
+        let proof = new_proof(7, reporter);
+        assert!(proofs.insert_proof(proof.clone(), 10, 100));
+        assert!(!proofs.insert_proof(new_proof(7, Pubkey::new_unique()), 10, 100));
+        assert_eq!(*notifier.0.lock().unwrap(), vec![proof.clone()]);
+
+        // Repeated reports from the same node and reports of untracked slots
+        // are ignored
+        proofs.add_reporter(7, reporter, 10);
+        proofs.add_reporter(8, Pubkey::new_unique(), 10);
+        proofs.add_reporter(7, Pubkey::new_unique(), 40);
+        let info = proofs.get(7).unwrap();
+        assert_eq!(info.leader, proof.leader);
+        assert_eq!(info.num_reporters, 2);
+        assert_eq!(info.reporters_stake, 50);
+        assert_eq!(info.spread_stake_pct(), 50.0);
+        assert_eq!(proofs.get(8), None);
+    }
+
+    #[test]
+    fn test_duplicate_shred_proofs_eviction() {
+        let proofs = DuplicateShredProofs::default();
+        for slot in 0..MAX_TRACKED_SLOTS as Slot + 2 {
+            proofs.insert_proof(new_proof(slot, Pubkey::new_unique()), 0, 0);
+        }
+        let tracked = proofs.proofs();
+        assert_eq!(tracked.len(), MAX_TRACKED_SLOTS);
+        assert_eq!(tracked[0].slot, 2);
+        assert_eq!(tracked[0].spread_stake_pct(), 0.0);
+    }
+}
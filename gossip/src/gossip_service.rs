@@ -6,6 +6,7 @@ use {
         cluster_info_metrics::submit_gossip_stats,
         contact_info::ContactInfo,
         epoch_specs::EpochSpecs,
+        quic_endpoint::{self, GossipQuicChannels},
     },
     crossbeam_channel::Sender,
     rand::{thread_rng, Rng},
@@ -18,10 +19,12 @@ use {
     solana_runtime::bank_forks::BankForks,
     solana_signer::Signer,
     solana_streamer::{
+        bandwidth_accounting::Subsystem,
         evicting_sender::EvictingSender,
         socket::SocketAddrSpace,
         streamer::{self, StreamerReceiveStats},
     },
+    solana_time_utils::timestamp,
     solana_tpu_client::tpu_client::{TpuClient, TpuClientConfig},
     std::{
         collections::HashSet,
@@ -46,6 +49,9 @@ impl GossipService {
         cluster_info: &Arc<ClusterInfo>,
         bank_forks: Option<Arc<RwLock<BankForks>>>,
         gossip_socket: UdpSocket,
+        // If provided, packets to peers which advertise a gossip QUIC
+        // address are sent over QUIC instead of UDP.
+        quic_channels: Option<GossipQuicChannels>,
         gossip_validators: Option<HashSet<Pubkey>>,
         should_check_duplicate_instance: bool,
         stats_reporter_sender: Option<Sender<Box<dyn FnOnce() + Send>>>,
@@ -60,12 +66,14 @@ impl GossipService {
             gossip_socket.local_addr().unwrap()
         );
         let socket_addr_space = *cluster_info.socket_addr_space();
-        let gossip_receiver_stats = Arc::new(StreamerReceiveStats::new("gossip_receiver"));
+        let gossip_receiver_stats = Arc::new(
+            StreamerReceiveStats::new("gossip_receiver").with_subsystem(Subsystem::Gossip),
+        );
         let t_receiver = streamer::receiver(
             "solRcvrGossip".to_string(),
             gossip_socket.clone(),
             exit.clone(),
-            request_sender,
+            request_sender.clone(),
             Recycler::default(),
             gossip_receiver_stats.clone(),
             Some(Duration::from_millis(1)), // coalesce
@@ -96,8 +104,30 @@ impl GossipService {
             gossip_validators,
             exit.clone(),
         );
+        let mut thread_hdls = Vec::new();
+        let response_receiver = match quic_channels {
+            None => response_receiver,
+            Some(GossipQuicChannels { sender, receiver }) => {
+                thread_hdls.push(quic_endpoint::spawn_quic_receiver(
+                    receiver,
+                    request_sender,
+                    exit.clone(),
+                ));
+                let (udp_sender, udp_receiver) =
+                    EvictingSender::new_bounded(GOSSIP_CHANNEL_CAPACITY);
+                thread_hdls.push(quic_endpoint::spawn_quic_router(
+                    cluster_info.clone(),
+                    response_receiver,
+                    udp_sender,
+                    sender,
+                    exit.clone(),
+                ));
+                udp_receiver
+            }
+        };
         let t_responder = streamer::responder(
             "Gossip",
+            Subsystem::Gossip,
             gossip_socket,
             response_receiver,
             socket_addr_space,
@@ -118,19 +148,20 @@ impl GossipService {
                             .unwrap_or_default();
 
                         submit_gossip_stats(&cluster_info.stats, &cluster_info.gossip, &stakes);
+                        cluster_info.peer_scores().maintain(timestamp());
                         gossip_receiver_stats.report();
                     }
                 }
             })
             .unwrap();
-        let thread_hdls = vec![
+        thread_hdls.extend([
             t_receiver,
             t_responder,
             t_socket_consume,
             t_listen,
             t_gossip,
             t_metrics,
-        ];
+        ]);
         Self { thread_hdls }
     }
 
@@ -377,6 +408,7 @@ pub fn make_gossip_node(
         None,
         gossip_socket,
         None,
+        None,
         should_check_duplicate_instance,
         None,
         exit,
@@ -412,6 +444,7 @@ mod tests {
             None,
             tn.sockets.gossip,
             None,
+            None,
             true, // should_check_duplicate_instance
             None,
             exit.clone(),
@@ -11,21 +11,26 @@ pub mod crds_gossip;
 pub mod crds_gossip_error;
 pub mod crds_gossip_pull;
 pub mod crds_gossip_push;
+pub mod crds_inspect;
+pub mod crds_priority;
 pub mod crds_shards;
 pub mod crds_value;
 mod deprecated;
 pub mod duplicate_shred;
 pub mod duplicate_shred_handler;
 pub mod duplicate_shred_listener;
+pub mod duplicate_shred_proofs;
 pub mod epoch_slots;
 pub mod epoch_specs;
 pub mod gossip_error;
 pub mod gossip_service;
 #[macro_use]
 mod legacy_contact_info;
+pub mod peer_scores;
 pub mod ping_pong;
 mod protocol;
 mod push_active_set;
+pub mod quic_endpoint;
 mod received_cache;
 pub mod restart_crds_values;
 pub mod weighted_shuffle;
@@ -0,0 +1,322 @@
+//! Scores gossip peers by how much they misbehave: packets which fail
+//! signature verification, floods of duplicate values and excessive pull
+//! requests.
+//!
+//! Every offense adds penalty points to the IP address which the packet was
+//! received from. Penalties halve every PENALTY_HALF_LIFE_MS, so peers which
+//! stop misbehaving are eventually restored. Pull requests from peers whose
+//! penalty reaches DEPRIORITIZE_PENALTY are not answered, and all packets from
+//! peers whose penalty reaches SHUN_PENALTY are dropped for SHUN_DURATION_MS.
+//! Addresses on the override list are never penalized.
+
+use {
+    itertools::Itertools,
+    std::{
+        collections::{HashMap, HashSet},
+        net::IpAddr,
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            RwLock,
+        },
+    },
+};
+
+const PENALTY_HALF_LIFE_MS: u64 = 60_000;
+pub const DEPRIORITIZE_PENALTY: u64 = 100;
+pub const SHUN_PENALTY: u64 = 1_000;
+const SHUN_DURATION_MS: u64 = 10 * 60_000;
+/// Maximum number of peers whose scores are tracked. Peers with the lowest
+/// penalties are evicted first.
+const MAX_TRACKED_PEERS: usize = 4_096;
+/// Number of pull requests a peer may send within a single batch of packets
+/// before the excess is penalized.
+pub(crate) const MAX_PULL_REQUESTS_PER_BATCH: u64 = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PeerOffense {
+    InvalidSignature,
+    DuplicateValue,
+    ExcessPullRequest,
+}
+
+impl PeerOffense {
+    fn penalty(&self) -> u64 {
+        match self {
+            Self::InvalidSignature => 10,
+            Self::DuplicateValue => 1,
+            Self::ExcessPullRequest => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerScore {
+    pub addr: IpAddr,
+    pub penalty: u64,
+    pub num_invalid_signatures: u64,
+    pub num_duplicate_values: u64,
+    pub num_excess_pull_requests: u64,
+    /// Milliseconds since the UNIX epoch until which all packets from the
+    /// peer are dropped
+    pub shunned_until: Option<u64>,
+}
+
+impl PeerScore {
+    pub fn is_deprioritized(&self) -> bool {
+        self.penalty >= DEPRIORITIZE_PENALTY
+    }
+}
+
+struct PeerState {
+    score: PeerScore,
+    last_decay: u64,
+}
+
+impl PeerState {
+    // Applies the halvings of the penalty which are due since last_decay.
+    fn decay(&mut self, now: u64) {
+        let halvings = now.saturating_sub(self.last_decay) / PENALTY_HALF_LIFE_MS;
+        if halvings > 0 {
+            self.score.penalty = u32::try_from(halvings)
+                .ok()
+                .and_then(|halvings| self.score.penalty.checked_shr(halvings))
+                .unwrap_or_default();
+            self.last_decay += halvings * PENALTY_HALF_LIFE_MS;
+        }
+    }
+
+    fn decayed_score(&self, now: u64) -> PeerScore {
+        let mut state = PeerState {
+            score: self.score.clone(),
+            last_decay: self.last_decay,
+        };
+        state.decay(now);
+        state.score
+    }
+}
+
+#[derive(Default)]
+pub struct PeerScores {
+    peers: RwLock<HashMap<IpAddr, PeerState>>,
+    overrides: RwLock<HashSet<IpAddr>>,
+    // Skips the lookup on every received packet while nobody is shunned.
+    num_shunned: AtomicUsize,
+    num_dropped_packets: AtomicU64,
+    num_dropped_pull_requests: AtomicU64,
+}
+
+impl PeerScores {
+    pub(crate) fn record(&self, addr: IpAddr, offense: PeerOffense, count: u64, now: u64) {
+        if count == 0 || self.overrides.read().unwrap().contains(&addr) {
+            return;
+        }
+        let mut peers = self.peers.write().unwrap();
+        if peers.len() >= MAX_TRACKED_PEERS && !peers.contains_key(&addr) {
+            return;
+        }
+        let state = peers.entry(addr).or_insert_with(|| PeerState {
+            score: PeerScore {
+                addr,
+                penalty: 0,
+                num_invalid_signatures: 0,
+                num_duplicate_values: 0,
+                num_excess_pull_requests: 0,
+                shunned_until: None,
+            },
+            last_decay: now,
+        });
+        state.decay(now);
+        let score = &mut state.score;
+        score.penalty = score
+            .penalty
+            .saturating_add(offense.penalty().saturating_mul(count));
+        let num_offenses = match offense {
+            PeerOffense::InvalidSignature => &mut score.num_invalid_signatures,
+            PeerOffense::DuplicateValue => &mut score.num_duplicate_values,
+            PeerOffense::ExcessPullRequest => &mut score.num_excess_pull_requests,
+        };
+        *num_offenses = num_offenses.saturating_add(count);
+        if score.penalty >= SHUN_PENALTY && score.shunned_until.is_none() {
+            warn!(
+                "shunning gossip peer {addr} for {}s, penalty: {}",
+                SHUN_DURATION_MS / 1000,
+                score.penalty
+            );
+            score.shunned_until = Some(now + SHUN_DURATION_MS);
+            self.num_shunned.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns true if all packets from the address should be dropped.
+    pub(crate) fn check_shunned(&self, addr: &IpAddr, now: u64) -> bool {
+        if self.num_shunned.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+        let peers = self.peers.read().unwrap();
+        let shunned = peers
+            .get(addr)
+            .and_then(|state| state.score.shunned_until)
+            .is_some_and(|shunned_until| now < shunned_until);
+        if shunned {
+            self.num_dropped_packets.fetch_add(1, Ordering::Relaxed);
+        }
+        shunned
+    }
+
+    /// Returns true if pull requests from the address should be dropped.
+    pub(crate) fn check_deprioritized(&self, addr: &IpAddr, now: u64) -> bool {
+        let peers = self.peers.read().unwrap();
+        let deprioritized = peers
+            .get(addr)
+            .is_some_and(|state| state.decayed_score(now).is_deprioritized());
+        if deprioritized {
+            self.num_dropped_pull_requests
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        deprioritized
+    }
+
+    /// Returns the scores of the tracked peers, highest penalty first.
+    pub fn scores(&self, now: u64) -> Vec<PeerScore> {
+        let peers = self.peers.read().unwrap();
+        peers
+            .values()
+            .map(|state| state.decayed_score(now))
+            .sorted_unstable_by_key(|score| std::cmp::Reverse(score.penalty))
+            .collect()
+    }
+
+    pub fn overrides(&self) -> Vec<IpAddr> {
+        self.overrides
+            .read()
+            .unwrap()
+            .iter()
+            .copied()
+            .sorted()
+            .collect()
+    }
+
+    /// Replaces the addresses which are never penalized, and forgives their
+    /// accrued penalties.
+    pub fn set_overrides(&self, overrides: Vec<IpAddr>) {
+        let overrides: HashSet<_> = overrides.into_iter().collect();
+        let mut peers = self.peers.write().unwrap();
+        for addr in &overrides {
+            if let Some(state) = peers.remove(addr) {
+                if state.score.shunned_until.is_some() {
+                    self.num_shunned.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+        *self.overrides.write().unwrap() = overrides;
+    }
+
+    /// Decays penalties, lifts expired shuns, evicts restored peers and
+    /// reports metrics.
+    pub(crate) fn maintain(&self, now: u64) {
+        let mut peers = self.peers.write().unwrap();
+        peers.retain(|addr, state| {
+            state.decay(now);
+            if state
+                .score
+                .shunned_until
+                .is_some_and(|shunned_until| shunned_until <= now)
+            {
+                info!("no longer shunning gossip peer {addr}");
+                state.score.shunned_until = None;
+                self.num_shunned.fetch_sub(1, Ordering::Relaxed);
+            }
+            state.score.penalty > 0 || state.score.shunned_until.is_some()
+        });
+        if peers.len() > MAX_TRACKED_PEERS / 2 {
+            // Makes room for new offenders by evicting the peers with the
+            // lowest penalties which are not shunned.
+            let min_penalty = peers
+                .values()
+                .map(|state| state.score.penalty)
+                .sorted_unstable_by_key(|&penalty| std::cmp::Reverse(penalty))
+                .nth(MAX_TRACKED_PEERS / 2)
+                .unwrap_or_default();
+            peers.retain(|_, state| {
+                state.score.penalty > min_penalty || state.score.shunned_until.is_some()
+            });
+        }
+        datapoint_info!(
+            "cluster_info_peer_scores",
+            ("num_peers", peers.len(), i64),
+            (
+                "num_deprioritized",
+                peers
+                    .values()
+                    .filter(|state| state.score.is_deprioritized())
+                    .count(),
+                i64
+            ),
+            ("num_shunned", self.num_shunned.load(Ordering::Relaxed), i64),
+            (
+                "max_penalty",
+                peers
+                    .values()
+                    .map(|state| state.score.penalty)
+                    .max()
+                    .unwrap_or_default(),
+                i64
+            ),
+            (
+                "num_dropped_packets",
+                self.num_dropped_packets.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_dropped_pull_requests",
+                self.num_dropped_pull_requests.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    #[test]
+    fn test_peer_scores() {
+        let scores = PeerScores::default();
+        let addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let now = 1_000_000;
+
+        scores.record(addr, PeerOffense::InvalidSignature, 10, now);
+        scores.record(other, PeerOffense::DuplicateValue, 1, now);
+        assert!(scores.check_deprioritized(&addr, now));
+        assert!(!scores.check_deprioritized(&other, now));
+        assert!(!scores.check_shunned(&addr, now));
+
+        scores.record(addr, PeerOffense::ExcessPullRequest, SHUN_PENALTY, now);
+        assert!(scores.check_shunned(&addr, now));
+        assert!(!scores.check_shunned(&other, now));
+        let tracked = scores.scores(now);
+        assert_eq!(tracked.len(), 2);
+        assert_eq!(tracked[0].addr, addr);
+        assert_eq!(tracked[0].penalty, 100 + SHUN_PENALTY);
+        assert_eq!(tracked[0].num_invalid_signatures, 10);
+        assert_eq!(tracked[0].num_excess_pull_requests, SHUN_PENALTY);
+
+        // Penalties decay, and shuns are lifted once they expire.
+        let now = now + SHUN_DURATION_MS;
+        scores.maintain(now);
+        assert!(!scores.check_shunned(&addr, now));
+        assert!(!scores.check_deprioritized(&addr, now));
+        assert_eq!(scores.scores(now).len(), 1);
+
+        // Overrides forgive penalties and are never penalized again.
+        scores.record(addr, PeerOffense::InvalidSignature, 1_000, now);
+        assert!(scores.check_shunned(&addr, now));
+        scores.set_overrides(vec![addr]);
+        assert!(!scores.check_shunned(&addr, now));
+        scores.record(addr, PeerOffense::InvalidSignature, 1_000, now);
+        assert!(!scores.check_deprioritized(&addr, now));
+        assert_eq!(scores.overrides(), vec![addr]);
+    }
+}
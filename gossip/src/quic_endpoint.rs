@@ -0,0 +1,896 @@
+//! Optional QUIC transport for gossip.
+//!
+//! Nodes which enable it advertise a gossip QUIC address in their
+//! contact-info, next to the gossip UDP address. Packets addressed to peers
+//! which advertise one, most notably the pull responses, are then written to
+//! unidirectional QUIC streams, one stream per packet, so that they are
+//! congestion-controlled and retransmitted if lost. All other peers are still
+//! reached over UDP.
+//!
+//! Packets sent over QUIC are subject to the same PACKET_DATA_SIZE limit as
+//! UDP packets, so that both are processed by the same gossip pipeline.
+
+use {
+    crate::cluster_info::ClusterInfo,
+    bytes::Bytes,
+    crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
+    futures::future::TryJoin,
+    itertools::Itertools,
+    quinn::{
+        crypto::rustls::{QuicClientConfig, QuicServerConfig},
+        ClientConfig, ClosedStream, ConnectError, Connecting, Connection, ConnectionError,
+        Endpoint, EndpointConfig, IdleTimeout, ReadToEndError, ServerConfig, TokioRuntime,
+        TransportConfig, VarInt, WriteError,
+    },
+    rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        CertificateError, KeyLogFile,
+    },
+    solana_keypair::Keypair,
+    solana_packet::{Meta, PacketFlags, PACKET_DATA_SIZE},
+    solana_perf::packet::{PacketBatch, PacketBatchRecycler, PACKETS_PER_BATCH},
+    solana_pubkey::Pubkey,
+    solana_streamer::streamer::ChannelSend,
+    solana_tls_utils::{
+        new_dummy_x509_certificate, tls_client_config_builder, tls_server_config_builder,
+    },
+    std::{
+        collections::{hash_map::Entry, HashMap},
+        io::Error as IoError,
+        net::{SocketAddr, UdpSocket},
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc,
+        },
+        thread::{Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+    tokio::{
+        sync::{
+            mpsc::{error::TrySendError, Receiver as AsyncReceiver, Sender as AsyncSender},
+            Mutex, RwLock as AsyncRwLock,
+        },
+        task::JoinHandle as AsyncJoinHandle,
+    },
+};
+
+const CLIENT_CHANNEL_BUFFER: usize = 1 << 14;
+const ROUTER_CHANNEL_BUFFER: usize = 256;
+const CONNECTION_CACHE_CAPACITY: usize = 4096;
+const ALPN_GOSSIP_PROTOCOL_ID: &[u8] = b"solana-gossip";
+const CONNECT_SERVER_NAME: &str = "solana-gossip";
+// Interval at which the gossip QUIC addresses of the peers are refreshed
+// from the CRDS table.
+const ROUTER_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+// Transport config.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(4);
+const MAX_CONCURRENT_UNI_STREAMS: u32 = 512;
+const MAX_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+const STREAM_RECEIVE_WINDOW: u32 = PACKET_DATA_SIZE as u32;
+
+const CONNECTION_CLOSE_ERROR_CODE_SHUTDOWN: VarInt = VarInt::from_u32(1);
+const CONNECTION_CLOSE_ERROR_CODE_DROPPED: VarInt = VarInt::from_u32(2);
+const CONNECTION_CLOSE_ERROR_CODE_INVALID_IDENTITY: VarInt = VarInt::from_u32(3);
+const CONNECTION_CLOSE_ERROR_CODE_REPLACED: VarInt = VarInt::from_u32(4);
+const CONNECTION_CLOSE_ERROR_CODE_EVICTED: VarInt = VarInt::from_u32(5);
+
+const CONNECTION_CLOSE_REASON_SHUTDOWN: &[u8] = b"SHUTDOWN";
+const CONNECTION_CLOSE_REASON_DROPPED: &[u8] = b"DROPPED";
+const CONNECTION_CLOSE_REASON_INVALID_IDENTITY: &[u8] = b"INVALID_IDENTITY";
+const CONNECTION_CLOSE_REASON_REPLACED: &[u8] = b"REPLACED";
+const CONNECTION_CLOSE_REASON_EVICTED: &[u8] = b"EVICTED";
+
+pub type AsyncTryJoinHandle = TryJoin<AsyncJoinHandle<()>, AsyncJoinHandle<()>>;
+
+/// The channels connecting the gossip service to a gossip QUIC endpoint
+pub struct GossipQuicChannels {
+    /// Outgoing packets, sent to the given gossip QUIC address
+    pub sender: AsyncSender<(SocketAddr, Bytes)>,
+    /// Incoming packets, with the identity and address of the remote node
+    pub receiver: Receiver<(Pubkey, SocketAddr, Bytes)>,
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Channel Send Error")]
+    ChannelSendError,
+    #[error(transparent)]
+    ClosedStream(#[from] ClosedStream),
+    #[error(transparent)]
+    ConnectError(#[from] ConnectError),
+    #[error(transparent)]
+    ConnectionError(#[from] ConnectionError),
+    #[error("Invalid Identity: {0:?}")]
+    InvalidIdentity(SocketAddr),
+    #[error(transparent)]
+    IoError(#[from] IoError),
+    #[error(transparent)]
+    ReadToEndError(#[from] ReadToEndError),
+    #[error(transparent)]
+    TlsError(#[from] rustls::Error),
+    #[error(transparent)]
+    WriteError(#[from] WriteError),
+}
+
+macro_rules! add_metric {
+    ($metric: expr) => {{
+        $metric.fetch_add(1, Ordering::Relaxed);
+    }};
+}
+
+#[allow(clippy::type_complexity)]
+pub fn new_quic_endpoint(
+    runtime: &tokio::runtime::Handle,
+    keypair: &Keypair,
+    socket: UdpSocket,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+) -> Result<
+    (
+        Endpoint,
+        AsyncSender<(SocketAddr, Bytes)>,
+        AsyncTryJoinHandle,
+    ),
+    Error,
+> {
+    let (cert, key) = new_dummy_x509_certificate(keypair);
+    let server_config = new_server_config(cert.clone(), key.clone_key())?;
+    let client_config = new_client_config(cert, key)?;
+    let mut endpoint = {
+        // Endpoint::new requires entering the runtime context,
+        // otherwise the code below will panic.
+        let _guard = runtime.enter();
+        Endpoint::new(
+            EndpointConfig::default(),
+            Some(server_config),
+            socket,
+            Arc::new(TokioRuntime),
+        )?
+    };
+    endpoint.set_default_client_config(client_config);
+    let cache = Arc::<Mutex<HashMap<Pubkey, Connection>>>::default();
+    let router = Arc::<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>::default();
+    let (client_sender, client_receiver) = tokio::sync::mpsc::channel(CLIENT_CHANNEL_BUFFER);
+    let server_task = runtime.spawn(run_server(
+        endpoint.clone(),
+        sender.clone(),
+        router.clone(),
+        cache.clone(),
+    ));
+    let client_task = runtime.spawn(run_client(
+        endpoint.clone(),
+        client_receiver,
+        sender,
+        router,
+        cache,
+    ));
+    let task = futures::future::try_join(server_task, client_task);
+    Ok((endpoint, client_sender, task))
+}
+
+pub fn close_quic_endpoint(endpoint: &Endpoint) {
+    endpoint.close(
+        CONNECTION_CLOSE_ERROR_CODE_SHUTDOWN,
+        CONNECTION_CLOSE_REASON_SHUTDOWN,
+    );
+}
+
+fn new_server_config(
+    cert: CertificateDer<'static>,
+    key: PrivateKeyDer<'static>,
+) -> Result<ServerConfig, rustls::Error> {
+    let mut config = tls_server_config_builder().with_single_cert(vec![cert], key)?;
+    config.alpn_protocols = vec![ALPN_GOSSIP_PROTOCOL_ID.to_vec()];
+    config.key_log = Arc::new(KeyLogFile::new());
+    let quic_server_config = QuicServerConfig::try_from(config)
+        .map_err(|_err| rustls::Error::InvalidCertificate(CertificateError::BadSignature))?;
+
+    let mut config = ServerConfig::with_crypto(Arc::new(quic_server_config));
+    config
+        .transport_config(Arc::new(new_transport_config()))
+        .migration(false);
+    Ok(config)
+}
+
+fn new_client_config(
+    cert: CertificateDer<'static>,
+    key: PrivateKeyDer<'static>,
+) -> Result<ClientConfig, rustls::Error> {
+    let mut config = tls_client_config_builder().with_client_auth_cert(vec![cert], key)?;
+    config.enable_early_data = true;
+    config.alpn_protocols = vec![ALPN_GOSSIP_PROTOCOL_ID.to_vec()];
+    let mut config = ClientConfig::new(Arc::new(QuicClientConfig::try_from(config).unwrap()));
+    config.transport_config(Arc::new(new_transport_config()));
+    Ok(config)
+}
+
+fn new_transport_config() -> TransportConfig {
+    let max_idle_timeout = IdleTimeout::try_from(MAX_IDLE_TIMEOUT).unwrap();
+    let mut config = TransportConfig::default();
+    config
+        .datagram_receive_buffer_size(None)
+        .keep_alive_interval(Some(KEEP_ALIVE_INTERVAL))
+        .max_concurrent_bidi_streams(VarInt::from(0u8))
+        .max_concurrent_uni_streams(VarInt::from(MAX_CONCURRENT_UNI_STREAMS))
+        .max_idle_timeout(Some(max_idle_timeout))
+        .stream_receive_window(VarInt::from(STREAM_RECEIVE_WINDOW));
+    config
+}
+
+async fn run_server(
+    endpoint: Endpoint,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+) {
+    let stats = Arc::<GossipQuicStats>::default();
+    let report_metrics_task =
+        tokio::task::spawn(report_metrics_task("gossip_quic_server", stats.clone()));
+    while let Some(incoming) = endpoint.accept().await {
+        let remote_addr: SocketAddr = incoming.remote_address();
+        match incoming.accept() {
+            Ok(connecting) => {
+                tokio::task::spawn(handle_connecting_task(
+                    connecting,
+                    sender.clone(),
+                    router.clone(),
+                    cache.clone(),
+                    stats.clone(),
+                ));
+            }
+            Err(error) => {
+                debug!("Error while accepting incoming connection: {error:?} from {remote_addr}");
+            }
+        }
+    }
+    report_metrics_task.abort();
+}
+
+async fn run_client(
+    endpoint: Endpoint,
+    mut receiver: AsyncReceiver<(SocketAddr, Bytes)>,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+) {
+    let stats = Arc::<GossipQuicStats>::default();
+    let report_metrics_task =
+        tokio::task::spawn(report_metrics_task("gossip_quic_client", stats.clone()));
+    while let Some((remote_address, bytes)) = receiver.recv().await {
+        let Some(bytes) = try_route_bytes(&remote_address, bytes, &*router.read().await, &stats)
+        else {
+            continue;
+        };
+        let receiver = {
+            let mut router = router.write().await;
+            let Some(bytes) = try_route_bytes(&remote_address, bytes, &router, &stats) else {
+                continue;
+            };
+            let (sender, receiver) = tokio::sync::mpsc::channel(ROUTER_CHANNEL_BUFFER);
+            sender.try_send(bytes).unwrap();
+            router.insert(remote_address, sender);
+            receiver
+        };
+        tokio::task::spawn(make_connection_task(
+            endpoint.clone(),
+            remote_address,
+            sender.clone(),
+            receiver,
+            router.clone(),
+            cache.clone(),
+            stats.clone(),
+        ));
+    }
+    close_quic_endpoint(&endpoint);
+    // Drop sender channels to unblock threads waiting on the receiving end.
+    router.write().await.clear();
+    report_metrics_task.abort();
+}
+
+fn try_route_bytes(
+    remote_address: &SocketAddr,
+    bytes: Bytes,
+    router: &HashMap<SocketAddr, AsyncSender<Bytes>>,
+    stats: &GossipQuicStats,
+) -> Option<Bytes> {
+    match router.get(remote_address) {
+        None => Some(bytes),
+        Some(sender) => match sender.try_send(bytes) {
+            Ok(()) => None,
+            Err(TrySendError::Full(_)) => {
+                debug!("TrySendError::Full {remote_address}");
+                add_metric!(stats.router_try_send_error_full);
+                None
+            }
+            Err(TrySendError::Closed(bytes)) => Some(bytes),
+        },
+    }
+}
+
+async fn handle_connecting_task(
+    connecting: Connecting,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+    stats: Arc<GossipQuicStats>,
+) {
+    if let Err(err) = handle_connecting(connecting, sender, router, cache, stats.clone()).await {
+        debug!("handle_connecting: {err:?}");
+        record_error(&err, &stats);
+    }
+}
+
+async fn handle_connecting(
+    connecting: Connecting,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+    stats: Arc<GossipQuicStats>,
+) -> Result<(), Error> {
+    let connection = connecting.await?;
+    let remote_address = connection.remote_address();
+    let remote_pubkey = get_remote_pubkey(&connection)?;
+    let receiver = {
+        let (sender, receiver) = tokio::sync::mpsc::channel(ROUTER_CHANNEL_BUFFER);
+        router.write().await.insert(remote_address, sender);
+        receiver
+    };
+    handle_connection(
+        remote_address,
+        remote_pubkey,
+        connection,
+        sender,
+        receiver,
+        router,
+        cache,
+        stats,
+    )
+    .await;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_connection(
+    remote_address: SocketAddr,
+    remote_pubkey: Pubkey,
+    connection: Connection,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    receiver: AsyncReceiver<Bytes>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+    stats: Arc<GossipQuicStats>,
+) {
+    cache_connection(remote_pubkey, connection.clone(), &cache, &stats).await;
+    let send_streams_task = tokio::task::spawn(send_streams_task(
+        connection.clone(),
+        receiver,
+        stats.clone(),
+    ));
+    let read_streams_task = tokio::task::spawn(read_streams_task(
+        remote_address,
+        remote_pubkey,
+        connection.clone(),
+        sender,
+        stats.clone(),
+    ));
+    match futures::future::try_join(send_streams_task, read_streams_task).await {
+        Err(err) => error!("handle_connection: {remote_pubkey}, {remote_address}, {err:?}"),
+        Ok(out) => {
+            if let (Err(ref err), _) = out {
+                debug!("send_streams_task: {remote_pubkey}, {remote_address}, {err:?}");
+                record_error(err, &stats);
+            }
+            if let (_, Err(ref err)) = out {
+                debug!("read_streams_task: {remote_pubkey}, {remote_address}, {err:?}");
+                record_error(err, &stats);
+            }
+        }
+    }
+    drop_connection(remote_pubkey, &connection, &cache).await;
+    if let Entry::Occupied(entry) = router.write().await.entry(remote_address) {
+        if entry.get().is_closed() {
+            entry.remove();
+        }
+    }
+}
+
+async fn read_streams_task(
+    remote_address: SocketAddr,
+    remote_pubkey: Pubkey,
+    connection: Connection,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    stats: Arc<GossipQuicStats>,
+) -> Result<(), Error> {
+    // Assert that send won't block.
+    debug_assert_eq!(sender.capacity(), None);
+    loop {
+        let mut stream = connection.accept_uni().await?;
+        let sender = sender.clone();
+        let stats = stats.clone();
+        // Read streams concurrently, so that a slow stream does not hold up
+        // the ones behind it.
+        tokio::task::spawn(async move {
+            match stream.read_to_end(PACKET_DATA_SIZE).await {
+                Ok(bytes) => {
+                    add_metric!(stats.streams_received);
+                    // The receiving end only goes away on shutdown.
+                    let _ = sender.send((remote_pubkey, remote_address, Bytes::from(bytes)));
+                }
+                Err(err) => {
+                    debug!("read_to_end: {remote_pubkey}, {remote_address}, {err:?}");
+                    record_error(&Error::from(err), &stats);
+                }
+            }
+        });
+    }
+}
+
+async fn send_streams_task(
+    connection: Connection,
+    mut receiver: AsyncReceiver<Bytes>,
+    stats: Arc<GossipQuicStats>,
+) -> Result<(), Error> {
+    tokio::pin! {
+        let connection_closed = connection.closed();
+    }
+    loop {
+        tokio::select! {
+            biased;
+            bytes = receiver.recv() => {
+                let Some(bytes) = bytes else {
+                    return Ok(());
+                };
+                // Opening a stream waits until the peer allows more
+                // concurrent streams, and writing to it is subject to flow
+                // and congestion control, which back-pressures the gossip
+                // service through the router channel.
+                let mut stream = connection.open_uni().await?;
+                stream.write_all(&bytes).await?;
+                stream.finish()?;
+                add_metric!(stats.streams_sent);
+            }
+            err = &mut connection_closed => return Err(Error::from(err)),
+        }
+    }
+}
+
+async fn make_connection_task(
+    endpoint: Endpoint,
+    remote_address: SocketAddr,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    receiver: AsyncReceiver<Bytes>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+    stats: Arc<GossipQuicStats>,
+) {
+    if let Err(err) = make_connection(
+        endpoint,
+        remote_address,
+        sender,
+        receiver,
+        router,
+        cache,
+        stats.clone(),
+    )
+    .await
+    {
+        debug!("make_connection: {remote_address}, {err:?}");
+        record_error(&err, &stats);
+    }
+}
+
+async fn make_connection(
+    endpoint: Endpoint,
+    remote_address: SocketAddr,
+    sender: Sender<(Pubkey, SocketAddr, Bytes)>,
+    receiver: AsyncReceiver<Bytes>,
+    router: Arc<AsyncRwLock<HashMap<SocketAddr, AsyncSender<Bytes>>>>,
+    cache: Arc<Mutex<HashMap<Pubkey, Connection>>>,
+    stats: Arc<GossipQuicStats>,
+) -> Result<(), Error> {
+    let connection = endpoint
+        .connect(remote_address, CONNECT_SERVER_NAME)?
+        .await?;
+    handle_connection(
+        connection.remote_address(),
+        get_remote_pubkey(&connection)?,
+        connection,
+        sender,
+        receiver,
+        router,
+        cache,
+        stats,
+    )
+    .await;
+    Ok(())
+}
+
+fn get_remote_pubkey(connection: &Connection) -> Result<Pubkey, Error> {
+    match solana_streamer::nonblocking::quic::get_remote_pubkey(connection) {
+        Some(remote_pubkey) => Ok(remote_pubkey),
+        None => {
+            connection.close(
+                CONNECTION_CLOSE_ERROR_CODE_INVALID_IDENTITY,
+                CONNECTION_CLOSE_REASON_INVALID_IDENTITY,
+            );
+            Err(Error::InvalidIdentity(connection.remote_address()))
+        }
+    }
+}
+
+async fn cache_connection(
+    remote_pubkey: Pubkey,
+    connection: Connection,
+    cache: &Mutex<HashMap<Pubkey, Connection>>,
+    stats: &GossipQuicStats,
+) {
+    let mut cache = cache.lock().await;
+    // Unlike turbine, gossip talks to unstaked nodes just as well, so once
+    // the cache is full an arbitrary connection is evicted instead of the
+    // connections of the least staked nodes.
+    if cache.len() >= CONNECTION_CACHE_CAPACITY && !cache.contains_key(&remote_pubkey) {
+        let evicted = cache.keys().next().copied();
+        if let Some(evicted) = evicted.and_then(|pubkey| cache.remove(&pubkey)) {
+            evicted.close(
+                CONNECTION_CLOSE_ERROR_CODE_EVICTED,
+                CONNECTION_CLOSE_REASON_EVICTED,
+            );
+            add_metric!(stats.connections_evicted);
+        }
+    }
+    if let Some(old) = cache.insert(remote_pubkey, connection) {
+        old.close(
+            CONNECTION_CLOSE_ERROR_CODE_REPLACED,
+            CONNECTION_CLOSE_REASON_REPLACED,
+        );
+    }
+}
+
+async fn drop_connection(
+    remote_pubkey: Pubkey,
+    connection: &Connection,
+    cache: &Mutex<HashMap<Pubkey, Connection>>,
+) {
+    connection.close(
+        CONNECTION_CLOSE_ERROR_CODE_DROPPED,
+        CONNECTION_CLOSE_REASON_DROPPED,
+    );
+    if let Entry::Occupied(entry) = cache.lock().await.entry(remote_pubkey) {
+        if entry.get().stable_id() == connection.stable_id() {
+            entry.remove();
+        }
+    }
+}
+
+impl<T> From<crossbeam_channel::SendError<T>> for Error {
+    fn from(_: crossbeam_channel::SendError<T>) -> Self {
+        Error::ChannelSendError
+    }
+}
+
+/// Sends the packets received from the gossip service to peers which
+/// advertise a gossip QUIC address through the endpoint, and the remaining
+/// packets on to the UDP responder.
+pub(crate) fn spawn_quic_router(
+    cluster_info: Arc<ClusterInfo>,
+    receiver: Receiver<PacketBatch>,
+    udp_sender: impl ChannelSend<PacketBatch>,
+    quic_sender: AsyncSender<(SocketAddr, Bytes)>,
+    exit: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    const RECV_TIMEOUT: Duration = Duration::from_secs(1);
+    Builder::new()
+        .name("solGossipQuicTx".to_string())
+        .spawn(move || {
+            let mut quic_addrs = HashMap::new();
+            let mut last_refresh: Option<Instant> = None;
+            while !exit.load(Ordering::Relaxed) {
+                let mut packet_batch = match receiver.recv_timeout(RECV_TIMEOUT) {
+                    Ok(packet_batch) => packet_batch,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                };
+                if last_refresh.is_none_or(|last| last.elapsed() >= ROUTER_REFRESH_INTERVAL) {
+                    quic_addrs = cluster_info.gossip_quic_addrs();
+                    last_refresh = Some(Instant::now());
+                }
+                let mut num_udp_packets = 0;
+                for packet in packet_batch.iter_mut() {
+                    let Some(quic_addr) = quic_addrs.get(&packet.meta().socket_addr()) else {
+                        num_udp_packets += 1;
+                        continue;
+                    };
+                    let Some(bytes) = packet.data(..).map(Bytes::copy_from_slice) else {
+                        continue;
+                    };
+                    match quic_sender.try_send((*quic_addr, bytes)) {
+                        Ok(()) => packet.meta_mut().set_discard(true),
+                        // Fall back to UDP while the endpoint is backed up.
+                        Err(TrySendError::Full(_)) => num_udp_packets += 1,
+                        Err(TrySendError::Closed(_)) => return,
+                    }
+                }
+                if num_udp_packets > 0 && udp_sender.send(packet_batch).is_err() {
+                    return;
+                }
+            }
+        })
+        .unwrap()
+}
+
+/// Hands the packets received through the endpoint to the gossip service,
+/// the same way as the packets received over UDP.
+pub(crate) fn spawn_quic_receiver(
+    receiver: Receiver<(Pubkey, SocketAddr, Bytes)>,
+    sender: impl ChannelSend<PacketBatch>,
+    exit: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    const RECV_TIMEOUT: Duration = Duration::from_secs(1);
+    const PACKET_COALESCE_DURATION: Duration = Duration::from_millis(1);
+    Builder::new()
+        .name("solGossipQuicRx".to_string())
+        .spawn(move || {
+            let recycler = PacketBatchRecycler::default();
+            while !exit.load(Ordering::Relaxed) {
+                let entry = match receiver.recv_timeout(RECV_TIMEOUT) {
+                    Ok(entry) => entry,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                };
+                let mut packet_batch =
+                    PacketBatch::new_with_recycler(&recycler, PACKETS_PER_BATCH, "gossip_quic");
+                unsafe {
+                    packet_batch.set_len(PACKETS_PER_BATCH);
+                };
+                let deadline = Instant::now() + PACKET_COALESCE_DURATION;
+                let entries = std::iter::once(entry).chain(
+                    std::iter::repeat_with(|| receiver.recv_deadline(deadline).ok()).while_some(),
+                );
+                let size = entries
+                    .filter(|(_, _, bytes)| bytes.len() <= PACKET_DATA_SIZE)
+                    .zip(packet_batch.iter_mut())
+                    .map(|((_pubkey, addr, bytes), packet)| {
+                        *packet.meta_mut() = Meta {
+                            size: bytes.len(),
+                            addr: addr.ip(),
+                            port: addr.port(),
+                            flags: PacketFlags::empty(),
+                        };
+                        packet.buffer_mut()[..bytes.len()].copy_from_slice(&bytes);
+                    })
+                    .count();
+                if size > 0 {
+                    packet_batch.truncate(size);
+                    if sender.send(packet_batch).is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+        .unwrap()
+}
+
+#[derive(Default)]
+struct GossipQuicStats {
+    connect_error_invalid_remote_address: AtomicU64,
+    connect_error_other: AtomicU64,
+    connection_error_application_closed: AtomicU64,
+    connection_error_connection_closed: AtomicU64,
+    connection_error_locally_closed: AtomicU64,
+    connection_error_reset: AtomicU64,
+    connection_error_timed_out: AtomicU64,
+    connection_error_transport_error: AtomicU64,
+    connection_error_version_mismatch: AtomicU64,
+    connection_error_cids_exhausted: AtomicU64,
+    connections_evicted: AtomicU64,
+    invalid_identity: AtomicU64,
+    read_to_end_error: AtomicU64,
+    router_try_send_error_full: AtomicU64,
+    streams_received: AtomicU64,
+    streams_sent: AtomicU64,
+    write_error: AtomicU64,
+}
+
+async fn report_metrics_task(name: &'static str, stats: Arc<GossipQuicStats>) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        report_metrics(name, &stats);
+    }
+}
+
+fn record_error(err: &Error, stats: &GossipQuicStats) {
+    match err {
+        Error::ChannelSendError => (),
+        Error::ClosedStream(_) => add_metric!(stats.write_error),
+        Error::ConnectError(ConnectError::InvalidRemoteAddress(_)) => {
+            add_metric!(stats.connect_error_invalid_remote_address)
+        }
+        Error::ConnectError(_) => add_metric!(stats.connect_error_other),
+        Error::ConnectionError(ConnectionError::VersionMismatch) => {
+            add_metric!(stats.connection_error_version_mismatch)
+        }
+        Error::ConnectionError(ConnectionError::TransportError(_)) => {
+            add_metric!(stats.connection_error_transport_error)
+        }
+        Error::ConnectionError(ConnectionError::ConnectionClosed(_)) => {
+            add_metric!(stats.connection_error_connection_closed)
+        }
+        Error::ConnectionError(ConnectionError::ApplicationClosed(_)) => {
+            add_metric!(stats.connection_error_application_closed)
+        }
+        Error::ConnectionError(ConnectionError::Reset) => add_metric!(stats.connection_error_reset),
+        Error::ConnectionError(ConnectionError::TimedOut) => {
+            add_metric!(stats.connection_error_timed_out)
+        }
+        Error::ConnectionError(ConnectionError::LocallyClosed) => {
+            add_metric!(stats.connection_error_locally_closed)
+        }
+        Error::ConnectionError(ConnectionError::CidsExhausted) => {
+            add_metric!(stats.connection_error_cids_exhausted)
+        }
+        Error::InvalidIdentity(_) => add_metric!(stats.invalid_identity),
+        Error::IoError(_) => (),
+        Error::ReadToEndError(_) => add_metric!(stats.read_to_end_error),
+        Error::TlsError(_) => (),
+        Error::WriteError(_) => add_metric!(stats.write_error),
+    }
+}
+
+fn report_metrics(name: &'static str, stats: &GossipQuicStats) {
+    macro_rules! reset_metric {
+        ($metric: expr) => {
+            $metric.swap(0, Ordering::Relaxed)
+        };
+    }
+    datapoint_info!(
+        name,
+        (
+            "connect_error_invalid_remote_address",
+            reset_metric!(stats.connect_error_invalid_remote_address),
+            i64
+        ),
+        (
+            "connect_error_other",
+            reset_metric!(stats.connect_error_other),
+            i64
+        ),
+        (
+            "connection_error_application_closed",
+            reset_metric!(stats.connection_error_application_closed),
+            i64
+        ),
+        (
+            "connection_error_connection_closed",
+            reset_metric!(stats.connection_error_connection_closed),
+            i64
+        ),
+        (
+            "connection_error_locally_closed",
+            reset_metric!(stats.connection_error_locally_closed),
+            i64
+        ),
+        (
+            "connection_error_reset",
+            reset_metric!(stats.connection_error_reset),
+            i64
+        ),
+        (
+            "connection_error_timed_out",
+            reset_metric!(stats.connection_error_timed_out),
+            i64
+        ),
+        (
+            "connection_error_transport_error",
+            reset_metric!(stats.connection_error_transport_error),
+            i64
+        ),
+        (
+            "connection_error_version_mismatch",
+            reset_metric!(stats.connection_error_version_mismatch),
+            i64
+        ),
+        (
+            "connection_error_cids_exhausted",
+            reset_metric!(stats.connection_error_cids_exhausted),
+            i64
+        ),
+        (
+            "connections_evicted",
+            reset_metric!(stats.connections_evicted),
+            i64
+        ),
+        (
+            "invalid_identity",
+            reset_metric!(stats.invalid_identity),
+            i64
+        ),
+        (
+            "read_to_end_error",
+            reset_metric!(stats.read_to_end_error),
+            i64
+        ),
+        (
+            "router_try_send_error_full",
+            reset_metric!(stats.router_try_send_error_full),
+            i64
+        ),
+        (
+            "streams_received",
+            reset_metric!(stats.streams_received),
+            i64
+        ),
+        ("streams_sent", reset_metric!(stats.streams_sent), i64),
+        ("write_error", reset_metric!(stats.write_error), i64),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        itertools::{izip, multiunzip},
+        solana_net_utils::bind_to_localhost,
+        solana_signer::Signer,
+        std::iter::repeat_with,
+    };
+
+    #[test]
+    fn test_quic_endpoint() {
+        const NUM_ENDPOINTS: usize = 3;
+        const RECV_TIMEOUT: Duration = Duration::from_secs(60);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(8)
+            .enable_all()
+            .build()
+            .unwrap();
+        let keypairs: Vec<Keypair> = repeat_with(Keypair::new).take(NUM_ENDPOINTS).collect();
+        let sockets: Vec<UdpSocket> = repeat_with(bind_to_localhost)
+            .take(NUM_ENDPOINTS)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let addresses: Vec<SocketAddr> = sockets
+            .iter()
+            .map(UdpSocket::local_addr)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            repeat_with(crossbeam_channel::unbounded::<(Pubkey, SocketAddr, Bytes)>)
+                .take(NUM_ENDPOINTS)
+                .unzip();
+        let (endpoints, senders, tasks): (Vec<_>, Vec<_>, Vec<_>) =
+            multiunzip(keypairs.iter().zip(sockets).zip(senders).map(
+                |((keypair, socket), sender)| {
+                    new_quic_endpoint(runtime.handle(), keypair, socket, sender).unwrap()
+                },
+            ));
+        // Send a unique message, as large as a gossip packet can be, from
+        // each endpoint to every other endpoint.
+        let new_bytes = |i: usize, j: usize| {
+            let mut bytes = format!("{i}=>{j}").into_bytes();
+            bytes.resize(PACKET_DATA_SIZE, 0xa5);
+            Bytes::from(bytes)
+        };
+        for (i, (keypair, &address, sender)) in izip!(&keypairs, &addresses, &senders).enumerate() {
+            for (j, &address) in addresses.iter().enumerate() {
+                if i != j {
+                    sender.blocking_send((address, new_bytes(i, j))).unwrap();
+                }
+            }
+            // Verify all messages are received.
+            for (j, receiver) in receivers.iter().enumerate() {
+                if i != j {
+                    let entry = (keypair.pubkey(), address, new_bytes(i, j));
+                    assert_eq!(receiver.recv_timeout(RECV_TIMEOUT).unwrap(), entry);
+                }
+            }
+        }
+        drop(senders);
+        for endpoint in endpoints {
+            close_quic_endpoint(&endpoint);
+        }
+        for task in tasks {
+            runtime.block_on(task).unwrap();
+        }
+    }
+}
@@ -47,6 +47,7 @@ fn test_node(exit: Arc<AtomicBool>) -> (Arc<ClusterInfo>, GossipService, UdpSock
         None,
         test_node.sockets.gossip,
         None,
+        None,
         true, // should_check_duplicate_instance
         None,
         exit,
@@ -75,6 +76,7 @@ fn test_node_with_bank(
         Some(bank_forks),
         test_node.sockets.gossip,
         None,
+        None,
         true, // should_check_duplicate_instance
         None,
         exit,
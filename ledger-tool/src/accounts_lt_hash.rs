@@ -0,0 +1,406 @@
+//! The `accounts-lt-hash` subcommand: audits the accounts lt hash
+//!
+//! When a bank is frozen, its accounts lt hash is updated incrementally from the accounts lt hash
+//! of its parent, with the accounts stored in its slot. `audit` replays the ledger and
+//! cross-checks this incremental value against two recalculations:
+//! - at every slot, from the accounts lt hash of the parent and the accounts stored in the slot,
+//!   whose previous versions are loaded from accounts-db instead of from the cache used while
+//!   freezing, and
+//! - at every checkpoint, from all the accounts, which is slow.
+//!
+//! As the accounts lt hash of a bank is derived from the one of its parent, every checkpoint after
+//! the first diverged slot diverges as well, while the recalculation from the parent only diverges
+//! at the slots whose update went wrong.
+
+use {
+    crate::{args::*, ledger_path::canonicalize_ledger_path, ledger_utils::*},
+    clap::{value_t, value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    log::*,
+    serde_derive::Serialize,
+    solana_accounts_db::accounts_hash::AccountsLtHash,
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_ledger::blockstore_processor::ProcessOptions,
+    solana_runtime::bank::Bank,
+    solana_sdk::clock::Slot,
+    std::{
+        fmt::{self, Display, Formatter},
+        path::Path,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// The number of slots between two recalculations from all the accounts by default
+const DEFAULT_CHECKPOINT_INTERVAL: &str = "1000";
+
+pub trait AccountsLtHashSubCommand {
+    fn accounts_lt_hash_subcommand(self) -> Self;
+}
+
+impl AccountsLtHashSubCommand for App<'_, '_> {
+    fn accounts_lt_hash_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("accounts-lt-hash")
+                .about("Accounts lt hash utilities")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("audit")
+                        .about(
+                            "Replay the ledger and cross-check the accounts lt hash updated \
+                             incrementally by each bank against recalculations of it, reporting \
+                             the first slot where they diverge",
+                        )
+                        .arg(load_genesis_arg())
+                        .args(&accounts_db_args())
+                        .args(&snapshot_args())
+                        .arg(
+                            Arg::with_name("halt_at_slot")
+                                .long("halt-at-slot")
+                                .value_name("SLOT")
+                                .validator(is_slot)
+                                .takes_value(true)
+                                .help("Halt processing at the given slot"),
+                        )
+                        .arg(
+                            Arg::with_name("start_slot")
+                                .long("start-slot")
+                                .value_name("SLOT")
+                                .validator(is_slot)
+                                .takes_value(true)
+                                .help(
+                                    "Audit the slots from this one on [default: the first \
+                                     replayed slot]",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("checkpoint_interval")
+                                .long("checkpoint-interval")
+                                .value_name("NUM_SLOTS")
+                                .validator(is_parsable::<u64>)
+                                .takes_value(true)
+                                .default_value(DEFAULT_CHECKPOINT_INTERVAL)
+                                .help(
+                                    "Recalculate the accounts lt hash from all the accounts at \
+                                     the first audited slot, then every this many slots, and at \
+                                     the last replayed slot",
+                                ),
+                        )
+                        .after_help(
+                            "The accounts lt hash must be enabled, either by its feature gate or \
+                             by --accounts-db-experimental-accumulator-hash. The accounts lt hash \
+                             of the bank loaded from the snapshot is not audited, see \
+                             --accounts-db-verify-experimental-accumulator-hash.",
+                        ),
+                ),
+        )
+    }
+}
+
+pub fn accounts_lt_hash_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    match matches.subcommand() {
+        ("audit", Some(arg_matches)) => {
+            let start_slot = value_t!(arg_matches, "start_slot", Slot).unwrap_or_default();
+            let checkpoint_interval = value_t_or_exit!(arg_matches, "checkpoint_interval", u64);
+            let output_format = OutputFormat::from_matches(arg_matches, "output_format", false);
+
+            let auditor = Arc::new(AccountsLtHashAuditor::new(start_slot, checkpoint_interval));
+            let process_options = ProcessOptions {
+                slot_callback: Some({
+                    let auditor = auditor.clone();
+                    Arc::new(move |bank: &Bank| auditor.audit_frozen_bank(bank))
+                }),
+                ..parse_process_options(&ledger_path, arg_matches)
+            };
+            let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+            let blockstore =
+                open_blockstore(&ledger_path, arg_matches, get_access_type(&process_options));
+            let LoadAndProcessLedgerOutput { bank_forks, .. } = load_and_process_ledger_or_exit(
+                arg_matches,
+                &genesis_config,
+                Arc::new(blockstore),
+                process_options,
+                None,
+            );
+            let working_bank = bank_forks.read().unwrap().working_bank();
+            let report = auditor.finish(&working_bank);
+            println!("{}", output_format.formatted_string(&report));
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Which recalculation of the accounts lt hash diverged from the incremental one
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Recalculation {
+    /// From the accounts lt hash of the parent and the accounts stored in the slot
+    FromParent,
+    /// From all the accounts
+    FromAllAccounts,
+}
+
+impl Display for Recalculation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::FromParent => write!(f, "from the parent"),
+            Self::FromAllAccounts => write!(f, "from all the accounts"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsLtHashDivergence {
+    pub slot: Slot,
+    pub recalculation: Recalculation,
+    pub incremental_checksum: String,
+    pub recalculated_checksum: String,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsLtHashAuditReport {
+    pub start_slot: Slot,
+    pub num_slots_audited: usize,
+    /// The slots not audited as their accounts lt hash is not enabled
+    pub num_slots_disabled: usize,
+    /// The slots recalculated from all the accounts, in increasing slot order
+    pub checkpoints: Vec<Slot>,
+    /// In increasing slot order
+    pub divergences: Vec<AccountsLtHashDivergence>,
+}
+
+impl AccountsLtHashAuditReport {
+    pub fn first_diverged_slot(&self) -> Option<Slot> {
+        self.divergences.first().map(|divergence| divergence.slot)
+    }
+
+    /// The last checkpoint before the first diverged slot
+    pub fn last_matched_checkpoint(&self) -> Option<Slot> {
+        let first_diverged_slot = self.first_diverged_slot().unwrap_or(Slot::MAX);
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|&&checkpoint| checkpoint < first_diverged_slot)
+            .copied()
+    }
+}
+
+impl VerboseDisplay for AccountsLtHashAuditReport {}
+impl QuietDisplay for AccountsLtHashAuditReport {}
+
+impl Display for AccountsLtHashAuditReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Audited {} slots from slot {}, recalculated from all the accounts at {} checkpoints",
+            self.num_slots_audited,
+            self.start_slot,
+            self.checkpoints.len(),
+        )?;
+        if self.num_slots_disabled > 0 {
+            writeln!(
+                f,
+                "Skipped {} slots whose accounts lt hash is not enabled",
+                self.num_slots_disabled
+            )?;
+        }
+        match self.first_diverged_slot() {
+            Some(first_diverged_slot) => {
+                writeln!(f, "First diverged slot: {first_diverged_slot}")?;
+            }
+            None => writeln!(f, "No divergence")?,
+        }
+        if let Some(last_matched_checkpoint) = self.last_matched_checkpoint() {
+            writeln!(f, "Last matched checkpoint: {last_matched_checkpoint}")?;
+        }
+        for divergence in &self.divergences {
+            writeln!(
+                f,
+                "Slot {}: recalculated {}: {} incremental: {}",
+                divergence.slot,
+                divergence.recalculation,
+                divergence.recalculated_checksum,
+                divergence.incremental_checksum,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct AuditState {
+    report: AccountsLtHashAuditReport,
+    /// The slot from which the next frozen bank is recalculated from all the accounts
+    next_checkpoint_slot: Slot,
+    last_audited_slot: Option<Slot>,
+}
+
+/// Audits the accounts lt hash of the banks frozen by replay
+pub struct AccountsLtHashAuditor {
+    start_slot: Slot,
+    checkpoint_interval: u64,
+    state: Mutex<AuditState>,
+}
+
+impl AccountsLtHashAuditor {
+    /// Creates an auditor of the slots from `start_slot` on, recalculating the accounts lt hash
+    /// from all the accounts every `checkpoint_interval` slots. A `checkpoint_interval` of zero
+    /// only recalculates it at the first and last audited slots.
+    pub fn new(start_slot: Slot, checkpoint_interval: u64) -> Self {
+        Self {
+            start_slot,
+            checkpoint_interval,
+            state: Mutex::new(AuditState {
+                report: AccountsLtHashAuditReport {
+                    start_slot,
+                    ..AccountsLtHashAuditReport::default()
+                },
+                next_checkpoint_slot: start_slot,
+                last_audited_slot: None,
+            }),
+        }
+    }
+
+    /// Audits the accounts lt hash of the frozen `bank`
+    pub fn audit_frozen_bank(&self, bank: &Bank) {
+        if bank.slot() < self.start_slot {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if !bank.is_accounts_lt_hash_enabled() {
+            state.report.num_slots_disabled += 1;
+            return;
+        }
+        state.report.num_slots_audited += 1;
+        state.last_audited_slot = Some(bank.slot());
+        let incremental = bank.accounts_lt_hash();
+        if let Some(recalculated) = bank.recalculate_accounts_lt_hash_from_parent() {
+            state.compare(
+                bank.slot(),
+                Recalculation::FromParent,
+                &incremental,
+                &recalculated,
+            );
+        }
+        if bank.slot() >= state.next_checkpoint_slot {
+            Self::checkpoint(&mut state, bank, &incremental);
+            state.next_checkpoint_slot = if self.checkpoint_interval == 0 {
+                Slot::MAX
+            } else {
+                bank.slot().saturating_add(self.checkpoint_interval)
+            };
+        }
+    }
+
+    /// Recalculates the accounts lt hash of the last replayed bank from all the accounts, unless
+    /// it was just done, and returns the report.
+    pub fn finish(&self, last_bank: &Bank) -> AccountsLtHashAuditReport {
+        let mut state = self.state.lock().unwrap();
+        if state.last_audited_slot == Some(last_bank.slot())
+            && state.report.checkpoints.last() != Some(&last_bank.slot())
+        {
+            Self::checkpoint(&mut state, last_bank, &last_bank.accounts_lt_hash());
+        }
+        let mut report = state.report.clone();
+        report.checkpoints.sort_unstable();
+        report.divergences.sort_by_key(|divergence| divergence.slot);
+        report
+    }
+
+    fn checkpoint(state: &mut AuditState, bank: &Bank, incremental: &AccountsLtHash) {
+        info!(
+            "Recalculating the accounts lt hash of slot {} from all the accounts",
+            bank.slot()
+        );
+        let recalculated = bank.recalculate_accounts_lt_hash();
+        state.report.checkpoints.push(bank.slot());
+        state.compare(
+            bank.slot(),
+            Recalculation::FromAllAccounts,
+            incremental,
+            &recalculated,
+        );
+    }
+}
+
+impl AuditState {
+    fn compare(
+        &mut self,
+        slot: Slot,
+        recalculation: Recalculation,
+        incremental: &AccountsLtHash,
+        recalculated: &AccountsLtHash,
+    ) {
+        if incremental != recalculated {
+            let divergence = AccountsLtHashDivergence {
+                slot,
+                recalculation,
+                incremental_checksum: incremental.0.checksum().to_string(),
+                recalculated_checksum: recalculated.0.checksum().to_string(),
+            };
+            warn!("Accounts lt hash diverged: {divergence:?}");
+            self.report.divergences.push(divergence);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::{account::AccountSharedData, pubkey::Pubkey},
+    };
+
+    #[test]
+    fn test_accounts_lt_hash_auditor() {
+        let genesis_config = create_genesis_config(1_000_000).genesis_config;
+        let (bank0, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        bank0
+            .rc
+            .accounts
+            .accounts_db
+            .set_is_experimental_accumulator_hash_enabled(true);
+        bank0.freeze();
+        let auditor = AccountsLtHashAuditor::new(1, 2);
+        // Slots before the start slot are not audited.
+        auditor.audit_frozen_bank(&bank0);
+
+        let mut parent = bank0;
+        for slot in 1..=4 {
+            let bank = Arc::new(Bank::new_from_parent(parent, &Pubkey::default(), slot));
+            bank.freeze();
+            if slot == 3 {
+                // An account the incremental update missed
+                let account = AccountSharedData::new(1, 0, &Pubkey::default());
+                bank.rc
+                    .accounts
+                    .accounts_db
+                    .store_cached((slot, &[(&Pubkey::new_unique(), &account)][..]), None);
+            }
+            auditor.audit_frozen_bank(&bank);
+            parent = bank;
+        }
+        let report = auditor.finish(&parent);
+
+        assert_eq!(report.num_slots_audited, 4);
+        assert_eq!(report.num_slots_disabled, 0);
+        assert_eq!(report.checkpoints, vec![1, 3, 4]);
+        let divergences: Vec<_> = report
+            .divergences
+            .iter()
+            .map(|divergence| (divergence.slot, divergence.recalculation))
+            .collect();
+        assert_eq!(
+            divergences,
+            vec![
+                (3, Recalculation::FromParent),
+                (3, Recalculation::FromAllAccounts),
+                (4, Recalculation::FromAllAccounts),
+            ]
+        );
+        assert_eq!(report.first_diverged_slot(), Some(3));
+        assert_eq!(report.last_matched_checkpoint(), Some(1));
+    }
+}
@@ -36,7 +36,7 @@ use {
         clock::Slot, hash::Hash, pubkey::Pubkey, shred_version::compute_shred_version,
         signature::Signature, signer::keypair::keypair_from_seed,
     },
-    solana_storage_bigtable::CredentialType,
+    solana_storage_bigtable::{CredentialType, LongTermStorage},
     solana_transaction_status::{ConfirmedBlock, UiTransactionEncoding, VersionedConfirmedBlock},
     std::{
         cmp::min,
@@ -56,9 +56,11 @@ async fn upload(
     force_reupload: bool,
     config: solana_storage_bigtable::LedgerStorageConfig,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
-        .await
-        .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
+    let bigtable: Arc<dyn LongTermStorage> = Arc::new(
+        solana_storage_bigtable::LedgerStorage::new_with_config(config)
+            .await
+            .map_err(|err| format!("Failed to connect to storage: {err:?}"))?,
+    );
 
     let config = ConfirmedBlockUploadConfig {
         force_reupload,
@@ -126,6 +126,7 @@ fn analyze_storage(blockstore: &Blockstore) -> Result<()> {
     analyze_column(blockstore, ShredCode::NAME)?;
     analyze_column(blockstore, TransactionStatus::NAME)?;
     analyze_column(blockstore, AddressSignatures::NAME)?;
+    analyze_column(blockstore, ProgramSignatures::NAME)?;
     analyze_column(blockstore, TransactionMemos::NAME)?;
     analyze_column(blockstore, TransactionStatusIndex::NAME)?;
     analyze_column(blockstore, Rewards::NAME)?;
@@ -154,6 +155,9 @@ fn raw_key_to_slot(key: &[u8], column_name: &str) -> Option<Slot> {
         cf::AddressSignatures::NAME => Some(cf::AddressSignatures::slot(
             cf::AddressSignatures::index(key),
         )),
+        cf::ProgramSignatures::NAME => Some(cf::ProgramSignatures::slot(
+            cf::ProgramSignatures::index(key),
+        )),
         cf::TransactionMemos::NAME => None, // does not implement slot()
         cf::TransactionStatusIndex::NAME => None, // does not implement slot()
         cf::Rewards::NAME => Some(cf::Rewards::slot(cf::Rewards::index(key))),
@@ -0,0 +1,683 @@
+//! The `export-parquet` subcommand: streams the rooted blocks of a slot range
+//! into Parquet files for data-warehouse ingestion.
+//!
+//! Each table is written to `<OUTPUT_DIR>/<TABLE>/slot_partition=<SLOT>/`,
+//! where `<SLOT>` is the zero-padded first slot of the partition, so that the
+//! output can be loaded as a hive-partitioned dataset. The tables are:
+//!   * blocks: one row per block.
+//!   * transactions: one row per transaction, in block order.
+//!   * rewards: one row per block reward.
+//!
+//! Columns are only ever added to the end of the schemas, in which case
+//! SCHEMA_VERSION is bumped; it is recorded in the metadata of every file.
+
+use {
+    crate::{
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+        ledger_utils::*,
+    },
+    arrow_array::{
+        builder::{
+            ArrayBuilder, BooleanBuilder, Int64Builder, ListBuilder, StringBuilder, UInt32Builder,
+            UInt64Builder, UInt8Builder,
+        },
+        ArrayRef, RecordBatch,
+    },
+    arrow_schema::{DataType, Field, Schema, SchemaRef},
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    parquet::{
+        arrow::ArrowWriter,
+        basic::{Compression, ZstdLevel},
+        file::properties::WriterProperties,
+        format::KeyValue,
+    },
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_ledger::{
+        blockstore::{Blockstore, BlockstoreError},
+        blockstore_options::AccessType,
+    },
+    solana_sdk::{clock::Slot, message::VersionedMessage},
+    solana_transaction_status::{
+        Reward, VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
+    },
+    std::{
+        fs::{self, File},
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+const SCHEMA_VERSION: &str = "1";
+const SCHEMA_VERSION_KEY: &str = "agave.export.schema_version";
+const DEFAULT_SLOTS_PER_PARTITION: &str = "10000";
+// Rows are buffered in memory up to this many per table before being written.
+const MAX_BUFFERED_ROWS: usize = 64 * 1024;
+
+pub trait ExportParquetSubCommand {
+    fn export_parquet_subcommand(self) -> Self;
+}
+
+impl ExportParquetSubCommand for App<'_, '_> {
+    fn export_parquet_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("export-parquet")
+                .about(
+                    "Export the blocks, transactions and rewards of the rooted slots of a slot \
+                     range to partitioned Parquet files",
+                )
+                .arg(
+                    Arg::with_name("starting_slot")
+                        .long("starting-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .help("Start exporting at this slot [default: first available block]"),
+                )
+                .arg(
+                    Arg::with_name("ending_slot")
+                        .long("ending-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .help("Stop exporting at this slot, inclusive [default: highest root]"),
+                )
+                .arg(
+                    Arg::with_name("output_dir")
+                        .long("output-dir")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Directory to write the tables to"),
+                )
+                .arg(
+                    Arg::with_name("slots_per_partition")
+                        .long("slots-per-partition")
+                        .value_name("NUM")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .default_value(DEFAULT_SLOTS_PER_PARTITION)
+                        .help("Number of slots in each partition of the tables"),
+                ),
+        )
+    }
+}
+
+pub fn export_parquet_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    do_export_parquet_process_command(ledger_path, matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_export_parquet_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -> Result<()> {
+    let output_dir = value_t_or_exit!(matches, "output_dir", PathBuf);
+    let slots_per_partition = value_t_or_exit!(matches, "slots_per_partition", u64);
+    if slots_per_partition == 0 {
+        return Err(LedgerToolError::BadArgument(
+            "--slots-per-partition must be greater than 0".to_string(),
+        ));
+    }
+
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let blockstore = open_blockstore(&ledger_path, matches, AccessType::Secondary);
+    let starting_slot = match value_t!(matches, "starting_slot", Slot) {
+        Ok(slot) => slot,
+        Err(_) => blockstore.get_first_available_block()?,
+    };
+    let ending_slot =
+        value_t!(matches, "ending_slot", Slot).unwrap_or_else(|_| blockstore.max_root());
+    if ending_slot < starting_slot {
+        return Err(LedgerToolError::BadArgument(format!(
+            "ending slot {ending_slot} is lower than starting slot {starting_slot}"
+        )));
+    }
+
+    let mut exporter = ParquetExporter::new(&output_dir, slots_per_partition);
+    export_blocks(&blockstore, starting_slot, ending_slot, &mut exporter)?;
+    let num_blocks = exporter.finish()?;
+    println!(
+        "Exported {num_blocks} blocks of slots {starting_slot}..={ending_slot} to {}",
+        output_dir.display()
+    );
+    Ok(())
+}
+
+fn export_blocks(
+    blockstore: &Blockstore,
+    starting_slot: Slot,
+    ending_slot: Slot,
+    exporter: &mut ParquetExporter,
+) -> Result<()> {
+    for slot in blockstore
+        .rooted_slot_iterator(starting_slot)?
+        .take_while(|&slot| slot <= ending_slot)
+    {
+        // The previous blockhash is not required so that the first block of a
+        // purged ledger can be exported too.
+        match blockstore.get_rooted_block(slot, false) {
+            Ok(block) => exporter.append_block(slot, block)?,
+            // Roots without shreds, such as the slots of snapshots the node
+            // started from, have no block to export.
+            Err(BlockstoreError::SlotUnavailable) => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Rows of a table, buffered in column builders
+trait TableRows: Default {
+    const NAME: &'static str;
+
+    fn schema() -> Schema;
+
+    fn num_rows(&self) -> usize;
+
+    /// Returns the columns of the buffered rows and resets the builders.
+    fn finish(&mut self) -> Vec<ArrayRef>;
+}
+
+struct Table<R> {
+    rows: R,
+    schema: SchemaRef,
+    dir: PathBuf,
+    writer: Option<PartitionWriter>,
+}
+
+// Writes a partition to a temporary file, which is renamed once complete so
+// that readers never see a partial partition.
+struct PartitionWriter {
+    writer: ArrowWriter<File>,
+    tmp_path: PathBuf,
+    path: PathBuf,
+}
+
+impl PartitionWriter {
+    fn create(table_dir: &Path, partition_slot: Slot, schema: SchemaRef) -> Result<Self> {
+        let dir = table_dir.join(format!("slot_partition={partition_slot:020}"));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("data.parquet");
+        let tmp_path = dir.join("data.parquet.tmp");
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                SCHEMA_VERSION_KEY.to_string(),
+                SCHEMA_VERSION.to_string(),
+            )]))
+            .build();
+        let writer = ArrowWriter::try_new(File::create(&tmp_path)?, schema, Some(properties))?;
+        Ok(Self {
+            writer,
+            tmp_path,
+            path,
+        })
+    }
+
+    fn close(self) -> Result<()> {
+        self.writer.close()?;
+        fs::rename(&self.tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl<R: TableRows> Table<R> {
+    fn new(output_dir: &Path) -> Self {
+        Self {
+            rows: R::default(),
+            schema: Arc::new(R::schema()),
+            dir: output_dir.join(R::NAME),
+            writer: None,
+        }
+    }
+
+    fn maybe_flush(&mut self, partition_slot: Slot) -> Result<()> {
+        if self.rows.num_rows() >= MAX_BUFFERED_ROWS {
+            self.flush(partition_slot)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, partition_slot: Slot) -> Result<()> {
+        if self.rows.num_rows() == 0 {
+            return Ok(());
+        }
+        if self.writer.is_none() {
+            self.writer = Some(PartitionWriter::create(
+                &self.dir,
+                partition_slot,
+                Arc::clone(&self.schema),
+            )?);
+        }
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), self.rows.finish())?;
+        self.writer.as_mut().unwrap().writer.write(&batch)?;
+        Ok(())
+    }
+
+    fn close_partition(&mut self, partition_slot: Slot) -> Result<()> {
+        self.flush(partition_slot)?;
+        if let Some(writer) = self.writer.take() {
+            writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+struct ParquetExporter {
+    slots_per_partition: u64,
+    // First slot of the partition being written
+    partition_slot: Option<Slot>,
+    num_blocks: usize,
+    blocks: Table<BlockRows>,
+    transactions: Table<TransactionRows>,
+    rewards: Table<RewardRows>,
+}
+
+impl ParquetExporter {
+    fn new(output_dir: &Path, slots_per_partition: u64) -> Self {
+        Self {
+            slots_per_partition,
+            partition_slot: None,
+            num_blocks: 0,
+            blocks: Table::new(output_dir),
+            transactions: Table::new(output_dir),
+            rewards: Table::new(output_dir),
+        }
+    }
+
+    fn append_block(&mut self, slot: Slot, block: VersionedConfirmedBlock) -> Result<()> {
+        let partition_slot = slot - slot % self.slots_per_partition;
+        if let Some(previous_partition_slot) = self.partition_slot {
+            if previous_partition_slot != partition_slot {
+                self.close_partition(previous_partition_slot)?;
+            }
+        }
+        self.partition_slot = Some(partition_slot);
+
+        self.blocks.rows.append(slot, &block);
+        for (index, transaction) in block.transactions.iter().enumerate() {
+            self.transactions
+                .rows
+                .append(slot, index as u32, transaction);
+        }
+        for reward in &block.rewards {
+            self.rewards.rows.append(slot, reward);
+        }
+        self.num_blocks += 1;
+
+        self.blocks.maybe_flush(partition_slot)?;
+        self.transactions.maybe_flush(partition_slot)?;
+        self.rewards.maybe_flush(partition_slot)
+    }
+
+    fn close_partition(&mut self, partition_slot: Slot) -> Result<()> {
+        self.blocks.close_partition(partition_slot)?;
+        self.transactions.close_partition(partition_slot)?;
+        self.rewards.close_partition(partition_slot)
+    }
+
+    /// Writes the remaining rows and returns the number of exported blocks.
+    fn finish(mut self) -> Result<usize> {
+        if let Some(partition_slot) = self.partition_slot {
+            self.close_partition(partition_slot)?;
+        }
+        Ok(self.num_blocks)
+    }
+}
+
+fn string_list() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)))
+}
+
+#[derive(Default)]
+struct BlockRows {
+    slot: UInt64Builder,
+    parent_slot: UInt64Builder,
+    blockhash: StringBuilder,
+    previous_blockhash: StringBuilder,
+    block_time: Int64Builder,
+    block_height: UInt64Builder,
+    num_transactions: UInt64Builder,
+    num_rewards: UInt64Builder,
+}
+
+impl BlockRows {
+    fn append(&mut self, slot: Slot, block: &VersionedConfirmedBlock) {
+        self.slot.append_value(slot);
+        self.parent_slot.append_value(block.parent_slot);
+        self.blockhash.append_value(&block.blockhash);
+        self.previous_blockhash
+            .append_value(&block.previous_blockhash);
+        self.block_time.append_option(block.block_time);
+        self.block_height.append_option(block.block_height);
+        self.num_transactions
+            .append_value(block.transactions.len() as u64);
+        self.num_rewards.append_value(block.rewards.len() as u64);
+    }
+}
+
+impl TableRows for BlockRows {
+    const NAME: &'static str = "blocks";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("parent_slot", DataType::UInt64, false),
+            Field::new("blockhash", DataType::Utf8, false),
+            Field::new("previous_blockhash", DataType::Utf8, false),
+            Field::new("block_time", DataType::Int64, true),
+            Field::new("block_height", DataType::UInt64, true),
+            Field::new("num_transactions", DataType::UInt64, false),
+            Field::new("num_rewards", DataType::UInt64, false),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.parent_slot.finish()),
+            Arc::new(self.blockhash.finish()),
+            Arc::new(self.previous_blockhash.finish()),
+            Arc::new(self.block_time.finish()),
+            Arc::new(self.block_height.finish()),
+            Arc::new(self.num_transactions.finish()),
+            Arc::new(self.num_rewards.finish()),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct TransactionRows {
+    slot: UInt64Builder,
+    index: UInt32Builder,
+    signature: StringBuilder,
+    version: StringBuilder,
+    account_keys: ListBuilder<StringBuilder>,
+    num_instructions: UInt32Builder,
+    fee: UInt64Builder,
+    success: BooleanBuilder,
+    error: StringBuilder,
+    compute_units_consumed: UInt64Builder,
+    log_messages: ListBuilder<StringBuilder>,
+}
+
+impl TransactionRows {
+    fn append(&mut self, slot: Slot, index: u32, transaction: &VersionedTransactionWithStatusMeta) {
+        let VersionedTransactionWithStatusMeta { transaction, meta } = transaction;
+        self.slot.append_value(slot);
+        self.index.append_value(index);
+        self.signature.append_value(
+            transaction
+                .signatures
+                .first()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        );
+        self.version.append_value(match transaction.message {
+            VersionedMessage::Legacy(_) => "legacy",
+            VersionedMessage::V0(_) => "0",
+        });
+        // Static keys first, then the loaded writable and readonly addresses,
+        // in the order in which instructions refer to accounts.
+        for key in transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .chain(&meta.loaded_addresses.writable)
+            .chain(&meta.loaded_addresses.readonly)
+        {
+            self.account_keys.values().append_value(key.to_string());
+        }
+        self.account_keys.append(true);
+        self.num_instructions
+            .append_value(transaction.message.instructions().len() as u32);
+        self.fee.append_value(meta.fee);
+        self.success.append_value(meta.status.is_ok());
+        self.error
+            .append_option(meta.status.as_ref().err().map(ToString::to_string));
+        self.compute_units_consumed
+            .append_option(meta.compute_units_consumed);
+        match &meta.log_messages {
+            Some(log_messages) => {
+                for log_message in log_messages {
+                    self.log_messages.values().append_value(log_message);
+                }
+                self.log_messages.append(true);
+            }
+            None => self.log_messages.append(false),
+        }
+    }
+}
+
+impl TableRows for TransactionRows {
+    const NAME: &'static str = "transactions";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("index", DataType::UInt32, false),
+            Field::new("signature", DataType::Utf8, false),
+            Field::new("version", DataType::Utf8, false),
+            Field::new("account_keys", string_list(), false),
+            Field::new("num_instructions", DataType::UInt32, false),
+            Field::new("fee", DataType::UInt64, false),
+            Field::new("success", DataType::Boolean, false),
+            Field::new("error", DataType::Utf8, true),
+            Field::new("compute_units_consumed", DataType::UInt64, true),
+            Field::new("log_messages", string_list(), true),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.index.finish()),
+            Arc::new(self.signature.finish()),
+            Arc::new(self.version.finish()),
+            Arc::new(self.account_keys.finish()),
+            Arc::new(self.num_instructions.finish()),
+            Arc::new(self.fee.finish()),
+            Arc::new(self.success.finish()),
+            Arc::new(self.error.finish()),
+            Arc::new(self.compute_units_consumed.finish()),
+            Arc::new(self.log_messages.finish()),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct RewardRows {
+    slot: UInt64Builder,
+    pubkey: StringBuilder,
+    lamports: Int64Builder,
+    post_balance: UInt64Builder,
+    reward_type: StringBuilder,
+    commission: UInt8Builder,
+}
+
+impl RewardRows {
+    fn append(&mut self, slot: Slot, reward: &Reward) {
+        self.slot.append_value(slot);
+        self.pubkey.append_value(&reward.pubkey);
+        self.lamports.append_value(reward.lamports);
+        self.post_balance.append_value(reward.post_balance);
+        self.reward_type.append_option(
+            reward
+                .reward_type
+                .map(|reward_type| reward_type.to_string()),
+        );
+        self.commission.append_option(reward.commission);
+    }
+}
+
+impl TableRows for RewardRows {
+    const NAME: &'static str = "rewards";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("pubkey", DataType::Utf8, false),
+            Field::new("lamports", DataType::Int64, false),
+            Field::new("post_balance", DataType::UInt64, false),
+            Field::new("reward_type", DataType::Utf8, true),
+            Field::new("commission", DataType::UInt8, true),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.pubkey.finish()),
+            Arc::new(self.lamports.finish()),
+            Arc::new(self.post_balance.finish()),
+            Arc::new(self.reward_type.finish()),
+            Arc::new(self.commission.finish()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        arrow_array::{cast::AsArray, types::UInt64Type},
+        parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signer},
+            system_transaction,
+            transaction::{TransactionError, VersionedTransaction},
+        },
+        solana_transaction_status::{RewardType, TransactionStatusMeta},
+        tempfile::TempDir,
+    };
+
+    fn new_block(parent_slot: Slot, num_transactions: usize) -> VersionedConfirmedBlock {
+        let transactions = (0..num_transactions)
+            .map(|i| {
+                let keypair = Keypair::new();
+                let transaction = system_transaction::transfer(
+                    &keypair,
+                    &keypair.pubkey(),
+                    1,
+                    Hash::new_unique(),
+                );
+                let status = if i % 2 == 0 {
+                    Ok(())
+                } else {
+                    Err(TransactionError::AccountNotFound)
+                };
+                VersionedTransactionWithStatusMeta {
+                    transaction: VersionedTransaction::from(transaction),
+                    meta: TransactionStatusMeta {
+                        status,
+                        fee: 5000,
+                        ..TransactionStatusMeta::default()
+                    },
+                }
+            })
+            .collect();
+        VersionedConfirmedBlock {
+            previous_blockhash: Hash::new_unique().to_string(),
+            blockhash: Hash::new_unique().to_string(),
+            parent_slot,
+            transactions,
+            rewards: vec![Reward {
+                pubkey: Keypair::new().pubkey().to_string(),
+                lamports: 42,
+                post_balance: 1042,
+                reward_type: Some(RewardType::Fee),
+                commission: None,
+            }],
+            num_partitions: None,
+            block_time: Some(1_700_000_000),
+            block_height: Some(parent_slot + 1),
+        }
+    }
+
+    fn read_table(path: &Path) -> Vec<RecordBatch> {
+        let file = File::open(path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let metadata = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap();
+        assert!(metadata
+            .iter()
+            .any(|kv| kv.key == SCHEMA_VERSION_KEY && kv.value.as_deref() == Some(SCHEMA_VERSION)));
+        builder
+            .build()
+            .unwrap()
+            .map(|batch| batch.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parquet_exporter() {
+        let output_dir = TempDir::new().unwrap();
+        let mut exporter = ParquetExporter::new(output_dir.path(), 10);
+        exporter.append_block(8, new_block(7, 2)).unwrap();
+        exporter.append_block(9, new_block(8, 0)).unwrap();
+        exporter.append_block(12, new_block(9, 3)).unwrap();
+        assert_eq!(exporter.finish().unwrap(), 3);
+
+        let partition = |table: &str, slot: Slot| {
+            output_dir
+                .path()
+                .join(table)
+                .join(format!("slot_partition={slot:020}"))
+                .join("data.parquet")
+        };
+        let blocks = read_table(&partition("blocks", 0));
+        assert_eq!(blocks[0].schema().fields(), BlockRows::schema().fields());
+        let slots: Vec<_> = blocks
+            .iter()
+            .flat_map(|batch| {
+                let slots = batch.column_by_name("slot").unwrap();
+                slots.as_primitive::<UInt64Type>().values().to_vec()
+            })
+            .collect();
+        assert_eq!(slots, vec![8, 9]);
+
+        let transactions = read_table(&partition("transactions", 10));
+        assert_eq!(
+            transactions
+                .iter()
+                .map(RecordBatch::num_rows)
+                .sum::<usize>(),
+            3
+        );
+        let transaction = &transactions[0];
+        assert_eq!(
+            transaction.schema().fields(),
+            TransactionRows::schema().fields()
+        );
+        let success = transaction.column_by_name("success").unwrap().as_boolean();
+        assert!(success.value(0));
+        assert!(!success.value(1));
+        let error = transaction.column_by_name("error").unwrap();
+        assert!(error.is_null(0));
+        assert!(!error.is_null(1));
+
+        let rewards = read_table(&partition("rewards", 10));
+        assert_eq!(rewards[0].schema().fields(), RewardRows::schema().fields());
+        assert_eq!(rewards[0].num_rows(), 1);
+        // No temporary files are left behind.
+        assert!(!partition("blocks", 0)
+            .with_extension("parquet.tmp")
+            .exists());
+    }
+}
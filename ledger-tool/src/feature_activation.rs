@@ -0,0 +1,350 @@
+//! Comparison of the replay of a slot range with a feature gate artificially
+//! activated against its canonical replay, for `simulate-feature-activation`.
+//!
+//! The feature is only inserted in the feature set of the replayed banks, the
+//! accounts changes of an actual activation, e.g. the migration of a builtin
+//! program, are not simulated. As the hash of a bank covers the hash of its
+//! parent, every slot descending from the first one with a diverging bank hash
+//! diverges as well, so the divergences of the transactions are more telling.
+
+use {
+    serde_derive::Serialize,
+    solana_runtime::bank::bank_hash_details::{SlotDetails, TransactionDetails},
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{collections::HashMap, fmt},
+};
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDivergence {
+    pub signature: String,
+    pub field: String,
+    pub canonical: String,
+    pub activated: String,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotDivergence {
+    pub slot: Slot,
+    pub canonical_bank_hash: String,
+    pub activated_bank_hash: String,
+    pub transactions: Vec<TransactionDivergence>,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureActivationReport {
+    pub feature_id: String,
+    pub activation_slot: Slot,
+    pub num_slots_compared: usize,
+    /// In increasing slot order
+    pub divergences: Vec<SlotDivergence>,
+    /// Slots replayed by only one of the replays
+    pub unmatched_slots: Vec<Slot>,
+}
+
+impl FeatureActivationReport {
+    /// Compares the slots recorded by the canonical replay with those
+    /// recorded by the replay with `feature_id` activated from
+    /// `activation_slot` on.
+    pub fn new(
+        feature_id: &Pubkey,
+        activation_slot: Slot,
+        canonical: Vec<SlotDetails>,
+        activated: Vec<SlotDetails>,
+    ) -> Self {
+        let mut activated: HashMap<_, _> = activated
+            .into_iter()
+            .map(|details| (details.slot, details))
+            .collect();
+        let mut report = Self {
+            feature_id: feature_id.to_string(),
+            activation_slot,
+            ..Self::default()
+        };
+        for canonical in canonical {
+            let Some(activated) = activated.remove(&canonical.slot) else {
+                report.unmatched_slots.push(canonical.slot);
+                continue;
+            };
+            report.num_slots_compared += 1;
+            let transactions = compare_transactions(&canonical, &activated);
+            if canonical.bank_hash != activated.bank_hash || !transactions.is_empty() {
+                report.divergences.push(SlotDivergence {
+                    slot: canonical.slot,
+                    canonical_bank_hash: canonical.bank_hash,
+                    activated_bank_hash: activated.bank_hash,
+                    transactions,
+                });
+            }
+        }
+        report.unmatched_slots.extend(activated.into_keys());
+        report.unmatched_slots.sort_unstable();
+        report.divergences.sort_by_key(|divergence| divergence.slot);
+        report
+    }
+
+    /// The number of transactions whose outcome diverged
+    pub fn num_diverged_transactions(&self) -> usize {
+        self.divergences
+            .iter()
+            .map(|divergence| {
+                let mut signatures: Vec<_> = divergence
+                    .transactions
+                    .iter()
+                    .map(|transaction| &transaction.signature)
+                    .collect();
+                signatures.dedup();
+                signatures.len()
+            })
+            .sum()
+    }
+}
+
+// Returns the divergences of the fee, status and consumed compute units of the
+// transactions of a slot. Transactions recorded by only one of the replays
+// diverge in their `recorded` field, and those committed by only one of them
+// in their `committed` field.
+fn compare_transactions(
+    canonical: &SlotDetails,
+    activated: &SlotDetails,
+) -> Vec<TransactionDivergence> {
+    let mut activated: HashMap<_, _> = activated
+        .transactions
+        .iter()
+        .map(|transaction| (transaction.signature.as_str(), transaction))
+        .collect();
+    let mut divergences = vec![];
+    let mut compare = |signature: &str, field: &str, canonical: String, activated: String| {
+        if canonical != activated {
+            divergences.push(TransactionDivergence {
+                signature: signature.to_string(),
+                field: field.to_string(),
+                canonical,
+                activated,
+            });
+        }
+    };
+    for canonical in &canonical.transactions {
+        let activated = activated.remove(canonical.signature.as_str());
+        compare_transaction(
+            &mut compare,
+            &canonical.signature,
+            Some(canonical),
+            activated,
+        );
+    }
+    let mut unmatched: Vec<_> = activated.into_values().collect();
+    unmatched.sort_by_key(|transaction| transaction.index);
+    for activated in unmatched {
+        compare_transaction(&mut compare, &activated.signature, None, Some(activated));
+    }
+    divergences
+}
+
+fn compare_transaction(
+    compare: &mut impl FnMut(&str, &str, String, String),
+    signature: &str,
+    canonical: Option<&TransactionDetails>,
+    activated: Option<&TransactionDetails>,
+) {
+    let (Some(canonical), Some(activated)) = (canonical, activated) else {
+        compare(
+            signature,
+            "recorded",
+            canonical.is_some().to_string(),
+            activated.is_some().to_string(),
+        );
+        return;
+    };
+    let (Some(canonical), Some(activated)) = (&canonical.commit_details, &activated.commit_details)
+    else {
+        compare(
+            signature,
+            "committed",
+            canonical.commit_details.is_some().to_string(),
+            activated.commit_details.is_some().to_string(),
+        );
+        return;
+    };
+    compare(
+        signature,
+        "status",
+        format!("{:?}", canonical.status),
+        format!("{:?}", activated.status),
+    );
+    compare(
+        signature,
+        "fee",
+        canonical.fee_details.total_fee().to_string(),
+        activated.fee_details.total_fee().to_string(),
+    );
+    compare(
+        signature,
+        "executedUnits",
+        canonical.executed_units.to_string(),
+        activated.executed_units.to_string(),
+    );
+}
+
+impl fmt::Display for FeatureActivationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Simulated the activation of {} from slot {}",
+            self.feature_id, self.activation_slot
+        )?;
+        writeln!(
+            f,
+            "Compared {} slots: {} diverged slots, {} diverged transactions",
+            self.num_slots_compared,
+            self.divergences.len(),
+            self.num_diverged_transactions(),
+        )?;
+        if let Some(first_divergence) = self.divergences.first() {
+            writeln!(f, "First diverged slot: {}", first_divergence.slot)?;
+        }
+        for divergence in &self.divergences {
+            writeln!(
+                f,
+                "Slot {}: bank hash canonical: {} activated: {}",
+                divergence.slot, divergence.canonical_bank_hash, divergence.activated_bank_hash
+            )?;
+            for transaction in &divergence.transactions {
+                writeln!(
+                    f,
+                    "  Transaction {}: {} canonical: {} activated: {}",
+                    transaction.signature,
+                    transaction.field,
+                    transaction.canonical,
+                    transaction.activated
+                )?;
+            }
+        }
+        if !self.unmatched_slots.is_empty() {
+            writeln!(
+                f,
+                "Slots replayed by only one replay: {:?}",
+                self.unmatched_slots
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::bank::bank_hash_details::TransactionCommitDetails,
+        solana_sdk::{
+            fee::FeeDetails,
+            transaction::{TransactionError, TransactionResult},
+        },
+    };
+
+    fn transaction(
+        signature: &str,
+        index: usize,
+        status: TransactionResult<()>,
+        executed_units: u64,
+    ) -> TransactionDetails {
+        TransactionDetails {
+            signature: signature.to_string(),
+            index,
+            accounts: vec![],
+            instructions: vec![],
+            is_simple_vote_tx: false,
+            commit_details: Some(TransactionCommitDetails {
+                status,
+                log_messages: None,
+                inner_instructions: None,
+                return_data: None,
+                executed_units,
+                fee_details: FeeDetails::default(),
+            }),
+        }
+    }
+
+    fn slot(slot: Slot, bank_hash: &str, transactions: Vec<TransactionDetails>) -> SlotDetails {
+        SlotDetails {
+            slot,
+            bank_hash: bank_hash.to_string(),
+            transactions,
+            ..SlotDetails::default()
+        }
+    }
+
+    #[test]
+    fn test_feature_activation_report() {
+        let feature_id = Pubkey::new_unique();
+        let canonical = vec![
+            slot(1, "hash1", vec![transaction("tx0", 0, Ok(()), 100)]),
+            slot(
+                2,
+                "hash2",
+                vec![
+                    transaction("tx1", 0, Ok(()), 100),
+                    transaction("tx2", 1, Ok(()), 100),
+                ],
+            ),
+            slot(3, "hash3", vec![]),
+        ];
+        let mut not_committed = transaction("tx3", 1, Ok(()), 0);
+        not_committed.commit_details = None;
+        let activated = vec![
+            slot(1, "hash1", vec![transaction("tx0", 0, Ok(()), 100)]),
+            slot(
+                2,
+                "hash2'",
+                vec![
+                    transaction("tx1", 0, Err(TransactionError::AccountInUse), 200),
+                    transaction("tx2", 1, Ok(()), 100),
+                ],
+            ),
+            slot(3, "hash3'", vec![not_committed]),
+            slot(4, "hash4'", vec![]),
+        ];
+
+        let report = FeatureActivationReport::new(&feature_id, 2, canonical, activated);
+        assert_eq!(report.num_slots_compared, 3);
+        assert_eq!(report.unmatched_slots, vec![4]);
+        assert_eq!(
+            report.divergences,
+            vec![
+                SlotDivergence {
+                    slot: 2,
+                    canonical_bank_hash: "hash2".to_string(),
+                    activated_bank_hash: "hash2'".to_string(),
+                    transactions: vec![
+                        TransactionDivergence {
+                            signature: "tx1".to_string(),
+                            field: "status".to_string(),
+                            canonical: "Ok(())".to_string(),
+                            activated: "Err(AccountInUse)".to_string(),
+                        },
+                        TransactionDivergence {
+                            signature: "tx1".to_string(),
+                            field: "executedUnits".to_string(),
+                            canonical: "100".to_string(),
+                            activated: "200".to_string(),
+                        },
+                    ],
+                },
+                SlotDivergence {
+                    slot: 3,
+                    canonical_bank_hash: "hash3".to_string(),
+                    activated_bank_hash: "hash3'".to_string(),
+                    transactions: vec![TransactionDivergence {
+                        signature: "tx3".to_string(),
+                        field: "recorded".to_string(),
+                        canonical: "false".to_string(),
+                        activated: "true".to_string(),
+                    }],
+                },
+            ]
+        );
+        assert_eq!(report.num_diverged_transactions(), 2);
+    }
+}
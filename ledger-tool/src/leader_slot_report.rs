@@ -0,0 +1,261 @@
+//! The `leader-slot-report` subcommand: summarizes the transactions recorded
+//! by a validator running with `--dump-leader-slot-transactions`
+
+use {
+    crate::error::{LedgerToolError, Result},
+    clap::{values_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_core::banking_stage::leader_slot_dump::{
+        is_leader_slot_dump, LeaderSlotDump, TransactionOutcome,
+    },
+    solana_sdk::clock::Slot,
+    std::{
+        collections::{BTreeMap, HashMap},
+        fmt::{self, Display, Formatter},
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+const NUM_TOP_ERRORS: usize = 5;
+
+pub trait LeaderSlotReportSubCommand {
+    fn leader_slot_report_subcommand(self) -> Self;
+}
+
+impl LeaderSlotReportSubCommand for App<'_, '_> {
+    fn leader_slot_report_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("leader-slot-report")
+                .about(
+                    "Summarize the transactions attempted during leader slots, as recorded by \
+                     --dump-leader-slot-transactions",
+                )
+                .arg(
+                    Arg::with_name("paths")
+                        .index(1)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .multiple(true)
+                        .required(true)
+                        .help("Leader slot dump files, or directories containing them"),
+                ),
+        )
+    }
+}
+
+pub fn leader_slot_report_process_command(matches: &ArgMatches<'_>) {
+    do_leader_slot_report_process_command(matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_leader_slot_report_process_command(matches: &ArgMatches<'_>) -> Result<()> {
+    let paths = values_t_or_exit!(matches, "paths", PathBuf);
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+
+    let mut dump_paths = vec![];
+    for path in paths {
+        if path.is_dir() {
+            dump_paths.extend(
+                fs::read_dir(&path)?
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<std::io::Result<Vec<_>>>()?
+                    .into_iter()
+                    .filter(|path| is_leader_slot_dump(path)),
+            );
+        } else {
+            dump_paths.push(path);
+        }
+    }
+    if dump_paths.is_empty() {
+        return Err(LedgerToolError::BadArgument(
+            "no leader slot dumps found".to_string(),
+        ));
+    }
+
+    let mut report = LeaderSlotReport::default();
+    for path in dump_paths {
+        let dump = read_dump(&path)?;
+        report.slots.push(LeaderSlotSummary::new(&dump));
+    }
+    report.slots.sort_unstable_by_key(|summary| summary.slot);
+    println!("{}", output_format.formatted_string(&report));
+    Ok(())
+}
+
+fn read_dump(path: &Path) -> Result<LeaderSlotDump> {
+    LeaderSlotDump::read(path).map_err(|err| {
+        LedgerToolError::Generic(format!(
+            "failed to read leader slot dump {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct LeaderSlotReport {
+    slots: Vec<LeaderSlotSummary>,
+}
+
+impl VerboseDisplay for LeaderSlotReport {}
+impl QuietDisplay for LeaderSlotReport {}
+
+impl Display for LeaderSlotReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (index, summary) in self.slots.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{summary}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct LeaderSlotSummary {
+    slot: Slot,
+    num_transactions: usize,
+    num_committed: usize,
+    num_committed_with_error: usize,
+    num_retried: usize,
+    num_dropped: usize,
+    /// Compute units consumed by committed transactions
+    compute_units: u64,
+    /// Microseconds between the first and the last attempted batch
+    duration_us: u32,
+    /// Number of transactions attempted by each worker
+    transactions_per_worker: BTreeMap<u32, usize>,
+    /// The most frequent reasons for transactions not being committed
+    /// successfully, most frequent first
+    top_errors: Vec<(String, usize)>,
+}
+
+impl LeaderSlotSummary {
+    fn new(dump: &LeaderSlotDump) -> Self {
+        let mut summary = Self {
+            slot: dump.slot,
+            num_transactions: dump.len(),
+            duration_us: dump.offsets_us.iter().copied().max().unwrap_or_default(),
+            ..Self::default()
+        };
+        for (outcome, compute_units) in dump.outcomes.iter().zip(&dump.compute_units) {
+            match outcome {
+                TransactionOutcome::Committed => summary.num_committed += 1,
+                TransactionOutcome::CommittedWithError => summary.num_committed_with_error += 1,
+                TransactionOutcome::Retried => summary.num_retried += 1,
+                TransactionOutcome::Dropped => summary.num_dropped += 1,
+            }
+            summary.compute_units += compute_units;
+        }
+        for worker_id in &dump.worker_ids {
+            *summary
+                .transactions_per_worker
+                .entry(*worker_id)
+                .or_default() += 1;
+        }
+
+        let mut errors = HashMap::<String, usize>::new();
+        for error in dump.errors.iter().flatten() {
+            *errors.entry(format!("{error:?}")).or_default() += 1;
+        }
+        let mut errors: Vec<_> = errors.into_iter().collect();
+        errors.sort_unstable_by(|(a_error, a_count), (b_error, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_error.cmp(b_error))
+        });
+        errors.truncate(NUM_TOP_ERRORS);
+        summary.top_errors = errors;
+        summary
+    }
+}
+
+impl Display for LeaderSlotSummary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Slot {}", self.slot)?;
+        writeln!(
+            f,
+            "  Transactions: {} over {}ms",
+            self.num_transactions,
+            self.duration_us / 1_000
+        )?;
+        writeln!(f, "    Committed: {}", self.num_committed)?;
+        writeln!(
+            f,
+            "    Committed with error: {}",
+            self.num_committed_with_error
+        )?;
+        writeln!(f, "    Retried: {}", self.num_retried)?;
+        writeln!(f, "    Dropped: {}", self.num_dropped)?;
+        writeln!(f, "  Compute units: {}", self.compute_units)?;
+        writeln!(f, "  Transactions per worker:")?;
+        for (worker_id, num_transactions) in &self.transactions_per_worker {
+            writeln!(f, "    {worker_id:>3}: {num_transactions}")?;
+        }
+        if !self.top_errors.is_empty() {
+            writeln!(f, "  Top errors:")?;
+            for (error, count) in &self.top_errors {
+                writeln!(f, "    {count:>8}  {error}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{signature::Signature, transaction::TransactionError},
+    };
+
+    #[test]
+    fn test_leader_slot_summary() {
+        let dump = LeaderSlotDump {
+            slot: 7,
+            signatures: (0..5).map(|_| Signature::new_unique()).collect(),
+            offsets_us: vec![0, 0, 120, 120, 4_500],
+            worker_ids: vec![2, 2, 3, 3, 3],
+            outcomes: vec![
+                TransactionOutcome::Committed,
+                TransactionOutcome::CommittedWithError,
+                TransactionOutcome::Retried,
+                TransactionOutcome::Retried,
+                TransactionOutcome::Dropped,
+            ],
+            compute_units: vec![300, 150, 0, 0, 0],
+            errors: vec![
+                None,
+                Some(TransactionError::InsufficientFundsForFee),
+                Some(TransactionError::AccountInUse),
+                Some(TransactionError::AccountInUse),
+                Some(TransactionError::BlockhashNotFound),
+            ],
+        };
+
+        let summary = LeaderSlotSummary::new(&dump);
+        assert_eq!(
+            summary,
+            LeaderSlotSummary {
+                slot: 7,
+                num_transactions: 5,
+                num_committed: 1,
+                num_committed_with_error: 1,
+                num_retried: 2,
+                num_dropped: 1,
+                compute_units: 450,
+                duration_us: 4_500,
+                transactions_per_worker: BTreeMap::from([(2, 2), (3, 3)]),
+                top_errors: vec![
+                    ("AccountInUse".to_string(), 2),
+                    ("BlockhashNotFound".to_string(), 1),
+                    ("InsufficientFundsForFee".to_string(), 1),
+                ],
+            }
+        );
+    }
+}
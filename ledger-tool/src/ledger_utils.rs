@@ -9,6 +9,7 @@ use {
     },
     solana_core::{
         accounts_hash_verifier::AccountsHashVerifier,
+        epoch_accounts_hash_scheduling::EpochAccountsHashScheduler,
         snapshot_packager_service::PendingSnapshotPackages, validator::BlockVerificationMethod,
     },
     solana_geyser_plugin_manager::geyser_plugin_service::{
@@ -25,7 +26,8 @@ use {
     },
     solana_measure::measure_time,
     solana_rpc::{
-        block_meta_service::BlockMetaService, transaction_status_service::TransactionStatusService,
+        block_meta_service::BlockMetaService,
+        transaction_status_service::{TransactionStatusFilter, TransactionStatusService},
     },
     solana_runtime::{
         accounts_background_service::{
@@ -321,6 +323,8 @@ pub fn load_and_process_ledger(
             transaction_notifier,
             write_blockstore.clone(),
             arg_matches.is_present("enable_extended_tx_metadata_storage"),
+            arg_matches.is_present("enable_program_signature_index"),
+            TransactionStatusFilter::default(),
             tss_exit.clone(),
         );
 
@@ -411,6 +415,7 @@ pub fn load_and_process_ledger(
         pending_snapshot_packages,
         exit.clone(),
         snapshot_controller.clone(),
+        Arc::new(EpochAccountsHashScheduler::default()),
     );
     let snapshot_request_handler = SnapshotRequestHandler {
         snapshot_controller: snapshot_controller.clone(),
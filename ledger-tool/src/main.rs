@@ -1,9 +1,13 @@
 #![allow(clippy::arithmetic_side_effects)]
 use {
     crate::{
+        accounts_lt_hash::*,
         args::*,
         bigtable::*,
         blockstore::*,
+        export_parquet::*,
+        feature_activation::FeatureActivationReport,
+        leader_slot_report::*,
         ledger_path::*,
         ledger_utils::*,
         output::{
@@ -11,6 +15,10 @@ use {
             SlotBankHash,
         },
         program::*,
+        snapshot::*,
+        tui::*,
+        turbine_tree::*,
+        verify_slots::SlotVerifier,
     },
     agave_feature_set::{self as feature_set, FeatureSet},
     agave_reserved_account_keys::ReservedAccountKeys,
@@ -45,8 +53,9 @@ use {
         blockstore::{banking_trace_path, create_new_ledger, Blockstore},
         blockstore_options::{AccessType, LedgerColumnOptions},
         blockstore_processor::{
-            ProcessSlotCallback, TransactionStatusMessage, TransactionStatusSender,
+            ProcessOptions, ProcessSlotCallback, TransactionStatusMessage, TransactionStatusSender,
         },
+        cost_calibration::CostCalibration,
     },
     solana_measure::{measure::Measure, measure_time},
     solana_runtime::{
@@ -105,14 +114,22 @@ use {
     },
 };
 
+mod accounts_lt_hash;
 mod args;
 mod bigtable;
 mod blockstore;
 mod error;
+mod export_parquet;
+mod feature_activation;
+mod leader_slot_report;
 mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod snapshot;
+mod tui;
+mod turbine_tree;
+mod verify_slots;
 
 fn parse_encoding_format(matches: &ArgMatches<'_>) -> UiAccountEncoding {
     match matches.value_of("encoding") {
@@ -605,20 +622,109 @@ fn read_banking_trace_event_file_paths_or_exit(banking_trace_path: PathBuf) -> V
     event_file_paths
 }
 
-struct SlotRecorderConfig {
+/// Records the details of the replayed slots
+struct SlotRecorder {
     transaction_recorder: Option<JoinHandle<()>>,
     transaction_status_sender: Option<TransactionStatusSender>,
     slot_details: Arc<Mutex<Vec<SlotDetails>>>,
+}
+
+impl SlotRecorder {
+    /// Returns the recorder along with the callback recording the details of
+    /// each replayed slot. If `include_tx`, their transactions are recorded
+    /// from the recorder's `transaction_status_sender`.
+    fn new(include_bank_hash_components: bool, include_tx: bool) -> (ProcessSlotCallback, Self) {
+        let slot_details = Arc::new(Mutex::new(Vec::new()));
+        let (transaction_status_sender, transaction_recorder) = if include_tx {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+
+            let slots = Arc::clone(&slot_details);
+            let transaction_recorder = Some(std::thread::spawn(move || {
+                record_transactions(receiver, slots);
+            }));
+
+            (
+                Some(TransactionStatusSender { sender }),
+                transaction_recorder,
+            )
+        } else {
+            (None, None)
+        };
+
+        let slot_callback = Arc::new({
+            let slots = Arc::clone(&slot_details);
+            move |bank: &Bank| {
+                let mut details = bank_hash_details::SlotDetails::new_from_bank(
+                    bank,
+                    include_bank_hash_components,
+                )
+                .unwrap();
+                let mut slots = slots.lock().unwrap();
+
+                if let Some(recorded_slot) = slots.iter_mut().find(|f| f.slot == details.slot) {
+                    // copy all fields except transactions
+                    swap(&mut recorded_slot.transactions, &mut details.transactions);
+
+                    *recorded_slot = details;
+                } else {
+                    slots.push(details);
+                }
+            }
+        });
+
+        (
+            slot_callback,
+            Self {
+                transaction_recorder,
+                transaction_status_sender,
+                slot_details,
+            },
+        )
+    }
+
+    /// Returns the recorded slots once the ledger has been replayed.
+    fn finish(mut self) -> Vec<SlotDetails> {
+        // Drop transaction_status_sender to break transaction_recorder
+        // out of its' recieve loop
+        let transaction_status_sender = self.transaction_status_sender.take();
+        drop(transaction_status_sender);
+        if let Some(transaction_recorder) = self.transaction_recorder {
+            transaction_recorder.join().unwrap();
+        }
+
+        std::mem::take(&mut *self.slot_details.lock().unwrap())
+    }
+}
+
+struct SlotRecorderConfig {
+    recorder: SlotRecorder,
     file: File,
 }
 
 fn setup_slot_recording(
     arg_matches: &ArgMatches,
-) -> (Option<ProcessSlotCallback>, Option<SlotRecorderConfig>) {
+) -> (
+    Option<ProcessSlotCallback>,
+    Option<SlotRecorderConfig>,
+    Option<Arc<SlotVerifier>>,
+) {
     let record_slots = arg_matches.occurrences_of("record_slots") > 0;
     let verify_slots = arg_matches.occurrences_of("verify_slots") > 0;
+    if !verify_slots {
+        // .requires() does not work with a .default_value() argument, so
+        // open-code it here as well
+        for (name, arg) in [
+            ("halt_on_divergence", "--halt-on-divergence"),
+            ("divergence_report", "--divergence-report <FILENAME>"),
+        ] {
+            if arg_matches.is_present(name) {
+                eprintln!("error: The argument '{arg}' requires '--verify-slots <FILENAME>'");
+                exit(1);
+            }
+        }
+    }
     match (record_slots, verify_slots) {
-        (false, false) => (None, None),
+        (false, false) => (None, None, None),
         (true, true) => {
             // .default_value() does not work with .conflicts_with() in clap 2.33
             // .conflicts_with("verify_slots")
@@ -649,91 +755,30 @@ fn setup_slot_recording(
                 }
             }
 
-            let slot_details = Arc::new(Mutex::new(Vec::new()));
-            let (transaction_status_sender, transaction_recorder) = if include_tx {
-                let (sender, receiver) = crossbeam_channel::unbounded();
-
-                let slots = Arc::clone(&slot_details);
-                let transaction_recorder = Some(std::thread::spawn(move || {
-                    record_transactions(receiver, slots);
-                }));
-
-                (
-                    Some(TransactionStatusSender { sender }),
-                    transaction_recorder,
-                )
-            } else {
-                (None, None)
-            };
-
-            let slot_callback = Arc::new({
-                let slots = Arc::clone(&slot_details);
-                move |bank: &Bank| {
-                    let mut details = bank_hash_details::SlotDetails::new_from_bank(
-                        bank,
-                        include_bank_hash_components,
-                    )
-                    .unwrap();
-                    let mut slots = slots.lock().unwrap();
-
-                    if let Some(recorded_slot) = slots.iter_mut().find(|f| f.slot == details.slot) {
-                        // copy all fields except transactions
-                        swap(&mut recorded_slot.transactions, &mut details.transactions);
-
-                        *recorded_slot = details;
-                    } else {
-                        slots.push(details);
-                    }
-                }
-            });
-
+            let (slot_callback, recorder) =
+                SlotRecorder::new(include_bank_hash_components, include_tx);
             (
                 Some(slot_callback as ProcessSlotCallback),
-                Some(SlotRecorderConfig {
-                    transaction_recorder,
-                    transaction_status_sender,
-                    slot_details,
-                    file,
-                }),
+                Some(SlotRecorderConfig { recorder, file }),
+                None,
             )
         }
         (false, true) => {
             let filename = Path::new(arg_matches.value_of_os("verify_slots").unwrap());
-            let file = File::open(filename).unwrap_or_else(|err| {
-                eprintln!("Unable to read file: {}: {err:#}", filename.display());
+            let verifier = SlotVerifier::new(
+                filename,
+                arg_matches.is_present("halt_on_divergence"),
+                arg_matches
+                    .value_of_os("divergence_report")
+                    .map(PathBuf::from),
+            )
+            .unwrap_or_else(|err| {
+                eprintln!("{err}");
                 exit(1);
             });
-            let reader = std::io::BufReader::new(file);
-            let details: bank_hash_details::BankHashDetails = serde_json::from_reader(reader)
-                .unwrap_or_else(|err| {
-                    eprintln!("Error loading slots file: {err:#}");
-                    exit(1);
-                });
-
-            let slots = Arc::new(Mutex::new(details.bank_hash_details));
-            let slot_callback = Arc::new(move |bank: &Bank| {
-                if slots.lock().unwrap().is_empty() {
-                    error!(
-                        "Expected slot: not found got slot: {} hash: {}",
-                        bank.slot(),
-                        bank.hash()
-                    );
-                } else {
-                    let bank_hash_details::SlotDetails {
-                        slot: expected_slot,
-                        bank_hash: expected_hash,
-                        ..
-                    } = slots.lock().unwrap().remove(0);
-                    if bank.slot() != expected_slot || bank.hash().to_string() != expected_hash {
-                        error!("Expected slot: {expected_slot} hash: {expected_hash} got slot: {} hash: {}",
-                                    bank.slot(), bank.hash());
-                    } else {
-                        info!("Expected slot: {expected_slot} hash: {expected_hash} correct");
-                    }
-                }
-            });
+            let verifier = Arc::new(verifier);
 
-            (Some(slot_callback as ProcessSlotCallback), None)
+            (Some(verifier.slot_callback()), None, Some(verifier))
         }
     }
 }
@@ -1129,6 +1174,16 @@ fn main() {
                              transaction info stored",
                         ),
                 )
+                .arg(
+                    Arg::with_name("enable_program_signature_index")
+                        .long("enable-program-signature-index")
+                        .requires("enable_rpc_transaction_history")
+                        .takes_value(false)
+                        .help(
+                            "Index the signatures of the transactions stored by the programs they \
+                             invoke",
+                        ),
+                )
                 .arg(
                     Arg::with_name("run_final_hash_calc")
                         .long("run-final-accounts-hash-calculation")
@@ -1180,6 +1235,26 @@ fn main() {
                         .value_name("FILENAME")
                         .help("Verify slots match contents of file"),
                 )
+                .arg(
+                    Arg::with_name("halt_on_divergence")
+                        .long("halt-on-divergence")
+                        .takes_value(false)
+                        .help(
+                            "Exit with failed status at the first slot whose bank hash, \
+                             capitalization or accounts delta hash differs from the file of \
+                             --verify-slots",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("divergence_report")
+                        .long("divergence-report")
+                        .value_name("FILENAME")
+                        .takes_value(true)
+                        .help(
+                            "Write a JSON report of the slots which differ from the file of \
+                             --verify-slots",
+                        ),
+                )
                 .arg(
                     Arg::with_name("record_slots_config")
                         .long("record-slots-config")
@@ -1630,7 +1705,83 @@ fn main() {
                 )
                 .arg(&allow_dead_slots_arg),
         )
+        .subcommand(
+            SubCommand::with_name("compute-cost-report")
+                .about(
+                    "Replay the ledger and report the measured execution time and compute units \
+                     of each program against the cost model",
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .args(&snapshot_config_args)
+                .arg(&halt_at_slot_arg)
+                .arg(&hard_forks_arg)
+                .arg(&allow_dead_slots_arg)
+                .arg(
+                    Arg::with_name("num_programs")
+                        .long("num-programs")
+                        .value_name("NUM")
+                        .takes_value(true)
+                        .validator(is_parsable::<usize>)
+                        .help(
+                            "Only report this many programs, those which took the longest to \
+                             execute [default: all]",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("simulate-feature-activation")
+                .about(
+                    "Replay the ledger twice, the second time with a feature gate activated, and \
+                     report how the bank hashes and the transactions diverge",
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .args(&snapshot_config_args)
+                .arg(&halt_at_slot_arg)
+                .arg(&hard_forks_arg)
+                .arg(&allow_dead_slots_arg)
+                .arg(
+                    Arg::with_name("feature")
+                        .long("feature")
+                        .value_name("FEATURE_ID")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_pubkey)
+                        .help("The feature gate to activate"),
+                )
+                .arg(
+                    Arg::with_name("activation_slot")
+                        .long("activation-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .help(
+                            "Activate the feature from this slot on [default: the first replayed \
+                             slot]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("divergence_report")
+                        .long("divergence-report")
+                        .value_name("FILENAME")
+                        .takes_value(true)
+                        .help("Write the divergences as JSON to this file"),
+                )
+                .after_help(
+                    "Only the feature set of the replayed banks is changed: the accounts changes \
+                     an actual activation performs, e.g. migrating a builtin program, are not \
+                     simulated. As a bank hash covers the hash of the parent bank, the bank \
+                     hashes of all the slots after the first diverging one diverge as well.",
+                ),
+        )
         .program_subcommand()
+        .tui_subcommand()
+        .leader_slot_report_subcommand()
+        .turbine_tree_subcommand()
+        .export_parquet_subcommand()
+        .snapshot_subcommand()
+        .accounts_lt_hash_subcommand()
         .get_matches();
 
     info!("{} {}", crate_name!(), solana_version::version!());
@@ -1648,6 +1799,20 @@ fn main() {
         ("bigtable", Some(arg_matches)) => bigtable_process_command(&ledger_path, arg_matches),
         ("blockstore", Some(arg_matches)) => blockstore_process_command(&ledger_path, arg_matches),
         ("program", Some(arg_matches)) => program(&ledger_path, arg_matches),
+        ("tui", Some(arg_matches)) => tui_process_command(&ledger_path, arg_matches),
+        ("leader-slot-report", Some(arg_matches)) => {
+            leader_slot_report_process_command(arg_matches)
+        }
+        ("turbine-tree", Some(arg_matches)) => {
+            turbine_tree_process_command(&ledger_path, arg_matches)
+        }
+        ("export-parquet", Some(arg_matches)) => {
+            export_parquet_process_command(&ledger_path, arg_matches)
+        }
+        ("snapshot", Some(arg_matches)) => snapshot_process_command(arg_matches),
+        ("accounts-lt-hash", Some(arg_matches)) => {
+            accounts_lt_hash_process_command(&ledger_path, arg_matches)
+        }
         // This match case provides legacy support for commands that were previously top level
         // subcommands of the binary, but have been moved under the blockstore subcommand.
         ("analyze-storage", Some(_))
@@ -1808,11 +1973,12 @@ fn main() {
                             Some(banking_trace_events.hash_overrides().clone());
                     }
 
-                    let (slot_callback, slot_recorder_config) = setup_slot_recording(arg_matches);
+                    let (slot_callback, slot_recorder_config, slot_verifier) =
+                        setup_slot_recording(arg_matches);
                     process_options.slot_callback = slot_callback;
                     let transaction_status_sender = slot_recorder_config
                         .as_ref()
-                        .and_then(|config| config.transaction_status_sender.clone());
+                        .and_then(|config| config.recorder.transaction_status_sender.clone());
 
                     let output_format =
                         OutputFormat::from_matches(arg_matches, "output_format", false);
@@ -1856,21 +2022,9 @@ fn main() {
                             .ok();
                     }
 
-                    if let Some(mut slot_recorder_config) = slot_recorder_config {
-                        // Drop transaction_status_sender to break transaction_recorder
-                        // out of its' recieve loop
-                        let transaction_status_sender =
-                            slot_recorder_config.transaction_status_sender.take();
-                        drop(transaction_status_sender);
-                        if let Some(transaction_recorder) =
-                            slot_recorder_config.transaction_recorder
-                        {
-                            transaction_recorder.join().unwrap();
-                        }
-
-                        let slot_details = slot_recorder_config.slot_details.lock().unwrap();
-                        let bank_hashes =
-                            bank_hash_details::BankHashDetails::new(slot_details.to_vec());
+                    if let Some(slot_recorder_config) = slot_recorder_config {
+                        let slot_details = slot_recorder_config.recorder.finish();
+                        let bank_hashes = bank_hash_details::BankHashDetails::new(slot_details);
 
                         // writing the json file ends up with a syscall for each number, comma, indentation etc.
                         // use BufWriter to speed things up
@@ -1878,6 +2032,10 @@ fn main() {
                         serde_json::to_writer_pretty(writer, &bank_hashes).unwrap();
                     }
 
+                    if let Some(slot_verifier) = slot_verifier {
+                        slot_verifier.finish();
+                    }
+
                     exit_signal.store(true, Ordering::Relaxed);
                     system_monitor_service.join().unwrap();
                 }
@@ -3151,6 +3309,93 @@ fn main() {
                         }
                     }
                 }
+                ("compute-cost-report", Some(arg_matches)) => {
+                    let cost_calibration = Arc::new(CostCalibration::default());
+                    let process_options = ProcessOptions {
+                        cost_calibration: Some(cost_calibration.clone()),
+                        ..parse_process_options(&ledger_path, arg_matches)
+                    };
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let blockstore = open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    );
+                    let _ = load_and_process_ledger_or_exit(
+                        arg_matches,
+                        &genesis_config,
+                        Arc::new(blockstore),
+                        process_options,
+                        None,
+                    );
+
+                    let mut report = cost_calibration.take_report();
+                    if let Ok(num_programs) = value_t!(arg_matches, "num_programs", usize) {
+                        report.programs.truncate(num_programs);
+                    }
+                    print!("{report}");
+                }
+                ("simulate-feature-activation", Some(arg_matches)) => {
+                    let feature_id = pubkey_of(arg_matches, "feature").unwrap();
+                    let activation_slot =
+                        value_t!(arg_matches, "activation_slot", Slot).unwrap_or_default();
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let replay = |simulated_feature_activation| {
+                        let (slot_callback, recorder) = SlotRecorder::new(false, true);
+                        let process_options = ProcessOptions {
+                            slot_callback: Some(slot_callback),
+                            simulated_feature_activation,
+                            ..parse_process_options(&ledger_path, arg_matches)
+                        };
+                        let blockstore = open_blockstore(
+                            &ledger_path,
+                            arg_matches,
+                            get_access_type(&process_options),
+                        );
+                        let LoadAndProcessLedgerOutput {
+                            bank_forks,
+                            accounts_background_service,
+                            ..
+                        } = load_and_process_ledger_or_exit(
+                            arg_matches,
+                            &genesis_config,
+                            Arc::new(blockstore),
+                            process_options,
+                            recorder.transaction_status_sender.clone(),
+                        );
+                        // The accounts of a replay are cleaned up when the
+                        // next one loads the ledger, so release them first.
+                        drop(bank_forks);
+                        accounts_background_service.join().unwrap();
+                        recorder.finish()
+                    };
+
+                    info!("Replaying the ledger with the canonical feature set");
+                    let canonical = replay(None);
+                    info!("Replaying the ledger with {feature_id} activated");
+                    let activated = replay(Some((feature_id, activation_slot)));
+
+                    let report = FeatureActivationReport::new(
+                        &feature_id,
+                        activation_slot,
+                        canonical,
+                        activated,
+                    );
+                    if let Some(report_path) = arg_matches.value_of_os("divergence_report") {
+                        let report_path = Path::new(report_path);
+                        let file = File::create(report_path).unwrap_or_else(|err| {
+                            eprintln!(
+                                "Unable to write to file: {}: {:#}",
+                                report_path.display(),
+                                err
+                            );
+                            exit(1);
+                        });
+                        serde_json::to_writer_pretty(std::io::BufWriter::new(file), &report)
+                            .unwrap();
+                    }
+                    print!("{report}");
+                }
                 ("", _) => {
                     eprintln!("{}", matches.usage());
                     exit(1);
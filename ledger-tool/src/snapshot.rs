@@ -0,0 +1,312 @@
+//! The `snapshot` subcommand: inspects snapshot archives
+
+use {
+    crate::error::{LedgerToolError, Result},
+    clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_runtime::snapshot_utils::{self, SnapshotArchiveInspection},
+    solana_sdk::{clock::Slot, native_token::lamports_to_sol},
+    std::{
+        fmt::{self, Display, Formatter},
+        path::PathBuf,
+    },
+};
+
+pub trait SnapshotSubCommand {
+    fn snapshot_subcommand(self) -> Self;
+}
+
+impl SnapshotSubCommand for App<'_, '_> {
+    fn snapshot_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("snapshot")
+                .about("Snapshot archive utilities")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("inspect")
+                        .about(
+                            "Print the bank fields, the storages, the largest accounts and the \
+                             capitalization by owner of a snapshot archive, without unpacking \
+                             it",
+                        )
+                        .arg(
+                            Arg::with_name("snapshot_archive")
+                                .value_name("ARCHIVE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to the full or incremental snapshot archive"),
+                        )
+                        .arg(
+                            Arg::with_name("num_largest_accounts")
+                                .long("num-largest-accounts")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .validator(is_parsable::<usize>)
+                                .default_value("20")
+                                .help("Number of accounts with the largest data to print"),
+                        )
+                        .arg(
+                            Arg::with_name("num_largest_owners")
+                                .long("num-largest-owners")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .validator(is_parsable::<usize>)
+                                .default_value("20")
+                                .help(
+                                    "Number of owners with the largest share of the \
+                                     capitalization to print",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("scratch_dir")
+                                .long("scratch-dir")
+                                .value_name("DIR")
+                                .takes_value(true)
+                                .help(
+                                    "Directory to unpack the account storages into, one at a \
+                                     time [default: the directory of the archive]",
+                                ),
+                        ),
+                ),
+        )
+    }
+}
+
+pub fn snapshot_process_command(matches: &ArgMatches<'_>) {
+    do_snapshot_process_command(matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_snapshot_process_command(matches: &ArgMatches<'_>) -> Result<()> {
+    match matches.subcommand() {
+        ("inspect", Some(arg_matches)) => {
+            let archive_path =
+                PathBuf::from(value_t_or_exit!(arg_matches, "snapshot_archive", String));
+            let num_largest_accounts = value_t_or_exit!(arg_matches, "num_largest_accounts", usize);
+            let num_largest_owners = value_t_or_exit!(arg_matches, "num_largest_owners", usize);
+            let scratch_dir = match arg_matches.value_of("scratch_dir") {
+                Some(scratch_dir) => PathBuf::from(scratch_dir),
+                None => archive_path
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+            };
+            let output_format = OutputFormat::from_matches(arg_matches, "output_format", false);
+
+            let scratch_dir = tempfile::Builder::new()
+                .prefix("snapshot-inspect-")
+                .tempdir_in(scratch_dir)?;
+            let inspection = snapshot_utils::inspect_snapshot_archive(
+                &archive_path,
+                scratch_dir.path(),
+                num_largest_accounts,
+                num_largest_owners,
+            )
+            .map_err(|err| {
+                LedgerToolError::Generic(format!(
+                    "failed to inspect {}: {err}",
+                    archive_path.display()
+                ))
+            })?;
+            let output = CliSnapshotInspection::from(inspection);
+            println!("{}", output_format.formatted_string(&output));
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliSnapshotInspection {
+    snapshot_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_slot: Option<Slot>,
+    slot: Slot,
+    parent_slot: Slot,
+    epoch: u64,
+    block_height: u64,
+    bank_hash: String,
+    parent_hash: String,
+    capitalization: u64,
+    accounts_data_len: u64,
+    transaction_count: u64,
+    signature_count: u64,
+    collector_id: String,
+    lamports_per_signature: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    epoch_accounts_hash: Option<String>,
+    num_storages: u64,
+    storages_len: u64,
+    num_stored_accounts: u64,
+    num_accounts: u64,
+    accounts_lamports: u64,
+    largest_accounts: Vec<CliInspectedAccount>,
+    largest_owners: Vec<CliOwnerCapitalization>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliInspectedAccount {
+    pubkey: String,
+    owner: String,
+    lamports: u64,
+    data_len: u64,
+    slot: Slot,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliOwnerCapitalization {
+    owner: String,
+    num_accounts: u64,
+    lamports: u64,
+    data_len: u64,
+}
+
+impl From<SnapshotArchiveInspection> for CliSnapshotInspection {
+    fn from(inspection: SnapshotArchiveInspection) -> Self {
+        let bank = inspection.bank;
+        Self {
+            snapshot_version: inspection.snapshot_version,
+            base_slot: inspection.base_slot,
+            slot: bank.slot,
+            parent_slot: bank.parent_slot,
+            epoch: bank.epoch,
+            block_height: bank.block_height,
+            bank_hash: bank.hash.to_string(),
+            parent_hash: bank.parent_hash.to_string(),
+            capitalization: bank.capitalization,
+            accounts_data_len: bank.accounts_data_len,
+            transaction_count: bank.transaction_count,
+            signature_count: bank.signature_count,
+            collector_id: bank.collector_id.to_string(),
+            lamports_per_signature: bank.lamports_per_signature,
+            epoch_accounts_hash: bank.epoch_accounts_hash.map(|hash| hash.to_string()),
+            num_storages: inspection.num_storages,
+            storages_len: inspection.storages_len,
+            num_stored_accounts: inspection.num_stored_accounts,
+            num_accounts: inspection.num_accounts,
+            accounts_lamports: inspection.accounts_lamports,
+            largest_accounts: inspection
+                .largest_accounts
+                .into_iter()
+                .map(|account| CliInspectedAccount {
+                    pubkey: account.pubkey.to_string(),
+                    owner: account.owner.to_string(),
+                    lamports: account.lamports,
+                    data_len: account.data_len,
+                    slot: account.slot,
+                })
+                .collect(),
+            largest_owners: inspection
+                .largest_owners
+                .into_iter()
+                .map(|owner| CliOwnerCapitalization {
+                    owner: owner.owner.to_string(),
+                    num_accounts: owner.num_accounts,
+                    lamports: owner.lamports,
+                    data_len: owner.data_len,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl VerboseDisplay for CliSnapshotInspection {}
+impl QuietDisplay for CliSnapshotInspection {}
+
+impl Display for CliSnapshotInspection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Snapshot version: {}", self.snapshot_version)?;
+        if let Some(base_slot) = self.base_slot {
+            writeln!(f, "Incremental snapshot of full snapshot slot: {base_slot}")?;
+        }
+        writeln!(f, "Slot: {} (parent: {})", self.slot, self.parent_slot)?;
+        writeln!(f, "Epoch: {}", self.epoch)?;
+        writeln!(f, "Block height: {}", self.block_height)?;
+        writeln!(f, "Bank hash: {}", self.bank_hash)?;
+        writeln!(f, "Parent hash: {}", self.parent_hash)?;
+        writeln!(
+            f,
+            "Capitalization: {} SOL",
+            lamports_to_sol(self.capitalization)
+        )?;
+        writeln!(f, "Accounts data length: {} bytes", self.accounts_data_len)?;
+        writeln!(f, "Transaction count: {}", self.transaction_count)?;
+        writeln!(f, "Signature count: {}", self.signature_count)?;
+        writeln!(f, "Collector: {}", self.collector_id)?;
+        writeln!(f, "Lamports per signature: {}", self.lamports_per_signature)?;
+        if let Some(epoch_accounts_hash) = &self.epoch_accounts_hash {
+            writeln!(f, "Epoch accounts hash: {epoch_accounts_hash}")?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Storages: {} ({} bytes of accounts)",
+            self.num_storages, self.storages_len
+        )?;
+        writeln!(
+            f,
+            "Stored accounts: {} ({} overwritten by newer storages or without lamports)",
+            self.num_stored_accounts,
+            self.num_stored_accounts - self.num_accounts,
+        )?;
+        writeln!(
+            f,
+            "Accounts: {} holding {} SOL",
+            self.num_accounts,
+            lamports_to_sol(self.accounts_lamports)
+        )?;
+        // The accounts of a full snapshot hold the whole capitalization.
+        if self.base_slot.is_none() && self.accounts_lamports != self.capitalization {
+            writeln!(
+                f,
+                "Warning: the accounts hold {} lamports, but the capitalization is {} lamports",
+                self.accounts_lamports, self.capitalization
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Largest accounts by data length:")?;
+        writeln!(
+            f,
+            "{:<44} {:<44} {:>12} {:>20} {:>12}",
+            "Pubkey", "Owner", "Data Length", "SOL", "Slot"
+        )?;
+        for account in &self.largest_accounts {
+            writeln!(
+                f,
+                "{:<44} {:<44} {:>12} {:>20} {:>12}",
+                account.pubkey,
+                account.owner,
+                account.data_len,
+                lamports_to_sol(account.lamports),
+                account.slot
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Capitalization by owner:")?;
+        writeln!(
+            f,
+            "{:<44} {:>12} {:>20} {:>8} {:>16}",
+            "Owner", "Accounts", "SOL", "Share", "Data Length"
+        )?;
+        for owner in &self.largest_owners {
+            writeln!(
+                f,
+                "{:<44} {:>12} {:>20} {:>7.3}% {:>16}",
+                owner.owner,
+                owner.num_accounts,
+                lamports_to_sol(owner.lamports),
+                100.0 * owner.lamports as f64 / self.accounts_lamports.max(1) as f64,
+                owner.data_len
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,591 @@
+//! The `tui` subcommand: an interactive terminal UI to browse the slots,
+//! entries and transactions of a local blockstore
+
+use {
+    crate::{
+        error::{LedgerToolError, Result},
+        ledger_path::canonicalize_ledger_path,
+    },
+    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    console::{style, Key, Term},
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_entry::entry::Entry,
+    solana_ledger::{blockstore::Blockstore, blockstore_options::AccessType},
+    solana_sdk::{
+        clock::Slot, native_token::lamports_to_sol, signature::Signature,
+        transaction::VersionedTransaction,
+    },
+    solana_transaction_status::TransactionStatusMeta,
+    std::{collections::HashSet, path::Path, str::FromStr},
+};
+
+const HELP: &str =
+    "up/down/j/k: move  pgup/pgdn: page  enter/l: open  esc/h: back  /: search  q: quit";
+
+pub trait TuiSubCommand {
+    fn tui_subcommand(self) -> Self;
+}
+
+impl TuiSubCommand for App<'_, '_> {
+    fn tui_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("tui")
+                .about(
+                    "Interactively browse the slots, entries and transactions of the ledger. \
+                     Press / to jump to a slot or transaction signature.",
+                )
+                .arg(
+                    Arg::with_name("starting_slot")
+                        .long("starting-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .default_value("0")
+                        .help("Start browsing at this slot"),
+                )
+                .arg(
+                    Arg::with_name("num_slots")
+                        .long("num-slots")
+                        .value_name("NUM")
+                        .takes_value(true)
+                        .validator(is_parsable::<usize>)
+                        .default_value("100000")
+                        .help("Maximum number of slots to load into the slot list"),
+                ),
+        )
+    }
+}
+
+pub fn tui_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    do_tui_process_command(ledger_path, matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_tui_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -> Result<()> {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let starting_slot = value_t_or_exit!(matches, "starting_slot", Slot);
+    let num_slots = value_t_or_exit!(matches, "num_slots", usize);
+
+    let blockstore = crate::open_blockstore(&ledger_path, matches, AccessType::Secondary);
+    let mut tui = LedgerTui::new(&blockstore, starting_slot, num_slots)?;
+
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(LedgerToolError::Generic(
+            "the tui subcommand requires an interactive terminal".to_string(),
+        ));
+    }
+    term.hide_cursor()?;
+    let result = run(&term, &mut tui);
+    term.show_cursor()?;
+    term.clear_screen()?;
+    result
+}
+
+fn run(term: &Term, tui: &mut LedgerTui) -> Result<()> {
+    loop {
+        let (rows, cols) = term.size();
+        term.clear_screen()?;
+        for line in tui.render(usize::from(rows), usize::from(cols)) {
+            term.write_line(&line)?;
+        }
+        match tui.handle_key(term.read_key()?, usize::from(rows)) {
+            Action::Continue => {}
+            Action::Quit => return Ok(()),
+            Action::Search => {
+                term.show_cursor()?;
+                term.write_str("Search (slot or signature): ")?;
+                let query = term.read_line()?;
+                term.hide_cursor()?;
+                tui.search(query.trim());
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Quit,
+    Search,
+}
+
+#[derive(Debug)]
+struct SlotSummary {
+    slot: Slot,
+    parent_slot: Option<Slot>,
+    is_root: bool,
+    is_dead: bool,
+    is_full: bool,
+    num_shreds: u64,
+}
+
+#[derive(Debug)]
+enum View {
+    Slots,
+    Entries {
+        slot: Slot,
+        entries: Vec<Entry>,
+    },
+    Transactions {
+        slot: Slot,
+        entry_index: usize,
+        transactions: Vec<VersionedTransaction>,
+    },
+    Transaction {
+        slot: Slot,
+        lines: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+struct ViewState {
+    view: View,
+    cursor: usize,
+}
+
+struct LedgerTui<'a> {
+    blockstore: &'a Blockstore,
+    slots: Vec<SlotSummary>,
+    /// Stack of views, the last one is displayed. The slot list is always at
+    /// the bottom of the stack.
+    stack: Vec<ViewState>,
+    status: String,
+}
+
+impl<'a> LedgerTui<'a> {
+    fn new(blockstore: &'a Blockstore, starting_slot: Slot, num_slots: usize) -> Result<Self> {
+        let slots = blockstore
+            .slot_meta_iterator(starting_slot)?
+            .take(num_slots)
+            .map(|(slot, meta)| SlotSummary {
+                slot,
+                parent_slot: meta.parent_slot,
+                is_root: blockstore.is_root(slot),
+                is_dead: blockstore.is_dead(slot),
+                is_full: meta.is_full(),
+                num_shreds: meta.consumed,
+            })
+            .collect::<Vec<_>>();
+        let status = format!("Loaded {} slots", slots.len());
+        Ok(Self {
+            blockstore,
+            slots,
+            stack: vec![ViewState {
+                view: View::Slots,
+                cursor: 0,
+            }],
+            status,
+        })
+    }
+
+    fn current(&self) -> &ViewState {
+        self.stack.last().expect("slot list is never popped")
+    }
+
+    fn current_mut(&mut self) -> &mut ViewState {
+        self.stack.last_mut().expect("slot list is never popped")
+    }
+
+    fn num_rows(&self) -> usize {
+        match &self.current().view {
+            View::Slots => self.slots.len(),
+            View::Entries { entries, .. } => entries.len(),
+            View::Transactions { transactions, .. } => transactions.len(),
+            View::Transaction { lines, .. } => lines.len(),
+        }
+    }
+
+    fn handle_key(&mut self, key: Key, term_rows: usize) -> Action {
+        let page = body_height(term_rows).max(1);
+        let last_row = self.num_rows().saturating_sub(1);
+        let cursor = self.current().cursor;
+        let new_cursor = match key {
+            Key::Char('q') => return Action::Quit,
+            Key::Char('/') => return Action::Search,
+            Key::ArrowUp | Key::Char('k') => cursor.saturating_sub(1),
+            Key::ArrowDown | Key::Char('j') => cursor.saturating_add(1).min(last_row),
+            Key::PageUp => cursor.saturating_sub(page),
+            Key::PageDown => cursor.saturating_add(page).min(last_row),
+            Key::Home | Key::Char('g') => 0,
+            Key::End | Key::Char('G') => last_row,
+            Key::Enter | Key::ArrowRight | Key::Char('l') => {
+                self.open_selected();
+                return Action::Continue;
+            }
+            Key::Escape | Key::Backspace | Key::ArrowLeft | Key::Char('h') => {
+                if self.stack.len() > 1 {
+                    self.stack.pop();
+                }
+                return Action::Continue;
+            }
+            _ => cursor,
+        };
+        self.current_mut().cursor = new_cursor;
+        Action::Continue
+    }
+
+    /// Opens the row under the cursor in a new view
+    fn open_selected(&mut self) {
+        let cursor = self.current().cursor;
+        let view = match &self.current().view {
+            View::Slots => match self.slots.get(cursor) {
+                Some(summary) => self.load_entries(summary.slot),
+                None => return,
+            },
+            View::Entries { slot, entries } => match entries.get(cursor) {
+                Some(entry) => Ok(View::Transactions {
+                    slot: *slot,
+                    entry_index: cursor,
+                    transactions: entry.transactions.clone(),
+                }),
+                None => return,
+            },
+            View::Transactions {
+                slot, transactions, ..
+            } => match transactions.get(cursor) {
+                Some(transaction) => Ok(self.transaction_view(*slot, transaction)),
+                None => return,
+            },
+            View::Transaction { .. } => return,
+        };
+        match view {
+            Ok(view) => self.stack.push(ViewState { view, cursor: 0 }),
+            Err(status) => self.status = status,
+        }
+    }
+
+    fn load_entries(&self, slot: Slot) -> std::result::Result<View, String> {
+        self.blockstore
+            .get_slot_entries_with_shred_info(
+                slot, /*start_index:*/ 0, /*allow_dead_slots:*/ true,
+            )
+            .map(|(entries, _num_shreds, _is_full)| View::Entries { slot, entries })
+            .map_err(|err| format!("Failed to load entries for slot {slot}: {err}"))
+    }
+
+    fn transaction_view(&self, slot: Slot, transaction: &VersionedTransaction) -> View {
+        let signature = transaction.signatures.first().copied().unwrap_or_default();
+        let meta = self
+            .blockstore
+            .read_transaction_status((signature, slot))
+            .ok()
+            .flatten();
+        View::Transaction {
+            slot,
+            lines: transaction_lines(slot, transaction, meta.as_ref()),
+        }
+    }
+
+    /// Jumps to the slot or transaction identified by `query`
+    fn search(&mut self, query: &str) {
+        if query.is_empty() {
+            return;
+        }
+        if let Ok(slot) = Slot::from_str(query) {
+            self.stack.truncate(1);
+            match self
+                .slots
+                .binary_search_by_key(&slot, |summary| summary.slot)
+            {
+                Ok(index) => {
+                    self.current_mut().cursor = index;
+                    self.open_selected();
+                }
+                Err(index) => {
+                    self.current_mut().cursor = index.min(self.slots.len().saturating_sub(1));
+                    self.status = format!("Slot {slot} is not in the loaded slot list");
+                }
+            }
+            return;
+        }
+        let Ok(signature) = Signature::from_str(query) else {
+            self.status = format!("{query} is neither a slot nor a transaction signature");
+            return;
+        };
+        let loaded_slots: HashSet<_> = self.slots.iter().map(|summary| summary.slot).collect();
+        let slot = match self
+            .blockstore
+            .get_transaction_status(signature, &loaded_slots)
+        {
+            Ok(Some((slot, _meta))) => slot,
+            Ok(None) => {
+                self.status = format!("Transaction {signature} not found");
+                return;
+            }
+            Err(err) => {
+                self.status = format!("Failed to look up transaction {signature}: {err}");
+                return;
+            }
+        };
+        let transaction = self
+            .blockstore
+            .get_slot_entries(slot, /*shred_start_index:*/ 0)
+            .ok()
+            .into_iter()
+            .flatten()
+            .flat_map(|entry| entry.transactions)
+            .find(|transaction| transaction.signatures.first() == Some(&signature));
+        let Some(transaction) = transaction else {
+            self.status =
+                format!("Transaction {signature} not found in the entries of slot {slot}");
+            return;
+        };
+        self.stack.truncate(1);
+        let view = self.transaction_view(slot, &transaction);
+        self.stack.push(ViewState { view, cursor: 0 });
+    }
+
+    fn title(&self) -> String {
+        match &self.current().view {
+            View::Slots => "Slots".to_string(),
+            View::Entries { slot, entries } => {
+                format!("Slot {slot} > {} entries", entries.len())
+            }
+            View::Transactions {
+                slot,
+                entry_index,
+                transactions,
+            } => format!(
+                "Slot {slot} > Entry {entry_index} > {} transactions",
+                transactions.len()
+            ),
+            View::Transaction { slot, .. } => format!("Slot {slot} > Transaction"),
+        }
+    }
+
+    fn row(&self, index: usize) -> String {
+        match &self.current().view {
+            View::Slots => {
+                let summary = &self.slots[index];
+                let parent = summary
+                    .parent_slot
+                    .map(|parent| parent.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                let state = if summary.is_dead {
+                    "dead"
+                } else if summary.is_root {
+                    "root"
+                } else if summary.is_full {
+                    "full"
+                } else {
+                    "partial"
+                };
+                format!(
+                    "{:>12}  parent {:>12}  {:<7}  {:>6} shreds",
+                    summary.slot, parent, state, summary.num_shreds
+                )
+            }
+            View::Entries { entries, .. } => {
+                let entry = &entries[index];
+                format!(
+                    "{index:>6}  {}  hashes {:>8}  {}",
+                    entry.hash,
+                    entry.num_hashes,
+                    if entry.is_tick() {
+                        "tick".to_string()
+                    } else {
+                        format!("{} transactions", entry.transactions.len())
+                    },
+                )
+            }
+            View::Transactions { transactions, .. } => {
+                let transaction = &transactions[index];
+                format!(
+                    "{index:>6}  {}  {} instructions",
+                    transaction.signatures.first().copied().unwrap_or_default(),
+                    transaction.message.instructions().len(),
+                )
+            }
+            View::Transaction { lines, .. } => lines[index].clone(),
+        }
+    }
+
+    fn render(&self, term_rows: usize, term_cols: usize) -> Vec<String> {
+        let height = body_height(term_rows);
+        let num_rows = self.num_rows();
+        let cursor = self.current().cursor;
+        // Keep the cursor in view, scrolling a page at a time
+        let start = cursor.saturating_sub(cursor % height.max(1));
+        let end = start.saturating_add(height).min(num_rows);
+
+        let mut lines = Vec::with_capacity(term_rows);
+        lines.push(style(truncate(&self.title(), term_cols)).bold().to_string());
+        for index in start..end {
+            let row = truncate(&self.row(index), term_cols.saturating_sub(2));
+            if index == cursor {
+                lines.push(style(format!("> {row}")).reverse().to_string());
+            } else {
+                lines.push(format!("  {row}"));
+            }
+        }
+        lines.extend(std::iter::repeat_n(
+            String::new(),
+            height.saturating_sub(end - start),
+        ));
+        lines.push(truncate(&self.status, term_cols));
+        lines.push(style(truncate(HELP, term_cols)).dim().to_string());
+        lines
+    }
+}
+
+/// Number of terminal rows available for the list, excluding the title,
+/// status and help lines
+fn body_height(term_rows: usize) -> usize {
+    term_rows.saturating_sub(4)
+}
+
+fn truncate(line: &str, max_chars: usize) -> String {
+    line.chars().take(max_chars).collect()
+}
+
+fn transaction_lines(
+    slot: Slot,
+    transaction: &VersionedTransaction,
+    meta: Option<&TransactionStatusMeta>,
+) -> Vec<String> {
+    let mut lines = vec![];
+    for signature in &transaction.signatures {
+        lines.push(format!("Signature: {signature}"));
+    }
+    lines.push(format!("Slot: {slot}"));
+    lines.push(format!("Version: {:?}", transaction.version()));
+    lines.push(format!(
+        "Recent blockhash: {}",
+        transaction.message.recent_blockhash()
+    ));
+
+    let Some(meta) = meta else {
+        lines.push("Status: unavailable (no transaction status metadata)".to_string());
+        return lines;
+    };
+    lines.push(format!(
+        "Status: {}",
+        match &meta.status {
+            Ok(()) => "Ok".to_string(),
+            Err(err) => format!("{err}"),
+        }
+    ));
+    lines.push(format!("Fee: ◎{}", lamports_to_sol(meta.fee)));
+    if let Some(compute_units_consumed) = meta.compute_units_consumed {
+        lines.push(format!("Compute units consumed: {compute_units_consumed}"));
+    }
+
+    lines.push(String::new());
+    lines.push("Account balance changes:".to_string());
+    let account_keys = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .chain(meta.loaded_addresses.writable.iter())
+        .chain(meta.loaded_addresses.readonly.iter());
+    for (index, account_key) in account_keys.enumerate() {
+        let pre_balance = meta.pre_balances.get(index).copied().unwrap_or_default();
+        let post_balance = meta.post_balances.get(index).copied().unwrap_or_default();
+        let delta = i128::from(post_balance) - i128::from(pre_balance);
+        let delta = if delta == 0 {
+            String::new()
+        } else {
+            let sign = if delta > 0 { "+" } else { "-" };
+            format!(
+                "  ({sign}◎{})",
+                lamports_to_sol(delta.unsigned_abs() as u64)
+            )
+        };
+        lines.push(format!(
+            "  {index:>3} {account_key:<44}  ◎{}{delta}",
+            lamports_to_sol(post_balance)
+        ));
+    }
+
+    if let Some(log_messages) = &meta.log_messages {
+        lines.push(String::new());
+        lines.push("Log messages:".to_string());
+        lines.extend(log_messages.iter().map(|message| format!("  {message}")));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::{blockstore::make_many_slot_entries, get_tmp_ledger_path_auto_delete},
+    };
+
+    #[test]
+    fn test_navigation() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let (shreds, _) = make_many_slot_entries(0, 5, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let mut tui = LedgerTui::new(&blockstore, 0, 3).unwrap();
+        assert_eq!(tui.num_rows(), 3);
+
+        assert_eq!(tui.handle_key(Key::ArrowDown, 24), Action::Continue);
+        assert_eq!(tui.current().cursor, 1);
+        tui.handle_key(Key::End, 24);
+        assert_eq!(tui.current().cursor, 2);
+        tui.handle_key(Key::ArrowDown, 24);
+        assert_eq!(tui.current().cursor, 2);
+
+        tui.handle_key(Key::Enter, 24);
+        assert!(matches!(tui.current().view, View::Entries { slot: 2, .. }));
+        assert!(tui.title().starts_with("Slot 2"));
+
+        tui.handle_key(Key::Escape, 24);
+        assert!(matches!(tui.current().view, View::Slots));
+        // The slot list is never popped
+        tui.handle_key(Key::Escape, 24);
+        assert_eq!(tui.stack.len(), 1);
+
+        assert_eq!(tui.handle_key(Key::Char('/'), 24), Action::Search);
+        assert_eq!(tui.handle_key(Key::Char('q'), 24), Action::Quit);
+    }
+
+    #[test]
+    fn test_search_slot() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let (shreds, _) = make_many_slot_entries(0, 5, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let mut tui = LedgerTui::new(&blockstore, 0, 10).unwrap();
+        tui.search("3");
+        assert_eq!(tui.stack.len(), 2);
+        assert_eq!(tui.stack[0].cursor, 3);
+        assert!(matches!(tui.current().view, View::Entries { slot: 3, .. }));
+
+        tui.search("42");
+        assert_eq!(tui.stack.len(), 1);
+        assert!(tui.status.contains("42"));
+
+        tui.search("not-a-slot");
+        assert!(tui
+            .status
+            .contains("neither a slot nor a transaction signature"));
+    }
+
+    #[test]
+    fn test_render() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let (shreds, _) = make_many_slot_entries(0, 30, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let mut tui = LedgerTui::new(&blockstore, 0, 30).unwrap();
+        let term_rows = 14;
+        let lines = tui.render(term_rows, 80);
+        assert_eq!(lines.len(), term_rows);
+
+        // Moving past the first page scrolls the list
+        tui.handle_key(Key::PageDown, term_rows);
+        assert_eq!(tui.current().cursor, body_height(term_rows));
+        let lines = tui.render(term_rows, 80);
+        assert_eq!(lines.len(), term_rows);
+        assert!(lines[1].contains(&body_height(term_rows).to_string()));
+    }
+}
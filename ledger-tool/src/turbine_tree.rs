@@ -0,0 +1,306 @@
+//! The `turbine-tree` subcommand: prints the turbine retransmit tree of a
+//! shred, as computed from the epoch stakes of the ledger, to debug shred
+//! propagation gaps
+
+use {
+    crate::{
+        args::*,
+        canonicalize_ledger_path,
+        error::{LedgerToolError, Result},
+        ledger_utils::*,
+    },
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_clap_utils::{
+        input_parsers::pubkey_of,
+        input_validators::{is_parsable, is_pubkey, is_slot},
+    },
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_ledger::{
+        blockstore_options::AccessType,
+        leader_schedule_utils::slot_leader_at,
+        shred::{ShredId, ShredType},
+    },
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    solana_turbine::cluster_nodes::{get_data_plane_fanout, RetransmitTree},
+    std::{
+        fmt::{self, Display, Formatter},
+        path::Path,
+        sync::Arc,
+    },
+};
+
+pub trait TurbineTreeSubCommand {
+    fn turbine_tree_subcommand(self) -> Self;
+}
+
+impl TurbineTreeSubCommand for App<'_, '_> {
+    fn turbine_tree_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("turbine-tree")
+                .about(
+                    "Print the turbine retransmit tree of a shred. Only staked nodes are \
+                     included, as the positions of unstaked nodes depend on gossip.",
+                )
+                .arg(&load_genesis_arg())
+                .args(&snapshot_args())
+                .args(&accounts_db_args())
+                .arg(
+                    Arg::with_name("slot")
+                        .long("slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .required(true)
+                        .help("Slot of the shred"),
+                )
+                .arg(
+                    Arg::with_name("shred_index")
+                        .long("shred-index")
+                        .value_name("INDEX")
+                        .takes_value(true)
+                        .validator(is_parsable::<u32>)
+                        .default_value("0")
+                        .help("Index of the shred within the slot"),
+                )
+                .arg(
+                    Arg::with_name("shred_type")
+                        .long("shred-type")
+                        .value_name("TYPE")
+                        .takes_value(true)
+                        .possible_values(&["data", "code"])
+                        .default_value("data")
+                        .help("Type of the shred"),
+                )
+                .arg(
+                    Arg::with_name("leader")
+                        .long("leader")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .validator(is_pubkey)
+                        .help(
+                            "Leader of the slot [default: the leader in the leader schedule of \
+                             the ledger]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("fanout")
+                        .long("fanout")
+                        .value_name("FANOUT")
+                        .takes_value(true)
+                        .validator(is_parsable::<usize>)
+                        .help(
+                            "Fanout of the tree [default: the fanout in effect at the slot as of \
+                             the ledger]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("node")
+                        .long("node")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .validator(is_pubkey)
+                        .help("Print the parents and the children of this node"),
+                ),
+        )
+    }
+}
+
+pub fn turbine_tree_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    do_turbine_tree_process_command(ledger_path, matches).unwrap_or_else(|err| {
+        eprintln!("Failed to complete command: {err:?}");
+        std::process::exit(1);
+    });
+}
+
+fn do_turbine_tree_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -> Result<()> {
+    let slot = value_t_or_exit!(matches, "slot", Slot);
+    let shred_index = value_t_or_exit!(matches, "shred_index", u32);
+    let shred_type = match matches.value_of("shred_type") {
+        Some("code") => ShredType::Code,
+        _ => ShredType::Data,
+    };
+    let node = pubkey_of(matches, "node");
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let bank = load_bank(&ledger_path, matches);
+    let leader = match pubkey_of(matches, "leader") {
+        Some(leader) => leader,
+        None => slot_leader_at(slot, &bank).ok_or_else(|| {
+            LedgerToolError::BadArgument(format!(
+                "no leader schedule for slot {slot}, try --leader"
+            ))
+        })?,
+    };
+    let fanout =
+        value_t!(matches, "fanout", usize).unwrap_or_else(|_| get_data_plane_fanout(slot, &bank));
+    let epoch = bank.epoch_schedule().get_epoch(slot);
+    let stakes = bank.epoch_staked_nodes(epoch).ok_or_else(|| {
+        LedgerToolError::BadArgument(format!(
+            "no stakes for epoch {epoch} as of slot {}",
+            bank.slot()
+        ))
+    })?;
+
+    let shred = ShredId::new(slot, shred_index, shred_type);
+    let tree = RetransmitTree::new(&leader, &shred, fanout, &stakes);
+    let output = CliTurbineTree::new(&tree, slot, &shred, &leader, fanout, node.as_ref())?;
+    println!("{}", output_format.formatted_string(&output));
+    Ok(())
+}
+
+fn load_bank(ledger_path: &Path, arg_matches: &ArgMatches<'_>) -> Arc<Bank> {
+    let process_options = parse_process_options(ledger_path, arg_matches);
+    let genesis_config = open_genesis_config_by(ledger_path, arg_matches);
+    let blockstore = open_blockstore(ledger_path, arg_matches, AccessType::Secondary);
+    let LoadAndProcessLedgerOutput { bank_forks, .. } = load_and_process_ledger_or_exit(
+        arg_matches,
+        &genesis_config,
+        Arc::new(blockstore),
+        process_options,
+        None,
+    );
+    let bank = bank_forks.read().unwrap().working_bank();
+    bank
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliTurbineTree {
+    slot: Slot,
+    shred_index: u32,
+    shred_type: String,
+    leader: String,
+    fanout: usize,
+    /// Parents and children of the node passed with --node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node: Option<CliTurbineTreeNeighbors>,
+    /// Staked nodes in the order of the shuffle
+    nodes: Vec<CliTurbineTreeNode>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliTurbineTreeNode {
+    index: usize,
+    root_distance: u8,
+    pubkey: String,
+    stake: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CliTurbineTreeNeighbors {
+    pubkey: String,
+    index: usize,
+    root_distance: u8,
+    /// Nodes which relay the shred to this node, the root of the tree first
+    parents: Vec<String>,
+    /// Staked nodes which this node retransmits the shred to
+    children: Vec<String>,
+}
+
+impl CliTurbineTree {
+    fn new(
+        tree: &RetransmitTree,
+        slot: Slot,
+        shred: &ShredId,
+        leader: &Pubkey,
+        fanout: usize,
+        node: Option<&Pubkey>,
+    ) -> Result<Self> {
+        let pubkey = |index: usize| tree.nodes()[index].0.to_string();
+        let node = node
+            .map(|node| {
+                let index = tree.get_index(node).ok_or_else(|| {
+                    LedgerToolError::BadArgument(format!(
+                        "{node} is not a staked node other than the leader"
+                    ))
+                })?;
+                let mut parents: Vec<_> =
+                    std::iter::successors(tree.get_parent(index), |&k| tree.get_parent(k))
+                        .map(pubkey)
+                        .collect();
+                parents.reverse();
+                Ok(CliTurbineTreeNeighbors {
+                    pubkey: node.to_string(),
+                    index,
+                    root_distance: tree.get_root_distance(index),
+                    parents,
+                    children: tree.get_children(index).into_iter().map(pubkey).collect(),
+                })
+            })
+            .transpose()?;
+        let nodes = tree
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(index, (node, stake))| CliTurbineTreeNode {
+                index,
+                root_distance: tree.get_root_distance(index),
+                pubkey: node.to_string(),
+                stake: *stake,
+                parent: tree.get_parent(index).map(pubkey),
+            })
+            .collect();
+        Ok(Self {
+            slot,
+            shred_index: shred.index(),
+            shred_type: format!("{:?}", shred.shred_type()),
+            leader: leader.to_string(),
+            fanout,
+            node,
+            nodes,
+        })
+    }
+}
+
+impl VerboseDisplay for CliTurbineTree {}
+impl QuietDisplay for CliTurbineTree {}
+
+impl Display for CliTurbineTree {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Slot: {}, shred: {} ({}), leader: {}, fanout: {}",
+            self.slot, self.shred_index, self.shred_type, self.leader, self.fanout
+        )?;
+        if let Some(node) = &self.node {
+            writeln!(
+                f,
+                "Node {}: index: {}, root distance: {}",
+                node.pubkey, node.index, node.root_distance
+            )?;
+            writeln!(f, "  Parents, root first:")?;
+            for parent in &node.parents {
+                writeln!(f, "    {parent}")?;
+            }
+            writeln!(f, "  Staked children:")?;
+            for child in &node.children {
+                writeln!(f, "    {child}")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(
+            f,
+            "{:>6} {:>5} {:<44} {:>20} {:<44}",
+            "Index", "Hops", "Node", "Stake", "Parent"
+        )?;
+        for node in &self.nodes {
+            writeln!(
+                f,
+                "{:>6} {:>5} {:<44} {:>20} {:<44}",
+                node.index,
+                node.root_distance,
+                node.pubkey,
+                node.stake,
+                node.parent.as_deref().unwrap_or("-"),
+            )?;
+        }
+        Ok(())
+    }
+}
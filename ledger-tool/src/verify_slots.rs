@@ -0,0 +1,309 @@
+//! Verification of replayed slots against a reference slots file, as written
+//! by `verify --record-slots`.
+//!
+//! The bank hash of every replayed slot is compared with the reference, as
+//! are its capitalization and accounts delta hash when the reference includes
+//! them. Divergences are collected into a machine-readable report so that
+//! consensus bugs can be bisected automatically.
+
+use {
+    log::*,
+    serde_derive::Serialize,
+    solana_ledger::blockstore_processor::ProcessSlotCallback,
+    solana_runtime::bank::{
+        bank_hash_details::{self, BankHashDetails, SlotDetails},
+        Bank,
+    },
+    solana_sdk::clock::Slot,
+    std::{
+        collections::HashMap,
+        fs::File,
+        io::{BufReader, BufWriter},
+        path::{Path, PathBuf},
+        process::exit,
+        sync::{Arc, Mutex},
+    },
+};
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDivergence {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotDivergence {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub fields: Vec<FieldDivergence>,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DivergenceReport {
+    pub reference_file: String,
+    pub num_slots_verified: u64,
+    /// Highest replayed slot matching the reference
+    pub last_matching_slot: Option<Slot>,
+    pub halted: bool,
+    pub divergences: Vec<SlotDivergence>,
+    /// Replayed slots which are not in the reference
+    pub missing_slots: Vec<Slot>,
+    /// Reference slots which have not been replayed
+    pub unreplayed_slots: Vec<Slot>,
+}
+
+#[derive(Default)]
+struct VerifierState {
+    reference: HashMap<Slot, SlotDetails>,
+    report: DivergenceReport,
+}
+
+pub struct SlotVerifier {
+    state: Mutex<VerifierState>,
+    halt_on_divergence: bool,
+    report_path: Option<PathBuf>,
+}
+
+impl SlotVerifier {
+    pub fn new(
+        reference_path: &Path,
+        halt_on_divergence: bool,
+        report_path: Option<PathBuf>,
+    ) -> Result<Self, String> {
+        let file = File::open(reference_path)
+            .map_err(|err| format!("Unable to read file: {}: {err:#}", reference_path.display()))?;
+        let details: BankHashDetails = serde_json::from_reader(BufReader::new(file))
+            .map_err(|err| format!("Error loading slots file: {err:#}"))?;
+        Ok(Self::new_with_reference(
+            reference_path.display().to_string(),
+            details.bank_hash_details,
+            halt_on_divergence,
+            report_path,
+        ))
+    }
+
+    fn new_with_reference(
+        reference_file: String,
+        reference: Vec<SlotDetails>,
+        halt_on_divergence: bool,
+        report_path: Option<PathBuf>,
+    ) -> Self {
+        let reference = reference
+            .into_iter()
+            .map(|details| (details.slot, details))
+            .collect();
+        Self {
+            state: Mutex::new(VerifierState {
+                reference,
+                report: DivergenceReport {
+                    reference_file,
+                    ..DivergenceReport::default()
+                },
+            }),
+            halt_on_divergence,
+            report_path,
+        }
+    }
+
+    pub fn slot_callback(self: &Arc<Self>) -> ProcessSlotCallback {
+        let verifier = Arc::clone(self);
+        Arc::new(move |bank: &Bank| verifier.verify_bank(bank))
+    }
+
+    fn verify_bank(&self, bank: &Bank) {
+        let mut state = self.state.lock().unwrap();
+        let Some(expected) = state.reference.remove(&bank.slot()) else {
+            error!(
+                "Expected slot: not found got slot: {} hash: {}",
+                bank.slot(),
+                bank.hash()
+            );
+            state.report.missing_slots.push(bank.slot());
+            return;
+        };
+        let actual = ActualSlot {
+            bank_hash: bank.hash().to_string(),
+            capitalization: bank.capitalization(),
+            // Computing the accounts delta hash is only needed when the
+            // reference has it.
+            accounts_delta_hash: expected
+                .bank_hash_components
+                .as_ref()
+                .and_then(|components| components.accounts_delta_hash.as_ref())
+                .and_then(|_| bank_hash_details::accounts_delta_hash(bank)),
+        };
+        let fields = compare_slot(&expected, &actual);
+        state.report.num_slots_verified += 1;
+        if fields.is_empty() {
+            info!(
+                "Expected slot: {} hash: {} correct",
+                expected.slot, expected.bank_hash
+            );
+            state.report.last_matching_slot =
+                state.report.last_matching_slot.max(Some(bank.slot()));
+            return;
+        }
+
+        for field in &fields {
+            error!(
+                "Slot {} diverged: {} expected: {} got: {}",
+                bank.slot(),
+                field.field,
+                field.expected,
+                field.actual
+            );
+        }
+        state.report.divergences.push(SlotDivergence {
+            slot: bank.slot(),
+            parent_slot: bank.parent_slot(),
+            fields,
+        });
+        if self.halt_on_divergence {
+            state.report.halted = true;
+            self.write_report(&mut state);
+            eprintln!("Halting at diverged slot {}", bank.slot());
+            exit(1);
+        }
+    }
+
+    /// Writes the report, if requested, once the ledger has been replayed.
+    pub fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        self.write_report(&mut state);
+    }
+
+    fn write_report(&self, state: &mut VerifierState) {
+        let mut unreplayed_slots: Vec<_> = state.reference.keys().copied().collect();
+        unreplayed_slots.sort_unstable();
+        state.report.unreplayed_slots = unreplayed_slots;
+        let Some(report_path) = &self.report_path else {
+            return;
+        };
+        let result = File::create(report_path)
+            .map_err(|err| err.to_string())
+            .and_then(|file| {
+                serde_json::to_writer_pretty(BufWriter::new(file), &state.report)
+                    .map_err(|err| err.to_string())
+            });
+        match result {
+            Ok(()) => info!("Wrote divergence report to {}", report_path.display()),
+            Err(err) => eprintln!(
+                "Unable to write divergence report: {}: {err}",
+                report_path.display()
+            ),
+        }
+    }
+}
+
+struct ActualSlot {
+    bank_hash: String,
+    capitalization: u64,
+    accounts_delta_hash: Option<String>,
+}
+
+// Returns the fields of the replayed slot which differ from the reference.
+// Fields missing from the reference are not compared.
+fn compare_slot(expected: &SlotDetails, actual: &ActualSlot) -> Vec<FieldDivergence> {
+    let mut fields = vec![];
+    let mut compare = |field: &str, expected: String, actual: String| {
+        if expected != actual {
+            fields.push(FieldDivergence {
+                field: field.to_string(),
+                expected,
+                actual,
+            });
+        }
+    };
+    compare(
+        "bankHash",
+        expected.bank_hash.clone(),
+        actual.bank_hash.clone(),
+    );
+    if let Some(capitalization) = expected.capitalization {
+        compare(
+            "capitalization",
+            capitalization.to_string(),
+            actual.capitalization.to_string(),
+        );
+    }
+    if let Some(accounts_delta_hash) = expected
+        .bank_hash_components
+        .as_ref()
+        .and_then(|components| components.accounts_delta_hash.clone())
+    {
+        compare(
+            "accountsDeltaHash",
+            accounts_delta_hash,
+            actual.accounts_delta_hash.clone().unwrap_or_default(),
+        );
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_runtime::bank::bank_hash_details::BankHashComponents};
+
+    #[test]
+    fn test_compare_slot() {
+        let mut expected = SlotDetails {
+            slot: 10,
+            bank_hash: "hash".to_string(),
+            ..SlotDetails::default()
+        };
+        let actual = ActualSlot {
+            bank_hash: "hash".to_string(),
+            capitalization: 42,
+            accounts_delta_hash: Some("delta".to_string()),
+        };
+        // Only the bank hash is compared when the reference has nothing else.
+        assert!(compare_slot(&expected, &actual).is_empty());
+
+        expected.capitalization = Some(42);
+        expected.bank_hash_components = Some(BankHashComponents {
+            accounts_delta_hash: Some("delta".to_string()),
+            ..BankHashComponents::default()
+        });
+        assert!(compare_slot(&expected, &actual).is_empty());
+
+        expected.bank_hash = "other".to_string();
+        expected.capitalization = Some(43);
+        assert_eq!(
+            compare_slot(&expected, &actual),
+            vec![
+                FieldDivergence {
+                    field: "bankHash".to_string(),
+                    expected: "other".to_string(),
+                    actual: "hash".to_string(),
+                },
+                FieldDivergence {
+                    field: "capitalization".to_string(),
+                    expected: "43".to_string(),
+                    actual: "42".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        let reference = [1, 3]
+            .into_iter()
+            .map(|slot| SlotDetails {
+                slot,
+                bank_hash: format!("bank{slot}"),
+                ..SlotDetails::default()
+            })
+            .collect();
+        let verifier =
+            SlotVerifier::new_with_reference("slots.json".to_string(), reference, false, None);
+        verifier.finish();
+        let state = verifier.state.lock().unwrap();
+        assert_eq!(state.report.unreplayed_slots, vec![1, 3]);
+        assert_eq!(state.report.num_slots_verified, 0);
+    }
+}
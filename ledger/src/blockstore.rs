@@ -263,6 +263,7 @@ pub struct Blockstore {
     optimistic_slots_cf: LedgerColumn<cf::OptimisticSlots>,
     orphans_cf: LedgerColumn<cf::Orphans>,
     perf_samples_cf: LedgerColumn<cf::PerfSamples>,
+    program_signatures_cf: LedgerColumn<cf::ProgramSignatures>,
     rewards_cf: LedgerColumn<cf::Rewards>,
     roots_cf: LedgerColumn<cf::Root>,
     transaction_memos_cf: LedgerColumn<cf::TransactionMemos>,
@@ -410,6 +411,7 @@ impl Blockstore {
         let optimistic_slots_cf = db.column();
         let orphans_cf = db.column();
         let perf_samples_cf = db.column();
+        let program_signatures_cf = db.column();
         let rewards_cf = db.column();
         let roots_cf = db.column();
         let transaction_memos_cf = db.column();
@@ -444,6 +446,7 @@ impl Blockstore {
             optimistic_slots_cf,
             orphans_cf,
             perf_samples_cf,
+            program_signatures_cf,
             rewards_cf,
             roots_cf,
             transaction_memos_cf,
@@ -867,6 +870,39 @@ impl Blockstore {
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
         self.merkle_root_meta_cf.submit_rocksdb_cf_metrics();
+        self.program_signatures_cf.submit_rocksdb_cf_metrics();
+    }
+
+    /// Returns the names of the column families of the blockstore.
+    pub fn column_names() -> &'static [&'static str] {
+        const COLUMNS: [&str; 21] = Rocks::columns();
+        &COLUMNS
+    }
+
+    fn check_column_name(name: &str) -> Result<()> {
+        if Self::column_names().contains(&name) {
+            Ok(())
+        } else {
+            Err(BlockstoreError::UnknownColumn(name.to_string()))
+        }
+    }
+
+    /// Compacts the whole of the column family `name`, blocking until the
+    /// compaction completes. Unlike the periodic compactions configured for
+    /// some columns, this may rewrite many files at once, so it is best done
+    /// when the node is not leader.
+    pub fn compact_column(&self, name: &str) -> Result<()> {
+        Self::check_column_name(name)?;
+        self.db.compact_cf(name);
+        Ok(())
+    }
+
+    /// Returns the estimated number of bytes compaction needs to rewrite in
+    /// the column family `name`, i.e. its compaction debt.
+    pub fn pending_compaction_bytes(&self, name: &str) -> Result<u64> {
+        Self::check_column_name(name)?;
+        let bytes = self.db.pending_compaction_bytes(name)?;
+        Ok(u64::try_from(bytes).unwrap_or_default())
     }
 
     /// Report the accumulated RPC API metrics
@@ -2574,6 +2610,83 @@ impl Blockstore {
         )
     }
 
+    /// Removes from `missing_indexes`, the indexes of missing data shreds of
+    /// the given `slot` in ascending order, those which are not needed to
+    /// recover their erasure batch.
+    ///
+    /// Any data or coding shred of an erasure batch counts the same toward
+    /// its recovery, so for each batch with a received coding shred only as
+    /// many of its missing data shreds are kept as are needed to recover the
+    /// rest. Requesting coding shreds instead would never take fewer
+    /// requests, and data shreds are usable without recovery. Batches which
+    /// already have enough shreds keep all their missing data shreds, as their
+    /// recovery must have failed.
+    pub fn prune_recoverable_data_indexes(
+        &self,
+        slot: Slot,
+        missing_indexes: Vec<u64>,
+    ) -> Vec<u64> {
+        let (Some(&first), Some(&last)) = (missing_indexes.first(), missing_indexes.last()) else {
+            return missing_indexes;
+        };
+        let Ok(Some(index)) = self.get_index(slot) else {
+            return missing_indexes;
+        };
+        let Ok(erasure_metas) = self
+            .erasure_meta_cf
+            .iter(IteratorMode::From((slot, 0), IteratorDirection::Forward))
+        else {
+            return missing_indexes;
+        };
+        // Number of missing data shreds to keep from each erasure batch which
+        // can be recovered, keyed by the range of its data shreds.
+        let mut num_needed = Vec::<(Range<u64>, usize)>::new();
+        for ((erasure_meta_slot, fec_set_index), erasure_meta) in erasure_metas {
+            if erasure_meta_slot != slot || fec_set_index > last {
+                break;
+            }
+            let Ok(erasure_meta) = deserialize::<ErasureMeta>(erasure_meta.as_ref()) else {
+                continue;
+            };
+            let data_shreds_indices = erasure_meta.data_shreds_indices();
+            if data_shreds_indices.end <= first {
+                continue;
+            }
+            let num_received = index.data().range(data_shreds_indices.clone()).count()
+                + index
+                    .coding()
+                    .range(erasure_meta.coding_shreds_indices())
+                    .count();
+            let num_data = (data_shreds_indices.end - data_shreds_indices.start) as usize;
+            if num_received < num_data {
+                num_needed.push((data_shreds_indices, num_data - num_received));
+            }
+        }
+        if num_needed.is_empty() {
+            return missing_indexes;
+        }
+        let mut num_needed = num_needed.into_iter().peekable();
+        missing_indexes
+            .into_iter()
+            .filter(|shred_index| {
+                while num_needed
+                    .next_if(|(data_shreds_indices, _)| data_shreds_indices.end <= *shred_index)
+                    .is_some()
+                {}
+                match num_needed.peek_mut() {
+                    Some((data_shreds_indices, count))
+                        if data_shreds_indices.contains(shred_index) =>
+                    {
+                        let keep = *count > 0;
+                        *count = count.saturating_sub(1);
+                        keep
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
     fn get_block_time(&self, slot: Slot) -> Result<Option<UnixTimestamp>> {
         let _lock = self.check_lowest_cleanup_slot(slot)?;
         self.blocktime_cf.get(slot)
@@ -3004,6 +3117,28 @@ impl Blockstore {
         )
     }
 
+    /// Indexes the signature of a transaction by the programs it invokes, in
+    /// the program signatures column.
+    pub fn add_program_signatures_to_batch<'a>(
+        &self,
+        slot: Slot,
+        signature: Signature,
+        program_ids: impl Iterator<Item = &'a Pubkey>,
+        transaction_index: usize,
+        db_write_batch: &mut WriteBatch,
+    ) -> Result<()> {
+        let transaction_index = u32::try_from(transaction_index)
+            .map_err(|_| BlockstoreError::TransactionIndexOverflow)?;
+        for program_id in program_ids {
+            self.program_signatures_cf.put_in_batch(
+                db_write_batch,
+                (*program_id, slot, transaction_index, signature),
+                &(),
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn read_transaction_memos(
         &self,
         signature: Signature,
@@ -3453,21 +3588,7 @@ impl Blockstore {
 
         // Fill in the status information for each found transaction
         let mut get_status_info_timer = Measure::start("get_status_info_timer");
-        let mut infos = vec![];
-        for (slot, signature) in address_signatures_iter {
-            let transaction_status =
-                self.get_transaction_status(signature, &confirmed_unrooted_slots)?;
-            let err = transaction_status.and_then(|(_slot, status)| status.status.err());
-            let memo = self.read_transaction_memos(signature, slot)?;
-            let block_time = self.get_block_time(slot)?;
-            infos.push(ConfirmedTransactionStatusWithSignature {
-                signature,
-                slot,
-                err,
-                memo,
-                block_time,
-            });
-        }
+        let infos = self.get_signature_infos(address_signatures_iter, &confirmed_unrooted_slots)?;
         get_status_info_timer.stop();
 
         datapoint_info!(
@@ -3505,6 +3626,103 @@ impl Blockstore {
         })
     }
 
+    /// Returns the signatures of the transactions invoking `program_id`, the
+    /// latest first, in the same way as
+    /// [`Blockstore::get_confirmed_signatures_for_address2`].
+    ///
+    /// Only the transactions written while the program signatures index was
+    /// enabled are returned.
+    pub fn get_confirmed_signatures_for_program(
+        &self,
+        program_id: Pubkey,
+        highest_slot: Slot, // highest_super_majority_root or highest_confirmed_slot
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> Result<SignatureInfosForAddress> {
+        self.rpc_api_metrics
+            .num_get_confirmed_signatures_for_program
+            .fetch_add(1, Ordering::Relaxed);
+
+        let max_root = self.max_root();
+        let confirmed_unrooted_slots: HashSet<_> =
+            AncestorIterator::new_inclusive(highest_slot, self)
+                .take_while(|&slot| slot > max_root)
+                .collect();
+
+        // The keys of the column are ordered by slot then transaction index,
+        // so `before` and `until` are located by their position in the ledger.
+        let get_position = |signature: Signature| -> Result<Option<(Slot, u32)>> {
+            let Some((slot, _)) =
+                self.get_transaction_status(signature, &confirmed_unrooted_slots)?
+            else {
+                return Ok(None);
+            };
+            let index = self
+                .get_block_signatures_rev(slot)?
+                .into_iter()
+                .rev()
+                .position(|other| other == signature);
+            Ok(index.map(|index| (slot, index as u32)))
+        };
+        let (slot, transaction_index) = match before {
+            None => (highest_slot.saturating_add(1), 0),
+            Some(before) => match get_position(before)? {
+                None => return Ok(SignatureInfosForAddress::default()),
+                Some(position) => position,
+            },
+        };
+        let until = until.map(get_position).transpose()?.flatten();
+        let lowest_slot = self.get_first_available_block()?;
+
+        let iterator = self.program_signatures_cf.iter(IteratorMode::From(
+            (program_id, slot, transaction_index, Signature::default()),
+            IteratorDirection::Reverse,
+        ))?;
+        let mut program_signatures = vec![];
+        for ((key_program_id, slot, transaction_index, signature), _) in iterator {
+            if program_signatures.len() >= limit
+                || key_program_id != program_id
+                || slot < lowest_slot
+                || until.is_some_and(|until| (slot, transaction_index) <= until)
+            {
+                break;
+            }
+            if self.is_root(slot) || confirmed_unrooted_slots.contains(&slot) {
+                program_signatures.push((slot, signature));
+            }
+        }
+
+        Ok(SignatureInfosForAddress {
+            infos: self.get_signature_infos(program_signatures, &confirmed_unrooted_slots)?,
+            found_before: true, // if `before` signature was not found, this method returned early
+        })
+    }
+
+    // Fills in the status information of each transaction
+    fn get_signature_infos(
+        &self,
+        signatures: impl IntoIterator<Item = (Slot, Signature)>,
+        confirmed_unrooted_slots: &HashSet<Slot>,
+    ) -> Result<Vec<ConfirmedTransactionStatusWithSignature>> {
+        let mut infos = vec![];
+        for (slot, signature) in signatures {
+            let transaction_status =
+                self.get_transaction_status(signature, confirmed_unrooted_slots)?;
+            let err = transaction_status.and_then(|(_slot, status)| status.status.err());
+            let memo = self.read_transaction_memos(signature, slot)?;
+            let block_time = self.get_block_time(slot)?;
+            infos.push(ConfirmedTransactionStatusWithSignature {
+                signature,
+                slot,
+                err,
+                memo,
+                block_time,
+            });
+        }
+        Ok(infos)
+    }
+
     pub fn read_rewards(&self, index: Slot) -> Result<Option<Rewards>> {
         self.rewards_cf
             .get_protobuf_or_bincode::<Rewards>(index)
@@ -6959,6 +7177,82 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_compact_column() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let (shreds, _) = make_slot_entries(1, 0, 10, /*merkle_variant:*/ true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        assert!(Blockstore::column_names().contains(&"data_shred"));
+        for name in Blockstore::column_names() {
+            blockstore.compact_column(name).unwrap();
+            blockstore.pending_compaction_bytes(name).unwrap();
+        }
+        assert_matches!(
+            blockstore.compact_column("unknown"),
+            Err(BlockstoreError::UnknownColumn(_))
+        );
+        assert_matches!(
+            blockstore.pending_compaction_bytes("unknown"),
+            Err(BlockstoreError::UnknownColumn(_))
+        );
+        // The shreds remain after compaction.
+        assert_eq!(blockstore.get_slot_entries(1, 0).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_prune_recoverable_data_indexes() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let slot = 1;
+        let (data_shreds, coding_shreds, leader_schedule_cache) =
+            setup_erasure_shreds(slot, 0, 100);
+        let num_data = data_shreds
+            .iter()
+            .filter(|shred| shred.fec_set_index() == 0)
+            .count();
+        let coding_shreds: Vec<_> = coding_shreds
+            .into_iter()
+            .filter(|shred| shred.fec_set_index() == 0)
+            .take(num_data / 2)
+            .collect();
+        let num_coding = coding_shreds.len();
+        assert!(num_coding > 0);
+
+        // Missing shreds of erasure batches without coding shreds are kept.
+        let missing: Vec<u64> = (1..=num_data as u64).collect();
+        assert_eq!(
+            blockstore.prune_recoverable_data_indexes(slot, missing.clone()),
+            missing
+        );
+        blockstore
+            .insert_shreds(
+                data_shreds[..1].to_vec(),
+                Some(&leader_schedule_cache),
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            blockstore.prune_recoverable_data_indexes(slot, missing.clone()),
+            missing
+        );
+
+        // Once coding shreds are received, only the data shreds needed to
+        // recover the erasure batch are kept.
+        blockstore
+            .insert_shreds(coding_shreds, Some(&leader_schedule_cache), false)
+            .unwrap();
+        let num_needed = num_data - 1 - num_coding;
+        let mut expected = missing[..num_needed].to_vec();
+        expected.push(num_data as u64);
+        assert_eq!(
+            blockstore.prune_recoverable_data_indexes(slot, missing),
+            expected
+        );
+    }
+
     #[test]
     fn test_find_missing_data_indexes_timeout() {
         let slot = 0;
@@ -9788,6 +10082,118 @@ pub mod tests {
         assert!(sig_infos.infos.is_empty());
     }
 
+    #[test]
+    fn test_get_confirmed_signatures_for_program() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let (shreds, _) = make_slot_entries(1, 0, 4, /*merkle_variant:*/ true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let program0 = solana_pubkey::new_rand();
+        let program1 = solana_pubkey::new_rand();
+        let mut signatures = HashMap::<(Slot, Pubkey), Vec<Signature>>::new();
+        for slot in 2..=4 {
+            let mut entries = vec![];
+            for program_id in [program0, program1, program0, program1] {
+                let transaction = Transaction::new_with_compiled_instructions(
+                    &[&Keypair::new()],
+                    &[program_id],
+                    Hash::default(),
+                    vec![],
+                    vec![CompiledInstruction::new(1, &(), vec![0])],
+                );
+                entries.push(next_entry_mut(&mut Hash::default(), 0, vec![transaction]));
+            }
+            let shreds = entries_to_test_shreds(
+                &entries,
+                slot,
+                slot - 1, // parent_slot
+                true,     // is_full_slot
+                0,        // version
+                true,     // merkle_variant
+            );
+            blockstore.insert_shreds(shreds, None, false).unwrap();
+
+            let mut batch = blockstore.get_write_batch().unwrap();
+            for (index, entry) in entries.into_iter().enumerate() {
+                let transaction = &entry.transactions[0];
+                let signature = transaction.signatures[0];
+                let program_id = &transaction.message.static_account_keys()[1];
+                blockstore
+                    .write_transaction_status(
+                        slot,
+                        signature,
+                        std::iter::empty(),
+                        TransactionStatusMeta::default(),
+                        index,
+                    )
+                    .unwrap();
+                blockstore
+                    .add_program_signatures_to_batch(
+                        slot,
+                        signature,
+                        std::iter::once(program_id),
+                        index,
+                        &mut batch,
+                    )
+                    .unwrap();
+                signatures
+                    .entry((slot, *program_id))
+                    .or_default()
+                    .push(signature);
+            }
+            blockstore.write_batch(batch).unwrap();
+        }
+        // Leave one slot unrooted to test only returns confirmed signatures
+        blockstore.set_roots([1, 2, 4].iter()).unwrap();
+        let highest_super_majority_root = 4;
+
+        let get_signatures = |before, until, limit| -> Vec<Signature> {
+            blockstore
+                .get_confirmed_signatures_for_program(
+                    program0,
+                    highest_super_majority_root,
+                    before,
+                    until,
+                    limit,
+                )
+                .unwrap()
+                .infos
+                .into_iter()
+                .map(|info| info.signature)
+                .collect()
+        };
+        let expected: Vec<_> = [4, 2]
+            .into_iter()
+            .flat_map(|slot| signatures[&(slot, program0)].iter().rev().copied())
+            .collect();
+        assert_eq!(get_signatures(None, None, usize::MAX), expected);
+
+        // Page through the signatures one at a time
+        let mut before = None;
+        for signature in &expected {
+            let page = get_signatures(before, None, 1);
+            assert_eq!(page, vec![*signature]);
+            before = Some(*signature);
+        }
+        assert!(get_signatures(before, None, 1).is_empty());
+
+        // `until` excludes the given signature and the older ones
+        assert_eq!(
+            get_signatures(None, Some(expected[2]), usize::MAX),
+            expected[..2]
+        );
+        // `before` may be a transaction not invoking the program
+        let before = signatures[&(4, program1)][1];
+        assert_eq!(get_signatures(Some(before), None, usize::MAX), expected);
+        let before = signatures[&(4, program1)][0];
+        assert_eq!(
+            get_signatures(Some(before), None, usize::MAX),
+            expected[1..]
+        );
+    }
+
     #[test]
     fn test_get_last_hash() {
         let entries: Vec<Entry> = vec![];
@@ -435,6 +435,12 @@ impl Blockstore {
             return Ok(());
         }
 
+        // The program signatures index is optional, skip its deletes if unused.
+        let program_signatures_empty = self
+            .program_signatures_cf
+            .iter(IteratorMode::Start)?
+            .next()
+            .is_none();
         let mut index0 = self.transaction_status_index_cf.get(0)?.unwrap_or_default();
         let mut index1 = self.transaction_status_index_cf.get(1)?.unwrap_or_default();
         let highest_primary_index_slot = self.get_highest_primary_index_slot();
@@ -489,6 +495,12 @@ impl Blockstore {
                             batch,
                             (*pubkey, slot, transaction_index, signature),
                         )?;
+                        if !program_signatures_empty {
+                            self.program_signatures_cf.delete_in_batch(
+                                batch,
+                                (*pubkey, slot, transaction_index, signature),
+                            )?;
+                        }
                         for primary_index in &primary_indexes {
                             self.address_signatures_cf.delete_deprecated_in_batch(
                                 batch,
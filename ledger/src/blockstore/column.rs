@@ -210,6 +210,16 @@ pub mod columns {
     /// * index type: `crate::shred::ErasureSetId` `(Slot, fec_set_index: u32)`
     /// * value type: [`blockstore_meta::MerkleRootMeta`]`
     pub struct MerkleRootMeta;
+
+    #[derive(Debug)]
+    /// The program signatures column
+    ///
+    /// Indexes the signatures of the transactions by the programs they
+    /// invoke, directly or through cross-program invocations.
+    ///
+    /// * index type: `(`[`Pubkey`]`, `[`Slot`]`, u32, `[`Signature`]`)`
+    /// * value type: `()`
+    pub struct ProgramSignatures;
 }
 
 macro_rules! convert_column_index_to_key_bytes {
@@ -308,6 +318,10 @@ impl TypedColumn for columns::AddressSignatures {
     type Type = blockstore_meta::AddressSignatureMeta;
 }
 
+impl TypedColumn for columns::ProgramSignatures {
+    type Type = ();
+}
+
 impl TypedColumn for columns::TransactionMemos {
     type Type = String;
 }
@@ -448,6 +462,46 @@ impl ColumnName for columns::AddressSignatures {
     const NAME: &'static str = "address_signatures";
 }
 
+impl Column for columns::ProgramSignatures {
+    type Index = (Pubkey, Slot, /*transaction index:*/ u32, Signature);
+    type Key = [u8; PUBKEY_BYTES
+        + std::mem::size_of::<Slot>()
+        + std::mem::size_of::<u32>()
+        + SIGNATURE_BYTES];
+
+    #[inline]
+    fn key((program_id, slot, transaction_index, signature): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+              ..32 => program_id.as_ref(),
+            32..40 => &slot.to_be_bytes(),
+            40..44 => &transaction_index.to_be_bytes(),
+            44..   => signature.as_ref(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+              0..32  => Pubkey::from,
+             32..40  => Slot::from_be_bytes,
+             40..44  => u32::from_be_bytes,
+             44..108 => Signature::from,
+        )
+    }
+
+    fn slot(index: Self::Index) -> Slot {
+        index.1
+    }
+
+    // The ProgramSignatures column is not keyed by slot so this method is meaningless
+    // See Column::as_index() declaration for more details
+    fn as_index(_index: u64) -> Self::Index {
+        (Pubkey::default(), 0, 0, Signature::default())
+    }
+}
+impl ColumnName for columns::ProgramSignatures {
+    const NAME: &'static str = "program_signatures";
+}
+
 impl ColumnIndexDeprecation for columns::AddressSignatures {
     const CURRENT_INDEX_LEN: usize = 108;
     type DeprecatedIndex = (u64, Pubkey, Slot, Signature);
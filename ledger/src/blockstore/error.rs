@@ -59,5 +59,26 @@ pub enum BlockstoreError {
     LegacyShred(Slot, u64),
     #[error("unable to read merkle root slot {0}, index {1}")]
     MissingMerkleRoot(Slot, u64),
+    #[error("unknown column family: {0}")]
+    UnknownColumn(String),
 }
 pub type Result<T> = std::result::Result<T, BlockstoreError>;
+
+impl BlockstoreError {
+    /// Returns true if the error indicates that the on-disk ledger is corrupt
+    pub fn is_corruption(&self) -> bool {
+        matches!(self, Self::RocksDb(err) if err.kind() == rocksdb::ErrorKind::Corruption)
+    }
+
+    /// Returns true if the error was caused by the ledger's filesystem running out of space
+    pub fn is_out_of_disk(&self) -> bool {
+        match self {
+            Self::Io(err) => err.kind() == std::io::ErrorKind::StorageFull,
+            Self::RocksDb(err) => {
+                err.kind() == rocksdb::ErrorKind::IOError
+                    && err.as_ref().contains("No space left on device")
+            }
+            _ => false,
+        }
+    }
+}
@@ -194,6 +194,7 @@ impl Rocks {
             new_cf_descriptor::<columns::BlockHeight>(options, oldest_slot),
             new_cf_descriptor::<columns::OptimisticSlots>(options, oldest_slot),
             new_cf_descriptor::<columns::MerkleRootMeta>(options, oldest_slot),
+            new_cf_descriptor::<columns::ProgramSignatures>(options, oldest_slot),
         ];
 
         // If the access type is Secondary, we don't need to open all of the
@@ -242,7 +243,7 @@ impl Rocks {
         cf_descriptors
     }
 
-    const fn columns() -> [&'static str; 20] {
+    pub(crate) const fn columns() -> [&'static str; 21] {
         [
             columns::ErasureMeta::NAME,
             columns::DeadSlots::NAME,
@@ -264,6 +265,7 @@ impl Rocks {
             columns::BlockHeight::NAME,
             columns::OptimisticSlots::NAME,
             columns::MerkleRootMeta::NAME,
+            columns::ProgramSignatures::NAME,
         ]
     }
 
@@ -446,6 +448,21 @@ impl Rocks {
         }
     }
 
+    /// Compacts the whole key range of the column family, blocking until the
+    /// compaction completes.
+    pub(crate) fn compact_cf(&self, cf_name: &str) {
+        let (start, end) = (None::<&[u8]>, None::<&[u8]>);
+        self.db
+            .compact_range_cf(self.cf_handle(cf_name), start, end);
+    }
+
+    pub(crate) fn pending_compaction_bytes(&self, cf_name: &str) -> Result<i64> {
+        self.get_int_property_cf(
+            self.cf_handle(cf_name),
+            RocksProperties::ESTIMATE_PENDING_COMPACTION_BYTES,
+        )
+    }
+
     pub(crate) fn live_files_metadata(&self) -> Result<Vec<LiveFile>> {
         match self.db.live_files() {
             Ok(live_files) => Ok(live_files),
@@ -520,6 +537,9 @@ impl<C: Column + ColumnName> LedgerColumn<C> {
             num_running_flushes: self
                 .get_int_property(RocksProperties::NUM_RUNNING_FLUSHES)
                 .unwrap_or(BLOCKSTORE_METRICS_ERROR),
+            estimate_pending_compaction_bytes: self
+                .get_int_property(RocksProperties::ESTIMATE_PENDING_COMPACTION_BYTES)
+                .unwrap_or(BLOCKSTORE_METRICS_ERROR),
             estimate_oldest_key_time: self
                 .get_int_property(RocksProperties::ESTIMATE_OLDEST_KEY_TIME)
                 .unwrap_or(BLOCKSTORE_METRICS_ERROR),
@@ -1241,6 +1261,7 @@ fn should_enable_cf_compaction(cf_name: &str) -> bool {
         columns::TransactionStatus::NAME
             | columns::TransactionMemos::NAME
             | columns::AddressSignatures::NAME
+            | columns::ProgramSignatures::NAME
     )
 }
 
@@ -1332,6 +1353,7 @@ pub mod tests {
         let columns_to_compact = [
             columns::TransactionStatus::NAME,
             columns::AddressSignatures::NAME,
+            columns::ProgramSignatures::NAME,
         ];
         columns_to_compact.iter().for_each(|cf_name| {
             assert!(should_enable_cf_compaction(cf_name));
@@ -144,6 +144,7 @@ pub(crate) struct BlockstoreRpcApiMetrics {
     pub num_get_complete_transaction: AtomicU64,
     pub num_get_confirmed_signatures_for_address: AtomicU64,
     pub num_get_confirmed_signatures_for_address2: AtomicU64,
+    pub num_get_confirmed_signatures_for_program: AtomicU64,
     pub num_get_rooted_block: AtomicU64,
     pub num_get_rooted_block_time: AtomicU64,
     pub num_get_rooted_transaction: AtomicU64,
@@ -163,6 +164,9 @@ impl BlockstoreRpcApiMetrics {
         let num_get_confirmed_signatures_for_address2 = self
             .num_get_confirmed_signatures_for_address2
             .swap(0, Ordering::Relaxed);
+        let num_get_confirmed_signatures_for_program = self
+            .num_get_confirmed_signatures_for_program
+            .swap(0, Ordering::Relaxed);
         let num_get_rooted_block = self.num_get_rooted_block.swap(0, Ordering::Relaxed);
         let num_get_rooted_block_time = self.num_get_rooted_block_time.swap(0, Ordering::Relaxed);
         let num_get_rooted_transaction = self.num_get_rooted_transaction.swap(0, Ordering::Relaxed);
@@ -178,6 +182,7 @@ impl BlockstoreRpcApiMetrics {
             .saturating_add(num_get_complete_transaction)
             .saturating_add(num_get_confirmed_signatures_for_address)
             .saturating_add(num_get_confirmed_signatures_for_address2)
+            .saturating_add(num_get_confirmed_signatures_for_program)
             .saturating_add(num_get_rooted_block)
             .saturating_add(num_get_rooted_block_time)
             .saturating_add(num_get_rooted_transaction)
@@ -204,6 +209,11 @@ impl BlockstoreRpcApiMetrics {
                     num_get_confirmed_signatures_for_address2 as i64,
                     i64
                 ),
+                (
+                    "num_get_confirmed_signatures_for_program",
+                    num_get_confirmed_signatures_for_program as i64,
+                    i64
+                ),
                 ("num_get_rooted_block", num_get_rooted_block as i64, i64),
                 (
                     "num_get_rooted_block_time",
@@ -316,6 +326,11 @@ pub struct BlockstoreRocksDbColumnFamilyMetrics {
     // RocksDB's internal property key: "rocksdb.num-running-flushes"
     pub num_running_flushes: i64,
 
+    // The estimated number of bytes compaction needs to rewrite to bring all
+    // levels down to under their target size, i.e. the compaction debt.
+    // RocksDB's internal property key: "rocksdb.estimate-pending-compaction-bytes"
+    pub estimate_pending_compaction_bytes: i64,
+
     // FIFO Compaction related
 
     // returns an estimation of the oldest key timestamp in the DB. Only vailable
@@ -393,6 +408,11 @@ impl BlockstoreRocksDbColumnFamilyMetrics {
                 i64
             ),
             ("num_running_flushes", self.num_running_flushes, i64),
+            (
+                "estimate_pending_compaction_bytes",
+                self.estimate_pending_compaction_bytes,
+                i64
+            ),
             // FIFO Compaction related
             (
                 "estimate_oldest_key_time",
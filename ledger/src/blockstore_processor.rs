@@ -3,7 +3,9 @@ use {
         block_error::BlockError,
         blockstore::{Blockstore, BlockstoreError},
         blockstore_meta::SlotMeta,
+        cost_calibration::CostCalibration,
         entry_notifier_service::{EntryNotification, EntryNotifierSender},
+        entry_verification_pool::EntryVerificationPool,
         leader_schedule_cache::LeaderScheduleCache,
         token_balances::collect_token_balances,
         use_snapshot_archives_at_startup::UseSnapshotArchivesAtStartup,
@@ -187,6 +189,11 @@ pub fn execute_batch<'a>(
         vec![]
     };
 
+    if block_verification {
+        // With io_uring, the accounts missing from the caches are read in a batch beforehand.
+        bank.load_transactions_accounts_into_read_cache(batch.sanitized_transactions());
+    }
+
     let pre_commit_callback = |_timings: &mut _, processing_results: &_| -> PreCommitResult {
         match extra_pre_commit_callback {
             None => {
@@ -870,6 +877,12 @@ pub struct ProcessOptions {
     pub hash_overrides: Option<HashOverrides>,
     pub abort_on_invalid_block: bool,
     pub no_block_cost_limits: bool,
+    /// Records the execution costs of the programs invoked by the processed slots
+    pub cost_calibration: Option<Arc<CostCalibration>>,
+    /// Activates the feature in the feature set of the banks processed from
+    /// the slot on, without the account changes of its actual activation, to
+    /// simulate the effects of the feature on the processed slots
+    pub simulated_feature_activation: Option<(Pubkey, Slot)>,
 }
 
 pub fn test_process_blockstore(
@@ -1196,6 +1209,7 @@ fn confirm_full_slot(
         entry_notification_sender,
         replay_vote_sender,
         recyclers,
+        None,
         opts.allow_dead_slots,
         opts.runtime_config.log_messages_bytes_limit,
         &ignored_prioritization_fee_cache,
@@ -1508,6 +1522,27 @@ pub struct ConfirmationProgress {
     pub num_shreds: u64,
     pub num_entries: usize,
     pub num_txs: usize,
+    /// The summaries of the entries replayed so far, only recorded if set
+    /// before the first entries of the slot are replayed
+    pub entry_summaries: Option<Vec<ReplayedEntrySummary>>,
+}
+
+/// Summary of a replayed entry and of its position in the block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayedEntrySummary {
+    /// The index of the entry in the block
+    pub index: usize,
+    pub hash: Hash,
+    pub num_hashes: u64,
+    pub num_transactions: u64,
+    /// The index in the block of the first transaction of the entry
+    pub starting_transaction_index: usize,
+}
+
+impl ReplayedEntrySummary {
+    pub fn is_tick(&self) -> bool {
+        self.num_transactions == 0
+    }
 }
 
 impl ConfirmationProgress {
@@ -1531,6 +1566,7 @@ pub fn confirm_slot(
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
     recyclers: &VerifyRecyclers,
+    entry_verification_pool: Option<&EntryVerificationPool>,
     allow_dead_slots: bool,
     log_messages_bytes_limit: Option<usize>,
     prioritization_fee_cache: &PrioritizationFeeCache,
@@ -1562,6 +1598,7 @@ pub fn confirm_slot(
         entry_notification_sender,
         replay_vote_sender,
         recyclers,
+        entry_verification_pool,
         log_messages_bytes_limit,
         prioritization_fee_cache,
     )
@@ -1579,6 +1616,7 @@ fn confirm_slot_entries(
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
     recyclers: &VerifyRecyclers,
+    entry_verification_pool: Option<&EntryVerificationPool>,
     log_messages_bytes_limit: Option<usize>,
     prioritization_fee_cache: &PrioritizationFeeCache,
 ) -> result::Result<(), BlockstoreProcessorError> {
@@ -1601,12 +1639,25 @@ fn confirm_slot_entries(
     let num_entries = entries.len();
     let mut entry_tx_starting_indexes = Vec::with_capacity(num_entries);
     let mut entry_tx_starting_index = progress.num_txs;
+    let mut entry_summaries = progress
+        .entry_summaries
+        .is_some()
+        .then(|| Vec::with_capacity(num_entries));
     let num_txs = entries
         .iter()
         .enumerate()
         .map(|(i, entry)| {
+            let entry_index = progress.num_entries.saturating_add(i);
+            if let Some(entry_summaries) = entry_summaries.as_mut() {
+                entry_summaries.push(ReplayedEntrySummary {
+                    index: entry_index,
+                    hash: entry.hash,
+                    num_hashes: entry.num_hashes,
+                    num_transactions: entry.transactions.len() as u64,
+                    starting_transaction_index: entry_tx_starting_index,
+                });
+            }
             if let Some(entry_notification_sender) = entry_notification_sender {
-                let entry_index = progress.num_entries.saturating_add(i);
                 if let Err(err) = entry_notification_sender.send(EntryNotification {
                     slot,
                     index: entry_index,
@@ -1657,11 +1708,15 @@ fn confirm_slot_entries(
     let last_entry_hash = entries.last().map(|e| e.hash);
     let verifier = if !skip_verification {
         datapoint_debug!("verify-batch-size", ("size", num_entries as i64, i64));
-        let entry_state = entries.start_verify(
-            &progress.last_entry,
-            replay_tx_thread_pool,
-            recyclers.clone(),
-        );
+        let start_verify = |thread_pool: &ThreadPool| {
+            entries.start_verify(&progress.last_entry, thread_pool, recyclers.clone())
+        };
+        let entry_state = match entry_verification_pool {
+            Some(entry_verification_pool) => {
+                entry_verification_pool.verify(num_entries, start_verify)
+            }
+            None => start_verify(replay_tx_thread_pool),
+        };
         if entry_state.status() == EntryVerificationStatus::Failure {
             warn!("Ledger proof of history failed at slot: {}", slot);
             return Err(BlockError::InvalidEntryHash.into());
@@ -1750,7 +1805,11 @@ fn confirm_slot_entries(
     }
 
     if let Some(mut verifier) = verifier {
-        let verified = verifier.finish_verify(replay_tx_thread_pool);
+        let verified = verifier.finish_verify(
+            entry_verification_pool
+                .map(EntryVerificationPool::thread_pool)
+                .unwrap_or(replay_tx_thread_pool),
+        );
         *poh_verify_elapsed += verifier.poh_duration_us();
         if !verified {
             warn!("Ledger proof of history failed at slot: {}", bank.slot());
@@ -1763,6 +1822,11 @@ fn confirm_slot_entries(
     progress.num_shreds += num_shreds;
     progress.num_entries += num_entries;
     progress.num_txs += num_txs;
+    if let (Some(progress_entry_summaries), Some(entry_summaries)) =
+        (progress.entry_summaries.as_mut(), entry_summaries)
+    {
+        progress_entry_summaries.extend(entry_summaries);
+    }
     if let Some(last_entry_hash) = last_entry_hash {
         progress.last_entry = last_entry_hash;
     }
@@ -1845,13 +1909,20 @@ fn process_next_slots(
         // Only process full slots in blockstore_processor, replay_stage
         // handles any partials
         if next_meta.is_full() {
-            let next_bank = Bank::new_from_parent(
+            let mut next_bank = Bank::new_from_parent(
                 bank.clone(),
                 &leader_schedule_cache
                     .slot_leader_at(*next_slot, Some(bank))
                     .unwrap(),
                 *next_slot,
             );
+            // The feature set is recomputed from the feature accounts at each
+            // epoch boundary, so the feature is activated on every new bank.
+            if let Some((feature_id, activation_slot)) = &opts.simulated_feature_activation {
+                if *next_slot >= *activation_slot && !next_bank.feature_set.is_active(feature_id) {
+                    next_bank.activate_feature(feature_id);
+                }
+            }
             trace!(
                 "New bank for slot {}, parent slot is {}",
                 next_slot,
@@ -1976,6 +2047,9 @@ fn load_frozen_forks(
                 continue;
             }
             txs += progress.num_txs;
+            if let Some(cost_calibration) = &opts.cost_calibration {
+                cost_calibration.record_program_timings(&timing.details.per_program_timings);
+            }
 
             // Block must be frozen by this point; otherwise,
             // process_single_slot() would have errored above.
@@ -3125,6 +3199,34 @@ pub mod tests {
         assert_eq!(bank.process_transaction(&tx), Ok(()));
     }
 
+    #[test]
+    fn test_process_blockstore_with_simulated_feature_activation() {
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(10_000);
+        let feature_id = agave_feature_set::disable_partitioned_rent_collection::id();
+        genesis_config.accounts.remove(&feature_id);
+        let ticks_per_slot = genesis_config.ticks_per_slot;
+        let (ledger_path, blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        for slot in 1..=3 {
+            fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, slot, slot - 1, blockhash);
+        }
+
+        let opts = ProcessOptions {
+            simulated_feature_activation: Some((feature_id, 2)),
+            ..ProcessOptions::default()
+        };
+        let (bank_forks, ..) =
+            test_process_blockstore(&genesis_config, &blockstore, &opts, Arc::default());
+        let bank_forks = bank_forks.read().unwrap();
+        assert_eq!(frozen_bank_slots(&bank_forks), vec![0, 1, 2, 3]);
+        for (slot, is_active) in [(0, false), (1, false), (2, true), (3, true)] {
+            let bank = bank_forks.get(slot).unwrap();
+            assert_eq!(bank.feature_set.is_active(&feature_id), is_active);
+        }
+    }
+
     #[test]
     fn test_process_ledger_simple() {
         solana_logger::setup();
@@ -4814,6 +4916,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
     }
@@ -4863,6 +4966,7 @@ pub mod tests {
         let replay_tx_thread_pool = create_thread_pool(1);
         let mut timing = ConfirmationTiming::default();
         let mut progress = ConfirmationProgress::new(genesis_hash);
+        progress.entry_summaries = Some(Vec::new());
         let amount = genesis_config.rent.minimum_balance(0);
         let keypair1 = Keypair::new();
         let keypair2 = Keypair::new();
@@ -4907,6 +5011,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
         .unwrap();
@@ -4939,6 +5044,7 @@ pub mod tests {
             bank.last_blockhash(),
         );
         let entry = next_entry(&new_hash, 1, vec![tx1, tx2, tx3]);
+        let last_hash = entry.hash;
 
         confirm_slot_entries(
             &bank,
@@ -4952,6 +5058,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
         .unwrap();
@@ -4964,6 +5071,25 @@ pub mod tests {
         } else {
             panic!("batch should have been sent");
         }
+        assert_eq!(
+            progress.entry_summaries.unwrap(),
+            vec![
+                ReplayedEntrySummary {
+                    index: 0,
+                    hash: new_hash,
+                    num_hashes: 1,
+                    num_transactions: 2,
+                    starting_transaction_index: 0,
+                },
+                ReplayedEntrySummary {
+                    index: 1,
+                    hash: last_hash,
+                    num_hashes: 1,
+                    num_transactions: 3,
+                    starting_transaction_index: 2,
+                },
+            ]
+        );
     }
 
     fn do_test_schedule_batches_for_execution(should_succeed: bool) {
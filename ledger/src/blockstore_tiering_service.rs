@@ -0,0 +1,244 @@
+//! Offloads the blocks of old rooted slots to the object storage of the
+//! `TieredBlockStorage` and purges the slots from the blockstore.
+
+use {
+    crate::{
+        blockstore::{Blockstore, BlockstoreError, PurgeType},
+        blockstore_tiering::TieredBlockStorage,
+    },
+    solana_measure::measure::Measure,
+    solana_runtime::commitment::BlockCommitmentCache,
+    solana_sdk::{clock::Slot, epoch_schedule::EpochSchedule},
+    std::{
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const OFFLOAD_INTERVAL: Duration = Duration::from_secs(1);
+// Slots are purged from the blockstore once this many are offloaded.
+const MAX_SLOTS_PER_BATCH: usize = 512;
+
+pub struct BlockstoreTieringService {
+    thread: JoinHandle<()>,
+}
+
+impl BlockstoreTieringService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        tiered_block_storage: Arc<TieredBlockStorage>,
+        blockstore: Arc<Blockstore>,
+        block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
+        max_complete_transaction_status_slot: Arc<AtomicU64>,
+        max_complete_rewards_slot: Arc<AtomicU64>,
+        epoch_schedule: EpochSchedule,
+        offload_after_epochs: u64,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        info!("Starting blockstore tiering service");
+        let thread = Builder::new()
+            .name("solBstoreTier".to_string())
+            .spawn(move || {
+                while !exit.load(Ordering::Relaxed) {
+                    // The highest slot eligible for offload is the highest root
+                    // with complete transaction-status metadata and rewards.
+                    let highest_complete_root = [
+                        max_complete_transaction_status_slot.load(Ordering::SeqCst),
+                        max_complete_rewards_slot.load(Ordering::SeqCst),
+                        block_commitment_cache.read().unwrap().root(),
+                    ]
+                    .into_iter()
+                    .min()
+                    .expect("root and max_complete slots exist");
+                    let end_slot = offload_end_slot(
+                        &epoch_schedule,
+                        highest_complete_root,
+                        offload_after_epochs,
+                    );
+                    match offload_slots(&tiered_block_storage, &blockstore, end_slot, &exit) {
+                        Ok(0) => sleep(OFFLOAD_INTERVAL),
+                        Ok(_) => (),
+                        Err(err) => {
+                            warn!("Failed to offload blockstore slots: {err}");
+                            sleep(OFFLOAD_INTERVAL);
+                        }
+                    }
+                }
+                info!("Blockstore tiering service has stopped");
+            })
+            .unwrap();
+
+        Self { thread }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread.join()
+    }
+}
+
+// Returns the slot from which rooted slots are kept in the blockstore: the
+// first slot of the epoch `offload_after_epochs` epochs before the epoch of
+// the root.
+fn offload_end_slot(epoch_schedule: &EpochSchedule, root: Slot, offload_after_epochs: u64) -> Slot {
+    epoch_schedule
+        .get_epoch(root)
+        .checked_sub(offload_after_epochs)
+        .map(|epoch| epoch_schedule.get_first_slot_in_epoch(epoch))
+        .unwrap_or_default()
+}
+
+// Uploads a batch of the rooted slots lower than `end_slot` and purges them
+// from the blockstore, except for the last one which provides the previous
+// blockhash of the next block. Returns the number of offloaded slots.
+fn offload_slots(
+    tiered_block_storage: &TieredBlockStorage,
+    blockstore: &Blockstore,
+    end_slot: Slot,
+    exit: &AtomicBool,
+) -> Result<usize, String> {
+    let start_slot = blockstore
+        .get_first_available_block()
+        .map_err(|err| err.to_string())?;
+    let slots: Vec<_> = blockstore
+        .rooted_slot_iterator(start_slot)
+        .map_err(|err| err.to_string())?
+        .take_while(|&slot| slot < end_slot)
+        .take(MAX_SLOTS_PER_BATCH)
+        .collect();
+    let num_slots = slots.len();
+    let Some(&last_slot) = slots.last() else {
+        return Ok(0);
+    };
+
+    let mut measure = Measure::start("offload_slots");
+    let mut num_blocks = 0;
+    for slot in slots {
+        if exit.load(Ordering::Relaxed) {
+            return Ok(0);
+        }
+        let block = match blockstore.get_rooted_block(slot, true) {
+            Ok(block) => block,
+            // Roots without shreds, such as the slots of snapshots the node
+            // started from, have no block to offload.
+            Err(BlockstoreError::SlotUnavailable) => continue,
+            Err(err) => return Err(format!("failed to read block {slot}: {err}")),
+        };
+        tiered_block_storage
+            .upload_block(slot, block)
+            .map_err(|err| format!("failed to upload block {slot}: {err}"))?;
+        num_blocks += 1;
+    }
+
+    // Purges the offloaded slots the same way the ledger cleanup does, so
+    // that reads of these slots fail with `SlotCleanedUp`.
+    if let Some(purge_slot) = last_slot.checked_sub(1) {
+        *blockstore.lowest_cleanup_slot.write().unwrap() = purge_slot;
+        blockstore.purge_slots(0, purge_slot, PurgeType::CompactionFilter);
+        blockstore.set_max_expired_slot(purge_slot);
+    }
+    measure.stop();
+    info!(
+        "Offloaded {num_blocks} blocks of slots {start_slot}..={last_slot} in {}ms",
+        measure.as_ms()
+    );
+    datapoint_info!(
+        "blockstore_tiering",
+        ("num_blocks", num_blocks, i64),
+        ("last_slot", last_slot, i64),
+        ("duration_ms", measure.as_ms(), i64),
+    );
+    Ok(num_slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            blockstore::entries_to_test_shreds,
+            blockstore_tiering::FileSystemObjectStore,
+            genesis_utils::{create_genesis_config, GenesisConfigInfo},
+        },
+        assert_matches::assert_matches,
+        solana_entry::entry::create_ticks,
+        solana_sdk::{epoch_schedule::MINIMUM_SLOTS_PER_EPOCH, hash::Hash},
+        tempfile::TempDir,
+    };
+
+    #[test]
+    fn test_offload_end_slot() {
+        let epoch_schedule = EpochSchedule::custom(
+            MINIMUM_SLOTS_PER_EPOCH,
+            MINIMUM_SLOTS_PER_EPOCH,
+            false, // warmup
+        );
+        let slots_per_epoch = MINIMUM_SLOTS_PER_EPOCH;
+        assert_eq!(offload_end_slot(&epoch_schedule, 10, 2), 0);
+        assert_eq!(offload_end_slot(&epoch_schedule, 2 * slots_per_epoch, 2), 0);
+        assert_eq!(
+            offload_end_slot(&epoch_schedule, 3 * slots_per_epoch + 5, 2),
+            slots_per_epoch
+        );
+    }
+
+    #[test]
+    fn test_offload_slots() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(1_000_000);
+        let (ledger_path, _blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        for slot in 1..=4 {
+            let shreds = entries_to_test_shreds(
+                &create_ticks(genesis_config.ticks_per_slot, 0, Hash::new_unique()),
+                slot,
+                slot - 1, // parent_slot
+                true,     // is_full_slot
+                0,        // version
+                true,     // merkle_variant
+            );
+            blockstore.insert_shreds(shreds, None, false).unwrap();
+            blockstore.set_roots([slot].iter()).unwrap();
+        }
+        let store_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let tiered_block_storage = TieredBlockStorage::new(
+            Arc::new(FileSystemObjectStore::new(store_dir.path())),
+            cache_dir.path(),
+            u64::MAX,
+        )
+        .unwrap();
+        let exit = AtomicBool::new(false);
+
+        // Only the slots lower than the end slot are offloaded.
+        assert_eq!(
+            offload_slots(&tiered_block_storage, &blockstore, 3, &exit).unwrap(),
+            3
+        );
+        for slot in 0..3 {
+            assert!(tiered_block_storage
+                .get_confirmed_block(slot)
+                .unwrap()
+                .is_some());
+        }
+        assert!(tiered_block_storage
+            .get_confirmed_block(3)
+            .unwrap()
+            .is_none());
+        assert_matches!(
+            blockstore.get_rooted_block(1, true),
+            Err(BlockstoreError::SlotCleanedUp)
+        );
+        // The last offloaded slot is kept for the previous blockhash of the
+        // next block.
+        assert!(blockstore.is_root(2));
+        assert_eq!(blockstore.get_first_available_block().unwrap(), 3);
+        assert!(blockstore.get_rooted_block(3, true).is_ok());
+        assert_eq!(
+            offload_slots(&tiered_block_storage, &blockstore, 3, &exit).unwrap(),
+            0
+        );
+    }
+}
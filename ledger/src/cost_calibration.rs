@@ -0,0 +1,271 @@
+//! Calibration of the cost model from the execution times measured while
+//! replaying and producing blocks.
+//!
+//! The cost model charges the compute units an instruction consumes, and sizes
+//! the block limits assuming that a compute unit executes in a fixed amount of
+//! time, `1 / COMPUTE_UNIT_TO_US_RATIO` microseconds. The calibration
+//! accumulates, per program, the compute units its instructions consumed and
+//! the time they took to execute, and compares the measured ratio to the fixed
+//! one. A calibration factor above 1 means the program takes longer to execute
+//! than the compute units it is charged account for.
+//!
+//! The validator periodically reports the programs that took the longest to
+//! execute in the `cost_calibration` metric, and `agave-ledger-tool
+//! compute-cost-report` prints the report of the slots it replays.
+
+use {
+    solana_cost_model::block_cost_limits::COMPUTE_UNIT_TO_US_RATIO,
+    solana_sdk::{pubkey::Pubkey, timing::AtomicInterval},
+    solana_timings::ProgramTiming,
+    std::{collections::HashMap, fmt, sync::Mutex},
+};
+
+/// The number of programs reported in the `cost_calibration` metric
+pub const NUM_REPORTED_PROGRAMS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProgramExecutionCost {
+    pub num_invocations: u64,
+    pub execute_us: u64,
+    pub compute_units: u64,
+}
+
+impl ProgramExecutionCost {
+    fn accumulate(&mut self, other: &Self) {
+        self.num_invocations = self.num_invocations.saturating_add(other.num_invocations);
+        self.execute_us = self.execute_us.saturating_add(other.execute_us);
+        self.compute_units = self.compute_units.saturating_add(other.compute_units);
+    }
+
+    /// The compute units the cost model assumes execute in the measured time
+    pub fn calibrated_compute_units(&self) -> u64 {
+        self.execute_us.saturating_mul(COMPUTE_UNIT_TO_US_RATIO)
+    }
+
+    /// The measured compute units executed per microsecond, `None` if no time
+    /// was measured
+    pub fn compute_units_per_us(&self) -> Option<f64> {
+        (self.execute_us > 0).then(|| self.compute_units as f64 / self.execute_us as f64)
+    }
+
+    /// The ratio of the calibrated compute units to the consumed ones, `None`
+    /// if no compute units were consumed
+    pub fn calibration_factor(&self) -> Option<f64> {
+        (self.compute_units > 0)
+            .then(|| self.calibrated_compute_units() as f64 / self.compute_units as f64)
+    }
+}
+
+impl From<&ProgramTiming> for ProgramExecutionCost {
+    fn from(timing: &ProgramTiming) -> Self {
+        Self {
+            num_invocations: timing
+                .count
+                .0
+                .saturating_add(timing.errored_txs_compute_consumed.len() as u64),
+            execute_us: timing.accumulated_us.0,
+            compute_units: timing
+                .accumulated_units
+                .0
+                .saturating_add(timing.total_errored_units.0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostCalibrationReport {
+    /// Sorted by decreasing execution time
+    pub programs: Vec<(Pubkey, ProgramExecutionCost)>,
+    pub total: ProgramExecutionCost,
+}
+
+impl CostCalibrationReport {
+    fn new(programs: HashMap<Pubkey, ProgramExecutionCost>) -> Self {
+        let mut programs: Vec<_> = programs.into_iter().collect();
+        programs
+            .sort_by(|(a_id, a), (b_id, b)| b.execute_us.cmp(&a.execute_us).then(a_id.cmp(b_id)));
+        let mut total = ProgramExecutionCost::default();
+        for (_, cost) in &programs {
+            total.accumulate(cost);
+        }
+        Self { programs, total }
+    }
+
+    fn report_metrics(&self) {
+        let report = |pubkey: String, cost: &ProgramExecutionCost| {
+            datapoint_info!(
+                "cost_calibration",
+                ("pubkey", pubkey, String),
+                ("num_invocations", cost.num_invocations, i64),
+                ("execute_us", cost.execute_us, i64),
+                ("compute_units", cost.compute_units, i64),
+                (
+                    "calibrated_compute_units",
+                    cost.calibrated_compute_units(),
+                    i64
+                ),
+                (
+                    "calibration_factor",
+                    cost.calibration_factor(),
+                    Option<f64>
+                ),
+            );
+        };
+        for (program_id, cost) in self.programs.iter().take(NUM_REPORTED_PROGRAMS) {
+            report(program_id.to_string(), cost);
+        }
+        report("all".to_string(), &self.total);
+    }
+}
+
+impl fmt::Display for CostCalibrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<44} {:>12} {:>14} {:>16} {:>10} {:>18} {:>12}",
+            "Program",
+            "Invocations",
+            "Execute us",
+            "Compute units",
+            "CU / us",
+            "Calibrated units",
+            "Calibration"
+        )?;
+        let mut write_row = |program: &str, cost: &ProgramExecutionCost| {
+            let format_ratio =
+                |ratio: Option<f64>| ratio.map_or("-".to_string(), |ratio| format!("{ratio:.2}"));
+            writeln!(
+                f,
+                "{:<44} {:>12} {:>14} {:>16} {:>10} {:>18} {:>12}",
+                program,
+                cost.num_invocations,
+                cost.execute_us,
+                cost.compute_units,
+                format_ratio(cost.compute_units_per_us()),
+                cost.calibrated_compute_units(),
+                format_ratio(cost.calibration_factor()),
+            )
+        };
+        for (program_id, cost) in &self.programs {
+            write_row(&program_id.to_string(), cost)?;
+        }
+        write_row("Total", &self.total)?;
+        writeln!(
+            f,
+            "The cost model assumes {COMPUTE_UNIT_TO_US_RATIO} compute units per us. A \
+             calibration above 1 means a program executes slower than it is charged for."
+        )
+    }
+}
+
+/// The execution costs measured since the latest report
+#[derive(Default)]
+pub struct CostCalibration {
+    programs: Mutex<HashMap<Pubkey, ProgramExecutionCost>>,
+    last_report: AtomicInterval,
+    /// Zero if the calibration is not reported as metrics
+    report_interval_ms: u64,
+}
+
+impl CostCalibration {
+    /// Creates a calibration reported in the `cost_calibration` metric every
+    /// `report_interval_ms`, and reset after each report. A
+    /// `report_interval_ms` of zero accumulates the costs until the report is
+    /// taken instead.
+    pub fn new(report_interval_ms: u64) -> Self {
+        Self {
+            report_interval_ms,
+            ..Self::default()
+        }
+    }
+
+    /// Records the execution costs of the programs invoked by a slot or a
+    /// batch of transactions, reporting the calibration if it is due.
+    pub fn record_program_timings(&self, per_program_timings: &HashMap<Pubkey, ProgramTiming>) {
+        if per_program_timings.is_empty() {
+            return;
+        }
+        {
+            let mut programs = self.programs.lock().unwrap();
+            for (program_id, timing) in per_program_timings {
+                programs
+                    .entry(*program_id)
+                    .or_default()
+                    .accumulate(&ProgramExecutionCost::from(timing));
+            }
+        }
+        if self.report_interval_ms > 0 && self.last_report.should_update(self.report_interval_ms) {
+            self.take_report().report_metrics();
+        }
+    }
+
+    /// The report of the costs recorded since the latest report
+    pub fn take_report(&self) -> CostCalibrationReport {
+        CostCalibrationReport::new(std::mem::take(&mut *self.programs.lock().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::num::Saturating};
+
+    fn program_timing(accumulated_us: u64, accumulated_units: u64, count: u64) -> ProgramTiming {
+        ProgramTiming {
+            accumulated_us: Saturating(accumulated_us),
+            accumulated_units: Saturating(accumulated_units),
+            count: Saturating(count),
+            ..ProgramTiming::default()
+        }
+    }
+
+    #[test]
+    fn test_cost_calibration() {
+        let calibration = CostCalibration::new(0);
+        assert_eq!(calibration.take_report(), CostCalibrationReport::default());
+
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let mut errored_timing = program_timing(100, 0, 0);
+        errored_timing.errored_txs_compute_consumed = vec![1_000];
+        errored_timing.total_errored_units = Saturating(1_000);
+        calibration.record_program_timings(&HashMap::from([
+            (program_a, program_timing(100, 6_000, 2)),
+            (program_b, errored_timing),
+        ]));
+        calibration
+            .record_program_timings(&HashMap::from([(program_a, program_timing(200, 3_000, 1))]));
+
+        let report = calibration.take_report();
+        let cost_a = ProgramExecutionCost {
+            num_invocations: 3,
+            execute_us: 300,
+            compute_units: 9_000,
+        };
+        let cost_b = ProgramExecutionCost {
+            num_invocations: 1,
+            execute_us: 100,
+            compute_units: 1_000,
+        };
+        assert_eq!(
+            report.programs,
+            vec![(program_a, cost_a), (program_b, cost_b)]
+        );
+        assert_eq!(
+            report.total,
+            ProgramExecutionCost {
+                num_invocations: 4,
+                execute_us: 400,
+                compute_units: 10_000,
+            }
+        );
+        assert_eq!(cost_a.compute_units_per_us(), Some(30.0));
+        assert_eq!(cost_a.calibration_factor(), Some(1.0));
+        assert_eq!(cost_b.calibrated_compute_units(), 3_000);
+        assert_eq!(cost_b.calibration_factor(), Some(3.0));
+        assert_eq!(ProgramExecutionCost::default().calibration_factor(), None);
+        assert_eq!(ProgramExecutionCost::default().compute_units_per_us(), None);
+
+        // Taking the report resets the calibration.
+        assert!(calibration.take_report().programs.is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! A thread pool dedicated to the PoH verification of the entries replayed, so
+//! that it does not contend with the execution of transactions on the replay
+//! thread pools while replaying full blocks.
+//!
+//! Each thread of the pool is pinned to one core of the configured core set,
+//! which the validator excludes from the cores of its other threads. The number
+//! of verifications waiting for or running on the pool, which the replays of
+//! concurrent forks queue, is reported along with the verification times in
+//! the `entry_verification_pool` metric.
+
+use {
+    core_affinity::CoreId,
+    rayon::{ThreadPool, ThreadPoolBuilder},
+    solana_measure::measure_us,
+    solana_sdk::timing::AtomicInterval,
+    std::sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+const STATS_REPORT_INTERVAL_MS: u64 = 1000;
+
+#[derive(Default)]
+struct EntryVerificationPoolStats {
+    last_report: AtomicInterval,
+    /// The verifications waiting for or running on the pool
+    queue_depth: AtomicUsize,
+    max_queue_depth: AtomicUsize,
+    num_verifications: AtomicU64,
+    num_entries: AtomicU64,
+    verify_us: AtomicU64,
+}
+
+impl EntryVerificationPoolStats {
+    fn maybe_report(&self, num_threads: usize) {
+        if !self.last_report.should_update(STATS_REPORT_INTERVAL_MS) {
+            return;
+        }
+        datapoint_info!(
+            "entry_verification_pool",
+            ("num_threads", num_threads, i64),
+            ("queue_depth", self.queue_depth.load(Ordering::Relaxed), i64),
+            (
+                "max_queue_depth",
+                self.max_queue_depth.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_verifications",
+                self.num_verifications.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_entries",
+                self.num_entries.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            ("verify_us", self.verify_us.swap(0, Ordering::Relaxed), i64),
+        );
+    }
+}
+
+pub struct EntryVerificationPool {
+    thread_pool: ThreadPool,
+    stats: EntryVerificationPoolStats,
+}
+
+impl EntryVerificationPool {
+    /// Creates a pool of one thread per core of `cpus`, each pinned to its
+    /// core.
+    pub fn new(cpus: Vec<usize>) -> Self {
+        assert!(!cpus.is_empty(), "no cores to verify entries on");
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(cpus.len())
+            .thread_name(|i| format!("solEntryVerif{i:02}"))
+            .start_handler(move |i| {
+                if !core_affinity::set_for_current(CoreId { id: cpus[i] }) {
+                    warn!(
+                        "Failed to pin the entry verification thread {i} to core {}",
+                        cpus[i]
+                    );
+                }
+            })
+            .build()
+            .expect("new rayon threadpool");
+        Self {
+            thread_pool,
+            stats: EntryVerificationPoolStats::default(),
+        }
+    }
+
+    pub fn thread_pool(&self) -> &ThreadPool {
+        &self.thread_pool
+    }
+
+    /// Runs `verify`, which verifies `num_entries` entries on the thread pool
+    /// it is given, accounting for it in the metrics of the pool.
+    pub fn verify<R>(&self, num_entries: usize, verify: impl FnOnce(&ThreadPool) -> R) -> R {
+        let stats = &self.stats;
+        let queue_depth = stats.queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
+        stats
+            .max_queue_depth
+            .fetch_max(queue_depth, Ordering::Relaxed);
+        let (result, verify_us) = measure_us!(verify(&self.thread_pool));
+        stats.queue_depth.fetch_sub(1, Ordering::Relaxed);
+        stats.num_verifications.fetch_add(1, Ordering::Relaxed);
+        stats
+            .num_entries
+            .fetch_add(num_entries as u64, Ordering::Relaxed);
+        stats.verify_us.fetch_add(verify_us, Ordering::Relaxed);
+        stats.maybe_report(self.thread_pool.current_num_threads());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_entry::entry::EntrySlice, solana_sdk::hash::Hash};
+
+    #[test]
+    fn test_verify() {
+        let pool = EntryVerificationPool::new(vec![0]);
+        assert_eq!(pool.thread_pool().current_num_threads(), 1);
+        let start_hash = Hash::new_unique();
+        let entries = solana_entry::entry::create_ticks(4, 1, start_hash);
+        let verify = |start_hash| {
+            pool.verify(entries.len(), |thread_pool| {
+                entries.verify(&start_hash, thread_pool)
+            })
+        };
+        assert!(verify(start_hash));
+        assert!(!verify(Hash::default()));
+        assert_eq!(pool.stats.queue_depth.load(Ordering::Relaxed), 0);
+    }
+}
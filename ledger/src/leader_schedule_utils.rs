@@ -7,6 +7,7 @@ use {
         clock::{Epoch, Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
         pubkey::Pubkey,
     },
+    solana_vote::vote_account::VoteAccountsHashMap,
     std::collections::HashMap,
 };
 
@@ -34,6 +35,50 @@ pub fn leader_schedule(epoch: Epoch, bank: &Bank) -> Option<LeaderSchedule> {
     }
 }
 
+/// Return the leader schedule `epoch` would have if its stakes were those of
+/// `vote_accounts`, rather than the epoch stakes of `bank`, to forecast the
+/// schedules of the epochs whose stakes are not known yet. Returns `None` if
+/// none of the vote accounts is staked.
+pub fn leader_schedule_from_vote_accounts(
+    epoch: Epoch,
+    bank: &Bank,
+    vote_accounts: &VoteAccountsHashMap,
+) -> Option<LeaderSchedule> {
+    let vote_accounts: VoteAccountsHashMap = vote_accounts
+        .iter()
+        .filter(|(_, (stake, _))| *stake > 0)
+        .map(|(vote_pubkey, stake_and_account)| (*vote_pubkey, stake_and_account.clone()))
+        .collect();
+    if vote_accounts.is_empty() {
+        return None;
+    }
+    // Past the leader schedule epoch of `bank`, the vote keyed schedule is
+    // only known to be used once its feature is activated.
+    let use_new_leader_schedule = bank
+        .should_use_vote_keyed_leader_schedule(epoch)
+        .unwrap_or(false);
+    let leader_schedule = if use_new_leader_schedule {
+        Box::new(VoteKeyedLeaderSchedule::new(
+            &vote_accounts,
+            epoch,
+            bank.get_slots_in_epoch(epoch),
+            NUM_CONSECUTIVE_LEADER_SLOTS,
+        )) as LeaderSchedule
+    } else {
+        let mut staked_nodes = HashMap::<Pubkey, u64>::new();
+        for (stake, vote_account) in vote_accounts.values() {
+            *staked_nodes.entry(*vote_account.node_pubkey()).or_default() += stake;
+        }
+        Box::new(IdentityKeyedLeaderSchedule::new(
+            &staked_nodes,
+            epoch,
+            bank.get_slots_in_epoch(epoch),
+            NUM_CONSECUTIVE_LEADER_SLOTS,
+        )) as LeaderSchedule
+    };
+    Some(leader_schedule)
+}
+
 /// Map of leader base58 identity pubkeys to the slot indices relative to the first epoch slot
 pub type LeaderScheduleByIdentity = HashMap<String, Vec<usize>>;
 
@@ -111,6 +156,36 @@ mod tests {
         assert_eq!(leader_schedule[2], pubkey);
     }
 
+    #[test_case(true; "vote keyed leader schedule")]
+    #[test_case(false; "identity keyed leader schedule")]
+    fn test_leader_schedule_from_vote_accounts(use_vote_keyed_leader_schedule: bool) {
+        let pubkey = solana_pubkey::new_rand();
+        let mut genesis_config =
+            create_genesis_config_with_leader(0, &pubkey, bootstrap_validator_stake_lamports())
+                .genesis_config;
+        if !use_vote_keyed_leader_schedule {
+            deactivate_features(
+                &mut genesis_config,
+                &vec![agave_feature_set::enable_vote_address_leader_schedule::id()],
+            );
+        }
+        let bank = Bank::new_for_tests(&genesis_config);
+
+        // The stakes of the bank yield the schedule of the bank.
+        let vote_accounts = bank.vote_accounts();
+        let forecast = leader_schedule_from_vote_accounts(0, &bank, &vote_accounts).unwrap();
+        assert_eq!(
+            forecast.get_slot_leaders(),
+            leader_schedule(0, &bank).unwrap().get_slot_leaders()
+        );
+
+        let mut vote_accounts = (*vote_accounts).clone();
+        for (stake, _) in vote_accounts.values_mut() {
+            *stake = 0;
+        }
+        assert!(leader_schedule_from_vote_accounts(0, &bank, &vote_accounts).is_none());
+    }
+
     #[test]
     fn test_leader_scheduler1_basic() {
         let pubkey = solana_pubkey::new_rand();
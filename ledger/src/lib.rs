@@ -17,13 +17,18 @@ pub mod blockstore_metric_report_service;
 pub mod blockstore_metrics;
 pub mod blockstore_options;
 pub mod blockstore_processor;
+pub mod blockstore_tiering;
+pub mod blockstore_tiering_service;
+pub mod cost_calibration;
 pub mod entry_notifier_interface;
 pub mod entry_notifier_service;
+pub mod entry_verification_pool;
 pub mod genesis_utils;
 pub mod leader_schedule;
 pub mod leader_schedule_cache;
 pub mod leader_schedule_utils;
 pub mod next_slots_iterator;
+pub mod object_store_ledger_storage;
 pub mod rooted_slot_iterator;
 pub mod shred;
 mod shredder;
@@ -0,0 +1,520 @@
+//! Long-term storage of the ledger in an object storage, for RPC nodes which
+//! serve historical blocks and transactions without BigTable.
+//!
+//! The objects mirror the rows of the BigTable tables:
+//!   * `blocks/<slot>`: the zstd compressed protobuf `ConfirmedBlock`.
+//!   * `tx/<signature>`: the slot and index of the transaction in its block,
+//!     with its error and memo.
+//!   * `tx-by-addr/<address>/<!slot>`: the protobuf `TransactionByAddr` of the
+//!     transactions of the slot which load the address.
+//!
+//! Slots are written as 16 hexadecimal digits, so that blocks are listed in
+//! slot order and the transactions of an address in reverse slot order.
+//! Entries are not stored.
+
+use {
+    crate::blockstore_tiering::{BlockstoreTieringError, ObjectStore, ZSTD_COMPRESSION_LEVEL},
+    agave_reserved_account_keys::ReservedAccountKeys,
+    async_trait::async_trait,
+    log::*,
+    prost::Message,
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        clock::Slot, pubkey::Pubkey, signature::Signature, transaction::TransactionError,
+    },
+    solana_storage_bigtable::{Error, LongTermStorage, Result},
+    solana_storage_proto::convert::{generated, tx_by_addr},
+    solana_transaction_status::{
+        extract_and_fmt_memos, ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
+        ConfirmedTransactionWithStatusMeta, TransactionByAddrInfo, TransactionConfirmationStatus,
+        TransactionStatus, VersionedConfirmedBlockWithEntries,
+    },
+    std::{collections::HashMap, fmt::Display, sync::Arc},
+};
+
+const BLOCKS_PREFIX: &str = "blocks/";
+
+#[derive(Serialize, Deserialize)]
+struct TransactionInfo {
+    slot: Slot,
+    index: u32,
+    err: Option<TransactionError>,
+    memo: Option<String>,
+}
+
+fn slot_to_key(slot: Slot) -> String {
+    format!("{slot:016x}")
+}
+
+fn block_key(slot: Slot) -> String {
+    format!("{BLOCKS_PREFIX}{}", slot_to_key(slot))
+}
+
+fn transaction_key(signature: &Signature) -> String {
+    format!("tx/{signature}")
+}
+
+fn address_prefix(address: &Pubkey) -> String {
+    format!("tx-by-addr/{address}/")
+}
+
+fn tx_by_addr_key(address: &Pubkey, slot: Slot) -> String {
+    format!("{}{}", address_prefix(address), slot_to_key(!slot))
+}
+
+fn key_to_slot(key: &str, prefix: &str) -> Result<Slot> {
+    key.strip_prefix(prefix)
+        .and_then(|key| Slot::from_str_radix(key, 16).ok())
+        .ok_or_else(|| corrupt_object(key, "invalid key"))
+}
+
+fn storage_error(err: BlockstoreTieringError) -> Error {
+    Error::LongTermStorageError(err.to_string())
+}
+
+fn corrupt_object(key: &str, err: impl Display) -> Error {
+    Error::LongTermStorageError(format!("corrupt object {key}: {err}"))
+}
+
+#[derive(Clone)]
+pub struct ObjectStoreLedgerStorage {
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl ObjectStoreLedgerStorage {
+    pub fn new(object_store: Arc<dyn ObjectStore>) -> Self {
+        Self { object_store }
+    }
+
+    // Runs the blocking requests of `f` off the async runtime
+    async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let storage = self.clone();
+        tokio::task::spawn_blocking(move || f(&storage))
+            .await
+            .map_err(Error::TokioJoinError)?
+    }
+
+    fn put(&self, key: &str, data: Vec<u8>) -> Result<()> {
+        self.object_store.put(key, data).map_err(storage_error)
+    }
+
+    fn get_block(&self, slot: Slot) -> Result<ConfirmedBlock> {
+        let key = block_key(slot);
+        let data = self
+            .object_store
+            .get(&key)
+            .map_err(storage_error)?
+            .ok_or(Error::BlockNotFound(slot))?;
+        let data = zstd::decode_all(data.as_slice())?;
+        generated::ConfirmedBlock::decode(data.as_slice())
+            .map_err(|err| corrupt_object(&key, err))?
+            .try_into()
+            .map_err(|err| corrupt_object(&key, err))
+    }
+
+    fn get_transaction_info(&self, signature: &Signature) -> Result<TransactionInfo> {
+        let key = transaction_key(signature);
+        let data = self
+            .object_store
+            .get(&key)
+            .map_err(storage_error)?
+            .ok_or(Error::SignatureNotFound)?;
+        bincode::deserialize(&data).map_err(|err| corrupt_object(&key, err))
+    }
+
+    fn get_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        let TransactionInfo { slot, index, .. } = self.get_transaction_info(signature)?;
+        let block = self.get_block(slot)?;
+        match block.transactions.into_iter().nth(index as usize) {
+            Some(tx_with_meta) if tx_with_meta.transaction_signature() == signature => {
+                Ok(Some(ConfirmedTransactionWithStatusMeta {
+                    slot,
+                    tx_with_meta,
+                    block_time: block.block_time,
+                }))
+            }
+            _ => {
+                warn!("Transaction info or confirmed block for {signature} is corrupt");
+                Ok(None)
+            }
+        }
+    }
+
+    fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>> {
+        let (first_slot, before_transaction_index) = match before_signature {
+            None => (Slot::MAX, 0),
+            Some(signature) => {
+                let TransactionInfo { slot, index, .. } = self.get_transaction_info(signature)?;
+                (slot, index)
+            }
+        };
+        let (last_slot, until_transaction_index) = match until_signature {
+            None => (0, u32::MAX),
+            Some(signature) => {
+                let TransactionInfo { slot, index, .. } = self.get_transaction_info(signature)?;
+                (slot, index)
+            }
+        };
+
+        let prefix = address_prefix(address);
+        // The keys sort in reverse slot order, so the listing starts right
+        // before the key of `first_slot`
+        let mut start_after = first_slot
+            .checked_add(1)
+            .map(|slot| tx_by_addr_key(address, slot));
+        let mut infos = vec![];
+        'outer: loop {
+            let keys = self
+                .object_store
+                .list(&prefix, start_after.as_deref(), limit.saturating_add(1))
+                .map_err(storage_error)?;
+            let Some(last_key) = keys.last().cloned() else {
+                break;
+            };
+            for key in keys {
+                let slot = !key_to_slot(&key, &prefix)?;
+                if slot < last_slot {
+                    break 'outer;
+                }
+                let Some(data) = self.object_store.get(&key).map_err(storage_error)? else {
+                    continue;
+                };
+                let tx_by_addr = tx_by_addr::TransactionByAddr::decode(data.as_slice())
+                    .map_err(|err| corrupt_object(&key, err))?;
+                let mut tx_by_addr_infos: Vec<TransactionByAddrInfo> = tx_by_addr
+                    .try_into()
+                    .map_err(|err| corrupt_object(&key, err))?;
+                tx_by_addr_infos.reverse();
+                for tx_by_addr_info in tx_by_addr_infos {
+                    // Filter out records before `before_transaction_index`
+                    if slot == first_slot && tx_by_addr_info.index >= before_transaction_index {
+                        continue;
+                    }
+                    // Filter out records after `until_transaction_index`
+                    if slot == last_slot && tx_by_addr_info.index <= until_transaction_index {
+                        continue;
+                    }
+                    infos.push((
+                        ConfirmedTransactionStatusWithSignature {
+                            signature: tx_by_addr_info.signature,
+                            slot,
+                            err: tx_by_addr_info.err,
+                            memo: tx_by_addr_info.memo,
+                            block_time: tx_by_addr_info.block_time,
+                        },
+                        tx_by_addr_info.index,
+                    ));
+                    if infos.len() >= limit {
+                        break 'outer;
+                    }
+                }
+            }
+            start_after = Some(last_key);
+        }
+        Ok(infos)
+    }
+
+    fn upload_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlockWithEntries,
+    ) -> Result<()> {
+        let confirmed_block = confirmed_block.block;
+        let reserved_account_keys = ReservedAccountKeys::new_all_activated();
+        let mut by_addr: HashMap<Pubkey, Vec<TransactionByAddrInfo>> = HashMap::new();
+        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
+            let err = transaction_with_meta.meta.status.clone().err();
+            let index = index as u32;
+            let signature = transaction_with_meta.transaction.signatures[0];
+            let memo = extract_and_fmt_memos(transaction_with_meta);
+
+            for address in transaction_with_meta.account_keys().iter() {
+                if !reserved_account_keys.is_reserved(address) {
+                    by_addr
+                        .entry(*address)
+                        .or_default()
+                        .push(TransactionByAddrInfo {
+                            signature,
+                            err: err.clone(),
+                            index,
+                            memo: memo.clone(),
+                            block_time: confirmed_block.block_time,
+                        });
+                }
+            }
+
+            let transaction_info = TransactionInfo {
+                slot,
+                index,
+                err,
+                memo,
+            };
+            let data = bincode::serialize(&transaction_info)
+                .map_err(|err| Error::LongTermStorageError(err.to_string()))?;
+            self.put(&transaction_key(&signature), data)?;
+        }
+
+        for (address, tx_by_addr_infos) in by_addr {
+            let tx_by_addr = tx_by_addr::TransactionByAddr {
+                tx_by_addrs: tx_by_addr_infos.into_iter().map(Into::into).collect(),
+            };
+            self.put(&tx_by_addr_key(&address, slot), tx_by_addr.encode_to_vec())?;
+        }
+
+        // Store the block itself last, so that a partially uploaded block is
+        // not visible to `get_confirmed_block()` and `get_confirmed_blocks()`
+        let data = generated::ConfirmedBlock::from(confirmed_block).encode_to_vec();
+        let data = zstd::encode_all(data.as_slice(), ZSTD_COMPRESSION_LEVEL)?;
+        self.put(&block_key(slot), data)
+    }
+}
+
+#[async_trait]
+impl LongTermStorage for ObjectStoreLedgerStorage {
+    async fn get_first_available_block(&self) -> Result<Option<Slot>> {
+        self.run(|storage| {
+            let keys = storage
+                .object_store
+                .list(BLOCKS_PREFIX, None, 1)
+                .map_err(storage_error)?;
+            keys.first()
+                .map(|key| key_to_slot(key, BLOCKS_PREFIX))
+                .transpose()
+        })
+        .await
+    }
+
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>> {
+        self.run(move |storage| {
+            let start_after = start_slot.checked_sub(1).map(block_key);
+            storage
+                .object_store
+                .list(BLOCKS_PREFIX, start_after.as_deref(), limit)
+                .map_err(storage_error)?
+                .iter()
+                .map(|key| key_to_slot(key, BLOCKS_PREFIX))
+                .collect()
+        })
+        .await
+    }
+
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock> {
+        self.run(move |storage| storage.get_block(slot)).await
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus> {
+        let signature = *signature;
+        let TransactionInfo { slot, err, .. } = self
+            .run(move |storage| storage.get_transaction_info(&signature))
+            .await?;
+        Ok(TransactionStatus {
+            slot,
+            confirmations: None,
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+            provenance: None,
+        })
+    }
+
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        let signature = *signature;
+        self.run(move |storage| storage.get_transaction(&signature))
+            .await
+    }
+
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>> {
+        let address = *address;
+        let before_signature = before_signature.copied();
+        let until_signature = until_signature.copied();
+        self.run(move |storage| {
+            storage.get_signatures_for_address(
+                &address,
+                before_signature.as_ref(),
+                until_signature.as_ref(),
+                limit,
+            )
+        })
+        .await
+    }
+
+    async fn upload_confirmed_block_with_entries(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlockWithEntries,
+    ) -> Result<()> {
+        self.run(move |storage| storage.upload_block(slot, confirmed_block))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::blockstore_tiering::FileSystemObjectStore,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signer},
+            system_transaction,
+            transaction::VersionedTransaction,
+        },
+        solana_transaction_status::{
+            TransactionStatusMeta, VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
+        },
+        tempfile::TempDir,
+    };
+
+    fn new_block(
+        slot: Slot,
+        transactions: Vec<VersionedTransaction>,
+    ) -> VersionedConfirmedBlockWithEntries {
+        VersionedConfirmedBlockWithEntries {
+            block: VersionedConfirmedBlock {
+                previous_blockhash: Hash::new_unique().to_string(),
+                blockhash: Hash::new_unique().to_string(),
+                parent_slot: slot - 1,
+                transactions: transactions
+                    .into_iter()
+                    .map(|transaction| VersionedTransactionWithStatusMeta {
+                        transaction,
+                        meta: TransactionStatusMeta::default(),
+                    })
+                    .collect(),
+                rewards: vec![],
+                num_partitions: None,
+                block_time: Some(1_700_000_000 + slot as i64),
+                block_height: Some(slot),
+            },
+            entries: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_object_store_ledger_storage() {
+        let store_dir = TempDir::new().unwrap();
+        let storage =
+            ObjectStoreLedgerStorage::new(Arc::new(FileSystemObjectStore::new(store_dir.path())));
+        assert_eq!(storage.get_first_available_block().await.unwrap(), None);
+
+        let payer = Keypair::new();
+        let address = Pubkey::new_unique();
+        let transfer = |lamports| {
+            VersionedTransaction::from(system_transaction::transfer(
+                &payer,
+                &address,
+                lamports,
+                Hash::new_unique(),
+            ))
+        };
+        let transactions: Vec<_> = (1..=3).map(transfer).collect();
+        let signatures: Vec<_> = transactions
+            .iter()
+            .map(|transaction| transaction.signatures[0])
+            .collect();
+        storage
+            .upload_confirmed_block_with_entries(5, new_block(5, transactions[..2].to_vec()))
+            .await
+            .unwrap();
+        storage
+            .upload_confirmed_block_with_entries(7, new_block(7, transactions[2..].to_vec()))
+            .await
+            .unwrap();
+
+        assert_eq!(storage.get_first_available_block().await.unwrap(), Some(5));
+        assert_eq!(storage.get_confirmed_blocks(0, 10).await.unwrap(), [5, 7]);
+        assert_eq!(storage.get_confirmed_blocks(6, 10).await.unwrap(), [7]);
+        assert_eq!(storage.get_confirmed_blocks(5, 1).await.unwrap(), [5]);
+        assert_eq!(
+            storage.get_confirmed_block(7).await.unwrap().block_time,
+            Some(1_700_000_007)
+        );
+        assert!(matches!(
+            storage.get_confirmed_block(6).await,
+            Err(Error::BlockNotFound(6))
+        ));
+
+        let status = storage.get_signature_status(&signatures[1]).await.unwrap();
+        assert_eq!(status.slot, 5);
+        assert_eq!(status.status, Ok(()));
+        assert!(matches!(
+            storage.get_signature_status(&Signature::default()).await,
+            Err(Error::SignatureNotFound)
+        ));
+        let transaction = storage
+            .get_confirmed_transaction(&signatures[2])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.slot, 7);
+        assert_eq!(
+            transaction.tx_with_meta.transaction_signature(),
+            &signatures[2]
+        );
+
+        // In descending ledger order
+        let signatures_for_address =
+            |before: Option<Signature>, until: Option<Signature>, limit| {
+                let storage = storage.clone();
+                async move {
+                    storage
+                        .get_confirmed_signatures_for_address(
+                            &address,
+                            before.as_ref(),
+                            until.as_ref(),
+                            limit,
+                        )
+                        .await
+                        .unwrap()
+                        .into_iter()
+                        .map(|(status, _)| status.signature)
+                        .collect::<Vec<_>>()
+                }
+            };
+        assert_eq!(
+            signatures_for_address(None, None, 10).await,
+            [signatures[2], signatures[1], signatures[0]]
+        );
+        assert_eq!(
+            signatures_for_address(None, None, 2).await,
+            [signatures[2], signatures[1]]
+        );
+        assert_eq!(
+            signatures_for_address(Some(signatures[1]), None, 10).await,
+            [signatures[0]]
+        );
+        assert_eq!(
+            signatures_for_address(None, Some(signatures[0]), 10).await,
+            [signatures[2], signatures[1]]
+        );
+        assert_eq!(
+            storage
+                .get_confirmed_signatures_for_address(&payer.pubkey(), None, None, 10)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
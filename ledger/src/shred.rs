@@ -119,6 +119,10 @@ const SIZE_OF_SIGNATURE: usize = SIGNATURE_BYTES;
 // a &[Entry].
 pub const DATA_SHREDS_PER_FEC_BLOCK: usize = 32;
 pub const CODING_SHREDS_PER_FEC_BLOCK: usize = 32;
+// The fewest coding shreds a leader may generate for an erasure batch of
+// DATA_SHREDS_PER_FEC_BLOCK data shreds. Erasure batches of 33 to 64 shreds
+// all have the Merkle proof size of a 32:32 batch.
+pub const MIN_CODING_SHREDS_PER_FEC_BLOCK: usize = 1;
 pub const SHREDS_PER_FEC_BLOCK: usize = DATA_SHREDS_PER_FEC_BLOCK + CODING_SHREDS_PER_FEC_BLOCK;
 
 // Statically compute the typical data batch size assuming:
@@ -393,7 +397,7 @@ use dispatch;
 
 impl Shred {
     dispatch!(fn common_header(&self) -> &ShredCommonHeader);
-    dispatch!(fn set_signature(&mut self, signature: Signature));
+    dispatch!(pub fn set_signature(&mut self, signature: Signature));
     dispatch!(fn signed_data(&self) -> Result<SignedData, Error>);
 
     dispatch!(pub fn chained_merkle_root(&self) -> Result<Hash, Error>);
@@ -837,6 +841,7 @@ pub(crate) fn make_merkle_shreds_from_entries(
     chained_merkle_root: Option<Hash>,
     next_shred_index: u32,
     next_code_index: u32,
+    num_coding_shreds: usize,
     reed_solomon_cache: &ReedSolomonCache,
     stats: &mut ProcessShredsStats,
 ) -> Result<impl Iterator<Item = Shred>, Error> {
@@ -855,6 +860,7 @@ pub(crate) fn make_merkle_shreds_from_entries(
         is_last_in_slot,
         next_shred_index,
         next_code_index,
+        num_coding_shreds,
         reed_solomon_cache,
         stats,
     )?;
@@ -1073,6 +1079,7 @@ mod tests {
             is_last_in_slot,
             rng.gen_range(0..671), // next_shred_index
             rng.gen_range(0..781), // next_code_index
+            CODING_SHREDS_PER_FEC_BLOCK,
             &ReedSolomonCache::default(),
             &mut ProcessShredsStats::default(),
         )
@@ -13,8 +13,9 @@ use {
                 Shred as ShredTrait, ShredCode as ShredCodeTrait, ShredData as ShredDataTrait,
             },
             CodingShredHeader, DataShredHeader, Error, ProcessShredsStats, ShredCommonHeader,
-            ShredFlags, ShredVariant, DATA_SHREDS_PER_FEC_BLOCK, SHREDS_PER_FEC_BLOCK,
-            SIZE_OF_CODING_SHRED_HEADERS, SIZE_OF_DATA_SHRED_HEADERS, SIZE_OF_SIGNATURE,
+            ShredFlags, ShredVariant, CODING_SHREDS_PER_FEC_BLOCK, DATA_SHREDS_PER_FEC_BLOCK,
+            MIN_CODING_SHREDS_PER_FEC_BLOCK, SHREDS_PER_FEC_BLOCK, SIZE_OF_CODING_SHRED_HEADERS,
+            SIZE_OF_DATA_SHRED_HEADERS, SIZE_OF_SIGNATURE,
         },
         shredder::{self, ReedSolomonCache},
     },
@@ -978,11 +979,18 @@ fn make_shreds_data<'a>(
 fn make_shreds_code(
     common_header: &mut ShredCommonHeader,
     num_data_shreds: usize,
+    num_coding_shreds: usize,
     is_last_in_slot: bool,
 ) -> impl Iterator<Item = ShredCode> + '_ {
     debug_assert_matches!(common_header.shred_variant, ShredVariant::MerkleCode { .. });
-    let erasure_batch_size = shredder::get_erasure_batch_size(num_data_shreds, is_last_in_slot);
-    let num_coding_shreds = erasure_batch_size - num_data_shreds;
+    // The last erasure batch of the slot has at least the default number of
+    // coding shreds, whatever the configured ratio.
+    let num_coding_shreds = if is_last_in_slot {
+        let erasure_batch_size = shredder::get_erasure_batch_size(num_data_shreds, is_last_in_slot);
+        num_coding_shreds.max(erasure_batch_size - num_data_shreds)
+    } else {
+        num_coding_shreds
+    };
     let mut coding_header = CodingShredHeader {
         num_data_shreds: num_data_shreds as u16,
         num_coding_shreds: num_coding_shreds as u16,
@@ -1015,9 +1023,16 @@ pub(super) fn make_shreds_from_data(
     is_last_in_slot: bool,
     next_shred_index: u32,
     next_code_index: u32,
+    // The coding shreds of each erasure batch of DATA_SHREDS_PER_FEC_BLOCK
+    // data shreds.
+    num_coding_shreds: usize,
     reed_solomon_cache: &ReedSolomonCache,
     stats: &mut ProcessShredsStats,
 ) -> Result<Vec<Shred>, Error> {
+    debug_assert!(
+        (MIN_CODING_SHREDS_PER_FEC_BLOCK..=CODING_SHREDS_PER_FEC_BLOCK)
+            .contains(&num_coding_shreds)
+    );
     let now = Instant::now();
     let chained = chained_merkle_root.is_some();
     let resigned = chained && is_last_in_slot;
@@ -1094,6 +1109,7 @@ pub(super) fn make_shreds_from_data(
             make_shreds_code(
                 &mut common_header_code,
                 DATA_SHREDS_PER_FEC_BLOCK,          // num_data_shreds
+                num_coding_shreds,                  // num_coding_shreds
                 is_last_in_slot && rest.is_empty(), // is_last_in_slot
             )
             .map(Shred::ShredCode),
@@ -1134,6 +1150,7 @@ pub(super) fn make_shreds_from_data(
             make_shreds_code(
                 &mut common_header_code,
                 DATA_SHREDS_PER_FEC_BLOCK,
+                num_coding_shreds,
                 is_last_in_slot,
             )
             .map(Shred::ShredCode),
@@ -1704,6 +1721,47 @@ mod test {
         }
     }
 
+    #[test_case(1)]
+    #[test_case(8)]
+    #[test_case(31)]
+    fn test_make_shreds_from_data_num_coding_shreds(num_coding_shreds: usize) {
+        let mut rng = rand::thread_rng();
+        let thread_pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let mut data = vec![0u8; 31200 * 3];
+        rng.fill(&mut data[..]);
+        let shreds = make_shreds_from_data(
+            &thread_pool,
+            &Keypair::new(),
+            Some(Hash::new_from_array(rng.gen())),
+            &data[..],
+            149_745_689, // slot
+            149_745_688, // parent_slot
+            rng.gen(),   // shred_version
+            0,           // reference_tick
+            true,        // is_last_in_slot
+            0,           // next_shred_index
+            0,           // next_code_index
+            num_coding_shreds,
+            &ReedSolomonCache::default(),
+            &mut ProcessShredsStats::default(),
+        )
+        .unwrap();
+        let batches: Vec<_> = shreds
+            .chunk_by(|a, b| a.fec_set_index() == b.fec_set_index())
+            .map(|batch| {
+                batch
+                    .iter()
+                    .filter(|shred| shred.shred_type() == ShredType::Code)
+                    .count()
+            })
+            .collect();
+        assert!(batches.len() > 1);
+        // The last erasure batch of the slot keeps the default ratio.
+        let (last, batches) = batches.split_last().unwrap();
+        assert_eq!(*last, CODING_SHREDS_PER_FEC_BLOCK);
+        assert!(batches.iter().all(|&count| count == num_coding_shreds));
+    }
+
     #[ignore]
     #[test_case(false, false)]
     #[test_case(false, true)]
@@ -1754,6 +1812,7 @@ mod test {
             is_last_in_slot,
             next_shred_index,
             next_code_index,
+            CODING_SHREDS_PER_FEC_BLOCK,
             reed_solomon_cache,
             &mut ProcessShredsStats::default(),
         )
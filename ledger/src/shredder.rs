@@ -1,6 +1,7 @@
 use {
     crate::shred::{
-        self, Error, ProcessShredsStats, Shred, ShredData, ShredFlags, DATA_SHREDS_PER_FEC_BLOCK,
+        self, Error, ProcessShredsStats, Shred, ShredData, ShredFlags, CODING_SHREDS_PER_FEC_BLOCK,
+        DATA_SHREDS_PER_FEC_BLOCK, MIN_CODING_SHREDS_PER_FEC_BLOCK,
     },
     itertools::Itertools,
     lazy_lru::LruCache,
@@ -55,6 +56,8 @@ pub struct Shredder {
     parent_slot: Slot,
     version: u16,
     reference_tick: u8,
+    // The coding shreds of each Merkle erasure batch but the last in slot.
+    num_coding_shreds: usize,
 }
 
 impl Shredder {
@@ -72,10 +75,21 @@ impl Shredder {
                 parent_slot,
                 reference_tick,
                 version,
+                num_coding_shreds: CODING_SHREDS_PER_FEC_BLOCK,
             })
         }
     }
 
+    /// Sets the number of coding shreds generated for each erasure batch of
+    /// `DATA_SHREDS_PER_FEC_BLOCK` data shreds, clamped to the range the
+    /// protocol allows. The last erasure batch of the slot has at least
+    /// `CODING_SHREDS_PER_FEC_BLOCK` coding shreds regardless.
+    pub fn with_num_coding_shreds(mut self, num_coding_shreds: usize) -> Self {
+        self.num_coding_shreds =
+            num_coding_shreds.clamp(MIN_CODING_SHREDS_PER_FEC_BLOCK, CODING_SHREDS_PER_FEC_BLOCK);
+        self
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn make_merkle_shreds_from_entries(
         &self,
@@ -100,6 +114,7 @@ impl Shredder {
             chained_merkle_root,
             next_shred_index,
             next_code_index,
+            self.num_coding_shreds,
             reed_solomon_cache,
             stats,
         )
@@ -4,4 +4,5 @@ pub mod cluster_tests;
 pub mod integration_tests;
 pub mod local_cluster;
 mod local_cluster_snapshot_utils;
+pub mod network_impairment;
 pub mod validator_configs;
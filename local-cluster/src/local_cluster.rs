@@ -3,6 +3,7 @@ use {
         cluster::{Cluster, ClusterValidatorInfo, QuicTpuClient, ValidatorInfo},
         cluster_tests,
         integration_tests::DEFAULT_NODE_STAKE,
+        network_impairment,
         validator_configs::*,
     },
     itertools::izip,
@@ -49,7 +50,7 @@ use {
         transport::TransportError,
     },
     solana_stake_program::stake_state,
-    solana_streamer::{socket::SocketAddrSpace, streamer::StakedNodes},
+    solana_streamer::{impairment, socket::SocketAddrSpace, streamer::StakedNodes},
     solana_tpu_client::tpu_client::{
         TpuClient, TpuClientConfig, DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_TPU_ENABLE_UDP,
         DEFAULT_TPU_USE_QUIC, DEFAULT_VOTE_USE_QUIC,
@@ -295,6 +296,7 @@ impl LocalCluster {
         let leader_vote_keypair = &keys_in_genesis[0].vote_keypair;
         let leader_pubkey = leader_keypair.pubkey();
         let leader_node = Node::new_localhost_with_pubkey(&leader_pubkey);
+        network_impairment::register_node(&leader_node);
 
         let GenesisConfigInfo {
             mut genesis_config,
@@ -509,6 +511,7 @@ impl LocalCluster {
         }
         let validator_pubkey = validator_keypair.pubkey();
         let validator_node = Node::new_localhost_with_pubkey(&validator_keypair.pubkey());
+        network_impairment::register_node(&validator_node);
         let contact_info = validator_node.info.clone();
         let (ledger_path, _blockhash) = create_new_tmp_ledger_with_size!(
             &self.genesis_config,
@@ -1034,6 +1037,9 @@ impl Cluster for LocalCluster {
     ) -> (Node, Vec<ContactInfo>) {
         // Update the stored ContactInfo for this node
         let node = Node::new_localhost_with_pubkey(pubkey);
+        // The impairments of the links of the node carry over to its new
+        // sockets
+        network_impairment::register_node(&node);
         cluster_validator_info.info.contact_info = node.info.clone();
         cluster_validator_info.config.rpc_addrs =
             Some((node.info.rpc().unwrap(), node.info.rpc_pubsub().unwrap()));
@@ -1158,5 +1164,8 @@ impl Cluster for LocalCluster {
 impl Drop for LocalCluster {
     fn drop(&mut self) {
         self.close();
+        for pubkey in self.validators.keys() {
+            impairment::unregister_node(pubkey);
+        }
     }
 }
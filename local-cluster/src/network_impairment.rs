@@ -0,0 +1,193 @@
+//! Programmable impairments of the network links between the validators of a
+//! local cluster, to test consensus and repair over a degraded network.
+//!
+//! The sockets of the validators are registered with
+//! [`solana_streamer::impairment`] as the cluster starts them, which applies
+//! the impairments to the packets received over UDP and by the QUIC servers
+//! of the streamer. The gossip, turbine and repair QUIC endpoints are not
+//! impaired.
+
+use {
+    log::*,
+    solana_gossip::cluster_info::Node,
+    solana_sdk::pubkey::Pubkey,
+    solana_streamer::impairment,
+    std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+pub use solana_streamer::impairment::LinkImpairment;
+
+const SCHEDULE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Registers the sockets of `node` for the impairments of the links of the
+/// node to apply to them
+pub(crate) fn register_node(node: &Node) {
+    let sockets = &node.sockets;
+    let addrs: Vec<SocketAddr> = [
+        &sockets.gossip,
+        &sockets.gossip_quic,
+        &sockets.tvu_quic,
+        &sockets.repair,
+        &sockets.repair_quic,
+        &sockets.serve_repair,
+        &sockets.serve_repair_quic,
+        &sockets.ancestor_hashes_requests,
+        &sockets.ancestor_hashes_requests_quic,
+        &sockets.tpu_vote_forwards_client,
+        &sockets.quic_forwards_client,
+        &sockets.quic_vote_client,
+        &sockets.rpc_sts_client,
+    ]
+    .into_iter()
+    .chain(&sockets.tvu)
+    .chain(&sockets.tpu)
+    .chain(&sockets.tpu_forwards)
+    .chain(&sockets.tpu_vote)
+    .chain(&sockets.broadcast)
+    .chain(&sockets.retransmit_sockets)
+    .chain(&sockets.tpu_quic)
+    .chain(&sockets.tpu_forwards_quic)
+    .chain(&sockets.tpu_vote_quic)
+    .chain(sockets.vortexor_receivers.iter().flatten())
+    .filter_map(|socket| socket.local_addr().ok())
+    .collect();
+    impairment::register_node(*node.info.pubkey(), addrs);
+}
+
+#[derive(Clone, Debug)]
+pub enum ImpairmentChange {
+    /// Sets the impairment of the link from one validator to another
+    Link {
+        from: Pubkey,
+        to: Pubkey,
+        impairment: LinkImpairment,
+    },
+    /// Sets the impairment of the links both ways between every two of the
+    /// validators
+    AllLinks {
+        validators: Vec<Pubkey>,
+        impairment: LinkImpairment,
+    },
+    /// Partitions the links both ways between the validators of different
+    /// groups, the validators which are in none of the groups are left as is
+    Partition(Vec<Vec<Pubkey>>),
+    /// Lifts the impairments of all the links
+    Heal,
+}
+
+impl ImpairmentChange {
+    pub fn apply(&self) {
+        info!("applying network impairment change: {self:?}");
+        match self {
+            Self::Link {
+                from,
+                to,
+                impairment,
+            } => impairment::set_link_impairment(*from, *to, *impairment),
+            Self::AllLinks {
+                validators,
+                impairment,
+            } => {
+                for from in validators {
+                    for to in validators.iter().filter(|to| *to != from) {
+                        impairment::set_link_impairment(*from, *to, *impairment);
+                    }
+                }
+            }
+            Self::Partition(groups) => {
+                for (i, group) in groups.iter().enumerate() {
+                    for (_, other_group) in groups.iter().enumerate().filter(|(j, _)| *j != i) {
+                        for from in group {
+                            for to in other_group {
+                                impairment::set_link_impairment(
+                                    *from,
+                                    *to,
+                                    LinkImpairment::partitioned(),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            Self::Heal => impairment::clear_link_impairments(),
+        }
+    }
+}
+
+/// Changes of the network impairments to apply at set times
+#[derive(Clone, Debug, Default)]
+pub struct ImpairmentSchedule {
+    /// The changes, with how long after the start of the schedule to apply
+    /// them
+    changes: Vec<(Duration, ImpairmentChange)>,
+}
+
+impl ImpairmentSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `change` once `offset` elapsed since the start of the schedule
+    pub fn at(mut self, offset: Duration, change: ImpairmentChange) -> Self {
+        self.changes.push((offset, change));
+        self
+    }
+
+    /// Starts applying the changes in the order of their offsets, the ones
+    /// with the same offset in the order they were added
+    pub fn start(mut self) -> RunningImpairmentSchedule {
+        self.changes.sort_by_key(|(offset, _)| *offset);
+        let exit = Arc::new(AtomicBool::new(false));
+        let thread_hdl = {
+            let exit = exit.clone();
+            Builder::new()
+                .name("solImpairSched".to_string())
+                .spawn(move || {
+                    let start = Instant::now();
+                    for (offset, change) in self.changes {
+                        while start.elapsed() < offset {
+                            if exit.load(Ordering::Relaxed) {
+                                return;
+                            }
+                            sleep(
+                                SCHEDULE_POLL_INTERVAL.min(offset.saturating_sub(start.elapsed())),
+                            );
+                        }
+                        change.apply();
+                    }
+                })
+                .unwrap()
+        };
+        RunningImpairmentSchedule { exit, thread_hdl }
+    }
+}
+
+pub struct RunningImpairmentSchedule {
+    exit: Arc<AtomicBool>,
+    thread_hdl: JoinHandle<()>,
+}
+
+impl RunningImpairmentSchedule {
+    pub fn is_finished(&self) -> bool {
+        self.thread_hdl.is_finished()
+    }
+
+    /// Waits for all the changes of the schedule to be applied
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+
+    /// Stops the schedule without applying the changes which are not due yet
+    pub fn stop(self) -> thread::Result<()> {
+        self.exit.store(true, Ordering::Relaxed);
+        self.thread_hdl.join()
+    }
+}
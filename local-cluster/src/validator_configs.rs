@@ -37,6 +37,9 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         debug_keys: config.debug_keys.clone(),
         contact_debug_interval: config.contact_debug_interval,
         contact_save_interval: config.contact_save_interval,
+        gossip_priority_weights: config.gossip_priority_weights.clone(),
+        enable_gossip_quic: config.enable_gossip_quic,
+        commission_guard_config: config.commission_guard_config.clone(),
         send_transaction_service_config: config.send_transaction_service_config.clone(),
         no_poh_speed_test: config.no_poh_speed_test,
         no_os_memory_stats_reporting: config.no_os_memory_stats_reporting,
@@ -44,6 +47,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         no_os_cpu_stats_reporting: config.no_os_cpu_stats_reporting,
         no_os_disk_stats_reporting: config.no_os_disk_stats_reporting,
         poh_pinned_cpu_core: config.poh_pinned_cpu_core,
+        poh_numa_node: config.poh_numa_node,
         warp_slot: config.warp_slot,
         accounts_db_test_hash_calculation: config.accounts_db_test_hash_calculation,
         accounts_db_skip_shrink: config.accounts_db_skip_shrink,
@@ -52,16 +56,41 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         staked_nodes_overrides: config.staked_nodes_overrides.clone(),
         validator_exit: Arc::new(RwLock::new(Exit::default())),
         poh_hashes_per_batch: config.poh_hashes_per_batch,
+        poh_calibration_interval_ms: config.poh_calibration_interval_ms,
         process_ledger_before_services: config.process_ledger_before_services,
         no_wait_for_vote_to_start_leader: config.no_wait_for_vote_to_start_leader,
         accounts_db_config: config.accounts_db_config.clone(),
         wait_to_vote_slot: config.wait_to_vote_slot,
+        clock_drift_config: config.clock_drift_config.clone(),
+        vote_refresh_config: config.vote_refresh_config.clone(),
+        shred_latency_slots: config.shred_latency_slots,
+        leader_slot_stats_slots: config.leader_slot_stats_slots,
+        confirmation_latency_slots: config.confirmation_latency_slots,
+        skipped_slot_reasons_slots: config.skipped_slot_reasons_slots,
+        account_congestion_slots: config.account_congestion_slots,
+        confirmation_proof_slots: config.confirmation_proof_slots,
+        buffered_transaction_samples: config.buffered_transaction_samples,
+        account_growth_epochs: config.account_growth_epochs,
+        bank_hash_breakdown_slots: config.bank_hash_breakdown_slots,
+        cost_calibration_report_interval_ms: config.cost_calibration_report_interval_ms,
+        pipeline_tracing_config: config.pipeline_tracing_config.clone(),
+        pinned_programs: config.pinned_programs.clone(),
+        precompile_programs: config.precompile_programs,
+        precompile_programs_slots: config.precompile_programs_slots,
+        failover_config: config.failover_config.clone(),
+        forensic_bundle_dir: config.forensic_bundle_dir.clone(),
+        repair_quic: config.repair_quic,
+        repair_peer_selection: config.repair_peer_selection,
         runtime_config: config.runtime_config.clone(),
         banking_trace_dir_byte_limit: config.banking_trace_dir_byte_limit,
         block_verification_method: config.block_verification_method.clone(),
         block_production_method: config.block_production_method.clone(),
         transaction_struct: config.transaction_struct.clone(),
         enable_block_production_forwarding: config.enable_block_production_forwarding,
+        forwarding_leader_fanout: config.forwarding_leader_fanout,
+        packet_provenance_cache_size: config.packet_provenance_cache_size,
+        dump_leader_slot_transactions: config.dump_leader_slot_transactions.clone(),
+        record_banking_events: config.record_banking_events.clone(),
         generator_config: config.generator_config.clone(),
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         wen_restart_proto_path: config.wen_restart_proto_path.clone(),
@@ -71,10 +100,19 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         rayon_global_threads: config.rayon_global_threads,
         replay_forks_threads: config.replay_forks_threads,
         replay_transactions_threads: config.replay_transactions_threads,
+        replay_fork_transactions_threads: config.replay_fork_transactions_threads,
+        entry_verification_cpus: config.entry_verification_cpus.clone(),
+        banking_thread_pinning: config.banking_thread_pinning.clone(),
+        sigverify_thread_pinning: config.sigverify_thread_pinning.clone(),
         tvu_shred_sigverify_threads: config.tvu_shred_sigverify_threads,
         delay_leader_block_for_pending_fork: config.delay_leader_block_for_pending_fork,
         use_tpu_client_next: config.use_tpu_client_next,
         retransmit_xdp: config.retransmit_xdp.clone(),
+        retransmit_egress_shaper: config.retransmit_egress_shaper,
+        remote_shred_signer: config.remote_shred_signer.clone(),
+        broadcast_fec_ratio: config.broadcast_fec_ratio.clone(),
+        shredstream: config.shredstream.clone(),
+        epoch_accounts_hash_scheduling: config.epoch_accounts_hash_scheduling.clone(),
     }
 }
 
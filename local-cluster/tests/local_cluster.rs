@@ -44,6 +44,7 @@ use {
             ValidatorTestConfig, DEFAULT_NODE_STAKE, RUST_LOG_FILTER,
         },
         local_cluster::{ClusterConfig, LocalCluster, DEFAULT_MINT_LAMPORTS},
+        network_impairment::{ImpairmentChange, ImpairmentSchedule, LinkImpairment},
         validator_configs::*,
     },
     solana_pubsub_client::pubsub_client::PubsubClient,
@@ -201,6 +202,57 @@ fn test_spend_and_verify_all_nodes_3() {
     );
 }
 
+#[test]
+#[serial]
+fn test_local_cluster_network_impairments() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let num_nodes = 3;
+    let cluster = LocalCluster::new_with_equal_stakes(
+        num_nodes,
+        DEFAULT_MINT_LAMPORTS,
+        DEFAULT_NODE_STAKE,
+        SocketAddrSpace::Unspecified,
+    );
+    let validators = cluster.get_node_pubkeys();
+
+    // Degrade all the links, cut one of the validators off for a while, and
+    // check that the cluster roots once the cut is lifted.
+    let schedule = ImpairmentSchedule::new()
+        .at(
+            Duration::ZERO,
+            ImpairmentChange::AllLinks {
+                validators: validators.clone(),
+                impairment: LinkImpairment {
+                    latency: Duration::from_millis(10),
+                    loss_rate: 0.05,
+                    partitioned: false,
+                },
+            },
+        )
+        .at(
+            Duration::from_secs(2),
+            ImpairmentChange::Partition(vec![validators[..1].to_vec(), validators[1..].to_vec()]),
+        )
+        .at(
+            Duration::from_secs(6),
+            ImpairmentChange::AllLinks {
+                validators,
+                impairment: LinkImpairment {
+                    latency: Duration::from_millis(10),
+                    ..LinkImpairment::default()
+                },
+            },
+        )
+        .start();
+    schedule.join().unwrap();
+    cluster.check_for_new_roots(
+        16,
+        "test_local_cluster_network_impairments",
+        SocketAddrSpace::Unspecified,
+    );
+    ImpairmentChange::Heal.apply();
+}
+
 #[test]
 #[serial]
 fn test_local_cluster_signature_subscribe() {
@@ -1,7 +1,7 @@
 //! The `metrics` module enables sending measurements to an `InfluxDB` instance
 
 use {
-    crate::{counter::CounterPoint, datapoint::DataPoint},
+    crate::{counter::CounterPoint, datapoint::DataPoint, prometheus},
     crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
     gethostname::gethostname,
     lazy_static::lazy_static,
@@ -14,7 +14,10 @@ use {
         convert::Into,
         env,
         fmt::Write,
-        sync::{Arc, Barrier, Mutex, Once, RwLock},
+        sync::{
+            atomic::{AtomicI32, Ordering},
+            Arc, Barrier, Mutex, Once, RwLock,
+        },
         thread,
         time::{Duration, Instant, UNIX_EPOCH},
     },
@@ -323,10 +326,16 @@ impl MetricsAgent {
                     }
                     MetricsCommand::Submit(point, level) => {
                         log!(level, "{}", point);
+                        if let Some(registry) = prometheus::registry() {
+                            registry.record_point(&point);
+                        }
                         points.push(point);
                     }
                     MetricsCommand::SubmitCounter(counter, _level, bucket) => {
                         debug!("{:?}", counter);
+                        if let Some(registry) = prometheus::registry() {
+                            registry.record_counter(&counter);
+                        }
                         let key = (counter.name, bucket);
                         if let Some(value) = counters.get_mut(&key) {
                             value.count += counter.count;
@@ -413,6 +422,10 @@ pub fn set_host_id(host_id: String) {
     *HOST_ID.write().unwrap() = host_id;
 }
 
+pub(crate) fn host_id() -> String {
+    HOST_ID.read().unwrap().clone()
+}
+
 /// Submits a new point from any thread.  Note that points are internally queued
 /// and transmitted periodically in batches.
 pub fn submit(point: DataPoint, level: log::Level) {
@@ -510,6 +523,16 @@ pub fn flush() {
 }
 
 /// Hook the panic handler to generate a data point on each panic
+/// Process exit code used by the panic hook installed with [`set_panic_hook`]
+static PANIC_EXIT_CODE: AtomicI32 = AtomicI32::new(1);
+
+/// Overrides the process exit code used by the panic hook. This allows a
+/// caller that is about to panic on a known fatal condition to surface a more
+/// specific exit code than the default of 1.
+pub fn set_panic_exit_code(exit_code: i32) {
+    PANIC_EXIT_CODE.store(exit_code, Ordering::Relaxed);
+}
+
 pub fn set_panic_hook(program: &'static str, version: Option<String>) {
     static SET_HOOK: Once = Once::new();
     SET_HOOK.call_once(|| {
@@ -537,7 +560,7 @@ pub fn set_panic_hook(program: &'static str, version: Option<String>) {
             flush();
 
             // Exit cleanly so the process don't limp along in a half-dead state
-            std::process::exit(1);
+            std::process::exit(PANIC_EXIT_CODE.load(Ordering::Relaxed));
         }));
     });
 }
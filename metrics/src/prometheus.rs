@@ -0,0 +1,401 @@
+//! The `prometheus` module serves the datapoints submitted to the metrics
+//! agent as a Prometheus `/metrics` endpoint
+//!
+//! Each numeric field of a datapoint is exported as the gauge
+//! `solana_<datapoint>_<field>` holding the latest submitted value. Counters
+//! are exported as `solana_<counter>_total`, accumulating the counts submitted
+//! since the start of the process. Names are lowercased and every character
+//! other than an ASCII letter, digit or underscore is replaced by an
+//! underscore. Every series is labeled with the `host_id` and the tags of its
+//! datapoint. String fields are not exported.
+
+use {
+    crate::{counter::CounterPoint, datapoint::DataPoint, metrics::host_id},
+    log::*,
+    std::{
+        collections::BTreeMap,
+        fmt::Write as _,
+        io::{self, BufRead, BufReader, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, OnceLock,
+        },
+        thread,
+        time::Duration,
+    },
+};
+
+const METRIC_NAME_PREFIX: &str = "solana_";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+/// Bounds the memory of the registry if a tag has unexpectedly many values
+const MAX_SERIES: usize = 200_000;
+const MAX_REQUEST_HEADER_LINES: usize = 100;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+static REGISTRY: OnceLock<Arc<PrometheusRegistry>> = OnceLock::new();
+
+/// Returns the registry recording the datapoints, once the exporter started
+pub(crate) fn registry() -> Option<&'static PrometheusRegistry> {
+    REGISTRY.get().map(Arc::as_ref)
+}
+
+/// Serves the datapoints submitted from now on at `http://<address>/metrics`
+pub fn start_exporter(address: SocketAddr) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    let registry = REGISTRY.get_or_init(Arc::default).clone();
+    thread::Builder::new()
+        .name("solMetricsProm".into())
+        .spawn(move || serve(listener, registry))?;
+    info!("serving Prometheus metrics at http://{address}/metrics");
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+struct MetricFamily {
+    metric_type: MetricType,
+    help: String,
+    /// Values by the rendered labels, other than `host_id`
+    series: BTreeMap<String, f64>,
+}
+
+#[derive(Default)]
+struct Families {
+    families: BTreeMap<String, MetricFamily>,
+    num_series: usize,
+}
+
+impl Families {
+    fn update(
+        &mut self,
+        name: String,
+        metric_type: MetricType,
+        help: impl FnOnce() -> String,
+        labels: &str,
+        update: impl FnOnce(&mut f64),
+    ) -> bool {
+        let family = self.families.entry(name).or_insert_with(|| MetricFamily {
+            metric_type,
+            help: help(),
+            series: BTreeMap::new(),
+        });
+        if family.metric_type != metric_type {
+            return true;
+        }
+        if let Some(value) = family.series.get_mut(labels) {
+            update(value);
+            return true;
+        }
+        if self.num_series >= MAX_SERIES {
+            return false;
+        }
+        self.num_series += 1;
+        update(family.series.entry(labels.to_string()).or_default());
+        true
+    }
+}
+
+#[derive(Default)]
+pub struct PrometheusRegistry {
+    families: Mutex<Families>,
+    warned_max_series: AtomicBool,
+}
+
+impl PrometheusRegistry {
+    pub fn record_point(&self, point: &DataPoint) {
+        let labels = render_labels(&point.tags);
+        let mut families = self.families.lock().unwrap();
+        let mut dropped = false;
+        for (field, value) in &point.fields {
+            let Some(value) = parse_field_value(value) else {
+                continue;
+            };
+            let name = format!(
+                "{METRIC_NAME_PREFIX}{}_{}",
+                sanitize_name(point.name),
+                sanitize_name(field)
+            );
+            let help = || format!("Field {field} of the datapoint {}", point.name);
+            dropped |= !families.update(name, MetricType::Gauge, help, &labels, |latest| {
+                *latest = value
+            });
+        }
+        drop(families);
+        if dropped {
+            self.warn_max_series();
+        }
+    }
+
+    pub fn record_counter(&self, counter: &CounterPoint) {
+        let name = format!("{METRIC_NAME_PREFIX}{}_total", sanitize_name(counter.name));
+        let help = || format!("Count of the counter {}", counter.name);
+        let mut families = self.families.lock().unwrap();
+        let increment = |total: &mut f64| *total += counter.count as f64;
+        let recorded = families.update(name, MetricType::Counter, help, "", increment);
+        drop(families);
+        if !recorded {
+            self.warn_max_series();
+        }
+    }
+
+    fn warn_max_series(&self) {
+        if !self.warned_max_series.swap(true, Ordering::Relaxed) {
+            warn!("Prometheus metrics exceed {MAX_SERIES} series, new series are dropped");
+        }
+    }
+
+    /// Renders the recorded series in the Prometheus text exposition format
+    pub fn render(&self, host_id: &str) -> String {
+        let host_id = format!("host_id=\"{}\"", escape_label_value(host_id));
+        let families = self.families.lock().unwrap();
+        let mut text = String::new();
+        for (name, family) in &families.families {
+            let _ = writeln!(text, "# HELP {name} {}", escape_help(&family.help));
+            let _ = writeln!(text, "# TYPE {name} {}", family.metric_type.as_str());
+            for (labels, value) in &family.series {
+                let separator = if labels.is_empty() { "" } else { "," };
+                let value = format_value(*value);
+                let _ = writeln!(text, "{name}{{{host_id}{separator}{labels}}} {value}");
+            }
+        }
+        text
+    }
+}
+
+fn serve(listener: TcpListener, registry: Arc<PrometheusRegistry>) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle_connection(stream, &registry) {
+                    debug!("Prometheus metrics request failed: {err}");
+                }
+            }
+            Err(err) => warn!("Prometheus metrics connection failed: {err}"),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, registry: &PrometheusRegistry) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers are ignored, but read so that the client does not see the
+    // connection reset before the response is sent
+    for _ in 0..MAX_REQUEST_HEADER_LINES {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    let (status, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", registry.render(&host_id())),
+        ("GET", _) => ("404 Not Found", "Not Found\n".to_string()),
+        _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_string()),
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()
+}
+
+/// Returns `name` lowercased, with every character other than an ASCII
+/// letter, digit or underscore replaced by an underscore
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Renders the tags as labels, sorted by name so that the series of a
+/// datapoint do not depend on the order of its tags
+fn render_labels(tags: &[(&'static str, String)]) -> String {
+    let mut labels: Vec<_> = tags
+        .iter()
+        .map(|(name, value)| (sanitize_name(name), value))
+        // `host_id` labels every series, and names starting with `__` are
+        // reserved by Prometheus
+        .filter(|(name, _)| name != "host_id" && !name.starts_with("__"))
+        .collect();
+    labels.sort_by(|(a, _), (b, _)| a.cmp(b));
+    labels.dedup_by(|(a, _), (b, _)| a == b);
+    let mut text = String::new();
+    for (name, value) in labels {
+        let separator = if text.is_empty() { "" } else { "," };
+        let prefix = if name.starts_with(|c: char| c.is_ascii_digit()) {
+            "_"
+        } else {
+            ""
+        };
+        let _ = write!(
+            text,
+            "{separator}{prefix}{name}=\"{}\"",
+            escape_label_value(value)
+        );
+    }
+    text
+}
+
+/// Parses the value of a datapoint field, `None` for a string field
+fn parse_field_value(value: &str) -> Option<f64> {
+    if let Some(value) = value.strip_suffix('i') {
+        return value.parse::<i64>().ok().map(|value| value as f64);
+    }
+    match value {
+        "true" => Some(1.0),
+        "false" => Some(0.0),
+        _ if value.starts_with('"') => None,
+        _ => value.parse().ok(),
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, std::io::Read};
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("replay-slot-stats"), "replay_slot_stats");
+        assert_eq!(
+            sanitize_name("bank-process_transactions"),
+            "bank_process_transactions"
+        );
+        assert_eq!(sanitize_name("cost_tracker.Stats"), "cost_tracker_stats");
+    }
+
+    #[test]
+    fn test_parse_field_value() {
+        assert_eq!(parse_field_value("42i"), Some(42.0));
+        assert_eq!(parse_field_value("-3i"), Some(-3.0));
+        assert_eq!(parse_field_value("1.5"), Some(1.5));
+        assert_eq!(parse_field_value("true"), Some(1.0));
+        assert_eq!(parse_field_value("false"), Some(0.0));
+        assert_eq!(parse_field_value("\"12\""), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let registry = PrometheusRegistry::default();
+        registry.record_point(
+            DataPoint::new("replay-slot-stats")
+                .add_tag("Kind", "a\"b")
+                .add_tag("host_id", "ignored")
+                .add_field_i64("slot", 7)
+                .add_field_f64("elapsed-ms", 1.5)
+                .add_field_bool("is_leader", true)
+                .add_field_str("reason", "unused"),
+        );
+        // The latest value of a gauge replaces the previous one.
+        registry.record_point(
+            DataPoint::new("replay-slot-stats")
+                .add_tag("Kind", "a\"b")
+                .add_field_i64("slot", 8),
+        );
+        registry.record_point(DataPoint::new("replay-slot-stats").add_field_i64("slot", 9));
+        let mut counter = CounterPoint::new("bank-process_transactions");
+        counter.count = 3;
+        registry.record_counter(&counter);
+        counter.count = 2;
+        registry.record_counter(&counter);
+
+        assert_eq!(
+            registry.render("host"),
+            "# HELP solana_bank_process_transactions_total Count of the counter \
+             bank-process_transactions\n\
+             # TYPE solana_bank_process_transactions_total counter\n\
+             solana_bank_process_transactions_total{host_id=\"host\"} 5\n\
+             # HELP solana_replay_slot_stats_elapsed_ms Field elapsed-ms of the datapoint \
+             replay-slot-stats\n\
+             # TYPE solana_replay_slot_stats_elapsed_ms gauge\n\
+             solana_replay_slot_stats_elapsed_ms{host_id=\"host\",kind=\"a\\\"b\"} 1.5\n\
+             # HELP solana_replay_slot_stats_is_leader Field is_leader of the datapoint \
+             replay-slot-stats\n\
+             # TYPE solana_replay_slot_stats_is_leader gauge\n\
+             solana_replay_slot_stats_is_leader{host_id=\"host\",kind=\"a\\\"b\"} 1\n\
+             # HELP solana_replay_slot_stats_slot Field slot of the datapoint \
+             replay-slot-stats\n\
+             # TYPE solana_replay_slot_stats_slot gauge\n\
+             solana_replay_slot_stats_slot{host_id=\"host\"} 9\n\
+             solana_replay_slot_stats_slot{host_id=\"host\",kind=\"a\\\"b\"} 8\n"
+        );
+    }
+
+    #[test]
+    fn test_serve() {
+        let registry = Arc::new(PrometheusRegistry::default());
+        registry.record_point(DataPoint::new("measurement").add_field_i64("i", 1));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_registry = registry.clone();
+        thread::spawn(move || serve(listener, server_registry));
+
+        let request = |request: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = request("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(&registry.render(&host_id())));
+        assert!(response.contains("solana_measurement_i{host_id="));
+        let response = request("GET /other HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        let response = request("POST /metrics HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
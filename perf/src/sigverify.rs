@@ -9,6 +9,7 @@ use {
         packet::{Packet, PacketBatch, PacketFlags, PACKET_DATA_SIZE},
         perf_libs,
         recycler::Recycler,
+        thread::ThreadPinning,
     },
     rayon::{prelude::*, ThreadPool},
     solana_hash::Hash,
@@ -17,18 +18,31 @@ use {
     solana_rayon_threadlimit::get_thread_count,
     solana_short_vec::decode_shortu16_len,
     solana_signature::Signature,
-    std::{convert::TryFrom, mem::size_of},
+    std::{convert::TryFrom, mem::size_of, sync::OnceLock},
 };
 
 // Empirically derived to constrain max verify latency to ~8ms at lower packet counts
 pub const VERIFY_PACKET_CHUNK_SIZE: usize = 128;
 
+static THREAD_POOL_PINNING: OnceLock<ThreadPinning> = OnceLock::new();
+
 lazy_static! {
-    static ref PAR_THREAD_POOL: ThreadPool = rayon::ThreadPoolBuilder::new()
-        .num_threads(get_thread_count())
-        .thread_name(|i| format!("solSigVerify{i:02}"))
-        .build()
-        .unwrap();
+    static ref PAR_THREAD_POOL: ThreadPool = {
+        // Once the threads are started, they can no longer be pinned.
+        let pinning = THREAD_POOL_PINNING.get_or_init(ThreadPinning::default);
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(get_thread_count())
+            .thread_name(|i| format!("solSigVerify{i:02}"))
+            .start_handler(move |_| pinning.pin_this_thread())
+            .build()
+            .unwrap()
+    };
+}
+
+/// Pins the threads which verify the signatures of packets. Returns false if the threads are
+/// already started, or pinned, in which case they are left as they are.
+pub fn set_thread_pool_pinning(pinning: ThreadPinning) -> bool {
+    THREAD_POOL_PINNING.set(pinning).is_ok()
 }
 
 pub type TxOffset = PinnedVec<u32>;
@@ -88,6 +88,114 @@ where
     }
 }
 
+/// The maximum number of NUMA nodes whose memory threads can be bound to
+#[cfg(target_os = "linux")]
+const MAX_NUMA_NODES: usize = 1024;
+
+/// The cores a group of threads runs on, and the NUMA node the threads allocate their memory on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThreadPinning {
+    /// The threads run on any of these cores, or on any core if it is empty
+    pub cpus: Vec<usize>,
+    /// The memory allocated by the threads, like the buffers of the packets they process, is
+    /// preferably allocated on this node
+    pub numa_node: Option<usize>,
+}
+
+impl ThreadPinning {
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty() && self.numa_node.is_none()
+    }
+
+    /// Pins the calling thread, logging a warning if it fails as the thread still runs unpinned
+    pub fn pin_this_thread(&self) {
+        if !self.cpus.is_empty() {
+            if let Err(err) = set_this_thread_cpu_affinity(&self.cpus) {
+                warn!("Failed to pin thread to cores {:?}: {err}", self.cpus);
+            }
+        }
+        if let Some(numa_node) = self.numa_node {
+            if let Err(err) = set_this_thread_preferred_numa_node(numa_node) {
+                warn!("Failed to bind the memory of thread to NUMA node {numa_node}: {err}");
+            }
+        }
+    }
+}
+
+/// Restricts the calling thread to run on the `cpus` cores. The threads spawned by the thread
+/// inherit its affinity.
+#[cfg(target_os = "linux")]
+pub fn set_this_thread_cpu_affinity(cpus: &[usize]) -> Result<(), String> {
+    // SAFETY: `cpu_set_t` is a plain bit mask, which is valid zeroed.
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(format!("core {cpu} is out of range"));
+        }
+        // SAFETY: `cpu` is in the range of the mask.
+        unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+    }
+    // SAFETY: `cpu_set` outlives the call, which only reads it.
+    let result =
+        unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+/// Restricts the calling thread to run on the `cpus` cores.
+///
+/// Fails on non-Linux systems.
+#[cfg(not(target_os = "linux"))]
+pub fn set_this_thread_cpu_affinity(_cpus: &[usize]) -> Result<(), String> {
+    Err(String::from(
+        "Failed to set thread's affinity: only supported on Linux",
+    ))
+}
+
+/// Makes the memory allocated by the calling thread preferably allocated on NUMA node
+/// `numa_node`, falling back to the other nodes when it is out of free memory.
+#[cfg(target_os = "linux")]
+pub fn set_this_thread_preferred_numa_node(numa_node: usize) -> Result<(), String> {
+    // From <linux/mempolicy.h>
+    const MPOL_PREFERRED: libc::c_int = 1;
+    const BITS_PER_WORD: usize = libc::c_ulong::BITS as usize;
+
+    if numa_node >= MAX_NUMA_NODES {
+        return Err(format!("NUMA node {numa_node} is out of range"));
+    }
+    let mut nodemask = [0 as libc::c_ulong; MAX_NUMA_NODES / BITS_PER_WORD];
+    nodemask[numa_node / BITS_PER_WORD] |= 1 << (numa_node % BITS_PER_WORD);
+    // SAFETY: `nodemask` outlives the call, which only reads it. The kernel reads one bit less
+    // than `maxnode`.
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_set_mempolicy,
+            MPOL_PREFERRED,
+            nodemask.as_ptr(),
+            MAX_NUMA_NODES + 1,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+/// Makes the memory allocated by the calling thread preferably allocated on NUMA node
+/// `numa_node`.
+///
+/// Fails on non-Linux systems.
+#[cfg(not(target_os = "linux"))]
+pub fn set_this_thread_preferred_numa_node(_numa_node: usize) -> Result<(), String> {
+    Err(String::from(
+        "Failed to set thread's memory policy: only supported on Linux",
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(target_os = "linux")]
@@ -123,6 +231,33 @@ mod tests {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_set_this_thread_cpu_affinity() {
+        // SAFETY: `sched_getcpu` has no preconditions.
+        let cpu = unsafe { libc::sched_getcpu() };
+        let result = std::thread::spawn(move || {
+            set_this_thread_cpu_affinity(&[cpu as usize])?;
+            // SAFETY: `sched_getcpu` has no preconditions.
+            Ok::<_, String>(unsafe { libc::sched_getcpu() })
+        })
+        .join()
+        .unwrap();
+        assert_eq!(result, Ok(cpu));
+        assert!(set_this_thread_cpu_affinity(&[libc::CPU_SETSIZE as usize]).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_set_this_thread_preferred_numa_node() {
+        // Every system has a node 0, even those which are not NUMA.
+        let result = std::thread::spawn(|| set_this_thread_preferred_numa_node(0))
+            .join()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        assert!(set_this_thread_preferred_numa_node(MAX_NUMA_NODES).is_err());
+    }
+
     #[test]
     fn test_is_niceness_adjustment_valid() {
         use super::is_niceness_adjustment_valid;
@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod leader_bank_notifier;
+pub mod poh_calibration_service;
 pub mod poh_recorder;
 pub mod poh_service;
 pub mod transaction_recorder;
@@ -0,0 +1,272 @@
+//! The `poh_calibration_service` module implements a service that measures
+//! the hash rate of the PoH service while it runs, and tunes the number of
+//! hashes it batches together.
+//!
+//! The PoH service accumulates how many hashes it computed and for how long it
+//! was busy, excluding the time it waited for the ideal PoH time. Once per
+//! interval the calibration service turns these into a hash rate, and compares
+//! it with the rate the cluster requires. Larger batches amortize the locking
+//! of PoH and the polling for records over more hashes, so the batch size is
+//! doubled while the rate is close to the requirement, and halved back towards
+//! the configured size once the rate is comfortably above it.
+//!
+//! A rate below the requirement, e.g. as the CPU is thermally throttled, is
+//! logged, reported in the `poh-calibration` metric and in the status served
+//! through the admin RPC.
+
+use {
+    log::*,
+    solana_entry::poh::compute_hash_time,
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    std::{
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+pub const DEFAULT_POH_CALIBRATION_INTERVAL_MS: u64 = 10_000;
+
+// The batch size is raised up to this multiple of the configured size.
+const MAX_HASHES_PER_BATCH_MULTIPLIER: u64 = 16;
+// Hash rates, in percent of the cluster requirement, under which the batch
+// size is raised and over which it is lowered.
+const RAISE_BATCH_SIZE_RATE_PERCENT: u64 = 110;
+const LOWER_BATCH_SIZE_RATE_PERCENT: u64 = 150;
+
+const SLEEP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The hash rate the cluster requires of the PoH of `bank`, `None` if PoH
+/// does not hash, as in low power mode
+pub fn target_hashes_per_second(bank: &Bank) -> Option<u64> {
+    let hashes_per_slot = bank.hashes_per_tick()? * bank.ticks_per_slot();
+    let target_slot_duration = Duration::from_nanos(bank.ns_per_slot as u64);
+    Some((hashes_per_slot as f64 / target_slot_duration.as_secs_f64()) as u64)
+}
+
+/// Measures the hash rate of this thread over `num_hashes` hashes.
+pub fn measure_hashes_per_second(num_hashes: u64) -> u64 {
+    let hash_time = compute_hash_time(num_hashes);
+    (num_hashes as f64 / hash_time.as_secs_f64()) as u64
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PohCalibrationStatus {
+    /// Measured over the latest interval
+    pub hashes_per_second: u64,
+    pub target_hashes_per_second: u64,
+    pub hashes_per_batch: u64,
+}
+
+impl PohCalibrationStatus {
+    pub fn is_throttled(&self) -> bool {
+        self.hashes_per_second < self.target_hashes_per_second
+    }
+}
+
+/// The calibration shared by the PoH service and the calibration service
+#[derive(Debug)]
+pub struct PohCalibration {
+    hashes_per_batch: AtomicU64,
+    min_hashes_per_batch: u64,
+    max_hashes_per_batch: u64,
+    /// Accumulated by the PoH service since the latest calibration
+    num_hashes: AtomicU64,
+    busy_ns: AtomicU64,
+    status: RwLock<Option<PohCalibrationStatus>>,
+}
+
+impl PohCalibration {
+    /// Creates a calibration batching `hashes_per_batch` hashes, the smallest
+    /// batch size it lowers to.
+    pub fn new(hashes_per_batch: u64) -> Self {
+        let hashes_per_batch = hashes_per_batch.max(1);
+        Self {
+            hashes_per_batch: AtomicU64::new(hashes_per_batch),
+            min_hashes_per_batch: hashes_per_batch,
+            max_hashes_per_batch: hashes_per_batch.saturating_mul(MAX_HASHES_PER_BATCH_MULTIPLIER),
+            num_hashes: AtomicU64::default(),
+            busy_ns: AtomicU64::default(),
+            status: RwLock::default(),
+        }
+    }
+
+    pub fn hashes_per_batch(&self) -> u64 {
+        self.hashes_per_batch.load(Ordering::Relaxed)
+    }
+
+    /// Records that the PoH service computed `num_hashes` hashes while busy for
+    /// `busy_ns` nanoseconds.
+    pub(crate) fn record_throughput(&self, num_hashes: u64, busy_ns: u64) {
+        self.num_hashes.fetch_add(num_hashes, Ordering::Relaxed);
+        self.busy_ns.fetch_add(busy_ns, Ordering::Relaxed);
+    }
+
+    /// The status as of the latest calibration, `None` before the first one
+    pub fn status(&self) -> Option<PohCalibrationStatus> {
+        *self.status.read().unwrap()
+    }
+
+    /// Measures the hash rate since the previous calibration against
+    /// `target_hashes_per_second` and adjusts the batch size, returning the
+    /// new status along with the previous one. Returns `None` if PoH did not
+    /// hash since the previous calibration.
+    fn calibrate(
+        &self,
+        target_hashes_per_second: u64,
+    ) -> Option<(PohCalibrationStatus, Option<PohCalibrationStatus>)> {
+        let num_hashes = self.num_hashes.swap(0, Ordering::Relaxed);
+        let busy_ns = self.busy_ns.swap(0, Ordering::Relaxed);
+        if num_hashes == 0 || busy_ns == 0 {
+            return None;
+        }
+        let hashes_per_second =
+            (u128::from(num_hashes) * 1_000_000_000 / u128::from(busy_ns)) as u64;
+        let rate_percent = (u128::from(hashes_per_second) * 100)
+            .checked_div(u128::from(target_hashes_per_second))
+            .unwrap_or(u128::MAX);
+
+        let mut hashes_per_batch = self.hashes_per_batch();
+        if rate_percent < u128::from(RAISE_BATCH_SIZE_RATE_PERCENT) {
+            hashes_per_batch = hashes_per_batch
+                .saturating_mul(2)
+                .min(self.max_hashes_per_batch);
+        } else if rate_percent > u128::from(LOWER_BATCH_SIZE_RATE_PERCENT) {
+            hashes_per_batch = (hashes_per_batch / 2).max(self.min_hashes_per_batch);
+        }
+        self.hashes_per_batch
+            .store(hashes_per_batch, Ordering::Relaxed);
+
+        let status = PohCalibrationStatus {
+            hashes_per_second,
+            target_hashes_per_second,
+            hashes_per_batch,
+        };
+        let prev_status = self.status.write().unwrap().replace(status);
+        Some((status, prev_status))
+    }
+}
+
+pub struct PohCalibrationService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl PohCalibrationService {
+    pub fn new(
+        calibration: Arc<PohCalibration>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        interval: Duration,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solPohCalibrate".to_string())
+            .spawn(move || {
+                info!("PohCalibrationService has started");
+                Self::run(&calibration, &bank_forks, interval, &exit);
+                info!("PohCalibrationService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn run(
+        calibration: &PohCalibration,
+        bank_forks: &RwLock<BankForks>,
+        interval: Duration,
+        exit: &AtomicBool,
+    ) {
+        let mut last_calibration = Instant::now();
+        while !exit.load(Ordering::Relaxed) {
+            sleep(SLEEP_INTERVAL);
+            if last_calibration.elapsed() < interval {
+                continue;
+            }
+            last_calibration = Instant::now();
+
+            let root_bank = bank_forks.read().unwrap().root_bank();
+            let Some(target_hashes_per_second) = target_hashes_per_second(&root_bank) else {
+                continue;
+            };
+            let Some((status, prev_status)) = calibration.calibrate(target_hashes_per_second)
+            else {
+                continue;
+            };
+            let was_throttled = prev_status.is_some_and(|status| status.is_throttled());
+            if status.is_throttled() && !was_throttled {
+                warn!(
+                    "PoH hash rate dropped to {} hashes per second, below the {} the cluster \
+                     requires, possibly from thermal throttling",
+                    status.hashes_per_second, status.target_hashes_per_second,
+                );
+            } else if !status.is_throttled() && was_throttled {
+                info!(
+                    "PoH hash rate recovered to {} hashes per second",
+                    status.hashes_per_second
+                );
+            }
+            datapoint_info!(
+                "poh-calibration",
+                ("hashes_per_second", status.hashes_per_second, i64),
+                (
+                    "target_hashes_per_second",
+                    status.target_hashes_per_second,
+                    i64
+                ),
+                ("hashes_per_batch", status.hashes_per_batch, i64),
+                ("throttled", status.is_throttled(), bool),
+            );
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibrate() {
+        let calibration = PohCalibration::new(64);
+        // PoH did not hash.
+        assert_eq!(calibration.calibrate(1_000), None);
+        assert_eq!(calibration.status(), None);
+
+        // Close to the target, the batch size is raised up to the maximum.
+        calibration.record_throughput(1_050, 1_000_000_000);
+        let (status, prev_status) = calibration.calibrate(1_000).unwrap();
+        assert_eq!(prev_status, None);
+        assert_eq!(status.hashes_per_second, 1_050);
+        assert_eq!(status.hashes_per_batch, 128);
+        assert!(!status.is_throttled());
+        for _ in 0..8 {
+            calibration.record_throughput(450, 500_000_000);
+            calibration.calibrate(1_000).unwrap();
+        }
+        let status = calibration.status().unwrap();
+        assert_eq!(status.hashes_per_second, 900);
+        assert_eq!(
+            status.hashes_per_batch,
+            64 * MAX_HASHES_PER_BATCH_MULTIPLIER
+        );
+        assert!(status.is_throttled());
+
+        // Comfortably above the target, the batch size is lowered back down to
+        // the configured size.
+        for _ in 0..8 {
+            calibration.record_throughput(2_000, 1_000_000_000);
+            calibration.calibrate(1_000).unwrap();
+        }
+        assert_eq!(calibration.hashes_per_batch(), 64);
+        // Within the margins, the batch size is kept.
+        calibration.record_throughput(1_200, 1_000_000_000);
+        let (status, prev_status) = calibration.calibrate(1_000).unwrap();
+        assert_eq!(status.hashes_per_batch, 64);
+        assert_eq!(prev_status.unwrap().hashes_per_second, 2_000);
+    }
+}
@@ -1,7 +1,10 @@
 //! The `poh_service` module implements a service that records the passing of
 //! "ticks", a measure of time in the PoH stream
 use {
-    crate::poh_recorder::{PohRecorder, Record},
+    crate::{
+        poh_calibration_service::PohCalibration,
+        poh_recorder::{PohRecorder, Record},
+    },
     crossbeam_channel::Receiver,
     log::*,
     solana_entry::poh::Poh,
@@ -60,10 +63,14 @@ impl PohTiming {
             total_send_record_result_us: 0,
         }
     }
-    fn report(&mut self, ticks_per_slot: u64) {
+    fn report(&mut self, ticks_per_slot: u64, calibration: &PohCalibration) {
         if self.last_metric.elapsed().as_millis() > 1000 {
             let elapsed_us = self.last_metric.elapsed().as_micros() as u64;
             let us_per_slot = (elapsed_us * ticks_per_slot) / self.num_ticks;
+            calibration.record_throughput(
+                self.num_hashes,
+                elapsed_us.saturating_sub(self.total_sleep_us) * 1000,
+            );
             datapoint_info!(
                 "poh-service",
                 ("ticks", self.num_ticks as i64, i64),
@@ -102,6 +109,31 @@ impl PohService {
         pinned_cpu_core: usize,
         hashes_per_batch: u64,
         record_receiver: Receiver<Record>,
+    ) -> Self {
+        Self::new_with_calibration(
+            poh_recorder,
+            poh_config,
+            poh_exit,
+            ticks_per_slot,
+            pinned_cpu_core,
+            None,
+            Arc::new(PohCalibration::new(hashes_per_batch)),
+            record_receiver,
+        )
+    }
+
+    /// Creates the service batching as many hashes together as `calibration`
+    /// currently selects. The memory of the tick producer is preferably
+    /// allocated on `numa_node`, if any.
+    pub fn new_with_calibration(
+        poh_recorder: Arc<RwLock<PohRecorder>>,
+        poh_config: &PohConfig,
+        poh_exit: Arc<AtomicBool>,
+        ticks_per_slot: u64,
+        pinned_cpu_core: usize,
+        numa_node: Option<usize>,
+        calibration: Arc<PohCalibration>,
+        record_receiver: Receiver<Record>,
     ) -> Self {
         let poh_config = poh_config.clone();
         let tick_producer = Builder::new()
@@ -130,11 +162,18 @@ impl PohService {
                     if let Some(cores) = core_affinity::get_core_ids() {
                         core_affinity::set_for_current(cores[pinned_cpu_core]);
                     }
+                    if let Some(numa_node) = numa_node {
+                        if let Err(err) =
+                            solana_perf::thread::set_this_thread_preferred_numa_node(numa_node)
+                        {
+                            warn!("failed to prefer numa node {numa_node} for PoH: {err}");
+                        }
+                    }
                     Self::tick_producer(
                         poh_recorder,
                         &poh_exit,
                         ticks_per_slot,
-                        hashes_per_batch,
+                        &calibration,
                         record_receiver,
                         Self::target_ns_per_tick(
                             ticks_per_slot,
@@ -328,7 +367,7 @@ impl PohService {
         poh_recorder: Arc<RwLock<PohRecorder>>,
         poh_exit: &AtomicBool,
         ticks_per_slot: u64,
-        hashes_per_batch: u64,
+        calibration: &PohCalibration,
         record_receiver: Receiver<Record>,
         target_ns_per_tick: u64,
     ) {
@@ -341,7 +380,7 @@ impl PohService {
                 &poh_recorder,
                 &mut timing,
                 &record_receiver,
-                hashes_per_batch,
+                calibration.hashes_per_batch(),
                 &poh,
                 target_ns_per_tick,
             );
@@ -359,7 +398,7 @@ impl PohService {
                 }
                 timing.num_ticks += 1;
 
-                timing.report(ticks_per_slot);
+                timing.report(ticks_per_slot, calibration);
                 if poh_exit.load(Ordering::Relaxed) {
                     break;
                 }
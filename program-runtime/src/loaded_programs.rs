@@ -19,7 +19,7 @@ use {
         thread,
     },
     std::{
-        collections::{hash_map::Entry, HashMap},
+        collections::{hash_map::Entry, HashMap, HashSet},
         fmt::{Debug, Formatter},
         sync::Weak,
     },
@@ -223,12 +223,54 @@ pub struct ProgramCacheStats {
     pub empty_entries: AtomicU64,
     /// water level of loaded entries currently cached
     pub water_level: AtomicU64,
+    /// a program was loaded, verified and compiled
+    pub loads: HashMap<Pubkey, ProgramLoadStats>,
+}
+
+/// Time spent loading a program into [ProgramCache].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProgramLoadStats {
+    pub num_loads: u64,
+    /// Microseconds it took to load, verify and compile the program
+    pub load_us: u64,
+    /// Microseconds of `load_us` it took to `executable.jit_compile`
+    pub jit_compile_us: u64,
+}
+
+impl ProgramLoadStats {
+    fn accumulate(&mut self, other: &Self) {
+        self.num_loads = self.num_loads.saturating_add(other.num_loads);
+        self.load_us = self.load_us.saturating_add(other.load_us);
+        self.jit_compile_us = self.jit_compile_us.saturating_add(other.jit_compile_us);
+    }
 }
 
 impl ProgramCacheStats {
     pub fn reset(&mut self) {
         *self = ProgramCacheStats::default();
     }
+
+    /// Records that loading `program_id` took `load_us`, of which
+    /// `jit_compile_us` compiling it.
+    pub fn record_load(&mut self, program_id: Pubkey, load_us: u64, jit_compile_us: u64) {
+        self.loads
+            .entry(program_id)
+            .or_default()
+            .accumulate(&ProgramLoadStats {
+                num_loads: 1,
+                load_us,
+                jit_compile_us,
+            });
+    }
+
+    /// The load times of all the programs loaded
+    pub fn total_loads(&self) -> ProgramLoadStats {
+        let mut total = ProgramLoadStats::default();
+        for stats in self.loads.values() {
+            total.accumulate(stats);
+        }
+        total
+    }
     pub fn log(&self) {
         let hits = self.hits.load(Ordering::Relaxed);
         let misses = self.misses.load(Ordering::Relaxed);
@@ -267,6 +309,50 @@ impl ProgramCacheStats {
     }
 }
 
+/// Statistics of [ProgramCache] accumulated over all the slots since it was
+/// created.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramCacheCumulativeStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub reloads: u64,
+    pub insertions: u64,
+    pub one_hit_wonders: u64,
+    /// water level as of the latest accumulated slot
+    pub water_level: u64,
+    pub evictions: HashMap<Pubkey, u64>,
+    pub loads: HashMap<Pubkey, ProgramLoadStats>,
+}
+
+impl ProgramCacheCumulativeStats {
+    /// Accumulates the statistics of a slot.
+    pub fn accumulate(&mut self, stats: &ProgramCacheStats) {
+        let add = |total: &mut u64, counter: &AtomicU64| {
+            *total = total.saturating_add(counter.load(Ordering::Relaxed));
+        };
+        add(&mut self.hits, &stats.hits);
+        add(&mut self.misses, &stats.misses);
+        add(&mut self.reloads, &stats.reloads);
+        add(&mut self.insertions, &stats.insertions);
+        add(&mut self.one_hit_wonders, &stats.one_hit_wonders);
+        self.water_level = stats.water_level.load(Ordering::Relaxed);
+        for (program_id, evictions) in &stats.evictions {
+            let total = self.evictions.entry(*program_id).or_default();
+            *total = total.saturating_add(*evictions);
+        }
+        for (program_id, loads) in &stats.loads {
+            self.loads.entry(*program_id).or_default().accumulate(loads);
+        }
+    }
+
+    /// The ratio of the lookups which found the program in the cache, `None`
+    /// without lookups
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits.saturating_add(self.misses);
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
 #[cfg(feature = "metrics")]
 /// Time measurements for loading a single [ProgramCacheEntry].
 #[derive(Debug, Default)]
@@ -638,6 +724,11 @@ pub struct ProgramCache<FG: ForkGraph> {
     pub programs_to_recompile: Vec<(Pubkey, Arc<ProgramCacheEntry>)>,
     /// Statistics counters
     pub stats: ProgramCacheStats,
+    /// Statistics counters accumulated over all the slots
+    pub cumulative_stats: ProgramCacheCumulativeStats,
+    /// Programs which are never evicted, and recompiled first ahead of an
+    /// environment change
+    pinned_programs: HashSet<Pubkey>,
     /// Reference to the block store
     pub fork_graph: Option<Weak<RwLock<FG>>>,
     /// Coordinates TX batches waiting for others to complete their task during cooperative loading
@@ -819,6 +910,8 @@ impl<FG: ForkGraph> ProgramCache<FG> {
             upcoming_environments: None,
             programs_to_recompile: Vec::default(),
             stats: ProgramCacheStats::default(),
+            cumulative_stats: ProgramCacheCumulativeStats::default(),
+            pinned_programs: HashSet::default(),
             fork_graph: None,
             loading_task_waiter: Arc::new(LoadingTaskWaiter::default()),
         }
@@ -828,6 +921,20 @@ impl<FG: ForkGraph> ProgramCache<FG> {
         self.fork_graph = Some(fork_graph);
     }
 
+    /// Pins the given programs in the cache, so that they are never evicted.
+    /// Programs are only loaded when a transaction first uses them.
+    pub fn set_pinned_programs(&mut self, pinned_programs: HashSet<Pubkey>) {
+        self.pinned_programs = pinned_programs;
+    }
+
+    pub fn pinned_programs(&self) -> &HashSet<Pubkey> {
+        &self.pinned_programs
+    }
+
+    pub fn is_pinned(&self, program_id: &Pubkey) -> bool {
+        self.pinned_programs.contains(program_id)
+    }
+
     /// Returns the current environments depending on the given epoch
     pub fn get_environments_for_epoch(&self, epoch: Epoch) -> ProgramRuntimeEnvironments {
         if epoch != self.latest_root_epoch {
@@ -1239,27 +1346,38 @@ impl<FG: ForkGraph> ProgramCache<FG> {
         }
     }
 
+    /// Returns the loaded entries which may be evicted, i.e. which are not
+    /// pinned, along with the number of loaded entries.
+    fn get_eviction_candidates(&self) -> (Vec<(Pubkey, Arc<ProgramCacheEntry>)>, usize) {
+        let mut candidates = self.get_flattened_entries(true, true);
+        let num_loaded = candidates.len();
+        if !self.pinned_programs.is_empty() {
+            candidates.retain(|(id, _program)| !self.pinned_programs.contains(id));
+        }
+        (candidates, num_loaded)
+    }
+
     /// Unloads programs which were used infrequently
     pub fn sort_and_unload(&mut self, shrink_to: PercentageInteger) {
-        let mut sorted_candidates = self.get_flattened_entries(true, true);
+        let (mut sorted_candidates, num_loaded) = self.get_eviction_candidates();
         sorted_candidates
             .sort_by_cached_key(|(_id, program)| program.tx_usage_counter.load(Ordering::Relaxed));
-        let num_to_unload = sorted_candidates
-            .len()
-            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT));
+        let num_to_unload = num_loaded
+            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT))
+            .min(sorted_candidates.len());
         self.unload_program_entries(sorted_candidates.iter().take(num_to_unload));
     }
 
     /// Evicts programs using 2's random selection, choosing the least used program out of the two entries.
     /// The eviction is performed enough number of times to reduce the cache usage to the given percentage.
     pub fn evict_using_2s_random_selection(&mut self, shrink_to: PercentageInteger, now: Slot) {
-        let mut candidates = self.get_flattened_entries(true, true);
+        let (mut candidates, num_loaded) = self.get_eviction_candidates();
         self.stats
             .water_level
-            .store(candidates.len() as u64, Ordering::Relaxed);
-        let num_to_unload = candidates
-            .len()
-            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT));
+            .store(num_loaded as u64, Ordering::Relaxed);
+        let num_to_unload = num_loaded
+            .saturating_sub(shrink_to.apply_to(MAX_LOADED_ENTRY_COUNT))
+            .min(candidates.len());
         fn random_index_and_usage_counter(
             candidates: &[(Pubkey, Arc<ProgramCacheEntry>)],
             now: Slot,
@@ -1372,8 +1490,9 @@ impl<FG: ForkGraph> solana_frozen_abi::abi_example::AbiExample for ProgramCache<
 mod tests {
     use {
         crate::loaded_programs::{
-            BlockRelation, ForkGraph, ProgramCache, ProgramCacheEntry, ProgramCacheEntryOwner,
-            ProgramCacheEntryType, ProgramCacheForTxBatch, ProgramCacheMatchCriteria,
+            BlockRelation, ForkGraph, ProgramCache, ProgramCacheCumulativeStats, ProgramCacheEntry,
+            ProgramCacheEntryOwner, ProgramCacheEntryType, ProgramCacheForTxBatch,
+            ProgramCacheMatchCriteria, ProgramCacheStats, ProgramLoadStats,
             ProgramRuntimeEnvironment, ProgramRuntimeEnvironments, DELAY_VISIBILITY_SLOT_OFFSET,
         },
         assert_matches::assert_matches,
@@ -1382,6 +1501,7 @@ mod tests {
         solana_pubkey::Pubkey,
         solana_sbpf::{elf::Executable, program::BuiltinProgram},
         std::{
+            collections::{HashMap, HashSet},
             fs::File,
             io::Read,
             ops::ControlFlow,
@@ -1764,6 +1884,79 @@ mod tests {
         assert_eq!(num_tombstones, num_tombstones_expected);
     }
 
+    #[test_case(false; "sort_and_unload")]
+    #[test_case(true; "evict_using_2s_random_selection")]
+    fn test_eviction_of_pinned_programs(random_eviction: bool) {
+        let num_loaded_entries = |cache: &ProgramCache<TestForkGraph>, program: &Pubkey| {
+            cache
+                .get_slot_versions_for_tests(program)
+                .iter()
+                .filter(|entry| matches!(entry.program, ProgramCacheEntryType::Loaded(_)))
+                .count()
+        };
+        let mut cache = new_mock_cache::<TestForkGraph>();
+        let pinned_program = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let mut programs = vec![];
+        program_deploy_test_helper(
+            &mut cache,
+            pinned_program,
+            vec![0, 10],
+            vec![1, 1],
+            &mut programs,
+        );
+        program_deploy_test_helper(
+            &mut cache,
+            program,
+            vec![0, 10],
+            vec![100, 100],
+            &mut programs,
+        );
+        cache.set_pinned_programs(HashSet::from([pinned_program]));
+        assert!(cache.is_pinned(&pinned_program));
+
+        if random_eviction {
+            cache.evict_using_2s_random_selection(Percentage::from(0), 21);
+            assert_eq!(cache.stats.water_level.load(Ordering::Relaxed), 4);
+        } else {
+            cache.sort_and_unload(Percentage::from(0));
+        }
+        // Only the entries of the program which is not pinned are evicted.
+        assert_eq!(num_loaded_entries(&cache, &pinned_program), 2);
+        assert_eq!(num_loaded_entries(&cache, &program), 0);
+        assert_eq!(cache.stats.evictions, HashMap::from([(program, 2)]));
+    }
+
+    #[test]
+    fn test_cumulative_stats() {
+        let program = Pubkey::new_unique();
+        let mut cumulative_stats = ProgramCacheCumulativeStats::default();
+        assert_eq!(cumulative_stats.hit_rate(), None);
+        for _ in 0..2 {
+            let mut stats = ProgramCacheStats::default();
+            stats.hits.store(3, Ordering::Relaxed);
+            stats.misses.store(1, Ordering::Relaxed);
+            stats.evictions.insert(program, 1);
+            stats.record_load(program, 100, 60);
+            cumulative_stats.accumulate(&stats);
+        }
+        assert_eq!(cumulative_stats.hits, 6);
+        assert_eq!(cumulative_stats.misses, 2);
+        assert_eq!(cumulative_stats.hit_rate(), Some(0.75));
+        assert_eq!(cumulative_stats.evictions, HashMap::from([(program, 2)]));
+        assert_eq!(
+            cumulative_stats.loads,
+            HashMap::from([(
+                program,
+                ProgramLoadStats {
+                    num_loads: 2,
+                    load_us: 200,
+                    jit_compile_us: 120,
+                }
+            )])
+        );
+    }
+
     #[test]
     fn test_usage_count_of_unloaded_program() {
         let mut cache = new_mock_cache::<TestForkGraph>();
@@ -32,8 +32,10 @@ use {
     solana_sdk::{
         account::{create_account_shared_data_for_test, Account, AccountSharedData},
         account_info::AccountInfo,
-        clock::{Epoch, Slot},
+        clock::{Clock, Epoch, Slot, UnixTimestamp},
         entrypoint::{deserialize, ProgramResult, SUCCESS},
+        epoch_rewards::EpochRewards,
+        epoch_schedule::EpochSchedule,
         fee_calculator::{FeeRateGovernor, DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE},
         genesis_config::{ClusterType, GenesisConfig},
         hash::Hash,
@@ -44,7 +46,7 @@ use {
         rent::Rent,
         signature::{Keypair, Signer},
         stable_layout::stable_instruction::StableInstruction,
-        sysvar::{Sysvar, SysvarId},
+        sysvar::{last_restart_slot::LastRestartSlot, Sysvar, SysvarId},
     },
     solana_timings::ExecuteTimings,
     solana_vote_program::vote_state::{self, VoteState, VoteStateVersions},
@@ -59,7 +61,7 @@ use {
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, Ordering},
-            Arc, RwLock,
+            Arc, LazyLock, RwLock,
         },
         time::{Duration, Instant},
     },
@@ -238,6 +240,41 @@ fn get_sysvar<T: Default + Sysvar + Sized + serde::de::DeserializeOwned + Clone>
     }
 }
 
+/// A syscall of the builtin programs, with the result to return in place of the
+/// one of the runtime
+#[derive(Clone, Debug, PartialEq)]
+pub enum StubbedSyscall {
+    GetClockSysvar(Clock),
+    GetEpochScheduleSysvar(EpochSchedule),
+    GetEpochRewardsSysvar(EpochRewards),
+    GetRentSysvar(Rent),
+    GetLastRestartSlot(LastRestartSlot),
+    GetReturnData(Option<(Pubkey, Vec<u8>)>),
+    GetStackHeight(u64),
+}
+
+/// The syscalls stubbed for each builtin program, by the program id
+static STUBBED_SYSCALLS: LazyLock<RwLock<HashMap<Pubkey, Vec<StubbedSyscall>>>> =
+    LazyLock::new(RwLock::default);
+
+/// Returns the result `select` picks among the syscalls stubbed for the
+/// program being executed, if any
+fn stubbed_syscall<T>(select: impl Fn(&StubbedSyscall) -> Option<T>) -> Option<T> {
+    let invoke_context = get_invoke_context();
+    let transaction_context = &invoke_context.transaction_context;
+    let program_id = transaction_context
+        .get_current_instruction_context()
+        .ok()?
+        .get_last_program_key(transaction_context)
+        .ok()?;
+    STUBBED_SYSCALLS
+        .read()
+        .unwrap()
+        .get(program_id)?
+        .iter()
+        .find_map(select)
+}
+
 struct SyscallStubs {}
 impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
     fn sol_log(&self, message: &str) {
@@ -375,22 +412,44 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
     }
 
     fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let stubbed = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetClockSysvar(clock) => Some(Arc::new(clock.clone())),
+            _ => None,
+        });
         get_sysvar(
-            get_invoke_context().get_sysvar_cache().get_clock(),
+            stubbed.map_or_else(|| get_invoke_context().get_sysvar_cache().get_clock(), Ok),
             var_addr,
         )
     }
 
     fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let stubbed = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetEpochScheduleSysvar(epoch_schedule) => {
+                Some(Arc::new(epoch_schedule.clone()))
+            }
+            _ => None,
+        });
         get_sysvar(
-            get_invoke_context().get_sysvar_cache().get_epoch_schedule(),
+            stubbed.map_or_else(
+                || get_invoke_context().get_sysvar_cache().get_epoch_schedule(),
+                Ok,
+            ),
             var_addr,
         )
     }
 
     fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let stubbed = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetEpochRewardsSysvar(epoch_rewards) => {
+                Some(Arc::new(epoch_rewards.clone()))
+            }
+            _ => None,
+        });
         get_sysvar(
-            get_invoke_context().get_sysvar_cache().get_epoch_rewards(),
+            stubbed.map_or_else(
+                || get_invoke_context().get_sysvar_cache().get_epoch_rewards(),
+                Ok,
+            ),
             var_addr,
         )
     }
@@ -401,19 +460,43 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
     }
 
     fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
-        get_sysvar(get_invoke_context().get_sysvar_cache().get_rent(), var_addr)
+        let stubbed = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetRentSysvar(rent) => Some(Arc::new(rent.clone())),
+            _ => None,
+        });
+        get_sysvar(
+            stubbed.map_or_else(|| get_invoke_context().get_sysvar_cache().get_rent(), Ok),
+            var_addr,
+        )
     }
 
     fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        let stubbed = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetLastRestartSlot(last_restart_slot) => {
+                Some(Arc::new(last_restart_slot.clone()))
+            }
+            _ => None,
+        });
         get_sysvar(
-            get_invoke_context()
-                .get_sysvar_cache()
-                .get_last_restart_slot(),
+            stubbed.map_or_else(
+                || {
+                    get_invoke_context()
+                        .get_sysvar_cache()
+                        .get_last_restart_slot()
+                },
+                Ok,
+            ),
             var_addr,
         )
     }
 
     fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        if let Some(return_data) = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetReturnData(return_data) => Some(return_data.clone()),
+            _ => None,
+        }) {
+            return return_data;
+        }
         let (program_id, data) = get_invoke_context().transaction_context.get_return_data();
         Some((*program_id, data.to_vec()))
     }
@@ -433,6 +516,12 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
     }
 
     fn sol_get_stack_height(&self) -> u64 {
+        if let Some(stack_height) = stubbed_syscall(|stub| match stub {
+            StubbedSyscall::GetStackHeight(stack_height) => Some(*stack_height),
+            _ => None,
+        }) {
+            return stack_height;
+        }
         let invoke_context = get_invoke_context();
         invoke_context.get_stack_height().try_into().unwrap()
     }
@@ -1135,6 +1224,57 @@ impl ProgramTestContext {
         bank.set_sysvar_for_tests(sysvar);
     }
 
+    /// Move the clock of the working bank forward by `duration` without
+    /// changing its slot, for time dependent program logic to be tested
+    /// without warping slots
+    pub fn warp_clock_by(&self, duration: Duration) {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let mut clock = bank.clock();
+        clock.unix_timestamp = clock
+            .unix_timestamp
+            .saturating_add(UnixTimestamp::try_from(duration.as_secs()).unwrap_or(i64::MAX));
+        bank.set_sysvar_for_tests(&clock);
+    }
+
+    /// Set the unix timestamp of the clock of the working bank
+    pub fn set_clock_unix_timestamp(&self, unix_timestamp: UnixTimestamp) {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let mut clock = bank.clock();
+        clock.unix_timestamp = unix_timestamp;
+        bank.set_sysvar_for_tests(&clock);
+    }
+
+    /// Overwrite the epoch schedule sysvar of the working bank.
+    ///
+    /// Only the sysvar the programs read is changed, the bank keeps deriving
+    /// its epochs from the epoch schedule of the genesis config.
+    pub fn set_epoch_schedule(&self, epoch_schedule: &EpochSchedule) {
+        self.set_sysvar(epoch_schedule);
+    }
+
+    /// Return `syscall`'s result to the builtin program `program_id` in place of
+    /// the one of the runtime, replacing any result stubbed before for the same
+    /// syscall.
+    ///
+    /// Only the builtin programs added with a `processor!` go through the
+    /// stubs, programs loaded from shared objects are left as is. The stubs
+    /// are process wide and are kept until cleared with
+    /// [`Self::clear_syscall_stubs`].
+    pub fn stub_syscall(&self, program_id: Pubkey, syscall: StubbedSyscall) {
+        let mut stubbed_syscalls = STUBBED_SYSCALLS.write().unwrap();
+        let program_stubs = stubbed_syscalls.entry(program_id).or_default();
+        program_stubs
+            .retain(|stub| std::mem::discriminant(stub) != std::mem::discriminant(&syscall));
+        program_stubs.push(syscall);
+    }
+
+    /// Stop stubbing the syscalls of the builtin program `program_id`
+    pub fn clear_syscall_stubs(&self, program_id: &Pubkey) {
+        STUBBED_SYSCALLS.write().unwrap().remove(program_id);
+    }
+
     /// Force the working bank ahead to a new slot
     pub fn warp_to_slot(&mut self, warp_slot: Slot) -> Result<(), ProgramTestError> {
         let mut bank_forks = self.bank_forks.write().unwrap();
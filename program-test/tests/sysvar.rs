@@ -1,10 +1,11 @@
 use {
-    solana_program_test::{processor, ProgramTest},
+    solana_program_test::{processor, ProgramTest, ProgramTestContext, StubbedSyscall},
     solana_sdk::{
         account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
         epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule, instruction::Instruction, msg,
         pubkey::Pubkey, rent::Rent, signature::Signer, sysvar::Sysvar, transaction::Transaction,
     },
+    std::time::Duration,
 };
 
 // Process instruction to invoke into another program
@@ -128,3 +129,87 @@ async fn get_epoch_rewards_sysvar() {
         .await
         .unwrap();
 }
+
+// Checks the clock and the epoch schedule the program observes against the
+// unix timestamp and the slots per epoch of the input
+fn clock_checker_process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    msg!("clock_checker");
+
+    let unix_timestamp = i64::from_le_bytes(input[..8].try_into().unwrap());
+    let clock = Clock::get()?;
+    assert_eq!(clock.unix_timestamp, unix_timestamp);
+
+    if let Some(slots_per_epoch) = input.get(8..16) {
+        let epoch_schedule = EpochSchedule::get()?;
+        assert_eq!(
+            epoch_schedule.slots_per_epoch,
+            u64::from_le_bytes(slots_per_epoch.try_into().unwrap())
+        );
+    }
+
+    Ok(())
+}
+
+async fn check_clock(context: &mut ProgramTestContext, program_id: Pubkey, input: Vec<u8>) {
+    let instructions = vec![Instruction::new_with_bytes(program_id, &input, vec![])];
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn warp_clock_and_set_epoch_schedule() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "clock_checker",
+        program_id,
+        processor!(clock_checker_process_instruction),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    context.set_clock_unix_timestamp(1_000_000);
+    context.warp_clock_by(Duration::from_secs(3_600));
+    context.set_epoch_schedule(&EpochSchedule::custom(64, 64, false));
+
+    let input = [1_003_600i64.to_le_bytes(), 64u64.to_le_bytes()].concat();
+    check_clock(&mut context, program_id, input).await;
+}
+
+#[tokio::test]
+async fn stub_syscall() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "clock_checker",
+        program_id,
+        processor!(clock_checker_process_instruction),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    context.set_clock_unix_timestamp(1_000);
+    context.stub_syscall(
+        program_id,
+        StubbedSyscall::GetClockSysvar(Clock {
+            unix_timestamp: 42,
+            ..Clock::default()
+        }),
+    );
+    check_clock(&mut context, program_id, 42i64.to_le_bytes().to_vec()).await;
+
+    // The program observes the clock of the bank again once the stubs are
+    // cleared.
+    context.clear_syscall_stubs(&program_id);
+    check_clock(&mut context, program_id, 1_000i64.to_le_bytes().to_vec()).await;
+}
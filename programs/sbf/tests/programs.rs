@@ -102,6 +102,7 @@ fn load_execute_and_commit_transaction(bank: &Bank, tx: Transaction) -> Transact
                 enable_cpi_recording: true,
                 enable_log_recording: true,
                 enable_return_data_recording: false,
+                enable_instruction_units_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -5072,6 +5073,7 @@ fn test_function_call_args() {
                 enable_cpi_recording: false,
                 enable_log_recording: false,
                 enable_return_data_recording: true,
+                enable_instruction_units_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -27,6 +27,10 @@ pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
 pub const JSON_RPC_SERVER_ERROR_EPOCH_REWARDS_PERIOD_ACTIVE: i64 = -32017;
 pub const JSON_RPC_SERVER_ERROR_SLOT_NOT_EPOCH_BOUNDARY: i64 = -32018;
 pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_UNREACHABLE: i64 = -32019;
+pub const JSON_RPC_SERVER_ERROR_COMMISSION_CHANGE_REJECTED: i64 = -32020;
+pub const JSON_RPC_SERVER_ERROR_PROGRAM_SIGNATURE_INDEX_NOT_AVAILABLE: i64 = -32021;
+pub const JSON_RPC_SERVER_ERROR_METHOD_RATE_LIMITED: i64 = -32022;
+pub const JSON_RPC_SERVER_ERROR_TRANSACTION_STATUS_FILTERED: i64 = -32023;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -78,6 +82,14 @@ pub enum RpcCustomError {
     SlotNotEpochBoundary { slot: Slot },
     #[error("LongTermStorageUnreachable")]
     LongTermStorageUnreachable,
+    #[error("CommissionChangeRejected")]
+    CommissionChangeRejected { message: String },
+    #[error("ProgramSignatureIndexNotAvailable")]
+    ProgramSignatureIndexNotAvailable,
+    #[error("MethodRateLimited")]
+    MethodRateLimited { method: String },
+    #[error("TransactionStatusFiltered")]
+    TransactionStatusFiltered { slot: Slot },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -252,6 +264,31 @@ impl From<RpcCustomError> for Error {
                 message: "Failed to query long-term storage; please try again".to_string(),
                 data: None,
             },
+            RpcCustomError::CommissionChangeRejected { message } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_COMMISSION_CHANGE_REJECTED),
+                message: format!("Commission change rejected: {message}"),
+                data: None,
+            },
+            RpcCustomError::ProgramSignatureIndexNotAvailable => Self {
+                code: ErrorCode::ServerError(
+                    JSON_RPC_SERVER_ERROR_PROGRAM_SIGNATURE_INDEX_NOT_AVAILABLE,
+                ),
+                message: "Program signature index is not available from this node".to_string(),
+                data: None,
+            },
+            RpcCustomError::MethodRateLimited { method } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_METHOD_RATE_LIMITED),
+                message: format!("Too many {method} requests, retry later"),
+                data: None,
+            },
+            RpcCustomError::TransactionStatusFiltered { slot } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_TRANSACTION_STATUS_FILTERED),
+                message: format!(
+                    "Block {slot} cannot be served, this node does not store the statuses of some \
+                     of its transactions"
+                ),
+                data: None,
+            },
         }
     }
 }
@@ -1,15 +1,25 @@
 use crate::client_error;
 pub use solana_rpc_client_types::response::{
     OptionalContext, ProcessedSignatureResult, ReceivedSignatureResult, Response,
-    RpcAccountBalance, RpcApiVersion, RpcBlockCommitment, RpcBlockProduction,
+    RpcAccountBalance, RpcAccountCongestion, RpcAccountDataChange, RpcAccountDelta,
+    RpcAccountGrowth, RpcApiVersion, RpcBlockCommitment, RpcBlockProduction,
     RpcBlockProductionRange, RpcBlockUpdate, RpcBlockUpdateError, RpcBlockhash,
-    RpcBlockhashFeeCalculator, RpcConfirmedTransactionStatusWithSignature, RpcContactInfo,
-    RpcFeeCalculator, RpcFeeRateGovernor, RpcIdentity, RpcInflationGovernor, RpcInflationRate,
-    RpcInflationReward, RpcKeyedAccount, RpcLeaderSchedule, RpcLogsResponse, RpcPerfSample,
-    RpcPrioritizationFee, RpcResponseContext, RpcSignatureConfirmation, RpcSignatureResult,
-    RpcSimulateTransactionResult, RpcSnapshotSlotInfo, RpcStorageTurn, RpcSupply,
-    RpcTokenAccountBalance, RpcVersionInfo, RpcVote, RpcVoteAccountInfo, RpcVoteAccountStatus,
-    SlotInfo, SlotTransactionStats, SlotUpdate, StakeActivationState,
+    RpcBlockhashFeeCalculator, RpcBufferedTransaction, RpcBufferedTransactions,
+    RpcConfirmationLatency, RpcConfirmationProof, RpcConfirmationVote,
+    RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcDuplicateShredProof,
+    RpcDurableNonce, RpcEpochAccountGrowth, RpcFeeCalculator, RpcFeeRateGovernor,
+    RpcHealthCondition, RpcHealthConditionKind, RpcHealthDetail, RpcIdentity, RpcInflationGovernor,
+    RpcInflationRate, RpcInflationReward, RpcKeyedAccount, RpcKeyedAccountsPage, RpcLeaderSchedule,
+    RpcLeaderScheduleForecast, RpcLeaderShredLatency, RpcLeaderSlot, RpcLeaderSlotSkippedReason,
+    RpcLeaderSlotStats, RpcLogsResponse, RpcPerfSample, RpcPrioritizationFee,
+    RpcPrioritizationFeePercentile, RpcPrioritizationFeeUpdate, RpcProgramAccountGrowth,
+    RpcProgramAccounts, RpcProgramCacheProgramStats, RpcProgramCacheStats, RpcResponseContext,
+    RpcShredLatency, RpcSignatureConfirmation, RpcSignatureResult, RpcSimulateTransactionResult,
+    RpcSkippedSlotCause, RpcSkippedSlotReason, RpcSlotAccountCongestion, RpcSlotAccountFilter,
+    RpcSlotConfirmationLatency, RpcSlotShredLatency, RpcSnapshotSlotInfo, RpcStorageTurn,
+    RpcSupply, RpcTokenAccountBalance, RpcVersionInfo, RpcVote, RpcVoteAccountDetailed,
+    RpcVoteAccountInfo, RpcVoteAccountStatus, RpcVoteLockout, RpcVoteTimestamp, SlotInfo,
+    SlotTransactionStats, SlotUpdate, StakeActivationState,
 };
 
 pub type RpcResult<T> = client_error::Result<Response<T>>;
@@ -62,6 +62,27 @@ pub struct RpcLeaderScheduleConfig {
     pub commitment: Option<CommitmentConfig>,
 }
 
+/// A hypothetical change of the stake delegated to a vote account
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStakeChange {
+    pub vote_pubkey: String, // validator vote address, as a base-58 encoded string
+    /// Lamports delegated to the vote account, or undelegated if negative
+    pub lamports: i64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderScheduleForecastConfig {
+    /// Defaults to the first epoch whose leader schedule is not fixed yet
+    pub epoch: Option<Epoch>,
+    #[serde(default)]
+    pub stake_changes: Vec<RpcStakeChange>,
+    pub identity: Option<String>, // validator identity, as a base-58 encoded string
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBlockProductionConfigRange {
@@ -88,6 +109,15 @@ pub struct RpcGetVoteAccountsConfig {
     pub delinquent_slot_distance: Option<u64>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcGetVoteAccountsDetailedConfig {
+    pub vote_pubkeys: Option<Vec<String>>, // vote addresses, as base-58 encoded strings
+    pub node_pubkeys: Option<Vec<String>>, // validator identities, as base-58 encoded strings
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RpcLeaderScheduleConfigWrapper {
@@ -138,12 +168,24 @@ pub struct RpcEpochConfig {
     pub min_context_slot: Option<Slot>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInflationRewardHistoryConfig {
+    /// Last epoch of the range, inclusive; the last completed epoch if not set
+    pub end_epoch: Option<Epoch>,
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub min_context_slot: Option<Slot>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum RpcAccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    DataSize,
+    NonceAuthority,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -164,6 +206,11 @@ pub struct RpcProgramAccountsConfig {
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
     pub sort_results: Option<bool>,
+    /// The max # of accounts returned, which paginates the accounts in pubkey order. Up to
+    /// `MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE`.
+    pub page_size: Option<usize>,
+    /// The `next_cursor` of the previous page, to get the next one
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -196,6 +243,32 @@ pub struct RpcSignatureSubscribeConfig {
     pub enable_received_notification: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountDeltaSubscribeConfig {
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    /// Changed byte ranges separated by fewer unchanged bytes than this are sent as one range
+    pub merge_gap: Option<usize>,
+    /// The account data is sent in full instead of as changes when more than this percentage of
+    /// it changed
+    pub full_data_threshold_percent: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPrioritizationFeeSubscribeConfig {
+    /// Percentiles, from 0 to 100, of the compute-unit prices to notify
+    pub percentiles: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNonceSubscribeConfig {
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RpcBlockSubscribeFilter {
@@ -44,6 +44,7 @@ pub enum RpcRequest {
     GetSignaturesForAddress,
     GetSignatureStatuses,
     GetSlot,
+    GetSlotAccountFilter,
     GetSlotLeader,
     GetSlotLeaders,
     GetStorageTurn,
@@ -61,6 +62,7 @@ pub enum RpcRequest {
     GetTransactionCount,
     GetVersion,
     GetVoteAccounts,
+    GetVoteAccountsDetailed,
     IsBlockhashValid,
     MinimumLedgerSlot,
     RegisterNode,
@@ -109,6 +111,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetSignaturesForAddress => "getSignaturesForAddress",
             RpcRequest::GetSignatureStatuses => "getSignatureStatuses",
             RpcRequest::GetSlot => "getSlot",
+            RpcRequest::GetSlotAccountFilter => "getSlotAccountFilter",
             RpcRequest::GetSlotLeader => "getSlotLeader",
             RpcRequest::GetSlotLeaders => "getSlotLeaders",
             RpcRequest::GetStakeMinimumDelegation => "getStakeMinimumDelegation",
@@ -126,6 +129,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetTransactionCount => "getTransactionCount",
             RpcRequest::GetVersion => "getVersion",
             RpcRequest::GetVoteAccounts => "getVoteAccounts",
+            RpcRequest::GetVoteAccountsDetailed => "getVoteAccountsDetailed",
             RpcRequest::IsBlockhashValid => "isBlockhashValid",
             RpcRequest::MinimumLedgerSlot => "minimumLedgerSlot",
             RpcRequest::RegisterNode => "registerNode",
@@ -144,10 +148,17 @@ pub const MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS: usize = 256;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE: u64 = 10_000;
 pub const MAX_GET_CONFIRMED_BLOCKS_RANGE: u64 = 500_000;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT: usize = 1_000;
+pub const MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS: u64 = 50;
 pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
 pub const NUM_LARGEST_ACCOUNTS: usize = 20;
 pub const MAX_GET_PROGRAM_ACCOUNT_FILTERS: usize = 4;
+pub const MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE: usize = 10_000;
 pub const MAX_GET_SLOT_LEADERS: usize = 5000;
+pub const MAX_GET_RECENT_LEADER_SLOTS: usize = 1_000;
+pub const MAX_GET_PROGRAM_CACHE_STATS_PROGRAMS: usize = 100;
+pub const MAX_SIMULATE_TRANSACTIONS: usize = 16;
+pub const MAX_GET_VOTE_ACCOUNTS_DETAILED_PUBKEYS: usize = 100;
+pub const MAX_LEADER_SCHEDULE_FORECAST_STAKE_CHANGES: usize = 1_000;
 
 // Limit the length of the `epoch_credits` array for each validator in a `get_vote_accounts`
 // response
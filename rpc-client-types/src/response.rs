@@ -163,6 +163,59 @@ pub struct RpcKeyedAccount {
     pub account: UiAccount,
 }
 
+/// An account, with its data as the byte ranges which changed since the previous notification
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountDelta {
+    pub lamports: u64,
+    pub owner: String,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    /// Length of the account data, which is truncated to it after the changes are applied
+    pub space: u64,
+    /// True if `changes` holds all the account data, rather than what changed since the previous
+    /// notification
+    pub full: bool,
+    pub changes: Vec<RpcAccountDataChange>,
+}
+
+/// A byte range of account data
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountDataChange {
+    pub offset: usize,
+    pub length: usize,
+    /// The bytes of the range, as base-64 encoded string
+    pub bytes: String,
+}
+
+/// The durable nonce of a nonce account, notified when it is advanced
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDurableNonce {
+    pub authority: String,
+    /// The durable nonce, as base-58 encoded string
+    pub durable_nonce: String,
+    pub lamports_per_signature: u64,
+}
+
+/// A page of the accounts of a program, in pubkey order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcKeyedAccountsPage {
+    pub accounts: Vec<RpcKeyedAccount>,
+    /// The cursor of the next page, if there are more accounts
+    pub next_cursor: Option<String>,
+}
+
+/// The accounts of a program, paginated if a page size or a cursor is requested
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RpcProgramAccounts {
+    Page(RpcKeyedAccountsPage),
+    All(Vec<RpcKeyedAccount>),
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SlotInfo {
     pub slot: Slot,
@@ -351,6 +404,129 @@ pub struct RpcVote {
     pub signature: String,
 }
 
+/// A duplicate shred proof observed in gossip for the first time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDuplicateShredProof {
+    pub slot: Slot,
+    /// The leader of the slot, which signed both shreds, as base-58 encoded
+    /// string
+    pub leader: String,
+    /// The node which published the proof, as base-58 encoded string
+    pub reporter: String,
+    /// The conflicting shreds, as base-64 encoded strings
+    pub shred1: String,
+    pub shred2: String,
+}
+
+/// The votes through which the node observed the optimistic confirmation of a slot, which
+/// together hold a supermajority of the epoch stake
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfirmationProof {
+    pub slot: Slot,
+    /// The bank hash voted for, as base-58 encoded string
+    pub bank_hash: String,
+    /// The total stake of the epoch of the slot
+    pub total_stake: u64,
+    /// The stake of the votes of the proof
+    pub confirmed_stake: u64,
+    pub votes: Vec<RpcConfirmationVote>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfirmationVote {
+    /// The vote account, as base-58 encoded string
+    pub vote_account: String,
+    /// The stake of the vote account in the epoch of the slot
+    pub stake: u64,
+    /// The last slot voted for by the vote transaction, which is a descendant of the slot if the
+    /// vote counts towards the slot through the tower of the voter
+    pub voted_slot: Slot,
+    /// The signature of the vote transaction, as base-58 encoded string
+    pub signature: String,
+    /// The vote transaction, as base-64 encoded string, if observed in gossip. The votes
+    /// observed only in the replayed blocks may be fetched with `getTransaction`
+    pub transaction: Option<String>,
+}
+
+/// A sample of the non-vote transactions buffered by the banking stage of the node
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBufferedTransactions {
+    /// The working slot of the node when sampled
+    pub slot: Slot,
+    /// The number of transactions buffered when sampled, including the ones being processed
+    pub num_buffered: u64,
+    pub transactions: Vec<RpcBufferedTransaction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBufferedTransaction {
+    /// The signature of the transaction, as base-58 encoded string
+    pub signature: String,
+    /// The priority the transaction is scheduled by, which is the reward of the leader per
+    /// compute unit of the transaction
+    pub priority: u64,
+    /// The estimated cost of the transaction, in compute units
+    pub cost: u64,
+    /// The slots since the transaction was received
+    pub slots_waited: u64,
+    /// The times the transaction was scheduled and returned to the buffer, e.g. because of an
+    /// account lock conflict or a cost limit
+    pub num_retries: u32,
+}
+
+/// The growth of the accounts of the node, as of the latest sample of its root
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountGrowth {
+    /// The root slot when sampled
+    pub slot: Slot,
+    /// The number of accounts in the accounts index, including the ones with zero lamports not
+    /// yet cleaned
+    pub num_accounts: u64,
+    /// The size of the data of the accounts, in bytes
+    pub accounts_data_size: u64,
+    /// The lamports for all the accounts to be rent exempt, estimated from `num_accounts` and
+    /// `accountsDataSize`
+    pub rent_exempt_reserve: u64,
+    /// The duration of the latest sample period, in seconds
+    pub sample_period_secs: u64,
+    /// The accounts created and deleted in the latest sample period
+    pub num_accounts_created: u64,
+    pub num_accounts_deleted: u64,
+    /// The growth of the accounts in the recent epochs, the latest first
+    pub epochs: Vec<RpcEpochAccountGrowth>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochAccountGrowth {
+    pub epoch: Epoch,
+    /// The latest root slot sampled in the epoch
+    pub slot: Slot,
+    /// The accounts created and deleted, including the ones of the forks which were not rooted
+    pub num_accounts_created: u64,
+    pub num_accounts_deleted: u64,
+    /// The change of the size of the data of the accounts, in bytes
+    pub accounts_data_size_delta: i64,
+    /// The programs creating the largest accounts, the largest first
+    pub top_programs: Vec<RpcProgramAccountGrowth>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramAccountGrowth {
+    /// The owner of the accounts created, as base-58 encoded string
+    pub program_id: String,
+    pub num_accounts_created: u64,
+    /// The data size of the accounts as created, in bytes
+    pub data_size_created: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcVoteAccountStatus {
@@ -358,6 +534,59 @@ pub struct RpcVoteAccountStatus {
     pub delinquent: Vec<RpcVoteAccountInfo>,
 }
 
+/// The vote state of a vote account, as parsed from its current version
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteAccountDetailed {
+    /// Vote account address, as base-58 encoded string
+    pub vote_pubkey: String,
+
+    /// The validator identity, as base-58 encoded string
+    pub node_pubkey: String,
+
+    /// The current stake, in lamports, delegated to this vote account
+    pub activated_stake: u64,
+
+    /// An 8-bit integer used as a fraction (commission/MAX_U8) for rewards payout
+    pub commission: u8,
+
+    /// Whether this account is staked for the current epoch
+    pub epoch_vote_account: bool,
+
+    /// The authorized voter for the current epoch, as base-58 encoded string
+    pub authorized_voter: Option<String>,
+
+    /// The votes of the tower of this vote account, from the oldest to the most recent
+    pub votes: Vec<RpcVoteLockout>,
+
+    /// The root slot of the tower of this vote account, if it has one
+    pub root_slot: Option<Slot>,
+
+    /// The full history of earned credits kept by this vote account
+    ///   each tuple is (Epoch, credits, prev_credits)
+    pub epoch_credits: Vec<(Epoch, u64, u64)>,
+
+    /// The credits earned by this vote account over its lifetime
+    pub credits: u64,
+
+    /// The most recent timestamp voted by this vote account
+    pub last_timestamp: RpcVoteTimestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteLockout {
+    pub slot: Slot,
+    pub confirmation_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteTimestamp {
+    pub slot: Slot,
+    pub timestamp: UnixTimestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcVoteAccountInfo {
@@ -527,6 +756,284 @@ pub struct RpcPrioritizationFee {
     pub prioritization_fee: u64,
 }
 
+/// The compute-unit prices paid in a block by the transactions which write lock any of the
+/// subscribed accounts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPrioritizationFeeUpdate {
+    pub slot: Slot,
+    pub num_transactions: usize,
+    /// The compute-unit price at each subscribed percentile, empty if there are no transactions
+    pub percentiles: Vec<RpcPrioritizationFeePercentile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPrioritizationFeePercentile {
+    pub percentile: u8,
+    /// In micro-lamports per compute unit
+    pub compute_unit_price: u64,
+}
+
+/// A bloom filter of the accounts written in a slot.
+///
+/// An account with address `pubkey` may have been written in the slot only if,
+/// for every `k` in `0..num_hashes`, bit `fnv1a_64(pubkey, k) % num_bits` is
+/// set, where `fnv1a_64(pubkey, k)` is the 64-bit FNV-1a hash of the address
+/// bytes using `k` as the offset basis.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotAccountFilter {
+    pub slot: Slot,
+    /// Number of distinct accounts written in the slot
+    pub num_accounts: u64,
+    pub num_bits: u64,
+    pub num_hashes: u64,
+    /// The bits of the filter as base64 encoded little-endian 64-bit words.
+    /// Bit `i` is bit `i % 64` of word `i / 64`.
+    pub bits: String,
+}
+
+/// Distributions of the arrival times of turbine shreds received by the node,
+/// relative to the estimated start of their slots.
+///
+/// `counts[i]` is the number of shreds which arrived within
+/// `bucketUpperBoundsMs[i]` milliseconds of the start of the slot, and after
+/// the previous bound. The last count is of the shreds which arrived later
+/// than the last bound.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcShredLatency {
+    pub bucket_upper_bounds_ms: Vec<u64>,
+    /// The most recent slots, in ascending order
+    pub slots: Vec<RpcSlotShredLatency>,
+    /// The slots above summed by leader, in descending order of the mean
+    /// latency
+    pub leaders: Vec<RpcLeaderShredLatency>,
+}
+
+/// A recent leader slot of the node
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderSlot {
+    pub slot: Slot,
+    pub rooted: bool,
+    /// Why the slot is skipped, if it is older than the root and not rooted
+    pub skipped_reason: Option<RpcLeaderSlotSkippedReason>,
+    /// What the banking stage did in the slot, if it had a bank for the slot
+    pub stats: Option<RpcLeaderSlotStats>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcLeaderSlotSkippedReason {
+    /// The node did not complete a block for the slot
+    NotProduced,
+    /// The node completed a block for the slot, but the cluster rooted another fork
+    NotRooted,
+}
+
+/// The probable cause of a skipped leader slot of the node, from what the node observed while it
+/// was the leader
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSkippedSlotReason {
+    pub slot: Slot,
+    pub cause: RpcSkippedSlotCause,
+    /// The slot the block of the node was built on, if the node started a block
+    pub parent_slot: Option<Slot>,
+    /// How long after the start of the slot in PoH the node started its block
+    pub start_delay_ms: Option<u64>,
+    /// How long the node took to complete its block, if it completed it
+    pub completion_time_ms: Option<u64>,
+    /// Number of transactions in the block, if the node completed it
+    pub num_transactions: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcSkippedSlotCause {
+    /// The node did not start a block for the slot, e.g. because its previous leader slots were
+    /// not propagated
+    NotStarted,
+    /// The node built its block late, or around a block of the previous leader which arrived
+    /// late and which the cluster rooted instead
+    LatePreviousBlock,
+    /// The node took much longer than a slot to complete its block, or did not complete it
+    PohStall,
+    /// The block has no transactions, as the banking stage had none buffered to execute
+    NoBankingBuffer,
+    /// The node completed its block in time, but the cluster rooted another fork
+    ForkedAway,
+}
+
+/// The most contended write locked accounts of a recent slot
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotAccountCongestion {
+    pub slot: Slot,
+    /// The compute units the transactions of a block may use per write locked account
+    pub account_cost_limit: u64,
+    pub accounts: Vec<RpcAccountCongestion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountCongestion {
+    pub account: String,
+    /// Compute units of the transactions in the block which write lock the account
+    pub consumed_units: u64,
+    /// Compute units of the transactions the node did not pack into its block, as they would
+    /// have exceeded the account cost limit. Only known for the leader slots of the node.
+    pub rejected_units: u64,
+}
+
+/// The statistics of the program cache of the node, accumulated since it started
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// The ratio of the lookups which found the program in the cache
+    pub hit_rate: Option<f64>,
+    pub reloads: u64,
+    pub insertions: u64,
+    pub evictions: u64,
+    /// Programs evicted after they were used only once
+    pub one_hit_wonders: u64,
+    /// The number of compiled programs in the cache
+    pub water_level: u64,
+    pub pinned_programs: Vec<String>,
+    /// The programs which took the longest to load, in decreasing order of load time
+    pub programs: Vec<RpcProgramCacheProgramStats>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramCacheProgramStats {
+    pub program_id: String,
+    pub loads: u64,
+    /// Microseconds it took to load, verify and compile the program
+    pub load_us: u64,
+    /// Microseconds of `load_us` it took to compile the program
+    pub jit_compile_us: u64,
+    pub evictions: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderSlotStats {
+    pub attempted_transactions: u64,
+    pub committed_transactions: u64,
+    pub committed_vote_transactions: u64,
+    pub committed_non_vote_transactions: u64,
+    pub compute_units_used: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotShredLatency {
+    pub slot: Slot,
+    pub leader: Option<String>,
+    pub num_shreds: u64,
+    pub mean_latency_ms: u64,
+    pub counts: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderShredLatency {
+    pub leader: String,
+    pub num_slots: u64,
+    pub num_shreds: u64,
+    pub mean_latency_ms: u64,
+    pub counts: Vec<u64>,
+}
+
+/// The times it took recent slots to be confirmed, from the arrival of their
+/// first shred at the node
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfirmationLatency {
+    /// The most recent slots, in ascending order
+    pub slots: Vec<RpcSlotConfirmationLatency>,
+    /// Mean of the optimistic confirmation latencies of the slots above
+    pub mean_optimistic_confirmation_latency_ms: Option<u64>,
+    /// Mean of the finalization latencies of the slots above
+    pub mean_finalization_latency_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotConfirmationLatency {
+    pub slot: Slot,
+    /// Estimated arrival of the first shred of the slot, in milliseconds since
+    /// the UNIX epoch, if the node received shreds of the slot
+    pub first_shred_timestamp: Option<u64>,
+    /// Milliseconds from the first shred to the optimistic confirmation of
+    /// the slot, if it was observed
+    pub optimistic_confirmation_latency_ms: Option<u64>,
+    /// Milliseconds from the first shred to the rooting of the slot, if it
+    /// was observed
+    pub finalization_latency_ms: Option<u64>,
+}
+
+/// The leader schedule an epoch would have if its stakes were the current
+/// stakes, changed by the requested stake changes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderScheduleForecast {
+    pub epoch: Epoch,
+    pub first_slot: Slot,
+    /// Lamports staked to the vote accounts the schedule was computed from
+    pub total_stake: u64,
+    /// The slot indices of each leader, relative to the first slot
+    pub schedule: RpcLeaderSchedule,
+}
+
+/// The conditions the health of the node is made of, the node is healthy if
+/// all of them are
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHealthDetail {
+    pub healthy: bool,
+    /// The conditions which could be checked, the others are left out, e.g.
+    /// the votes of a node without vote account
+    pub conditions: Vec<RpcHealthCondition>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHealthCondition {
+    pub kind: RpcHealthConditionKind,
+    pub healthy: bool,
+    /// The measured value, in the unit of the threshold
+    pub value: u64,
+    /// The value above which the condition is unhealthy
+    pub threshold: u64,
+    /// A description of the state of the condition
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcHealthConditionKind {
+    /// Slots the latest optimistically confirmed slot replayed by the node is
+    /// behind the one of the cluster
+    BehindOnSlots,
+    /// Slots since the latest vote of the node landed, in the optimistically
+    /// confirmed bank
+    NoRecentVotes,
+    /// Slots the latest snapshot archive is behind the root
+    SnapshotStale,
+    /// Whether the accounts verification of the startup is still running, as
+    /// 0 or 1
+    AccountsDbCatchingUp,
+    /// Slots above the root the node has not received all the shreds of,
+    /// which it repairs
+    RepairStorm,
+}
+
 #[cfg(test)]
 pub mod tests {
 
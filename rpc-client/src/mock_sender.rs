@@ -21,8 +21,8 @@ use {
             RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcIdentity,
             RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
             RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus,
+            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountDetailed,
+            RpcVoteAccountInfo, RpcVoteAccountStatus, RpcVoteLockout, RpcVoteTimestamp,
         },
     },
     solana_signature::Signature,
@@ -143,6 +143,7 @@ impl RpcSender for MockSender {
                         confirmations: None,
                         err,
                         confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                        provenance: None,
                     })
                 };
                 let statuses: Vec<Option<TransactionStatus>> = params.as_array().unwrap()[0]
@@ -287,6 +288,25 @@ impl RpcSender for MockSender {
                     }],
                 })
             }
+            "getVoteAccountsDetailed" => json!(vec![RpcVoteAccountDetailed {
+                vote_pubkey: PUBKEY.to_string(),
+                node_pubkey: PUBKEY.to_string(),
+                activated_stake: 0,
+                commission: 0,
+                epoch_vote_account: false,
+                authorized_voter: Some(PUBKEY.to_string()),
+                votes: vec![RpcVoteLockout {
+                    slot: 1,
+                    confirmation_count: 1,
+                }],
+                root_slot: None,
+                epoch_credits: vec![],
+                credits: 0,
+                last_timestamp: RpcVoteTimestamp {
+                    slot: 1,
+                    timestamp: 0,
+                },
+            }]),
             "sendTransaction" => {
                 let signature = if self.url == "malicious" {
                     Signature::from([8; 64]).to_string()
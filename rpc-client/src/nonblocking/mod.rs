@@ -1 +1,2 @@
 pub mod rpc_client;
+pub mod transaction_session;
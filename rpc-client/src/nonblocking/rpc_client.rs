@@ -2166,6 +2166,55 @@ impl RpcClient {
             .await
     }
 
+    /// Returns the parsed vote states of the vote accounts matching the vote and
+    /// node pubkeys of `config`, in decreasing order of activated stake.
+    ///
+    /// The vote accounts match if their vote pubkey is one of
+    /// [`RpcGetVoteAccountsDetailedConfig::vote_pubkeys`] and their node pubkey
+    /// is one of [`RpcGetVoteAccountsDetailedConfig::node_pubkeys`], the filters
+    /// which are `None` matching all the vote accounts.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteAccountsDetailed` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcGetVoteAccountsDetailedConfig,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let vote_keypairs = [Keypair::new(), Keypair::new()];
+    /// let config = RpcGetVoteAccountsDetailedConfig {
+    ///     vote_pubkeys: Some(
+    ///         vote_keypairs
+    ///             .iter()
+    ///             .map(|vote_keypair| vote_keypair.pubkey().to_string())
+    ///             .collect(),
+    ///     ),
+    ///     commitment: Some(CommitmentConfig::processed()),
+    ///     ..RpcGetVoteAccountsDetailedConfig::default()
+    /// };
+    /// let vote_accounts = rpc_client.get_vote_accounts_detailed(config).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_vote_accounts_detailed(
+        &self,
+        config: RpcGetVoteAccountsDetailedConfig,
+    ) -> ClientResult<Vec<RpcVoteAccountDetailed>> {
+        self.send(RpcRequest::GetVoteAccountsDetailed, json!([config]))
+            .await
+    }
+
     pub async fn wait_for_max_stake(
         &self,
         commitment: CommitmentConfig,
@@ -3977,6 +4026,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     page_size: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -0,0 +1,388 @@
+//! A high-level API for landing a transaction.
+//!
+//! Nearly every application that submits transactions re-implements the same
+//! sequence: fetch a recent blockhash, sign, optionally simulate, send,
+//! poll for the desired commitment, rebroadcast while the blockhash is still
+//! valid, and start over with a new blockhash once it expires. A
+//! [`TransactionSession`] encapsulates that sequence.
+
+use {
+    crate::nonblocking::rpc_client::RpcClient,
+    log::*,
+    solana_commitment_config::CommitmentConfig,
+    solana_hash::Hash,
+    solana_instruction::Instruction,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_rpc_client_api::{
+        client_error::Result as ClientResult,
+        config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+        request::RpcError,
+        response::{RpcResult, RpcSimulateTransactionResult},
+    },
+    solana_signature::Signature,
+    solana_signer::signers::Signers,
+    solana_transaction::Transaction,
+    std::time::{Duration, Instant},
+    tokio::time::sleep,
+};
+
+/// Configuration of a [`TransactionSession`]
+#[derive(Clone, Debug)]
+pub struct TransactionSessionConfig {
+    /// The commitment the transaction is tracked to. This is also the
+    /// commitment at which blockhash expiry is decided.
+    pub commitment: CommitmentConfig,
+    /// Whether to simulate the transaction before it is first sent, failing
+    /// the session if the simulation fails
+    pub simulate: bool,
+    /// Number of times the transaction is re-signed with a new blockhash and
+    /// sent again after its blockhash expires
+    pub max_resubmissions: usize,
+    /// How often the transaction status is polled
+    pub poll_interval: Duration,
+    /// How often the transaction is rebroadcast while its blockhash is valid
+    pub rebroadcast_interval: Duration,
+    /// Configuration used when sending the transaction. Rebroadcasts of an
+    /// already sent transaction always skip preflight.
+    pub send_config: RpcSendTransactionConfig,
+}
+
+impl Default for TransactionSessionConfig {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            simulate: true,
+            max_resubmissions: 3,
+            poll_interval: Duration::from_millis(500),
+            rebroadcast_interval: Duration::from_secs(2),
+            send_config: RpcSendTransactionConfig::default(),
+        }
+    }
+}
+
+/// A transaction that landed with the desired commitment
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionSessionOutcome {
+    /// Signature of the transaction that landed
+    pub signature: Signature,
+    /// The blockhash the transaction that landed was signed with
+    pub blockhash: Hash,
+    /// Number of times the transaction was signed and sent, including
+    /// resubmissions after blockhash expiry
+    pub attempts: usize,
+}
+
+/// Builds, simulates, sends, and tracks a transaction until it lands with the
+/// desired commitment, resubmitting it with a new blockhash if it expires.
+///
+/// # Examples
+///
+/// ```
+/// # use solana_rpc_client_api::client_error::Error;
+/// # use solana_rpc_client::nonblocking::{
+/// #     rpc_client::RpcClient, transaction_session::TransactionSession,
+/// # };
+/// # use solana_instruction::{AccountMeta, Instruction};
+/// # use solana_keypair::Keypair;
+/// # use solana_pubkey::Pubkey;
+/// # use solana_signer::Signer;
+/// # futures::executor::block_on(async {
+/// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+/// #     let payer = Keypair::new();
+/// #     let instruction = Instruction::new_with_bytes(
+/// #         Pubkey::new_unique(),
+/// #         &[],
+/// #         vec![AccountMeta::new(payer.pubkey(), true)],
+/// #     );
+/// let session = TransactionSession::new(
+///     &rpc_client,
+///     &[instruction],
+///     Some(&payer.pubkey()),
+///     &[&payer],
+/// );
+/// let outcome = session.send().await?;
+/// println!("landed {} after {} attempt(s)", outcome.signature, outcome.attempts);
+/// #     Ok::<(), Error>(())
+/// # })?;
+/// # Ok::<(), Error>(())
+/// ```
+pub struct TransactionSession<'a, T: Signers + ?Sized> {
+    rpc_client: &'a RpcClient,
+    message: Message,
+    signers: &'a T,
+    config: TransactionSessionConfig,
+}
+
+impl<'a, T: Signers + ?Sized> TransactionSession<'a, T> {
+    pub fn new(
+        rpc_client: &'a RpcClient,
+        instructions: &[Instruction],
+        payer: Option<&Pubkey>,
+        signers: &'a T,
+    ) -> Self {
+        Self {
+            rpc_client,
+            message: Message::new(instructions, payer),
+            signers,
+            config: TransactionSessionConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: TransactionSessionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn config(&self) -> &TransactionSessionConfig {
+        &self.config
+    }
+
+    /// Signs the transaction with the latest blockhash, returning it along
+    /// with the last block height at which the blockhash is valid
+    pub async fn build(&self) -> ClientResult<(Transaction, u64)> {
+        let (blockhash, last_valid_block_height) = self
+            .rpc_client
+            .get_latest_blockhash_with_commitment(self.config.commitment)
+            .await?;
+        let mut transaction = Transaction::new_unsigned(self.message.clone());
+        transaction.try_sign(self.signers, blockhash)?;
+        Ok((transaction, last_valid_block_height))
+    }
+
+    /// Builds and simulates the transaction
+    pub async fn simulate(&self) -> RpcResult<RpcSimulateTransactionResult> {
+        let (transaction, _last_valid_block_height) = self.build().await?;
+        self.simulate_transaction(&transaction).await
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> RpcResult<RpcSimulateTransactionResult> {
+        self.rpc_client
+            .simulate_transaction_with_config(
+                transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: true,
+                    commitment: Some(self.config.commitment),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await
+    }
+
+    /// Sends the transaction and waits for it to land with the configured
+    /// commitment.
+    ///
+    /// # Errors
+    ///
+    /// Returns the transaction error if the simulation or the landed
+    /// transaction fails, and an [`RpcError::ForUser`] if the transaction
+    /// expired more than [`TransactionSessionConfig::max_resubmissions`]
+    /// times.
+    pub async fn send(&self) -> ClientResult<TransactionSessionOutcome> {
+        for attempt in 0..=self.config.max_resubmissions {
+            let (transaction, last_valid_block_height) = self.build().await?;
+            if attempt == 0 && self.config.simulate {
+                if let Some(err) = self.simulate_transaction(&transaction).await?.value.err {
+                    return Err(err.into());
+                }
+            }
+
+            let signature = self
+                .rpc_client
+                .send_transaction_with_config(&transaction, self.config.send_config)
+                .await?;
+            if self
+                .track(&transaction, &signature, last_valid_block_height)
+                .await?
+            {
+                return Ok(TransactionSessionOutcome {
+                    signature,
+                    blockhash: transaction.message.recent_blockhash,
+                    attempts: attempt + 1,
+                });
+            }
+            debug!(
+                "transaction {signature} expired at block height {last_valid_block_height}, \
+                 attempt {}",
+                attempt + 1
+            );
+        }
+
+        Err(RpcError::ForUser(format!(
+            "transaction expired {} times without landing",
+            self.config.max_resubmissions + 1
+        ))
+        .into())
+    }
+
+    /// Waits for `transaction` to land, rebroadcasting it periodically.
+    /// Returns `Ok(false)` once its blockhash has expired.
+    async fn track(
+        &self,
+        transaction: &Transaction,
+        signature: &Signature,
+        last_valid_block_height: u64,
+    ) -> ClientResult<bool> {
+        let rebroadcast_config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            max_retries: Some(0),
+            ..self.config.send_config
+        };
+        let mut last_broadcast = Instant::now();
+        loop {
+            // Read the block height before the status, so that a transaction
+            // that lands in the last valid block is not reported as expired
+            let block_height = self
+                .rpc_client
+                .get_block_height_with_commitment(self.config.commitment)
+                .await?;
+            match self
+                .rpc_client
+                .get_signature_status_with_commitment(signature, self.config.commitment)
+                .await?
+            {
+                Some(Ok(())) => return Ok(true),
+                Some(Err(err)) => return Err(err.into()),
+                None if block_height > last_valid_block_height => return Ok(false),
+                None => {}
+            }
+
+            if last_broadcast.elapsed() >= self.config.rebroadcast_interval {
+                if let Err(err) = self
+                    .rpc_client
+                    .send_transaction_with_config(transaction, rebroadcast_config)
+                    .await
+                {
+                    debug!("failed to rebroadcast transaction {signature}: {err}");
+                }
+                last_broadcast = Instant::now();
+            }
+            sleep(self.config.poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::mock_sender::Mocks,
+        serde_json::json,
+        solana_instruction::AccountMeta,
+        solana_keypair::Keypair,
+        solana_rpc_client_api::{
+            client_error::ErrorKind,
+            request::RpcRequest,
+            response::{Response, RpcResponseContext},
+        },
+        solana_signer::Signer,
+        solana_transaction_error::TransactionError,
+    };
+
+    fn test_instruction(from: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(*from, true)],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_send_succeeds() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let payer = Keypair::new();
+        let session = TransactionSession::new(
+            &rpc_client,
+            &[test_instruction(&payer.pubkey())],
+            Some(&payer.pubkey()),
+            &[&payer],
+        );
+        let (transaction, _) = session.build().await.unwrap();
+        let outcome = session.send().await.unwrap();
+        assert_eq!(outcome.signature, transaction.signatures[0]);
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_error() {
+        let rpc_client = RpcClient::new_mock("instruction_error".to_string());
+        let payer = Keypair::new();
+        let err = TransactionSession::new(
+            &rpc_client,
+            &[test_instruction(&payer.pubkey())],
+            Some(&payer.pubkey()),
+            &[&payer],
+        )
+        .send()
+        .await
+        .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_send_simulation_failure() {
+        let mut mocks = Mocks::default();
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None
+                },
+                value: RpcSimulateTransactionResult {
+                    err: Some(TransactionError::AccountNotFound),
+                    logs: None,
+                    accounts: None,
+                    units_consumed: None,
+                    return_data: None,
+                    inner_instructions: None,
+                    replacement_blockhash: None,
+                },
+            }),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        let payer = Keypair::new();
+        let err = TransactionSession::new(
+            &rpc_client,
+            &[test_instruction(&payer.pubkey())],
+            Some(&payer.pubkey()),
+            &[&payer],
+        )
+        .send()
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.get_transaction_error(),
+            Some(TransactionError::AccountNotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_expired() {
+        // The mock blockhash is valid through block height 1234
+        let mut mocks = Mocks::default();
+        mocks.insert(RpcRequest::GetBlockHeight, json!(1235));
+        let rpc_client = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+        let payer = Keypair::new();
+        let err = TransactionSession::new(
+            &rpc_client,
+            &[test_instruction(&payer.pubkey())],
+            Some(&payer.pubkey()),
+            &[&payer],
+        )
+        .with_config(TransactionSessionConfig {
+            max_resubmissions: 0,
+            ..TransactionSessionConfig::default()
+        })
+        .send()
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::RpcError(RpcError::ForUser(_))
+        ));
+    }
+}
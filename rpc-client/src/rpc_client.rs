@@ -14,7 +14,13 @@ use {
     crate::{
         http_sender::HttpSender,
         mock_sender::{mock_encoded_account, MockSender},
-        nonblocking::{self, rpc_client::get_rpc_request_str},
+        nonblocking::{
+            self,
+            rpc_client::get_rpc_request_str,
+            transaction_session::{
+                TransactionSession, TransactionSessionConfig, TransactionSessionOutcome,
+            },
+        },
         rpc_sender::*,
     },
     serde::Serialize,
@@ -27,6 +33,7 @@ use {
     solana_epoch_schedule::EpochSchedule,
     solana_feature_gate_interface::Feature,
     solana_hash::Hash,
+    solana_instruction::Instruction,
     solana_message::{v0, Message as LegacyMessage},
     solana_pubkey::Pubkey,
     solana_rpc_client_api::{
@@ -36,6 +43,7 @@ use {
         response::*,
     },
     solana_signature::Signature,
+    solana_signer::signers::Signers,
     solana_transaction::{uses_durable_nonce, versioned::VersionedTransaction, Transaction},
     solana_transaction_error::TransactionResult,
     solana_transaction_status_client_types::{
@@ -662,6 +670,52 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).send_and_confirm_transaction(transaction))
     }
 
+    /// Builds, simulates, sends, and tracks a transaction until it lands with
+    /// the commitment in `config`, resubmitting it with a new blockhash if it
+    /// expires.
+    ///
+    /// See [`TransactionSession`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::{
+    /// #     nonblocking::transaction_session::TransactionSessionConfig, rpc_client::RpcClient,
+    /// # };
+    /// # use solana_instruction::{AccountMeta, Instruction};
+    /// # use solana_keypair::Keypair;
+    /// # use solana_pubkey::Pubkey;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let payer = Keypair::new();
+    /// # let instruction = Instruction::new_with_bytes(
+    /// #     Pubkey::new_unique(),
+    /// #     &[],
+    /// #     vec![AccountMeta::new(payer.pubkey(), true)],
+    /// # );
+    /// let outcome = rpc_client.send_with_transaction_session(
+    ///     &[instruction],
+    ///     Some(&payer.pubkey()),
+    ///     &[&payer],
+    ///     TransactionSessionConfig::default(),
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn send_with_transaction_session<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: Option<&Pubkey>,
+        signers: &T,
+        config: TransactionSessionConfig,
+    ) -> ClientResult<TransactionSessionOutcome> {
+        self.invoke(
+            TransactionSession::new(self.rpc_client.as_ref(), instructions, payer, signers)
+                .with_config(config)
+                .send(),
+        )
+    }
+
     #[cfg(feature = "spinner")]
     pub fn send_and_confirm_transaction_with_spinner(
         &self,
@@ -1864,6 +1918,51 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_vote_accounts_with_config(config))
     }
 
+    /// Returns the parsed vote states of the vote accounts matching the vote and
+    /// node pubkeys of `config`, in decreasing order of activated stake.
+    ///
+    /// The vote accounts match if their vote pubkey is one of
+    /// [`RpcGetVoteAccountsDetailedConfig::vote_pubkeys`] and their node pubkey
+    /// is one of [`RpcGetVoteAccountsDetailedConfig::node_pubkeys`], the filters
+    /// which are `None` matching all the vote accounts.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteAccountsDetailed` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcGetVoteAccountsDetailedConfig,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let vote_keypairs = [Keypair::new(), Keypair::new()];
+    /// let config = RpcGetVoteAccountsDetailedConfig {
+    ///     vote_pubkeys: Some(
+    ///         vote_keypairs
+    ///             .iter()
+    ///             .map(|vote_keypair| vote_keypair.pubkey().to_string())
+    ///             .collect(),
+    ///     ),
+    ///     commitment: Some(CommitmentConfig::processed()),
+    ///     ..RpcGetVoteAccountsDetailedConfig::default()
+    /// };
+    /// let vote_accounts = rpc_client.get_vote_accounts_detailed(config)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_vote_accounts_detailed(
+        &self,
+        config: RpcGetVoteAccountsDetailedConfig,
+    ) -> ClientResult<Vec<RpcVoteAccountDetailed>> {
+        self.invoke((self.rpc_client.as_ref()).get_vote_accounts_detailed(config))
+    }
+
     pub fn wait_for_max_stake(
         &self,
         commitment: CommitmentConfig,
@@ -3315,6 +3414,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     page_size: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3972,6 +4073,8 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4007,6 +4110,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        page_size: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
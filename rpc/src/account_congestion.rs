@@ -0,0 +1,188 @@
+//! The congestion of the most contended write locked accounts of the recent
+//! slots, recorded by replay from the cost tracker of each bank it freezes and
+//! served through `getAccountCongestion`.
+//!
+//! The cost tracker accumulates the compute units of the transactions of a
+//! block per write locked account. While the node is the leader, it also
+//! accumulates the compute units of the transactions the banking stage could
+//! not pack as they would have exceeded the limit of an account, so the demand
+//! for an account beyond its limit is only known for the leader slots of the
+//! node.
+
+use {
+    solana_cost_model::cost_tracker::WritableAccountCost,
+    solana_rpc_client_api::response::{RpcAccountCongestion, RpcSlotAccountCongestion},
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{collections::BTreeMap, sync::Mutex},
+};
+
+/// Number of the most contended accounts retained per slot
+pub const MAX_ACCOUNTS_PER_SLOT: usize = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SlotAccountCongestion {
+    account_cost_limit: u64,
+    /// In decreasing order of demand
+    accounts: Vec<WritableAccountCost>,
+}
+
+struct TrackerState {
+    slots: Mutex<BTreeMap<Slot, SlotAccountCongestion>>,
+    max_slots: usize,
+}
+
+/// The congestion of the most contended accounts of the most recent slots
+#[derive(Default)]
+pub struct AccountCongestionTracker {
+    /// `None` if tracking is disabled
+    state: Option<TrackerState>,
+}
+
+impl AccountCongestionTracker {
+    /// Creates a tracker retaining the congestion of up to `max_slots` slots.
+    /// A `max_slots` of zero disables tracking.
+    pub fn new(max_slots: usize) -> Self {
+        Self {
+            state: (max_slots > 0).then(|| TrackerState {
+                slots: Mutex::default(),
+                max_slots,
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records the most contended accounts of the frozen `bank`, evicting the
+    /// oldest slot once `max_slots` are retained.
+    pub fn record_frozen_bank(&self, bank: &Bank) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let congestion = {
+            let cost_tracker = bank.read_cost_tracker().unwrap();
+            SlotAccountCongestion {
+                account_cost_limit: cost_tracker.get_account_limit(),
+                accounts: cost_tracker.most_demanded_writable_accounts(MAX_ACCOUNTS_PER_SLOT),
+            }
+        };
+        let mut slots = state.slots.lock().unwrap();
+        slots.insert(bank.slot(), congestion);
+        while slots.len() > state.max_slots {
+            slots.pop_first();
+        }
+    }
+
+    /// The congestion of the retained slots, in increasing slot order. If
+    /// `accounts` is not empty, only these accounts are returned, for the
+    /// slots in which they were among the most contended.
+    pub fn to_rpc(&self, accounts: &[Pubkey]) -> Vec<RpcSlotAccountCongestion> {
+        let Some(state) = &self.state else {
+            return Vec::new();
+        };
+        let slots = state.slots.lock().unwrap();
+        slots
+            .iter()
+            .map(|(&slot, congestion)| RpcSlotAccountCongestion {
+                slot,
+                account_cost_limit: congestion.account_cost_limit,
+                accounts: congestion
+                    .accounts
+                    .iter()
+                    .filter(|account_cost| {
+                        accounts.is_empty() || accounts.contains(&account_cost.account)
+                    })
+                    .map(|account_cost| RpcAccountCongestion {
+                        account: account_cost.account.to_string(),
+                        consumed_units: account_cost.cost,
+                        rejected_units: account_cost.rejected_cost,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_cost_model::transaction_cost::{TransactionCost, WritableKeysTransaction},
+        solana_runtime::genesis_utils::create_genesis_config,
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_disabled() {
+        let tracker = AccountCongestionTracker::default();
+        assert!(!tracker.is_enabled());
+        let bank = Bank::new_for_tests(&create_genesis_config(1).genesis_config);
+        tracker.record_frozen_bank(&bank);
+        assert!(tracker.to_rpc(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_record_frozen_bank() {
+        let tracker = AccountCongestionTracker::new(2);
+        assert!(tracker.is_enabled());
+        let bank0 = Arc::new(Bank::new_for_tests(
+            &create_genesis_config(1).genesis_config,
+        ));
+        let hot_account = Pubkey::new_unique();
+        let cold_account = Pubkey::new_unique();
+        let hot_tx = WritableKeysTransaction(vec![hot_account]);
+        let both_tx = WritableKeysTransaction(vec![hot_account, cold_account]);
+        {
+            let mut cost_tracker = bank0.write_cost_tracker().unwrap();
+            cost_tracker
+                .try_add(&TransactionCost::SimpleVote {
+                    transaction: &hot_tx,
+                })
+                .unwrap();
+            cost_tracker
+                .try_add(&TransactionCost::SimpleVote {
+                    transaction: &both_tx,
+                })
+                .unwrap();
+        }
+        tracker.record_frozen_bank(&bank0);
+
+        let congestion = tracker.to_rpc(&[]);
+        assert_eq!(congestion.len(), 1);
+        assert_eq!(congestion[0].slot, 0);
+        assert_eq!(
+            congestion[0].account_cost_limit,
+            bank0.read_cost_tracker().unwrap().get_account_limit()
+        );
+        let accounts: Vec<_> = congestion[0]
+            .accounts
+            .iter()
+            .map(|account| account.account.clone())
+            .collect();
+        assert_eq!(
+            accounts,
+            vec![hot_account.to_string(), cold_account.to_string()]
+        );
+        assert_eq!(
+            congestion[0].accounts[0].consumed_units,
+            2 * congestion[0].accounts[1].consumed_units
+        );
+        assert_eq!(congestion[0].accounts[0].rejected_units, 0);
+        // Only the requested accounts are returned.
+        let congestion = tracker.to_rpc(&[cold_account]);
+        assert_eq!(congestion[0].accounts.len(), 1);
+        assert_eq!(congestion[0].accounts[0].account, cold_account.to_string());
+
+        // The oldest slots are evicted.
+        for slot in 1..=2 {
+            let bank = Bank::new_from_parent(bank0.clone(), &Pubkey::default(), slot);
+            tracker.record_frozen_bank(&bank);
+        }
+        let congestion = tracker.to_rpc(&[]);
+        let slots: Vec<_> = congestion.iter().map(|slot| slot.slot).collect();
+        assert_eq!(slots, vec![1, 2]);
+        assert!(congestion[0].accounts.is_empty());
+    }
+}
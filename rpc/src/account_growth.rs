@@ -0,0 +1,288 @@
+//! The growth of the accounts of the node in the recent epochs, sampled at the root by the
+//! account growth service and served through `getAccountGrowth`, to follow the growth of the
+//! accounts state and the programs driving it.
+//!
+//! The accounts created and deleted are counted by accounts-db as it updates the accounts index,
+//! so the samples need no scan of the accounts. Each sample is attributed to the epoch of the root
+//! it is taken at.
+
+use {
+    solana_accounts_db::account_growth::OwnerAccountGrowth,
+    solana_rpc_client_api::response::{
+        RpcAccountGrowth, RpcEpochAccountGrowth, RpcProgramAccountGrowth,
+    },
+    solana_sdk::{
+        clock::{Epoch, Slot},
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::{HashMap, VecDeque},
+        sync::Mutex,
+        time::Duration,
+    },
+};
+
+/// The number of the programs creating the largest accounts reported for each epoch
+pub const MAX_TOP_PROGRAMS: usize = 10;
+
+/// A sample of the accounts, taken at a root
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountGrowthSample {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    /// The duration since the previous sample
+    pub sample_period: Duration,
+    pub num_accounts: u64,
+    pub accounts_data_size: u64,
+    pub rent_exempt_reserve: u64,
+    /// The accounts created and deleted since the previous sample
+    pub num_accounts_created: u64,
+    pub num_accounts_deleted: u64,
+    /// The change of the size of the accounts data since the previous sample
+    pub accounts_data_size_delta: i64,
+    /// The accounts created by owner since the previous sample
+    pub created_by_owner: HashMap<Pubkey, OwnerAccountGrowth>,
+}
+
+/// The `num_programs` programs creating the largest accounts, the largest first
+pub fn top_programs(
+    created_by_owner: &HashMap<Pubkey, OwnerAccountGrowth>,
+    num_programs: usize,
+) -> Vec<(Pubkey, OwnerAccountGrowth)> {
+    let mut programs: Vec<_> = created_by_owner
+        .iter()
+        .map(|(owner, growth)| (*owner, *growth))
+        .collect();
+    programs.sort_unstable_by(|(owner, growth), (other_owner, other_growth)| {
+        (
+            other_growth.data_len_created,
+            other_growth.num_accounts_created,
+            owner,
+        )
+            .cmp(&(
+                growth.data_len_created,
+                growth.num_accounts_created,
+                other_owner,
+            ))
+    });
+    programs.truncate(num_programs);
+    programs
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EpochAccountGrowth {
+    epoch: Epoch,
+    slot: Slot,
+    num_accounts_created: u64,
+    num_accounts_deleted: u64,
+    accounts_data_size_delta: i64,
+    created_by_owner: HashMap<Pubkey, OwnerAccountGrowth>,
+}
+
+impl EpochAccountGrowth {
+    fn new(epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            slot: 0,
+            num_accounts_created: 0,
+            num_accounts_deleted: 0,
+            accounts_data_size_delta: 0,
+            created_by_owner: HashMap::new(),
+        }
+    }
+
+    fn accumulate(&mut self, sample: &AccountGrowthSample) {
+        self.slot = sample.slot;
+        self.num_accounts_created += sample.num_accounts_created;
+        self.num_accounts_deleted += sample.num_accounts_deleted;
+        self.accounts_data_size_delta += sample.accounts_data_size_delta;
+        for (owner, growth) in &sample.created_by_owner {
+            self.created_by_owner.entry(*owner).or_default().add(growth);
+        }
+    }
+
+    fn to_rpc(&self) -> RpcEpochAccountGrowth {
+        RpcEpochAccountGrowth {
+            epoch: self.epoch,
+            slot: self.slot,
+            num_accounts_created: self.num_accounts_created,
+            num_accounts_deleted: self.num_accounts_deleted,
+            accounts_data_size_delta: self.accounts_data_size_delta,
+            top_programs: top_programs(&self.created_by_owner, MAX_TOP_PROGRAMS)
+                .into_iter()
+                .map(|(program_id, growth)| RpcProgramAccountGrowth {
+                    program_id: program_id.to_string(),
+                    num_accounts_created: growth.num_accounts_created,
+                    data_size_created: growth.data_len_created,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TrackedGrowth {
+    latest: Option<AccountGrowthSample>,
+    /// The latest epoch last
+    epochs: VecDeque<EpochAccountGrowth>,
+}
+
+struct TrackerState {
+    max_epochs: usize,
+    growth: Mutex<TrackedGrowth>,
+}
+
+/// The growth of the accounts in the most recent epochs
+#[derive(Default)]
+pub struct AccountGrowthTracker {
+    /// `None` if the tracking is disabled
+    state: Option<TrackerState>,
+}
+
+impl AccountGrowthTracker {
+    /// Creates a tracker of the growth of up to `max_epochs` epochs. A `max_epochs` of zero
+    /// disables the tracking.
+    pub fn new(max_epochs: usize) -> Self {
+        Self {
+            state: (max_epochs > 0).then(|| TrackerState {
+                max_epochs,
+                growth: Mutex::default(),
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records a new sample, in the growth of its epoch, evicting the oldest epoch once
+    /// `max_epochs` are retained.
+    pub fn record(&self, sample: AccountGrowthSample) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut growth = state.growth.lock().unwrap();
+        if growth
+            .epochs
+            .back()
+            .is_none_or(|epoch| epoch.epoch != sample.epoch)
+        {
+            growth
+                .epochs
+                .push_back(EpochAccountGrowth::new(sample.epoch));
+        }
+        growth.epochs.back_mut().unwrap().accumulate(&sample);
+        while growth.epochs.len() > state.max_epochs {
+            growth.epochs.pop_front();
+        }
+        growth.latest = Some(sample);
+    }
+
+    pub fn to_rpc(&self) -> Option<RpcAccountGrowth> {
+        let state = self.state.as_ref()?;
+        let growth = state.growth.lock().unwrap();
+        let latest = growth.latest.as_ref()?;
+        Some(RpcAccountGrowth {
+            slot: latest.slot,
+            num_accounts: latest.num_accounts,
+            accounts_data_size: latest.accounts_data_size,
+            rent_exempt_reserve: latest.rent_exempt_reserve,
+            sample_period_secs: latest.sample_period.as_secs(),
+            num_accounts_created: latest.num_accounts_created,
+            num_accounts_deleted: latest.num_accounts_deleted,
+            epochs: growth
+                .epochs
+                .iter()
+                .rev()
+                .map(EpochAccountGrowth::to_rpc)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_sample(
+        slot: Slot,
+        epoch: Epoch,
+        created_by_owner: &[(Pubkey, u64)],
+    ) -> AccountGrowthSample {
+        AccountGrowthSample {
+            slot,
+            epoch,
+            sample_period: Duration::from_secs(60),
+            num_accounts: 100,
+            accounts_data_size: 1_000,
+            rent_exempt_reserve: 10_000,
+            num_accounts_created: 3,
+            num_accounts_deleted: 1,
+            accounts_data_size_delta: -10,
+            created_by_owner: created_by_owner
+                .iter()
+                .map(|(owner, data_len_created)| {
+                    (
+                        *owner,
+                        OwnerAccountGrowth {
+                            num_accounts_created: 1,
+                            data_len_created: *data_len_created,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_account_growth_tracker() {
+        let tracker = AccountGrowthTracker::default();
+        assert!(!tracker.is_enabled());
+        tracker.record(new_sample(1, 0, &[]));
+        assert_eq!(tracker.to_rpc(), None);
+
+        let tracker = AccountGrowthTracker::new(2);
+        assert_eq!(tracker.to_rpc(), None);
+        let (program, other_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        tracker.record(new_sample(10, 1, &[(program, 5)]));
+        tracker.record(new_sample(20, 1, &[(program, 5), (other_program, 20)]));
+        let growth = tracker.to_rpc().unwrap();
+        assert_eq!(growth.slot, 20);
+        assert_eq!(growth.sample_period_secs, 60);
+        assert_eq!(growth.num_accounts_created, 3);
+        assert_eq!(
+            growth.epochs,
+            [RpcEpochAccountGrowth {
+                epoch: 1,
+                slot: 20,
+                num_accounts_created: 6,
+                num_accounts_deleted: 2,
+                accounts_data_size_delta: -20,
+                top_programs: vec![
+                    RpcProgramAccountGrowth {
+                        program_id: other_program.to_string(),
+                        num_accounts_created: 1,
+                        data_size_created: 20,
+                    },
+                    RpcProgramAccountGrowth {
+                        program_id: program.to_string(),
+                        num_accounts_created: 2,
+                        data_size_created: 10,
+                    },
+                ],
+            }]
+        );
+
+        // Only the most recent epochs are retained, the latest first.
+        tracker.record(new_sample(40, 2, &[]));
+        tracker.record(new_sample(60, 3, &[]));
+        let epochs: Vec<_> = tracker
+            .to_rpc()
+            .unwrap()
+            .epochs
+            .iter()
+            .map(|epoch| epoch.epoch)
+            .collect();
+        assert_eq!(epochs, [3, 2]);
+    }
+}
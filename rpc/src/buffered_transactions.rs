@@ -0,0 +1,155 @@
+//! Samples of the non-vote transactions buffered by the banking stage,
+//! published periodically by the transaction scheduler and served through
+//! `getBufferedTransactions`, to diagnose why some transactions, e.g. the ones
+//! paying low fees, wait in the buffer for long.
+
+use {
+    solana_rpc_client_api::response::{RpcBufferedTransaction, RpcBufferedTransactions},
+    solana_sdk::{clock::Slot, signature::Signature},
+    std::sync::Mutex,
+};
+
+/// A transaction buffered by the banking stage
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferedTransaction {
+    pub signature: Signature,
+    pub priority: u64,
+    pub cost: u64,
+    /// The slots since the transaction was received
+    pub slots_waited: u64,
+    pub num_retries: u32,
+}
+
+impl BufferedTransaction {
+    fn to_rpc(&self) -> RpcBufferedTransaction {
+        RpcBufferedTransaction {
+            signature: self.signature.to_string(),
+            priority: self.priority,
+            cost: self.cost,
+            slots_waited: self.slots_waited,
+            num_retries: self.num_retries,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct BufferedTransactionSample {
+    slot: Slot,
+    num_buffered: usize,
+    transactions: Vec<BufferedTransaction>,
+}
+
+struct SamplerState {
+    max_samples: usize,
+    latest: Mutex<Option<BufferedTransactionSample>>,
+}
+
+/// The latest sample of the buffered transactions
+#[derive(Default)]
+pub struct BufferedTransactionSampler {
+    /// `None` if sampling is disabled
+    state: Option<SamplerState>,
+}
+
+impl BufferedTransactionSampler {
+    /// Creates a sampler of up to `max_samples` buffered transactions. A
+    /// `max_samples` of zero disables sampling.
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            state: (max_samples > 0).then(|| SamplerState {
+                max_samples,
+                latest: Mutex::default(),
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// The most transactions a sample holds, zero if sampling is disabled
+    pub fn max_samples(&self) -> usize {
+        self.state.as_ref().map_or(0, |state| state.max_samples)
+    }
+
+    /// Replaces the latest sample with `transactions`, sampled out of the
+    /// `num_buffered` transactions buffered in `slot`.
+    pub fn record(
+        &self,
+        slot: Slot,
+        num_buffered: usize,
+        mut transactions: Vec<BufferedTransaction>,
+    ) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        transactions.truncate(state.max_samples);
+        *state.latest.lock().unwrap() = Some(BufferedTransactionSample {
+            slot,
+            num_buffered,
+            transactions,
+        });
+    }
+
+    pub fn to_rpc(&self) -> Option<RpcBufferedTransactions> {
+        let state = self.state.as_ref()?;
+        let latest = state.latest.lock().unwrap();
+        let sample = latest.as_ref()?;
+        Some(RpcBufferedTransactions {
+            slot: sample.slot,
+            num_buffered: sample.num_buffered as u64,
+            transactions: sample
+                .transactions
+                .iter()
+                .map(BufferedTransaction::to_rpc)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_transaction(priority: u64) -> BufferedTransaction {
+        BufferedTransaction {
+            signature: Signature::new_unique(),
+            priority,
+            cost: 1_000,
+            slots_waited: 3,
+            num_retries: 1,
+        }
+    }
+
+    #[test]
+    fn test_buffered_transaction_sampler() {
+        let sampler = BufferedTransactionSampler::default();
+        assert!(!sampler.is_enabled());
+        assert_eq!(sampler.max_samples(), 0);
+        sampler.record(1, 1, vec![new_transaction(1)]);
+        assert_eq!(sampler.to_rpc(), None);
+
+        let sampler = BufferedTransactionSampler::new(2);
+        assert_eq!(sampler.max_samples(), 2);
+        assert_eq!(sampler.to_rpc(), None);
+        let transactions: Vec<_> = (0..3).map(new_transaction).collect();
+        sampler.record(5, 10, transactions.clone());
+        assert_eq!(
+            sampler.to_rpc(),
+            Some(RpcBufferedTransactions {
+                slot: 5,
+                num_buffered: 10,
+                transactions: transactions[..2]
+                    .iter()
+                    .map(BufferedTransaction::to_rpc)
+                    .collect(),
+            })
+        );
+
+        // Only the latest sample is kept.
+        sampler.record(6, 0, vec![]);
+        let sample = sampler.to_rpc().unwrap();
+        assert_eq!(sample.slot, 6);
+        assert!(sample.transactions.is_empty());
+    }
+}
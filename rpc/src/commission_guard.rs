@@ -0,0 +1,247 @@
+//! Refuses to submit transactions, received through `sendTransaction`, which
+//! change the commission of the node's own vote account by more than a
+//! configured amount within a window of epochs.
+//!
+//! This protects against fat-fingered or compromised tooling submitting
+//! commission changes through the node. The guard can be lifted through the
+//! admin RPC service when a large change is intended.
+
+use {
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Epoch, message::SanitizedMessage, pubkey::Pubkey},
+    solana_vote_program::vote_instruction::VoteInstruction,
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thiserror::Error,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommissionGuardConfig {
+    /// Maximum change of the commission within a window, in percentage points
+    pub max_change: u8,
+    /// Length of the window, in epochs
+    pub window_epochs: u64,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CommissionGuardError {
+    #[error(
+        "changing the commission of vote account {vote_account} from {baseline}% to \
+         {commission}% exceeds the maximum change of {max_change}% per {window_epochs} epoch(s)"
+    )]
+    MaxChangeExceeded {
+        vote_account: Pubkey,
+        baseline: u8,
+        commission: u8,
+        max_change: u8,
+        window_epochs: u64,
+    },
+}
+
+/// The commission of the vote account when the current window started
+struct CommissionWindow {
+    start_epoch: Epoch,
+    baseline: u8,
+}
+
+pub struct CommissionGuard {
+    vote_account: Pubkey,
+    config: CommissionGuardConfig,
+    window: Mutex<Option<CommissionWindow>>,
+    /// Lets every commission change through while set
+    override_enabled: AtomicBool,
+}
+
+impl CommissionGuard {
+    pub fn new(vote_account: Pubkey, config: CommissionGuardConfig) -> Self {
+        Self {
+            vote_account,
+            config,
+            window: Mutex::default(),
+            override_enabled: AtomicBool::default(),
+        }
+    }
+
+    pub fn vote_account(&self) -> &Pubkey {
+        &self.vote_account
+    }
+
+    pub fn config(&self) -> &CommissionGuardConfig {
+        &self.config
+    }
+
+    pub fn set_override(&self, enabled: bool) {
+        self.override_enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_override_enabled(&self) -> bool {
+        self.override_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the commissions which `message` sets on the guarded vote
+    /// account
+    fn requested_commissions<'a>(
+        &'a self,
+        message: &'a SanitizedMessage,
+    ) -> impl Iterator<Item = u8> + 'a {
+        let account_keys = message.account_keys();
+        message
+            .program_instructions_iter()
+            .filter(|(program_id, _)| solana_vote_program::check_id(program_id))
+            .filter_map(move |(_, instruction)| {
+                let vote_account = account_keys.get(usize::from(*instruction.accounts.first()?))?;
+                if vote_account != &self.vote_account {
+                    return None;
+                }
+                match bincode::deserialize(&instruction.data).ok()? {
+                    VoteInstruction::UpdateCommission(commission) => Some(commission),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Checks the commission changes in `message` against the window which
+    /// contains the epoch of `bank`.
+    ///
+    /// A window starts at the first commission change checked after the
+    /// previous window has ended, and every change within the window is
+    /// compared with the commission of the vote account at that time.
+    pub fn check(
+        &self,
+        bank: &Bank,
+        message: &SanitizedMessage,
+    ) -> Result<(), CommissionGuardError> {
+        let mut commissions = self.requested_commissions(message).peekable();
+        if commissions.peek().is_none() {
+            return Ok(());
+        }
+        if self.is_override_enabled() {
+            warn!(
+                "commission guard overridden, letting commission change of {} through",
+                self.vote_account
+            );
+            return Ok(());
+        }
+
+        let mut window = self.window.lock().unwrap();
+        let epoch = bank.epoch();
+        if window
+            .as_ref()
+            .is_none_or(|window| epoch >= window.start_epoch + self.config.window_epochs)
+        {
+            // A vote account which does not exist yet has no commission to
+            // protect.
+            let Some(vote_account) = bank.get_vote_account(&self.vote_account) else {
+                return Ok(());
+            };
+            *window = Some(CommissionWindow {
+                start_epoch: epoch,
+                baseline: vote_account.vote_state_view().commission(),
+            });
+        }
+        let baseline = window.as_ref().unwrap().baseline;
+        for commission in commissions {
+            if commission.abs_diff(baseline) > self.config.max_change {
+                datapoint_warn!(
+                    "commission_guard-rejected",
+                    ("baseline", baseline, i64),
+                    ("commission", commission, i64),
+                );
+                return Err(CommissionGuardError::MaxChangeExceeded {
+                    vote_account: self.vote_account,
+                    baseline,
+                    commission,
+                    max_change: self.config.max_change,
+                    window_epochs: self.config.window_epochs,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::genesis_utils::{
+            create_genesis_config_with_vote_accounts, GenesisConfigInfo, ValidatorVoteKeypairs,
+        },
+        solana_sdk::{
+            hash::Hash,
+            message::Message,
+            reserved_account_keys::ReservedAccountKeys,
+            signature::{Keypair, Signer},
+        },
+        solana_vote_program::vote_instruction,
+    };
+
+    fn new_message(vote_account: &Pubkey, commission: u8) -> SanitizedMessage {
+        let withdrawer = Keypair::new();
+        let instruction =
+            vote_instruction::update_commission(vote_account, &withdrawer.pubkey(), commission);
+        let message = Message::new_with_blockhash(
+            &[instruction],
+            Some(&withdrawer.pubkey()),
+            &Hash::default(),
+        );
+        SanitizedMessage::try_from_legacy_message(message, &ReservedAccountKeys::empty_key_set())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_commission_guard() {
+        let validator_keypairs = ValidatorVoteKeypairs::new_rand();
+        let vote_account = validator_keypairs.vote_keypair.pubkey();
+        let GenesisConfigInfo { genesis_config, .. } =
+            create_genesis_config_with_vote_accounts(10_000, &[validator_keypairs], vec![100]);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let commission = bank
+            .get_vote_account(&vote_account)
+            .unwrap()
+            .vote_state_view()
+            .commission();
+
+        let guard = CommissionGuard::new(
+            vote_account,
+            CommissionGuardConfig {
+                max_change: 5,
+                window_epochs: 1,
+            },
+        );
+        assert_eq!(
+            guard.check(
+                &bank,
+                &new_message(&vote_account, commission.saturating_add(5))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            guard.check(
+                &bank,
+                &new_message(&vote_account, commission.saturating_add(6))
+            ),
+            Err(CommissionGuardError::MaxChangeExceeded {
+                vote_account,
+                baseline: commission,
+                commission: commission.saturating_add(6),
+                max_change: 5,
+                window_epochs: 1,
+            })
+        );
+        // Other vote accounts are not guarded
+        assert_eq!(
+            guard.check(&bank, &new_message(&Pubkey::new_unique(), 100)),
+            Ok(())
+        );
+
+        guard.set_override(true);
+        assert_eq!(guard.check(&bank, &new_message(&vote_account, 100)), Ok(()));
+        guard.set_override(false);
+        assert!(guard
+            .check(&bank, &new_message(&vote_account, 100))
+            .is_err());
+    }
+}
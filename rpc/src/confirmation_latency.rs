@@ -0,0 +1,347 @@
+//! The times from the arrival of the first shred of the recent slots to their
+//! optimistic confirmation and to their rooting, recorded by the optimistically
+//! confirmed bank tracker, reported as the `confirmation_latency` metric and
+//! served through `getConfirmationLatency`.
+//!
+//! The arrival of the first shred of a slot is the `first_shred_timestamp` of
+//! its slot meta, which the blockstore extrapolates from the reference tick of
+//! the first shred it inserts. Optimistic confirmations are observed when the
+//! vote listener reports them and roots when replay sets them, so the latencies
+//! include the delivery of these notifications.
+
+use {
+    solana_ledger::blockstore::Blockstore,
+    solana_rpc_client_api::response::{RpcConfirmationLatency, RpcSlotConfirmationLatency},
+    solana_sdk::clock::Slot,
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    },
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct SlotConfirmationLatency {
+    /// In milliseconds since the UNIX epoch, as are the timestamps below
+    first_shred_timestamp: Option<u64>,
+    optimistic_confirmation_timestamp: Option<u64>,
+    finalization_timestamp: Option<u64>,
+}
+
+impl SlotConfirmationLatency {
+    fn latency_ms(&self, timestamp: Option<u64>) -> Option<u64> {
+        Some(timestamp?.saturating_sub(self.first_shred_timestamp?))
+    }
+
+    fn optimistic_confirmation_latency_ms(&self) -> Option<u64> {
+        self.latency_ms(self.optimistic_confirmation_timestamp)
+    }
+
+    fn finalization_latency_ms(&self) -> Option<u64> {
+        self.latency_ms(self.finalization_timestamp)
+    }
+
+    fn to_rpc(&self, slot: Slot) -> RpcSlotConfirmationLatency {
+        RpcSlotConfirmationLatency {
+            slot,
+            first_shred_timestamp: self.first_shred_timestamp,
+            optimistic_confirmation_latency_ms: self.optimistic_confirmation_latency_ms(),
+            finalization_latency_ms: self.finalization_latency_ms(),
+        }
+    }
+
+    fn report(&self, slot: Slot) {
+        datapoint_info!(
+            "confirmation_latency",
+            ("slot", slot, i64),
+            (
+                "optimistic_confirmation_latency_ms",
+                self.optimistic_confirmation_latency_ms(),
+                Option<i64>
+            ),
+            (
+                "finalization_latency_ms",
+                self.finalization_latency_ms(),
+                Option<i64>
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+struct ConfirmationLatencies {
+    slots: BTreeMap<Slot, SlotConfirmationLatency>,
+    /// The highest root recorded
+    root: Option<Slot>,
+}
+
+struct TrackerState {
+    latencies: Mutex<ConfirmationLatencies>,
+    max_slots: usize,
+    blockstore: Arc<Blockstore>,
+}
+
+/// The confirmation latencies of the most recent slots
+#[derive(Default)]
+pub struct ConfirmationLatencyTracker {
+    /// `None` if tracking is disabled
+    state: Option<TrackerState>,
+}
+
+impl ConfirmationLatencyTracker {
+    /// Creates a tracker retaining the latencies of up to `max_slots` slots.
+    /// A `max_slots` of zero disables tracking.
+    pub fn new(max_slots: usize, blockstore: Arc<Blockstore>) -> Self {
+        Self {
+            state: (max_slots > 0).then(|| TrackerState {
+                latencies: Mutex::default(),
+                max_slots,
+                blockstore,
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records the optimistic confirmation of `slot` at `timestamp`
+    /// milliseconds since the UNIX epoch.
+    pub fn record_optimistic_confirmation(&self, slot: Slot, timestamp: u64) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut latencies = state.latencies.lock().unwrap();
+        let is_rooted = latencies.root.is_some_and(|root| slot <= root);
+        if is_rooted && !latencies.slots.contains_key(&slot) {
+            return;
+        }
+        let Some(latency) = state.slot_latency(&mut latencies.slots, slot) else {
+            return;
+        };
+        latency
+            .optimistic_confirmation_timestamp
+            .get_or_insert(timestamp);
+    }
+
+    /// Records the rooting of `root`, and of the slots rooted since the
+    /// previous root, at `timestamp` milliseconds since the UNIX epoch. The
+    /// rooted slots are reported as metrics, and the slots of the abandoned
+    /// forks are discarded.
+    ///
+    /// The slots rooted before the first call are ignored.
+    pub fn record_root(&self, root: Slot, timestamp: u64) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut latencies = state.latencies.lock().unwrap();
+        let new_roots: Vec<Slot> = match latencies.root {
+            Some(prev_root) if prev_root >= root => return,
+            Some(prev_root) => match state.blockstore.rooted_slot_iterator(prev_root + 1) {
+                Ok(roots) => roots.take_while(|&slot| slot <= root).collect(),
+                Err(err) => {
+                    warn!("Failed to read the roots after {prev_root}: {err}");
+                    vec![root]
+                }
+            },
+            None => vec![root],
+        };
+        latencies.root = Some(root);
+        for slot in new_roots {
+            if let Some(latency) = state.slot_latency(&mut latencies.slots, slot) {
+                if latency.finalization_timestamp.is_none() {
+                    latency.finalization_timestamp = Some(timestamp);
+                    latency.report(slot);
+                }
+            }
+        }
+        latencies
+            .slots
+            .retain(|&slot, latency| slot > root || latency.finalization_timestamp.is_some());
+    }
+
+    pub fn to_rpc(&self) -> RpcConfirmationLatency {
+        let slots: Vec<_> = self
+            .state
+            .iter()
+            .flat_map(|state| {
+                let latencies = state.latencies.lock().unwrap();
+                latencies
+                    .slots
+                    .iter()
+                    .map(|(&slot, latency)| latency.to_rpc(slot))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        RpcConfirmationLatency {
+            mean_optimistic_confirmation_latency_ms: mean(
+                slots
+                    .iter()
+                    .filter_map(|slot| slot.optimistic_confirmation_latency_ms),
+            ),
+            mean_finalization_latency_ms: mean(
+                slots.iter().filter_map(|slot| slot.finalization_latency_ms),
+            ),
+            slots,
+        }
+    }
+}
+
+impl TrackerState {
+    // Returns the entry of `slot`, inserting it if there is room for it.
+    fn slot_latency<'a>(
+        &self,
+        slots: &'a mut BTreeMap<Slot, SlotConfirmationLatency>,
+        slot: Slot,
+    ) -> Option<&'a mut SlotConfirmationLatency> {
+        if !slots.contains_key(&slot) {
+            if slots.len() >= self.max_slots {
+                if slots
+                    .first_key_value()
+                    .is_some_and(|(&first, _)| slot < first)
+                {
+                    return None;
+                }
+                slots.pop_first();
+            }
+            let first_shred_timestamp = match self.blockstore.meta(slot) {
+                Ok(meta) => meta
+                    .map(|meta| meta.first_shred_timestamp)
+                    .filter(|&timestamp| timestamp > 0),
+                Err(err) => {
+                    warn!("Failed to read the slot meta of {slot}: {err}");
+                    None
+                }
+            };
+            slots.insert(
+                slot,
+                SlotConfirmationLatency {
+                    first_shred_timestamp,
+                    ..SlotConfirmationLatency::default()
+                },
+            );
+        }
+        slots.get_mut(&slot)
+    }
+}
+
+fn mean(values: impl Iterator<Item = u64>) -> Option<u64> {
+    let (sum, count) = values.fold((0u64, 0u64), |(sum, count), value| {
+        (sum.saturating_add(value), count + 1)
+    });
+    sum.checked_div(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::{blockstore::make_slot_entries, get_tmp_ledger_path_auto_delete},
+    };
+
+    // Inserts a shred of each slot, chained to the previous slot, returning
+    // the first shred timestamps of the slots.
+    fn insert_slots(blockstore: &Blockstore, slots: &[Slot]) -> Vec<u64> {
+        let mut parent = 0;
+        slots
+            .iter()
+            .map(|&slot| {
+                let (shreds, _) = make_slot_entries(slot, parent, 1, true);
+                blockstore.insert_shreds(shreds, None, false).unwrap();
+                parent = slot;
+                blockstore
+                    .meta(slot)
+                    .unwrap()
+                    .unwrap()
+                    .first_shred_timestamp
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_disabled() {
+        let tracker = ConfirmationLatencyTracker::default();
+        assert!(!tracker.is_enabled());
+        tracker.record_optimistic_confirmation(1, 1_000);
+        tracker.record_root(1, 1_000);
+        assert!(tracker.to_rpc().slots.is_empty());
+    }
+
+    #[test]
+    fn test_record() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let timestamps = insert_slots(&blockstore, &[1, 2, 3, 4]);
+        let tracker = ConfirmationLatencyTracker::new(3, blockstore.clone());
+        assert!(tracker.is_enabled());
+
+        // Only the first root is recorded at first.
+        blockstore.set_roots([1].iter()).unwrap();
+        tracker.record_root(1, timestamps[0] + 900);
+        tracker.record_optimistic_confirmation(2, timestamps[1] + 400);
+        tracker.record_optimistic_confirmation(3, timestamps[2] + 500);
+        // Confirmations of slots already rooted are ignored.
+        tracker.record_optimistic_confirmation(0, timestamps[0]);
+        // The roots since the previous root are recorded.
+        blockstore.set_roots([2, 3].iter()).unwrap();
+        tracker.record_root(3, timestamps[2] + 1_300);
+        let latency = tracker.to_rpc();
+        let slots: Vec<_> = latency.slots.iter().map(|slot| slot.slot).collect();
+        assert_eq!(slots, vec![1, 2, 3]);
+        assert_eq!(latency.slots[0].first_shred_timestamp, Some(timestamps[0]));
+        assert_eq!(latency.slots[0].optimistic_confirmation_latency_ms, None);
+        assert_eq!(latency.slots[0].finalization_latency_ms, Some(900));
+        assert_eq!(
+            latency.slots[1].finalization_latency_ms,
+            Some(timestamps[2] + 1_300 - timestamps[1])
+        );
+        assert_eq!(
+            latency.slots[2].optimistic_confirmation_latency_ms,
+            Some(500)
+        );
+        assert_eq!(latency.slots[2].finalization_latency_ms, Some(1_300));
+        assert_eq!(
+            latency.mean_optimistic_confirmation_latency_ms,
+            Some((400 + 500) / 2)
+        );
+
+        // Older slots are evicted.
+        tracker.record_optimistic_confirmation(4, timestamps[3] + 600);
+        let slots: Vec<_> = tracker
+            .to_rpc()
+            .slots
+            .iter()
+            .map(|slot| slot.slot)
+            .collect();
+        assert_eq!(slots, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_abandoned_forks() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let tracker = ConfirmationLatencyTracker::new(8, blockstore.clone());
+        blockstore.set_roots([1].iter()).unwrap();
+        tracker.record_root(1, 1_000);
+        // Slots without shreds have no latencies.
+        tracker.record_optimistic_confirmation(2, 2_000);
+        tracker.record_optimistic_confirmation(5, 2_000);
+        assert_eq!(tracker.to_rpc().slots[1].first_shred_timestamp, None);
+        assert_eq!(
+            tracker.to_rpc().mean_optimistic_confirmation_latency_ms,
+            None
+        );
+        // Slot 2 is not on the rooted fork.
+        blockstore.set_roots([3].iter()).unwrap();
+        tracker.record_root(3, 3_000);
+        let slots: Vec<_> = tracker
+            .to_rpc()
+            .slots
+            .iter()
+            .map(|slot| slot.slot)
+            .collect();
+        assert_eq!(slots, vec![1, 3, 5]);
+        // Lower roots are ignored.
+        tracker.record_root(2, 4_000);
+        assert_eq!(tracker.to_rpc().slots.len(), 3);
+    }
+}
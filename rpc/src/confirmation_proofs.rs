@@ -0,0 +1,223 @@
+//! The proofs of the optimistic confirmation of the recent slots, built by the
+//! vote listener from the votes it observes and served through
+//! `getConfirmationProof`, so that light clients and bridges can verify the
+//! confirmation of a slot independently of the node.
+//!
+//! The votes for each slot and bank hash are recorded, from gossip and replay,
+//! until the stake of the votes for the slot reaches the optimistic
+//! confirmation threshold. The votes at that point are then retained as the
+//! proof of the slot. A vote counts towards a slot either by voting for it last
+//! or by having it in its tower, in which case the slot is an ancestor of the
+//! slot last voted for.
+
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_rpc_client_api::response::{RpcConfirmationProof, RpcConfirmationVote},
+    solana_sdk::{
+        clock::Slot, hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::Mutex,
+    },
+};
+
+/// A vote which counts towards the confirmation of a slot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfirmationVote {
+    pub vote_account: Pubkey,
+    /// The stake of the vote account in the epoch of the slot
+    pub stake: u64,
+    /// The last slot voted for by the vote transaction
+    pub voted_slot: Slot,
+    pub signature: Signature,
+    /// The vote transaction, if observed in gossip
+    pub transaction: Option<Transaction>,
+}
+
+impl ConfirmationVote {
+    fn to_rpc(&self) -> RpcConfirmationVote {
+        RpcConfirmationVote {
+            vote_account: self.vote_account.to_string(),
+            stake: self.stake,
+            voted_slot: self.voted_slot,
+            signature: self.signature.to_string(),
+            transaction: self.transaction.as_ref().map(|transaction| {
+                BASE64_STANDARD.encode(bincode::serialize(transaction).unwrap())
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ConfirmationProof {
+    bank_hash: Hash,
+    total_stake: u64,
+    votes: Vec<ConfirmationVote>,
+}
+
+#[derive(Default)]
+struct ProofSlots {
+    /// The votes for the slots above the root which are not yet confirmed
+    pending: BTreeMap<Slot, HashMap<Hash, Vec<ConfirmationVote>>>,
+    confirmed: BTreeMap<Slot, ConfirmationProof>,
+}
+
+struct CacheState {
+    slots: Mutex<ProofSlots>,
+    max_slots: usize,
+}
+
+/// The proofs of the optimistic confirmation of the most recent slots
+#[derive(Default)]
+pub struct ConfirmationProofCache {
+    /// `None` if the proofs are disabled
+    state: Option<CacheState>,
+}
+
+impl ConfirmationProofCache {
+    /// Creates a cache retaining the proofs of up to `max_slots` confirmed
+    /// slots. A `max_slots` of zero disables the proofs.
+    pub fn new(max_slots: usize) -> Self {
+        Self {
+            state: (max_slots > 0).then(|| CacheState {
+                slots: Mutex::default(),
+                max_slots,
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records a new vote for `slot` and `bank_hash`, unless the slot is
+    /// already confirmed.
+    pub fn record_vote(&self, slot: Slot, bank_hash: Hash, vote: ConfirmationVote) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut slots = state.slots.lock().unwrap();
+        if slots.confirmed.contains_key(&slot) {
+            return;
+        }
+        slots
+            .pending
+            .entry(slot)
+            .or_default()
+            .entry(bank_hash)
+            .or_default()
+            .push(vote);
+    }
+
+    /// Records the optimistic confirmation of `slot` and `bank_hash`, retaining
+    /// the votes recorded for them as the proof of the slot and evicting the
+    /// oldest proof once `max_slots` are retained.
+    pub fn record_confirmation(&self, slot: Slot, bank_hash: Hash, total_stake: u64) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut slots = state.slots.lock().unwrap();
+        let Some(mut votes) = slots.pending.remove(&slot) else {
+            return;
+        };
+        let Some(votes) = votes.remove(&bank_hash) else {
+            return;
+        };
+        slots.confirmed.insert(
+            slot,
+            ConfirmationProof {
+                bank_hash,
+                total_stake,
+                votes,
+            },
+        );
+        while slots.confirmed.len() > state.max_slots {
+            slots.confirmed.pop_first();
+        }
+    }
+
+    /// Drops the votes of the slots older than `root`, which are never
+    /// confirmed past the root.
+    pub fn record_root(&self, root: Slot) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut slots = state.slots.lock().unwrap();
+        slots.pending = slots.pending.split_off(&root);
+    }
+
+    /// The proof of the confirmation of `slot`, if retained
+    pub fn to_rpc(&self, slot: Slot) -> Option<RpcConfirmationProof> {
+        let state = self.state.as_ref()?;
+        let slots = state.slots.lock().unwrap();
+        let proof = slots.confirmed.get(&slot)?;
+        Some(RpcConfirmationProof {
+            slot,
+            bank_hash: proof.bank_hash.to_string(),
+            total_stake: proof.total_stake,
+            confirmed_stake: proof.votes.iter().map(|vote| vote.stake).sum(),
+            votes: proof.votes.iter().map(ConfirmationVote::to_rpc).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_vote(stake: u64, voted_slot: Slot) -> ConfirmationVote {
+        ConfirmationVote {
+            vote_account: Pubkey::new_unique(),
+            stake,
+            voted_slot,
+            signature: Signature::new_unique(),
+            transaction: None,
+        }
+    }
+
+    #[test]
+    fn test_confirmation_proof_cache() {
+        let cache = ConfirmationProofCache::default();
+        assert!(!cache.is_enabled());
+        cache.record_vote(1, Hash::default(), new_vote(10, 1));
+        cache.record_confirmation(1, Hash::default(), 10);
+        assert_eq!(cache.to_rpc(1), None);
+
+        let cache = ConfirmationProofCache::new(2);
+        let (hash, other_hash) = (Hash::new_unique(), Hash::new_unique());
+        let votes = [new_vote(30, 5), new_vote(40, 6)];
+        cache.record_vote(5, hash, votes[0].clone());
+        cache.record_vote(5, other_hash, new_vote(20, 5));
+        cache.record_vote(5, hash, votes[1].clone());
+        assert_eq!(cache.to_rpc(5), None);
+        cache.record_confirmation(5, hash, 100);
+        // Votes after the confirmation are not part of the proof.
+        cache.record_vote(5, hash, new_vote(10, 5));
+        assert_eq!(
+            cache.to_rpc(5),
+            Some(RpcConfirmationProof {
+                slot: 5,
+                bank_hash: hash.to_string(),
+                total_stake: 100,
+                confirmed_stake: 70,
+                votes: votes.iter().map(ConfirmationVote::to_rpc).collect(),
+            })
+        );
+
+        // The votes of slots older than the root are dropped.
+        cache.record_vote(6, hash, new_vote(70, 6));
+        cache.record_root(7);
+        cache.record_confirmation(6, hash, 100);
+        assert_eq!(cache.to_rpc(6), None);
+
+        // The oldest proofs are evicted.
+        for slot in [8, 9] {
+            cache.record_vote(slot, hash, new_vote(70, slot));
+            cache.record_confirmation(slot, hash, 100);
+        }
+        assert_eq!(cache.to_rpc(5), None);
+        assert_eq!(cache.to_rpc(8).unwrap().votes.len(), 1);
+        assert!(cache.to_rpc(9).is_some());
+    }
+}
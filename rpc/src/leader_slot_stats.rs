@@ -0,0 +1,135 @@
+//! Banking statistics of the recent leader slots of this node, recorded by the
+//! banking stage as it commits transactions and served through
+//! `getRecentLeaderSlots`.
+
+use {
+    solana_rpc_client_api::response::RpcLeaderSlotStats,
+    solana_sdk::clock::Slot,
+    std::{collections::BTreeMap, sync::Mutex},
+};
+
+/// What the banking stage did in a leader slot
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LeaderSlotStats {
+    /// Number of transactions which attempted processing
+    pub attempted_transactions: u64,
+    pub committed_vote_transactions: u64,
+    pub committed_non_vote_transactions: u64,
+    /// Compute units used by the committed transactions
+    pub compute_units: u64,
+}
+
+impl LeaderSlotStats {
+    fn add(&mut self, other: &Self) {
+        self.attempted_transactions = self
+            .attempted_transactions
+            .saturating_add(other.attempted_transactions);
+        self.committed_vote_transactions = self
+            .committed_vote_transactions
+            .saturating_add(other.committed_vote_transactions);
+        self.committed_non_vote_transactions = self
+            .committed_non_vote_transactions
+            .saturating_add(other.committed_non_vote_transactions);
+        self.compute_units = self.compute_units.saturating_add(other.compute_units);
+    }
+
+    pub fn to_rpc(&self) -> RpcLeaderSlotStats {
+        RpcLeaderSlotStats {
+            attempted_transactions: self.attempted_transactions,
+            committed_transactions: self
+                .committed_vote_transactions
+                .saturating_add(self.committed_non_vote_transactions),
+            committed_vote_transactions: self.committed_vote_transactions,
+            committed_non_vote_transactions: self.committed_non_vote_transactions,
+            compute_units_used: self.compute_units,
+        }
+    }
+}
+
+/// The banking statistics of the most recent leader slots
+#[derive(Default)]
+pub struct LeaderSlotStatsTracker {
+    /// `None` if tracking is disabled
+    slots: Option<Mutex<BTreeMap<Slot, LeaderSlotStats>>>,
+    max_slots: usize,
+}
+
+impl LeaderSlotStatsTracker {
+    /// Creates a tracker retaining the statistics of up to `max_slots` leader
+    /// slots. A `max_slots` of zero disables tracking.
+    pub fn new(max_slots: usize) -> Self {
+        Self {
+            slots: (max_slots > 0).then(Mutex::default),
+            max_slots,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.slots.is_some()
+    }
+
+    /// Adds `stats` to the statistics of the leader slot `slot`
+    pub fn record(&self, slot: Slot, stats: &LeaderSlotStats) {
+        let Some(slots) = &self.slots else {
+            return;
+        };
+        let mut slots = slots.lock().unwrap();
+        slots.entry(slot).or_default().add(stats);
+        while slots.len() > self.max_slots {
+            slots.pop_first();
+        }
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<LeaderSlotStats> {
+        self.slots
+            .as_ref()
+            .and_then(|slots| slots.lock().unwrap().get(&slot).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_stats(attempted_transactions: u64) -> LeaderSlotStats {
+        LeaderSlotStats {
+            attempted_transactions,
+            committed_vote_transactions: 1,
+            committed_non_vote_transactions: 2,
+            compute_units: 100,
+        }
+    }
+
+    #[test]
+    fn test_disabled() {
+        let tracker = LeaderSlotStatsTracker::default();
+        assert!(!tracker.is_enabled());
+        tracker.record(1, &new_stats(3));
+        assert_eq!(tracker.get(1), None);
+    }
+
+    #[test]
+    fn test_record() {
+        let tracker = LeaderSlotStatsTracker::new(2);
+        assert!(tracker.is_enabled());
+        tracker.record(4, &new_stats(3));
+        tracker.record(4, &new_stats(5));
+        assert_eq!(
+            tracker.get(4),
+            Some(LeaderSlotStats {
+                attempted_transactions: 8,
+                committed_vote_transactions: 2,
+                committed_non_vote_transactions: 4,
+                compute_units: 200,
+            })
+        );
+        assert_eq!(tracker.get(4).unwrap().to_rpc().committed_transactions, 6);
+
+        // The oldest slots are evicted.
+        tracker.record(5, &new_stats(1));
+        tracker.record(6, &new_stats(1));
+        assert_eq!(tracker.get(4), None);
+        assert_eq!(tracker.get(5), Some(new_stats(1)));
+        assert_eq!(tracker.get(6), Some(new_stats(1)));
+    }
+}
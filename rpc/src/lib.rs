@@ -1,21 +1,37 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod account_congestion;
+pub mod account_growth;
 pub mod block_meta_service;
+pub mod buffered_transactions;
 mod cluster_tpu_info;
+pub mod commission_guard;
+pub mod confirmation_latency;
+pub mod confirmation_proofs;
 pub mod filter;
+pub mod leader_slot_stats;
 pub mod max_slots;
 pub mod optimistically_confirmed_bank_tracker;
 pub mod parsed_token_accounts;
+pub mod proxy_protocol;
+pub mod response_fields;
 pub mod rpc;
 mod rpc_cache;
 pub mod rpc_completed_slots_service;
+#[cfg(feature = "grpc")]
+pub mod rpc_grpc_service;
 pub mod rpc_health;
 pub mod rpc_pubsub;
 pub mod rpc_pubsub_service;
+pub mod rpc_rate_limiter;
 pub mod rpc_service;
 pub mod rpc_subscription_tracker;
 pub mod rpc_subscriptions;
+pub mod shred_latency;
+pub mod skipped_slot_reasons;
+pub mod slot_account_filter;
 pub mod slot_status_notifier;
 pub mod transaction_notifier_interface;
+pub mod transaction_provenance;
 pub mod transaction_status_service;
 
 #[macro_use]
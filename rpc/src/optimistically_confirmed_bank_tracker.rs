@@ -7,10 +7,19 @@
 //! BankNotification::OptimisticallyConfirmed --> SlotNotification::OptimisticallyConfirmed
 //! BankNotification::Frozen --> SlotNotification::Frozen
 //! BankNotification::NewRootedChain --> SlotNotification::Root for the roots in the chain.
+//! If slot account filters are enabled, a filter is also built for every frozen bank and sent as
+//! SlotNotification::AccountFilter.
+//! The optimistic confirmations and the new roots are also recorded by the
+//! ConfirmationLatencyTracker.
 
 use {
-    crate::rpc_subscriptions::RpcSubscriptions,
+    crate::{
+        confirmation_latency::ConfirmationLatencyTracker,
+        rpc_subscriptions::RpcSubscriptions,
+        slot_account_filter::{SlotAccountFilter, SlotAccountFilterCache},
+    },
     crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
+    solana_measure::measure_us,
     solana_rpc_client_api::response::{SlotTransactionStats, SlotUpdate},
     solana_runtime::{
         bank::Bank, bank_forks::BankForks, prioritization_fee_cache::PrioritizationFeeCache,
@@ -55,6 +64,8 @@ pub enum SlotNotification {
     Frozen((Slot, Slot)),
     /// The (Slot, Parent Slot) pair for the root slot
     Root((Slot, Slot)),
+    /// The filter of the accounts written in a frozen slot
+    AccountFilter(Arc<SlotAccountFilter>),
 }
 
 impl std::fmt::Debug for BankNotification {
@@ -95,6 +106,8 @@ impl OptimisticallyConfirmedBankTracker {
         subscriptions: Arc<RpcSubscriptions>,
         slot_notification_subscribers: Option<Arc<RwLock<Vec<SlotNotificationSender>>>>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        slot_account_filter_cache: Arc<SlotAccountFilterCache>,
+        confirmation_latency_tracker: Arc<ConfirmationLatencyTracker>,
     ) -> Self {
         let mut pending_optimistically_confirmed_banks = HashSet::new();
         let mut last_notified_confirmed_slot: Slot = 0;
@@ -118,6 +131,8 @@ impl OptimisticallyConfirmedBankTracker {
                     &mut newest_root_slot,
                     &slot_notification_subscribers,
                     &prioritization_fee_cache,
+                    &slot_account_filter_cache,
+                    &confirmation_latency_tracker,
                 ) {
                     break;
                 }
@@ -138,8 +153,26 @@ impl OptimisticallyConfirmedBankTracker {
         newest_root_slot: &mut Slot,
         slot_notification_subscribers: &Option<Arc<RwLock<Vec<SlotNotificationSender>>>>,
         prioritization_fee_cache: &PrioritizationFeeCache,
+        slot_account_filter_cache: &SlotAccountFilterCache,
+        confirmation_latency_tracker: &ConfirmationLatencyTracker,
     ) -> Result<(), RecvTimeoutError> {
         let notification = receiver.recv_timeout(Duration::from_secs(1))?;
+        match &notification {
+            BankNotification::OptimisticallyConfirmed(slot) => {
+                confirmation_latency_tracker.record_optimistic_confirmation(*slot, timestamp());
+            }
+            BankNotification::Frozen(bank) => {
+                Self::build_slot_account_filter(
+                    bank,
+                    slot_account_filter_cache,
+                    slot_notification_subscribers,
+                );
+            }
+            BankNotification::NewRootBank(bank) => {
+                confirmation_latency_tracker.record_root(bank.slot(), timestamp());
+            }
+            BankNotification::NewRootedChain(_) => {}
+        }
         Self::process_notification(
             notification,
             bank_forks,
@@ -155,6 +188,29 @@ impl OptimisticallyConfirmedBankTracker {
         Ok(())
     }
 
+    fn build_slot_account_filter(
+        bank: &Bank,
+        slot_account_filter_cache: &SlotAccountFilterCache,
+        slot_notification_subscribers: &Option<Arc<RwLock<Vec<SlotNotificationSender>>>>,
+    ) {
+        if !slot_account_filter_cache.is_enabled() {
+            return;
+        }
+        let (filter, build_us) = measure_us!(Arc::new(SlotAccountFilter::new_from_bank(bank)));
+        datapoint_debug!(
+            "slot_account_filter",
+            ("slot", filter.slot(), i64),
+            ("num_accounts", filter.num_accounts(), i64),
+            ("num_bits", filter.num_bits(), i64),
+            ("build_us", build_us, i64),
+        );
+        slot_account_filter_cache.insert(filter.clone());
+        Self::notify_slot_status(
+            slot_notification_subscribers,
+            SlotNotification::AccountFilter(filter),
+        );
+    }
+
     fn notify_slot_status(
         slot_notification_subscribers: &Option<Arc<RwLock<Vec<SlotNotificationSender>>>>,
         notification: SlotNotification,
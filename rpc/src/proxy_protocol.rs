@@ -0,0 +1,238 @@
+//! Support for version 2 of the PROXY protocol.
+//!
+//! When the node runs behind an L4 load balancer, every connection appears to
+//! originate from the load balancer. A load balancer configured to speak the
+//! PROXY protocol prefixes each connection with a binary header carrying the
+//! address of the real client, which is parsed here.
+//!
+//! See <https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt>
+
+use {
+    std::{
+        io,
+        net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+        time::Duration,
+    },
+    thiserror::Error,
+    tokio::io::{AsyncRead, AsyncReadExt},
+};
+
+/// Every version 2 header starts with this signature
+const SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+/// Length of the fixed part of the header: signature, version and command,
+/// address family and protocol, and length of the address block
+const FIXED_HEADER_LEN: usize = 16;
+/// Upper bound on the address block, which also holds any TLVs
+const MAX_ADDRESS_BLOCK_LEN: usize = 1024;
+
+/// How long a new connection may take to send its PROXY protocol header
+pub const PROXY_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+const VERSION_2: u8 = 0x2;
+const COMMAND_LOCAL: u8 = 0x0;
+const COMMAND_PROXY: u8 = 0x1;
+const FAMILY_UNSPEC: u8 = 0x0;
+const FAMILY_INET: u8 = 0x1;
+const FAMILY_INET6: u8 = 0x2;
+
+#[derive(Debug, Error)]
+pub enum ProxyProtocolError {
+    #[error("invalid PROXY protocol signature")]
+    InvalidSignature,
+    #[error("unsupported PROXY protocol version {0}")]
+    UnsupportedVersion(u8),
+    #[error("unsupported PROXY protocol command {0}")]
+    UnsupportedCommand(u8),
+    #[error("PROXY protocol address block of {0} bytes is too long")]
+    AddressBlockTooLong(usize),
+    #[error("PROXY protocol address block of {0} bytes is too short")]
+    AddressBlockTooShort(usize),
+    #[error("timed out waiting for the PROXY protocol header")]
+    Timeout,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Addresses of the original connection relayed by the proxy
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProxyHeader {
+    /// The address of the client, or `None` if the proxy did not relay one,
+    /// e.g. for its own health checks
+    pub source: Option<SocketAddr>,
+    /// The address the client connected to
+    pub destination: Option<SocketAddr>,
+}
+
+/// Reads a PROXY protocol header from the start of `stream`, leaving the
+/// stream positioned at the first byte of the proxied connection.
+pub async fn read_proxy_header<R: AsyncRead + Unpin>(
+    stream: &mut R,
+) -> Result<ProxyHeader, ProxyProtocolError> {
+    let read_header = async {
+        let mut header = [0u8; FIXED_HEADER_LEN];
+        stream.read_exact(&mut header).await?;
+        let address_block_len = parse_fixed_header(&header)?;
+        let mut address_block = vec![0u8; address_block_len];
+        stream.read_exact(&mut address_block).await?;
+        parse_address_block(&header, &address_block)
+    };
+    tokio::time::timeout(PROXY_HEADER_TIMEOUT, read_header)
+        .await
+        .map_err(|_| ProxyProtocolError::Timeout)?
+}
+
+/// Validates the fixed part of a header, returning the length of the address
+/// block that follows it
+fn parse_fixed_header(header: &[u8; FIXED_HEADER_LEN]) -> Result<usize, ProxyProtocolError> {
+    if header[..SIGNATURE.len()] != SIGNATURE {
+        return Err(ProxyProtocolError::InvalidSignature);
+    }
+    let version = header[12] >> 4;
+    if version != VERSION_2 {
+        return Err(ProxyProtocolError::UnsupportedVersion(version));
+    }
+    let address_block_len = u16::from_be_bytes([header[14], header[15]]) as usize;
+    if address_block_len > MAX_ADDRESS_BLOCK_LEN {
+        return Err(ProxyProtocolError::AddressBlockTooLong(address_block_len));
+    }
+    Ok(address_block_len)
+}
+
+fn parse_address_block(
+    header: &[u8; FIXED_HEADER_LEN],
+    address_block: &[u8],
+) -> Result<ProxyHeader, ProxyProtocolError> {
+    const UNKNOWN: ProxyHeader = ProxyHeader {
+        source: None,
+        destination: None,
+    };
+
+    match header[12] & 0x0f {
+        // Connections initiated by the proxy itself carry no addresses
+        COMMAND_LOCAL => return Ok(UNKNOWN),
+        COMMAND_PROXY => {}
+        command => return Err(ProxyProtocolError::UnsupportedCommand(command)),
+    }
+
+    // The transport protocol is ignored. Any trailing TLVs are skipped.
+    let too_short = || ProxyProtocolError::AddressBlockTooShort(address_block.len());
+    match header[13] >> 4 {
+        FAMILY_INET => {
+            let block: &[u8; 12] = address_block
+                .get(..12)
+                .and_then(|block| block.try_into().ok())
+                .ok_or_else(too_short)?;
+            let ip = |offset: usize| {
+                Ipv4Addr::new(
+                    block[offset],
+                    block[offset + 1],
+                    block[offset + 2],
+                    block[offset + 3],
+                )
+            };
+            let port = |offset: usize| u16::from_be_bytes([block[offset], block[offset + 1]]);
+            Ok(ProxyHeader {
+                source: Some(SocketAddrV4::new(ip(0), port(8)).into()),
+                destination: Some(SocketAddrV4::new(ip(4), port(10)).into()),
+            })
+        }
+        FAMILY_INET6 => {
+            let block: &[u8; 36] = address_block
+                .get(..36)
+                .and_then(|block| block.try_into().ok())
+                .ok_or_else(too_short)?;
+            let ip = |offset: usize| {
+                let octets: [u8; 16] = block[offset..offset + 16].try_into().unwrap();
+                Ipv6Addr::from(octets)
+            };
+            let port = |offset: usize| u16::from_be_bytes([block[offset], block[offset + 1]]);
+            Ok(ProxyHeader {
+                source: Some(SocketAddrV6::new(ip(0), port(32), 0, 0).into()),
+                destination: Some(SocketAddrV6::new(ip(16), port(34), 0, 0).into()),
+            })
+        }
+        FAMILY_UNSPEC => Ok(UNKNOWN),
+        // Unix sockets do not carry a usable address
+        _ => Ok(UNKNOWN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_header(command: u8, family: u8, address_block: &[u8]) -> Vec<u8> {
+        let mut header = SIGNATURE.to_vec();
+        header.push((VERSION_2 << 4) | command);
+        // Stream transport
+        header.push((family << 4) | 0x1);
+        header.extend_from_slice(&(address_block.len() as u16).to_be_bytes());
+        header.extend_from_slice(address_block);
+        header
+    }
+
+    #[tokio::test]
+    async fn test_read_proxy_header_inet() {
+        let mut address_block = vec![192, 168, 1, 10, 10, 0, 0, 1];
+        address_block.extend_from_slice(&56324u16.to_be_bytes());
+        address_block.extend_from_slice(&8900u16.to_be_bytes());
+        let mut stream = encode_header(COMMAND_PROXY, FAMILY_INET, &address_block);
+        stream.extend_from_slice(b"GET / HTTP/1.1");
+
+        let mut stream = stream.as_slice();
+        let header = read_proxy_header(&mut stream).await.unwrap();
+        assert_eq!(header.source, Some("192.168.1.10:56324".parse().unwrap()));
+        assert_eq!(header.destination, Some("10.0.0.1:8900".parse().unwrap()));
+        assert_eq!(stream, b"GET / HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_read_proxy_header_inet6_with_tlvs() {
+        let source: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let destination: Ipv6Addr = "2001:db8::2".parse().unwrap();
+        let mut address_block = source.octets().to_vec();
+        address_block.extend_from_slice(&destination.octets());
+        address_block.extend_from_slice(&443u16.to_be_bytes());
+        address_block.extend_from_slice(&8900u16.to_be_bytes());
+        // A NOOP TLV
+        address_block.extend_from_slice(&[0x04, 0x00, 0x02, 0xff, 0xff]);
+        let stream = encode_header(COMMAND_PROXY, FAMILY_INET6, &address_block);
+
+        let header = read_proxy_header(&mut stream.as_slice()).await.unwrap();
+        assert_eq!(header.source, Some("[2001:db8::1]:443".parse().unwrap()));
+        assert_eq!(
+            header.destination,
+            Some("[2001:db8::2]:8900".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_proxy_header_local() {
+        let stream = encode_header(COMMAND_LOCAL, FAMILY_UNSPEC, &[]);
+        let header = read_proxy_header(&mut stream.as_slice()).await.unwrap();
+        assert_eq!(header.source, None);
+        assert_eq!(header.destination, None);
+    }
+
+    #[tokio::test]
+    async fn test_read_proxy_header_invalid() {
+        let stream = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n".to_vec();
+        assert!(matches!(
+            read_proxy_header(&mut stream.as_slice()).await,
+            Err(ProxyProtocolError::InvalidSignature)
+        ));
+
+        let stream = encode_header(COMMAND_PROXY, FAMILY_INET, &[127, 0, 0, 1]);
+        assert!(matches!(
+            read_proxy_header(&mut stream.as_slice()).await,
+            Err(ProxyProtocolError::AddressBlockTooShort(4))
+        ));
+
+        let mut stream = encode_header(COMMAND_PROXY, FAMILY_INET, &[]);
+        stream[12] = (1 << 4) | COMMAND_PROXY;
+        assert!(matches!(
+            read_proxy_header(&mut stream.as_slice()).await,
+            Err(ProxyProtocolError::UnsupportedVersion(1))
+        ));
+    }
+}
@@ -0,0 +1,357 @@
+//! Selection of the fields of the `getBlock` and `getTransaction` responses, so callers only pay
+//! for the encoding and the egress of the parts they need.
+//!
+//! A field is named by its path of keys in the JSON response, e.g. `blockTime` or `meta.err`. The
+//! fields of the transactions of a block are named under `transactions`, e.g.
+//! `transactions.meta.err`. Selecting a field selects everything under it.
+
+use {
+    jsonrpc_core::{Error, Result},
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    solana_transaction_status::{
+        BlockEncodingOptions, TransactionDetails, TransactionStatusMeta, TransactionWithStatusMeta,
+        UiTransactionEncoding,
+    },
+};
+
+pub const MAX_RESPONSE_FIELDS: usize = 32;
+
+const BLOCK_FIELDS: &[&str] = &[
+    "previousBlockhash",
+    "blockhash",
+    "parentSlot",
+    "transactions",
+    "signatures",
+    "rewards",
+    "numRewardPartitions",
+    "blockTime",
+    "blockHeight",
+];
+
+const CONFIRMED_TRANSACTION_FIELDS: &[&str] =
+    &["slot", "transaction", "meta", "version", "blockTime"];
+
+/// The fields of each transaction of a block
+const TRANSACTION_FIELDS: &[&str] = &["transaction", "meta", "version"];
+
+const META_FIELDS: &[&str] = &[
+    "err",
+    "status",
+    "fee",
+    "preBalances",
+    "postBalances",
+    "innerInstructions",
+    "logMessages",
+    "preTokenBalances",
+    "postTokenBalances",
+    "rewards",
+    "loadedAddresses",
+    "returnData",
+    "computeUnitsConsumed",
+    "costUnits",
+];
+
+/// A response, or the selected fields of it
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SelectedFields<T> {
+    All(T),
+    Selected(Value),
+}
+
+impl<T: Serialize> SelectedFields<T> {
+    pub fn new(response: T, fields: Option<&ResponseFields>) -> Result<Self> {
+        match fields {
+            Some(fields) => fields.select(response),
+            None => Ok(Self::All(response)),
+        }
+    }
+}
+
+/// The paths of the selected fields of a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseFields {
+    paths: Vec<Vec<String>>,
+}
+
+impl ResponseFields {
+    pub fn parse_block_fields(fields: &[String]) -> Result<Self> {
+        Self::parse(fields, |path| match path {
+            [transactions, transaction_path @ ..] if transactions == "transactions" => {
+                transaction_path.is_empty()
+                    || is_transaction_path(TRANSACTION_FIELDS, transaction_path)
+            }
+            [field, ..] => BLOCK_FIELDS.contains(&field.as_str()),
+            [] => false,
+        })
+    }
+
+    pub fn parse_transaction_fields(fields: &[String]) -> Result<Self> {
+        Self::parse(fields, |path| {
+            is_transaction_path(CONFIRMED_TRANSACTION_FIELDS, path)
+        })
+    }
+
+    fn parse(fields: &[String], is_valid_path: impl Fn(&[String]) -> bool) -> Result<Self> {
+        if fields.is_empty() || fields.len() > MAX_RESPONSE_FIELDS {
+            return Err(Error::invalid_params(format!(
+                "Invalid fields: between 1 and {MAX_RESPONSE_FIELDS} fields must be provided"
+            )));
+        }
+        let paths = fields
+            .iter()
+            .map(|field| {
+                let path: Vec<String> = field.split('.').map(str::to_string).collect();
+                if path.iter().any(String::is_empty) || !is_valid_path(&path) {
+                    return Err(Error::invalid_params(format!("Invalid field: {field}")));
+                }
+                Ok(path)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { paths })
+    }
+
+    /// Returns true if the field of `path`, or a field under it, is selected
+    pub fn selects(&self, path: &[&str]) -> bool {
+        self.paths.iter().any(|selected| {
+            selected
+                .iter()
+                .zip(path)
+                .all(|(selected, component)| selected == component)
+        })
+    }
+
+    /// Adjusts the options to skip the encoding of the unselected parts of a block
+    pub fn block_encoding_options(&self, options: BlockEncodingOptions) -> BlockEncodingOptions {
+        let transaction_details = match options.transaction_details {
+            TransactionDetails::Full | TransactionDetails::Accounts
+                if !self.selects(&["transactions"]) =>
+            {
+                TransactionDetails::None
+            }
+            TransactionDetails::Signatures if !self.selects(&["signatures"]) => {
+                TransactionDetails::None
+            }
+            transaction_details => transaction_details,
+        };
+        BlockEncodingOptions {
+            transaction_details,
+            show_rewards: options.show_rewards
+                && (self.selects(&["rewards"])
+                    || self.selects(&["transactions", "meta", "rewards"])),
+            max_supported_transaction_version: options.max_supported_transaction_version,
+        }
+    }
+
+    /// Returns the encoding of the transactions under `prefix`. If the transactions themselves
+    /// are not selected, they are encoded in binary, which is cheaper than parsing them to JSON.
+    pub fn transaction_encoding(
+        &self,
+        prefix: &[&str],
+        encoding: UiTransactionEncoding,
+    ) -> UiTransactionEncoding {
+        // The status meta of JSON parsed transactions is parsed too.
+        let meta_depends_on_encoding = encoding == UiTransactionEncoding::JsonParsed
+            && self.selects(&[prefix, &["meta"][..]].concat());
+        if self.selects(&[prefix, &["transaction"][..]].concat()) || meta_depends_on_encoding {
+            encoding
+        } else {
+            UiTransactionEncoding::Base64
+        }
+    }
+
+    /// Drops the unselected sections of the status meta of a transaction under `prefix`, before
+    /// its encoding
+    pub fn trim_transaction_with_status_meta(
+        &self,
+        prefix: &[&str],
+        tx_with_meta: &mut TransactionWithStatusMeta,
+    ) {
+        if let TransactionWithStatusMeta::Complete(tx_with_meta) = tx_with_meta {
+            self.trim_status_meta(prefix, &mut tx_with_meta.meta);
+        }
+    }
+
+    fn trim_status_meta(&self, prefix: &[&str], meta: &mut TransactionStatusMeta) {
+        let selects = |field| self.selects(&[prefix, &["meta", field][..]].concat());
+        if !selects("innerInstructions") {
+            meta.inner_instructions = None;
+        }
+        if !selects("logMessages") {
+            meta.log_messages = None;
+        }
+        if !selects("preTokenBalances") {
+            meta.pre_token_balances = None;
+        }
+        if !selects("postTokenBalances") {
+            meta.post_token_balances = None;
+        }
+        if !selects("rewards") {
+            meta.rewards = None;
+        }
+        if !selects("returnData") {
+            meta.return_data = None;
+        }
+    }
+
+    /// Returns the selected fields of `response`
+    pub fn select<T: Serialize>(&self, response: T) -> Result<SelectedFields<T>> {
+        let mut value = serde_json::to_value(response).map_err(|err| Error {
+            code: jsonrpc_core::ErrorCode::InternalError,
+            message: format!("Failed to serialize response: {err}"),
+            data: None,
+        })?;
+        let paths: Vec<&[String]> = self.paths.iter().map(Vec::as_slice).collect();
+        retain_paths(&mut value, &paths);
+        Ok(SelectedFields::Selected(value))
+    }
+}
+
+fn is_transaction_path(fields: &[&str], path: &[String]) -> bool {
+    match path {
+        [meta, field, ..] if meta == "meta" => META_FIELDS.contains(&field.as_str()),
+        [field, ..] => fields.contains(&field.as_str()),
+        [] => false,
+    }
+}
+
+/// Retains the fields of `value` under any of `paths`, relative to `value`. The paths apply to
+/// each element of an array.
+fn retain_paths(value: &mut Value, paths: &[&[String]]) {
+    if paths.iter().any(|path| path.is_empty()) {
+        return;
+    }
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                retain_paths(value, paths);
+            }
+        }
+        Value::Object(object) => object.retain(|key, value| {
+            let paths: Vec<&[String]> = paths
+                .iter()
+                .filter(|path| &path[0] == key)
+                .map(|path| &path[1..])
+                .collect();
+            if paths.is_empty() {
+                return false;
+            }
+            retain_paths(value, &paths);
+            true
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    fn fields(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        assert!(ResponseFields::parse_block_fields(&fields(&[
+            "blockTime",
+            "signatures",
+            "transactions.meta.err",
+            "transactions.transaction.signatures",
+        ]))
+        .is_ok());
+        assert!(ResponseFields::parse_transaction_fields(&fields(&["slot", "meta.fee"])).is_ok());
+
+        assert!(ResponseFields::parse_block_fields(&[]).is_err());
+        assert!(ResponseFields::parse_block_fields(&fields(&["slot"])).is_err());
+        assert!(ResponseFields::parse_block_fields(&fields(&["transactions.slot"])).is_err());
+        assert!(ResponseFields::parse_block_fields(&fields(&["transactions..meta"])).is_err());
+        assert!(ResponseFields::parse_transaction_fields(&fields(&["meta.unknown"])).is_err());
+        assert!(ResponseFields::parse_transaction_fields(&fields(&["blockhash"])).is_err());
+        assert!(ResponseFields::parse_transaction_fields(&fields(&["slot"; 33])).is_err());
+    }
+
+    #[test]
+    fn test_selects() {
+        let response_fields =
+            ResponseFields::parse_block_fields(&fields(&["blockTime", "transactions.meta.err"]))
+                .unwrap();
+        assert!(response_fields.selects(&["blockTime"]));
+        assert!(response_fields.selects(&["transactions"]));
+        assert!(response_fields.selects(&["transactions", "meta"]));
+        assert!(response_fields.selects(&["transactions", "meta", "err"]));
+        assert!(!response_fields.selects(&["transactions", "meta", "fee"]));
+        assert!(!response_fields.selects(&["transactions", "transaction"]));
+        assert!(!response_fields.selects(&["rewards"]));
+
+        let options = response_fields.block_encoding_options(BlockEncodingOptions {
+            transaction_details: TransactionDetails::Full,
+            show_rewards: true,
+            max_supported_transaction_version: Some(0),
+        });
+        assert_eq!(options.transaction_details, TransactionDetails::Full);
+        assert!(!options.show_rewards);
+        assert_eq!(
+            response_fields.transaction_encoding(&["transactions"], UiTransactionEncoding::Json),
+            UiTransactionEncoding::Base64
+        );
+        assert_eq!(
+            response_fields
+                .transaction_encoding(&["transactions"], UiTransactionEncoding::JsonParsed),
+            UiTransactionEncoding::JsonParsed
+        );
+
+        let response_fields = ResponseFields::parse_block_fields(&fields(&["blockTime"])).unwrap();
+        let options = response_fields.block_encoding_options(BlockEncodingOptions {
+            transaction_details: TransactionDetails::Full,
+            show_rewards: true,
+            max_supported_transaction_version: None,
+        });
+        assert_eq!(options.transaction_details, TransactionDetails::None);
+    }
+
+    #[test]
+    fn test_trim_status_meta() {
+        let response_fields =
+            ResponseFields::parse_transaction_fields(&fields(&["meta.logMessages"])).unwrap();
+        let mut meta = TransactionStatusMeta {
+            log_messages: Some(vec!["log".to_string()]),
+            inner_instructions: Some(vec![]),
+            rewards: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        };
+        response_fields.trim_status_meta(&[], &mut meta);
+        assert_eq!(meta.log_messages, Some(vec!["log".to_string()]));
+        assert_eq!(meta.inner_instructions, None);
+        assert_eq!(meta.rewards, None);
+    }
+
+    #[test]
+    fn test_select() {
+        let block = json!({
+            "blockhash": "hash",
+            "blockTime": 42,
+            "transactions": [
+                {"transaction": ["tx1", "base64"], "meta": {"err": null, "fee": 5000}},
+                {
+                    "transaction": ["tx2", "base64"],
+                    "meta": {"err": {"InstructionError": [0, "Custom"]}, "fee": 5000},
+                },
+            ],
+        });
+        let response_fields =
+            ResponseFields::parse_block_fields(&fields(&["blockTime", "transactions.meta.err"]))
+                .unwrap();
+        assert_eq!(
+            response_fields.select(block).unwrap(),
+            SelectedFields::Selected(json!({
+                "blockTime": 42,
+                "transactions": [
+                    {"meta": {"err": null}},
+                    {"meta": {"err": {"InstructionError": [0, "Custom"]}}},
+                ],
+            }))
+        );
+    }
+}
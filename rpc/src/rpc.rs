@@ -3,15 +3,32 @@
 use solana_runtime::installed_scheduler_pool::BankWithScheduler;
 use {
     crate::{
-        filter::filter_allows, max_slots::MaxSlots,
+        account_congestion::AccountCongestionTracker,
+        account_growth::AccountGrowthTracker,
+        buffered_transactions::BufferedTransactionSampler,
+        commission_guard::CommissionGuard,
+        confirmation_latency::ConfirmationLatencyTracker,
+        confirmation_proofs::ConfirmationProofCache,
+        filter::filter_allows,
+        leader_slot_stats::LeaderSlotStatsTracker,
+        max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
-        parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        parsed_token_accounts::*,
+        response_fields::{ResponseFields, SelectedFields},
+        rpc_cache::LargestAccountsCache,
+        rpc_health::*,
+        rpc_rate_limiter::RpcRateLimitConfig,
+        shred_latency::ShredLatencyTracker,
+        skipped_slot_reasons::SkippedSlotReasonsTracker,
+        slot_account_filter::SlotAccountFilterCache,
+        transaction_provenance::TransactionProvenanceCache,
+        transaction_status_service::TransactionStatusFilter,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::{config::Options, serialize},
     crossbeam_channel::{unbounded, Receiver, Sender},
     jsonrpc_core::{
-        futures::future::{self, FutureExt, OptionFuture},
+        futures::future::{self, FutureExt},
         types::error,
         BoxFuture, Error, Metadata, Result,
     },
@@ -35,7 +52,9 @@ use {
     solana_ledger::{
         blockstore::{Blockstore, BlockstoreError, SignatureInfosForAddress},
         blockstore_meta::{PerfSample, PerfSampleV1, PerfSampleV2},
+        blockstore_tiering::{BlockstoreTieringConfig, TieredBlockStorage},
         leader_schedule_cache::LeaderScheduleCache,
+        leader_schedule_utils,
     },
     solana_metrics::inc_new_counter_info,
     solana_perf::packet::PACKET_DATA_SIZE,
@@ -47,9 +66,13 @@ use {
         request::{
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
-            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-            MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
+            MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS, MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE,
+            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_PROGRAM_CACHE_STATS_PROGRAMS,
+            MAX_GET_RECENT_LEADER_SLOTS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
+            MAX_GET_SLOT_LEADERS, MAX_GET_VOTE_ACCOUNTS_DETAILED_PUBKEYS,
+            MAX_LEADER_SCHEDULE_FORECAST_STAKE_CHANGES, MAX_MULTIPLE_ACCOUNTS,
+            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, MAX_SIMULATE_TRANSACTIONS,
+            NUM_LARGEST_ACCOUNTS,
         },
         response::{Response as RpcResponse, *},
     },
@@ -65,7 +88,7 @@ use {
     solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
-        clock::{Slot, UnixTimestamp, MAX_PROCESSING_AGE},
+        clock::{Epoch, Slot, UnixTimestamp, MAX_PROCESSING_AGE},
         commitment_config::{CommitmentConfig, CommitmentLevel},
         epoch_info::EpochInfo,
         epoch_rewards_hasher::EpochRewardsHasher,
@@ -73,9 +96,10 @@ use {
         exit::Exit,
         hash::Hash,
         message::SanitizedMessage,
+        nonce,
         pubkey::{Pubkey, PUBKEY_BYTES},
         signature::{Keypair, Signature, Signer},
-        system_instruction,
+        system_instruction, system_program,
         transaction::{
             self, AddressLoader, MessageHash, SanitizedTransaction, TransactionError,
             VersionedTransaction, MAX_TX_ACCOUNT_LOCKS,
@@ -83,7 +107,7 @@ use {
     },
     solana_send_transaction_service::send_transaction_service::TransactionInfo,
     solana_stake_program,
-    solana_storage_bigtable::Error as StorageError,
+    solana_storage_bigtable::{Error as StorageError, LongTermStorage},
     solana_transaction_context::TransactionAccount,
     solana_transaction_status::{
         map_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
@@ -128,7 +152,10 @@ use {
         send_transaction_service::Config as SendTransactionServiceConfig,
         send_transaction_service::SendTransactionService, test_utils::ClientWithCreator,
     },
-    solana_streamer::socket::SocketAddrSpace,
+    solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
+        socket::SocketAddrSpace,
+    },
 };
 
 pub mod account_resolver;
@@ -137,6 +164,9 @@ type RpcCustomResult<T> = std::result::Result<T, RpcCustomError>;
 
 pub const MAX_REQUEST_BODY_SIZE: usize = 50 * (1 << 10); // 50kB
 pub const PERFORMANCE_SAMPLES_LIMIT: usize = 720;
+/// The offset of the authority in the data of the initialized nonce accounts, after the version
+/// and the state
+const NONCE_ACCOUNT_AUTHORITY_OFFSET: usize = 8;
 
 fn new_response<T>(bank: &Bank, value: T) -> RpcResponse<T> {
     RpcResponse {
@@ -159,10 +189,23 @@ fn is_finalized(
 pub struct JsonRpcConfig {
     pub enable_rpc_transaction_history: bool,
     pub enable_extended_tx_metadata_storage: bool,
+    /// Index the signatures of the stored transactions by the programs they
+    /// invoke, for `getSignaturesForProgram`
+    pub enable_program_signature_index: bool,
+    /// Which transactions the statuses are written to the blockstore for, the
+    /// blocks containing the others cannot be served from the blockstore
+    pub transaction_status_filter: TransactionStatusFilter,
     pub faucet_addr: Option<SocketAddr>,
     pub health_check_slot_distance: u64,
+    /// The thresholds of the conditions of `getHealthDetail`
+    pub health_detail_config: RpcHealthDetailConfig,
     pub skip_preflight_health_check: bool,
     pub rpc_bigtable_config: Option<RpcBigtableConfig>,
+    /// Serve historical blocks and transactions from an object storage, in
+    /// place of BigTable
+    pub rpc_object_store_ledger_storage_config: Option<RpcObjectStoreLedgerStorageConfig>,
+    /// Offload old blocks to an object storage and serve them from it
+    pub rpc_blockstore_tiering_config: Option<BlockstoreTieringConfig>,
     pub max_multiple_accounts: Option<usize>,
     pub account_indexes: AccountSecondaryIndexes,
     pub rpc_threads: usize,
@@ -173,6 +216,13 @@ pub struct JsonRpcConfig {
     pub max_request_body_size: Option<usize>,
     /// Disable the health check, used for tests and TestValidator
     pub disable_health_check: bool,
+    /// Number of recent slots whose written account filters are kept for
+    /// `getSlotAccountFilter`; zero disables the filters
+    pub slot_account_filter_slots: usize,
+    pub rate_limit_config: RpcRateLimitConfig,
+    /// Serve the core queries over gRPC too, on this address; requires the
+    /// `grpc` feature
+    pub grpc_addr: Option<SocketAddr>,
 }
 
 impl Default for JsonRpcConfig {
@@ -180,10 +230,15 @@ impl Default for JsonRpcConfig {
         Self {
             enable_rpc_transaction_history: Default::default(),
             enable_extended_tx_metadata_storage: Default::default(),
+            enable_program_signature_index: Default::default(),
+            transaction_status_filter: TransactionStatusFilter::default(),
             faucet_addr: Option::default(),
             health_check_slot_distance: Default::default(),
+            health_detail_config: RpcHealthDetailConfig::default(),
             skip_preflight_health_check: bool::default(),
             rpc_bigtable_config: Option::default(),
+            rpc_object_store_ledger_storage_config: Option::default(),
+            rpc_blockstore_tiering_config: Option::default(),
             max_multiple_accounts: Option::default(),
             account_indexes: AccountSecondaryIndexes::default(),
             rpc_threads: 1,
@@ -193,6 +248,9 @@ impl Default for JsonRpcConfig {
             rpc_scan_and_fix_roots: Default::default(),
             max_request_body_size: Option::default(),
             disable_health_check: Default::default(),
+            slot_account_filter_slots: Default::default(),
+            rate_limit_config: RpcRateLimitConfig::default(),
+            grpc_addr: None,
         }
     }
 }
@@ -230,6 +288,15 @@ impl Default for RpcBigtableConfig {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct RpcObjectStoreLedgerStorageConfig {
+    /// Location of the object storage, as accepted by
+    /// `blockstore_tiering::object_store_from_url`
+    pub object_store_url: String,
+    /// Upload new rooted blocks into the object storage
+    pub enable_ledger_upload: bool,
+}
+
 #[derive(Clone)]
 pub struct JsonRpcRequestProcessor {
     bank_forks: Arc<RwLock<BankForks>>,
@@ -243,7 +310,9 @@ pub struct JsonRpcRequestProcessor {
     cluster_info: Arc<ClusterInfo>,
     genesis_hash: Hash,
     transaction_sender: Sender<TransactionInfo>,
-    bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+    /// BigTable, or another backend of the long-term storage of the ledger
+    bigtable_ledger_storage: Option<Arc<dyn LongTermStorage>>,
+    tiered_block_storage: Option<Arc<TieredBlockStorage>>,
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
     max_slots: Arc<MaxSlots>,
@@ -251,7 +320,20 @@ pub struct JsonRpcRequestProcessor {
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    transaction_provenance_cache: Arc<TransactionProvenanceCache>,
+    slot_account_filter_cache: Arc<SlotAccountFilterCache>,
+    shred_latency_tracker: Arc<ShredLatencyTracker>,
+    leader_slot_stats_tracker: Arc<LeaderSlotStatsTracker>,
+    confirmation_latency_tracker: Arc<ConfirmationLatencyTracker>,
+    skipped_slot_reasons_tracker: Arc<SkippedSlotReasonsTracker>,
+    account_congestion_tracker: Arc<AccountCongestionTracker>,
+    confirmation_proof_cache: Arc<ConfirmationProofCache>,
+    buffered_transaction_sampler: Arc<BufferedTransactionSampler>,
+    account_growth_tracker: Arc<AccountGrowthTracker>,
+    commission_guard: Option<Arc<CommissionGuard>>,
     runtime: Arc<Runtime>,
+    /// Identifies the client of the request, for the rate limits of the methods
+    pub(crate) rate_limit_key: Option<Arc<str>>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
 
@@ -340,7 +422,7 @@ impl JsonRpcRequestProcessor {
     }
 
     #[allow(deprecated)]
-    fn bank(&self, commitment: Option<CommitmentConfig>) -> Arc<Bank> {
+    pub(crate) fn bank(&self, commitment: Option<CommitmentConfig>) -> Arc<Bank> {
         debug!("RPC commitment_config: {:?}", commitment);
 
         let commitment = commitment.unwrap_or_default();
@@ -407,7 +489,8 @@ impl JsonRpcRequestProcessor {
         health: Arc<RpcHealth>,
         cluster_info: Arc<ClusterInfo>,
         genesis_hash: Hash,
-        bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+        bigtable_ledger_storage: Option<Arc<dyn LongTermStorage>>,
+        tiered_block_storage: Option<Arc<TieredBlockStorage>>,
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
         max_slots: Arc<MaxSlots>,
@@ -415,6 +498,17 @@ impl JsonRpcRequestProcessor {
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        transaction_provenance_cache: Arc<TransactionProvenanceCache>,
+        slot_account_filter_cache: Arc<SlotAccountFilterCache>,
+        shred_latency_tracker: Arc<ShredLatencyTracker>,
+        leader_slot_stats_tracker: Arc<LeaderSlotStatsTracker>,
+        confirmation_latency_tracker: Arc<ConfirmationLatencyTracker>,
+        skipped_slot_reasons_tracker: Arc<SkippedSlotReasonsTracker>,
+        account_congestion_tracker: Arc<AccountCongestionTracker>,
+        confirmation_proof_cache: Arc<ConfirmationProofCache>,
+        buffered_transaction_sampler: Arc<BufferedTransactionSampler>,
+        account_growth_tracker: Arc<AccountGrowthTracker>,
+        commission_guard: Option<Arc<CommissionGuard>>,
         runtime: Arc<Runtime>,
     ) -> (Self, Receiver<TransactionInfo>) {
         let (transaction_sender, transaction_receiver) = unbounded();
@@ -431,6 +525,7 @@ impl JsonRpcRequestProcessor {
                 genesis_hash,
                 transaction_sender,
                 bigtable_ledger_storage,
+                tiered_block_storage,
                 optimistically_confirmed_bank,
                 largest_accounts_cache,
                 max_slots,
@@ -438,7 +533,19 @@ impl JsonRpcRequestProcessor {
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
                 prioritization_fee_cache,
+                transaction_provenance_cache,
+                slot_account_filter_cache,
+                shred_latency_tracker,
+                leader_slot_stats_tracker,
+                confirmation_latency_tracker,
+                skipped_slot_reasons_tracker,
+                account_congestion_tracker,
+                confirmation_proof_cache,
+                buffered_transaction_sampler,
+                account_growth_tracker,
+                commission_guard,
                 runtime,
+                rate_limit_key: None,
             },
             transaction_receiver,
         )
@@ -519,6 +626,7 @@ impl JsonRpcRequestProcessor {
             genesis_hash,
             transaction_sender,
             bigtable_ledger_storage: None,
+            tiered_block_storage: None,
             optimistically_confirmed_bank,
             largest_accounts_cache: Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             max_slots: Arc::new(MaxSlots::default()),
@@ -526,7 +634,19 @@ impl JsonRpcRequestProcessor {
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             max_complete_rewards_slot: Arc::new(AtomicU64::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
+            transaction_provenance_cache: Arc::new(TransactionProvenanceCache::default()),
+            slot_account_filter_cache: Arc::new(SlotAccountFilterCache::default()),
+            shred_latency_tracker: Arc::new(ShredLatencyTracker::default()),
+            leader_slot_stats_tracker: Arc::new(LeaderSlotStatsTracker::default()),
+            confirmation_latency_tracker: Arc::new(ConfirmationLatencyTracker::default()),
+            skipped_slot_reasons_tracker: Arc::new(SkippedSlotReasonsTracker::default()),
+            account_congestion_tracker: Arc::new(AccountCongestionTracker::default()),
+            confirmation_proof_cache: Arc::new(ConfirmationProofCache::default()),
+            buffered_transaction_sampler: Arc::new(BufferedTransactionSampler::default()),
+            account_growth_tracker: Arc::new(AccountGrowthTracker::default()),
+            commission_guard: None,
             runtime,
+            rate_limit_key: None,
         }
     }
 
@@ -606,7 +726,8 @@ impl JsonRpcRequestProcessor {
         mut filters: Vec<RpcFilterType>,
         with_context: bool,
         sort_results: bool,
-    ) -> Result<OptionalContext<Vec<RpcKeyedAccount>>> {
+        page: Option<ProgramAccountsPage>,
+    ) -> Result<OptionalContext<RpcProgramAccounts>> {
         let RpcAccountInfoConfig {
             encoding,
             data_slice: data_slice_config,
@@ -619,6 +740,8 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
+        // The page is sorted by itself, which is cheaper than sorting all the accounts.
+        let sort_results = sort_results && page.is_none();
         let keyed_accounts = {
             if let Some(owner) = get_spl_token_owner_filter(&program_id, &filters) {
                 self.get_filtered_spl_token_accounts_by_owner(
@@ -648,6 +771,10 @@ impl JsonRpcRequestProcessor {
                 .await?
             }
         };
+        let (keyed_accounts, next_cursor) = match &page {
+            Some(page) => page.select(keyed_accounts),
+            None => (keyed_accounts, None),
+        };
         let accounts = if is_known_spl_token_id(&program_id)
             && encoding == UiAccountEncoding::JsonParsed
         {
@@ -663,6 +790,13 @@ impl JsonRpcRequestProcessor {
                 })
                 .collect::<Result<Vec<_>>>()?
         };
+        let accounts = match page {
+            Some(_) => RpcProgramAccounts::Page(RpcKeyedAccountsPage {
+                accounts,
+                next_cursor,
+            }),
+            None => RpcProgramAccounts::All(accounts),
+        };
         Ok(match with_context {
             true => OptionalContext::Context(new_response(&bank, accounts)),
             false => OptionalContext::NoContext(accounts),
@@ -716,6 +850,66 @@ impl JsonRpcRequestProcessor {
                 .saturating_sub(1),
         };
 
+        self.get_epoch_inflation_reward(&addresses, epoch, context_config, first_available_block)
+            .await
+    }
+
+    /// Returns the inflation rewards of `addresses` over the epochs from `start_epoch` to
+    /// `config.end_epoch`, or the last completed epoch, omitting the epochs without a reward
+    pub async fn get_inflation_reward_history(
+        &self,
+        addresses: Vec<Pubkey>,
+        start_epoch: Epoch,
+        config: Option<RpcInflationRewardHistoryConfig>,
+    ) -> Result<Vec<Vec<RpcInflationReward>>> {
+        let config = config.unwrap_or_default();
+        let epoch_schedule = self.get_epoch_schedule();
+        let context_config = RpcContextConfig {
+            commitment: config.commitment,
+            min_context_slot: config.min_context_slot,
+        };
+        let last_completed_epoch = epoch_schedule
+            .get_epoch(self.get_slot(context_config)?)
+            .saturating_sub(1);
+        let end_epoch = config.end_epoch.unwrap_or(last_completed_epoch);
+        if end_epoch < start_epoch {
+            return Err(Error::invalid_params(format!(
+                "end epoch {end_epoch} is before start epoch {start_epoch}"
+            )));
+        }
+        if end_epoch - start_epoch >= MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS {
+            return Err(Error::invalid_params(format!(
+                "Epoch range too large; max {MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS}"
+            )));
+        }
+        let first_available_block = self.get_first_available_block().await;
+
+        let mut history = vec![vec![]; addresses.len()];
+        for epoch in start_epoch..=end_epoch {
+            let rewards = self
+                .get_epoch_inflation_reward(
+                    &addresses,
+                    epoch,
+                    context_config,
+                    first_available_block,
+                )
+                .await?;
+            for (address_history, reward) in history.iter_mut().zip(rewards) {
+                address_history.extend(reward);
+            }
+        }
+        Ok(history)
+    }
+
+    async fn get_epoch_inflation_reward(
+        &self,
+        addresses: &[Pubkey],
+        epoch: Epoch,
+        context_config: RpcContextConfig,
+        first_available_block: Slot,
+    ) -> Result<Vec<Option<RpcInflationReward>>> {
+        let epoch_schedule = self.get_epoch_schedule();
+
         // Rewards for this epoch are found in the first confirmed block of the next epoch
         let first_slot_in_epoch = epoch_schedule.get_first_slot_in_epoch(epoch.saturating_add(1));
         if first_slot_in_epoch < first_available_block {
@@ -749,7 +943,8 @@ impl JsonRpcRequestProcessor {
         let Ok(Some(epoch_boundary_block)) = self
             .get_block(
                 first_confirmed_block_in_epoch,
-                Some(RpcBlockConfig::rewards_with_commitment(config.commitment).into()),
+                Some(RpcBlockConfig::rewards_with_commitment(context_config.commitment).into()),
+                None,
             )
             .await
         else {
@@ -854,7 +1049,11 @@ impl JsonRpcRequestProcessor {
                 let Ok(Some(block)) = self
                     .get_block(
                         slot,
-                        Some(RpcBlockConfig::rewards_with_commitment(config.commitment).into()),
+                        Some(
+                            RpcBlockConfig::rewards_with_commitment(context_config.commitment)
+                                .into(),
+                        ),
+                        None,
                     )
                     .await
                 else {
@@ -1139,6 +1338,55 @@ impl JsonRpcRequestProcessor {
         ))
     }
 
+    fn get_leader_schedule_forecast(
+        &self,
+        config: RpcLeaderScheduleForecastConfig,
+    ) -> Result<RpcLeaderScheduleForecast> {
+        let identity = config.identity.as_deref().map(verify_pubkey).transpose()?;
+        if config.stake_changes.len() > MAX_LEADER_SCHEDULE_FORECAST_STAKE_CHANGES {
+            return Err(Error::invalid_params(format!(
+                "Too many stake changes; max {MAX_LEADER_SCHEDULE_FORECAST_STAKE_CHANGES}"
+            )));
+        }
+
+        let bank = self.bank(config.commitment);
+        // The schedules up to the leader schedule epoch are fixed by their epoch stakes
+        let first_forecast_epoch = bank.get_leader_schedule_epoch(bank.slot()) + 1;
+        let epoch = config.epoch.unwrap_or(first_forecast_epoch);
+        if epoch < first_forecast_epoch {
+            return Err(Error::invalid_params(format!(
+                "The leader schedule of epoch {epoch} is fixed, see getLeaderSchedule; the first \
+                 epoch which can be forecast is {first_forecast_epoch}"
+            )));
+        }
+
+        let mut vote_accounts = (*bank.vote_accounts()).clone();
+        for stake_change in &config.stake_changes {
+            let vote_pubkey = verify_pubkey(&stake_change.vote_pubkey)?;
+            let (stake, _) = vote_accounts.get_mut(&vote_pubkey).ok_or_else(|| {
+                Error::invalid_params(format!("Unknown vote account {vote_pubkey}"))
+            })?;
+            *stake = stake.saturating_add_signed(stake_change.lamports);
+        }
+        let total_stake = vote_accounts.values().map(|(stake, _)| *stake).sum();
+        let leader_schedule =
+            leader_schedule_utils::leader_schedule_from_vote_accounts(epoch, &bank, &vote_accounts)
+                .ok_or_else(|| Error::invalid_params("No vote account is staked"))?;
+        let mut schedule = leader_schedule_utils::leader_schedule_by_identity(
+            leader_schedule.get_slot_leaders().iter().enumerate(),
+        );
+        if let Some(identity) = identity {
+            let identity = identity.to_string();
+            schedule.retain(|leader, _| *leader == identity);
+        }
+        Ok(RpcLeaderScheduleForecast {
+            epoch,
+            first_slot: bank.epoch_schedule().get_first_slot_in_epoch(epoch),
+            total_stake,
+            schedule,
+        })
+    }
+
     fn get_vote_accounts(
         &self,
         config: Option<RpcGetVoteAccountsConfig>,
@@ -1218,6 +1466,87 @@ impl JsonRpcRequestProcessor {
         })
     }
 
+    /// Returns the parsed vote states of the vote accounts of the bank which match the vote and
+    /// node pubkeys of `config`, if any, in decreasing order of activated stake
+    fn get_vote_accounts_detailed(
+        &self,
+        config: Option<RpcGetVoteAccountsDetailedConfig>,
+    ) -> Result<Vec<RpcVoteAccountDetailed>> {
+        let config = config.unwrap_or_default();
+        let verify_pubkeys = |pubkey_strs: Option<Vec<String>>| {
+            pubkey_strs
+                .map(|pubkey_strs| {
+                    if pubkey_strs.len() > MAX_GET_VOTE_ACCOUNTS_DETAILED_PUBKEYS {
+                        return Err(Error::invalid_params(format!(
+                            "Too many inputs provided; max {MAX_GET_VOTE_ACCOUNTS_DETAILED_PUBKEYS}"
+                        )));
+                    }
+                    pubkey_strs
+                        .iter()
+                        .map(|pubkey_str| verify_pubkey(pubkey_str))
+                        .collect::<Result<HashSet<_>>>()
+                })
+                .transpose()
+        };
+        let filter_by_vote_pubkeys = verify_pubkeys(config.vote_pubkeys)?;
+        let filter_by_node_pubkeys = verify_pubkeys(config.node_pubkeys)?;
+
+        let bank = self.bank(config.commitment);
+        let epoch = bank.epoch();
+        let vote_accounts = bank.vote_accounts();
+        let epoch_vote_accounts = bank
+            .epoch_vote_accounts(epoch)
+            .ok_or_else(Error::invalid_request)?;
+        let mut vote_accounts_detailed: Vec<_> = vote_accounts
+            .iter()
+            .filter(|(vote_pubkey, (_, account))| {
+                filter_by_vote_pubkeys
+                    .as_ref()
+                    .is_none_or(|vote_pubkeys| vote_pubkeys.contains(*vote_pubkey))
+                    && filter_by_node_pubkeys
+                        .as_ref()
+                        .is_none_or(|node_pubkeys| node_pubkeys.contains(account.node_pubkey()))
+            })
+            .map(|(vote_pubkey, (activated_stake, account))| {
+                let vote_state_view = account.vote_state_view();
+                let last_timestamp = vote_state_view.last_timestamp();
+                RpcVoteAccountDetailed {
+                    vote_pubkey: vote_pubkey.to_string(),
+                    node_pubkey: vote_state_view.node_pubkey().to_string(),
+                    activated_stake: *activated_stake,
+                    commission: vote_state_view.commission(),
+                    epoch_vote_account: epoch_vote_accounts.contains_key(vote_pubkey),
+                    authorized_voter: vote_state_view
+                        .get_authorized_voter(epoch)
+                        .map(|authorized_voter| authorized_voter.to_string()),
+                    votes: vote_state_view
+                        .votes_iter()
+                        .map(|lockout| RpcVoteLockout {
+                            slot: lockout.slot(),
+                            confirmation_count: lockout.confirmation_count(),
+                        })
+                        .collect(),
+                    root_slot: vote_state_view.root_slot(),
+                    epoch_credits: vote_state_view
+                        .epoch_credits_iter()
+                        .map(Into::into)
+                        .collect(),
+                    credits: vote_state_view.credits(),
+                    last_timestamp: RpcVoteTimestamp {
+                        slot: last_timestamp.slot,
+                        timestamp: last_timestamp.timestamp,
+                    },
+                }
+            })
+            .collect();
+        vote_accounts_detailed.sort_unstable_by(|a, b| {
+            b.activated_stake
+                .cmp(&a.activated_stake)
+                .then_with(|| a.vote_pubkey.cmp(&b.vote_pubkey))
+        });
+        Ok(vote_accounts_detailed)
+    }
+
     fn check_blockstore_root<T>(
         &self,
         result: &std::result::Result<T, BlockstoreError>,
@@ -1263,6 +1592,35 @@ impl JsonRpcRequestProcessor {
         Ok(())
     }
 
+    // The blocks containing transactions whose statuses were filtered out are
+    // missing some of their metadata.
+    fn check_transaction_status_filtered<T>(
+        &self,
+        result: &std::result::Result<T, BlockstoreError>,
+        slot: Slot,
+    ) -> Result<()> {
+        if let Err(BlockstoreError::MissingTransactionMetadata) = result {
+            if !self.config.transaction_status_filter.is_complete() {
+                return Err(RpcCustomError::TransactionStatusFiltered { slot }.into());
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the block of the slot from the object storage the blockstore
+    // offloads old blocks to, if any.
+    async fn get_tiered_block(&self, slot: Slot) -> Option<ConfirmedBlock> {
+        let tiered_block_storage = self.tiered_block_storage.clone()?;
+        self.runtime
+            .spawn_blocking(move || tiered_block_storage.get_confirmed_block(slot))
+            .await
+            .expect("Failed to spawn blocking task")
+            .unwrap_or_else(|err| {
+                warn!("Failed to get block {slot} from the blockstore tiering: {err}");
+                None
+            })
+    }
+
     fn check_bigtable_result<T>(
         &self,
         result: &std::result::Result<T, solana_storage_bigtable::Error>,
@@ -1286,23 +1644,56 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    /// Returns the block of `slot`, skipping the encoding of the parts of it which are not
+    /// selected by `fields`
     pub async fn get_block(
         &self,
         slot: Slot,
         config: Option<RpcEncodingConfigWrapper<RpcBlockConfig>>,
+        fields: Option<ResponseFields>,
     ) -> Result<Option<UiConfirmedBlock>> {
-        self.check_if_transaction_history_enabled()?;
-
         let config = config
             .map(|config| config.convert_to_current())
             .unwrap_or_default();
-        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Json);
-        let encoding_options = BlockEncodingOptions {
+        let mut encoding = config.encoding.unwrap_or(UiTransactionEncoding::Json);
+        let mut encoding_options = BlockEncodingOptions {
             transaction_details: config.transaction_details.unwrap_or_default(),
             show_rewards: config.rewards.unwrap_or(true),
             max_supported_transaction_version: config.max_supported_transaction_version,
         };
+        if let Some(fields) = &fields {
+            encoding_options = fields.block_encoding_options(encoding_options);
+            encoding = fields.transaction_encoding(&["transactions"], encoding);
+        }
         let commitment = config.commitment.unwrap_or_default();
+        let Some(mut confirmed_block) = self.get_confirmed_block(slot, commitment).await? else {
+            return Ok(None);
+        };
+        let encoded_block = self
+            .runtime
+            .spawn_blocking(move || {
+                if let Some(fields) = &fields {
+                    for tx_with_meta in &mut confirmed_block.transactions {
+                        fields.trim_transaction_with_status_meta(&["transactions"], tx_with_meta);
+                    }
+                }
+                confirmed_block
+                    .encode_with_options(encoding, encoding_options)
+                    .map_err(RpcCustomError::from)
+            })
+            .await
+            .expect("Failed to spawn blocking task")?;
+        Ok(Some(encoded_block))
+    }
+
+    /// Returns the block of `slot` at `commitment`, from the blockstore or
+    /// else from the long-term storages
+    pub(crate) async fn get_confirmed_block(
+        &self,
+        slot: Slot,
+        commitment: CommitmentConfig,
+    ) -> Result<Option<ConfirmedBlock>> {
+        self.check_if_transaction_history_enabled()?;
         check_is_at_least_confirmed(commitment)?;
 
         // Block is old enough to be finalized
@@ -1323,38 +1714,26 @@ impl JsonRpcRequestProcessor {
                 .await
                 .expect("Failed to spawn blocking task");
             self.check_blockstore_root(&result, slot)?;
-            let encode_block = |confirmed_block: ConfirmedBlock| async move {
-                let mut encoded_block = self
-                    .runtime
-                    .spawn_blocking(move || {
-                        confirmed_block
-                            .encode_with_options(encoding, encoding_options)
-                            .map_err(RpcCustomError::from)
-                    })
-                    .await
-                    .expect("Failed to spawn blocking task")?;
+            let with_genesis_time = |mut confirmed_block: ConfirmedBlock| {
                 if slot == 0 {
-                    encoded_block.block_time = Some(self.genesis_creation_time());
-                    encoded_block.block_height = Some(0);
+                    confirmed_block.block_time = Some(self.genesis_creation_time());
+                    confirmed_block.block_height = Some(0);
                 }
-                Ok::<UiConfirmedBlock, Error>(encoded_block)
+                confirmed_block
             };
             if result.is_err() {
+                if let Some(confirmed_block) = self.get_tiered_block(slot).await {
+                    return Ok(Some(with_genesis_time(confirmed_block)));
+                }
                 if let Some(bigtable_ledger_storage) = &self.bigtable_ledger_storage {
                     let bigtable_result = bigtable_ledger_storage.get_confirmed_block(slot).await;
                     self.check_bigtable_result(&bigtable_result)?;
-                    let encoded_block_future: OptionFuture<_> =
-                        bigtable_result.ok().map(encode_block).into();
-                    return encoded_block_future.await.transpose();
+                    return Ok(bigtable_result.ok().map(with_genesis_time));
                 }
             }
             self.check_slot_cleaned_up(&result, slot)?;
-            let encoded_block_future: OptionFuture<_> = result
-                .ok()
-                .map(ConfirmedBlock::from)
-                .map(encode_block)
-                .into();
-            return encoded_block_future.await.transpose();
+            self.check_transaction_status_filtered(&result, slot)?;
+            return Ok(result.ok().map(ConfirmedBlock::from).map(with_genesis_time));
         } else if commitment.is_confirmed() {
             // Check if block is confirmed
             let confirmed_bank = self.bank(Some(CommitmentConfig::confirmed()));
@@ -1368,10 +1747,11 @@ impl JsonRpcRequestProcessor {
                     })
                     .await
                     .expect("Failed to spawn blocking task");
-                let encoded_block_future: OptionFuture<_> = result
+                self.check_transaction_status_filtered(&result, slot)?;
+                return Ok(result
                     .ok()
                     .map(ConfirmedBlock::from)
-                    .map(|mut confirmed_block| async move {
+                    .map(|mut confirmed_block| {
                         if confirmed_block.block_time.is_none()
                             || confirmed_block.block_height.is_none()
                         {
@@ -1385,20 +1765,8 @@ impl JsonRpcRequestProcessor {
                                 }
                             }
                         }
-                        let encoded_block = self
-                            .runtime
-                            .spawn_blocking(move || {
-                                confirmed_block
-                                    .encode_with_options(encoding, encoding_options)
-                                    .map_err(RpcCustomError::from)
-                            })
-                            .await
-                            .expect("Failed to spawn blocking task")?;
-
-                        Ok(encoded_block)
-                    })
-                    .into();
-                return encoded_block_future.await.transpose();
+                        confirmed_block
+                    }));
             }
         }
 
@@ -1592,6 +1960,9 @@ impl JsonRpcRequestProcessor {
             let result = self.blockstore.get_rooted_block_time(slot);
             self.check_blockstore_root(&result, slot)?;
             if result.is_err() {
+                if let Some(confirmed_block) = self.get_tiered_block(slot).await {
+                    return Ok(confirmed_block.block_time);
+                }
                 if let Some(bigtable_ledger_storage) = &self.bigtable_ledger_storage {
                     let bigtable_result = bigtable_ledger_storage.get_confirmed_block(slot).await;
                     self.check_bigtable_result(&bigtable_result)?;
@@ -1680,6 +2051,7 @@ impl JsonRpcRequestProcessor {
                             confirmations: None,
                             err,
                             confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                            provenance: self.transaction_provenance_cache.get(&signature),
                         }
                     })
                 {
@@ -1735,20 +2107,27 @@ impl JsonRpcRequestProcessor {
             } else {
                 Some(TransactionConfirmationStatus::Processed)
             },
+            provenance: self.transaction_provenance_cache.get(&signature),
         })
     }
 
+    /// Returns the transaction of `signature`, skipping the encoding of the parts of it which are
+    /// not selected by `fields`
     pub async fn get_transaction(
         &self,
         signature: Signature,
         config: Option<RpcEncodingConfigWrapper<RpcTransactionConfig>>,
+        fields: Option<ResponseFields>,
     ) -> Result<Option<EncodedConfirmedTransactionWithStatusMeta>> {
         self.check_if_transaction_history_enabled()?;
 
         let config = config
             .map(|config| config.convert_to_current())
             .unwrap_or_default();
-        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Json);
+        let mut encoding = config.encoding.unwrap_or(UiTransactionEncoding::Json);
+        if let Some(fields) = &fields {
+            encoding = fields.transaction_encoding(&[], encoding);
+        }
         let max_supported_transaction_version = config.max_supported_transaction_version;
         let commitment = config.commitment.unwrap_or_default();
         check_is_at_least_confirmed(commitment)?;
@@ -1772,7 +2151,10 @@ impl JsonRpcRequestProcessor {
             .expect("Failed to spawn blocking task");
 
         let encode_transaction =
-                |confirmed_tx_with_meta: ConfirmedTransactionWithStatusMeta| -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+                |mut confirmed_tx_with_meta: ConfirmedTransactionWithStatusMeta| -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+                    if let Some(fields) = &fields {
+                        fields.trim_transaction_with_status_meta(&[], &mut confirmed_tx_with_meta.tx_with_meta);
+                    }
                     Ok(confirmed_tx_with_meta.encode(encoding, max_supported_transaction_version).map_err(RpcCustomError::from)?)
                 };
 
@@ -1827,27 +2209,8 @@ impl JsonRpcRequestProcessor {
     ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
         self.check_if_transaction_history_enabled()?;
 
-        let commitment = config.commitment.unwrap_or_default();
-        check_is_at_least_confirmed(commitment)?;
-
-        let highest_super_majority_root = self
-            .block_commitment_cache
-            .read()
-            .unwrap()
-            .highest_super_majority_root();
-        let highest_slot = if commitment.is_confirmed() {
-            let confirmed_bank = self.get_bank_with_config(config)?;
-            confirmed_bank.slot()
-        } else {
-            let min_context_slot = config.min_context_slot.unwrap_or_default();
-            if highest_super_majority_root < min_context_slot {
-                return Err(RpcCustomError::MinContextSlotNotReached {
-                    context_slot: highest_super_majority_root,
-                }
-                .into());
-            }
-            highest_super_majority_root
-        };
+        let (highest_slot, highest_super_majority_root) =
+            self.get_signatures_highest_slot(config)?;
 
         let SignatureInfosForAddress {
             infos: mut results,
@@ -1857,27 +2220,6 @@ impl JsonRpcRequestProcessor {
             .get_confirmed_signatures_for_address2(address, highest_slot, before, until, limit)
             .map_err(|err| Error::invalid_params(format!("{err}")))?;
 
-        let map_results = |results: Vec<ConfirmedTransactionStatusWithSignature>| {
-            results
-                .into_iter()
-                .map(|x| {
-                    let mut item: RpcConfirmedTransactionStatusWithSignature = x.into();
-                    if item.slot <= highest_super_majority_root {
-                        item.confirmation_status = Some(TransactionConfirmationStatus::Finalized);
-                    } else {
-                        item.confirmation_status = Some(TransactionConfirmationStatus::Confirmed);
-                        if item.block_time.is_none() {
-                            let r_bank_forks = self.bank_forks.read().unwrap();
-                            item.block_time = r_bank_forks
-                                .get(item.slot)
-                                .map(|bank| bank.clock().unix_timestamp);
-                        }
-                    }
-                    item
-                })
-                .collect()
-        };
-
         if results.len() < limit {
             if let Some(bigtable_ledger_storage) = &self.bigtable_ledger_storage {
                 let mut bigtable_before = before;
@@ -1937,7 +2279,83 @@ impl JsonRpcRequestProcessor {
             }
         }
 
-        Ok(map_results(results))
+        Ok(self.map_signature_infos(results, highest_super_majority_root))
+    }
+
+    pub fn get_signatures_for_program(
+        &self,
+        program_id: Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+        config: RpcContextConfig,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        self.check_if_transaction_history_enabled()?;
+        if !self.config.enable_program_signature_index {
+            return Err(RpcCustomError::ProgramSignatureIndexNotAvailable.into());
+        }
+
+        let (highest_slot, highest_super_majority_root) =
+            self.get_signatures_highest_slot(config)?;
+
+        let SignatureInfosForAddress { infos, .. } = self
+            .blockstore
+            .get_confirmed_signatures_for_program(program_id, highest_slot, before, until, limit)
+            .map_err(|err| Error::invalid_params(format!("{err}")))?;
+
+        Ok(self.map_signature_infos(infos, highest_super_majority_root))
+    }
+
+    // Returns the highest slot whose signatures are listed for the commitment
+    // of the request, and the highest super majority root.
+    fn get_signatures_highest_slot(&self, config: RpcContextConfig) -> Result<(Slot, Slot)> {
+        let commitment = config.commitment.unwrap_or_default();
+        check_is_at_least_confirmed(commitment)?;
+
+        let highest_super_majority_root = self
+            .block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root();
+        let highest_slot = if commitment.is_confirmed() {
+            let confirmed_bank = self.get_bank_with_config(config)?;
+            confirmed_bank.slot()
+        } else {
+            let min_context_slot = config.min_context_slot.unwrap_or_default();
+            if highest_super_majority_root < min_context_slot {
+                return Err(RpcCustomError::MinContextSlotNotReached {
+                    context_slot: highest_super_majority_root,
+                }
+                .into());
+            }
+            highest_super_majority_root
+        };
+        Ok((highest_slot, highest_super_majority_root))
+    }
+
+    fn map_signature_infos(
+        &self,
+        results: Vec<ConfirmedTransactionStatusWithSignature>,
+        highest_super_majority_root: Slot,
+    ) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        results
+            .into_iter()
+            .map(|x| {
+                let mut item: RpcConfirmedTransactionStatusWithSignature = x.into();
+                if item.slot <= highest_super_majority_root {
+                    item.confirmation_status = Some(TransactionConfirmationStatus::Finalized);
+                } else {
+                    item.confirmation_status = Some(TransactionConfirmationStatus::Confirmed);
+                    if item.block_time.is_none() {
+                        let r_bank_forks = self.bank_forks.read().unwrap();
+                        item.block_time = r_bank_forks
+                            .get(item.slot)
+                            .map(|bank| bank.clock().unix_timestamp);
+                    }
+                }
+                item
+            })
+            .collect()
     }
 
     pub async fn get_first_available_block(&self) -> Slot {
@@ -2199,48 +2617,150 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, accounts))
     }
 
-    /// Use a set of filters to get an iterator of keyed program accounts from a bank
-    async fn get_filtered_program_accounts(
+    pub async fn get_nonce_accounts_by_authority(
         &self,
-        bank: Arc<Bank>,
-        program_id: Pubkey,
-        mut filters: Vec<RpcFilterType>,
+        authority: Pubkey,
+        config: Option<RpcAccountInfoConfig>,
         sort_results: bool,
-    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
-        optimize_filters(&mut filters);
-        if self
+    ) -> Result<RpcResponse<Vec<RpcKeyedAccount>>> {
+        let RpcAccountInfoConfig {
+            encoding,
+            data_slice: data_slice_config,
+            commitment,
+            min_context_slot,
+        } = config.unwrap_or_default();
+        let bank = self.get_bank_with_config(RpcContextConfig {
+            commitment,
+            min_context_slot,
+        })?;
+        let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
+
+        // The by-authority accounts index checks for the nonce state and authority on inclusion,
+        // but an account may remain in it after its authority changed, so the filters are applied
+        // to the indexed accounts too.
+        let filters = vec![
+            RpcFilterType::DataSize(nonce::State::size() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                NONCE_ACCOUNT_AUTHORITY_OFFSET,
+                authority.to_bytes().into(),
+            )),
+        ];
+        let keyed_accounts = if self
             .config
             .account_indexes
-            .contains(&AccountIndex::ProgramId)
+            .contains(&AccountIndex::NonceAuthority)
         {
-            if !self.config.account_indexes.include_key(&program_id) {
+            if !self.config.account_indexes.include_key(&authority) {
                 return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
-                    index_key: program_id.to_string(),
-                });
+                    index_key: authority.to_string(),
+                }
+                .into());
             }
             self.get_filtered_indexed_accounts(
                 &bank,
-                &IndexKey::ProgramId(program_id),
-                &program_id,
+                &IndexKey::NonceAuthority(authority),
+                &system_program::id(),
                 filters,
                 sort_results,
             )
             .await
             .map_err(|e| RpcCustomError::ScanError {
                 message: e.to_string(),
-            })
+            })?
         } else {
-            // this path does not need to provide a mb limit because we only want to support secondary indexes
-            let scan_order = if sort_results {
-                ScanOrder::Sorted
-            } else {
-                ScanOrder::Unsorted
-            };
-            self.runtime
-                .spawn_blocking(move || {
-                    bank.get_filtered_program_accounts(
-                        &program_id,
-                        |account: &AccountSharedData| {
+            self.get_filtered_program_accounts(
+                Arc::clone(&bank),
+                system_program::id(),
+                filters,
+                sort_results,
+            )
+            .await?
+        };
+        let accounts = keyed_accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                Ok(RpcKeyedAccount {
+                    pubkey: pubkey.to_string(),
+                    account: encode_account(&account, &pubkey, encoding, data_slice_config)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(new_response(&bank, accounts))
+    }
+
+    /// Returns the key of the data size index to scan for the accounts of `program_id` matching
+    /// `filters`, if the index is enabled for `program_id` and one of `filters` is on data size
+    fn data_size_index_key(
+        &self,
+        program_id: &Pubkey,
+        filters: &[RpcFilterType],
+    ) -> Option<IndexKey> {
+        let account_indexes = &self.config.account_indexes;
+        if !account_indexes.contains(&AccountIndex::DataSize)
+            || !account_indexes.include_key(program_id)
+        {
+            return None;
+        }
+        filters.iter().find_map(|filter| match filter {
+            RpcFilterType::DataSize(data_size) => Some(IndexKey::DataSize(*program_id, *data_size)),
+            _ => None,
+        })
+    }
+
+    /// Use a set of filters to get an iterator of keyed program accounts from a bank
+    async fn get_filtered_program_accounts(
+        &self,
+        bank: Arc<Bank>,
+        program_id: Pubkey,
+        mut filters: Vec<RpcFilterType>,
+        sort_results: bool,
+    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
+        optimize_filters(&mut filters);
+        if let Some(index_key) = self.data_size_index_key(&program_id, &filters) {
+            self.get_filtered_indexed_accounts(
+                &bank,
+                &index_key,
+                &program_id,
+                filters,
+                sort_results,
+            )
+            .await
+            .map_err(|e| RpcCustomError::ScanError {
+                message: e.to_string(),
+            })
+        } else if self
+            .config
+            .account_indexes
+            .contains(&AccountIndex::ProgramId)
+        {
+            if !self.config.account_indexes.include_key(&program_id) {
+                return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
+                    index_key: program_id.to_string(),
+                });
+            }
+            self.get_filtered_indexed_accounts(
+                &bank,
+                &IndexKey::ProgramId(program_id),
+                &program_id,
+                filters,
+                sort_results,
+            )
+            .await
+            .map_err(|e| RpcCustomError::ScanError {
+                message: e.to_string(),
+            })
+        } else {
+            // this path does not need to provide a mb limit because we only want to support secondary indexes
+            let scan_order = if sort_results {
+                ScanOrder::Sorted
+            } else {
+                ScanOrder::Unsorted
+            };
+            self.runtime
+                .spawn_blocking(move || {
+                    bank.get_filtered_program_accounts(
+                        &program_id,
+                        |account: &AccountSharedData| {
                             filters
                                 .iter()
                                 .all(|filter_type| filter_allows(filter_type, account))
@@ -2401,6 +2921,193 @@ impl JsonRpcRequestProcessor {
             })
             .collect())
     }
+
+    fn get_slot_account_filter(&self, slot: Slot) -> Result<Option<RpcSlotAccountFilter>> {
+        if !self.slot_account_filter_cache.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self
+            .slot_account_filter_cache
+            .get(slot)
+            .map(|filter| filter.to_rpc()))
+    }
+
+    fn get_shred_latency(&self) -> Result<RpcShredLatency> {
+        if !self.shred_latency_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.shred_latency_tracker.to_rpc())
+    }
+
+    fn get_confirmation_latency(&self) -> Result<RpcConfirmationLatency> {
+        if !self.confirmation_latency_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.confirmation_latency_tracker.to_rpc())
+    }
+
+    fn get_account_congestion(
+        &self,
+        accounts: Vec<Pubkey>,
+    ) -> Result<Vec<RpcSlotAccountCongestion>> {
+        if !self.account_congestion_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.account_congestion_tracker.to_rpc(&accounts))
+    }
+
+    fn get_confirmation_proof(&self, slot: Slot) -> Result<Option<RpcConfirmationProof>> {
+        if !self.confirmation_proof_cache.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.confirmation_proof_cache.to_rpc(slot))
+    }
+
+    fn get_buffered_transactions(&self) -> Result<Option<RpcBufferedTransactions>> {
+        if !self.buffered_transaction_sampler.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.buffered_transaction_sampler.to_rpc())
+    }
+
+    fn get_account_growth(&self) -> Result<Option<RpcAccountGrowth>> {
+        if !self.account_growth_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.account_growth_tracker.to_rpc())
+    }
+
+    fn get_program_cache_stats(&self) -> RpcProgramCacheStats {
+        let root_bank = self.bank_forks.read().unwrap().root_bank();
+        let (stats, pinned_programs) = root_bank.program_cache_cumulative_stats();
+        let program_ids: HashSet<_> = stats.loads.keys().chain(stats.evictions.keys()).collect();
+        let mut programs: Vec<_> = program_ids
+            .into_iter()
+            .map(|program_id| {
+                let loads = stats.loads.get(program_id).copied().unwrap_or_default();
+                RpcProgramCacheProgramStats {
+                    program_id: program_id.to_string(),
+                    loads: loads.num_loads,
+                    load_us: loads.load_us,
+                    jit_compile_us: loads.jit_compile_us,
+                    evictions: stats.evictions.get(program_id).copied().unwrap_or_default(),
+                }
+            })
+            .collect();
+        programs.sort_by(|a, b| {
+            b.load_us
+                .cmp(&a.load_us)
+                .then(b.evictions.cmp(&a.evictions))
+                .then_with(|| a.program_id.cmp(&b.program_id))
+        });
+        programs.truncate(MAX_GET_PROGRAM_CACHE_STATS_PROGRAMS);
+        let mut pinned_programs: Vec<_> = pinned_programs
+            .iter()
+            .map(|program_id| program_id.to_string())
+            .collect();
+        pinned_programs.sort_unstable();
+
+        RpcProgramCacheStats {
+            hits: stats.hits,
+            misses: stats.misses,
+            hit_rate: stats.hit_rate(),
+            reloads: stats.reloads,
+            insertions: stats.insertions,
+            evictions: stats.evictions.values().sum(),
+            one_hit_wonders: stats.one_hit_wonders,
+            water_level: stats.water_level,
+            pinned_programs,
+            programs,
+        }
+    }
+
+    fn get_skipped_slot_reasons(&self) -> Result<Vec<RpcSkippedSlotReason>> {
+        if !self.skipped_slot_reasons_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        Ok(self.skipped_slot_reasons_tracker.to_rpc())
+    }
+
+    fn get_recent_leader_slots(&self, limit: Option<usize>) -> Result<Vec<RpcLeaderSlot>> {
+        if !self.leader_slot_stats_tracker.is_enabled() {
+            return Err(Error::invalid_request());
+        }
+        let limit = limit.unwrap_or(MAX_GET_RECENT_LEADER_SLOTS);
+        if limit > MAX_GET_RECENT_LEADER_SLOTS {
+            return Err(Error::invalid_params(format!(
+                "Invalid limit; max {MAX_GET_RECENT_LEADER_SLOTS}"
+            )));
+        }
+        Ok(self.recent_leader_slots(&self.cluster_info.id(), limit))
+    }
+
+    /// Returns the most recent `limit` leader slots of `identity` up to the highest slot, newest
+    /// first, from the leader schedules which are cached
+    fn recent_leader_slots(&self, identity: &Pubkey, limit: usize) -> Vec<RpcLeaderSlot> {
+        let (highest_slot, root, epoch_schedule) = {
+            let bank_forks = self.bank_forks.read().unwrap();
+            let root_bank = bank_forks.root_bank();
+            (
+                bank_forks.highest_slot(),
+                root_bank.slot(),
+                root_bank.epoch_schedule().clone(),
+            )
+        };
+
+        let mut leader_slots = Vec::with_capacity(limit);
+        let mut epoch = epoch_schedule.get_epoch(highest_slot);
+        while leader_slots.len() < limit {
+            let Some(leader_schedule) = self.leader_schedule_cache.get_epoch_leader_schedule(epoch)
+            else {
+                break;
+            };
+            let first_slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+            if let Some(slot_indexes) = leader_schedule.get_leader_slots_map().get(identity) {
+                let remaining = limit - leader_slots.len();
+                leader_slots.extend(
+                    slot_indexes
+                        .iter()
+                        .rev()
+                        .map(|slot_index| first_slot + *slot_index as Slot)
+                        .filter(|slot| *slot <= highest_slot)
+                        .take(remaining),
+                );
+            }
+            let Some(previous_epoch) = epoch.checked_sub(1) else {
+                break;
+            };
+            epoch = previous_epoch;
+        }
+
+        leader_slots
+            .into_iter()
+            .map(|slot| {
+                let rooted = slot == root || self.blockstore.is_root(slot);
+                let skipped_reason = (slot < root && !rooted).then(|| {
+                    let is_full = self
+                        .blockstore
+                        .meta(slot)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|meta| meta.is_full());
+                    if is_full {
+                        RpcLeaderSlotSkippedReason::NotRooted
+                    } else {
+                        RpcLeaderSlotSkippedReason::NotProduced
+                    }
+                });
+                RpcLeaderSlot {
+                    slot,
+                    rooted,
+                    skipped_reason,
+                    stats: self
+                        .leader_slot_stats_tracker
+                        .get(slot)
+                        .map(|stats| stats.to_rpc()),
+                }
+            })
+            .collect()
+    }
 }
 
 pub(crate) fn optimize_filters(filters: &mut [RpcFilterType]) {
@@ -2423,6 +3130,74 @@ fn verify_transaction(transaction: &SanitizedTransaction) -> Result<()> {
     Ok(())
 }
 
+/// A page of the accounts of `getProgramAccounts`, in pubkey order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramAccountsPage {
+    size: usize,
+    /// The accounts of the page come after this one
+    after: Option<Pubkey>,
+}
+
+/// The version of the encoding of the cursors of `getProgramAccounts` pages. A cursor is the
+/// base-58 encoding of the version followed by the pubkey of the last account of its page.
+const PROGRAM_ACCOUNTS_CURSOR_VERSION: u8 = 1;
+
+impl ProgramAccountsPage {
+    /// Returns the requested page, if any
+    fn from_config(page_size: Option<usize>, cursor: Option<&str>) -> Result<Option<Self>> {
+        if page_size.is_none() && cursor.is_none() {
+            return Ok(None);
+        }
+        let size = page_size.unwrap_or(MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE);
+        if !(1..=MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE).contains(&size) {
+            return Err(Error::invalid_params(format!(
+                "Invalid page size; must be between 1 and {MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE}"
+            )));
+        }
+        let after = cursor.map(Self::decode_cursor).transpose()?;
+        Ok(Some(Self { size, after }))
+    }
+
+    fn encode_cursor(pubkey: &Pubkey) -> String {
+        let mut cursor = Vec::with_capacity(1 + PUBKEY_BYTES);
+        cursor.push(PROGRAM_ACCOUNTS_CURSOR_VERSION);
+        cursor.extend_from_slice(pubkey.as_ref());
+        bs58::encode(cursor).into_string()
+    }
+
+    fn decode_cursor(cursor: &str) -> Result<Pubkey> {
+        let cursor = bs58::decode(cursor).into_vec().unwrap_or_default();
+        match cursor.split_first() {
+            Some((&PROGRAM_ACCOUNTS_CURSOR_VERSION, pubkey)) => Pubkey::try_from(pubkey).ok(),
+            _ => None,
+        }
+        .ok_or_else(|| Error::invalid_params("Invalid param: cursor"))
+    }
+
+    /// Returns the accounts of the page, in pubkey order, and the cursor of the next page if
+    /// there are more accounts
+    fn select(
+        &self,
+        mut keyed_accounts: Vec<(Pubkey, AccountSharedData)>,
+    ) -> (Vec<(Pubkey, AccountSharedData)>, Option<String>) {
+        if let Some(after) = self.after {
+            keyed_accounts.retain(|(pubkey, _)| *pubkey > after);
+        }
+        let has_more = keyed_accounts.len() > self.size;
+        if has_more {
+            // Only the accounts of the page are sorted.
+            keyed_accounts.select_nth_unstable_by_key(self.size, |(pubkey, _)| *pubkey);
+            keyed_accounts.truncate(self.size);
+        }
+        keyed_accounts.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+        let next_cursor = keyed_accounts
+            .last()
+            .filter(|_| has_more)
+            .map(|(pubkey, _)| Self::encode_cursor(pubkey));
+        (keyed_accounts, next_cursor)
+    }
+}
+
 pub(crate) fn verify_filters(filters: &[RpcFilterType]) -> Result<()> {
     if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
         return Err(Error::invalid_params(format!(
@@ -2690,6 +3465,8 @@ fn _send_transaction(
     durable_nonce_info: Option<(Pubkey, Hash)>,
     max_retries: Option<usize>,
 ) -> Result<String> {
+    // Accounted once, the retries of the send transaction service are not
+    bandwidth_accounting::record_egress(Subsystem::Rpc, 1, wire_transaction.len());
     let transaction_info = TransactionInfo::new(
         signature,
         wire_transaction,
@@ -2733,6 +3510,9 @@ pub mod rpc_minimal {
         #[rpc(meta, name = "getHealth")]
         fn get_health(&self, meta: Self::Metadata) -> Result<String>;
 
+        #[rpc(meta, name = "getHealthDetail")]
+        fn get_health_detail(&self, meta: Self::Metadata) -> Result<RpcHealthDetail>;
+
         #[rpc(meta, name = "getIdentity")]
         fn get_identity(&self, meta: Self::Metadata) -> Result<RpcIdentity>;
 
@@ -2823,6 +3603,11 @@ pub mod rpc_minimal {
             }
         }
 
+        fn get_health_detail(&self, meta: Self::Metadata) -> Result<RpcHealthDetail> {
+            debug!("get_health_detail rpc request received");
+            Ok(meta.health.check_detail())
+        }
+
         fn get_identity(&self, meta: Self::Metadata) -> Result<RpcIdentity> {
             debug!("get_identity rpc request received");
             Ok(RpcIdentity {
@@ -2993,6 +3778,13 @@ pub mod rpc_bank {
             meta: Self::Metadata,
             config: Option<RpcBlockProductionConfig>,
         ) -> Result<RpcResponse<RpcBlockProduction>>;
+
+        #[rpc(meta, name = "getLeaderScheduleForecast")]
+        fn get_leader_schedule_forecast(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcLeaderScheduleForecastConfig>,
+        ) -> Result<RpcLeaderScheduleForecast>;
     }
 
     pub struct BankDataImpl;
@@ -3155,6 +3947,15 @@ pub mod rpc_bank {
                 },
             ))
         }
+
+        fn get_leader_schedule_forecast(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcLeaderScheduleForecastConfig>,
+        ) -> Result<RpcLeaderScheduleForecast> {
+            debug!("get_leader_schedule_forecast rpc request received");
+            meta.get_leader_schedule_forecast(config.unwrap_or_default())
+        }
     }
 }
 
@@ -3308,7 +4109,7 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>>;
+        ) -> BoxFuture<Result<OptionalContext<RpcProgramAccounts>>>;
 
         #[rpc(meta, name = "getLargestAccounts")]
         fn get_largest_accounts(
@@ -3353,6 +4154,14 @@ pub mod rpc_accounts_scan {
             token_account_filter: RpcTokenAccountsFilter,
             config: Option<RpcAccountInfoConfig>,
         ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>>;
+
+        #[rpc(meta, name = "getNonceAccountsByAuthority")]
+        fn get_nonce_accounts_by_authority(
+            &self,
+            meta: Self::Metadata,
+            authority_str: String,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>>;
     }
 
     pub struct AccountsScanImpl;
@@ -3364,26 +4173,38 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>> {
+        ) -> BoxFuture<Result<OptionalContext<RpcProgramAccounts>>> {
             debug!(
                 "get_program_accounts rpc request received: {:?}",
                 program_id_str
             );
             async move {
                 let program_id = verify_pubkey(&program_id_str)?;
-                let (config, filters, with_context, sort_results) = if let Some(config) = config {
-                    (
-                        Some(config.account_config),
-                        config.filters.unwrap_or_default(),
-                        config.with_context.unwrap_or_default(),
-                        config.sort_results.unwrap_or(true),
-                    )
-                } else {
-                    (None, vec![], false, true)
-                };
+                let (config, filters, with_context, sort_results, page) =
+                    if let Some(config) = config {
+                        (
+                            Some(config.account_config),
+                            config.filters.unwrap_or_default(),
+                            config.with_context.unwrap_or_default(),
+                            config.sort_results.unwrap_or(true),
+                            ProgramAccountsPage::from_config(
+                                config.page_size,
+                                config.cursor.as_deref(),
+                            )?,
+                        )
+                    } else {
+                        (None, vec![], false, true, None)
+                    };
                 verify_filters(&filters)?;
-                meta.get_program_accounts(program_id, config, filters, with_context, sort_results)
-                    .await
+                meta.get_program_accounts(
+                    program_id,
+                    config,
+                    filters,
+                    with_context,
+                    sort_results,
+                    page,
+                )
+                .await
             }
             .boxed()
         }
@@ -3462,6 +4283,24 @@ pub mod rpc_accounts_scan {
             }
             .boxed()
         }
+
+        fn get_nonce_accounts_by_authority(
+            &self,
+            meta: Self::Metadata,
+            authority_str: String,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>> {
+            debug!(
+                "get_nonce_accounts_by_authority rpc request received: {:?}",
+                authority_str
+            );
+            async move {
+                let authority = verify_pubkey(&authority_str)?;
+                meta.get_nonce_accounts_by_authority(authority, config, true)
+                    .await
+            }
+            .boxed()
+        }
     }
 }
 
@@ -3485,6 +4324,15 @@ pub mod rpc_full {
             config: Option<RpcEpochConfig>,
         ) -> BoxFuture<Result<Vec<Option<RpcInflationReward>>>>;
 
+        #[rpc(meta, name = "getInflationRewardHistory")]
+        fn get_inflation_reward_history(
+            &self,
+            meta: Self::Metadata,
+            address_strs: Vec<String>,
+            start_epoch: Epoch,
+            config: Option<RpcInflationRewardHistoryConfig>,
+        ) -> BoxFuture<Result<Vec<Vec<RpcInflationReward>>>>;
+
         #[rpc(meta, name = "getClusterNodes")]
         fn get_cluster_nodes(&self, meta: Self::Metadata) -> Result<Vec<RpcContactInfo>>;
 
@@ -3534,6 +4382,14 @@ pub mod rpc_full {
             config: Option<RpcSimulateTransactionConfig>,
         ) -> Result<RpcResponse<RpcSimulateTransactionResult>>;
 
+        #[rpc(meta, name = "simulateTransactions")]
+        fn simulate_transactions(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateTransactionConfig>,
+        ) -> Result<RpcResponse<Vec<RpcSimulateTransactionResult>>>;
+
         #[rpc(meta, name = "minimumLedgerSlot")]
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
@@ -3543,7 +4399,8 @@ pub mod rpc_full {
             meta: Self::Metadata,
             slot: Slot,
             config: Option<RpcEncodingConfigWrapper<RpcBlockConfig>>,
-        ) -> BoxFuture<Result<Option<UiConfirmedBlock>>>;
+            fields: Option<Vec<String>>,
+        ) -> BoxFuture<Result<Option<SelectedFields<UiConfirmedBlock>>>>;
 
         #[rpc(meta, name = "getBlockTime")]
         fn get_block_time(
@@ -3576,7 +4433,8 @@ pub mod rpc_full {
             meta: Self::Metadata,
             signature_str: String,
             config: Option<RpcEncodingConfigWrapper<RpcTransactionConfig>>,
-        ) -> BoxFuture<Result<Option<EncodedConfirmedTransactionWithStatusMeta>>>;
+            fields: Option<Vec<String>>,
+        ) -> BoxFuture<Result<Option<SelectedFields<EncodedConfirmedTransactionWithStatusMeta>>>>;
 
         #[rpc(meta, name = "getSignaturesForAddress")]
         fn get_signatures_for_address(
@@ -3586,6 +4444,14 @@ pub mod rpc_full {
             config: Option<RpcSignaturesForAddressConfig>,
         ) -> BoxFuture<Result<Vec<RpcConfirmedTransactionStatusWithSignature>>>;
 
+        #[rpc(meta, name = "getSignaturesForProgram")]
+        fn get_signatures_for_program(
+            &self,
+            meta: Self::Metadata,
+            program_id: String,
+            config: Option<RpcSignaturesForAddressConfig>,
+        ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>>;
+
         #[rpc(meta, name = "getFirstAvailableBlock")]
         fn get_first_available_block(&self, meta: Self::Metadata) -> BoxFuture<Result<Slot>>;
 
@@ -3625,6 +4491,65 @@ pub mod rpc_full {
             meta: Self::Metadata,
             pubkey_strs: Option<Vec<String>>,
         ) -> Result<Vec<RpcPrioritizationFee>>;
+
+        #[rpc(meta, name = "getSlotAccountFilter")]
+        fn get_slot_account_filter(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcSlotAccountFilter>>;
+
+        #[rpc(meta, name = "getShredLatency")]
+        fn get_shred_latency(&self, meta: Self::Metadata) -> Result<RpcShredLatency>;
+
+        #[rpc(meta, name = "getConfirmationLatency")]
+        fn get_confirmation_latency(&self, meta: Self::Metadata) -> Result<RpcConfirmationLatency>;
+
+        #[rpc(meta, name = "getAccountCongestion")]
+        fn get_account_congestion(
+            &self,
+            meta: Self::Metadata,
+            pubkey_strs: Option<Vec<String>>,
+        ) -> Result<Vec<RpcSlotAccountCongestion>>;
+
+        #[rpc(meta, name = "getConfirmationProof")]
+        fn get_confirmation_proof(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcConfirmationProof>>;
+
+        #[rpc(meta, name = "getBufferedTransactions")]
+        fn get_buffered_transactions(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<Option<RpcBufferedTransactions>>;
+
+        #[rpc(meta, name = "getAccountGrowth")]
+        fn get_account_growth(&self, meta: Self::Metadata) -> Result<Option<RpcAccountGrowth>>;
+
+        #[rpc(meta, name = "getProgramCacheStats")]
+        fn get_program_cache_stats(&self, meta: Self::Metadata) -> Result<RpcProgramCacheStats>;
+
+        #[rpc(meta, name = "getSkippedSlotReasons")]
+        fn get_skipped_slot_reasons(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<Vec<RpcSkippedSlotReason>>;
+
+        #[rpc(meta, name = "getRecentLeaderSlots")]
+        fn get_recent_leader_slots(
+            &self,
+            meta: Self::Metadata,
+            limit: Option<usize>,
+        ) -> Result<Vec<RpcLeaderSlot>>;
+
+        #[rpc(meta, name = "getVoteAccountsDetailed")]
+        fn get_vote_accounts_detailed(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcGetVoteAccountsDetailedConfig>,
+        ) -> Result<Vec<RpcVoteAccountDetailed>>;
     }
 
     pub struct FullImpl;
@@ -3857,6 +4782,14 @@ pub mod rpc_full {
             )?;
             let signature = *transaction.signature();
 
+            if let Some(commission_guard) = &meta.commission_guard {
+                commission_guard
+                    .check(preflight_bank, transaction.message())
+                    .map_err(|err| RpcCustomError::CommissionChangeRejected {
+                        message: err.to_string(),
+                    })?;
+            }
+
             let mut last_valid_block_height = preflight_bank
                 .get_blockhash_last_valid_block_height(transaction.message().recent_blockhash())
                 .unwrap_or(0);
@@ -3902,6 +4835,9 @@ pub mod rpc_full {
                     units_consumed,
                     return_data,
                     inner_instructions: _, // Always `None` due to `enable_cpi_recording = false`
+                    instruction_units_consumed: _,
+                    fee_details: _,
+                    loaded_accounts_data_size: _,
                 } = preflight_bank.simulate_transaction(&transaction, false)
                 {
                     match err {
@@ -4000,6 +4936,9 @@ pub mod rpc_full {
                 units_consumed,
                 return_data,
                 inner_instructions,
+                instruction_units_consumed: _,
+                fee_details: _,
+                loaded_accounts_data_size: _,
             } = bank.simulate_transaction(&transaction, enable_cpi_recording);
 
             let account_keys = transaction.message().account_keys();
@@ -4022,53 +4961,220 @@ pub mod rpc_full {
                     )));
                 }
 
-                if result.is_err() {
-                    Some(vec![None; config_accounts.addresses.len()])
-                } else {
-                    let mut post_simulation_accounts_map = HashMap::new();
-                    for (pubkey, data) in post_simulation_accounts {
-                        post_simulation_accounts_map.insert(pubkey, data);
-                    }
-
-                    Some(
-                        config_accounts
-                            .addresses
-                            .iter()
-                            .map(|address_str| {
-                                let pubkey = verify_pubkey(address_str)?;
-                                get_encoded_account(
-                                    bank,
-                                    &pubkey,
-                                    accounts_encoding,
-                                    None,
-                                    Some(&post_simulation_accounts_map),
-                                )
-                            })
-                            .collect::<Result<Vec<_>>>()?,
-                    )
-                }
+                if result.is_err() {
+                    Some(vec![None; config_accounts.addresses.len()])
+                } else {
+                    let mut post_simulation_accounts_map = HashMap::new();
+                    for (pubkey, data) in post_simulation_accounts {
+                        post_simulation_accounts_map.insert(pubkey, data);
+                    }
+
+                    Some(
+                        config_accounts
+                            .addresses
+                            .iter()
+                            .map(|address_str| {
+                                let pubkey = verify_pubkey(address_str)?;
+                                get_encoded_account(
+                                    bank,
+                                    &pubkey,
+                                    accounts_encoding,
+                                    None,
+                                    Some(&post_simulation_accounts_map),
+                                )
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+            } else {
+                None
+            };
+
+            let inner_instructions = inner_instructions.map(|info| {
+                map_inner_instructions(info)
+                    .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
+                    .collect()
+            });
+
+            Ok(new_response(
+                bank,
+                RpcSimulateTransactionResult {
+                    err: result.err(),
+                    logs: Some(logs),
+                    accounts,
+                    units_consumed: Some(units_consumed),
+                    return_data: return_data.map(|return_data| return_data.into()),
+                    inner_instructions,
+                    replacement_blockhash: blockhash,
+                },
+            ))
+        }
+
+        fn simulate_transactions(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateTransactionConfig>,
+        ) -> Result<RpcResponse<Vec<RpcSimulateTransactionResult>>> {
+            debug!("simulate_transactions rpc request received");
+            if data.is_empty() {
+                return Err(Error::invalid_params("No transactions provided"));
+            }
+            if data.len() > MAX_SIMULATE_TRANSACTIONS {
+                return Err(Error::invalid_params(format!(
+                    "Too many transactions provided; max {MAX_SIMULATE_TRANSACTIONS}"
+                )));
+            }
+            let RpcSimulateTransactionConfig {
+                sig_verify,
+                replace_recent_blockhash,
+                commitment,
+                encoding,
+                accounts: config_accounts,
+                min_context_slot,
+                inner_instructions: enable_cpi_recording,
+            } = config.unwrap_or_default();
+            let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
+            let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "unsupported encoding: {tx_encoding}. Supported encodings: base58, base64"
+                ))
+            })?;
+
+            let bank = &*meta.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot,
+            })?;
+            let mut blockhash: Option<RpcBlockhash> = None;
+            if replace_recent_blockhash {
+                if sig_verify {
+                    return Err(Error::invalid_params(
+                        "sigVerify may not be used with replaceRecentBlockhash",
+                    ));
+                }
+                let recent_blockhash = bank.last_blockhash();
+                let last_valid_block_height = bank
+                    .get_blockhash_last_valid_block_height(&recent_blockhash)
+                    .expect("bank blockhash queue should contain blockhash");
+                blockhash.replace(RpcBlockhash {
+                    blockhash: recent_blockhash.to_string(),
+                    last_valid_block_height,
+                });
+            }
+
+            let transactions = data
+                .into_iter()
+                .map(|data| {
+                    let (_, mut unsanitized_tx) =
+                        decode_and_deserialize::<VersionedTransaction>(data, binary_encoding)?;
+                    if replace_recent_blockhash {
+                        unsanitized_tx
+                            .message
+                            .set_recent_blockhash(bank.last_blockhash());
+                    }
+                    let transaction = sanitize_transaction(
+                        unsanitized_tx,
+                        bank,
+                        bank.get_reserved_account_keys(),
+                    )?;
+                    if sig_verify {
+                        verify_transaction(&transaction)?;
+                    }
+                    Ok(transaction)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let config_accounts = if let Some(config_accounts) = config_accounts {
+                let accounts_encoding = config_accounts
+                    .encoding
+                    .unwrap_or(UiAccountEncoding::Base64);
+
+                if accounts_encoding == UiAccountEncoding::Binary
+                    || accounts_encoding == UiAccountEncoding::Base58
+                {
+                    return Err(Error::invalid_params("base58 encoding not supported"));
+                }
+
+                let number_of_accounts = transactions
+                    .iter()
+                    .map(|transaction| transaction.message().account_keys().len())
+                    .sum::<usize>();
+                if config_accounts.addresses.len() > number_of_accounts {
+                    return Err(Error::invalid_params(format!(
+                        "Too many accounts provided; max {number_of_accounts}"
+                    )));
+                }
+
+                let addresses = config_accounts
+                    .addresses
+                    .iter()
+                    .map(|address_str| verify_pubkey(address_str))
+                    .collect::<Result<Vec<_>>>()?;
+                Some((addresses, accounts_encoding))
             } else {
                 None
             };
 
-            let inner_instructions = inner_instructions.map(|info| {
-                map_inner_instructions(info)
-                    .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
-                    .collect()
-            });
+            let simulations = bank.simulate_transactions(&transactions, enable_cpi_recording);
 
-            Ok(new_response(
-                bank,
-                RpcSimulateTransactionResult {
+            // The state of the requested accounts after each transaction, accumulating the
+            // changes of the transactions which succeeded so far
+            let mut post_simulation_accounts_map = HashMap::new();
+            let mut results = Vec::with_capacity(simulations.len());
+            for (simulation, transaction) in simulations.into_iter().zip(&transactions) {
+                let TransactionSimulationResult {
+                    result,
+                    logs,
+                    post_simulation_accounts,
+                    units_consumed,
+                    return_data,
+                    inner_instructions,
+                    instruction_units_consumed: _,
+                    fee_details: _,
+                    loaded_accounts_data_size: _,
+                } = simulation;
+
+                let accounts = match &config_accounts {
+                    Some((addresses, _)) if result.is_err() => Some(vec![None; addresses.len()]),
+                    Some((addresses, accounts_encoding)) => {
+                        post_simulation_accounts_map.extend(post_simulation_accounts);
+                        Some(
+                            addresses
+                                .iter()
+                                .map(|pubkey| {
+                                    get_encoded_account(
+                                        bank,
+                                        pubkey,
+                                        *accounts_encoding,
+                                        None,
+                                        Some(&post_simulation_accounts_map),
+                                    )
+                                })
+                                .collect::<Result<Vec<_>>>()?,
+                        )
+                    }
+                    None => None,
+                };
+
+                let account_keys = transaction.message().account_keys();
+                let inner_instructions = inner_instructions.map(|info| {
+                    map_inner_instructions(info)
+                        .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
+                        .collect()
+                });
+
+                results.push(RpcSimulateTransactionResult {
                     err: result.err(),
                     logs: Some(logs),
                     accounts,
                     units_consumed: Some(units_consumed),
                     return_data: return_data.map(|return_data| return_data.into()),
                     inner_instructions,
-                    replacement_blockhash: blockhash,
-                },
-            ))
+                    replacement_blockhash: blockhash.clone(),
+                });
+            }
+
+            Ok(new_response(bank, results))
         }
 
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot> {
@@ -4081,9 +5187,19 @@ pub mod rpc_full {
             meta: Self::Metadata,
             slot: Slot,
             config: Option<RpcEncodingConfigWrapper<RpcBlockConfig>>,
-        ) -> BoxFuture<Result<Option<UiConfirmedBlock>>> {
+            fields: Option<Vec<String>>,
+        ) -> BoxFuture<Result<Option<SelectedFields<UiConfirmedBlock>>>> {
             debug!("get_block rpc request received: {:?}", slot);
-            Box::pin(async move { meta.get_block(slot, config).await })
+            Box::pin(async move {
+                let fields = fields
+                    .as_deref()
+                    .map(ResponseFields::parse_block_fields)
+                    .transpose()?;
+                meta.get_block(slot, config, fields.clone())
+                    .await?
+                    .map(|block| SelectedFields::new(block, fields.as_ref()))
+                    .transpose()
+            })
         }
 
         fn get_blocks(
@@ -4132,13 +5248,24 @@ pub mod rpc_full {
             meta: Self::Metadata,
             signature_str: String,
             config: Option<RpcEncodingConfigWrapper<RpcTransactionConfig>>,
-        ) -> BoxFuture<Result<Option<EncodedConfirmedTransactionWithStatusMeta>>> {
+            fields: Option<Vec<String>>,
+        ) -> BoxFuture<Result<Option<SelectedFields<EncodedConfirmedTransactionWithStatusMeta>>>>
+        {
             debug!("get_transaction rpc request received: {:?}", signature_str);
             let signature = verify_signature(&signature_str);
             if let Err(err) = signature {
                 return Box::pin(future::err(err));
             }
-            Box::pin(async move { meta.get_transaction(signature.unwrap(), config).await })
+            Box::pin(async move {
+                let fields = fields
+                    .as_deref()
+                    .map(ResponseFields::parse_transaction_fields)
+                    .transpose()?;
+                meta.get_transaction(signature.unwrap(), config, fields.clone())
+                    .await?
+                    .map(|transaction| SelectedFields::new(transaction, fields.as_ref()))
+                    .transpose()
+            })
         }
 
         fn get_signatures_for_address(
@@ -4175,6 +5302,34 @@ pub mod rpc_full {
             }
         }
 
+        fn get_signatures_for_program(
+            &self,
+            meta: Self::Metadata,
+            program_id: String,
+            config: Option<RpcSignaturesForAddressConfig>,
+        ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+            debug!("get_signatures_for_program rpc request received: {program_id:?}");
+            let RpcSignaturesForAddressConfig {
+                before,
+                until,
+                limit,
+                commitment,
+                min_context_slot,
+            } = config.unwrap_or_default();
+            let (program_id, before, until, limit) =
+                verify_and_parse_signatures_for_address_params(program_id, before, until, limit)?;
+            meta.get_signatures_for_program(
+                program_id,
+                before,
+                until,
+                limit,
+                RpcContextConfig {
+                    commitment,
+                    min_context_slot,
+                },
+            )
+        }
+
         fn get_first_available_block(&self, meta: Self::Metadata) -> BoxFuture<Result<Slot>> {
             debug!("get_first_available_block rpc request received");
             Box::pin(async move { Ok(meta.get_first_available_block().await) })
@@ -4204,6 +5359,34 @@ pub mod rpc_full {
             Box::pin(async move { meta.get_inflation_reward(addresses, config).await })
         }
 
+        fn get_inflation_reward_history(
+            &self,
+            meta: Self::Metadata,
+            address_strs: Vec<String>,
+            start_epoch: Epoch,
+            config: Option<RpcInflationRewardHistoryConfig>,
+        ) -> BoxFuture<Result<Vec<Vec<RpcInflationReward>>>> {
+            debug!(
+                "get_inflation_reward_history rpc request received: {:?} from epoch {}",
+                address_strs.len(),
+                start_epoch
+            );
+
+            let addresses = match address_strs
+                .iter()
+                .map(|address_str| verify_pubkey(address_str))
+                .collect::<Result<Vec<_>>>()
+            {
+                Ok(addresses) => addresses,
+                Err(err) => return Box::pin(future::err(err)),
+            };
+
+            Box::pin(async move {
+                meta.get_inflation_reward_history(addresses, start_epoch, config)
+                    .await
+            })
+        }
+
         fn get_latest_blockhash(
             &self,
             meta: Self::Metadata,
@@ -4280,6 +5463,100 @@ pub mod rpc_full {
                 .collect::<Result<Vec<_>>>()?;
             meta.get_recent_prioritization_fees(pubkeys)
         }
+
+        fn get_slot_account_filter(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcSlotAccountFilter>> {
+            debug!("get_slot_account_filter rpc request received: {:?}", slot);
+            meta.get_slot_account_filter(slot)
+        }
+
+        fn get_shred_latency(&self, meta: Self::Metadata) -> Result<RpcShredLatency> {
+            debug!("get_shred_latency rpc request received");
+            meta.get_shred_latency()
+        }
+
+        fn get_confirmation_latency(&self, meta: Self::Metadata) -> Result<RpcConfirmationLatency> {
+            debug!("get_confirmation_latency rpc request received");
+            meta.get_confirmation_latency()
+        }
+
+        fn get_account_congestion(
+            &self,
+            meta: Self::Metadata,
+            pubkey_strs: Option<Vec<String>>,
+        ) -> Result<Vec<RpcSlotAccountCongestion>> {
+            let pubkey_strs = pubkey_strs.unwrap_or_default();
+            debug!(
+                "get_account_congestion rpc request received: {:?} pubkeys",
+                pubkey_strs.len()
+            );
+            if pubkey_strs.len() > MAX_TX_ACCOUNT_LOCKS {
+                return Err(Error::invalid_params(format!(
+                    "Too many inputs provided; max {MAX_TX_ACCOUNT_LOCKS}"
+                )));
+            }
+            let pubkeys = pubkey_strs
+                .into_iter()
+                .map(|pubkey_str| verify_pubkey(&pubkey_str))
+                .collect::<Result<Vec<_>>>()?;
+            meta.get_account_congestion(pubkeys)
+        }
+
+        fn get_confirmation_proof(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcConfirmationProof>> {
+            debug!("get_confirmation_proof rpc request received: {:?}", slot);
+            meta.get_confirmation_proof(slot)
+        }
+
+        fn get_buffered_transactions(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<Option<RpcBufferedTransactions>> {
+            debug!("get_buffered_transactions rpc request received");
+            meta.get_buffered_transactions()
+        }
+
+        fn get_account_growth(&self, meta: Self::Metadata) -> Result<Option<RpcAccountGrowth>> {
+            debug!("get_account_growth rpc request received");
+            meta.get_account_growth()
+        }
+
+        fn get_program_cache_stats(&self, meta: Self::Metadata) -> Result<RpcProgramCacheStats> {
+            debug!("get_program_cache_stats rpc request received");
+            Ok(meta.get_program_cache_stats())
+        }
+
+        fn get_skipped_slot_reasons(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<Vec<RpcSkippedSlotReason>> {
+            debug!("get_skipped_slot_reasons rpc request received");
+            meta.get_skipped_slot_reasons()
+        }
+
+        fn get_recent_leader_slots(
+            &self,
+            meta: Self::Metadata,
+            limit: Option<usize>,
+        ) -> Result<Vec<RpcLeaderSlot>> {
+            debug!("get_recent_leader_slots rpc request received: {:?}", limit);
+            meta.get_recent_leader_slots(limit)
+        }
+
+        fn get_vote_accounts_detailed(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcGetVoteAccountsDetailedConfig>,
+        ) -> Result<Vec<RpcVoteAccountDetailed>> {
+            debug!("get_vote_accounts_detailed rpc request received");
+            meta.get_vote_accounts_detailed(config)
+        }
     }
 }
 
@@ -4461,6 +5738,8 @@ pub fn populate_blockstore_for_tests(
             None,
             blockstore,
             false,
+            true,
+            crate::transaction_status_service::TransactionStatusFilter::default(),
             tss_exit.clone(),
         );
 
@@ -4490,11 +5769,16 @@ pub mod tests {
             rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *,
         },
         crate::{
+            account_growth::AccountGrowthSample,
+            buffered_transactions::BufferedTransaction,
+            confirmation_proofs::ConfirmationVote,
+            leader_slot_stats::LeaderSlotStats,
             optimistically_confirmed_bank_tracker::{
                 BankNotification, OptimisticallyConfirmedBankTracker,
             },
             rpc_service::service_runtime,
             rpc_subscriptions::RpcSubscriptions,
+            slot_account_filter::SlotAccountFilter,
         },
         agave_reserved_account_keys::ReservedAccountKeys,
         bincode::deserialize,
@@ -4520,6 +5804,7 @@ pub mod tests {
         solana_rpc_client_api::{
             custom_error::{
                 JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_PROGRAM_SIGNATURE_INDEX_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -4565,7 +5850,9 @@ pub mod tests {
         },
         solana_vote_program::{
             vote_instruction,
-            vote_state::{self, TowerSync, VoteInit, VoteStateVersions, MAX_LOCKOUT_HISTORY},
+            vote_state::{
+                self, BlockTimestamp, TowerSync, VoteInit, VoteStateVersions, MAX_LOCKOUT_HISTORY,
+            },
         },
         spl_pod::optional_keys::OptionalNonZeroPubkey,
         spl_token_2022::{
@@ -4780,6 +6067,7 @@ pub mod tests {
                 rpc_threads,
                 rpc_blocking_threads,
                 rpc_niceness_adj,
+                slot_account_filter_slots,
                 ..
             } = config;
             let meta = JsonRpcRequestProcessor::new(
@@ -4793,6 +6081,7 @@ pub mod tests {
                 cluster_info,
                 Hash::default(),
                 None,
+                None,
                 optimistically_confirmed_bank,
                 Arc::new(RwLock::new(LargestAccountsCache::new(30))),
                 max_slots.clone(),
@@ -4800,6 +6089,17 @@ pub mod tests {
                 max_complete_transaction_status_slot.clone(),
                 max_complete_rewards_slot,
                 Arc::new(PrioritizationFeeCache::default()),
+                Arc::new(TransactionProvenanceCache::default()),
+                Arc::new(SlotAccountFilterCache::new(slot_account_filter_slots)),
+                Arc::new(ShredLatencyTracker::default()),
+                Arc::new(LeaderSlotStatsTracker::default()),
+                Arc::new(ConfirmationLatencyTracker::default()),
+                Arc::new(SkippedSlotReasonsTracker::default()),
+                Arc::new(AccountCongestionTracker::default()),
+                Arc::new(ConfirmationProofCache::default()),
+                Arc::new(BufferedTransactionSampler::default()),
+                Arc::new(AccountGrowthTracker::default()),
+                None,
                 service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
             )
             .0;
@@ -5154,7 +6454,7 @@ pub mod tests {
         let rpc = RpcHandler::start();
         let version = solana_version::Version::default();
         let request = create_test_request("getClusterNodes", None);
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([{
             "pubkey": rpc.identity.to_string(),
             "gossip": "127.0.0.1:8000",
@@ -5211,7 +6511,7 @@ pub mod tests {
             .expect("write to blockstore");
 
         let request = create_test_request("getRecentPerformanceSamples", None);
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([{
             "slot": slot,
             "numSlots": num_slots,
@@ -5422,6 +6722,38 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_inflation_reward_history_range() {
+        let rpc = RpcHandler::start();
+        let address = Pubkey::new_unique().to_string();
+
+        // Without an end epoch, the range ends at the last completed epoch, 0
+        let request = create_test_request("getInflationRewardHistory", Some(json!([[address], 5])));
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        assert_eq!(message, "end epoch 0 is before start epoch 5");
+
+        let request = create_test_request(
+            "getInflationRewardHistory",
+            Some(json!([
+                [address],
+                0,
+                {"endEpoch": MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS},
+            ])),
+        );
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        assert_eq!(
+            message,
+            format!("Epoch range too large; max {MAX_GET_INFLATION_REWARD_HISTORY_EPOCHS}")
+        );
+
+        let request =
+            create_test_request("getInflationRewardHistory", Some(json!([["invalid"], 0])));
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_rpc_get_inflation() {
         let rpc = RpcHandler::start();
@@ -5492,6 +6824,71 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_leader_schedule_forecast() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let first_forecast_epoch = bank.get_leader_schedule_epoch(bank.slot()) + 1;
+        let leader_vote_pubkey = rpc.leader_vote_keypair.pubkey().to_string();
+
+        let request = create_test_request("getLeaderScheduleForecast", None);
+        let result: RpcLeaderScheduleForecast =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.epoch, first_forecast_epoch);
+        assert_eq!(
+            result.first_slot,
+            bank.epoch_schedule()
+                .get_first_slot_in_epoch(first_forecast_epoch)
+        );
+        assert_eq!(
+            result.schedule,
+            HashMap::from_iter(std::iter::once((
+                rpc.leader_pubkey().to_string(),
+                Vec::from_iter(0..bank.get_slots_in_epoch(first_forecast_epoch) as usize),
+            )))
+        );
+
+        // Hypothetical stake changes
+        let request = create_test_request(
+            "getLeaderScheduleForecast",
+            Some(json!([{
+                "epoch": first_forecast_epoch + 1,
+                "stakeChanges": [{"votePubkey": leader_vote_pubkey, "lamports": 42}],
+            }])),
+        );
+        let forecast: RpcLeaderScheduleForecast =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(forecast.epoch, first_forecast_epoch + 1);
+        assert_eq!(forecast.total_stake, result.total_stake + 42);
+
+        let request = create_test_request(
+            "getLeaderScheduleForecast",
+            Some(json!([{
+                "stakeChanges": [{"votePubkey": leader_vote_pubkey, "lamports": i64::MIN}],
+            }])),
+        );
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        assert_eq!(message, "No vote account is staked");
+
+        let request = create_test_request(
+            "getLeaderScheduleForecast",
+            Some(json!([{
+                "stakeChanges": [{"votePubkey": Pubkey::new_unique().to_string(), "lamports": 1}],
+            }])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        // The schedules of the current epochs are fixed.
+        let request = create_test_request(
+            "getLeaderScheduleForecast",
+            Some(json!([{"epoch": bank.epoch()}])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_rpc_get_slot_leaders() {
         let rpc = RpcHandler::start();
@@ -5542,7 +6939,7 @@ pub mod tests {
             "getAccountInfo",
             Some(json!([rpc.mint_keypair.pubkey().to_string()])),
         );
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!({
             "context": {"slot": 0, "apiVersion": RpcApiVersion::default()},
             "value":{
@@ -5566,7 +6963,7 @@ pub mod tests {
             "getAccountInfo",
             Some(json!([address, {"encoding": "base64"}])),
         );
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([BASE64_STANDARD.encode(&data), "base64"]);
         assert_eq!(result["value"]["data"], expected);
         assert_eq!(result["value"]["space"], 5);
@@ -5575,7 +6972,7 @@ pub mod tests {
             "getAccountInfo",
             Some(json!([address, {"encoding": "base64", "dataSlice": {"length": 2, "offset": 1}}])),
         );
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([BASE64_STANDARD.encode(&data[1..3]), "base64"]);
         assert_eq!(result["value"]["data"], expected);
         assert_eq!(result["value"]["space"], 5);
@@ -5584,7 +6981,7 @@ pub mod tests {
             "getAccountInfo",
             Some(json!([address, {"encoding": "binary", "dataSlice": {"length": 2, "offset": 1}}])),
         );
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = bs58::encode(&data[1..3]).into_string();
         assert_eq!(result["value"]["data"], expected);
         assert_eq!(result["value"]["space"], 5);
@@ -5595,7 +6992,7 @@ pub mod tests {
                 json!([address, {"encoding": "jsonParsed", "dataSlice": {"length": 2, "offset": 1}}]),
             ),
         );
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([BASE64_STANDARD.encode(&data[1..3]), "base64"]);
         assert_eq!(
             result["value"]["data"], expected,
@@ -5932,22 +7329,162 @@ pub mod tests {
         let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
         assert_eq!(result.len(), 1);
 
-        let request = create_test_request(
-            "getProgramAccounts",
-            Some(json!([
-                system_program::id().to_string(),
-                {"filters": [{
-                    "memcmp": {
-                        "offset": 4,
-                        "bytes": bs58::encode(vec![1, 0, 0, 0]).into_string(),
-                    },
-                }, {
-                    "dataSize": 1,
-                }]}, // Filter on Initialized and non-matching data size
-            ])),
-        );
-        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
-        assert_eq!(result.len(), 0);
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{
+                    "memcmp": {
+                        "offset": 4,
+                        "bytes": bs58::encode(vec![1, 0, 0, 0]).into_string(),
+                    },
+                }, {
+                    "dataSize": 1,
+                }]}, // Filter on Initialized and non-matching data size
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_rpc_get_program_accounts_pagination() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let program_id = Pubkey::new_unique();
+        let mut pubkeys: Vec<_> = (0..5)
+            .map(|_| {
+                let pubkey = Pubkey::new_unique();
+                bank.store_account(&pubkey, &AccountSharedData::new(42, 0, &program_id));
+                pubkey
+            })
+            .collect();
+        pubkeys.sort_unstable();
+
+        // The pages are in pubkey order, and the last one has no cursor.
+        let mut cursor = None;
+        let mut paginated_pubkeys = vec![];
+        let mut num_pages = 0;
+        loop {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"pageSize": 2, "cursor": cursor},
+                ])),
+            );
+            let page: RpcKeyedAccountsPage = parse_success_result(rpc.handle_request_sync(request));
+            assert!(page.accounts.len() <= 2);
+            paginated_pubkeys.extend(page.accounts.into_iter().map(|account| account.pubkey));
+            num_pages += 1;
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(num_pages, 3);
+        assert_eq!(
+            paginated_pubkeys,
+            pubkeys.iter().map(Pubkey::to_string).collect::<Vec<_>>()
+        );
+
+        // The page size is bounded.
+        for page_size in [0, MAX_GET_PROGRAM_ACCOUNTS_PAGE_SIZE + 1] {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([program_id.to_string(), {"pageSize": page_size}])),
+            );
+            let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(code, ErrorCode::InvalidParams.code());
+        }
+
+        // Cursors are opaque, so a pubkey is not one.
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([program_id.to_string(), {"cursor": pubkeys[0].to_string()}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_nonce_accounts_by_authority() {
+        let account_indexes = [
+            AccountSecondaryIndexes::default(),
+            AccountSecondaryIndexes {
+                keys: None,
+                indexes: HashSet::from([AccountIndex::NonceAuthority]),
+            },
+        ];
+        for account_indexes in account_indexes {
+            let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+                account_indexes,
+                ..JsonRpcConfig::default()
+            });
+            let bank = rpc.working_bank();
+            let authority = Pubkey::new_unique();
+            let new_nonce_account = |authority: &Pubkey| {
+                AccountSharedData::new_data(
+                    42,
+                    &nonce::state::Versions::new(nonce::State::new_initialized(
+                        authority,
+                        DurableNonce::default(),
+                        1000,
+                    )),
+                    &system_program::id(),
+                )
+                .unwrap()
+            };
+            let mut pubkeys: Vec<_> = (0..3)
+                .map(|_| {
+                    let pubkey = Pubkey::new_unique();
+                    bank.store_account(&pubkey, &new_nonce_account(&authority));
+                    pubkey
+                })
+                .collect();
+            pubkeys.sort();
+            bank.store_account(
+                &Pubkey::new_unique(),
+                &new_nonce_account(&Pubkey::new_unique()),
+            );
+
+            // The nonce account whose authority changed is not returned.
+            let reassigned_pubkey = pubkeys.pop().unwrap();
+            bank.store_account(
+                &reassigned_pubkey,
+                &new_nonce_account(&Pubkey::new_unique()),
+            );
+
+            let request = create_test_request(
+                "getNonceAccountsByAuthority",
+                Some(json!([authority.to_string(), {"encoding": "jsonParsed"}])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            let result_pubkeys: Vec<_> = result
+                .value
+                .iter()
+                .map(|keyed_account| keyed_account.pubkey.clone())
+                .collect();
+            assert_eq!(
+                result_pubkeys,
+                pubkeys.iter().map(ToString::to_string).collect::<Vec<_>>()
+            );
+            assert!(matches!(
+                &result.value[0].account.data,
+                solana_account_decoder::UiAccountData::Json(parsed_account)
+                    if parsed_account.program == "nonce"
+            ));
+
+            let request = create_test_request(
+                "getNonceAccountsByAuthority",
+                Some(json!([Pubkey::new_unique().to_string()])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert!(result.value.is_empty());
+        }
     }
 
     #[test]
@@ -6573,6 +8110,75 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_simulate_transactions() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let alice = Keypair::new();
+        let bob_pubkey = Pubkey::new_unique();
+        let fund_alice = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &alice.pubkey(),
+            10 * rent_exempt_amount,
+            recent_blockhash,
+        );
+        let fund_bob =
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let encoded_txs = [&fund_alice, &fund_bob]
+            .map(|tx| BASE64_STANDARD.encode(serialize(tx).unwrap()))
+            .to_vec();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let request = create_test_request(
+            "simulateTransactions",
+            Some(json!([
+                encoded_txs,
+                {
+                    "encoding": "base64",
+                    "accounts": {"addresses": [bob_pubkey.to_string()]},
+                },
+            ])),
+        );
+        let result: RpcResponse<Vec<RpcSimulateTransactionResult>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.len(), 2);
+        assert_eq!(result.value[0].err, None);
+        // Bob only exists after the second transaction.
+        assert_eq!(result.value[0].accounts, Some(vec![None]));
+        assert_eq!(result.value[1].err, None);
+        let bob_account = result.value[1].accounts.as_ref().unwrap()[0]
+            .as_ref()
+            .unwrap();
+        assert_eq!(bob_account.lamports, rent_exempt_amount);
+        // Nothing is committed to the bank.
+        assert_eq!(bank.get_balance(&bob_pubkey), 0);
+
+        let request = create_test_request(
+            "simulateTransactions",
+            Some(json!([Vec::<String>::new(), {"encoding": "base64"}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        let request = create_test_request(
+            "simulateTransactions",
+            Some(json!([
+                vec![encoded_txs[0].clone(); MAX_SIMULATE_TRANSACTIONS + 1],
+                {"encoding": "base64"},
+            ])),
+        );
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        assert_eq!(
+            message,
+            format!("Too many transactions provided; max {MAX_SIMULATE_TRANSACTIONS}")
+        );
+    }
+
     #[test]
     #[should_panic(expected = "simulation bank must be frozen")]
     fn test_rpc_simulate_transaction_panic_on_unfrozen_bank() {
@@ -6768,6 +8374,7 @@ pub mod tests {
             cluster_info,
             Hash::default(),
             None,
+            None,
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
@@ -6775,6 +8382,17 @@ pub mod tests {
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::new(TransactionProvenanceCache::default()),
+            Arc::new(SlotAccountFilterCache::default()),
+            Arc::new(ShredLatencyTracker::default()),
+            Arc::new(LeaderSlotStatsTracker::default()),
+            Arc::new(ConfirmationLatencyTracker::default()),
+            Arc::new(SkippedSlotReasonsTracker::default()),
+            Arc::new(AccountCongestionTracker::default()),
+            Arc::new(ConfirmationProofCache::default()),
+            Arc::new(BufferedTransactionSampler::default()),
+            Arc::new(AccountGrowthTracker::default()),
+            None,
             runtime.clone(),
         );
 
@@ -6991,11 +8609,33 @@ pub mod tests {
     fn test_rpc_get_identity() {
         let rpc = RpcHandler::start();
         let request = create_test_request("getIdentity", None);
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected: Value = json!({ "identity": rpc.identity.to_string() });
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_health_detail() {
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getHealthDetail", None);
+        let result: RpcHealthDetail = parse_success_result(rpc.handle_request_sync(request));
+        let kinds: Vec<_> = result
+            .conditions
+            .iter()
+            .map(|condition| condition.kind)
+            .collect();
+        // No optimistically confirmed slot is known, no vote account is
+        // checked and no snapshot is generated
+        assert_eq!(
+            kinds,
+            vec![
+                RpcHealthConditionKind::AccountsDbCatchingUp,
+                RpcHealthConditionKind::RepairStorm,
+            ]
+        );
+        assert!(result.healthy);
+    }
+
     #[test]
     fn test_rpc_get_max_slots() {
         let rpc = RpcHandler::start();
@@ -7015,7 +8655,7 @@ pub mod tests {
     fn test_rpc_get_version() {
         let rpc = RpcHandler::start();
         let request = create_test_request("getVersion", None);
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = {
             let version = solana_version::Version::default();
             json!({
@@ -7072,6 +8712,7 @@ pub mod tests {
             cluster_info,
             Hash::default(),
             None,
+            None,
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
@@ -7079,6 +8720,17 @@ pub mod tests {
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::new(TransactionProvenanceCache::default()),
+            Arc::new(SlotAccountFilterCache::default()),
+            Arc::new(ShredLatencyTracker::default()),
+            Arc::new(LeaderSlotStatsTracker::default()),
+            Arc::new(ConfirmationLatencyTracker::default()),
+            Arc::new(SkippedSlotReasonsTracker::default()),
+            Arc::new(AccountCongestionTracker::default()),
+            Arc::new(ConfirmationProofCache::default()),
+            Arc::new(BufferedTransactionSampler::default()),
+            Arc::new(AccountGrowthTracker::default()),
+            None,
             runtime,
         );
 
@@ -7152,7 +8804,7 @@ pub mod tests {
         assert_eq!(result, expected);
 
         let request = create_test_request("getBlockCommitment", Some(json!([1u64])));
-        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!({
             "commitment": null,
             "totalStake": expected_total_stake,
@@ -7203,6 +8855,52 @@ pub mod tests {
         assert_eq!(response, expected);
     }
 
+    #[test]
+    fn test_get_signatures_for_program() {
+        let program_id = system_program::id().to_string();
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getSignaturesForProgram", Some(json!([program_id])));
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(
+            code,
+            JSON_RPC_SERVER_ERROR_PROGRAM_SIGNATURE_INDEX_NOT_AVAILABLE
+        );
+
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            enable_rpc_transaction_history: true,
+            enable_program_signature_index: true,
+            ..JsonRpcConfig::default()
+        });
+        let signatures = rpc.create_test_transactions_and_populate_blockstore();
+
+        let request = create_test_request("getSignaturesForProgram", Some(json!([program_id])));
+        let result: Vec<RpcConfirmedTransactionStatusWithSignature> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let result: Vec<_> = result.into_iter().map(|info| info.signature).collect();
+        assert_eq!(
+            result,
+            vec![signatures[1].to_string(), signatures[0].to_string()]
+        );
+
+        let request = create_test_request(
+            "getSignaturesForProgram",
+            Some(json!([program_id, {"before": signatures[1].to_string(), "limit": 1}])),
+        );
+        let result: Vec<RpcConfirmedTransactionStatusWithSignature> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].signature, signatures[0].to_string());
+        assert!(result[0].err.is_none());
+
+        let request = create_test_request(
+            "getSignaturesForProgram",
+            Some(json!([Pubkey::new_unique().to_string()])),
+        );
+        let result: Vec<RpcConfirmedTransactionStatusWithSignature> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn test_get_block() {
         let mut rpc = RpcHandler::start();
@@ -7310,6 +9008,46 @@ pub mod tests {
         assert_eq!(response, expected);
     }
 
+    #[test]
+    fn test_get_block_fields() {
+        let rpc = RpcHandler::start();
+        rpc.create_test_transactions_and_populate_blockstore();
+
+        let request = create_test_request(
+            "getBlock",
+            Some(json!([0u64, null, ["blockTime", "transactions.meta.err"]])),
+        );
+        let result: serde_json::Value = parse_success_result(rpc.handle_request_sync(request));
+        let block = result.as_object().unwrap();
+        assert_eq!(
+            block.keys().collect::<Vec<_>>(),
+            vec!["blockTime", "transactions"]
+        );
+        let transactions = block["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 2);
+        for transaction in transactions {
+            assert_eq!(
+                transaction.as_object().unwrap().keys().collect::<Vec<_>>(),
+                vec!["meta"]
+            );
+        }
+        assert!(transactions
+            .iter()
+            .any(|transaction| transaction["meta"] == json!({"err": null})));
+        assert!(transactions.iter().any(|transaction| transaction["meta"]
+            == json!({"err": {"InstructionError": [0, {"Custom": 1}]}})));
+
+        let request = create_test_request("getBlock", Some(json!([0u64, null, ["meta.err"]])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidParams.code(),
+                String::from("Invalid field: meta.err")
+            )
+        );
+    }
+
     #[test]
     fn test_get_block_config() {
         let rpc = RpcHandler::start();
@@ -8775,6 +10513,7 @@ pub mod tests {
             cluster_info,
             Hash::default(),
             None,
+            None,
             optimistically_confirmed_bank.clone(),
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
@@ -8782,6 +10521,17 @@ pub mod tests {
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::new(TransactionProvenanceCache::default()),
+            Arc::new(SlotAccountFilterCache::default()),
+            Arc::new(ShredLatencyTracker::default()),
+            Arc::new(LeaderSlotStatsTracker::default()),
+            Arc::new(ConfirmationLatencyTracker::default()),
+            Arc::new(SkippedSlotReasonsTracker::default()),
+            Arc::new(AccountCongestionTracker::default()),
+            Arc::new(ConfirmationProofCache::default()),
+            Arc::new(BufferedTransactionSampler::default()),
+            Arc::new(AccountGrowthTracker::default()),
+            None,
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
 
@@ -9265,4 +11015,410 @@ pub mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_rpc_get_slot_account_filter() {
+        let request = create_test_request("getSlotAccountFilter", Some(json!([0u64])));
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            slot_account_filter_slots: 2,
+            ..JsonRpcConfig::default()
+        });
+        let written = Pubkey::new_unique();
+        rpc.meta
+            .slot_account_filter_cache
+            .insert(Arc::new(SlotAccountFilter::new(5, &[written])));
+
+        let request = create_test_request("getSlotAccountFilter", Some(json!([5u64])));
+        let result: Option<RpcSlotAccountFilter> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let result = result.unwrap();
+        assert_eq!(result.slot, 5);
+        assert_eq!(result.num_accounts, 1);
+        assert_eq!(result, SlotAccountFilter::new(5, &[written]).to_rpc());
+
+        let request = create_test_request("getSlotAccountFilter", Some(json!([6u64])));
+        let result: Option<RpcSlotAccountFilter> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_rpc_get_shred_latency() {
+        let request = create_test_request("getShredLatency", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        let leader_schedule_cache = rpc.meta.leader_schedule_cache.clone();
+        rpc.meta.shred_latency_tracker =
+            Arc::new(ShredLatencyTracker::new(4, leader_schedule_cache));
+        rpc.meta.shred_latency_tracker.record([0, 0, 1], 1_000);
+
+        let request = create_test_request("getShredLatency", None);
+        let result: RpcShredLatency = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.shred_latency_tracker.to_rpc());
+        assert_eq!(result.slots.len(), 2);
+        assert_eq!(result.slots[0].num_shreds, 2);
+        let leader = rpc.leader_pubkey().to_string();
+        assert_eq!(result.slots[0].leader, Some(leader));
+        assert_eq!(result.leaders.len(), 1);
+        assert_eq!(result.leaders[0].num_slots, 2);
+        assert_eq!(result.leaders[0].num_shreds, 3);
+    }
+
+    #[test]
+    fn test_rpc_get_confirmation_latency() {
+        let request = create_test_request("getConfirmationLatency", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.confirmation_latency_tracker =
+            Arc::new(ConfirmationLatencyTracker::new(4, rpc.blockstore.clone()));
+        let slot = rpc.working_bank().slot();
+        let first_shred_timestamp = rpc
+            .blockstore
+            .meta(slot)
+            .unwrap()
+            .map(|meta| meta.first_shred_timestamp)
+            .filter(|&timestamp| timestamp > 0);
+        rpc.meta
+            .confirmation_latency_tracker
+            .record_optimistic_confirmation(slot, solana_sdk::timing::timestamp());
+
+        let request = create_test_request("getConfirmationLatency", None);
+        let result: RpcConfirmationLatency = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.confirmation_latency_tracker.to_rpc());
+        assert_eq!(result.slots.len(), 1);
+        assert_eq!(result.slots[0].slot, slot);
+        assert_eq!(result.slots[0].first_shred_timestamp, first_shred_timestamp);
+        assert_eq!(result.slots[0].finalization_latency_ms, None);
+    }
+
+    #[test]
+    fn test_rpc_get_account_congestion() {
+        let request = create_test_request("getAccountCongestion", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.account_congestion_tracker = Arc::new(AccountCongestionTracker::new(4));
+        let bank = rpc.working_bank();
+        rpc.meta
+            .account_congestion_tracker
+            .record_frozen_bank(&bank);
+
+        let request = create_test_request("getAccountCongestion", None);
+        let result: Vec<RpcSlotAccountCongestion> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.account_congestion_tracker.to_rpc(&[]));
+        assert_eq!(result[0].slot, bank.slot());
+
+        let request = create_test_request(
+            "getAccountCongestion",
+            Some(json!([[Pubkey::new_unique().to_string()]])),
+        );
+        let result: Vec<RpcSlotAccountCongestion> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert!(result[0].accounts.is_empty());
+
+        let request = create_test_request("getAccountCongestion", Some(json!([["invalid"]])));
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_confirmation_proof() {
+        let request = create_test_request("getConfirmationProof", Some(json!([1])));
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.confirmation_proof_cache = Arc::new(ConfirmationProofCache::new(4));
+        let bank_hash = Hash::new_unique();
+        rpc.meta.confirmation_proof_cache.record_vote(
+            1,
+            bank_hash,
+            ConfirmationVote {
+                vote_account: Pubkey::new_unique(),
+                stake: 100,
+                voted_slot: 2,
+                signature: Signature::new_unique(),
+                transaction: None,
+            },
+        );
+        rpc.meta
+            .confirmation_proof_cache
+            .record_confirmation(1, bank_hash, 100);
+
+        let request = create_test_request("getConfirmationProof", Some(json!([1])));
+        let result: Option<RpcConfirmationProof> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.confirmation_proof_cache.to_rpc(1));
+        assert_eq!(result.unwrap().confirmed_stake, 100);
+
+        let request = create_test_request("getConfirmationProof", Some(json!([2])));
+        let result: Option<RpcConfirmationProof> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_rpc_get_buffered_transactions() {
+        let request = create_test_request("getBufferedTransactions", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.buffered_transaction_sampler = Arc::new(BufferedTransactionSampler::new(4));
+        let request = create_test_request("getBufferedTransactions", None);
+        let result: Option<RpcBufferedTransactions> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+
+        let signature = Signature::new_unique();
+        rpc.meta.buffered_transaction_sampler.record(
+            3,
+            7,
+            vec![BufferedTransaction {
+                signature,
+                priority: 10,
+                cost: 1_000,
+                slots_waited: 2,
+                num_retries: 1,
+            }],
+        );
+        let request = create_test_request("getBufferedTransactions", None);
+        let result: Option<RpcBufferedTransactions> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.buffered_transaction_sampler.to_rpc());
+        let result = result.unwrap();
+        assert_eq!(result.num_buffered, 7);
+        assert_eq!(result.transactions[0].signature, signature.to_string());
+    }
+
+    #[test]
+    fn test_rpc_get_account_growth() {
+        let request = create_test_request("getAccountGrowth", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.account_growth_tracker = Arc::new(AccountGrowthTracker::new(2));
+        let request = create_test_request("getAccountGrowth", None);
+        let result: Option<RpcAccountGrowth> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+
+        rpc.meta.account_growth_tracker.record(AccountGrowthSample {
+            slot: 5,
+            epoch: 1,
+            num_accounts: 10,
+            num_accounts_created: 2,
+            ..AccountGrowthSample::default()
+        });
+        let request = create_test_request("getAccountGrowth", None);
+        let result: Option<RpcAccountGrowth> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.account_growth_tracker.to_rpc());
+        let result = result.unwrap();
+        assert_eq!(result.num_accounts, 10);
+        assert_eq!(result.epochs[0].num_accounts_created, 2);
+    }
+
+    #[test]
+    fn test_rpc_get_program_cache_stats() {
+        let rpc = RpcHandler::start();
+        let pinned_program = Pubkey::new_unique();
+        let bank = rpc.working_bank();
+        bank.set_pinned_programs_in_program_cache(HashSet::from([pinned_program]));
+
+        let request = create_test_request("getProgramCacheStats", None);
+        let result: RpcProgramCacheStats = parse_success_result(rpc.handle_request_sync(request));
+        let (stats, _pinned_programs) = bank.program_cache_cumulative_stats();
+        assert_eq!(result.hits, stats.hits);
+        assert_eq!(result.misses, stats.misses);
+        assert_eq!(result.pinned_programs, vec![pinned_program.to_string()]);
+        assert!(result.programs.len() <= MAX_GET_PROGRAM_CACHE_STATS_PROGRAMS);
+    }
+
+    #[test]
+    fn test_rpc_get_skipped_slot_reasons() {
+        let request = create_test_request("getSkippedSlotReasons", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.skipped_slot_reasons_tracker = Arc::new(SkippedSlotReasonsTracker::new(
+            4,
+            rpc.blockstore.clone(),
+            rpc.meta.leader_schedule_cache.clone(),
+        ));
+        let slot = rpc.working_bank().slot();
+        let leader = rpc
+            .meta
+            .leader_schedule_cache
+            .slot_leader_at(slot + 1, None)
+            .unwrap();
+        // The leader slot after the working bank is not rooted.
+        rpc.meta
+            .skipped_slot_reasons_tracker
+            .record_root(slot, &leader);
+        rpc.meta
+            .skipped_slot_reasons_tracker
+            .record_root(slot + 2, &leader);
+
+        let request = create_test_request("getSkippedSlotReasons", None);
+        let result: Vec<RpcSkippedSlotReason> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, rpc.meta.skipped_slot_reasons_tracker.to_rpc());
+        assert_eq!(result[0].slot, slot + 1);
+        assert_eq!(result[0].cause, RpcSkippedSlotCause::NotStarted);
+    }
+
+    #[test]
+    fn test_rpc_get_recent_leader_slots() {
+        let request = create_test_request("getRecentLeaderSlots", None);
+        let (code, _message) =
+            parse_failure_response(RpcHandler::start().handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidRequest.code());
+
+        let mut rpc = RpcHandler::start();
+        rpc.meta.leader_slot_stats_tracker = Arc::new(LeaderSlotStatsTracker::new(4));
+        let request = create_test_request(
+            "getRecentLeaderSlots",
+            Some(json!([MAX_GET_RECENT_LEADER_SLOTS + 1])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        // The node is not in the leader schedule.
+        let request = create_test_request("getRecentLeaderSlots", None);
+        let result: Vec<RpcLeaderSlot> = parse_success_result(rpc.handle_request_sync(request));
+        assert!(result.is_empty());
+
+        let stats = LeaderSlotStats {
+            attempted_transactions: 3,
+            committed_vote_transactions: 1,
+            committed_non_vote_transactions: 1,
+            compute_units: 100,
+        };
+        let slot = rpc.working_bank().slot();
+        rpc.meta.leader_slot_stats_tracker.record(slot, &stats);
+        let root = rpc.bank_forks.read().unwrap().root();
+        assert_eq!(
+            rpc.meta.recent_leader_slots(&rpc.leader_pubkey(), 1),
+            vec![RpcLeaderSlot {
+                slot,
+                rooted: slot == root,
+                skipped_reason: None,
+                stats: Some(stats.to_rpc()),
+            }],
+        );
+    }
+
+    #[test]
+    fn test_rpc_get_vote_accounts_detailed() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let leader_vote_pubkey = rpc.leader_vote_keypair.pubkey();
+
+        // Create a vote account with no stake, which voted a few times.
+        let alice_vote_keypair = Keypair::new();
+        let mut alice_vote_state = VoteState::new(
+            &VoteInit {
+                node_pubkey: rpc.mint_keypair.pubkey(),
+                authorized_voter: alice_vote_keypair.pubkey(),
+                authorized_withdrawer: alice_vote_keypair.pubkey(),
+                commission: 7,
+            },
+            &bank.get_sysvar_cache_for_tests().get_clock().unwrap(),
+        );
+        for slot in 1..=3 {
+            alice_vote_state.process_next_vote_slot(slot, 0, 0);
+        }
+        alice_vote_state.root_slot = Some(0);
+        alice_vote_state.epoch_credits = vec![(0, 8, 0)];
+        alice_vote_state.last_timestamp = BlockTimestamp {
+            slot: 3,
+            timestamp: 42,
+        };
+        rpc.store_vote_account(&alice_vote_keypair.pubkey(), alice_vote_state);
+
+        let request = create_test_request(
+            "getVoteAccountsDetailed",
+            Some(json!([{"votePubkeys": [alice_vote_keypair.pubkey().to_string()]}])),
+        );
+        let result: Vec<RpcVoteAccountDetailed> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result,
+            vec![RpcVoteAccountDetailed {
+                vote_pubkey: alice_vote_keypair.pubkey().to_string(),
+                node_pubkey: rpc.mint_keypair.pubkey().to_string(),
+                activated_stake: 0,
+                commission: 7,
+                epoch_vote_account: false,
+                authorized_voter: Some(alice_vote_keypair.pubkey().to_string()),
+                votes: (1..=3)
+                    .map(|slot| RpcVoteLockout {
+                        slot,
+                        confirmation_count: 4 - slot as u32,
+                    })
+                    .collect(),
+                root_slot: Some(0),
+                epoch_credits: vec![(0, 8, 0)],
+                credits: 8,
+                last_timestamp: RpcVoteTimestamp {
+                    slot: 3,
+                    timestamp: 42,
+                },
+            }],
+        );
+
+        let request = create_test_request(
+            "getVoteAccountsDetailed",
+            Some(json!([{"nodePubkeys": [rpc.leader_pubkey().to_string()]}])),
+        );
+        let result: Vec<RpcVoteAccountDetailed> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].vote_pubkey, leader_vote_pubkey.to_string());
+        assert!(result[0].activated_stake > 0);
+        assert!(result[0].epoch_vote_account);
+
+        // The staked vote accounts come first.
+        let request = create_test_request("getVoteAccountsDetailed", None);
+        let result: Vec<RpcVoteAccountDetailed> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let vote_pubkeys: Vec<_> = result.iter().map(|info| info.vote_pubkey.clone()).collect();
+        assert_eq!(
+            vote_pubkeys,
+            vec![
+                leader_vote_pubkey.to_string(),
+                alice_vote_keypair.pubkey().to_string()
+            ],
+        );
+
+        let vote_pubkeys: Vec<_> = (0..=MAX_GET_VOTE_ACCOUNTS_DETAILED_PUBKEYS)
+            .map(|_| Pubkey::new_unique().to_string())
+            .collect();
+        let request = create_test_request(
+            "getVoteAccountsDetailed",
+            Some(json!([{ "votePubkeys": vote_pubkeys }])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
 }
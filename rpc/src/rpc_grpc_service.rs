@@ -0,0 +1,228 @@
+//! gRPC interface of the core RPC queries.
+//!
+//! Accounts, transaction statuses and blocks are served by the request processor of the JSON RPC
+//! API, blocks and errors in the messages of the storage protos. The slots of the node are
+//! streamed as its processed bank changes.
+
+pub mod generated {
+    tonic::include_proto!("solana.rpc");
+}
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    generated::{
+        rpc_server::{Rpc, RpcServer},
+        Account, CommitmentLevel, Confirmations, GetAccountRequest, GetAccountResponse,
+        GetBlockRequest, GetBlockResponse, GetTransactionStatusRequest,
+        GetTransactionStatusResponse, SlotUpdate, SubscribeSlotsRequest, TransactionStatus,
+    },
+    jsonrpc_core::{
+        futures::{stream, Stream},
+        ErrorCode,
+    },
+    log::*,
+    solana_rpc_client_api::config::RpcSignatureStatusConfig,
+    solana_sdk::{
+        account::ReadableAccount, commitment_config::CommitmentConfig, pubkey::Pubkey,
+        signature::Signature,
+    },
+    solana_transaction_status::TransactionConfirmationStatus,
+    std::{future::Future, net::SocketAddr, pin::Pin, time::Duration},
+    tonic::{Request, Response, Status},
+};
+
+/// How often the processed bank is checked for a new slot, for the slot subscriptions
+const SLOT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Serves the gRPC interface on `grpc_addr`, until `exit` completes
+pub async fn serve(
+    grpc_addr: SocketAddr,
+    meta: JsonRpcRequestProcessor,
+    exit: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    info!("rpc grpc bound to {grpc_addr:?}");
+    tonic::transport::Server::builder()
+        .add_service(RpcServer::new(RpcGrpcService { meta }))
+        .serve_with_shutdown(grpc_addr, exit)
+        .await
+}
+
+struct RpcGrpcService {
+    meta: JsonRpcRequestProcessor,
+}
+
+fn commitment_config(commitment: i32) -> Result<CommitmentConfig, Status> {
+    match CommitmentLevel::from_i32(commitment) {
+        Some(CommitmentLevel::Finalized) => Ok(CommitmentConfig::finalized()),
+        Some(CommitmentLevel::Confirmed) => Ok(CommitmentConfig::confirmed()),
+        Some(CommitmentLevel::Processed) => Ok(CommitmentConfig::processed()),
+        None => Err(Status::invalid_argument(format!(
+            "invalid commitment {commitment}"
+        ))),
+    }
+}
+
+fn status_from_rpc_error(err: jsonrpc_core::Error) -> Status {
+    match err.code {
+        ErrorCode::InvalidParams | ErrorCode::InvalidRequest => {
+            Status::invalid_argument(err.message)
+        }
+        ErrorCode::ServerError(_) => Status::failed_precondition(err.message),
+        _ => Status::internal(err.message),
+    }
+}
+
+#[tonic::async_trait]
+impl Rpc for RpcGrpcService {
+    async fn get_account(
+        &self,
+        request: Request<GetAccountRequest>,
+    ) -> Result<Response<GetAccountResponse>, Status> {
+        let request = request.into_inner();
+        let pubkey = Pubkey::try_from(request.pubkey.as_slice())
+            .map_err(|_| Status::invalid_argument("invalid pubkey"))?;
+        let bank = self.meta.bank(Some(commitment_config(request.commitment)?));
+        let slot = bank.slot();
+        let account = tokio::task::spawn_blocking(move || bank.get_account(&pubkey))
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+        Ok(Response::new(GetAccountResponse {
+            slot,
+            account: account.map(|account| Account {
+                lamports: account.lamports(),
+                owner: account.owner().to_bytes().to_vec(),
+                executable: account.executable(),
+                rent_epoch: account.rent_epoch(),
+                data: account.data().to_vec(),
+            }),
+        }))
+    }
+
+    async fn get_transaction_status(
+        &self,
+        request: Request<GetTransactionStatusRequest>,
+    ) -> Result<Response<GetTransactionStatusResponse>, Status> {
+        let request = request.into_inner();
+        let signature = Signature::try_from(request.signature.as_slice())
+            .map_err(|_| Status::invalid_argument("invalid signature"))?;
+        let response = self
+            .meta
+            .get_signature_statuses(
+                vec![signature],
+                Some(RpcSignatureStatusConfig {
+                    search_transaction_history: request.search_transaction_history,
+                }),
+            )
+            .await
+            .map_err(status_from_rpc_error)?;
+        let status = response.value.into_iter().flatten().next().map(|status| {
+            let confirmation_status = match status.confirmation_status {
+                Some(TransactionConfirmationStatus::Processed) => CommitmentLevel::Processed,
+                Some(TransactionConfirmationStatus::Confirmed) => CommitmentLevel::Confirmed,
+                Some(TransactionConfirmationStatus::Finalized) | None => CommitmentLevel::Finalized,
+            };
+            TransactionStatus {
+                slot: status.slot,
+                confirmations: status.confirmations.map(|confirmations| Confirmations {
+                    confirmations: confirmations as u64,
+                }),
+                err: status.err.map(Into::into),
+                confirmation_status: confirmation_status.into(),
+            }
+        });
+        Ok(Response::new(GetTransactionStatusResponse {
+            slot: response.context.slot,
+            status,
+        }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<GetBlockRequest>,
+    ) -> Result<Response<GetBlockResponse>, Status> {
+        let request = request.into_inner();
+        let commitment = commitment_config(request.commitment)?;
+        let block = self
+            .meta
+            .get_confirmed_block(request.slot, commitment)
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(Response::new(GetBlockResponse {
+            block: block.map(Into::into),
+        }))
+    }
+
+    type SubscribeSlotsStream = Pin<Box<dyn Stream<Item = Result<SlotUpdate, Status>> + Send>>;
+
+    async fn subscribe_slots(
+        &self,
+        _request: Request<SubscribeSlotsRequest>,
+    ) -> Result<Response<Self::SubscribeSlotsStream>, Status> {
+        let mut interval = tokio::time::interval(SLOT_POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let slot_updates = stream::unfold(
+            (self.meta.clone(), interval, None),
+            |(meta, mut interval, last_slot)| async move {
+                loop {
+                    interval.tick().await;
+                    let bank = meta.bank(Some(CommitmentConfig::processed()));
+                    if last_slot == Some(bank.slot()) {
+                        continue;
+                    }
+                    let slot_update = SlotUpdate {
+                        slot: bank.slot(),
+                        parent: bank.parent_slot(),
+                        confirmed: meta.bank(Some(CommitmentConfig::confirmed())).slot(),
+                        root: meta.bank(Some(CommitmentConfig::finalized())).slot(),
+                    };
+                    return Some((Ok(slot_update), (meta, interval, Some(bank.slot()))));
+                }
+            },
+        );
+        Ok(Response::new(Box::pin(slot_updates)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::transaction::TransactionError};
+
+    #[test]
+    fn test_commitment_config() {
+        assert_eq!(
+            commitment_config(CommitmentLevel::Finalized.into()).unwrap(),
+            CommitmentConfig::finalized()
+        );
+        assert_eq!(
+            commitment_config(CommitmentLevel::Processed.into()).unwrap(),
+            CommitmentConfig::processed()
+        );
+        assert_eq!(
+            commitment_config(3).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_status_from_rpc_error() {
+        assert_eq!(
+            status_from_rpc_error(jsonrpc_core::Error::invalid_params("bad")).code(),
+            tonic::Code::InvalidArgument
+        );
+        let err: jsonrpc_core::Error =
+            solana_rpc_client_api::custom_error::RpcCustomError::BlockNotAvailable { slot: 1 }
+                .into();
+        assert_eq!(
+            status_from_rpc_error(err).code(),
+            tonic::Code::FailedPrecondition
+        );
+        // The errors of the statuses share the encoding of the storage protos.
+        let err = solana_storage_proto::convert::tx_by_addr::TransactionError::from(
+            TransactionError::AccountInUse,
+        );
+        assert_eq!(
+            TransactionError::try_from(err).unwrap(),
+            TransactionError::AccountInUse
+        );
+    }
+}
@@ -1,13 +1,46 @@
 use {
     crate::optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
     solana_ledger::blockstore::Blockstore,
-    solana_sdk::clock::Slot,
+    solana_rpc_client_api::response::{
+        RpcHealthCondition, RpcHealthConditionKind, RpcHealthDetail,
+    },
+    solana_runtime::{snapshot_config::SnapshotConfig, snapshot_utils},
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
     std::sync::{
         atomic::{AtomicBool, Ordering},
         Arc, RwLock,
     },
 };
 
+pub const DEFAULT_HEALTH_MAX_SLOTS_SINCE_LAST_VOTE: u64 = 150;
+// Twice the default full snapshot interval
+pub const DEFAULT_HEALTH_MAX_SNAPSHOT_AGE_SLOTS: u64 = 100_000;
+pub const DEFAULT_HEALTH_MAX_INCOMPLETE_SLOTS: u64 = 32;
+// Bounds the scan of the slot metas above the root by the repair storm check
+const MAX_SCANNED_SLOT_METAS: usize = 10_000;
+
+/// The thresholds of the conditions of `RpcHealth::check_detail()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcHealthDetailConfig {
+    /// The vote account whose votes are checked, `None` if the node does not
+    /// vote
+    pub vote_account: Option<Pubkey>,
+    pub max_slots_since_last_vote: u64,
+    pub max_snapshot_age_slots: u64,
+    pub max_incomplete_slots: u64,
+}
+
+impl Default for RpcHealthDetailConfig {
+    fn default() -> Self {
+        Self {
+            vote_account: None,
+            max_slots_since_last_vote: DEFAULT_HEALTH_MAX_SLOTS_SINCE_LAST_VOTE,
+            max_snapshot_age_slots: DEFAULT_HEALTH_MAX_SNAPSHOT_AGE_SLOTS,
+            max_incomplete_slots: DEFAULT_HEALTH_MAX_INCOMPLETE_SLOTS,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum RpcHealthStatus {
     Ok,
@@ -21,6 +54,9 @@ pub struct RpcHealth {
     health_check_slot_distance: u64,
     override_health_check: Arc<AtomicBool>,
     startup_verification_complete: Arc<AtomicBool>,
+    detail_config: RpcHealthDetailConfig,
+    /// `None` if the node does not generate snapshots
+    snapshot_config: Option<SnapshotConfig>,
     #[cfg(test)]
     stub_health_status: std::sync::RwLock<Option<RpcHealthStatus>>,
 }
@@ -39,11 +75,26 @@ impl RpcHealth {
             health_check_slot_distance,
             override_health_check,
             startup_verification_complete,
+            detail_config: RpcHealthDetailConfig::default(),
+            snapshot_config: None,
             #[cfg(test)]
             stub_health_status: std::sync::RwLock::new(None),
         }
     }
 
+    /// Sets the thresholds of `check_detail()`, and the snapshot archives the
+    /// staleness of which it checks
+    pub fn with_detail_config(
+        mut self,
+        detail_config: RpcHealthDetailConfig,
+        snapshot_config: Option<SnapshotConfig>,
+    ) -> Self {
+        self.detail_config = detail_config;
+        self.snapshot_config =
+            snapshot_config.filter(|snapshot_config| snapshot_config.should_generate_snapshots());
+        self
+    }
+
     pub fn check(&self) -> RpcHealthStatus {
         #[cfg(test)]
         {
@@ -81,17 +132,9 @@ impl RpcHealth {
             .bank
             .slot();
 
-        let mut optimistic_slot_infos = match self.blockstore.get_latest_optimistic_slots(1) {
-            Ok(infos) => infos,
-            Err(err) => {
-                warn!("health check: blockstore error: {err}");
-                return RpcHealthStatus::Unknown;
-            }
-        };
-        let Some((cluster_latest_optimistically_confirmed_slot, _, _)) =
-            optimistic_slot_infos.pop()
+        let Some(cluster_latest_optimistically_confirmed_slot) =
+            self.cluster_latest_optimistically_confirmed_slot()
         else {
-            warn!("health check: blockstore does not contain any optimistically confirmed slots");
             return RpcHealthStatus::Unknown;
         };
 
@@ -112,6 +155,139 @@ impl RpcHealth {
         }
     }
 
+    fn cluster_latest_optimistically_confirmed_slot(&self) -> Option<Slot> {
+        let mut optimistic_slot_infos = match self.blockstore.get_latest_optimistic_slots(1) {
+            Ok(infos) => infos,
+            Err(err) => {
+                warn!("health check: blockstore error: {err}");
+                return None;
+            }
+        };
+        let Some((cluster_latest_optimistically_confirmed_slot, _, _)) =
+            optimistic_slot_infos.pop()
+        else {
+            warn!("health check: blockstore does not contain any optimistically confirmed slots");
+            return None;
+        };
+        Some(cluster_latest_optimistically_confirmed_slot)
+    }
+
+    /// Checks each of the conditions the health of the node is made of, the
+    /// ones which cannot be checked are left out
+    pub fn check_detail(&self) -> RpcHealthDetail {
+        let mut conditions = vec![];
+
+        let catching_up = !self.startup_verification_complete.load(Ordering::Acquire);
+        conditions.push(new_condition(
+            RpcHealthConditionKind::AccountsDbCatchingUp,
+            u64::from(catching_up),
+            0,
+            if catching_up {
+                "the accounts are being verified".to_string()
+            } else {
+                "the accounts are verified".to_string()
+            },
+        ));
+
+        let bank = self
+            .optimistically_confirmed_bank
+            .read()
+            .unwrap()
+            .bank
+            .clone();
+        if let Some(cluster_slot) = self.cluster_latest_optimistically_confirmed_slot() {
+            let num_slots = cluster_slot.saturating_sub(bank.slot());
+            conditions.push(new_condition(
+                RpcHealthConditionKind::BehindOnSlots,
+                num_slots,
+                self.health_check_slot_distance,
+                format!(
+                    "behind by {num_slots} slots: me={}, latest cluster={cluster_slot}",
+                    bank.slot()
+                ),
+            ));
+        }
+
+        if let Some(vote_account) = &self.detail_config.vote_account {
+            let last_voted_slot = bank
+                .get_vote_account(vote_account)
+                .and_then(|vote_account| vote_account.vote_state_view().last_voted_slot());
+            let (num_slots, message) = match last_voted_slot {
+                Some(last_voted_slot) => {
+                    let num_slots = bank.slot().saturating_sub(last_voted_slot);
+                    (
+                        num_slots,
+                        format!(
+                            "last vote landed for slot {last_voted_slot}, {num_slots} slots ago"
+                        ),
+                    )
+                }
+                None => (bank.slot(), format!("no vote of {vote_account} landed")),
+            };
+            conditions.push(new_condition(
+                RpcHealthConditionKind::NoRecentVotes,
+                num_slots,
+                self.detail_config.max_slots_since_last_vote,
+                message,
+            ));
+        }
+
+        let root = self.blockstore.max_root();
+        if let Some(snapshot_config) = &self.snapshot_config {
+            let snapshot_slot = snapshot_utils::get_highest_full_snapshot_archive_slot(
+                &snapshot_config.full_snapshot_archives_dir,
+            )
+            .map(|full_snapshot_slot| {
+                snapshot_utils::get_highest_incremental_snapshot_archive_slot(
+                    &snapshot_config.incremental_snapshot_archives_dir,
+                    full_snapshot_slot,
+                )
+                .unwrap_or(full_snapshot_slot)
+            });
+            let (num_slots, message) = match snapshot_slot {
+                Some(snapshot_slot) => {
+                    let num_slots = root.saturating_sub(snapshot_slot);
+                    (
+                        num_slots,
+                        format!(
+                            "latest snapshot archive of slot {snapshot_slot}, {num_slots} slots \
+                             behind the root"
+                        ),
+                    )
+                }
+                None => (root, "no snapshot archive".to_string()),
+            };
+            conditions.push(new_condition(
+                RpcHealthConditionKind::SnapshotStale,
+                num_slots,
+                self.detail_config.max_snapshot_age_slots,
+                message,
+            ));
+        }
+
+        match self.blockstore.slot_meta_iterator(root.saturating_add(1)) {
+            Ok(slot_metas) => {
+                let num_incomplete_slots = slot_metas
+                    .take(MAX_SCANNED_SLOT_METAS)
+                    .filter(|(_, slot_meta)| !slot_meta.is_full())
+                    .count() as u64;
+                conditions.push(new_condition(
+                    RpcHealthConditionKind::RepairStorm,
+                    num_incomplete_slots,
+                    self.detail_config.max_incomplete_slots,
+                    format!("{num_incomplete_slots} incomplete slots above the root {root}"),
+                ));
+            }
+            Err(err) => warn!("health check: blockstore error: {err}"),
+        }
+
+        RpcHealthDetail {
+            healthy: self.override_health_check.load(Ordering::Relaxed)
+                || conditions.iter().all(|condition| condition.healthy),
+            conditions,
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn stub(
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
@@ -132,11 +308,27 @@ impl RpcHealth {
     }
 }
 
+fn new_condition(
+    kind: RpcHealthConditionKind,
+    value: u64,
+    threshold: u64,
+    message: String,
+) -> RpcHealthCondition {
+    RpcHealthCondition {
+        kind,
+        healthy: value <= threshold,
+        value,
+        threshold,
+        message,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use {
         super::*,
         solana_ledger::{
+            blockstore::make_slot_entries,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             get_tmp_ledger_path_auto_delete,
         },
@@ -211,4 +403,97 @@ pub mod tests {
         optimistically_confirmed_bank.write().unwrap().bank = bank16.clone();
         assert_eq!(health.check(), RpcHealthStatus::Ok);
     }
+
+    #[test]
+    fn test_get_health_detail() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let bank0 = bank_forks.read().unwrap().root_bank();
+        let startup_verification_complete = Arc::clone(bank0.get_startup_verification_complete());
+        let vote_account = Pubkey::new_unique();
+        let health = RpcHealth::new(
+            optimistically_confirmed_bank.clone(),
+            blockstore.clone(),
+            10,
+            Arc::new(AtomicBool::new(false)),
+            startup_verification_complete,
+        )
+        .with_detail_config(
+            RpcHealthDetailConfig {
+                vote_account: Some(vote_account),
+                max_slots_since_last_vote: 0,
+                max_incomplete_slots: 1,
+                ..RpcHealthDetailConfig::default()
+            },
+            None,
+        );
+        let condition = |detail: &RpcHealthDetail, kind| {
+            detail
+                .conditions
+                .iter()
+                .find(|condition| condition.kind == kind)
+                .cloned()
+        };
+
+        // Startup verification not complete, no optimistically confirmed slot
+        // known and no snapshot generated
+        let detail = health.check_detail();
+        assert!(!detail.healthy);
+        assert!(
+            !condition(&detail, RpcHealthConditionKind::AccountsDbCatchingUp)
+                .unwrap()
+                .healthy
+        );
+        assert_eq!(
+            condition(&detail, RpcHealthConditionKind::BehindOnSlots),
+            None
+        );
+        assert_eq!(
+            condition(&detail, RpcHealthConditionKind::SnapshotStale),
+            None
+        );
+        // The vote account has not voted, but slot 0 is not behind
+        assert!(
+            condition(&detail, RpcHealthConditionKind::NoRecentVotes)
+                .unwrap()
+                .healthy
+        );
+
+        bank0.set_startup_verification_complete();
+        blockstore
+            .insert_optimistic_slot(15, &Hash::default(), UnixTimestamp::default())
+            .unwrap();
+        let detail = health.check_detail();
+        let behind = condition(&detail, RpcHealthConditionKind::BehindOnSlots).unwrap();
+        assert!(!behind.healthy);
+        assert_eq!((behind.value, behind.threshold), (15, 10));
+
+        let bank5 = Arc::new(Bank::new_from_parent(bank0, &Pubkey::default(), 5));
+        optimistically_confirmed_bank.write().unwrap().bank = bank5;
+        let detail = health.check_detail();
+        let no_recent_votes = condition(&detail, RpcHealthConditionKind::NoRecentVotes).unwrap();
+        assert!(!no_recent_votes.healthy);
+        assert_eq!(no_recent_votes.value, 5);
+        assert!(
+            condition(&detail, RpcHealthConditionKind::BehindOnSlots)
+                .unwrap()
+                .healthy
+        );
+
+        // Slots 1 and 2 are missing their first shred
+        for slot in [1, 2] {
+            let (mut shreds, _) = make_slot_entries(slot, slot - 1, 10, true);
+            shreds.remove(0);
+            blockstore.insert_shreds(shreds, None, false).unwrap();
+        }
+        let detail = health.check_detail();
+        let repair_storm = condition(&detail, RpcHealthConditionKind::RepairStorm).unwrap();
+        assert!(!repair_storm.healthy);
+        assert_eq!(repair_storm.value, 2);
+    }
 }
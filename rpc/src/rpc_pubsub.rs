@@ -6,10 +6,11 @@ use {
         rpc::{check_is_at_least_confirmed, optimize_filters, verify_filters},
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
-            AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionParams,
-            SubscriptionToken,
+            AccountDeltaSubscriptionParams, AccountSubscriptionParams, BlockSubscriptionKind,
+            BlockSubscriptionParams, LogsSubscriptionKind, LogsSubscriptionParams,
+            NonceSubscriptionParams, PrioritizationFeeSubscriptionParams,
+            ProgramSubscriptionParams, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionParams, SubscriptionToken,
         },
     },
     dashmap::DashMap,
@@ -19,18 +20,28 @@ use {
     solana_account_decoder::{UiAccount, UiAccountEncoding},
     solana_rpc_client_api::{
         config::{
-            RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
+            RpcAccountDeltaSubscribeConfig, RpcAccountInfoConfig, RpcBlockSubscribeConfig,
+            RpcBlockSubscribeFilter, RpcNonceSubscribeConfig, RpcPrioritizationFeeSubscribeConfig,
             RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
             RpcTransactionLogsFilter,
         },
         response::{
-            Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
+            Response as RpcResponse, RpcAccountDelta, RpcBlockUpdate, RpcDuplicateShredProof,
+            RpcDurableNonce, RpcKeyedAccount, RpcLogsResponse, RpcPrioritizationFeeUpdate,
             RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
         },
     },
-    solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
+    solana_sdk::{
+        clock::Slot, pubkey::Pubkey, signature::Signature, transaction::MAX_TX_ACCOUNT_LOCKS,
+    },
     solana_transaction_status::UiTransactionEncoding,
-    std::{str::FromStr, sync::Arc},
+    std::{
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+    },
 };
 
 // We have to keep both of the following traits to not break backwards compatibility.
@@ -76,6 +87,57 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification of the account data ranges changed since the previous notification,
+    // every time account data is changed
+    // Accepts pubkey parameter as base-58 encoded string
+    #[pubsub(
+        subscription = "accountDeltaNotification",
+        subscribe,
+        name = "accountDeltaSubscribe"
+    )]
+    fn account_delta_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcAccountDelta>>,
+        pubkey_str: String,
+        config: Option<RpcAccountDeltaSubscribeConfig>,
+    );
+
+    // Unsubscribe from account delta notification subscription.
+    #[pubsub(
+        subscription = "accountDeltaNotification",
+        unsubscribe,
+        name = "accountDeltaUnsubscribe"
+    )]
+    fn account_delta_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
+    // Get notification every time the durable nonce of a nonce account is advanced
+    // Accepts pubkey parameter as base-58 encoded string
+    #[pubsub(subscription = "nonceNotification", subscribe, name = "nonceSubscribe")]
+    fn nonce_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcDurableNonce>>,
+        pubkey_str: String,
+        config: Option<RpcNonceSubscribeConfig>,
+    );
+
+    // Unsubscribe from nonce notification subscription.
+    #[pubsub(
+        subscription = "nonceNotification",
+        unsubscribe,
+        name = "nonceUnsubscribe"
+    )]
+    fn nonce_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
     // Get notification every time account data owned by a particular program is changed
     // Accepts pubkey parameter as base-58 encoded string
     #[pubsub(
@@ -230,6 +292,58 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification when a duplicate shred proof is observed for the first time
+    #[pubsub(
+        subscription = "duplicateShredProofNotification",
+        subscribe,
+        name = "duplicateShredProofSubscribe"
+    )]
+    fn duplicate_shred_proof_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcDuplicateShredProof>,
+    );
+
+    // Unsubscribe from duplicate shred proof notification subscription.
+    #[pubsub(
+        subscription = "duplicateShredProofNotification",
+        unsubscribe,
+        name = "duplicateShredProofUnsubscribe"
+    )]
+    fn duplicate_shred_proof_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
+    // Get notification of the compute-unit prices paid in every confirmed block by the
+    // transactions writing any of the accounts
+    // Accepts optional pubkey parameters as base-58 encoded strings
+    #[pubsub(
+        subscription = "prioritizationFeeNotification",
+        subscribe,
+        name = "prioritizationFeeSubscribe"
+    )]
+    fn prioritization_fee_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcPrioritizationFeeUpdate>,
+        pubkey_strs: Option<Vec<String>>,
+        config: Option<RpcPrioritizationFeeSubscribeConfig>,
+    );
+
+    // Unsubscribe from prioritization fee notification subscription.
+    #[pubsub(
+        subscription = "prioritizationFeeNotification",
+        unsubscribe,
+        name = "prioritizationFeeUnsubscribe"
+    )]
+    fn prioritization_fee_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
     // Get notification when a new root is set
     #[pubsub(subscription = "rootNotification", subscribe, name = "rootSubscribe")]
     fn root_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<Slot>);
@@ -268,6 +382,33 @@ mod internal {
         #[rpc(name = "accountUnsubscribe")]
         fn account_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification of the account data ranges changed since the previous notification,
+        // every time account data is changed
+        // Accepts pubkey parameter as base-58 encoded string
+        #[rpc(name = "accountDeltaSubscribe")]
+        fn account_delta_subscribe(
+            &self,
+            pubkey_str: String,
+            config: Option<RpcAccountDeltaSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from account delta notification subscription.
+        #[rpc(name = "accountDeltaUnsubscribe")]
+        fn account_delta_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
+        // Get notification every time the durable nonce of a nonce account is advanced
+        // Accepts pubkey parameter as base-58 encoded string
+        #[rpc(name = "nonceSubscribe")]
+        fn nonce_subscribe(
+            &self,
+            pubkey_str: String,
+            config: Option<RpcNonceSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from nonce notification subscription.
+        #[rpc(name = "nonceUnsubscribe")]
+        fn nonce_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get notification every time account data owned by a particular program is changed
         // Accepts pubkey parameter as base-58 encoded string
         #[rpc(name = "programSubscribe")]
@@ -342,6 +483,27 @@ mod internal {
         #[rpc(name = "voteUnsubscribe")]
         fn vote_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification when a duplicate shred proof is observed for the first time
+        #[rpc(name = "duplicateShredProofSubscribe")]
+        fn duplicate_shred_proof_subscribe(&self) -> Result<SubscriptionId>;
+
+        // Unsubscribe from duplicate shred proof notification subscription.
+        #[rpc(name = "duplicateShredProofUnsubscribe")]
+        fn duplicate_shred_proof_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
+        // Get notification of the compute-unit prices paid in every confirmed block by the
+        // transactions writing any of the accounts
+        #[rpc(name = "prioritizationFeeSubscribe")]
+        fn prioritization_fee_subscribe(
+            &self,
+            pubkey_strs: Option<Vec<String>>,
+            config: Option<RpcPrioritizationFeeSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from prioritization fee notification subscription.
+        #[rpc(name = "prioritizationFeeUnsubscribe")]
+        fn prioritization_fee_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get notification when a new root is set
         #[rpc(name = "rootSubscribe")]
         fn root_subscribe(&self) -> Result<SubscriptionId>;
@@ -356,6 +518,15 @@ mod internal {
     }
 }
 
+/// Unchanged bytes between changed ones that are sent with them, as a range costs more to encode
+const DEFAULT_ACCOUNT_DELTA_MERGE_GAP: usize = 16;
+const DEFAULT_ACCOUNT_DELTA_FULL_DATA_THRESHOLD_PERCENT: u8 = 50;
+
+const DEFAULT_PRIORITIZATION_FEE_PERCENTILES: [u8; 5] = [25, 50, 75, 90, 100];
+const MAX_PRIORITIZATION_FEE_PERCENTILES: usize = 20;
+
+static NEXT_ACCOUNT_DELTA_NONCE: AtomicU64 = AtomicU64::new(0);
+
 pub struct RpcSolPubSubImpl {
     config: PubSubConfig,
     subscription_control: SubscriptionControl,
@@ -444,6 +615,56 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn account_delta_subscribe(
+        &self,
+        pubkey_str: String,
+        config: Option<RpcAccountDeltaSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        let RpcAccountDeltaSubscribeConfig {
+            commitment,
+            merge_gap,
+            full_data_threshold_percent,
+        } = config.unwrap_or_default();
+        let full_data_threshold_percent = full_data_threshold_percent
+            .unwrap_or(DEFAULT_ACCOUNT_DELTA_FULL_DATA_THRESHOLD_PERCENT);
+        if full_data_threshold_percent > 100 {
+            return Err(Error {
+                code: ErrorCode::InvalidParams,
+                message: "Invalid Request: fullDataThresholdPercent must be at most 100".into(),
+                data: None,
+            });
+        }
+        let params = AccountDeltaSubscriptionParams {
+            pubkey: param::<Pubkey>(&pubkey_str, "pubkey")?,
+            commitment: commitment.unwrap_or_default(),
+            merge_gap: merge_gap.unwrap_or(DEFAULT_ACCOUNT_DELTA_MERGE_GAP),
+            full_data_threshold_percent,
+            nonce: NEXT_ACCOUNT_DELTA_NONCE.fetch_add(1, Ordering::Relaxed),
+        };
+        self.subscribe(SubscriptionParams::AccountDelta(params))
+    }
+
+    fn account_delta_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
+    fn nonce_subscribe(
+        &self,
+        pubkey_str: String,
+        config: Option<RpcNonceSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        let RpcNonceSubscribeConfig { commitment } = config.unwrap_or_default();
+        let params = NonceSubscriptionParams {
+            pubkey: param::<Pubkey>(&pubkey_str, "pubkey")?,
+            commitment: commitment.unwrap_or_default(),
+        };
+        self.subscribe(SubscriptionParams::Nonce(params))
+    }
+
+    fn nonce_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
     fn program_subscribe(
         &self,
         pubkey_str: String,
@@ -591,6 +812,73 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn duplicate_shred_proof_subscribe(&self) -> Result<SubscriptionId> {
+        if !self.config.enable_duplicate_shred_proof_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        self.subscribe(SubscriptionParams::DuplicateShredProof)
+    }
+
+    fn duplicate_shred_proof_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        if !self.config.enable_duplicate_shred_proof_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        self.unsubscribe(id)
+    }
+
+    fn prioritization_fee_subscribe(
+        &self,
+        pubkey_strs: Option<Vec<String>>,
+        config: Option<RpcPrioritizationFeeSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        if !self.config.enable_prioritization_fee_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        let pubkey_strs = pubkey_strs.unwrap_or_default();
+        if pubkey_strs.len() > MAX_TX_ACCOUNT_LOCKS {
+            return Err(Error::invalid_params(format!(
+                "Too many inputs provided; max {MAX_TX_ACCOUNT_LOCKS}"
+            )));
+        }
+        let mut accounts = pubkey_strs
+            .iter()
+            .map(|pubkey_str| param::<Pubkey>(pubkey_str, "pubkey"))
+            .collect::<Result<Vec<_>>>()?;
+        accounts.sort_unstable();
+        accounts.dedup();
+
+        let mut percentiles = config
+            .unwrap_or_default()
+            .percentiles
+            .unwrap_or_else(|| DEFAULT_PRIORITIZATION_FEE_PERCENTILES.to_vec());
+        if percentiles.is_empty() || percentiles.len() > MAX_PRIORITIZATION_FEE_PERCENTILES {
+            return Err(Error::invalid_params(format!(
+                "Invalid Request: between 1 and {MAX_PRIORITIZATION_FEE_PERCENTILES} percentiles \
+                 must be provided"
+            )));
+        }
+        if percentiles.iter().any(|&percentile| percentile > 100) {
+            return Err(Error::invalid_params(
+                "Invalid Request: percentiles must be at most 100",
+            ));
+        }
+        percentiles.sort_unstable();
+        percentiles.dedup();
+
+        let params = PrioritizationFeeSubscriptionParams {
+            accounts,
+            percentiles,
+        };
+        self.subscribe(SubscriptionParams::PrioritizationFee(params))
+    }
+
+    fn prioritization_fee_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        if !self.config.enable_prioritization_fee_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        self.unsubscribe(id)
+    }
+
     fn root_subscribe(&self) -> Result<SubscriptionId> {
         self.subscribe(SubscriptionParams::Root)
     }
@@ -620,6 +908,7 @@ mod tests {
         jsonrpc_core::{IoHandler, Response},
         serial_test::serial,
         solana_account_decoder::{parse_account_data::parse_account_data_v3, UiAccountEncoding},
+        solana_gossip::duplicate_shred_proofs::DuplicateShredProof,
         solana_rpc_client_api::response::{
             ProcessedSignatureResult, ReceivedSignatureResult, RpcSignatureResult, SlotInfo,
         },
@@ -631,6 +920,7 @@ mod tests {
                 activate_all_features, create_genesis_config,
                 create_genesis_config_with_vote_accounts, GenesisConfigInfo, ValidatorVoteKeypairs,
             },
+            prioritization_fee_cache::{BlockTransactionFees, TransactionFee},
         },
         solana_sdk::{
             account::ReadableAccount,
@@ -1412,6 +1702,98 @@ mod tests {
         assert!(rpc.vote_unsubscribe(sub_id).is_ok());
     }
 
+    #[test]
+    #[serial]
+    fn test_duplicate_shred_proof_subscribe() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let max_complete_rewards_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::default_with_bank_forks(
+            max_complete_transaction_status_slot,
+            max_complete_rewards_slot,
+            bank_forks,
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+        let sub_id = rpc.duplicate_shred_proof_subscribe().unwrap();
+
+        rpc_subscriptions.notify_duplicate_shred_proof(DuplicateShredProof {
+            slot: 7,
+            leader: Pubkey::default(),
+            reporter: Pubkey::default(),
+            shred1: vec![1, 2, 3],
+            shred2: vec![4, 5, 6],
+        });
+        let response = receiver.recv();
+        assert_eq!(
+            response,
+            r#"{"jsonrpc":"2.0","method":"duplicateShredProofNotification","params":{"result":{"slot":7,"leader":"11111111111111111111111111111111","reporter":"11111111111111111111111111111111","shred1":"AQID","shred2":"BAUG"},"subscription":0}}"#
+        );
+
+        assert!(rpc.duplicate_shred_proof_unsubscribe(42.into()).is_err());
+        assert!(rpc.duplicate_shred_proof_unsubscribe(sub_id).is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prioritization_fee_subscribe() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let max_complete_rewards_slot = Arc::new(AtomicU64::default());
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::default_with_bank_forks(
+            max_complete_transaction_status_slot,
+            max_complete_rewards_slot,
+            bank_forks,
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+
+        let account = Pubkey::new_unique();
+        let invalid_config = RpcPrioritizationFeeSubscribeConfig {
+            percentiles: Some(vec![50, 101]),
+        };
+        assert!(rpc
+            .prioritization_fee_subscribe(None, Some(invalid_config))
+            .is_err());
+        assert!(rpc
+            .prioritization_fee_subscribe(Some(vec!["invalid".to_string()]), None)
+            .is_err());
+        let config = RpcPrioritizationFeeSubscribeConfig {
+            percentiles: Some(vec![100, 50, 50]),
+        };
+        let sub_id = rpc
+            .prioritization_fee_subscribe(Some(vec![account.to_string()]), Some(config))
+            .unwrap();
+
+        rpc_subscriptions.notify_prioritization_fees(Arc::new(BlockTransactionFees {
+            slot: 7,
+            transaction_fees: vec![
+                TransactionFee {
+                    compute_unit_price: 3,
+                    writable_accounts: vec![account],
+                },
+                TransactionFee {
+                    compute_unit_price: 100,
+                    writable_accounts: vec![Pubkey::new_unique()],
+                },
+                TransactionFee {
+                    compute_unit_price: 9,
+                    writable_accounts: vec![Pubkey::new_unique(), account],
+                },
+            ],
+        }));
+        let response = receiver.recv();
+        assert_eq!(
+            response,
+            r#"{"jsonrpc":"2.0","method":"prioritizationFeeNotification","params":{"result":{"slot":7,"numTransactions":2,"percentiles":[{"percentile":50,"computeUnitPrice":3},{"percentile":100,"computeUnitPrice":9}]},"subscription":0}}"#
+        );
+
+        assert!(rpc.prioritization_fee_unsubscribe(42.into()).is_err());
+        assert!(rpc.prioritization_fee_unsubscribe(sub_id).is_ok());
+    }
+
     #[test]
     fn test_get_version() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
@@ -2,6 +2,7 @@
 
 use {
     crate::{
+        proxy_protocol::{read_proxy_header, ProxyHeader},
         rpc_pubsub::{RpcSolPubSubImpl, RpcSolPubSubInternal},
         rpc_subscription_tracker::{
             SubscriptionControl, SubscriptionId, SubscriptionParams, SubscriptionToken,
@@ -41,11 +42,16 @@ pub const DEFAULT_WORKER_THREADS: usize = 1;
 pub struct PubSubConfig {
     pub enable_block_subscription: bool,
     pub enable_vote_subscription: bool,
+    pub enable_duplicate_shred_proof_subscription: bool,
+    pub enable_prioritization_fee_subscription: bool,
     pub max_active_subscriptions: usize,
     pub queue_capacity_items: usize,
     pub queue_capacity_bytes: usize,
     pub worker_threads: usize,
     pub notification_threads: Option<NonZeroUsize>,
+    /// Expect every connection to start with a PROXY protocol header that
+    /// carries the address of the client
+    pub proxy_protocol: bool,
 }
 
 impl Default for PubSubConfig {
@@ -53,11 +59,14 @@ impl Default for PubSubConfig {
         Self {
             enable_block_subscription: false,
             enable_vote_subscription: false,
+            enable_duplicate_shred_proof_subscription: false,
+            enable_prioritization_fee_subscription: false,
             max_active_subscriptions: MAX_ACTIVE_SUBSCRIPTIONS,
             queue_capacity_items: DEFAULT_QUEUE_CAPACITY_ITEMS,
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(get_thread_count()),
+            proxy_protocol: false,
         }
     }
 }
@@ -67,11 +76,14 @@ impl PubSubConfig {
         Self {
             enable_block_subscription: false,
             enable_vote_subscription: false,
+            enable_duplicate_shred_proof_subscription: false,
+            enable_prioritization_fee_subscription: false,
             max_active_subscriptions: MAX_ACTIVE_SUBSCRIPTIONS,
             queue_capacity_items: DEFAULT_TEST_QUEUE_CAPACITY_ITEMS,
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(2),
+            proxy_protocol: false,
         }
     }
 }
@@ -136,6 +148,7 @@ struct SentNotificationStats {
     num_slots_updates: AtomicUsize,
     num_root: AtomicUsize,
     num_vote: AtomicUsize,
+    num_duplicate_shred_proof: AtomicUsize,
     num_block: AtomicUsize,
     total_creation_to_queue_time_us: AtomicU64,
     last_report: AtomicInterval,
@@ -186,6 +199,11 @@ impl SentNotificationStats {
                     self.num_vote.swap(0, Ordering::Relaxed) as i64,
                     i64
                 ),
+                (
+                    "num_duplicate_shred_proof",
+                    self.num_duplicate_shred_proof.swap(0, Ordering::Relaxed) as i64,
+                    i64
+                ),
                 (
                     "num_block",
                     self.num_block.swap(0, Ordering::Relaxed) as i64,
@@ -237,6 +255,11 @@ fn increment_sent_notification_stats(
         SubscriptionParams::Vote => {
             stats.num_vote.fetch_add(1, Ordering::Relaxed);
         }
+        SubscriptionParams::DuplicateShredProof => {
+            stats
+                .num_duplicate_shred_proof
+                .fetch_add(1, Ordering::Relaxed);
+        }
         SubscriptionParams::Block(_) => {
             stats.num_block.fetch_add(1, Ordering::Relaxed);
         }
@@ -336,6 +359,8 @@ pub fn test_connection(
         PubSubConfig {
             enable_block_subscription: true,
             enable_vote_subscription: true,
+            enable_duplicate_shred_proof_subscription: true,
+            enable_prioritization_fee_subscription: true,
             queue_capacity_items: 100,
             ..PubSubConfig::default()
         },
@@ -447,13 +472,23 @@ async fn listen(
     loop {
         select! {
             result = listener.accept() => match result {
-                Ok((socket, addr)) => {
-                    debug!("new client ({:?})", addr);
+                Ok((mut socket, mut addr)) => {
                     let subscription_control = subscription_control.clone();
                     let config = config.clone();
                     let tripwire = tripwire.clone();
                     let counter_token = counter.create_token();
                     tokio::spawn(async move {
+                        if config.proxy_protocol {
+                            match read_proxy_header(&mut socket).await {
+                                Ok(ProxyHeader { source: Some(source), .. }) => addr = source,
+                                Ok(_) => {}
+                                Err(err) => {
+                                    warn!("invalid PROXY protocol header ({:?}): {}", addr, err);
+                                    return;
+                                }
+                            }
+                        }
+                        debug!("new client ({:?})", addr);
                         let handle = handle_connection(
                             socket, subscription_control, config, tripwire
                         );
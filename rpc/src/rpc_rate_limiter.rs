@@ -0,0 +1,318 @@
+//! Rate limits of the JSON RPC requests, by client and by method.
+//!
+//! Clients are identified by the value of the configured API key header if they send one, or
+//! else by the source IP in the `X-Forwarded-For` header. The HTTP server does not expose the
+//! addresses of its peers, so the clients connecting directly without an API key share one quota.
+//!
+//! Each client has a token bucket for all its requests, and one for each of the methods with a
+//! quota of their own. A request over the quota of its client is answered with `429 Too Many
+//! Requests`, and a call over the quota of its method with a `MethodRateLimited` error.
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    governor::{DefaultKeyedRateLimiter, Quota},
+    jsonrpc_core::{
+        futures::future::{self, Either},
+        Call, Middleware, Output,
+    },
+    jsonrpc_http_server::hyper::{self, header::HeaderMap},
+    solana_metrics::datapoint_info,
+    solana_rpc_client_api::custom_error::RpcCustomError,
+    solana_sdk::timing::AtomicInterval,
+    std::{
+        collections::HashMap,
+        future::Future,
+        num::NonZeroU32,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+    },
+};
+
+/// # of clients tracked by a token bucket limiter before the idle ones are dropped
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+const STATS_REPORT_INTERVAL_MS: u64 = 10_000;
+
+/// Key of the clients which are identified by neither an API key nor a forwarded source IP
+const UNIDENTIFIED_CLIENT_KEY: &str = "unidentified";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RpcRateLimitConfig {
+    /// Requests per second allowed from each client, with bursts of as many
+    pub requests_per_second: Option<NonZeroU32>,
+    /// Calls per second allowed from each client to the methods with a quota of their own
+    pub method_requests_per_second: HashMap<String, NonZeroU32>,
+    /// Header identifying clients by API key, in place of their source IP
+    pub api_key_header: Option<String>,
+}
+
+impl RpcRateLimitConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.requests_per_second.is_some() || !self.method_requests_per_second.is_empty()
+    }
+}
+
+#[derive(Default)]
+struct RpcRateLimiterStats {
+    last_report: AtomicInterval,
+    allowed_requests: AtomicU64,
+    limited_requests: AtomicU64,
+    allowed_method_calls: AtomicU64,
+    limited_method_calls: AtomicU64,
+}
+
+impl RpcRateLimiterStats {
+    fn maybe_report(&self) {
+        if !self.last_report.should_update(STATS_REPORT_INTERVAL_MS) {
+            return;
+        }
+        datapoint_info!(
+            "rpc_rate_limiter",
+            (
+                "allowed_requests",
+                self.allowed_requests.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "limited_requests",
+                self.limited_requests.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "allowed_method_calls",
+                self.allowed_method_calls.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "limited_method_calls",
+                self.limited_method_calls.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+    }
+}
+
+pub struct RpcRateLimiter {
+    requests: Option<DefaultKeyedRateLimiter<Arc<str>>>,
+    methods: HashMap<String, DefaultKeyedRateLimiter<Arc<str>>>,
+    api_key_header: Option<hyper::header::HeaderName>,
+    stats: RpcRateLimiterStats,
+}
+
+impl RpcRateLimiter {
+    /// Returns None if `config` sets no quota
+    pub fn new(config: &RpcRateLimitConfig) -> Result<Option<Self>, String> {
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+        let api_key_header = config
+            .api_key_header
+            .as_ref()
+            .map(|header| {
+                hyper::header::HeaderName::try_from(header.as_str())
+                    .map_err(|err| format!("invalid rate limit API key header {header}: {err}"))
+            })
+            .transpose()?;
+        Ok(Some(Self {
+            requests: config
+                .requests_per_second
+                .map(|rate| DefaultKeyedRateLimiter::keyed(Quota::per_second(rate))),
+            methods: config
+                .method_requests_per_second
+                .iter()
+                .map(|(method, rate)| {
+                    (
+                        method.clone(),
+                        DefaultKeyedRateLimiter::keyed(Quota::per_second(*rate)),
+                    )
+                })
+                .collect(),
+            api_key_header,
+            stats: RpcRateLimiterStats::default(),
+        }))
+    }
+
+    /// Returns the key identifying the client which sent a request with `headers`
+    pub fn client_key(&self, headers: &HeaderMap) -> Arc<str> {
+        let api_key = self
+            .api_key_header
+            .as_ref()
+            .and_then(|header| headers.get(header))
+            .and_then(|value| value.to_str().ok());
+        if let Some(api_key) = api_key {
+            return format!("key:{api_key}").into();
+        }
+        headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .map(|ip| format!("ip:{ip}").into())
+            .unwrap_or_else(|| UNIDENTIFIED_CLIENT_KEY.into())
+    }
+
+    /// Takes a token from the bucket of `client`, returning false if it is empty
+    pub fn check_request(&self, client: &Arc<str>) -> bool {
+        let Some(requests) = &self.requests else {
+            return true;
+        };
+        let allowed = Self::check_key(requests, client);
+        if allowed {
+            self.stats.allowed_requests.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.stats.limited_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        self.stats.maybe_report();
+        allowed
+    }
+
+    /// Takes a token from the bucket of `client` for `method`, returning false if it is empty
+    pub fn check_method(&self, client: &Arc<str>, method: &str) -> bool {
+        let Some(method_requests) = self.methods.get(method) else {
+            return true;
+        };
+        let allowed = Self::check_key(method_requests, client);
+        if allowed {
+            self.stats
+                .allowed_method_calls
+                .fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.stats
+                .limited_method_calls
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.stats.maybe_report();
+        allowed
+    }
+
+    fn check_key(limiter: &DefaultKeyedRateLimiter<Arc<str>>, client: &Arc<str>) -> bool {
+        if limiter.len() > MAX_TRACKED_CLIENTS {
+            limiter.retain_recent();
+        }
+        limiter.check_key(client).is_ok()
+    }
+
+    pub(crate) fn too_many_requests() -> hyper::Response<hyper::Body> {
+        hyper::Response::builder()
+            .status(hyper::StatusCode::TOO_MANY_REQUESTS)
+            .body(hyper::Body::from("Too many requests, retry later"))
+            .unwrap()
+    }
+}
+
+/// Enforces the quotas of the methods, on each call of the requests
+#[derive(Default)]
+pub struct RpcRateLimitMiddleware {
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+}
+
+impl RpcRateLimitMiddleware {
+    pub fn new(rate_limiter: Option<Arc<RpcRateLimiter>>) -> Self {
+        Self { rate_limiter }
+    }
+}
+
+impl Middleware<JsonRpcRequestProcessor> for RpcRateLimitMiddleware {
+    type Future = future::Ready<Option<jsonrpc_core::Response>>;
+    type CallFuture = future::Ready<Option<Output>>;
+
+    fn on_call<F, X>(
+        &self,
+        call: Call,
+        meta: JsonRpcRequestProcessor,
+        next: F,
+    ) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, JsonRpcRequestProcessor) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let (Some(rate_limiter), Some(client)) = (&self.rate_limiter, &meta.rate_limit_key) else {
+            return Either::Right(next(call, meta));
+        };
+        match &call {
+            Call::MethodCall(method_call)
+                if !rate_limiter.check_method(client, &method_call.method) =>
+            {
+                let error = RpcCustomError::MethodRateLimited {
+                    method: method_call.method.clone(),
+                };
+                Either::Left(future::ready(Some(Output::from(
+                    Err(error.into()),
+                    method_call.id.clone(),
+                    method_call.jsonrpc,
+                ))))
+            }
+            Call::Notification(notification)
+                if !rate_limiter.check_method(client, &notification.method) =>
+            {
+                Either::Left(future::ready(None))
+            }
+            _ => Either::Right(next(call, meta)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(config: RpcRateLimitConfig) -> RpcRateLimiter {
+        RpcRateLimiter::new(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_disabled() {
+        assert!(RpcRateLimiter::new(&RpcRateLimitConfig::default())
+            .unwrap()
+            .is_none());
+        assert!(RpcRateLimiter::new(&RpcRateLimitConfig {
+            requests_per_second: NonZeroU32::new(1),
+            api_key_header: Some("bad header".to_string()),
+            ..RpcRateLimitConfig::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_client_key() {
+        let limiter = limiter(RpcRateLimitConfig {
+            requests_per_second: NonZeroU32::new(1),
+            api_key_header: Some("x-api-key".to_string()),
+            ..RpcRateLimitConfig::default()
+        });
+        let mut headers = HeaderMap::new();
+        assert_eq!(&*limiter.client_key(&headers), UNIDENTIFIED_CLIENT_KEY);
+        headers.insert("x-forwarded-for", "10.0.0.1, 10.0.0.2".parse().unwrap());
+        assert_eq!(&*limiter.client_key(&headers), "ip:10.0.0.1");
+        headers.insert("x-api-key", "secret".parse().unwrap());
+        assert_eq!(&*limiter.client_key(&headers), "key:secret");
+    }
+
+    #[test]
+    fn test_check() {
+        let limiter = limiter(RpcRateLimitConfig {
+            requests_per_second: NonZeroU32::new(2),
+            method_requests_per_second: HashMap::from([(
+                "getProgramAccounts".to_string(),
+                NonZeroU32::new(1).unwrap(),
+            )]),
+            ..RpcRateLimitConfig::default()
+        });
+        let client = Arc::from("ip:10.0.0.1");
+        let other_client = Arc::from("ip:10.0.0.2");
+        assert!(limiter.check_request(&client));
+        assert!(limiter.check_request(&client));
+        assert!(!limiter.check_request(&client));
+        assert!(limiter.check_request(&other_client));
+
+        assert!(limiter.check_method(&client, "getProgramAccounts"));
+        assert!(!limiter.check_method(&client, "getProgramAccounts"));
+        assert!(limiter.check_method(&other_client, "getProgramAccounts"));
+        // Methods without a quota of their own are not limited.
+        assert!(limiter.check_method(&client, "getSlot"));
+        assert!(limiter.check_method(&client, "getSlot"));
+    }
+}
@@ -2,12 +2,24 @@
 
 use {
     crate::{
+        account_congestion::AccountCongestionTracker,
+        account_growth::AccountGrowthTracker,
+        buffered_transactions::BufferedTransactionSampler,
         cluster_tpu_info::ClusterTpuInfo,
+        commission_guard::CommissionGuard,
+        confirmation_latency::ConfirmationLatencyTracker,
+        confirmation_proofs::ConfirmationProofCache,
+        leader_slot_stats::LeaderSlotStatsTracker,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         rpc::{rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *},
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        rpc_rate_limiter::{RpcRateLimitMiddleware, RpcRateLimiter},
+        shred_latency::ShredLatencyTracker,
+        skipped_slot_reasons::SkippedSlotReasonsTracker,
+        slot_account_filter::SlotAccountFilterCache,
+        transaction_provenance::TransactionProvenanceCache,
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
@@ -20,8 +32,12 @@ use {
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
         bigtable_upload::ConfirmedBlockUploadConfig,
-        bigtable_upload_service::BigTableUploadService, blockstore::Blockstore,
+        bigtable_upload_service::BigTableUploadService,
+        blockstore::Blockstore,
+        blockstore_tiering::{self, BlockstoreTieringConfig, TieredBlockStorage},
+        blockstore_tiering_service::BlockstoreTieringService,
         leader_schedule_cache::LeaderScheduleCache,
+        object_store_ledger_storage::ObjectStoreLedgerStorage,
     },
     solana_metrics::inc_new_counter_info,
     solana_perf::thread::renice_this_thread,
@@ -42,8 +58,9 @@ use {
         send_transaction_service::{self, SendTransactionService},
         transaction_client::{ConnectionCacheClient, TpuClientNextClient, TransactionClient},
     },
-    solana_storage_bigtable::CredentialType,
+    solana_storage_bigtable::{CredentialType, LongTermStorage},
     std::{
+        io::SeekFrom,
         net::{SocketAddr, UdpSocket},
         path::{Path, PathBuf},
         sync::{
@@ -52,13 +69,19 @@ use {
         },
         thread::{self, Builder, JoinHandle},
     },
-    tokio::runtime::{Builder as TokioBuilder, Runtime as TokioRuntime},
+    tokio::{
+        io::{AsyncReadExt, AsyncSeekExt},
+        runtime::{Builder as TokioBuilder, Runtime as TokioRuntime},
+    },
     tokio_util::codec::{BytesCodec, FramedRead},
 };
 
 const FULL_SNAPSHOT_REQUEST_PATH: &str = "/snapshot.tar.bz2";
 const INCREMENTAL_SNAPSHOT_REQUEST_PATH: &str = "/incremental-snapshot.tar.bz2";
 const LARGEST_ACCOUNTS_CACHE_DURATION: u64 = 60 * 60 * 2;
+// Directory of the ledger holding the blocks fetched back from the object
+// storage of the blockstore tiering
+const BLOCKSTORE_TIERING_CACHE_DIR: &str = "tiering_cache";
 
 pub struct JsonRpcService {
     thread_hdl: JoinHandle<()>,
@@ -68,6 +91,8 @@ pub struct JsonRpcService {
 
     close_handle: Option<CloseHandle>,
 
+    health: Arc<RpcHealth>,
+
     client_updater: Arc<dyn NotifyKeyUpdate + Send + Sync>,
 }
 
@@ -78,6 +103,7 @@ struct RpcRequestMiddleware {
     snapshot_config: Option<SnapshotConfig>,
     bank_forks: Arc<RwLock<BankForks>>,
     health: Arc<RpcHealth>,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
 }
 
 impl RpcRequestMiddleware {
@@ -86,6 +112,7 @@ impl RpcRequestMiddleware {
         snapshot_config: Option<SnapshotConfig>,
         bank_forks: Arc<RwLock<BankForks>>,
         health: Arc<RpcHealth>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
     ) -> Self {
         Self {
             ledger_path,
@@ -100,6 +127,7 @@ impl RpcRequestMiddleware {
             snapshot_config,
             bank_forks,
             health,
+            rate_limiter,
         }
     }
 
@@ -192,7 +220,7 @@ impl RpcRequestMiddleware {
         }
     }
 
-    fn process_file_get(&self, path: &str) -> RequestMiddlewareAction {
+    fn process_file_get(&self, path: &str, range: Option<&str>) -> RequestMiddlewareAction {
         let filename = {
             let stem = Self::strip_leading_slash(path).expect("path already verified");
             match path {
@@ -207,27 +235,53 @@ impl RpcRequestMiddleware {
             }
         };
 
-        let file_length = std::fs::metadata(&filename)
-            .map(|m| m.len())
-            .unwrap_or(0)
-            .to_string();
+        let file_length = std::fs::metadata(&filename).map(|m| m.len()).unwrap_or(0);
         info!("get {} -> {:?} ({} bytes)", path, filename, file_length);
+        let range = range.map(str::to_string);
         RequestMiddlewareAction::Respond {
             should_validate_hosts: true,
-            response: Box::pin(async {
+            response: Box::pin(async move {
                 match Self::open_no_follow(filename).await {
                     Err(err) => Ok(if err.kind() == std::io::ErrorKind::NotFound {
                         Self::not_found()
                     } else {
                         Self::internal_server_error()
                     }),
-                    Ok(file) => {
-                        let stream =
-                            FramedRead::new(file, BytesCodec::new()).map_ok(|b| b.freeze());
+                    Ok(mut file) => {
+                        let response = hyper::Response::builder()
+                            .header(hyper::header::ACCEPT_RANGES, "bytes");
+                        let (response, length) = match range {
+                            None => (response, file_length),
+                            Some(range) => {
+                                let Some((start, end)) = parse_byte_range(&range, file_length)
+                                else {
+                                    return Ok(hyper::Response::builder()
+                                        .status(hyper::StatusCode::RANGE_NOT_SATISFIABLE)
+                                        .header(
+                                            hyper::header::CONTENT_RANGE,
+                                            format!("bytes */{file_length}"),
+                                        )
+                                        .body(hyper::Body::empty())
+                                        .unwrap());
+                                };
+                                if file.seek(SeekFrom::Start(start)).await.is_err() {
+                                    return Ok(Self::internal_server_error());
+                                }
+                                (
+                                    response.status(hyper::StatusCode::PARTIAL_CONTENT).header(
+                                        hyper::header::CONTENT_RANGE,
+                                        format!("bytes {start}-{end}/{file_length}"),
+                                    ),
+                                    end - start + 1,
+                                )
+                            }
+                        };
+                        let stream = FramedRead::new(file.take(length), BytesCodec::new())
+                            .map_ok(|b| b.freeze());
                         let body = hyper::Body::wrap_stream(stream);
 
-                        Ok(hyper::Response::builder()
-                            .header(hyper::header::CONTENT_LENGTH, file_length)
+                        Ok(response
+                            .header(hyper::header::CONTENT_LENGTH, length)
                             .body(body)
                             .unwrap())
                     }
@@ -247,6 +301,24 @@ impl RpcRequestMiddleware {
     }
 }
 
+// Parses a `Range` header of a single range of bytes into the first and last
+// byte of the range, which is all that downloads of snapshots request.
+// Returns `None` if the range can't be satisfied.
+fn parse_byte_range(range: &str, file_length: u64) -> Option<(u64, u64)> {
+    let (start, end) = range.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let last_byte = file_length.checked_sub(1)?;
+    let (start, end) = if start.is_empty() {
+        // A suffix range, for the last bytes of the file
+        let suffix_length: u64 = end.parse().ok()?;
+        (file_length - suffix_length.min(file_length), last_byte)
+    } else if end.is_empty() {
+        (start.parse().ok()?, last_byte)
+    } else {
+        (start.parse().ok()?, end.parse::<u64>().ok()?.min(last_byte))
+    };
+    (start <= end).then_some((start, end))
+}
+
 impl RequestMiddleware for RpcRequestMiddleware {
     fn on_request(&self, request: hyper::Request<hyper::Body>) -> RequestMiddlewareAction {
         trace!("request uri: {}", request.uri());
@@ -298,13 +370,23 @@ impl RequestMiddleware for RpcRequestMiddleware {
         if let Some(path) = match_supply_path(request.uri().path()) {
             process_rest(&self.bank_forks, path)
         } else if self.is_file_get_path(request.uri().path()) {
-            self.process_file_get(request.uri().path())
+            self.process_file_get(
+                request.uri().path(),
+                request
+                    .headers()
+                    .get(hyper::header::RANGE)
+                    .and_then(|range| range.to_str().ok()),
+            )
         } else if request.uri().path() == "/health" {
             hyper::Response::builder()
                 .status(hyper::StatusCode::OK)
                 .body(hyper::Body::from(self.health_check()))
                 .unwrap()
                 .into()
+        } else if self.rate_limiter.as_ref().is_some_and(|rate_limiter| {
+            !rate_limiter.check_request(&rate_limiter.client_key(request.headers()))
+        }) {
+            RpcRateLimiter::too_many_requests().into()
         } else {
             request.into()
         }
@@ -398,6 +480,17 @@ pub struct JsonRpcServiceConfig<'a> {
     pub max_complete_transaction_status_slot: Arc<AtomicU64>,
     pub max_complete_rewards_slot: Arc<AtomicU64>,
     pub prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    pub transaction_provenance_cache: Arc<TransactionProvenanceCache>,
+    pub slot_account_filter_cache: Arc<SlotAccountFilterCache>,
+    pub shred_latency_tracker: Arc<ShredLatencyTracker>,
+    pub leader_slot_stats_tracker: Arc<LeaderSlotStatsTracker>,
+    pub confirmation_latency_tracker: Arc<ConfirmationLatencyTracker>,
+    pub skipped_slot_reasons_tracker: Arc<SkippedSlotReasonsTracker>,
+    pub account_congestion_tracker: Arc<AccountCongestionTracker>,
+    pub confirmation_proof_cache: Arc<ConfirmationProofCache>,
+    pub buffered_transaction_sampler: Arc<BufferedTransactionSampler>,
+    pub account_growth_tracker: Arc<AccountGrowthTracker>,
+    pub commission_guard: Option<Arc<CommissionGuard>>,
     pub client_option: ClientOption<'a>,
 }
 
@@ -462,6 +555,17 @@ impl JsonRpcService {
                     config.max_complete_transaction_status_slot,
                     config.max_complete_rewards_slot,
                     config.prioritization_fee_cache,
+                    config.transaction_provenance_cache,
+                    config.slot_account_filter_cache,
+                    config.shred_latency_tracker,
+                    config.leader_slot_stats_tracker,
+                    config.confirmation_latency_tracker,
+                    config.skipped_slot_reasons_tracker,
+                    config.account_congestion_tracker,
+                    config.confirmation_proof_cache,
+                    config.buffered_transaction_sampler,
+                    config.account_growth_tracker,
+                    config.commission_guard,
                     runtime,
                 )?;
                 Ok(json_rpc_service)
@@ -507,6 +611,17 @@ impl JsonRpcService {
                     config.max_complete_transaction_status_slot,
                     config.max_complete_rewards_slot,
                     config.prioritization_fee_cache,
+                    config.transaction_provenance_cache,
+                    config.slot_account_filter_cache,
+                    config.shred_latency_tracker,
+                    config.leader_slot_stats_tracker,
+                    config.confirmation_latency_tracker,
+                    config.skipped_slot_reasons_tracker,
+                    config.account_congestion_tracker,
+                    config.confirmation_proof_cache,
+                    config.buffered_transaction_sampler,
+                    config.account_growth_tracker,
+                    config.commission_guard,
                     runtime,
                 )?;
                 Ok(json_rpc_service)
@@ -586,6 +701,17 @@ impl JsonRpcService {
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             prioritization_fee_cache,
+            Arc::new(TransactionProvenanceCache::default()),
+            Arc::new(SlotAccountFilterCache::default()),
+            Arc::new(ShredLatencyTracker::default()),
+            Arc::new(LeaderSlotStatsTracker::default()),
+            Arc::new(ConfirmationLatencyTracker::default()),
+            Arc::new(SkippedSlotReasonsTracker::default()),
+            Arc::new(AccountCongestionTracker::default()),
+            Arc::new(ConfirmationProofCache::default()),
+            Arc::new(BufferedTransactionSampler::default()),
+            Arc::new(AccountGrowthTracker::default()),
+            None,
             runtime,
         )?;
         Ok(json_rpc_service)
@@ -621,19 +747,34 @@ impl JsonRpcService {
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        transaction_provenance_cache: Arc<TransactionProvenanceCache>,
+        slot_account_filter_cache: Arc<SlotAccountFilterCache>,
+        shred_latency_tracker: Arc<ShredLatencyTracker>,
+        leader_slot_stats_tracker: Arc<LeaderSlotStatsTracker>,
+        confirmation_latency_tracker: Arc<ConfirmationLatencyTracker>,
+        skipped_slot_reasons_tracker: Arc<SkippedSlotReasonsTracker>,
+        account_congestion_tracker: Arc<AccountCongestionTracker>,
+        confirmation_proof_cache: Arc<ConfirmationProofCache>,
+        buffered_transaction_sampler: Arc<BufferedTransactionSampler>,
+        account_growth_tracker: Arc<AccountGrowthTracker>,
+        commission_guard: Option<Arc<CommissionGuard>>,
         runtime: Arc<TokioRuntime>,
     ) -> Result<Self, String> {
         info!("rpc bound to {:?}", rpc_addr);
         info!("rpc configuration: {:?}", config);
         let rpc_niceness_adj = config.rpc_niceness_adj;
-
-        let health = Arc::new(RpcHealth::new(
-            Arc::clone(&optimistically_confirmed_bank),
-            Arc::clone(&blockstore),
-            config.health_check_slot_distance,
-            override_health_check,
-            startup_verification_complete,
-        ));
+        let rate_limiter = RpcRateLimiter::new(&config.rate_limit_config)?.map(Arc::new);
+
+        let health = Arc::new(
+            RpcHealth::new(
+                Arc::clone(&optimistically_confirmed_bank),
+                Arc::clone(&blockstore),
+                config.health_check_slot_distance,
+                override_health_check,
+                startup_verification_complete,
+            )
+            .with_detail_config(config.health_detail_config.clone(), snapshot_config.clone()),
+        );
 
         let largest_accounts_cache = Arc::new(RwLock::new(LargestAccountsCache::new(
             LARGEST_ACCOUNTS_CACHE_DURATION,
@@ -641,6 +782,20 @@ impl JsonRpcService {
 
         let exit_bigtable_ledger_upload_service = Arc::new(AtomicBool::new(false));
 
+        // Uploads the rooted blocks to the long-term storage, BigTable or another backend
+        let start_ledger_upload_service = |ledger_storage: Arc<dyn LongTermStorage>| {
+            Arc::new(BigTableUploadService::new_with_config(
+                runtime.clone(),
+                ledger_storage,
+                blockstore.clone(),
+                block_commitment_cache.clone(),
+                max_complete_transaction_status_slot.clone(),
+                max_complete_rewards_slot.clone(),
+                ConfirmedBlockUploadConfig::default(),
+                exit_bigtable_ledger_upload_service.clone(),
+            ))
+        };
+
         let (bigtable_ledger_storage, _bigtable_ledger_upload_service) =
             if let Some(RpcBigtableConfig {
                 enable_bigtable_ledger_upload,
@@ -664,21 +819,11 @@ impl JsonRpcService {
                     ))
                     .map(|bigtable_ledger_storage| {
                         info!("BigTable ledger storage initialized");
+                        let bigtable_ledger_storage: Arc<dyn LongTermStorage> =
+                            Arc::new(bigtable_ledger_storage);
 
-                        let bigtable_ledger_upload_service = if enable_bigtable_ledger_upload {
-                            Some(Arc::new(BigTableUploadService::new_with_config(
-                                runtime.clone(),
-                                bigtable_ledger_storage.clone(),
-                                blockstore.clone(),
-                                block_commitment_cache.clone(),
-                                max_complete_transaction_status_slot.clone(),
-                                max_complete_rewards_slot.clone(),
-                                ConfirmedBlockUploadConfig::default(),
-                                exit_bigtable_ledger_upload_service.clone(),
-                            )))
-                        } else {
-                            None
-                        };
+                        let bigtable_ledger_upload_service = enable_bigtable_ledger_upload
+                            .then(|| start_ledger_upload_service(bigtable_ledger_storage.clone()));
 
                         (
                             Some(bigtable_ledger_storage),
@@ -689,11 +834,81 @@ impl JsonRpcService {
                         error!("Failed to initialize BigTable ledger storage: {:?}", err);
                         (None, None)
                     })
+            } else if let Some(RpcObjectStoreLedgerStorageConfig {
+                ref object_store_url,
+                enable_ledger_upload,
+            }) = config.rpc_object_store_ledger_storage_config
+            {
+                blockstore_tiering::object_store_from_url(object_store_url)
+                    .map(|object_store| {
+                        info!("Object store ledger storage initialized");
+                        let ledger_storage: Arc<dyn LongTermStorage> =
+                            Arc::new(ObjectStoreLedgerStorage::new(object_store));
+
+                        let ledger_upload_service = enable_ledger_upload
+                            .then(|| start_ledger_upload_service(ledger_storage.clone()));
+
+                        (Some(ledger_storage), ledger_upload_service)
+                    })
+                    .unwrap_or_else(|err| {
+                        error!("Failed to initialize object store ledger storage: {err}");
+                        (None, None)
+                    })
+            } else {
+                (None, None)
+            };
+
+        let exit_blockstore_tiering_service = Arc::new(AtomicBool::new(false));
+
+        let (tiered_block_storage, _blockstore_tiering_service) =
+            if let Some(BlockstoreTieringConfig {
+                ref object_store_url,
+                offload_after_epochs,
+                cache_max_bytes,
+            }) = config.rpc_blockstore_tiering_config
+            {
+                blockstore_tiering::object_store_from_url(object_store_url)
+                    .and_then(|object_store| {
+                        TieredBlockStorage::new(
+                            object_store,
+                            &ledger_path.join(BLOCKSTORE_TIERING_CACHE_DIR),
+                            cache_max_bytes,
+                        )
+                    })
+                    .map(|tiered_block_storage| {
+                        info!("Blockstore tiering initialized");
+                        let tiered_block_storage = Arc::new(tiered_block_storage);
+                        let epoch_schedule = bank_forks
+                            .read()
+                            .unwrap()
+                            .root_bank()
+                            .epoch_schedule()
+                            .clone();
+                        let blockstore_tiering_service = BlockstoreTieringService::new(
+                            tiered_block_storage.clone(),
+                            blockstore.clone(),
+                            block_commitment_cache.clone(),
+                            max_complete_transaction_status_slot.clone(),
+                            max_complete_rewards_slot.clone(),
+                            epoch_schedule,
+                            offload_after_epochs,
+                            exit_blockstore_tiering_service.clone(),
+                        );
+                        (
+                            Some(tiered_block_storage),
+                            Some(Arc::new(blockstore_tiering_service)),
+                        )
+                    })
+                    .unwrap_or_else(|err| {
+                        error!("Failed to initialize blockstore tiering: {err}");
+                        (None, None)
+                    })
             } else {
                 (None, None)
             };
 
         let full_api = config.full_api;
+        let grpc_addr = config.grpc_addr;
         let max_request_body_size = config
             .max_request_body_size
             .unwrap_or(MAX_REQUEST_BODY_SIZE);
@@ -708,6 +923,7 @@ impl JsonRpcService {
             cluster_info.clone(),
             genesis_hash,
             bigtable_ledger_storage,
+            tiered_block_storage,
             optimistically_confirmed_bank,
             largest_accounts_cache,
             max_slots,
@@ -715,6 +931,17 @@ impl JsonRpcService {
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             prioritization_fee_cache,
+            transaction_provenance_cache,
+            slot_account_filter_cache,
+            shred_latency_tracker,
+            leader_slot_stats_tracker,
+            confirmation_latency_tracker,
+            skipped_slot_reasons_tracker,
+            account_congestion_tracker,
+            confirmation_proof_cache,
+            buffered_transaction_sampler,
+            account_growth_tracker,
+            commission_guard,
             Arc::clone(&runtime),
         );
 
@@ -726,10 +953,39 @@ impl JsonRpcService {
             exit,
         ));
 
+        #[cfg(feature = "grpc")]
+        if let Some(grpc_addr) = grpc_addr {
+            let (grpc_exit_sender, grpc_exit_receiver) = tokio::sync::oneshot::channel();
+            runtime.spawn({
+                let request_processor = request_processor.clone();
+                async move {
+                    let exit = async {
+                        let _ = grpc_exit_receiver.await;
+                    };
+                    if let Err(err) =
+                        crate::rpc_grpc_service::serve(grpc_addr, request_processor, exit).await
+                    {
+                        warn!("gRPC service unavailable error: {err}");
+                    }
+                }
+            });
+            validator_exit
+                .write()
+                .unwrap()
+                .register_exit(Box::new(move || {
+                    let _ = grpc_exit_sender.send(());
+                }));
+        }
+        #[cfg(not(feature = "grpc"))]
+        if grpc_addr.is_some() {
+            return Err("gRPC is not supported, build with the grpc feature".to_string());
+        }
+
         #[cfg(test)]
         let test_request_processor = request_processor.clone();
 
         let ledger_path = ledger_path.to_path_buf();
+        let health_ = health.clone();
 
         let (close_handle_sender, close_handle_receiver) = unbounded();
         let thread_hdl = Builder::new()
@@ -737,7 +993,9 @@ impl JsonRpcService {
             .spawn(move || {
                 renice_this_thread(rpc_niceness_adj).unwrap();
 
-                let mut io = MetaIoHandler::default();
+                let mut io = MetaIoHandler::with_middleware(RpcRateLimitMiddleware::new(
+                    rate_limiter.clone(),
+                ));
 
                 io.extend_with(rpc_minimal::MinimalImpl.to_delegate());
                 if full_api {
@@ -752,16 +1010,21 @@ impl JsonRpcService {
                     snapshot_config,
                     bank_forks.clone(),
                     health.clone(),
+                    rate_limiter.clone(),
                 );
                 let server = ServerBuilder::with_meta_extractor(
                     io,
                     move |req: &hyper::Request<hyper::Body>| {
                         let xbigtable = req.headers().get("x-bigtable");
-                        if xbigtable.is_some_and(|v| v == "disabled") {
+                        let mut request_processor = if xbigtable.is_some_and(|v| v == "disabled") {
                             request_processor.clone_without_bigtable()
                         } else {
                             request_processor.clone()
-                        }
+                        };
+                        request_processor.rate_limit_key = rate_limiter
+                            .as_ref()
+                            .map(|rate_limiter| rate_limiter.client_key(req.headers()));
+                        request_processor
                     },
                 )
                 .event_loop_executor(runtime.handle().clone())
@@ -789,6 +1052,7 @@ impl JsonRpcService {
                 close_handle_sender.send(Ok(server.close_handle())).unwrap();
                 server.wait();
                 exit_bigtable_ledger_upload_service.store(true, Ordering::Relaxed);
+                exit_blockstore_tiering_service.store(true, Ordering::Relaxed);
             })
             .unwrap();
 
@@ -805,6 +1069,7 @@ impl JsonRpcService {
             #[cfg(test)]
             request_processor: test_request_processor,
             close_handle: Some(close_handle),
+            health: health_,
             client_updater: Arc::new(client) as Arc<dyn NotifyKeyUpdate + Send + Sync>,
         })
     }
@@ -823,6 +1088,10 @@ impl JsonRpcService {
     pub fn get_client_key_updater(&self) -> Arc<dyn NotifyKeyUpdate + Send + Sync> {
         self.client_updater.clone()
     }
+
+    pub fn health(&self) -> Arc<RpcHealth> {
+        self.health.clone()
+    }
 }
 
 pub fn service_runtime(
@@ -865,7 +1134,10 @@ pub fn service_runtime(
 mod tests {
     use {
         super::*,
-        crate::rpc::{create_validator_exit, tests::new_test_cluster_info},
+        crate::{
+            rpc::{create_validator_exit, tests::new_test_cluster_info},
+            rpc_rate_limiter::RpcRateLimitConfig,
+        },
         solana_ledger::{
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             get_tmp_ledger_path_auto_delete,
@@ -879,6 +1151,7 @@ mod tests {
         std::{
             io::Write,
             net::{IpAddr, Ipv4Addr},
+            num::NonZeroU32,
         },
         tokio::runtime::Runtime,
     };
@@ -1027,12 +1300,14 @@ mod tests {
             None,
             bank_forks.clone(),
             health.clone(),
+            None,
         );
         let rrm_with_snapshot_config = RpcRequestMiddleware::new(
             ledger_path.path().to_path_buf(),
             Some(SnapshotConfig::default()),
             bank_forks,
             health,
+            None,
         );
 
         assert!(rrm.is_file_get_path(DEFAULT_GENESIS_DOWNLOAD_PATH));
@@ -1120,6 +1395,77 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(parse_byte_range("bytes=0-0", 10), Some((0, 0)));
+        assert_eq!(parse_byte_range("bytes=2-5", 10), Some((2, 5)));
+        assert_eq!(parse_byte_range("bytes=2-", 10), Some((2, 9)));
+        assert_eq!(parse_byte_range("bytes=2-100", 10), Some((2, 9)));
+        assert_eq!(parse_byte_range("bytes=-3", 10), Some((7, 9)));
+        assert_eq!(parse_byte_range("bytes=-100", 10), Some((0, 9)));
+        assert_eq!(parse_byte_range("bytes=10-", 10), None);
+        assert_eq!(parse_byte_range("bytes=5-2", 10), None);
+        assert_eq!(parse_byte_range("bytes=-0", 10), None);
+        assert_eq!(parse_byte_range("bytes=0-0", 0), None);
+        assert_eq!(parse_byte_range("bytes=0-1,4-5", 10), None);
+        assert_eq!(parse_byte_range("items=0-1", 10), None);
+    }
+
+    #[test]
+    fn test_rate_limited_request() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let bank_forks = create_bank_forks();
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let rate_limiter = RpcRateLimiter::new(&RpcRateLimitConfig {
+            requests_per_second: NonZeroU32::new(1),
+            ..RpcRateLimitConfig::default()
+        })
+        .unwrap()
+        .map(Arc::new);
+        let rrm = RpcRequestMiddleware::new(
+            ledger_path.path().to_path_buf(),
+            None,
+            bank_forks,
+            RpcHealth::stub(optimistically_confirmed_bank, blockstore),
+            rate_limiter,
+        );
+        let request = |forwarded_for: &str| {
+            hyper::Request::post("/")
+                .header("x-forwarded-for", forwarded_for)
+                .body(hyper::Body::empty())
+                .unwrap()
+        };
+
+        assert!(matches!(
+            rrm.on_request(request("10.0.0.1")),
+            RequestMiddlewareAction::Proceed { .. }
+        ));
+        if let RequestMiddlewareAction::Respond { response, .. } =
+            rrm.on_request(request("10.0.0.1"))
+        {
+            let response = Runtime::new().unwrap().block_on(response).unwrap();
+            assert_eq!(response.status(), hyper::StatusCode::TOO_MANY_REQUESTS);
+        } else {
+            panic!("Unexpected RequestMiddlewareAction variant");
+        }
+        // Each client has its own quota, and the health check is not limited.
+        assert!(matches!(
+            rrm.on_request(request("10.0.0.2")),
+            RequestMiddlewareAction::Proceed { .. }
+        ));
+        assert!(matches!(
+            rrm.on_request(
+                hyper::Request::get("/health")
+                    .header("x-forwarded-for", "10.0.0.1")
+                    .body(hyper::Body::empty())
+                    .unwrap()
+            ),
+            RequestMiddlewareAction::Respond { .. }
+        ));
+    }
+
     #[test]
     fn test_process_file_get() {
         let runtime = Runtime::new().unwrap();
@@ -1135,10 +1481,11 @@ mod tests {
             None,
             bank_forks,
             RpcHealth::stub(optimistically_confirmed_bank, blockstore),
+            None,
         );
 
         // File does not exist => request should fail.
-        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -1153,7 +1500,7 @@ mod tests {
         }
 
         // Normal file exist => request should succeed.
-        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -1162,6 +1509,32 @@ mod tests {
             panic!("Unexpected RequestMiddlewareAction variant");
         }
 
+        // Range of the file => request should return the range.
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, Some("bytes=7-"));
+        if let RequestMiddlewareAction::Respond { response, .. } = action {
+            let response = runtime.block_on(response).unwrap();
+            assert_eq!(response.status(), 206);
+            assert_eq!(
+                response.headers()[hyper::header::CONTENT_RANGE],
+                "bytes 7-11/12"
+            );
+            let body = runtime
+                .block_on(hyper::body::to_bytes(response.into_body()))
+                .unwrap();
+            assert_eq!(&body[..], b"be ok");
+        } else {
+            panic!("Unexpected RequestMiddlewareAction variant");
+        }
+
+        // Range past the end of the file => request should fail.
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, Some("bytes=12-"));
+        if let RequestMiddlewareAction::Respond { response, .. } = action {
+            let response = runtime.block_on(response).unwrap();
+            assert_eq!(response.status(), 416);
+        } else {
+            panic!("Unexpected RequestMiddlewareAction variant");
+        }
+
         std::fs::remove_file(&genesis_path).unwrap();
         {
             let mut file = std::fs::File::create(ledger_path.path().join("wrong")).unwrap();
@@ -1170,7 +1543,7 @@ mod tests {
         symlink::symlink_file("wrong", &genesis_path).unwrap();
 
         // File is a symbolic link => request should fail.
-        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH);
+        let action = rrm.process_file_get(DEFAULT_GENESIS_DOWNLOAD_PATH, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -9,7 +9,8 @@ use {
         bank_forks::BankForks,
     },
     solana_sdk::{
-        clock::Slot, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature,
+        clock::Slot, commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+        signature::Signature,
     },
     solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
     std::{
@@ -17,7 +18,7 @@ use {
         fmt,
         sync::{
             atomic::{AtomicU64, Ordering},
-            Arc, RwLock, Weak,
+            Arc, Mutex, RwLock, Weak,
         },
     },
     thiserror::Error,
@@ -42,21 +43,27 @@ impl From<SubscriptionId> for u64 {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SubscriptionParams {
     Account(AccountSubscriptionParams),
+    AccountDelta(AccountDeltaSubscriptionParams),
     Block(BlockSubscriptionParams),
     Logs(LogsSubscriptionParams),
+    Nonce(NonceSubscriptionParams),
+    PrioritizationFee(PrioritizationFeeSubscriptionParams),
     Program(ProgramSubscriptionParams),
     Signature(SignatureSubscriptionParams),
     Slot,
     SlotsUpdates,
     Root,
     Vote,
+    DuplicateShredProof,
 }
 
 impl SubscriptionParams {
     fn method(&self) -> &'static str {
         match self {
             SubscriptionParams::Account(_) => "accountNotification",
+            SubscriptionParams::AccountDelta(_) => "accountDeltaNotification",
             SubscriptionParams::Logs(_) => "logsNotification",
+            SubscriptionParams::Nonce(_) => "nonceNotification",
             SubscriptionParams::Program(_) => "programNotification",
             SubscriptionParams::Signature(_) => "signatureNotification",
             SubscriptionParams::Slot => "slotNotification",
@@ -64,34 +71,44 @@ impl SubscriptionParams {
             SubscriptionParams::Block(_) => "blockNotification",
             SubscriptionParams::Root => "rootNotification",
             SubscriptionParams::Vote => "voteNotification",
+            SubscriptionParams::DuplicateShredProof => "duplicateShredProofNotification",
+            SubscriptionParams::PrioritizationFee(_) => "prioritizationFeeNotification",
         }
     }
 
     fn commitment(&self) -> Option<CommitmentConfig> {
         match self {
             SubscriptionParams::Account(params) => Some(params.commitment),
+            SubscriptionParams::AccountDelta(params) => Some(params.commitment),
             SubscriptionParams::Logs(params) => Some(params.commitment),
+            SubscriptionParams::Nonce(params) => Some(params.commitment),
             SubscriptionParams::Program(params) => Some(params.commitment),
             SubscriptionParams::Signature(params) => Some(params.commitment),
             SubscriptionParams::Block(params) => Some(params.commitment),
             SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
             | SubscriptionParams::Root
-            | SubscriptionParams::Vote => None,
+            | SubscriptionParams::Vote
+            | SubscriptionParams::DuplicateShredProof
+            | SubscriptionParams::PrioritizationFee(_) => None,
         }
     }
 
     fn is_commitment_watcher(&self) -> bool {
         let commitment = match self {
             SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::AccountDelta(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
+            SubscriptionParams::Nonce(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
             SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
-            | SubscriptionParams::Vote => return false,
+            | SubscriptionParams::Vote
+            | SubscriptionParams::DuplicateShredProof
+            | SubscriptionParams::PrioritizationFee(_) => return false,
         };
         !commitment.is_confirmed()
     }
@@ -99,14 +116,18 @@ impl SubscriptionParams {
     fn is_gossip_watcher(&self) -> bool {
         let commitment = match self {
             SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::AccountDelta(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
+            SubscriptionParams::Nonce(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
             SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
-            | SubscriptionParams::Vote => return false,
+            | SubscriptionParams::Vote
+            | SubscriptionParams::DuplicateShredProof
+            | SubscriptionParams::PrioritizationFee(_) => return false,
         };
         commitment.is_confirmed()
     }
@@ -118,6 +139,8 @@ impl SubscriptionParams {
                 | SubscriptionParams::SlotsUpdates
                 | SubscriptionParams::Root
                 | SubscriptionParams::Vote
+                | SubscriptionParams::DuplicateShredProof
+                | SubscriptionParams::PrioritizationFee(_)
         )
     }
 }
@@ -130,6 +153,25 @@ pub struct AccountSubscriptionParams {
     pub commitment: CommitmentConfig,
 }
 
+/// Account delta subscriptions are never shared, as the changes notified to each are relative to
+/// the notifications it got before.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountDeltaSubscriptionParams {
+    pub pubkey: Pubkey,
+    pub commitment: CommitmentConfig,
+    pub merge_gap: usize,
+    pub full_data_threshold_percent: u8,
+    /// Unique to each subscription
+    pub nonce: u64,
+}
+
+/// Notifies the durable nonce of a nonce account each time it is advanced
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonceSubscriptionParams {
+    pub pubkey: Pubkey,
+    pub commitment: CommitmentConfig,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BlockSubscriptionParams {
     pub commitment: CommitmentConfig,
@@ -159,6 +201,14 @@ pub enum LogsSubscriptionKind {
     Single(Pubkey),
 }
 
+/// The accounts and the percentiles are sorted and deduplicated, so equal subscriptions are
+/// shared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrioritizationFeeSubscriptionParams {
+    pub accounts: Vec<Pubkey>,
+    pub percentiles: Vec<u8>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProgramSubscriptionParams {
     pub pubkey: Pubkey,
@@ -320,6 +370,10 @@ pub struct SubscriptionInfo {
     params: SubscriptionParams,
     method: &'static str,
     pub last_notified_slot: RwLock<Slot>,
+    /// The account data last notified to an account delta subscription
+    pub last_notified_account_data: Mutex<Option<Vec<u8>>>,
+    /// The durable nonce last seen by a nonce subscription
+    pub last_notified_durable_nonce: Mutex<Option<Hash>>,
     commitment: Option<CommitmentConfig>,
 }
 
@@ -449,6 +503,8 @@ impl SubscriptionsTracker {
     ) {
         let info = Arc::new(SubscriptionInfo {
             last_notified_slot: RwLock::new(last_notified_slot()),
+            last_notified_account_data: Mutex::default(),
+            last_notified_durable_nonce: Mutex::default(),
             id,
             commitment: params.commitment(),
             method: params.method(),
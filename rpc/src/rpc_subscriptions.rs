@@ -7,12 +7,14 @@ use {
         parsed_token_accounts::{get_parsed_token_account, get_parsed_token_accounts},
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
-            AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionInfo,
-            SubscriptionParams, SubscriptionsTracker,
+            AccountDeltaSubscriptionParams, AccountSubscriptionParams, BlockSubscriptionKind,
+            BlockSubscriptionParams, LogsSubscriptionKind, LogsSubscriptionParams,
+            NonceSubscriptionParams, PrioritizationFeeSubscriptionParams,
+            ProgramSubscriptionParams, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionInfo, SubscriptionParams, SubscriptionsTracker,
         },
     },
+    base64::{prelude::BASE64_STANDARD, Engine},
     crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
     itertools::Either,
     rayon::prelude::*,
@@ -20,21 +22,28 @@ use {
     solana_account_decoder::{
         encode_ui_account, parse_token::is_known_spl_token_id, UiAccount, UiAccountEncoding,
     },
+    solana_gossip::duplicate_shred_proofs::{DuplicateShredProof, DuplicateShredProofNotifier},
     solana_ledger::{blockstore::Blockstore, get_tmp_ledger_path},
     solana_measure::measure::Measure,
     solana_rpc_client_api::response::{
-        ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse, RpcBlockUpdate,
-        RpcBlockUpdateError, RpcKeyedAccount, RpcLogsResponse, RpcResponseContext,
+        ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse,
+        RpcAccountDataChange, RpcAccountDelta, RpcBlockUpdate, RpcBlockUpdateError,
+        RpcDuplicateShredProof, RpcDurableNonce, RpcKeyedAccount, RpcLogsResponse,
+        RpcPrioritizationFeePercentile, RpcPrioritizationFeeUpdate, RpcResponseContext,
         RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
     },
     solana_runtime::{
         bank::{Bank, TransactionLogInfo},
         bank_forks::BankForks,
         commitment::{BlockCommitmentCache, CommitmentSlots},
+        prioritization_fee_cache::{BlockTransactionFees, PrioritizationFeeNotifier},
     },
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
+        account_utils::StateMut,
         clock::Slot,
+        hash::Hash,
+        nonce,
         pubkey::Pubkey,
         signature::Signature,
         timing::timestamp,
@@ -48,6 +57,7 @@ use {
         cell::RefCell,
         collections::{HashMap, VecDeque},
         io::Cursor,
+        ops::Range,
         str,
         sync::{
             atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
@@ -97,6 +107,8 @@ pub enum NotificationEntry {
     Slot(SlotInfo),
     SlotUpdate(SlotUpdate),
     Vote((Pubkey, VoteTransaction, Signature)),
+    DuplicateShredProof(DuplicateShredProof),
+    PrioritizationFees(Arc<BlockTransactionFees>),
     Root(Slot),
     Bank(CommitmentSlots),
     Gossip(Slot),
@@ -110,6 +122,16 @@ impl std::fmt::Debug for NotificationEntry {
         match self {
             NotificationEntry::Root(root) => write!(f, "Root({root})"),
             NotificationEntry::Vote(vote) => write!(f, "Vote({vote:?})"),
+            NotificationEntry::DuplicateShredProof(proof) => write!(
+                f,
+                "DuplicateShredProof({{slot: {}, leader: {}}})",
+                proof.slot, proof.leader
+            ),
+            NotificationEntry::PrioritizationFees(block_transaction_fees) => write!(
+                f,
+                "PrioritizationFees({{slot: {}}})",
+                block_transaction_fees.slot
+            ),
             NotificationEntry::Slot(slot_info) => write!(f, "Slot({slot_info:?})"),
             NotificationEntry::SlotUpdate(slot_update) => {
                 write!(f, "SlotUpdate({slot_update:?})")
@@ -393,6 +415,149 @@ fn filter_account_result(
     (account, last_modified_slot)
 }
 
+fn filter_account_delta_result(
+    result: Option<(AccountSharedData, Slot)>,
+    params: &AccountDeltaSubscriptionParams,
+    last_notified_slot: Slot,
+    last_notified_data: &Mutex<Option<Vec<u8>>>,
+) -> (Option<RpcAccountDelta>, Slot) {
+    // Like account subscriptions, a missing account is notified as an empty one, and a fork
+    // switch as the account state on the new fork.
+    let (account, last_modified_slot) = result.unwrap_or_default();
+    if last_modified_slot == last_notified_slot {
+        return (None, last_modified_slot);
+    }
+
+    let mut last_notified_data = last_notified_data.lock().unwrap();
+    let data = account.data();
+    // Until the subscription is notified once, there is nothing for the changes to be relative to.
+    let changes = last_notified_data
+        .as_deref()
+        .map(|previous_data| account_data_changes(previous_data, data, params.merge_gap))
+        .filter(|changes| {
+            let changed_len: usize = changes.iter().map(Range::len).sum();
+            changed_len.saturating_mul(100)
+                <= data
+                    .len()
+                    .saturating_mul(usize::from(params.full_data_threshold_percent))
+        });
+    let full = changes.is_none();
+    let changes = changes.unwrap_or_else(|| {
+        if data.is_empty() {
+            vec![]
+        } else {
+            vec![0..data.len()]
+        }
+    });
+    let delta = RpcAccountDelta {
+        lamports: account.lamports(),
+        owner: account.owner().to_string(),
+        executable: account.executable(),
+        rent_epoch: account.rent_epoch(),
+        space: data.len() as u64,
+        full,
+        changes: changes
+            .into_iter()
+            .map(|range| RpcAccountDataChange {
+                offset: range.start,
+                length: range.len(),
+                bytes: BASE64_STANDARD.encode(&data[range]),
+            })
+            .collect(),
+    };
+    *last_notified_data = Some(data.to_vec());
+    (Some(delta), last_modified_slot)
+}
+
+fn filter_nonce_result(
+    result: Option<(AccountSharedData, Slot)>,
+    _params: &NonceSubscriptionParams,
+    last_notified_slot: Slot,
+    last_notified_durable_nonce: &Mutex<Option<Hash>>,
+) -> (Option<RpcDurableNonce>, Slot) {
+    let (account, last_modified_slot) = result.unwrap_or_default();
+    let data = match StateMut::<nonce::state::Versions>::state(&account)
+        .as_ref()
+        .map(nonce::state::Versions::state)
+    {
+        Ok(nonce::State::Initialized(data)) => Some(data.clone()),
+        _ => None,
+    };
+    let durable_nonce = data.as_ref().map(|data| *data.durable_nonce.as_hash());
+
+    let mut last_notified_durable_nonce = last_notified_durable_nonce.lock().unwrap();
+    // The durable nonce the account holds when it is subscribed to is not notified.
+    if last_modified_slot == last_notified_slot && last_notified_durable_nonce.is_none() {
+        *last_notified_durable_nonce = durable_nonce;
+        return (None, last_modified_slot);
+    }
+    // Other changes of the account, like transfers, are not notified either.
+    let data = data.filter(|_| durable_nonce != *last_notified_durable_nonce);
+    let nonce = data.map(|data| {
+        *last_notified_durable_nonce = durable_nonce;
+        RpcDurableNonce {
+            authority: data.authority.to_string(),
+            durable_nonce: data.blockhash().to_string(),
+            lamports_per_signature: data.get_lamports_per_signature(),
+        }
+    });
+    (nonce, last_modified_slot)
+}
+
+/// Returns the byte ranges of `data` which differ from `previous_data`, including the bytes past
+/// the end of `previous_data`.  Ranges separated by fewer than `merge_gap` unchanged bytes are
+/// merged.
+fn account_data_changes(previous_data: &[u8], data: &[u8], merge_gap: usize) -> Vec<Range<usize>> {
+    let is_changed = |offset: usize| previous_data.get(offset) != Some(&data[offset]);
+    let mut changes = Vec::<Range<usize>>::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        if !is_changed(offset) {
+            offset += 1;
+            continue;
+        }
+        let start = offset;
+        while offset < data.len() && is_changed(offset) {
+            offset += 1;
+        }
+        match changes.last_mut() {
+            Some(last) if start - last.end < merge_gap => last.end = offset,
+            _ => changes.push(start..offset),
+        }
+    }
+    changes
+}
+
+/// Returns the compute-unit prices of the block at the percentiles of the subscription, by the
+/// nearest-rank method
+fn prioritization_fee_update(
+    params: &PrioritizationFeeSubscriptionParams,
+    block_transaction_fees: &BlockTransactionFees,
+) -> RpcPrioritizationFeeUpdate {
+    let compute_unit_prices = block_transaction_fees.compute_unit_prices(&params.accounts);
+    let num_transactions = compute_unit_prices.len();
+    let percentiles = if compute_unit_prices.is_empty() {
+        vec![]
+    } else {
+        params
+            .percentiles
+            .iter()
+            .map(|&percentile| {
+                let rank = (usize::from(percentile) * num_transactions).div_ceil(100);
+                RpcPrioritizationFeePercentile {
+                    percentile,
+                    compute_unit_price: compute_unit_prices[rank.saturating_sub(1)],
+                }
+            })
+            .collect()
+    };
+    RpcPrioritizationFeeUpdate {
+        slot: block_transaction_fees.slot,
+        num_transactions,
+        percentiles,
+    }
+}
+
 fn filter_signature_result(
     result: Option<transaction::Result<()>>,
     _params: &SignatureSubscriptionParams,
@@ -457,24 +622,25 @@ fn initial_last_notified_slot(
     block_commitment_cache: &RwLock<BlockCommitmentCache>,
     optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
 ) -> Option<Slot> {
-    match params {
-        SubscriptionParams::Account(params) => {
-            let slot = if params.commitment.is_finalized() {
-                block_commitment_cache
-                    .read()
-                    .unwrap()
-                    .highest_super_majority_root()
-            } else if params.commitment.is_confirmed() {
-                optimistically_confirmed_bank.read().unwrap().bank.slot()
-            } else {
-                block_commitment_cache.read().unwrap().slot()
-            };
+    let (pubkey, commitment) = match params {
+        SubscriptionParams::Account(params) => (&params.pubkey, &params.commitment),
+        SubscriptionParams::AccountDelta(params) => (&params.pubkey, &params.commitment),
+        SubscriptionParams::Nonce(params) => (&params.pubkey, &params.commitment),
+        _ => return None,
+    };
+    let slot = if commitment.is_finalized() {
+        block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root()
+    } else if commitment.is_confirmed() {
+        optimistically_confirmed_bank.read().unwrap().bank.slot()
+    } else {
+        block_commitment_cache.read().unwrap().slot()
+    };
 
-            let bank = bank_forks.read().unwrap().get(slot)?;
-            Some(bank.get_account_modified_slot(&params.pubkey)?.1)
-        }
-        _ => None,
-    }
+    let bank = bank_forks.read().unwrap().get(slot)?;
+    Some(bank.get_account_modified_slot(pubkey)?.1)
 }
 
 #[derive(Default)]
@@ -527,6 +693,18 @@ impl Drop for RpcSubscriptions {
     }
 }
 
+impl DuplicateShredProofNotifier for RpcSubscriptions {
+    fn notify_duplicate_shred_proof(&self, proof: &DuplicateShredProof) {
+        self.notify_duplicate_shred_proof(proof.clone());
+    }
+}
+
+impl PrioritizationFeeNotifier for RpcSubscriptions {
+    fn notify_block_transaction_fees(&self, block_transaction_fees: Arc<BlockTransactionFees>) {
+        self.notify_prioritization_fees(block_transaction_fees);
+    }
+}
+
 impl RpcSubscriptions {
     pub fn new(
         exit: Arc<AtomicBool>,
@@ -738,6 +916,16 @@ impl RpcSubscriptions {
         self.enqueue_notification(NotificationEntry::Vote((vote_pubkey, vote, signature)));
     }
 
+    pub fn notify_duplicate_shred_proof(&self, proof: DuplicateShredProof) {
+        self.enqueue_notification(NotificationEntry::DuplicateShredProof(proof));
+    }
+
+    pub fn notify_prioritization_fees(&self, block_transaction_fees: Arc<BlockTransactionFees>) {
+        self.enqueue_notification(NotificationEntry::PrioritizationFees(
+            block_transaction_fees,
+        ));
+    }
+
     pub fn notify_roots(&self, mut rooted_slots: Vec<Slot>) {
         rooted_slots.sort_unstable();
         rooted_slots.into_iter().for_each(|root| {
@@ -839,6 +1027,40 @@ impl RpcSubscriptions {
                                 notifier.notify(&rpc_vote, sub, false);
                             }
                         }
+                        NotificationEntry::DuplicateShredProof(proof) => {
+                            if let Some(sub) = subscriptions
+                                .node_progress_watchers()
+                                .get(&SubscriptionParams::DuplicateShredProof)
+                            {
+                                let rpc_proof = RpcDuplicateShredProof {
+                                    slot: proof.slot,
+                                    leader: proof.leader.to_string(),
+                                    reporter: proof.reporter.to_string(),
+                                    shred1: BASE64_STANDARD.encode(&proof.shred1),
+                                    shred2: BASE64_STANDARD.encode(&proof.shred2),
+                                };
+                                debug!("duplicate shred proof notify: slot {}", proof.slot);
+                                inc_new_counter_info!(
+                                    "rpc-subscription-notify-duplicate-shred-proof",
+                                    1
+                                );
+                                notifier.notify(&rpc_proof, sub, false);
+                            }
+                        }
+                        NotificationEntry::PrioritizationFees(ref block_transaction_fees) => {
+                            for (params, sub) in subscriptions.node_progress_watchers() {
+                                let SubscriptionParams::PrioritizationFee(params) = params else {
+                                    continue;
+                                };
+                                let update =
+                                    prioritization_fee_update(params, block_transaction_fees);
+                                inc_new_counter_info!(
+                                    "rpc-subscription-notify-prioritization-fee",
+                                    1
+                                );
+                                notifier.notify(&update, sub, false);
+                            }
+                        }
                         NotificationEntry::Root(root) => {
                             if let Some(sub) = subscriptions
                                 .node_progress_watchers()
@@ -984,6 +1206,58 @@ impl RpcSubscriptions {
                         }
                     }
                 }
+                SubscriptionParams::AccountDelta(params) => {
+                    num_accounts_found.fetch_add(1, Ordering::Relaxed);
+                    if let Some(slot) = slot {
+                        let notified = check_commitment_and_notify(
+                            params,
+                            subscription,
+                            bank_forks,
+                            slot,
+                            |bank, params| bank.get_account_modified_slot(&params.pubkey),
+                            |result, params, last_notified_slot, _bank| {
+                                filter_account_delta_result(
+                                    result,
+                                    params,
+                                    last_notified_slot,
+                                    &subscription.last_notified_account_data,
+                                )
+                            },
+                            notifier,
+                            false,
+                        );
+
+                        if notified {
+                            num_accounts_notified.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                SubscriptionParams::Nonce(params) => {
+                    num_accounts_found.fetch_add(1, Ordering::Relaxed);
+                    if let Some(slot) = slot {
+                        let notified = check_commitment_and_notify(
+                            params,
+                            subscription,
+                            bank_forks,
+                            slot,
+                            |bank, params| bank.get_account_modified_slot(&params.pubkey),
+                            |result, params, last_notified_slot, _bank| {
+                                filter_nonce_result(
+                                    result,
+                                    params,
+                                    last_notified_slot,
+                                    &subscription.last_notified_durable_nonce,
+                                )
+                            },
+                            notifier,
+                            false,
+                        );
+
+                        if notified {
+                            num_accounts_notified.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
                 SubscriptionParams::Block(params) => {
                     num_blocks_found.fetch_add(1, Ordering::Relaxed);
                     if let Some(slot) = slot {
@@ -1245,11 +1519,13 @@ pub(crate) mod tests {
         solana_runtime::{
             commitment::BlockCommitment,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
-            prioritization_fee_cache::PrioritizationFeeCache,
+            prioritization_fee_cache::{PrioritizationFeeCache, TransactionFee},
         },
         solana_sdk::{
+            account::WritableAccount,
             commitment_config::CommitmentConfig,
             message::Message,
+            nonce::state::DurableNonce,
             signature::{Keypair, Signer},
             stake, system_instruction, system_program, system_transaction,
             transaction::Transaction,
@@ -3113,4 +3389,248 @@ pub(crate) mod tests {
         rpc7.root_unsubscribe(sub_id7).unwrap();
         assert_eq!(subscriptions.total(), 0);
     }
+
+    #[test]
+    fn test_account_data_changes() {
+        let previous_data = [0u8; 64];
+        let mut data = previous_data;
+        assert!(account_data_changes(&previous_data, &data, 0).is_empty());
+
+        data[1] = 1;
+        data[2] = 1;
+        data[6] = 1;
+        data[40] = 1;
+        assert_eq!(
+            account_data_changes(&previous_data, &data, 0),
+            vec![1..3, 6..7, 40..41],
+        );
+        // Ranges separated by fewer than the merge gap are merged.
+        assert_eq!(
+            account_data_changes(&previous_data, &data, 4),
+            vec![1..7, 40..41],
+        );
+        // Data past the end of the previous data is changed, and truncated data is not.
+        assert_eq!(
+            account_data_changes(&previous_data[..32], &data, 0),
+            vec![1..3, 6..7, 32..64],
+        );
+        assert_eq!(
+            account_data_changes(&data, &previous_data[..32], 0),
+            vec![1..3, 6..7],
+        );
+    }
+
+    #[test]
+    fn test_prioritization_fee_update() {
+        let account_a = Pubkey::new_unique();
+        let account_b = Pubkey::new_unique();
+        let block_transaction_fees = BlockTransactionFees {
+            slot: 7,
+            transaction_fees: (1..=10)
+                .map(|compute_unit_price| TransactionFee {
+                    compute_unit_price,
+                    writable_accounts: vec![if compute_unit_price % 2 == 0 {
+                        account_a
+                    } else {
+                        account_b
+                    }],
+                })
+                .collect(),
+        };
+        let percentile = |percentile, compute_unit_price| RpcPrioritizationFeePercentile {
+            percentile,
+            compute_unit_price,
+        };
+
+        let params = PrioritizationFeeSubscriptionParams {
+            accounts: vec![],
+            percentiles: vec![0, 25, 50, 90, 100],
+        };
+        assert_eq!(
+            prioritization_fee_update(&params, &block_transaction_fees),
+            RpcPrioritizationFeeUpdate {
+                slot: 7,
+                num_transactions: 10,
+                percentiles: vec![
+                    percentile(0, 1),
+                    percentile(25, 3),
+                    percentile(50, 5),
+                    percentile(90, 9),
+                    percentile(100, 10),
+                ],
+            }
+        );
+
+        let params = PrioritizationFeeSubscriptionParams {
+            accounts: vec![account_a],
+            percentiles: vec![50, 100],
+        };
+        assert_eq!(
+            prioritization_fee_update(&params, &block_transaction_fees),
+            RpcPrioritizationFeeUpdate {
+                slot: 7,
+                num_transactions: 5,
+                percentiles: vec![percentile(50, 6), percentile(100, 10)],
+            }
+        );
+
+        // No percentiles are notified for a block without transactions writing the accounts.
+        let params = PrioritizationFeeSubscriptionParams {
+            accounts: vec![Pubkey::new_unique()],
+            percentiles: vec![50],
+        };
+        assert_eq!(
+            prioritization_fee_update(&params, &block_transaction_fees),
+            RpcPrioritizationFeeUpdate {
+                slot: 7,
+                num_transactions: 0,
+                percentiles: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_filter_account_delta_result() {
+        let params = AccountDeltaSubscriptionParams {
+            pubkey: Pubkey::new_unique(),
+            commitment: CommitmentConfig::processed(),
+            merge_gap: 0,
+            full_data_threshold_percent: 50,
+            nonce: 0,
+        };
+        let owner = Pubkey::new_unique();
+        let mut account = AccountSharedData::new(1, 8, &owner);
+        let last_notified_data = Mutex::default();
+
+        // The first notification has all the data.
+        let (delta, slot) = filter_account_delta_result(
+            Some((account.clone(), 1)),
+            &params,
+            0,
+            &last_notified_data,
+        );
+        assert_eq!(slot, 1);
+        let delta = delta.unwrap();
+        assert!(delta.full);
+        assert_eq!(delta.space, 8);
+        assert_eq!(
+            delta.changes,
+            vec![RpcAccountDataChange {
+                offset: 0,
+                length: 8,
+                bytes: BASE64_STANDARD.encode([0; 8]),
+            }],
+        );
+
+        // An account which is not modified since the last notification is not notified.
+        let (delta, _) = filter_account_delta_result(
+            Some((account.clone(), 1)),
+            &params,
+            1,
+            &last_notified_data,
+        );
+        assert!(delta.is_none());
+
+        // Then only the changes are notified.
+        account.data_as_mut_slice()[3] = 7;
+        let (delta, _) = filter_account_delta_result(
+            Some((account.clone(), 2)),
+            &params,
+            1,
+            &last_notified_data,
+        );
+        let delta = delta.unwrap();
+        assert!(!delta.full);
+        assert_eq!(
+            delta.changes,
+            vec![RpcAccountDataChange {
+                offset: 3,
+                length: 1,
+                bytes: BASE64_STANDARD.encode([7]),
+            }],
+        );
+
+        // Unless more of the data changed than the threshold.
+        account.set_data_from_slice(&[9; 8]);
+        let (delta, _) =
+            filter_account_delta_result(Some((account, 3)), &params, 2, &last_notified_data);
+        assert!(delta.unwrap().full);
+
+        // A missing account is notified as empty.
+        let (delta, slot) = filter_account_delta_result(None, &params, 3, &last_notified_data);
+        assert_eq!(slot, 0);
+        let delta = delta.unwrap();
+        assert_eq!(delta.lamports, 0);
+        assert_eq!(delta.space, 0);
+        assert!(delta.changes.is_empty());
+    }
+
+    #[test]
+    fn test_filter_nonce_result() {
+        let params = NonceSubscriptionParams {
+            pubkey: Pubkey::new_unique(),
+            commitment: CommitmentConfig::processed(),
+        };
+        let authority = Pubkey::new_unique();
+        let new_nonce_account = |blockhash: &Hash, lamports: u64| {
+            AccountSharedData::new_data(
+                lamports,
+                &nonce::state::Versions::new(nonce::State::new_initialized(
+                    &authority,
+                    DurableNonce::from_blockhash(blockhash),
+                    5000,
+                )),
+                &system_program::id(),
+            )
+            .unwrap()
+        };
+        let (blockhash, next_blockhash) = (Hash::new_unique(), Hash::new_unique());
+        let last_notified_durable_nonce = Mutex::default();
+
+        // The durable nonce of the account when it is subscribed to is not notified.
+        let (nonce, slot) = filter_nonce_result(
+            Some((new_nonce_account(&blockhash, 42), 1)),
+            &params,
+            1,
+            &last_notified_durable_nonce,
+        );
+        assert_eq!((nonce, slot), (None, 1));
+
+        // Nor are the changes of the account which do not advance its durable nonce.
+        let (nonce, _) = filter_nonce_result(
+            Some((new_nonce_account(&blockhash, 43), 2)),
+            &params,
+            1,
+            &last_notified_durable_nonce,
+        );
+        assert_eq!(nonce, None);
+
+        // Advancing it is notified, once.
+        let (nonce, slot) = filter_nonce_result(
+            Some((new_nonce_account(&next_blockhash, 43), 3)),
+            &params,
+            1,
+            &last_notified_durable_nonce,
+        );
+        assert_eq!(slot, 3);
+        let expected_nonce = RpcDurableNonce {
+            authority: authority.to_string(),
+            durable_nonce: DurableNonce::from_blockhash(&next_blockhash)
+                .as_hash()
+                .to_string(),
+            lamports_per_signature: 5000,
+        };
+        assert_eq!(nonce, Some(expected_nonce));
+        let (nonce, _) = filter_nonce_result(
+            Some((new_nonce_account(&next_blockhash, 43), 3)),
+            &params,
+            3,
+            &last_notified_durable_nonce,
+        );
+        assert_eq!(nonce, None);
+
+        // Accounts which are not initialized nonce accounts are not notified.
+        let (nonce, slot) = filter_nonce_result(None, &params, 3, &last_notified_durable_nonce);
+        assert_eq!((nonce, slot), (None, 0));
+    }
 }
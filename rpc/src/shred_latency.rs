@@ -0,0 +1,300 @@
+//! Distributions of the arrival times of turbine shreds relative to the start
+//! of their slots, recorded by the shred fetch stage, reported as the
+//! `shred_latency` metric and served through `getShredLatency`.
+//!
+//! Shreds do not carry a timestamp, so the start of a slot is estimated from
+//! the arrival of the first shred received from the leader's window of
+//! NUM_CONSECUTIVE_LEADER_SLOTS slots, assuming the leader produces a slot
+//! every DEFAULT_MS_PER_SLOT. Latencies thus grow across the window if the
+//! leader is slower than that, and spread out if turbine places this node far
+//! from the root of the tree.
+
+use {
+    solana_ledger::leader_schedule_cache::LeaderScheduleCache,
+    solana_rpc_client_api::response::{
+        RpcLeaderShredLatency, RpcShredLatency, RpcSlotShredLatency,
+    },
+    solana_sdk::{
+        clock::{Slot, DEFAULT_MS_PER_SLOT, NUM_CONSECUTIVE_LEADER_SLOTS},
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Upper bounds, in milliseconds, of the buckets of latencies
+pub const SHRED_LATENCY_BUCKETS_MS: [u64; 8] = [50, 100, 200, 400, 800, 1_600, 3_200, 6_400];
+const NUM_BUCKETS: usize = SHRED_LATENCY_BUCKETS_MS.len() + 1;
+/// Slots are reported as metrics once shreds of a slot this much higher have
+/// been received.
+const REPORT_DELAY_SLOTS: Slot = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SlotShredLatency {
+    /// Estimated start of the slot, in milliseconds since the UNIX epoch
+    start_ms: u64,
+    counts: [u64; NUM_BUCKETS],
+    sum_latency_ms: u64,
+    reported: bool,
+}
+
+impl SlotShredLatency {
+    fn new(start_ms: u64) -> Self {
+        Self {
+            start_ms,
+            counts: [0; NUM_BUCKETS],
+            sum_latency_ms: 0,
+            reported: false,
+        }
+    }
+
+    fn add(&mut self, arrival_ms: u64) {
+        let latency_ms = arrival_ms.saturating_sub(self.start_ms);
+        let bucket = SHRED_LATENCY_BUCKETS_MS.partition_point(|&bound| bound < latency_ms);
+        self.counts[bucket] += 1;
+        self.sum_latency_ms = self.sum_latency_ms.saturating_add(latency_ms);
+    }
+
+    fn num_shreds(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    fn to_rpc(&self, slot: Slot, leader: Option<&Pubkey>) -> RpcSlotShredLatency {
+        let num_shreds = self.num_shreds();
+        RpcSlotShredLatency {
+            slot,
+            leader: leader.map(Pubkey::to_string),
+            num_shreds,
+            mean_latency_ms: self
+                .sum_latency_ms
+                .checked_div(num_shreds)
+                .unwrap_or_default(),
+            counts: self.counts.to_vec(),
+        }
+    }
+
+    fn report(&self, slot: Slot, leader: Option<&Pubkey>) {
+        let counts = &self.counts;
+        datapoint_info!(
+            "shred_latency",
+            ("slot", slot, i64),
+            (
+                "leader",
+                leader.map(Pubkey::to_string).unwrap_or_default(),
+                String
+            ),
+            ("num_shreds", self.num_shreds(), i64),
+            (
+                "mean_latency_ms",
+                self.sum_latency_ms
+                    .checked_div(self.num_shreds())
+                    .unwrap_or_default(),
+                i64
+            ),
+            ("le_50ms", counts[0], i64),
+            ("le_100ms", counts[1], i64),
+            ("le_200ms", counts[2], i64),
+            ("le_400ms", counts[3], i64),
+            ("le_800ms", counts[4], i64),
+            ("le_1600ms", counts[5], i64),
+            ("le_3200ms", counts[6], i64),
+            ("le_6400ms", counts[7], i64),
+            ("gt_6400ms", counts[8], i64),
+        );
+    }
+}
+
+/// The shred latencies of the most recent slots
+#[derive(Default)]
+pub struct ShredLatencyTracker {
+    /// `None` if tracking is disabled
+    slots: Option<Mutex<BTreeMap<Slot, SlotShredLatency>>>,
+    max_slots: usize,
+    leader_schedule_cache: Arc<LeaderScheduleCache>,
+}
+
+impl ShredLatencyTracker {
+    /// Creates a tracker retaining the latencies of up to `max_slots` slots.
+    /// A `max_slots` of zero disables tracking.
+    pub fn new(max_slots: usize, leader_schedule_cache: Arc<LeaderScheduleCache>) -> Self {
+        Self {
+            slots: (max_slots > 0).then(Mutex::default),
+            max_slots,
+            leader_schedule_cache,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.slots.is_some()
+    }
+
+    /// Records the slots of turbine shreds which arrived at `arrival_ms`
+    /// milliseconds since the UNIX epoch.
+    pub fn record(&self, shred_slots: impl IntoIterator<Item = Slot>, arrival_ms: u64) {
+        let Some(slots) = &self.slots else {
+            return;
+        };
+        let mut slots = slots.lock().unwrap();
+        for slot in shred_slots {
+            if !slots.contains_key(&slot) {
+                if slots.len() >= self.max_slots
+                    && slots
+                        .first_key_value()
+                        .is_some_and(|(&first, _)| slot < first)
+                {
+                    continue;
+                }
+                let start_ms = estimate_slot_start(&slots, slot, arrival_ms);
+                slots.insert(slot, SlotShredLatency::new(start_ms));
+            }
+            slots.get_mut(&slot).unwrap().add(arrival_ms);
+        }
+        let Some(&highest) = slots.keys().next_back() else {
+            return;
+        };
+        slots
+            .range_mut(..=highest.saturating_sub(REPORT_DELAY_SLOTS))
+            .rev()
+            .take_while(|(_, latency)| !latency.reported)
+            .for_each(|(&slot, latency)| {
+                latency.report(slot, self.slot_leader(slot).as_ref());
+                latency.reported = true;
+            });
+        while slots.len() > self.max_slots {
+            slots.pop_first();
+        }
+    }
+
+    fn slot_leader(&self, slot: Slot) -> Option<Pubkey> {
+        self.leader_schedule_cache.slot_leader_at(slot, None)
+    }
+
+    pub fn to_rpc(&self) -> RpcShredLatency {
+        let slots: Vec<_> = self
+            .slots
+            .iter()
+            .flat_map(|slots| {
+                let slots = slots.lock().unwrap();
+                slots
+                    .iter()
+                    .map(|(&slot, latency)| (slot, latency.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .map(|(slot, latency)| latency.to_rpc(slot, self.slot_leader(slot).as_ref()))
+            .collect();
+        let mut leaders = HashMap::<&str, RpcLeaderShredLatency>::new();
+        for slot in &slots {
+            let Some(leader) = &slot.leader else {
+                continue;
+            };
+            let summary = leaders
+                .entry(leader.as_str())
+                .or_insert_with(|| RpcLeaderShredLatency {
+                    leader: leader.clone(),
+                    num_slots: 0,
+                    num_shreds: 0,
+                    mean_latency_ms: 0,
+                    counts: vec![0; NUM_BUCKETS],
+                });
+            summary.num_slots += 1;
+            // Holds the sum of the latencies until all slots are added.
+            summary.mean_latency_ms += slot.mean_latency_ms * slot.num_shreds;
+            summary.num_shreds += slot.num_shreds;
+            for (count, slot_count) in summary.counts.iter_mut().zip(&slot.counts) {
+                *count += slot_count;
+            }
+        }
+        let mut leaders: Vec<_> = leaders
+            .into_values()
+            .map(|mut summary| {
+                summary.mean_latency_ms = summary
+                    .mean_latency_ms
+                    .checked_div(summary.num_shreds)
+                    .unwrap_or_default();
+                summary
+            })
+            .collect();
+        leaders.sort_unstable_by(|a, b| {
+            (b.mean_latency_ms, &a.leader).cmp(&(a.mean_latency_ms, &b.leader))
+        });
+        RpcShredLatency {
+            bucket_upper_bounds_ms: SHRED_LATENCY_BUCKETS_MS.to_vec(),
+            slots,
+            leaders,
+        }
+    }
+}
+
+// Returns the start of the slot, in milliseconds since the UNIX epoch,
+// extrapolated from the first tracked slot of the same leader window, if any.
+fn estimate_slot_start(
+    slots: &BTreeMap<Slot, SlotShredLatency>,
+    slot: Slot,
+    arrival_ms: u64,
+) -> u64 {
+    let window_start = slot - slot % NUM_CONSECUTIVE_LEADER_SLOTS;
+    let offset_ms = |slot: Slot| (slot - window_start) * DEFAULT_MS_PER_SLOT;
+    slots
+        .range(window_start..window_start + NUM_CONSECUTIVE_LEADER_SLOTS)
+        .next()
+        .map(|(&other, latency)| {
+            latency
+                .start_ms
+                .saturating_sub(offset_ms(other))
+                .saturating_add(offset_ms(slot))
+        })
+        .unwrap_or(arrival_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled() {
+        let tracker = ShredLatencyTracker::default();
+        assert!(!tracker.is_enabled());
+        tracker.record([1, 2, 3], 1_000);
+        assert!(tracker.to_rpc().slots.is_empty());
+    }
+
+    #[test]
+    fn test_record() {
+        let tracker = ShredLatencyTracker::new(3, Arc::default());
+        assert!(tracker.is_enabled());
+        let start_ms = 1_000_000;
+        // The first shred of a window marks the start of the slot.
+        tracker.record([8, 8], start_ms);
+        tracker.record([8], start_ms + 120);
+        // The next slot of the window is expected to start a slot later.
+        tracker.record([9], start_ms + DEFAULT_MS_PER_SLOT + 30);
+        tracker.record([10], start_ms + 3 * DEFAULT_MS_PER_SLOT);
+        // Slots older than the tracked ones are ignored once full.
+        tracker.record([7], start_ms);
+        let latency = tracker.to_rpc();
+        assert_eq!(latency.bucket_upper_bounds_ms, SHRED_LATENCY_BUCKETS_MS);
+        let slots: Vec<_> = latency.slots.iter().map(|slot| slot.slot).collect();
+        assert_eq!(slots, vec![8, 9, 10]);
+        assert_eq!(latency.slots[0].num_shreds, 3);
+        assert_eq!(latency.slots[0].mean_latency_ms, 40);
+        assert_eq!(latency.slots[0].counts, vec![2, 0, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(latency.slots[1].counts, vec![1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(latency.slots[2].mean_latency_ms, DEFAULT_MS_PER_SLOT);
+        assert_eq!(latency.slots[2].counts, vec![0, 0, 0, 1, 0, 0, 0, 0, 0]);
+        // Slots of unknown leaders are not summed.
+        assert_eq!(latency.slots[0].leader, None);
+        assert!(latency.leaders.is_empty());
+
+        // Older slots are evicted.
+        tracker.record([12], start_ms + 4 * DEFAULT_MS_PER_SLOT);
+        let slots: Vec<_> = tracker
+            .to_rpc()
+            .slots
+            .iter()
+            .map(|slot| slot.slot)
+            .collect();
+        assert_eq!(slots, vec![9, 10, 12]);
+    }
+}
@@ -0,0 +1,339 @@
+//! The probable causes of the skipped leader slots of this node, determined
+//! from what replay observed of the blocks of the node once their slots are
+//! older than the root, reported as the `skipped_slot_reason` metric and
+//! served through `getSkippedSlotReasons`.
+//!
+//! Replay records when the node starts a block, how far PoH was into the slot
+//! at the time, and when the block completes with how many transactions the
+//! banking stage executed into it. A leader slot of the node which the cluster
+//! did not root is then attributed to the first of these that applies:
+//!
+//! * the node did not start a block for the slot;
+//! * the node built its block around a slot which the cluster rooted, or
+//!   started it half a slot late, as it waited for the previous leader;
+//! * the block did not complete, or took more than one and a half slots to;
+//! * the block has no transactions;
+//! * otherwise the cluster rooted another fork.
+
+use {
+    solana_ledger::{blockstore::Blockstore, leader_schedule_cache::LeaderScheduleCache},
+    solana_rpc_client_api::response::{RpcSkippedSlotCause, RpcSkippedSlotReason},
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+/// What replay observed of a block of the node
+#[derive(Clone, Debug)]
+struct LeaderBlock {
+    parent_slot: Slot,
+    /// How long after the start of the slot in PoH the block started
+    start_delay: Duration,
+    /// The target duration of the slot
+    slot_duration: Duration,
+    started: Instant,
+    /// How long the block took to complete, and its number of transactions,
+    /// once completed
+    completion: Option<(Duration, u64)>,
+}
+
+impl LeaderBlock {
+    fn cause(&self, slot: Slot, blockstore: &Blockstore) -> RpcSkippedSlotCause {
+        let is_built_around_root =
+            (self.parent_slot + 1..slot).any(|skipped| blockstore.is_root(skipped));
+        if is_built_around_root || self.start_delay >= self.slot_duration / 2 {
+            return RpcSkippedSlotCause::LatePreviousBlock;
+        }
+        match self.completion {
+            Some((completion_time, num_transactions))
+                if completion_time <= self.slot_duration * 3 / 2 =>
+            {
+                if num_transactions == 0 {
+                    RpcSkippedSlotCause::NoBankingBuffer
+                } else {
+                    RpcSkippedSlotCause::ForkedAway
+                }
+            }
+            _ => RpcSkippedSlotCause::PohStall,
+        }
+    }
+}
+
+fn cause_str(cause: RpcSkippedSlotCause) -> &'static str {
+    match cause {
+        RpcSkippedSlotCause::NotStarted => "not_started",
+        RpcSkippedSlotCause::LatePreviousBlock => "late_previous_block",
+        RpcSkippedSlotCause::PohStall => "poh_stall",
+        RpcSkippedSlotCause::NoBankingBuffer => "no_banking_buffer",
+        RpcSkippedSlotCause::ForkedAway => "forked_away",
+    }
+}
+
+fn report(reason: &RpcSkippedSlotReason) {
+    datapoint_info!(
+        "skipped_slot_reason",
+        ("slot", reason.slot, i64),
+        ("cause", cause_str(reason.cause), String),
+        ("parent_slot", reason.parent_slot, Option<i64>),
+        ("start_delay_ms", reason.start_delay_ms, Option<i64>),
+        ("completion_time_ms", reason.completion_time_ms, Option<i64>),
+        ("num_transactions", reason.num_transactions, Option<i64>),
+    );
+}
+
+#[derive(Default)]
+struct SkippedSlots {
+    /// The blocks of the node newer than the root
+    blocks: BTreeMap<Slot, LeaderBlock>,
+    reasons: BTreeMap<Slot, RpcSkippedSlotReason>,
+    /// The highest root recorded
+    root: Option<Slot>,
+}
+
+struct TrackerState {
+    slots: Mutex<SkippedSlots>,
+    max_slots: usize,
+    blockstore: Arc<Blockstore>,
+    leader_schedule_cache: Arc<LeaderScheduleCache>,
+}
+
+/// The probable causes of the most recent skipped leader slots of the node
+#[derive(Default)]
+pub struct SkippedSlotReasonsTracker {
+    /// `None` if tracking is disabled
+    state: Option<TrackerState>,
+}
+
+impl SkippedSlotReasonsTracker {
+    /// Creates a tracker retaining the causes of up to `max_slots` skipped
+    /// slots. A `max_slots` of zero disables tracking.
+    pub fn new(
+        max_slots: usize,
+        blockstore: Arc<Blockstore>,
+        leader_schedule_cache: Arc<LeaderScheduleCache>,
+    ) -> Self {
+        Self {
+            state: (max_slots > 0).then(|| TrackerState {
+                slots: Mutex::default(),
+                max_slots,
+                blockstore,
+                leader_schedule_cache,
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Records the start of `bank`, a block of the node, when PoH is at
+    /// `poh_tick_height`.
+    pub fn record_leader_block_started(&self, bank: &Bank, poh_tick_height: u64) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let ticks_per_slot = bank.ticks_per_slot().max(1);
+        let slot_start_tick_height = bank.max_tick_height().saturating_sub(ticks_per_slot);
+        let start_delay_ticks = poh_tick_height.saturating_sub(slot_start_tick_height);
+        let slot_duration = Duration::from_nanos(bank.ns_per_slot as u64);
+        let start_delay = Duration::from_nanos(
+            (bank.ns_per_slot * u128::from(start_delay_ticks) / u128::from(ticks_per_slot)) as u64,
+        );
+        state.slots.lock().unwrap().blocks.insert(
+            bank.slot(),
+            LeaderBlock {
+                parent_slot: bank.parent_slot(),
+                start_delay,
+                slot_duration,
+                started: Instant::now(),
+                completion: None,
+            },
+        );
+    }
+
+    /// Records the completion of `bank`, a block of the node.
+    pub fn record_leader_block_completed(&self, bank: &Bank) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut slots = state.slots.lock().unwrap();
+        if let Some(block) = slots.blocks.get_mut(&bank.slot()) {
+            block
+                .completion
+                .get_or_insert((block.started.elapsed(), bank.executed_transaction_count()));
+        }
+    }
+
+    /// Records the rooting of `root`, determining the causes of the leader
+    /// slots of `identity` since the previous root which are skipped. The
+    /// causes are reported as metrics.
+    ///
+    /// The slots before the first root recorded are ignored.
+    pub fn record_root(&self, root: Slot, identity: &Pubkey) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut slots = state.slots.lock().unwrap();
+        let prev_root = match slots.root {
+            Some(prev_root) if prev_root >= root => return,
+            prev_root => prev_root,
+        };
+        slots.root = Some(root);
+        for slot in prev_root.map_or(root, |prev_root| prev_root + 1)..root {
+            if state.leader_schedule_cache.slot_leader_at(slot, None) != Some(*identity)
+                || state.blockstore.is_root(slot)
+            {
+                continue;
+            }
+            let reason = match slots.blocks.get(&slot) {
+                Some(block) => RpcSkippedSlotReason {
+                    slot,
+                    cause: block.cause(slot, &state.blockstore),
+                    parent_slot: Some(block.parent_slot),
+                    start_delay_ms: Some(block.start_delay.as_millis() as u64),
+                    completion_time_ms: block
+                        .completion
+                        .map(|(completion_time, _)| completion_time.as_millis() as u64),
+                    num_transactions: block
+                        .completion
+                        .map(|(_, num_transactions)| num_transactions),
+                },
+                None => RpcSkippedSlotReason {
+                    slot,
+                    cause: RpcSkippedSlotCause::NotStarted,
+                    parent_slot: None,
+                    start_delay_ms: None,
+                    completion_time_ms: None,
+                    num_transactions: None,
+                },
+            };
+            report(&reason);
+            if slots.reasons.len() >= state.max_slots {
+                slots.reasons.pop_first();
+            }
+            slots.reasons.insert(slot, reason);
+        }
+        slots.blocks.retain(|&slot, _| slot > root);
+    }
+
+    /// Returns the causes of the most recent skipped slots, in ascending order
+    pub fn to_rpc(&self) -> Vec<RpcSkippedSlotReason> {
+        self.state
+            .iter()
+            .flat_map(|state| {
+                let slots = state.slots.lock().unwrap();
+                slots.reasons.values().cloned().collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::{
+            genesis_utils::{create_genesis_config, GenesisConfigInfo},
+            get_tmp_ledger_path_auto_delete,
+        },
+    };
+
+    #[test]
+    fn test_disabled() {
+        let tracker = SkippedSlotReasonsTracker::default();
+        assert!(!tracker.is_enabled());
+        tracker.record_root(0, &Pubkey::new_unique());
+        tracker.record_root(4, &Pubkey::new_unique());
+        assert!(tracker.to_rpc().is_empty());
+    }
+
+    #[test]
+    fn test_record() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            validator_pubkey,
+            ..
+        } = create_genesis_config(1_000_000);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        // The validator is the leader of every slot.
+        let tracker = SkippedSlotReasonsTracker::new(
+            3,
+            blockstore.clone(),
+            Arc::new(LeaderScheduleCache::new_from_bank(&bank0)),
+        );
+        assert!(tracker.is_enabled());
+        tracker.record_root(0, &validator_pubkey);
+        let ticks_per_slot = bank0.ticks_per_slot();
+
+        // Slot 1 is not started, and slot 2 has no transactions.
+        let bank2 = Arc::new(Bank::new_from_parent(bank0, &validator_pubkey, 2));
+        tracker.record_leader_block_started(&bank2, 2 * ticks_per_slot);
+        tracker.record_leader_block_completed(&bank2);
+        // Slot 4 is built around slot 3, which is rooted.
+        let bank4 = Bank::new_from_parent(bank2.clone(), &validator_pubkey, 4);
+        tracker.record_leader_block_started(&bank4, 4 * ticks_per_slot);
+        tracker.record_leader_block_completed(&bank4);
+        // Slot 5 is not completed.
+        let bank4 = Arc::new(bank4);
+        let bank5 = Bank::new_from_parent(bank4.clone(), &validator_pubkey, 5);
+        tracker.record_leader_block_started(&bank5, 5 * ticks_per_slot);
+        // Slot 6 is started late.
+        let bank6 = Bank::new_from_parent(bank4.clone(), &validator_pubkey, 6);
+        tracker.record_leader_block_started(&bank6, 6 * ticks_per_slot + ticks_per_slot / 2);
+        // Slot 7 is completed in time with a transaction.
+        let bank7 = Bank::new_from_parent(bank4, &validator_pubkey, 7);
+        tracker.record_leader_block_started(&bank7, 7 * ticks_per_slot);
+        bank7
+            .transfer(1, &mint_keypair, &Pubkey::new_unique())
+            .unwrap();
+        tracker.record_leader_block_completed(&bank7);
+
+        blockstore.set_roots([3].iter()).unwrap();
+        tracker.record_root(3, &validator_pubkey);
+        let reasons = tracker.to_rpc();
+        let causes: Vec<_> = reasons
+            .iter()
+            .map(|reason| (reason.slot, reason.cause))
+            .collect();
+        assert_eq!(
+            causes,
+            vec![
+                (1, RpcSkippedSlotCause::NotStarted),
+                (2, RpcSkippedSlotCause::NoBankingBuffer),
+            ]
+        );
+        assert_eq!(reasons[0].parent_slot, None);
+        assert_eq!(reasons[1].parent_slot, Some(0));
+        assert_eq!(reasons[1].start_delay_ms, Some(0));
+        assert_eq!(reasons[1].num_transactions, Some(0));
+
+        blockstore.set_roots([8].iter()).unwrap();
+        tracker.record_root(8, &validator_pubkey);
+        // Older slots are evicted.
+        let causes: Vec<_> = tracker
+            .to_rpc()
+            .iter()
+            .map(|reason| (reason.slot, reason.cause))
+            .collect();
+        assert_eq!(
+            causes,
+            vec![
+                (5, RpcSkippedSlotCause::PohStall),
+                (6, RpcSkippedSlotCause::LatePreviousBlock),
+                (7, RpcSkippedSlotCause::ForkedAway),
+            ]
+        );
+        assert_eq!(tracker.to_rpc()[2].num_transactions, Some(1));
+
+        // Slots of other leaders are ignored.
+        tracker.record_root(10, &Pubkey::new_unique());
+        assert_eq!(tracker.to_rpc().len(), 3);
+    }
+}
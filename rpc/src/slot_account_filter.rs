@@ -0,0 +1,200 @@
+//! Compact per-slot filters of the accounts written in each frozen bank,
+//! served through `getSlotAccountFilter` and published to Geyser plugins.
+//!
+//! Indexers and wallets tracking a set of accounts can test the filter of a
+//! slot and skip fetching the block if none of their accounts may have been
+//! written in it.
+
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    bv::Bits,
+    solana_bloom::bloom::Bloom,
+    solana_rpc_client_api::response::RpcSlotAccountFilter,
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, RwLock},
+    },
+};
+
+/// Target false positive rate of each filter
+const SLOT_ACCOUNT_FILTER_FALSE_RATE: f64 = 0.01;
+/// Upper bound on the size of each filter, 128 KiB
+const SLOT_ACCOUNT_FILTER_MAX_BITS: usize = 1 << 20;
+
+/// A bloom filter of the accounts written in a slot.
+///
+/// Unlike gossip bloom filters, the hash keys are not randomized, so every
+/// node builds the same filter for the same bank. See
+/// [`RpcSlotAccountFilter`] for how the bits are derived.
+#[derive(Debug)]
+pub struct SlotAccountFilter {
+    slot: Slot,
+    num_accounts: usize,
+    bloom: Bloom<Pubkey>,
+}
+
+impl SlotAccountFilter {
+    pub fn new(slot: Slot, pubkeys: &[Pubkey]) -> Self {
+        let mut bloom = Bloom::random(
+            pubkeys.len(),
+            SLOT_ACCOUNT_FILTER_FALSE_RATE,
+            SLOT_ACCOUNT_FILTER_MAX_BITS,
+        );
+        // Use at least one hash, so that the filter of a slot without any
+        // written accounts matches nothing
+        bloom.keys = (0..bloom.keys.len().max(1) as u64).collect();
+        for pubkey in pubkeys {
+            bloom.add(pubkey);
+        }
+        Self {
+            slot,
+            num_accounts: pubkeys.len(),
+            bloom,
+        }
+    }
+
+    /// Builds the filter of the accounts written in `bank`, which must be
+    /// frozen
+    pub fn new_from_bank(bank: &Bank) -> Self {
+        Self::new(bank.slot(), &bank.get_pubkeys_modified_since_parent())
+    }
+
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    pub fn num_accounts(&self) -> usize {
+        self.num_accounts
+    }
+
+    pub fn num_bits(&self) -> u64 {
+        self.bloom.bits.len()
+    }
+
+    pub fn num_hashes(&self) -> u64 {
+        self.bloom.keys.len() as u64
+    }
+
+    /// Returns false if `pubkey` was definitely not written in the slot
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.bloom.contains(pubkey)
+    }
+
+    /// Returns the bits of the filter as 64-bit words. Bit `i` is bit
+    /// `i % 64` of word `i / 64`.
+    pub fn words(&self) -> Vec<u64> {
+        let bits = &self.bloom.bits;
+        (0..bits.block_len()).map(|i| bits.get_block(i)).collect()
+    }
+
+    pub fn to_rpc(&self) -> RpcSlotAccountFilter {
+        let bytes: Vec<u8> = self
+            .words()
+            .into_iter()
+            .flat_map(u64::to_le_bytes)
+            .collect();
+        RpcSlotAccountFilter {
+            slot: self.slot,
+            num_accounts: self.num_accounts as u64,
+            num_bits: self.num_bits(),
+            num_hashes: self.num_hashes(),
+            bits: BASE64_STANDARD.encode(bytes),
+        }
+    }
+}
+
+/// The filters of the most recently frozen slots
+#[derive(Default)]
+pub struct SlotAccountFilterCache {
+    /// `None` if the filters are disabled
+    filters: Option<RwLock<BTreeMap<Slot, Arc<SlotAccountFilter>>>>,
+    max_slots: usize,
+}
+
+impl SlotAccountFilterCache {
+    /// Creates a cache holding the filters of up to `max_slots` slots. A
+    /// `max_slots` of zero disables the filters.
+    pub fn new(max_slots: usize) -> Self {
+        Self {
+            filters: (max_slots > 0).then(RwLock::default),
+            max_slots,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.filters.is_some()
+    }
+
+    /// Inserts `filter`, evicting the filters of the oldest slots if the
+    /// cache is full. If banks of several forks are frozen at the same slot,
+    /// the filter of the last bank wins.
+    pub fn insert(&self, filter: Arc<SlotAccountFilter>) {
+        let Some(filters) = &self.filters else {
+            return;
+        };
+        let mut filters = filters.write().unwrap();
+        filters.insert(filter.slot(), filter);
+        while filters.len() > self.max_slots {
+            filters.pop_first();
+        }
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<Arc<SlotAccountFilter>> {
+        self.filters
+            .as_ref()
+            .and_then(|filters| filters.read().unwrap().get(&slot).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_account_filter() {
+        let written: Vec<_> = (0..1_000).map(|_| Pubkey::new_unique()).collect();
+        let filter = SlotAccountFilter::new(5, &written);
+        assert_eq!(filter.slot(), 5);
+        assert_eq!(filter.num_accounts(), 1_000);
+        assert!(written.iter().all(|pubkey| filter.contains(pubkey)));
+
+        let num_false_positives = (0..10_000)
+            .filter(|_| filter.contains(&Pubkey::new_unique()))
+            .count();
+        assert!(num_false_positives < 300, "{num_false_positives}");
+
+        // The filter is deterministic
+        let rpc_filter = filter.to_rpc();
+        assert_eq!(rpc_filter, SlotAccountFilter::new(5, &written).to_rpc());
+        assert_eq!(rpc_filter.num_hashes, filter.num_hashes());
+        let bytes = BASE64_STANDARD.decode(&rpc_filter.bits).unwrap();
+        assert_eq!(bytes.len() as u64, rpc_filter.num_bits.div_ceil(64) * 8);
+    }
+
+    #[test]
+    fn test_slot_account_filter_empty() {
+        let filter = SlotAccountFilter::new(5, &[]);
+        assert_eq!(filter.num_accounts(), 0);
+        assert_eq!(filter.num_hashes(), 1);
+        assert!(!filter.contains(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_slot_account_filter_cache() {
+        let disabled = SlotAccountFilterCache::default();
+        assert!(!disabled.is_enabled());
+        disabled.insert(Arc::new(SlotAccountFilter::new(1, &[])));
+        assert!(disabled.get(1).is_none());
+
+        let cache = SlotAccountFilterCache::new(2);
+        assert!(cache.is_enabled());
+        for slot in [3, 1, 2] {
+            cache.insert(Arc::new(SlotAccountFilter::new(slot, &[])));
+        }
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.get(2).unwrap().slot(), 2);
+        assert_eq!(cache.get(3).unwrap().slot(), 3);
+    }
+}
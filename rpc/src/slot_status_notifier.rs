@@ -1,4 +1,5 @@
 use {
+    crate::slot_account_filter::SlotAccountFilter,
     solana_sdk::clock::Slot,
     std::sync::{Arc, RwLock},
 };
@@ -24,6 +25,10 @@ pub trait SlotStatusNotifierInterface {
 
     /// Notified when the slot is marked "Dead"
     fn notify_slot_dead(&self, slot: Slot, error: String);
+
+    /// Notified when the filter of the accounts written in a frozen slot is
+    /// built
+    fn notify_slot_account_filter(&self, filter: &SlotAccountFilter);
 }
 
 pub type SlotStatusNotifier = Arc<RwLock<dyn SlotStatusNotifierInterface + Sync + Send>>;
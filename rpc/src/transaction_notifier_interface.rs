@@ -4,6 +4,15 @@ use {
     std::sync::Arc,
 };
 
+/// A transaction processed in a slot, as notified in batches
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionNotification<'a> {
+    pub transaction_slot_index: usize,
+    pub signature: &'a Signature,
+    pub transaction_status_meta: &'a TransactionStatusMeta,
+    pub transaction: &'a SanitizedTransaction,
+}
+
 pub trait TransactionNotifier {
     fn notify_transaction(
         &self,
@@ -13,6 +22,20 @@ pub trait TransactionNotifier {
         transaction_status_meta: &TransactionStatusMeta,
         transaction: &SanitizedTransaction,
     );
+
+    /// Notified of the transactions of a batch at once. Defaults to notifying of each
+    /// transaction on its own.
+    fn notify_transactions(&self, slot: Slot, transactions: &[TransactionNotification<'_>]) {
+        for transaction in transactions {
+            self.notify_transaction(
+                slot,
+                transaction.transaction_slot_index,
+                transaction.signature,
+                transaction.transaction_status_meta,
+                transaction.transaction,
+            );
+        }
+    }
 }
 
 pub type TransactionNotifierArc = Arc<dyn TransactionNotifier + Sync + Send>;
@@ -0,0 +1,78 @@
+//! Bounded cache of the ingest provenance of recently committed transactions,
+//! populated by the banking stage and served through `getSignatureStatuses`.
+
+use {
+    lru::LruCache,
+    solana_sdk::signature::Signature,
+    solana_transaction_status::TransactionProvenance,
+    std::sync::Mutex,
+};
+
+#[derive(Default)]
+pub struct TransactionProvenanceCache {
+    /// `None` if provenance tracking is disabled
+    cache: Option<Mutex<LruCache<Signature, TransactionProvenance>>>,
+}
+
+impl TransactionProvenanceCache {
+    /// Creates a cache holding the provenance of up to `capacity`
+    /// transactions. A `capacity` of zero disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: (capacity > 0).then(|| Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    pub fn insert(&self, signature: Signature, provenance: TransactionProvenance) {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().put(signature, provenance);
+        }
+    }
+
+    pub fn get(&self, signature: &Signature) -> Option<TransactionProvenance> {
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.lock().unwrap().get(signature).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_provenance(commit_slot: u64) -> TransactionProvenance {
+        TransactionProvenance {
+            remote_addr: "127.0.0.1".to_string(),
+            received_at: 1_000,
+            buffer_latency_us: 10,
+            commit_latency_us: 100,
+            commit_slot,
+        }
+    }
+
+    #[test]
+    fn test_disabled() {
+        let cache = TransactionProvenanceCache::default();
+        assert!(!cache.is_enabled());
+        let signature = Signature::new_unique();
+        cache.insert(signature, new_provenance(1));
+        assert_eq!(cache.get(&signature), None);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let cache = TransactionProvenanceCache::new(2);
+        assert!(cache.is_enabled());
+        let signatures: Vec<_> = (0..3).map(|_| Signature::new_unique()).collect();
+        for (slot, signature) in signatures.iter().enumerate() {
+            cache.insert(*signature, new_provenance(slot as u64));
+        }
+        assert_eq!(cache.get(&signatures[0]), None);
+        assert_eq!(cache.get(&signatures[1]), Some(new_provenance(1)));
+        assert_eq!(cache.get(&signatures[2]), Some(new_provenance(2)));
+    }
+}
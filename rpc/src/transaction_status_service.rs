@@ -1,14 +1,19 @@
 use {
-    crate::transaction_notifier_interface::TransactionNotifierArc,
+    crate::transaction_notifier_interface::{TransactionNotification, TransactionNotifierArc},
     crossbeam_channel::{Receiver, RecvTimeoutError},
-    itertools::izip,
+    itertools::{izip, Itertools},
     solana_ledger::{
         blockstore::{Blockstore, BlockstoreError},
         blockstore_processor::{TransactionStatusBatch, TransactionStatusMessage},
     },
+    solana_sdk::{
+        message::SanitizedMessage, pubkey::Pubkey, signature::Signature,
+        transaction::SanitizedTransaction,
+    },
     solana_svm::transaction_commit_result::CommittedTransaction,
     solana_transaction_status::{
-        extract_and_fmt_memos, map_inner_instructions, Reward, TransactionStatusMeta,
+        extract_and_fmt_memos, map_inner_instructions, InnerInstructions, Reward,
+        TransactionStatusMeta,
     },
     std::{
         sync::{
@@ -26,6 +31,51 @@ const TSS_TEST_QUIESCE_NUM_RETRIES: usize = 100;
 #[cfg(feature = "dev-context-only-utils")]
 const TSS_TEST_QUIESCE_SLEEP_TIME_MS: u64 = 50;
 
+/// Which transactions the statuses are written to the blockstore for, along
+/// with their memos and their signatures indexed by address and by program.
+/// The statuses of all the transactions are still notified to the plugins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionStatusFilter {
+    pub skip_vote_transactions: bool,
+    /// Percentage of the successful transactions written, sampled by their
+    /// signature. The failed transactions are all written
+    pub successful_transaction_sample_percent: u8,
+}
+
+impl Default for TransactionStatusFilter {
+    fn default() -> Self {
+        Self {
+            skip_vote_transactions: false,
+            successful_transaction_sample_percent: 100,
+        }
+    }
+}
+
+impl TransactionStatusFilter {
+    /// Whether the statuses of all the transactions are written
+    pub fn is_complete(&self) -> bool {
+        !self.skip_vote_transactions && self.successful_transaction_sample_percent >= 100
+    }
+
+    pub fn should_write(&self, transaction: &SanitizedTransaction, succeeded: bool) -> bool {
+        if self.skip_vote_transactions && transaction.is_simple_vote_transaction() {
+            return false;
+        }
+        !succeeded
+            || is_sampled(
+                transaction.signature(),
+                self.successful_transaction_sample_percent,
+            )
+    }
+}
+
+// Samples by the first bytes of the signature, which are uniformly distributed,
+// so that the same transactions are sampled across nodes and restarts.
+fn is_sampled(signature: &Signature, sample_percent: u8) -> bool {
+    let prefix = u64::from_le_bytes(signature.as_ref()[..8].try_into().unwrap());
+    prefix % 100 < u64::from(sample_percent)
+}
+
 pub struct TransactionStatusService {
     thread_hdl: JoinHandle<()>,
     #[cfg(feature = "dev-context-only-utils")]
@@ -40,6 +90,8 @@ impl TransactionStatusService {
         transaction_notifier: Option<TransactionNotifierArc>,
         blockstore: Arc<Blockstore>,
         enable_extended_tx_metadata_storage: bool,
+        enable_program_signature_index: bool,
+        transaction_status_filter: TransactionStatusFilter,
         exit: Arc<AtomicBool>,
     ) -> Self {
         let transaction_status_receiver = Arc::new(write_transaction_status_receiver);
@@ -73,6 +125,8 @@ impl TransactionStatusService {
                         transaction_notifier.clone(),
                         &blockstore,
                         enable_extended_tx_metadata_storage,
+                        enable_program_signature_index,
+                        &transaction_status_filter,
                     ) {
                         Ok(_) => {}
                         Err(err) => {
@@ -99,6 +153,8 @@ impl TransactionStatusService {
         transaction_notifier: Option<TransactionNotifierArc>,
         blockstore: &Blockstore,
         enable_extended_tx_metadata_storage: bool,
+        enable_program_signature_index: bool,
+        transaction_status_filter: &TransactionStatusFilter,
     ) -> Result<(), BlockstoreError> {
         match transaction_status_message {
             TransactionStatusMessage::Batch(TransactionStatusBatch {
@@ -112,6 +168,7 @@ impl TransactionStatusService {
             }) => {
                 let mut status_and_memos_batch = blockstore.get_write_batch()?;
 
+                let mut committed_transactions = Vec::with_capacity(transactions.len());
                 for (
                     transaction,
                     commit_result,
@@ -166,7 +223,7 @@ impl TransactionStatusService {
                             .collect(),
                     );
                     let loaded_addresses = transaction.get_loaded_addresses();
-                    let mut transaction_status_meta = TransactionStatusMeta {
+                    let transaction_status_meta = TransactionStatusMeta {
                         status,
                         fee,
                         pre_balances,
@@ -181,16 +238,45 @@ impl TransactionStatusService {
                         compute_units_consumed: Some(executed_units),
                         cost_units: cost,
                     };
+                    committed_transactions.push((
+                        transaction,
+                        transaction_status_meta,
+                        transaction_index,
+                    ));
+                }
 
-                    if let Some(transaction_notifier) = transaction_notifier.as_ref() {
-                        transaction_notifier.notify_transaction(
-                            slot,
-                            transaction_index,
-                            transaction.signature(),
-                            &transaction_status_meta,
-                            &transaction,
-                        );
-                    }
+                // The transactions of the batch are notified of at once, so that plugins can
+                // take them in one go.
+                if let Some(transaction_notifier) = transaction_notifier.as_ref() {
+                    let notifications: Vec<_> = committed_transactions
+                        .iter()
+                        .map(
+                            |(transaction, transaction_status_meta, transaction_index)| {
+                                TransactionNotification {
+                                    transaction_slot_index: *transaction_index,
+                                    signature: transaction.signature(),
+                                    transaction_status_meta,
+                                    transaction,
+                                }
+                            },
+                        )
+                        .collect();
+                    transaction_notifier.notify_transactions(slot, &notifications);
+                }
+
+                for (transaction, mut transaction_status_meta, transaction_index) in
+                    committed_transactions
+                {
+                    let write_status = enable_rpc_transaction_history
+                        && transaction_status_filter
+                            .should_write(&transaction, transaction_status_meta.status.is_ok());
+                    // Collected before the inner instructions are possibly dropped below.
+                    let program_ids = (write_status && enable_program_signature_index).then(|| {
+                        get_invoked_program_ids(
+                            transaction.message(),
+                            transaction_status_meta.inner_instructions.as_deref(),
+                        )
+                    });
 
                     if !(enable_extended_tx_metadata_storage || transaction_notifier.is_some()) {
                         transaction_status_meta.log_messages.take();
@@ -198,7 +284,7 @@ impl TransactionStatusService {
                         transaction_status_meta.return_data.take();
                     }
 
-                    if enable_rpc_transaction_history {
+                    if write_status {
                         if let Some(memos) = extract_and_fmt_memos(transaction.message()) {
                             blockstore.add_transaction_memos_to_batch(
                                 transaction.signature(),
@@ -223,6 +309,16 @@ impl TransactionStatusService {
                             transaction_index,
                             &mut status_and_memos_batch,
                         )?;
+
+                        if let Some(program_ids) = program_ids {
+                            blockstore.add_program_signatures_to_batch(
+                                slot,
+                                *transaction.signature(),
+                                program_ids.into_iter(),
+                                transaction_index,
+                                &mut status_and_memos_batch,
+                            )?;
+                        }
                     }
                 }
 
@@ -260,6 +356,27 @@ impl TransactionStatusService {
     }
 }
 
+// Returns the programs invoked by the transaction, directly or through
+// cross-program invocations.
+fn get_invoked_program_ids<'a>(
+    message: &'a SanitizedMessage,
+    inner_instructions: Option<&[InnerInstructions]>,
+) -> Vec<&'a Pubkey> {
+    let account_keys = message.account_keys();
+    let inner_instructions = inner_instructions
+        .into_iter()
+        .flatten()
+        .flat_map(|inner_instructions| &inner_instructions.instructions)
+        .map(|inner_instruction| &inner_instruction.instruction);
+    message
+        .instructions()
+        .iter()
+        .chain(inner_instructions)
+        .filter_map(|instruction| account_keys.get(usize::from(instruction.program_id_index)))
+        .unique()
+        .collect()
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use {
@@ -277,7 +394,8 @@ pub(crate) mod tests {
             account_utils::StateMut,
             clock::Slot,
             fee::FeeDetails,
-            hash::Hash,
+            hash::{hash, Hash},
+            instruction::CompiledInstruction,
             nonce::{self, state::DurableNonce},
             nonce_account,
             pubkey::Pubkey,
@@ -291,7 +409,7 @@ pub(crate) mod tests {
         },
         solana_svm::transaction_execution_result::TransactionLoadedAccountsStats,
         solana_transaction_status::{
-            token_balances::TransactionTokenBalancesSet, TransactionStatusMeta,
+            token_balances::TransactionTokenBalancesSet, InnerInstruction, TransactionStatusMeta,
             TransactionTokenBalance,
         },
         std::sync::{atomic::AtomicBool, Arc},
@@ -456,6 +574,8 @@ pub(crate) mod tests {
             Some(test_notifier.clone()),
             blockstore,
             false,
+            false,
+            TransactionStatusFilter::default(),
             exit.clone(),
         );
 
@@ -560,6 +680,8 @@ pub(crate) mod tests {
             Some(test_notifier.clone()),
             blockstore,
             false,
+            false,
+            TransactionStatusFilter::default(),
             exit.clone(),
         );
 
@@ -604,4 +726,90 @@ pub(crate) mod tests {
             result2.transaction.message_hash()
         );
     }
+
+    #[test]
+    fn test_transaction_status_filter() {
+        let create_transaction = |is_simple_vote_tx| {
+            SanitizedTransaction::try_create(
+                VersionedTransaction::from(build_test_transaction_legacy()),
+                MessageHash::Compute,
+                Some(is_simple_vote_tx),
+                SimpleAddressLoader::Disabled,
+                &ReservedAccountKeys::empty_key_set(),
+            )
+            .unwrap()
+        };
+        let transaction = create_transaction(false);
+        let vote_transaction = create_transaction(true);
+
+        let filter = TransactionStatusFilter::default();
+        assert!(filter.is_complete());
+        assert!(filter.should_write(&transaction, true));
+        assert!(filter.should_write(&vote_transaction, true));
+
+        let filter = TransactionStatusFilter {
+            skip_vote_transactions: true,
+            ..TransactionStatusFilter::default()
+        };
+        assert!(!filter.is_complete());
+        assert!(filter.should_write(&transaction, true));
+        assert!(!filter.should_write(&vote_transaction, true));
+        assert!(!filter.should_write(&vote_transaction, false));
+
+        let filter = TransactionStatusFilter {
+            skip_vote_transactions: false,
+            successful_transaction_sample_percent: 0,
+        };
+        assert!(!filter.is_complete());
+        assert!(!filter.should_write(&transaction, true));
+        // The failed transactions are all written.
+        assert!(filter.should_write(&transaction, false));
+
+        // Roughly the sampled percentage of the signatures is sampled.
+        let num_sampled = (0..1_000u64)
+            .filter(|i| {
+                let mut signature = [0u8; 64];
+                signature[..32].copy_from_slice(hash(&i.to_le_bytes()).as_ref());
+                is_sampled(&Signature::from(signature), 30)
+            })
+            .count();
+        assert!((250..350).contains(&num_sampled), "{num_sampled}");
+    }
+
+    #[test]
+    fn test_get_invoked_program_ids() {
+        let transaction = SanitizedTransaction::try_create(
+            VersionedTransaction::from(build_test_transaction_legacy()),
+            MessageHash::Compute,
+            None,
+            SimpleAddressLoader::Disabled,
+            &ReservedAccountKeys::empty_key_set(),
+        )
+        .unwrap();
+        let message = transaction.message();
+        let account_keys: Vec<_> = message.account_keys().iter().copied().collect();
+        assert_eq!(account_keys[1], solana_sdk::system_program::id());
+        assert_eq!(
+            get_invoked_program_ids(message, None),
+            vec![&account_keys[1]]
+        );
+
+        let inner_instruction = |program_id_index| InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(program_id_index, vec![], vec![]),
+            stack_height: Some(2),
+        };
+        let inner_instructions = [InnerInstructions {
+            index: 0,
+            instructions: vec![
+                inner_instruction(1),
+                inner_instruction(0),
+                // Out of bounds indexes are ignored.
+                inner_instruction(5),
+            ],
+        }];
+        assert_eq!(
+            get_invoked_program_ids(message, Some(&inner_instructions)),
+            vec![&account_keys[1], &account_keys[0]]
+        );
+    }
 }
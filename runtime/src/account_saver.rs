@@ -225,6 +225,7 @@ mod tests {
                     inner_instructions: None,
                     return_data: None,
                     executed_units: 0,
+                    instruction_executed_units: None,
                     accounts_data_len_delta: 0,
                 },
                 loaded_transaction,
@@ -58,12 +58,14 @@ use {
         transaction_batch::{OwnedOrBorrowed, TransactionBatch},
     },
     accounts_lt_hash::{CacheValue as AccountsLtHashCacheValue, Stats as AccountsLtHashStats},
+    address_lookup_table::AddressLookupTableCache,
     agave_feature_set::{self as feature_set, FeatureSet},
     agave_precompiles::{get_precompile, get_precompiles, is_precompile},
     agave_reserved_account_keys::ReservedAccountKeys,
     ahash::{AHashSet, RandomState},
     dashmap::{DashMap, DashSet},
     log::*,
+    missing_accounts::MissingAccountLoader,
     rayon::{
         iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator},
         ThreadPoolBuilder,
@@ -104,7 +106,8 @@ use {
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{meas_dur, measure::Measure, measure_time, measure_us},
     solana_program_runtime::{
-        invoke_context::BuiltinFunctionWithContext, loaded_programs::ProgramCacheEntry,
+        invoke_context::BuiltinFunctionWithContext,
+        loaded_programs::{ProgramCacheCumulativeStats, ProgramCacheEntry, ProgramCacheEntryType},
     },
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_with_meta::TransactionWithMeta,
@@ -225,6 +228,7 @@ mod check_transactions;
 pub mod epoch_accounts_hash_utils;
 mod fee_distribution;
 mod metrics;
+pub mod missing_accounts;
 pub(crate) mod partitioned_epoch_rewards;
 mod recent_blockhashes_account;
 mod serde_snapshot;
@@ -340,6 +344,12 @@ pub struct TransactionSimulationResult {
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
     pub inner_instructions: Option<Vec<InnerInstructions>>,
+    /// The compute units consumed by each top level instruction executed, up to the one which
+    /// failed
+    pub instruction_units_consumed: Option<Vec<u64>>,
+    /// The fees the transaction would be charged, if it could pay for them
+    pub fee_details: Option<FeeDetails>,
+    pub loaded_accounts_data_size: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -587,6 +597,8 @@ impl PartialEq for Bank {
             stats_for_accounts_lt_hash: _,
             block_id,
             bank_hash_stats: _,
+            address_lookup_table_cache: _,
+            missing_account_loader: _,
             // Ignore new fields explicitly if they do not impact PartialEq.
             // Adding ".." will remove compile-time checks that if a new field
             // is added to the struct, this PartialEq is accordingly updated.
@@ -945,6 +957,13 @@ pub struct Bank {
 
     /// Accounts stats for computing the bank hash
     bank_hash_stats: AtomicBankHashStats,
+
+    /// The address lookup tables loaded to resolve the addresses of transactions
+    address_lookup_table_cache: AddressLookupTableCache,
+
+    /// Loads the accounts missing from the bank as transactions access them,
+    /// shared by all the banks descending from the one it is set on
+    missing_account_loader: Arc<RwLock<Option<Arc<MissingAccountLoader>>>>,
 }
 
 #[derive(Debug)]
@@ -1144,6 +1163,8 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::default(),
+            address_lookup_table_cache: AddressLookupTableCache::default(),
+            missing_account_loader: Arc::default(),
         };
 
         bank.transaction_processor =
@@ -1400,6 +1421,10 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::default(),
+            address_lookup_table_cache: AddressLookupTableCache::new_from_parent(
+                &parent.address_lookup_table_cache,
+            ),
+            missing_account_loader: parent.missing_account_loader.clone(),
         };
 
         let (_, ancestors_time_us) = measure_us!({
@@ -1505,13 +1530,16 @@ impl Bank {
                 .stats,
             parent.slot(),
         );
+        report_address_lookup_table_cache_stats(
+            &parent.address_lookup_table_cache.stats,
+            parent.slot(),
+        );
 
-        new.transaction_processor
-            .program_cache
-            .write()
-            .unwrap()
-            .stats
-            .reset();
+        {
+            let mut program_cache = new.transaction_processor.program_cache.write().unwrap();
+            let stats = std::mem::take(&mut program_cache.stats);
+            program_cache.cumulative_stats.accumulate(&stats);
+        }
 
         new
     }
@@ -1524,6 +1552,88 @@ impl Bank {
             .set_fork_graph(fork_graph);
     }
 
+    /// Pins the given programs in the program cache shared by all the banks.
+    pub fn set_pinned_programs_in_program_cache(&self, pinned_programs: HashSet<Pubkey>) {
+        self.transaction_processor
+            .program_cache
+            .write()
+            .unwrap()
+            .set_pinned_programs(pinned_programs);
+    }
+
+    /// The statistics of the program cache shared by all the banks,
+    /// accumulated up to the parent of the latest bank, along with its pinned
+    /// programs
+    pub fn program_cache_cumulative_stats(&self) -> (ProgramCacheCumulativeStats, HashSet<Pubkey>) {
+        let program_cache = self.transaction_processor.program_cache.read().unwrap();
+        (
+            program_cache.cumulative_stats.clone(),
+            program_cache.pinned_programs().clone(),
+        )
+    }
+
+    /// Loads, verifies and compiles the given programs into the program cache
+    /// shared by all the banks, skipping the builtins and the programs which
+    /// are already compiled. The usage counter of each compiled program starts
+    /// at its given number of invocations, so it is not the first evicted.
+    /// Returns the number of programs compiled.
+    pub fn precompile_programs(&self, programs: &[(Pubkey, u64)]) -> usize {
+        let builtin_program_ids = self
+            .transaction_processor
+            .builtin_program_ids
+            .read()
+            .unwrap()
+            .clone();
+        let (environments, compiled_program_ids) = {
+            let program_cache = self.transaction_processor.program_cache.read().unwrap();
+            let compiled_program_ids: HashSet<_> = program_cache
+                .get_flattened_entries(true, true)
+                .into_iter()
+                .map(|(program_id, _)| program_id)
+                .collect();
+            (
+                program_cache.get_environments_for_epoch(self.epoch),
+                compiled_program_ids,
+            )
+        };
+        let mut num_compiled = 0;
+        for (program_id, num_invocations) in programs {
+            if builtin_program_ids.contains(program_id) || compiled_program_ids.contains(program_id)
+            {
+                continue;
+            }
+            let mut execute_timings = ExecuteTimings::default();
+            let (program, load_us) = measure_us!(load_program_with_pubkey(
+                self,
+                &environments,
+                program_id,
+                self.slot,
+                &mut execute_timings,
+                false,
+            ));
+            // Tombstones, e.g. of closed programs or of accounts which are not
+            // programs, are not worth caching ahead of their invocation.
+            let Some(program) = program
+                .filter(|program| matches!(program.program, ProgramCacheEntryType::Loaded(_)))
+            else {
+                continue;
+            };
+            program
+                .tx_usage_counter
+                .store(*num_invocations, Ordering::Relaxed);
+            let mut program_cache = self.transaction_processor.program_cache.write().unwrap();
+            program_cache.stats.record_load(
+                *program_id,
+                load_us,
+                execute_timings.details.create_executor_jit_compile_us.0,
+            );
+            if program_cache.assign_program(*program_id, program) {
+                num_compiled += 1;
+            }
+        }
+        num_compiled
+    }
+
     fn prepare_program_cache_for_upcoming_feature_set(&self) {
         let (_epoch, slot_index) = self.epoch_schedule.get_epoch_and_slot_index(self.slot);
         let slots_in_epoch = self.epoch_schedule.get_slots_in_epoch(self.epoch);
@@ -1604,11 +1714,17 @@ impl Bank {
                 upcoming_environments.program_runtime_v2 = Arc::new(program_runtime_environment_v2);
             }
             program_cache.upcoming_environments = Some(upcoming_environments);
-            program_cache.programs_to_recompile = program_cache
+            let mut programs_to_recompile = program_cache
                 .get_flattened_entries(changed_program_runtime_v1, changed_program_runtime_v2);
-            program_cache
-                .programs_to_recompile
-                .sort_by_cached_key(|(_id, program)| program.decayed_usage_counter(self.slot));
+            // The last programs are recompiled first, pinned programs before
+            // the others.
+            programs_to_recompile.sort_by_cached_key(|(id, program)| {
+                (
+                    program_cache.is_pinned(id),
+                    program.decayed_usage_counter(self.slot),
+                )
+            });
+            program_cache.programs_to_recompile = programs_to_recompile;
         }
     }
 
@@ -1877,6 +1993,8 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::new(&fields.bank_hash_stats),
+            address_lookup_table_cache: AddressLookupTableCache::default(),
+            missing_account_loader: Arc::default(),
         };
 
         bank.transaction_processor =
@@ -3269,7 +3387,8 @@ impl Bank {
     ) -> TransactionSimulationResult {
         let account_keys = transaction.account_keys();
         let number_of_accounts = account_keys.len();
-        let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
+        let account_overrides =
+            self.get_account_overrides_for_simulation(slice::from_ref(&account_keys));
         let batch = self.prepare_unlocked_batch_from_single_tx(transaction);
         let mut timings = ExecuteTimings::default();
 
@@ -3293,6 +3412,7 @@ impl Bank {
                     enable_cpi_recording,
                     enable_log_recording: true,
                     enable_return_data_recording: true,
+                    enable_instruction_units_recording: true,
                 },
             },
         );
@@ -3314,31 +3434,129 @@ impl Bank {
         let processing_result = processing_results
             .pop()
             .unwrap_or(Err(TransactionError::InvalidProgramForExecution));
-        let (post_simulation_accounts, result, logs, return_data, inner_instructions) =
-            match processing_result {
-                Ok(processed_tx) => match processed_tx {
-                    ProcessedTransaction::Executed(executed_tx) => {
-                        let details = executed_tx.execution_details;
-                        let post_simulation_accounts = executed_tx
-                            .loaded_transaction
-                            .accounts
-                            .into_iter()
-                            .take(number_of_accounts)
-                            .collect::<Vec<_>>();
-                        (
-                            post_simulation_accounts,
-                            details.status,
-                            details.log_messages,
-                            details.return_data,
-                            details.inner_instructions,
-                        )
-                    }
-                    ProcessedTransaction::FeesOnly(fees_only_tx) => {
-                        (vec![], Err(fees_only_tx.load_error), None, None, None)
-                    }
+        Self::simulation_result(processing_result, number_of_accounts, units_consumed)
+    }
+
+    /// Run transactions one after the other against a frozen bank without committing the
+    /// results, each transaction seeing the account changes of the ones before it
+    pub fn simulate_transactions(
+        &self,
+        transactions: &[impl TransactionWithMeta],
+        enable_cpi_recording: bool,
+    ) -> Vec<TransactionSimulationResult> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_transactions_unchecked(transactions, enable_cpi_recording)
+    }
+
+    /// Run transactions one after the other against a bank without committing the results; does
+    /// not check if the bank is frozen, enabling use in single-Bank test frameworks
+    pub fn simulate_transactions_unchecked(
+        &self,
+        transactions: &[impl TransactionWithMeta],
+        enable_cpi_recording: bool,
+    ) -> Vec<TransactionSimulationResult> {
+        let account_keys = transactions
+            .iter()
+            .map(|transaction| transaction.account_keys())
+            .collect::<Vec<_>>();
+        let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
+        let tx_account_lock_limit = self.get_transaction_account_lock_limit();
+        let lock_results = transactions
+            .iter()
+            .map(|transaction| {
+                validate_account_locks(transaction.account_keys(), tx_account_lock_limit)
+            })
+            .collect();
+        // The transactions of a batch are processed in order, with the accounts loaded by the
+        // later ones reflecting the changes made by the earlier ones
+        let mut batch =
+            TransactionBatch::new(lock_results, self, OwnedOrBorrowed::Borrowed(transactions));
+        batch.set_needs_unlock(false);
+        let mut timings = ExecuteTimings::default();
+
+        let LoadAndExecuteTransactionsOutput {
+            processing_results, ..
+        } = self.load_and_execute_transactions(
+            &batch,
+            MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
+            &mut timings,
+            &mut TransactionErrorMetrics::default(),
+            TransactionProcessingConfig {
+                account_overrides: Some(&account_overrides),
+                check_program_modification_slot: self.check_program_modification_slot,
+                log_messages_bytes_limit: None,
+                limit_to_load_programs: true,
+                recording_config: ExecutionRecordingConfig {
+                    enable_cpi_recording,
+                    enable_log_recording: true,
+                    enable_return_data_recording: true,
+                    enable_instruction_units_recording: true,
                 },
-                Err(error) => (vec![], Err(error), None, None, None),
-            };
+            },
+        );
+
+        debug!("simulate_transactions: {:?}", timings);
+
+        processing_results
+            .into_iter()
+            .zip(&account_keys)
+            .map(|(processing_result, account_keys)| {
+                let units_consumed = match &processing_result {
+                    Ok(ProcessedTransaction::Executed(executed_tx)) => {
+                        executed_tx.execution_details.executed_units
+                    }
+                    _ => 0,
+                };
+                Self::simulation_result(processing_result, account_keys.len(), units_consumed)
+            })
+            .collect()
+    }
+
+    fn simulation_result(
+        processing_result: TransactionProcessingResult,
+        number_of_accounts: usize,
+        units_consumed: u64,
+    ) -> TransactionSimulationResult {
+        let fee_details = processing_result
+            .as_ref()
+            .ok()
+            .map(|processed_tx| processed_tx.fee_details());
+        let loaded_accounts_data_size = processing_result
+            .as_ref()
+            .map_or(0, |processed_tx| processed_tx.loaded_accounts_data_size());
+        let (
+            post_simulation_accounts,
+            result,
+            logs,
+            return_data,
+            inner_instructions,
+            instruction_units_consumed,
+        ) = match processing_result {
+            Ok(processed_tx) => match processed_tx {
+                ProcessedTransaction::Executed(executed_tx) => {
+                    let details = executed_tx.execution_details;
+                    let post_simulation_accounts = executed_tx
+                        .loaded_transaction
+                        .accounts
+                        .into_iter()
+                        .take(number_of_accounts)
+                        .collect::<Vec<_>>();
+                    (
+                        post_simulation_accounts,
+                        details.status,
+                        details.log_messages,
+                        details.return_data,
+                        details.inner_instructions,
+                        details.instruction_executed_units,
+                    )
+                }
+                ProcessedTransaction::FeesOnly(fees_only_tx) => {
+                    (vec![], Err(fees_only_tx.load_error), None, None, None, None)
+                }
+            },
+            Err(error) => (vec![], Err(error), None, None, None, None),
+        };
         let logs = logs.unwrap_or_default();
 
         TransactionSimulationResult {
@@ -3348,13 +3566,22 @@ impl Bank {
             units_consumed,
             return_data,
             inner_instructions,
+            instruction_units_consumed,
+            fee_details,
+            loaded_accounts_data_size,
         }
     }
 
-    fn get_account_overrides_for_simulation(&self, account_keys: &AccountKeys) -> AccountOverrides {
+    fn get_account_overrides_for_simulation(
+        &self,
+        account_keys: &[AccountKeys],
+    ) -> AccountOverrides {
         let mut account_overrides = AccountOverrides::default();
         let slot_history_id = sysvar::slot_history::id();
-        if account_keys.iter().any(|pubkey| *pubkey == slot_history_id) {
+        if account_keys
+            .iter()
+            .any(|account_keys| account_keys.iter().any(|pubkey| *pubkey == slot_history_id))
+        {
             let current_account = self.get_account_with_fixed_root(&slot_history_id);
             let slot_history = current_account
                 .as_ref()
@@ -3418,6 +3645,7 @@ impl Bank {
         processing_config: TransactionProcessingConfig,
     ) -> LoadAndExecuteTransactionsOutput {
         let sanitized_txs = batch.sanitized_transactions();
+        self.load_missing_transaction_accounts(sanitized_txs);
 
         let (check_results, check_us) = measure_us!(self.check_transactions(
             sanitized_txs,
@@ -3758,6 +3986,8 @@ impl Bank {
             self.rc
                 .accounts
                 .store_cached(to_store, transactions.as_deref());
+            self.address_lookup_table_cache
+                .invalidate(accounts_to_store.iter().map(|(pubkey, _account)| *pubkey));
         });
 
         self.collect_rent(&processing_results);
@@ -4690,6 +4920,7 @@ impl Bank {
                 enable_cpi_recording: false,
                 enable_log_recording: true,
                 enable_return_data_recording: true,
+                enable_instruction_units_recording: false,
             },
             &mut ExecuteTimings::default(),
             Some(1000 * 1000),
@@ -4788,8 +5019,10 @@ impl Bank {
         let mut m = Measure::start("stakes_cache.check_and_store");
         let new_warmup_cooldown_rate_epoch = self.new_warmup_cooldown_rate_epoch();
 
+        let mut pubkeys = Vec::with_capacity(accounts.len());
         (0..accounts.len()).for_each(|i| {
             accounts.account(i, |account| {
+                pubkeys.push(*account.pubkey());
                 self.stakes_cache.check_and_store(
                     account.pubkey(),
                     &account,
@@ -4799,6 +5032,7 @@ impl Bank {
         });
         self.update_bank_hash_stats(&accounts);
         self.rc.accounts.store_accounts_cached(accounts);
+        self.address_lookup_table_cache.invalidate(&pubkeys);
         m.stop();
         self.rc
             .accounts
@@ -5168,6 +5402,14 @@ impl Bank {
         self.rc.accounts.load_by_program_slot(self.slot(), None)
     }
 
+    /// Returns the pubkeys of all the accounts stored in this slot
+    pub fn get_pubkeys_modified_since_parent(&self) -> Vec<Pubkey> {
+        self.rc
+            .accounts
+            .accounts_db
+            .get_pubkeys_for_slot(self.slot())
+    }
+
     // if you want get_account_modified_since_parent without fixed_root, please define so...
     fn get_account_modified_since_parent_with_fixed_root(
         &self,
@@ -5912,6 +6154,19 @@ impl Bank {
             .load_account_into_read_cache(&self.ancestors, key);
     }
 
+    /// Loads the accounts of `transactions` which are in storages into the read only accounts
+    /// cache in a batch, so that executing the transactions finds them there.
+    pub fn load_transactions_accounts_into_read_cache(&self, transactions: &[impl SVMMessage]) {
+        let mut pubkeys = Vec::new();
+        for transaction in transactions {
+            pubkeys.extend(transaction.account_keys().iter());
+        }
+        self.rc
+            .accounts
+            .accounts_db
+            .load_accounts_into_read_cache(&self.ancestors, &pubkeys);
+    }
+
     pub fn update_accounts_hash(
         &self,
         data_source: CalcAccountsHashDataSource,
@@ -6989,6 +7244,10 @@ impl TransactionProcessingCallback for Bank {
             .accounts_db
             .account_matches_owners(&self.ancestors, account, owners)
             .ok()
+            .or_else(|| {
+                let account = self.get_missing_account(account)?;
+                owners.iter().position(|owner| account.owner() == owner)
+            })
     }
 
     fn get_account_shared_data(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
@@ -6997,6 +7256,7 @@ impl TransactionProcessingCallback for Bank {
             .accounts_db
             .load_with_fixed_root(&self.ancestors, pubkey)
             .map(|(acc, _)| acc)
+            .or_else(|| self.get_missing_account(pubkey))
     }
 
     // NOTE: must hold idempotent for the same set of arguments
@@ -2,7 +2,7 @@ use {
     super::Bank,
     agave_feature_set as feature_set,
     rayon::prelude::*,
-    solana_accounts_db::accounts_db::AccountsDb,
+    solana_accounts_db::{accounts_db::AccountsDb, accounts_hash::AccountsLtHash},
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{meas_dur, measure::Measure},
     solana_sdk::{
@@ -310,6 +310,52 @@ impl Bank {
         delta_lt_hash
     }
 
+    /// Returns the accounts lt hash, as updated incrementally when freezing the bank
+    pub fn accounts_lt_hash(&self) -> AccountsLtHash {
+        self.accounts_lt_hash.lock().unwrap().clone()
+    }
+
+    /// Recalculates the accounts lt hash of this bank from all its accounts
+    ///
+    /// This is independent of the accounts lt hash updated incrementally when freezing the bank,
+    /// so it can be used to audit it.  Every account in the index is hashed, so this is slow.
+    pub fn recalculate_accounts_lt_hash(&self) -> AccountsLtHash {
+        self.rc
+            .accounts
+            .accounts_db
+            .calculate_accounts_lt_hash_at_startup_from_index(&self.ancestors, self.slot())
+    }
+
+    /// Recalculates the accounts lt hash of this bank from the accounts lt hash of its parent
+    ///
+    /// For each account stored in this slot, the previous version of the account is loaded from
+    /// accounts-db instead of from the accounts lt hash cache used when freezing the bank.
+    /// Returns `None` if the bank has no parent.
+    pub fn recalculate_accounts_lt_hash_from_parent(&self) -> Option<AccountsLtHash> {
+        let parent = self.parent()?;
+        let mut accounts_lt_hash = parent.accounts_lt_hash.lock().unwrap().clone();
+        let accounts_curr = self
+            .rc
+            .accounts
+            .accounts_db
+            .get_pubkey_account_for_slot(self.slot());
+        for (pubkey, curr_account) in accounts_curr {
+            let prev_account = self
+                .rc
+                .accounts
+                .load_with_fixed_root_do_not_populate_read_cache(&parent.ancestors, &pubkey);
+            if let Some((prev_account, _slot)) = prev_account {
+                accounts_lt_hash
+                    .0
+                    .mix_out(&AccountsDb::lt_hash_account(&prev_account, &pubkey).0);
+            }
+            accounts_lt_hash
+                .0
+                .mix_in(&AccountsDb::lt_hash_account(&curr_account, &pubkey).0);
+        }
+        Some(accounts_lt_hash)
+    }
+
     /// Caches initial state of writeable accounts
     ///
     /// If a transaction account is writeable, cache its initial account state.
@@ -830,6 +876,66 @@ mod tests {
         assert_eq!(expected_accounts_lt_hash, calculated_accounts_lt_hash);
     }
 
+    #[test_case(Features::None; "no features")]
+    #[test_case(Features::All; "all features")]
+    fn test_recalculate_accounts_lt_hash(features: Features) {
+        let (genesis_config, mint_keypair) = genesis_config_with(features);
+        let (mut bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        bank.rc
+            .accounts
+            .accounts_db
+            .set_is_experimental_accumulator_hash_enabled(features == Features::None);
+
+        // ensure the accounts lt hash is enabled, otherwise this test doesn't actually do anything...
+        assert!(bank.is_accounts_lt_hash_enabled());
+
+        // slot 0 has no parent to recalculate from
+        bank.freeze();
+        assert!(bank.recalculate_accounts_lt_hash_from_parent().is_none());
+
+        let amount = cmp::max(
+            bank.get_minimum_balance_for_rent_exemption(0),
+            LAMPORTS_PER_SOL,
+        );
+        let recipient = pubkey::new_rand();
+        for _ in 0..3 {
+            let slot = bank.slot() + 1;
+            bank =
+                new_bank_from_parent_with_bank_forks(&bank_forks, bank, &Pubkey::default(), slot);
+            // transfer to both an existing and a new account
+            bank.register_unique_recent_blockhash_for_test();
+            bank.transfer(amount, &mint_keypair, &recipient).unwrap();
+            bank.transfer(amount, &mint_keypair, &pubkey::new_rand())
+                .unwrap();
+            bank.freeze();
+
+            let accounts_lt_hash = bank.accounts_lt_hash.lock().unwrap().clone();
+            assert_eq!(
+                bank.recalculate_accounts_lt_hash_from_parent(),
+                Some(accounts_lt_hash.clone()),
+            );
+            assert_eq!(bank.recalculate_accounts_lt_hash(), accounts_lt_hash);
+        }
+
+        // an account the incremental update missed is caught by both recalculations
+        let slot = bank.slot() + 1;
+        bank = new_bank_from_parent_with_bank_forks(&bank_forks, bank, &Pubkey::default(), slot);
+        bank.freeze();
+        let accounts_lt_hash = bank.accounts_lt_hash.lock().unwrap().clone();
+        bank.rc.accounts.accounts_db.store_for_tests(
+            bank.slot(),
+            &[(
+                &pubkey::new_rand(),
+                &AccountSharedData::new(amount, 0, &Pubkey::default()),
+            )],
+        );
+        assert_ne!(
+            bank.recalculate_accounts_lt_hash_from_parent(),
+            Some(accounts_lt_hash.clone()),
+        );
+        assert_ne!(bank.recalculate_accounts_lt_hash(), accounts_lt_hash);
+    }
+
     #[test_case(Features::None; "no features")]
     #[test_case(Features::All; "all features")]
     fn test_calculate_accounts_lt_hash_at_startup_from_storages(features: Features) {
@@ -1,17 +1,30 @@
 use {
     super::Bank,
+    solana_accounts_db::accounts::Accounts,
     solana_sdk::{
-        address_lookup_table::error::AddressLookupError,
+        account::{AccountSharedData, ReadableAccount},
+        address_lookup_table::{self, error::AddressLookupError},
         clock::Slot,
         message::{
             v0::{LoadedAddresses, MessageAddressTableLookup},
             AddressLoaderError,
         },
+        pubkey::Pubkey,
         transaction::AddressLoader,
     },
     solana_svm_transaction::message_address_table_lookup::SVMMessageAddressTableLookup,
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            RwLock,
+        },
+    },
 };
 
+/// The maximum number of address lookup tables cached by a bank
+const MAX_CACHED_LOOKUP_TABLES: usize = 1024;
+
 fn into_address_loader_error(err: AddressLookupError) -> AddressLoaderError {
     match err {
         AddressLookupError::LookupTableAccountNotFound => {
@@ -53,19 +66,171 @@ impl Bank {
         let mut deactivation_slot = u64::MAX;
         let mut loaded_addresses = LoadedAddresses::default();
         for address_table_lookup in address_table_lookups {
+            let table_account = self
+                .address_lookup_table_cache
+                .get_or_load(address_table_lookup.account_key, || {
+                    self.rc
+                        .accounts
+                        .load_lookup_table_account(
+                            &self.ancestors,
+                            address_table_lookup.account_key,
+                        )
+                        .or_else(|| self.load_missing_account(address_table_lookup.account_key))
+                })
+                .ok_or(AddressLoaderError::LookupTableAccountNotFound)?;
             deactivation_slot = deactivation_slot.min(
-                self.rc
-                    .accounts
-                    .load_lookup_table_addresses_into(
-                        &self.ancestors,
-                        address_table_lookup,
-                        &slot_hashes,
-                        &mut loaded_addresses,
-                    )
-                    .map_err(into_address_loader_error)?,
+                Accounts::lookup_table_addresses_into(
+                    &table_account,
+                    self.ancestors.max_slot(),
+                    address_table_lookup,
+                    &slot_hashes,
+                    &mut loaded_addresses,
+                )
+                .map_err(into_address_loader_error)?,
             );
         }
 
         Ok((loaded_addresses, deactivation_slot))
     }
 }
+
+#[derive(Debug, Default)]
+pub(crate) struct AddressLookupTableCacheStats {
+    pub(crate) hits: AtomicU64,
+    pub(crate) misses: AtomicU64,
+    pub(crate) invalidations: AtomicU64,
+    pub(crate) evictions: AtomicU64,
+}
+
+/// The address lookup table accounts loaded by a bank to resolve the addresses of transactions,
+/// as many of them use the same popular tables.
+///
+/// The cache of a bank starts as a copy of the cache of its frozen parent, and the cached tables
+/// are invalidated when they are stored to the bank.
+#[derive(Debug, Default)]
+pub(crate) struct AddressLookupTableCache {
+    tables: RwLock<HashMap<Pubkey, AccountSharedData>>,
+    /// Incremented on each store, so that a table loaded before it is not cached after it
+    store_count: AtomicU64,
+    pub(crate) stats: AddressLookupTableCacheStats,
+}
+
+impl AddressLookupTableCache {
+    pub(crate) fn new_from_parent(parent: &Self) -> Self {
+        Self {
+            tables: RwLock::new(parent.tables.read().unwrap().clone()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the cached account of the table at `address`, loading it with `load` if it is not
+    /// cached
+    pub(crate) fn get_or_load(
+        &self,
+        address: &Pubkey,
+        load: impl FnOnce() -> Option<AccountSharedData>,
+    ) -> Option<AccountSharedData> {
+        if let Some(account) = self.tables.read().unwrap().get(address) {
+            self.stats.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(account.clone());
+        }
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
+
+        let store_count = self.store_count.load(Ordering::SeqCst);
+        let account = load()?;
+        // The accounts which are not tables fail to resolve addresses, there is no point in
+        // caching them.
+        if account.owner() != &address_lookup_table::program::id() {
+            return Some(account);
+        }
+        let mut tables = self.tables.write().unwrap();
+        if self.store_count.load(Ordering::SeqCst) == store_count {
+            if tables.len() >= MAX_CACHED_LOOKUP_TABLES && !tables.contains_key(address) {
+                let evicted = *tables.keys().next().unwrap();
+                tables.remove(&evicted);
+                self.stats.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+            tables.insert(*address, account.clone());
+        }
+        Some(account)
+    }
+
+    /// Invalidates the cached tables among the accounts at `pubkeys` stored to the bank. Must be
+    /// called after the accounts are stored.
+    pub(crate) fn invalidate<'a>(&self, pubkeys: impl IntoIterator<Item = &'a Pubkey>) {
+        self.store_count.fetch_add(1, Ordering::SeqCst);
+        let stored_tables: Vec<_> = {
+            let tables = self.tables.read().unwrap();
+            if tables.is_empty() {
+                return;
+            }
+            pubkeys
+                .into_iter()
+                .filter(|pubkey| tables.contains_key(pubkey))
+                .copied()
+                .collect()
+        };
+        if stored_tables.is_empty() {
+            return;
+        }
+        let mut tables = self.tables.write().unwrap();
+        for pubkey in &stored_tables {
+            tables.remove(pubkey);
+        }
+        self.stats
+            .invalidations
+            .fetch_add(stored_tables.len() as u64, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::system_program};
+
+    #[test]
+    fn test_address_lookup_table_cache() {
+        let cache = AddressLookupTableCache::default();
+        let (table_address, other_address) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let table_account = AccountSharedData::new(1, 56, &address_lookup_table::program::id());
+        let other_account = AccountSharedData::new(1, 0, &system_program::id());
+
+        assert_eq!(
+            cache.get_or_load(&table_address, || Some(table_account.clone())),
+            Some(table_account.clone())
+        );
+        // The table is loaded once, the other accounts are not cached.
+        assert_eq!(
+            cache.get_or_load(&table_address, || panic!("the table is cached")),
+            Some(table_account.clone())
+        );
+        assert_eq!(
+            cache.get_or_load(&other_address, || Some(other_account.clone())),
+            Some(other_account.clone())
+        );
+        assert_eq!(cache.get_or_load(&other_address, || None), None);
+        assert_eq!(cache.stats.hits.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.stats.misses.load(Ordering::Relaxed), 3);
+
+        // The cache of a child bank starts with the tables of its parent.
+        let child_cache = AddressLookupTableCache::new_from_parent(&cache);
+        assert_eq!(
+            child_cache.get_or_load(&table_address, || panic!("the table is cached")),
+            Some(table_account.clone())
+        );
+
+        // Storing the table invalidates it.
+        cache.invalidate([&table_address]);
+        assert_eq!(cache.stats.invalidations.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.get_or_load(&table_address, || None), None);
+
+        // A table loaded before a store is not cached after it.
+        assert_eq!(
+            cache.get_or_load(&table_address, || {
+                cache.invalidate([&other_address]);
+                Some(table_account.clone())
+            }),
+            Some(table_account.clone())
+        );
+        assert_eq!(cache.get_or_load(&table_address, || None), None);
+    }
+}
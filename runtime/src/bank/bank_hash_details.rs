@@ -22,7 +22,7 @@ use {
     solana_svm::transaction_commit_result::CommittedTransaction,
     solana_transaction_context::TransactionReturnData,
     solana_transaction_status_client_types::UiInstruction,
-    std::str::FromStr,
+    std::{fmt, str::FromStr},
 };
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -106,6 +106,8 @@ impl From<CommittedTransaction> for TransactionCommitDetails {
 pub struct SlotDetails {
     pub slot: Slot,
     pub bank_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub capitalization: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none", default, flatten)]
     pub bank_hash_components: Option<BankHashComponents>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -137,20 +139,7 @@ impl SlotDetails {
         }
 
         let bank_hash_components = if include_bank_hash_components {
-            let accounts_delta_hash = (!bank
-                .feature_set
-                .is_active(&feature_set::remove_accounts_delta_hash::id()))
-            .then(|| {
-                // This bank is frozen; as a result, we know that the state has been
-                // hashed which means the delta hash is Some(). So, .unwrap() is safe
-                bank.rc
-                    .accounts
-                    .accounts_db
-                    .get_accounts_delta_hash(slot)
-                    .unwrap()
-                    .0
-                    .to_string()
-            });
+            let accounts_delta_hash = accounts_delta_hash(bank);
             let accounts = bank.get_accounts_for_bank_hash_details();
 
             Some(BankHashComponents {
@@ -182,12 +171,122 @@ impl SlotDetails {
         Ok(Self {
             slot,
             bank_hash: bank.hash().to_string(),
+            capitalization: Some(bank.capitalization()),
             bank_hash_components,
             transactions: Vec::new(),
         })
     }
 }
 
+/// The components hashed into the bank hash of a frozen bank, except for the
+/// accounts it modified, which are cheap to retain for the recent banks
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct BankHashBreakdown {
+    pub slot: Slot,
+    pub bank_hash: String,
+    pub parent_slot: Slot,
+    pub parent_bank_hash: String,
+    /// `None` once the bank hash does not include it anymore
+    pub accounts_delta_hash: Option<String>,
+    pub signature_count: u64,
+    pub last_blockhash: String,
+    /// `None` unless the epoch accounts hash is hashed into this bank
+    pub epoch_accounts_hash: Option<String>,
+    /// `None` unless the accounts lt hash is hashed into the bank hash
+    pub accounts_lt_hash_checksum: Option<String>,
+    /// Whether the bank hash is extended with the data of a hard fork
+    pub is_hard_fork: bool,
+}
+
+impl BankHashBreakdown {
+    pub fn new_from_bank(bank: &Bank) -> Result<Self, String> {
+        let slot = bank.slot();
+        if !bank.is_frozen() {
+            return Err(format!(
+                "Bank {slot} must be frozen in order to get its bank hash breakdown"
+            ));
+        }
+        // Mirrors `Bank::hash_internal_state()`, which only hashes the epoch
+        // accounts hash in while the accounts lt hash is not.
+        let is_accounts_lt_hash_enabled = bank
+            .feature_set
+            .is_active(&feature_set::accounts_lt_hash::id());
+        Ok(Self {
+            slot,
+            bank_hash: bank.hash().to_string(),
+            parent_slot: bank.parent_slot(),
+            parent_bank_hash: bank.parent_hash().to_string(),
+            accounts_delta_hash: accounts_delta_hash(bank),
+            signature_count: bank.signature_count(),
+            last_blockhash: bank.last_blockhash().to_string(),
+            // The bank is already frozen so this should not have to wait
+            epoch_accounts_hash: (!is_accounts_lt_hash_enabled)
+                .then(|| bank.wait_get_epoch_accounts_hash())
+                .flatten()
+                .map(|hash| hash.as_ref().to_string()),
+            accounts_lt_hash_checksum: is_accounts_lt_hash_enabled.then(|| {
+                bank.accounts_lt_hash
+                    .lock()
+                    .unwrap()
+                    .0
+                    .checksum()
+                    .to_string()
+            }),
+            is_hard_fork: bank
+                .hard_forks()
+                .get_hash_data(slot, bank.parent_slot())
+                .is_some(),
+        })
+    }
+}
+
+impl fmt::Display for BankHashBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "slot: {} bank_hash: {} parent_slot: {} parent_bank_hash: {}",
+            self.slot, self.bank_hash, self.parent_slot, self.parent_bank_hash
+        )?;
+        if let Some(accounts_delta_hash) = &self.accounts_delta_hash {
+            write!(f, " accounts_delta_hash: {accounts_delta_hash}")?;
+        }
+        write!(
+            f,
+            " signature_count: {} last_blockhash: {}",
+            self.signature_count, self.last_blockhash
+        )?;
+        if let Some(epoch_accounts_hash) = &self.epoch_accounts_hash {
+            write!(f, " epoch_accounts_hash: {epoch_accounts_hash}")?;
+        }
+        if let Some(checksum) = &self.accounts_lt_hash_checksum {
+            write!(f, " accounts_lt_hash_checksum: {checksum}")?;
+        }
+        if self.is_hard_fork {
+            write!(f, " hard_fork: true")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the accounts delta hash of a frozen bank, or `None` if the bank hash
+/// does not include it anymore.
+pub fn accounts_delta_hash(bank: &Bank) -> Option<String> {
+    (!bank
+        .feature_set
+        .is_active(&feature_set::remove_accounts_delta_hash::id()))
+    .then(|| {
+        // This bank is frozen; as a result, we know that the state has been
+        // hashed which means the delta hash is Some(). So, .unwrap() is safe
+        bank.rc
+            .accounts
+            .accounts_db
+            .get_accounts_delta_hash(bank.slot())
+            .unwrap()
+            .0
+            .to_string()
+    })
+}
+
 /// Wrapper around a Vec<_> to facilitate custom Serialize/Deserialize trait
 /// implementations.
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
@@ -319,7 +418,7 @@ pub fn write_bank_hash_details_file(bank: &Bank) -> std::result::Result<(), Stri
 
 #[cfg(test)]
 pub mod tests {
-    use super::*;
+    use {super::*, std::sync::Arc};
 
     fn build_details(num_slots: usize) -> BankHashDetails {
         let slot_details: Vec<_> = (0..num_slots)
@@ -346,6 +445,7 @@ pub mod tests {
                 SlotDetails {
                     slot,
                     bank_hash: format!("bank{slot}"),
+                    capitalization: (slot % 5 != 0).then_some(slot * 1_000),
                     bank_hash_components: Some(BankHashComponents {
                         parent_bank_hash: "parent_bank_hash".into(),
                         accounts_delta_hash: if slot % 4 == 0 {
@@ -386,4 +486,24 @@ pub mod tests {
 
         assert_eq!(bank_hash_details, deserialized_bank_hash_details);
     }
+
+    #[test]
+    fn test_bank_hash_breakdown() {
+        let genesis_config = crate::genesis_utils::create_genesis_config(1_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::default(), 1);
+        assert!(BankHashBreakdown::new_from_bank(&bank1).is_err());
+
+        bank1.freeze();
+        let breakdown = BankHashBreakdown::new_from_bank(&bank1).unwrap();
+        assert_eq!(breakdown.slot, 1);
+        assert_eq!(breakdown.bank_hash, bank1.hash().to_string());
+        assert_eq!(breakdown.parent_slot, 0);
+        assert_eq!(breakdown.parent_bank_hash, bank0.hash().to_string());
+        assert_eq!(breakdown.signature_count, 0);
+        assert_eq!(breakdown.last_blockhash, bank1.last_blockhash().to_string());
+        assert!(!breakdown.is_hard_fork);
+        let display = breakdown.to_string();
+        assert!(display.starts_with(&format!("slot: 1 bank_hash: {}", breakdown.bank_hash)));
+    }
 }
@@ -1,5 +1,5 @@
 use {
-    crate::bank::Bank,
+    crate::bank::{address_lookup_table::AddressLookupTableCacheStats, Bank},
     solana_program_runtime::loaded_programs::ProgramCacheStats,
     solana_sdk::clock::{Epoch, Slot},
     std::sync::atomic::{
@@ -8,6 +8,10 @@ use {
     },
 };
 
+/// The number of programs whose loads are reported per slot, those which took
+/// the longest to load
+const NUM_REPORTED_PROGRAM_LOADS: usize = 10;
+
 pub(crate) struct NewEpochTimings {
     pub(crate) thread_pool_time_us: u64,
     pub(crate) apply_feature_activations_time_us: u64,
@@ -212,6 +216,20 @@ pub(crate) fn report_partitioned_reward_metrics(bank: &Bank, timings: RewardsSto
     );
 }
 
+pub(crate) fn report_address_lookup_table_cache_stats(
+    stats: &AddressLookupTableCacheStats,
+    slot: Slot,
+) {
+    datapoint_info!(
+        "address-lookup-table-cache-stats",
+        ("slot", slot, i64),
+        ("hits", stats.hits.load(Relaxed), i64),
+        ("misses", stats.misses.load(Relaxed), i64),
+        ("invalidations", stats.invalidations.load(Relaxed), i64),
+        ("evictions", stats.evictions.load(Relaxed), i64),
+    );
+}
+
 /// Logs the measurement values
 pub(crate) fn report_loaded_programs_stats(stats: &ProgramCacheStats, slot: Slot) {
     let hits = stats.hits.load(Ordering::Relaxed);
@@ -226,6 +244,7 @@ pub(crate) fn report_loaded_programs_stats(stats: &ProgramCacheStats, slot: Slot
     let prunes_environment = stats.prunes_environment.load(Ordering::Relaxed);
     let empty_entries = stats.empty_entries.load(Ordering::Relaxed);
     let water_level = stats.water_level.load(Ordering::Relaxed);
+    let loads = stats.total_loads();
     datapoint_info!(
         "loaded-programs-cache-stats",
         ("slot", slot, i64),
@@ -241,6 +260,21 @@ pub(crate) fn report_loaded_programs_stats(stats: &ProgramCacheStats, slot: Slot
         ("prunes_environment", prunes_environment, i64),
         ("empty_entries", empty_entries, i64),
         ("water_level", water_level, i64),
+        ("loads", loads.num_loads, i64),
+        ("load_us", loads.load_us, i64),
+        ("jit_compile_us", loads.jit_compile_us, i64),
     );
+    let mut program_loads: Vec<_> = stats.loads.iter().collect();
+    program_loads.sort_unstable_by_key(|(_program_id, loads)| std::cmp::Reverse(loads.load_us));
+    for (program_id, loads) in program_loads.into_iter().take(NUM_REPORTED_PROGRAM_LOADS) {
+        datapoint_info!(
+            "loaded-programs-cache-program-loads",
+            ("slot", slot, i64),
+            ("program_id", program_id.to_string(), String),
+            ("loads", loads.num_loads, i64),
+            ("load_us", loads.load_us, i64),
+            ("jit_compile_us", loads.jit_compile_us, i64),
+        );
+    }
     stats.log();
 }
@@ -0,0 +1,314 @@
+//! Loading of the accounts missing from the banks from a source outside of the
+//! ledger, such as the cluster a test validator clones its state from, as the
+//! transactions processed by the banks first access them.
+//!
+//! Along with the accounts of a transaction, the programs owning them, the
+//! program data of the upgradeable programs among them and the address lookup
+//! tables of the transaction are loaded. The accounts loaded are stored to the
+//! first bank which is not frozen to load them. The frozen banks preceding the
+//! store only serve them to the transactions they simulate.
+//!
+//! The accounts loaded are not part of the blocks of the bank, which other
+//! nodes would fail to replay, so the loading is only meant for nodes which
+//! do not share their blocks with a cluster, such as test validators.
+
+use {
+    super::Bank,
+    log::*,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Slot,
+        pubkey::Pubkey,
+    },
+    solana_svm_transaction::svm_message::SVMMessage,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, RwLock},
+    },
+};
+
+/// How many times in a row the owners and the program data of the accounts
+/// loaded are loaded in turn: the accounts of a transaction, then their owner
+/// programs, then the program data of those
+const MAX_LOAD_ROUNDS: usize = 3;
+
+/// A source of the accounts missing from the banks
+pub trait AccountSource: Send + Sync {
+    /// Returns the accounts of `pubkeys` which exist in the source
+    fn get_accounts(&self, pubkeys: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)>;
+}
+
+struct LoadedAccount {
+    account: AccountSharedData,
+    /// The slot of the bank the account was stored to, if any
+    stored_slot: Option<Slot>,
+}
+
+/// Loads the accounts missing from the banks from an [`AccountSource`],
+/// looking up each account in the source at most once
+pub struct MissingAccountLoader {
+    source: Box<dyn AccountSource>,
+    /// The accounts looked up in the source, with the ones it has
+    looked_up: RwLock<HashMap<Pubkey, Option<LoadedAccount>>>,
+}
+
+impl MissingAccountLoader {
+    pub fn new(source: Box<dyn AccountSource>) -> Self {
+        Self {
+            source,
+            looked_up: RwLock::default(),
+        }
+    }
+
+    /// Returns the accounts of `pubkeys` which were never stored to a bank,
+    /// looking up the ones which were not looked up before in the source
+    fn load(&self, pubkeys: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut looked_up = self.looked_up.write().unwrap();
+        let pubkeys_to_look_up: Vec<_> = pubkeys
+            .iter()
+            .filter(|pubkey| !looked_up.contains_key(pubkey))
+            .copied()
+            .collect();
+        if !pubkeys_to_look_up.is_empty() {
+            let mut accounts: HashMap<_, _> = self
+                .source
+                .get_accounts(&pubkeys_to_look_up)
+                .into_iter()
+                .collect();
+            for pubkey in pubkeys_to_look_up {
+                let loaded_account = accounts.remove(&pubkey).map(|account| LoadedAccount {
+                    account,
+                    stored_slot: None,
+                });
+                looked_up.insert(pubkey, loaded_account);
+            }
+        }
+
+        pubkeys
+            .iter()
+            .filter_map(|pubkey| match looked_up.get(pubkey) {
+                Some(Some(LoadedAccount {
+                    account,
+                    stored_slot: None,
+                })) => Some((*pubkey, account.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn set_stored(&self, pubkeys: impl IntoIterator<Item = Pubkey>, slot: Slot) {
+        let mut looked_up = self.looked_up.write().unwrap();
+        for pubkey in pubkeys {
+            if let Some(Some(loaded_account)) = looked_up.get_mut(&pubkey) {
+                loaded_account.stored_slot.get_or_insert(slot);
+            }
+        }
+    }
+
+    /// Returns the account of `pubkey` loaded before, for a bank of `slot`
+    /// preceding its store
+    fn loaded_account(&self, pubkey: &Pubkey, slot: Slot) -> Option<AccountSharedData> {
+        match self.looked_up.read().unwrap().get(pubkey)? {
+            Some(LoadedAccount {
+                account,
+                stored_slot,
+            }) if stored_slot.is_none_or(|stored_slot| slot < stored_slot) => Some(account.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the address of the program data of `account`, if it is an
+/// upgradeable program
+fn programdata_address(account: &AccountSharedData) -> Option<Pubkey> {
+    if !bpf_loader_upgradeable::check_id(account.owner()) {
+        return None;
+    }
+    match account.deserialize_data() {
+        Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) => Some(programdata_address),
+        _ => None,
+    }
+}
+
+impl Bank {
+    /// Sets the loader of the accounts missing from the bank, which is shared
+    /// with all the banks descending from it
+    pub fn set_missing_account_loader(&self, loader: Arc<MissingAccountLoader>) {
+        *self.missing_account_loader.write().unwrap() = Some(loader);
+    }
+
+    fn missing_account_loader(&self) -> Option<Arc<MissingAccountLoader>> {
+        self.missing_account_loader.read().unwrap().clone()
+    }
+
+    /// Loads the accounts of `transactions` which are missing from the bank
+    pub(super) fn load_missing_transaction_accounts(&self, transactions: &[impl SVMMessage]) {
+        let Some(loader) = self.missing_account_loader() else {
+            return;
+        };
+        let pubkeys: HashSet<_> = transactions
+            .iter()
+            .flat_map(|transaction| transaction.account_keys().iter().copied())
+            .collect();
+        self.load_missing_accounts(&loader, pubkeys);
+    }
+
+    /// Returns the account of `pubkey`, loading it if it is missing from the
+    /// bank
+    pub(super) fn load_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        let loader = self.missing_account_loader()?;
+        self.load_missing_accounts(&loader, HashSet::from([*pubkey]));
+        self.get_account_with_fixed_root(pubkey)
+            .or_else(|| self.get_missing_account(pubkey))
+    }
+
+    /// Returns the account of `pubkey` loaded for the bank if it is frozen,
+    /// as the accounts loaded are not stored to the frozen banks
+    pub(super) fn get_missing_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        if !self.is_frozen() {
+            return None;
+        }
+        self.missing_account_loader()?
+            .loaded_account(pubkey, self.slot())
+    }
+
+    fn load_missing_accounts(&self, loader: &MissingAccountLoader, pubkeys: HashSet<Pubkey>) {
+        let is_missing = |pubkey: &Pubkey| self.get_account_with_fixed_root(pubkey).is_none();
+        let mut pubkeys: Vec<_> = pubkeys.into_iter().filter(is_missing).collect();
+        for _ in 0..MAX_LOAD_ROUNDS {
+            if pubkeys.is_empty() {
+                break;
+            }
+            let accounts = loader.load(&pubkeys);
+            if !self.is_frozen() {
+                for (pubkey, account) in &accounts {
+                    debug!("storing missing account {pubkey} to slot {}", self.slot());
+                    self.store_account_and_update_capitalization(pubkey, account);
+                }
+                loader.set_stored(accounts.iter().map(|(pubkey, _)| *pubkey), self.slot());
+            }
+
+            let loaded_pubkeys: HashSet<_> = accounts.iter().map(|(pubkey, _)| *pubkey).collect();
+            pubkeys = accounts
+                .iter()
+                .flat_map(|(_, account)| {
+                    std::iter::once(*account.owner()).chain(programdata_address(account))
+                })
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .filter(|pubkey| !loaded_pubkeys.contains(pubkey) && is_missing(pubkey))
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        solana_sdk::{account::Account, system_program},
+        std::sync::Mutex,
+    };
+
+    #[derive(Default)]
+    struct TestAccountSource {
+        accounts: HashMap<Pubkey, AccountSharedData>,
+        looked_up: Arc<Mutex<Vec<Pubkey>>>,
+    }
+
+    impl AccountSource for TestAccountSource {
+        fn get_accounts(&self, pubkeys: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)> {
+            self.looked_up.lock().unwrap().extend(pubkeys);
+            pubkeys
+                .iter()
+                .filter_map(|pubkey| Some((*pubkey, self.accounts.get(pubkey)?.clone())))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_load_missing_accounts() {
+        let genesis_config = create_genesis_config(1_000_000).genesis_config;
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+
+        let program_id = Pubkey::new_unique();
+        let programdata_address = Pubkey::new_unique();
+        let owner_id = Pubkey::new_unique();
+        let account_id = Pubkey::new_unique();
+        let frozen_account_id = Pubkey::new_unique();
+        let missing_id = Pubkey::new_unique();
+        let program = AccountSharedData::from(Account {
+            lamports: 1,
+            data: bincode::serialize(&UpgradeableLoaderState::Program {
+                programdata_address,
+            })
+            .unwrap(),
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        });
+        let programdata = AccountSharedData::new(2, 0, &bpf_loader_upgradeable::id());
+        let owner = AccountSharedData::new(3, 0, &program_id);
+        let account = AccountSharedData::new(4, 0, &owner_id);
+        let frozen_account = AccountSharedData::new(5, 0, &system_program::id());
+        let source = TestAccountSource {
+            accounts: HashMap::from([
+                (program_id, program.clone()),
+                (programdata_address, programdata.clone()),
+                (owner_id, owner.clone()),
+                (account_id, account.clone()),
+                (frozen_account_id, frozen_account.clone()),
+            ]),
+            ..TestAccountSource::default()
+        };
+        let looked_up = source.looked_up.clone();
+        bank.set_missing_account_loader(Arc::new(MissingAccountLoader::new(Box::new(source))));
+
+        // The frozen banks serve the accounts they load without storing them.
+        bank.freeze();
+        assert_eq!(
+            bank.load_missing_account(&frozen_account_id),
+            Some(frozen_account.clone())
+        );
+        assert_eq!(bank.get_account(&frozen_account_id), None);
+
+        // The owners of the accounts and the program data of the programs are
+        // loaded along with them.
+        let bank = Bank::new_from_parent(bank, &Pubkey::default(), 1);
+        let capitalization = bank.capitalization();
+        assert_eq!(
+            bank.load_missing_account(&account_id),
+            Some(account.clone())
+        );
+        assert_eq!(bank.get_account(&owner_id), Some(owner));
+        assert_eq!(bank.get_account(&program_id), Some(program));
+        assert_eq!(bank.get_account(&programdata_address), Some(programdata));
+        assert_eq!(
+            bank.load_missing_account(&frozen_account_id),
+            Some(frozen_account)
+        );
+        assert_eq!(bank.load_missing_account(&missing_id), None);
+        assert_eq!(bank.capitalization(), capitalization + 15);
+
+        // The accounts are looked up once, and not loaded again once stored.
+        bank.store_account(&account_id, &AccountSharedData::default());
+        assert_eq!(bank.load_missing_account(&account_id), None);
+        assert_eq!(bank.load_missing_account(&missing_id), None);
+        let mut looked_up = looked_up.lock().unwrap().clone();
+        looked_up.sort_unstable();
+        let mut expected_looked_up = vec![
+            frozen_account_id,
+            account_id,
+            owner_id,
+            program_id,
+            programdata_address,
+            missing_id,
+        ];
+        expected_looked_up.sort_unstable();
+        assert_eq!(looked_up, expected_looked_up);
+    }
+}
@@ -256,6 +256,7 @@ fn new_executed_processing_result(
                 inner_instructions: None,
                 return_data: None,
                 executed_units: 0,
+                instruction_executed_units: None,
                 accounts_data_len_delta: 0,
             },
             programs_modified_by_tx: HashMap::new(),
@@ -4756,6 +4757,71 @@ fn test_get_filtered_indexed_accounts() {
     assert_eq!(indexed_accounts[0], (address, new_account));
 }
 
+#[test]
+fn test_get_filtered_data_size_indexed_accounts() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let mut account_indexes = AccountSecondaryIndexes::default();
+    account_indexes.indexes.insert(AccountIndex::DataSize);
+    let bank_config = BankTestConfig {
+        accounts_db_config: AccountsDbConfig {
+            account_indexes: Some(account_indexes),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        },
+    };
+    let bank = Arc::new(Bank::new_with_config_for_tests(
+        &genesis_config,
+        bank_config,
+    ));
+
+    let program_id = Pubkey::new_unique();
+    let address = Pubkey::new_unique();
+    let account = AccountSharedData::new(1, 42, &program_id);
+    bank.store_account(&address, &account);
+    // Accounts of other sizes or other owners are not scanned
+    bank.store_account(
+        &Pubkey::new_unique(),
+        &AccountSharedData::new(1, 43, &program_id),
+    );
+    bank.store_account(
+        &Pubkey::new_unique(),
+        &AccountSharedData::new(1, 42, &Pubkey::new_unique()),
+    );
+
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::DataSize(program_id, 42),
+            |_| true,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(indexed_accounts, vec![(address, account)]);
+
+    // Like with the program id index, a resized account remains in the index under its previous
+    // data size, so scans still need a post-processing filter.
+    let resized_account = AccountSharedData::new(1, 43, &program_id);
+    let bank = Arc::new(new_from_parent(bank));
+    bank.store_account(&address, &resized_account);
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::DataSize(program_id, 42),
+            |account| account.data().len() == 42,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert!(indexed_accounts.is_empty());
+    let indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::DataSize(program_id, 43),
+            |_| true,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(indexed_accounts.len(), 2);
+}
+
 #[test]
 fn test_status_cache_ancestors() {
     solana_logger::setup();
@@ -7167,6 +7233,64 @@ fn test_bank_load_program() {
     }
 }
 
+#[test]
+fn test_bank_precompile_programs() {
+    let (genesis_config, _mint_keypair) = create_genesis_config_no_tx_fee(1_000_000_000);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    goto_end_of_slot(bank.clone());
+    let bank = new_bank_from_parent_with_bank_forks(&bank_forks, bank, &Pubkey::default(), 42);
+
+    let program_key = solana_pubkey::new_rand();
+    let programdata_key = solana_pubkey::new_rand();
+    let mut file = File::open("../programs/bpf_loader/test_elfs/out/noop_aligned.so").unwrap();
+    let mut elf = Vec::new();
+    file.read_to_end(&mut elf).unwrap();
+    let mut program_account = AccountSharedData::new_data(
+        40,
+        &UpgradeableLoaderState::Program {
+            programdata_address: programdata_key,
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    program_account.set_executable(true);
+    let programdata_data_offset = UpgradeableLoaderState::size_of_programdata_metadata();
+    let mut programdata_account = AccountSharedData::new(
+        40,
+        programdata_data_offset + elf.len(),
+        &bpf_loader_upgradeable::id(),
+    );
+    programdata_account
+        .set_state(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: None,
+        })
+        .unwrap();
+    programdata_account.data_as_mut_slice()[programdata_data_offset..].copy_from_slice(&elf);
+    bank.store_account_and_update_capitalization(&program_key, &program_account);
+    bank.store_account_and_update_capitalization(&programdata_key, &programdata_account);
+
+    // Builtins and missing programs are skipped.
+    let programs = [
+        (program_key, 7),
+        (system_program::id(), 3),
+        (Pubkey::new_unique(), 1),
+    ];
+    assert_eq!(bank.precompile_programs(&programs), 1);
+    {
+        let program_cache = bank.transaction_processor.program_cache.read().unwrap();
+        let [program] = program_cache.get_slot_versions_for_tests(&program_key) else {
+            panic!();
+        };
+        assert_matches!(program.program, ProgramCacheEntryType::Loaded(_));
+        assert_eq!(program.tx_usage_counter.load(Ordering::Relaxed), 7);
+        assert_eq!(program_cache.stats.loads[&program_key].num_loads, 1);
+    }
+    // Compiled programs are not compiled again.
+    assert_eq!(bank.precompile_programs(&programs), 0);
+}
+
 #[allow(deprecated)]
 #[test]
 fn test_bpf_loader_upgradeable_deploy_with_max_len() {
@@ -9651,6 +9775,7 @@ fn test_tx_log_order() {
                 enable_cpi_recording: false,
                 enable_log_recording: true,
                 enable_return_data_recording: false,
+                enable_instruction_units_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -9759,6 +9884,7 @@ fn test_tx_return_data() {
                     enable_cpi_recording: false,
                     enable_log_recording: false,
                     enable_return_data_recording: true,
+                    enable_instruction_units_recording: false,
                 },
                 &mut ExecuteTimings::default(),
                 None,
@@ -13400,10 +13526,63 @@ fn test_failed_simulation_load_error() {
             units_consumed: 0,
             return_data: None,
             inner_instructions: None,
+            instruction_units_consumed: None,
+            fee_details: None,
+            loaded_accounts_data_size: 0,
         }
     );
 }
 
+/// Test that simulated transactions see the account changes of the ones before them
+#[test]
+fn test_simulate_transactions() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let fund_alice = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &mint_keypair,
+        &alice.pubkey(),
+        10_000_000,
+        bank.last_blockhash(),
+    ));
+    let fund_bob = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &alice,
+        &bob,
+        1_000_000,
+        bank.last_blockhash(),
+    ));
+    bank.freeze();
+
+    // Alone, the transfer to bob has no funded fee payer.
+    let simulation = bank.simulate_transaction(&fund_bob, false);
+    assert_eq!(simulation.result, Err(TransactionError::AccountNotFound));
+
+    let simulations = bank.simulate_transactions(&[fund_alice, fund_bob], false);
+    assert_eq!(simulations.len(), 2);
+    assert_eq!(simulations[0].result, Ok(()));
+    assert_eq!(simulations[1].result, Ok(()));
+    // The compute units are broken down by instruction, along with the fees and the size of the
+    // accounts loaded.
+    assert_eq!(
+        simulations[0].instruction_units_consumed,
+        Some(vec![simulations[0].units_consumed])
+    );
+    assert!(simulations[0].fee_details.is_some());
+    assert!(simulations[0].loaded_accounts_data_size > 0);
+    let bob_account = simulations[1]
+        .post_simulation_accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == bob)
+        .map(|(_, account)| account.lamports());
+    assert_eq!(bob_account, Some(1_000_000));
+
+    // Nothing is committed to the bank.
+    assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+    assert_eq!(bank.get_balance(&bob), 0);
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_fee_details() {
     // TX  | PROCESSING RESULT           | COLLECT            | COLLECT
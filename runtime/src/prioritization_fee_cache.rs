@@ -30,6 +30,49 @@ const MAX_UNFINALIZED_SLOTS: u64 = 128;
 
 type UnfinalizedPrioritizationFees = BTreeMap<Slot, HashMap<BankId, PrioritizationFee>>;
 
+type UnfinalizedTransactionFees = BTreeMap<Slot, HashMap<BankId, Vec<TransactionFee>>>;
+
+/// The compute-unit price of a non-vote transaction, and the accounts it write locks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionFee {
+    pub compute_unit_price: u64,
+    pub writable_accounts: Vec<Pubkey>,
+}
+
+/// The fees of the transactions of a block, once its prioritization fee is finalized
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockTransactionFees {
+    pub slot: Slot,
+    pub transaction_fees: Vec<TransactionFee>,
+}
+
+impl BlockTransactionFees {
+    /// Returns the sorted compute-unit prices of the transactions which write lock any of
+    /// `accounts`, or of every transaction if `accounts` is empty
+    pub fn compute_unit_prices(&self, accounts: &[Pubkey]) -> Vec<u64> {
+        let mut compute_unit_prices: Vec<_> = self
+            .transaction_fees
+            .iter()
+            .filter(|transaction_fee| {
+                accounts.is_empty()
+                    || transaction_fee
+                        .writable_accounts
+                        .iter()
+                        .any(|account| accounts.contains(account))
+            })
+            .map(|transaction_fee| transaction_fee.compute_unit_price)
+            .collect();
+        compute_unit_prices.sort_unstable();
+        compute_unit_prices
+    }
+}
+
+/// Receives the transaction fees of every block finalized by `PrioritizationFeeCache`, from the
+/// thread of the cache
+pub trait PrioritizationFeeNotifier: Send + Sync {
+    fn notify_block_transaction_fees(&self, block_transaction_fees: Arc<BlockTransactionFees>);
+}
+
 #[derive(Debug, Default)]
 struct PrioritizationFeeCacheMetrics {
     // Count of transactions that successfully updated each slot's prioritization fee cache.
@@ -123,7 +166,6 @@ impl PrioritizationFeeCacheMetrics {
     }
 }
 
-#[derive(Debug)]
 enum CacheServiceUpdate {
     TransactionUpdate {
         slot: Slot,
@@ -135,6 +177,7 @@ enum CacheServiceUpdate {
         slot: Slot,
         bank_id: BankId,
     },
+    AddNotifier(Arc<dyn PrioritizationFeeNotifier>),
     Exit,
 }
 
@@ -268,6 +311,16 @@ impl PrioritizationFeeCache {
             });
     }
 
+    /// Registers a notifier of the transaction fees of the blocks finalized from now on. The
+    /// per-transaction fees are only collected while there are notifiers.
+    pub fn add_notifier(&self, notifier: Arc<dyn PrioritizationFeeNotifier>) {
+        self.sender
+            .send(CacheServiceUpdate::AddNotifier(notifier))
+            .unwrap_or_else(|err| {
+                warn!("prioritization fee cache adding notifier failed: {:?}", err)
+            });
+    }
+
     /// Internal function is invoked by worker thread to update slot's minimum prioritization fee.
     fn update_cache(
         unfinalized: &mut UnfinalizedPrioritizationFees,
@@ -350,6 +403,32 @@ impl PrioritizationFeeCache {
         }
     }
 
+    /// Notifies the transaction fees recorded for `bank_id` once `slot` is finalized, and drops the
+    /// fees of the other banks of the slot and of the stale slots.
+    fn notify_transaction_fees(
+        unfinalized_transaction_fees: &mut UnfinalizedTransactionFees,
+        notifiers: &[Arc<dyn PrioritizationFeeNotifier>],
+        slot: Slot,
+        bank_id: BankId,
+    ) {
+        *unfinalized_transaction_fees = unfinalized_transaction_fees
+            .split_off(&slot.checked_sub(MAX_UNFINALIZED_SLOTS).unwrap_or_default());
+        let transaction_fees = unfinalized_transaction_fees
+            .remove(&slot)
+            .and_then(|mut slot_transaction_fees| slot_transaction_fees.remove(&bank_id))
+            .unwrap_or_default();
+        if notifiers.is_empty() {
+            return;
+        }
+        let block_transaction_fees = Arc::new(BlockTransactionFees {
+            slot,
+            transaction_fees,
+        });
+        for notifier in notifiers {
+            notifier.notify_block_transaction_fees(block_transaction_fees.clone());
+        }
+    }
+
     fn service_loop(
         cache: Arc<RwLock<BTreeMap<Slot, PrioritizationFee>>>,
         cache_max_size: usize,
@@ -359,6 +438,8 @@ impl PrioritizationFeeCache {
         // Potentially there are more than one bank that updates Prioritization Fee
         // for a slot. The updates are tracked and finalized by bank_id.
         let mut unfinalized = UnfinalizedPrioritizationFees::new();
+        let mut unfinalized_transaction_fees = UnfinalizedTransactionFees::new();
+        let mut notifiers: Vec<Arc<dyn PrioritizationFeeNotifier>> = vec![];
 
         loop {
             let update = match receiver.try_recv() {
@@ -378,14 +459,27 @@ impl PrioritizationFeeCache {
                     bank_id,
                     transaction_fee,
                     writable_accounts,
-                } => Self::update_cache(
-                    &mut unfinalized,
-                    slot,
-                    bank_id,
-                    transaction_fee,
-                    writable_accounts,
-                    &metrics,
-                ),
+                } => {
+                    if !notifiers.is_empty() {
+                        unfinalized_transaction_fees
+                            .entry(slot)
+                            .or_default()
+                            .entry(bank_id)
+                            .or_default()
+                            .push(TransactionFee {
+                                compute_unit_price: transaction_fee,
+                                writable_accounts: writable_accounts.clone(),
+                            });
+                    }
+                    Self::update_cache(
+                        &mut unfinalized,
+                        slot,
+                        bank_id,
+                        transaction_fee,
+                        writable_accounts,
+                        &metrics,
+                    )
+                }
                 CacheServiceUpdate::BankFinalized { slot, bank_id } => {
                     Self::finalize_slot(
                         &mut unfinalized,
@@ -395,8 +489,15 @@ impl PrioritizationFeeCache {
                         bank_id,
                         &metrics,
                     );
+                    Self::notify_transaction_fees(
+                        &mut unfinalized_transaction_fees,
+                        &notifiers,
+                        slot,
+                        bank_id,
+                    );
                     metrics.report(slot);
                 }
+                CacheServiceUpdate::AddNotifier(notifier) => notifiers.push(notifier),
                 CacheServiceUpdate::Exit => {
                     break;
                 }
@@ -941,4 +1042,58 @@ mod tests {
             );
         }
     }
+
+    struct TestNotifier(Sender<Arc<BlockTransactionFees>>);
+
+    impl PrioritizationFeeNotifier for TestNotifier {
+        fn notify_block_transaction_fees(&self, block_transaction_fees: Arc<BlockTransactionFees>) {
+            self.0.send(block_transaction_fees).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_notify_block_transaction_fees() {
+        let prioritization_fee_cache = PrioritizationFeeCache::default();
+        let (sender, receiver) = unbounded();
+        prioritization_fee_cache.add_notifier(Arc::new(TestNotifier(sender)));
+
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank0 = Bank::new_for_benches(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank0);
+        let bank = bank_forks.read().unwrap().working_bank();
+        let collector = solana_pubkey::new_rand();
+        let bank1 = Arc::new(Bank::new_from_parent(bank, &collector, 1));
+
+        let signer = Pubkey::new_unique();
+        let write_account_a = Pubkey::new_unique();
+        let write_account_b = Pubkey::new_unique();
+        let txs = vec![
+            build_sanitized_transaction_for_test(5, &signer, &write_account_a),
+            build_sanitized_transaction_for_test(2, &signer, &write_account_b),
+            build_sanitized_transaction_for_test(3, &signer, &write_account_a),
+        ];
+        sync_update(&prioritization_fee_cache, bank1.clone(), txs.iter());
+        sync_finalize_priority_fee_for_test(&prioritization_fee_cache, 1, bank1.bank_id());
+
+        let block_transaction_fees = receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("notified block transaction fees");
+        assert_eq!(block_transaction_fees.slot, 1);
+        assert_eq!(block_transaction_fees.transaction_fees.len(), 3);
+        assert_eq!(
+            block_transaction_fees.compute_unit_prices(&[write_account_a]),
+            vec![3, 5]
+        );
+        assert_eq!(
+            block_transaction_fees.compute_unit_prices(&[write_account_b, signer]),
+            vec![2, 3, 5]
+        );
+        assert_eq!(
+            block_transaction_fees.compute_unit_prices(&[]),
+            vec![2, 3, 5]
+        );
+        assert!(block_transaction_fees
+            .compute_unit_prices(&[Pubkey::new_unique()])
+            .is_empty());
+    }
 }
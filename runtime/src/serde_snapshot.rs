@@ -1250,6 +1250,8 @@ where
         .unwrap();
 
     handle.join().unwrap();
+    // The replay of the snapshot in pubkey order goes through the index, so waits for it.
+    accounts_db.notify_snapshot_replay();
     measure_notify.stop();
 
     datapoint_info!(
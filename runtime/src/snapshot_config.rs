@@ -4,7 +4,10 @@ use {
         snapshot_utils::{self, ArchiveFormat, SnapshotVersion, ZstdConfig},
     },
     solana_sdk::clock::Slot,
-    std::{num::NonZeroUsize, path::PathBuf},
+    std::{
+        num::{NonZeroU64, NonZeroUsize},
+        path::PathBuf,
+    },
 };
 
 /// Snapshot configuration and runtime information
@@ -43,6 +46,14 @@ pub struct SnapshotConfig {
 
     // Thread niceness adjustment for snapshot packager service
     pub packager_thread_niceness_adj: i8,
+
+    /// Limit on the rate at which the snapshot packager reads the account
+    /// storages into archives, in bytes per second
+    pub packager_io_bytes_per_second: Option<NonZeroU64>,
+
+    /// Re-read and verify each snapshot archive after it is made, and only
+    /// push the snapshot hashes of the verified archives to gossip
+    pub verify_archives: bool,
 }
 
 impl Default for SnapshotConfig {
@@ -65,6 +76,8 @@ impl Default for SnapshotConfig {
             maximum_incremental_snapshot_archives_to_retain:
                 snapshot_utils::DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN,
             packager_thread_niceness_adj: 0,
+            packager_io_bytes_per_second: None,
+            verify_archives: false,
         }
     }
 }
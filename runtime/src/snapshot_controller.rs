@@ -6,6 +6,7 @@ use {
         bank::{epoch_accounts_hash_utils, Bank, SquashTiming},
         bank_forks::SetRootError,
         snapshot_config::SnapshotConfig,
+        snapshot_utils::{SnapshotArchiveVerificationStatus, SnapshotPackagingProgress},
     },
     log::*,
     solana_measure::measure::Measure,
@@ -28,6 +29,8 @@ pub struct SnapshotController {
     abs_request_sender: SnapshotRequestSender,
     snapshot_config: SnapshotConfig,
     latest_abs_request_slot: AtomicU64,
+    packaging_progress: Arc<SnapshotPackagingProgress>,
+    archive_verification_status: Arc<SnapshotArchiveVerificationStatus>,
 }
 
 impl SnapshotController {
@@ -40,6 +43,8 @@ impl SnapshotController {
             abs_request_sender,
             snapshot_config,
             latest_abs_request_slot: AtomicU64::new(root_slot),
+            packaging_progress: Arc::default(),
+            archive_verification_status: Arc::default(),
         }
     }
 
@@ -51,6 +56,18 @@ impl SnapshotController {
         &self.abs_request_sender
     }
 
+    /// Progress of the snapshot package being archived by the snapshot
+    /// packager service
+    pub fn packaging_progress(&self) -> &Arc<SnapshotPackagingProgress> {
+        &self.packaging_progress
+    }
+
+    /// Verifications of the snapshot archives made by the snapshot packager
+    /// service
+    pub fn archive_verification_status(&self) -> &Arc<SnapshotArchiveVerificationStatus> {
+        &self.archive_verification_status
+    }
+
     fn latest_abs_request_slot(&self) -> Slot {
         self.latest_abs_request_slot.load(Ordering::Relaxed)
     }
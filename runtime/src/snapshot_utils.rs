@@ -44,7 +44,7 @@ use {
         fmt, fs,
         io::{BufReader, BufWriter, Error as IoError, Read, Result as IoResult, Seek, Write},
         mem,
-        num::NonZeroUsize,
+        num::{NonZeroU64, NonZeroUsize},
         ops::RangeInclusive,
         path::{Path, PathBuf},
         process::ExitStatus,
@@ -63,8 +63,13 @@ use {
 };
 
 mod archive_format;
+mod archive_inspection;
+mod archive_verification;
+mod packaging_progress;
 pub mod snapshot_storage_rebuilder;
-pub use archive_format::*;
+pub use {
+    archive_format::*, archive_inspection::*, archive_verification::*, packaging_progress::*,
+};
 
 pub const SNAPSHOT_STATUS_CACHE_FILENAME: &str = "status_cache";
 pub const SNAPSHOT_VERSION_FILENAME: &str = "version";
@@ -352,6 +357,9 @@ pub enum SnapshotError {
     #[error("snapshot epoch stakes are invalid: {0}")]
     VerifyEpochStakes(#[from] VerifyEpochStakesError),
 
+    #[error("snapshot archive is invalid: {0}")]
+    VerifyArchive(#[from] VerifySnapshotArchiveError),
+
     #[error("bank_snapshot_info new_from_dir failed: {0}")]
     NewFromDir(#[from] SnapshotNewFromDirError),
 
@@ -756,10 +764,28 @@ pub fn remove_tmp_snapshot_archives(snapshot_archives_dir: impl AsRef<Path>) {
     }
 }
 
-/// Serializes and archives a snapshot package
+/// Serializes and archives a snapshot package, reporting the progress to
+/// `progress` if given
 pub fn serialize_and_archive_snapshot_package(
     snapshot_package: SnapshotPackage,
     snapshot_config: &SnapshotConfig,
+    progress: Option<&SnapshotPackagingProgress>,
+) -> Result<SnapshotArchiveInfo> {
+    if let Some(progress) = progress {
+        progress.start(snapshot_package.slot, snapshot_package.snapshot_kind);
+    }
+    let result =
+        do_serialize_and_archive_snapshot_package(snapshot_package, snapshot_config, progress);
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+    result
+}
+
+fn do_serialize_and_archive_snapshot_package(
+    snapshot_package: SnapshotPackage,
+    snapshot_config: &SnapshotConfig,
+    progress: Option<&SnapshotPackagingProgress>,
 ) -> Result<SnapshotArchiveInfo> {
     let SnapshotPackage {
         snapshot_kind,
@@ -833,6 +859,8 @@ pub fn serialize_and_archive_snapshot_package(
         &bank_snapshot_info.snapshot_dir,
         snapshot_archive_path,
         snapshot_config.archive_format,
+        snapshot_config.packager_io_bytes_per_second,
+        progress,
     )?;
 
     Ok(snapshot_archive_info)
@@ -980,7 +1008,9 @@ fn serialize_snapshot(
     do_serialize_snapshot().map_err(|err| SnapshotError::AddBankSnapshot(err, slot))
 }
 
-/// Archives a snapshot into `archive_path`
+/// Archives a snapshot into `archive_path`, reading the account storages at
+/// most at `io_bytes_per_second` if given
+#[allow(clippy::too_many_arguments)]
 fn archive_snapshot(
     snapshot_kind: SnapshotKind,
     snapshot_slot: Slot,
@@ -989,6 +1019,8 @@ fn archive_snapshot(
     bank_snapshot_dir: impl AsRef<Path>,
     archive_path: impl AsRef<Path>,
     archive_format: ArchiveFormat,
+    io_bytes_per_second: Option<NonZeroU64>,
+    progress: Option<&SnapshotPackagingProgress>,
 ) -> Result<SnapshotArchiveInfo> {
     use ArchiveSnapshotPackageError as E;
     const SNAPSHOTS_DIR: &str = "snapshots";
@@ -1048,12 +1080,23 @@ fn archive_snapshot(
         archive_format.extension(),
     ));
 
+    if let Some(progress) = progress {
+        progress.start_archiving(
+            snapshot_storages
+                .iter()
+                .map(|storage| storage.capacity())
+                .sum(),
+        );
+    }
     {
         let mut archive_file = fs::File::create(&staging_archive_path)
             .map_err(|err| E::CreateArchiveFile(err, staging_archive_path.clone()))?;
 
         let do_archive_files = |encoder: &mut dyn Write| -> std::result::Result<(), E> {
-            let mut archive = tar::Builder::new(encoder);
+            // Throttles the bytes before compression, which are the bytes
+            // read from the account storages.
+            let mut archive =
+                tar::Builder::new(ThrottledWriter::new(encoder, io_bytes_per_second, progress));
             // Disable sparse file handling.  This seems to be the root cause of an issue when
             // upgrading v2.0 to v2.1, and the tar crate from 0.4.41 to 0.4.42.
             // Since the tarball will still go through compression (zstd/etc) afterwards, disabling
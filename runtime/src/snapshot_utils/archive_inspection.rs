@@ -0,0 +1,428 @@
+//! Inspection of the contents of a snapshot archive, which streams through the
+//! archive instead of unpacking it, to debug snapshot bloat and mismatches.
+
+use {
+    super::{
+        snapshot_storage_rebuilder::get_slot_and_append_vec_id,
+        untar_snapshot_create_shared_buffer, Result, SnapshotError, SNAPSHOT_VERSION_FILENAME,
+    },
+    crate::{
+        bank::BankFieldsToDeserialize,
+        serde_snapshot::{self, snapshot_storage_lengths_from_fields},
+        shared_buffer_reader::SharedBufferReader,
+        snapshot_archive_info::{
+            FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo, SnapshotArchiveInfoGetter,
+        },
+        snapshot_hash::SnapshotHash,
+    },
+    solana_accounts_db::accounts_file::{AccountsFile, StorageAccess},
+    solana_sdk::{clock::Slot, epoch_schedule::Epoch, hash::Hash, pubkey::Pubkey},
+    std::{
+        cmp::Reverse,
+        collections::{BinaryHeap, HashMap},
+        fs::{self, File},
+        io::{self, BufReader, Error as IoError, Read},
+        path::{Component, Path, PathBuf},
+    },
+    tar::Archive,
+};
+
+/// Fields of the bank of the snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectedBankFields {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub epoch: Epoch,
+    pub block_height: u64,
+    pub hash: Hash,
+    pub parent_hash: Hash,
+    pub capitalization: u64,
+    pub accounts_data_len: u64,
+    pub transaction_count: u64,
+    pub signature_count: u64,
+    pub collector_id: Pubkey,
+    pub lamports_per_signature: u64,
+    pub epoch_accounts_hash: Option<Hash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectedAccount {
+    pub pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data_len: u64,
+    /// Slot of the storage holding the account
+    pub slot: Slot,
+}
+
+/// Accounts of an owner, and their share of the capitalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerCapitalization {
+    pub owner: Pubkey,
+    pub num_accounts: u64,
+    pub lamports: u64,
+    pub data_len: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotArchiveInspection {
+    pub snapshot_version: String,
+    /// Slot of the full snapshot of an incremental snapshot archive
+    pub base_slot: Option<Slot>,
+    pub bank: InspectedBankFields,
+    pub num_storages: u64,
+    /// Length of the accounts in the storages
+    pub storages_len: u64,
+    /// Accounts in the storages, including the ones which are overwritten
+    /// by an account of a newer storage
+    pub num_stored_accounts: u64,
+    /// Accounts with lamports, as of the newest storage holding them
+    pub num_accounts: u64,
+    pub accounts_lamports: u64,
+    pub accounts_data_len: u64,
+    /// Accounts with the largest data, largest first
+    pub largest_accounts: Vec<InspectedAccount>,
+    /// Owners with the largest share of the capitalization, largest first
+    pub largest_owners: Vec<OwnerCapitalization>,
+}
+
+#[derive(Clone, Copy)]
+struct LatestAccount {
+    slot: Slot,
+    owner_index: u32,
+    lamports: u64,
+    data_len: u64,
+}
+
+/// Snapshot archive streamed through by `stream_snapshot_archive()`
+pub(crate) struct StreamedSnapshotArchive {
+    pub(crate) snapshot_version: String,
+    /// Slot in the file name of the archive
+    pub(crate) slot: Slot,
+    /// Hash in the file name of the archive
+    pub(crate) hash: SnapshotHash,
+    /// Slot of the full snapshot of an incremental snapshot archive
+    pub(crate) base_slot: Option<Slot>,
+    pub(crate) bank_fields: BankFieldsToDeserialize,
+    pub(crate) num_storages: u64,
+    /// Length of the accounts in the storages
+    pub(crate) storages_len: u64,
+    /// Storages referenced by the bank fields, but missing from the archive
+    pub(crate) num_missing_storages: u64,
+}
+
+/// Streams through the snapshot archive at `archive_path`, calling
+/// `storage_callback` with the slot of each account storage and the storage.
+///
+/// The storages are unpacked into `scratch_dir` one at a time, as accounts
+/// can only be read from files.
+pub(crate) fn stream_snapshot_archive(
+    archive_path: &Path,
+    scratch_dir: &Path,
+    mut storage_callback: impl FnMut(Slot, &AccountsFile) -> Result<()>,
+) -> Result<StreamedSnapshotArchive> {
+    let (archive_format, slot, hash, base_slot) =
+        match FullSnapshotArchiveInfo::new_from_path(archive_path.to_path_buf()) {
+            Ok(info) => (info.archive_format(), info.slot(), *info.hash(), None),
+            Err(_) => {
+                let info =
+                    IncrementalSnapshotArchiveInfo::new_from_path(archive_path.to_path_buf())?;
+                (
+                    info.archive_format(),
+                    info.slot(),
+                    *info.hash(),
+                    Some(info.base_slot()),
+                )
+            }
+        };
+    // Fails early, as reading an archive which can't be opened panics.
+    File::open(archive_path)?;
+    let shared_buffer = untar_snapshot_create_shared_buffer(archive_path, archive_format);
+    let mut archive = Archive::new(SharedBufferReader::new(&shared_buffer));
+
+    let mut snapshot_version = None;
+    let mut bank_fields = None;
+    let mut storage_lengths = None;
+    let mut num_storages = 0;
+    let mut storages_len = 0;
+    let storage_path = scratch_dir.join("storage");
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let components: Vec<_> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        match components.as_slice() {
+            [SNAPSHOT_VERSION_FILENAME] => {
+                let mut version = String::new();
+                entry.read_to_string(&mut version)?;
+                snapshot_version = Some(version.trim().to_string());
+            }
+            // The bank fields of the snapshot are in snapshots/<slot>/<slot>.
+            ["snapshots", slot_dir, file_name] if slot_dir == file_name => {
+                let (fields, accounts_db_fields) =
+                    serde_snapshot::fields_from_stream(&mut BufReader::new(&mut entry))?;
+                storage_lengths = Some(snapshot_storage_lengths_from_fields(&accounts_db_fields));
+                bank_fields = Some(fields);
+            }
+            ["accounts", file_name] => {
+                let (slot, id) = get_slot_and_append_vec_id(file_name)?;
+                // The bank fields are archived before the storages. Each
+                // length is removed once used, so that duplicated storages
+                // are rejected and the missing ones are left.
+                let current_len = storage_lengths
+                    .as_mut()
+                    .ok_or_else(|| {
+                        IoError::other("snapshot archive has storages before its bank fields")
+                    })?
+                    .get_mut(&slot)
+                    .and_then(|lengths| lengths.remove(&id))
+                    .ok_or_else(|| SnapshotError::InvalidAppendVecPath(path.clone()))?;
+                io::copy(&mut entry, &mut File::create(&storage_path)?)?;
+                let (storage, _num_accounts) =
+                    AccountsFile::new_from_file(&storage_path, current_len, StorageAccess::File)?;
+                num_storages += 1;
+                storages_len += current_len as u64;
+                storage_callback(slot, &storage)?;
+                drop(storage);
+                fs::remove_file(&storage_path)?;
+            }
+            _ => (),
+        }
+    }
+    let bank_fields = bank_fields.ok_or_else(|| {
+        SnapshotError::NoSnapshotSlotDir(PathBuf::from(archive_path).join("snapshots"))
+    })?;
+    let num_missing_storages = storage_lengths
+        .unwrap_or_default()
+        .values()
+        .map(|lengths| lengths.len() as u64)
+        .sum();
+
+    Ok(StreamedSnapshotArchive {
+        snapshot_version: snapshot_version.unwrap_or_default(),
+        slot,
+        hash,
+        base_slot,
+        bank_fields,
+        num_storages,
+        storages_len,
+        num_missing_storages,
+    })
+}
+
+/// Inspects the snapshot archive at `archive_path`, keeping the
+/// `num_largest_accounts` accounts with the largest data and the
+/// `num_largest_owners` owners with the largest share of the capitalization.
+///
+/// The storages are unpacked into `scratch_dir` one at a time, as accounts
+/// can only be read from files.
+pub fn inspect_snapshot_archive(
+    archive_path: &Path,
+    scratch_dir: &Path,
+    num_largest_accounts: usize,
+    num_largest_owners: usize,
+) -> Result<SnapshotArchiveInspection> {
+    let mut num_stored_accounts = 0;
+    let mut owners = HashMap::<Pubkey, u32>::new();
+    let mut latest_accounts = HashMap::<Pubkey, LatestAccount>::new();
+    let streamed = stream_snapshot_archive(archive_path, scratch_dir, |slot, storage| {
+        storage.scan_accounts_without_data(|account| {
+            num_stored_accounts += 1;
+            let num_owners = owners.len() as u32;
+            let owner_index = *owners.entry(*account.owner).or_insert(num_owners);
+            let latest_account = LatestAccount {
+                slot,
+                owner_index,
+                lamports: account.lamports,
+                data_len: account.data_len as u64,
+            };
+            latest_accounts
+                .entry(*account.pubkey)
+                .and_modify(|latest| {
+                    if latest.slot <= slot {
+                        *latest = latest_account;
+                    }
+                })
+                .or_insert(latest_account);
+        });
+        Ok(())
+    })?;
+    let bank_fields = streamed.bank_fields;
+    let bank = InspectedBankFields {
+        slot: bank_fields.slot,
+        parent_slot: bank_fields.parent_slot,
+        epoch: bank_fields.epoch,
+        block_height: bank_fields.block_height,
+        hash: bank_fields.hash,
+        parent_hash: bank_fields.parent_hash,
+        capitalization: bank_fields.capitalization,
+        accounts_data_len: bank_fields.accounts_data_len,
+        transaction_count: bank_fields.transaction_count,
+        signature_count: bank_fields.signature_count,
+        collector_id: bank_fields.collector_id,
+        lamports_per_signature: bank_fields.fee_rate_governor.lamports_per_signature,
+        epoch_accounts_hash: bank_fields.epoch_accounts_hash,
+    };
+
+    let mut owner_pubkeys = vec![Pubkey::default(); owners.len()];
+    for (owner, index) in owners {
+        owner_pubkeys[index as usize] = owner;
+    }
+    let mut num_accounts = 0;
+    let mut accounts_lamports = 0;
+    let mut accounts_data_len = 0;
+    let mut owners_capitalization = vec![(0, 0, 0); owner_pubkeys.len()];
+    // Min-heap of the accounts with the largest data
+    let mut largest_accounts = BinaryHeap::with_capacity(num_largest_accounts + 1);
+    for (pubkey, account) in latest_accounts {
+        // Accounts without lamports have been deleted.
+        if account.lamports == 0 {
+            continue;
+        }
+        num_accounts += 1;
+        accounts_lamports += account.lamports;
+        accounts_data_len += account.data_len;
+        let owner = &mut owners_capitalization[account.owner_index as usize];
+        owner.0 += 1;
+        owner.1 += account.lamports;
+        owner.2 += account.data_len;
+        largest_accounts.push(Reverse((
+            account.data_len,
+            pubkey,
+            account.slot,
+            account.lamports,
+            account.owner_index,
+        )));
+        if largest_accounts.len() > num_largest_accounts {
+            largest_accounts.pop();
+        }
+    }
+    let largest_accounts = largest_accounts
+        .into_sorted_vec()
+        .into_iter()
+        .map(
+            |Reverse((data_len, pubkey, slot, lamports, owner_index))| InspectedAccount {
+                pubkey,
+                owner: owner_pubkeys[owner_index as usize],
+                lamports,
+                data_len,
+                slot,
+            },
+        )
+        .collect();
+    let mut largest_owners: Vec<_> = owner_pubkeys
+        .into_iter()
+        .zip(owners_capitalization)
+        .filter(|(_, (num_accounts, _, _))| *num_accounts > 0)
+        .map(
+            |(owner, (num_accounts, lamports, data_len))| OwnerCapitalization {
+                owner,
+                num_accounts,
+                lamports,
+                data_len,
+            },
+        )
+        .collect();
+    largest_owners.sort_unstable_by(|a, b| b.lamports.cmp(&a.lamports));
+    largest_owners.truncate(num_largest_owners);
+
+    Ok(SnapshotArchiveInspection {
+        snapshot_version: streamed.snapshot_version,
+        base_slot: streamed.base_slot,
+        bank,
+        num_storages: streamed.num_storages,
+        storages_len: streamed.storages_len,
+        num_stored_accounts,
+        num_accounts,
+        accounts_lamports,
+        accounts_data_len,
+        largest_accounts,
+        largest_owners,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            bank::Bank, snapshot_bank_utils::bank_to_full_snapshot_archive,
+            snapshot_utils::ArchiveFormat,
+        },
+        solana_sdk::{account::AccountSharedData, genesis_config::create_genesis_config},
+        tempfile::TempDir,
+    };
+
+    #[test]
+    fn test_inspect_snapshot_archive() {
+        let (mut genesis_config, _mint_keypair) = create_genesis_config(1_000_000_000);
+        let owner = Pubkey::new_unique();
+        let large_account = Pubkey::new_unique();
+        genesis_config.add_account(
+            large_account,
+            AccountSharedData::new(1_000_000, 10_000, &owner),
+        );
+        genesis_config.add_account(
+            Pubkey::new_unique(),
+            AccountSharedData::new(2_000_000, 100, &owner),
+        );
+        let bank = Bank::new_for_tests(&genesis_config);
+        while !bank.is_complete() {
+            bank.register_unique_tick();
+        }
+
+        let bank_snapshots_dir = TempDir::new().unwrap();
+        let full_snapshot_archives_dir = TempDir::new().unwrap();
+        let incremental_snapshot_archives_dir = TempDir::new().unwrap();
+        let snapshot_archive_info = bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &bank,
+            None,
+            full_snapshot_archives_dir.path(),
+            incremental_snapshot_archives_dir.path(),
+            ArchiveFormat::Tar,
+        )
+        .unwrap();
+
+        let scratch_dir = TempDir::new().unwrap();
+        let inspection =
+            inspect_snapshot_archive(snapshot_archive_info.path(), scratch_dir.path(), 1, 100)
+                .unwrap();
+        assert_eq!(inspection.base_slot, None);
+        assert_eq!(inspection.bank.slot, bank.slot());
+        assert_eq!(inspection.bank.hash, bank.hash());
+        assert_eq!(inspection.bank.capitalization, bank.capitalization());
+        assert_eq!(inspection.accounts_lamports, bank.capitalization());
+        assert!(inspection.num_storages > 0);
+        assert!(inspection.num_stored_accounts >= inspection.num_accounts);
+        assert_eq!(
+            inspection.largest_accounts,
+            vec![InspectedAccount {
+                pubkey: large_account,
+                owner,
+                lamports: 1_000_000,
+                data_len: 10_000,
+                slot: bank.slot(),
+            }]
+        );
+        assert_eq!(
+            inspection
+                .largest_owners
+                .iter()
+                .find(|owner_capitalization| owner_capitalization.owner == owner),
+            Some(&OwnerCapitalization {
+                owner,
+                num_accounts: 2,
+                lamports: 3_000_000,
+                data_len: 10_100,
+            })
+        );
+        // The storage has been removed from the scratch dir.
+        assert_eq!(fs::read_dir(scratch_dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,331 @@
+//! Verification of the contents of a snapshot archive, which streams through
+//! the archive like its inspection, to detect corrupt archives before they
+//! are served to other nodes.
+
+use {
+    super::{archive_inspection::stream_snapshot_archive, Result},
+    crate::{snapshot_hash::SnapshotHash, snapshot_package::SnapshotKind},
+    solana_accounts_db::{
+        accounts_db::AccountsDb,
+        accounts_hash::{AccountsLtHash, MerkleOrLatticeAccountsHash},
+        epoch_accounts_hash::EpochAccountsHash,
+    },
+    solana_lattice_hash::lt_hash::{Checksum, LtHash},
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::{
+        collections::HashMap,
+        path::Path,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            RwLock,
+        },
+        time::{Duration, SystemTime},
+    },
+    thiserror::Error,
+};
+
+/// Errors that can happen in `verify_snapshot_archive()`
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum VerifySnapshotArchiveError {
+    #[error("slot mismatch: bank fields: {0}, archive file name: {1}")]
+    MismatchedSlot(Slot, Slot),
+
+    #[error("{0} storages of the bank fields are missing from the archive")]
+    MissingStorages(u64),
+
+    #[error("capitalization mismatch: bank fields: {0}, accounts: {1}")]
+    MismatchedCapitalization(u64, u64),
+
+    #[error("accounts lt hash mismatch: bank fields: {0}, accounts: {1}")]
+    MismatchedAccountsLtHash(Checksum, Checksum),
+}
+
+/// What was verified of a snapshot archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotArchiveVerification {
+    pub num_storages: u64,
+    /// Accounts in the storages, including the ones which are overwritten
+    /// by an account of a newer storage
+    pub num_stored_accounts: u64,
+    /// If the accounts lt hash of the bank fields was recomputed from the
+    /// accounts, which is only possible for full snapshots
+    pub accounts_lt_hash_verified: bool,
+    /// If the snapshot hash in the archive file name was derived from the
+    /// accounts lt hash of the bank fields.  Snapshot hashes made from the
+    /// merkle accounts hash can't be verified without recomputing it.
+    pub snapshot_hash_verified: bool,
+}
+
+/// Location of the newest version of an account: the slot of its storage,
+/// the position of its storage in the archive, and its index in the storage
+type NewestAccount = (Slot, u32, u32);
+
+/// Verifies the snapshot archive at `archive_path`:
+/// - its bank fields are of the slot in its file name
+/// - it holds all the storages of its bank fields, and only those
+/// - the accounts of a full snapshot hold its capitalization, and hash to its
+///   accounts lt hash
+///
+/// It also checks if the hash in its file name is derived from its accounts lt
+/// hash, which is not an error otherwise.
+///
+/// The storages are unpacked into `scratch_dir` one at a time.  A full
+/// snapshot with an accounts lt hash is streamed through twice: once to find
+/// the newest version of each account, and once to hash those.
+pub fn verify_snapshot_archive(
+    archive_path: &Path,
+    scratch_dir: &Path,
+) -> Result<SnapshotArchiveVerification> {
+    let mut num_stored_accounts = 0;
+    let mut newest_accounts = HashMap::<Pubkey, NewestAccount>::new();
+    let mut num_streamed_storages = 0u32;
+    let streamed = stream_snapshot_archive(archive_path, scratch_dir, |slot, storage| {
+        let storage_position = num_streamed_storages;
+        num_streamed_storages += 1;
+        let mut index = 0u32;
+        storage.scan_accounts_without_data(|account| {
+            num_stored_accounts += 1;
+            let account_position = (slot, storage_position, index);
+            index += 1;
+            newest_accounts
+                .entry(*account.pubkey)
+                .and_modify(|newest| {
+                    if newest.0 <= slot {
+                        *newest = account_position;
+                    }
+                })
+                .or_insert(account_position);
+        });
+        Ok(())
+    })?;
+    let bank_fields = &streamed.bank_fields;
+
+    if bank_fields.slot != streamed.slot {
+        return Err(
+            VerifySnapshotArchiveError::MismatchedSlot(bank_fields.slot, streamed.slot).into(),
+        );
+    }
+    if streamed.num_missing_storages > 0 {
+        return Err(
+            VerifySnapshotArchiveError::MissingStorages(streamed.num_missing_storages).into(),
+        );
+    }
+
+    // Only the accounts of a full snapshot make up the whole bank.
+    let mut accounts_lt_hash_verified = false;
+    if streamed.base_slot.is_none() {
+        let mut accounts_lamports = 0u64;
+        let mut accounts_lt_hash = AccountsLtHash(LtHash::identity());
+        let mut num_streamed_storages = 0u32;
+        stream_snapshot_archive(archive_path, scratch_dir, |slot, storage| {
+            let storage_position = num_streamed_storages;
+            num_streamed_storages += 1;
+            let mut index = 0u32;
+            storage.scan_accounts(|account| {
+                let account_position = (slot, storage_position, index);
+                index += 1;
+                if newest_accounts.get(account.pubkey) != Some(&account_position) {
+                    return;
+                }
+                accounts_lamports = accounts_lamports.saturating_add(account.lamports);
+                if bank_fields.accounts_lt_hash.is_some() {
+                    let account_lt_hash = AccountsDb::lt_hash_account(&account, account.pubkey);
+                    accounts_lt_hash.0.mix_in(&account_lt_hash.0);
+                }
+            });
+            Ok(())
+        })?;
+
+        if accounts_lamports != bank_fields.capitalization {
+            return Err(VerifySnapshotArchiveError::MismatchedCapitalization(
+                bank_fields.capitalization,
+                accounts_lamports,
+            )
+            .into());
+        }
+        if let Some(expected_accounts_lt_hash) = &bank_fields.accounts_lt_hash {
+            if accounts_lt_hash != *expected_accounts_lt_hash {
+                return Err(VerifySnapshotArchiveError::MismatchedAccountsLtHash(
+                    expected_accounts_lt_hash.0.checksum(),
+                    accounts_lt_hash.0.checksum(),
+                )
+                .into());
+            }
+            accounts_lt_hash_verified = true;
+        }
+    }
+
+    let mut snapshot_hash_verified = false;
+    if let Some(accounts_lt_hash) = &bank_fields.accounts_lt_hash {
+        let snapshot_hash = SnapshotHash::new(
+            &MerkleOrLatticeAccountsHash::Lattice,
+            bank_fields
+                .epoch_accounts_hash
+                .map(EpochAccountsHash::new)
+                .as_ref(),
+            Some(accounts_lt_hash.0.checksum()),
+        );
+        // A snapshot of a bank with an accounts lt hash may still have been
+        // hashed with the merkle accounts hash, so a mismatch is not an error.
+        snapshot_hash_verified = snapshot_hash == streamed.hash;
+    }
+
+    Ok(SnapshotArchiveVerification {
+        num_storages: streamed.num_storages,
+        num_stored_accounts,
+        accounts_lt_hash_verified,
+        snapshot_hash_verified,
+    })
+}
+
+/// Verification of a snapshot archive made by the snapshot packager service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotArchiveVerificationRecord {
+    pub slot: Slot,
+    pub kind: SnapshotKind,
+    /// When the verification finished
+    pub verified_at: SystemTime,
+    pub duration: Duration,
+    /// What was verified, or why the verification failed
+    pub result: std::result::Result<SnapshotArchiveVerification, String>,
+}
+
+/// Tracks the verifications of the snapshot archives made by the snapshot
+/// packager service
+#[derive(Debug, Default)]
+pub struct SnapshotArchiveVerificationStatus {
+    latest: RwLock<Option<SnapshotArchiveVerificationRecord>>,
+    latest_failure: RwLock<Option<SnapshotArchiveVerificationRecord>>,
+    num_verified: AtomicU64,
+    num_failed: AtomicU64,
+}
+
+impl SnapshotArchiveVerificationStatus {
+    /// Returns the latest verification, if any.
+    pub fn latest(&self) -> Option<SnapshotArchiveVerificationRecord> {
+        self.latest.read().unwrap().clone()
+    }
+
+    /// Returns the latest failed verification, if any.
+    pub fn latest_failure(&self) -> Option<SnapshotArchiveVerificationRecord> {
+        self.latest_failure.read().unwrap().clone()
+    }
+
+    pub fn num_verified(&self) -> u64 {
+        self.num_verified.load(Ordering::Relaxed)
+    }
+
+    pub fn num_failed(&self) -> u64 {
+        self.num_failed.load(Ordering::Relaxed)
+    }
+
+    pub fn record(&self, record: SnapshotArchiveVerificationRecord) {
+        if record.result.is_ok() {
+            self.num_verified.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.num_failed.fetch_add(1, Ordering::Relaxed);
+            *self.latest_failure.write().unwrap() = Some(record.clone());
+        }
+        *self.latest.write().unwrap() = Some(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            bank::Bank,
+            genesis_utils::create_genesis_config,
+            snapshot_archive_info::SnapshotArchiveInfoGetter,
+            snapshot_bank_utils::bank_to_full_snapshot_archive,
+            snapshot_utils::{build_full_snapshot_archive_path, ArchiveFormat, SnapshotError},
+        },
+        solana_sdk::native_token::LAMPORTS_PER_SOL,
+        std::{fs, sync::Arc},
+        tempfile::TempDir,
+    };
+
+    #[test]
+    fn test_verify_snapshot_archive() {
+        let genesis_config_info = create_genesis_config(1_000 * LAMPORTS_PER_SOL);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config_info.genesis_config));
+        assert!(bank0.is_snapshots_lt_hash_enabled());
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        bank1
+            .transfer(
+                LAMPORTS_PER_SOL,
+                &genesis_config_info.mint_keypair,
+                &Pubkey::new_unique(),
+            )
+            .unwrap();
+        bank1.fill_bank_with_ticks_for_tests();
+        bank1.squash();
+        bank1.force_flush_accounts_cache();
+
+        let bank_snapshots_dir = TempDir::new().unwrap();
+        let archives_dir = TempDir::new().unwrap();
+        let scratch_dir = TempDir::new().unwrap();
+        let archive_info = bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &bank1,
+            None,
+            &archives_dir,
+            &archives_dir,
+            ArchiveFormat::Tar,
+        )
+        .unwrap();
+
+        let verification =
+            verify_snapshot_archive(archive_info.path(), scratch_dir.path()).unwrap();
+        assert!(verification.num_storages > 0);
+        assert!(verification.accounts_lt_hash_verified);
+        assert!(verification.snapshot_hash_verified);
+
+        // An archive named after another slot is rejected.
+        let renamed_path = build_full_snapshot_archive_path(
+            archives_dir.path(),
+            bank1.slot() + 1,
+            archive_info.hash(),
+            ArchiveFormat::Tar,
+        );
+        fs::rename(archive_info.path(), &renamed_path).unwrap();
+        assert!(matches!(
+            verify_snapshot_archive(&renamed_path, scratch_dir.path()),
+            Err(SnapshotError::VerifyArchive(
+                VerifySnapshotArchiveError::MismatchedSlot(1, 2)
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_archive_verification_status() {
+        let status = SnapshotArchiveVerificationStatus::default();
+        assert!(status.latest().is_none());
+
+        let record = |slot, result| SnapshotArchiveVerificationRecord {
+            slot,
+            kind: SnapshotKind::FullSnapshot,
+            verified_at: SystemTime::now(),
+            duration: Duration::ZERO,
+            result,
+        };
+        status.record(record(
+            1,
+            Err(VerifySnapshotArchiveError::MissingStorages(1).to_string()),
+        ));
+        status.record(record(
+            2,
+            Ok(SnapshotArchiveVerification {
+                num_storages: 1,
+                num_stored_accounts: 1,
+                accounts_lt_hash_verified: true,
+                snapshot_hash_verified: true,
+            }),
+        ));
+        assert_eq!(status.num_verified(), 1);
+        assert_eq!(status.num_failed(), 1);
+        assert_eq!(status.latest().unwrap().slot, 2);
+        assert_eq!(status.latest_failure().unwrap().slot, 1);
+    }
+}
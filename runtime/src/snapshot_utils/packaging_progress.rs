@@ -0,0 +1,178 @@
+//! Progress of the snapshot package being archived, and the IO throttling of
+//! the archiving.
+
+use {
+    crate::snapshot_package::SnapshotKind,
+    solana_sdk::clock::Slot,
+    std::{
+        io::{self, Write},
+        num::NonZeroU64,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            RwLock,
+        },
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SnapshotPackagingStage {
+    /// Serializing the bank and hard-linking the account storages
+    Serializing,
+    /// Writing the account storages into the archive
+    Archiving,
+}
+
+/// The snapshot package being handled
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotPackagingJob {
+    pub slot: Slot,
+    pub kind: SnapshotKind,
+    pub stage: SnapshotPackagingStage,
+    /// Size of the account storages to archive
+    pub total_bytes: u64,
+    /// Bytes written into the archive, before compression
+    pub archived_bytes: u64,
+    pub elapsed: Duration,
+}
+
+/// Tracks the progress of the snapshot package being handled, if any
+#[derive(Debug, Default)]
+pub struct SnapshotPackagingProgress {
+    job: RwLock<Option<JobState>>,
+    archived_bytes: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct JobState {
+    slot: Slot,
+    kind: SnapshotKind,
+    stage: SnapshotPackagingStage,
+    total_bytes: u64,
+    start: Instant,
+}
+
+impl SnapshotPackagingProgress {
+    /// Returns the snapshot package being handled, if any.
+    pub fn job(&self) -> Option<SnapshotPackagingJob> {
+        let job = (*self.job.read().unwrap())?;
+        Some(SnapshotPackagingJob {
+            slot: job.slot,
+            kind: job.kind,
+            stage: job.stage,
+            total_bytes: job.total_bytes,
+            archived_bytes: self.archived_bytes.load(Ordering::Relaxed),
+            elapsed: job.start.elapsed(),
+        })
+    }
+
+    pub(crate) fn start(&self, slot: Slot, kind: SnapshotKind) {
+        self.archived_bytes.store(0, Ordering::Relaxed);
+        *self.job.write().unwrap() = Some(JobState {
+            slot,
+            kind,
+            stage: SnapshotPackagingStage::Serializing,
+            total_bytes: 0,
+            start: Instant::now(),
+        });
+    }
+
+    pub(crate) fn start_archiving(&self, total_bytes: u64) {
+        if let Some(job) = self.job.write().unwrap().as_mut() {
+            job.stage = SnapshotPackagingStage::Archiving;
+            job.total_bytes = total_bytes;
+        }
+    }
+
+    pub(crate) fn finish(&self) {
+        *self.job.write().unwrap() = None;
+    }
+}
+
+/// Writer limiting the rate at which bytes are written to it, and counting
+/// them into the archived bytes of the progress
+pub(crate) struct ThrottledWriter<'a, W> {
+    inner: W,
+    bytes_per_second: Option<NonZeroU64>,
+    progress: Option<&'a SnapshotPackagingProgress>,
+    start: Instant,
+    written_bytes: u64,
+}
+
+impl<'a, W: Write> ThrottledWriter<'a, W> {
+    pub(crate) fn new(
+        inner: W,
+        bytes_per_second: Option<NonZeroU64>,
+        progress: Option<&'a SnapshotPackagingProgress>,
+    ) -> Self {
+        Self {
+            inner,
+            bytes_per_second,
+            progress,
+            start: Instant::now(),
+            written_bytes: 0,
+        }
+    }
+}
+
+impl<W: Write> Write for ThrottledWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let num_bytes = self.inner.write(buf)?;
+        self.written_bytes += num_bytes as u64;
+        if let Some(progress) = self.progress {
+            progress
+                .archived_bytes
+                .fetch_add(num_bytes as u64, Ordering::Relaxed);
+        }
+        if let Some(bytes_per_second) = self.bytes_per_second {
+            // Sleeps until the average rate since the start gets back under the
+            // limit, so that short bursts are smoothed out.
+            let target =
+                Duration::from_secs_f64(self.written_bytes as f64 / bytes_per_second.get() as f64);
+            if let Some(delay) = target.checked_sub(self.start.elapsed()) {
+                thread::sleep(delay);
+            }
+        }
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttled_writer() {
+        let progress = SnapshotPackagingProgress::default();
+        progress.start(42, SnapshotKind::FullSnapshot);
+        assert_eq!(
+            progress.job().unwrap().stage,
+            SnapshotPackagingStage::Serializing
+        );
+        progress.start_archiving(1_000);
+
+        let start = Instant::now();
+        let mut buffer = vec![];
+        let mut writer =
+            ThrottledWriter::new(&mut buffer, NonZeroU64::new(10_000), Some(&progress));
+        for _ in 0..10 {
+            writer.write_all(&[0; 100]).unwrap();
+        }
+        // 1,000 bytes at 10,000 bytes per second take at least 100ms.
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(buffer.len(), 1_000);
+
+        let job = progress.job().unwrap();
+        assert_eq!(job.slot, 42);
+        assert_eq!(job.stage, SnapshotPackagingStage::Archiving);
+        assert_eq!(job.total_bytes, 1_000);
+        assert_eq!(job.archived_bytes, 1_000);
+        progress.finish();
+        assert!(progress.job().is_none());
+    }
+}
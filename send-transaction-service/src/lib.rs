@@ -1,10 +1,12 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod send_transaction_service;
 pub mod send_transaction_service_stats;
+pub mod status_webhook;
 #[cfg(any(test, feature = "dev-context-only-utils"))]
 pub mod test_utils;
 pub mod tpu_info;
 pub mod transaction_client;
+pub mod transaction_store;
 
 pub use {
     send_transaction_service_stats::SendTransactionServiceStats,
@@ -9,8 +9,10 @@ pub use crate::{
 use {
     crate::{
         send_transaction_service_stats::SendTransactionServiceStatsReport,
+        status_webhook::{StatusWebhook, TransactionStatus},
         tpu_info::TpuInfo,
         transaction_client::{ConnectionCacheClient, TransactionClient},
+        transaction_store::TransactionStore,
     },
     crossbeam_channel::{Receiver, RecvTimeoutError},
     itertools::Itertools,
@@ -23,6 +25,7 @@ use {
     std::{
         collections::hash_map::{Entry, HashMap},
         net::SocketAddr,
+        path::PathBuf,
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc, Mutex, RwLock,
@@ -63,6 +66,7 @@ pub const MAX_BATCH_SEND_RATE_MS: usize = 100_000;
 pub struct SendTransactionService {
     receive_txn_thread: JoinHandle<()>,
     retry_thread: JoinHandle<()>,
+    status_webhook_thread: Option<JoinHandle<()>>,
     exit: Arc<AtomicBool>,
 }
 
@@ -72,9 +76,9 @@ pub struct TransactionInfo {
     pub last_valid_block_height: u64,
     pub durable_nonce_info: Option<(Pubkey, Hash)>,
     pub max_retries: Option<usize>,
-    retries: usize,
+    pub(crate) retries: usize,
     /// Last time the transaction was sent
-    last_sent_time: Option<Instant>,
+    pub(crate) last_sent_time: Option<Instant>,
 }
 
 impl TransactionInfo {
@@ -132,6 +136,10 @@ pub struct Config {
     /// When the retry pool exceeds this max size, new transactions are dropped after their first broadcast attempt
     pub retry_pool_max_size: usize,
     pub tpu_peers: Option<Vec<SocketAddr>>,
+    /// Directory persisting the transactions of the retry pool, to retry them again after a restart
+    pub transaction_store_path: Option<PathBuf>,
+    /// URL the status of each transaction leaving the retry pool is posted to
+    pub status_webhook_url: Option<String>,
 }
 
 impl Default for Config {
@@ -145,6 +153,63 @@ impl Default for Config {
             batch_send_rate_ms: DEFAULT_BATCH_SEND_RATE_MS,
             retry_pool_max_size: MAX_TRANSACTION_RETRY_POOL_SIZE,
             tpu_peers: None,
+            transaction_store_path: None,
+            status_webhook_url: None,
+        }
+    }
+}
+
+/// Records the transactions entering and leaving the retry pool, if configured to
+#[derive(Default)]
+struct RetryPoolRecorder {
+    transaction_store: Option<TransactionStore>,
+    status_webhook: Option<StatusWebhook>,
+}
+
+impl RetryPoolRecorder {
+    fn record_added(&self, transaction_info: &TransactionInfo) {
+        if let Some(transaction_store) = &self.transaction_store {
+            if let Err(err) = transaction_store.insert(transaction_info) {
+                warn!(
+                    "Failed to store transaction {}: {err}",
+                    transaction_info.signature
+                );
+            }
+        }
+    }
+
+    fn record_removed(&self, signature: &Signature, status: TransactionStatus) {
+        if let Some(transaction_store) = &self.transaction_store {
+            if let Err(err) = transaction_store.remove(signature) {
+                warn!("Failed to remove stored transaction {signature}: {err}");
+            }
+        }
+        self.notify(signature, status);
+    }
+
+    fn notify(&self, signature: &Signature, status: TransactionStatus) {
+        if let Some(status_webhook) = &self.status_webhook {
+            status_webhook.notify(signature, status);
+        }
+    }
+
+    /// Returns the transactions stored before a restart
+    fn load(&self) -> HashMap<Signature, TransactionInfo> {
+        let Some(transaction_store) = &self.transaction_store else {
+            return HashMap::new();
+        };
+        match transaction_store.load() {
+            Ok(transactions) => {
+                info!("Loaded {} stored transactions", transactions.len());
+                transactions
+                    .into_iter()
+                    .map(|transaction_info| (transaction_info.signature, transaction_info))
+                    .collect()
+            }
+            Err(err) => {
+                error!("Failed to load the stored transactions: {err}");
+                HashMap::new()
+            }
         }
     }
 }
@@ -212,7 +277,25 @@ impl SendTransactionService {
     ) -> Self {
         let stats_report = Arc::new(SendTransactionServiceStatsReport::default());
 
-        let retry_transactions = Arc::new(Mutex::new(HashMap::new()));
+        let transaction_store = config.transaction_store_path.as_ref().and_then(|path| {
+            TransactionStore::open(path)
+                .inspect_err(|err| {
+                    error!("Failed to open transaction store {}: {err}", path.display())
+                })
+                .ok()
+        });
+        let (status_webhook, status_webhook_thread) = config
+            .status_webhook_url
+            .clone()
+            .map(|url| StatusWebhook::new(url, stats_report.clone(), exit.clone()))
+            .unzip();
+        let recorder = Arc::new(RetryPoolRecorder {
+            transaction_store,
+            status_webhook,
+        });
+
+        // Transactions stored before a restart are retried right away
+        let retry_transactions = Arc::new(Mutex::new(recorder.load()));
 
         let receive_txn_thread = Self::receive_txn_thread(
             receiver,
@@ -220,6 +303,7 @@ impl SendTransactionService {
             retry_transactions.clone(),
             config.clone(),
             stats_report.clone(),
+            recorder.clone(),
             exit.clone(),
         );
 
@@ -229,11 +313,13 @@ impl SendTransactionService {
             retry_transactions,
             config,
             stats_report,
+            recorder,
             exit.clone(),
         );
         Self {
             receive_txn_thread,
             retry_thread,
+            status_webhook_thread,
             exit,
         }
     }
@@ -252,6 +338,7 @@ impl SendTransactionService {
             ..
         }: Config,
         stats_report: Arc<SendTransactionServiceStatsReport>,
+        recorder: Arc<RetryPoolRecorder>,
         exit: Arc<AtomicBool>,
     ) -> JoinHandle<()> {
         let mut last_batch_sent = Instant::now();
@@ -313,7 +400,8 @@ impl SendTransactionService {
                         let mut retry_transactions = retry_transactions.lock().unwrap();
                         let mut transactions_to_retry: usize = 0;
                         let mut transactions_added_to_retry: usize = 0;
-                        for (signature, mut transaction_info) in transactions.drain() {
+                        let mut drained_transactions = transactions.drain();
+                        for (signature, mut transaction_info) in drained_transactions.by_ref() {
                             // drop transactions with 0 max retries
                             let max_retries = transaction_info
                                 .get_max_retries(default_max_retries, service_max_retries);
@@ -326,14 +414,24 @@ impl SendTransactionService {
                             let entry = retry_transactions.entry(signature);
                             if let Entry::Vacant(_) = entry {
                                 if retry_len >= retry_pool_max_size {
+                                    recorder.notify(&signature, TransactionStatus::Dropped);
                                     break;
                                 } else {
                                     transaction_info.last_sent_time = Some(last_sent_time);
                                     saturating_add_assign!(transactions_added_to_retry, 1);
+                                    recorder.record_added(&transaction_info);
                                     entry.or_insert(transaction_info);
                                 }
                             }
                         }
+                        // The rest of the batch does not fit in the retry pool either
+                        for (signature, transaction_info) in drained_transactions {
+                            let max_retries = transaction_info
+                                .get_max_retries(default_max_retries, service_max_retries);
+                            if max_retries != Some(0) {
+                                recorder.notify(&signature, TransactionStatus::Dropped);
+                            }
+                        }
                         stats.retry_queue_overflow.fetch_add(
                             transactions_to_retry.saturating_sub(transactions_added_to_retry)
                                 as u64,
@@ -357,6 +455,7 @@ impl SendTransactionService {
         retry_transactions: Arc<Mutex<HashMap<Signature, TransactionInfo>>>,
         config: Config,
         stats_report: Arc<SendTransactionServiceStatsReport>,
+        recorder: Arc<RetryPoolRecorder>,
         exit: Arc<AtomicBool>,
     ) -> JoinHandle<()> {
         debug!("Starting send-transaction-service::retry_thread.");
@@ -389,6 +488,7 @@ impl SendTransactionService {
                         &client,
                         &config,
                         stats,
+                        &recorder,
                     );
                     stats_report.report();
 
@@ -421,6 +521,7 @@ impl SendTransactionService {
             ..
         }: &Config,
         stats: &SendTransactionServiceStats,
+        recorder: &RetryPoolRecorder,
     ) -> ProcessTransactionsResult {
         let mut result = ProcessTransactionsResult::default();
 
@@ -436,6 +537,7 @@ impl SendTransactionService {
                 info!("Transaction is rooted: {}", signature);
                 result.rooted += 1;
                 stats.rooted_transactions.fetch_add(1, Ordering::Relaxed);
+                recorder.record_removed(signature, TransactionStatus::Rooted);
                 return false;
             }
             let signature_status = working_bank.get_signature_status_slot(signature);
@@ -453,6 +555,7 @@ impl SendTransactionService {
                     info!("Dropping expired durable-nonce transaction: {}", signature);
                     result.expired += 1;
                     stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
+                    recorder.record_removed(signature, TransactionStatus::Expired);
                     return false;
                 }
             }
@@ -460,6 +563,7 @@ impl SendTransactionService {
                 info!("Dropping expired transaction: {}", signature);
                 result.expired += 1;
                 stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
+                recorder.record_removed(signature, TransactionStatus::Expired);
                 return false;
             }
 
@@ -473,6 +577,7 @@ impl SendTransactionService {
                     stats
                         .transactions_exceeding_max_retries
                         .fetch_add(1, Ordering::Relaxed);
+                    recorder.record_removed(signature, TransactionStatus::Dropped);
                     return false;
                 }
             }
@@ -520,6 +625,7 @@ impl SendTransactionService {
                         info!("Dropping failed transaction: {}", signature);
                         result.failed += 1;
                         stats.failed_transactions.fetch_add(1, Ordering::Relaxed);
+                        recorder.record_removed(signature, TransactionStatus::Failed);
                         false
                     } else {
                         result.retained += 1;
@@ -552,6 +658,7 @@ impl SendTransactionService {
         for signature in exceeded_retries_transactions {
             info!("Dropping transaction due to max retries: {signature}");
             transactions.remove(&signature);
+            recorder.record_removed(&signature, TransactionStatus::Dropped);
         }
 
         result
@@ -560,7 +667,11 @@ impl SendTransactionService {
     pub fn join(self) -> thread::Result<()> {
         self.receive_txn_thread.join()?;
         self.exit.store(true, Ordering::Relaxed);
-        self.retry_thread.join()
+        self.retry_thread.join()?;
+        if let Some(status_webhook_thread) = self.status_webhook_thread {
+            status_webhook_thread.join()?;
+        }
+        Ok(())
     }
 }
 #[cfg(test)]
@@ -745,6 +856,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -774,6 +886,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -803,6 +916,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -832,6 +946,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert_eq!(transactions.len(), 1);
         assert_eq!(
@@ -863,6 +978,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert_eq!(transactions.len(), 1);
         assert_eq!(
@@ -904,6 +1020,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1012,6 +1129,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1040,6 +1158,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1070,6 +1189,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1098,6 +1218,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1127,6 +1248,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert!(transactions.is_empty());
         assert_eq!(
@@ -1156,6 +1278,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert_eq!(transactions.len(), 1);
         assert_eq!(
@@ -1187,6 +1310,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert_eq!(transactions.len(), 1);
         assert_eq!(
@@ -1215,6 +1339,7 @@ mod test {
             &client,
             &config,
             &stats,
+            &RetryPoolRecorder::default(),
         );
         assert_eq!(transactions.len(), 0);
         assert_eq!(
@@ -1227,6 +1352,61 @@ mod test {
         client.stop();
     }
 
+    #[test]
+    fn process_transactions_records_removed_transactions() {
+        use crate::test_utils::CreateClient;
+
+        let (genesis_config, _mint_keypair) = create_genesis_config(4);
+        let (_, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        let root_bank = Bank::new_from_parent(
+            bank_forks.read().unwrap().working_bank(),
+            &Pubkey::default(),
+            1,
+        );
+        let root_bank = bank_forks
+            .write()
+            .unwrap()
+            .insert(root_bank)
+            .clone_without_scheduler();
+        let client = ConnectionCacheClient::<NullTpuInfo>::create_client(
+            None,
+            "127.0.0.1:0".parse().unwrap(),
+            None,
+            1,
+        );
+        let store_dir = tempfile::tempdir().unwrap();
+        let recorder = RetryPoolRecorder {
+            transaction_store: Some(TransactionStore::open(store_dir.path()).unwrap()),
+            status_webhook: None,
+        };
+
+        let expired_transaction = TransactionInfo::new(
+            Signature::new_unique(),
+            vec![],
+            0,
+            None,
+            None,
+            Some(Instant::now()),
+        );
+        recorder.record_added(&expired_transaction);
+        // A restarted service loads the stored transactions.
+        let mut transactions = recorder.load();
+        assert!(transactions.contains_key(&expired_transaction.signature));
+
+        let result = SendTransactionService::process_transactions(
+            &root_bank,
+            &root_bank,
+            &mut transactions,
+            &client,
+            &Config::default(),
+            &SendTransactionServiceStats::default(),
+            &recorder,
+        );
+        assert_eq!(result.expired, 1);
+        assert!(transactions.is_empty());
+        assert!(recorder.load().is_empty());
+    }
+
     #[test]
     fn retry_durable_nonce_transactions_with_connection_cache() {
         retry_durable_nonce_transactions::<ConnectionCacheClient<NullTpuInfo>>(None);
@@ -51,6 +51,15 @@ pub struct SendTransactionServiceStats {
 
     /// Count of transactions failed
     pub failed_transactions: AtomicU64,
+
+    /// Count of transaction statuses posted to the webhook
+    pub status_notifications_sent: AtomicU64,
+
+    /// Count of transaction statuses the webhook failed to take
+    pub status_notifications_failed: AtomicU64,
+
+    /// Count of transaction statuses dropped because too many were waiting to be posted
+    pub status_notifications_dropped: AtomicU64,
 }
 
 #[derive(Default)]
@@ -141,6 +150,27 @@ impl SendTransactionServiceStatsReport {
                     "failed-tx",
                     self.stats.failed_transactions.swap(0, Ordering::Relaxed),
                     i64
+                ),
+                (
+                    "status-notifications-sent",
+                    self.stats
+                        .status_notifications_sent
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "status-notifications-failed",
+                    self.stats
+                        .status_notifications_failed
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "status-notifications-dropped",
+                    self.stats
+                        .status_notifications_dropped
+                        .swap(0, Ordering::Relaxed),
+                    i64
                 )
             );
         }
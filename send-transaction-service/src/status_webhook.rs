@@ -0,0 +1,165 @@
+//! Notifications of the final status of the transactions of the retry pool,
+//! posted as JSON to a webhook by a thread of their own.
+
+use {
+    crate::send_transaction_service_stats::SendTransactionServiceStatsReport,
+    crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TrySendError},
+    log::*,
+    serde_derive::Serialize,
+    solana_sdk::signature::Signature,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+/// Maximum number of notifications waiting to be posted, before new ones are dropped
+const MAX_PENDING_NOTIFICATIONS: usize = 100_000;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Why a transaction left the retry pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionStatus {
+    /// The transaction is in a rooted bank
+    Rooted,
+    /// The transaction was processed with an error
+    Failed,
+    /// The blockhash or durable nonce of the transaction expired
+    Expired,
+    /// The transaction exceeded its retries, or did not fit in the retry pool
+    Dropped,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusNotification {
+    pub signature: String,
+    pub status: TransactionStatus,
+}
+
+pub struct StatusWebhook {
+    sender: Sender<TransactionStatusNotification>,
+    stats_report: Arc<SendTransactionServiceStatsReport>,
+}
+
+impl StatusWebhook {
+    /// Returns the webhook and the thread posting its notifications to `url`, until `exit`
+    pub(crate) fn new(
+        url: String,
+        stats_report: Arc<SendTransactionServiceStatsReport>,
+        exit: Arc<AtomicBool>,
+    ) -> (Self, JoinHandle<()>) {
+        let (sender, receiver) = bounded(MAX_PENDING_NOTIFICATIONS);
+        let thread = Self::post_thread(url, receiver, stats_report.clone(), exit);
+        (
+            Self {
+                sender,
+                stats_report,
+            },
+            thread,
+        )
+    }
+
+    pub fn notify(&self, signature: &Signature, status: TransactionStatus) {
+        let notification = TransactionStatusNotification {
+            signature: signature.to_string(),
+            status,
+        };
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(notification) {
+            self.stats_report
+                .stats
+                .status_notifications_dropped
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn post_thread(
+        url: String,
+        receiver: Receiver<TransactionStatusNotification>,
+        stats_report: Arc<SendTransactionServiceStatsReport>,
+        exit: Arc<AtomicBool>,
+    ) -> JoinHandle<()> {
+        Builder::new()
+            .name("solStxWebhook".to_string())
+            .spawn(move || {
+                let client = reqwest::blocking::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()
+                    .expect("build webhook client");
+                while !exit.load(Ordering::Relaxed) {
+                    let notification = match receiver.recv_timeout(EXIT_CHECK_INTERVAL) {
+                        Ok(notification) => notification,
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    let stats = &stats_report.stats;
+                    match client
+                        .post(&url)
+                        .json(&notification)
+                        .send()
+                        .and_then(|response| response.error_for_status())
+                    {
+                        Ok(_) => {
+                            stats
+                                .status_notifications_sent
+                                .fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(err) => {
+                            debug!("Failed to post status of {}: {err}", notification.signature);
+                            stats
+                                .status_notifications_failed
+                                .fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            })
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_json() {
+        let notification = TransactionStatusNotification {
+            signature: Signature::default().to_string(),
+            status: TransactionStatus::Rooted,
+        };
+        assert_eq!(
+            serde_json::to_value(&notification).unwrap(),
+            serde_json::json!({
+                "signature": Signature::default().to_string(),
+                "status": "rooted",
+            })
+        );
+    }
+
+    #[test]
+    fn test_notify_full() {
+        let stats_report = Arc::new(SendTransactionServiceStatsReport::default());
+        let (sender, _receiver) = bounded(1);
+        let webhook = StatusWebhook {
+            sender,
+            stats_report: stats_report.clone(),
+        };
+        webhook.notify(&Signature::default(), TransactionStatus::Expired);
+        webhook.notify(&Signature::default(), TransactionStatus::Dropped);
+        assert_eq!(
+            stats_report
+                .stats
+                .status_notifications_dropped
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+}
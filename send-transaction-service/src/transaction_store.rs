@@ -0,0 +1,164 @@
+//! Persistence of the transactions of the retry pool, so a restarted service
+//! keeps retrying them.
+//!
+//! Each transaction is stored in a file of its own, named by its signature,
+//! which is written when the transaction enters the retry pool and removed
+//! once it leaves it. The count of retries of a transaction is not updated on
+//! disk as it is retried, so it restarts from the stored count after a
+//! restart.
+
+use {
+    crate::send_transaction_service::TransactionInfo,
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature},
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+const TRANSACTION_FILE_EXTENSION: &str = "tx";
+
+#[derive(Serialize, Deserialize)]
+struct StoredTransaction {
+    signature: Signature,
+    wire_transaction: Vec<u8>,
+    last_valid_block_height: u64,
+    durable_nonce_info: Option<(Pubkey, Hash)>,
+    max_retries: Option<usize>,
+    retries: usize,
+}
+
+pub struct TransactionStore {
+    path: PathBuf,
+}
+
+impl TransactionStore {
+    /// Opens the store in the directory `path`, creating it if needed
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        fs::create_dir_all(&path)?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn transaction_path(&self, signature: &Signature) -> PathBuf {
+        self.path
+            .join(signature.to_string())
+            .with_extension(TRANSACTION_FILE_EXTENSION)
+    }
+
+    /// Stores `transaction_info`, replacing any transaction with the same signature
+    pub fn insert(&self, transaction_info: &TransactionInfo) -> io::Result<()> {
+        let stored_transaction = StoredTransaction {
+            signature: transaction_info.signature,
+            wire_transaction: transaction_info.wire_transaction.clone(),
+            last_valid_block_height: transaction_info.last_valid_block_height,
+            durable_nonce_info: transaction_info.durable_nonce_info,
+            max_retries: transaction_info.max_retries,
+            retries: transaction_info.retries,
+        };
+        let data = bincode::serialize(&stored_transaction)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        // Written to a temporary file first, so a crash never leaves a partial transaction
+        let path = self.transaction_path(&transaction_info.signature);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn remove(&self, signature: &Signature) -> io::Result<()> {
+        match fs::remove_file(self.transaction_path(signature)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the stored transactions, removing the files which do not hold one
+    pub fn load(&self) -> io::Result<Vec<TransactionInfo>> {
+        let mut transactions = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str())
+                != Some(TRANSACTION_FILE_EXTENSION)
+            {
+                continue;
+            }
+            match bincode::deserialize::<StoredTransaction>(&fs::read(&path)?) {
+                Ok(stored_transaction) => {
+                    let mut transaction_info = TransactionInfo::new(
+                        stored_transaction.signature,
+                        stored_transaction.wire_transaction,
+                        stored_transaction.last_valid_block_height,
+                        stored_transaction.durable_nonce_info,
+                        stored_transaction.max_retries,
+                        None,
+                    );
+                    transaction_info.retries = stored_transaction.retries;
+                    transactions.push(transaction_info);
+                }
+                Err(err) => {
+                    warn!(
+                        "Removing invalid stored transaction {}: {err}",
+                        path.display()
+                    );
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_transaction_info(max_retries: Option<usize>) -> TransactionInfo {
+        TransactionInfo::new(
+            Signature::new_unique(),
+            vec![1, 2, 3],
+            42,
+            Some((Pubkey::new_unique(), Hash::new_unique())),
+            max_retries,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_transaction_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TransactionStore::open(dir.path().join("transactions")).unwrap();
+        assert!(store.load().unwrap().is_empty());
+
+        let first = new_transaction_info(Some(5));
+        let mut second = new_transaction_info(None);
+        second.retries = 3;
+        store.insert(&first).unwrap();
+        store.insert(&second).unwrap();
+        fs::write(store.path().join("invalid.tx"), [0xff]).unwrap();
+
+        let mut loaded = store.load().unwrap();
+        loaded.sort_by_key(|transaction_info| transaction_info.max_retries);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].signature, second.signature);
+        assert_eq!(loaded[0].retries, 3);
+        assert_eq!(loaded[1].signature, first.signature);
+        assert_eq!(loaded[1].wire_transaction, first.wire_transaction);
+        assert_eq!(loaded[1].last_valid_block_height, 42);
+        assert_eq!(loaded[1].durable_nonce_info, first.durable_nonce_info);
+        assert!(loaded[1].last_sent_time.is_none());
+        // The invalid file is removed.
+        assert!(!store.path().join("invalid.tx").exists());
+
+        store.remove(&first.signature).unwrap();
+        store.remove(&first.signature).unwrap();
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].signature, second.signature);
+    }
+}
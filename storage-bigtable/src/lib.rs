@@ -44,8 +44,11 @@ extern crate serde_derive;
 mod access_token;
 mod bigtable;
 mod compression;
+mod long_term_storage;
 mod root_ca_certificate;
 
+pub use crate::long_term_storage::LongTermStorage;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("BigTable: {0}")]
@@ -65,6 +68,9 @@ pub enum Error {
 
     #[error("tokio error")]
     TokioJoinError(JoinError),
+
+    #[error("Long-term storage: {0}")]
+    LongTermStorageError(String),
 }
 
 impl std::convert::From<bigtable::Error> for Error {
@@ -350,6 +356,7 @@ impl From<TransactionInfo> for TransactionStatus {
             status,
             err,
             confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+            provenance: None,
         }
     }
 }
@@ -0,0 +1,234 @@
+//! Accounting of the UDP and QUIC bytes the node sends and receives, by the
+//! subsystem they are attributed to, for operators to tell which subsystems
+//! use their bandwidth.
+//!
+//! The counters are process wide, so that the sockets of every subsystem can
+//! record to them without the accounting being threaded through each of them.
+//! The bytes counted are the payloads of the datagrams and of the QUIC
+//! streams, without the IP, UDP and QUIC overheads, handed to or read from the
+//! sockets.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        LazyLock, Mutex,
+    },
+    time::Instant,
+};
+
+const NUM_SUBSYSTEMS: usize = 7;
+
+static BANDWIDTH_ACCOUNTING: LazyLock<BandwidthAccounting> =
+    LazyLock::new(BandwidthAccounting::default);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// Shreds received from and retransmitted to the turbine tree, and the
+    /// shreds broadcast by the leader
+    Turbine,
+    /// Repair requests of the node, and the responses it receives
+    Repair,
+    /// Repair requests of other nodes served by the node
+    RepairServe,
+    Gossip,
+    /// Transactions and votes received by the node, and the ones it forwards
+    Tpu,
+    /// Transactions submitted through the RPC and sent to the leaders
+    Rpc,
+    Other,
+}
+
+impl Subsystem {
+    pub const ALL: [Self; NUM_SUBSYSTEMS] = [
+        Self::Turbine,
+        Self::Repair,
+        Self::RepairServe,
+        Self::Gossip,
+        Self::Tpu,
+        Self::Rpc,
+        Self::Other,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Turbine => "turbine",
+            Self::Repair => "repair",
+            Self::RepairServe => "repair_serve",
+            Self::Gossip => "gossip",
+            Self::Tpu => "tpu",
+            Self::Rpc => "rpc",
+            Self::Other => "other",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    ingress_packets: AtomicU64,
+    ingress_bytes: AtomicU64,
+    egress_packets: AtomicU64,
+    egress_bytes: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubsystemBandwidth {
+    pub subsystem: Subsystem,
+    /// Totals since the start of the node
+    pub ingress_packets: u64,
+    pub ingress_bytes: u64,
+    pub egress_packets: u64,
+    pub egress_bytes: u64,
+    /// Rates over the latest sampling interval, zero until sampled
+    pub ingress_bytes_per_sec: u64,
+    pub egress_bytes_per_sec: u64,
+}
+
+struct Sample {
+    at: Instant,
+    bandwidths: [SubsystemBandwidth; NUM_SUBSYSTEMS],
+}
+
+pub struct BandwidthAccounting {
+    counters: [Counters; NUM_SUBSYSTEMS],
+    latest_sample: Mutex<Sample>,
+}
+
+impl Default for BandwidthAccounting {
+    fn default() -> Self {
+        let bandwidths = Subsystem::ALL.map(|subsystem| SubsystemBandwidth {
+            subsystem,
+            ingress_packets: 0,
+            ingress_bytes: 0,
+            egress_packets: 0,
+            egress_bytes: 0,
+            ingress_bytes_per_sec: 0,
+            egress_bytes_per_sec: 0,
+        });
+        Self {
+            counters: std::array::from_fn(|_| Counters::default()),
+            latest_sample: Mutex::new(Sample {
+                at: Instant::now(),
+                bandwidths,
+            }),
+        }
+    }
+}
+
+impl BandwidthAccounting {
+    pub fn record_ingress(&self, subsystem: Subsystem, num_packets: usize, num_bytes: usize) {
+        let counters = &self.counters[subsystem.index()];
+        counters
+            .ingress_packets
+            .fetch_add(num_packets as u64, Ordering::Relaxed);
+        counters
+            .ingress_bytes
+            .fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_egress(&self, subsystem: Subsystem, num_packets: usize, num_bytes: usize) {
+        let counters = &self.counters[subsystem.index()];
+        counters
+            .egress_packets
+            .fetch_add(num_packets as u64, Ordering::Relaxed);
+        counters
+            .egress_bytes
+            .fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Computes the rates since the previous sample, and reports them with
+    /// the totals as metrics
+    pub fn sample(&self) -> Vec<SubsystemBandwidth> {
+        let mut latest_sample = self.latest_sample.lock().unwrap();
+        let now = Instant::now();
+        let elapsed_ms = now.duration_since(latest_sample.at).as_millis().max(1) as u64;
+        let bandwidths = Subsystem::ALL.map(|subsystem| {
+            let counters = &self.counters[subsystem.index()];
+            let previous = &latest_sample.bandwidths[subsystem.index()];
+            let ingress_bytes = counters.ingress_bytes.load(Ordering::Relaxed);
+            let egress_bytes = counters.egress_bytes.load(Ordering::Relaxed);
+            SubsystemBandwidth {
+                subsystem,
+                ingress_packets: counters.ingress_packets.load(Ordering::Relaxed),
+                ingress_bytes,
+                egress_packets: counters.egress_packets.load(Ordering::Relaxed),
+                egress_bytes,
+                ingress_bytes_per_sec: (ingress_bytes - previous.ingress_bytes) * 1000 / elapsed_ms,
+                egress_bytes_per_sec: (egress_bytes - previous.egress_bytes) * 1000 / elapsed_ms,
+            }
+        });
+        *latest_sample = Sample {
+            at: now,
+            bandwidths,
+        };
+        for bandwidth in &bandwidths {
+            datapoint_info!(
+                "bandwidth_accounting",
+                "subsystem" => bandwidth.subsystem.name(),
+                ("ingress_packets", bandwidth.ingress_packets, i64),
+                ("ingress_bytes", bandwidth.ingress_bytes, i64),
+                ("egress_packets", bandwidth.egress_packets, i64),
+                ("egress_bytes", bandwidth.egress_bytes, i64),
+                ("ingress_bytes_per_sec", bandwidth.ingress_bytes_per_sec, i64),
+                ("egress_bytes_per_sec", bandwidth.egress_bytes_per_sec, i64),
+            );
+        }
+        bandwidths.to_vec()
+    }
+
+    /// The totals and rates of the latest sample
+    pub fn latest_sample(&self) -> Vec<SubsystemBandwidth> {
+        self.latest_sample.lock().unwrap().bandwidths.to_vec()
+    }
+}
+
+/// The accounting the sockets of the node record to
+pub fn bandwidth_accounting() -> &'static BandwidthAccounting {
+    &BANDWIDTH_ACCOUNTING
+}
+
+pub fn record_ingress(subsystem: Subsystem, num_packets: usize, num_bytes: usize) {
+    BANDWIDTH_ACCOUNTING.record_ingress(subsystem, num_packets, num_bytes);
+}
+
+pub fn record_egress(subsystem: Subsystem, num_packets: usize, num_bytes: usize) {
+    BANDWIDTH_ACCOUNTING.record_egress(subsystem, num_packets, num_bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[test]
+    fn test_bandwidth_accounting() {
+        let accounting = BandwidthAccounting::default();
+        accounting.record_ingress(Subsystem::Gossip, 2, 2_000);
+        accounting.record_egress(Subsystem::Gossip, 1, 500);
+        accounting.record_egress(Subsystem::Turbine, 10, 12_000);
+        std::thread::sleep(Duration::from_millis(10));
+
+        let bandwidths = accounting.sample();
+        assert_eq!(bandwidths.len(), Subsystem::ALL.len());
+        let gossip = bandwidths[Subsystem::Gossip.index()];
+        assert_eq!(gossip.subsystem, Subsystem::Gossip);
+        assert_eq!((gossip.ingress_packets, gossip.ingress_bytes), (2, 2_000));
+        assert_eq!((gossip.egress_packets, gossip.egress_bytes), (1, 500));
+        assert!(gossip.ingress_bytes_per_sec > 0);
+        let turbine = bandwidths[Subsystem::Turbine.index()];
+        assert_eq!(turbine.egress_bytes, 12_000);
+        assert_eq!(turbine.ingress_bytes, 0);
+        assert_eq!(accounting.latest_sample(), bandwidths);
+
+        // The rates are of the bytes since the previous sample only.
+        std::thread::sleep(Duration::from_millis(10));
+        accounting.record_ingress(Subsystem::Rpc, 1, 100);
+        let bandwidths = accounting.sample();
+        let gossip = bandwidths[Subsystem::Gossip.index()];
+        assert_eq!(gossip.ingress_bytes, 2_000);
+        assert_eq!(gossip.ingress_bytes_per_sec, 0);
+        assert!(bandwidths[Subsystem::Rpc.index()].ingress_bytes_per_sec > 0);
+    }
+}
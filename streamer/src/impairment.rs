@@ -0,0 +1,321 @@
+//! Simulated impairments of the network links between the nodes of a local
+//! cluster, to test consensus and repair over a degraded network.
+//!
+//! The sockets of each node are registered with the identity of the node, and
+//! impairments are set for the links from one node to another. The UDP
+//! receivers and the QUIC servers of the streamer apply the impairment of the
+//! link a packet came over as they receive it: the packet is dropped if the
+//! link is partitioned or loses it, and is otherwise held back by the latency
+//! of the link. Packets from sockets which are not registered, and all packets
+//! while no impairments are set, are let through untouched.
+//!
+//! The impairments are process wide, as the nodes of a local cluster run in
+//! the same process and the sockets are spread over all of their services.
+
+use {
+    crate::{packet::PacketBatch, streamer::ChannelSend},
+    crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
+    rand::Rng,
+    solana_pubkey::Pubkey,
+    std::{
+        cmp::{Ordering as CmpOrdering, Reverse},
+        collections::{BinaryHeap, HashMap},
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            LazyLock, RwLock,
+        },
+        thread::Builder,
+        time::{Duration, Instant},
+    },
+};
+
+/// Whether any link is impaired, so that the receivers can skip the lookups
+/// while none is
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static IMPAIRMENTS: LazyLock<RwLock<NetworkImpairments>> = LazyLock::new(RwLock::default);
+
+static DELAY_LINE: LazyLock<Sender<DelayedDelivery>> = LazyLock::new(|| {
+    let (sender, receiver) = unbounded();
+    Builder::new()
+        .name("solNetImpair".to_string())
+        .spawn(move || run_delay_line(receiver))
+        .unwrap();
+    sender
+});
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkImpairment {
+    /// How long the packets over the link are held back
+    pub latency: Duration,
+    /// The probability of a packet over the link being lost, from 0 to 1
+    pub loss_rate: f64,
+    /// Whether no packet makes it over the link
+    pub partitioned: bool,
+}
+
+impl LinkImpairment {
+    pub fn partitioned() -> Self {
+        Self {
+            partitioned: true,
+            ..Self::default()
+        }
+    }
+
+    fn is_unimpaired(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn drops_packet(&self, rng: &mut impl Rng) -> bool {
+        self.partitioned || (self.loss_rate > 0.0 && rng.gen::<f64>() < self.loss_rate)
+    }
+}
+
+#[derive(Default)]
+struct NetworkImpairments {
+    /// The node each registered socket belongs to
+    nodes: HashMap<SocketAddr, Pubkey>,
+    /// The impaired links, by the nodes they go from and to
+    links: HashMap<(Pubkey, Pubkey), LinkImpairment>,
+}
+
+/// Registers the sockets of `node`, in place of the ones registered before,
+/// for the packets sent from and received on them to be subject to the
+/// impairments of the links of `node`
+pub fn register_node(node: Pubkey, addrs: impl IntoIterator<Item = SocketAddr>) {
+    let mut impairments = IMPAIRMENTS.write().unwrap();
+    impairments.nodes.retain(|_, owner| *owner != node);
+    impairments
+        .nodes
+        .extend(addrs.into_iter().map(|addr| (addr, node)));
+}
+
+/// Forgets the sockets of `node` and the impairments of its links
+pub fn unregister_node(node: &Pubkey) {
+    let mut impairments = IMPAIRMENTS.write().unwrap();
+    impairments.nodes.retain(|_, owner| owner != node);
+    impairments
+        .links
+        .retain(|(from, to), _| from != node && to != node);
+    ENABLED.store(!impairments.links.is_empty(), Ordering::Relaxed);
+}
+
+/// Sets the impairment of the link from `from` to `to`, the link the other way
+/// around is left as is
+pub fn set_link_impairment(from: Pubkey, to: Pubkey, impairment: LinkImpairment) {
+    let mut impairments = IMPAIRMENTS.write().unwrap();
+    if impairment.is_unimpaired() {
+        impairments.links.remove(&(from, to));
+    } else {
+        impairments.links.insert((from, to), impairment);
+    }
+    ENABLED.store(!impairments.links.is_empty(), Ordering::Relaxed);
+}
+
+pub fn link_impairment(from: &Pubkey, to: &Pubkey) -> LinkImpairment {
+    let impairments = IMPAIRMENTS.read().unwrap();
+    impairments
+        .links
+        .get(&(*from, *to))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Lifts the impairments of all the links
+pub fn clear_link_impairments() {
+    let mut impairments = IMPAIRMENTS.write().unwrap();
+    impairments.links.clear();
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Applies the impairments of the links the packets of `packet_batch` came
+/// over to the socket at `local_addr`.
+///
+/// Returns the packets to be delivered right away, if any. The packets held
+/// back by the latency of their link are sent to `sender` once it elapses.
+pub(crate) fn impair_packet_batch<S>(
+    local_addr: SocketAddr,
+    packet_batch: PacketBatch,
+    sender: &S,
+) -> Option<PacketBatch>
+where
+    S: ChannelSend<PacketBatch> + Clone,
+{
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Some(packet_batch);
+    }
+    let impairments = IMPAIRMENTS.read().unwrap();
+    let Some(to) = impairments.nodes.get(&local_addr) else {
+        return Some(packet_batch);
+    };
+
+    let mut rng = rand::thread_rng();
+    let mut delivered = Vec::with_capacity(packet_batch.len());
+    let mut delayed = HashMap::<Duration, Vec<_>>::new();
+    for packet in packet_batch.iter() {
+        let impairment = impairments
+            .nodes
+            .get(&packet.meta().socket_addr())
+            .and_then(|from| impairments.links.get(&(*from, *to)));
+        match impairment {
+            None => delivered.push(packet.clone()),
+            Some(impairment) if impairment.drops_packet(&mut rng) => (),
+            Some(impairment) if impairment.latency.is_zero() => delivered.push(packet.clone()),
+            Some(impairment) => delayed
+                .entry(impairment.latency)
+                .or_default()
+                .push(packet.clone()),
+        }
+    }
+    drop(impairments);
+
+    if !delayed.is_empty() {
+        let now = Instant::now();
+        for (latency, packets) in delayed {
+            let sender = sender.clone();
+            let packet_batch = PacketBatch::new(packets);
+            // The delay line runs for as long as the process
+            let _ = DELAY_LINE.send(DelayedDelivery {
+                due: now + latency,
+                deliver: Box::new(move || {
+                    // The receiving end may be gone by the time the latency
+                    // elapses
+                    let _ = sender.try_send(packet_batch);
+                }),
+            });
+        }
+    }
+    (!delivered.is_empty()).then(|| PacketBatch::new(delivered))
+}
+
+struct DelayedDelivery {
+    due: Instant,
+    deliver: Box<dyn FnOnce() + Send>,
+}
+
+/// A delivery pending in the delay line, which orders the deliveries by when
+/// they are due and then by when they were sent to it
+struct PendingDelivery {
+    due: Instant,
+    seq: u64,
+    deliver: Box<dyn FnOnce() + Send>,
+}
+
+impl PendingDelivery {
+    fn key(&self) -> Reverse<(Instant, u64)> {
+        Reverse((self.due, self.seq))
+    }
+}
+
+impl PartialEq for PendingDelivery {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for PendingDelivery {}
+
+impl PartialOrd for PendingDelivery {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PendingDelivery {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.key().cmp(&other.key())
+    }
+}
+
+fn run_delay_line(receiver: Receiver<DelayedDelivery>) {
+    let mut pending = BinaryHeap::<PendingDelivery>::new();
+    let mut seq = 0;
+    loop {
+        let now = Instant::now();
+        while pending.peek().is_some_and(|delivery| delivery.due <= now) {
+            (pending.pop().unwrap().deliver)();
+        }
+        let received = match pending.peek() {
+            Some(delivery) => receiver.recv_deadline(delivery.due),
+            None => receiver.recv().map_err(RecvTimeoutError::from),
+        };
+        match received {
+            Ok(DelayedDelivery { due, deliver }) => {
+                pending.push(PendingDelivery { due, seq, deliver });
+                seq += 1;
+            }
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::packet::Packet};
+
+    fn packet_from(addr: SocketAddr) -> Packet {
+        let mut packet = Packet::default();
+        packet.meta_mut().set_socket_addr(&addr);
+        packet
+    }
+
+    #[test]
+    fn test_impair_packet_batch() {
+        let receiving_addr = SocketAddr::from(([127, 0, 0, 1], 1000));
+        let impaired_addr = SocketAddr::from(([127, 0, 0, 1], 1001));
+        let partitioned_addr = SocketAddr::from(([127, 0, 0, 1], 1002));
+        let unregistered_addr = SocketAddr::from(([127, 0, 0, 1], 1003));
+        let receiving_node = Pubkey::new_unique();
+        let impaired_node = Pubkey::new_unique();
+        let partitioned_node = Pubkey::new_unique();
+        register_node(receiving_node, [receiving_addr]);
+        register_node(impaired_node, [impaired_addr]);
+        register_node(partitioned_node, [partitioned_addr]);
+
+        let impairment = LinkImpairment {
+            latency: Duration::from_millis(50),
+            ..LinkImpairment::default()
+        };
+        set_link_impairment(impaired_node, receiving_node, impairment);
+        set_link_impairment(
+            partitioned_node,
+            receiving_node,
+            LinkImpairment::partitioned(),
+        );
+        assert_eq!(link_impairment(&impaired_node, &receiving_node), impairment);
+        assert_eq!(
+            link_impairment(&receiving_node, &impaired_node),
+            LinkImpairment::default()
+        );
+
+        let (sender, receiver) = unbounded();
+        let packet_batch = PacketBatch::new(vec![
+            packet_from(impaired_addr),
+            packet_from(partitioned_addr),
+            packet_from(unregistered_addr),
+        ]);
+        let delivered = impair_packet_batch(receiving_addr, packet_batch, &sender).unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].meta().socket_addr(), unregistered_addr);
+
+        assert!(receiver.try_recv().is_err());
+        let delayed = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(delayed.len(), 1);
+        assert_eq!(delayed[0].meta().socket_addr(), impaired_addr);
+
+        // The packets received by the nodes on the other end of the links are
+        // left as is.
+        let packet_batch = PacketBatch::new(vec![packet_from(receiving_addr)]);
+        let delivered = impair_packet_batch(impaired_addr, packet_batch, &sender).unwrap();
+        assert_eq!(delivered.len(), 1);
+
+        for node in [receiving_node, impaired_node, partitioned_node] {
+            unregister_node(&node);
+        }
+        let packet_batch = PacketBatch::new(vec![packet_from(partitioned_addr)]);
+        let delivered = impair_packet_batch(receiving_addr, packet_batch, &sender).unwrap();
+        assert_eq!(delivered.len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod bandwidth_accounting;
 pub mod evicting_sender;
+pub mod impairment;
 pub mod msghdr;
 pub mod nonblocking;
 pub mod packet;
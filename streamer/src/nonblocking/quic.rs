@@ -1,5 +1,7 @@
 use {
     crate::{
+        bandwidth_accounting::{self, Subsystem},
+        impairment,
         nonblocking::{
             connection_rate_limiter::{ConnectionRateLimiter, TotalConnectionRateLimiter},
             stream_throttle::{
@@ -7,7 +9,9 @@ use {
                 STREAM_THROTTLING_INTERVAL_MS,
             },
         },
-        quic::{configure_server, QuicServerError, QuicServerParams, StreamerStats},
+        quic::{
+            configure_server, QuicServerError, QuicServerParams, StreamerStats, VirtualStakeConfig,
+        },
         streamer::StakedNodes,
     },
     async_channel::{bounded as async_bounded, Receiver as AsyncReceiver, Sender as AsyncSender},
@@ -190,6 +194,7 @@ pub fn spawn_server_multi(
         wait_for_chunk_timeout,
         coalesce,
         coalesce_channel_size,
+        virtual_stake,
     } = quic_server_params;
     let concurrent_connections = max_staked_connections + max_unstaked_connections;
     let max_concurrent_connections = concurrent_connections + concurrent_connections / 4;
@@ -224,6 +229,7 @@ pub fn spawn_server_multi(
         coalesce,
         coalesce_channel_size,
         max_concurrent_connections,
+        Arc::new(virtual_stake),
     ));
     Ok(SpawnNonBlockingServerResult {
         endpoints,
@@ -295,6 +301,7 @@ async fn run_server(
     coalesce: Duration,
     coalesce_channel_size: usize,
     max_concurrent_connections: usize,
+    virtual_stake: Arc<VirtualStakeConfig>,
 ) {
     let rate_limiter = ConnectionRateLimiter::new(max_connections_per_ipaddr_per_min);
     let overall_connection_rate_limiter =
@@ -316,12 +323,17 @@ async fn run_server(
     let staked_connection_table: Arc<Mutex<ConnectionTable>> =
         Arc::new(Mutex::new(ConnectionTable::new()));
     let (sender, receiver) = async_bounded(coalesce_channel_size);
+    // All the endpoints of the server belong to the same node
+    let local_addr = endpoints
+        .first()
+        .and_then(|endpoint| endpoint.local_addr().ok());
     tokio::spawn(packet_batch_sender(
         packet_sender,
         receiver,
         exit.clone(),
         stats.clone(),
         coalesce,
+        local_addr,
     ));
 
     let mut accepts = endpoints
@@ -431,6 +443,7 @@ async fn run_server(
                         stats.clone(),
                         wait_for_chunk_timeout,
                         stream_load_ema.clone(),
+                        virtual_stake.clone(),
                     ));
                 }
                 Err(err) => {
@@ -472,17 +485,41 @@ pub fn get_remote_pubkey(connection: &Connection) -> Option<Pubkey> {
 fn get_connection_stake(
     connection: &Connection,
     staked_nodes: &RwLock<StakedNodes>,
-) -> Option<(Pubkey, u64, u64, u64, u64)> {
-    let pubkey = get_remote_pubkey(connection)?;
+    virtual_stake: &VirtualStakeConfig,
+) -> Option<(Option<Pubkey>, u64, u64, u64, u64)> {
+    let pubkey = get_remote_pubkey(connection);
     debug!("Peer public key is {pubkey:?}");
-    let staked_nodes = staked_nodes.read().unwrap();
-    Some((
-        pubkey,
-        staked_nodes.get_node_stake(&pubkey)?,
-        staked_nodes.total_stake(),
-        staked_nodes.max_stake(),
-        staked_nodes.min_stake(),
-    ))
+    let (stake, total_stake, max_stake, min_stake) = {
+        let staked_nodes = staked_nodes.read().unwrap();
+        (
+            pubkey.and_then(|pubkey| staked_nodes.get_node_stake(&pubkey)),
+            staked_nodes.total_stake(),
+            staked_nodes.max_stake(),
+            staked_nodes.min_stake(),
+        )
+    };
+    match virtual_stake.get_stake(&connection.remote_address().ip()) {
+        Some(virtual_stake) if stake.is_none_or(|stake| stake < virtual_stake) => {
+            // The connection gets the share of the streams of a node with the virtual stake, as
+            // if its stake was the virtual one.
+            let total_stake = total_stake
+                .saturating_sub(stake.unwrap_or_default())
+                .saturating_add(virtual_stake);
+            let min_stake = if min_stake == 0 {
+                virtual_stake
+            } else {
+                min_stake.min(virtual_stake)
+            };
+            Some((
+                pubkey,
+                virtual_stake,
+                total_stake,
+                max_stake.max(virtual_stake),
+                min_stake,
+            ))
+        }
+        _ => Some((pubkey, stake?, total_stake, max_stake, min_stake)),
+    }
 }
 
 pub fn compute_max_allowed_uni_streams(peer_type: ConnectionPeerType, total_stake: u64) -> usize {
@@ -720,6 +757,7 @@ async fn setup_connection(
     stats: Arc<StreamerStats>,
     wait_for_chunk_timeout: Duration,
     stream_load_ema: Arc<StakedStreamLoadEMA>,
+    virtual_stake: Arc<VirtualStakeConfig>,
 ) {
     const PRUNE_RANDOM_SAMPLE_SIZE: usize = 2;
     let from = connecting.remote_address();
@@ -732,7 +770,9 @@ async fn setup_connection(
             Ok(new_connection) => {
                 stats.total_new_connections.fetch_add(1, Ordering::Relaxed);
 
-                let params = get_connection_stake(&new_connection, &staked_nodes).map_or(
+                let connection_stake =
+                    get_connection_stake(&new_connection, &staked_nodes, &virtual_stake);
+                let params = connection_stake.map_or(
                     NewConnectionHandlerParams::new_unstaked(
                         packet_sender.clone(),
                         max_connections_per_peer,
@@ -752,7 +792,7 @@ async fn setup_connection(
                         };
                         NewConnectionHandlerParams {
                             packet_sender,
-                            remote_pubkey: Some(pubkey),
+                            remote_pubkey: pubkey,
                             peer_type,
                             total_stake,
                             max_connections_per_peer,
@@ -895,6 +935,7 @@ async fn packet_batch_sender(
     exit: Arc<AtomicBool>,
     stats: Arc<StreamerStats>,
     coalesce: Duration,
+    local_addr: Option<SocketAddr>,
 ) {
     trace!("enter packet_batch_sender");
     let recycler = PacketBatchRecycler::default();
@@ -923,6 +964,15 @@ async fn packet_batch_sender(
                 let len = packet_batch.len();
                 track_streamer_fetch_packet_performance(&packet_perf_measure, &stats);
 
+                let packet_batch = match local_addr {
+                    Some(local_addr) => {
+                        impairment::impair_packet_batch(local_addr, packet_batch, &packet_sender)
+                    }
+                    None => Some(packet_batch),
+                };
+                let Some(packet_batch) = packet_batch else {
+                    break;
+                };
                 if let Err(e) = packet_sender.try_send(packet_batch) {
                     stats
                         .total_packet_batch_send_err
@@ -1269,6 +1319,8 @@ async fn handle_chunks(
     // done receiving chunks
     let bytes_sent = accum.meta.size;
     let chunks_sent = accum.chunks.len();
+    // The QUIC server of the streamer serves the TPU.
+    bandwidth_accounting::record_ingress(Subsystem::Tpu, 1, bytes_sent);
 
     if let Err(err) = packet_sender.send(accum.clone()).await {
         stats
@@ -1557,7 +1609,7 @@ pub mod test {
         solana_keypair::Keypair,
         solana_net_utils::bind_to_localhost,
         solana_signer::Signer,
-        std::collections::HashMap,
+        std::collections::{HashMap, HashSet},
         tokio::time::sleep,
     };
 
@@ -1705,6 +1757,7 @@ pub mod test {
             exit.clone(),
             stats,
             DEFAULT_TPU_COALESCE,
+            None,
         ));
 
         let num_packets = 1000;
@@ -1999,6 +2052,52 @@ pub mod test {
         t.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_quic_server_virtually_staked_connection() {
+        solana_logger::setup();
+        let s = bind_to_localhost().unwrap();
+        let exit = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = unbounded();
+        let keypair = Keypair::new();
+        let server_address = s.local_addr().unwrap();
+        let staked_nodes = Arc::new(RwLock::new(StakedNodes::default()));
+        let SpawnNonBlockingServerResult {
+            endpoints: _,
+            stats,
+            thread: t,
+            max_concurrent_connections: _,
+        } = spawn_server(
+            "quic_streamer_test",
+            s,
+            &keypair,
+            sender,
+            exit.clone(),
+            staked_nodes,
+            QuicServerParams {
+                max_unstaked_connections: 0, // Do not allow any connection from unstaked clients/nodes
+                coalesce_channel_size: 100_000, // smaller channel size for faster test
+                virtual_stake: VirtualStakeConfig {
+                    stake: 100_000,
+                    include_loopback: true,
+                    addrs: HashSet::default(),
+                },
+                ..QuicServerParams::default()
+            },
+        )
+        .unwrap();
+
+        // The unstaked client connecting from the loopback address is accepted as staked.
+        check_multiple_writes(receiver, server_address, None).await;
+        exit.store(true, Ordering::Relaxed);
+        t.await.unwrap();
+        assert_eq!(
+            stats
+                .connection_added_from_staked_peer
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_quic_server_multiple_streams() {
         solana_logger::setup();
@@ -18,7 +18,8 @@ use {
     },
     solana_tls_utils::{new_dummy_x509_certificate, tls_server_config_builder},
     std::{
-        net::UdpSocket,
+        collections::HashSet,
+        net::{IpAddr, UdpSocket},
         sync::{
             atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
             Arc, Mutex, RwLock,
@@ -588,6 +589,26 @@ pub fn spawn_server(
     )
 }
 
+/// The addresses whose connections are treated as the ones of a node with some stake, so that
+/// the traffic of the node itself and of its trusted peers is not starved by the staked peers
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VirtualStakeConfig {
+    /// The virtual stake, zero disables it
+    pub stake: u64,
+    /// Whether the connections from the loopback addresses are virtually staked
+    pub include_loopback: bool,
+    /// The other addresses whose connections are virtually staked
+    pub addrs: HashSet<IpAddr>,
+}
+
+impl VirtualStakeConfig {
+    /// The virtual stake of the connections from `ip`, if any
+    pub fn get_stake(&self, ip: &IpAddr) -> Option<u64> {
+        (self.stake > 0 && ((self.include_loopback && ip.is_loopback()) || self.addrs.contains(ip)))
+            .then_some(self.stake)
+    }
+}
+
 #[derive(Clone)]
 pub struct QuicServerParams {
     pub max_connections_per_peer: usize,
@@ -598,6 +619,7 @@ pub struct QuicServerParams {
     pub wait_for_chunk_timeout: Duration,
     pub coalesce: Duration,
     pub coalesce_channel_size: usize,
+    pub virtual_stake: VirtualStakeConfig,
 }
 
 impl Default for QuicServerParams {
@@ -611,6 +633,7 @@ impl Default for QuicServerParams {
             wait_for_chunk_timeout: DEFAULT_WAIT_FOR_CHUNK_TIMEOUT,
             coalesce: DEFAULT_TPU_COALESCE,
             coalesce_channel_size: DEFAULT_MAX_COALESCE_CHANNEL_SIZE,
+            virtual_stake: VirtualStakeConfig::default(),
         }
     }
 }
@@ -699,6 +722,28 @@ mod test {
         (t, exit, receiver, server_address)
     }
 
+    #[test]
+    fn test_virtual_stake_config() {
+        let trusted_addr = IpAddr::from([10, 0, 0, 1]);
+        let other_addr = IpAddr::from([10, 0, 0, 2]);
+        let loopback_addr = IpAddr::from([127, 0, 0, 1]);
+        let mut config = VirtualStakeConfig {
+            stake: 0,
+            include_loopback: true,
+            addrs: HashSet::from([trusted_addr]),
+        };
+        assert_eq!(config.get_stake(&loopback_addr), None);
+        assert_eq!(config.get_stake(&trusted_addr), None);
+
+        config.stake = 42;
+        assert_eq!(config.get_stake(&loopback_addr), Some(42));
+        assert_eq!(config.get_stake(&trusted_addr), Some(42));
+        assert_eq!(config.get_stake(&other_addr), None);
+
+        config.include_loopback = false;
+        assert_eq!(config.get_stake(&loopback_addr), None);
+    }
+
     #[test]
     fn test_quic_server_exit() {
         let (t, exit, _receiver, _server_address) = setup_quic_server();
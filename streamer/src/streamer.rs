@@ -3,6 +3,8 @@
 
 use {
     crate::{
+        bandwidth_accounting::{self, Subsystem},
+        impairment,
         packet::{self, PacketBatch, PacketBatchRecycler, PACKETS_PER_BATCH},
         sendmmsg::{batch_send, SendPktsError},
         socket::SocketAddrSpace,
@@ -97,12 +99,15 @@ pub struct StreamerReceiveStats {
     pub full_packet_batches_count: AtomicUsize,
     pub max_channel_len: AtomicUsize,
     pub num_packets_dropped: AtomicUsize,
+    /// The subsystem the received bytes are attributed to
+    pub subsystem: Subsystem,
 }
 
 impl StreamerReceiveStats {
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
+            subsystem: Subsystem::Other,
             packets_count: AtomicUsize::default(),
             packet_batches_count: AtomicUsize::default(),
             full_packet_batches_count: AtomicUsize::default(),
@@ -111,6 +116,11 @@ impl StreamerReceiveStats {
         }
     }
 
+    pub fn with_subsystem(mut self, subsystem: Subsystem) -> Self {
+        self.subsystem = subsystem;
+        self
+    }
+
     pub fn report(&self) {
         datapoint_info!(
             self.name,
@@ -148,7 +158,7 @@ pub type Result<T> = std::result::Result<T, StreamerError>;
 fn recv_loop(
     socket: &UdpSocket,
     exit: &AtomicBool,
-    packet_batch_sender: &impl ChannelSend<PacketBatch>,
+    packet_batch_sender: &(impl ChannelSend<PacketBatch> + Clone),
     recycler: &PacketBatchRecycler,
     stats: &StreamerReceiveStats,
     coalesce: Option<Duration>,
@@ -156,6 +166,7 @@ fn recv_loop(
     in_vote_only_mode: Option<Arc<AtomicBool>>,
     is_staked_service: bool,
 ) -> Result<()> {
+    let local_addr = socket.local_addr()?;
     loop {
         let mut packet_batch = if use_pinned_memory {
             PacketBatch::new_with_recycler(recycler, PACKETS_PER_BATCH, stats.name)
@@ -192,9 +203,18 @@ fn recv_loop(
                     if len == PACKETS_PER_BATCH {
                         full_packet_batches_count.fetch_add(1, Ordering::Relaxed);
                     }
+                    let num_bytes = packet_batch.iter().map(|p| p.meta().size).sum();
+                    bandwidth_accounting::record_ingress(stats.subsystem, len, num_bytes);
                     packet_batch
                         .iter_mut()
                         .for_each(|p| p.meta_mut().set_from_staked_node(is_staked_service));
+                    let Some(packet_batch) = impairment::impair_packet_batch(
+                        local_addr,
+                        packet_batch,
+                        packet_batch_sender,
+                    ) else {
+                        break;
+                    };
                     match packet_batch_sender.try_send(packet_batch) {
                         Ok(_) => {}
                         Err(TrySendError::Full(_)) => {
@@ -216,7 +236,7 @@ pub fn receiver(
     thread_name: String,
     socket: Arc<UdpSocket>,
     exit: Arc<AtomicBool>,
-    packet_batch_sender: impl ChannelSend<PacketBatch>,
+    packet_batch_sender: impl ChannelSend<PacketBatch> + Clone,
     recycler: PacketBatchRecycler,
     stats: Arc<StreamerReceiveStats>,
     coalesce: Option<Duration>,
@@ -420,6 +440,7 @@ fn recv_send(
     sock: &UdpSocket,
     r: &PacketBatchReceiver,
     socket_addr_space: &SocketAddrSpace,
+    subsystem: Subsystem,
     stats: &mut Option<StreamerSendStats>,
 ) -> Result<()> {
     let timer = Duration::new(1, 0);
@@ -432,7 +453,10 @@ fn recv_send(
         let data = pkt.data(..)?;
         socket_addr_space.check(&addr).then_some((data, addr))
     });
-    batch_send(sock, packets.collect::<Vec<_>>())?;
+    let packets: Vec<_> = packets.collect();
+    let num_bytes = packets.iter().map(|(data, _)| data.len()).sum();
+    bandwidth_accounting::record_egress(subsystem, packets.len(), num_bytes);
+    batch_send(sock, packets)?;
     Ok(())
 }
 
@@ -461,6 +485,7 @@ pub fn recv_packet_batches(
 
 pub fn responder(
     name: &'static str,
+    subsystem: Subsystem,
     sock: Arc<UdpSocket>,
     r: PacketBatchReceiver,
     socket_addr_space: SocketAddrSpace,
@@ -479,7 +504,7 @@ pub fn responder(
             }
 
             loop {
-                if let Err(e) = recv_send(&sock, &r, &socket_addr_space, &mut stats) {
+                if let Err(e) = recv_send(&sock, &r, &socket_addr_space, subsystem, &mut stats) {
                     match e {
                         StreamerError::RecvTimeout(RecvTimeoutError::Disconnected) => break,
                         StreamerError::RecvTimeout(RecvTimeoutError::Timeout) => (),
@@ -575,6 +600,7 @@ mod test {
             let (s_responder, r_responder) = unbounded();
             let t_responder = responder(
                 "SendTest",
+                Subsystem::Other,
                 Arc::new(send),
                 r_responder,
                 SocketAddrSpace::Unspecified,
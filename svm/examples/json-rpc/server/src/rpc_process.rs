@@ -332,6 +332,7 @@ impl JsonRpcRequestProcessor {
                     enable_cpi_recording,
                     enable_log_recording: true,
                     enable_return_data_recording: true,
+                    enable_instruction_units_recording: false,
                 },
             },
         );
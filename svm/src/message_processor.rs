@@ -12,12 +12,15 @@ use {
 /// For each instruction it calls the program entrypoint method and verifies that the result of
 /// the call does not violate the bank's accounting rules.
 /// The accounts are committed back to the bank only if every instruction succeeds.
+/// The compute units consumed by each instruction are pushed to `instruction_consumed_units`, if
+/// given.
 pub(crate) fn process_message(
     message: &impl SVMMessage,
     program_indices: &[Vec<IndexOfAccount>],
     invoke_context: &mut InvokeContext,
     execute_timings: &mut ExecuteTimings,
     accumulated_consumed_units: &mut u64,
+    mut instruction_consumed_units: Option<&mut Vec<u64>>,
 ) -> Result<(), TransactionError> {
     debug_assert_eq!(program_indices.len(), message.num_instructions());
     for (top_level_instruction_index, ((program_id, instruction), program_indices)) in message
@@ -70,6 +73,9 @@ pub(crate) fn process_message(
 
         *accumulated_consumed_units =
             accumulated_consumed_units.saturating_add(compute_units_consumed);
+        if let Some(instruction_consumed_units) = instruction_consumed_units.as_mut() {
+            instruction_consumed_units.push(compute_units_consumed);
+        }
         execute_timings.details.accumulate_program(
             program_id,
             process_instruction_us,
@@ -253,14 +259,17 @@ mod tests {
             SVMTransactionExecutionBudget::default(),
             SVMTransactionExecutionCost::default(),
         );
+        let mut instruction_consumed_units = vec![];
         let result = process_message(
             &message,
             &program_indices,
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            Some(&mut instruction_consumed_units),
         );
         assert!(result.is_ok());
+        assert_eq!(instruction_consumed_units, vec![1]);
         assert_eq!(
             transaction_context
                 .accounts()
@@ -313,6 +322,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
         assert_eq!(
             result,
@@ -357,6 +367,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
         assert_eq!(
             result,
@@ -493,6 +504,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
         assert_eq!(
             result,
@@ -532,6 +544,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
         assert!(result.is_ok());
 
@@ -568,6 +581,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -694,6 +708,7 @@ mod tests {
             &mut invoke_context,
             &mut ExecuteTimings::default(),
             &mut 0,
+            None,
         );
 
         assert_eq!(
@@ -39,6 +39,9 @@ pub struct TransactionExecutionDetails {
     pub inner_instructions: Option<InnerInstructionsList>,
     pub return_data: Option<TransactionReturnData>,
     pub executed_units: u64,
+    /// The compute units consumed by each top level instruction executed, up
+    /// to the one which failed, if recorded
+    pub instruction_executed_units: Option<Vec<u64>>,
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
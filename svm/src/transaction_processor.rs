@@ -84,6 +84,7 @@ pub struct ExecutionRecordingConfig {
     pub enable_cpi_recording: bool,
     pub enable_log_recording: bool,
     pub enable_return_data_recording: bool,
+    pub enable_instruction_units_recording: bool,
 }
 
 impl ExecutionRecordingConfig {
@@ -92,6 +93,7 @@ impl ExecutionRecordingConfig {
             enable_return_data_recording: option,
             enable_log_recording: option,
             enable_cpi_recording: option,
+            enable_instruction_units_recording: option,
         }
     }
 }
@@ -726,7 +728,8 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
 
                 let program_to_store = program_to_load.map(|(key, count)| {
                     // Load, verify and compile one program.
-                    let program = load_program_with_pubkey(
+                    let jit_compile_us = execute_timings.details.create_executor_jit_compile_us;
+                    let (program, load_us) = measure_us!(load_program_with_pubkey(
                         callback,
                         &program_cache.get_environments_for_epoch(self.epoch),
                         &key,
@@ -734,9 +737,11 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                         execute_timings,
                         false,
                     )
-                    .expect("called load_program_with_pubkey() with nonexistent account");
+                    .expect("called load_program_with_pubkey() with nonexistent account"));
+                    let jit_compile_us =
+                        (execute_timings.details.create_executor_jit_compile_us - jit_compile_us).0;
                     program.tx_usage_counter.store(count, Ordering::Relaxed);
-                    (key, program)
+                    (key, program, load_us, jit_compile_us)
                 });
 
                 let task_waiter = Arc::clone(&program_cache.loading_task_waiter);
@@ -744,9 +749,12 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 // Unlock the global cache again.
             };
 
-            if let Some((key, program)) = program_to_store {
+            if let Some((key, program, load_us, jit_compile_us)) = program_to_store {
                 loaded_programs_for_txs.as_mut().unwrap().loaded_missing = true;
                 let mut program_cache = self.program_cache.write().unwrap();
+                program_cache
+                    .stats
+                    .record_load(key, load_us, jit_compile_us);
                 // Submit our last completed loading task.
                 if program_cache.finish_cooperative_loading_task(self.slot, key, program)
                     && limit_to_load_programs
@@ -843,6 +851,10 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         };
 
         let mut executed_units = 0u64;
+        let mut instruction_executed_units = config
+            .recording_config
+            .enable_instruction_units_recording
+            .then(|| Vec::with_capacity(tx.num_instructions()));
         let sysvar_cache = &self.sysvar_cache.read().unwrap();
 
         let mut invoke_context = InvokeContext::new(
@@ -867,6 +879,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             &mut invoke_context,
             execute_timings,
             &mut executed_units,
+            instruction_executed_units.as_mut(),
         );
         process_message_time.stop();
 
@@ -952,6 +965,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 inner_instructions,
                 return_data,
                 executed_units,
+                instruction_executed_units,
                 accounts_data_len_delta,
             },
             loaded_transaction,
@@ -257,6 +257,7 @@ fn svm_concurrent() {
                     enable_log_recording: true,
                     enable_return_data_recording: false,
                     enable_cpi_recording: false,
+                    enable_instruction_units_recording: false,
                 },
                 ..Default::default()
             };
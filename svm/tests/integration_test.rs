@@ -104,6 +104,7 @@ impl SvmTestEnvironment<'_> {
                 enable_log_recording: true,
                 enable_return_data_recording: true,
                 enable_cpi_recording: false,
+                enable_instruction_units_recording: false,
             },
             ..Default::default()
         };
@@ -0,0 +1,252 @@
+//! Named branches of the ledger of a test validator, to save the state an
+//! expensive setup left the ledger in and later reset the ledger to it, or
+//! fork several ledgers from it.
+//!
+//! A branch is a copy of the ledger, taken while no validator runs on it,
+//! under the `branches` directory of the ledger. The blockstore, genesis,
+//! keypairs, tower and snapshot archives of the ledger are copied, so that a
+//! validator started on a ledger restored from the branch resumes where the
+//! ledger was when the branch was saved. The accounts and the bank snapshots
+//! are left out, as the bank snapshots link to the accounts of the ledger they
+//! were taken in: the validator rebuilds them from the snapshot archives and
+//! the blockstore.
+
+use {
+    solana_runtime::snapshot_utils::TMP_SNAPSHOT_ARCHIVE_PREFIX,
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// The directory of a ledger which holds its branches
+pub const BRANCHES_DIR: &str = "branches";
+
+/// The entries of a ledger which are kept when it is reset to a branch
+const PRESERVED_ENTRIES: &[&str] = &[BRANCHES_DIR, "ledger.lock"];
+
+/// The entries of a ledger which are not copied to its branches
+const EXCLUDED_ENTRIES: &[&str] = &[
+    BRANCHES_DIR,
+    "ledger.lock",
+    "admin.rpc",
+    "accounts",
+    "snapshot",
+    "validator.log",
+];
+
+pub fn branches_dir(ledger_path: &Path) -> PathBuf {
+    ledger_path.join(BRANCHES_DIR)
+}
+
+fn branch_path(ledger_path: &Path, name: &str) -> io::Result<PathBuf> {
+    if name.is_empty()
+        || name.starts_with('.')
+        || name.contains(|c| std::path::is_separator(c) || c == '\0')
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid branch name: {name:?}"),
+        ));
+    }
+    Ok(branches_dir(ledger_path).join(name))
+}
+
+fn is_excluded(file_name: &str) -> bool {
+    EXCLUDED_ENTRIES.contains(&file_name)
+        || file_name.starts_with(TMP_SNAPSHOT_ARCHIVE_PREFIX)
+        || (file_name.starts_with("validator-") && file_name.ends_with(".log"))
+}
+
+/// Copies the regular files and directories under `from` to `to`, including
+/// the top level entries of `from` `include` returns true for
+fn copy_dir(from: &Path, to: &Path, include: impl Fn(&str) -> bool) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if !include(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &to.join(entry.file_name()), |_| true)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Saves the ledger at `ledger_path` into the branch `name`, in place of the
+/// branch of the same name saved before, if any.
+///
+/// No validator may run on the ledger while the branch is saved.
+///
+/// Returns the path of the branch.
+pub fn save_ledger_branch(ledger_path: &Path, name: &str) -> io::Result<PathBuf> {
+    let branch_path = branch_path(ledger_path, name)?;
+    if !crate::TestValidatorGenesis::ledger_exists(ledger_path) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no test validator ledger at {}", ledger_path.display()),
+        ));
+    }
+
+    // The branch is copied aside first, for a failed save to leave the branch
+    // saved before intact
+    let tmp_branch_path = branches_dir(ledger_path).join(format!(".{name}.tmp"));
+    if tmp_branch_path.exists() {
+        fs::remove_dir_all(&tmp_branch_path)?;
+    }
+    copy_dir(ledger_path, &tmp_branch_path, |file_name| {
+        !is_excluded(file_name)
+    })?;
+    if branch_path.exists() {
+        fs::remove_dir_all(&branch_path)?;
+    }
+    fs::rename(&tmp_branch_path, &branch_path)?;
+    Ok(branch_path)
+}
+
+/// Returns the names of the branches saved of the ledger at `ledger_path`, in
+/// alphabetical order
+pub fn ledger_branches(ledger_path: &Path) -> io::Result<Vec<String>> {
+    let branches_dir = branches_dir(ledger_path);
+    if !branches_dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut names = vec![];
+    for entry in fs::read_dir(branches_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_dir() && !name.starts_with('.') {
+            names.push(name);
+        }
+    }
+    names.sort_unstable();
+    Ok(names)
+}
+
+/// Resets the ledger at `ledger_path` to the branch `name` of the ledger at
+/// `branch_ledger_path`, which is the ledger itself to go back to one of its
+/// branches, or another ledger to fork the ledger from one of its branches.
+///
+/// The branches of the ledger at `ledger_path` are kept. No validator may run
+/// on the ledger while it is reset.
+pub fn restore_ledger_branch(
+    branch_ledger_path: &Path,
+    name: &str,
+    ledger_path: &Path,
+) -> io::Result<()> {
+    let branch_path = branch_path(branch_ledger_path, name)?;
+    if !branch_path.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no branch {name:?} of the ledger at {}",
+                branch_ledger_path.display()
+            ),
+        ));
+    }
+
+    fs::create_dir_all(ledger_path)?;
+    for entry in fs::read_dir(ledger_path)? {
+        let entry = entry?;
+        if PRESERVED_ENTRIES.contains(&&*entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    copy_dir(&branch_path, ledger_path, |_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::TestValidatorGenesis,
+        solana_sdk::{
+            commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair,
+            system_transaction,
+        },
+    };
+
+    #[test]
+    fn test_branch_name() {
+        let ledger_path = Path::new("ledger");
+        assert_eq!(
+            branch_path(ledger_path, "setup").unwrap(),
+            ledger_path.join(BRANCHES_DIR).join("setup")
+        );
+        for name in ["", ".", "..", ".hidden", "a/b"] {
+            assert_eq!(
+                branch_path(ledger_path, name).unwrap_err().kind(),
+                io::ErrorKind::InvalidInput
+            );
+        }
+    }
+
+    #[test]
+    fn test_save_and_restore_ledger_branch() {
+        let ledger_dir = tempfile::tempdir().unwrap();
+        let ledger_path = ledger_dir.path().join("ledger");
+        let fork_path = ledger_dir.path().join("fork");
+        let recipient = Pubkey::new_unique();
+
+        let transfer = |payer: &Keypair, lamports| {
+            let (test_validator, _) = TestValidatorGenesis::default()
+                .ledger_path(&ledger_path)
+                .start();
+            let rpc_client = test_validator.get_rpc_client();
+            let blockhash = rpc_client.get_latest_blockhash().unwrap();
+            rpc_client
+                .send_and_confirm_transaction_with_spinner_and_commitment(
+                    &system_transaction::transfer(payer, &recipient, lamports, blockhash),
+                    CommitmentConfig::finalized(),
+                )
+                .unwrap();
+        };
+        let balance = |ledger_path: &Path| {
+            let (test_validator, _) = TestValidatorGenesis::default()
+                .ledger_path(ledger_path)
+                .start();
+            test_validator
+                .get_rpc_client()
+                .get_balance_with_commitment(&recipient, CommitmentConfig::processed())
+                .unwrap()
+                .value
+        };
+
+        let (test_validator, payer) = TestValidatorGenesis::default()
+            .ledger_path(&ledger_path)
+            .start();
+        drop(test_validator);
+        transfer(&payer, 1_000_000);
+        assert!(save_ledger_branch(&ledger_path, "setup")
+            .unwrap()
+            .join("genesis.bin")
+            .exists());
+        assert_eq!(ledger_branches(&ledger_path).unwrap(), vec!["setup"]);
+
+        // The ledger goes on from the branch and is later reset to it.
+        transfer(&payer, 1_000_000);
+        assert_eq!(balance(&ledger_path), 2_000_000);
+        restore_ledger_branch(&ledger_path, "setup", &ledger_path).unwrap();
+        assert_eq!(balance(&ledger_path), 1_000_000);
+        assert_eq!(ledger_branches(&ledger_path).unwrap(), vec!["setup"]);
+
+        // Another ledger is forked from the branch.
+        restore_ledger_branch(&ledger_path, "setup", &fork_path).unwrap();
+        assert_eq!(balance(&fork_path), 1_000_000);
+        assert_eq!(
+            restore_ledger_branch(&ledger_path, "missing", &fork_path)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod ledger_branch;
+
 use {
     agave_feature_set::{FeatureSet, FEATURE_NAMES},
     base64::{prelude::BASE64_STANDARD, Engine},
@@ -33,6 +35,7 @@ use {
     solana_rpc_client::{nonblocking, rpc_client::RpcClient},
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
     solana_runtime::{
+        bank::missing_accounts::{AccountSource, MissingAccountLoader},
         bank_forks::BankForks,
         genesis_utils::{self, create_genesis_config_with_leader_ex_no_features},
         runtime_config::RuntimeConfig,
@@ -40,6 +43,7 @@ use {
     },
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
+        address_lookup_table::{self, state::AddressLookupTable},
         bpf_loader_upgradeable::UpgradeableLoaderState,
         clock::{Slot, DEFAULT_MS_PER_SLOT},
         commitment_config::CommitmentConfig,
@@ -65,6 +69,7 @@ use {
         path::{Path, PathBuf},
         str::FromStr,
         sync::{Arc, RwLock},
+        thread,
         time::Duration,
     },
     tokio::time::sleep,
@@ -134,6 +139,7 @@ pub struct TestValidatorGenesis {
     pub tpu_enable_udp: bool,
     pub geyser_plugin_manager: Arc<RwLock<GeyserPluginManager>>,
     admin_rpc_service_post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
+    clone_on_demand_rpc_url: Option<String>,
 }
 
 impl Default for TestValidatorGenesis {
@@ -167,6 +173,7 @@ impl Default for TestValidatorGenesis {
             geyser_plugin_manager: Arc::new(RwLock::new(GeyserPluginManager::new())),
             admin_rpc_service_post_init:
                 Arc::<RwLock<Option<AdminRpcRequestMetadataPostInit>>>::default(),
+            clone_on_demand_rpc_url: Option::<String>::default(),
         }
     }
 }
@@ -207,6 +214,72 @@ fn try_transform_program_data(
     }
 }
 
+fn try_transform_lookup_table(
+    address: &Pubkey,
+    account: &mut AccountSharedData,
+) -> Result<(), String> {
+    if account.owner() != &address_lookup_table::program::id() {
+        return Err(format!(
+            "Account {address} not owned by address lookup table program"
+        ));
+    }
+    let mut meta = AddressLookupTable::deserialize(account.data())
+        .map_err(|_| format!("Failed to read address lookup table account {address}"))?
+        .meta;
+    // Overwrite the slot the table was last extended at to `0`, for all of
+    // its addresses to be active
+    meta.last_extended_slot = 0;
+    AddressLookupTable::overwrite_meta_data(account.data_as_mut_slice(), meta)
+        .map_err(|_| format!("Failed to write to address lookup table account {address}"))
+}
+
+/// Clones the accounts missing from the ledger from a cluster as the
+/// transactions first access them
+struct RpcAccountSource {
+    rpc_client: RpcClient,
+}
+
+impl RpcAccountSource {
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut accounts = vec![];
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            info!("Fetching {:?} on demand over RPC...", chunk);
+            let responses = match self.rpc_client.get_multiple_accounts(chunk) {
+                Ok(responses) => responses,
+                Err(err) => {
+                    warn!("Failed to fetch {:?}: {err}", chunk);
+                    continue;
+                }
+            };
+            for (address, account) in chunk.iter().zip(responses) {
+                let Some(account) = account else {
+                    continue;
+                };
+                let mut account_shared_data = AccountSharedData::from(account);
+                // ignore the errors, the account is cloned as is then
+                try_transform_program_data(address, &mut account_shared_data)
+                    .or_else(|_| try_transform_lookup_table(address, &mut account_shared_data))
+                    .ok();
+                accounts.push((*address, account_shared_data));
+            }
+        }
+        accounts
+    }
+}
+
+impl AccountSource for RpcAccountSource {
+    fn get_accounts(&self, addresses: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)> {
+        // The accounts are fetched from the threads of the RPC service too,
+        // which the blocking client can't be used from
+        thread::scope(|scope| {
+            scope
+                .spawn(|| self.fetch_accounts(addresses))
+                .join()
+                .unwrap()
+        })
+    }
+}
+
 impl TestValidatorGenesis {
     /// Adds features to deactivate to a set, eliminating redundancies
     /// during `initialize_ledger`, if member of the set is not a Feature
@@ -426,6 +499,15 @@ impl TestValidatorGenesis {
         Ok(self)
     }
 
+    /// Clones the accounts missing from the ledger from the cluster of
+    /// `rpc_url` as the transactions first access them, along with the
+    /// programs owning them, the executable data of the upgradeable programs
+    /// and the address lookup tables of the transactions
+    pub fn clone_accounts_on_demand(&mut self, rpc_url: String) -> &mut Self {
+        self.clone_on_demand_rpc_url = Some(rpc_url);
+        self
+    }
+
     pub fn clone_feature_set(&mut self, rpc_client: &RpcClient) -> Result<&mut Self, String> {
         for feature_ids in FEATURE_NAMES
             .keys()
@@ -1032,7 +1114,7 @@ impl TestValidator {
             validator_config.tower_storage = tower_storage.clone();
         }
 
-        let validator = Some(Validator::new(
+        let validator = Validator::new(
             node,
             Arc::new(validator_identity),
             &ledger_path,
@@ -1046,7 +1128,18 @@ impl TestValidator {
             socket_addr_space,
             ValidatorTpuConfig::new_for_tests(config.tpu_enable_udp),
             config.admin_rpc_service_post_init.clone(),
-        )?);
+        )?;
+        if let Some(rpc_url) = &config.clone_on_demand_rpc_url {
+            let source = RpcAccountSource {
+                rpc_client: RpcClient::new(rpc_url.clone()),
+            };
+            validator
+                .bank_forks
+                .read()
+                .unwrap()
+                .root_bank()
+                .set_missing_account_loader(Arc::new(MissingAccountLoader::new(Box::new(source))));
+        }
 
         let test_validator = TestValidator {
             ledger_path,
@@ -1055,7 +1148,7 @@ impl TestValidator {
             rpc_url,
             tpu,
             gossip,
-            validator,
+            validator: Some(validator),
             vote_account_address,
         };
         Ok(test_validator)
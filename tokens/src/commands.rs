@@ -2626,6 +2626,7 @@ mod tests {
                 status: Ok(()),
                 err: None,
                 confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                provenance: None,
             })],
             &mut confirmations,
         )
@@ -2646,6 +2647,7 @@ mod tests {
                 status: Ok(()),
                 err: None,
                 confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                provenance: None,
             })],
             &mut confirmations,
         )
@@ -311,6 +311,7 @@ mod tests {
             err: None,
             status: Ok(()),
             confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+            provenance: None,
         };
         assert_eq!(
             update_finalized_transaction(&mut db, &signature, Some(transaction_status), 0, 0)
@@ -339,6 +340,7 @@ mod tests {
             err: Some(TransactionError::AccountNotFound),
             status: Ok(()),
             confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+            provenance: None,
         };
         assert_eq!(
             update_finalized_transaction(&mut db, &signature, Some(transaction_status), 0, 0)
@@ -364,6 +366,7 @@ mod tests {
             err: None,
             status: Ok(()),
             confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+            provenance: None,
         };
         assert_eq!(
             update_finalized_transaction(&mut db, &signature, Some(transaction_status), 0, 0)
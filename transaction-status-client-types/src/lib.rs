@@ -621,6 +621,28 @@ pub struct TransactionStatus {
     pub status: TransactionResult<()>, // legacy field
     pub err: Option<TransactionError>,
     pub confirmation_status: Option<TransactionConfirmationStatus>,
+    /// Where and when the node serving the request ingested the transaction,
+    /// if it produced the block and was tracking packet provenance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<TransactionProvenance>,
+}
+
+/// Ingest timeline of a transaction through a leader's TPU pipeline
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionProvenance {
+    /// Address of the peer the transaction packet was received from
+    pub remote_addr: String,
+    /// Milliseconds since the UNIX epoch at which the banking stage received
+    /// the transaction packet
+    pub received_at: u64,
+    /// Microseconds from receipt until the transaction was buffered by the
+    /// scheduler
+    pub buffer_latency_us: u64,
+    /// Microseconds from receipt until the transaction was committed
+    pub commit_latency_us: u64,
+    /// Slot in which the transaction was committed
+    pub commit_slot: u64,
 }
 
 impl TransactionStatus {
@@ -684,6 +706,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+            provenance: None,
         };
 
         assert!(status.satisfies_commitment(CommitmentConfig::finalized()));
@@ -696,6 +719,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+            provenance: None,
         };
 
         assert!(!status.satisfies_commitment(CommitmentConfig::finalized()));
@@ -708,6 +732,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: Some(TransactionConfirmationStatus::Processed),
+            provenance: None,
         };
 
         assert!(!status.satisfies_commitment(CommitmentConfig::finalized()));
@@ -720,6 +745,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: None,
+            provenance: None,
         };
 
         assert!(!status.satisfies_commitment(CommitmentConfig::finalized()));
@@ -733,6 +759,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: None,
+            provenance: None,
         };
         assert!(!status.satisfies_commitment(CommitmentConfig::confirmed()));
 
@@ -742,6 +769,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: None,
+            provenance: None,
         };
         assert!(status.satisfies_commitment(CommitmentConfig::confirmed()));
 
@@ -751,6 +779,7 @@ mod test {
             status: Ok(()),
             err: None,
             confirmation_status: None,
+            provenance: None,
         };
         assert!(status.satisfies_commitment(CommitmentConfig::confirmed()));
     }
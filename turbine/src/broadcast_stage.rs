@@ -8,7 +8,11 @@ use {
         fail_entry_verification_broadcast_run::FailEntryVerificationBroadcastRun,
         standard_broadcast_run::StandardBroadcastRun,
     },
-    crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache},
+    crate::{
+        cluster_nodes::{self, ClusterNodes, ClusterNodesCache},
+        fec_ratio::FecRatio,
+        remote_shred_signer::RemoteShredSigner,
+    },
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, RecvError, RecvTimeoutError, Sender},
     itertools::{Either, Itertools},
@@ -31,6 +35,7 @@ use {
         timing::{timestamp, AtomicInterval},
     },
     solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
         sendmmsg::{batch_send, SendPktsError},
         socket::SocketAddrSpace,
     },
@@ -121,6 +126,8 @@ impl BroadcastStageType {
         bank_forks: Arc<RwLock<BankForks>>,
         shred_version: u16,
         quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        remote_shred_signer: Option<Arc<RemoteShredSigner>>,
+        fec_ratio: Option<Arc<FecRatio>>,
     ) -> BroadcastStage {
         match self {
             BroadcastStageType::Standard => BroadcastStage::new(
@@ -132,7 +139,9 @@ impl BroadcastStageType {
                 blockstore,
                 bank_forks,
                 quic_endpoint_sender,
-                StandardBroadcastRun::new(shred_version),
+                StandardBroadcastRun::new(shred_version)
+                    .with_remote_shred_signer(remote_shred_signer)
+                    .with_fec_ratio(fec_ratio),
             ),
 
             BroadcastStageType::FailEntryVerification => BroadcastStage::new(
@@ -478,6 +487,16 @@ pub fn broadcast_shreds(
     shred_select.stop();
     transmit_stats.shred_select += shred_select.as_us();
     let num_udp_packets = packets.len();
+    let num_bytes = packets
+        .iter()
+        .chain(&quic_packets)
+        .map(|(shred, _)| shred.len())
+        .sum();
+    bandwidth_accounting::record_egress(
+        Subsystem::Turbine,
+        num_udp_packets + quic_packets.len(),
+        num_bytes,
+    );
     let mut send_mmsg_time = Measure::start("send_mmsg");
     match batch_send(s, packets) {
         Ok(()) => (),
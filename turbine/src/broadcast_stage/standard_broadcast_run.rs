@@ -5,13 +5,20 @@ use {
         broadcast_utils::{self, ReceiveResults},
         *,
     },
-    crate::cluster_nodes::ClusterNodesCache,
+    crate::{cluster_nodes::ClusterNodesCache, fec_ratio::FecRatio},
     solana_entry::entry::Entry,
     solana_ledger::{
         blockstore,
-        shred::{shred_code, ProcessShredsStats, ReedSolomonCache, Shred, ShredType, Shredder},
+        shred::{
+            shred_code, ProcessShredsStats, ReedSolomonCache, Shred, ShredType, Shredder,
+            CODING_SHREDS_PER_FEC_BLOCK,
+        },
+    },
+    solana_sdk::{
+        hash::Hash,
+        signature::{Keypair, Signer},
+        timing::AtomicInterval,
     },
-    solana_sdk::{hash::Hash, signature::Keypair, timing::AtomicInterval},
     std::{borrow::Cow, sync::RwLock},
     tokio::sync::mpsc::Sender as AsyncSender,
 };
@@ -36,6 +43,10 @@ pub struct StandardBroadcastRun {
     num_batches: usize,
     cluster_nodes_cache: Arc<ClusterNodesCache<BroadcastStage>>,
     reed_solomon_cache: Arc<ReedSolomonCache>,
+    // Signs the Merkle roots instead of the local identity keypair, if any.
+    remote_shred_signer: Option<Arc<RemoteShredSigner>>,
+    // The number of coding shreds of each erasure batch, if not the default.
+    fec_ratio: Option<Arc<FecRatio>>,
 }
 
 #[derive(Debug)]
@@ -66,6 +77,55 @@ impl StandardBroadcastRun {
             num_batches: 0,
             cluster_nodes_cache,
             reed_solomon_cache: Arc::<ReedSolomonCache>::default(),
+            remote_shred_signer: None,
+            fec_ratio: None,
+        }
+    }
+
+    pub(super) fn with_fec_ratio(mut self, fec_ratio: Option<Arc<FecRatio>>) -> Self {
+        self.fec_ratio = fec_ratio;
+        self
+    }
+
+    fn new_shredder(&self, reference_tick: u8) -> Shredder {
+        let num_coding_shreds = self
+            .fec_ratio
+            .as_ref()
+            .map(|fec_ratio| fec_ratio.num_coding_shreds(self.slot))
+            .unwrap_or(CODING_SHREDS_PER_FEC_BLOCK);
+        Shredder::new(self.slot, self.parent, reference_tick, self.shred_version)
+            .unwrap()
+            .with_num_coding_shreds(num_coding_shreds)
+    }
+
+    fn record_fec_ratio(&self, shreds: &[Shred]) {
+        if let Some(fec_ratio) = &self.fec_ratio {
+            let num_data_shreds = shreds.iter().filter(|shred| shred.is_data()).count();
+            let num_coding_shreds = shreds.len() - num_data_shreds;
+            fec_ratio.record_broadcast(self.slot, num_data_shreds, num_coding_shreds);
+        }
+    }
+
+    pub(super) fn with_remote_shred_signer(
+        mut self,
+        remote_shred_signer: Option<Arc<RemoteShredSigner>>,
+    ) -> Self {
+        self.remote_shred_signer = remote_shred_signer;
+        self
+    }
+
+    // Replaces the local signatures of the shreds with the ones of the remote
+    // signer, if any.
+    fn maybe_sign_remotely(&self, keypair: &Keypair, shreds: &mut [Shred]) {
+        let Some(remote_shred_signer) = &self.remote_shred_signer else {
+            return;
+        };
+        let num_fallbacks = remote_shred_signer.sign_shreds(&keypair.pubkey(), shreds);
+        if num_fallbacks > 0 {
+            warn!(
+                "{num_fallbacks} erasure batches of slot {} signed by the local keypair",
+                self.slot
+            );
         }
     }
 
@@ -83,21 +143,22 @@ impl StandardBroadcastRun {
         }
         // Set the reference_tick as if the PoH completed for this slot
         let reference_tick = max_ticks_in_slot;
-        let shreds: Vec<_> =
-            Shredder::new(self.slot, self.parent, reference_tick, self.shred_version)
-                .unwrap()
-                .make_merkle_shreds_from_entries(
-                    keypair,
-                    &[],  // entries
-                    true, // is_last_in_slot,
-                    Some(self.chained_merkle_root),
-                    self.next_shred_index,
-                    self.next_code_index,
-                    &self.reed_solomon_cache,
-                    stats,
-                )
-                .inspect(|shred| stats.record_shred(shred))
-                .collect();
+        let mut shreds: Vec<_> = self
+            .new_shredder(reference_tick)
+            .make_merkle_shreds_from_entries(
+                keypair,
+                &[],  // entries
+                true, // is_last_in_slot,
+                Some(self.chained_merkle_root),
+                self.next_shred_index,
+                self.next_code_index,
+                &self.reed_solomon_cache,
+                stats,
+            )
+            .inspect(|shred| stats.record_shred(shred))
+            .collect();
+        self.maybe_sign_remotely(keypair, &mut shreds);
+        self.record_fec_ratio(&shreds);
         if let Some(shred) = shreds.iter().max_by_key(|shred| shred.fec_set_index()) {
             self.chained_merkle_root = shred.merkle_root().unwrap();
         }
@@ -117,28 +178,29 @@ impl StandardBroadcastRun {
         max_data_shreds_per_slot: u32,
         max_code_shreds_per_slot: u32,
     ) -> std::result::Result<Vec<Shred>, BroadcastError> {
-        let shreds: Vec<_> =
-            Shredder::new(self.slot, self.parent, reference_tick, self.shred_version)
-                .unwrap()
-                .make_merkle_shreds_from_entries(
-                    keypair,
-                    entries,
-                    is_slot_end,
-                    Some(self.chained_merkle_root),
-                    self.next_shred_index,
-                    self.next_code_index,
-                    &self.reed_solomon_cache,
-                    process_stats,
-                )
-                .inspect(|shred| {
-                    process_stats.record_shred(shred);
-                    let next_index = match shred.shred_type() {
-                        ShredType::Code => &mut self.next_code_index,
-                        ShredType::Data => &mut self.next_shred_index,
-                    };
-                    *next_index = (*next_index).max(shred.index() + 1);
-                })
-                .collect();
+        let mut shreds: Vec<_> = self
+            .new_shredder(reference_tick)
+            .make_merkle_shreds_from_entries(
+                keypair,
+                entries,
+                is_slot_end,
+                Some(self.chained_merkle_root),
+                self.next_shred_index,
+                self.next_code_index,
+                &self.reed_solomon_cache,
+                process_stats,
+            )
+            .inspect(|shred| {
+                process_stats.record_shred(shred);
+                let next_index = match shred.shred_type() {
+                    ShredType::Code => &mut self.next_code_index,
+                    ShredType::Data => &mut self.next_shred_index,
+                };
+                *next_index = (*next_index).max(shred.index() + 1);
+            })
+            .collect();
+        self.maybe_sign_remotely(keypair, &mut shreds);
+        self.record_fec_ratio(&shreds);
         if let Some(shred) = shreds.iter().max_by_key(|shred| shred.fec_set_index()) {
             self.chained_merkle_root = shred.merkle_root().unwrap();
         }
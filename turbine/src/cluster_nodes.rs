@@ -97,6 +97,21 @@ pub struct ClusterNodes<T> {
 // on the entire cache.
 type LruCacheOnce<K, V> = RwLock<LruCache<K, Arc<OnceLock<V>>>>;
 
+/// The turbine retransmit tree of a shred, limited to the staked nodes.
+///
+/// Unstaked nodes are shuffled after all staked nodes, and their positions
+/// depend on the contact-infos in each node's gossip table. The positions of
+/// the staked nodes only depend on the epoch stakes, the slot leader and the
+/// shred, so they can be computed offline.
+pub struct RetransmitTree {
+    fanout: usize,
+    // Staked nodes other than the slot leader, in the order of the shuffle.
+    // The first node is the root of the tree.
+    nodes: Vec<(Pubkey, /*stake:*/ u64)>,
+    // Reverse index from nodes pubkey to their index in self.nodes.
+    index: HashMap<Pubkey, /*index:*/ usize>,
+}
+
 pub struct ClusterNodesCache<T> {
     cache: LruCacheOnce<Epoch, (/*as of:*/ Instant, Arc<ClusterNodes<T>>)>,
     ttl: Duration, // Time to live.
@@ -300,6 +315,78 @@ impl ClusterNodes<RetransmitStage> {
     }
 }
 
+impl RetransmitTree {
+    pub fn new(
+        slot_leader: &Pubkey,
+        shred: &ShredId,
+        fanout: usize,
+        stakes: &HashMap<Pubkey, u64>,
+    ) -> Self {
+        let mut nodes: Vec<Node> = stakes
+            .iter()
+            .filter(|(_, stake)| **stake > 0)
+            .map(|(&pubkey, &stake)| Node {
+                node: NodeId::from(pubkey),
+                stake,
+            })
+            .collect();
+        // Same order of nodes as in new_cluster_nodes, where the staked nodes
+        // precede the unstaked ones.
+        sort_and_dedup_nodes(&mut nodes);
+        let stakes = nodes.iter().map(|node| node.stake);
+        let mut weighted_shuffle = WeightedShuffle::new("retransmit-tree", stakes);
+        if let Some(index) = nodes.iter().position(|node| node.pubkey() == slot_leader) {
+            weighted_shuffle.remove_index(index);
+        }
+        let mut rng = get_seeded_rng(slot_leader, shred);
+        let nodes: Vec<_> = weighted_shuffle
+            .shuffle(&mut rng)
+            .map(|index| (*nodes[index].pubkey(), nodes[index].stake))
+            .collect();
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (pubkey, _))| (*pubkey, index))
+            .collect();
+        Self {
+            fanout,
+            nodes,
+            index,
+        }
+    }
+
+    /// Returns the staked nodes and their stakes in the order of the shuffle.
+    pub fn nodes(&self) -> &[(Pubkey, /*stake:*/ u64)] {
+        &self.nodes
+    }
+
+    /// Returns the index of the node within the shuffle, or None if the node
+    /// is not staked or is the slot leader.
+    pub fn get_index(&self, pubkey: &Pubkey) -> Option<usize> {
+        self.index.get(pubkey).copied()
+    }
+
+    /// Returns the number of hops from the root of the tree to the node.
+    pub fn get_root_distance(&self, index: usize) -> u8 {
+        get_root_distance(index, self.fanout)
+    }
+
+    /// Returns the index of the node which retransmits shreds to the given
+    /// node, or None if the node is the root of the tree.
+    pub fn get_parent(&self, index: usize) -> Option<usize> {
+        let (parent, _) = get_retransmit_parent(self.fanout, index, &self.nodes)?;
+        self.get_index(&parent)
+    }
+
+    /// Returns the indices of the staked nodes which the given node
+    /// retransmits shreds to. Unstaked nodes which the node retransmits
+    /// shreds to are not included.
+    pub fn get_children(&self, index: usize) -> Vec<usize> {
+        let (_, children) = get_retransmit_peers(self.fanout, |k| k == index, 0..self.nodes.len());
+        children.collect()
+    }
+}
+
 pub fn new_cluster_nodes<T: 'static>(
     cluster_info: &ClusterInfo,
     cluster_type: ClusterType,
@@ -670,7 +757,7 @@ pub fn make_test_cluster<R: Rng>(
     (nodes, stakes, cluster_info)
 }
 
-pub(crate) fn get_data_plane_fanout(shred_slot: Slot, root_bank: &Bank) -> usize {
+pub fn get_data_plane_fanout(shred_slot: Slot, root_bank: &Bank) -> usize {
     if check_feature_activation(
         &feature_set::disable_turbine_fanout_experiments::id(),
         shred_slot,
@@ -728,6 +815,7 @@ mod tests {
     use {
         super::*,
         itertools::Itertools,
+        solana_ledger::shred::ShredType,
         std::{fmt::Debug, hash::Hash},
         test_case::test_case,
     };
@@ -771,6 +859,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retransmit_tree() {
+        let mut rng = rand::thread_rng();
+        let (_nodes, stakes, cluster_info) = make_test_cluster(&mut rng, 1_000, None);
+        let cluster_nodes =
+            new_cluster_nodes::<RetransmitStage>(&cluster_info, ClusterType::Development, &stakes);
+        let slot_leader = cluster_nodes.nodes[1].pubkey();
+        let shred = ShredId::new(rng.gen(), rng.gen(), ShredType::Data);
+        let tree = RetransmitTree::new(slot_leader, &shred, DATA_PLANE_FANOUT, &stakes);
+        assert!(tree.get_index(slot_leader).is_none());
+        // Staked nodes are in the same order as in the shuffle done when
+        // retransmitting the shred.
+        let shuffle: Vec<_> = {
+            let mut weighted_shuffle = cluster_nodes.weighted_shuffle.clone();
+            weighted_shuffle.remove_index(cluster_nodes.index[slot_leader]);
+            let mut rng = get_seeded_rng(slot_leader, &shred);
+            weighted_shuffle
+                .shuffle(&mut rng)
+                .map(|k| *cluster_nodes.nodes[k].pubkey())
+                .take(tree.nodes().len())
+                .collect()
+        };
+        assert_eq!(
+            tree.nodes().iter().map(|(pubkey, _)| *pubkey).collect_vec(),
+            shuffle
+        );
+        // Parents and children are consistent with each other.
+        assert_eq!(tree.get_parent(0), None);
+        assert_eq!(tree.get_root_distance(0), 0);
+        for index in 0..tree.nodes().len() {
+            for child in tree.get_children(index) {
+                assert_eq!(tree.get_parent(child), Some(index));
+                assert_eq!(
+                    tree.get_root_distance(child),
+                    tree.get_root_distance(index) + 1
+                );
+            }
+        }
+        let num_children: usize = (0..tree.nodes().len())
+            .map(|index| tree.get_children(index).len())
+            .sum();
+        assert_eq!(num_children, tree.nodes().len() - 1);
+    }
+
     #[test]
     fn test_cluster_nodes_broadcast() {
         let mut rng = rand::thread_rng();
@@ -0,0 +1,167 @@
+//! Shaping of the retransmit egress, which caps the bandwidth of the shreds
+//! the node retransmits to its children in the turbine tree.
+//!
+//! On links which cannot sustain the retransmit fanout, sending all shreds
+//! overflows the socket buffers, and the kernel drops shreds regardless of
+//! their value. The shaper instead drops the shreds of the least valuable
+//! priority class first: once the bucket of tokens runs low, shreds of old
+//! slots are no longer retransmitted, then shreds of recent slots the node
+//! is not the first hop of. Within a shred, the children earlier in the
+//! turbine tree are sent to first, since they retransmit the shred further.
+
+use {
+    solana_sdk::{clock::Slot, packet::PACKET_DATA_SIZE},
+    std::{sync::Mutex, time::Instant},
+};
+
+/// Shreds of slots this far behind the working bank or more are of the
+/// `Low` priority class.
+pub const RECENT_SLOTS: Slot = 32;
+// Lower bursts would starve the lower priority classes entirely, as their
+// reserved share of the burst leaves less than a shred.
+const MIN_BURST_BYTES: u64 = 4 * PACKET_DATA_SIZE as u64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EgressShaperConfig {
+    /// The sustained egress rate
+    pub max_bytes_per_sec: u64,
+    /// The bytes which can be sent in a burst above the sustained rate
+    pub burst_bytes: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriorityClass {
+    /// Shreds of old slots, which the cluster has most likely moved past
+    Low,
+    /// Shreds of recent slots received from a node upstream in the tree
+    Medium,
+    /// Shreds of recent slots received from the leader
+    High,
+}
+
+impl PriorityClass {
+    pub fn new(slot: Slot, working_slot: Slot, root_distance: u8) -> Self {
+        if working_slot.saturating_sub(slot) >= RECENT_SLOTS {
+            Self::Low
+        } else if root_distance == 0 {
+            Self::High
+        } else {
+            Self::Medium
+        }
+    }
+
+    // The share of the burst, in percent, which is kept for the higher
+    // priority classes.
+    fn reserved_percent(&self) -> u64 {
+        match self {
+            Self::High => 0,
+            Self::Medium => 25,
+            Self::Low => 50,
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: u64,
+    refilled: Instant,
+}
+
+pub struct EgressShaper {
+    config: EgressShaperConfig,
+    bucket: Mutex<TokenBucket>,
+}
+
+impl EgressShaper {
+    pub fn new(mut config: EgressShaperConfig) -> Self {
+        config.burst_bytes = config.burst_bytes.max(MIN_BURST_BYTES);
+        Self {
+            config,
+            bucket: Mutex::new(TokenBucket {
+                tokens: config.burst_bytes,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Returns how many of the `num_addrs` children, from the earliest in
+    /// the turbine tree, a shred of `shred_size` bytes and of `class` may be
+    /// sent to.
+    pub fn admit(&self, class: PriorityClass, shred_size: usize, num_addrs: usize) -> usize {
+        self.admit_at(Instant::now(), class, shred_size, num_addrs)
+    }
+
+    fn admit_at(
+        &self,
+        now: Instant,
+        class: PriorityClass,
+        shred_size: usize,
+        num_addrs: usize,
+    ) -> usize {
+        if shred_size == 0 {
+            return num_addrs;
+        }
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed_us = now.saturating_duration_since(bucket.refilled).as_micros() as u64;
+        let refill = self.config.max_bytes_per_sec.saturating_mul(elapsed_us) / 1_000_000;
+        // Only advance the refill time once at least a token is refilled, so
+        // that frequent calls still refill the bucket.
+        if refill > 0 {
+            bucket.tokens = bucket
+                .tokens
+                .saturating_add(refill)
+                .min(self.config.burst_bytes);
+            bucket.refilled = now;
+        }
+        let reserved = self.config.burst_bytes * class.reserved_percent() / 100;
+        let available = bucket.tokens.saturating_sub(reserved);
+        let admitted = (available / shred_size as u64).min(num_addrs as u64) as usize;
+        bucket.tokens -= (admitted * shred_size) as u64;
+        admitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[test]
+    fn test_priority_class() {
+        assert_eq!(PriorityClass::new(100, 100, 0), PriorityClass::High);
+        assert_eq!(PriorityClass::new(100, 110, 1), PriorityClass::Medium);
+        assert_eq!(PriorityClass::new(100, 132, 0), PriorityClass::Low);
+        // Shreds of slots past the working bank are recent.
+        assert_eq!(PriorityClass::new(120, 100, 0), PriorityClass::High);
+    }
+
+    #[test]
+    fn test_egress_shaper() {
+        let shaper = EgressShaper::new(EgressShaperConfig {
+            max_bytes_per_sec: 100_000,
+            burst_bytes: 10_000,
+        });
+        assert_eq!(shaper.config.burst_bytes, 10_000);
+        let start = shaper.bucket.lock().unwrap().refilled;
+        // Low priority shreds leave half of the burst to the others.
+        assert_eq!(shaper.admit_at(start, PriorityClass::Low, 1_000, 8), 5);
+        assert_eq!(shaper.admit_at(start, PriorityClass::Low, 1_000, 8), 0);
+        // Medium priority shreds leave a quarter of the burst.
+        assert_eq!(shaper.admit_at(start, PriorityClass::Medium, 1_000, 8), 2);
+        assert_eq!(shaper.admit_at(start, PriorityClass::High, 1_000, 8), 3);
+        assert_eq!(shaper.admit_at(start, PriorityClass::High, 1_000, 8), 0);
+
+        // 10ms refill 1_000 bytes.
+        let now = start + Duration::from_millis(10);
+        assert_eq!(shaper.admit_at(now, PriorityClass::Medium, 1_000, 8), 0);
+        assert_eq!(shaper.admit_at(now, PriorityClass::High, 1_000, 8), 1);
+
+        // The bucket does not fill past the burst.
+        let now = start + Duration::from_secs(10);
+        assert_eq!(shaper.admit_at(now, PriorityClass::High, 1_000, 200), 10);
+
+        let shaper = EgressShaper::new(EgressShaperConfig {
+            max_bytes_per_sec: 100_000,
+            burst_bytes: 0,
+        });
+        assert_eq!(shaper.config.burst_bytes, MIN_BURST_BYTES);
+    }
+}
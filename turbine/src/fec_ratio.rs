@@ -0,0 +1,175 @@
+//! The number of coding shreds the leader generates for each erasure batch of
+//! `DATA_SHREDS_PER_FEC_BLOCK` data shreds, which trades the bandwidth of the
+//! broadcast against the resilience of the blocks to packet losses.
+//!
+//! In the experiment mode, the leader slots rotate through a list of numbers
+//! of coding shreds. For each leader slot, the shreds broadcast and the
+//! repair requests then served for the slot, which are those of the nodes
+//! which could not recover it from turbine, are reported once the slot is
+//! old enough that no more repairs are expected.
+
+use {
+    solana_ledger::shred::{CODING_SHREDS_PER_FEC_BLOCK, MIN_CODING_SHREDS_PER_FEC_BLOCK},
+    solana_sdk::clock::Slot,
+    std::{collections::BTreeMap, sync::Mutex},
+};
+
+// Slots are reported once this far behind the latest leader slot.
+const REPORT_DELAY_SLOTS: Slot = 128;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FecRatioConfig {
+    /// The coding shreds of each erasure batch
+    pub num_coding_shreds: usize,
+    /// If not empty, the leader slots rotate through these numbers of coding
+    /// shreds instead
+    pub experiment: Vec<usize>,
+}
+
+impl Default for FecRatioConfig {
+    fn default() -> Self {
+        Self {
+            num_coding_shreds: CODING_SHREDS_PER_FEC_BLOCK,
+            experiment: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct SlotPropagation {
+    num_coding_shreds_per_batch: usize,
+    num_data_shreds: usize,
+    num_coding_shreds: usize,
+    num_repair_requests: usize,
+}
+
+pub struct FecRatio {
+    config: FecRatioConfig,
+    // The leader slots which are not yet reported.
+    slots: Mutex<BTreeMap<Slot, SlotPropagation>>,
+}
+
+impl FecRatio {
+    pub fn new(mut config: FecRatioConfig) -> Self {
+        let clamp = |num_coding_shreds: usize| {
+            num_coding_shreds.clamp(MIN_CODING_SHREDS_PER_FEC_BLOCK, CODING_SHREDS_PER_FEC_BLOCK)
+        };
+        config.num_coding_shreds = clamp(config.num_coding_shreds);
+        config.experiment.iter_mut().for_each(|n| *n = clamp(*n));
+        Self {
+            config,
+            slots: Mutex::default(),
+        }
+    }
+
+    pub fn is_experiment(&self) -> bool {
+        !self.config.experiment.is_empty()
+    }
+
+    /// The coding shreds of each erasure batch of the leader slot
+    pub fn num_coding_shreds(&self, slot: Slot) -> usize {
+        let experiment = &self.config.experiment;
+        if experiment.is_empty() {
+            self.config.num_coding_shreds
+        } else {
+            experiment[(slot % experiment.len() as Slot) as usize]
+        }
+    }
+
+    /// Records the shreds broadcast for the leader slot
+    pub fn record_broadcast(&self, slot: Slot, num_data_shreds: usize, num_coding_shreds: usize) {
+        if !self.is_experiment() {
+            return;
+        }
+        let mut slots = self.slots.lock().unwrap();
+        let entry = slots.entry(slot).or_insert_with(|| SlotPropagation {
+            num_coding_shreds_per_batch: self.num_coding_shreds(slot),
+            ..SlotPropagation::default()
+        });
+        entry.num_data_shreds += num_data_shreds;
+        entry.num_coding_shreds += num_coding_shreds;
+        let reported = slots.split_off(&slot.saturating_sub(REPORT_DELAY_SLOTS));
+        let reported = std::mem::replace(&mut *slots, reported);
+        drop(slots);
+        for (slot, propagation) in reported {
+            Self::report(slot, &propagation);
+        }
+    }
+
+    /// Records a repair request served for `slot`, which is ignored unless
+    /// the slot is a leader slot of the experiment
+    pub fn record_repair_request(&self, slot: Slot) {
+        if !self.is_experiment() {
+            return;
+        }
+        if let Some(entry) = self.slots.lock().unwrap().get_mut(&slot) {
+            entry.num_repair_requests += 1;
+        }
+    }
+
+    fn report(slot: Slot, propagation: &SlotPropagation) {
+        datapoint_info!(
+            "broadcast-fec-experiment",
+            ("slot", slot, i64),
+            (
+                "num_coding_shreds_per_batch",
+                propagation.num_coding_shreds_per_batch,
+                i64
+            ),
+            ("num_data_shreds", propagation.num_data_shreds, i64),
+            ("num_coding_shreds", propagation.num_coding_shreds, i64),
+            ("num_repair_requests", propagation.num_repair_requests, i64),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fec_ratio() {
+        let fec_ratio = FecRatio::new(FecRatioConfig {
+            num_coding_shreds: 100,
+            experiment: Vec::new(),
+        });
+        assert!(!fec_ratio.is_experiment());
+        assert_eq!(fec_ratio.num_coding_shreds(7), CODING_SHREDS_PER_FEC_BLOCK);
+        fec_ratio.record_broadcast(7, 32, 32);
+        assert!(fec_ratio.slots.lock().unwrap().is_empty());
+
+        let fec_ratio = FecRatio::new(FecRatioConfig {
+            num_coding_shreds: CODING_SHREDS_PER_FEC_BLOCK,
+            experiment: vec![0, 16, 32],
+        });
+        assert!(fec_ratio.is_experiment());
+        let num_coding_shreds: Vec<_> = (9..13)
+            .map(|slot| fec_ratio.num_coding_shreds(slot))
+            .collect();
+        assert_eq!(num_coding_shreds, [1, 16, 32, 1]);
+
+        fec_ratio.record_broadcast(10, 32, 16);
+        fec_ratio.record_broadcast(10, 64, 48);
+        fec_ratio.record_repair_request(10);
+        // Not a leader slot.
+        fec_ratio.record_repair_request(11);
+        assert_eq!(
+            fec_ratio.slots.lock().unwrap()[&10],
+            SlotPropagation {
+                num_coding_shreds_per_batch: 16,
+                num_data_shreds: 96,
+                num_coding_shreds: 64,
+                num_repair_requests: 1,
+            }
+        );
+        // Slot 10 is reported once old enough.
+        fec_ratio.record_broadcast(10 + REPORT_DELAY_SLOTS, 32, 32);
+        assert!(fec_ratio.slots.lock().unwrap().contains_key(&10));
+        fec_ratio.record_broadcast(11 + REPORT_DELAY_SLOTS, 32, 32);
+        let slots = fec_ratio.slots.lock().unwrap();
+        assert_eq!(
+            slots.keys().copied().collect::<Vec<_>>(),
+            [10 + REPORT_DELAY_SLOTS, 11 + REPORT_DELAY_SLOTS]
+        );
+    }
+}
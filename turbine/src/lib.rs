@@ -3,8 +3,12 @@
 mod addr_cache;
 pub mod broadcast_stage;
 pub mod cluster_nodes;
+pub mod egress_shaper;
+pub mod fec_ratio;
 pub mod quic_endpoint;
+pub mod remote_shred_signer;
 pub mod retransmit_stage;
+pub mod shredstream;
 pub mod sigverify_shreds;
 pub mod xdp;
 
@@ -0,0 +1,333 @@
+//! Signing of the Merkle roots of the shreds the node broadcasts as leader by
+//! a remote signer over HTTP, such as one holding the identity key in an HSM.
+//!
+//! For each erasure batch, the signer is sent the Merkle root:
+//! - `POST {url}/{node_pubkey}/sign`, with the 32 bytes Merkle root as the
+//!   body, returns the 64 bytes signature of the root by the node identity.
+//!
+//! The roots of the erasure batches of a broadcast batch are signed in
+//! parallel within a latency budget, so that a slow or unreachable signer
+//! delays the block by at most the budget. The batches the signer did not
+//! sign in time, or for which it returned an invalid signature, keep the
+//! signature of the local identity keypair.
+
+use {
+    rayon::{prelude::*, ThreadPool, ThreadPoolBuilder},
+    solana_ledger::shred::Shred,
+    solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Signature, SIGNATURE_BYTES},
+        timing::AtomicInterval,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        sync::atomic::{AtomicU64, Ordering},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+pub const DEFAULT_REMOTE_SHRED_SIGNER_LATENCY_BUDGET: Duration = Duration::from_millis(50);
+const NUM_SIGNER_THREADS: usize = 8;
+const STATS_SUBMIT_INTERVAL_MS: u64 = 2_000;
+
+#[derive(Error, Debug)]
+pub enum RemoteShredSignerError {
+    #[error("failed to build the http client: {0}")]
+    HttpClient(#[from] reqwest::Error),
+
+    #[error("failed to build the signer thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteShredSignerConfig {
+    pub url: String,
+    /// The time the signatures of a broadcast batch are waited for
+    pub latency_budget: Duration,
+}
+
+#[derive(Default)]
+struct RemoteShredSignerStats {
+    num_signed: AtomicU64,
+    num_timeouts: AtomicU64,
+    num_errors: AtomicU64,
+    num_invalid_signatures: AtomicU64,
+    max_elapsed_us: AtomicU64,
+}
+
+pub struct RemoteShredSigner {
+    url: String,
+    latency_budget: Duration,
+    client: reqwest::blocking::Client,
+    thread_pool: ThreadPool,
+    stats: RemoteShredSignerStats,
+    last_stats_submit: AtomicInterval,
+}
+
+impl RemoteShredSigner {
+    pub fn new(config: RemoteShredSignerConfig) -> Result<Self, RemoteShredSignerError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(config.latency_budget)
+            .build()?;
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(NUM_SIGNER_THREADS)
+            .thread_name(|i| format!("solShredSign{i:02}"))
+            .build()?;
+        Ok(Self {
+            url: config.url.trim_end_matches('/').to_string(),
+            latency_budget: config.latency_budget,
+            client,
+            thread_pool,
+            stats: RemoteShredSignerStats::default(),
+            last_stats_submit: AtomicInterval::default(),
+        })
+    }
+
+    /// Replaces the signatures of `shreds` with the ones of the remote
+    /// signer for `node_pubkey`. Returns the number of erasure batches which
+    /// kept the local signatures.
+    pub fn sign_shreds(&self, node_pubkey: &Pubkey, shreds: &mut [Shred]) -> usize {
+        let start = Instant::now();
+        let roots: Vec<Hash> = shreds
+            .iter()
+            .filter_map(|shred| shred.merkle_root().ok())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let signatures: HashMap<Hash, Signature> = self.thread_pool.install(|| {
+            roots
+                .par_iter()
+                .filter_map(|root| {
+                    let signature = self.sign_root(start, node_pubkey, root)?;
+                    Some((*root, signature))
+                })
+                .collect()
+        });
+        for shred in shreds.iter_mut() {
+            if let Some(signature) = shred
+                .merkle_root()
+                .ok()
+                .and_then(|root| signatures.get(&root))
+            {
+                shred.set_signature(*signature);
+            }
+        }
+        let num_fallbacks = roots.len() - signatures.len();
+        self.stats
+            .num_signed
+            .fetch_add(signatures.len() as u64, Ordering::Relaxed);
+        self.stats
+            .max_elapsed_us
+            .fetch_max(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+        self.maybe_submit_stats();
+        num_fallbacks
+    }
+
+    // Returns None if the signer did not return a valid signature within the
+    // latency budget from start.
+    fn sign_root(&self, start: Instant, node_pubkey: &Pubkey, root: &Hash) -> Option<Signature> {
+        let Some(remaining) = self.latency_budget.checked_sub(start.elapsed()) else {
+            self.stats.num_timeouts.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let response = self
+            .client
+            .post(format!("{}/{node_pubkey}/sign", self.url))
+            .timeout(remaining)
+            .body(root.to_bytes().to_vec())
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .and_then(reqwest::blocking::Response::bytes);
+        let signature = match response {
+            Ok(bytes) => <[u8; SIGNATURE_BYTES]>::try_from(bytes.as_ref())
+                .ok()
+                .map(Signature::from),
+            Err(err) if err.is_timeout() => {
+                self.stats.num_timeouts.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            Err(err) => {
+                warn!("Remote shred signer failed to sign {root}: {err}");
+                self.stats.num_errors.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+        match signature {
+            Some(signature) if signature.verify(node_pubkey.as_ref(), root.as_ref()) => {
+                Some(signature)
+            }
+            _ => {
+                error!("Remote shred signer returned an invalid signature of {root}");
+                self.stats
+                    .num_invalid_signatures
+                    .fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn maybe_submit_stats(&self) {
+        if !self
+            .last_stats_submit
+            .should_update(STATS_SUBMIT_INTERVAL_MS)
+        {
+            return;
+        }
+        let stats = &self.stats;
+        datapoint_info!(
+            "remote_shred_signer",
+            (
+                "num_signed",
+                stats.num_signed.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_timeouts",
+                stats.num_timeouts.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_errors",
+                stats.num_errors.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_invalid_signatures",
+                stats.num_invalid_signatures.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "max_elapsed_us",
+                stats.max_elapsed_us.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_entry::entry::Entry,
+        solana_ledger::shred::{ProcessShredsStats, ReedSolomonCache, Shredder},
+        solana_sdk::signature::{Keypair, Signer},
+        std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+        },
+    };
+
+    // Signs the Merkle roots with keypair.
+    fn spawn_remote_shred_signer(keypair: Keypair) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = BufReader::new(stream.unwrap());
+                let mut line = String::new();
+                let mut content_length = 0;
+                loop {
+                    line.clear();
+                    stream.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.trim_end().split_once(": ") {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                stream.read_exact(&mut body).unwrap();
+                let signature = keypair.sign_message(&body);
+                let mut stream = stream.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: {SIGNATURE_BYTES}\r\nconnection: \
+                     close\r\n\r\n"
+                )
+                .unwrap();
+                stream.write_all(signature.as_ref()).unwrap();
+            }
+        });
+        url
+    }
+
+    fn make_shreds(keypair: &Keypair) -> Vec<Shred> {
+        let entries: Vec<_> = (0..200)
+            .map(|_| Entry::new(&Hash::new_unique(), 1, vec![]))
+            .collect();
+        Shredder::new(2, 1, 0, 0)
+            .unwrap()
+            .make_merkle_shreds_from_entries(
+                keypair,
+                &entries,
+                true, // is_last_in_slot
+                Some(Hash::default()),
+                0, // next_shred_index
+                0, // next_code_index
+                &ReedSolomonCache::default(),
+                &mut ProcessShredsStats::default(),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn test_remote_shred_signer() {
+        let identity = Keypair::new();
+        let url = spawn_remote_shred_signer(identity.insecure_clone());
+        let signer = RemoteShredSigner::new(RemoteShredSignerConfig {
+            url,
+            latency_budget: Duration::from_secs(5),
+        })
+        .unwrap();
+        // The shreds are signed locally by another keypair, which the remote
+        // signatures replace.
+        let mut shreds = make_shreds(&Keypair::new());
+        assert!(!shreds.iter().any(|shred| shred.verify(&identity.pubkey())));
+        assert_eq!(signer.sign_shreds(&identity.pubkey(), &mut shreds), 0);
+        assert!(shreds.iter().all(|shred| shred.verify(&identity.pubkey())));
+    }
+
+    #[test]
+    fn test_remote_shred_signer_fallback() {
+        let identity = Keypair::new();
+        // The signer signs with another key than the identity.
+        let url = spawn_remote_shred_signer(Keypair::new());
+        let signer = RemoteShredSigner::new(RemoteShredSignerConfig {
+            url,
+            latency_budget: Duration::from_secs(5),
+        })
+        .unwrap();
+        let mut shreds = make_shreds(&identity);
+        let num_batches = shreds
+            .iter()
+            .map(|shred| shred.merkle_root().unwrap())
+            .collect::<HashSet<_>>()
+            .len();
+        assert_eq!(
+            signer.sign_shreds(&identity.pubkey(), &mut shreds),
+            num_batches
+        );
+        assert!(shreds.iter().all(|shred| shred.verify(&identity.pubkey())));
+
+        // Nothing listens on the port, so the local signatures are kept too.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let signer = RemoteShredSigner::new(RemoteShredSignerConfig {
+            url,
+            latency_budget: Duration::from_millis(500),
+        })
+        .unwrap();
+        assert_eq!(
+            signer.sign_shreds(&identity.pubkey(), &mut shreds),
+            num_batches
+        );
+        assert!(shreds.iter().all(|shred| shred.verify(&identity.pubkey())));
+    }
+}
@@ -4,6 +4,8 @@ use {
     crate::{
         addr_cache::AddrCache,
         cluster_nodes::{self, ClusterNodes, ClusterNodesCache, Error, MAX_NUM_TURBINE_HOPS},
+        egress_shaper::{EgressShaper, EgressShaperConfig, PriorityClass},
+        shredstream::{Shredstream, ShredstreamConfig},
         xdp::{XdpConfig, XdpRetransmitter, XdpSender},
     },
     bytes::Bytes,
@@ -29,6 +31,7 @@ use {
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, timing::timestamp},
     solana_streamer::{
+        bandwidth_accounting::{self, Subsystem},
         sendmmsg::{multi_target_send, SendPktsError},
         socket::SocketAddrSpace,
     },
@@ -96,6 +99,7 @@ struct RetransmitStats {
     addr_cache_miss: AtomicUsize,
     num_nodes: AtomicUsize,
     num_addrs_failed: AtomicUsize,
+    num_addrs_shaped: AtomicUsize,
     num_shreds_dropped_xdp_full: AtomicUsize,
     num_loopback_errs: AtomicUsize,
     num_shreds: usize,
@@ -135,6 +139,7 @@ impl RetransmitStats {
             ("num_small_batches", self.num_small_batches, i64),
             ("num_nodes", *self.num_nodes.get_mut(), i64),
             ("num_addrs_failed", *self.num_addrs_failed.get_mut(), i64),
+            ("num_addrs_shaped", *self.num_addrs_shaped.get_mut(), i64),
             (
                 "num_shreds_dropped_xdp_full",
                 *self.num_shreds_dropped_xdp_full.get_mut(),
@@ -229,6 +234,8 @@ fn retransmit(
     retransmit_sockets: &[UdpSocket],
     quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
     xdp_sender: Option<&XdpSender>,
+    egress_shaper: Option<&EgressShaper>,
+    shredstream: Option<&Shredstream>,
     stats: &mut RetransmitStats,
     cluster_nodes_cache: &ClusterNodesCache<RetransmitStage>,
     addr_cache: &mut AddrCache,
@@ -314,12 +321,15 @@ fn retransmit(
         retransmit_shred(
             shred,
             &root_bank,
+            working_bank.slot(),
             shred_deduper,
             &cache,
             addr_cache,
             socket_addr_space,
             socket,
             quic_endpoint_sender,
+            egress_shaper,
+            shredstream,
             stats,
         )
     };
@@ -371,12 +381,15 @@ fn retransmit(
 fn retransmit_shred(
     shred: shred::Payload,
     root_bank: &Bank,
+    working_slot: Slot,
     shred_deduper: &ShredDeduper,
     cache: &HashMap<Slot, (/*leader:*/ Pubkey, Arc<ClusterNodes<RetransmitStage>>)>,
     addr_cache: &AddrCache,
     socket_addr_space: &SocketAddrSpace,
     socket: RetransmitSocket<'_>,
     quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+    egress_shaper: Option<&EgressShaper>,
+    shredstream: Option<&Shredstream>,
     stats: &RetransmitStats,
 ) -> Option<RetransmitShredOutput> {
     let key = shred::layout::get_shred_id(shred.as_ref())?;
@@ -386,6 +399,9 @@ fn retransmit_shred(
         stats.num_shreds_skipped.fetch_add(1, Ordering::Relaxed);
         return None;
     }
+    if let Some(shredstream) = shredstream {
+        shredstream.send(&key, shred.as_ref(), quic_endpoint_sender);
+    }
     let mut compute_turbine_peers = Measure::start("turbine_start");
     let (root_distance, addrs) =
         get_retransmit_addrs(&key, root_bank, cache, addr_cache, socket_addr_space, stats)?;
@@ -397,11 +413,24 @@ fn retransmit_shred(
         .map(|flags| flags.contains(ShredFlags::LAST_SHRED_IN_SLOT))
         .unwrap_or_default();
     let mut retransmit_time = Measure::start("retransmit_to");
-    let num_addrs = addrs.len();
+    let shred_size = shred.len();
+    // The children earlier in the turbine tree are first in addrs.
+    let send_addrs = match egress_shaper {
+        None => &addrs[..],
+        Some(egress_shaper) => {
+            let class = PriorityClass::new(key.slot(), working_slot, root_distance);
+            let num_admitted = egress_shaper.admit(class, shred_size, addrs.len());
+            stats
+                .num_addrs_shaped
+                .fetch_add(addrs.len() - num_admitted, Ordering::Relaxed);
+            &addrs[..num_admitted]
+        }
+    };
+    let num_addrs = send_addrs.len();
     let num_nodes = match cluster_nodes::get_broadcast_protocol(&key) {
         Protocol::QUIC => {
             let shred = Bytes::from(shred::Payload::unwrap_or_clone(shred));
-            addrs
+            send_addrs
                 .iter()
                 .filter_map(|&addr| quic_endpoint_sender.try_send((addr, shred.clone())).ok())
                 .count()
@@ -410,7 +439,7 @@ fn retransmit_shred(
             RetransmitSocket::Xdp(sender) => {
                 let mut sent = num_addrs;
                 if num_addrs > 0 {
-                    if let Err(e) = sender.try_send(key.index(), addrs.to_vec(), shred) {
+                    if let Err(e) = sender.try_send(key.index(), send_addrs.to_vec(), shred) {
                         log::warn!("xdp channel full: {e:?}");
                         stats
                             .num_shreds_dropped_xdp_full
@@ -420,16 +449,19 @@ fn retransmit_shred(
                 }
                 sent
             }
-            RetransmitSocket::Socket(socket) => match multi_target_send(socket, shred, &addrs) {
-                Ok(()) => num_addrs,
-                Err(SendPktsError::IoError(ioerr, num_failed)) => {
-                    error!("retransmit_to multi_target_send error: {ioerr:?}, {num_failed}/{} packets failed", num_addrs);
-                    num_addrs - num_failed
+            RetransmitSocket::Socket(socket) => {
+                match multi_target_send(socket, shred, send_addrs) {
+                    Ok(()) => num_addrs,
+                    Err(SendPktsError::IoError(ioerr, num_failed)) => {
+                        error!("retransmit_to multi_target_send error: {ioerr:?}, {num_failed}/{} packets failed", num_addrs);
+                        num_addrs - num_failed
+                    }
                 }
-            },
+            }
         },
     };
     retransmit_time.stop();
+    bandwidth_accounting::record_egress(Subsystem::Turbine, num_nodes, num_nodes * shred_size);
     stats
         .num_addrs_failed
         .fetch_add(num_addrs - num_nodes, Ordering::Relaxed);
@@ -555,6 +587,8 @@ impl RetransmitStage {
     /// * `leader_schedule_cache` - The leader schedule to verify shreds
     /// * `cluster_info` - This structure needs to be updated and populated by the bank and via gossip.
     /// * `retransmit_receiver` - Receive channel for batches of shreds to be retransmitted.
+    /// * `egress_shaper_config` - Caps the bandwidth of the retransmitted shreds, if any.
+    /// * `shredstream_config` - The subscribers the received shreds are forwarded to, if any.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         bank_forks: Arc<RwLock<BankForks>>,
@@ -567,6 +601,8 @@ impl RetransmitStage {
         rpc_subscriptions: Option<Arc<RpcSubscriptions>>,
         slot_status_notifier: Option<SlotStatusNotifier>,
         xdp_config: Option<XdpConfig>,
+        egress_shaper_config: Option<EgressShaperConfig>,
+        shredstream_config: Option<ShredstreamConfig>,
     ) -> Self {
         let cluster_nodes_cache = ClusterNodesCache::<RetransmitStage>::new(
             CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
@@ -576,6 +612,8 @@ impl RetransmitStage {
         let mut stats = RetransmitStats::new(Instant::now());
         let mut addr_cache = AddrCache::with_capacity(/*capacity:*/ 4);
         let mut shred_deduper = ShredDeduper::new(&mut rng, DEDUPER_NUM_BITS);
+        let egress_shaper = egress_shaper_config.map(EgressShaper::new);
+        let shredstream = shredstream_config.map(Shredstream::new);
 
         let thread_pool = {
             let num_threads = retransmit_sockets.len();
@@ -611,6 +649,8 @@ impl RetransmitStage {
                         &retransmit_sockets,
                         &quic_endpoint_sender,
                         xdp_sender.as_ref(),
+                        egress_shaper.as_ref(),
+                        shredstream.as_ref(),
                         &mut stats,
                         &cluster_nodes_cache,
                         &mut addr_cache,
@@ -681,6 +721,7 @@ impl RetransmitStats {
             addr_cache_miss: AtomicUsize::default(),
             num_nodes: AtomicUsize::default(),
             num_addrs_failed: AtomicUsize::default(),
+            num_addrs_shaped: AtomicUsize::default(),
             num_shreds_dropped_xdp_full: AtomicUsize::default(),
             num_loopback_errs: AtomicUsize::default(),
             num_shreds: 0usize,
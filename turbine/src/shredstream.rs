@@ -0,0 +1,250 @@
+//! Shredstream forwards the shreds the node receives from turbine and repair,
+//! and the ones it recovers, to a set of subscribers, so that low latency
+//! consumers such as block engines and indexers need not capture the turbine
+//! port.
+//!
+//! The shreds are sent as datagrams through the turbine QUIC endpoint, the
+//! same as the shreds retransmitted to the turbine children over QUIC, so a
+//! subscriber runs a turbine QUIC server. Each subscriber is sent the shreds
+//! matching its filter, once each, as the retransmit stage dedups them.
+//!
+//! Subscribers are given on the command line as:
+//!   `HOST:PORT[,min-slot=SLOT][,max-slot=SLOT][,data-only]`
+
+use {
+    bytes::Bytes,
+    solana_ledger::shred::{ShredId, ShredType},
+    solana_sdk::{clock::Slot, timing::AtomicInterval},
+    std::{
+        net::SocketAddr,
+        str::FromStr,
+        sync::atomic::{AtomicU64, Ordering},
+    },
+    thiserror::Error,
+    tokio::sync::mpsc::Sender as AsyncSender,
+};
+
+const STATS_SUBMIT_INTERVAL_MS: u64 = 2_000;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseShredstreamSubscriberError {
+    #[error("invalid subscriber address: {0}")]
+    InvalidAddress(String),
+
+    #[error("invalid subscriber filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("min-slot {min_slot} is greater than max-slot {max_slot}")]
+    InvalidSlotRange { min_slot: Slot, max_slot: Slot },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShredstreamFilter {
+    /// The lowest slot of the shreds sent, if any
+    pub min_slot: Option<Slot>,
+    /// The highest slot of the shreds sent, if any
+    pub max_slot: Option<Slot>,
+    /// Whether only the data shreds are sent, without the coding shreds
+    pub data_shreds_only: bool,
+}
+
+impl ShredstreamFilter {
+    fn matches(&self, slot: Slot, shred_type: ShredType) -> bool {
+        self.min_slot.is_none_or(|min_slot| slot >= min_slot)
+            && self.max_slot.is_none_or(|max_slot| slot <= max_slot)
+            && (!self.data_shreds_only || shred_type == ShredType::Data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShredstreamSubscriber {
+    pub addr: SocketAddr,
+    pub filter: ShredstreamFilter,
+}
+
+impl FromStr for ShredstreamSubscriber {
+    type Err = ParseShredstreamSubscriberError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',');
+        let addr = parts.next().unwrap_or_default();
+        let addr = solana_net_utils::parse_host_port(addr)
+            .map_err(|_| ParseShredstreamSubscriberError::InvalidAddress(addr.to_string()))?;
+        let mut filter = ShredstreamFilter::default();
+        for part in parts {
+            let invalid_filter =
+                || ParseShredstreamSubscriberError::InvalidFilter(part.to_string());
+            match part.split_once('=') {
+                None if part == "data-only" => filter.data_shreds_only = true,
+                Some(("min-slot", slot)) => {
+                    filter.min_slot = Some(slot.parse().map_err(|_| invalid_filter())?);
+                }
+                Some(("max-slot", slot)) => {
+                    filter.max_slot = Some(slot.parse().map_err(|_| invalid_filter())?);
+                }
+                _ => return Err(invalid_filter()),
+            }
+        }
+        if let (Some(min_slot), Some(max_slot)) = (filter.min_slot, filter.max_slot) {
+            if min_slot > max_slot {
+                return Err(ParseShredstreamSubscriberError::InvalidSlotRange {
+                    min_slot,
+                    max_slot,
+                });
+            }
+        }
+        Ok(Self { addr, filter })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShredstreamConfig {
+    pub subscribers: Vec<ShredstreamSubscriber>,
+}
+
+#[derive(Default)]
+struct ShredstreamStats {
+    num_shreds_sent: AtomicU64,
+    // Shreds not sent because the QUIC endpoint channel is full.
+    num_shreds_dropped: AtomicU64,
+}
+
+pub struct Shredstream {
+    subscribers: Vec<ShredstreamSubscriber>,
+    stats: ShredstreamStats,
+    last_stats_submit: AtomicInterval,
+}
+
+impl Shredstream {
+    pub fn new(config: ShredstreamConfig) -> Self {
+        Self {
+            subscribers: config.subscribers,
+            stats: ShredstreamStats::default(),
+            last_stats_submit: AtomicInterval::default(),
+        }
+    }
+
+    /// Sends the shred to the subscribers whose filters it matches
+    pub fn send(
+        &self,
+        key: &ShredId,
+        shred: &[u8],
+        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+    ) {
+        let mut payload = None;
+        for subscriber in &self.subscribers {
+            if !subscriber.filter.matches(key.slot(), key.shred_type()) {
+                continue;
+            }
+            let payload = payload.get_or_insert_with(|| Bytes::copy_from_slice(shred));
+            let counter = match quic_endpoint_sender.try_send((subscriber.addr, payload.clone())) {
+                Ok(()) => &self.stats.num_shreds_sent,
+                Err(_) => &self.stats.num_shreds_dropped,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        self.maybe_submit_stats();
+    }
+
+    fn maybe_submit_stats(&self) {
+        if !self
+            .last_stats_submit
+            .should_update(STATS_SUBMIT_INTERVAL_MS)
+        {
+            return;
+        }
+        datapoint_info!(
+            "shredstream",
+            (
+                "num_shreds_sent",
+                self.stats.num_shreds_sent.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "num_shreds_dropped",
+                self.stats.num_shreds_dropped.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    #[test]
+    fn test_parse_shredstream_subscriber() {
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 8001));
+        assert_eq!(
+            "127.0.0.1:8001".parse(),
+            Ok(ShredstreamSubscriber {
+                addr,
+                filter: ShredstreamFilter::default(),
+            })
+        );
+        assert_eq!(
+            "127.0.0.1:8001,min-slot=10,max-slot=20,data-only".parse(),
+            Ok(ShredstreamSubscriber {
+                addr,
+                filter: ShredstreamFilter {
+                    min_slot: Some(10),
+                    max_slot: Some(20),
+                    data_shreds_only: true,
+                },
+            })
+        );
+        assert_eq!(
+            "127.0.0.1:8001,min-slot=x".parse::<ShredstreamSubscriber>(),
+            Err(ParseShredstreamSubscriberError::InvalidFilter(
+                "min-slot=x".to_string()
+            ))
+        );
+        assert_eq!(
+            "127.0.0.1:8001,coding-only".parse::<ShredstreamSubscriber>(),
+            Err(ParseShredstreamSubscriberError::InvalidFilter(
+                "coding-only".to_string()
+            ))
+        );
+        assert_eq!(
+            "127.0.0.1:8001,min-slot=20,max-slot=10".parse::<ShredstreamSubscriber>(),
+            Err(ParseShredstreamSubscriberError::InvalidSlotRange {
+                min_slot: 20,
+                max_slot: 10,
+            })
+        );
+        assert!(",data-only".parse::<ShredstreamSubscriber>().is_err());
+    }
+
+    #[test]
+    fn test_shredstream_send() {
+        let subscriber = |port: u16, filter| ShredstreamSubscriber {
+            addr: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+            filter,
+        };
+        let shredstream = Shredstream::new(ShredstreamConfig {
+            subscribers: vec![
+                subscriber(1, ShredstreamFilter::default()),
+                subscriber(
+                    2,
+                    ShredstreamFilter {
+                        min_slot: Some(5),
+                        max_slot: None,
+                        data_shreds_only: true,
+                    },
+                ),
+            ],
+        });
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(16);
+        let shred = [7u8; 16];
+        shredstream.send(&ShredId::new(4, 0, ShredType::Data), &shred, &sender);
+        shredstream.send(&ShredId::new(5, 0, ShredType::Code), &shred, &sender);
+        shredstream.send(&ShredId::new(5, 1, ShredType::Data), &shred, &sender);
+        let mut ports = Vec::new();
+        while let Ok((addr, payload)) = receiver.try_recv() {
+            assert_eq!(payload.as_ref(), shred);
+            ports.push(addr.port());
+        }
+        assert_eq!(ports, [1, 1, 1, 2]);
+    }
+}
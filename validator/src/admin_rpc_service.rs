@@ -1,4 +1,5 @@
 use {
+    base64::{prelude::BASE64_STANDARD, Engine},
     crossbeam_channel::Sender,
     jsonrpc_core::{BoxFuture, ErrorCode, MetaIoHandler, Metadata, Result},
     jsonrpc_core_client::{transports::ipc, RpcError},
@@ -8,27 +9,61 @@ use {
     },
     log::*,
     serde::{de::Deserializer, Deserialize, Serialize},
-    solana_accounts_db::accounts_index::AccountIndex,
+    solana_accounts_db::{
+        accounts_index::AccountIndex, ancient_packing_policy::AncientPackingPolicy,
+        owner_disk_usage::AccountsDiskUsage,
+    },
     solana_core::{
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::account_conflicts::AccountConflictCounts,
         consensus::{tower_storage::TowerStorage, Tower},
+        epoch_accounts_hash_scheduling::EpochAccountsHashStatus,
+        failover::{FailoverCoordinator, FailoverStatus},
+        forensic_bundles::ForensicBundleManifest,
+        identity_switch::{
+            self, StagedIdentityConfig, StagedIdentityContext, StagedIdentityReport,
+            DEFAULT_IDENTITY_OBSERVATION_MS,
+        },
+        leader_pause::LeaderPauseStatus,
+        program_precompilation::ProgramPrecompilationReport,
         repair::repair_service,
         validator::ValidatorStartProgress,
     },
     solana_geyser_plugin_manager::GeyserPluginManagerRequest,
-    solana_gossip::contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
+    solana_gossip::{
+        contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
+        crds_inspect::{
+            CrdsInspectFilter, CrdsInspectPage, CrdsValueInfo, CrdsValueType,
+            DEFAULT_CRDS_INSPECT_LIMIT,
+        },
+        duplicate_shred_proofs::DuplicateShredProofInfo,
+        peer_scores::PeerScore,
+    },
+    solana_poh::poh_calibration_service::PohCalibration,
     solana_rpc::rpc::verify_pubkey,
-    solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
+    solana_rpc_client_api::{
+        config::RpcAccountIndex, custom_error::RpcCustomError, response::RpcHealthDetail,
+    },
+    solana_runtime::{
+        bank::bank_hash_details::BankHashBreakdown,
+        snapshot_package::SnapshotKind,
+        snapshot_utils::{
+            SnapshotArchiveVerificationRecord, SnapshotPackagingJob, SnapshotPackagingStage,
+        },
+    },
     solana_sdk::{
+        clock::Slot,
         exit::Exit,
+        native_token::lamports_to_sol,
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
     },
+    solana_streamer::bandwidth_accounting::{self, SubsystemBandwidth},
     std::{
-        collections::{HashMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet},
         env, error,
         fmt::{self, Display},
-        net::SocketAddr,
+        net::{IpAddr, SocketAddr},
         path::{Path, PathBuf},
         sync::{Arc, RwLock},
         thread::{self, Builder},
@@ -144,548 +179,2438 @@ impl Display for AdminRpcRepairWhitelist {
 impl solana_cli_output::VerboseDisplay for AdminRpcRepairWhitelist {}
 impl solana_cli_output::QuietDisplay for AdminRpcRepairWhitelist {}
 
-#[rpc]
-pub trait AdminRpc {
-    type Metadata;
-
-    #[rpc(meta, name = "exit")]
-    fn exit(&self, meta: Self::Metadata) -> Result<()>;
-
-    #[rpc(meta, name = "reloadPlugin")]
-    fn reload_plugin(
-        &self,
-        meta: Self::Metadata,
-        name: String,
-        config_file: String,
-    ) -> BoxFuture<Result<()>>;
-
-    #[rpc(meta, name = "unloadPlugin")]
-    fn unload_plugin(&self, meta: Self::Metadata, name: String) -> BoxFuture<Result<()>>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcDuplicateShredProof {
+    pub slot: u64,
+    pub leader: String,
+    pub first_observed: u64,
+    pub num_reporters: usize,
+    pub spread_stake_pct: f64,
+}
 
-    #[rpc(meta, name = "loadPlugin")]
-    fn load_plugin(&self, meta: Self::Metadata, config_file: String) -> BoxFuture<Result<String>>;
+impl From<DuplicateShredProofInfo> for AdminRpcDuplicateShredProof {
+    fn from(info: DuplicateShredProofInfo) -> Self {
+        Self {
+            slot: info.slot,
+            leader: info.leader.to_string(),
+            first_observed: info.first_observed,
+            num_reporters: info.num_reporters,
+            spread_stake_pct: info.spread_stake_pct(),
+        }
+    }
+}
 
-    #[rpc(meta, name = "listPlugins")]
-    fn list_plugins(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<String>>>;
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcDuplicateShredProofs {
+    pub proofs: Vec<AdminRpcDuplicateShredProof>,
+}
 
-    #[rpc(meta, name = "rpcAddress")]
-    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>>;
+impl Display for AdminRpcDuplicateShredProofs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.proofs.is_empty() {
+            return writeln!(f, "No duplicate shred proofs observed");
+        }
+        writeln!(
+            f,
+            "{:>12}  {:<44}  {:>15}  {:>9}  {:>7}",
+            "Slot", "Leader", "First Observed", "Reporters", "Spread"
+        )?;
+        for proof in &self.proofs {
+            writeln!(
+                f,
+                "{:>12}  {:<44}  {:>15}  {:>9}  {:>6.2}%",
+                proof.slot,
+                proof.leader,
+                proof.first_observed,
+                proof.num_reporters,
+                proof.spread_stake_pct
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcDuplicateShredProofs {}
+impl solana_cli_output::QuietDisplay for AdminRpcDuplicateShredProofs {}
 
-    #[rpc(name = "setLogFilter")]
-    fn set_log_filter(&self, filter: String) -> Result<()>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcForensicBundle {
+    pub name: String,
+    pub slot: Slot,
+    pub reason: String,
+    pub created: u64,
+    pub frozen_hash: Option<String>,
+    pub cluster_hash: Option<String>,
+    pub files: Vec<String>,
+}
 
-    #[rpc(meta, name = "startTime")]
-    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime>;
+impl From<ForensicBundleManifest> for AdminRpcForensicBundle {
+    fn from(manifest: ForensicBundleManifest) -> Self {
+        Self {
+            name: manifest.name,
+            slot: manifest.slot,
+            reason: manifest.reason.as_str().to_string(),
+            created: manifest.created,
+            frozen_hash: manifest.frozen_hash,
+            cluster_hash: manifest.cluster_hash,
+            files: manifest.files,
+        }
+    }
+}
 
-    #[rpc(meta, name = "startProgress")]
-    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress>;
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcForensicBundles {
+    pub bundles: Vec<AdminRpcForensicBundle>,
+}
 
-    #[rpc(meta, name = "addAuthorizedVoter")]
-    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()>;
+impl Display for AdminRpcForensicBundles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.bundles.is_empty() {
+            return writeln!(f, "No forensic bundles captured");
+        }
+        writeln!(
+            f,
+            "{:>12}  {:<21}  {:>15}  {}",
+            "Slot", "Reason", "Created", "Name"
+        )?;
+        for bundle in &self.bundles {
+            writeln!(
+                f,
+                "{:>12}  {:<21}  {:>15}  {}",
+                bundle.slot, bundle.reason, bundle.created, bundle.name
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcForensicBundles {}
+impl solana_cli_output::QuietDisplay for AdminRpcForensicBundles {}
 
-    #[rpc(meta, name = "addAuthorizedVoterFromBytes")]
-    fn add_authorized_voter_from_bytes(&self, meta: Self::Metadata, keypair: Vec<u8>)
-        -> Result<()>;
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcForensicBundleFile {
+    pub name: String,
+    /// Base64 encoded
+    pub data: String,
+}
 
-    #[rpc(meta, name = "removeAllAuthorizedVoters")]
-    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()>;
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcForensicBundleContents {
+    pub bundle: AdminRpcForensicBundle,
+    pub files: Vec<AdminRpcForensicBundleFile>,
+}
 
-    #[rpc(meta, name = "setIdentity")]
-    fn set_identity(
-        &self,
-        meta: Self::Metadata,
-        keypair_file: String,
-        require_tower: bool,
-    ) -> Result<()>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcPohCalibration {
+    pub hashes_per_batch: u64,
+    /// Measured over the latest calibration interval, `None` before the first
+    /// calibration
+    pub hashes_per_second: Option<u64>,
+    pub target_hashes_per_second: Option<u64>,
+    /// Whether the hash rate is below the cluster requirement
+    pub throttled: bool,
+}
 
-    #[rpc(meta, name = "setIdentityFromBytes")]
-    fn set_identity_from_bytes(
-        &self,
-        meta: Self::Metadata,
-        identity_keypair: Vec<u8>,
-        require_tower: bool,
-    ) -> Result<()>;
+impl From<&PohCalibration> for AdminRpcPohCalibration {
+    fn from(calibration: &PohCalibration) -> Self {
+        let status = calibration.status();
+        Self {
+            hashes_per_batch: calibration.hashes_per_batch(),
+            hashes_per_second: status.map(|status| status.hashes_per_second),
+            target_hashes_per_second: status.map(|status| status.target_hashes_per_second),
+            throttled: status.is_some_and(|status| status.is_throttled()),
+        }
+    }
+}
 
-    #[rpc(meta, name = "setStakedNodesOverrides")]
-    fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()>;
+impl Display for AdminRpcPohCalibration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Hashes per batch: {}", self.hashes_per_batch)?;
+        match (self.hashes_per_second, self.target_hashes_per_second) {
+            (Some(hashes_per_second), Some(target_hashes_per_second)) => {
+                writeln!(
+                    f,
+                    "Hash rate: {hashes_per_second} hashes per second, cluster requirement \
+                     {target_hashes_per_second}"
+                )?;
+                if self.throttled {
+                    writeln!(f, "Warning: the hash rate is below the cluster requirement")?;
+                }
+            }
+            _ => writeln!(f, "The hash rate has not been measured yet")?,
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcPohCalibration {}
+impl solana_cli_output::QuietDisplay for AdminRpcPohCalibration {}
 
-    #[rpc(meta, name = "contactInfo")]
-    fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcCrdsValue {
+    pub ordinal: u64,
+    pub origin: String,
+    pub value_type: String,
+    pub hash: String,
+    pub wallclock: u64,
+    /// Milliseconds since the value was inserted or last updated locally
+    pub age_ms: u64,
+    /// Milliseconds between the wallclock of the origin when it created the
+    /// value and the local time; negative if the clock of the origin is ahead
+    pub wallclock_age_ms: i64,
+    pub num_bytes: usize,
+    pub summary: String,
+}
 
-    #[rpc(meta, name = "repairShredFromPeer")]
-    fn repair_shred_from_peer(
-        &self,
-        meta: Self::Metadata,
-        pubkey: Option<Pubkey>,
-        slot: u64,
-        shred_index: u64,
-    ) -> Result<()>;
+impl AdminRpcCrdsValue {
+    fn new(info: CrdsValueInfo, now: u64) -> Self {
+        Self {
+            ordinal: info.ordinal,
+            origin: info.origin.to_string(),
+            value_type: info.value_type.to_string(),
+            hash: info.hash.to_string(),
+            wallclock: info.wallclock,
+            age_ms: info.age(now),
+            wallclock_age_ms: (now as i64).saturating_sub(info.wallclock as i64),
+            num_bytes: info.num_bytes,
+            summary: info.summary,
+        }
+    }
+}
 
-    #[rpc(meta, name = "repairWhitelist")]
-    fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcCrdsValues {
+    pub values: Vec<AdminRpcCrdsValue>,
+    /// Cursor to request the next page with
+    pub next_cursor: u64,
+    pub num_values: usize,
+    pub num_origins: usize,
+    pub num_values_by_type: BTreeMap<String, usize>,
+}
 
-    #[rpc(meta, name = "setRepairWhitelist")]
-    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()>;
+impl AdminRpcCrdsValues {
+    fn new(page: CrdsInspectPage, now: u64) -> Self {
+        Self {
+            values: page
+                .values
+                .into_iter()
+                .map(|info| AdminRpcCrdsValue::new(info, now))
+                .collect(),
+            next_cursor: page.next_cursor,
+            num_values: page.num_values,
+            num_origins: page.num_origins,
+            num_values_by_type: page
+                .num_values_by_type
+                .into_iter()
+                .map(|(value_type, count)| (value_type.to_string(), count))
+                .collect(),
+        }
+    }
+}
 
-    #[rpc(meta, name = "getSecondaryIndexKeySize")]
-    fn get_secondary_index_key_size(
-        &self,
-        meta: Self::Metadata,
-        pubkey_str: String,
-    ) -> Result<HashMap<RpcAccountIndex, usize>>;
+impl Display for AdminRpcCrdsValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "CRDS table: {} values from {} nodes",
+            self.num_values, self.num_origins
+        )?;
+        for (value_type, count) in &self.num_values_by_type {
+            writeln!(f, "  {value_type:<16} {count:>8}")?;
+        }
+        writeln!(f)?;
+        if self.values.is_empty() {
+            return writeln!(f, "No matching values");
+        }
+        writeln!(
+            f,
+            "{:>10}  {:<44}  {:<16}  {:>10}  {:>12}  {:>6}  Summary",
+            "Ordinal", "Origin", "Type", "Age (ms)", "Clock (ms)", "Bytes"
+        )?;
+        for value in &self.values {
+            writeln!(
+                f,
+                "{:>10}  {:<44}  {:<16}  {:>10}  {:>12}  {:>6}  {}",
+                value.ordinal,
+                value.origin,
+                value.value_type,
+                value.age_ms,
+                value.wallclock_age_ms,
+                value.num_bytes,
+                value.summary,
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Next cursor: {}", self.next_cursor)
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcCrdsValues {}
+impl solana_cli_output::QuietDisplay for AdminRpcCrdsValues {}
 
-    #[rpc(meta, name = "setPublicTpuAddress")]
-    fn set_public_tpu_address(
-        &self,
-        meta: Self::Metadata,
-        public_tpu_addr: SocketAddr,
-    ) -> Result<()>;
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcGossipPeerScore {
+    pub addr: String,
+    pub penalty: u64,
+    pub num_invalid_signatures: u64,
+    pub num_duplicate_values: u64,
+    pub num_excess_pull_requests: u64,
+    pub deprioritized: bool,
+    pub shunned_until: Option<u64>,
+}
 
-    #[rpc(meta, name = "setPublicTpuForwardsAddress")]
-    fn set_public_tpu_forwards_address(
-        &self,
-        meta: Self::Metadata,
-        public_tpu_forwards_addr: SocketAddr,
-    ) -> Result<()>;
+impl From<PeerScore> for AdminRpcGossipPeerScore {
+    fn from(score: PeerScore) -> Self {
+        Self {
+            addr: score.addr.to_string(),
+            penalty: score.penalty,
+            num_invalid_signatures: score.num_invalid_signatures,
+            num_duplicate_values: score.num_duplicate_values,
+            num_excess_pull_requests: score.num_excess_pull_requests,
+            deprioritized: score.is_deprioritized(),
+            shunned_until: score.shunned_until,
+        }
+    }
 }
 
-pub struct AdminRpcImpl;
-impl AdminRpc for AdminRpcImpl {
-    type Metadata = AdminRpcRequestMetadata;
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcGossipPeerScores {
+    pub scores: Vec<AdminRpcGossipPeerScore>,
+    pub overrides: Vec<String>,
+}
 
-    fn exit(&self, meta: Self::Metadata) -> Result<()> {
-        debug!("exit admin rpc request received");
+impl Display for AdminRpcGossipPeerScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Overrides: {:?}", &self.overrides)?;
+        if self.scores.is_empty() {
+            return writeln!(f, "No misbehaving gossip peers");
+        }
+        writeln!(
+            f,
+            "{:<39}  {:>8}  {:>9}  {:>10}  {:>13}  {:<15}",
+            "Address", "Penalty", "Bad Sigs", "Duplicates", "Pull Requests", "Status"
+        )?;
+        for score in &self.scores {
+            let status = match score.shunned_until {
+                Some(shunned_until) => format!("shunned until {shunned_until}"),
+                None if score.deprioritized => "deprioritized".to_string(),
+                None => String::default(),
+            };
+            writeln!(
+                f,
+                "{:<39}  {:>8}  {:>9}  {:>10}  {:>13}  {:<15}",
+                score.addr,
+                score.penalty,
+                score.num_invalid_signatures,
+                score.num_duplicate_values,
+                score.num_excess_pull_requests,
+                status,
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcGossipPeerScores {}
+impl solana_cli_output::QuietDisplay for AdminRpcGossipPeerScores {}
 
-        thread::Builder::new()
-            .name("solProcessExit".into())
-            .spawn(move || {
-                // Delay exit signal until this RPC request completes, otherwise the caller of `exit` might
-                // receive a confusing error as the validator shuts down before a response is sent back.
-                thread::sleep(Duration::from_millis(100));
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcColumnCompaction {
+    pub column: String,
+    pub pending_compaction_bytes: u64,
+}
 
-                warn!("validator exit requested");
-                meta.validator_exit.write().unwrap().exit();
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBlockstoreCompaction {
+    /// Column families waiting to be compacted, the next first
+    pub queued: Vec<String>,
+    pub columns: Vec<AdminRpcColumnCompaction>,
+}
 
-                // TODO: Debug why Exit doesn't always cause the validator to fully exit
-                // (rocksdb background processing or some other stuck thread perhaps?).
-                //
-                // If the process is still alive after five seconds, exit harder
-                thread::sleep(Duration::from_secs(
-                    env::var("SOLANA_VALIDATOR_EXIT_TIMEOUT")
-                        .ok()
-                        .and_then(|x| x.parse().ok())
-                        .unwrap_or(5),
-                ));
-                warn!("validator exit timeout");
-                std::process::exit(0);
-            })
-            .unwrap();
+impl Display for AdminRpcBlockstoreCompaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.queued.is_empty() {
+            writeln!(f, "No compactions scheduled")?;
+        } else {
+            writeln!(f, "Scheduled compactions: {}", self.queued.join(", "))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<32}  {:>24}", "Column", "Pending Compaction Bytes")?;
+        for column in &self.columns {
+            writeln!(
+                f,
+                "{:<32}  {:>24}",
+                column.column, column.pending_compaction_bytes
+            )?;
+        }
         Ok(())
     }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcBlockstoreCompaction {}
+impl solana_cli_output::QuietDisplay for AdminRpcBlockstoreCompaction {}
 
-    fn reload_plugin(
-        &self,
-        meta: Self::Metadata,
-        name: String,
-        config_file: String,
-    ) -> BoxFuture<Result<()>> {
-        Box::pin(async move {
-            // Construct channel for plugin to respond to this particular rpc request instance
-            let (response_sender, response_receiver) = oneshot_channel();
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSnapshotPackagingJob {
+    pub slot: Slot,
+    /// Base slot of incremental snapshots
+    pub base_slot: Option<Slot>,
+    /// "serializing" or "archiving"
+    pub stage: String,
+    pub total_bytes: u64,
+    pub archived_bytes: u64,
+    pub elapsed_ms: u64,
+}
 
-            // Send request to plugin manager if there is a geyser service
-            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
-                rpc_to_manager_sender
-                    .send(GeyserPluginManagerRequest::ReloadPlugin {
-                        name,
-                        config_file,
-                        response_sender,
-                    })
-                    .expect("GeyerPluginService should never drop request receiver");
-            } else {
-                return Err(jsonrpc_core::Error {
-                    code: ErrorCode::InvalidRequest,
-                    message: "No geyser plugin service".to_string(),
-                    data: None,
-                });
+impl From<SnapshotPackagingJob> for AdminRpcSnapshotPackagingJob {
+    fn from(job: SnapshotPackagingJob) -> Self {
+        Self {
+            slot: job.slot,
+            base_slot: match job.kind {
+                SnapshotKind::FullSnapshot => None,
+                SnapshotKind::IncrementalSnapshot(base_slot) => Some(base_slot),
+            },
+            stage: match job.stage {
+                SnapshotPackagingStage::Serializing => "serializing",
+                SnapshotPackagingStage::Archiving => "archiving",
             }
+            .to_string(),
+            total_bytes: job.total_bytes,
+            archived_bytes: job.archived_bytes,
+            elapsed_ms: job.elapsed.as_millis() as u64,
+        }
+    }
+}
 
-            // Await response from plugin manager
-            response_receiver
-                .await
-                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
-        })
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSubsystemBandwidth {
+    pub subsystem: String,
+    pub ingress_packets: u64,
+    pub ingress_bytes: u64,
+    pub egress_packets: u64,
+    pub egress_bytes: u64,
+    /// Rates over the latest sampling interval
+    pub ingress_bytes_per_sec: u64,
+    pub egress_bytes_per_sec: u64,
+}
+
+impl From<SubsystemBandwidth> for AdminRpcSubsystemBandwidth {
+    fn from(bandwidth: SubsystemBandwidth) -> Self {
+        Self {
+            subsystem: bandwidth.subsystem.name().to_string(),
+            ingress_packets: bandwidth.ingress_packets,
+            ingress_bytes: bandwidth.ingress_bytes,
+            egress_packets: bandwidth.egress_packets,
+            egress_bytes: bandwidth.egress_bytes,
+            ingress_bytes_per_sec: bandwidth.ingress_bytes_per_sec,
+            egress_bytes_per_sec: bandwidth.egress_bytes_per_sec,
+        }
     }
+}
 
-    fn load_plugin(&self, meta: Self::Metadata, config_file: String) -> BoxFuture<Result<String>> {
-        Box::pin(async move {
-            // Construct channel for plugin to respond to this particular rpc request instance
-            let (response_sender, response_receiver) = oneshot_channel();
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBandwidth {
+    pub subsystems: Vec<AdminRpcSubsystemBandwidth>,
+}
 
-            // Send request to plugin manager if there is a geyser service
-            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
-                rpc_to_manager_sender
-                    .send(GeyserPluginManagerRequest::LoadPlugin {
-                        config_file,
-                        response_sender,
-                    })
-                    .expect("GeyerPluginService should never drop request receiver");
+impl Display for AdminRpcBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<13} {:>14} {:>14} {:>16} {:>16}",
+            "Subsystem", "Ingress B/s", "Egress B/s", "Ingress bytes", "Egress bytes"
+        )?;
+        for bandwidth in &self.subsystems {
+            writeln!(
+                f,
+                "{:<13} {:>14} {:>14} {:>16} {:>16}",
+                bandwidth.subsystem,
+                bandwidth.ingress_bytes_per_sec,
+                bandwidth.egress_bytes_per_sec,
+                bandwidth.ingress_bytes,
+                bandwidth.egress_bytes,
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcBandwidth {}
+impl solana_cli_output::QuietDisplay for AdminRpcBandwidth {}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AdminRpcHealthDetail(pub RpcHealthDetail);
+
+impl Display for AdminRpcHealthDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let detail = &self.0;
+        writeln!(
+            f,
+            "Health: {}",
+            if detail.healthy { "ok" } else { "unhealthy" }
+        )?;
+        for condition in &detail.conditions {
+            let kind = format!("{:?}", condition.kind);
+            writeln!(
+                f,
+                "{:<9} {kind:<22} {:>8} / {:<8} {}",
+                if condition.healthy { "ok" } else { "unhealthy" },
+                condition.value,
+                condition.threshold,
+                condition.message,
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcHealthDetail {}
+impl solana_cli_output::QuietDisplay for AdminRpcHealthDetail {}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSnapshotPackagingProgress {
+    /// The snapshot package being handled, if any
+    pub job: Option<AdminRpcSnapshotPackagingJob>,
+}
+
+impl Display for AdminRpcSnapshotPackagingProgress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(job) = &self.job else {
+            return writeln!(f, "No snapshot package is being handled");
+        };
+        match job.base_slot {
+            None => writeln!(f, "Full snapshot of slot {}", job.slot)?,
+            Some(base_slot) => writeln!(
+                f,
+                "Incremental snapshot of slot {} with base slot {base_slot}",
+                job.slot
+            )?,
+        }
+        writeln!(f, "Stage: {}", job.stage)?;
+        if job.stage == "archiving" {
+            let percentage = if job.total_bytes == 0 {
+                100.0
             } else {
-                return Err(jsonrpc_core::Error {
-                    code: ErrorCode::InvalidRequest,
-                    message: "No geyser plugin service".to_string(),
-                    data: None,
-                });
+                job.archived_bytes as f64 * 100.0 / job.total_bytes as f64
+            };
+            writeln!(
+                f,
+                "Archived: {} of {} bytes ({percentage:.1}%)",
+                job.archived_bytes, job.total_bytes
+            )?;
+        }
+        writeln!(f, "Elapsed: {}ms", job.elapsed_ms)
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcSnapshotPackagingProgress {}
+impl solana_cli_output::QuietDisplay for AdminRpcSnapshotPackagingProgress {}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSnapshotArchiveVerificationRecord {
+    pub slot: Slot,
+    /// Base slot of incremental snapshots
+    pub base_slot: Option<Slot>,
+    /// Unix timestamp of the end of the verification, in seconds
+    pub verified_at: u64,
+    pub duration_ms: u64,
+    /// Why the verification failed, if it did
+    pub error: Option<String>,
+    pub num_storages: u64,
+    pub num_stored_accounts: u64,
+    pub accounts_lt_hash_verified: bool,
+    pub snapshot_hash_verified: bool,
+}
+
+impl From<SnapshotArchiveVerificationRecord> for AdminRpcSnapshotArchiveVerificationRecord {
+    fn from(record: SnapshotArchiveVerificationRecord) -> Self {
+        let (error, verification) = match record.result {
+            Ok(verification) => (None, Some(verification)),
+            Err(err) => (Some(err), None),
+        };
+        Self {
+            slot: record.slot,
+            base_slot: match record.kind {
+                SnapshotKind::FullSnapshot => None,
+                SnapshotKind::IncrementalSnapshot(base_slot) => Some(base_slot),
+            },
+            verified_at: record
+                .verified_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            duration_ms: record.duration.as_millis() as u64,
+            error,
+            num_storages: verification
+                .as_ref()
+                .map_or(0, |verification| verification.num_storages),
+            num_stored_accounts: verification
+                .as_ref()
+                .map_or(0, |verification| verification.num_stored_accounts),
+            accounts_lt_hash_verified: verification
+                .as_ref()
+                .is_some_and(|verification| verification.accounts_lt_hash_verified),
+            snapshot_hash_verified: verification
+                .as_ref()
+                .is_some_and(|verification| verification.snapshot_hash_verified),
+        }
+    }
+}
+
+impl Display for AdminRpcSnapshotArchiveVerificationRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base_slot {
+            None => write!(f, "full snapshot of slot {}", self.slot)?,
+            Some(base_slot) => write!(
+                f,
+                "incremental snapshot of slot {} with base slot {base_slot}",
+                self.slot
+            )?,
+        }
+        write!(f, ", in {}ms: ", self.duration_ms)?;
+        if let Some(error) = &self.error {
+            return write!(f, "invalid: {error}");
+        }
+        write!(
+            f,
+            "valid, {} storages, {} stored accounts",
+            self.num_storages, self.num_stored_accounts
+        )?;
+        if self.accounts_lt_hash_verified {
+            write!(f, ", accounts lt hash verified")?;
+        }
+        if self.snapshot_hash_verified {
+            write!(f, ", snapshot hash verified")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSnapshotArchiveVerification {
+    pub num_verified: u64,
+    pub num_failed: u64,
+    pub latest: Option<AdminRpcSnapshotArchiveVerificationRecord>,
+    pub latest_failure: Option<AdminRpcSnapshotArchiveVerificationRecord>,
+}
+
+impl Display for AdminRpcSnapshotArchiveVerification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Verified snapshot archives: {} valid, {} invalid",
+            self.num_verified, self.num_failed
+        )?;
+        match &self.latest {
+            None => writeln!(f, "No snapshot archive has been verified")?,
+            Some(latest) => writeln!(f, "Latest: {latest}")?,
+        }
+        if let Some(latest_failure) = &self.latest_failure {
+            writeln!(f, "Latest invalid: {latest_failure}")?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcSnapshotArchiveVerification {}
+impl solana_cli_output::QuietDisplay for AdminRpcSnapshotArchiveVerification {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAncientPackingPolicy {
+    pub ideal_storage_size: u64,
+    pub max_ancient_storages: usize,
+    /// If None, the shrink ratio of all storages applies
+    pub shrink_ratio: Option<f64>,
+}
+
+impl From<AncientPackingPolicy> for AdminRpcAncientPackingPolicy {
+    fn from(policy: AncientPackingPolicy) -> Self {
+        Self {
+            ideal_storage_size: policy.ideal_storage_size,
+            max_ancient_storages: policy.max_ancient_storages,
+            shrink_ratio: policy.shrink_ratio,
+        }
+    }
+}
+
+impl From<AdminRpcAncientPackingPolicy> for AncientPackingPolicy {
+    fn from(policy: AdminRpcAncientPackingPolicy) -> Self {
+        Self {
+            ideal_storage_size: policy.ideal_storage_size,
+            max_ancient_storages: policy.max_ancient_storages,
+            shrink_ratio: policy.shrink_ratio,
+        }
+    }
+}
+
+impl Display for AdminRpcAncientPackingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Ideal storage size: {} bytes", self.ideal_storage_size)?;
+        writeln!(f, "Max ancient storages: {}", self.max_ancient_storages)?;
+        match self.shrink_ratio {
+            Some(shrink_ratio) => writeln!(f, "Shrink ratio: {shrink_ratio}"),
+            None => writeln!(f, "Shrink ratio: the shrink ratio of all storages"),
+        }
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcAncientPackingPolicy {}
+impl solana_cli_output::QuietDisplay for AdminRpcAncientPackingPolicy {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcOwnerDiskUsage {
+    pub owner: String,
+    pub num_accounts: u64,
+    pub data_len: u64,
+    pub lamports: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountsDiskUsage {
+    pub num_storages: usize,
+    /// # of storages the disk usage is of, as they are summarized a few at a time
+    pub num_summarized_storages: usize,
+    pub num_owners: usize,
+    /// The owners with the largest data length first
+    pub owners: Vec<AdminRpcOwnerDiskUsage>,
+}
+
+impl AdminRpcAccountsDiskUsage {
+    fn new(disk_usage: AccountsDiskUsage, num_owners: Option<usize>) -> Self {
+        let mut owners: Vec<_> = disk_usage.by_owner.into_iter().collect();
+        owners.sort_unstable_by(|(owner0, usage0), (owner1, usage1)| {
+            usage1
+                .data_len
+                .cmp(&usage0.data_len)
+                .then_with(|| owner0.cmp(owner1))
+        });
+        let total_num_owners = owners.len();
+        owners.truncate(num_owners.unwrap_or(total_num_owners));
+        Self {
+            num_storages: disk_usage.num_storages,
+            num_summarized_storages: disk_usage.num_summarized_storages,
+            num_owners: total_num_owners,
+            owners: owners
+                .into_iter()
+                .map(|(owner, usage)| AdminRpcOwnerDiskUsage {
+                    owner: owner.to_string(),
+                    num_accounts: usage.num_accounts,
+                    data_len: usage.data_len,
+                    lamports: usage.lamports,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Display for AdminRpcAccountsDiskUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Summarized storages: {} of {}",
+            self.num_summarized_storages, self.num_storages
+        )?;
+        writeln!(f, "Owners: {}", self.num_owners)?;
+        writeln!(
+            f,
+            "{:<44} {:>12} {:>16} {:>20}",
+            "Owner", "Accounts", "Data Length", "SOL"
+        )?;
+        for owner in &self.owners {
+            writeln!(
+                f,
+                "{:<44} {:>12} {:>16} {:>20}",
+                owner.owner,
+                owner.num_accounts,
+                owner.data_len,
+                lamports_to_sol(owner.lamports)
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcAccountsDiskUsage {}
+impl solana_cli_output::QuietDisplay for AdminRpcAccountsDiskUsage {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcProgramPrecompilation {
+    /// The root bank the programs were compiled for
+    pub slot: Slot,
+    pub num_candidates: usize,
+    pub num_compiled: usize,
+    pub elapsed_ms: u64,
+}
+
+impl From<ProgramPrecompilationReport> for AdminRpcProgramPrecompilation {
+    fn from(report: ProgramPrecompilationReport) -> Self {
+        Self {
+            slot: report.slot,
+            num_candidates: report.num_candidates,
+            num_compiled: report.num_compiled,
+            elapsed_ms: report.elapsed_ms,
+        }
+    }
+}
+
+impl Display for AdminRpcProgramPrecompilation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Compiled {} of the {} most invoked programs for slot {} in {}ms",
+            self.num_compiled, self.num_candidates, self.slot, self.elapsed_ms
+        )?;
+        writeln!(
+            f,
+            "Builtins and programs which were already compiled are skipped"
+        )
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcProgramPrecompilation {}
+impl solana_cli_output::QuietDisplay for AdminRpcProgramPrecompilation {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBankHashBreakdown {
+    pub slot: Slot,
+    pub bank_hash: String,
+    pub parent_slot: Slot,
+    pub parent_bank_hash: String,
+    pub accounts_delta_hash: Option<String>,
+    pub signature_count: u64,
+    pub last_blockhash: String,
+    pub epoch_accounts_hash: Option<String>,
+    pub accounts_lt_hash_checksum: Option<String>,
+    pub is_hard_fork: bool,
+}
+
+impl From<BankHashBreakdown> for AdminRpcBankHashBreakdown {
+    fn from(breakdown: BankHashBreakdown) -> Self {
+        Self {
+            slot: breakdown.slot,
+            bank_hash: breakdown.bank_hash,
+            parent_slot: breakdown.parent_slot,
+            parent_bank_hash: breakdown.parent_bank_hash,
+            accounts_delta_hash: breakdown.accounts_delta_hash,
+            signature_count: breakdown.signature_count,
+            last_blockhash: breakdown.last_blockhash,
+            epoch_accounts_hash: breakdown.epoch_accounts_hash,
+            accounts_lt_hash_checksum: breakdown.accounts_lt_hash_checksum,
+            is_hard_fork: breakdown.is_hard_fork,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBankHashBreakdowns {
+    /// In increasing slot order, the versions of a slot in the order they
+    /// were frozen
+    pub breakdowns: Vec<AdminRpcBankHashBreakdown>,
+}
+
+impl Display for AdminRpcBankHashBreakdowns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.breakdowns.is_empty() {
+            return writeln!(f, "No frozen slots retained");
+        }
+        for breakdown in &self.breakdowns {
+            writeln!(f, "Slot {}", breakdown.slot)?;
+            writeln!(f, "  Bank hash:                 {}", breakdown.bank_hash)?;
+            writeln!(
+                f,
+                "  Parent bank hash:          {} (slot {})",
+                breakdown.parent_bank_hash, breakdown.parent_slot
+            )?;
+            if let Some(accounts_delta_hash) = &breakdown.accounts_delta_hash {
+                writeln!(f, "  Accounts delta hash:       {accounts_delta_hash}")?;
+            }
+            writeln!(
+                f,
+                "  Signature count:           {}",
+                breakdown.signature_count
+            )?;
+            writeln!(
+                f,
+                "  Last blockhash:            {}",
+                breakdown.last_blockhash
+            )?;
+            if let Some(epoch_accounts_hash) = &breakdown.epoch_accounts_hash {
+                writeln!(f, "  Epoch accounts hash:       {epoch_accounts_hash}")?;
+            }
+            if let Some(checksum) = &breakdown.accounts_lt_hash_checksum {
+                writeln!(f, "  Accounts lt hash checksum: {checksum}")?;
             }
+            if breakdown.is_hard_fork {
+                writeln!(f, "  Hard fork:                 true")?;
+            }
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcBankHashBreakdowns {}
+impl solana_cli_output::QuietDisplay for AdminRpcBankHashBreakdowns {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcFailoverStatus {
+    pub is_active: bool,
+    pub identity: String,
+    pub term: u64,
+    pub auto_promote: bool,
+    pub is_handing_over: bool,
+    pub peer_pubkey: String,
+    pub peer_address: String,
+    /// `None` until a heartbeat of the peer is received
+    pub peer_is_active: Option<bool>,
+    pub peer_term: Option<u64>,
+    pub peer_last_voted_slot: Option<Slot>,
+    pub ms_since_peer_heartbeat: Option<u64>,
+    pub peer_tower_last_voted_slot: Option<Slot>,
+    /// Why the latest automatic promotion was refused
+    pub refusal: Option<String>,
+}
 
-            // Await response from plugin manager
-            response_receiver
-                .await
-                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+impl From<FailoverStatus> for AdminRpcFailoverStatus {
+    fn from(status: FailoverStatus) -> Self {
+        Self {
+            is_active: status.is_active,
+            identity: status.identity.to_string(),
+            term: status.term,
+            auto_promote: status.auto_promote,
+            is_handing_over: status.is_handing_over,
+            peer_pubkey: status.peer_pubkey.to_string(),
+            peer_address: status.peer_address.to_string(),
+            peer_is_active: status.peer_is_active,
+            peer_term: status.peer_term,
+            peer_last_voted_slot: status.peer_last_voted_slot,
+            ms_since_peer_heartbeat: status.ms_since_peer_heartbeat,
+            peer_tower_last_voted_slot: status.peer_tower_last_voted_slot,
+            refusal: status.refusal,
+        }
+    }
+}
+
+impl Display for AdminRpcFailoverStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let role = match (self.is_active, self.is_handing_over) {
+            (true, _) => "active",
+            (false, true) => "standby, handing over",
+            (false, false) => "standby",
+        };
+        writeln!(f, "Role:                  {role}")?;
+        writeln!(f, "Identity:              {}", self.identity)?;
+        writeln!(f, "Term:                  {}", self.term)?;
+        writeln!(
+            f,
+            "Automatic promotion:   {}",
+            if self.auto_promote {
+                "enabled"
+            } else {
+                "paused"
+            }
+        )?;
+        writeln!(
+            f,
+            "Peer:                  {} ({})",
+            self.peer_pubkey, self.peer_address
+        )?;
+        match (self.peer_is_active, self.ms_since_peer_heartbeat) {
+            (Some(peer_is_active), Some(ms_since_peer_heartbeat)) => {
+                writeln!(
+                    f,
+                    "Peer role:             {}",
+                    if peer_is_active { "active" } else { "standby" }
+                )?;
+                writeln!(
+                    f,
+                    "Peer term:             {}",
+                    self.peer_term.unwrap_or_default()
+                )?;
+                writeln!(f, "Last peer heartbeat:   {ms_since_peer_heartbeat}ms ago")?;
+            }
+            _ => writeln!(f, "Last peer heartbeat:   none received")?,
+        }
+        if let Some(slot) = self.peer_last_voted_slot {
+            writeln!(f, "Peer last voted slot:  {slot}")?;
+        }
+        if let Some(slot) = self.peer_tower_last_voted_slot {
+            writeln!(f, "Received tower voted:  {slot}")?;
+        }
+        if let Some(refusal) = &self.refusal {
+            writeln!(f, "Last refusal:          {refusal}")?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcFailoverStatus {}
+impl solana_cli_output::QuietDisplay for AdminRpcFailoverStatus {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcStagedIdentityReport {
+    pub previous_identity: String,
+    pub identity: String,
+    pub authorized_voter: String,
+    pub root_slot: Slot,
+    pub tower_last_voted_slot: Option<Slot>,
+    pub vote_account_last_voted_slot: Option<Slot>,
+}
+
+impl From<StagedIdentityReport> for AdminRpcStagedIdentityReport {
+    fn from(report: StagedIdentityReport) -> Self {
+        Self {
+            previous_identity: report.previous_identity.to_string(),
+            identity: report.identity.to_string(),
+            authorized_voter: report.authorized_voter.to_string(),
+            root_slot: report.root_slot,
+            tower_last_voted_slot: report.tower_last_voted_slot,
+            vote_account_last_voted_slot: report.vote_account_last_voted_slot,
+        }
+    }
+}
+
+impl Display for AdminRpcStagedIdentityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Previous identity:             {}",
+            self.previous_identity
+        )?;
+        writeln!(f, "Identity:                      {}", self.identity)?;
+        writeln!(
+            f,
+            "Authorized voter:              {}",
+            self.authorized_voter
+        )?;
+        writeln!(f, "Root slot:                     {}", self.root_slot)?;
+        let slot = |slot: Option<Slot>| slot.map_or_else(|| "none".to_string(), |s| s.to_string());
+        writeln!(
+            f,
+            "Tower last voted slot:         {}",
+            slot(self.tower_last_voted_slot)
+        )?;
+        writeln!(
+            f,
+            "Vote account last voted slot:  {}",
+            slot(self.vote_account_last_voted_slot)
+        )
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcStagedIdentityReport {}
+impl solana_cli_output::QuietDisplay for AdminRpcStagedIdentityReport {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcLeaderPauseStatus {
+    pub is_paused: bool,
+    pub paused_ms: Option<u64>,
+    pub num_skipped_slots: Option<u64>,
+    pub last_skipped_slot: Option<Slot>,
+}
+
+impl Display for AdminRpcLeaderPauseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.is_paused {
+            return writeln!(f, "Block production is not paused");
+        }
+        writeln!(
+            f,
+            "Block production paused for {}ms",
+            self.paused_ms.unwrap_or_default()
+        )?;
+        write!(
+            f,
+            "Skipped leader slots: {}",
+            self.num_skipped_slots.unwrap_or_default()
+        )?;
+        if let Some(slot) = self.last_skipped_slot {
+            write!(f, ", the last one {slot}")?;
+        }
+        writeln!(f)
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcLeaderPauseStatus {}
+impl solana_cli_output::QuietDisplay for AdminRpcLeaderPauseStatus {}
+
+impl From<LeaderPauseStatus> for AdminRpcLeaderPauseStatus {
+    fn from(status: LeaderPauseStatus) -> Self {
+        Self {
+            is_paused: status.is_paused,
+            paused_ms: status.paused_ms,
+            num_skipped_slots: status.num_skipped_slots,
+            last_skipped_slot: status.last_skipped_slot,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountConflict {
+    pub account: String,
+    pub num_lock_conflicts: u64,
+    pub num_retries: u64,
+}
+
+impl From<(Pubkey, AccountConflictCounts)> for AdminRpcAccountConflict {
+    fn from((account, counts): (Pubkey, AccountConflictCounts)) -> Self {
+        Self {
+            account: account.to_string(),
+            num_lock_conflicts: counts.num_lock_conflicts,
+            num_retries: counts.num_retries,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSlotAccountConflicts {
+    pub slot: Slot,
+    /// The most contended accounts first
+    pub accounts: Vec<AdminRpcAccountConflict>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountConflicts {
+    /// In increasing slot order
+    pub slots: Vec<AdminRpcSlotAccountConflicts>,
+}
+
+impl Display for AdminRpcAccountConflicts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.slots.is_empty() {
+            return writeln!(f, "No account conflicts in the recent leader slots");
+        }
+        for slot in &self.slots {
+            writeln!(f, "Slot {}", slot.slot)?;
+            writeln!(
+                f,
+                "  {:<44}  {:>14}  {:>10}",
+                "Account", "Lock conflicts", "Retries"
+            )?;
+            for account in &slot.accounts {
+                writeln!(
+                    f,
+                    "  {:<44}  {:>14}  {:>10}",
+                    account.account, account.num_lock_conflicts, account.num_retries
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcAccountConflicts {}
+impl solana_cli_output::QuietDisplay for AdminRpcAccountConflicts {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcEpochAccountsHashStatus {
+    pub is_deprioritized: bool,
+    pub pending_slot: Option<Slot>,
+    pub is_calculating: bool,
+    pub last_calculated_slot: Option<Slot>,
+    pub last_calculation_ms: Option<u64>,
+}
+
+impl Display for AdminRpcEpochAccountsHashStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.pending_slot, self.is_calculating) {
+            (Some(slot), true) => {
+                writeln!(f, "Calculating the epoch accounts hash of slot {slot}")?
+            }
+            (Some(slot), false) => writeln!(
+                f,
+                "Waiting to calculate the epoch accounts hash of slot {slot}"
+            )?,
+            (None, _) => writeln!(f, "No epoch accounts hash calculation pending")?,
+        }
+        if self.is_deprioritized {
+            writeln!(f, "Calculations are deprioritized")?;
+        }
+        if let Some(slot) = self.last_calculated_slot {
+            writeln!(
+                f,
+                "Last calculated the epoch accounts hash of slot {slot} in {}ms",
+                self.last_calculation_ms.unwrap_or_default()
+            )?;
+        }
+        Ok(())
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcEpochAccountsHashStatus {}
+impl solana_cli_output::QuietDisplay for AdminRpcEpochAccountsHashStatus {}
+
+impl From<EpochAccountsHashStatus> for AdminRpcEpochAccountsHashStatus {
+    fn from(status: EpochAccountsHashStatus) -> Self {
+        Self {
+            is_deprioritized: status.is_deprioritized,
+            pending_slot: status.pending_slot,
+            is_calculating: status.is_calculating,
+            last_calculated_slot: status.last_calculated_slot,
+            last_calculation_ms: status.last_calculation_ms,
+        }
+    }
+}
+
+#[rpc]
+pub trait AdminRpc {
+    type Metadata;
+
+    #[rpc(meta, name = "exit")]
+    fn exit(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "reloadPlugin")]
+    fn reload_plugin(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+        config_file: String,
+    ) -> BoxFuture<Result<()>>;
+
+    #[rpc(meta, name = "unloadPlugin")]
+    fn unload_plugin(&self, meta: Self::Metadata, name: String) -> BoxFuture<Result<()>>;
+
+    #[rpc(meta, name = "loadPlugin")]
+    fn load_plugin(&self, meta: Self::Metadata, config_file: String) -> BoxFuture<Result<String>>;
+
+    #[rpc(meta, name = "listPlugins")]
+    fn list_plugins(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<String>>>;
+
+    #[rpc(meta, name = "rpcAddress")]
+    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>>;
+
+    #[rpc(name = "setLogFilter")]
+    fn set_log_filter(&self, filter: String) -> Result<()>;
+
+    #[rpc(meta, name = "startTime")]
+    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime>;
+
+    #[rpc(meta, name = "startProgress")]
+    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress>;
+
+    #[rpc(meta, name = "addAuthorizedVoter")]
+    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()>;
+
+    #[rpc(meta, name = "addAuthorizedVoterFromBytes")]
+    fn add_authorized_voter_from_bytes(&self, meta: Self::Metadata, keypair: Vec<u8>)
+        -> Result<()>;
+
+    #[rpc(meta, name = "removeAllAuthorizedVoters")]
+    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "setIdentity")]
+    fn set_identity(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        require_tower: bool,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "setIdentityFromBytes")]
+    fn set_identity_from_bytes(
+        &self,
+        meta: Self::Metadata,
+        identity_keypair: Vec<u8>,
+        require_tower: bool,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "setIdentityStaged")]
+    fn set_identity_staged(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        max_tower_lag_slots: Option<u64>,
+        observation_ms: Option<u64>,
+    ) -> Result<AdminRpcStagedIdentityReport>;
+
+    #[rpc(meta, name = "setIdentityStagedFromBytes")]
+    fn set_identity_staged_from_bytes(
+        &self,
+        meta: Self::Metadata,
+        identity_keypair: Vec<u8>,
+        max_tower_lag_slots: Option<u64>,
+        observation_ms: Option<u64>,
+    ) -> Result<AdminRpcStagedIdentityReport>;
+
+    #[rpc(meta, name = "setStakedNodesOverrides")]
+    fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()>;
+
+    #[rpc(meta, name = "contactInfo")]
+    fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
+
+    #[rpc(meta, name = "repairShredFromPeer")]
+    fn repair_shred_from_peer(
+        &self,
+        meta: Self::Metadata,
+        pubkey: Option<Pubkey>,
+        slot: u64,
+        shred_index: u64,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "repairWhitelist")]
+    fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist>;
+
+    #[rpc(meta, name = "setRepairWhitelist")]
+    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()>;
+
+    #[rpc(meta, name = "getSecondaryIndexKeySize")]
+    fn get_secondary_index_key_size(
+        &self,
+        meta: Self::Metadata,
+        pubkey_str: String,
+    ) -> Result<HashMap<RpcAccountIndex, usize>>;
+
+    #[rpc(meta, name = "setPublicTpuAddress")]
+    fn set_public_tpu_address(
+        &self,
+        meta: Self::Metadata,
+        public_tpu_addr: SocketAddr,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "setPublicTpuForwardsAddress")]
+    fn set_public_tpu_forwards_address(
+        &self,
+        meta: Self::Metadata,
+        public_tpu_forwards_addr: SocketAddr,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "setBankingWorkerThreads")]
+    fn set_banking_worker_threads(&self, meta: Self::Metadata, num_workers: u32) -> Result<()>;
+
+    #[rpc(meta, name = "setCommissionGuardOverride")]
+    fn set_commission_guard_override(&self, meta: Self::Metadata, enabled: bool) -> Result<()>;
+
+    #[rpc(meta, name = "duplicateShredProofs")]
+    fn duplicate_shred_proofs(&self, meta: Self::Metadata) -> Result<AdminRpcDuplicateShredProofs>;
+
+    #[rpc(meta, name = "forensicBundles")]
+    fn forensic_bundles(&self, meta: Self::Metadata) -> Result<AdminRpcForensicBundles>;
+
+    #[rpc(meta, name = "forensicBundle")]
+    fn forensic_bundle(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+    ) -> Result<AdminRpcForensicBundleContents>;
+
+    #[rpc(meta, name = "pohCalibration")]
+    fn poh_calibration(&self, meta: Self::Metadata) -> Result<AdminRpcPohCalibration>;
+
+    #[rpc(meta, name = "crdsValues")]
+    fn crds_values(
+        &self,
+        meta: Self::Metadata,
+        types: Option<Vec<String>>,
+        origin: Option<Pubkey>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<AdminRpcCrdsValues>;
+
+    #[rpc(meta, name = "gossipPeerScores")]
+    fn gossip_peer_scores(&self, meta: Self::Metadata) -> Result<AdminRpcGossipPeerScores>;
+
+    #[rpc(meta, name = "setGossipPeerScoreOverrides")]
+    fn set_gossip_peer_score_overrides(
+        &self,
+        meta: Self::Metadata,
+        overrides: Vec<IpAddr>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "scheduleBlockstoreCompaction")]
+    fn schedule_blockstore_compaction(
+        &self,
+        meta: Self::Metadata,
+        columns: Vec<String>,
+    ) -> Result<Vec<String>>;
+
+    #[rpc(meta, name = "blockstoreCompaction")]
+    fn blockstore_compaction(&self, meta: Self::Metadata) -> Result<AdminRpcBlockstoreCompaction>;
+
+    #[rpc(meta, name = "snapshotPackagingProgress")]
+    fn snapshot_packaging_progress(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcSnapshotPackagingProgress>;
+
+    #[rpc(meta, name = "snapshotArchiveVerification")]
+    fn snapshot_archive_verification(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcSnapshotArchiveVerification>;
+
+    #[rpc(meta, name = "healthDetail")]
+    fn health_detail(&self, meta: Self::Metadata) -> Result<AdminRpcHealthDetail>;
+
+    #[rpc(meta, name = "bandwidth")]
+    fn bandwidth(&self, meta: Self::Metadata) -> Result<AdminRpcBandwidth>;
+
+    #[rpc(meta, name = "ancientPackingPolicy")]
+    fn ancient_packing_policy(&self, meta: Self::Metadata) -> Result<AdminRpcAncientPackingPolicy>;
+
+    #[rpc(meta, name = "setAncientPackingPolicy")]
+    fn set_ancient_packing_policy(
+        &self,
+        meta: Self::Metadata,
+        policy: AdminRpcAncientPackingPolicy,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "accountsDiskUsage")]
+    fn accounts_disk_usage(
+        &self,
+        meta: Self::Metadata,
+        num_owners: Option<usize>,
+    ) -> Result<AdminRpcAccountsDiskUsage>;
+
+    #[rpc(meta, name = "precompilePrograms")]
+    fn precompile_programs(
+        &self,
+        meta: Self::Metadata,
+        num_programs: usize,
+    ) -> Result<AdminRpcProgramPrecompilation>;
+
+    #[rpc(meta, name = "bankHashBreakdowns")]
+    fn bank_hash_breakdowns(
+        &self,
+        meta: Self::Metadata,
+        slot: Option<Slot>,
+        num_slots: Option<usize>,
+    ) -> Result<AdminRpcBankHashBreakdowns>;
+
+    #[rpc(meta, name = "failoverStatus")]
+    fn failover_status(&self, meta: Self::Metadata) -> Result<AdminRpcFailoverStatus>;
+
+    #[rpc(meta, name = "failoverPromote")]
+    fn failover_promote(&self, meta: Self::Metadata, force: bool) -> Result<()>;
+
+    #[rpc(meta, name = "failoverDemote")]
+    fn failover_demote(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "setFailoverAutoPromote")]
+    fn set_failover_auto_promote(&self, meta: Self::Metadata, enabled: bool) -> Result<()>;
+
+    #[rpc(meta, name = "pauseLeader")]
+    fn pause_leader(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "resumeLeader")]
+    fn resume_leader(&self, meta: Self::Metadata) -> Result<AdminRpcLeaderPauseStatus>;
+
+    #[rpc(meta, name = "leaderPauseStatus")]
+    fn leader_pause_status(&self, meta: Self::Metadata) -> Result<AdminRpcLeaderPauseStatus>;
+
+    #[rpc(meta, name = "accountConflicts")]
+    fn account_conflicts(
+        &self,
+        meta: Self::Metadata,
+        slot: Option<Slot>,
+        num_accounts: Option<usize>,
+    ) -> Result<AdminRpcAccountConflicts>;
+
+    #[rpc(meta, name = "epochAccountsHashStatus")]
+    fn epoch_accounts_hash_status(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcEpochAccountsHashStatus>;
+
+    #[rpc(meta, name = "setEpochAccountsHashDeprioritized")]
+    fn set_epoch_accounts_hash_deprioritized(
+        &self,
+        meta: Self::Metadata,
+        is_deprioritized: bool,
+    ) -> Result<AdminRpcEpochAccountsHashStatus>;
+}
+
+pub struct AdminRpcImpl;
+impl AdminRpc for AdminRpcImpl {
+    type Metadata = AdminRpcRequestMetadata;
+
+    fn exit(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("exit admin rpc request received");
+
+        thread::Builder::new()
+            .name("solProcessExit".into())
+            .spawn(move || {
+                // Delay exit signal until this RPC request completes, otherwise the caller of `exit` might
+                // receive a confusing error as the validator shuts down before a response is sent back.
+                thread::sleep(Duration::from_millis(100));
+
+                warn!("validator exit requested");
+                meta.validator_exit.write().unwrap().exit();
+
+                // TODO: Debug why Exit doesn't always cause the validator to fully exit
+                // (rocksdb background processing or some other stuck thread perhaps?).
+                //
+                // If the process is still alive after five seconds, exit harder
+                thread::sleep(Duration::from_secs(
+                    env::var("SOLANA_VALIDATOR_EXIT_TIMEOUT")
+                        .ok()
+                        .and_then(|x| x.parse().ok())
+                        .unwrap_or(5),
+                ));
+                warn!("validator exit timeout");
+                std::process::exit(0);
+            })
+            .unwrap();
+        Ok(())
+    }
+
+    fn reload_plugin(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+        config_file: String,
+    ) -> BoxFuture<Result<()>> {
+        Box::pin(async move {
+            // Construct channel for plugin to respond to this particular rpc request instance
+            let (response_sender, response_receiver) = oneshot_channel();
+
+            // Send request to plugin manager if there is a geyser service
+            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
+                rpc_to_manager_sender
+                    .send(GeyserPluginManagerRequest::ReloadPlugin {
+                        name,
+                        config_file,
+                        response_sender,
+                    })
+                    .expect("GeyerPluginService should never drop request receiver");
+            } else {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "No geyser plugin service".to_string(),
+                    data: None,
+                });
+            }
+
+            // Await response from plugin manager
+            response_receiver
+                .await
+                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        })
+    }
+
+    fn load_plugin(&self, meta: Self::Metadata, config_file: String) -> BoxFuture<Result<String>> {
+        Box::pin(async move {
+            // Construct channel for plugin to respond to this particular rpc request instance
+            let (response_sender, response_receiver) = oneshot_channel();
+
+            // Send request to plugin manager if there is a geyser service
+            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
+                rpc_to_manager_sender
+                    .send(GeyserPluginManagerRequest::LoadPlugin {
+                        config_file,
+                        response_sender,
+                    })
+                    .expect("GeyerPluginService should never drop request receiver");
+            } else {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "No geyser plugin service".to_string(),
+                    data: None,
+                });
+            }
+
+            // Await response from plugin manager
+            response_receiver
+                .await
+                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        })
+    }
+
+    fn unload_plugin(&self, meta: Self::Metadata, name: String) -> BoxFuture<Result<()>> {
+        Box::pin(async move {
+            // Construct channel for plugin to respond to this particular rpc request instance
+            let (response_sender, response_receiver) = oneshot_channel();
+
+            // Send request to plugin manager if there is a geyser service
+            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
+                rpc_to_manager_sender
+                    .send(GeyserPluginManagerRequest::UnloadPlugin {
+                        name,
+                        response_sender,
+                    })
+                    .expect("GeyerPluginService should never drop request receiver");
+            } else {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "No geyser plugin service".to_string(),
+                    data: None,
+                });
+            }
+
+            // Await response from plugin manager
+            response_receiver
+                .await
+                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        })
+    }
+
+    fn list_plugins(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<String>>> {
+        Box::pin(async move {
+            // Construct channel for plugin to respond to this particular rpc request instance
+            let (response_sender, response_receiver) = oneshot_channel();
+
+            // Send request to plugin manager
+            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
+                rpc_to_manager_sender
+                    .send(GeyserPluginManagerRequest::ListPlugins { response_sender })
+                    .expect("GeyerPluginService should never drop request receiver");
+            } else {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "No geyser plugin service".to_string(),
+                    data: None,
+                });
+            }
+
+            // Await response from plugin manager
+            response_receiver
+                .await
+                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        })
+    }
+
+    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>> {
+        debug!("rpc_addr admin rpc request received");
+        Ok(meta.rpc_addr)
+    }
+
+    fn set_log_filter(&self, filter: String) -> Result<()> {
+        debug!("set_log_filter admin rpc request received");
+        solana_logger::setup_with(&filter);
+        Ok(())
+    }
+
+    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime> {
+        debug!("start_time admin rpc request received");
+        Ok(meta.start_time)
+    }
+
+    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress> {
+        debug!("start_progress admin rpc request received");
+        Ok(*meta.start_progress.read().unwrap())
+    }
+
+    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()> {
+        debug!("add_authorized_voter request received");
+
+        let authorized_voter = read_keypair_file(keypair_file)
+            .map_err(|err| jsonrpc_core::error::Error::invalid_params(format!("{err}")))?;
+
+        AdminRpcImpl::add_authorized_voter_keypair(meta, authorized_voter)
+    }
+
+    fn add_authorized_voter_from_bytes(
+        &self,
+        meta: Self::Metadata,
+        keypair: Vec<u8>,
+    ) -> Result<()> {
+        debug!("add_authorized_voter_from_bytes request received");
+
+        let authorized_voter = Keypair::from_bytes(&keypair).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read authorized voter keypair from provided byte array: {err}"
+            ))
+        })?;
+
+        AdminRpcImpl::add_authorized_voter_keypair(meta, authorized_voter)
+    }
+
+    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("remove_all_authorized_voters received");
+        meta.authorized_voter_keypairs.write().unwrap().clear();
+        Ok(())
+    }
+
+    fn set_identity(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        require_tower: bool,
+    ) -> Result<()> {
+        debug!("set_identity request received");
+
+        let identity_keypair = read_keypair_file(&keypair_file).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read identity keypair from {keypair_file}: {err}"
+            ))
+        })?;
+
+        AdminRpcImpl::set_identity_keypair(meta, identity_keypair, require_tower)
+    }
+
+    fn set_identity_from_bytes(
+        &self,
+        meta: Self::Metadata,
+        identity_keypair: Vec<u8>,
+        require_tower: bool,
+    ) -> Result<()> {
+        debug!("set_identity_from_bytes request received");
+
+        let identity_keypair = Keypair::from_bytes(&identity_keypair).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read identity keypair from provided byte array: {err}"
+            ))
+        })?;
+
+        AdminRpcImpl::set_identity_keypair(meta, identity_keypair, require_tower)
+    }
+
+    fn set_identity_staged(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        max_tower_lag_slots: Option<u64>,
+        observation_ms: Option<u64>,
+    ) -> Result<AdminRpcStagedIdentityReport> {
+        debug!("set_identity_staged request received");
+
+        let identity_keypair = read_keypair_file(&keypair_file).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read identity keypair from {keypair_file}: {err}"
+            ))
+        })?;
+
+        AdminRpcImpl::set_identity_keypair_staged(
+            meta,
+            identity_keypair,
+            max_tower_lag_slots,
+            observation_ms,
+        )
+    }
+
+    fn set_identity_staged_from_bytes(
+        &self,
+        meta: Self::Metadata,
+        identity_keypair: Vec<u8>,
+        max_tower_lag_slots: Option<u64>,
+        observation_ms: Option<u64>,
+    ) -> Result<AdminRpcStagedIdentityReport> {
+        debug!("set_identity_staged_from_bytes request received");
+
+        let identity_keypair = Keypair::from_bytes(&identity_keypair).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read identity keypair from provided byte array: {err}"
+            ))
+        })?;
+
+        AdminRpcImpl::set_identity_keypair_staged(
+            meta,
+            identity_keypair,
+            max_tower_lag_slots,
+            observation_ms,
+        )
+    }
+
+    fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()> {
+        let loaded_config = load_staked_nodes_overrides(&path)
+            .map_err(|err| {
+                error!(
+                    "Failed to load staked nodes overrides from {}: {}",
+                    &path, err
+                );
+                jsonrpc_core::error::Error::internal_error()
+            })?
+            .staked_map_id;
+        let mut write_staked_nodes = meta.staked_nodes_overrides.write().unwrap();
+        write_staked_nodes.clear();
+        write_staked_nodes.extend(loaded_config);
+        info!("Staked nodes overrides loaded from {}", path);
+        debug!("overrides map: {:?}", write_staked_nodes);
+        Ok(())
+    }
+
+    fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo> {
+        meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
+    }
+
+    fn repair_shred_from_peer(
+        &self,
+        meta: Self::Metadata,
+        pubkey: Option<Pubkey>,
+        slot: u64,
+        shred_index: u64,
+    ) -> Result<()> {
+        debug!("repair_shred_from_peer request received");
+
+        meta.with_post_init(|post_init| {
+            repair_service::RepairService::request_repair_for_shred_from_peer(
+                post_init.cluster_info.clone(),
+                post_init.cluster_slots.clone(),
+                pubkey,
+                slot,
+                shred_index,
+                &post_init.repair_socket.clone(),
+                post_init.outstanding_repair_requests.clone(),
+            );
+            Ok(())
+        })
+    }
+
+    fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist> {
+        debug!("repair_whitelist request received");
+
+        meta.with_post_init(|post_init| {
+            let whitelist: Vec<_> = post_init
+                .repair_whitelist
+                .read()
+                .unwrap()
+                .iter()
+                .copied()
+                .collect();
+            Ok(AdminRpcRepairWhitelist { whitelist })
+        })
+    }
+
+    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()> {
+        debug!("set_repair_whitelist request received");
+
+        let whitelist: HashSet<Pubkey> = whitelist.into_iter().collect();
+        meta.with_post_init(|post_init| {
+            *post_init.repair_whitelist.write().unwrap() = whitelist;
+            warn!(
+                "Repair whitelist set to {:?}",
+                &post_init.repair_whitelist.read().unwrap()
+            );
+            Ok(())
+        })
+    }
+
+    fn get_secondary_index_key_size(
+        &self,
+        meta: Self::Metadata,
+        pubkey_str: String,
+    ) -> Result<HashMap<RpcAccountIndex, usize>> {
+        debug!(
+            "get_secondary_index_key_size rpc request received: {:?}",
+            pubkey_str
+        );
+        let index_key = verify_pubkey(&pubkey_str)?;
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+
+            // Take ref to enabled AccountSecondaryIndexes
+            let enabled_account_indexes = &bank.accounts().accounts_db.account_indexes;
+
+            // Exit if secondary indexes are not enabled
+            if enabled_account_indexes.is_empty() {
+                debug!("get_secondary_index_key_size: secondary index not enabled.");
+                return Ok(HashMap::new());
+            };
+
+            // Make sure the requested key is not explicitly excluded
+            if !enabled_account_indexes.include_key(&index_key) {
+                return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
+                    index_key: index_key.to_string(),
+                }
+                .into());
+            }
+
+            // Grab a ref to the AccountsDbfor this Bank
+            let accounts_index = &bank.accounts().accounts_db.accounts_index;
+
+            // Find the size of the key in every index where it exists
+            let found_sizes = enabled_account_indexes
+                .indexes
+                .iter()
+                .filter_map(|index| {
+                    accounts_index
+                        .get_index_key_size(index, &index_key)
+                        .map(|size| (rpc_account_index_from_account_index(index), size))
+                })
+                .collect::<HashMap<_, _>>();
+
+            // Note: Will return an empty HashMap if no keys are found.
+            if found_sizes.is_empty() {
+                debug!("get_secondary_index_key_size: key not found in the secondary index.");
+            }
+            Ok(found_sizes)
+        })
+    }
+
+    fn set_public_tpu_address(
+        &self,
+        meta: Self::Metadata,
+        public_tpu_addr: SocketAddr,
+    ) -> Result<()> {
+        debug!("set_public_tpu_address rpc request received: {public_tpu_addr}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .cluster_info
+                .my_contact_info()
+                .tpu(Protocol::UDP)
+                .ok_or_else(|| {
+                    error!(
+                        "The public TPU address isn't being published. The node is likely in \
+                         repair mode. See help for --restricted-repair-only-mode for more \
+                         information."
+                    );
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            post_init
+                .cluster_info
+                .set_tpu(public_tpu_addr)
+                .map_err(|err| {
+                    error!("Failed to set public TPU address to {public_tpu_addr}: {err}");
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            let my_contact_info = post_init.cluster_info.my_contact_info();
+            warn!(
+                "Public TPU addresses set to {:?} (udp) and {:?} (quic)",
+                my_contact_info.tpu(Protocol::UDP),
+                my_contact_info.tpu(Protocol::QUIC),
+            );
+            Ok(())
+        })
+    }
+
+    fn set_public_tpu_forwards_address(
+        &self,
+        meta: Self::Metadata,
+        public_tpu_forwards_addr: SocketAddr,
+    ) -> Result<()> {
+        debug!("set_public_tpu_forwards_address rpc request received: {public_tpu_forwards_addr}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .cluster_info
+                .my_contact_info()
+                .tpu_forwards(Protocol::UDP)
+                .ok_or_else(|| {
+                    error!(
+                        "The public TPU Forwards address isn't being published. The node is \
+                         likely in repair mode. See help for --restricted-repair-only-mode for \
+                         more information."
+                    );
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            post_init
+                .cluster_info
+                .set_tpu_forwards(public_tpu_forwards_addr)
+                .map_err(|err| {
+                    error!("Failed to set public TPU address to {public_tpu_forwards_addr}: {err}");
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            let my_contact_info = post_init.cluster_info.my_contact_info();
+            warn!(
+                "Public TPU Forwards addresses set to {:?} (udp) and {:?} (quic)",
+                my_contact_info.tpu_forwards(Protocol::UDP),
+                my_contact_info.tpu_forwards(Protocol::QUIC),
+            );
+            Ok(())
         })
     }
 
-    fn unload_plugin(&self, meta: Self::Metadata, name: String) -> BoxFuture<Result<()>> {
-        Box::pin(async move {
-            // Construct channel for plugin to respond to this particular rpc request instance
-            let (response_sender, response_receiver) = oneshot_channel();
-
-            // Send request to plugin manager if there is a geyser service
-            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
-                rpc_to_manager_sender
-                    .send(GeyserPluginManagerRequest::UnloadPlugin {
-                        name,
-                        response_sender,
-                    })
-                    .expect("GeyerPluginService should never drop request receiver");
-            } else {
-                return Err(jsonrpc_core::Error {
-                    code: ErrorCode::InvalidRequest,
-                    message: "No geyser plugin service".to_string(),
-                    data: None,
-                });
-            }
+    fn set_banking_worker_threads(&self, meta: Self::Metadata, num_workers: u32) -> Result<()> {
+        debug!("set_banking_worker_threads request received: {num_workers}");
 
-            // Await response from plugin manager
-            response_receiver
-                .await
-                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        meta.with_post_init(|post_init| {
+            let banking_stage = post_init.banking_stage.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params("banking stage is not running")
+            })?;
+            banking_stage
+                .set_num_workers(num_workers)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+            warn!("Banking stage worker threads set to {num_workers}");
+            Ok(())
         })
     }
 
-    fn list_plugins(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<String>>> {
-        Box::pin(async move {
-            // Construct channel for plugin to respond to this particular rpc request instance
-            let (response_sender, response_receiver) = oneshot_channel();
+    fn set_commission_guard_override(&self, meta: Self::Metadata, enabled: bool) -> Result<()> {
+        debug!("set_commission_guard_override request received: {enabled}");
 
-            // Send request to plugin manager
-            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
-                rpc_to_manager_sender
-                    .send(GeyserPluginManagerRequest::ListPlugins { response_sender })
-                    .expect("GeyerPluginService should never drop request receiver");
+        meta.with_post_init(|post_init| {
+            let commission_guard = post_init.commission_guard.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(
+                    "commission guard is not enabled, see --max-commission-change",
+                )
+            })?;
+            commission_guard.set_override(enabled);
+            if enabled {
+                warn!(
+                    "Commission guard of vote account {} overridden",
+                    commission_guard.vote_account()
+                );
             } else {
-                return Err(jsonrpc_core::Error {
-                    code: ErrorCode::InvalidRequest,
-                    message: "No geyser plugin service".to_string(),
-                    data: None,
-                });
+                warn!(
+                    "Commission guard of vote account {} restored",
+                    commission_guard.vote_account()
+                );
             }
-
-            // Await response from plugin manager
-            response_receiver
-                .await
-                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+            Ok(())
         })
     }
 
-    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>> {
-        debug!("rpc_addr admin rpc request received");
-        Ok(meta.rpc_addr)
+    fn duplicate_shred_proofs(&self, meta: Self::Metadata) -> Result<AdminRpcDuplicateShredProofs> {
+        debug!("duplicate_shred_proofs request received");
+
+        meta.with_post_init(|post_init| {
+            let proofs = post_init
+                .duplicate_shred_proofs
+                .proofs()
+                .into_iter()
+                .map(AdminRpcDuplicateShredProof::from)
+                .collect();
+            Ok(AdminRpcDuplicateShredProofs { proofs })
+        })
     }
 
-    fn set_log_filter(&self, filter: String) -> Result<()> {
-        debug!("set_log_filter admin rpc request received");
-        solana_logger::setup_with(&filter);
-        Ok(())
+    fn forensic_bundles(&self, meta: Self::Metadata) -> Result<AdminRpcForensicBundles> {
+        debug!("forensic_bundles request received");
+
+        meta.with_post_init(|post_init| {
+            let forensic_bundles = post_init.forensic_bundles.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(
+                    "forensic bundles are not enabled, see --forensic-bundle-dir",
+                )
+            })?;
+            let bundles = forensic_bundles
+                .list()
+                .map_err(|err| {
+                    error!("Failed to list the forensic bundles: {err}");
+                    jsonrpc_core::error::Error::internal_error()
+                })?
+                .into_iter()
+                .map(AdminRpcForensicBundle::from)
+                .collect();
+            Ok(AdminRpcForensicBundles { bundles })
+        })
     }
 
-    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime> {
-        debug!("start_time admin rpc request received");
-        Ok(meta.start_time)
+    fn forensic_bundle(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+    ) -> Result<AdminRpcForensicBundleContents> {
+        debug!("forensic_bundle request received: {name}");
+
+        meta.with_post_init(|post_init| {
+            let forensic_bundles = post_init.forensic_bundles.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(
+                    "forensic bundles are not enabled, see --forensic-bundle-dir",
+                )
+            })?;
+            let (manifest, files) = forensic_bundles
+                .read(&name)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+            let files = files
+                .into_iter()
+                .map(|(name, data)| AdminRpcForensicBundleFile {
+                    name,
+                    data: BASE64_STANDARD.encode(data),
+                })
+                .collect();
+            Ok(AdminRpcForensicBundleContents {
+                bundle: AdminRpcForensicBundle::from(manifest),
+                files,
+            })
+        })
     }
 
-    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress> {
-        debug!("start_progress admin rpc request received");
-        Ok(*meta.start_progress.read().unwrap())
+    fn poh_calibration(&self, meta: Self::Metadata) -> Result<AdminRpcPohCalibration> {
+        debug!("poh_calibration request received");
+
+        meta.with_post_init(|post_init| {
+            let poh_calibration = post_init.poh_calibration.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(
+                    "PoH calibration is not enabled, see --poh-calibration-interval-ms",
+                )
+            })?;
+            Ok(AdminRpcPohCalibration::from(poh_calibration.as_ref()))
+        })
     }
 
-    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()> {
-        debug!("add_authorized_voter request received");
+    fn crds_values(
+        &self,
+        meta: Self::Metadata,
+        types: Option<Vec<String>>,
+        origin: Option<Pubkey>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<AdminRpcCrdsValues> {
+        debug!("crds_values request received");
+
+        let types = types
+            .unwrap_or_default()
+            .iter()
+            .map(|value_type| value_type.parse::<CrdsValueType>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+        let filter = CrdsInspectFilter {
+            types,
+            origin,
+            cursor: cursor.unwrap_or_default(),
+            limit: limit.unwrap_or(DEFAULT_CRDS_INSPECT_LIMIT),
+        };
+        meta.with_post_init(|post_init| {
+            let page = post_init.cluster_info.inspect_crds(&filter);
+            let now = solana_sdk::timing::timestamp();
+            Ok(AdminRpcCrdsValues::new(page, now))
+        })
+    }
 
-        let authorized_voter = read_keypair_file(keypair_file)
-            .map_err(|err| jsonrpc_core::error::Error::invalid_params(format!("{err}")))?;
+    fn gossip_peer_scores(&self, meta: Self::Metadata) -> Result<AdminRpcGossipPeerScores> {
+        debug!("gossip_peer_scores request received");
 
-        AdminRpcImpl::add_authorized_voter_keypair(meta, authorized_voter)
+        meta.with_post_init(|post_init| {
+            let peer_scores = post_init.cluster_info.peer_scores();
+            let now = solana_sdk::timing::timestamp();
+            Ok(AdminRpcGossipPeerScores {
+                scores: peer_scores
+                    .scores(now)
+                    .into_iter()
+                    .map(AdminRpcGossipPeerScore::from)
+                    .collect(),
+                overrides: peer_scores
+                    .overrides()
+                    .iter()
+                    .map(IpAddr::to_string)
+                    .collect(),
+            })
+        })
     }
 
-    fn add_authorized_voter_from_bytes(
+    fn set_gossip_peer_score_overrides(
         &self,
         meta: Self::Metadata,
-        keypair: Vec<u8>,
+        overrides: Vec<IpAddr>,
     ) -> Result<()> {
-        debug!("add_authorized_voter_from_bytes request received");
+        debug!("set_gossip_peer_score_overrides request received: {overrides:?}");
 
-        let authorized_voter = Keypair::from_bytes(&keypair).map_err(|err| {
-            jsonrpc_core::error::Error::invalid_params(format!(
-                "Failed to read authorized voter keypair from provided byte array: {err}"
-            ))
-        })?;
+        meta.with_post_init(|post_init| {
+            post_init
+                .cluster_info
+                .peer_scores()
+                .set_overrides(overrides);
+            Ok(())
+        })
+    }
 
-        AdminRpcImpl::add_authorized_voter_keypair(meta, authorized_voter)
+    fn schedule_blockstore_compaction(
+        &self,
+        meta: Self::Metadata,
+        columns: Vec<String>,
+    ) -> Result<Vec<String>> {
+        debug!("schedule_blockstore_compaction request received: {columns:?}");
+
+        meta.with_post_init(|post_init| {
+            let queued = post_init
+                .blockstore_compaction_scheduler
+                .schedule(columns)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+            info!("Blockstore compactions scheduled: {queued:?}");
+            Ok(queued)
+        })
     }
 
-    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()> {
-        debug!("remove_all_authorized_voters received");
-        meta.authorized_voter_keypairs.write().unwrap().clear();
-        Ok(())
+    fn blockstore_compaction(&self, meta: Self::Metadata) -> Result<AdminRpcBlockstoreCompaction> {
+        debug!("blockstore_compaction request received");
+
+        meta.with_post_init(|post_init| {
+            let scheduler = &post_init.blockstore_compaction_scheduler;
+            Ok(AdminRpcBlockstoreCompaction {
+                queued: scheduler.queued(),
+                columns: scheduler
+                    .pending_compaction_bytes()
+                    .into_iter()
+                    .map(
+                        |(column, pending_compaction_bytes)| AdminRpcColumnCompaction {
+                            column: column.to_string(),
+                            pending_compaction_bytes,
+                        },
+                    )
+                    .collect(),
+            })
+        })
     }
 
-    fn set_identity(
+    fn snapshot_packaging_progress(
         &self,
         meta: Self::Metadata,
-        keypair_file: String,
-        require_tower: bool,
-    ) -> Result<()> {
-        debug!("set_identity request received");
-
-        let identity_keypair = read_keypair_file(&keypair_file).map_err(|err| {
-            jsonrpc_core::error::Error::invalid_params(format!(
-                "Failed to read identity keypair from {keypair_file}: {err}"
-            ))
-        })?;
+    ) -> Result<AdminRpcSnapshotPackagingProgress> {
+        debug!("snapshot_packaging_progress request received");
 
-        AdminRpcImpl::set_identity_keypair(meta, identity_keypair, require_tower)
+        meta.with_post_init(|post_init| {
+            Ok(AdminRpcSnapshotPackagingProgress {
+                job: post_init.snapshot_packaging_progress.job().map(Into::into),
+            })
+        })
     }
 
-    fn set_identity_from_bytes(
+    fn snapshot_archive_verification(
         &self,
         meta: Self::Metadata,
-        identity_keypair: Vec<u8>,
-        require_tower: bool,
-    ) -> Result<()> {
-        debug!("set_identity_from_bytes request received");
+    ) -> Result<AdminRpcSnapshotArchiveVerification> {
+        debug!("snapshot_archive_verification request received");
 
-        let identity_keypair = Keypair::from_bytes(&identity_keypair).map_err(|err| {
-            jsonrpc_core::error::Error::invalid_params(format!(
-                "Failed to read identity keypair from provided byte array: {err}"
-            ))
-        })?;
+        meta.with_post_init(|post_init| {
+            let status = &post_init.snapshot_archive_verification_status;
+            Ok(AdminRpcSnapshotArchiveVerification {
+                num_verified: status.num_verified(),
+                num_failed: status.num_failed(),
+                latest: status.latest().map(Into::into),
+                latest_failure: status.latest_failure().map(Into::into),
+            })
+        })
+    }
 
-        AdminRpcImpl::set_identity_keypair(meta, identity_keypair, require_tower)
+    fn health_detail(&self, meta: Self::Metadata) -> Result<AdminRpcHealthDetail> {
+        debug!("health_detail request received");
+
+        meta.with_post_init(|post_init| {
+            let rpc_health = post_init.rpc_health.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(
+                    "The health is checked by the RPC service, which is not enabled",
+                )
+            })?;
+            Ok(AdminRpcHealthDetail(rpc_health.check_detail()))
+        })
     }
 
-    fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()> {
-        let loaded_config = load_staked_nodes_overrides(&path)
-            .map_err(|err| {
-                error!(
-                    "Failed to load staked nodes overrides from {}: {}",
-                    &path, err
-                );
-                jsonrpc_core::error::Error::internal_error()
-            })?
-            .staked_map_id;
-        let mut write_staked_nodes = meta.staked_nodes_overrides.write().unwrap();
-        write_staked_nodes.clear();
-        write_staked_nodes.extend(loaded_config);
-        info!("Staked nodes overrides loaded from {}", path);
-        debug!("overrides map: {:?}", write_staked_nodes);
-        Ok(())
+    fn bandwidth(&self, _meta: Self::Metadata) -> Result<AdminRpcBandwidth> {
+        debug!("bandwidth request received");
+
+        Ok(AdminRpcBandwidth {
+            subsystems: bandwidth_accounting::bandwidth_accounting()
+                .latest_sample()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
     }
 
-    fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo> {
-        meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
+    fn ancient_packing_policy(&self, meta: Self::Metadata) -> Result<AdminRpcAncientPackingPolicy> {
+        debug!("ancient_packing_policy request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            Ok(bank.accounts().accounts_db.ancient_packing_policy().into())
+        })
     }
 
-    fn repair_shred_from_peer(
+    fn set_ancient_packing_policy(
         &self,
         meta: Self::Metadata,
-        pubkey: Option<Pubkey>,
-        slot: u64,
-        shred_index: u64,
+        policy: AdminRpcAncientPackingPolicy,
     ) -> Result<()> {
-        debug!("repair_shred_from_peer request received");
+        debug!("set_ancient_packing_policy request received: {policy:?}");
 
         meta.with_post_init(|post_init| {
-            repair_service::RepairService::request_repair_for_shred_from_peer(
-                post_init.cluster_info.clone(),
-                post_init.cluster_slots.clone(),
-                pubkey,
-                slot,
-                shred_index,
-                &post_init.repair_socket.clone(),
-                post_init.outstanding_repair_requests.clone(),
-            );
-            Ok(())
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts()
+                .accounts_db
+                .set_ancient_packing_policy(policy.into())
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(format!("{err}")))
         })
     }
 
-    fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist> {
-        debug!("repair_whitelist request received");
+    fn accounts_disk_usage(
+        &self,
+        meta: Self::Metadata,
+        num_owners: Option<usize>,
+    ) -> Result<AdminRpcAccountsDiskUsage> {
+        debug!("accounts_disk_usage request received");
 
         meta.with_post_init(|post_init| {
-            let whitelist: Vec<_> = post_init
-                .repair_whitelist
-                .read()
-                .unwrap()
-                .iter()
-                .copied()
-                .collect();
-            Ok(AdminRpcRepairWhitelist { whitelist })
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let disk_usage = bank
+                .accounts()
+                .accounts_db
+                .owner_disk_usage()
+                .ok_or_else(|| jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "The disk usage by owner is not tracked, see \
+                              --accounts-db-track-owner-disk-usage"
+                        .to_string(),
+                    data: None,
+                })?;
+            Ok(AdminRpcAccountsDiskUsage::new(disk_usage, num_owners))
         })
     }
 
-    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()> {
-        debug!("set_repair_whitelist request received");
+    fn precompile_programs(
+        &self,
+        meta: Self::Metadata,
+        num_programs: usize,
+    ) -> Result<AdminRpcProgramPrecompilation> {
+        debug!("precompile_programs request received: {num_programs}");
 
-        let whitelist: HashSet<Pubkey> = whitelist.into_iter().collect();
+        if num_programs == 0 {
+            return Err(jsonrpc_core::error::Error::invalid_params(
+                "the number of programs must be greater than zero",
+            ));
+        }
         meta.with_post_init(|post_init| {
-            *post_init.repair_whitelist.write().unwrap() = whitelist;
-            warn!(
-                "Repair whitelist set to {:?}",
-                &post_init.repair_whitelist.read().unwrap()
+            let report = post_init
+                .program_precompiler
+                .precompile(num_programs)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))?;
+            info!(
+                "Precompiled {} of the {} most invoked programs",
+                report.num_compiled, report.num_candidates
             );
-            Ok(())
+            Ok(report.into())
         })
     }
 
-    fn get_secondary_index_key_size(
+    fn bank_hash_breakdowns(
         &self,
         meta: Self::Metadata,
-        pubkey_str: String,
-    ) -> Result<HashMap<RpcAccountIndex, usize>> {
-        debug!(
-            "get_secondary_index_key_size rpc request received: {:?}",
-            pubkey_str
-        );
-        let index_key = verify_pubkey(&pubkey_str)?;
+        slot: Option<Slot>,
+        num_slots: Option<usize>,
+    ) -> Result<AdminRpcBankHashBreakdowns> {
+        debug!("bank_hash_breakdowns request received: {slot:?} {num_slots:?}");
+
         meta.with_post_init(|post_init| {
-            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let tracker = &post_init.bank_hash_breakdowns;
+            if !tracker.is_enabled() {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "The bank hash components are not tracked, see \
+                              --bank-hash-breakdown-slots"
+                        .to_string(),
+                    data: None,
+                });
+            }
+            let breakdowns = match slot {
+                Some(slot) => tracker.get(slot),
+                None => tracker.recent(num_slots.unwrap_or(usize::MAX)),
+            };
+            Ok(AdminRpcBankHashBreakdowns {
+                breakdowns: breakdowns.into_iter().map(Into::into).collect(),
+            })
+        })
+    }
 
-            // Take ref to enabled AccountSecondaryIndexes
-            let enabled_account_indexes = &bank.accounts().accounts_db.account_indexes;
+    fn failover_status(&self, meta: Self::Metadata) -> Result<AdminRpcFailoverStatus> {
+        debug!("failover_status request received");
+        AdminRpcImpl::with_failover(meta, |failover| Ok(failover.status().into()))
+    }
 
-            // Exit if secondary indexes are not enabled
-            if enabled_account_indexes.is_empty() {
-                debug!("get_secondary_index_key_size: secondary index not enabled.");
-                return Ok(HashMap::new());
-            };
+    fn failover_promote(&self, meta: Self::Metadata, force: bool) -> Result<()> {
+        debug!("failover_promote request received: {force}");
+        AdminRpcImpl::with_failover(meta, |failover| {
+            failover
+                .promote(force)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
+        })
+    }
 
-            // Make sure the requested key is not explicitly excluded
-            if !enabled_account_indexes.include_key(&index_key) {
-                return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
-                    index_key: index_key.to_string(),
-                }
-                .into());
-            }
+    fn failover_demote(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("failover_demote request received");
+        AdminRpcImpl::with_failover(meta, |failover| {
+            failover
+                .demote()
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
+        })
+    }
 
-            // Grab a ref to the AccountsDbfor this Bank
-            let accounts_index = &bank.accounts().accounts_db.accounts_index;
+    fn set_failover_auto_promote(&self, meta: Self::Metadata, enabled: bool) -> Result<()> {
+        debug!("set_failover_auto_promote request received: {enabled}");
+        AdminRpcImpl::with_failover(meta, |failover| {
+            failover.set_auto_promote(enabled);
+            Ok(())
+        })
+    }
 
-            // Find the size of the key in every index where it exists
-            let found_sizes = enabled_account_indexes
-                .indexes
-                .iter()
-                .filter_map(|index| {
-                    accounts_index
-                        .get_index_key_size(index, &index_key)
-                        .map(|size| (rpc_account_index_from_account_index(index), size))
-                })
-                .collect::<HashMap<_, _>>();
+    fn pause_leader(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("pause_leader request received");
+        meta.with_post_init(|post_init| {
+            post_init
+                .leader_pause
+                .pause()
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
+        })
+    }
 
-            // Note: Will return an empty HashMap if no keys are found.
-            if found_sizes.is_empty() {
-                debug!("get_secondary_index_key_size: key not found in the secondary index.");
-            }
-            Ok(found_sizes)
+    fn resume_leader(&self, meta: Self::Metadata) -> Result<AdminRpcLeaderPauseStatus> {
+        debug!("resume_leader request received");
+        meta.with_post_init(|post_init| {
+            post_init
+                .leader_pause
+                .resume()
+                .map(AdminRpcLeaderPauseStatus::from)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
         })
     }
 
-    fn set_public_tpu_address(
+    fn leader_pause_status(&self, meta: Self::Metadata) -> Result<AdminRpcLeaderPauseStatus> {
+        debug!("leader_pause_status request received");
+        meta.with_post_init(|post_init| Ok(post_init.leader_pause.status().into()))
+    }
+
+    fn account_conflicts(
         &self,
         meta: Self::Metadata,
-        public_tpu_addr: SocketAddr,
-    ) -> Result<()> {
-        debug!("set_public_tpu_address rpc request received: {public_tpu_addr}");
+        slot: Option<Slot>,
+        num_accounts: Option<usize>,
+    ) -> Result<AdminRpcAccountConflicts> {
+        debug!("account_conflicts request received: {slot:?} {num_accounts:?}");
 
         meta.with_post_init(|post_init| {
-            post_init
-                .cluster_info
-                .my_contact_info()
-                .tpu(Protocol::UDP)
-                .ok_or_else(|| {
-                    error!(
-                        "The public TPU address isn't being published. The node is likely in \
-                         repair mode. See help for --restricted-repair-only-mode for more \
-                         information."
-                    );
-                    jsonrpc_core::error::Error::internal_error()
-                })?;
-            post_init
-                .cluster_info
-                .set_tpu(public_tpu_addr)
-                .map_err(|err| {
-                    error!("Failed to set public TPU address to {public_tpu_addr}: {err}");
-                    jsonrpc_core::error::Error::internal_error()
-                })?;
-            let my_contact_info = post_init.cluster_info.my_contact_info();
-            warn!(
-                "Public TPU addresses set to {:?} (udp) and {:?} (quic)",
-                my_contact_info.tpu(Protocol::UDP),
-                my_contact_info.tpu(Protocol::QUIC),
-            );
-            Ok(())
+            let banking_stage = post_init.banking_stage.as_ref().ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params("banking stage is not running")
+            })?;
+            let slots = banking_stage
+                .account_conflicts()
+                .top_accounts(slot, num_accounts.unwrap_or(usize::MAX))
+                .into_iter()
+                .map(|(slot, accounts)| AdminRpcSlotAccountConflicts {
+                    slot,
+                    accounts: accounts.into_iter().map(Into::into).collect(),
+                })
+                .collect();
+            Ok(AdminRpcAccountConflicts { slots })
         })
     }
 
-    fn set_public_tpu_forwards_address(
+    fn epoch_accounts_hash_status(
         &self,
         meta: Self::Metadata,
-        public_tpu_forwards_addr: SocketAddr,
-    ) -> Result<()> {
-        debug!("set_public_tpu_forwards_address rpc request received: {public_tpu_forwards_addr}");
+    ) -> Result<AdminRpcEpochAccountsHashStatus> {
+        debug!("epoch_accounts_hash_status request received");
+        meta.with_post_init(|post_init| Ok(post_init.epoch_accounts_hash_scheduler.status().into()))
+    }
 
+    fn set_epoch_accounts_hash_deprioritized(
+        &self,
+        meta: Self::Metadata,
+        is_deprioritized: bool,
+    ) -> Result<AdminRpcEpochAccountsHashStatus> {
+        debug!("set_epoch_accounts_hash_deprioritized request received: {is_deprioritized}");
         meta.with_post_init(|post_init| {
-            post_init
-                .cluster_info
-                .my_contact_info()
-                .tpu_forwards(Protocol::UDP)
-                .ok_or_else(|| {
-                    error!(
-                        "The public TPU Forwards address isn't being published. The node is \
-                         likely in repair mode. See help for --restricted-repair-only-mode for \
-                         more information."
-                    );
-                    jsonrpc_core::error::Error::internal_error()
-                })?;
-            post_init
-                .cluster_info
-                .set_tpu_forwards(public_tpu_forwards_addr)
-                .map_err(|err| {
-                    error!("Failed to set public TPU address to {public_tpu_forwards_addr}: {err}");
-                    jsonrpc_core::error::Error::internal_error()
-                })?;
-            let my_contact_info = post_init.cluster_info.my_contact_info();
-            warn!(
-                "Public TPU Forwards addresses set to {:?} (udp) and {:?} (quic)",
-                my_contact_info.tpu_forwards(Protocol::UDP),
-                my_contact_info.tpu_forwards(Protocol::QUIC),
-            );
-            Ok(())
+            let scheduler = &post_init.epoch_accounts_hash_scheduler;
+            scheduler.set_deprioritized(is_deprioritized);
+            Ok(scheduler.status().into())
         })
     }
 }
 
 impl AdminRpcImpl {
+    fn with_failover<F, R>(meta: AdminRpcRequestMetadata, func: F) -> Result<R>
+    where
+        F: FnOnce(&FailoverCoordinator) -> Result<R>,
+    {
+        meta.with_post_init(|post_init| {
+            let failover = post_init
+                .failover
+                .as_ref()
+                .ok_or_else(|| jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "Failover is not enabled, see --failover-peer".to_string(),
+                    data: None,
+                })?;
+            func(failover)
+        })
+    }
+
     fn add_authorized_voter_keypair(
         meta: AdminRpcRequestMetadata,
         authorized_voter: Keypair,
@@ -722,20 +2647,43 @@ impl AdminRpcImpl {
                     })?;
             }
 
-            for n in post_init.notifies.iter() {
-                if let Err(err) = n.update_key(&identity_keypair) {
-                    error!("Error updating network layer keypair: {err}");
-                }
-            }
-
-            solana_metrics::set_host_id(identity_keypair.pubkey().to_string());
-            post_init
-                .cluster_info
-                .set_keypair(Arc::new(identity_keypair));
-            warn!("Identity set to {}", post_init.cluster_info.id());
+            identity_switch::set_identity(
+                &post_init.cluster_info,
+                &post_init.notifies,
+                Arc::new(identity_keypair),
+            );
             Ok(())
         })
     }
+
+    fn set_identity_keypair_staged(
+        meta: AdminRpcRequestMetadata,
+        identity_keypair: Keypair,
+        max_tower_lag_slots: Option<u64>,
+        observation_ms: Option<u64>,
+    ) -> Result<AdminRpcStagedIdentityReport> {
+        let config = StagedIdentityConfig {
+            max_tower_lag_slots,
+            observation_period: Duration::from_millis(
+                observation_ms.unwrap_or(DEFAULT_IDENTITY_OBSERVATION_MS),
+            ),
+        };
+        meta.with_post_init(|post_init| {
+            let authorized_voter_keypairs = meta.authorized_voter_keypairs.read().unwrap().clone();
+            let context = StagedIdentityContext {
+                cluster_info: &post_init.cluster_info,
+                notifies: &post_init.notifies,
+                bank_forks: &post_init.bank_forks,
+                vote_account: &post_init.vote_account,
+                authorized_voter_keypairs: &authorized_voter_keypairs,
+                tower_storage: meta.tower_storage.as_ref(),
+            };
+            context
+                .set_identity_staged(Arc::new(identity_keypair), &config)
+                .map(AdminRpcStagedIdentityReport::from)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(err.to_string()))
+        })
+    }
 }
 
 fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAccountIndex {
@@ -743,6 +2691,8 @@ fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAcco
         AccountIndex::ProgramId => RpcAccountIndex::ProgramId,
         AccountIndex::SplTokenOwner => RpcAccountIndex::SplTokenOwner,
         AccountIndex::SplTokenMint => RpcAccountIndex::SplTokenMint,
+        AccountIndex::DataSize => RpcAccountIndex::DataSize,
+        AccountIndex::NonceAuthority => RpcAccountIndex::NonceAuthority,
     }
 }
 
@@ -872,22 +2822,33 @@ mod tests {
         solana_accounts_db::{
             accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
             accounts_index::AccountSecondaryIndexes,
+            owner_disk_usage::OwnerDiskUsage,
         },
         solana_core::{
+            bank_hash_breakdowns::{BankHashBreakdownTracker, DEFAULT_BANK_HASH_BREAKDOWN_SLOTS},
+            blockstore_compaction_service::BlockstoreCompactionScheduler,
             consensus::tower_storage::NullTowerStorage,
+            leader_pause::LeaderPause,
+            program_precompilation::{ProgramPrecompiler, DEFAULT_PRECOMPILE_PROGRAMS_SLOTS},
             validator::{Validator, ValidatorConfig, ValidatorTpuConfig},
         },
         solana_gossip::cluster_info::{ClusterInfo, Node},
         solana_ledger::{
+            blockstore::Blockstore,
             create_new_tmp_ledger,
             genesis_utils::{
                 create_genesis_config, create_genesis_config_with_leader, GenesisConfigInfo,
             },
+            get_tmp_ledger_path_auto_delete,
         },
         solana_net_utils::bind_to_unspecified,
         solana_program_option::COption,
         solana_program_pack::Pack,
-        solana_rpc::rpc::create_validator_exit,
+        solana_rpc::{
+            optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
+            rpc::create_validator_exit, rpc_health::RpcHealth,
+        },
+        solana_rpc_client_api::response::RpcHealthConditionKind,
         solana_runtime::{
             bank::{Bank, BankTestConfig},
             bank_forks::BankForks,
@@ -897,22 +2858,25 @@ mod tests {
             pubkey::Pubkey,
             system_program,
         },
-        solana_streamer::socket::SocketAddrSpace,
+        solana_streamer::{bandwidth_accounting::Subsystem, socket::SocketAddrSpace},
         solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
         spl_generic_token::token,
         spl_token_2022::state::{Account as TokenAccount, AccountState as TokenAccountState, Mint},
         std::{collections::HashSet, fs::remove_dir_all, sync::atomic::AtomicBool},
+        tempfile::TempDir,
     };
 
     #[derive(Default)]
     struct TestConfig {
         account_indexes: AccountSecondaryIndexes,
+        track_owner_disk_usage: bool,
     }
 
     struct RpcHandler {
         io: MetaIoHandler<AdminRpcRequestMetadata>,
         meta: AdminRpcRequestMetadata,
         bank_forks: Arc<RwLock<BankForks>>,
+        _ledger_path: TempDir,
     }
 
     impl RpcHandler {
@@ -936,12 +2900,15 @@ mod tests {
             let (bank_forks, vote_keypair) = new_bank_forks_with_config(BankTestConfig {
                 accounts_db_config: AccountsDbConfig {
                     account_indexes: Some(config.account_indexes),
+                    track_owner_disk_usage: config.track_owner_disk_usage,
                     ..ACCOUNTS_DB_CONFIG_FOR_TESTING
                 },
             });
             let vote_account = vote_keypair.pubkey();
             let start_progress = Arc::new(RwLock::new(ValidatorStartProgress::default()));
             let repair_whitelist = Arc::new(RwLock::new(HashSet::new()));
+            let ledger_path = get_tmp_ledger_path_auto_delete!();
+            let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
             let meta = AdminRpcRequestMetadata {
                 rpc_addr: None,
                 start_time: SystemTime::now(),
@@ -950,7 +2917,7 @@ mod tests {
                 authorized_voter_keypairs: Arc::new(RwLock::new(vec![vote_keypair])),
                 tower_storage: Arc::new(NullTowerStorage {}),
                 post_init: Arc::new(RwLock::new(Some(AdminRpcRequestMetadataPostInit {
-                    cluster_info,
+                    cluster_info: cluster_info.clone(),
                     bank_forks: bank_forks.clone(),
                     vote_account,
                     repair_whitelist,
@@ -962,6 +2929,34 @@ mod tests {
                     cluster_slots: Arc::new(
                         solana_core::cluster_slots_service::cluster_slots::ClusterSlots::default(),
                     ),
+                    banking_stage: None,
+                    duplicate_shred_proofs: Arc::default(),
+                    forensic_bundles: None,
+                    poh_calibration: Some(Arc::new(PohCalibration::new(64))),
+                    commission_guard: None,
+                    blockstore_compaction_scheduler: Arc::new(BlockstoreCompactionScheduler::new(
+                        blockstore.clone(),
+                    )),
+                    program_precompiler: Arc::new(ProgramPrecompiler::new(
+                        blockstore.clone(),
+                        bank_forks.clone(),
+                        DEFAULT_PRECOMPILE_PROGRAMS_SLOTS,
+                    )),
+                    bank_hash_breakdowns: Arc::new(BankHashBreakdownTracker::new(
+                        DEFAULT_BANK_HASH_BREAKDOWN_SLOTS,
+                    )),
+                    failover: None,
+                    leader_pause: Arc::new(LeaderPause::new(cluster_info.clone())),
+                    epoch_accounts_hash_scheduler: Arc::default(),
+                    snapshot_packaging_progress: Arc::default(),
+                    snapshot_archive_verification_status: Arc::default(),
+                    rpc_health: Some(Arc::new(RpcHealth::new(
+                        OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+                        blockstore.clone(),
+                        128,
+                        Arc::default(),
+                        Arc::new(AtomicBool::new(true)),
+                    ))),
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
                 rpc_to_plugin_manager_sender: None,
@@ -973,6 +2968,7 @@ mod tests {
                 io,
                 meta,
                 bank_forks,
+                _ledger_path: ledger_path,
             }
         }
 
@@ -994,6 +2990,333 @@ mod tests {
         (BankForks::new_rw_arc(bank), Arc::new(voting_keypair))
     }
 
+    #[test]
+    fn test_schedule_blockstore_compaction() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"scheduleBlockstoreCompaction","params":[["meta","unknown"]]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(result["error"].is_object());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"scheduleBlockstoreCompaction","params":[["meta","data_shred","meta"]]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let queued: Vec<String> = serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(queued, vec!["meta", "data_shred"]);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"blockstoreCompaction"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let compaction: AdminRpcBlockstoreCompaction =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(compaction.queued, vec!["meta", "data_shred"]);
+        assert_eq!(compaction.columns.len(), Blockstore::column_names().len());
+    }
+
+    #[test]
+    fn test_snapshot_packaging_progress() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"snapshotPackagingProgress"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let progress: AdminRpcSnapshotPackagingProgress =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert!(progress.job.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_archive_verification() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"snapshotArchiveVerification"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let verification: AdminRpcSnapshotArchiveVerification =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(verification.num_verified, 0);
+        assert_eq!(verification.num_failed, 0);
+        assert!(verification.latest.is_none());
+    }
+
+    #[test]
+    fn test_health_detail() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"healthDetail"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let AdminRpcHealthDetail(detail) =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert!(detail.healthy);
+        assert_eq!(
+            detail.conditions[0].kind,
+            RpcHealthConditionKind::AccountsDbCatchingUp
+        );
+    }
+
+    #[test]
+    fn test_bandwidth() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"bandwidth"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let bandwidth: AdminRpcBandwidth =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        let subsystems: Vec<_> = bandwidth
+            .subsystems
+            .iter()
+            .map(|bandwidth| bandwidth.subsystem.as_str())
+            .collect();
+        assert_eq!(
+            subsystems,
+            Subsystem::ALL.map(|subsystem| subsystem.name()).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_poh_calibration() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"pohCalibration"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let calibration: AdminRpcPohCalibration =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(calibration.hashes_per_batch, 64);
+        assert_eq!(calibration.hashes_per_second, None);
+        assert!(!calibration.throttled);
+    }
+
+    #[test]
+    fn test_ancient_packing_policy() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = &rpc;
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"ancientPackingPolicy"}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let policy: AdminRpcAncientPackingPolicy =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(
+            AncientPackingPolicy::from(policy),
+            AncientPackingPolicy::default()
+        );
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAncientPackingPolicy","params":[{"idealStorageSize":1000000,"maxAncientStorages":100,"shrinkRatio":0.5}]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(result.get("error").is_none());
+        assert_eq!(
+            rpc.root_bank()
+                .accounts()
+                .accounts_db
+                .ancient_packing_policy(),
+            AncientPackingPolicy {
+                ideal_storage_size: 1_000_000,
+                max_ancient_storages: 100,
+                shrink_ratio: Some(0.5),
+            }
+        );
+
+        // invalid policies are rejected
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAncientPackingPolicy","params":[{"idealStorageSize":0,"maxAncientStorages":100,"shrinkRatio":null}]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(result.get("error").is_some());
+    }
+
+    #[test]
+    fn test_accounts_disk_usage() {
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"accountsDiskUsage","params":[1]}"#;
+        for track_owner_disk_usage in [false, true] {
+            let rpc = RpcHandler::start_with_config(TestConfig {
+                track_owner_disk_usage,
+                ..TestConfig::default()
+            });
+            let RpcHandler { io, meta, .. } = &rpc;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            if !track_owner_disk_usage {
+                assert!(result.get("error").is_some());
+                continue;
+            }
+            let disk_usage: AdminRpcAccountsDiskUsage =
+                serde_json::from_value(result["result"].clone()).unwrap();
+            assert!(disk_usage.num_summarized_storages <= disk_usage.num_storages);
+            assert!(disk_usage.owners.len() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_bank_hash_breakdowns() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = &rpc;
+        let bank = rpc.root_bank();
+        bank.freeze();
+        meta.with_post_init(|post_init| {
+            post_init.bank_hash_breakdowns.record_frozen_bank(&bank);
+            Ok(())
+        })
+        .unwrap();
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"bankHashBreakdowns","params":[null, 1]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let breakdowns: AdminRpcBankHashBreakdowns =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(breakdowns.breakdowns.len(), 1);
+        assert_eq!(breakdowns.breakdowns[0].slot, bank.slot());
+        assert_eq!(breakdowns.breakdowns[0].bank_hash, bank.hash().to_string());
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"bankHashBreakdowns","params":[{}]}}"#,
+            bank.slot() + 1
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let breakdowns: AdminRpcBankHashBreakdowns =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert!(breakdowns.breakdowns.is_empty());
+    }
+
+    #[test]
+    fn test_failover_disabled() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = &rpc;
+
+        for req in [
+            r#"{"jsonrpc":"2.0","id":1,"method":"failoverStatus","params":[]}"#,
+            r#"{"jsonrpc":"2.0","id":1,"method":"failoverPromote","params":[false]}"#,
+            r#"{"jsonrpc":"2.0","id":1,"method":"failoverDemote","params":[]}"#,
+            r#"{"jsonrpc":"2.0","id":1,"method":"setFailoverAutoPromote","params":[true]}"#,
+        ] {
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            assert_eq!(
+                result["error"]["message"],
+                "Failover is not enabled, see --failover-peer"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pause_leader() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = &rpc;
+
+        let status_req = r#"{"jsonrpc":"2.0","id":1,"method":"leaderPauseStatus","params":[]}"#;
+        let pause_req = r#"{"jsonrpc":"2.0","id":1,"method":"pauseLeader","params":[]}"#;
+        let resume_req = r#"{"jsonrpc":"2.0","id":1,"method":"resumeLeader","params":[]}"#;
+        let request = |req: &str| -> Value {
+            let res = io.handle_request_sync(req, meta.clone());
+            serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        assert_eq!(request(status_req)["result"]["isPaused"], false);
+        assert!(request(resume_req)["error"].is_object());
+        assert_eq!(request(pause_req)["result"], Value::Null);
+        assert!(request(pause_req)["error"].is_object());
+        let status = request(status_req);
+        assert_eq!(status["result"]["isPaused"], true);
+        assert_eq!(status["result"]["numSkippedSlots"], 0);
+        let tpu = meta.with_post_init(|post_init| {
+            Ok(post_init.cluster_info.my_contact_info().tpu(Protocol::UDP))
+        });
+        assert_eq!(tpu, Ok(None));
+
+        let status = request(resume_req);
+        assert_eq!(status["result"]["isPaused"], true);
+        assert_eq!(request(status_req)["result"]["isPaused"], false);
+        let tpu = meta.with_post_init(|post_init| {
+            Ok(post_init.cluster_info.my_contact_info().tpu(Protocol::UDP))
+        });
+        assert!(tpu.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_precompile_programs() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = &rpc;
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"precompilePrograms","params":[0]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(result.get("error").is_some());
+
+        // The blockstore has no rooted slots to count the invocations of.
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"precompilePrograms","params":[10]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let precompilation: AdminRpcProgramPrecompilation =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(precompilation.slot, rpc.root_bank().slot());
+        assert_eq!(precompilation.num_candidates, 0);
+        assert_eq!(precompilation.num_compiled, 0);
+    }
+
+    #[test]
+    fn test_admin_rpc_accounts_disk_usage() {
+        let owners = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let disk_usage = AccountsDiskUsage {
+            num_storages: 3,
+            num_summarized_storages: 2,
+            by_owner: HashMap::from([
+                (
+                    owners[0],
+                    OwnerDiskUsage {
+                        num_accounts: 1,
+                        data_len: 10,
+                        lamports: 1,
+                    },
+                ),
+                (
+                    owners[1],
+                    OwnerDiskUsage {
+                        num_accounts: 2,
+                        data_len: 20,
+                        lamports: 2,
+                    },
+                ),
+            ]),
+        };
+        let rpc_disk_usage = AdminRpcAccountsDiskUsage::new(disk_usage.clone(), Some(1));
+        assert_eq!(rpc_disk_usage.num_owners, 2);
+        // the owner with the largest data length is first
+        assert_eq!(
+            rpc_disk_usage.owners,
+            vec![AdminRpcOwnerDiskUsage {
+                owner: owners[1].to_string(),
+                num_accounts: 2,
+                data_len: 20,
+                lamports: 2,
+            }]
+        );
+        let rpc_disk_usage = AdminRpcAccountsDiskUsage::new(disk_usage, None);
+        assert_eq!(rpc_disk_usage.owners.len(), 2);
+        assert_eq!(rpc_disk_usage.owners[1].owner, owners[0].to_string());
+    }
+
     #[test]
     fn test_secondary_index_key_sizes() {
         for secondary_index_enabled in [true, false] {
@@ -1011,7 +3334,10 @@ mod tests {
             };
 
             // RPC & Bank Setup
-            let rpc = RpcHandler::start_with_config(TestConfig { account_indexes });
+            let rpc = RpcHandler::start_with_config(TestConfig {
+                account_indexes,
+                ..TestConfig::default()
+            });
 
             let bank = rpc.root_bank();
             let RpcHandler { io, meta, .. } = rpc;
@@ -1343,6 +3669,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_identity_staged_refused() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+
+        let RpcHandler { io, meta, .. } = rpc;
+
+        let previous_validator_id =
+            meta.with_post_init(|post_init| Ok(post_init.cluster_info.id()));
+        let validator_id_bytes = format!("{:?}", Keypair::new().to_bytes());
+
+        // The vote account is not linked to the new identity.
+        let set_id_request = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"setIdentityStagedFromBytes","params":[{validator_id_bytes}, null, 0]}}"#,
+        );
+        let response = io.handle_request_sync(&set_id_request, meta.clone());
+        let parsed_response: Value = serde_json::from_str(&response.expect("actual response"))
+            .expect("actual response deserialization");
+        assert!(parsed_response["error"].is_object());
+        assert_eq!(
+            meta.with_post_init(|post_init| Ok(post_init.cluster_info.id())),
+            previous_validator_id
+        );
+    }
+
     struct TestValidatorWithAdminRpc {
         meta: AdminRpcRequestMetadata,
         io: MetaIoHandler<AdminRpcRequestMetadata>,
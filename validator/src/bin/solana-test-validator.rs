@@ -73,6 +73,8 @@ fn main() {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "data-size" => AccountIndex::DataSize,
+            "nonce-authority" => AccountIndex::NonceAuthority,
             _ => unreachable!(),
         })
         .collect();
@@ -95,11 +97,38 @@ fn main() {
 
     let mut ledger_lock = ledger_lockfile(&ledger_path);
     let _ledger_write_guard = lock_ledger(&ledger_path, &mut ledger_lock);
+    if matches.is_present("list_branches") {
+        let branches = ledger_branch::ledger_branches(&ledger_path).unwrap_or_else(|err| {
+            println!("Error: Unable to list the branches of the ledger: {err}");
+            exit(1);
+        });
+        for branch in branches {
+            println!("{branch}");
+        }
+        exit(0);
+    }
+    if let Some(branch) = matches.value_of("save_branch") {
+        let branch_path =
+            ledger_branch::save_ledger_branch(&ledger_path, branch).unwrap_or_else(|err| {
+                println!("Error: Unable to save branch {branch}: {err}");
+                exit(1);
+            });
+        println!("Saved branch {branch} to {}", branch_path.display());
+        exit(0);
+    }
     if reset_ledger {
         remove_directory_contents(&ledger_path).unwrap_or_else(|err| {
             println!("Error: Unable to remove {}: {}", ledger_path.display(), err);
             exit(1);
         })
+    } else if let Some(branch) = matches.value_of("reset_to_branch") {
+        let branch_ledger_path = value_t!(matches, "branch_ledger_path", PathBuf)
+            .unwrap_or_else(|_| ledger_path.clone());
+        ledger_branch::restore_ledger_branch(&branch_ledger_path, branch, &ledger_path)
+            .unwrap_or_else(|err| {
+                println!("Error: Unable to reset the ledger to branch {branch}: {err}");
+                exit(1);
+            });
     }
     solana_runtime::snapshot_utils::remove_tmp_snapshot_archives(&ledger_path);
 
@@ -286,6 +315,7 @@ fn main() {
             .unwrap_or_default();
 
     let clone_feature_set = matches.is_present("clone_feature_set");
+    let clone_on_demand = matches.is_present("clone_on_demand");
 
     let warp_slot = if matches.is_present("warp_slot") {
         Some(match matches.value_of("warp_slot") {
@@ -470,6 +500,7 @@ fn main() {
     genesis.rpc_config(JsonRpcConfig {
         enable_rpc_transaction_history: true,
         enable_extended_tx_metadata_storage: true,
+        enable_program_signature_index: true,
         rpc_bigtable_config,
         faucet_addr: Some(faucet_addr),
         account_indexes,
@@ -525,6 +556,15 @@ fn main() {
         }
     }
 
+    if clone_on_demand {
+        genesis.clone_accounts_on_demand(
+            cluster_rpc_client
+                .as_ref()
+                .expect("--clone-on-demand requires --json-rpc-url argument")
+                .url(),
+        );
+    }
+
     if let Some(warp_slot) = warp_slot {
         genesis.warp_slot(warp_slot);
     }
@@ -592,6 +632,10 @@ fn main() {
 fn remove_directory_contents(ledger_path: &Path) -> Result<(), io::Error> {
     for entry in fs::read_dir(ledger_path)? {
         let entry = entry?;
+        // The branches saved of the ledger outlive its resets
+        if entry.file_name() == ledger_branch::BRANCHES_DIR {
+            continue;
+        }
         if entry.metadata()?.is_dir() {
             fs::remove_dir_all(entry.path())?
         } else {
@@ -4,7 +4,7 @@ use {
     rand::{seq::SliceRandom, thread_rng, Rng},
     rayon::prelude::*,
     solana_core::validator::{ValidatorConfig, ValidatorStartProgress},
-    solana_download_utils::{download_snapshot_archive, DownloadProgressRecord},
+    solana_download_utils::{download_snapshot_archive_from_peers, DownloadProgressRecord},
     solana_genesis_utils::download_then_check_genesis_hash,
     solana_gossip::{
         cluster_info::{ClusterInfo, Node},
@@ -163,6 +163,7 @@ fn start_gossip_node(
         &cluster_info,
         None,
         gossip_socket,
+        None,
         gossip_validators,
         should_check_duplicate_instance,
         None,
@@ -361,6 +362,7 @@ fn shutdown_gossip_service(gossip: (Arc<ClusterInfo>, Arc<AtomicBool>, GossipSer
 #[allow(clippy::too_many_arguments)]
 pub fn attempt_download_genesis_and_snapshot(
     rpc_contact_info: &ContactInfo,
+    mirror_rpc_addrs: &[SocketAddr],
     ledger_path: &Path,
     validator_config: &mut ValidatorConfig,
     bootstrap_config: &RpcBootstrapConfig,
@@ -413,6 +415,7 @@ pub fn attempt_download_genesis_and_snapshot(
         download_abort_count,
         snapshot_hash,
         rpc_contact_info,
+        mirror_rpc_addrs,
     )?;
 
     if let Some(url) = bootstrap_config.check_vote_account.as_ref() {
@@ -631,11 +634,19 @@ pub fn rpc_bootstrap(
             &bootstrap_config,
         );
         let (rpc_contact_info, snapshot_hash, rpc_client) = vetted_rpc_nodes.pop().unwrap();
+        // The other vetted nodes with the same snapshots serve chunks of the
+        // snapshot archives in parallel with this node.
+        let mirror_rpc_addrs: Vec<_> = vetted_rpc_nodes
+            .iter()
+            .filter(|(_, peer_snapshot_hash, _)| *peer_snapshot_hash == snapshot_hash)
+            .filter_map(|(peer_contact_info, _, _)| peer_contact_info.rpc())
+            .collect();
         get_rpc_nodes_time += get_rpc_nodes_start.elapsed();
 
         let snapshot_download_start = Instant::now();
         let download_result = attempt_download_genesis_and_snapshot(
             &rpc_contact_info,
+            &mirror_rpc_addrs,
             ledger_path,
             validator_config,
             &bootstrap_config,
@@ -1117,6 +1128,7 @@ fn download_snapshots(
     download_abort_count: &mut u64,
     snapshot_hash: Option<SnapshotHash>,
     rpc_contact_info: &ContactInfo,
+    mirror_rpc_addrs: &[SocketAddr],
 ) -> Result<(), String> {
     if snapshot_hash.is_none() {
         return Ok(());
@@ -1162,6 +1174,7 @@ fn download_snapshots(
             maximum_snapshot_download_abort,
             download_abort_count,
             rpc_contact_info,
+            mirror_rpc_addrs,
             full_snapshot_hash,
             SnapshotKind::FullSnapshot,
         )?;
@@ -1195,6 +1208,7 @@ fn download_snapshots(
                     maximum_snapshot_download_abort,
                     download_abort_count,
                     rpc_contact_info,
+                    mirror_rpc_addrs,
                     incremental_snapshot_hash,
                     SnapshotKind::IncrementalSnapshot(full_snapshot_hash.0),
                 )?;
@@ -1218,6 +1232,7 @@ fn download_snapshot(
     maximum_snapshot_download_abort: u64,
     download_abort_count: &mut u64,
     rpc_contact_info: &ContactInfo,
+    mirror_rpc_addrs: &[SocketAddr],
     desired_snapshot_hash: (Slot, Hash),
     snapshot_kind: SnapshotKind,
 ) -> Result<(), String> {
@@ -1238,10 +1253,11 @@ fn download_snapshot(
         desired_snapshot_hash.0,
         solana_runtime::snapshot_hash::SnapshotHash(desired_snapshot_hash.1),
     );
-    download_snapshot_archive(
+    download_snapshot_archive_from_peers(
         &rpc_contact_info
             .rpc()
             .ok_or_else(|| String::from("Invalid RPC address"))?,
+        mirror_rpc_addrs,
         full_snapshot_archives_dir,
         incremental_snapshot_archives_dir,
         desired_snapshot_hash,
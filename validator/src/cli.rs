@@ -14,11 +14,33 @@ use {
             is_parsable, is_pubkey, is_pubkey_or_keypair, is_slot, is_url_or_moniker,
         },
     },
-    solana_core::banking_trace::BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
+    solana_core::{
+        bank_hash_breakdowns::DEFAULT_BANK_HASH_BREAKDOWN_SLOTS,
+        banking_trace::BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
+        clock_drift_service::DEFAULT_MAX_CLOCK_DRIFT_MS,
+        consensus::vote_refresh::{
+            DEFAULT_VOTE_REFRESH_BLOCKHEIGHT, DEFAULT_VOTE_REFRESH_INTERVAL_MS,
+        },
+        failover::{
+            DEFAULT_FAILOVER_HEARTBEAT_INTERVAL_MS, DEFAULT_FAILOVER_MAX_MISSED_HEARTBEATS,
+        },
+        forwarding_stage::DEFAULT_FORWARDING_LEADER_FANOUT,
+        pipeline_tracing::DEFAULT_PIPELINE_TRACING_SAMPLE_INTERVAL,
+        program_precompilation::DEFAULT_PRECOMPILE_PROGRAMS_SLOTS,
+    },
     solana_faucet::faucet::{self, FAUCET_PORT},
+    solana_ledger::blockstore_tiering,
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
+    solana_poh::poh_calibration_service::DEFAULT_POH_CALIBRATION_INTERVAL_MS,
     solana_rayon_threadlimit::get_thread_count,
-    solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
+    solana_rpc::{
+        rpc::MAX_REQUEST_BODY_SIZE,
+        rpc_health::{
+            DEFAULT_HEALTH_MAX_INCOMPLETE_SLOTS, DEFAULT_HEALTH_MAX_SLOTS_SINCE_LAST_VOTE,
+            DEFAULT_HEALTH_MAX_SNAPSHOT_AGE_SLOTS,
+        },
+        rpc_pubsub_service::PubSubConfig,
+    },
     solana_rpc_client_api::request::{DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_MULTIPLE_ACCOUNTS},
     solana_runtime::{
         snapshot_bank_utils::{
@@ -42,6 +64,7 @@ use {
         DEFAULT_MAX_UNSTAKED_CONNECTIONS, DEFAULT_QUIC_ENDPOINTS,
     },
     solana_tpu_client::tpu_client::{DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_VOTE_USE_QUIC},
+    solana_turbine::remote_shred_signer::DEFAULT_REMOTE_SHRED_SIGNER_LATENCY_BUDGET,
     std::{path::PathBuf, str::FromStr},
 };
 
@@ -65,8 +88,22 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
         .global_setting(AppSettings::UnifiedHelpMessage)
         .global_setting(AppSettings::VersionlessSubcommands)
         .subcommand(commands::exit::command())
+        .subcommand(commands::account_conflicts::command())
+        .subcommand(commands::accounts_disk_usage::command())
+        .subcommand(commands::ancient_packing_policy::command())
         .subcommand(commands::authorized_voter::command())
+        .subcommand(commands::bandwidth::command())
+        .subcommand(commands::bank_hash_breakdowns::command())
+        .subcommand(commands::blockstore_compaction::command())
+        .subcommand(commands::commission_guard::command())
         .subcommand(commands::contact_info::command())
+        .subcommand(commands::crds_values::command())
+        .subcommand(commands::duplicate_shred_proofs::command())
+        .subcommand(commands::epoch_accounts_hash::command())
+        .subcommand(commands::failover::command())
+        .subcommand(commands::forensic_bundles::command())
+        .subcommand(commands::gossip_peer_scores::command())
+        .subcommand(commands::health::command())
         .subcommand(commands::repair_shred_from_peer::command())
         .subcommand(commands::repair_whitelist::command())
         .subcommand(
@@ -74,9 +111,15 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
         )
         .subcommand(commands::monitor::command())
         .subcommand(SubCommand::with_name("run").about("Run the validator"))
+        .subcommand(commands::pause_leader::command())
         .subcommand(commands::plugin::command())
+        .subcommand(commands::poh_calibration::command())
+        .subcommand(commands::precompile_programs::command())
+        .subcommand(commands::resume_leader::command())
+        .subcommand(commands::set_banking_threads::command())
         .subcommand(commands::set_identity::command())
         .subcommand(commands::set_log_filter::command())
+        .subcommand(commands::snapshot_progress::command())
         .subcommand(commands::staked_nodes_overrides::command())
         .subcommand(commands::wait_for_restart_window::command())
         .subcommand(commands::set_public_address::command());
@@ -373,8 +416,23 @@ pub struct DefaultArgs {
 
     pub genesis_archive_unpacked_size: String,
     pub health_check_slot_distance: String,
+    pub health_max_slots_since_last_vote: String,
+    pub health_max_snapshot_age_slots: String,
+    pub health_max_incomplete_slots: String,
+    pub retransmit_egress_burst_ms: String,
+    pub remote_shred_signer_latency_budget_ms: String,
     pub tower_storage: String,
     pub etcd_domain_name: String,
+    pub commission_change_window_epochs: String,
+    pub failover_heartbeat_interval_ms: String,
+    pub failover_max_missed_heartbeats: String,
+    pub max_clock_drift_ms: String,
+    pub vote_refresh_blockheight: String,
+    pub vote_refresh_interval_ms: String,
+    pub poh_calibration_interval_ms: String,
+    pub precompile_programs_slots: String,
+    pub bank_hash_breakdown_slots: String,
+    pub pipeline_tracing_sample_interval: String,
     pub send_transaction_service_config: send_transaction_service::Config,
 
     pub rpc_max_multiple_accounts: String,
@@ -394,6 +452,8 @@ pub struct DefaultArgs {
     pub rpc_bigtable_instance_name: String,
     pub rpc_bigtable_app_profile_id: String,
     pub rpc_bigtable_max_message_size: String,
+    pub blockstore_tiering_offload_epochs: String,
+    pub blockstore_tiering_cache_size: String,
     pub rpc_max_request_body_size: String,
     pub rpc_pubsub_worker_threads: String,
     pub rpc_pubsub_notification_threads: String,
@@ -432,6 +492,8 @@ pub struct DefaultArgs {
     pub num_quic_endpoints: String,
     pub vote_use_quic: String,
 
+    pub forwarding_leader_fanout: String,
+
     pub banking_trace_dir_byte_limit: String,
 
     pub wen_restart_path: String,
@@ -451,8 +513,25 @@ impl DefaultArgs {
             genesis_archive_unpacked_size: MAX_GENESIS_ARCHIVE_UNPACKED_SIZE.to_string(),
             rpc_max_multiple_accounts: MAX_MULTIPLE_ACCOUNTS.to_string(),
             health_check_slot_distance: DELINQUENT_VALIDATOR_SLOT_DISTANCE.to_string(),
+            health_max_slots_since_last_vote: DEFAULT_HEALTH_MAX_SLOTS_SINCE_LAST_VOTE.to_string(),
+            health_max_snapshot_age_slots: DEFAULT_HEALTH_MAX_SNAPSHOT_AGE_SLOTS.to_string(),
+            health_max_incomplete_slots: DEFAULT_HEALTH_MAX_INCOMPLETE_SLOTS.to_string(),
+            retransmit_egress_burst_ms: "100".to_string(),
+            remote_shred_signer_latency_budget_ms: DEFAULT_REMOTE_SHRED_SIGNER_LATENCY_BUDGET
+                .as_millis()
+                .to_string(),
             tower_storage: "file".to_string(),
             etcd_domain_name: "localhost".to_string(),
+            commission_change_window_epochs: "1".to_string(),
+            failover_heartbeat_interval_ms: DEFAULT_FAILOVER_HEARTBEAT_INTERVAL_MS.to_string(),
+            failover_max_missed_heartbeats: DEFAULT_FAILOVER_MAX_MISSED_HEARTBEATS.to_string(),
+            max_clock_drift_ms: DEFAULT_MAX_CLOCK_DRIFT_MS.to_string(),
+            vote_refresh_blockheight: DEFAULT_VOTE_REFRESH_BLOCKHEIGHT.to_string(),
+            vote_refresh_interval_ms: DEFAULT_VOTE_REFRESH_INTERVAL_MS.to_string(),
+            poh_calibration_interval_ms: DEFAULT_POH_CALIBRATION_INTERVAL_MS.to_string(),
+            precompile_programs_slots: DEFAULT_PRECOMPILE_PROGRAMS_SLOTS.to_string(),
+            bank_hash_breakdown_slots: DEFAULT_BANK_HASH_BREAKDOWN_SLOTS.to_string(),
+            pipeline_tracing_sample_interval: DEFAULT_PIPELINE_TRACING_SAMPLE_INTERVAL.to_string(),
             rpc_pubsub_max_active_subscriptions: PubSubConfig::default()
                 .max_active_subscriptions
                 .to_string(),
@@ -490,6 +569,9 @@ impl DefaultArgs {
                 .to_string(),
             rpc_bigtable_max_message_size: solana_storage_bigtable::DEFAULT_MAX_MESSAGE_SIZE
                 .to_string(),
+            blockstore_tiering_offload_epochs: blockstore_tiering::DEFAULT_OFFLOAD_AFTER_EPOCHS
+                .to_string(),
+            blockstore_tiering_cache_size: blockstore_tiering::DEFAULT_CACHE_MAX_BYTES.to_string(),
             rpc_pubsub_worker_threads: "4".to_string(),
             rpc_pubsub_notification_threads: get_thread_count().to_string(),
             maximum_full_snapshot_archives_to_retain: DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN
@@ -526,6 +608,7 @@ impl DefaultArgs {
             tpu_max_fwd_unstaked_connections: 0.to_string(),
             tpu_max_streams_per_ms: DEFAULT_MAX_STREAMS_PER_MS.to_string(),
             num_quic_endpoints: DEFAULT_QUIC_ENDPOINTS.to_string(),
+            forwarding_leader_fanout: DEFAULT_FORWARDING_LEADER_FANOUT.to_string(),
             rpc_max_request_body_size: MAX_REQUEST_BODY_SIZE.to_string(),
             banking_trace_dir_byte_limit: BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT.to_string(),
             wen_restart_path: "wen_restart_progress.proto".to_string(),
@@ -632,6 +715,47 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                      resume an existing ledger (if present)",
                 ),
         )
+        .arg(
+            Arg::with_name("save_branch")
+                .long("save-branch")
+                .value_name("NAME")
+                .takes_value(true)
+                .conflicts_with_all(&["reset", "reset_to_branch", "list_branches"])
+                .help(
+                    "Save the ledger into the branch NAME, in place of the branch of the same \
+                     name saved before, and exit. The ledger is left as is, to be later reset \
+                     to the branch, or other ledgers forked from it, with --reset-to-branch",
+                ),
+        )
+        .arg(
+            Arg::with_name("reset_to_branch")
+                .long("reset-to-branch")
+                .value_name("NAME")
+                .takes_value(true)
+                .conflicts_with("reset")
+                .help(
+                    "Reset the ledger to the branch NAME saved with --save-branch before \
+                     starting. The branches of the ledger are kept",
+                ),
+        )
+        .arg(
+            Arg::with_name("branch_ledger_path")
+                .long("branch-ledger")
+                .value_name("DIR")
+                .takes_value(true)
+                .requires("reset_to_branch")
+                .help(
+                    "Take the branch of --reset-to-branch from the ledger at DIR, to fork \
+                     the ledger from a branch of another ledger [default: --ledger]",
+                ),
+        )
+        .arg(
+            Arg::with_name("list_branches")
+                .long("list-branches")
+                .takes_value(false)
+                .conflicts_with_all(&["reset", "reset_to_branch"])
+                .help("List the branches saved of the ledger and exit"),
+        )
         .arg(
             Arg::with_name("quiet")
                 .short("q")
@@ -652,7 +776,13 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "data-size",
+                    "nonce-authority",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
@@ -1013,6 +1143,19 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .help("Override the runtime's account lock limit per transaction"),
         )
+        .arg(
+            Arg::with_name("clone_on_demand")
+                .long("clone-on-demand")
+                .takes_value(false)
+                .requires("json_rpc_url")
+                .help(
+                    "Copy the accounts missing from the ledger from the cluster referenced by \
+                     the --url argument as transactions first access them, along with the \
+                     programs owning them, the executable data of the upgradeable programs and \
+                     the address lookup tables of the transactions. Unlike --clone, this \
+                     applies to an existing ledger as well",
+                ),
+        )
         .arg(
             Arg::with_name("clone_feature_set")
                 .long("clone-feature-set")
@@ -18,6 +18,7 @@ pub struct DefaultThreadArgs {
     pub rayon_global_threads: String,
     pub replay_forks_threads: String,
     pub replay_transactions_threads: String,
+    pub replay_fork_transactions_threads: String,
     pub rocksdb_compaction_threads: String,
     pub rocksdb_flush_threads: String,
     pub tvu_receive_threads: String,
@@ -39,6 +40,8 @@ impl Default for DefaultThreadArgs {
             replay_forks_threads: ReplayForksThreadsArg::bounded_default().to_string(),
             replay_transactions_threads: ReplayTransactionsThreadsArg::bounded_default()
                 .to_string(),
+            replay_fork_transactions_threads: ReplayForkTransactionsThreadsArg::bounded_default()
+                .to_string(),
             rocksdb_compaction_threads: RocksdbCompactionThreadsArg::bounded_default().to_string(),
             rocksdb_flush_threads: RocksdbFlushThreadsArg::bounded_default().to_string(),
             tvu_receive_threads: TvuReceiveThreadsArg::bounded_default().to_string(),
@@ -58,6 +61,9 @@ pub fn thread_args<'a>(defaults: &DefaultThreadArgs) -> Vec<Arg<'_, 'a>> {
         new_thread_arg::<RayonGlobalThreadsArg>(&defaults.rayon_global_threads),
         new_thread_arg::<ReplayForksThreadsArg>(&defaults.replay_forks_threads),
         new_thread_arg::<ReplayTransactionsThreadsArg>(&defaults.replay_transactions_threads),
+        new_thread_arg::<ReplayForkTransactionsThreadsArg>(
+            &defaults.replay_fork_transactions_threads,
+        ),
         new_thread_arg::<RocksdbCompactionThreadsArg>(&defaults.rocksdb_compaction_threads),
         new_thread_arg::<RocksdbFlushThreadsArg>(&defaults.rocksdb_flush_threads),
         new_thread_arg::<TvuReceiveThreadsArg>(&defaults.tvu_receive_threads),
@@ -86,6 +92,7 @@ pub struct NumThreadConfig {
     pub rayon_global_threads: NonZeroUsize,
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
+    pub replay_fork_transactions_threads: NonZeroUsize,
     pub rocksdb_compaction_threads: NonZeroUsize,
     pub rocksdb_flush_threads: NonZeroUsize,
     pub tvu_receive_threads: NonZeroUsize,
@@ -131,6 +138,11 @@ pub fn parse_num_threads_args(matches: &ArgMatches) -> NumThreadConfig {
             ReplayTransactionsThreadsArg::NAME,
             NonZeroUsize
         ),
+        replay_fork_transactions_threads: value_t_or_exit!(
+            matches,
+            ReplayForkTransactionsThreadsArg::NAME,
+            NonZeroUsize
+        ),
         rocksdb_compaction_threads: value_t_or_exit!(
             matches,
             RocksdbCompactionThreadsArg::NAME,
@@ -260,7 +272,8 @@ struct ReplayForksThreadsArg;
 impl ThreadArg for ReplayForksThreadsArg {
     const NAME: &'static str = "replay_forks_threads";
     const LONG_NAME: &'static str = "replay-forks-threads";
-    const HELP: &'static str = "Number of threads to use for replay of blocks on different forks";
+    const HELP: &'static str = "Number of threads to use for replay of blocks on different forks, \
+                                which caps the number of forks replayed concurrently";
 
     fn default() -> usize {
         // Default to single threaded fork execution
@@ -284,6 +297,19 @@ impl ThreadArg for ReplayTransactionsThreadsArg {
     }
 }
 
+struct ReplayForkTransactionsThreadsArg;
+impl ThreadArg for ReplayForkTransactionsThreadsArg {
+    const NAME: &'static str = "replay_fork_transactions_threads";
+    const LONG_NAME: &'static str = "replay-fork-transactions-threads";
+    const HELP: &'static str = "Number of threads to use for transaction replay of each fork \
+                                replayed concurrently with other forks";
+
+    fn default() -> usize {
+        // Share the threads of transaction replay between the forks replayed concurrently
+        (get_max_thread_count() / ReplayForksThreadsArg::max()).max(1)
+    }
+}
+
 struct RocksdbCompactionThreadsArg;
 impl ThreadArg for RocksdbCompactionThreadsArg {
     const NAME: &'static str = "rocksdb_compaction_threads";
@@ -0,0 +1,136 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_cli_output::OutputFormat,
+    solana_sdk::clock::Slot,
+    std::path::Path,
+};
+
+const COMMAND: &str = "account-conflicts";
+
+const DEFAULT_NUM_ACCOUNTS: &str = "10";
+
+#[derive(Debug, PartialEq)]
+pub struct AccountConflictsArgs {
+    pub slot: Option<Slot>,
+    pub num_accounts: usize,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for AccountConflictsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(AccountConflictsArgs {
+            slot: value_t!(matches, "slot", Slot).ok(),
+            num_accounts: value_t_or_exit!(matches, "num_accounts", usize),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about(
+            "Display the writable accounts most contended by the transactions of the recent \
+             leader slots",
+        )
+        .arg(
+            Arg::with_name("slot")
+                .long("slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .help("Display the accounts of this leader slot only"),
+        )
+        .arg(
+            Arg::with_name("num_accounts")
+                .long("num-accounts")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(DEFAULT_NUM_ACCOUNTS)
+                .help("Number of the most contended accounts to display for each slot"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the accounts are ranked by the transactions which could not lock them, then \
+             by the transactions retried for any reason, such as the account cost limit",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let AccountConflictsArgs {
+        slot,
+        num_accounts,
+        output,
+    } = AccountConflictsArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let account_conflicts = admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .account_conflicts(slot, Some(num_accounts))
+            .await
+    })?;
+    println!("{}", output.formatted_string(&account_conflicts));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_account_conflicts_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            AccountConflictsArgs {
+                slot: None,
+                num_accounts: 10,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_account_conflicts_with_values() {
+        verify_args_struct_by_command(
+            command(),
+            vec![
+                COMMAND,
+                "--slot",
+                "42",
+                "--num-accounts",
+                "3",
+                "--output",
+                "json",
+            ],
+            AccountConflictsArgs {
+                slot: Some(42),
+                num_accounts: 3,
+                output: OutputFormat::Json,
+            },
+        );
+        verify_args_struct_by_command_is_error::<AccountConflictsArgs>(
+            command(),
+            vec![COMMAND, "--num-accounts", "all"],
+        );
+    }
+}
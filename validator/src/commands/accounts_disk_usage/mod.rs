@@ -0,0 +1,110 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "accounts-disk-usage";
+
+const DEFAULT_NUM_OWNERS: &str = "20";
+
+#[derive(Debug, PartialEq)]
+pub struct AccountsDiskUsageArgs {
+    pub num_owners: usize,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for AccountsDiskUsageArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(AccountsDiskUsageArgs {
+            num_owners: value_t_or_exit!(matches, "num_owners", usize),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display the disk usage of account storages by owner")
+        .arg(
+            Arg::with_name("num_owners")
+                .long("num-owners")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(DEFAULT_NUM_OWNERS)
+                .help("Number of owners with the largest data length to display"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the validator must run with --accounts-db-track-owner-disk-usage. The disk \
+             usage is only of the storages summarized so far, and includes the dead accounts \
+             of storages which are not shrunk yet",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let AccountsDiskUsageArgs { num_owners, output } =
+        AccountsDiskUsageArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let disk_usage = admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .accounts_disk_usage(Some(num_owners))
+            .await
+    })?;
+    println!("{}", output.formatted_string(&disk_usage));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_accounts_disk_usage_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            AccountsDiskUsageArgs {
+                num_owners: 20,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_accounts_disk_usage_with_values() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--num-owners", "5", "--output", "json"],
+            AccountsDiskUsageArgs {
+                num_owners: 5,
+                output: OutputFormat::Json,
+            },
+        );
+        verify_args_struct_by_command_is_error::<AccountsDiskUsageArgs>(
+            command(),
+            vec![COMMAND, "--num-owners", "many"],
+        );
+    }
+}
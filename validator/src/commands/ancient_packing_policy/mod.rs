@@ -0,0 +1,232 @@
+use {
+    crate::{
+        admin_rpc_service::{self, AdminRpcAncientPackingPolicy},
+        commands::{Error, FromClapArgMatches, Result},
+    },
+    clap::{value_t, App, Arg, ArgMatches, SubCommand},
+    solana_accounts_db::ancient_packing_policy::AncientPackingPolicy,
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "ancient-packing-policy";
+
+#[derive(Debug, PartialEq)]
+pub struct AncientPackingPolicyArgs {
+    pub config: Option<String>,
+    pub ideal_storage_size: Option<u64>,
+    pub max_ancient_storages: Option<usize>,
+    pub shrink_ratio: Option<f64>,
+    pub default_shrink_ratio: bool,
+    pub output: OutputFormat,
+}
+
+impl AncientPackingPolicyArgs {
+    fn changes_policy(&self) -> bool {
+        self.config.is_some()
+            || self.ideal_storage_size.is_some()
+            || self.max_ancient_storages.is_some()
+            || self.shrink_ratio.is_some()
+            || self.default_shrink_ratio
+    }
+}
+
+impl FromClapArgMatches for AncientPackingPolicyArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(AncientPackingPolicyArgs {
+            config: matches.value_of("config").map(str::to_string),
+            ideal_storage_size: value_t!(matches, "ideal_storage_size", u64).ok(),
+            max_ancient_storages: value_t!(matches, "max_ancient_storages", usize).ok(),
+            shrink_ratio: value_t!(matches, "shrink_ratio", f64).ok(),
+            default_shrink_ratio: matches.is_present("default_shrink_ratio"),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display or change the policy of packing ancient storages")
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .takes_value(true)
+                .conflicts_with_all(&[
+                    "ideal_storage_size",
+                    "max_ancient_storages",
+                    "shrink_ratio",
+                    "default_shrink_ratio",
+                ])
+                .help(
+                    "Set the policy from a YAML file, like the one of \
+                     --accounts-db-ancient-packing-policy",
+                ),
+        )
+        .arg(
+            Arg::with_name("ideal_storage_size")
+                .long("ideal-storage-size")
+                .value_name("BYTES")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help("The smallest size of the ancient storages accounts are packed into"),
+        )
+        .arg(
+            Arg::with_name("max_ancient_storages")
+                .long("max-ancient-storages")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help("The number of ancient storages the packing should converge to"),
+        )
+        .arg(
+            Arg::with_name("shrink_ratio")
+                .long("shrink-ratio")
+                .value_name("RATIO")
+                .takes_value(true)
+                .validator(is_parsable::<f64>)
+                .conflicts_with("default_shrink_ratio")
+                .help("Shrink the ancient storages with a lower ratio of alive bytes"),
+        )
+        .arg(
+            Arg::with_name("default_shrink_ratio")
+                .long("default-shrink-ratio")
+                .takes_value(false)
+                .help("Shrink the ancient storages with the shrink ratio of all storages"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the policy only applies to the currently running validator instance, from \
+             its next packing of ancient storages on",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let args = AncientPackingPolicyArgs::from_clap_arg_match(matches)?;
+    let changes_policy = args.changes_policy();
+    let AncientPackingPolicyArgs {
+        config,
+        ideal_storage_size,
+        max_ancient_storages,
+        shrink_ratio,
+        default_shrink_ratio,
+        output,
+    } = args;
+    let config_policy = config
+        .map(|config| {
+            AncientPackingPolicy::load(&config)
+                .map(AdminRpcAncientPackingPolicy::from)
+                .map_err(|err| Error::Dynamic(format!("failed to load {config}: {err}").into()))
+        })
+        .transpose()?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let policy = admin_rpc_service::runtime().block_on(async move {
+        let admin_client = admin_client.await?;
+        if !changes_policy {
+            return admin_client.ancient_packing_policy().await;
+        }
+        let mut policy = match config_policy {
+            Some(policy) => policy,
+            None => admin_client.ancient_packing_policy().await?,
+        };
+        if let Some(ideal_storage_size) = ideal_storage_size {
+            policy.ideal_storage_size = ideal_storage_size;
+        }
+        if let Some(max_ancient_storages) = max_ancient_storages {
+            policy.max_ancient_storages = max_ancient_storages;
+        }
+        if shrink_ratio.is_some() || default_shrink_ratio {
+            policy.shrink_ratio = shrink_ratio;
+        }
+        admin_client.set_ancient_packing_policy(policy).await?;
+        admin_client.ancient_packing_policy().await
+    })?;
+    println!("{}", output.formatted_string(&policy));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_ancient_packing_policy_default() {
+        let args = AncientPackingPolicyArgs {
+            config: None,
+            ideal_storage_size: None,
+            max_ancient_storages: None,
+            shrink_ratio: None,
+            default_shrink_ratio: false,
+            output: OutputFormat::Display,
+        };
+        assert!(!args.changes_policy());
+        verify_args_struct_by_command(command(), vec![COMMAND], args);
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_ancient_packing_policy_with_values() {
+        let args = AncientPackingPolicyArgs {
+            config: None,
+            ideal_storage_size: Some(1_000_000),
+            max_ancient_storages: Some(1000),
+            shrink_ratio: Some(0.5),
+            default_shrink_ratio: false,
+            output: OutputFormat::Json,
+        };
+        assert!(args.changes_policy());
+        verify_args_struct_by_command(
+            command(),
+            vec![
+                COMMAND,
+                "--ideal-storage-size",
+                "1000000",
+                "--max-ancient-storages",
+                "1000",
+                "--shrink-ratio",
+                "0.5",
+                "--output",
+                "json",
+            ],
+            args,
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_ancient_packing_policy_config() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--config", "policy.yml"],
+            AncientPackingPolicyArgs {
+                config: Some("policy.yml".to_string()),
+                ideal_storage_size: None,
+                max_ancient_storages: None,
+                shrink_ratio: None,
+                default_shrink_ratio: false,
+                output: OutputFormat::Display,
+            },
+        );
+        verify_args_struct_by_command_is_error::<AncientPackingPolicyArgs>(
+            command(),
+            vec![COMMAND, "--config", "policy.yml", "--shrink-ratio", "0.5"],
+        );
+        verify_args_struct_by_command_is_error::<AncientPackingPolicyArgs>(
+            command(),
+            vec![COMMAND, "--shrink-ratio", "0.5", "--default-shrink-ratio"],
+        );
+    }
+}
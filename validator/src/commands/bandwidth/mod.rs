@@ -0,0 +1,88 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "bandwidth";
+
+#[derive(Debug, PartialEq)]
+pub struct BandwidthArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for BandwidthArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(BandwidthArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display the bandwidth used by each subsystem of the validator")
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let bandwidth_args = BandwidthArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let bandwidth = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.bandwidth().await })?;
+    println!("{}", bandwidth_args.output.formatted_string(&bandwidth));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_bandwidth_output_json() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            BandwidthArgs {
+                output: OutputFormat::Json,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_bandwidth_output_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            BandwidthArgs {
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_bandwidth_output_invalid() {
+        verify_args_struct_by_command_is_error::<BandwidthArgs>(
+            command(),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_cli_output::OutputFormat,
+    solana_sdk::clock::Slot,
+    std::path::Path,
+};
+
+const COMMAND: &str = "bank-hash-breakdowns";
+
+const DEFAULT_NUM_SLOTS: &str = "10";
+
+#[derive(Debug, PartialEq)]
+pub struct BankHashBreakdownsArgs {
+    pub slot: Option<Slot>,
+    pub num_slots: usize,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for BankHashBreakdownsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(BankHashBreakdownsArgs {
+            slot: value_t!(matches, "slot", Slot).ok(),
+            num_slots: value_t_or_exit!(matches, "num_slots", usize),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display the components of the bank hashes of the recently frozen slots")
+        .arg(
+            Arg::with_name("slot")
+                .long("slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .help("Display every frozen version of this slot only"),
+        )
+        .arg(
+            Arg::with_name("num_slots")
+                .long("num-slots")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(DEFAULT_NUM_SLOTS)
+                .help("Number of the most recent frozen slots to display, unless --slot is given"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the validator must run with --bank-hash-breakdown-slots. Compare the \
+             components with the ones of another validator to find which one diverges",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let BankHashBreakdownsArgs {
+        slot,
+        num_slots,
+        output,
+    } = BankHashBreakdownsArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let breakdowns = admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .bank_hash_breakdowns(slot, Some(num_slots))
+            .await
+    })?;
+    println!("{}", output.formatted_string(&breakdowns));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_bank_hash_breakdowns_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            BankHashBreakdownsArgs {
+                slot: None,
+                num_slots: 10,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_bank_hash_breakdowns_with_values() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--slot", "42", "--output", "json"],
+            BankHashBreakdownsArgs {
+                slot: Some(42),
+                num_slots: 10,
+                output: OutputFormat::Json,
+            },
+        );
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--num-slots", "3"],
+            BankHashBreakdownsArgs {
+                slot: None,
+                num_slots: 3,
+                output: OutputFormat::Display,
+            },
+        );
+        verify_args_struct_by_command_is_error::<BankHashBreakdownsArgs>(
+            command(),
+            vec![COMMAND, "--slot", "latest"],
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{values_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    itertools::Itertools,
+    solana_cli_output::OutputFormat,
+    solana_ledger::blockstore::Blockstore,
+    std::path::Path,
+};
+
+pub const COMMAND: &str = "blockstore-compaction";
+
+#[derive(Debug, PartialEq)]
+pub struct BlockstoreCompactionStatusArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for BlockstoreCompactionStatusArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(BlockstoreCompactionStatusArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BlockstoreCompactionScheduleArgs {
+    /// Column families to compact, all of them if empty
+    pub columns: Vec<String>,
+}
+
+impl FromClapArgMatches for BlockstoreCompactionScheduleArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        let columns = if matches.is_present("column") {
+            values_t!(matches, "column", String)?
+                .into_iter()
+                .unique()
+                .collect()
+        } else {
+            Vec::default()
+        };
+        Ok(BlockstoreCompactionScheduleArgs { columns })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Manage manual compactions of the blockstore column families")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("status")
+                .about(
+                    "Display the scheduled compactions and the estimated compaction debt of each \
+                     column family",
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("schedule")
+                .about("Schedule manual compactions of blockstore column families")
+                .arg(
+                    Arg::with_name("column")
+                        .long("column")
+                        .value_name("NAME")
+                        .multiple(true)
+                        .takes_value(true)
+                        .possible_values(Blockstore::column_names())
+                        .help("Column family to compact [default: all column families]"),
+                )
+                .after_help(
+                    "Note: compactions run one column family at a time, only while the \
+                     validator is not about to be leader, and only apply to the currently \
+                     running validator instance",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    match matches.subcommand() {
+        ("status", Some(subcommand_matches)) => {
+            let BlockstoreCompactionStatusArgs { output } =
+                BlockstoreCompactionStatusArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let compaction = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.blockstore_compaction().await })?;
+
+            println!("{}", output.formatted_string(&compaction));
+        }
+        ("schedule", Some(subcommand_matches)) => {
+            let BlockstoreCompactionScheduleArgs { columns } =
+                BlockstoreCompactionScheduleArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let queued = admin_rpc_service::runtime().block_on(async move {
+                admin_client
+                    .await?
+                    .schedule_blockstore_compaction(columns)
+                    .await
+            })?;
+
+            println!("Scheduled compactions: {}", queued.join(", "));
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_args_struct_by_command_blockstore_compaction_status_with_output() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "status", "--output", "json"]);
+        let subcommand_matches = matches.subcommand_matches("status").unwrap();
+        let args = BlockstoreCompactionStatusArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            BlockstoreCompactionStatusArgs {
+                output: OutputFormat::Json
+            }
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_blockstore_compaction_schedule() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "schedule"]);
+        let subcommand_matches = matches.subcommand_matches("schedule").unwrap();
+        let args =
+            BlockstoreCompactionScheduleArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            BlockstoreCompactionScheduleArgs {
+                columns: Vec::default()
+            }
+        );
+
+        let app = command();
+        let matches = app.get_matches_from(vec![
+            COMMAND,
+            "schedule",
+            "--column",
+            "data_shred",
+            "--column",
+            "meta",
+            "--column",
+            "data_shred",
+        ]);
+        let subcommand_matches = matches.subcommand_matches("schedule").unwrap();
+        let args =
+            BlockstoreCompactionScheduleArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            BlockstoreCompactionScheduleArgs {
+                columns: vec!["data_shred".to_string(), "meta".to_string()]
+            }
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, AppSettings, ArgMatches, SubCommand},
+    std::path::Path,
+};
+
+const COMMAND: &str = "commission-guard";
+
+#[derive(Debug, PartialEq)]
+pub struct CommissionGuardArgs {
+    /// Whether commission changes exceeding --max-commission-change are let
+    /// through
+    pub override_enabled: bool,
+}
+
+impl FromClapArgMatches for CommissionGuardArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(CommissionGuardArgs {
+            override_enabled: matches.subcommand_name() == Some("override"),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Override the guard set by --max-commission-change")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("override").about(
+            "Let commission changes of the vote account through regardless of \
+             --max-commission-change, until the guard is restored",
+        ))
+        .subcommand(
+            SubCommand::with_name("restore")
+                .about("Enforce --max-commission-change again after an override"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let CommissionGuardArgs { override_enabled } =
+        CommissionGuardArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .set_commission_guard_override(override_enabled)
+            .await
+    })?;
+    if override_enabled {
+        println!("Commission guard overridden");
+    } else {
+        println!("Commission guard restored");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_commission_guard_default() {
+        verify_args_struct_by_command_is_error::<CommissionGuardArgs>(command(), vec![COMMAND]);
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_commission_guard_override() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "override"],
+            CommissionGuardArgs {
+                override_enabled: true,
+            },
+        );
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "restore"],
+            CommissionGuardArgs {
+                override_enabled: false,
+            },
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t, values_t, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_parsable, is_pubkey},
+    solana_cli_output::OutputFormat,
+    solana_gossip::crds_inspect::CrdsValueType,
+    solana_sdk::pubkey::Pubkey,
+    std::path::Path,
+};
+
+const COMMAND: &str = "crds-values";
+
+#[derive(Debug, PartialEq)]
+pub struct CrdsValuesArgs {
+    pub types: Vec<CrdsValueType>,
+    pub origin: Option<Pubkey>,
+    pub cursor: Option<u64>,
+    pub limit: Option<usize>,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for CrdsValuesArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(CrdsValuesArgs {
+            types: values_t!(matches, "type", CrdsValueType).unwrap_or_default(),
+            origin: value_t!(matches, "origin", Pubkey).ok(),
+            cursor: value_t!(matches, "cursor", u64).ok(),
+            limit: value_t!(matches, "limit", usize).ok(),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about(
+            "Display the values in the validator's gossip table, with their age, one page at a \
+             time",
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPE")
+                .takes_value(true)
+                .multiple(true)
+                .possible_values(&CrdsValueType::NAMES)
+                .help("Only display values of this type [default: all types]"),
+        )
+        .arg(
+            Arg::with_name("origin")
+                .long("origin")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .help("Only display values which originate from this node"),
+        )
+        .arg(
+            Arg::with_name("cursor")
+                .long("cursor")
+                .value_name("CURSOR")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help(
+                    "Start at this position of the table, e.g. the next cursor displayed with \
+                     the previous page [default: start of the table]",
+                ),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help(
+                    "Maximum number of values to display; the validator caps the size of a \
+                     page [default: set by the validator]",
+                ),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let CrdsValuesArgs {
+        types,
+        origin,
+        cursor,
+        limit,
+        output,
+    } = CrdsValuesArgs::from_clap_arg_match(matches)?;
+
+    let types = (!types.is_empty()).then(|| types.iter().map(CrdsValueType::to_string).collect());
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let crds_values = admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .crds_values(types, origin, cursor, limit)
+            .await
+    })?;
+
+    println!("{}", output.formatted_string(&crds_values));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_crds_values_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            CrdsValuesArgs {
+                types: vec![],
+                origin: None,
+                cursor: None,
+                limit: None,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_crds_values_with_filters() {
+        let origin = Pubkey::new_unique();
+        let origin_str = origin.to_string();
+        verify_args_struct_by_command(
+            command(),
+            vec![
+                COMMAND,
+                "--type",
+                "vote",
+                "--type",
+                "duplicate-shred",
+                "--origin",
+                &origin_str,
+                "--cursor",
+                "42",
+                "--limit",
+                "10",
+                "--output",
+                "json",
+            ],
+            CrdsValuesArgs {
+                types: vec![CrdsValueType::Vote, CrdsValueType::DuplicateShred],
+                origin: Some(origin),
+                cursor: Some(42),
+                limit: Some(10),
+                output: OutputFormat::Json,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_crds_values_invalid_type() {
+        verify_args_struct_by_command_is_error::<CrdsValuesArgs>(
+            command(),
+            vec![COMMAND, "--type", "votes"],
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "duplicate-shred-proofs";
+
+#[derive(Debug, PartialEq)]
+pub struct DuplicateShredProofsArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for DuplicateShredProofsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(DuplicateShredProofsArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about(
+            "Display the slots with duplicate shred proofs observed in gossip, and how far the \
+             proofs have spread",
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let duplicate_shred_proofs_args = DuplicateShredProofsArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let duplicate_shred_proofs = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.duplicate_shred_proofs().await })?;
+
+    println!(
+        "{}",
+        duplicate_shred_proofs_args
+            .output
+            .formatted_string(&duplicate_shred_proofs)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_duplicate_shred_proofs_output_json() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            DuplicateShredProofsArgs {
+                output: OutputFormat::Json,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_duplicate_shred_proofs_output_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            DuplicateShredProofsArgs {
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_duplicate_shred_proofs_output_invalid() {
+        verify_args_struct_by_command_is_error::<DuplicateShredProofsArgs>(
+            command(),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "epoch-accounts-hash";
+
+#[derive(Debug, PartialEq)]
+pub struct EpochAccountsHashStatusArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for EpochAccountsHashStatusArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(EpochAccountsHashStatusArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Manage the scheduling of the epoch accounts hash calculation")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Display the progress of the epoch accounts hash calculation")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(SubCommand::with_name("deprioritize").about(
+            "Defer the calculation which has yet to start until the latest slot it can start, \
+             to help the validator catch up",
+        ))
+        .subcommand(
+            SubCommand::with_name("restore")
+                .about("Start the deferred calculation as scheduled again"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let status = match matches.subcommand() {
+        ("status", Some(subcommand_matches)) => {
+            let EpochAccountsHashStatusArgs { output } =
+                EpochAccountsHashStatusArgs::from_clap_arg_match(subcommand_matches)?;
+            let status = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.epoch_accounts_hash_status().await })?;
+            println!("{}", output.formatted_string(&status));
+            return Ok(());
+        }
+        ("deprioritize", _) => admin_rpc_service::runtime().block_on(async move {
+            admin_client
+                .await?
+                .set_epoch_accounts_hash_deprioritized(true)
+                .await
+        })?,
+        ("restore", _) => admin_rpc_service::runtime().block_on(async move {
+            admin_client
+                .await?
+                .set_epoch_accounts_hash_deprioritized(false)
+                .await
+        })?,
+        _ => unreachable!(),
+    };
+    print!("{status}");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_args_struct_by_command_epoch_accounts_hash_status() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "status"]);
+        let subcommand_matches = matches.subcommand_matches("status").unwrap();
+        let args = EpochAccountsHashStatusArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            EpochAccountsHashStatusArgs {
+                output: OutputFormat::Display
+            }
+        );
+
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "status", "--output", "json"]);
+        let subcommand_matches = matches.subcommand_matches("status").unwrap();
+        let args = EpochAccountsHashStatusArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            EpochAccountsHashStatusArgs {
+                output: OutputFormat::Json
+            }
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_epoch_accounts_hash_deprioritize() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "deprioritize"]);
+        assert!(matches.subcommand_matches("deprioritize").is_some());
+
+        let app = command();
+        let result = app.get_matches_from_safe(vec![COMMAND]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "failover";
+
+#[derive(Debug, PartialEq)]
+pub struct FailoverStatusArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for FailoverStatusArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(FailoverStatusArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FailoverPromoteArgs {
+    pub force: bool,
+}
+
+impl FromClapArgMatches for FailoverPromoteArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(FailoverPromoteArgs {
+            force: matches.is_present("force"),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Manage the hot-standby failover between this validator and its peer")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Display the failover role of the validator and the state of its peer")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("promote")
+                .about("Take the shared identity, once the peer is no longer active")
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .takes_value(false)
+                        .help(
+                            "Promote even though the peer is active or the latest tower of the \
+                             shared identity is missing or older than the last vote of the peer. \
+                             This may cause the shared identity to vote twice",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("demote")
+                .about("Return to the node identity and hand over to the peer"),
+        )
+        .subcommand(
+            SubCommand::with_name("pause")
+                .about("Prevent the validator from promoting itself when the peer goes silent"),
+        )
+        .subcommand(
+            SubCommand::with_name("resume")
+                .about("Let the validator promote itself again when the peer goes silent"),
+        )
+        .after_help("Note: the validator must run with --failover-peer")
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    match matches.subcommand() {
+        ("status", Some(subcommand_matches)) => {
+            let FailoverStatusArgs { output } =
+                FailoverStatusArgs::from_clap_arg_match(subcommand_matches)?;
+            let status = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.failover_status().await })?;
+            println!("{}", output.formatted_string(&status));
+        }
+        ("promote", Some(subcommand_matches)) => {
+            let FailoverPromoteArgs { force } =
+                FailoverPromoteArgs::from_clap_arg_match(subcommand_matches)?;
+            admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.failover_promote(force).await })?;
+            println!("Promoted to the shared identity");
+        }
+        ("demote", _) => {
+            admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.failover_demote().await })?;
+            println!("Demoted to the node identity");
+        }
+        ("pause", _) => {
+            admin_rpc_service::runtime().block_on(async move {
+                admin_client.await?.set_failover_auto_promote(false).await
+            })?;
+        }
+        ("resume", _) => {
+            admin_rpc_service::runtime().block_on(async move {
+                admin_client.await?.set_failover_auto_promote(true).await
+            })?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_args_struct_by_command_failover_status() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "status"]);
+        let subcommand_matches = matches.subcommand_matches("status").unwrap();
+        let args = FailoverStatusArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            FailoverStatusArgs {
+                output: OutputFormat::Display
+            }
+        );
+
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "status", "--output", "json"]);
+        let subcommand_matches = matches.subcommand_matches("status").unwrap();
+        let args = FailoverStatusArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            FailoverStatusArgs {
+                output: OutputFormat::Json
+            }
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_failover_promote() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "promote"]);
+        let subcommand_matches = matches.subcommand_matches("promote").unwrap();
+        let args = FailoverPromoteArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(args, FailoverPromoteArgs { force: false });
+
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "promote", "--force"]);
+        let subcommand_matches = matches.subcommand_matches("promote").unwrap();
+        let args = FailoverPromoteArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(args, FailoverPromoteArgs { force: true });
+    }
+}
@@ -0,0 +1,154 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+const COMMAND: &str = "forensic-bundles";
+
+#[derive(Debug, PartialEq)]
+pub struct ForensicBundlesArgs {
+    /// The bundle to fetch, `None` to list the bundles
+    pub fetch: Option<String>,
+    /// Defaults to the current directory
+    pub output_dir: Option<PathBuf>,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for ForensicBundlesArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(ForensicBundlesArgs {
+            fetch: matches.value_of("fetch").map(str::to_string),
+            output_dir: matches.value_of("output_dir").map(PathBuf::from),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about(
+            "Display the forensic bundles captured for duplicate slots and for bank hash \
+             mismatches, or fetch one of them",
+        )
+        .arg(
+            Arg::with_name("fetch")
+                .long("fetch")
+                .takes_value(true)
+                .value_name("BUNDLE")
+                .help("Fetch the files of this bundle into the output directory"),
+        )
+        .arg(
+            Arg::with_name("output_dir")
+                .long("output-dir")
+                .takes_value(true)
+                .value_name("DIR")
+                .requires("fetch")
+                .help("Directory to write the fetched bundle into [default: current directory]"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .conflicts_with("fetch")
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let forensic_bundles_args = ForensicBundlesArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let Some(name) = forensic_bundles_args.fetch else {
+        let forensic_bundles = admin_rpc_service::runtime()
+            .block_on(async move { admin_client.await?.forensic_bundles().await })?;
+        println!(
+            "{}",
+            forensic_bundles_args
+                .output
+                .formatted_string(&forensic_bundles)
+        );
+        return Ok(());
+    };
+
+    let contents = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.forensic_bundle(name).await })?;
+    let bundle_dir = forensic_bundles_args
+        .output_dir
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(&contents.bundle.name);
+    fs::create_dir_all(&bundle_dir)?;
+    for file in contents.files {
+        let data = BASE64_STANDARD
+            .decode(&file.data)
+            .map_err(std::io::Error::other)?;
+        fs::write(bundle_dir.join(&file.name), data)?;
+    }
+    println!(
+        "Fetched forensic bundle {} into {}",
+        contents.bundle.name,
+        bundle_dir.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_forensic_bundles_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            ForensicBundlesArgs {
+                fetch: None,
+                output_dir: None,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_forensic_bundles_fetch() {
+        verify_args_struct_by_command(
+            command(),
+            vec![
+                COMMAND,
+                "--fetch",
+                "1-bank-hash-mismatch-2",
+                "--output-dir",
+                "/tmp",
+            ],
+            ForensicBundlesArgs {
+                fetch: Some("1-bank-hash-mismatch-2".to_string()),
+                output_dir: Some(PathBuf::from("/tmp")),
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_forensic_bundles_output_dir_without_fetch() {
+        verify_args_struct_by_command_is_error::<ForensicBundlesArgs>(
+            command(),
+            vec![COMMAND, "--output-dir", "/tmp"],
+        );
+    }
+}
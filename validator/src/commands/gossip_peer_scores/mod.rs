@@ -0,0 +1,176 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{values_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    itertools::Itertools,
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::OutputFormat,
+    std::{net::IpAddr, path::Path},
+};
+
+pub const COMMAND: &str = "gossip-peer-scores";
+
+#[derive(Debug, PartialEq)]
+pub struct GossipPeerScoresGetArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for GossipPeerScoresGetArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(GossipPeerScoresGetArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GossipPeerScoresSetOverridesArgs {
+    pub overrides: Vec<IpAddr>,
+}
+
+impl FromClapArgMatches for GossipPeerScoresSetOverridesArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        let overrides = values_t!(matches, "addr", IpAddr)?
+            .into_iter()
+            .unique()
+            .collect::<Vec<_>>();
+        Ok(GossipPeerScoresSetOverridesArgs { overrides })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Manage the scores of misbehaving gossip peers")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("get")
+                .about(
+                    "Display the penalties of misbehaving gossip peers, and whether their pull \
+                     requests are ignored or all their packets are dropped",
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-overrides")
+                .about("Set the addresses of gossip peers which are never penalized")
+                .setting(AppSettings::ArgRequiredElseHelp)
+                .arg(
+                    Arg::with_name("addr")
+                        .long("addr")
+                        .validator(is_parsable::<IpAddr>)
+                        .value_name("IP ADDRESS")
+                        .multiple(true)
+                        .takes_value(true)
+                        .required(true)
+                        .help("IP address of a gossip peer which is never penalized"),
+                )
+                .after_help(
+                    "Note: overrides replace the existing ones, forgive the penalties of the \
+                     given peers and only apply to the currently running validator instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-all-overrides")
+                .about("Clear the addresses of gossip peers which are never penalized"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    match matches.subcommand() {
+        ("get", Some(subcommand_matches)) => {
+            let gossip_peer_scores_get_args =
+                GossipPeerScoresGetArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let gossip_peer_scores = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.gossip_peer_scores().await })?;
+
+            println!(
+                "{}",
+                gossip_peer_scores_get_args
+                    .output
+                    .formatted_string(&gossip_peer_scores)
+            );
+        }
+        ("set-overrides", Some(subcommand_matches)) => {
+            let GossipPeerScoresSetOverridesArgs { overrides } =
+                GossipPeerScoresSetOverridesArgs::from_clap_arg_match(subcommand_matches)?;
+
+            set_gossip_peer_score_overrides(ledger_path, overrides)?;
+        }
+        ("remove-all-overrides", _) => {
+            set_gossip_peer_score_overrides(ledger_path, Vec::default())?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+fn set_gossip_peer_score_overrides(ledger_path: &Path, overrides: Vec<IpAddr>) -> Result<()> {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .set_gossip_peer_score_overrides(overrides)
+            .await
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    #[test]
+    fn verify_args_struct_by_command_gossip_peer_scores_get_with_output() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "get", "--output", "json"]);
+        let subcommand_matches = matches.subcommand_matches("get").unwrap();
+        let args = GossipPeerScoresGetArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            GossipPeerScoresGetArgs {
+                output: OutputFormat::Json
+            }
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_gossip_peer_scores_set_overrides() {
+        let app = command();
+        let matches = app.get_matches_from(vec![
+            COMMAND,
+            "set-overrides",
+            "--addr",
+            "10.0.0.1",
+            "--addr",
+            "10.0.0.2",
+            "--addr",
+            "10.0.0.1",
+        ]);
+        let subcommand_matches = matches.subcommand_matches("set-overrides").unwrap();
+        let args =
+            GossipPeerScoresSetOverridesArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            GossipPeerScoresSetOverridesArgs {
+                overrides: vec![
+                    IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)),
+                ],
+            }
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "health";
+
+#[derive(Debug, PartialEq)]
+pub struct HealthArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for HealthArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(HealthArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display each of the conditions the health of the validator is made of")
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let health_args = HealthArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let health_detail = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.health_detail().await })?;
+    println!("{}", health_args.output.formatted_string(&health_detail));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_health_output_json() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            HealthArgs {
+                output: OutputFormat::Json,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_health_output_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            HealthArgs {
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_health_output_invalid() {
+        verify_args_struct_by_command_is_error::<HealthArgs>(
+            command(),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
@@ -1,14 +1,34 @@
+pub mod account_conflicts;
+pub mod accounts_disk_usage;
+pub mod ancient_packing_policy;
 pub mod authorized_voter;
+pub mod bandwidth;
+pub mod bank_hash_breakdowns;
+pub mod blockstore_compaction;
+pub mod commission_guard;
 pub mod contact_info;
+pub mod crds_values;
+pub mod duplicate_shred_proofs;
+pub mod epoch_accounts_hash;
 pub mod exit;
+pub mod failover;
+pub mod forensic_bundles;
+pub mod gossip_peer_scores;
+pub mod health;
 pub mod monitor;
+pub mod pause_leader;
 pub mod plugin;
+pub mod poh_calibration;
+pub mod precompile_programs;
 pub mod repair_shred_from_peer;
 pub mod repair_whitelist;
+pub mod resume_leader;
 pub mod run;
+pub mod set_banking_threads;
 pub mod set_identity;
 pub mod set_log_filter;
 pub mod set_public_address;
+pub mod snapshot_progress;
 pub mod staked_nodes_overrides;
 pub mod wait_for_restart_window;
 
@@ -0,0 +1,26 @@
+use {
+    crate::{admin_rpc_service, commands::Result},
+    clap::{App, ArgMatches, SubCommand},
+    std::path::Path,
+};
+
+const COMMAND: &str = "pause-leader";
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Skip the upcoming leader slots of the validator, which keeps voting")
+        .after_help(
+            "Note: a block started before the pause completes, and the TPU addresses are \
+             withdrawn from gossip until resume-leader. The pause only applies to the currently \
+             running validator instance",
+        )
+}
+
+pub fn execute(_matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.pause_leader().await })?;
+    println!("Block production paused");
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "precompile-programs";
+
+const DEFAULT_NUM_PROGRAMS: &str = "100";
+
+#[derive(Debug, PartialEq)]
+pub struct PrecompileProgramsArgs {
+    pub num_programs: usize,
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for PrecompileProgramsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(PrecompileProgramsArgs {
+            num_programs: value_t_or_exit!(matches, "num_programs", usize),
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Compile the most invoked programs into the program cache")
+        .arg(
+            Arg::with_name("num_programs")
+                .long("num-programs")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(DEFAULT_NUM_PROGRAMS)
+                .help("Number of the most invoked programs to compile"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the invocations are counted over the latest rooted slots of the blockstore, \
+             see --precompile-programs-slots. Builtins and the programs which are already \
+             compiled are skipped",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let PrecompileProgramsArgs {
+        num_programs,
+        output,
+    } = PrecompileProgramsArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let precompilation = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.precompile_programs(num_programs).await })?;
+    println!("{}", output.formatted_string(&precompilation));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_precompile_programs_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            PrecompileProgramsArgs {
+                num_programs: 100,
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_precompile_programs_with_values() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--num-programs", "5", "--output", "json"],
+            PrecompileProgramsArgs {
+                num_programs: 5,
+                output: OutputFormat::Json,
+            },
+        );
+        verify_args_struct_by_command_is_error::<PrecompileProgramsArgs>(
+            command(),
+            vec![COMMAND, "--num-programs", "many"],
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "resume-leader";
+
+#[derive(Debug, PartialEq)]
+pub struct ResumeLeaderArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for ResumeLeaderArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(ResumeLeaderArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Resume block production paused by pause-leader")
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let ResumeLeaderArgs { output } = ResumeLeaderArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let status = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.resume_leader().await })?;
+    println!("{}", output.formatted_string(&status));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::commands::tests::verify_args_struct_by_command};
+
+    #[test]
+    fn verify_args_struct_by_command_resume_leader() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            ResumeLeaderArgs {
+                output: OutputFormat::Display,
+            },
+        );
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            ResumeLeaderArgs {
+                output: OutputFormat::Json,
+            },
+        );
+    }
+}
@@ -4,8 +4,8 @@ use {
     solana_clap_utils::{
         hidden_unless_forced,
         input_validators::{
-            is_keypair_or_ask_keyword, is_parsable, is_pow2, is_pubkey, is_pubkey_or_keypair,
-            is_slot, is_within_range, validate_cpu_ranges,
+            is_keypair, is_keypair_or_ask_keyword, is_parsable, is_pow2, is_pubkey, is_pubkey_or_keypair,
+            is_slot, is_url, is_valid_percentage, is_within_range, validate_cpu_ranges,
             validate_maximum_full_snapshot_archives_to_retain,
             validate_maximum_incremental_snapshot_archives_to_retain,
         },
@@ -13,15 +13,24 @@ use {
     },
     solana_core::{
         banking_trace::DirByteLimit,
+        consensus::vote_refresh::VoteRefreshMode,
+        forwarding_stage::MAX_FORWARDING_LEADER_FANOUT,
+        repair::repair_peer_selector::RepairPeerSelection,
         validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
     },
+    solana_gossip::crds_priority::CrdsPriorityWeights,
     solana_ledger::use_snapshot_archives_at_startup,
     solana_runtime::snapshot_utils::{SnapshotVersion, SUPPORTED_ARCHIVE_COMPRESSION},
+    solana_sdk::clock::MAX_PROCESSING_AGE,
     solana_send_transaction_service::send_transaction_service::{
         MAX_BATCH_SEND_RATE_MS, MAX_TRANSACTION_BATCH_SIZE,
     },
+    solana_turbine::shredstream::ShredstreamSubscriber,
     solana_unified_scheduler_pool::DefaultSchedulerPool,
-    std::str::FromStr,
+    std::{
+        num::{NonZeroU32, NonZeroU64},
+        str::FromStr,
+    },
 };
 
 const EXCLUDE_KEY: &str = "account-index-exclude-key";
@@ -69,6 +78,117 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  or set by the --authorized-voter argument",
             ),
     )
+    .arg(
+        Arg::with_name("max_commission_change")
+            .long("max-commission-change")
+            .value_name("PERCENT")
+            .takes_value(true)
+            .validator(is_valid_percentage)
+            .help(
+                "Refuse to submit transactions, received through the sendTransaction RPC \
+                 method, which change the commission of the vote account by more than PERCENT \
+                 percentage points within --commission-change-window-epochs. The guard can be \
+                 overridden through the admin RPC service. [default: no limit]",
+            ),
+    )
+    .arg(
+        Arg::with_name("commission_change_window_epochs")
+            .long("commission-change-window-epochs")
+            .value_name("EPOCHS")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..))
+            .default_value(&default_args.commission_change_window_epochs)
+            .help("Length of the window over which --max-commission-change is enforced"),
+    )
+    .arg(
+        Arg::with_name("failover_peer")
+            .long("failover-peer")
+            .value_name("HOST:PORT")
+            .takes_value(true)
+            .validator(solana_net_utils::is_host_port)
+            .requires_all(&[
+                "failover_peer_pubkey",
+                "failover_bind_address",
+                "failover_identity",
+                "failover_keypair",
+            ])
+            .help(
+                "Enable the hot-standby failover with the validator listening at this \
+                 address. The active validator runs with --failover-identity, the standby \
+                 with --failover-keypair, and it takes over the identity once the active \
+                 validator stops sending heartbeats",
+            ),
+    )
+    .arg(
+        Arg::with_name("failover_peer_pubkey")
+            .long("failover-peer-pubkey")
+            .value_name("PUBKEY")
+            .takes_value(true)
+            .validator(is_pubkey)
+            .requires("failover_peer")
+            .help("The --failover-keypair pubkey of the failover peer"),
+    )
+    .arg(
+        Arg::with_name("failover_bind_address")
+            .long("failover-bind-address")
+            .value_name("HOST:PORT")
+            .takes_value(true)
+            .validator(solana_net_utils::is_host_port)
+            .requires("failover_peer")
+            .help("Address to receive the heartbeats of the failover peer at"),
+    )
+    .arg(
+        Arg::with_name("failover_identity")
+            .long("failover-identity")
+            .value_name("KEYPAIR")
+            .takes_value(true)
+            .validator(is_keypair)
+            .requires("failover_peer")
+            .help("The identity shared with the failover peer, taken by the active validator"),
+    )
+    .arg(
+        Arg::with_name("failover_keypair")
+            .long("failover-keypair")
+            .value_name("KEYPAIR")
+            .takes_value(true)
+            .validator(is_keypair)
+            .requires("failover_peer")
+            .help(
+                "The node keypair of this validator, which signs its heartbeats and is its \
+                 identity while it stands by",
+            ),
+    )
+    .arg(
+        Arg::with_name("failover_heartbeat_interval_ms")
+            .long("failover-heartbeat-interval-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..))
+            .default_value(&default_args.failover_heartbeat_interval_ms)
+            .help("Interval between the heartbeats sent to the failover peer"),
+    )
+    .arg(
+        Arg::with_name("failover_max_missed_heartbeats")
+            .long("failover-max-missed-heartbeats")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..))
+            .default_value(&default_args.failover_max_missed_heartbeats)
+            .help(
+                "Number of heartbeats of the active failover peer missed before the standby \
+                 promotes itself",
+            ),
+    )
+    .arg(
+        Arg::with_name("no_failover_auto_promote")
+            .long("no-failover-auto-promote")
+            .takes_value(false)
+            .requires("failover_peer")
+            .help(
+                "Only promote the standby through the failover promote command, instead of \
+                 once the active failover peer missed --failover-max-missed-heartbeats",
+            ),
+    )
     .arg(
         Arg::with_name("init_complete_file")
             .long("init-complete-file")
@@ -159,6 +279,29 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .validator(port_validator)
             .help("Enable JSON RPC on this port, and the next port for the RPC websocket"),
     )
+    .arg(
+        Arg::with_name("rpc_grpc_port")
+            .long("rpc-grpc-port")
+            .value_name("PORT")
+            .takes_value(true)
+            .validator(port_validator)
+            .requires("rpc_port")
+            .help(
+                "Also serve the core RPC queries over gRPC on this port. Requires a validator \
+                 built with the grpc feature",
+            ),
+    )
+    .arg(
+        Arg::with_name("metrics_prometheus_port")
+            .long("metrics-prometheus-port")
+            .value_name("PORT")
+            .takes_value(true)
+            .validator(port_validator)
+            .help(
+                "Also serve the metrics datapoints in the Prometheus exposition format at \
+                 /metrics on this port of the RPC bind address",
+            ),
+    )
     .arg(
         Arg::with_name("full_rpc_api")
             .long("full-rpc-api")
@@ -205,6 +348,32 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .takes_value(false)
             .help("Upload new confirmed blocks into a BigTable instance"),
     )
+    .arg(
+        Arg::with_name("rpc_object_store_ledger_storage")
+            .long("rpc-object-store-ledger-storage")
+            .value_name("URL")
+            .takes_value(true)
+            .requires("enable_rpc_transaction_history")
+            .conflicts_with_all(&[
+                "enable_rpc_bigtable_ledger_storage",
+                "enable_bigtable_ledger_upload",
+            ])
+            .help(
+                "Fetch historical transaction info from this object storage, in place of a \
+                 BigTable instance, as a fallback to local ledger data. Accepts the same URLs \
+                 as --blockstore-tiering-url",
+            ),
+    )
+    .arg(
+        Arg::with_name("enable_object_store_ledger_upload")
+            .long("enable-object-store-ledger-upload")
+            .requires("rpc_object_store_ledger_storage")
+            .takes_value(false)
+            .help(
+                "Upload new confirmed blocks into the object storage of \
+                 --rpc-object-store-ledger-storage",
+            ),
+    )
     .arg(
         Arg::with_name("enable_extended_tx_metadata_storage")
             .long("enable-extended-tx-metadata-storage")
@@ -215,6 +384,41 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  transaction info stored",
             ),
     )
+    .arg(
+        Arg::with_name("enable_program_signature_index")
+            .long("enable-program-signature-index")
+            .requires("enable_rpc_transaction_history")
+            .takes_value(false)
+            .help(
+                "Index the signatures of the historical transactions stored by the programs \
+                 they invoke, enabling the getSignaturesForProgram RPC method",
+            ),
+    )
+    .arg(
+        Arg::with_name("transaction_history_skip_votes")
+            .long("transaction-history-skip-votes")
+            .requires("enable_rpc_transaction_history")
+            .takes_value(false)
+            .help(
+                "Do not store the statuses, memos and address signatures of the vote \
+                 transactions in the historical transaction info. The getBlock RPC method \
+                 cannot serve the blocks containing vote transactions from the blockstore",
+            ),
+    )
+    .arg(
+        Arg::with_name("transaction_history_sample_percent")
+            .long("transaction-history-sample-percent")
+            .value_name("PERCENT")
+            .requires("enable_rpc_transaction_history")
+            .takes_value(true)
+            .validator(is_valid_percentage)
+            .help(
+                "Store the historical transaction info of PERCENT percent of the successful \
+                 transactions, sampled by signature, and of all the failed transactions. The \
+                 getBlock RPC method cannot serve the blocks containing unsampled transactions \
+                 from the blockstore",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_max_multiple_accounts")
             .long("rpc-max-multiple-accounts")
@@ -238,6 +442,42 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  latest optimistically confirmed slot",
             ),
     )
+    .arg(
+        Arg::with_name("health_max_slots_since_last_vote")
+            .long("health-max-slots-since-last-vote")
+            .value_name("SLOTS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.health_max_slots_since_last_vote)
+            .help(
+                "Report the votes of this validator as unhealthy in getHealthDetail if its \
+                 latest landed vote is older than this number of slots",
+            ),
+    )
+    .arg(
+        Arg::with_name("health_max_snapshot_age_slots")
+            .long("health-max-snapshot-age-slots")
+            .value_name("SLOTS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.health_max_snapshot_age_slots)
+            .help(
+                "Report the snapshots of this validator as stale in getHealthDetail if its \
+                 latest snapshot archive is older than this number of slots behind the root",
+            ),
+    )
+    .arg(
+        Arg::with_name("health_max_incomplete_slots")
+            .long("health-max-incomplete-slots")
+            .value_name("SLOTS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.health_max_incomplete_slots)
+            .help(
+                "Report a repair storm in getHealthDetail if more than this number of slots \
+                 above the root are missing shreds",
+            ),
+    )
     .arg(
         Arg::with_name("skip_preflight_health_check")
             .long("skip-preflight-health-check")
@@ -337,6 +577,20 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .takes_value(true)
             .help("Use DIR as file tower storage location [default: --ledger value]"),
     )
+    .arg(
+        Arg::with_name("remote_tower_storage")
+            .long("remote-tower-storage")
+            .value_name("URL")
+            .takes_value(true)
+            .conflicts_with("tower")
+            .validator(is_url)
+            .help(
+                "Store the tower in the service at URL, such as one running alongside the \
+                 remote signer of the identity, instead of in a file. The service holds the \
+                 tower for the last validator instance which loaded it, so that a hot spare \
+                 can take over without copying the tower file.",
+            ),
+    )
     .arg(
         Arg::with_name("gossip_port")
             .long("gossip-port")
@@ -497,6 +751,26 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  increases priority, positive value decreases priority.",
             ),
     )
+    .arg(
+        Arg::with_name("snapshot_packager_io_limit")
+            .long("snapshot-packager-io-limit")
+            .value_name("MEGABYTES_PER_SECOND")
+            .takes_value(true)
+            .validator(is_parsable::<NonZeroU64>)
+            .help(
+                "Limit the rate at which the snapshot packager reads the account storages \
+                 into snapshot archives, in megabytes per second [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("snapshot_archive_verification")
+            .long("snapshot-archive-verification")
+            .takes_value(false)
+            .help(
+                "Re-read and verify each snapshot archive in the background after it is made, \
+                 and only advertise the snapshot hashes of the valid archives in gossip",
+            ),
+    )
     .arg(
         Arg::with_name("minimal_snapshot_download_speed")
             .long("minimal-snapshot-download-speed")
@@ -687,6 +961,251 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  supermajority of stake is visible on gossip before starting PoH",
             ),
     )
+    .arg(
+        Arg::with_name("max_clock_drift_ms")
+            .long("max-clock-drift-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.max_clock_drift_ms)
+            .help(
+                "Warn when the local clock is off by more than this many milliseconds from \
+                 the stake-weighted clock of the cluster, as observed in gossip votes",
+            ),
+    )
+    .arg(
+        Arg::with_name("refuse_to_vote_on_clock_drift")
+            .long("refuse-to-vote-on-clock-drift")
+            .help(
+                "Stop voting while the local clock is off by more than --max-clock-drift-ms \
+                 from the cluster",
+            ),
+    )
+    .arg(
+        Arg::with_name("vote_refresh_mode")
+            .long("vote-refresh-mode")
+            .value_name("MODE")
+            .takes_value(true)
+            .possible_values(VoteRefreshMode::cli_names())
+            .default_value(VoteRefreshMode::default().into())
+            .help(
+                "When to resend the last vote with a newer blockhash while it has not landed \
+                 on the voted fork. blockheight: once its blockhash is \
+                 --vote-refresh-blockheight blocks old. leader-rotation: also whenever a new \
+                 leader builds on the voted fork",
+            ),
+    )
+    .arg(
+        Arg::with_name("vote_refresh_blockheight")
+            .long("vote-refresh-blockheight")
+            .value_name("BLOCKS")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..MAX_PROCESSING_AGE))
+            .default_value(&default_args.vote_refresh_blockheight)
+            .help("Age of the blockhash of the last vote, in blocks, from which it is refreshed"),
+    )
+    .arg(
+        Arg::with_name("vote_refresh_interval_ms")
+            .long("vote-refresh-interval-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.vote_refresh_interval_ms)
+            .help("Minimum time between two refreshes of the last vote"),
+    )
+    .arg(
+        Arg::with_name("shred_latency_slots")
+            .long("shred-latency-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Record the arrival times of turbine shreds relative to the start of their \
+                 slots, and retain them for this many recent slots. The distributions are \
+                 reported as metrics and served by getShredLatency. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("leader_slot_stats_slots")
+            .long("leader-slot-stats-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Record how many transactions the banking stage attempted and committed, and \
+                 the compute units they used, in this many recent leader slots. The \
+                 statistics are served by getRecentLeaderSlots. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("confirmation_latency_slots")
+            .long("confirmation-latency-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Record the times from the first shred of each slot to its optimistic \
+                 confirmation and to its rooting, and retain them for this many recent \
+                 slots. The latencies are reported as metrics and served by \
+                 getConfirmationLatency. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("skipped_slot_reasons_slots")
+            .long("skipped-slot-reasons-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Determine the probable cause of each skipped leader slot of this node, from \
+                 when its block started and completed and how many transactions it has, and \
+                 retain them for this many recent skipped slots. The causes are reported as \
+                 metrics and served by getSkippedSlotReasons. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("account_congestion_slots")
+            .long("account-congestion-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Retain the compute units consumed by, and rejected for exceeding the account \
+                 limit of, the most contended write locked accounts of this many recent slots, \
+                 served by getAccountCongestion. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("confirmation_proof_slots")
+            .long("confirmation-proof-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Retain the votes by which each of this many recent slots reached \
+                 optimistic confirmation, with their signatures and the vote transactions \
+                 seen in gossip, served by getConfirmationProof. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("buffered_transaction_samples")
+            .long("buffered-transaction-samples")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Sample this many of the non-vote transactions buffered by the banking \
+                 stage, with their priorities, the slots they waited and the times they \
+                 were retried, served by getBufferedTransactions. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("account_growth_epochs")
+            .long("account-growth-epochs")
+            .value_name("EPOCHS")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Sample the accounts created and deleted, the accounts data size and its \
+                 rent-exempt reserve every minute, and retain their growth with the programs \
+                 creating the largest accounts for this many recent epochs, reported as \
+                 metrics and served by getAccountGrowth. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("bank_hash_breakdown_slots")
+            .long("bank-hash-breakdown-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .default_value(&default_args.bank_hash_breakdown_slots)
+            .help(
+                "Retain the components of the bank hashes of this many recent frozen slots, \
+                 served by the bank-hash-breakdowns command to compare them with the ones of \
+                 another validator. 0 disables the tracking",
+            ),
+    )
+    .arg(
+        Arg::with_name("cost_calibration_report_interval_ms")
+            .long("cost-calibration-report-interval-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Measure the execution time and compute units of each program while replaying \
+                 and producing blocks, and report them against the cost model in the \
+                 cost_calibration metric this often. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("pipeline_tracing_otlp_endpoint")
+            .long("pipeline-tracing-otlp-endpoint")
+            .value_name("URL")
+            .takes_value(true)
+            .help(
+                "Trace sampled packet batches from sigverify through the banking stage \
+                 scheduler, execution and commit, exporting the spans to this OTLP gRPC \
+                 collector endpoint, e.g. http://127.0.0.1:4317. Requires a validator built \
+                 with the otel feature",
+            ),
+    )
+    .arg(
+        Arg::with_name("pipeline_tracing_sample_interval")
+            .long("pipeline-tracing-sample-interval")
+            .value_name("BATCHES")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .requires("pipeline_tracing_otlp_endpoint")
+            .default_value(&default_args.pipeline_tracing_sample_interval)
+            .help("Trace one in every BATCHES packet batches"),
+    )
+    .arg(
+        Arg::with_name("pinned_programs")
+            .long("pin-program")
+            .validator(is_pubkey)
+            .value_name("PROGRAM_ID")
+            .multiple(true)
+            .takes_value(true)
+            .help(
+                "Never evict this program from the program cache once it is loaded, and \
+                 recompile it first ahead of a program runtime change. May be specified \
+                 multiple times.",
+            ),
+    )
+    .arg(
+        Arg::with_name("precompile_programs")
+            .long("precompile-programs")
+            .value_name("NUM")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Compile the NUM programs invoked the most in the latest rooted slots into \
+                 the program cache before voting, so their first invocations after a \
+                 restart do not wait for them to be compiled. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("precompile_programs_slots")
+            .long("precompile-programs-slots")
+            .value_name("SLOTS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.precompile_programs_slots)
+            .help("Number of the latest rooted slots to count the program invocations of"),
+    )
+    .arg(
+        Arg::with_name("forensic_bundle_dir")
+            .long("forensic-bundle-dir")
+            .value_name("DIR")
+            .takes_value(true)
+            .help(
+                "Capture a forensic bundle into this directory whenever a duplicate shred \
+                 proof is observed, or a slot is frozen with a different bank hash than the \
+                 one the cluster confirmed. Bundles hold the shreds of the slot and, for \
+                 bank hash mismatches, the bank hash components. They are listed and \
+                 fetched with the forensic-bundles subcommand",
+            ),
+    )
     .arg(
         Arg::with_name("no_wait_for_vote_to_start_leader")
             .hidden(hidden_unless_forced())
@@ -749,6 +1268,24 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  request from validators outside this set [default: all validators]",
             ),
     )
+    .arg(
+        Arg::with_name("repair_quic")
+            .long("repair-quic")
+            .takes_value(false)
+            .help(
+                "Request repairs over QUIC from the validators which advertise a QUIC repair \
+                 port, and over UDP from the others. Ignored on mainnet-beta, where repair \
+                 QUIC endpoints are not started.",
+            ),
+    )
+    .arg(
+        Arg::with_name("repair_peer_selection")
+            .long("repair-peer-selection")
+            .value_name("POLICY")
+            .takes_value(true)
+            .possible_values(RepairPeerSelection::cli_names())
+            .help(RepairPeerSelection::cli_message()),
+    )
     .arg(
         Arg::with_name("repair_whitelist")
             .hidden(hidden_unless_forced())
@@ -775,6 +1312,31 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  push/pull from from validators outside this set. [default: all validators]",
             ),
     )
+    .arg(
+        Arg::with_name("gossip_priority_weights")
+            .long("gossip-priority-weights")
+            .value_name("TYPE=WEIGHT,...")
+            .takes_value(true)
+            .validator(is_parsable::<CrdsPriorityWeights>)
+            .help(
+                "Relative weights of the types of gossip values, used to order outgoing values \
+                 when gossip is saturated. Values of the same weight are ordered by the stake \
+                 of their origin. Types are contact-info, vote, duplicate-shred, epoch-slots, \
+                 restart and other. Types which are not listed keep their default weight. \
+                 [default: contact-info=2,vote=4,duplicate-shred=8,epoch-slots=1,restart=4,\
+                 other=1]",
+            ),
+    )
+    .arg(
+        Arg::with_name("enable_gossip_quic")
+            .long("enable-gossip-quic")
+            .takes_value(false)
+            .help(
+                "Advertise a gossip QUIC address and send gossip packets, most notably pull \
+                 responses, over QUIC to peers which advertise one too. Other peers are still \
+                 reached over UDP.",
+            ),
+    )
     .arg(
         Arg::with_name("tpu_coalesce_ms")
             .long("tpu-coalesce-ms")
@@ -820,6 +1382,33 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .hidden(hidden_unless_forced())
             .help("Controls the rate of the clients connections per IpAddr per minute."),
     )
+    .arg(
+        Arg::with_name("tpu_virtual_stake")
+            .long("tpu-virtual-stake")
+            .value_name("LAMPORTS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Treat the TPU QUIC connections from the loopback addresses, from the gossip \
+                 host of this validator and from the --tpu-virtual-stake-peer addresses as the \
+                 ones of a node with this stake, so that the transactions the validator sends \
+                 itself are not starved by the staked peers. [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("tpu_virtual_stake_peers")
+            .long("tpu-virtual-stake-peer")
+            .value_name("HOST")
+            .takes_value(true)
+            .number_of_values(1)
+            .multiple(true)
+            .requires("tpu_virtual_stake")
+            .validator(solana_net_utils::is_host)
+            .help(
+                "Treat the TPU QUIC connections from this trusted peer as virtually staked. \
+                 May be specified multiple times.",
+            ),
+    )
     .arg(
         Arg::with_name("vote_use_quic")
             .long("vote-use-quic")
@@ -1002,6 +1591,44 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .default_value(&default_args.rpc_bigtable_max_message_size)
             .help("Max encoding and decoding message size used in Bigtable Grpc client"),
     )
+    .arg(
+        Arg::with_name("blockstore_tiering_url")
+            .long("blockstore-tiering-url")
+            .value_name("URL")
+            .takes_value(true)
+            .requires("enable_rpc_transaction_history")
+            .conflicts_with("limit_ledger_size")
+            .help(
+                "Offload the blocks of old rooted slots to this object storage and purge them \
+                 from the blockstore. RPC fetches these blocks back from the object storage. \
+                 Accepted URLs: s3://BUCKET[/PREFIX], gs://BUCKET[/PREFIX], \
+                 https://HOST[:PORT]/BUCKET[/PREFIX] for other S3-compatible services, and \
+                 file://PATH. Requests are signed with the credentials of the \
+                 AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY environment variables, in the \
+                 region of the AWS_REGION environment variable",
+            ),
+    )
+    .arg(
+        Arg::with_name("blockstore_tiering_offload_epochs")
+            .long("blockstore-tiering-offload-epochs")
+            .value_name("EPOCHS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.blockstore_tiering_offload_epochs)
+            .help("Offload rooted slots once they are this many epochs older than the root"),
+    )
+    .arg(
+        Arg::with_name("blockstore_tiering_cache_size")
+            .long("blockstore-tiering-cache-size")
+            .value_name("BYTES")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.blockstore_tiering_cache_size)
+            .help(
+                "Maximum size of the local cache of the blocks fetched back from the object \
+                 storage, in <LEDGER>/tiering_cache",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_pubsub_worker_threads")
             .long("rpc-pubsub-worker-threads")
@@ -1024,6 +1651,25 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .takes_value(false)
             .help("Enable the unstable RPC PubSub `voteSubscribe` subscription"),
     )
+    .arg(
+        Arg::with_name("rpc_pubsub_enable_duplicate_shred_proof_subscription")
+            .long("rpc-pubsub-enable-duplicate-shred-proof-subscription")
+            .takes_value(false)
+            .help(
+                "Enable the unstable RPC PubSub `duplicateShredProofSubscribe` subscription, \
+                 which publishes every duplicate shred proof the first time it is observed in \
+                 gossip",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_pubsub_enable_prioritization_fee_subscription")
+            .long("rpc-pubsub-enable-prioritization-fee-subscription")
+            .takes_value(false)
+            .help(
+                "Enable the unstable RPC PubSub `prioritizationFeeSubscribe` subscription, \
+                 which publishes the compute-unit price percentiles of every confirmed block",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_pubsub_max_active_subscriptions")
             .long("rpc-pubsub-max-active-subscriptions")
@@ -1077,6 +1723,16 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  notifications. 0 will disable RPC PubSub notifications",
             ),
     )
+    .arg(
+        Arg::with_name("rpc_pubsub_proxy_protocol")
+            .long("rpc-pubsub-proxy-protocol")
+            .takes_value(false)
+            .help(
+                "Require every RPC PubSub connection to start with a PROXY protocol v2 header, \
+                 so that the address of the client behind an L4 load balancer is known. \
+                 Connections without a valid header are closed",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_send_transaction_retry_ms")
             .long("rpc-send-retry-ms")
@@ -1166,6 +1822,27 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .requires("rpc_send_transaction_tpu_peer")
             .help("With `--rpc-send-transaction-tpu-peer HOST:PORT`, also send to the current leader")
     )
+    .arg(
+        Arg::with_name("rpc_send_transaction_store_path")
+            .long("rpc-send-transaction-store-path")
+            .value_name("DIR")
+            .takes_value(true)
+            .help(
+                "Persist the transactions of the retry pool in this directory, to keep retrying \
+                 them after a restart",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_send_transaction_status_webhook")
+            .long("rpc-send-transaction-status-webhook")
+            .value_name("URL")
+            .takes_value(true)
+            .validator(is_url)
+            .help(
+                "POST the final status of each transaction of the retry pool to this URL, as \
+                 JSON with its signature and a status of rooted, failed, expired or dropped",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_scan_and_fix_roots")
             .long("rpc-scan-and-fix-roots")
@@ -1182,6 +1859,52 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .default_value(&default_args.rpc_max_request_body_size)
             .help("The maximum request body size accepted by rpc service"),
     )
+    .arg(
+        Arg::with_name("rpc_rate_limit")
+            .long("rpc-rate-limit")
+            .value_name("REQUESTS_PER_SECOND")
+            .takes_value(true)
+            .validator(is_parsable::<NonZeroU32>)
+            .help(
+                "Limit the JSON RPC requests of each client to this many per second, answering \
+                 the requests over the limit with 429 Too Many Requests. Clients are identified \
+                 by --rpc-rate-limit-api-key-header, or else by the source IP forwarded in the \
+                 X-Forwarded-For header [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_method_rate_limit")
+            .long("rpc-method-rate-limit")
+            .value_name("METHOD=REQUESTS_PER_SECOND")
+            .takes_value(true)
+            .multiple(true)
+            .help(
+                "Limit the calls of each client to METHOD to this many per second. May be \
+                 specified multiple times [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_rate_limit_api_key_header")
+            .long("rpc-rate-limit-api-key-header")
+            .value_name("HEADER")
+            .takes_value(true)
+            .help(
+                "Identify the clients of the JSON RPC rate limits by the value of this header, \
+                 for those which send it",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_slot_account_filter_slots")
+            .long("rpc-slot-account-filter-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Build a filter of the accounts written in every frozen slot, serve the filters \
+                 of this many recent slots through getSlotAccountFilter and publish them to \
+                 Geyser plugins [default: disabled]",
+            ),
+    )
     .arg(
         Arg::with_name("geyser_plugin_config")
             .long("geyser-plugin-config")
@@ -1261,6 +1984,17 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             })
             .help("EXPERIMENTAL: Specify which CPU core PoH is pinned to"),
     )
+    .arg(
+        Arg::with_name("poh_numa_node")
+            .long("poh-numa-node")
+            .takes_value(true)
+            .value_name("NODE")
+            .validator(is_parsable::<usize>)
+            .help(
+                "Allocate the memory of PoH on the specified NUMA node, the node of the \
+                 --experimental-poh-pinned-cpu-core core",
+            ),
+    )
     .arg(
         Arg::with_name("poh_hashes_per_batch")
             .hidden(hidden_unless_forced())
@@ -1269,6 +2003,78 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .value_name("NUM")
             .help("Specify hashes per batch in PoH service"),
     )
+    .arg(
+        Arg::with_name("poh_calibration_interval_ms")
+            .long("poh-calibration-interval-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.poh_calibration_interval_ms)
+            .help(
+                "How often to measure the PoH hash rate, raising the hashes per batch up to \
+                 16 times --poh-hashes-per-batch while the rate is close to the cluster \
+                 requirement, and alerting when it drops below. 0 disables the calibration",
+            ),
+    )
+    .arg(
+        Arg::with_name("entry_verification_cpu_cores")
+            .long("entry-verification-cpu-cores")
+            .takes_value(true)
+            .value_name("CPU_LIST")
+            .validator(|value| validate_cpu_ranges(value, "--entry-verification-cpu-cores"))
+            .help(
+                "Verify the PoH of the replayed entries on a thread pool of its own, with one \
+                 thread pinned to each of the specified CPU cores, which the other threads of \
+                 the validator do not run on. By default, the entries are verified on the \
+                 replay thread pools",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_cpu_cores")
+            .long("banking-cpu-cores")
+            .takes_value(true)
+            .value_name("CPU_LIST")
+            .validator(|value| validate_cpu_ranges(value, "--banking-cpu-cores"))
+            .help(
+                "Pin the banking stage scheduler and worker threads to the specified CPU \
+                 cores, which the other threads of the validator do not run on. Defaults to \
+                 the cores of --banking-numa-node, if any",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_numa_node")
+            .long("banking-numa-node")
+            .takes_value(true)
+            .value_name("NODE")
+            .validator(is_parsable::<usize>)
+            .help(
+                "Allocate the memory of the banking stage threads, the packet buffers \
+                 included, on the specified NUMA node",
+            ),
+    )
+    .arg(
+        Arg::with_name("sigverify_cpu_cores")
+            .long("sigverify-cpu-cores")
+            .takes_value(true)
+            .value_name("CPU_LIST")
+            .validator(|value| validate_cpu_ranges(value, "--sigverify-cpu-cores"))
+            .help(
+                "Pin the TPU sigverify threads and the signature verification thread pool to \
+                 the specified CPU cores, which the other threads of the validator do not run \
+                 on. Defaults to the cores of --sigverify-numa-node, if any",
+            ),
+    )
+    .arg(
+        Arg::with_name("sigverify_numa_node")
+            .long("sigverify-numa-node")
+            .takes_value(true)
+            .value_name("NODE")
+            .validator(is_parsable::<usize>)
+            .help(
+                "Allocate the memory of the sigverify threads, the packet buffers included, \
+                 on the specified NUMA node",
+            ),
+    )
     .arg(
         Arg::with_name("process_ledger_before_services")
             .long("process-ledger-before-services")
@@ -1280,7 +2086,13 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .long("account-index")
             .takes_value(true)
             .multiple(true)
-            .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+            .possible_values(&[
+                "program-id",
+                "spl-token-owner",
+                "spl-token-mint",
+                "data-size",
+                "nonce-authority",
+            ])
             .value_name("INDEX")
             .help("Enable an accounts index, indexed by the selected account field"),
     )
@@ -1363,6 +2175,31 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .possible_values(&["mmap", "file"])
             .help("Access account storages using this method")
     )
+    .arg(
+        Arg::with_name("accounts_db_track_owner_disk_usage")
+            .long("accounts-db-track-owner-disk-usage")
+            .takes_value(false)
+            .help(
+                "Track the disk usage of account storages by owner, for \
+                 `agave-validator accounts-disk-usage`. Storages are summarized when they are \
+                 shrunk, and a few at a time by each clean, so the disk usage covers all \
+                 storages only some time after startup",
+            ),
+    )
+    .arg(
+        Arg::with_name("accounts_io_backend")
+            .long("accounts-io-backend")
+            .value_name("BACKEND")
+            .takes_value(true)
+            .possible_values(&["pread", "io-uring"])
+            .help(
+                "Read batches of accounts from account storages using this backend. With \
+                 io-uring, the accounts of transaction batches in replay and of the accounts \
+                 hash verification at startup are read together, which is faster on NVMe drives. \
+                 Only account storages accessed by file are read in batches, and io-uring is \
+                 only available on Linux [default: pread]",
+            ),
+    )
     .arg(
         Arg::with_name("accounts_db_ancient_append_vecs")
             .long("accounts-db-ancient-append-vecs")
@@ -1393,6 +2230,36 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .help("The number of ancient storages the ancient slot combining should converge to.")
             .hidden(hidden_unless_forced()),
     )
+    .arg(
+        Arg::with_name("accounts_db_ancient_packing_policy")
+            .long("accounts-db-ancient-packing-policy")
+            .value_name("FILE")
+            .takes_value(true)
+            .conflicts_with_all(&[
+                "accounts_db_ancient_storage_ideal_size",
+                "accounts_db_max_ancient_storages",
+            ])
+            .help(
+                "YAML file of the policy of packing ancient storages, with any of the fields \
+                 ideal_storage_size, max_ancient_storages and shrink_ratio. The policy can be \
+                 changed while the validator is running with `agave-validator \
+                 ancient-packing-policy`.",
+            )
+            .hidden(hidden_unless_forced()),
+    )
+    .arg(
+        Arg::with_name("accounts_db_ancient_storage_compression_level")
+            .long("accounts-db-ancient-storage-compression-level")
+            .value_name("LEVEL")
+            .validator(is_parsable::<i32>)
+            .takes_value(true)
+            .help(
+                "Compress ancient storages with zstd at this level once they are written. \
+                 Compressed storages take less disk space, but are decompressed into memory \
+                 when opened.",
+            )
+            .hidden(hidden_unless_forced()),
+    )
     .arg(
         Arg::with_name("accounts_db_hash_calculation_pubkey_bins")
             .long("accounts-db-hash-calculation-pubkey-bins")
@@ -1402,6 +2269,17 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .help("The number of pubkey bins used for accounts hash calculation.")
             .hidden(hidden_unless_forced()),
     )
+    .arg(
+        Arg::with_name("accounts_db_checkpoint_hash_calculation")
+            .long("accounts-db-checkpoint-hash-calculation")
+            .takes_value(false)
+            .help(
+                "Checkpoint the full accounts hash calculation in the accounts hash cache \
+                 directory, so that a calculation interrupted by a restart, like the one \
+                 verifying the accounts at startup, resumes from its last completed range of \
+                 pubkey bins",
+            ),
+    )
     .arg(
         Arg::with_name("accounts_db_cache_limit_mb")
             .long("accounts-db-cache-limit-mb")
@@ -1588,6 +2466,56 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .possible_values(BlockProductionMethod::cli_names())
             .help(BlockProductionMethod::cli_message()),
     )
+    .arg(
+        Arg::with_name("forwarding_leader_fanout")
+            .long("forwarding-leader-fanout")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .hidden(hidden_unless_forced())
+            .default_value(&default_args.forwarding_leader_fanout)
+            .validator(|s| is_within_range(s, 1..=MAX_FORWARDING_LEADER_FANOUT))
+            .help(
+                "Number of distinct upcoming leaders that unprocessed transactions are \
+                 forwarded to",
+            ),
+    )
+    .arg(
+        Arg::with_name("packet_provenance_cache_size")
+            .long("packet-provenance-cache-size")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Track where and when transaction packets are received by the banking stage, \
+                 and retain the provenance of this many recently committed transactions. \
+                 The provenance is reported by getSignatureStatuses. \
+                 [default: 0, disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("dump_leader_slot_transactions")
+            .long("dump-leader-slot-transactions")
+            .value_name("DIR")
+            .takes_value(true)
+            .help(
+                "Record every transaction attempted by the banking stage during this \
+                 validator's leader slots, whether committed, retried, or dropped, to one file \
+                 per slot in this directory. Analyze the files with \
+                 `agave-ledger-tool leader-slot-report`",
+            ),
+    )
+    .arg(
+        Arg::with_name("record_banking_events")
+            .long("record-banking-events")
+            .value_name("DIR")
+            .takes_value(true)
+            .help(
+                "Record the packet batches received by the banking stage during this \
+                 validator's leader slots, along with the boundaries of the slots, to one file \
+                 per run of consecutive leader slots in this directory, so that they can be \
+                 replayed deterministically to test changes to the transaction scheduler",
+            ),
+    )
     .arg(
         Arg::with_name("transaction_struct")
             .long("transaction-structure")
@@ -1678,4 +2606,117 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .requires("retransmit_xdp_cpu_cores")
             .help("EXPERIMENTAL: Enable XDP zero copy. Requires hardware support"),
     )
+    .arg(
+        Arg::with_name("retransmit_egress_limit_mbps")
+            .long("retransmit-egress-limit-mbps")
+            .takes_value(true)
+            .value_name("MBPS")
+            .validator(is_parsable::<u64>)
+            .help(
+                "Cap the bandwidth of the retransmitted shreds to this number of megabits per \
+                 second. Once the link is saturated, the shreds of old slots are dropped first, \
+                 then the shreds not received from the leader, then the children last in the \
+                 turbine tree [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("retransmit_egress_burst_ms")
+            .long("retransmit-egress-burst-ms")
+            .takes_value(true)
+            .value_name("MILLISECONDS")
+            .validator(is_parsable::<u64>)
+            .requires("retransmit_egress_limit_mbps")
+            .default_value(&default_args.retransmit_egress_burst_ms)
+            .help(
+                "The retransmitted shreds may burst above --retransmit-egress-limit-mbps by \
+                 the bytes sent over this number of milliseconds at the limit",
+            ),
+    )
+    .arg(
+        Arg::with_name("remote_shred_signer_url")
+            .long("remote-shred-signer-url")
+            .takes_value(true)
+            .value_name("URL")
+            .validator(is_url)
+            .help(
+                "Sign the shreds of the blocks produced by the validator with the remote signer \
+                 at URL, such as one holding the identity key in an HSM. The shreds the signer \
+                 does not sign in time keep the signature of the local identity keypair",
+            ),
+    )
+    .arg(
+        Arg::with_name("remote_shred_signer_latency_budget_ms")
+            .long("remote-shred-signer-latency-budget-ms")
+            .takes_value(true)
+            .value_name("MILLISECONDS")
+            .validator(is_parsable::<u64>)
+            .requires("remote_shred_signer_url")
+            .default_value(&default_args.remote_shred_signer_latency_budget_ms)
+            .help("The time the remote signer has to sign each batch of broadcast shreds"),
+    )
+    .arg(
+        Arg::with_name("broadcast_coding_shreds")
+            .long("broadcast-coding-shreds")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .validator(|s| is_within_range(s, 1..=32))
+            .help(
+                "Number of coding shreds generated for each erasure batch of 32 data shreds of \
+                 the blocks produced by the validator. Fewer coding shreds use less bandwidth \
+                 but make the blocks less resilient to packet losses. The last erasure batch of \
+                 a block has 32 coding shreds regardless [default: 32]",
+            ),
+    )
+    .arg(
+        Arg::with_name("broadcast_fec_experiment")
+            .long("broadcast-fec-experiment")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .multiple(true)
+            .validator(|s| is_within_range(s, 1..=32))
+            .conflicts_with("broadcast_coding_shreds")
+            .help(
+                "Rotate the leader slots through these numbers of coding shreds per erasure \
+                 batch, and report the shreds broadcast and the repairs served for each leader \
+                 slot as metrics. May be specified multiple times.",
+            ),
+    )
+    .arg(
+        Arg::with_name("shredstream_subscriber")
+            .long("shredstream-subscriber")
+            .takes_value(true)
+            .value_name("HOST:PORT[,FILTER]")
+            .multiple(true)
+            .validator(is_parsable::<ShredstreamSubscriber>)
+            .help(
+                "Forward the shreds received from turbine and repair, and the recovered ones, to \
+                 the turbine QUIC server at HOST:PORT. The shreds may be filtered with \
+                 min-slot=SLOT, max-slot=SLOT and data-only, as in \
+                 HOST:PORT,min-slot=SLOT,data-only. May be specified multiple times.",
+            ),
+    )
+    .arg(
+        Arg::with_name("epoch_accounts_hash_start_delay_slots")
+            .long("epoch-accounts-hash-start-delay-slots")
+            .takes_value(true)
+            .value_name("SLOTS")
+            .validator(is_slot)
+            .help(
+                "Delay the epoch accounts hash calculation by this many slots after its start \
+                 slot is rooted, to leave the resources to replay, e.g. at the start of the \
+                 epoch. The calculation starts at most a quarter of an epoch after its start \
+                 slot regardless [default: 0]",
+            ),
+    )
+    .arg(
+        Arg::with_name("epoch_accounts_hash_threads")
+            .long("epoch-accounts-hash-threads")
+            .takes_value(true)
+            .value_name("NUM_THREADS")
+            .validator(|s| is_within_range(s, 1..))
+            .help(
+                "Calculate the epoch accounts hash with a dedicated pool of this many threads, \
+                 instead of the accounts hash threads of accounts-db",
+            ),
+    )
 }
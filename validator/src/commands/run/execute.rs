@@ -16,6 +16,8 @@ use {
             AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
             AccountsIndexConfig, IndexLimitMb, ScanFilter,
         },
+        ancient_packing_policy::AncientPackingPolicy,
+        file_io::IoBackend,
         utils::{
             create_all_accounts_run_and_snapshot_dirs, create_and_canonicalize_directories,
             create_and_canonicalize_directory,
@@ -26,18 +28,28 @@ use {
     },
     solana_core::{
         banking_trace::DISABLED_BAKING_TRACE_DIR,
-        consensus::tower_storage,
+        clock_drift_service::ClockDriftConfig,
+        consensus::{
+            tower_storage,
+            vote_refresh::{VoteRefreshConfig, VoteRefreshMode},
+        },
+        epoch_accounts_hash_scheduling::EpochAccountsHashSchedulingConfig,
+        exit_reason::{self, ExitReason},
+        failover::FailoverConfig,
+        pipeline_tracing::PipelineTracingConfig,
+        repair::repair_peer_selector::RepairPeerSelection,
         system_monitor_service::SystemMonitorService,
         tpu::DEFAULT_TPU_COALESCE,
         validator::{
             is_snapshot_config_valid, BlockProductionMethod, BlockVerificationMethod,
-            TransactionStructure, Validator, ValidatorConfig, ValidatorError,
-            ValidatorStartProgress, ValidatorTpuConfig,
+            TransactionStructure, Validator, ValidatorConfig, ValidatorStartProgress,
+            ValidatorTpuConfig,
         },
     },
     solana_gossip::{
         cluster_info::{Node, NodeConfig},
         contact_info::ContactInfo,
+        crds_priority::CrdsPriorityWeights,
     },
     solana_ledger::{
         blockstore_cleanup_service::{DEFAULT_MAX_LEDGER_SHREDS, DEFAULT_MIN_MAX_LEDGER_SHREDS},
@@ -45,14 +57,19 @@ use {
             AccessType, BlockstoreCompressionType, BlockstoreOptions, BlockstoreRecoveryMode,
             LedgerColumnOptions,
         },
+        blockstore_tiering::BlockstoreTieringConfig,
         use_snapshot_archives_at_startup::{self, UseSnapshotArchivesAtStartup},
     },
     solana_logger::redirect_stderr_to_file,
-    solana_perf::recycler::enable_recycler_warming,
+    solana_perf::{recycler::enable_recycler_warming, thread::ThreadPinning},
     solana_poh::poh_service,
     solana_rpc::{
-        rpc::{JsonRpcConfig, RpcBigtableConfig},
+        commission_guard::CommissionGuardConfig,
+        rpc::{JsonRpcConfig, RpcBigtableConfig, RpcObjectStoreLedgerStorageConfig},
+        rpc_health::RpcHealthDetailConfig,
         rpc_pubsub_service::PubSubConfig,
+        rpc_rate_limiter::RpcRateLimitConfig,
+        transaction_status_service::TransactionStatusFilter,
     },
     solana_runtime::{
         runtime_config::RuntimeConfig,
@@ -67,14 +84,23 @@ use {
         signature::{Keypair, Signer},
     },
     solana_send_transaction_service::send_transaction_service,
-    solana_streamer::{quic::QuicServerParams, socket::SocketAddrSpace},
+    solana_streamer::{
+        quic::{QuicServerParams, VirtualStakeConfig},
+        socket::SocketAddrSpace,
+    },
     solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
-    solana_turbine::xdp::{set_cpu_affinity, XdpConfig},
+    solana_turbine::{
+        egress_shaper::EgressShaperConfig,
+        fec_ratio::FecRatioConfig,
+        remote_shred_signer::RemoteShredSignerConfig,
+        shredstream::{ShredstreamConfig, ShredstreamSubscriber},
+        xdp::{set_cpu_affinity, XdpConfig},
+    },
     std::{
-        collections::HashSet,
+        collections::{HashMap, HashSet},
         fs::{self, File},
         net::{IpAddr, Ipv4Addr, SocketAddr},
-        num::NonZeroUsize,
+        num::{NonZeroU32, NonZeroU64, NonZeroUsize},
         path::{Path, PathBuf},
         process::exit,
         str::FromStr,
@@ -107,6 +133,7 @@ pub fn execute(
         rayon_global_threads,
         replay_forks_threads,
         replay_transactions_threads,
+        replay_fork_transactions_threads,
         rocksdb_compaction_threads,
         rocksdb_flush_threads,
         tvu_receive_threads,
@@ -149,9 +176,15 @@ pub fn execute(
 
     solana_core::validator::report_target_features();
 
+    // The standby of a failover votes with the shared identity once promoted
     let authorized_voter_keypairs = keypairs_of(matches, "authorized_voter_keypairs")
         .map(|keypairs| keypairs.into_iter().map(Arc::new).collect())
-        .unwrap_or_else(|| vec![Arc::new(keypair_of(matches, "identity").expect("identity"))]);
+        .unwrap_or_else(|| {
+            let authorized_voter = keypair_of(matches, "failover_identity")
+                .or_else(|| keypair_of(matches, "identity"))
+                .expect("identity");
+            vec![Arc::new(authorized_voter)]
+        });
     let authorized_voter_keypairs = Arc::new(RwLock::new(authorized_voter_keypairs));
 
     let staked_nodes_overrides_path = matches
@@ -366,7 +399,14 @@ pub fn execute(
         .ok()
         .or_else(|| get_cluster_shred_version(&entrypoint_addrs, bind_address));
 
-    let tower_storage: Arc<dyn tower_storage::TowerStorage> =
+    let tower_storage: Arc<dyn tower_storage::TowerStorage> = if let Some(url) =
+        matches.value_of("remote_tower_storage")
+    {
+        Arc::new(
+            tower_storage::RemoteTowerStorage::new(url)
+                .map_err(|err| format!("failed to create the remote tower storage: {err}"))?,
+        )
+    } else {
         match value_t_or_exit!(matches, "tower_storage", String).as_str() {
             "file" => {
                 let tower_path = value_t!(matches, "tower", PathBuf)
@@ -398,7 +438,8 @@ pub fn execute(
                 )
             }
             _ => unreachable!(),
-        };
+        }
+    };
 
     let mut accounts_index_config = AccountsIndexConfig {
         num_flush_threads: Some(accounts_index_flush_threads),
@@ -492,6 +533,17 @@ pub fn execute(
             }
         })
         .unwrap_or_default();
+    let io_backend = matches
+        .value_of("accounts_io_backend")
+        .map(|backend| match backend {
+            "pread" => IoBackend::Pread,
+            "io-uring" => IoBackend::IoUring,
+            _ => {
+                // clap will enforce one of the above values is given
+                unreachable!("invalid value given to accounts-io-backend")
+            }
+        })
+        .unwrap_or_default();
 
     let scan_filter_for_shrinking = matches
         .value_of("accounts_db_scan_filter_for_shrinking")
@@ -506,6 +558,27 @@ pub fn execute(
         })
         .unwrap_or_default();
 
+    let (ancient_storage_ideal_size, max_ancient_storages, ancient_storage_shrink_ratio) =
+        match matches.value_of("accounts_db_ancient_packing_policy") {
+            Some(path) => {
+                let policy = AncientPackingPolicy::load(path).map_err(|err| {
+                    format!("failed to load the ancient packing policy from {path}: {err}")
+                })?;
+                (
+                    Some(policy.ideal_storage_size),
+                    Some(policy.max_ancient_storages),
+                    policy.shrink_ratio,
+                )
+            }
+            None => (
+                value_t!(matches, "accounts_db_ancient_storage_ideal_size", u64).ok(),
+                value_t!(matches, "accounts_db_max_ancient_storages", usize).ok(),
+                None,
+            ),
+        };
+
+    let account_growth_epochs =
+        value_t!(matches, "account_growth_epochs", usize).unwrap_or_default();
     let accounts_db_config = AccountsDbConfig {
         index: Some(accounts_index_config),
         account_indexes: Some(account_indexes.clone()),
@@ -518,24 +591,30 @@ pub fn execute(
             .ok()
             .map(|mb| mb * MB as u64),
         ancient_append_vec_offset: value_t!(matches, "accounts_db_ancient_append_vecs", i64).ok(),
-        ancient_storage_ideal_size: value_t!(
+        ancient_storage_ideal_size,
+        max_ancient_storages,
+        ancient_storage_shrink_ratio,
+        ancient_storage_compression_level: value_t!(
             matches,
-            "accounts_db_ancient_storage_ideal_size",
-            u64
+            "accounts_db_ancient_storage_compression_level",
+            i32
         )
         .ok(),
-        max_ancient_storages: value_t!(matches, "accounts_db_max_ancient_storages", usize).ok(),
         hash_calculation_pubkey_bins: value_t!(
             matches,
             "accounts_db_hash_calculation_pubkey_bins",
             usize
         )
         .ok(),
+        checkpoint_hash_calculation: matches.is_present("accounts_db_checkpoint_hash_calculation"),
         exhaustively_verify_refcounts: matches.is_present("accounts_db_verify_refcounts"),
         create_ancient_storage,
         test_skip_rewrites_but_include_in_bank_hash: matches
             .is_present("accounts_db_test_skip_rewrites"),
         storage_access,
+        io_backend,
+        track_owner_disk_usage: matches.is_present("accounts_db_track_owner_disk_usage"),
+        track_account_growth: account_growth_epochs > 0,
         scan_filter_for_shrinking,
         enable_experimental_accumulator_hash: matches
             .is_present("accounts_db_experimental_accumulator_hash"),
@@ -584,6 +663,26 @@ pub fn execute(
         None
     };
 
+    let rpc_object_store_ledger_storage_config = matches
+        .value_of("rpc_object_store_ledger_storage")
+        .map(|object_store_url| RpcObjectStoreLedgerStorageConfig {
+            object_store_url: object_store_url.to_string(),
+            enable_ledger_upload: matches.is_present("enable_object_store_ledger_upload"),
+        });
+
+    let rpc_blockstore_tiering_config =
+        matches
+            .value_of("blockstore_tiering_url")
+            .map(|object_store_url| BlockstoreTieringConfig {
+                object_store_url: object_store_url.to_string(),
+                offload_after_epochs: value_t_or_exit!(
+                    matches,
+                    "blockstore_tiering_offload_epochs",
+                    u64
+                ),
+                cache_max_bytes: value_t_or_exit!(matches, "blockstore_tiering_cache_size", u64),
+            });
+
     let rpc_send_retry_rate_ms = value_t_or_exit!(matches, "rpc_send_transaction_retry_ms", u64);
     let rpc_send_batch_size = value_t_or_exit!(matches, "rpc_send_transaction_batch_size", usize);
     let rpc_send_batch_send_rate_ms =
@@ -617,6 +716,21 @@ pub fn execute(
         .map_err(|err| {
             format!("failed to parse rpc send-transaction-service tpu peer address: {err}")
         })?;
+    let rpc_method_rate_limits = matches
+        .values_of("rpc_method_rate_limit")
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            let (method, rate) = value
+                .split_once('=')
+                .ok_or_else(|| format!("{value} is not METHOD=REQUESTS_PER_SECOND"))?;
+            let rate = rate
+                .parse::<NonZeroU32>()
+                .map_err(|err| format!("invalid rate of {method}: {err}"))?;
+            Ok((method.to_string(), rate))
+        })
+        .collect::<Result<HashMap<_, _>, String>>()
+        .map_err(|err| format!("failed to parse rpc method rate limit: {err}"))?;
     let rpc_send_transaction_also_leader = matches.is_present("rpc_send_transaction_also_leader");
     let leader_forward_count =
         if rpc_send_transaction_tpu_peers.is_some() && !rpc_send_transaction_also_leader {
@@ -637,6 +751,83 @@ pub fn execute(
             xdp_zero_copy,
         )
     });
+    let retransmit_egress_shaper = value_t!(matches, "retransmit_egress_limit_mbps", u64)
+        .ok()
+        .map(|limit_mbps| {
+            let max_bytes_per_sec = limit_mbps.saturating_mul(1_000_000) / 8;
+            let burst_ms = value_t_or_exit!(matches, "retransmit_egress_burst_ms", u64);
+            EgressShaperConfig {
+                max_bytes_per_sec,
+                burst_bytes: max_bytes_per_sec.saturating_mul(burst_ms) / 1000,
+            }
+        });
+    let shredstream = values_t!(matches, "shredstream_subscriber", ShredstreamSubscriber)
+        .ok()
+        .map(|subscribers| ShredstreamConfig { subscribers });
+    let epoch_accounts_hash_scheduling = EpochAccountsHashSchedulingConfig {
+        start_delay_slots: value_t!(matches, "epoch_accounts_hash_start_delay_slots", Slot)
+            .unwrap_or_default(),
+        num_threads: value_t!(matches, "epoch_accounts_hash_threads", NonZeroUsize).ok(),
+    };
+    let broadcast_fec_ratio = if matches.is_present("broadcast_fec_experiment") {
+        Some(FecRatioConfig {
+            experiment: values_t_or_exit!(matches, "broadcast_fec_experiment", usize),
+            ..FecRatioConfig::default()
+        })
+    } else {
+        value_t!(matches, "broadcast_coding_shreds", usize)
+            .ok()
+            .map(|num_coding_shreds| FecRatioConfig {
+                num_coding_shreds,
+                ..FecRatioConfig::default()
+            })
+    };
+    let remote_shred_signer = matches.value_of("remote_shred_signer_url").map(|url| {
+        let latency_budget_ms =
+            value_t_or_exit!(matches, "remote_shred_signer_latency_budget_ms", u64);
+        RemoteShredSignerConfig {
+            url: url.to_string(),
+            latency_budget: Duration::from_millis(latency_budget_ms),
+        }
+    });
+
+    let failover_config = matches
+        .value_of("failover_peer")
+        .map(|peer_address| {
+            let shared_identity = Arc::new(keypair_of(matches, "failover_identity").unwrap());
+            let node_keypair = Arc::new(keypair_of(matches, "failover_keypair").unwrap());
+            if identity_keypair.pubkey() != shared_identity.pubkey()
+                && identity_keypair.pubkey() != node_keypair.pubkey()
+            {
+                return Err(
+                    "--identity must be either --failover-identity or --failover-keypair"
+                        .to_string(),
+                );
+            }
+            Ok(FailoverConfig {
+                bind_address: solana_net_utils::parse_host_port(
+                    matches.value_of("failover_bind_address").unwrap(),
+                )
+                .map_err(|err| format!("failed to parse failover bind address: {err}"))?,
+                peer_address: solana_net_utils::parse_host_port(peer_address)
+                    .map_err(|err| format!("failed to parse failover peer address: {err}"))?,
+                peer_pubkey: pubkey_of(matches, "failover_peer_pubkey").unwrap(),
+                node_keypair,
+                shared_identity,
+                heartbeat_interval: Duration::from_millis(value_t_or_exit!(
+                    matches,
+                    "failover_heartbeat_interval_ms",
+                    u64
+                )),
+                max_missed_heartbeats: value_t_or_exit!(
+                    matches,
+                    "failover_max_missed_heartbeats",
+                    u64
+                ),
+                auto_promote: !matches.is_present("no_failover_auto_promote"),
+            })
+        })
+        .transpose()?;
 
     let mut validator_config = ValidatorConfig {
         require_tower: matches.is_present("require_tower"),
@@ -654,7 +845,19 @@ pub fn execute(
             enable_rpc_transaction_history: matches.is_present("enable_rpc_transaction_history"),
             enable_extended_tx_metadata_storage: matches.is_present("enable_cpi_and_log_storage")
                 || matches.is_present("enable_extended_tx_metadata_storage"),
+            enable_program_signature_index: matches.is_present("enable_program_signature_index"),
+            transaction_status_filter: TransactionStatusFilter {
+                skip_vote_transactions: matches.is_present("transaction_history_skip_votes"),
+                successful_transaction_sample_percent: value_t!(
+                    matches,
+                    "transaction_history_sample_percent",
+                    u8
+                )
+                .unwrap_or(100),
+            },
             rpc_bigtable_config,
+            rpc_object_store_ledger_storage_config,
+            rpc_blockstore_tiering_config,
             faucet_addr: matches.value_of("rpc_faucet_addr").map(|address| {
                 solana_net_utils::parse_host_port(address).expect("failed to parse faucet address")
             }),
@@ -669,6 +872,21 @@ pub fn execute(
                 "health_check_slot_distance",
                 u64
             ),
+            health_detail_config: RpcHealthDetailConfig {
+                // Set once the vote account is known
+                vote_account: None,
+                max_slots_since_last_vote: value_t_or_exit!(
+                    matches,
+                    "health_max_slots_since_last_vote",
+                    u64
+                ),
+                max_snapshot_age_slots: value_t_or_exit!(
+                    matches,
+                    "health_max_snapshot_age_slots",
+                    u64
+                ),
+                max_incomplete_slots: value_t_or_exit!(matches, "health_max_incomplete_slots", u64),
+            },
             disable_health_check: false,
             rpc_threads: value_t_or_exit!(matches, "rpc_threads", usize),
             rpc_blocking_threads: value_t_or_exit!(matches, "rpc_blocking_threads", usize),
@@ -681,6 +899,16 @@ pub fn execute(
                 usize
             )),
             skip_preflight_health_check: matches.is_present("skip_preflight_health_check"),
+            slot_account_filter_slots: value_t!(matches, "rpc_slot_account_filter_slots", usize)
+                .unwrap_or_default(),
+            rate_limit_config: RpcRateLimitConfig {
+                requests_per_second: value_t!(matches, "rpc_rate_limit", NonZeroU32).ok(),
+                method_requests_per_second: rpc_method_rate_limits,
+                api_key_header: value_t!(matches, "rpc_rate_limit_api_key_header", String).ok(),
+            },
+            grpc_addr: value_t!(matches, "rpc_grpc_port", u16)
+                .ok()
+                .map(|rpc_grpc_port| SocketAddr::new(rpc_bind_address, rpc_grpc_port)),
         },
         on_start_geyser_plugin_config_files,
         geyser_plugin_always_enabled: matches.is_present("geyser_plugin_always_enabled"),
@@ -696,6 +924,10 @@ pub fn execute(
         pubsub_config: PubSubConfig {
             enable_block_subscription: matches.is_present("rpc_pubsub_enable_block_subscription"),
             enable_vote_subscription: matches.is_present("rpc_pubsub_enable_vote_subscription"),
+            enable_duplicate_shred_proof_subscription: matches
+                .is_present("rpc_pubsub_enable_duplicate_shred_proof_subscription"),
+            enable_prioritization_fee_subscription: matches
+                .is_present("rpc_pubsub_enable_prioritization_fee_subscription"),
             max_active_subscriptions: value_t_or_exit!(
                 matches,
                 "rpc_pubsub_max_active_subscriptions",
@@ -715,6 +947,7 @@ pub fn execute(
             notification_threads: value_t!(matches, "rpc_pubsub_notification_threads", usize)
                 .ok()
                 .and_then(NonZeroUsize::new),
+            proxy_protocol: matches.is_present("rpc_pubsub_proxy_protocol"),
         },
         voting_disabled: matches.is_present("no_voting") || restricted_repair_only_mode,
         wait_for_supermajority: value_t!(matches, "wait_for_supermajority", Slot).ok(),
@@ -728,6 +961,66 @@ pub fn execute(
             || matches.is_present("skip_startup_ledger_verification")),
         debug_keys,
         contact_debug_interval,
+        gossip_priority_weights: value_t!(matches, "gossip_priority_weights", CrdsPriorityWeights)
+            .unwrap_or_default(),
+        enable_gossip_quic: matches.is_present("enable_gossip_quic"),
+        commission_guard_config: value_t!(matches, "max_commission_change", u8).ok().map(
+            |max_change| CommissionGuardConfig {
+                max_change,
+                window_epochs: value_t_or_exit!(matches, "commission_change_window_epochs", u64),
+            },
+        ),
+        failover_config,
+        clock_drift_config: ClockDriftConfig {
+            max_drift_ms: value_t_or_exit!(matches, "max_clock_drift_ms", u64),
+            refuse_to_vote: matches.is_present("refuse_to_vote_on_clock_drift"),
+        },
+        vote_refresh_config: VoteRefreshConfig {
+            mode: value_t_or_exit!(matches, "vote_refresh_mode", VoteRefreshMode),
+            blockheight: value_t_or_exit!(matches, "vote_refresh_blockheight", usize),
+            min_interval: Duration::from_millis(value_t_or_exit!(
+                matches,
+                "vote_refresh_interval_ms",
+                u64
+            )),
+        },
+        shred_latency_slots: value_t!(matches, "shred_latency_slots", usize).unwrap_or_default(),
+        leader_slot_stats_slots: value_t!(matches, "leader_slot_stats_slots", usize)
+            .unwrap_or_default(),
+        confirmation_latency_slots: value_t!(matches, "confirmation_latency_slots", usize)
+            .unwrap_or_default(),
+        skipped_slot_reasons_slots: value_t!(matches, "skipped_slot_reasons_slots", usize)
+            .unwrap_or_default(),
+        account_congestion_slots: value_t!(matches, "account_congestion_slots", usize)
+            .unwrap_or_default(),
+        confirmation_proof_slots: value_t!(matches, "confirmation_proof_slots", usize)
+            .unwrap_or_default(),
+        buffered_transaction_samples: value_t!(matches, "buffered_transaction_samples", usize)
+            .unwrap_or_default(),
+        account_growth_epochs,
+        bank_hash_breakdown_slots: value_t_or_exit!(matches, "bank_hash_breakdown_slots", usize),
+        cost_calibration_report_interval_ms: value_t!(
+            matches,
+            "cost_calibration_report_interval_ms",
+            u64
+        )
+        .unwrap_or_default(),
+        pipeline_tracing_config: matches.value_of("pipeline_tracing_otlp_endpoint").map(
+            |otlp_endpoint| PipelineTracingConfig {
+                otlp_endpoint: otlp_endpoint.to_string(),
+                sample_interval: value_t_or_exit!(matches, "pipeline_tracing_sample_interval", u64),
+            },
+        ),
+        pinned_programs: values_t!(matches, "pinned_programs", Pubkey)
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        precompile_programs: value_t!(matches, "precompile_programs", usize).unwrap_or_default(),
+        precompile_programs_slots: value_t_or_exit!(matches, "precompile_programs_slots", u64),
+        forensic_bundle_dir: matches.value_of("forensic_bundle_dir").map(PathBuf::from),
+        repair_quic: matches.is_present("repair_quic"),
+        repair_peer_selection: value_t!(matches, "repair_peer_selection", RepairPeerSelection)
+            .unwrap_or_default(),
         send_transaction_service_config: send_transaction_service::Config {
             retry_rate_ms: rpc_send_retry_rate_ms,
             leader_forward_count,
@@ -750,6 +1043,10 @@ pub fn execute(
                 usize
             ),
             tpu_peers: rpc_send_transaction_tpu_peers,
+            transaction_store_path: value_t!(matches, "rpc_send_transaction_store_path", PathBuf)
+                .ok(),
+            status_webhook_url: value_t!(matches, "rpc_send_transaction_status_webhook", String)
+                .ok(),
         },
         no_poh_speed_test: matches.is_present("no_poh_speed_test"),
         no_os_memory_stats_reporting: matches.is_present("no_os_memory_stats_reporting"),
@@ -758,8 +1055,10 @@ pub fn execute(
         no_os_disk_stats_reporting: matches.is_present("no_os_disk_stats_reporting"),
         poh_pinned_cpu_core: value_of(matches, "poh_pinned_cpu_core")
             .unwrap_or(poh_service::DEFAULT_PINNED_CPU_CORE),
+        poh_numa_node: value_of(matches, "poh_numa_node"),
         poh_hashes_per_batch: value_of(matches, "poh_hashes_per_batch")
             .unwrap_or(poh_service::DEFAULT_HASHES_PER_BATCH),
+        poh_calibration_interval_ms: value_t_or_exit!(matches, "poh_calibration_interval_ms", u64),
         process_ledger_before_services: matches.is_present("process_ledger_before_services"),
         accounts_db_test_hash_calculation: matches.is_present("accounts_db_test_hash_calculation"),
         accounts_db_config,
@@ -781,12 +1080,32 @@ pub fn execute(
         rayon_global_threads,
         replay_forks_threads,
         replay_transactions_threads,
+        replay_fork_transactions_threads,
+        entry_verification_cpus: matches
+            .value_of("entry_verification_cpu_cores")
+            .map(|cpus| parse_cpu_ranges(cpus).unwrap())
+            .unwrap_or_default(),
+        banking_thread_pinning: thread_pinning_of(
+            matches,
+            "banking_cpu_cores",
+            "banking_numa_node",
+        )?,
+        sigverify_thread_pinning: thread_pinning_of(
+            matches,
+            "sigverify_cpu_cores",
+            "sigverify_numa_node",
+        )?,
         tvu_shred_sigverify_threads: tvu_sigverify_threads,
         delay_leader_block_for_pending_fork: matches
             .is_present("delay_leader_block_for_pending_fork"),
         wen_restart_proto_path: value_t!(matches, "wen_restart", PathBuf).ok(),
         wen_restart_coordinator: value_t!(matches, "wen_restart_coordinator", Pubkey).ok(),
         retransmit_xdp,
+        retransmit_egress_shaper,
+        remote_shred_signer,
+        broadcast_fec_ratio,
+        shredstream,
+        epoch_accounts_hash_scheduling,
         ..ValidatorConfig::default()
     };
 
@@ -802,6 +1121,15 @@ pub fn execute(
         .unwrap_or_default()
         .iter()
         .cloned()
+        .chain(validator_config.entry_verification_cpus.iter().copied())
+        .chain(validator_config.banking_thread_pinning.cpus.iter().copied())
+        .chain(
+            validator_config
+                .sigverify_thread_pinning
+                .cpus
+                .iter()
+                .copied(),
+        )
         .collect::<HashSet<_>>();
     let available = available.difference(&reserved);
     set_cpu_affinity(available.into_iter().copied()).unwrap();
@@ -813,6 +1141,12 @@ pub fn execute(
         }
         Keypair::new().pubkey()
     });
+    if !validator_config.voting_disabled {
+        validator_config
+            .rpc_config
+            .health_detail_config
+            .vote_account = Some(vote_account);
+    }
 
     let dynamic_port_range =
         solana_net_utils::parse_port_range(matches.value_of("dynamic_port_range").unwrap())
@@ -859,6 +1193,10 @@ pub fn execute(
     );
     let snapshot_packager_niceness_adj =
         value_t_or_exit!(matches, "snapshot_packager_niceness_adj", i8);
+    let snapshot_packager_io_bytes_per_second =
+        value_t!(matches, "snapshot_packager_io_limit", NonZeroU64)
+            .ok()
+            .map(|mb| mb.saturating_mul(NonZeroU64::new(1_024 * 1_024).unwrap()));
     let minimal_snapshot_download_speed =
         value_t_or_exit!(matches, "minimal_snapshot_download_speed", f32);
     let maximum_snapshot_download_abort =
@@ -1009,6 +1347,8 @@ pub fn execute(
         maximum_full_snapshot_archives_to_retain,
         maximum_incremental_snapshot_archives_to_retain,
         packager_thread_niceness_adj: snapshot_packager_niceness_adj,
+        packager_io_bytes_per_second: snapshot_packager_io_bytes_per_second,
+        verify_archives: matches.is_present("snapshot_archive_verification"),
     };
 
     info!(
@@ -1066,6 +1406,15 @@ pub fn execute(
     )
     .unwrap_or_default();
     validator_config.enable_block_production_forwarding = staked_nodes_overrides_path.is_some();
+    validator_config.forwarding_leader_fanout =
+        value_t_or_exit!(matches, "forwarding_leader_fanout", usize);
+    validator_config.packet_provenance_cache_size =
+        value_t!(matches, "packet_provenance_cache_size", usize).unwrap_or_default();
+    validator_config.dump_leader_slot_transactions = matches
+        .value_of("dump_leader_slot_transactions")
+        .map(PathBuf::from);
+    validator_config.record_banking_events =
+        matches.value_of("record_banking_events").map(PathBuf::from);
     validator_config.unified_scheduler_handler_threads =
         value_t!(matches, "unified_scheduler_handler_threads", usize).ok();
 
@@ -1089,6 +1438,7 @@ pub fn execute(
 
     let mut ledger_lock = ledger_lockfile(&ledger_path);
     let _ledger_write_guard = lock_ledger(&ledger_path, &mut ledger_lock);
+    exit_reason::init(&ledger_path);
 
     let start_progress = Arc::new(RwLock::new(ValidatorStartProgress::default()));
     let admin_service_post_init = Arc::new(RwLock::new(None));
@@ -1199,6 +1549,14 @@ pub fn execute(
     let tpu_max_connections_per_ipaddr_per_minute: u64 =
         value_t_or_exit!(matches, "tpu_max_connections_per_ipaddr_per_minute", u64);
     let max_streams_per_ms = value_t_or_exit!(matches, "tpu_max_streams_per_ms", u64);
+    let tpu_virtual_stake = value_t!(matches, "tpu_virtual_stake", u64).unwrap_or_default();
+    let tpu_virtual_stake_peers = matches
+        .values_of("tpu_virtual_stake_peers")
+        .into_iter()
+        .flatten()
+        .map(solana_net_utils::parse_host)
+        .collect::<Result<HashSet<IpAddr>, String>>()
+        .map_err(|err| format!("failed to parse tpu virtual stake peer address: {err}"))?;
 
     let node_config = NodeConfig {
         gossip_addr,
@@ -1261,6 +1619,11 @@ pub fn execute(
 
     solana_metrics::set_host_id(identity_keypair.pubkey().to_string());
     solana_metrics::set_panic_hook("validator", Some(String::from(solana_version)));
+    if let Ok(metrics_prometheus_port) = value_t!(matches, "metrics_prometheus_port", u16) {
+        let address = SocketAddr::new(rpc_bind_address, metrics_prometheus_port);
+        solana_metrics::prometheus::start_exporter(address)
+            .map_err(|err| format!("unable to serve the Prometheus metrics at {address}: {err}"))?;
+    }
     solana_entry::entry::init_poh();
     snapshot_utils::remove_tmp_snapshot_archives(&full_snapshot_archives_dir);
     snapshot_utils::remove_tmp_snapshot_archives(&incremental_snapshot_archives_dir);
@@ -1304,6 +1667,14 @@ pub fn execute(
     // the one pushed by bootstrap.
     node.info.hot_swap_pubkey(identity_keypair.pubkey());
 
+    let tpu_virtual_stake = VirtualStakeConfig {
+        stake: tpu_virtual_stake,
+        include_loopback: true,
+        addrs: tpu_virtual_stake_peers
+            .into_iter()
+            .chain(std::iter::once(gossip_host))
+            .collect(),
+    };
     let tpu_quic_server_config = QuicServerParams {
         max_connections_per_peer: tpu_max_connections_per_peer.try_into().unwrap(),
         max_staked_connections: tpu_max_staked_connections.try_into().unwrap(),
@@ -1311,6 +1682,7 @@ pub fn execute(
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
         coalesce: tpu_coalesce,
+        virtual_stake: tpu_virtual_stake.clone(),
         ..Default::default()
     };
 
@@ -1321,14 +1693,16 @@ pub fn execute(
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
         coalesce: tpu_coalesce,
+        virtual_stake: tpu_virtual_stake,
         ..Default::default()
     };
 
     // Vote shares TPU forward's characteristics, except that we accept 1 connection
-    // per peer and no unstaked connections are accepted.
+    // per peer and no unstaked or virtually staked connections are accepted.
     let mut vote_quic_server_config = tpu_fwd_quic_server_config.clone();
     vote_quic_server_config.max_connections_per_peer = 1;
     vote_quic_server_config.max_unstaked_connections = 0;
+    vote_quic_server_config.virtual_stake = VirtualStakeConfig::default();
 
     let validator = match Validator::new(
         node,
@@ -1353,26 +1727,27 @@ pub fn execute(
         },
         admin_service_post_init,
     ) {
-        Ok(validator) => Ok(validator),
+        Ok(validator) => validator,
         Err(err) => {
-            if matches!(
-                err.downcast_ref(),
-                Some(&ValidatorError::WenRestartFinished)
-            ) {
-                // 200 is a special error code, see
-                // https://github.com/solana-foundation/solana-improvement-documents/pull/46
-                error!("Please remove --wen_restart and use --wait_for_supermajority as instructed above");
-                exit(200);
+            let reason = ExitReason::from_error(&err);
+            exit_reason::record(reason, format!("{err:?}"));
+            match reason {
+                ExitReason::WenRestartFinished => {
+                    error!("Please remove --wen_restart and use --wait_for_supermajority as instructed above");
+                }
+                ExitReason::Unclassified => return Err(format!("{err:?}").into()),
+                _ => error!("Failed to start validator: {err:?}"),
             }
-            Err(format!("{err:?}"))
+            exit(reason.exit_code());
         }
-    }?;
+    };
 
     if let Some(filename) = init_complete_file {
         File::create(filename).map_err(|err| format!("unable to create {filename}: {err}"))?;
     }
     info!("Validator initialized");
     validator.join();
+    exit_reason::record(ExitReason::Clean, "validator exited");
     info!("Validator exiting..");
 
     Ok(())
@@ -1387,6 +1762,29 @@ fn hardforks_of(matches: &ArgMatches<'_>, name: &str) -> Option<Vec<Slot>> {
     }
 }
 
+/// Returns the pinning of the `cpus_name` cores and the `numa_node_name` node, the cores
+/// defaulting to the ones of the node
+fn thread_pinning_of(
+    matches: &ArgMatches<'_>,
+    cpus_name: &str,
+    numa_node_name: &str,
+) -> Result<ThreadPinning, String> {
+    let numa_node = value_of::<usize>(matches, numa_node_name);
+    let cpus = match (matches.value_of(cpus_name), numa_node) {
+        (Some(cpus), _) => parse_cpu_ranges(cpus).unwrap(),
+        (None, Some(numa_node)) => {
+            let path = format!("/sys/devices/system/node/node{numa_node}/cpulist");
+            fs::read_to_string(&path)
+                .and_then(|cpus| parse_cpu_ranges(cpus.trim()))
+                .map_err(|err| {
+                    format!("failed to read the cores of numa node {numa_node}: {err}")
+                })?
+        }
+        (None, None) => Vec::new(),
+    };
+    Ok(ThreadPinning { cpus, numa_node })
+}
+
 fn validators_set(
     identity_pubkey: &Pubkey,
     matches: &ArgMatches<'_>,
@@ -1454,6 +1852,8 @@ fn process_account_indexes(matches: &ArgMatches) -> AccountSecondaryIndexes {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "data-size" => AccountIndex::DataSize,
+            "nonce-authority" => AccountIndex::NonceAuthority,
             _ => unreachable!(),
         })
         .collect();
@@ -0,0 +1,82 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    std::path::Path,
+};
+
+const COMMAND: &str = "set-banking-threads";
+
+#[derive(Debug, PartialEq)]
+pub struct SetBankingThreadsArgs {
+    pub num_workers: u32,
+}
+
+impl FromClapArgMatches for SetBankingThreadsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(SetBankingThreadsArgs {
+            num_workers: value_t!(matches, "num_workers", u32)?,
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Adjust the number of banking stage threads processing non-vote transactions")
+        .arg(
+            Arg::with_name("num_workers")
+                .index(1)
+                .value_name("NUMBER")
+                .takes_value(true)
+                .required(true)
+                .validator(is_parsable::<u32>)
+                .help("New number of banking stage worker threads"),
+        )
+        .after_help(
+            "Note: the new number of threads only applies to the currently running validator \
+             instance. Transactions buffered by the banking stage at the time of the change are \
+             dropped.",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let SetBankingThreadsArgs { num_workers } =
+        SetBankingThreadsArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime().block_on(async move {
+        admin_client
+            .await?
+            .set_banking_worker_threads(num_workers)
+            .await
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_set_banking_threads_default() {
+        verify_args_struct_by_command_is_error::<SetBankingThreadsArgs>(command(), vec![COMMAND]);
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_set_banking_threads_with_num_workers() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "8"],
+            SetBankingThreadsArgs { num_workers: 8 },
+        );
+    }
+}
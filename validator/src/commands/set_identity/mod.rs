@@ -4,8 +4,8 @@ use {
         commands::{FromClapArgMatches, Result},
     },
     clap::{value_t, App, Arg, ArgMatches, SubCommand},
-    solana_clap_utils::input_validators::is_keypair,
-    solana_sdk::signature::{read_keypair, Signer},
+    solana_clap_utils::input_validators::{is_keypair, is_parsable},
+    solana_sdk::signature::{read_keypair, read_keypair_file, Signer},
     std::{fs, path::Path},
 };
 
@@ -16,6 +16,9 @@ const COMMAND: &str = "set-identity";
 pub struct SetIdentityArgs {
     pub identity: Option<String>,
     pub require_tower: bool,
+    pub staged: bool,
+    pub max_tower_lag_slots: Option<u64>,
+    pub observation_ms: Option<u64>,
 }
 
 impl FromClapArgMatches for SetIdentityArgs {
@@ -23,6 +26,9 @@ impl FromClapArgMatches for SetIdentityArgs {
         Ok(SetIdentityArgs {
             identity: value_t!(matches, "identity", String).ok(),
             require_tower: matches.is_present("require_tower"),
+            staged: matches.is_present("staged"),
+            max_tower_lag_slots: value_t!(matches, "max_tower_lag_slots", u64).ok(),
+            observation_ms: value_t!(matches, "observation_ms", u64).ok(),
         })
     }
 }
@@ -44,6 +50,40 @@ pub fn command<'a>() -> App<'a, 'a> {
                 .takes_value(false)
                 .help("Refuse to set the validator identity if saved tower state is not found"),
         )
+        .arg(
+            Arg::with_name("staged")
+                .long("staged")
+                .takes_value(false)
+                .conflicts_with("require_tower")
+                .help(
+                    "Validate the linkage of the vote account to the new identity, the \
+                     availability of its authorized voter and the freshness of the saved tower \
+                     of the new identity, and watch gossip for another node advertising it \
+                     before and after setting it. The previous identity is restored if another \
+                     node advertises the new identity after it was set",
+                ),
+        )
+        .arg(
+            Arg::with_name("max_tower_lag_slots")
+                .long("max-tower-lag-slots")
+                .value_name("SLOTS")
+                .takes_value(true)
+                .requires("staged")
+                .validator(is_parsable::<u64>)
+                .help("Refuse a saved tower whose last vote is more slots before the root"),
+        )
+        .arg(
+            Arg::with_name("observation_ms")
+                .long("observation-ms")
+                .value_name("MILLISECONDS")
+                .takes_value(true)
+                .requires("staged")
+                .validator(is_parsable::<u64>)
+                .help(
+                    "How long gossip is watched before and after setting the identity \
+                     [default: 10000]",
+                ),
+        )
         .after_help(
             "Note: the new identity only applies to the currently running validator instance",
         )
@@ -53,9 +93,35 @@ pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
     let SetIdentityArgs {
         identity,
         require_tower,
+        staged,
+        max_tower_lag_slots,
+        observation_ms,
     } = SetIdentityArgs::from_clap_arg_match(matches)?;
 
-    if let Some(identity_keypair) = identity {
+    if staged {
+        let identity_keypair = match identity {
+            Some(identity_keypair) => read_keypair_file(identity_keypair)?,
+            None => read_keypair(&mut std::io::stdin())?,
+        };
+
+        println!(
+            "Staging new validator identity: {}",
+            identity_keypair.pubkey()
+        );
+
+        let admin_client = admin_rpc_service::connect(ledger_path);
+        let report = admin_rpc_service::runtime().block_on(async move {
+            admin_client
+                .await?
+                .set_identity_staged_from_bytes(
+                    Vec::from(identity_keypair.to_bytes()),
+                    max_tower_lag_slots,
+                    observation_ms,
+                )
+                .await
+        })?;
+        print!("{report}");
+    } else if let Some(identity_keypair) = identity {
         let identity_keypair = fs::canonicalize(&identity_keypair)?;
 
         println!(
@@ -118,6 +184,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_set_identity_staged() {
+        verify_args_struct_by_command(
+            command(),
+            vec![
+                COMMAND,
+                "--staged",
+                "--max-tower-lag-slots",
+                "32",
+                "--observation-ms",
+                "5000",
+            ],
+            SetIdentityArgs {
+                staged: true,
+                max_tower_lag_slots: Some(32),
+                observation_ms: Some(5000),
+                ..SetIdentityArgs::default()
+            },
+        );
+    }
+
     #[test]
     fn verify_args_struct_by_command_set_identity_with_require_tower() {
         verify_args_struct_by_command(
@@ -0,0 +1,139 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "snapshot-progress";
+
+#[derive(Debug, PartialEq)]
+pub struct SnapshotProgressArgs {
+    pub output: OutputFormat,
+    pub archive_verification: bool,
+}
+
+impl FromClapArgMatches for SnapshotProgressArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(SnapshotProgressArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+            archive_verification: matches.is_present("archive_verification"),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display the progress of the snapshot package being archived")
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .arg(
+            Arg::with_name("archive_verification")
+                .long("archive-verification")
+                .takes_value(false)
+                .help(
+                    "Display the verifications of the snapshot archives instead, see \
+                     --snapshot-archive-verification",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let snapshot_progress_args = SnapshotProgressArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    if snapshot_progress_args.archive_verification {
+        let verification = admin_rpc_service::runtime()
+            .block_on(async move { admin_client.await?.snapshot_archive_verification().await })?;
+        println!(
+            "{}",
+            snapshot_progress_args
+                .output
+                .formatted_string(&verification)
+        );
+    } else {
+        let progress = admin_rpc_service::runtime()
+            .block_on(async move { admin_client.await?.snapshot_packaging_progress().await })?;
+        println!(
+            "{}",
+            snapshot_progress_args.output.formatted_string(&progress)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_snapshot_progress_output_json() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            SnapshotProgressArgs {
+                output: OutputFormat::Json,
+                archive_verification: false,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_snapshot_progress_output_json_compact() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json-compact"],
+            SnapshotProgressArgs {
+                output: OutputFormat::JsonCompact,
+                archive_verification: false,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_snapshot_progress_output_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            SnapshotProgressArgs {
+                output: OutputFormat::Display,
+                archive_verification: false,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_snapshot_progress_archive_verification() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--archive-verification"],
+            SnapshotProgressArgs {
+                output: OutputFormat::Display,
+                archive_verification: true,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_snapshot_progress_output_invalid() {
+        verify_args_struct_by_command_is_error::<SnapshotProgressArgs>(
+            command(),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
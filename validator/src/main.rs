@@ -44,15 +44,57 @@ pub fn main() {
         )
         .inspect_err(|err| error!("Failed to start validator: {err}"))
         .map_err(commands::Error::Dynamic),
+        ("account-conflicts", Some(subcommand_matches)) => {
+            commands::account_conflicts::execute(subcommand_matches, &ledger_path)
+        }
+        ("accounts-disk-usage", Some(subcommand_matches)) => {
+            commands::accounts_disk_usage::execute(subcommand_matches, &ledger_path)
+        }
+        ("bank-hash-breakdowns", Some(subcommand_matches)) => {
+            commands::bank_hash_breakdowns::execute(subcommand_matches, &ledger_path)
+        }
+        ("ancient-packing-policy", Some(subcommand_matches)) => {
+            commands::ancient_packing_policy::execute(subcommand_matches, &ledger_path)
+        }
         ("authorized-voter", Some(authorized_voter_subcommand_matches)) => {
             commands::authorized_voter::execute(authorized_voter_subcommand_matches, &ledger_path)
         }
+        ("blockstore-compaction", Some(subcommand_matches)) => {
+            commands::blockstore_compaction::execute(subcommand_matches, &ledger_path)
+        }
+        ("commission-guard", Some(subcommand_matches)) => {
+            commands::commission_guard::execute(subcommand_matches, &ledger_path)
+        }
+        ("failover", Some(subcommand_matches)) => {
+            commands::failover::execute(subcommand_matches, &ledger_path)
+        }
+        ("pause-leader", Some(subcommand_matches)) => {
+            commands::pause_leader::execute(subcommand_matches, &ledger_path)
+        }
+        ("resume-leader", Some(subcommand_matches)) => {
+            commands::resume_leader::execute(subcommand_matches, &ledger_path)
+        }
         ("plugin", Some(plugin_subcommand_matches)) => {
             commands::plugin::execute(plugin_subcommand_matches, &ledger_path)
         }
         ("contact-info", Some(subcommand_matches)) => {
             commands::contact_info::execute(subcommand_matches, &ledger_path)
         }
+        ("crds-values", Some(subcommand_matches)) => {
+            commands::crds_values::execute(subcommand_matches, &ledger_path)
+        }
+        ("duplicate-shred-proofs", Some(subcommand_matches)) => {
+            commands::duplicate_shred_proofs::execute(subcommand_matches, &ledger_path)
+        }
+        ("epoch-accounts-hash", Some(subcommand_matches)) => {
+            commands::epoch_accounts_hash::execute(subcommand_matches, &ledger_path)
+        }
+        ("forensic-bundles", Some(subcommand_matches)) => {
+            commands::forensic_bundles::execute(subcommand_matches, &ledger_path)
+        }
+        ("gossip-peer-scores", Some(subcommand_matches)) => {
+            commands::gossip_peer_scores::execute(subcommand_matches, &ledger_path)
+        }
         ("exit", Some(subcommand_matches)) => {
             commands::exit::execute(subcommand_matches, &ledger_path)
         }
@@ -60,6 +102,24 @@ pub fn main() {
         ("staked-nodes-overrides", Some(subcommand_matches)) => {
             commands::staked_nodes_overrides::execute(subcommand_matches, &ledger_path)
         }
+        ("set-banking-threads", Some(subcommand_matches)) => {
+            commands::set_banking_threads::execute(subcommand_matches, &ledger_path)
+        }
+        ("poh-calibration", Some(subcommand_matches)) => {
+            commands::poh_calibration::execute(subcommand_matches, &ledger_path)
+        }
+        ("precompile-programs", Some(subcommand_matches)) => {
+            commands::precompile_programs::execute(subcommand_matches, &ledger_path)
+        }
+        ("bandwidth", Some(subcommand_matches)) => {
+            commands::bandwidth::execute(subcommand_matches, &ledger_path)
+        }
+        ("health", Some(subcommand_matches)) => {
+            commands::health::execute(subcommand_matches, &ledger_path)
+        }
+        ("snapshot-progress", Some(subcommand_matches)) => {
+            commands::snapshot_progress::execute(subcommand_matches, &ledger_path)
+        }
         ("set-identity", Some(subcommand_matches)) => {
             commands::set_identity::execute(subcommand_matches, &ledger_path)
         }